@@ -13,11 +13,20 @@
 //! Modules are stored in build/dependency order: dependencies first, entry point last.
 //! The launcher executes them in order on a single VM so globals accumulate correctly.
 //!
+//! Bundles (`atlas bundle`) append one more section to the payload, after the modules:
+//!
+//!   [manifest_len: u32 LE]
+//!   [manifest_json]             ← `BundleManifest`: entry-point flag + baked security
+//!
+//! Archives without it run with full permissions and exit 0, as before.
+//!
 //! CRITICAL: ATLAS_BC_MAGIC must be byte-for-byte identical to the constant in
 //! crates/atlas-launcher/src/self_path.rs. Any divergence means the launcher
 //! will never find its payload. (D-048)
 
 use crate::error::{BuildError, BuildResult};
+use atlas_config::SecurityConfig;
+use serde::Serialize;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
@@ -69,6 +78,17 @@ pub fn find_launcher_binary() -> Option<PathBuf> {
     None
 }
 
+/// Run settings baked into a bundle, read back by the launcher.
+///
+/// Must stay field-for-field compatible with `atlas_launcher::execute::BundleManifest`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BundleManifest {
+    /// The entry module declares `fn main`; its return value is the exit code
+    pub main: bool,
+    /// Permissions the program runs with; `None` allows everything
+    pub security: Option<SecurityConfig>,
+}
+
 /// Package a native binary: copy the launcher, append the multi-module archive, and
 /// write the ATLAS_BC_MAGIC trailer.
 ///
@@ -78,6 +98,34 @@ pub fn emit_native_binary(
     launcher_path: &Path,
     module_bytecodes: &[Vec<u8>],
     output_path: &Path,
+) -> BuildResult<()> {
+    write_archive(launcher_path, module_bytecodes, None, output_path)
+}
+
+/// Package a standalone bundle: like [`emit_native_binary`], plus a manifest
+/// telling the launcher how to run the program and which permissions to grant.
+pub fn emit_bundle(
+    launcher_path: &Path,
+    module_bytecodes: &[Vec<u8>],
+    manifest: &BundleManifest,
+    output_path: &Path,
+) -> BuildResult<()> {
+    let manifest = serde_json::to_vec(manifest).map_err(|e| {
+        BuildError::BuildFailed(format!("Failed to serialize bundle manifest: {e}"))
+    })?;
+    write_archive(
+        launcher_path,
+        module_bytecodes,
+        Some(&manifest),
+        output_path,
+    )
+}
+
+fn write_archive(
+    launcher_path: &Path,
+    module_bytecodes: &[Vec<u8>],
+    manifest: Option<&[u8]>,
+    output_path: &Path,
 ) -> BuildResult<()> {
     // Copy the launcher binary to the output path
     std::fs::copy(launcher_path, output_path).map_err(|e| BuildError::io(output_path, e))?;
@@ -96,6 +144,11 @@ pub fn emit_native_binary(
         payload.extend_from_slice(bc);
     }
 
+    if let Some(manifest) = manifest {
+        payload.extend_from_slice(&(manifest.len() as u32).to_le_bytes());
+        payload.extend_from_slice(manifest);
+    }
+
     let payload_len = payload.len() as u64;
 
     // Append payload + ATLAS_BC_MAGIC + payload_len (trailer)
@@ -13,7 +13,7 @@ use crate::targets::{ArtifactMetadata, BuildArtifact, BuildTarget, TargetKind};
 use atlas_package::manifest::PackageManifest;
 use atlas_runtime::module_loader::ModuleRegistry;
 use atlas_runtime::{
-    Binder, Bytecode, Compiler, Diagnostic, Lexer, Parser, SymbolTable, TypeChecker,
+    AssetPolicy, Binder, Bytecode, Compiler, Diagnostic, Lexer, Parser, SymbolTable, TypeChecker,
 };
 
 // Note: Parallel compilation disabled for now due to Bytecode containing non-Send types (Rc<>)
@@ -550,7 +550,7 @@ impl Builder {
             let module_name = self.path_to_module_name(source_path)?;
 
             // Quick parse to get imports (don't need full type checking yet)
-            let mut lexer = Lexer::new(&source);
+            let mut lexer = Lexer::new(&source).with_file(source_path.display().to_string());
             let (tokens, lex_diagnostics) = lexer.tokenize();
 
             if !lex_diagnostics.is_empty() {
@@ -661,7 +661,7 @@ impl Builder {
         let source = fs::read_to_string(source_path).map_err(|e| BuildError::io(source_path, e))?;
 
         // Lex
-        let mut lexer = Lexer::new(&source);
+        let mut lexer = Lexer::new(&source).with_file(source_path.display().to_string());
         let (tokens, lex_diagnostics) = lexer.tokenize();
 
         if lex_diagnostics.iter().any(|d| d.is_error()) {
@@ -713,6 +713,15 @@ impl Builder {
         } else {
             Compiler::new()
         };
+        if let Some(assets) = &self.manifest.assets {
+            compiler.set_asset_policy(Some(AssetPolicy::new(
+                &self.root_dir,
+                assets.include.clone(),
+            )));
+        }
+        if let Some(dir) = source_path.parent() {
+            compiler.set_source_dir(dir);
+        }
 
         let bytecode = compiler.compile(&program).map_err(|diagnostics| {
             BuildError::compilation(module_name, format_diagnostics(&diagnostics))
@@ -740,7 +749,7 @@ impl Builder {
     ) -> BuildResult<SymbolTable> {
         let source = fs::read_to_string(source_path).map_err(|e| BuildError::io(source_path, e))?;
 
-        let mut lexer = Lexer::new(&source);
+        let mut lexer = Lexer::new(&source).with_file(source_path.display().to_string());
         let (tokens, _) = lexer.tokenize();
 
         let mut parser = Parser::new(tokens);
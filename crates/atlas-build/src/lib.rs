@@ -107,6 +107,19 @@ pub fn validate_packages(project_dir: &std::path::Path) -> Result<(), String> {
         ));
     }
 
+    // `[lints] duplicate-versions = "deny"` fails the build once the locked graph
+    // holds more semver-incompatible duplicates than the budget allows.
+    let lints = manifest.lints.clone().unwrap_or_default();
+    let duplicates = atlas_package::DuplicateReport::from_lockfile(&manifest, &lockfile);
+    if duplicates.check(&lints) == atlas_package::DuplicateLint::Deny {
+        return Err(format!(
+            "{} duplicate package version(s) exceed the duplicate-budget of {}\n{}",
+            duplicates.duplicate_count(),
+            lints.duplicate_budget,
+            duplicates.report().trim_end()
+        ));
+    }
+
     // Determine pkg store root: ATLAS_CACHE_DIR (compat) → ATLAS_HOME/pkg → ~/atlas/pkg
     // Mirrors the H-411 layout used by atlas-runtime's module resolver.
     let pkg_root = if let Ok(val) = std::env::var("ATLAS_CACHE_DIR") {
@@ -305,4 +318,24 @@ mod package_validation_tests {
             "should error for path dep that doesn't exist"
         );
     }
+
+    #[test]
+    fn test_duplicate_versions_over_budget_errors() {
+        let tmp = TempDir::new().expect("tempdir");
+        let content = "[package]\nname = \"test-pkg\"\nversion = \"0.1.0\"\n\n[dependencies]\njson = { path = \"json\" }\nweb = { path = \"web\" }\n\n[lints]\nduplicate-versions = \"deny\"\n";
+        fs::write(tmp.path().join("atlas.toml"), content).expect("write atlas.toml");
+
+        // web was built against json 1.4.0 while the project locks json 2.0.0
+        let root = tmp.path().display();
+        let lock = format!(
+            "version = 1\n\n[[packages]]\nname = \"json\"\nversion = \"2.0.0\"\n\n[packages.source]\ntype = \"path\"\npath = \"{root}\"\n\n[[packages]]\nname = \"web\"\nversion = \"1.0.0\"\n\n[packages.source]\ntype = \"path\"\npath = \"{root}\"\n\n[packages.dependencies]\njson = \"1.4.0\"\n"
+        );
+        fs::write(tmp.path().join("atlas.lock"), lock).expect("write atlas.lock");
+
+        let msg = validate_packages(tmp.path()).expect_err("duplicate json must fail");
+        assert!(
+            msg.contains("test-pkg -> web v1.0.0 -> json v1.4.0"),
+            "{msg}"
+        );
+    }
 }
@@ -1,4 +1,6 @@
-use atlas_build::binary_emit::{emit_native_binary, find_launcher_binary, ATLAS_BC_MAGIC};
+use atlas_build::binary_emit::{
+    emit_bundle, emit_native_binary, find_launcher_binary, BundleManifest, ATLAS_BC_MAGIC,
+};
 use atlas_build::targets::{BuildTarget, TargetKind};
 use std::path::PathBuf;
 
@@ -123,6 +125,38 @@ fn test_find_appended_bytecode_roundtrip_via_emit() {
     let _ = std::fs::remove_file(&output_path);
 }
 
+#[test]
+fn test_emit_bundle_manifest_roundtrip() {
+    let launcher_path = temp_path("launcher_bundle");
+    std::fs::write(&launcher_path, b"fake_launcher_bytes").expect("write launcher");
+
+    let security = atlas_config::SecurityConfig {
+        mode: Some("strict".to_string()),
+        ..Default::default()
+    };
+    let manifest = BundleManifest {
+        main: true,
+        security: Some(security.clone()),
+    };
+    let output_path = temp_path("bundle");
+    let module_bytecodes = vec![b"module a".to_vec(), b"module b".to_vec()];
+    emit_bundle(&launcher_path, &module_bytecodes, &manifest, &output_path).expect("emit_bundle");
+
+    let program = atlas_launcher::self_path::find_embedded_program(&output_path)
+        .expect("io error")
+        .expect("bundle must be found");
+    assert_eq!(program.modules, module_bytecodes);
+
+    let read: atlas_launcher::execute::BundleManifest =
+        serde_json::from_slice(&program.manifest.expect("bundle has a manifest"))
+            .expect("manifest is valid JSON");
+    assert!(read.main);
+    assert_eq!(read.security, Some(security));
+
+    let _ = std::fs::remove_file(&launcher_path);
+    let _ = std::fs::remove_file(&output_path);
+}
+
 #[test]
 fn test_find_launcher_binary_returns_none_when_not_on_path() {
     // Temporarily clear PATH to ensure find_launcher_binary() returns None
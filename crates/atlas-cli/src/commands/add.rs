@@ -1,7 +1,7 @@
 //! Add dependency command (atlas add)
 
 use anyhow::{bail, Context, Result};
-use atlas_package::manifest::{Dependency, DetailedDependency, PackageManifest};
+use atlas_package::manifest::{Dependency, DetailedDependency, PackageManifest, ReleaseChannel};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -32,6 +32,8 @@ pub struct AddArgs {
     pub optional: bool,
     /// Rename the dependency
     pub rename: Option<String>,
+    /// Release channel (nightly admits pre-releases)
+    pub channel: Option<ReleaseChannel>,
     /// Project directory (defaults to current)
     pub project_dir: PathBuf,
     /// Dry run (don't modify files)
@@ -55,6 +57,7 @@ impl Default for AddArgs {
             no_default_features: false,
             optional: false,
             rename: None,
+            channel: None,
             project_dir: PathBuf::from("."),
             dry_run: false,
             verbose: false,
@@ -182,6 +185,7 @@ fn build_dependency(args: &AddArgs) -> Result<Dependency> {
         && !args.no_default_features
         && !args.optional
         && args.rename.is_none()
+        && args.channel.is_none()
     {
         let version = args.version.clone().unwrap_or_else(|| "*".to_string());
         return Ok(Dependency::Simple(version));
@@ -212,6 +216,7 @@ fn build_dependency(args: &AddArgs) -> Result<Dependency> {
             None
         },
         rename: args.rename.clone(),
+        channel: args.channel,
     };
 
     Ok(Dependency::Detailed(detailed))
@@ -370,6 +375,20 @@ version = "0.1.0"
         }
     }
 
+    #[test]
+    fn test_build_nightly_dependency() {
+        let args = AddArgs {
+            package: "foo".to_string(),
+            version: Some("^1.0.0".to_string()),
+            channel: Some(ReleaseChannel::Nightly),
+            ..Default::default()
+        };
+
+        let dep = build_dependency(&args).unwrap();
+        assert_eq!(dep.channel(), ReleaseChannel::Nightly);
+        assert_eq!(dep.version_constraint(), Some("^1.0.0"));
+    }
+
     #[test]
     fn test_conflicting_git_and_path() {
         let args = AddArgs {
@@ -460,6 +479,7 @@ version = "0.1.0"
             features: None,
             default_features: None,
             rename: None,
+            channel: None,
         });
         let info = format_dependency_info(&dep);
         assert!(info.contains("git:"));
@@ -0,0 +1,739 @@
+//! Public API report and semver check (atlas api)
+//!
+//! A package's public API is everything its modules `export`. `atlas api report`
+//! lists it; `atlas api diff <old-version>` compares it against the release
+//! tagged `v<old-version>` by `atlas publish`, classifies every change as
+//! breaking or additive, and checks that the version in atlas.toml is bumped
+//! far enough to cover them.
+
+use super::publish::find_manifest;
+use anyhow::{bail, Context, Result};
+use atlas_package::manifest::PackageManifest;
+use atlas_runtime::ast::{
+    EnumVariant, ExportItem, FunctionDecl, Item, OwnershipAnnotation, StructField, TypeParam,
+    TypeRef,
+};
+use atlas_runtime::{Lexer, Parser};
+use semver::{Version, VersionReq};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+
+/// Arguments for `atlas api report` and `atlas api diff`
+#[derive(Debug, Clone)]
+pub struct ApiArgs {
+    /// Project directory (defaults to current)
+    pub project_dir: PathBuf,
+    /// Emit JSON instead of text
+    pub json: bool,
+}
+
+impl Default for ApiArgs {
+    fn default() -> Self {
+        Self {
+            project_dir: PathBuf::from("."),
+            json: false,
+        }
+    }
+}
+
+/// One exported item, keyed in [`ApiReport`] by `module::name`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiItem {
+    /// Human-readable declaration, e.g. `fn add(a: number, b: number): number`
+    pub signature: String,
+    /// What callers depend on besides a function's parameters; parameter
+    /// names are not part of it
+    contract: String,
+    /// Parameters of an exported function, for telling optional additions apart
+    params: Option<Vec<ApiParam>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ApiParam {
+    /// Ownership and type; the name is not part of the contract
+    ty: String,
+    optional: bool,
+}
+
+/// The exported items of every module in a package
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApiReport {
+    pub items: BTreeMap<String, ApiItem>,
+}
+
+/// Whether an API change forces a semver-incompatible release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    Additive,
+    Breaking,
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangeKind::Additive => write!(f, "additive"),
+            ChangeKind::Breaking => write!(f, "breaking"),
+        }
+    }
+}
+
+/// A single difference between two API reports
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiChange {
+    pub kind: ChangeKind,
+    /// `module::name` of the item
+    pub path: String,
+    pub description: String,
+}
+
+impl ApiReport {
+    /// Build a report from `(module, source)` pairs, where `module` is the
+    /// file's path below the source root without its extension
+    pub fn from_sources<I>(sources: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut report = ApiReport::default();
+        for (module, source) in sources {
+            let (tokens, lex_diags) = Lexer::new(&source).tokenize();
+            let (program, parse_diags) = Parser::new(tokens).parse();
+            if let Some(error) = lex_diags.iter().chain(&parse_diags).find(|d| d.is_error()) {
+                bail!("Cannot read the API of '{}': {}", module, error.message);
+            }
+
+            for item in &program.items {
+                let Item::Export(export) = item else {
+                    continue;
+                };
+                for (name, api_item) in export_items(&export.item) {
+                    report
+                        .items
+                        .insert(format!("{}::{}", module, name), api_item);
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Report for the package sources in the working tree
+    pub fn from_dir(project_dir: &Path) -> Result<Self> {
+        let root = source_root(project_dir);
+        let mut sources = Vec::new();
+        for entry in WalkDir::new(&root)
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !is_skipped_dir(e.path()))
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+            if !is_atlas_file(path) {
+                continue;
+            }
+            let source = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let relative = path.strip_prefix(&root).unwrap_or(path);
+            sources.push((module_name(relative), source));
+        }
+        Self::from_sources(sources)
+    }
+
+    /// Report for the package sources as committed at git `tag`
+    pub fn from_git_tag(project_dir: &Path, tag: &str) -> Result<Self> {
+        let root = source_root(project_dir);
+        let relative_root = root.strip_prefix(project_dir).unwrap_or(Path::new(""));
+
+        let output = git(project_dir, &["ls-tree", "-r", "--name-only", tag])
+            .with_context(|| format!("Release tag '{}' not found", tag))?;
+        let mut sources = Vec::new();
+        for file in output.lines().map(Path::new) {
+            let Ok(relative) = file.strip_prefix(relative_root) else {
+                continue;
+            };
+            let skipped = relative
+                .parent()
+                .is_some_and(|dir| dir.components().any(|c| is_skipped_dir(c.as_ref())));
+            if !is_atlas_file(file) || skipped {
+                continue;
+            }
+            let spec = format!("{}:./{}", tag, file.to_string_lossy());
+            let source = git(project_dir, &["show", &spec])?;
+            sources.push((module_name(relative), source));
+        }
+        Self::from_sources(sources)
+    }
+}
+
+/// Changes from `old` to `new`, breaking ones first
+pub fn diff(old: &ApiReport, new: &ApiReport) -> Vec<ApiChange> {
+    let mut changes = Vec::new();
+    for (path, old_item) in &old.items {
+        match new.items.get(path) {
+            None => changes.push(ApiChange {
+                kind: ChangeKind::Breaking,
+                path: path.clone(),
+                description: format!("removed `{}`", old_item.signature),
+            }),
+            Some(new_item)
+                if new_item.contract != old_item.contract || new_item.params != old_item.params =>
+            {
+                changes.push(compare_items(path, old_item, new_item))
+            }
+            Some(_) => {}
+        }
+    }
+    for (path, new_item) in &new.items {
+        if !old.items.contains_key(path) {
+            changes.push(ApiChange {
+                kind: ChangeKind::Additive,
+                path: path.clone(),
+                description: format!("added `{}`", new_item.signature),
+            });
+        }
+    }
+    changes.sort_by(|a, b| b.kind.cmp(&a.kind).then_with(|| a.path.cmp(&b.path)));
+    changes
+}
+
+/// Check that going from `old` to `new` is a large enough version bump
+///
+/// Breaking changes need a version outside `^old`; additive ones need a new
+/// minor version once the package is 1.0 or later.
+pub fn check_version(
+    old: &Version,
+    new: &Version,
+    changes: &[ApiChange],
+) -> std::result::Result<(), String> {
+    if new <= old {
+        return Err(format!("version {} is not newer than {}", new, old));
+    }
+    let compatible = VersionReq::parse(&format!("^{}", old)).is_ok_and(|req| req.matches(new));
+    let has = |kind: ChangeKind| changes.iter().any(|change| change.kind == kind);
+
+    if has(ChangeKind::Breaking) && compatible {
+        return Err(format!(
+            "breaking API changes need version {} or later, not {}",
+            minimum_version(old, ChangeKind::Breaking),
+            new
+        ));
+    }
+    if has(ChangeKind::Additive)
+        && old.major > 0
+        && (new.major, new.minor) == (old.major, old.minor)
+    {
+        return Err(format!(
+            "new API needs version {} or later, not {}",
+            minimum_version(old, ChangeKind::Additive),
+            new
+        ));
+    }
+    Ok(())
+}
+
+/// Smallest version after `old` that may contain a change of `kind`
+pub fn minimum_version(old: &Version, kind: ChangeKind) -> Version {
+    match kind {
+        ChangeKind::Breaking if old.major > 0 => Version::new(old.major + 1, 0, 0),
+        ChangeKind::Breaking if old.minor > 0 => Version::new(0, old.minor + 1, 0),
+        ChangeKind::Breaking => Version::new(0, 0, old.patch + 1),
+        ChangeKind::Additive if old.major > 0 => Version::new(old.major, old.minor + 1, 0),
+        ChangeKind::Additive => Version::new(old.major, old.minor, old.patch + 1),
+    }
+}
+
+/// Run `atlas api report`
+pub fn report(args: ApiArgs) -> Result<()> {
+    let manifest_path = find_manifest(&args.project_dir)?;
+    let project_dir = manifest_path.parent().unwrap_or(Path::new("."));
+    let report = ApiReport::from_dir(project_dir)?;
+
+    if args.json {
+        let items: serde_json::Map<String, serde_json::Value> = report
+            .items
+            .iter()
+            .map(|(path, item)| (path.clone(), item.signature.clone().into()))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&items)?);
+        return Ok(());
+    }
+
+    if report.items.is_empty() {
+        println!("No exported items");
+    }
+    for (path, item) in &report.items {
+        println!("{}  {}", path, item.signature);
+    }
+    Ok(())
+}
+
+/// Run `atlas api diff <old-version>`; fails if atlas.toml's version is too
+/// small for the changes found
+pub fn diff_against(args: ApiArgs, old_version: &str) -> Result<()> {
+    let manifest_path = find_manifest(&args.project_dir)?;
+    let project_dir = manifest_path.parent().unwrap_or(Path::new("."));
+    let manifest =
+        PackageManifest::from_file(&manifest_path).context("Failed to read atlas.toml")?;
+
+    let old_version = Version::parse(old_version.trim_start_matches('v'))
+        .with_context(|| format!("Invalid version '{}'", old_version))?;
+    let tag = format!("v{}", old_version);
+
+    let old = ApiReport::from_git_tag(project_dir, &tag)?;
+    let new = ApiReport::from_dir(project_dir)?;
+    let changes = diff(&old, &new);
+
+    let current = &manifest.package.version;
+    let verdict = if *current == old_version {
+        Ok(())
+    } else {
+        check_version(&old_version, current, &changes)
+    };
+    let required = changes
+        .iter()
+        .map(|change| change.kind)
+        .max()
+        .map(|kind| minimum_version(&old_version, kind));
+
+    if args.json {
+        let changes: Vec<serde_json::Value> = changes
+            .iter()
+            .map(|change| {
+                serde_json::json!({
+                    "kind": change.kind.to_string(),
+                    "path": change.path,
+                    "description": change.description,
+                })
+            })
+            .collect();
+        let output = serde_json::json!({
+            "old_version": old_version.to_string(),
+            "version": current.to_string(),
+            "required_version": required.as_ref().map(ToString::to_string),
+            "changes": changes,
+            "error": verdict.as_ref().err(),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        if changes.is_empty() {
+            println!("No API changes since {}", tag);
+        } else {
+            println!("API changes since {}:", tag);
+            for change in &changes {
+                println!(
+                    "  {:<9} {}: {}",
+                    change.kind, change.path, change.description
+                );
+            }
+        }
+        if let Some(required) = &required {
+            println!("\nNext version must be at least {}", required);
+        }
+    }
+
+    verdict.map_err(|e| anyhow::anyhow!("atlas.toml version {}: {}", current, e))
+}
+
+/// `(name, item)` for everything an export declaration makes public
+fn export_items(item: &ExportItem) -> Vec<(String, ApiItem)> {
+    let plain = |name: &str, signature: String| {
+        vec![(
+            name.to_string(),
+            ApiItem {
+                contract: signature.clone(),
+                signature,
+                params: None,
+            },
+        )]
+    };
+
+    match item {
+        ExportItem::Function(func) => vec![(func.name.name.clone(), function_item(func))],
+        ExportItem::Variable(var) => {
+            let keyword = if var.mutable { "let mut" } else { "let" };
+            plain(
+                &var.name.name,
+                format!(
+                    "{} {}{}",
+                    keyword,
+                    var.name.name,
+                    annotation(var.type_ref.as_ref())
+                ),
+            )
+        }
+        ExportItem::Const(decl) => plain(
+            &decl.name.name,
+            format!(
+                "const {}{}",
+                decl.name.name,
+                annotation(decl.type_ref.as_ref())
+            ),
+        ),
+        ExportItem::TypeAlias(alias) => plain(
+            &alias.name.name,
+            format!(
+                "type {}{} = {}",
+                alias.name.name,
+                type_params(&alias.type_params),
+                format_type_ref(&alias.type_ref)
+            ),
+        ),
+        ExportItem::Struct(decl) => plain(
+            &decl.name.name,
+            format!(
+                "struct {}{} {{ {} }}",
+                decl.name.name,
+                type_params(&decl.type_params),
+                fields(&decl.fields)
+            ),
+        ),
+        ExportItem::Enum(decl) => {
+            let variants: Vec<String> = decl
+                .variants
+                .iter()
+                .map(|variant| match variant {
+                    EnumVariant::Unit { name, .. } => name.name.clone(),
+                    EnumVariant::Tuple { name, fields, .. } => {
+                        let types: Vec<String> = fields.iter().map(format_type_ref).collect();
+                        format!("{}({})", name.name, types.join(", "))
+                    }
+                    EnumVariant::Struct {
+                        name, fields: f, ..
+                    } => {
+                        format!("{} {{ {} }}", name.name, fields(f))
+                    }
+                })
+                .collect();
+            plain(
+                &decl.name.name,
+                format!(
+                    "enum {}{} {{ {} }}",
+                    decl.name.name,
+                    type_params(&decl.type_params),
+                    variants.join(", ")
+                ),
+            )
+        }
+        ExportItem::ReExport { names, source, .. } => names
+            .iter()
+            .flat_map(|spec| {
+                let exported = spec.alias.as_ref().unwrap_or(&spec.name);
+                plain(
+                    &exported.name,
+                    format!("{} from \"{}\"", spec.name.name, source),
+                )
+            })
+            .collect(),
+    }
+}
+
+fn function_item(func: &FunctionDecl) -> ApiItem {
+    let params: Vec<ApiParam> = func
+        .params
+        .iter()
+        .map(|param| {
+            let ownership = match (&param.ownership, param.ownership_explicit) {
+                (Some(OwnershipAnnotation::Own), true) => "own ",
+                (Some(OwnershipAnnotation::Borrow), true) => "borrow ",
+                (Some(OwnershipAnnotation::Share), true) => "share ",
+                _ => "",
+            };
+            ApiParam {
+                ty: format!("{}{}", ownership, format_type_ref(&param.type_ref)),
+                optional: param.default_value.is_some(),
+            }
+        })
+        .collect();
+
+    let rendered: Vec<String> = func
+        .params
+        .iter()
+        .zip(&params)
+        .map(|(param, shape)| {
+            let default = if shape.optional { " = …" } else { "" };
+            format!("{}: {}{}", param.name.name, shape.ty, default)
+        })
+        .collect();
+    let returns = func
+        .return_type
+        .as_ref()
+        .map(|ty| format!(": {}", format_type_ref(ty)))
+        .unwrap_or_default();
+    let head = format!(
+        "{}fn {}{}",
+        if func.is_async { "async " } else { "" },
+        func.name.name,
+        type_params(&func.type_params)
+    );
+
+    ApiItem {
+        signature: format!("{}({}){}", head, rendered.join(", "), returns),
+        contract: format!("{}(){}", head, returns),
+        params: Some(params),
+    }
+}
+
+/// Classify a changed item; a function that only gained optional trailing
+/// parameters (or made existing ones optional) is still callable the old way
+fn compare_items(path: &str, old: &ApiItem, new: &ApiItem) -> ApiChange {
+    let still_callable = match (&old.params, &new.params) {
+        (Some(old_params), Some(new_params)) => {
+            old.contract == new.contract
+                && new_params.len() >= old_params.len()
+                && old_params
+                    .iter()
+                    .zip(new_params)
+                    .all(|(a, b)| a.ty == b.ty && (a.optional || !b.optional))
+                && new_params[old_params.len()..].iter().all(|p| p.optional)
+        }
+        _ => false,
+    };
+    ApiChange {
+        kind: if still_callable {
+            ChangeKind::Additive
+        } else {
+            ChangeKind::Breaking
+        },
+        path: path.to_string(),
+        description: format!("`{}` → `{}`", old.signature, new.signature),
+    }
+}
+
+fn annotation(type_ref: Option<&TypeRef>) -> String {
+    type_ref
+        .map(|ty| format!(": {}", format_type_ref(ty)))
+        .unwrap_or_default()
+}
+
+fn type_params(params: &[TypeParam]) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+    let rendered: Vec<String> = params
+        .iter()
+        .map(|param| {
+            let bounds: Vec<&str> = param
+                .trait_bounds
+                .iter()
+                .map(|bound| bound.trait_name.as_str())
+                .collect();
+            if bounds.is_empty() {
+                param.name.clone()
+            } else {
+                format!("{}: {}", param.name, bounds.join(" + "))
+            }
+        })
+        .collect();
+    format!("<{}>", rendered.join(", "))
+}
+
+fn fields(fields: &[StructField]) -> String {
+    let rendered: Vec<String> = fields
+        .iter()
+        .map(|field| format!("{}: {}", field.name.name, format_type_ref(&field.type_ref)))
+        .collect();
+    rendered.join(", ")
+}
+
+fn format_type_ref(type_ref: &TypeRef) -> String {
+    let join = |types: &[TypeRef], sep: &str| {
+        types
+            .iter()
+            .map(format_type_ref)
+            .collect::<Vec<_>>()
+            .join(sep)
+    };
+    match type_ref {
+        TypeRef::Named(name, _) => name.clone(),
+        TypeRef::Array(inner, _) => format!("{}[]", format_type_ref(inner)),
+        TypeRef::Function {
+            params,
+            return_type,
+            ..
+        } => format!(
+            "({}) -> {}",
+            join(params, ", "),
+            format_type_ref(return_type)
+        ),
+        TypeRef::Structural { members, .. } => {
+            let rendered: Vec<String> = members
+                .iter()
+                .map(|m| format!("{}: {}", m.name, format_type_ref(&m.type_ref)))
+                .collect();
+            format!("{{ {} }}", rendered.join(", "))
+        }
+        TypeRef::Generic {
+            name, type_args, ..
+        } => format!("{}<{}>", name, join(type_args, ", ")),
+        TypeRef::Union { members, .. } => join(members, " | "),
+        TypeRef::Intersection { members, .. } => join(members, " & "),
+        TypeRef::Future { inner, .. } => format!("Future<{}>", format_type_ref(inner)),
+        TypeRef::Tuple { elements, .. } => format!("({})", join(elements, ", ")),
+        TypeRef::SelfType(_) => "self".to_string(),
+    }
+}
+
+/// `src/` when the package has one, else the project directory
+fn source_root(project_dir: &Path) -> PathBuf {
+    let src = project_dir.join("src");
+    if src.is_dir() {
+        src
+    } else {
+        project_dir.to_path_buf()
+    }
+}
+
+/// Directories that never hold importable package modules
+fn is_skipped_dir(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.') || matches!(name, "target" | "tests"))
+}
+
+fn is_atlas_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("atlas" | "atl")
+    )
+}
+
+/// `math/vector.atl` → `math/vector`
+fn module_name(relative: &Path) -> String {
+    relative
+        .with_extension("")
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn git(project_dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(project_dir)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(files: &[(&str, &str)]) -> ApiReport {
+        ApiReport::from_sources(
+            files
+                .iter()
+                .map(|(module, source)| (module.to_string(), source.to_string())),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_report_lists_exports_only() {
+        let api = report(&[(
+            "math",
+            "export fn add(a: number, b: number): number { return a + b; }\n\
+             fn helper(): number { return 1; }\n\
+             export const PI: number = 3.14;\n",
+        )]);
+        assert_eq!(
+            api.items.keys().collect::<Vec<_>>(),
+            vec!["math::PI", "math::add"]
+        );
+        assert_eq!(
+            api.items["math::add"].signature,
+            "fn add(a: number, b: number): number"
+        );
+    }
+
+    #[test]
+    fn test_diff_classifies_changes() {
+        let old = report(&[(
+            "lib",
+            "export fn f(a: number): number { return a; }\n\
+             export fn g(a: number): number { return a; }\n\
+             export fn gone(): number { return 0; }\n",
+        )]);
+        let new = report(&[(
+            "lib",
+            "export fn f(x: number, y: number = 1): number { return x; }\n\
+             export fn g(a: string): number { return 0; }\n\
+             export fn added(): number { return 0; }\n",
+        )]);
+
+        let changes = diff(&old, &new);
+        let kind_of = |path: &str| changes.iter().find(|c| c.path == path).unwrap().kind;
+        assert_eq!(kind_of("lib::f"), ChangeKind::Additive);
+        assert_eq!(kind_of("lib::g"), ChangeKind::Breaking);
+        assert_eq!(kind_of("lib::gone"), ChangeKind::Breaking);
+        assert_eq!(kind_of("lib::added"), ChangeKind::Additive);
+        assert_eq!(changes[0].kind, ChangeKind::Breaking, "breaking first");
+    }
+
+    #[test]
+    fn test_check_version_requires_matching_bump() {
+        let breaking = [ApiChange {
+            kind: ChangeKind::Breaking,
+            path: "lib::f".to_string(),
+            description: String::new(),
+        }];
+        let additive = [ApiChange {
+            kind: ChangeKind::Additive,
+            ..breaking[0].clone()
+        }];
+        let v = |s: &str| Version::parse(s).unwrap();
+
+        assert!(check_version(&v("1.2.0"), &v("1.3.0"), &breaking).is_err());
+        assert!(check_version(&v("1.2.0"), &v("2.0.0"), &breaking).is_ok());
+        assert!(check_version(&v("0.4.1"), &v("0.5.0"), &breaking).is_ok());
+        assert!(check_version(&v("1.2.0"), &v("1.2.1"), &additive).is_err());
+        assert!(check_version(&v("0.4.1"), &v("0.4.2"), &additive).is_ok());
+        assert!(check_version(&v("1.2.0"), &v("1.2.0"), &[]).is_err());
+    }
+
+    #[test]
+    fn test_report_from_git_tag() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path();
+        let run = |args: &[&str]| {
+            Command::new("git")
+                .arg("-C")
+                .arg(path)
+                .args(args)
+                .output()
+                .unwrap()
+        };
+        std::fs::create_dir(path.join("src")).unwrap();
+        std::fs::write(
+            path.join("src/lib.atl"),
+            "export fn old(): number { return 1; }\n",
+        )
+        .unwrap();
+        run(&["init"]);
+        run(&["config", "user.email", "test@example.com"]);
+        run(&["config", "user.name", "Test"]);
+        run(&["add", "."]);
+        run(&["commit", "-m", "init"]);
+        run(&["tag", "v1.0.0"]);
+        std::fs::write(
+            path.join("src/lib.atl"),
+            "export fn added(): number { return 1; }\n",
+        )
+        .unwrap();
+
+        let old = ApiReport::from_git_tag(path, "v1.0.0").unwrap();
+        let new = ApiReport::from_dir(path).unwrap();
+        assert!(old.items.contains_key("lib::old"));
+        assert!(new.items.contains_key("lib::added"));
+        assert!(ApiReport::from_git_tag(path, "v9.9.9").is_err());
+    }
+}
@@ -0,0 +1,250 @@
+//! Bundle command - package a program as a standalone executable (atlas bundle)
+//!
+//! Compiles the entry file with every module and locked package it imports
+//! into one bytecode program, then appends it to a copy of `atlas-launcher`
+//! together with the project's `[security]` settings. The result runs on
+//! machines without the Atlas toolchain.
+
+use anyhow::{bail, Context, Result};
+use atlas_build::binary_emit::{emit_bundle, find_launcher_binary, BundleManifest};
+use atlas_build::PackageManifest;
+use atlas_config::SecurityConfig;
+use atlas_runtime::{Atlas, SecurityContext};
+use std::path::{Path, PathBuf};
+
+/// Arguments for the bundle command
+#[derive(Debug, Clone)]
+pub struct BundleArgs {
+    /// Entry file (defaults to the project's entry point)
+    pub file: Option<PathBuf>,
+    /// Output path (defaults to `target/bundle/<name>`)
+    pub output: Option<PathBuf>,
+    /// Grant every permission instead of the project's `[security]` settings
+    pub allow_all: bool,
+    /// Project directory (defaults to current)
+    pub project_dir: PathBuf,
+    /// Quiet output (errors only)
+    pub quiet: bool,
+}
+
+impl Default for BundleArgs {
+    fn default() -> Self {
+        Self {
+            file: None,
+            output: None,
+            allow_all: false,
+            project_dir: PathBuf::from("."),
+            quiet: false,
+        }
+    }
+}
+
+/// Run the bundle command, returning the path of the written executable
+pub fn run(args: BundleArgs) -> Result<PathBuf> {
+    let entry = match &args.file {
+        Some(file) => file.clone(),
+        None => find_entry(&args.project_dir)?,
+    };
+    let entry_dir = entry.parent().unwrap_or(Path::new("."));
+    let root = find_project_root(entry_dir).unwrap_or_else(|| entry_dir.to_path_buf());
+
+    if let Err(msg) = atlas_build::validate_packages(&root) {
+        bail!("{}", msg);
+    }
+
+    let security = if args.allow_all {
+        None
+    } else {
+        let security = load_security(&root)?;
+        if security.is_none() && !args.quiet {
+            eprintln!(
+                "warning: no [security] section in atlas.toml — the bundle runs with all permissions"
+            );
+        }
+        security
+    };
+
+    // Compilation reads the project's modules and packages from disk
+    let runtime = Atlas::new_with_security(SecurityContext::allow_all());
+    let entry_str = entry.to_string_lossy();
+    let program = match runtime.compile_file(&entry_str) {
+        Ok(program) => program,
+        Err(diagnostics) => {
+            let source = std::fs::read_to_string(&entry).ok();
+            crate::diagnostics::emit_diagnostics_stderr(
+                &diagnostics,
+                source.as_deref(),
+                Some(&entry_str),
+            );
+            bail!("Failed to compile {}", entry.display());
+        }
+    };
+
+    let launcher = find_launcher_binary().ok_or(atlas_build::BuildError::LauncherNotFound)?;
+
+    let output = match args.output {
+        Some(output) => output,
+        None => {
+            let name = bundle_name(&root, &entry);
+            let file_name = if cfg!(windows) {
+                format!("{name}.exe")
+            } else {
+                name
+            };
+            root.join("target").join("bundle").join(file_name)
+        }
+    };
+    if let Some(dir) = output.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+
+    let manifest = BundleManifest {
+        main: program.has_main,
+        security,
+    };
+    emit_bundle(
+        &launcher,
+        &[program.bytecode.to_bytes()],
+        &manifest,
+        &output,
+    )?;
+
+    if !args.quiet {
+        let size = std::fs::metadata(&output).map(|m| m.len()).unwrap_or(0);
+        println!(
+            "Bundled {} → {} ({:.1} MB)",
+            entry.display(),
+            output.display(),
+            size as f64 / (1024.0 * 1024.0)
+        );
+    }
+    Ok(output)
+}
+
+/// Entry point of the project: `[entry] main`, else `src/main.atl(as)`
+fn find_entry(project_dir: &Path) -> Result<PathBuf> {
+    let root = find_project_root(project_dir).unwrap_or_else(|| project_dir.to_path_buf());
+
+    if let Ok(manifest) = PackageManifest::from_file(&root.join("atlas.toml")) {
+        if let Some(main) = manifest.entry.and_then(|entry| entry.main) {
+            return Ok(root.join(main));
+        }
+    }
+
+    ["src/main.atl", "src/main.atlas", "main.atl", "main.atlas"]
+        .iter()
+        .map(|candidate| root.join(candidate))
+        .find(|path| path.is_file())
+        .with_context(|| {
+            format!(
+                "No entry point found in {}. Pass a file or add [entry] main to atlas.toml",
+                root.display()
+            )
+        })
+}
+
+/// Nearest directory at or above `dir` holding an atlas.toml
+fn find_project_root(dir: &Path) -> Option<PathBuf> {
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    dir.ancestors()
+        .find(|ancestor| ancestor.join("atlas.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// The `[security]` section of the project's atlas.toml, if any
+fn load_security(root: &Path) -> Result<Option<SecurityConfig>> {
+    let manifest_path = root.join("atlas.toml");
+    let Ok(content) = std::fs::read_to_string(&manifest_path) else {
+        return Ok(None);
+    };
+    let table: toml::Table = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+
+    let Some(section) = table.get("security") else {
+        return Ok(None);
+    };
+    let config: SecurityConfig = section
+        .clone()
+        .try_into()
+        .context("Invalid [security] section in atlas.toml")?;
+    if !SecurityConfig::is_valid_mode(config.mode()) {
+        bail!(
+            "Invalid security mode '{}' (expected none, standard or strict)",
+            config.mode()
+        );
+    }
+    Ok(Some(config))
+}
+
+/// Package name from atlas.toml, else the entry file's stem
+fn bundle_name(root: &Path, entry: &Path) -> String {
+    PackageManifest::from_file(&root.join("atlas.toml"))
+        .map(|manifest| manifest.package.name)
+        .unwrap_or_else(|_| {
+            entry
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "bundle".to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_security_section() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("atlas.toml"),
+            "[package]\nname = \"tool\"\nversion = \"0.1.0\"\n\n[security]\nmode = \"strict\"\nnetwork = { allow = [\"api.example.com\"] }\n",
+        )
+        .unwrap();
+
+        let config = load_security(dir.path()).unwrap().unwrap();
+        assert_eq!(config.mode(), "strict");
+        assert_eq!(
+            config.network.unwrap().allow,
+            vec!["api.example.com".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_load_security_rejects_unknown_mode() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("atlas.toml"),
+            "[security]\nmode = \"paranoid\"\n",
+        )
+        .unwrap();
+
+        assert!(load_security(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_find_entry_defaults_to_src_main() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/main.atl"), "fn main() {}").unwrap();
+
+        let entry = find_entry(dir.path()).unwrap();
+        assert!(entry.ends_with("src/main.atl"));
+    }
+
+    #[test]
+    fn test_bundle_reports_compile_errors() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("broken.atl");
+        std::fs::write(&file, "let x: number = \"nope\";").unwrap();
+
+        let result = run(BundleArgs {
+            file: Some(file),
+            allow_all: true,
+            project_dir: dir.path().to_path_buf(),
+            quiet: true,
+            ..BundleArgs::default()
+        });
+        assert!(result.is_err());
+    }
+}
@@ -1,8 +1,97 @@
-//! check command — DEPRECATED
-//!
-//! `atlas check` only analyzed a single file in isolation and missed cross-module
-//! errors, making it misleading. Use `atlas run` instead.
-//!
-//! The command is kept registered in the CLI so users get a clear deprecation
-//! message rather than "unknown command". The dispatch in main.rs prints the
-//! message and exits 1 — this module is no longer called.
+//! Check command - type-check Atlas source files without running them
+
+use super::run::compile_with_daemon;
+use anyhow::Result;
+use atlas_lsp::daemon::CompileResult;
+use atlas_runtime::diagnostic::error_codes::EXTERN_NOT_PRECOMPILABLE;
+use atlas_runtime::{Atlas, BytecodeCache, Diagnostic, SecurityContext};
+use std::path::Path;
+
+/// Check an Atlas source file and every module it imports
+///
+/// A running `atlas daemon` for the project checks the file from its warm
+/// module cache; otherwise the file is checked here. Diagnostics go to
+/// stderr, or to stdout as JSON when `json_output` is set.
+pub fn run(file_path: &str, json_output: bool) -> Result<()> {
+    let diagnostics = check_file(Path::new(file_path));
+    if diagnostics.is_empty() {
+        if json_output {
+            crate::diagnostics::emit_diagnostics_json(&[], None, Some(file_path));
+        }
+        return Ok(());
+    }
+
+    let source = std::fs::read_to_string(file_path).ok();
+    if json_output {
+        crate::diagnostics::emit_diagnostics_json(&diagnostics, source.as_deref(), Some(file_path));
+    } else {
+        eprintln!("Errors occurred while checking {}:", file_path);
+        crate::diagnostics::emit_diagnostics_stderr(
+            &diagnostics,
+            source.as_deref(),
+            Some(file_path),
+        );
+    }
+    Err(anyhow::anyhow!("Check failed"))
+}
+
+/// Errors in `path` and its imports, through the daemon when one answers
+pub(crate) fn check_file(path: &Path) -> Vec<Diagnostic> {
+    match compile_with_daemon(path) {
+        Some(CompileResult::Compiled(_)) => Vec::new(),
+        Some(CompileResult::Diagnostics(diagnostics)) => diagnostics,
+        Some(CompileResult::Unsupported(_)) | None => check_locally(path),
+    }
+}
+
+fn check_locally(path: &Path) -> Vec<Diagnostic> {
+    let runtime = Atlas::new_with_security(SecurityContext::allow_all())
+        .with_bytecode_cache(BytecodeCache::for_packages());
+    match runtime.compile_file(&path.to_string_lossy()) {
+        Ok(_) => Vec::new(),
+        // The program checked fine; its extern bindings just cannot be precompiled
+        Err(diagnostics)
+            if diagnostics
+                .iter()
+                .all(|d| d.code == EXTERN_NOT_PRECOMPILABLE.code) =>
+        {
+            Vec::new()
+        }
+        Err(diagnostics) => diagnostics,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_check_clean_file() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("main.atl");
+        fs::write(&file, "let x: number = 1 + 2;\n").unwrap();
+        assert!(run(file.to_str().unwrap(), false).is_ok());
+    }
+
+    #[test]
+    fn test_check_reports_cross_module_errors() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("math.atl"),
+            "export fn add(a: number, b: number): number { return a + b; }\n",
+        )
+        .unwrap();
+        let file = dir.path().join("main.atl");
+        fs::write(
+            &file,
+            "import { add } from \"./math\";\nlet s: string = add(1, 2);\n",
+        )
+        .unwrap();
+
+        let diagnostics = check_file(&file);
+        assert!(!diagnostics.is_empty());
+        assert!(run(file.to_str().unwrap(), true).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+//! Compile command - ahead-of-time compile a program to bytecode (atlas compile)
+//!
+//! Checks and compiles the entry file with every module and locked package
+//! it imports into one `.atbc` file, which `VM::from_file` runs without the
+//! sources. Installed packages go through the package bytecode cache, so
+//! compiling again (or a later `atlas run`) only recompiles what changed.
+
+use anyhow::{bail, Context, Result};
+use atlas_runtime::bytecode::BYTECODE_FILE_EXTENSION;
+use atlas_runtime::{Atlas, BytecodeCache, SecurityContext};
+use std::path::{Path, PathBuf};
+
+/// Arguments for the compile command
+#[derive(Debug, Clone, Default)]
+pub struct CompileArgs {
+    /// Entry file
+    pub file: PathBuf,
+    /// Output path (defaults to the entry file with an `.atbc` extension)
+    pub output: Option<PathBuf>,
+    /// Compile every package from source, bypassing the bytecode cache
+    pub no_cache: bool,
+    /// Output diagnostics in JSON format
+    pub json: bool,
+    /// Quiet output (errors only)
+    pub quiet: bool,
+}
+
+/// Run the compile command, returning the path of the written file
+pub fn run(args: CompileArgs) -> Result<PathBuf> {
+    let entry_dir = args.file.parent().unwrap_or(Path::new("."));
+    if let Err(msg) = atlas_build::validate_packages(entry_dir) {
+        bail!("{}", msg);
+    }
+
+    // Compilation reads the project's modules and packages from disk
+    let mut runtime = Atlas::new_with_security(SecurityContext::allow_all());
+    if !args.no_cache {
+        runtime = runtime.with_bytecode_cache(BytecodeCache::for_packages());
+    }
+
+    let entry = args.file.to_string_lossy();
+    let program = match runtime.compile_file(&entry) {
+        Ok(program) => program,
+        Err(diagnostics) => {
+            let source = std::fs::read_to_string(&args.file).ok();
+            if args.json {
+                crate::diagnostics::emit_diagnostics_json(
+                    &diagnostics,
+                    source.as_deref(),
+                    Some(&entry),
+                );
+            } else {
+                crate::diagnostics::emit_diagnostics_stderr(
+                    &diagnostics,
+                    source.as_deref(),
+                    Some(&entry),
+                );
+            }
+            bail!("Failed to compile {}", args.file.display());
+        }
+    };
+
+    let output = args
+        .output
+        .unwrap_or_else(|| args.file.with_extension(BYTECODE_FILE_EXTENSION));
+    if let Some(dir) = output.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    program
+        .bytecode
+        .write_to_file(&output)
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+
+    if !args.quiet {
+        let cached = runtime.bytecode_cache().map_or(0, |cache| cache.hits());
+        println!(
+            "Compiled {} → {} ({} cached package module{})",
+            args.file.display(),
+            output.display(),
+            cached,
+            if cached == 1 { "" } else { "s" }
+        );
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atlas_runtime::VM;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_compile_writes_runnable_bytecode() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("util.atl"),
+            "export fn double(n: number): number { return n * 2; }",
+        )
+        .unwrap();
+        let file = dir.path().join("main.atl");
+        std::fs::write(&file, r#"import { double } from "./util"; double(21);"#).unwrap();
+
+        let output = run(CompileArgs {
+            file,
+            quiet: true,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(output, dir.path().join("main.atbc"));
+
+        let mut vm = VM::from_file(&output).unwrap();
+        let value = vm.run(&SecurityContext::allow_all()).unwrap();
+        assert_eq!(value, Some(atlas_runtime::Value::Number(42.0)));
+    }
+
+    #[test]
+    fn test_compile_reports_type_errors() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("main.atl");
+        std::fs::write(&file, r#"let x: number = "nope";"#).unwrap();
+
+        let err = run(CompileArgs {
+            file,
+            output: Some(dir.path().join("out.atbc")),
+            quiet: true,
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("Failed to compile"));
+        assert!(!dir.path().join("out.atbc").exists());
+    }
+}
@@ -0,0 +1,156 @@
+//! Daemon command - keep a workspace warm for `atlas run` and the LSP
+//!
+//! `atlas daemon` starts a background process serving the project root
+//! (see `atlas_lsp::daemon`). `atlas run` compiles through it when one is
+//! running, and the language server takes its workspace symbol index from
+//! it instead of parsing every file at startup.
+
+use anyhow::{Context, Result};
+use atlas_lsp::daemon::{socket_path, Daemon, DaemonClient};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How long `atlas daemon start` waits for the daemon to answer
+const START_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Arguments shared by the daemon subcommands
+#[derive(Debug, Clone)]
+pub struct DaemonArgs {
+    /// Workspace root; defaults to the project enclosing the current directory
+    pub root: Option<PathBuf>,
+    /// Stop after this many minutes without a request (0 = never)
+    pub idle_timeout: u64,
+    /// Output status as JSON
+    pub json: bool,
+}
+
+impl Default for DaemonArgs {
+    fn default() -> Self {
+        Self {
+            root: None,
+            idle_timeout: 60,
+            json: false,
+        }
+    }
+}
+
+impl DaemonArgs {
+    /// The root to serve: `--root`, else the nearest directory with an
+    /// atlas.toml, else the current directory
+    fn root(&self) -> Result<PathBuf> {
+        let root = match &self.root {
+            Some(root) => root.clone(),
+            None => {
+                let cwd = std::env::current_dir()?;
+                cwd.ancestors()
+                    .find(|dir| dir.join("atlas.toml").exists())
+                    .unwrap_or(&cwd)
+                    .to_path_buf()
+            }
+        };
+        root.canonicalize()
+            .with_context(|| format!("Cannot open workspace root {}", root.display()))
+    }
+}
+
+/// Start a daemon in the background and wait until it answers
+pub fn start(args: DaemonArgs) -> Result<()> {
+    let root = args.root()?;
+    let socket = socket_path(&root);
+    if let Ok(status) = DaemonClient::connect(&socket).and_then(|mut d| d.status()) {
+        println!(
+            "Atlas daemon already serving {} (pid {})",
+            root.display(),
+            status.pid
+        );
+        return Ok(());
+    }
+
+    let mut command = std::process::Command::new(std::env::current_exe()?);
+    command
+        .args(["daemon", "serve", "--root"])
+        .arg(&root)
+        .arg(format!("--idle-timeout={}", args.idle_timeout))
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    // Own process group, so Ctrl-C in this terminal does not reach it
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+    let mut child = command.spawn().context("Failed to start the daemon")?;
+
+    let started = Instant::now();
+    loop {
+        if let Ok(status) = DaemonClient::connect(&socket).and_then(|mut d| d.status()) {
+            println!(
+                "Atlas daemon serving {} (pid {})",
+                root.display(),
+                status.pid
+            );
+            return Ok(());
+        }
+        if let Some(exit) = child.try_wait()? {
+            anyhow::bail!(
+                "Daemon exited with {}; run `atlas daemon serve` to see why",
+                exit
+            );
+        }
+        if started.elapsed() > START_TIMEOUT {
+            anyhow::bail!("Daemon did not answer on {}", socket.display());
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// Serve in the foreground until stopped
+pub fn serve(args: DaemonArgs) -> Result<()> {
+    let root = args.root()?;
+    let socket = socket_path(&root);
+    let mut daemon = Daemon::bind(&root, &socket)
+        .with_context(|| format!("Cannot listen on {}", socket.display()))?;
+    if args.idle_timeout > 0 {
+        daemon = daemon.with_idle_timeout(Duration::from_secs(args.idle_timeout * 60));
+    }
+    eprintln!(
+        "Atlas daemon serving {} on {}",
+        daemon.root().display(),
+        socket.display()
+    );
+    daemon.serve()?;
+    Ok(())
+}
+
+/// Print what the daemon for the root is serving
+pub fn status(args: DaemonArgs) -> Result<()> {
+    let root = args.root()?;
+    let mut client = connect(&root)?;
+    let status = client.status()?;
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&status)?);
+    } else {
+        println!("Root:          {}", status.root.display());
+        println!("PID:           {}", status.pid);
+        println!("Version:       {}", status.version);
+        println!("Uptime:        {}s", status.uptime_secs);
+        println!("Requests:      {}", status.requests);
+        println!(
+            "Modules:       {} cached ({} hits, {} misses)",
+            status.modules, status.module_hits, status.module_misses
+        );
+        println!("Indexed files: {}", status.indexed_files);
+    }
+    Ok(())
+}
+
+/// Stop the daemon for the root
+pub fn stop(args: DaemonArgs) -> Result<()> {
+    let root = args.root()?;
+    connect(&root)?.shutdown()?;
+    println!("Atlas daemon for {} stopped", root.display());
+    Ok(())
+}
+
+fn connect(root: &Path) -> Result<DaemonClient> {
+    DaemonClient::connect(&socket_path(root))
+        .with_context(|| format!("No Atlas daemon is serving {}", root.display()))
+}
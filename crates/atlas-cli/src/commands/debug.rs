@@ -1,11 +1,12 @@
 //! Debug command - interactive debugger for Atlas programs
 //!
 //! Provides a command-line debugger with breakpoints, stepping,
-//! variable inspection, and expression evaluation.
+//! variable inspection, and expression evaluation, or with `--dap` a Debug
+//! Adapter Protocol server for graphical debuggers.
 
 use anyhow::Result;
 use atlas_runtime::debugger::DebuggerSession;
-use atlas_runtime::{Compiler, DiagnosticLevel, Lexer, Parser};
+use atlas_runtime::{Atlas, SecurityContext};
 use std::fs;
 use std::path::Path;
 
@@ -14,13 +15,16 @@ use crate::debugger::repl::DebugRepl;
 /// Arguments for the debug command
 #[derive(Debug, Clone)]
 pub struct DebugArgs {
-    /// Path to the Atlas source file
+    /// Path to the Atlas source file (optional with `dap`, where the client
+    /// can launch one instead)
     pub file: String,
     /// Initial breakpoints (line numbers)
     pub breakpoints: Vec<u32>,
     /// Stop at entry point (reserved for future use)
     #[allow(dead_code)]
     pub stop_at_entry: bool,
+    /// Serve the Debug Adapter Protocol on stdio instead of the REPL
+    pub dap: bool,
 }
 
 impl Default for DebugArgs {
@@ -29,59 +33,39 @@ impl Default for DebugArgs {
             file: String::new(),
             breakpoints: Vec::new(),
             stop_at_entry: true,
+            dap: false,
         }
     }
 }
 
 /// Run the debugger
 pub fn run(args: DebugArgs) -> Result<()> {
+    if args.dap {
+        let program = (!args.file.is_empty()).then_some(args.file.as_str());
+        crate::debugger::dap::run_stdio(program)?;
+        return Ok(());
+    }
+
     let path = Path::new(&args.file);
 
     // Read the source file
     let source = fs::read_to_string(path)
         .map_err(|e| anyhow::anyhow!("Failed to read source file '{}': {}", args.file, e))?;
 
-    // Compile to bytecode
-    let (tokens, lexer_diags) = Lexer::new(&source).with_file(args.file.as_str()).tokenize();
-
-    // Check for lexer errors
-    let has_lexer_errors = lexer_diags
-        .iter()
-        .any(|d| d.level == DiagnosticLevel::Error);
-    if has_lexer_errors {
-        eprintln!("\x1b[31mLexer errors:\x1b[0m");
-        let errors: Vec<_> = lexer_diags
-            .iter()
-            .filter(|d| d.level == DiagnosticLevel::Error)
-            .cloned()
-            .collect();
-        crate::diagnostics::emit_diagnostics_stderr(&errors, Some(&source), Some(&args.file));
-        anyhow::bail!("Failed to lex source file");
-    }
-
-    let (ast, parser_diags) = Parser::new(tokens).parse();
-
-    // Check for parser errors
-    let has_parser_errors = parser_diags
-        .iter()
-        .any(|d| d.level == DiagnosticLevel::Error);
-    if has_parser_errors {
-        eprintln!("\x1b[31mParser errors:\x1b[0m");
-        let errors: Vec<_> = parser_diags
-            .iter()
-            .filter(|d| d.level == DiagnosticLevel::Error)
-            .cloned()
-            .collect();
-        crate::diagnostics::emit_diagnostics_stderr(&errors, Some(&source), Some(&args.file));
-        anyhow::bail!("Failed to parse source file");
-    }
-
-    // Compile to bytecode
-    let mut compiler = Compiler::new();
-    let bytecode = compiler.compile(&ast).map_err(|diags| {
-        crate::diagnostics::emit_diagnostics_stderr(&diags, Some(&source), Some(&args.file));
-        anyhow::anyhow!("Failed to compile source file")
-    })?;
+    // Compile the entry file together with its imports; the debugger's source
+    // map resolves instructions from imported modules to their own files
+    let runtime = Atlas::new_with_security(SecurityContext::allow_all());
+    let bytecode = match runtime.compile_file(&args.file) {
+        Ok(program) => program.bytecode,
+        Err(diagnostics) => {
+            crate::diagnostics::emit_diagnostics_stderr(
+                &diagnostics,
+                Some(&source),
+                Some(&args.file),
+            );
+            anyhow::bail!("Failed to compile source file");
+        }
+    };
 
     // Create debugger session
     let file_name = path
@@ -155,6 +139,7 @@ mod tests {
             file: "test.atlas".to_string(),
             breakpoints: vec![1, 5, 10],
             stop_at_entry: false,
+            dap: false,
         };
 
         assert_eq!(args.breakpoints.len(), 3);
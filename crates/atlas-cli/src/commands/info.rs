@@ -0,0 +1,130 @@
+//! Show registry details for a package (atlas info)
+
+use super::search::open_registry;
+use anyhow::{Context, Result};
+use atlas_package::PackageInfo;
+
+/// Arguments for the info command
+#[derive(Debug, Clone, Default)]
+pub struct InfoArgs {
+    /// Package name
+    pub package: String,
+    /// Registry URL or local registry directory
+    pub registry: Option<String>,
+}
+
+/// Run the info command
+pub fn run(args: InfoArgs) -> Result<()> {
+    let info = open_registry(args.registry.as_deref())
+        .package_info(&args.package)
+        .with_context(|| format!("Could not get info for '{}'", args.package))?;
+    print!("{}", format_info(&info));
+    Ok(())
+}
+
+/// Render package details the way `atlas info` prints them
+pub fn format_info(info: &PackageInfo) -> String {
+    let mut out = format!("{} v{}\n", info.name, info.latest);
+    if let Some(description) = &info.description {
+        out.push_str(&format!("{}\n", description));
+    }
+    out.push('\n');
+
+    let fields = [
+        ("license", info.license.clone()),
+        ("repository", info.repository.clone()),
+        ("homepage", info.homepage.clone()),
+        ("downloads", info.downloads.map(|d| d.to_string())),
+    ];
+    for (label, value) in fields {
+        if let Some(value) = value {
+            out.push_str(&format!("{:<14}{}\n", format!("{}:", label), value));
+        }
+    }
+    if !info.keywords.is_empty() {
+        out.push_str(&format!(
+            "{:<14}{}\n",
+            "keywords:",
+            info.keywords.join(", ")
+        ));
+    }
+
+    let versions: Vec<String> = info
+        .versions
+        .iter()
+        .rev()
+        .map(|e| {
+            if e.yanked {
+                format!("{} (yanked)", e.version)
+            } else {
+                e.version.to_string()
+            }
+        })
+        .collect();
+    out.push_str(&format!("{:<14}{}\n", "versions:", versions.join(", ")));
+    if !info.features.is_empty() {
+        out.push_str(&format!(
+            "{:<14}{}\n",
+            "features:",
+            info.features.join(", ")
+        ));
+    }
+    if info.dependencies.is_empty() {
+        out.push_str(&format!("{:<14}none\n", "dependencies:"));
+    } else {
+        out.push_str(&format!(
+            "{:<14}{}\n",
+            "dependencies:",
+            info.dependencies.join(", ")
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_info_from_local_registry() {
+        let temp = TempDir::new().expect("tempdir");
+        for (version, metadata) in [
+            ("1.0.0", "license = \"MIT\"\n"),
+            (
+                "1.1.0",
+                "description = \"HTTP client\"\nlicense = \"MIT\"\ndownloads = 12\ndependencies = [\"url\"]\n\n[features]\ntls = []\n",
+            ),
+            ("2.0.0", "yanked = true\n"),
+        ] {
+            let dir = temp.path().join("http").join(version);
+            fs::create_dir_all(&dir).expect("create version dir");
+            fs::write(dir.join("metadata.toml"), metadata).expect("write metadata");
+        }
+
+        let info = open_registry(Some(&temp.path().display().to_string()))
+            .package_info("http")
+            .unwrap();
+        let output = format_info(&info);
+        assert!(output.starts_with("http v1.1.0\nHTTP client\n"), "{output}");
+        assert!(output.contains("license:      MIT"), "{output}");
+        assert!(output.contains("downloads:    12"), "{output}");
+        assert!(
+            output.contains("versions:     2.0.0 (yanked), 1.1.0, 1.0.0"),
+            "{output}"
+        );
+        assert!(output.contains("features:     tls"), "{output}");
+        assert!(output.contains("dependencies: url"), "{output}");
+    }
+
+    #[test]
+    fn test_info_unknown_package_errors() {
+        let temp = TempDir::new().expect("tempdir");
+        let args = InfoArgs {
+            package: "missing".to_string(),
+            registry: Some(temp.path().display().to_string()),
+        };
+        assert!(run(args).is_err());
+    }
+}
@@ -2,12 +2,15 @@
 
 use anyhow::{bail, Context, Result};
 use atlas_package::installer::Installer;
+use atlas_package::lockfile::Lockfile;
 use atlas_package::manifest::PackageManifest;
+use atlas_package::target::{BuildContext, TargetPlatform};
+use atlas_package::{DuplicateLint, DuplicateReport};
 use std::path::{Path, PathBuf};
 
 /// Arguments for the install command
 #[derive(Debug, Clone)]
-#[allow(dead_code)] // packages + production used in P07 (filter by name, skip dev-deps)
+#[allow(dead_code)] // packages used in P07 (filter by name)
 pub struct InstallArgs {
     /// Specific packages to install (empty = all from manifest)
     pub packages: Vec<String>,
@@ -23,6 +26,9 @@ pub struct InstallArgs {
     pub verbose: bool,
     /// Quiet output (errors only)
     pub quiet: bool,
+    /// Registry URL or local registry directory (defaults to
+    /// `ATLAS_REGISTRY_URL`, then the default registry)
+    pub registry: Option<String>,
 }
 
 impl Default for InstallArgs {
@@ -35,6 +41,7 @@ impl Default for InstallArgs {
             dry_run: false,
             verbose: false,
             quiet: false,
+            registry: None,
         }
     }
 }
@@ -50,7 +57,13 @@ pub fn run(args: InstallArgs) -> Result<()> {
     let manifest =
         PackageManifest::from_file(&manifest_path).context("Failed to read atlas.toml")?;
 
-    if manifest.dependencies.is_empty() && manifest.dev_dependencies.is_empty() {
+    let context = if args.production {
+        BuildContext::production(TargetPlatform::host())
+    } else {
+        BuildContext::development(TargetPlatform::host())
+    };
+
+    if manifest.dependencies_for(&context).is_empty() {
         if !args.quiet {
             println!("Nothing to install.");
         }
@@ -62,7 +75,9 @@ pub fn run(args: InstallArgs) -> Result<()> {
         .parent()
         .unwrap_or_else(|| std::path::Path::new("."))
         .to_path_buf();
-    let installer = Installer::new(cache_dir, project_dir);
+    let installer = Installer::new(cache_dir, project_dir)
+        .with_context(context)
+        .with_registry(super::search::open_registry(args.registry.as_deref()));
 
     if args.dry_run {
         let plan = installer
@@ -90,7 +105,32 @@ pub fn run(args: InstallArgs) -> Result<()> {
         println!("Wrote atlas.lock ({} package(s))", lockfile.packages.len());
     }
 
-    Ok(())
+    check_duplicates(&manifest, &lockfile, args.quiet)
+}
+
+/// Report semver-incompatible duplicates and apply `[lints] duplicate-versions`
+fn check_duplicates(manifest: &PackageManifest, lockfile: &Lockfile, quiet: bool) -> Result<()> {
+    let lints = manifest.lints.clone().unwrap_or_default();
+    let report = DuplicateReport::from_lockfile(manifest, lockfile);
+    match report.check(&lints) {
+        DuplicateLint::Clean => Ok(()),
+        DuplicateLint::Warn => {
+            if !quiet {
+                eprintln!(
+                    "warning: {} duplicate package version(s) in the dependency graph\n{}",
+                    report.duplicate_count(),
+                    report.report()
+                );
+            }
+            Ok(())
+        }
+        DuplicateLint::Deny => bail!(
+            "{} duplicate package version(s) exceed the duplicate-budget of {}\n{}",
+            report.duplicate_count(),
+            lints.duplicate_budget,
+            report.report()
+        ),
+    }
 }
 
 fn get_cache_dir() -> PathBuf {
@@ -171,20 +211,22 @@ version = "0.1.0"
 
     #[test]
     fn test_install_registry_dep_errors() {
-        // Simple string deps are registry deps — must fail with helpful message.
+        // Simple string deps are registry deps — an empty registry has none of them.
         let temp = TempDir::new().expect("tempdir");
+        let registry = TempDir::new().expect("tempdir");
         create_test_manifest(temp.path());
 
         let args = InstallArgs {
             project_dir: temp.path().to_path_buf(),
             quiet: true,
+            registry: Some(registry.path().display().to_string()),
             ..Default::default()
         };
 
         let err = run(args).unwrap_err();
-        let msg = err.to_string();
+        let msg = format!("{err:#}");
         assert!(
-            msg.contains("registry") || msg.contains("community index") || msg.contains("install"),
+            msg.contains("not found") || msg.contains("install"),
             "unexpected error: {msg}"
         );
     }
@@ -204,6 +246,38 @@ version = "0.1.0"
         assert!(run(args).is_ok());
     }
 
+    #[test]
+    fn test_install_production_skips_dev_dependencies() {
+        let temp = TempDir::new().expect("tempdir");
+        let manifest = r#"[package]
+name = "test-project"
+version = "0.1.0"
+
+[dev-dependencies]
+test-utils = "^0.1"
+"#;
+        fs::write(temp.path().join("atlas.toml"), manifest).expect("write manifest");
+        let registry = TempDir::new().expect("tempdir");
+
+        // The registry dev-dep would fail to install; production never touches it.
+        let args = InstallArgs {
+            project_dir: temp.path().to_path_buf(),
+            production: true,
+            quiet: true,
+            registry: Some(registry.path().display().to_string()),
+            ..Default::default()
+        };
+        assert!(run(args).is_ok());
+
+        let args = InstallArgs {
+            project_dir: temp.path().to_path_buf(),
+            quiet: true,
+            registry: Some(registry.path().display().to_string()),
+            ..Default::default()
+        };
+        assert!(run(args).is_err());
+    }
+
     #[test]
     fn test_install_no_manifest() {
         let temp = TempDir::new().expect("tempdir");
@@ -215,4 +289,54 @@ version = "0.1.0"
 
         assert!(run(args).is_err());
     }
+
+    #[test]
+    fn test_install_denies_duplicates_over_budget() {
+        let temp = TempDir::new().expect("tempdir");
+        for (name, version) in [("json", "2.0.0"), ("web", "1.0.0")] {
+            let dir = temp.path().join(name);
+            fs::create_dir_all(&dir).expect("mkdir");
+            fs::write(
+                dir.join("atlas.toml"),
+                format!("[package]\nname = \"{name}\"\nversion = \"{version}\"\n"),
+            )
+            .expect("write manifest");
+        }
+        // web was built against json 1.x
+        fs::write(
+            temp.path().join("web").join("atlas.lock"),
+            "version = 1\n\n[[packages]]\nname = \"json\"\nversion = \"1.4.0\"\n\n[packages.source]\ntype = \"registry\"\n",
+        )
+        .expect("write lock");
+
+        let manifest = |lints: &str| {
+            format!(
+                "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\njson = {{ path = \"json\", version = \"2.0.0\" }}\nweb = {{ path = \"web\", version = \"1.0.0\" }}\n{lints}"
+            )
+        };
+        let args = InstallArgs {
+            project_dir: temp.path().to_path_buf(),
+            force: true,
+            quiet: true,
+            ..Default::default()
+        };
+
+        fs::write(temp.path().join("atlas.toml"), manifest("")).expect("write manifest");
+        assert!(run(args.clone()).is_ok(), "warn is the default level");
+
+        fs::write(
+            temp.path().join("atlas.toml"),
+            manifest("\n[lints]\nduplicate-versions = \"deny\"\n"),
+        )
+        .expect("write manifest");
+        let err = run(args.clone()).unwrap_err().to_string();
+        assert!(err.contains("app -> web v1.0.0 -> json v1.4.0"), "{err}");
+
+        fs::write(
+            temp.path().join("atlas.toml"),
+            manifest("\n[lints]\nduplicate-versions = \"deny\"\nduplicate-budget = 1\n"),
+        )
+        .expect("write manifest");
+        assert!(run(args).is_ok());
+    }
 }
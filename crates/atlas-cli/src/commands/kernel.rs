@@ -0,0 +1,111 @@
+//! Kernel command - Jupyter kernel for notebooks
+//!
+//! `atlas kernel --install` registers a kernelspec so Jupyter lists Atlas as
+//! a notebook language; Jupyter then launches
+//! `atlas kernel --connection-file <file>` itself (see `crate::kernel`).
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+
+/// Arguments for the kernel command
+#[derive(Debug, Clone, Default)]
+pub struct KernelArgs {
+    /// Connection file written by Jupyter
+    pub connection_file: Option<PathBuf>,
+    /// Write the kernelspec instead of serving
+    pub install: bool,
+    /// Kernelspec directory to install into (defaults to the user's
+    /// Jupyter data directory)
+    pub prefix: Option<PathBuf>,
+    /// Log requests to stderr
+    pub verbose: bool,
+}
+
+/// Run the kernel, or install its kernelspec
+pub fn run(args: KernelArgs) -> Result<()> {
+    if args.install {
+        let kernels_dir = match args.prefix {
+            Some(prefix) => prefix.join("share").join("jupyter").join("kernels"),
+            None => user_kernels_dir()?,
+        };
+        let spec_dir = install_kernelspec(&kernels_dir, &std::env::current_exe()?)?;
+        println!("Installed Atlas kernelspec in {}", spec_dir.display());
+        return Ok(());
+    }
+
+    let connection_file = args
+        .connection_file
+        .ok_or_else(|| anyhow!("--connection-file is required (Jupyter passes it)"))?;
+    crate::kernel::run(&connection_file, args.verbose)
+}
+
+/// `kernels/` under the per-user Jupyter data directory
+fn user_kernels_dir() -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os("JUPYTER_DATA_DIR") {
+        return Ok(PathBuf::from(dir).join("kernels"));
+    }
+    let data_dir = if cfg!(target_os = "macos") {
+        dirs::home_dir().map(|home| home.join("Library").join("Jupyter"))
+    } else {
+        dirs::data_dir().map(|data| data.join("jupyter"))
+    };
+    data_dir
+        .map(|dir| dir.join("kernels"))
+        .ok_or_else(|| anyhow!("cannot locate the Jupyter data directory; pass --prefix"))
+}
+
+/// Write `<kernels_dir>/atlas/kernel.json` pointing at `executable`
+fn install_kernelspec(kernels_dir: &Path, executable: &Path) -> Result<PathBuf> {
+    let spec_dir = kernels_dir.join("atlas");
+    std::fs::create_dir_all(&spec_dir)
+        .with_context(|| format!("cannot create {}", spec_dir.display()))?;
+    let spec = json!({
+        "argv": [
+            executable.to_string_lossy(),
+            "kernel",
+            "--connection-file",
+            "{connection_file}",
+        ],
+        "display_name": "Atlas",
+        "language": "atlas",
+        "metadata": { "debugger": false },
+    });
+    let spec_file = spec_dir.join("kernel.json");
+    std::fs::write(&spec_file, serde_json::to_string_pretty(&spec)? + "\n")
+        .with_context(|| format!("cannot write {}", spec_file.display()))?;
+    Ok(spec_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_install_kernelspec() {
+        let temp = TempDir::new().unwrap();
+        let spec_dir = install_kernelspec(temp.path(), Path::new("/usr/bin/atlas")).unwrap();
+        assert_eq!(spec_dir, temp.path().join("atlas"));
+
+        let spec: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(spec_dir.join("kernel.json")).unwrap())
+                .unwrap();
+        assert_eq!(
+            spec["argv"],
+            json!([
+                "/usr/bin/atlas",
+                "kernel",
+                "--connection-file",
+                "{connection_file}"
+            ])
+        );
+        assert_eq!(spec["language"], "atlas");
+    }
+
+    #[test]
+    fn test_missing_connection_file_is_an_error() {
+        let err = run(KernelArgs::default()).unwrap_err();
+        assert!(err.to_string().contains("--connection-file"));
+    }
+}
@@ -1,21 +1,33 @@
 pub mod add;
+pub mod api;
 pub mod ast;
 pub mod build;
+pub mod bundle;
 pub mod check;
+pub mod compile;
+pub mod daemon;
 pub mod debug;
 pub mod explain;
 pub mod fmt;
+pub mod info;
 pub mod init;
 pub mod install;
+pub mod kernel;
 pub mod lsp;
 pub mod new;
+pub mod playground;
 pub mod profile;
 pub mod publish;
 pub mod remove;
 pub mod repl;
 pub mod repl_tui;
+pub mod resolve;
 pub mod run;
+pub mod script;
+pub mod search;
+pub mod suppressions;
 pub mod test;
 pub mod typecheck;
 pub mod update;
 pub mod watch;
+pub mod why;
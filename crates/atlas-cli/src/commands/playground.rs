@@ -0,0 +1,489 @@
+//! Playground command - local web UI for trying Atlas code
+//!
+//! Serves a single-page editor over HTTP. Each submitted program runs in a
+//! fresh sandboxed runtime: no filesystem, network, process or environment
+//! access, plus time and memory limits. Output is streamed back while the
+//! program runs as newline-delimited JSON events:
+//!
+//! ```text
+//! {"type":"output","text":"Hello\n"}
+//! {"type":"result","value":"3"}
+//! {"type":"error","message":"Runtime error: ..."}
+//! {"type":"done","elapsedMs":12}
+//! ```
+
+use anyhow::Result;
+use atlas_runtime::api::{Runtime, RuntimeConfig};
+use atlas_runtime::stdlib::OutputWriter;
+use atlas_runtime::Value;
+use serde_json::json;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The web UI
+const INDEX_HTML: &str = include_str!("playground.html");
+
+/// Largest program accepted by `POST /run`
+const MAX_SOURCE_BYTES: usize = 64 * 1024;
+
+/// Largest request head (request line plus headers)
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+/// Programs allowed to run at once; further runs get `503`
+const MAX_CONCURRENT_RUNS: usize = 8;
+
+/// How long past the runtime's own timeout a run may go before the
+/// connection gives up on it (covers builtins that block between ticks)
+const TIMEOUT_GRACE: Duration = Duration::from_secs(1);
+
+/// Arguments for the playground command
+#[derive(Debug, Clone)]
+pub struct PlaygroundArgs {
+    /// Bind address
+    pub host: String,
+    /// Port to listen on
+    pub port: u16,
+    /// Execution time limit per run
+    pub timeout: Duration,
+    /// Memory limit per run, in bytes
+    pub max_memory: usize,
+    /// Log each request to stderr
+    pub verbose: bool,
+}
+
+impl Default for PlaygroundArgs {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: 8765,
+            timeout: Duration::from_secs(5),
+            max_memory: 16 * 1024 * 1024,
+            verbose: false,
+        }
+    }
+}
+
+/// Run the playground server until interrupted
+pub fn run(args: PlaygroundArgs) -> Result<()> {
+    let addr: SocketAddr = format!("{}:{}", args.host, args.port).parse()?;
+    let listener = TcpListener::bind(addr)?;
+    let addr = listener.local_addr()?;
+
+    eprintln!(
+        "\x1b[32mAtlas playground\x1b[0m running at \x1b[33mhttp://{}\x1b[0m",
+        addr
+    );
+    if !addr.ip().is_loopback() {
+        eprintln!(
+            "\x1b[33mwarning\x1b[0m: listening on a non-loopback address; anyone who can reach it can run code (sandboxed) on this machine"
+        );
+    }
+    eprintln!("Press Ctrl+C to stop.");
+
+    serve(listener, args)
+}
+
+/// Accept connections on `listener`, one thread per connection
+fn serve(listener: TcpListener, args: PlaygroundArgs) -> Result<()> {
+    let args = Arc::new(args);
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                if args.verbose {
+                    eprintln!("accept failed: {}", err);
+                }
+                continue;
+            }
+        };
+        let args = Arc::clone(&args);
+        let active = Arc::clone(&active);
+        thread::spawn(move || {
+            if let Err(err) = handle_connection(stream, &args, &active) {
+                if args.verbose {
+                    eprintln!("connection error: {}", err);
+                }
+            }
+        });
+    }
+    Ok(())
+}
+
+/// A parsed HTTP request
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+/// Why a request could not be read
+#[derive(Debug, PartialEq)]
+enum RequestError {
+    /// Malformed request line or headers (`400`)
+    BadRequest(&'static str),
+    /// Body over [`MAX_SOURCE_BYTES`] (`413`)
+    TooLarge,
+}
+
+/// Read one request (head and `Content-Length` body) from `reader`
+fn read_request(reader: &mut impl BufRead) -> std::io::Result<Result<Request, RequestError>> {
+    let mut head_len = 0;
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    head_len += line.len();
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target), Some(_version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Ok(Err(RequestError::BadRequest("malformed request line")));
+    };
+    let method = method.to_string();
+    let path = target.split('?').next().unwrap_or(target).to_string();
+
+    let mut content_length = 0usize;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(Err(RequestError::BadRequest("unexpected end of headers")));
+        }
+        head_len += line.len();
+        if head_len > MAX_HEAD_BYTES {
+            return Ok(Err(RequestError::BadRequest("request head too large")));
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                let Ok(len) = value.trim().parse() else {
+                    return Ok(Err(RequestError::BadRequest("invalid Content-Length")));
+                };
+                content_length = len;
+            }
+        }
+    }
+
+    if content_length > MAX_SOURCE_BYTES {
+        return Ok(Err(RequestError::TooLarge));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Ok(Request { method, path, body }))
+}
+
+fn handle_connection(stream: TcpStream, args: &PlaygroundArgs, active: &AtomicUsize) -> Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut stream = stream;
+    let request = match read_request(&mut reader)? {
+        Ok(request) => request,
+        Err(RequestError::BadRequest(msg)) => {
+            return write_response(&mut stream, "400 Bad Request", "text/plain", msg.as_bytes());
+        }
+        Err(RequestError::TooLarge) => {
+            let msg = format!("program is larger than {} bytes", MAX_SOURCE_BYTES);
+            return write_response(
+                &mut stream,
+                "413 Payload Too Large",
+                "text/plain",
+                msg.as_bytes(),
+            );
+        }
+    };
+    if args.verbose {
+        eprintln!("{} {}", request.method, request.path);
+    }
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/") | ("GET", "/index.html") => write_response(
+            &mut stream,
+            "200 OK",
+            "text/html; charset=utf-8",
+            INDEX_HTML.as_bytes(),
+        ),
+        ("POST", "/run") => {
+            let Ok(source) = String::from_utf8(request.body) else {
+                return write_response(
+                    &mut stream,
+                    "400 Bad Request",
+                    "text/plain",
+                    b"program is not valid UTF-8",
+                );
+            };
+            if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONCURRENT_RUNS {
+                active.fetch_sub(1, Ordering::SeqCst);
+                return write_response(
+                    &mut stream,
+                    "503 Service Unavailable",
+                    "text/plain",
+                    b"too many programs running, try again shortly",
+                );
+            }
+            let result = stream_run(&mut stream, source, args);
+            active.fetch_sub(1, Ordering::SeqCst);
+            result
+        }
+        (_, "/") | (_, "/index.html") | (_, "/run") => write_response(
+            &mut stream,
+            "405 Method Not Allowed",
+            "text/plain",
+            b"method not allowed",
+        ),
+        _ => write_response(&mut stream, "404 Not Found", "text/plain", b"not found"),
+    }
+}
+
+fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+) -> Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// One line of the `/run` response stream
+enum Event {
+    Output(String),
+    Result(String),
+    Error(String),
+    Done(Duration),
+}
+
+impl Event {
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Event::Output(text) => json!({ "type": "output", "text": text }),
+            Event::Result(value) => json!({ "type": "result", "value": value }),
+            Event::Error(message) => json!({ "type": "error", "message": message }),
+            Event::Done(elapsed) => {
+                json!({ "type": "done", "elapsedMs": elapsed.as_millis() as u64 })
+            }
+        }
+    }
+}
+
+/// `print` and `console.*` output, forwarded to the connection as it is written
+struct EventWriter(Sender<Event>);
+
+impl Write for EventWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let _ = self
+            .0
+            .send(Event::Output(String::from_utf8_lossy(buf).into_owned()));
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Run `source` on its own thread and stream its events to `stream`.
+///
+/// The response has no length: it ends when the connection closes, after the
+/// `done` event (or an `error` event if the run outlives its time budget).
+fn stream_run(stream: &mut TcpStream, source: String, args: &PlaygroundArgs) -> Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: application/x-ndjson\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n"
+    )?;
+    stream.flush()?;
+
+    let (tx, rx) = mpsc::channel();
+    let output: OutputWriter = Arc::new(Mutex::new(Box::new(EventWriter(tx.clone()))));
+    let config = RuntimeConfig::sandboxed()
+        .with_output(output)
+        .with_max_execution_time(args.timeout)
+        .with_max_memory_bytes(args.max_memory);
+    thread::spawn(move || {
+        let start = Instant::now();
+        let mut runtime = Runtime::from_config(config);
+        let outcome = runtime.eval(&source);
+        // Drop the runtime first so its output sender goes before `done`.
+        drop(runtime);
+        match outcome {
+            Ok(Value::Null) => {}
+            Ok(value) => {
+                let _ = tx.send(Event::Result(value.to_string()));
+            }
+            Err(err) => {
+                let _ = tx.send(Event::Error(err.to_string()));
+            }
+        }
+        let _ = tx.send(Event::Done(start.elapsed()));
+    });
+
+    let deadline = Instant::now() + args.timeout + TIMEOUT_GRACE;
+    loop {
+        match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(event) => {
+                write_event(stream, &event)?;
+                if matches!(event, Event::Done(_)) {
+                    break;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {
+                let msg = format!(
+                    "program did not finish within {} ms",
+                    args.timeout.as_millis()
+                );
+                write_event(stream, &Event::Error(msg))?;
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_event(stream: &mut TcpStream, event: &Event) -> Result<()> {
+    writeln!(stream, "{}", event.to_json())?;
+    stream.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read};
+
+    fn parse(raw: &str) -> Result<Request, RequestError> {
+        read_request(&mut Cursor::new(raw.as_bytes())).unwrap()
+    }
+
+    /// Start a server on an OS-assigned port and return its address
+    fn start_server(args: PlaygroundArgs) -> Option<SocketAddr> {
+        let listener = match TcpListener::bind("127.0.0.1:0") {
+            Ok(listener) => listener,
+            Err(err) => {
+                eprintln!("Skipping playground server test: {}", err);
+                return None;
+            }
+        };
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, args));
+        Some(addr)
+    }
+
+    fn send(addr: SocketAddr, raw: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(raw.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    fn post_run(addr: SocketAddr, source: &str) -> Vec<serde_json::Value> {
+        let response = send(
+            addr,
+            &format!(
+                "POST /run HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+                source.len(),
+                source
+            ),
+        );
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        assert!(head.starts_with("HTTP/1.1 200 OK"), "{head}");
+        body.lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_read_request_with_body() {
+        let request =
+            parse("POST /run?x=1 HTTP/1.1\r\nHost: a\r\ncontent-length: 5\r\n\r\nhello").unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/run");
+        assert_eq!(request.body, b"hello");
+    }
+
+    #[test]
+    fn test_read_request_rejects_bad_input() {
+        assert_eq!(
+            parse("GARBAGE\r\n\r\n").unwrap_err(),
+            RequestError::BadRequest("malformed request line")
+        );
+        assert_eq!(
+            parse("POST /run HTTP/1.1\r\nContent-Length: lots\r\n\r\n").unwrap_err(),
+            RequestError::BadRequest("invalid Content-Length")
+        );
+        let too_big = format!(
+            "POST /run HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_SOURCE_BYTES + 1
+        );
+        assert_eq!(parse(&too_big).unwrap_err(), RequestError::TooLarge);
+    }
+
+    #[test]
+    fn test_serves_index_and_404() {
+        let Some(addr) = start_server(PlaygroundArgs::default()) else {
+            return;
+        };
+        let index = send(addr, "GET / HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        assert!(index.starts_with("HTTP/1.1 200 OK"));
+        assert!(index.contains("Atlas Playground"));
+
+        let missing = send(addr, "GET /nope HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        assert!(missing.starts_with("HTTP/1.1 404 Not Found"));
+    }
+
+    #[test]
+    fn test_run_streams_output_and_result() {
+        let Some(addr) = start_server(PlaygroundArgs::default()) else {
+            return;
+        };
+        let events = post_run(addr, "console.log(\"hi\");\n1 + 2");
+        let output: String = events
+            .iter()
+            .filter(|e| e["type"] == "output")
+            .map(|e| e["text"].as_str().unwrap())
+            .collect();
+        assert_eq!(output, "hi\n");
+        assert!(events
+            .iter()
+            .any(|e| e["type"] == "result" && e["value"] == "3"));
+        assert_eq!(events.last().unwrap()["type"], "done");
+    }
+
+    #[test]
+    fn test_run_is_sandboxed() {
+        let Some(addr) = start_server(PlaygroundArgs::default()) else {
+            return;
+        };
+        let events = post_run(addr, r#"file.read("/etc/hostname")"#);
+        assert!(
+            events.iter().any(|e| e["type"] == "result"
+                && e["value"].as_str().unwrap().contains("permission denied")),
+            "{events:?}"
+        );
+    }
+
+    #[test]
+    fn test_run_respects_timeout() {
+        let Some(addr) = start_server(PlaygroundArgs {
+            timeout: Duration::from_millis(200),
+            ..PlaygroundArgs::default()
+        }) else {
+            return;
+        };
+        let events = post_run(addr, "while true { }");
+        assert!(events.iter().any(|e| e["type"] == "error"), "{events:?}");
+    }
+}
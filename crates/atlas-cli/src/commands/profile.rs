@@ -5,6 +5,7 @@ use atlas_runtime::binder::Binder;
 use atlas_runtime::compiler::Compiler;
 use atlas_runtime::lexer::Lexer;
 use atlas_runtime::parser::Parser;
+use atlas_runtime::profiler::StackWeight;
 use atlas_runtime::security::SecurityContext;
 use atlas_runtime::typechecker::TypeChecker;
 use atlas_runtime::vm::VM;
@@ -22,6 +23,12 @@ pub struct ProfileArgs {
     pub output_file: Option<PathBuf>,
     /// Show full detailed report (true) or summary only (false)
     pub detailed: bool,
+    /// Write folded call stacks (flamegraph/speedscope input) to this file
+    pub folded_file: Option<PathBuf>,
+    /// What each folded stack is weighted by
+    pub folded_weight: StackWeight,
+    /// Also track allocations and peak memory
+    pub memory: bool,
 }
 
 impl ProfileArgs {
@@ -31,6 +38,9 @@ impl ProfileArgs {
             hotspot_threshold: 1.0,
             output_file: None,
             detailed: true,
+            folded_file: None,
+            folded_weight: StackWeight::Time,
+            memory: false,
         }
     }
 }
@@ -39,7 +49,9 @@ impl ProfileArgs {
 ///
 /// Compiles the source through the full pipeline (lex → parse → bind → check →
 /// compile), then executes it in the VM with profiling enabled and prints a
-/// performance report.
+/// performance report. The detailed report ends with the call tree, after the
+/// allocation tables when memory profiling is on; folded stacks are written
+/// separately when requested.
 pub fn run(args: ProfileArgs) -> Result<()> {
     let source = std::fs::read_to_string(&args.file)
         .with_context(|| format!("Failed to read file: {}", args.file))?;
@@ -92,6 +104,9 @@ pub fn run(args: ProfileArgs) -> Result<()> {
     // --- Run with profiling ---
     let security = SecurityContext::allow_all();
     let mut vm = VM::with_profiling(bytecode);
+    if args.memory {
+        vm.enable_allocation_profiling();
+    }
 
     let result = vm
         .run(&security)
@@ -112,7 +127,10 @@ pub fn run(args: ProfileArgs) -> Result<()> {
     let report = profiler.generate_report(args.hotspot_threshold);
 
     let report_text = if args.detailed {
-        report.format_detailed()
+        let mut text = report.format_detailed();
+        text.push_str("[ Call Tree ]\n");
+        text.push_str(&profiler.call_tree().format_tree());
+        text
     } else {
         format!("{}\n", report.format_summary())
     };
@@ -128,6 +146,13 @@ pub fn run(args: ProfileArgs) -> Result<()> {
         }
     }
 
+    if let Some(ref path) = args.folded_file {
+        let folded = profiler.call_tree().folded_stacks(args.folded_weight);
+        std::fs::write(path.as_path(), folded)
+            .with_context(|| format!("Failed to write folded stacks to {}", path.display()))?;
+        println!("Folded stacks saved to {}", path.display());
+    }
+
     Ok(())
 }
 
@@ -178,6 +203,9 @@ mod tests {
             hotspot_threshold: 1.0,
             output_file: Some(out.path().to_path_buf()),
             detailed: true,
+            folded_file: None,
+            folded_weight: StackWeight::Time,
+            memory: false,
         };
         run(args).unwrap();
         let content = std::fs::read_to_string(out.path()).unwrap();
@@ -196,6 +224,9 @@ mod tests {
             hotspot_threshold: 1.0,
             output_file: None,
             detailed: false,
+            folded_file: None,
+            folded_weight: StackWeight::Time,
+            memory: false,
         };
         assert!(run(args).is_ok());
     }
@@ -217,10 +248,70 @@ mod tests {
             hotspot_threshold: 50.0,
             output_file: None,
             detailed: true,
+            folded_file: None,
+            folded_weight: StackWeight::Time,
+            memory: false,
         };
         assert!(run(args).is_ok());
     }
 
+    #[test]
+    fn test_profile_report_includes_call_tree() {
+        let src = write_temp("fn square(x: number): number { return x * x; } let y = square(4);");
+        let out = NamedTempFile::new().unwrap();
+        let mut args = ProfileArgs::new(src.path().to_str().unwrap());
+        args.output_file = Some(out.path().to_path_buf());
+        run(args).unwrap();
+        let content = std::fs::read_to_string(out.path()).unwrap();
+        assert!(
+            content.contains("[ Call Tree ]"),
+            "report content: {}",
+            content
+        );
+        assert!(content.contains("  square"), "report content: {}", content);
+    }
+
+    #[test]
+    fn test_profile_writes_folded_stacks() {
+        let src = write_temp(
+            "fn inner(x: number): number { return x + 1; } fn outer(x: number): number { return inner(x) * 2; } let y = outer(1);",
+        );
+        let folded = NamedTempFile::new().unwrap();
+        let mut args = ProfileArgs::new(src.path().to_str().unwrap());
+        args.detailed = false;
+        args.folded_file = Some(folded.path().to_path_buf());
+        args.folded_weight = StackWeight::Instructions;
+        run(args).unwrap();
+        let content = std::fs::read_to_string(folded.path()).unwrap();
+        assert!(
+            content
+                .lines()
+                .any(|line| line.starts_with("<main>;outer;inner ")),
+            "folded stacks: {}",
+            content
+        );
+    }
+
+    #[test]
+    fn test_profile_memory_report() {
+        let src = write_temp(
+            r#"fn greet(n: number): string { return "hi " + str(n); } let a = greet(1); let b = [1, 2, 3];"#,
+        );
+        let out = NamedTempFile::new().unwrap();
+        let mut args = ProfileArgs::new(src.path().to_str().unwrap());
+        args.output_file = Some(out.path().to_path_buf());
+        args.memory = true;
+        run(args).unwrap();
+        let content = std::fs::read_to_string(out.path()).unwrap();
+        assert!(content.contains("[ Allocations ]"), "report: {}", content);
+        assert!(
+            content.contains("[ Allocation Sites ]"),
+            "report: {}",
+            content
+        );
+        assert!(content.contains("greet"), "report: {}", content);
+    }
+
     #[test]
     fn test_profile_syntax_error() {
         let f = write_temp("let x: number = ;");
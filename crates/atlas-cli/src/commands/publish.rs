@@ -7,10 +7,26 @@
 //!
 //! Optionally, if ATLAS_INDEX_TOKEN is set, package metadata is POST-ed to a
 //! community index for discoverability — non-blocking, best-effort only.
+//!
+//! With `--sign`, the tagged tree is archived with `git archive` and its checksum
+//! is signed with the ed25519 key from the credentials store, so installers can
+//! verify the release against the publisher's registered public key.
+//!
+//! With `--upload`, the package directory is instead packed into a tarball and
+//! uploaded to an archive registry (`PUT /packages/<name>/<version>`) — for
+//! registries that serve tarballs rather than git tags. The token comes from
+//! `ATLAS_REGISTRY_TOKEN` or `[registry] token` in the credentials store.
+//!
+//! Before tagging, the exported API is compared with the newest earlier release
+//! tag and a version bump too small for the changes is warned about (see
+//! `atlas api diff`).
 
+use super::api;
+use super::search::DEFAULT_REGISTRY_URL;
 use anyhow::{bail, Context, Result};
 use atlas_package::manifest::PackageManifest;
 use atlas_package::validator::Validator;
+use atlas_package::{CredentialsStore, PackageSignature, PackageUpload, Registry, RemoteRegistry};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -21,8 +37,8 @@ use std::process::Command;
 pub struct PublishArgs {
     /// Project directory (defaults to current).
     pub project_dir: PathBuf,
-    /// Reserved: registry URL (unused — publishing is git-native per D-059).
-    #[allow(dead_code)]
+    /// Registry URL for `--yank` and `--upload` (publishing itself is
+    /// git-native per D-059).
     pub registry: Option<String>,
     /// Skip all validation checks.
     pub no_verify: bool,
@@ -32,6 +48,14 @@ pub struct PublishArgs {
     pub allow_dirty: bool,
     /// Verbose output.
     pub verbose: bool,
+    /// Yank this version of the package instead of publishing.
+    pub yank: Option<String>,
+    /// With `yank`: restore a previously yanked version.
+    pub undo: bool,
+    /// Sign the release with the key from the credentials store.
+    pub sign: bool,
+    /// Upload a tarball to the registry instead of tagging the release.
+    pub upload: bool,
 }
 
 impl Default for PublishArgs {
@@ -43,6 +67,10 @@ impl Default for PublishArgs {
             dry_run: false,
             allow_dirty: false,
             verbose: false,
+            yank: None,
+            undo: false,
+            sign: false,
+            upload: false,
         }
     }
 }
@@ -63,6 +91,10 @@ pub fn run(args: PublishArgs) -> Result<()> {
     let manifest =
         PackageManifest::from_file(&manifest_path).context("Failed to read atlas.toml")?;
 
+    if let Some(yank_version) = &args.yank {
+        return run_yank(&args, &manifest, yank_version);
+    }
+
     let pkg_name = &manifest.package.name;
     let version = &manifest.package.version;
     let tag = format!("v{}", version);
@@ -89,6 +121,10 @@ pub fn run(args: PublishArgs) -> Result<()> {
         }
     }
 
+    if args.upload {
+        return run_upload(&args, project_dir);
+    }
+
     // ── 3. Get remote URL (origin) ────────────────────────────────────────────
     let remote_url = get_remote_url(project_dir).unwrap_or_default();
 
@@ -103,11 +139,19 @@ pub fn run(args: PublishArgs) -> Result<()> {
     // ── 5. Verify tag does not exist locally ─────────────────────────────────
     check_tag_not_local(project_dir, &tag)?;
 
+    // ── 5b. Compare the public API with the previous release ─────────────────
+    if !args.no_verify {
+        check_api_against_previous(project_dir, version, args.verbose);
+    }
+
     // ── 6. Print plan / dry-run ───────────────────────────────────────────────
     println!("Publishing {} {}", pkg_name, tag);
 
     if args.dry_run {
         println!("\n[Dry run] Would create tag: {}", tag);
+        if args.sign {
+            println!("[Dry run] Would sign the release archive");
+        }
         println!("No changes made.");
         return Ok(());
     }
@@ -116,6 +160,27 @@ pub fn run(args: PublishArgs) -> Result<()> {
     create_local_tag(project_dir, &tag, pkg_name, version)?;
     println!("{} Created tag {}", green_check(), tag);
 
+    let signed = if args.sign {
+        let store = CredentialsStore::default_location();
+        let release = sign_release(project_dir, &tag, pkg_name, version, &store)?;
+        if release.new_key {
+            println!(
+                "Generated a signing key in {}\nRegister its public key with the registry:\n  {}",
+                store.path().display(),
+                release.public_key
+            );
+        }
+        println!(
+            "{} Signed {} with key {}",
+            green_check(),
+            tag,
+            release.signature.key_id
+        );
+        Some(release)
+    } else {
+        None
+    };
+
     // ── 8. Guide user ─────────────────────────────────────────────────────────
     println!("\nNext step — push the tag to make it available:");
     println!("  git push origin {}", tag);
@@ -136,7 +201,14 @@ pub fn run(args: PublishArgs) -> Result<()> {
 
     // ── 9. Optional community index POST (best-effort) ────────────────────────
     if let Ok(token) = std::env::var("ATLAS_INDEX_TOKEN") {
-        post_to_community_index(&token, pkg_name, version, &remote_url, args.verbose);
+        post_to_community_index(
+            &token,
+            pkg_name,
+            version,
+            &remote_url,
+            signed.as_ref(),
+            args.verbose,
+        );
     }
 
     Ok(())
@@ -242,6 +314,54 @@ fn check_tag_not_local(project_dir: &Path, tag: &str) -> Result<()> {
     Ok(())
 }
 
+/// Warn when the version bump is too small for the API changes since the
+/// newest earlier release tag. Never fails the publish.
+fn check_api_against_previous(project_dir: &Path, version: &semver::Version, verbose: bool) {
+    let Some(previous) = previous_release(project_dir, version) else {
+        return;
+    };
+    let tag = format!("v{}", previous);
+    let reports = api::ApiReport::from_git_tag(project_dir, &tag)
+        .and_then(|old| Ok((old, api::ApiReport::from_dir(project_dir)?)));
+    let (old, new) = match reports {
+        Ok(reports) => reports,
+        Err(e) => {
+            if verbose {
+                println!("Skipping API check against {}: {}", tag, e);
+            }
+            return;
+        }
+    };
+
+    let changes = api::diff(&old, &new);
+    match api::check_version(&previous, version, &changes) {
+        Ok(()) if verbose => println!(
+            "{} Version bump covers {} API change(s) since {}",
+            green_check(),
+            changes.len(),
+            tag
+        ),
+        Ok(()) => {}
+        Err(e) => eprintln!(
+            "warning: {} (run `atlas api diff {}` for details)",
+            e, previous
+        ),
+    }
+}
+
+/// Newest `v<version>` tag older than `version`
+fn previous_release(project_dir: &Path, version: &semver::Version) -> Option<semver::Version> {
+    let output = Command::new("git")
+        .args(["-C", &project_dir.to_string_lossy(), "tag", "--list", "v*"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|tag| semver::Version::parse(tag.trim().strip_prefix('v')?).ok())
+        .filter(|tagged| tagged < version)
+        .max()
+}
+
 /// Create an annotated local git tag.
 fn create_local_tag(
     project_dir: &Path,
@@ -269,6 +389,180 @@ fn create_local_tag(
     Ok(())
 }
 
+// ── signing ──────────────────────────────────────────────────────────────────
+
+/// A signed release: archive checksum plus the publisher's signature over it.
+struct SignedRelease {
+    checksum: String,
+    signature: PackageSignature,
+    public_key: String,
+    /// The key was generated by this publish (first use of `--sign`).
+    new_key: bool,
+}
+
+/// Archive the tagged tree and sign its checksum with the stored key,
+/// generating a key on first use.
+fn sign_release(
+    project_dir: &Path,
+    tag: &str,
+    pkg_name: &str,
+    version: &semver::Version,
+    store: &CredentialsStore,
+) -> Result<SignedRelease> {
+    let output = Command::new("git")
+        .args([
+            "-C",
+            &project_dir.to_string_lossy(),
+            "archive",
+            "--format=tar.gz",
+            tag,
+        ])
+        .output()
+        .context("Failed to run git archive")?;
+    if !output.status.success() {
+        bail!(
+            "git archive '{}' failed: {}",
+            tag,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let (key, new_key) = store
+        .signing_key_or_generate()
+        .with_context(|| format!("Failed to load signing key from {}", store.path().display()))?;
+    let checksum = atlas_package::Downloader::calculate_checksum(&output.stdout);
+    Ok(SignedRelease {
+        signature: key.sign_package(pkg_name, version, &checksum),
+        checksum,
+        public_key: key.public_key_hex(),
+        new_key,
+    })
+}
+
+// ── upload ───────────────────────────────────────────────────────────────────
+
+/// Pack the package directory and upload it to an archive registry.
+fn run_upload(args: &PublishArgs, project_dir: &Path) -> Result<()> {
+    let mut upload = PackageUpload::from_dir(project_dir).context("Failed to package")?;
+    let (name, version) = (upload.name().to_string(), upload.version().clone());
+    let registry_url = registry_url(args);
+    println!(
+        "Packaged {} {} ({} bytes, sha256 {})",
+        name,
+        version,
+        upload.archive.len(),
+        upload.checksum
+    );
+
+    if args.sign {
+        let store = CredentialsStore::default_location();
+        let (key, new_key) = store.signing_key_or_generate().with_context(|| {
+            format!("Failed to load signing key from {}", store.path().display())
+        })?;
+        if new_key {
+            println!(
+                "Generated a signing key in {}\nRegister its public key with the registry:\n  {}",
+                store.path().display(),
+                key.public_key_hex()
+            );
+        }
+        let signature = key.sign_package(&name, &version, &upload.checksum);
+        upload = upload.with_signature(signature);
+    }
+
+    if args.dry_run {
+        println!("\n[Dry run] Would upload to {}", registry_url);
+        println!("No changes made.");
+        return Ok(());
+    }
+
+    let token = registry_token()?;
+    RemoteRegistry::new(registry_url.clone())
+        .with_token(token)
+        .publish(&upload)
+        .with_context(|| format!("Failed to publish {} {}", name, version))?;
+
+    println!(
+        "{} Published {} {} to {}",
+        green_check(),
+        name,
+        version,
+        registry_url
+    );
+    println!("\nConsumers can depend on this package with:");
+    println!("  {} = \"^{}\"", name, version);
+    Ok(())
+}
+
+/// Token for registry writes: `ATLAS_REGISTRY_TOKEN`, then the credentials
+/// store's `[registry] token`.
+fn registry_token() -> Result<String> {
+    if let Ok(token) = std::env::var("ATLAS_REGISTRY_TOKEN") {
+        return Ok(token);
+    }
+    let store = CredentialsStore::default_location();
+    store
+        .registry_token()
+        .with_context(|| format!("Failed to read {}", store.path().display()))?
+        .with_context(|| {
+            format!(
+                "No registry token: set ATLAS_REGISTRY_TOKEN or add [registry] token to {}",
+                store.path().display()
+            )
+        })
+}
+
+/// `--registry`, then `ATLAS_REGISTRY_URL`, then the default registry.
+fn registry_url(args: &PublishArgs) -> String {
+    args.registry
+        .clone()
+        .or_else(|| std::env::var("ATLAS_REGISTRY_URL").ok())
+        .unwrap_or_else(|| DEFAULT_REGISTRY_URL.to_string())
+}
+
+// ── yank ─────────────────────────────────────────────────────────────────────
+
+/// Yank (or with `--undo`, restore) a published version in the registry.
+///
+/// Yanked versions stay downloadable for projects that already lock them, but
+/// are never chosen by a new resolution.
+fn run_yank(args: &PublishArgs, manifest: &PackageManifest, version: &str) -> Result<()> {
+    let pkg_name = &manifest.package.name;
+    let version = semver::Version::parse(version.trim_start_matches('v'))
+        .with_context(|| format!("Invalid version '{}'", version))?;
+
+    let token = std::env::var("ATLAS_INDEX_TOKEN")
+        .context("ATLAS_INDEX_TOKEN must be set to yank a version")?;
+    let registry_url = registry_url(args);
+
+    let action = if args.undo { "Unyank" } else { "Yank" };
+    if args.dry_run {
+        println!(
+            "[Dry run] Would {} {} {} on {}",
+            action.to_lowercase(),
+            pkg_name,
+            version,
+            registry_url
+        );
+        return Ok(());
+    }
+
+    RemoteRegistry::new(registry_url)
+        .with_token(token)
+        .yank(pkg_name, &version, !args.undo)
+        .with_context(|| {
+            format!(
+                "Failed to {} {} {}",
+                action.to_lowercase(),
+                pkg_name,
+                version
+            )
+        })?;
+
+    println!("{} {}ed {} {}", green_check(), action, pkg_name, version);
+    Ok(())
+}
+
 // ── community index ───────────────────────────────────────────────────────────
 
 /// POST minimal package metadata to a community index for discoverability.
@@ -280,15 +574,26 @@ fn post_to_community_index(
     name: &str,
     version: &semver::Version,
     repo_url: &str,
+    signed: Option<&SignedRelease>,
     verbose: bool,
 ) {
     // Build a minimal JSON payload.
-    let payload = format!(
-        r#"{{"name":"{name}","version":"{version}","repository":"{repo_url}"}}"#,
+    let mut payload = format!(
+        r#"{{"name":"{name}","version":"{version}","repository":"{repo_url}""#,
         name = name,
         version = version,
         repo_url = repo_url,
     );
+    if let Some(release) = signed {
+        payload.push_str(&format!(
+            r#","checksum":"{}","public_key":"{}","signature":{{"key-id":"{}","signature":"{}"}}"#,
+            release.checksum,
+            release.public_key,
+            release.signature.key_id,
+            release.signature.signature
+        ));
+    }
+    payload.push('}');
 
     // Use curl if available — avoids pulling in an HTTP client dep for a
     // best-effort, optional side-channel.
@@ -332,7 +637,7 @@ fn post_to_community_index(
 // ── filesystem helpers ────────────────────────────────────────────────────────
 
 /// Find atlas.toml by walking up from `start_dir`.
-fn find_manifest(start_dir: &Path) -> Result<PathBuf> {
+pub(crate) fn find_manifest(start_dir: &Path) -> Result<PathBuf> {
     let mut current = start_dir
         .canonicalize()
         .unwrap_or_else(|_| start_dir.to_path_buf());
@@ -469,6 +774,30 @@ license = "MIT"
         assert!(String::from_utf8_lossy(&out.stdout).trim().is_empty());
     }
 
+    #[test]
+    fn test_upload_dry_run_packages_without_git() {
+        let temp = TempDir::new().expect("test");
+        write_manifest(
+            temp.path(),
+            r#"[package]
+name = "my-lib"
+version = "1.0.0"
+description = "Test library"
+license = "MIT"
+"#,
+        );
+
+        let args = PublishArgs {
+            project_dir: temp.path().to_path_buf(),
+            dry_run: true,
+            allow_dirty: true,
+            upload: true,
+            registry: Some("http://127.0.0.1:9".to_string()),
+            ..Default::default()
+        };
+        run(args).expect("dry-run upload needs neither git nor network");
+    }
+
     // ── validation ────────────────────────────────────────────────────────────
 
     #[test]
@@ -599,4 +928,60 @@ license = "MIT"
         };
         assert!(run(args).is_err());
     }
+
+    // ── yank ──────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_yank_invalid_version_errors() {
+        let temp = TempDir::new().expect("test");
+        write_manifest(
+            temp.path(),
+            r#"[package]
+name = "my-lib"
+version = "1.0.0"
+"#,
+        );
+        let args = PublishArgs {
+            project_dir: temp.path().to_path_buf(),
+            yank: Some("not-a-version".to_string()),
+            ..Default::default()
+        };
+        let err = run(args).unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid version"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    // ── signing ───────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_sign_release_verifies_against_public_key() {
+        let temp = TempDir::new().expect("test");
+        write_manifest(
+            temp.path(),
+            "[package]\nname = \"my-lib\"\nversion = \"1.0.0\"\n",
+        );
+        init_git_repo(temp.path());
+        commit_all(temp.path());
+        let version = semver::Version::new(1, 0, 0);
+        create_local_tag(temp.path(), "v1.0.0", "my-lib", &version).expect("test");
+
+        let store = CredentialsStore::new(temp.path().join("home").join("credentials.toml"));
+        let first = sign_release(temp.path(), "v1.0.0", "my-lib", &version, &store).expect("test");
+        assert!(first.new_key);
+
+        let payload = atlas_package::signing::signed_payload("my-lib", &version, &first.checksum);
+        assert!(atlas_package::signing::verify(
+            &first.public_key,
+            &payload,
+            &first.signature.signature
+        ));
+
+        // The stored key is reused on the next publish
+        let second = sign_release(temp.path(), "v1.0.0", "my-lib", &version, &store).expect("test");
+        assert!(!second.new_key);
+        assert_eq!(second.public_key, first.public_key);
+    }
 }
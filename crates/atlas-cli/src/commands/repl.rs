@@ -2,7 +2,7 @@
 
 use anyhow::Result;
 use atlas_runtime::repl::ReplBinding;
-use atlas_runtime::{InputCompleteness, MultilineInput, ReplCore, Type};
+use atlas_runtime::{InputCompleteness, MultilineInput, PrettyConfig, ReplCore, Type};
 use rustyline::error::ReadlineError;
 use rustyline::DefaultEditor;
 use std::path::Path;
@@ -157,7 +157,7 @@ pub fn run(use_tui: bool, no_history: bool, config: &crate::config::Config) -> R
                             if let Some(value) = result.value {
                                 // Don't print null values
                                 if !matches!(value, atlas_runtime::Value::Null) {
-                                    println!("{}", value);
+                                    println!("{}", value.pretty(&PrettyConfig::default()));
                                 }
                             }
 
@@ -169,7 +169,7 @@ pub fn run(use_tui: bool, no_history: bool, config: &crate::config::Config) -> R
                                         binding.name,
                                         format_type(&binding.ty, config.no_color),
                                         if binding.mutable { "(mut) " } else { "" },
-                                        binding.value
+                                        binding.value.pretty(&PrettyConfig::compact())
                                     );
                                 }
 
@@ -259,7 +259,7 @@ fn handle_load(repl: &mut ReplCore, path_str: &str, config: &crate::config::Conf
                             "  {}: {} = {}",
                             binding.name,
                             format_type(&binding.ty, config.no_color),
-                            binding.value
+                            binding.value.pretty(&PrettyConfig::compact())
                         );
                     }
                 }
@@ -332,7 +332,7 @@ fn print_vars(bindings: &[ReplBinding], page: usize, no_color: bool) {
             format_type(&binding.ty, no_color),
             "global",
             if binding.mutable { "(mut) " } else { "" },
-            binding.value
+            binding.value.pretty(&PrettyConfig::compact())
         );
     }
 }
@@ -1,7 +1,7 @@
 //! TUI-based REPL using ratatui
 
 use anyhow::Result;
-use atlas_runtime::ReplCore;
+use atlas_runtime::{PrettyConfig, ReplCore};
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
     execute,
@@ -67,26 +67,18 @@ impl App {
                 self.input.insert(self.cursor_pos, c);
                 self.cursor_pos += 1;
             }
-            KeyCode::Backspace => {
-                if self.cursor_pos > 0 {
-                    self.input.remove(self.cursor_pos - 1);
-                    self.cursor_pos -= 1;
-                }
+            KeyCode::Backspace if self.cursor_pos > 0 => {
+                self.input.remove(self.cursor_pos - 1);
+                self.cursor_pos -= 1;
             }
-            KeyCode::Delete => {
-                if self.cursor_pos < self.input.len() {
-                    self.input.remove(self.cursor_pos);
-                }
+            KeyCode::Delete if self.cursor_pos < self.input.len() => {
+                self.input.remove(self.cursor_pos);
             }
-            KeyCode::Left => {
-                if self.cursor_pos > 0 {
-                    self.cursor_pos -= 1;
-                }
+            KeyCode::Left if self.cursor_pos > 0 => {
+                self.cursor_pos -= 1;
             }
-            KeyCode::Right => {
-                if self.cursor_pos < self.input.len() {
-                    self.cursor_pos += 1;
-                }
+            KeyCode::Right if self.cursor_pos < self.input.len() => {
+                self.cursor_pos += 1;
             }
             KeyCode::Home => {
                 self.cursor_pos = 0;
@@ -126,7 +118,7 @@ impl App {
             if matches!(value, atlas_runtime::Value::Null) {
                 String::new()
             } else {
-                value.to_string()
+                value.pretty(&PrettyConfig::default())
             }
         } else {
             String::new()
@@ -0,0 +1,176 @@
+//! Resolve dependencies and rewrite the lockfile (atlas resolve)
+//!
+//! With `--minimal-versions` every version requirement is resolved to the
+//! lowest matching release instead of the highest. The result replaces
+//! `atlas.lock` for the next build; the previous lockfile is kept as
+//! `atlas.lock.bak` so it can be restored afterwards.
+
+use anyhow::{bail, Context, Result};
+use atlas_package::installer::Installer;
+use atlas_package::manifest::PackageManifest;
+use atlas_package::ResolutionMode;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Arguments for the resolve command
+#[derive(Debug, Clone)]
+pub struct ResolveArgs {
+    /// Select the lowest versions satisfying each requirement
+    pub minimal_versions: bool,
+    /// Project directory (defaults to current)
+    pub project_dir: PathBuf,
+    /// Verbose output
+    pub verbose: bool,
+    /// Registry URL or local registry directory (defaults to
+    /// `ATLAS_REGISTRY_URL`, then the default registry)
+    pub registry: Option<String>,
+}
+
+impl Default for ResolveArgs {
+    fn default() -> Self {
+        Self {
+            minimal_versions: false,
+            project_dir: PathBuf::from("."),
+            verbose: false,
+            registry: None,
+        }
+    }
+}
+
+/// Run the resolve command
+pub fn run(args: ResolveArgs) -> Result<()> {
+    let manifest_path = find_manifest(&args.project_dir)?;
+    let project_dir = manifest_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .to_path_buf();
+    let lockfile_path = project_dir.join("atlas.lock");
+
+    let manifest =
+        PackageManifest::from_file(&manifest_path).context("Failed to read atlas.toml")?;
+
+    let mode = if args.minimal_versions {
+        ResolutionMode::Minimal
+    } else {
+        ResolutionMode::Highest
+    };
+
+    let installer = Installer::new(get_cache_dir(), project_dir.clone())
+        .with_registry(super::search::open_registry(args.registry.as_deref()));
+    let lockfile = installer
+        .resolve(&manifest, mode)
+        .context("Failed to resolve dependencies")?;
+
+    if args.verbose {
+        for pkg in &lockfile.packages {
+            println!("  {} v{}", pkg.name, pkg.version);
+        }
+    }
+
+    if args.minimal_versions && lockfile_path.exists() {
+        let backup = project_dir.join("atlas.lock.bak");
+        fs::copy(&lockfile_path, &backup).context("Failed to back up atlas.lock")?;
+        println!("Saved previous lockfile to atlas.lock.bak");
+    }
+
+    lockfile
+        .write_to_file(&lockfile_path)
+        .context("Failed to write atlas.lock")?;
+
+    if args.minimal_versions {
+        println!(
+            "Wrote minimal-version atlas.lock ({} package(s))",
+            lockfile.packages.len()
+        );
+        println!("Build and test now to check the declared lower bounds; restore with 'mv atlas.lock.bak atlas.lock'.");
+    } else {
+        println!("Wrote atlas.lock ({} package(s))", lockfile.packages.len());
+    }
+
+    Ok(())
+}
+
+fn get_cache_dir() -> PathBuf {
+    // ATLAS_HOME overrides the entire ~/atlas/ root.
+    // ATLAS_CACHE_DIR kept for backwards compatibility (takes precedence if set).
+    if let Ok(dir) = std::env::var("ATLAS_CACHE_DIR") {
+        return PathBuf::from(dir);
+    }
+    let root = std::env::var("ATLAS_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("atlas")
+        });
+    root.join("pkg")
+}
+
+/// Find atlas.toml manifest file
+fn find_manifest(start_dir: &Path) -> Result<PathBuf> {
+    let mut current = start_dir
+        .canonicalize()
+        .unwrap_or_else(|_| start_dir.to_path_buf());
+
+    loop {
+        let manifest_path = current.join("atlas.toml");
+        if manifest_path.exists() {
+            return Ok(manifest_path);
+        }
+
+        if !current.pop() {
+            break;
+        }
+    }
+
+    bail!(
+        "Could not find atlas.toml in {} or any parent directory",
+        start_dir.display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atlas_package::Lockfile;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_minimal_backs_up_lockfile() {
+        let project = TempDir::new().expect("tempdir");
+        let sibling = TempDir::new().expect("tempdir");
+        fs::write(
+            project.path().join("atlas.toml"),
+            format!(
+                "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\nutils = {{ path = \"{}\" }}\n",
+                sibling.path().display()
+            ),
+        )
+        .expect("write manifest");
+        fs::write(project.path().join("atlas.lock"), "version = 1\n").expect("write lockfile");
+
+        run(ResolveArgs {
+            minimal_versions: true,
+            project_dir: project.path().to_path_buf(),
+            verbose: false,
+            ..ResolveArgs::default()
+        })
+        .unwrap();
+
+        let backup = fs::read_to_string(project.path().join("atlas.lock.bak")).unwrap();
+        assert_eq!(backup, "version = 1\n");
+        let lockfile = Lockfile::from_file(&project.path().join("atlas.lock")).unwrap();
+        assert!(lockfile.get_package("utils").is_some());
+    }
+
+    #[test]
+    fn test_resolve_without_manifest_errors() {
+        let temp = TempDir::new().expect("tempdir");
+        let err = run(ResolveArgs {
+            project_dir: temp.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("atlas.toml"));
+    }
+}
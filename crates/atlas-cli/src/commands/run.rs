@@ -1,32 +1,78 @@
 //! Run command - execute Atlas source files
 
 use anyhow::Result;
-use atlas_runtime::{Atlas, SecurityContext};
+use atlas_lsp::daemon::{CompileResult, DaemonClient};
+use atlas_runtime::{Atlas, BytecodeCache, RunOutcome, SecurityContext};
+use std::path::Path;
 
 /// Run an Atlas source file
 ///
-/// Compiles and executes the source file, printing the result to stdout.
+/// Compiles and executes the source file. A script's final value is printed
+/// to stdout; a program with `fn main` instead yields `main`'s exit code
+/// (an `Err` return is reported on stderr first).
+/// A script declaring `//! dependencies:` runs from a temporary project
+/// holding them (see `script.rs`).
 /// If `json_output` is true, diagnostics are printed in JSON format.
-pub fn run(file_path: &str, json_output: bool) -> Result<()> {
+/// With `bytecode_cache`, installed packages are loaded from the package
+/// bytecode cache instead of being recompiled, and a running `atlas daemon`
+/// for the project compiles the program from its warm module cache. With
+/// `timings`, the program is compiled here instead, and the time spent in
+/// each pipeline phase is printed to stderr afterwards (as JSON when
+/// `json_output` is set), whether or not the program succeeded.
+pub fn run(file_path: &str, json_output: bool, bytecode_cache: bool, timings: bool) -> Result<i32> {
+    let script = super::script::prepare(Path::new(file_path))?;
+    let (project_dir, entry) = match &script {
+        Some(project) => (project.dir.as_path(), project.entry.as_path()),
+        None => (
+            Path::new(file_path).parent().unwrap_or(Path::new(".")),
+            Path::new(file_path),
+        ),
+    };
+
     // Validate package state before compilation
-    let project_dir = std::path::Path::new(file_path)
-        .parent()
-        .unwrap_or(std::path::Path::new("."));
     if let Err(msg) = atlas_build::validate_packages(project_dir) {
         anyhow::bail!("{}", msg);
     }
 
     // Create runtime with full permissions (like go run, cargo run, python, node, etc.)
-    let runtime = Atlas::new_with_security(SecurityContext::allow_all());
+    let mut runtime = Atlas::new_with_security(SecurityContext::allow_all());
+    if bytecode_cache {
+        runtime = runtime.with_bytecode_cache(BytecodeCache::for_packages());
+    }
+
+    // run_file supports module imports and the `fn main` entry point
+    let precompiled = if bytecode_cache && !timings {
+        compile_with_daemon(entry)
+    } else {
+        None
+    };
+    let outcome = match precompiled {
+        Some(CompileResult::Compiled(program)) => runtime.run_compiled(program),
+        Some(CompileResult::Diagnostics(diagnostics)) => Err(diagnostics),
+        Some(CompileResult::Unsupported(_)) | None => runtime.run_file(&entry.to_string_lossy()),
+    };
+    if timings {
+        let timings = runtime.timings();
+        if json_output {
+            eprintln!("{}", serde_json::json!({ "timings": timings.to_json() }));
+        } else {
+            eprint!("{}", timings.to_table());
+        }
+    }
 
-    // Use eval_file to support module imports
-    match runtime.eval_file(file_path) {
-        Ok(value) => {
+    match outcome {
+        Ok(RunOutcome::Script(value)) => {
             // Print the result value if it's not null
             if !matches!(value, atlas_runtime::Value::Null) {
                 println!("{}", value);
             }
-            Ok(())
+            Ok(0)
+        }
+        Ok(RunOutcome::Exited { code, error }) => {
+            if let Some(error) = error {
+                eprintln!("error: {}", error);
+            }
+            Ok(code)
         }
         Err(diagnostics) => {
             // Print all diagnostics
@@ -53,6 +99,12 @@ pub fn run(file_path: &str, json_output: bool) -> Result<()> {
     }
 }
 
+/// Compile `entry` through the daemon serving its project, if one is
+/// running and answers
+pub(crate) fn compile_with_daemon(entry: &Path) -> Option<CompileResult> {
+    DaemonClient::find(entry)?.compile(entry).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -66,13 +118,52 @@ mod tests {
         let mut temp_file = NamedTempFile::new().unwrap();
         writeln!(temp_file, "1 + 2;").unwrap();
 
-        let result = run(temp_file.path().to_str().unwrap(), false);
+        let result = run(temp_file.path().to_str().unwrap(), false, false, false);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_run_main_return_is_exit_code() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(
+            temp_file,
+            "fn main(_args: string[]): number {{ return 3; }}"
+        )
+        .unwrap();
+
+        let code = run(temp_file.path().to_str().unwrap(), false, false, false).unwrap();
+        assert_eq!(code, 3);
+    }
+
+    #[test]
+    fn test_run_main_err_exits_one() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(
+            temp_file,
+            "fn main(): Result<number, string> {{ return Err(\"boom\"); }}"
+        )
+        .unwrap();
+
+        let code = run(temp_file.path().to_str().unwrap(), false, false, false).unwrap();
+        assert_eq!(code, 1);
+    }
+
+    #[test]
+    fn test_run_script_with_shebang() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(
+            temp_file,
+            "#!/usr/bin/env atlas\nfn main(): number {{ return 4; }}"
+        )
+        .unwrap();
+
+        let code = run(temp_file.path().to_str().unwrap(), false, false, false).unwrap();
+        assert_eq!(code, 4);
+    }
+
     #[test]
     fn test_run_missing_file() {
-        let result = run("nonexistent.atl", false);
+        let result = run("nonexistent.atl", false, false, false);
         assert!(result.is_err());
     }
 
@@ -82,10 +173,19 @@ mod tests {
         let mut temp_file = NamedTempFile::new().unwrap();
         writeln!(temp_file, "let x: number = \"wrong\";").unwrap();
 
-        let result = run(temp_file.path().to_str().unwrap(), true);
+        let result = run(temp_file.path().to_str().unwrap(), true, false, false);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_run_with_timings() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "let x = 1 + 2;").unwrap();
+
+        assert!(run(temp_file.path().to_str().unwrap(), false, false, true).is_ok());
+        assert!(run(temp_file.path().to_str().unwrap(), true, false, true).is_ok());
+    }
+
     #[test]
     fn test_format_diagnostic_plain() {
         let diag = Diagnostic::error("Test error".to_string(), Span::new(0, 3));
@@ -0,0 +1,169 @@
+//! Single-file scripts with embedded dependencies
+//!
+//! A script may declare packages in its leading `//!` comment block instead
+//! of living in a project directory:
+//!
+//! ```text
+//! #!/usr/bin/env atlas
+//! //! dependencies: http = "1.2"
+//! //!   json = { version = "0.3", features = ["pretty"] }
+//! ```
+//!
+//! `atlas run` turns that block into an `atlas.toml` in a temporary project,
+//! installs it once, and runs a copy of the script from there so package
+//! imports resolve against the project's `atlas.lock`.
+
+use super::install::{self, InstallArgs};
+use anyhow::{Context, Result};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Temporary project backing a script with embedded dependencies
+#[derive(Debug, Clone)]
+pub struct ScriptProject {
+    /// Project directory holding `atlas.toml` and `atlas.lock`
+    pub dir: PathBuf,
+    /// Copy of the script inside `dir`; this is the file to run
+    pub entry: PathBuf,
+}
+
+/// Dependency lines of the script's `//! dependencies:` header, in
+/// `[dependencies]` TOML syntax, or `None` if it declares none.
+///
+/// The header is the run of `//!` lines at the top of the file (after an
+/// optional shebang). Entries may follow `dependencies:` on the same line or
+/// on the lines after it, until another `key:` heading.
+pub fn embedded_dependencies(source: &str) -> Option<Vec<String>> {
+    let mut lines = source.lines().peekable();
+    if lines.peek().is_some_and(|line| line.starts_with("#!")) {
+        lines.next();
+    }
+
+    let mut dependencies = Vec::new();
+    let mut in_dependencies = false;
+    for line in lines {
+        let Some(content) = line.trim().strip_prefix("//!") else {
+            break;
+        };
+        let content = content.trim();
+
+        if let Some(rest) = content.strip_prefix("dependencies:") {
+            in_dependencies = true;
+            if !rest.trim().is_empty() {
+                dependencies.push(rest.trim().to_string());
+            }
+        } else if is_heading(content) {
+            in_dependencies = false;
+        } else if in_dependencies && !content.is_empty() {
+            dependencies.push(content.to_string());
+        }
+    }
+
+    (!dependencies.is_empty()).then_some(dependencies)
+}
+
+/// `name:` with nothing that could make it a TOML entry
+fn is_heading(content: &str) -> bool {
+    content.strip_suffix(':').is_some_and(|key| {
+        !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    })
+}
+
+/// Set up the temporary project for `script`, installing its dependencies
+/// the first time. Returns `None` for scripts without embedded dependencies.
+///
+/// Projects live under the system temp directory, keyed by the script's path
+/// and dependency list, so later runs reuse the installed packages. Relative
+/// imports of sibling files are not available to such scripts.
+pub fn prepare(script: &Path) -> Result<Option<ScriptProject>> {
+    let Ok(source) = fs::read_to_string(script) else {
+        // Reported by the runtime, like any other unreadable file
+        return Ok(None);
+    };
+    let Some(dependencies) = embedded_dependencies(&source) else {
+        return Ok(None);
+    };
+
+    let manifest = format!(
+        "[package]\nname = \"script\"\nversion = \"0.0.0\"\n\n[dependencies]\n{}\n",
+        dependencies.join("\n")
+    );
+
+    let mut hasher = DefaultHasher::new();
+    script
+        .canonicalize()
+        .unwrap_or_else(|_| script.to_path_buf())
+        .hash(&mut hasher);
+    manifest.hash(&mut hasher);
+    let dir = std::env::temp_dir()
+        .join("atlas-scripts")
+        .join(format!("{:016x}", hasher.finish()));
+
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create script project {}", dir.display()))?;
+
+    if !dir.join("atlas.lock").exists() {
+        fs::write(dir.join("atlas.toml"), &manifest).context("Failed to write script manifest")?;
+        install::run(InstallArgs {
+            project_dir: dir.clone(),
+            quiet: true,
+            ..InstallArgs::default()
+        })
+        .with_context(|| {
+            format!(
+                "Failed to install dependencies declared in {}",
+                script.display()
+            )
+        })?;
+    }
+
+    let file_name = script.file_name().unwrap_or("main.atlas".as_ref());
+    let entry = dir.join(file_name);
+    fs::write(&entry, &source).context("Failed to copy script into its project")?;
+
+    Ok(Some(ScriptProject { dir, entry }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inline_and_continued_entries() {
+        let source = "#!/usr/bin/env atlas\n//! dependencies: http = \"1.2\"\n//!   json = { version = \"0.3\" }\nlet x = 1;\n";
+        assert_eq!(
+            embedded_dependencies(source),
+            Some(vec![
+                "http = \"1.2\"".to_string(),
+                "json = { version = \"0.3\" }".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_header_ends_at_first_code_line() {
+        let source = "//! A tool\nlet x = 1;\n//! dependencies: http = \"1\"\n";
+        assert_eq!(embedded_dependencies(source), None);
+    }
+
+    #[test]
+    fn test_other_heading_closes_dependencies() {
+        let source = "//! dependencies:\n//! http = \"1\"\n//! notes:\n//! not = \"a dep\"\n";
+        assert_eq!(
+            embedded_dependencies(source),
+            Some(vec!["http = \"1\"".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_script_without_header_needs_no_project() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, b"#!/usr/bin/env atlas\nprint(1);\n").unwrap();
+        assert!(prepare(file.path()).unwrap().is_none());
+    }
+}
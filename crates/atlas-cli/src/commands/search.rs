@@ -0,0 +1,158 @@
+//! Search the package registry (atlas search)
+
+use anyhow::{Context, Result};
+use atlas_package::{LocalRegistry, Registry, RemoteRegistry, SearchQuery, SearchResults};
+use std::path::PathBuf;
+
+/// Default registry API root
+pub(crate) const DEFAULT_REGISTRY_URL: &str = "https://index.atlaslang.dev/api";
+
+/// Arguments for the search command
+#[derive(Debug, Clone)]
+pub struct SearchArgs {
+    /// Search terms (matched against names, keywords and descriptions)
+    pub query: String,
+    /// 1-based result page
+    pub page: usize,
+    /// Results per page
+    pub per_page: usize,
+    /// Registry URL or local registry directory
+    pub registry: Option<String>,
+}
+
+impl Default for SearchArgs {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            page: 1,
+            per_page: 20,
+            registry: None,
+        }
+    }
+}
+
+/// Run the search command
+pub fn run(args: SearchArgs) -> Result<()> {
+    let results = search(&args)?;
+    print!("{}", format_results(&args.query, &results));
+    Ok(())
+}
+
+/// Query the registry for one page of results
+pub fn search(args: &SearchArgs) -> Result<SearchResults> {
+    let query = SearchQuery::new(args.query.clone())
+        .with_page(args.page)
+        .with_per_page(args.per_page);
+    open_registry(args.registry.as_deref())
+        .search(&query)
+        .with_context(|| format!("Search for '{}' failed", args.query))
+}
+
+/// Render results the way `atlas search` prints them
+pub fn format_results(text: &str, results: &SearchResults) -> String {
+    if results.total == 0 {
+        return format!("No packages found for '{}'\n", text);
+    }
+
+    let width = results
+        .hits
+        .iter()
+        .map(|h| h.name.len() + h.version.to_string().len() + 3)
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+    for hit in &results.hits {
+        let label = format!("{} v{}", hit.name, hit.version);
+        let description = hit.description.as_deref().unwrap_or("");
+        out.push_str(&format!(
+            "{:<width$}  {}\n",
+            label,
+            description,
+            width = width
+        ));
+    }
+    out.push_str(&format!(
+        "\nPage {} of {} ({} package(s))\n",
+        results.page,
+        results.total_pages(),
+        results.total
+    ));
+    out
+}
+
+/// Open the registry named by `--registry`, `ATLAS_REGISTRY_URL`, or the default.
+/// A path to an existing directory opens a local registry.
+pub(crate) fn open_registry(registry: Option<&str>) -> Box<dyn Registry> {
+    let location = registry
+        .map(str::to_string)
+        .or_else(|| std::env::var("ATLAS_REGISTRY_URL").ok())
+        .unwrap_or_else(|| DEFAULT_REGISTRY_URL.to_string());
+
+    let path = PathBuf::from(location.strip_prefix("file://").unwrap_or(&location));
+    if path.is_dir() {
+        Box::new(LocalRegistry::new(path))
+    } else {
+        Box::new(RemoteRegistry::new(location))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_registry(root: &std::path::Path) {
+        for (name, version, description) in [
+            ("http", "1.0.0", "HTTP client"),
+            ("http-router", "0.3.0", "Routing for HTTP servers"),
+            ("json", "2.1.0", "JSON codec"),
+        ] {
+            let dir = root.join(name).join(version);
+            fs::create_dir_all(&dir).expect("create version dir");
+            fs::write(
+                dir.join("metadata.toml"),
+                format!("description = \"{}\"\n", description),
+            )
+            .expect("write metadata");
+        }
+    }
+
+    #[test]
+    fn test_search_local_registry_paginates() {
+        let temp = TempDir::new().expect("tempdir");
+        create_registry(temp.path());
+
+        let args = SearchArgs {
+            query: "http".to_string(),
+            per_page: 1,
+            registry: Some(temp.path().display().to_string()),
+            ..Default::default()
+        };
+        let results = search(&args).unwrap();
+        assert_eq!(results.total, 2);
+        assert_eq!(results.hits.len(), 1);
+        assert_eq!(results.hits[0].name, "http");
+
+        let output = format_results(&args.query, &results);
+        assert!(output.contains("http v1.0.0"), "{output}");
+        assert!(output.contains("Page 1 of 2"), "{output}");
+    }
+
+    #[test]
+    fn test_search_no_results() {
+        let temp = TempDir::new().expect("tempdir");
+        create_registry(temp.path());
+
+        let args = SearchArgs {
+            query: "yaml".to_string(),
+            registry: Some(temp.path().display().to_string()),
+            ..Default::default()
+        };
+        let results = search(&args).unwrap();
+        assert_eq!(
+            format_results(&args.query, &results),
+            "No packages found for 'yaml'\n"
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Audit `// atlas-ignore:` directives (atlas suppressions)
+//!
+//! Lists every inline suppression under the given paths so they can be
+//! reviewed instead of piling up unnoticed. With `--strict` the command
+//! fails when a directive gives no reason or names a rule that does not
+//! exist, which makes it usable as a CI gate.
+
+use anyhow::{bail, Context, Result};
+use atlas_runtime::diagnostic::suppression::{parse_suppressions, rule_code, Suppression};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Arguments for the suppressions command
+#[derive(Debug, Clone, Default)]
+pub struct SuppressionsArgs {
+    /// Files or directories to scan (defaults to the current directory)
+    pub paths: Vec<PathBuf>,
+    /// Print the report as JSON
+    pub json: bool,
+    /// Fail on directives without a reason or with unknown rules
+    pub strict: bool,
+}
+
+/// A directive and the file it was found in
+#[derive(Debug, Clone)]
+pub struct FileSuppression {
+    pub file: PathBuf,
+    pub suppression: Suppression,
+}
+
+/// Run the suppressions command
+pub fn run(args: SuppressionsArgs) -> Result<()> {
+    let found = collect(&args.paths)?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&to_json(&found))?);
+    } else {
+        print!("{}", report(&found));
+    }
+
+    if args.strict {
+        let problems = found
+            .iter()
+            .map(|f| &f.suppression)
+            .filter(|s| s.reason.is_none() || !s.unknown_rules().is_empty())
+            .count();
+        if problems > 0 {
+            bail!(
+                "{} suppression(s) without a reason or with unknown rules",
+                problems
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Every directive in the Atlas files under `paths`, in path order
+pub fn collect(paths: &[PathBuf]) -> Result<Vec<FileSuppression>> {
+    let mut files = Vec::new();
+    if paths.is_empty() {
+        collect_files(Path::new("."), &mut files)?;
+    }
+    for path in paths {
+        collect_files(path, &mut files)?;
+    }
+    files.sort();
+
+    let mut found = Vec::new();
+    for file in files {
+        let source = std::fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        found.extend(
+            parse_suppressions(&source)
+                .into_iter()
+                .map(|suppression| FileSuppression {
+                    file: file.clone(),
+                    suppression,
+                }),
+        );
+    }
+    Ok(found)
+}
+
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(path)
+        .with_context(|| format!("Failed to read directory {}", path.display()))?
+    {
+        let path = entry?.path();
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if path.is_dir() {
+            // Build output and VCS/tooling directories hold no sources of ours
+            if !name.starts_with('.') && name != "target" {
+                collect_files(&path, files)?;
+            }
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext == "at" || ext == "atl" || ext == "atlas")
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Human-readable report: one line per directive, then a count per rule
+pub fn report(found: &[FileSuppression]) -> String {
+    if found.is_empty() {
+        return "No suppressions found\n".to_string();
+    }
+
+    let mut out = String::new();
+    let mut per_rule: BTreeMap<String, usize> = BTreeMap::new();
+    for f in found {
+        let s = &f.suppression;
+        out.push_str(&format!(
+            "{}:{}  {}",
+            f.file.display(),
+            s.line,
+            s.rules.join(", ")
+        ));
+        match &s.reason {
+            Some(reason) => out.push_str(&format!("  -- {}", reason)),
+            None => out.push_str("  (no reason given)"),
+        }
+        for rule in s.unknown_rules() {
+            out.push_str(&format!("  [unknown rule `{}`]", rule));
+        }
+        out.push('\n');
+        for rule in &s.rules {
+            let key = rule_code(rule).unwrap_or(rule.as_str()).to_string();
+            *per_rule.entry(key).or_default() += 1;
+        }
+    }
+
+    out.push_str(&format!("\n{} suppression(s)\n", found.len()));
+    for (rule, count) in per_rule {
+        out.push_str(&format!("  {:>4}  {}\n", count, rule));
+    }
+    out
+}
+
+fn to_json(found: &[FileSuppression]) -> serde_json::Value {
+    let entries: Vec<serde_json::Value> = found
+        .iter()
+        .map(|f| {
+            serde_json::json!({
+                "file": f.file.display().to_string(),
+                "line": f.suppression.line,
+                "target_line": f.suppression.target_line,
+                "rules": f.suppression.rules,
+                "reason": f.suppression.reason,
+                "unknown_rules": f.suppression.unknown_rules(),
+            })
+        })
+        .collect();
+    serde_json::json!({ "suppressions": entries })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, name: &str, source: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, source).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_collects_directives_recursively() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "src/main.atlas",
+            "// atlas-ignore: unused-variable -- debugging\nlet x = 1;\n",
+        );
+        write(
+            dir.path(),
+            "src/util/math.atl",
+            "let y = 2; // atlas-ignore: AT2005\n",
+        );
+        write(
+            dir.path(),
+            "target/out.atlas",
+            "// atlas-ignore: shadowing\n",
+        );
+
+        let found = collect(&[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(found.len(), 2);
+        assert!(found[0].file.ends_with("src/main.atlas"));
+        assert_eq!(found[0].suppression.target_line, 2);
+        assert_eq!(found[0].suppression.reason.as_deref(), Some("debugging"));
+        assert!(found[1].file.ends_with("src/util/math.atl"));
+        assert_eq!(found[1].suppression.target_line, 1);
+    }
+
+    #[test]
+    fn test_report_flags_unknown_rules_and_missing_reasons() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = write(
+            dir.path(),
+            "main.atlas",
+            "// atlas-ignore: no-such-rule\nlet x = 1;\n",
+        );
+
+        let found = collect(&[file]).unwrap();
+        let text = report(&found);
+        assert!(text.contains("main.atlas:1  no-such-rule"), "{}", text);
+        assert!(text.contains("(no reason given)"), "{}", text);
+        assert!(text.contains("[unknown rule `no-such-rule`]"), "{}", text);
+        assert!(text.contains("1 suppression(s)"), "{}", text);
+    }
+
+    #[test]
+    fn test_strict_fails_on_unexplained_suppression() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = write(
+            dir.path(),
+            "main.atlas",
+            "let x = 1; // atlas-ignore: AT2001\n",
+        );
+        let args = SuppressionsArgs {
+            paths: vec![file],
+            json: true,
+            strict: true,
+        };
+        assert!(run(args).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 //! Typecheck dump command - output type information as JSON
 
 use anyhow::{Context, Result};
+use atlas_runtime::diagnostic::suppression::{is_suppressed, parse_suppressions};
 use atlas_runtime::{Binder, Lexer, Parser, TypeChecker};
 use std::fs;
 
@@ -58,9 +59,10 @@ pub fn run(file_path: &str) -> Result<()> {
         return Err(anyhow::anyhow!("Type errors"));
     }
 
-    // Print warnings (they don't block success)
+    // Print warnings (they don't block success) unless `atlas-ignore`d
+    let suppressions = parse_suppressions(&source);
     for diag in &typecheck_diagnostics {
-        if diag.is_warning() {
+        if diag.is_warning() && !is_suppressed(diag, &suppressions) {
             eprintln!("{}", diag.to_json_string().unwrap());
         }
     }
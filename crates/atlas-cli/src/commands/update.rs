@@ -4,13 +4,22 @@
 //! semver tag (or latest satisfying the declared version constraint), updates
 //! `atlas.lock`, and re-fetches the new version into the local cache.
 //!
+//! For registry deps: re-resolves against the versions the registry offers
+//! now, starting from `atlas.lock`, and updates the lock entries.
+//!
+//! `--conservative` limits every bump to newer patch releases of the locked
+//! `major.minor`; the default (`--aggressive`) takes the newest version the
+//! constraint allows.
+//!
 //! For path deps: always considered up-to-date (no remote version check).
-//! Registry deps are not yet supported (D-059: git is the registry).
 
 use anyhow::{bail, Context, Result};
 use atlas_package::fetcher::GitFetcher;
-use atlas_package::manifest::{Dependency, PackageManifest};
-use atlas_package::{LockedPackage, LockedSource, Lockfile};
+use atlas_package::manifest::{req_matches, Dependency, PackageManifest};
+use atlas_package::{
+    BuildContext, LockedPackage, LockedSource, Lockfile, LockfileUpdate, RegistryManager, Resolver,
+    TargetPlatform, UpdateMode,
+};
 use semver::{Version, VersionReq};
 use std::path::{Path, PathBuf};
 
@@ -29,6 +38,12 @@ pub struct UpdateArgs {
     pub dry_run: bool,
     /// Verbose output.
     pub verbose: bool,
+    /// Consider pre-release versions for every dependency.
+    pub pre: bool,
+    /// How far locked versions may move.
+    pub mode: UpdateMode,
+    /// Registry URL or local registry directory for registry deps.
+    pub registry: Option<String>,
 }
 
 impl Default for UpdateArgs {
@@ -39,6 +54,9 @@ impl Default for UpdateArgs {
             project_dir: PathBuf::from("."),
             dry_run: false,
             verbose: false,
+            pre: false,
+            mode: UpdateMode::default(),
+            registry: None,
         }
     }
 }
@@ -110,6 +128,8 @@ pub fn run(args: UpdateArgs) -> Result<()> {
             dep,
             existing_lockfile.as_ref(),
             &fetcher,
+            args.pre || dep.channel().allows_prerelease(),
+            args.mode,
             args.verbose,
         )? {
             Some(result) => updates.push(result),
@@ -121,10 +141,18 @@ pub fn run(args: UpdateArgs) -> Result<()> {
         }
     }
 
+    let base_lockfile = existing_lockfile.unwrap_or_else(Lockfile::new);
+    let registry_update =
+        update_registry_dependencies(&args, &manifest, &base_lockfile, &target_names)?;
+    let registry_changes = registry_update
+        .as_ref()
+        .map(|u| u.diff.changes.as_slice())
+        .unwrap_or_default();
+
     // Report.
     let needs_update: Vec<&UpdateResult> = updates.iter().filter(|u| u.needs_update).collect();
 
-    if needs_update.is_empty() {
+    if needs_update.is_empty() && registry_changes.is_empty() {
         println!("\n{} All packages are up to date.", green_check());
         return Ok(());
     }
@@ -138,6 +166,10 @@ pub fn run(args: UpdateArgs) -> Result<()> {
             .unwrap_or_else(|| "(new)".to_string());
         println!("  {} {} {} → {}", arrow_up(), u.name, old, u.new_tag);
     }
+    for change in registry_changes {
+        println!("  {} {}", arrow_up(), change.describe());
+    }
+    let total = needs_update.len() + registry_changes.len();
 
     if args.verbose {
         let current: Vec<&UpdateResult> = updates.iter().filter(|u| !u.needs_update).collect();
@@ -147,12 +179,13 @@ pub fn run(args: UpdateArgs) -> Result<()> {
     }
 
     if args.dry_run {
-        println!("\n[Dry run] Would update {} package(s)", needs_update.len());
+        println!("\n[Dry run] Would update {} package(s)", total);
         return Ok(());
     }
 
-    // Apply updates: re-fetch and update lockfile.
-    let mut lockfile = existing_lockfile.unwrap_or_else(Lockfile::new);
+    // Apply updates: take the re-resolved registry entries, then re-fetch
+    // git deps and update their lock entries.
+    let mut lockfile = registry_update.map(|u| u.lockfile).unwrap_or(base_lockfile);
 
     for u in &needs_update {
         println!("  Fetching {} {}...", u.name, u.new_tag);
@@ -178,8 +211,8 @@ pub fn run(args: UpdateArgs) -> Result<()> {
     println!(
         "\n{} Updated {} package{}",
         green_check(),
-        needs_update.len(),
-        if needs_update.len() == 1 { "" } else { "s" }
+        total,
+        if total == 1 { "" } else { "s" }
     );
 
     Ok(())
@@ -210,14 +243,71 @@ fn resolve_target_names(args: &UpdateArgs, manifest: &PackageManifest) -> Result
     }
 }
 
+/// Re-resolve the targeted registry deps against the registry.
+///
+/// Returns `None` without contacting the registry when no target is a
+/// registry dep.
+fn update_registry_dependencies(
+    args: &UpdateArgs,
+    manifest: &PackageManifest,
+    lockfile: &Lockfile,
+    target_names: &[String],
+) -> Result<Option<LockfileUpdate>> {
+    let registry_targets: Vec<String> = target_names
+        .iter()
+        .filter(|name| {
+            manifest
+                .find_dependency(name)
+                .is_some_and(is_registry_dependency)
+        })
+        .cloned()
+        .collect();
+    if registry_targets.is_empty() {
+        return Ok(None);
+    }
+
+    let mut registry = RegistryManager::new();
+    registry.add_registry(super::search::open_registry(args.registry.as_deref()));
+
+    let mut resolver = Resolver::new();
+    resolver.set_context(BuildContext::development(TargetPlatform::host()));
+    resolver.set_allow_prereleases(args.pre);
+    match resolver.update(manifest, lockfile, &registry, args.mode, &registry_targets) {
+        Ok(update) => Ok(Some(update)),
+        // Without an explicitly chosen registry, an unreachable default
+        // registry should not block updating git deps.
+        Err(e) if args.registry.is_none() && std::env::var("ATLAS_REGISTRY_URL").is_err() => {
+            eprintln!(
+                "warning: registry dependencies left unchanged ({}); pass --registry to update them",
+                e
+            );
+            Ok(None)
+        }
+        Err(e) => Err(e).context("Failed to update registry dependencies"),
+    }
+}
+
+fn is_registry_dependency(dep: &Dependency) -> bool {
+    match dep {
+        Dependency::Simple(_) => true,
+        Dependency::Detailed(d) => d.git.is_none() && d.path.is_none(),
+    }
+}
+
 /// Check a single dep for available updates.
 ///
 /// Returns `None` for path/registry deps (no remote version to compare).
+/// Pre-release tags are only considered when `allow_pre` is set or the
+/// constraint names one explicitly. In conservative mode only tags in the
+/// locked `major.minor` are considered while the lock still satisfies the
+/// constraint.
 fn check_dep_for_update(
     name: &str,
     dep: &Dependency,
     lockfile: Option<&Lockfile>,
     fetcher: &GitFetcher,
+    allow_pre: bool,
+    mode: UpdateMode,
     verbose: bool,
 ) -> Result<Option<UpdateResult>> {
     let detailed = match dep {
@@ -250,8 +340,20 @@ fn check_dep_for_update(
         .list_remote_tags(&url)
         .with_context(|| format!("Failed to list remote tags for '{}'", name))?;
 
+    let old_version = lockfile
+        .and_then(|lf| lf.get_package(name))
+        .map(|p| p.version.clone());
+    let raw_tags = match &old_version {
+        Some(old)
+            if mode == UpdateMode::Conservative && req.as_ref().is_none_or(|r| r.matches(old)) =>
+        {
+            same_minor_tags(raw_tags, old)
+        }
+        _ => raw_tags,
+    };
+
     // Filter to semver tags (with optional 'v' prefix), apply constraint.
-    let best = best_semver_tag(&raw_tags, req.as_ref());
+    let best = best_semver_tag(&raw_tags, req.as_ref(), allow_pre);
 
     let (new_tag, new_version) = match best {
         Some(pair) => pair,
@@ -263,10 +365,6 @@ fn check_dep_for_update(
         }
     };
 
-    let old_version = lockfile
-        .and_then(|lf| lf.get_package(name))
-        .map(|p| p.version.clone());
-
     let needs_update = old_version
         .as_ref()
         .map(|old| &new_version > old)
@@ -284,16 +382,24 @@ fn check_dep_for_update(
 
 /// From a list of raw tag strings, find the highest semver tag satisfying `req`
 /// (or the global maximum if no constraint). Returns `(tag_string, version)`.
-fn best_semver_tag(tags: &[String], req: Option<&VersionReq>) -> Option<(String, Version)> {
+///
+/// Pre-release tags are skipped unless `allow_pre` is set or `req` names them.
+fn best_semver_tag(
+    tags: &[String],
+    req: Option<&VersionReq>,
+    allow_pre: bool,
+) -> Option<(String, Version)> {
     let mut candidates: Vec<(String, Version)> = tags
         .iter()
         .filter_map(|tag| {
             let stripped = tag.strip_prefix('v').unwrap_or(tag);
             let v = Version::parse(stripped).ok()?;
-            if let Some(r) = req {
-                if !r.matches(&v) {
-                    return None;
-                }
+            let matches = match req {
+                Some(r) => req_matches(r, &v, allow_pre),
+                None => allow_pre || v.pre.is_empty(),
+            };
+            if !matches {
+                return None;
             }
             Some((tag.clone(), v))
         })
@@ -304,6 +410,17 @@ fn best_semver_tag(tags: &[String], req: Option<&VersionReq>) -> Option<(String,
     candidates.into_iter().next()
 }
 
+/// Tags whose version shares `locked`'s major and minor (non-semver tags are
+/// dropped too, as `best_semver_tag` would)
+fn same_minor_tags(tags: Vec<String>, locked: &Version) -> Vec<String> {
+    tags.into_iter()
+        .filter(|tag| {
+            Version::parse(tag.strip_prefix('v').unwrap_or(tag))
+                .is_ok_and(|v| v.major == locked.major && v.minor == locked.minor)
+        })
+        .collect()
+}
+
 /// Find atlas.toml by walking up from `start_dir`.
 fn find_manifest(start_dir: &Path) -> Result<PathBuf> {
     let mut current = start_dir
@@ -353,8 +470,7 @@ fn arrow_up() -> &'static str {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use atlas_package::{LockedSource, Lockfile};
-    use std::collections::HashMap;
+    use atlas_package::Lockfile;
     use std::fs;
     use tempfile::TempDir;
 
@@ -367,7 +483,7 @@ mod tests {
             "v1.2.0".to_string(),
             "v1.1.0".to_string(),
         ];
-        let (tag, ver) = best_semver_tag(&tags, None).unwrap();
+        let (tag, ver) = best_semver_tag(&tags, None, false).unwrap();
         assert_eq!(tag, "v1.2.0");
         assert_eq!(ver, Version::new(1, 2, 0));
     }
@@ -380,11 +496,29 @@ mod tests {
             "v2.0.0".to_string(),
         ];
         let req = VersionReq::parse("^1").unwrap();
-        let (tag, ver) = best_semver_tag(&tags, Some(&req)).unwrap();
+        let (tag, ver) = best_semver_tag(&tags, Some(&req), false).unwrap();
         assert_eq!(tag, "v1.5.0");
         assert_eq!(ver, Version::new(1, 5, 0));
     }
 
+    #[test]
+    fn test_best_semver_tag_skips_prereleases_without_opt_in() {
+        let tags = vec![
+            "v1.0.0".to_string(),
+            "v1.1.0-beta.1".to_string(),
+            "v2.0.0-rc.1".to_string(),
+        ];
+        let (tag, _) = best_semver_tag(&tags, None, false).unwrap();
+        assert_eq!(tag, "v1.0.0");
+
+        let req = VersionReq::parse("^1").unwrap();
+        let (tag, _) = best_semver_tag(&tags, Some(&req), true).unwrap();
+        assert_eq!(tag, "v1.1.0-beta.1");
+
+        let (tag, _) = best_semver_tag(&tags, None, true).unwrap();
+        assert_eq!(tag, "v2.0.0-rc.1");
+    }
+
     #[test]
     fn test_best_semver_tag_filters_non_semver() {
         let tags = vec![
@@ -392,29 +526,41 @@ mod tests {
             "latest".to_string(),
             "v1.0.0".to_string(),
         ];
-        let (tag, _) = best_semver_tag(&tags, None).unwrap();
+        let (tag, _) = best_semver_tag(&tags, None, false).unwrap();
         assert_eq!(tag, "v1.0.0");
     }
 
     #[test]
     fn test_best_semver_tag_empty_returns_none() {
-        assert!(best_semver_tag(&[], None).is_none());
+        assert!(best_semver_tag(&[], None, false).is_none());
     }
 
     #[test]
     fn test_best_semver_tag_all_non_semver_returns_none() {
         let tags = vec!["nightly".to_string(), "edge".to_string()];
-        assert!(best_semver_tag(&tags, None).is_none());
+        assert!(best_semver_tag(&tags, None, false).is_none());
     }
 
     #[test]
     fn test_best_semver_tag_no_v_prefix() {
         let tags = vec!["1.0.0".to_string(), "1.2.0".to_string()];
-        let (tag, ver) = best_semver_tag(&tags, None).unwrap();
+        let (tag, ver) = best_semver_tag(&tags, None, false).unwrap();
         assert_eq!(tag, "1.2.0");
         assert_eq!(ver, Version::new(1, 2, 0));
     }
 
+    #[test]
+    fn test_same_minor_tags_keeps_locked_line() {
+        let tags = vec![
+            "v1.2.0".to_string(),
+            "v1.2.4".to_string(),
+            "v1.3.0".to_string(),
+            "nightly".to_string(),
+        ];
+        let kept = same_minor_tags(tags, &Version::new(1, 2, 0));
+        assert_eq!(kept, vec!["v1.2.0".to_string(), "v1.2.4".to_string()]);
+    }
+
     // ── resolve_target_names ──────────────────────────────────────────────────
 
     #[test]
@@ -525,6 +671,49 @@ version = "0.1.0"
         assert!(!temp.path().join("atlas.lock").exists());
     }
 
+    #[test]
+    fn test_registry_deps_update_from_local_registry() {
+        let temp = TempDir::new().unwrap();
+        let registry = TempDir::new().unwrap();
+        for version in ["1.2.0", "1.2.5", "1.3.0"] {
+            fs::create_dir_all(registry.path().join("http").join(version)).unwrap();
+        }
+        fs::write(
+            temp.path().join("atlas.toml"),
+            "[package]\nname = \"test\"\nversion = \"0.1.0\"\n\n[dependencies]\nhttp = \"^1.2\"\n",
+        )
+        .unwrap();
+        fs::write(
+            temp.path().join("atlas.lock"),
+            "version = 1\n\n[[packages]]\nname = \"http\"\nversion = \"1.2.0\"\n\n[packages.source]\ntype = \"registry\"\n",
+        )
+        .unwrap();
+
+        let args = UpdateArgs {
+            project_dir: temp.path().to_path_buf(),
+            registry: Some(registry.path().display().to_string()),
+            mode: UpdateMode::Conservative,
+            ..Default::default()
+        };
+        run(args.clone()).unwrap();
+        let lockfile = Lockfile::from_file(&temp.path().join("atlas.lock")).unwrap();
+        assert_eq!(
+            lockfile.get_package("http").unwrap().version,
+            Version::new(1, 2, 5)
+        );
+
+        run(UpdateArgs {
+            mode: UpdateMode::Aggressive,
+            ..args
+        })
+        .unwrap();
+        let lockfile = Lockfile::from_file(&temp.path().join("atlas.lock")).unwrap();
+        assert_eq!(
+            lockfile.get_package("http").unwrap().version,
+            Version::new(1, 3, 0)
+        );
+    }
+
     // ── find_manifest ─────────────────────────────────────────────────────────
 
     #[test]
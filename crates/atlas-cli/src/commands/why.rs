@@ -0,0 +1,197 @@
+//! Explain a locked dependency (atlas why)
+//!
+//! Rebuilds the resolution from `atlas.toml` + `atlas.lock` and reports which
+//! constraints pulled a package in and why its locked version was chosen.
+
+use anyhow::{bail, Context, Result};
+use atlas_package::manifest::PackageManifest;
+use atlas_package::{Lockfile, Resolution};
+use semver::Version;
+use std::path::{Path, PathBuf};
+
+/// Arguments for the why command
+#[derive(Debug, Clone)]
+pub struct WhyArgs {
+    /// Package spec: `name` or `name@version`
+    pub package: String,
+    /// Project directory (defaults to current)
+    pub project_dir: PathBuf,
+}
+
+impl Default for WhyArgs {
+    fn default() -> Self {
+        Self {
+            package: String::new(),
+            project_dir: PathBuf::from("."),
+        }
+    }
+}
+
+/// Run the why command
+pub fn run(args: WhyArgs) -> Result<()> {
+    print!("{}", explain(&args)?);
+    Ok(())
+}
+
+/// Build the report printed by `atlas why`
+pub fn explain(args: &WhyArgs) -> Result<String> {
+    let (name, requested) = parse_spec(&args.package)?;
+
+    let manifest_path = find_manifest(&args.project_dir)?;
+    let lockfile_path = manifest_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("atlas.lock");
+
+    let manifest =
+        PackageManifest::from_file(&manifest_path).context("Failed to read atlas.toml")?;
+    if !lockfile_path.exists() {
+        bail!("No atlas.lock found. Run 'atlas install' first.");
+    }
+    let lockfile = Lockfile::from_file(&lockfile_path).context("Failed to read atlas.lock")?;
+
+    let resolution = Resolution::from_lockfile(&manifest, &lockfile);
+    let Some(explanation) = resolution.explain(name) else {
+        bail!("Package '{}' is not in atlas.lock", name);
+    };
+
+    let mut report = String::new();
+    if let Some(version) = requested {
+        if version != explanation.version {
+            report.push_str(&format!(
+                "{}@{} is not locked; atlas.lock has v{}\n",
+                name, version, explanation.version
+            ));
+            if let Some(rejected) = explanation.why_not(&version) {
+                report.push_str(&format!("  {}\n", rejected.reason()));
+            } else {
+                let excluded: Vec<String> = explanation
+                    .required_by
+                    .iter()
+                    .filter(|c| !c.requirement.matches(&version))
+                    .map(|c| format!("{} requires {}", c.source, c.requirement))
+                    .collect();
+                if !excluded.is_empty() {
+                    report.push_str(&format!("  excluded: {}\n", excluded.join(", ")));
+                }
+            }
+            report.push('\n');
+        }
+    }
+    report.push_str(&explanation.report());
+    Ok(report)
+}
+
+/// Split `name@version` into its parts
+fn parse_spec(spec: &str) -> Result<(&str, Option<Version>)> {
+    match spec.split_once('@') {
+        Some((name, version)) => {
+            let version = Version::parse(version.trim_start_matches('v'))
+                .with_context(|| format!("Invalid version in '{}'", spec))?;
+            Ok((name, Some(version)))
+        }
+        None => Ok((spec, None)),
+    }
+}
+
+/// Find atlas.toml manifest file
+fn find_manifest(start_dir: &Path) -> Result<PathBuf> {
+    let mut current = start_dir
+        .canonicalize()
+        .unwrap_or_else(|_| start_dir.to_path_buf());
+
+    loop {
+        let manifest_path = current.join("atlas.toml");
+        if manifest_path.exists() {
+            return Ok(manifest_path);
+        }
+
+        if !current.pop() {
+            break;
+        }
+    }
+
+    bail!(
+        "Could not find atlas.toml in {} or any parent directory",
+        start_dir.display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_project(dir: &Path) {
+        let manifest = r#"[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+web = "^2.0"
+"#;
+        let lockfile = r#"version = 1
+
+[[packages]]
+name = "json"
+version = "1.4.0"
+
+[packages.source]
+type = "registry"
+
+[[packages]]
+name = "web"
+version = "2.1.0"
+
+[packages.source]
+type = "registry"
+
+[packages.dependencies]
+json = "1.4.0"
+"#;
+        fs::write(dir.join("atlas.toml"), manifest).expect("write manifest");
+        fs::write(dir.join("atlas.lock"), lockfile).expect("write lockfile");
+    }
+
+    #[test]
+    fn test_why_reports_dependents() {
+        let temp = TempDir::new().expect("tempdir");
+        create_project(temp.path());
+
+        let report = explain(&WhyArgs {
+            package: "json".to_string(),
+            project_dir: temp.path().to_path_buf(),
+        })
+        .unwrap();
+        assert!(report.starts_with("json v1.4.0"), "{report}");
+        assert!(report.contains("web v2.1.0 requires =1.4.0"), "{report}");
+    }
+
+    #[test]
+    fn test_why_explains_unlocked_version() {
+        let temp = TempDir::new().expect("tempdir");
+        create_project(temp.path());
+
+        let report = explain(&WhyArgs {
+            package: "web@1.9.0".to_string(),
+            project_dir: temp.path().to_path_buf(),
+        })
+        .unwrap();
+        assert!(report.contains("web@1.9.0 is not locked"), "{report}");
+        assert!(report.contains("excluded: app requires ^2.0"), "{report}");
+    }
+
+    #[test]
+    fn test_why_unknown_package_errors() {
+        let temp = TempDir::new().expect("tempdir");
+        create_project(temp.path());
+
+        let err = explain(&WhyArgs {
+            package: "missing".to_string(),
+            project_dir: temp.path().to_path_buf(),
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("not in atlas.lock"));
+    }
+}
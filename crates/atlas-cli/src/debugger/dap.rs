@@ -0,0 +1,1183 @@
+//! Debug Adapter Protocol server
+//!
+//! Serves a [`DebuggerSession`] to graphical debuggers such as VS Code. The
+//! adapter speaks DAP over a byte stream (stdin/stdout for `atlas debug
+//! --dap`): each message is a JSON body preceded by a `Content-Length` header.
+//!
+//! The program runs on the thread that reads requests, so requests are only
+//! handled while the program is paused or finished, and `pause` is refused:
+//! a running program cannot be interrupted. Program output is sent to
+//! the client as `output` events rather than written to stdout, where it
+//! would corrupt the protocol stream.
+
+use atlas_runtime::debugger::{
+    BreakpointId, DebugRequest, DebugResponse, DebuggerSession, PauseReason, SourceLocation,
+    VariableScope, WatchTarget,
+};
+use atlas_runtime::{Atlas, SecurityContext};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// The only thread an Atlas program has
+const THREAD_ID: i64 = 1;
+
+/// Exception breakpoint filter that pauses on runtime errors
+const ERROR_FILTER: &str = "error";
+
+// ── Transport ─────────────────────────────────────────────────────────────────
+
+/// Read one message, or `None` at end of input
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return match length {
+                None => Ok(None),
+                Some(_) => Err(invalid("input ended inside message headers")),
+            };
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                let value = value.trim();
+                length = Some(
+                    value
+                        .parse::<usize>()
+                        .map_err(|_| invalid(&format!("invalid Content-Length '{value}'")))?,
+                );
+            }
+        }
+    }
+
+    let length = length.ok_or_else(|| invalid("message has no Content-Length header"))?;
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| invalid(&format!("message is not JSON: {e}")))
+}
+
+/// Write one message with its `Content-Length` header
+pub fn write_message(writer: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = serde_json::to_vec(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n", body.len())?;
+    writer.write_all(&body)?;
+    writer.flush()
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// The outgoing half of the connection, shared with the program's output
+/// writer so output events and responses share one sequence
+#[derive(Clone)]
+struct Transport {
+    out: Arc<Mutex<Box<dyn Write + Send>>>,
+    seq: Arc<AtomicI64>,
+}
+
+impl Transport {
+    fn new(out: Box<dyn Write + Send>) -> Self {
+        Self {
+            out: Arc::new(Mutex::new(out)),
+            seq: Arc::new(AtomicI64::new(1)),
+        }
+    }
+
+    fn send(&self, mut message: Value) -> io::Result<()> {
+        message["seq"] = json!(self.seq.fetch_add(1, Ordering::SeqCst));
+        let mut out = self.out.lock().unwrap_or_else(|e| e.into_inner());
+        write_message(&mut *out, &message)
+    }
+
+    fn event(&self, event: &str, body: Value) -> io::Result<()> {
+        let mut message = json!({ "type": "event", "event": event });
+        if !body.is_null() {
+            message["body"] = body;
+        }
+        self.send(message)
+    }
+}
+
+/// Program output, forwarded to the client as `output` events
+struct OutputEvents(Transport);
+
+impl Write for OutputEvents {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let output = String::from_utf8_lossy(buf);
+        self.0
+            .event("output", json!({ "category": "stdout", "output": output }))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// ── Server ────────────────────────────────────────────────────────────────────
+
+/// A request the server could not carry out, reported to the client as a
+/// failed response
+type RequestResult = Result<Value, String>;
+
+/// Debug Adapter Protocol server driving one Atlas program
+pub struct DapServer {
+    transport: Transport,
+    security: SecurityContext,
+    /// Program named on the command line, debugged by an `attach` request
+    preloaded: Option<PathBuf>,
+    session: Option<DebuggerSession>,
+    stop_on_entry: bool,
+    /// Breakpoint ids registered for each source, as the client names it
+    breakpoints: HashMap<String, Vec<BreakpointId>>,
+    /// Watchpoint ids registered by the last `setDataBreakpoints`
+    data_breakpoints: Vec<BreakpointId>,
+    /// Set once the program has finished; no further execution is possible
+    terminated: bool,
+}
+
+impl DapServer {
+    /// Create a server that writes protocol messages to `out`
+    pub fn new(out: Box<dyn Write + Send>) -> Self {
+        Self {
+            transport: Transport::new(out),
+            security: SecurityContext::allow_all(),
+            preloaded: None,
+            session: None,
+            stop_on_entry: false,
+            breakpoints: HashMap::new(),
+            data_breakpoints: Vec::new(),
+            terminated: false,
+        }
+    }
+
+    /// Debug `program` when the client attaches instead of launching
+    pub fn with_program(mut self, program: impl Into<PathBuf>) -> Self {
+        self.preloaded = Some(program.into());
+        self
+    }
+
+    /// Handle requests from `input` until the client disconnects or the
+    /// input ends
+    pub fn serve(&mut self, mut input: impl BufRead) -> io::Result<()> {
+        while let Some(message) = read_message(&mut input)? {
+            if message["type"] != "request" {
+                continue;
+            }
+            if !self.handle(&message)? {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle one request; `false` once the client has disconnected
+    fn handle(&mut self, request: &Value) -> io::Result<bool> {
+        let command = request["command"].as_str().unwrap_or_default();
+        let args = &request["arguments"];
+        match command {
+            "initialize" => {
+                self.respond(request, Ok(capabilities()))?;
+            }
+            "launch" => {
+                let result = match args["program"].as_str() {
+                    Some(program) => self.load(Path::new(program), args),
+                    None => Err("launch requires a 'program' to debug".to_string()),
+                };
+                self.respond_then_initialized(request, result)?;
+            }
+            "attach" => {
+                let result = match self.preloaded.clone() {
+                    Some(program) => self.load(&program, args),
+                    None => Err(
+                        "no program to attach to; start the adapter with `atlas debug --dap <file>`"
+                            .to_string(),
+                    ),
+                };
+                self.respond_then_initialized(request, result)?;
+            }
+            "setBreakpoints" => {
+                let result = self.set_breakpoints(args);
+                self.respond(request, result)?;
+            }
+            "dataBreakpointInfo" => {
+                let result = data_breakpoint_info(args);
+                self.respond(request, result)?;
+            }
+            "setDataBreakpoints" => {
+                let result = self.set_data_breakpoints(args);
+                self.respond(request, result)?;
+            }
+            "setExceptionBreakpoints" => {
+                let result = self.set_exception_breakpoints(args);
+                self.respond(request, result)?;
+            }
+            "configurationDone" => {
+                self.respond(request, Ok(Value::Null))?;
+                if self.stop_on_entry {
+                    self.step(DebugRequest::StepInto, "entry")?;
+                } else {
+                    self.run(DebugRequest::Continue)?;
+                }
+            }
+            "threads" => {
+                let threads = json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] });
+                self.respond(request, Ok(threads))?;
+            }
+            "stackTrace" => {
+                let result = self.stack_trace();
+                self.respond(request, result)?;
+            }
+            "scopes" => {
+                let result = self.scopes(args);
+                self.respond(request, result)?;
+            }
+            "variables" => {
+                let result = self.variables(args);
+                self.respond(request, result)?;
+            }
+            "evaluate" => {
+                let result = self.evaluate(args);
+                self.respond(request, result)?;
+            }
+            "continue" => {
+                let result = self
+                    .paused()
+                    .map(|_| json!({ "allThreadsContinued": true }));
+                let ok = result.is_ok();
+                self.respond(request, result)?;
+                if ok {
+                    self.run(DebugRequest::Continue)?;
+                }
+            }
+            "next" | "stepIn" | "stepOut" => {
+                let result = self.paused().map(|_| Value::Null);
+                let ok = result.is_ok();
+                self.respond(request, result)?;
+                if ok {
+                    match command {
+                        "next" => self.run(DebugRequest::StepOver)?,
+                        "stepIn" => self.step(DebugRequest::StepInto, "step")?,
+                        _ => self.run(DebugRequest::StepOut)?,
+                    }
+                }
+            }
+            // Requests are only read while the program is stopped, so a
+            // running program can never be interrupted
+            "pause" => {
+                self.respond(
+                    request,
+                    Err("pause is not supported; set a breakpoint to stop the program".to_string()),
+                )?;
+            }
+            "terminate" => {
+                self.respond(request, Ok(Value::Null))?;
+                self.finish(None)?;
+            }
+            "disconnect" => {
+                self.respond(request, Ok(Value::Null))?;
+                return Ok(false);
+            }
+            _ => {
+                self.respond(request, Err(format!("unsupported request '{command}'")))?;
+            }
+        }
+        Ok(true)
+    }
+
+    fn respond(&self, request: &Value, result: RequestResult) -> io::Result<()> {
+        let mut response = json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": result.is_ok(),
+        });
+        match result {
+            Ok(Value::Null) => {}
+            Ok(body) => response["body"] = body,
+            Err(message) => response["message"] = json!(message),
+        }
+        self.transport.send(response)
+    }
+
+    /// Answer `launch`/`attach`, then invite the client to send breakpoints
+    /// once there is a program to bind them to
+    fn respond_then_initialized(&self, request: &Value, result: RequestResult) -> io::Result<()> {
+        let ok = result.is_ok();
+        self.respond(request, result)?;
+        if ok {
+            self.transport.event("initialized", Value::Null)?;
+        }
+        Ok(())
+    }
+
+    /// Compile `program` with its imports and open a session on it
+    fn load(&mut self, program: &Path, args: &Value) -> RequestResult {
+        let path = program
+            .canonicalize()
+            .unwrap_or_else(|_| program.to_path_buf());
+        let path_str = path.to_string_lossy().to_string();
+        let source = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read source file '{}': {}", path_str, e))?;
+
+        let runtime = Atlas::new_with_security(self.security.clone());
+        let bytecode = match runtime.compile_file(&path_str) {
+            Ok(program) => program.bytecode,
+            Err(diagnostics) => {
+                let errors: Vec<String> = diagnostics
+                    .iter()
+                    .filter(|d| d.is_error())
+                    .map(|d| format!("{}:{}: {}", d.line, d.column, d.message))
+                    .collect();
+                return Err(format!(
+                    "Failed to compile {}\n{}",
+                    path_str,
+                    errors.join("\n")
+                ));
+            }
+        };
+
+        let mut session = DebuggerSession::new(bytecode, &source, &path_str);
+        session.set_output_writer(Arc::new(Mutex::new(Box::new(OutputEvents(
+            self.transport.clone(),
+        )))));
+        self.session = Some(session);
+        self.stop_on_entry = args["stopOnEntry"].as_bool().unwrap_or(false);
+        self.breakpoints.clear();
+        self.terminated = false;
+        Ok(Value::Null)
+    }
+
+    fn session(&mut self) -> Result<&mut DebuggerSession, String> {
+        self.session
+            .as_mut()
+            .ok_or_else(|| "no program is loaded; send 'launch' or 'attach' first".to_string())
+    }
+
+    /// The session, provided its program can still run
+    fn paused(&mut self) -> Result<&mut DebuggerSession, String> {
+        if self.terminated {
+            return Err("the program has finished".to_string());
+        }
+        self.session()
+    }
+
+    // ── Breakpoints ───────────────────────────────────────────────────────────
+
+    /// Replace the breakpoints of one source with the requested lines
+    fn set_breakpoints(&mut self, args: &Value) -> RequestResult {
+        let client_path = args["source"]["path"]
+            .as_str()
+            .ok_or("setBreakpoints requires a source path")?
+            .to_string();
+        let lines: Vec<u32> = args["breakpoints"]
+            .as_array()
+            .map(|bps| {
+                bps.iter()
+                    .filter_map(|bp| bp["line"].as_u64())
+                    .map(|line| line as u32)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let old = self.breakpoints.remove(&client_path).unwrap_or_default();
+        let session = self.session()?;
+        for id in old {
+            session.process_request(DebugRequest::RemoveBreakpoint { id });
+        }
+
+        let file = session_file(session, &client_path);
+        let mut ids = Vec::new();
+        let mut breakpoints = Vec::new();
+        for line in lines {
+            let location = SourceLocation::new(file.clone(), line, 1);
+            if let DebugResponse::BreakpointSet { breakpoint } =
+                session.process_request(DebugRequest::SetBreakpoint { location })
+            {
+                ids.push(breakpoint.id);
+                breakpoints.push(json!({
+                    "id": breakpoint.id,
+                    "verified": breakpoint.verified,
+                    "line": line,
+                    "source": { "path": client_path },
+                }));
+            }
+        }
+        self.breakpoints.insert(client_path, ids);
+        Ok(json!({ "breakpoints": breakpoints }))
+    }
+
+    /// Replace the data breakpoints with the requested variables
+    fn set_data_breakpoints(&mut self, args: &Value) -> RequestResult {
+        let old = std::mem::take(&mut self.data_breakpoints);
+        let session = self.session()?;
+        for id in old {
+            session.process_request(DebugRequest::RemoveWatchpoint { id });
+        }
+
+        let mut ids = Vec::new();
+        let mut breakpoints = Vec::new();
+        for requested in args["breakpoints"].as_array().into_iter().flatten() {
+            let target = requested["dataId"]
+                .as_str()
+                .and_then(|data_id| serde_json::from_str::<WatchTarget>(data_id).ok());
+            let Some(target) = target else {
+                breakpoints.push(json!({ "verified": false, "message": "unknown data id" }));
+                continue;
+            };
+            match session.process_request(DebugRequest::SetWatchpoint { target }) {
+                DebugResponse::WatchpointSet { watchpoint } => {
+                    ids.push(watchpoint.id);
+                    breakpoints.push(json!({ "id": watchpoint.id, "verified": true }));
+                }
+                DebugResponse::Error { message } => {
+                    breakpoints.push(json!({ "verified": false, "message": message }));
+                }
+                _ => breakpoints.push(json!({ "verified": false })),
+            }
+        }
+        self.data_breakpoints = ids;
+        Ok(json!({ "breakpoints": breakpoints }))
+    }
+
+    fn set_exception_breakpoints(&mut self, args: &Value) -> RequestResult {
+        let enabled = args["filters"]
+            .as_array()
+            .is_some_and(|filters| filters.iter().any(|f| f == ERROR_FILTER));
+        self.session()?
+            .process_request(DebugRequest::SetBreakOnError {
+                enabled,
+                kinds: Vec::new(),
+            });
+        Ok(Value::Null)
+    }
+
+    // ── Execution ─────────────────────────────────────────────────────────────
+
+    /// Resume with `request` and report where the program stopped
+    fn run(&mut self, request: DebugRequest) -> io::Result<()> {
+        let Some(session) = self.session.as_mut() else {
+            return Ok(());
+        };
+        session.process_request(request);
+        let response = session.run_until_pause(&self.security);
+        self.report(response, "step")
+    }
+
+    /// Step one source line, entering calls
+    ///
+    /// The VM steps one instruction at a time, so keep stepping until the
+    /// program reaches another line or call frame.
+    fn step(&mut self, request: DebugRequest, reason: &str) -> io::Result<()> {
+        let Some(session) = self.session.as_mut() else {
+            return Ok(());
+        };
+        let start = position(session);
+        let response = loop {
+            session.process_request(request.clone());
+            let response = session.run_until_pause(&self.security);
+            let stepped = matches!(
+                response,
+                DebugResponse::Paused {
+                    reason: PauseReason::Step,
+                    ..
+                }
+            );
+            if !stepped || session.is_stopped() || position(session) != start {
+                break response;
+            }
+        };
+        self.report(response, reason)
+    }
+
+    /// Tell the client why the program stopped
+    fn report(&mut self, response: DebugResponse, step_reason: &str) -> io::Result<()> {
+        let finished = self.session.as_ref().is_some_and(|s| s.is_stopped());
+        match response {
+            DebugResponse::Error { message } => self.finish(Some(message)),
+            _ if finished => self.finish(None),
+            DebugResponse::Paused { reason, .. } => {
+                let body = match reason {
+                    PauseReason::Breakpoint { id } => {
+                        json!({ "reason": "breakpoint", "hitBreakpointIds": [id] })
+                    }
+                    PauseReason::Watchpoint {
+                        id,
+                        old_value,
+                        new_value,
+                    } => json!({
+                        "reason": "data breakpoint",
+                        "hitBreakpointIds": [id],
+                        "text": format!(
+                            "{} -> {}",
+                            old_value.as_deref().unwrap_or("<unset>"),
+                            new_value
+                        ),
+                    }),
+                    PauseReason::Step => json!({ "reason": step_reason }),
+                    PauseReason::ManualPause => json!({ "reason": "pause" }),
+                    PauseReason::Exception { message, kind } => json!({
+                        "reason": "exception",
+                        "description": kind,
+                        "text": message,
+                    }),
+                };
+                let mut body = body;
+                body["threadId"] = json!(THREAD_ID);
+                body["allThreadsStopped"] = json!(true);
+                self.transport.event("stopped", body)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Report the end of the program, with the runtime error that ended it
+    fn finish(&mut self, error: Option<String>) -> io::Result<()> {
+        if self.terminated {
+            return Ok(());
+        }
+        self.terminated = true;
+        let exit_code = match error {
+            Some(message) => {
+                self.transport.event(
+                    "output",
+                    json!({ "category": "stderr", "output": format!("{message}\n") }),
+                )?;
+                1
+            }
+            None => 0,
+        };
+        self.transport
+            .event("exited", json!({ "exitCode": exit_code }))?;
+        self.transport.event("terminated", Value::Null)
+    }
+
+    // ── Inspection ────────────────────────────────────────────────────────────
+
+    fn stack_trace(&mut self) -> RequestResult {
+        let session = self.paused()?;
+        let DebugResponse::StackTrace { frames } = session.process_request(DebugRequest::GetStack)
+        else {
+            return Err("the stack is not available".to_string());
+        };
+        let frames: Vec<Value> = frames
+            .iter()
+            .map(|frame| {
+                let mut dap = json!({
+                    "id": frame.index,
+                    "name": frame.function_name,
+                    "line": 0,
+                    "column": 0,
+                });
+                if let Some(location) = &frame.location {
+                    dap["line"] = json!(location.line);
+                    dap["column"] = json!(location.column);
+                    dap["source"] = source(&location.file);
+                }
+                dap
+            })
+            .collect();
+        Ok(json!({ "totalFrames": frames.len(), "stackFrames": frames }))
+    }
+
+    /// Each frame has a locals scope and a globals scope; variable references
+    /// encode the frame and which of the two it is
+    fn scopes(&mut self, args: &Value) -> RequestResult {
+        self.paused()?;
+        let frame = args["frameId"].as_u64().unwrap_or(0) as i64;
+        Ok(json!({ "scopes": [
+            { "name": "Locals", "variablesReference": frame * 2 + 1, "expensive": false },
+            { "name": "Globals", "variablesReference": frame * 2 + 2, "expensive": false },
+        ] }))
+    }
+
+    fn variables(&mut self, args: &Value) -> RequestResult {
+        let reference = args["variablesReference"].as_u64().unwrap_or(0);
+        if reference == 0 {
+            return Ok(json!({ "variables": [] }));
+        }
+        let frame = ((reference - 1) / 2) as usize;
+        let globals = reference.is_multiple_of(2);
+        let variables: Vec<Value> = self
+            .paused()?
+            .scoped_variables(frame)
+            .into_iter()
+            .filter(|v| (v.scope == VariableScope::Global) == globals)
+            .map(|v| {
+                json!({
+                    "name": v.variable.name,
+                    "value": v.variable.value,
+                    "type": v.variable.type_name,
+                    "variablesReference": 0,
+                })
+            })
+            .collect();
+        Ok(json!({ "variables": variables }))
+    }
+
+    fn evaluate(&mut self, args: &Value) -> RequestResult {
+        let expression = args["expression"]
+            .as_str()
+            .ok_or("evaluate requires an expression")?
+            .to_string();
+        let frame_index = args["frameId"].as_u64().unwrap_or(0) as usize;
+        match self.paused()?.process_request(DebugRequest::Evaluate {
+            expression,
+            frame_index,
+        }) {
+            DebugResponse::EvalResult { value, type_name } => Ok(json!({
+                "result": value,
+                "type": type_name,
+                "variablesReference": 0,
+            })),
+            DebugResponse::Error { message } => Err(message),
+            _ => Err("evaluation failed".to_string()),
+        }
+    }
+}
+
+/// What this adapter supports, sent in reply to `initialize`
+fn capabilities() -> Value {
+    json!({
+        "supportsConfigurationDoneRequest": true,
+        "supportsEvaluateForHovers": true,
+        "supportsTerminateRequest": true,
+        "supportsDataBreakpoints": true,
+        "exceptionBreakpointFilters": [{
+            "filter": ERROR_FILTER,
+            "label": "Runtime errors",
+            "default": false,
+        }],
+    })
+}
+
+/// Describe the variable a data breakpoint would watch
+///
+/// The data id is the serialized [`WatchTarget`]: a local for a variable of
+/// a frame's Locals scope, otherwise a global.
+fn data_breakpoint_info(args: &Value) -> RequestResult {
+    let name = args["name"]
+        .as_str()
+        .ok_or("dataBreakpointInfo requires a variable name")?
+        .to_string();
+    let reference = args["variablesReference"].as_u64().unwrap_or(0);
+    let target = if reference == 0 || reference.is_multiple_of(2) {
+        WatchTarget::Global { name }
+    } else {
+        WatchTarget::Local {
+            frame_index: ((reference - 1) / 2) as usize,
+            name,
+        }
+    };
+    let data_id = serde_json::to_string(&target).map_err(|e| e.to_string())?;
+    Ok(json!({
+        "dataId": data_id,
+        "description": format!("{target} changes"),
+        "accessTypes": ["write"],
+        "canPersist": matches!(target, WatchTarget::Global { .. }),
+    }))
+}
+
+/// The file name the session's source map uses for a path from the client
+fn session_file(session: &DebuggerSession, client_path: &str) -> String {
+    let wanted = Path::new(client_path).canonicalize().ok();
+    session
+        .source_map()
+        .files()
+        .into_iter()
+        .find(|file| {
+            file == client_path
+                || (wanted.is_some() && Path::new(file).canonicalize().ok() == wanted)
+        })
+        .unwrap_or_else(|| client_path.to_string())
+}
+
+/// A DAP source for a file named by the source map
+fn source(file: &str) -> Value {
+    let name = Path::new(file)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| file.to_string());
+    json!({ "name": name, "path": file })
+}
+
+/// Call depth and source line, which a line step must move away from
+fn position(session: &mut DebuggerSession) -> (usize, Option<(String, u32)>) {
+    match session.process_request(DebugRequest::GetStack) {
+        DebugResponse::StackTrace { frames } => {
+            let line = frames
+                .first()
+                .and_then(|f| f.location.as_ref())
+                .map(|l| (l.file.clone(), l.line));
+            (frames.len(), line)
+        }
+        _ => (0, None),
+    }
+}
+
+/// Serve DAP on stdin/stdout, optionally for a program given on the command
+/// line
+pub fn run_stdio(program: Option<&str>) -> io::Result<()> {
+    let mut server = DapServer::new(Box::new(io::stdout()));
+    if let Some(program) = program {
+        server = server.with_program(program);
+    }
+    server.serve(io::stdin().lock())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use tempfile::NamedTempFile;
+
+    /// Writer whose bytes the test reads back after the server is done
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn program(source: &str) -> NamedTempFile {
+        let mut file = tempfile::Builder::new().suffix(".atl").tempfile().unwrap();
+        file.write_all(source.as_bytes()).unwrap();
+        file.flush().unwrap();
+        file
+    }
+
+    fn path(file: &NamedTempFile) -> String {
+        file.path()
+            .canonicalize()
+            .unwrap()
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Run `requests` through a server and return every message it sent
+    fn session(server: DapServer, requests: &[(&str, Value)]) -> Vec<Value> {
+        let captured = Captured::default();
+        let mut server = DapServer {
+            transport: Transport::new(Box::new(captured.clone())),
+            ..server
+        };
+        let mut input = Vec::new();
+        for (seq, (command, arguments)) in requests.iter().enumerate() {
+            let request = json!({
+                "seq": seq + 1,
+                "type": "request",
+                "command": command,
+                "arguments": arguments,
+            });
+            write_message(&mut input, &request).unwrap();
+        }
+        server.serve(Cursor::new(input)).unwrap();
+
+        let bytes = captured.0.lock().unwrap().clone();
+        let mut reader = Cursor::new(bytes);
+        let mut messages = Vec::new();
+        while let Some(message) = read_message(&mut reader).unwrap() {
+            messages.push(message);
+        }
+        messages
+    }
+
+    fn server() -> DapServer {
+        DapServer::new(Box::new(io::sink()))
+    }
+
+    fn response<'a>(messages: &'a [Value], command: &str) -> &'a Value {
+        messages
+            .iter()
+            .find(|m| m["type"] == "response" && m["command"] == command)
+            .unwrap_or_else(|| panic!("no response to {command}"))
+    }
+
+    fn events<'a>(messages: &'a [Value], event: &str) -> Vec<&'a Value> {
+        messages
+            .iter()
+            .filter(|m| m["type"] == "event" && m["event"] == event)
+            .collect()
+    }
+
+    fn launch(file: &NamedTempFile) -> (&'static str, Value) {
+        ("launch", json!({ "program": path(file) }))
+    }
+
+    fn breakpoints(file: &NamedTempFile, lines: &[u32]) -> (&'static str, Value) {
+        let breakpoints: Vec<Value> = lines.iter().map(|l| json!({ "line": l })).collect();
+        (
+            "setBreakpoints",
+            json!({ "source": { "path": path(file) }, "breakpoints": breakpoints }),
+        )
+    }
+
+    const PROGRAM: &str = "fn double(n: number): number {\n    let twice = n * 2;\n    return twice;\n}\nlet start = 20;\nlet result = double(start);\nconsole.log(str(result));\n";
+
+    #[test]
+    fn test_message_framing_round_trips() {
+        let mut bytes = Vec::new();
+        write_message(&mut bytes, &json!({ "seq": 1, "type": "request" })).unwrap();
+        write_message(&mut bytes, &json!({ "seq": 2, "type": "event" })).unwrap();
+        assert!(bytes.starts_with(b"Content-Length: "));
+
+        let mut reader = Cursor::new(bytes);
+        assert_eq!(read_message(&mut reader).unwrap().unwrap()["seq"], 1);
+        assert_eq!(read_message(&mut reader).unwrap().unwrap()["seq"], 2);
+        assert!(read_message(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_message_without_length_is_rejected() {
+        let mut reader = Cursor::new(b"Content-Type: json\r\n\r\n{}".to_vec());
+        assert!(read_message(&mut reader).is_err());
+    }
+
+    #[test]
+    fn test_data_breakpoint_stops_when_a_global_changes() {
+        let file = program("let mut count = 0;\ncount = count + 1;\ncount = count + 1;\n");
+        let data_id = serde_json::to_string(&WatchTarget::Global {
+            name: "count".to_string(),
+        })
+        .unwrap();
+        let messages = session(
+            server(),
+            &[
+                ("initialize", json!({})),
+                launch(&file),
+                (
+                    "dataBreakpointInfo",
+                    json!({ "name": "count", "variablesReference": 2 }),
+                ),
+                (
+                    "setDataBreakpoints",
+                    json!({ "breakpoints": [{ "dataId": data_id, "accessType": "write" }] }),
+                ),
+                ("configurationDone", json!({})),
+                ("continue", json!({ "threadId": THREAD_ID })),
+                ("disconnect", json!({})),
+            ],
+        );
+
+        assert_eq!(
+            response(&messages, "initialize")["body"]["supportsDataBreakpoints"],
+            true
+        );
+        let info = &response(&messages, "dataBreakpointInfo")["body"];
+        assert_eq!(info["dataId"], data_id);
+        assert_eq!(info["accessTypes"], json!(["write"]));
+        let set = &response(&messages, "setDataBreakpoints")["body"]["breakpoints"][0];
+        assert_eq!(set["verified"], true);
+
+        let stops = events(&messages, "stopped");
+        assert_eq!(stops.len(), 2);
+        assert_eq!(stops[0]["body"]["reason"], "data breakpoint");
+        assert_eq!(stops[0]["body"]["hitBreakpointIds"], json!([set["id"]]));
+        assert_eq!(stops[0]["body"]["text"], "<unset> -> 0");
+        assert_eq!(stops[1]["body"]["text"], "0 -> 1");
+    }
+
+    #[test]
+    fn test_initialize_and_launch() {
+        let file = program(PROGRAM);
+        let messages = session(
+            server(),
+            &[
+                ("initialize", json!({ "adapterID": "atlas" })),
+                launch(&file),
+                ("configurationDone", json!({})),
+                ("disconnect", json!({})),
+            ],
+        );
+
+        let init = response(&messages, "initialize");
+        assert_eq!(init["success"], true);
+        assert_eq!(init["body"]["supportsConfigurationDoneRequest"], true);
+        assert_eq!(response(&messages, "launch")["success"], true);
+        assert_eq!(events(&messages, "initialized").len(), 1);
+
+        // Without breakpoints the program runs to completion
+        let output: String = events(&messages, "output")
+            .iter()
+            .map(|e| e["body"]["output"].as_str().unwrap())
+            .collect();
+        assert_eq!(output, "40\n");
+        assert_eq!(events(&messages, "exited")[0]["body"]["exitCode"], 0);
+        assert_eq!(events(&messages, "terminated").len(), 1);
+
+        // Sequence numbers increase across responses and events
+        let seqs: Vec<i64> = messages
+            .iter()
+            .map(|m| m["seq"].as_i64().unwrap())
+            .collect();
+        assert!(seqs.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_breakpoint_stack_and_variables() {
+        let file = program(PROGRAM);
+        let messages = session(
+            server(),
+            &[
+                ("initialize", json!({})),
+                launch(&file),
+                breakpoints(&file, &[3]),
+                ("configurationDone", json!({})),
+                ("threads", json!({})),
+                ("stackTrace", json!({ "threadId": THREAD_ID })),
+                ("scopes", json!({ "frameId": 0 })),
+                ("variables", json!({ "variablesReference": 1 })),
+                ("variables", json!({ "variablesReference": 2 })),
+                ("continue", json!({ "threadId": THREAD_ID })),
+                ("disconnect", json!({})),
+            ],
+        );
+
+        let set = &response(&messages, "setBreakpoints")["body"]["breakpoints"][0];
+        assert_eq!(set["verified"], true);
+        assert_eq!(set["line"], 3);
+
+        let stopped = events(&messages, "stopped");
+        assert_eq!(stopped[0]["body"]["reason"], "breakpoint");
+        assert_eq!(stopped[0]["body"]["hitBreakpointIds"][0], set["id"]);
+
+        let threads = &response(&messages, "threads")["body"]["threads"];
+        assert_eq!(threads[0]["id"], THREAD_ID);
+
+        let frames = response(&messages, "stackTrace")["body"]["stackFrames"]
+            .as_array()
+            .unwrap();
+        assert_eq!(frames[0]["name"], "double");
+        assert_eq!(frames[0]["line"], 3);
+        assert_eq!(frames[0]["source"]["path"], path(&file));
+        assert_eq!(frames[1]["line"], 6);
+
+        let scopes = &response(&messages, "scopes")["body"]["scopes"];
+        assert_eq!(scopes[0]["name"], "Locals");
+        assert_eq!(scopes[1]["name"], "Globals");
+
+        let variables: Vec<&Value> = messages
+            .iter()
+            .filter(|m| m["command"] == "variables")
+            .collect();
+        let locals = variables[0]["body"]["variables"].as_array().unwrap();
+        let twice = locals.iter().find(|v| v["name"] == "twice").unwrap();
+        assert_eq!(twice["value"], "40");
+        assert_eq!(twice["type"], "number");
+        let globals = variables[1]["body"]["variables"].as_array().unwrap();
+        assert!(globals.iter().any(|v| v["name"] == "start"));
+        assert!(!globals.iter().any(|v| v["name"] == "twice"));
+
+        assert_eq!(events(&messages, "terminated").len(), 1);
+    }
+
+    #[test]
+    fn test_replacing_breakpoints_removes_old_ones() {
+        let file = program(PROGRAM);
+        let messages = session(
+            server(),
+            &[
+                launch(&file),
+                breakpoints(&file, &[2]),
+                breakpoints(&file, &[]),
+                ("configurationDone", json!({})),
+                ("disconnect", json!({})),
+            ],
+        );
+        assert!(events(&messages, "stopped").is_empty());
+        assert_eq!(events(&messages, "exited")[0]["body"]["exitCode"], 0);
+    }
+
+    #[test]
+    fn test_stepping_moves_between_lines() {
+        let file = program(PROGRAM);
+        let messages = session(
+            server(),
+            &[
+                launch(&file),
+                breakpoints(&file, &[6]),
+                ("configurationDone", json!({})),
+                ("stepIn", json!({ "threadId": THREAD_ID })),
+                ("stackTrace", json!({ "threadId": THREAD_ID })),
+                ("next", json!({ "threadId": THREAD_ID })),
+                ("stackTrace", json!({ "threadId": THREAD_ID })),
+                ("stepOut", json!({ "threadId": THREAD_ID })),
+                ("stackTrace", json!({ "threadId": THREAD_ID })),
+                ("disconnect", json!({})),
+            ],
+        );
+
+        let stopped = events(&messages, "stopped");
+        assert_eq!(stopped.len(), 4);
+        assert!(stopped[1..].iter().all(|e| e["body"]["reason"] == "step"));
+
+        let traces: Vec<&Value> = messages
+            .iter()
+            .filter(|m| m["command"] == "stackTrace")
+            .map(|m| &m["body"]["stackFrames"])
+            .collect();
+        // Into `double`, on to its next line, then back out to the caller
+        assert_eq!(traces[0][0]["name"], "double");
+        assert_eq!(traces[0][0]["line"], 2);
+        assert_eq!(traces[1][0]["line"], 3);
+        assert_eq!(traces[2].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_stop_on_entry_and_evaluate() {
+        let file = program(PROGRAM);
+        let messages = session(
+            server(),
+            &[
+                (
+                    "launch",
+                    json!({ "program": path(&file), "stopOnEntry": true }),
+                ),
+                breakpoints(&file, &[7]),
+                ("configurationDone", json!({})),
+                ("continue", json!({ "threadId": THREAD_ID })),
+                (
+                    "evaluate",
+                    json!({ "expression": "result + 2", "frameId": 0 }),
+                ),
+                (
+                    "evaluate",
+                    json!({ "expression": "result +", "frameId": 0 }),
+                ),
+                ("disconnect", json!({})),
+            ],
+        );
+
+        let stopped = events(&messages, "stopped");
+        assert_eq!(stopped[0]["body"]["reason"], "entry");
+        assert_eq!(stopped[1]["body"]["reason"], "breakpoint");
+
+        let evaluations: Vec<&Value> = messages
+            .iter()
+            .filter(|m| m["command"] == "evaluate")
+            .collect();
+        assert_eq!(evaluations[0]["body"]["result"], "42");
+        assert_eq!(evaluations[1]["success"], false);
+    }
+
+    #[test]
+    fn test_runtime_error_ends_session_with_failure() {
+        let file = program("let items = [1, 2];\nlet x = items[5];\n");
+        let messages = session(
+            server(),
+            &[
+                launch(&file),
+                ("configurationDone", json!({})),
+                ("continue", json!({ "threadId": THREAD_ID })),
+                ("disconnect", json!({})),
+            ],
+        );
+
+        let stderr = events(&messages, "output");
+        assert_eq!(stderr[0]["body"]["category"], "stderr");
+        assert_eq!(events(&messages, "exited")[0]["body"]["exitCode"], 1);
+        assert_eq!(response(&messages, "continue")["success"], false);
+    }
+
+    #[test]
+    fn test_exception_breakpoint_pauses_on_error() {
+        let file = program("let items = [1, 2];\nlet x = items[5];\n");
+        let messages = session(
+            server(),
+            &[
+                launch(&file),
+                (
+                    "setExceptionBreakpoints",
+                    json!({ "filters": [ERROR_FILTER] }),
+                ),
+                ("configurationDone", json!({})),
+                ("stackTrace", json!({ "threadId": THREAD_ID })),
+                ("continue", json!({ "threadId": THREAD_ID })),
+                ("disconnect", json!({})),
+            ],
+        );
+
+        let stopped = events(&messages, "stopped");
+        assert_eq!(stopped[0]["body"]["reason"], "exception");
+        let frames = &response(&messages, "stackTrace")["body"]["stackFrames"];
+        assert_eq!(frames[0]["line"], 2);
+        // Resuming delivers the error
+        assert_eq!(events(&messages, "exited")[0]["body"]["exitCode"], 1);
+    }
+
+    #[test]
+    fn test_attach_debugs_preloaded_program() {
+        let file = program(PROGRAM);
+        let messages = session(
+            server().with_program(path(&file)),
+            &[
+                ("attach", json!({})),
+                ("configurationDone", json!({})),
+                ("disconnect", json!({})),
+            ],
+        );
+        assert_eq!(response(&messages, "attach")["success"], true);
+        assert_eq!(events(&messages, "exited")[0]["body"]["exitCode"], 0);
+
+        let messages = session(server(), &[("attach", json!({}))]);
+        assert_eq!(response(&messages, "attach")["success"], false);
+    }
+
+    #[test]
+    fn test_launch_reports_compile_errors() {
+        let file = program("let x = ;\n");
+        let messages = session(server(), &[launch(&file), ("disconnect", json!({}))]);
+        let launch = response(&messages, "launch");
+        assert_eq!(launch["success"], false);
+        assert!(launch["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Failed to compile"));
+        assert!(events(&messages, "initialized").is_empty());
+    }
+
+    #[test]
+    fn test_pause_is_refused() {
+        let file = program(PROGRAM);
+        let messages = session(
+            server(),
+            &[
+                ("initialize", json!({})),
+                launch(&file),
+                breakpoints(&file, &[3]),
+                ("configurationDone", json!({})),
+                ("pause", json!({ "threadId": THREAD_ID })),
+                ("disconnect", json!({})),
+            ],
+        );
+
+        let pause = response(&messages, "pause");
+        assert_eq!(pause["success"], false);
+        assert!(pause["message"].as_str().unwrap().contains("not supported"));
+        // Only the breakpoint stopped the program
+        assert_eq!(events(&messages, "stopped").len(), 1);
+    }
+
+    #[test]
+    fn test_unknown_request_fails() {
+        let messages = session(server(), &[("readMemory", json!({}))]);
+        assert_eq!(response(&messages, "readMemory")["success"], false);
+    }
+}
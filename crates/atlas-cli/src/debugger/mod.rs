@@ -1,5 +1,7 @@
 //! Debugger CLI infrastructure
 //!
-//! Provides interactive debugging REPL for Atlas programs.
+//! Provides the interactive debugging REPL and the Debug Adapter Protocol
+//! server for Atlas programs.
 
+pub mod dap;
 pub mod repl;
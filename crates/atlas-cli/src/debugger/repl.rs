@@ -4,6 +4,7 @@
 
 use atlas_runtime::debugger::{
     BreakpointId, DebugRequest, DebugResponse, DebuggerSession, PauseReason, SourceLocation,
+    WatchTarget,
 };
 use atlas_runtime::SecurityContext;
 use rustyline::error::ReadlineError;
@@ -120,8 +121,12 @@ impl DebugRepl {
             "out" | "finish" => self.cmd_out(),
             "break" | "b" => self.cmd_break(args),
             "delete" | "d" | "clear" => self.cmd_delete(args),
+            "catch" => self.cmd_catch(args),
             "list" | "l" => self.cmd_list(args),
             "breakpoints" | "bp" => self.cmd_breakpoints(),
+            "watch" | "w" => self.cmd_watch(args),
+            "unwatch" => self.cmd_unwatch(args),
+            "watchpoints" | "wp" => self.cmd_watchpoints(),
             "vars" | "v" | "locals" => self.cmd_vars(args),
             "print" | "p" | "inspect" => self.cmd_print(args),
             "backtrace" | "bt" | "where" => self.cmd_backtrace(),
@@ -152,6 +157,11 @@ impl DebugRepl {
         println!("    delete <id>, d      Delete breakpoint by ID");
         println!("    delete all          Delete all breakpoints");
         println!("    breakpoints, bp     List all breakpoints");
+        println!("    catch [<kind>...]   Pause when a runtime error is raised");
+        println!("    catch off           Stop pausing on runtime errors");
+        println!("    watch <var>, w      Pause when a variable changes");
+        println!("    unwatch <id>        Delete watchpoint by ID");
+        println!("    watchpoints, wp     List all watchpoints");
         println!();
         println!("  \x1b[1;33mInspection:\x1b[0m");
         println!("    vars, v, locals     Show local variables");
@@ -280,6 +290,29 @@ impl DebugRepl {
         }
     }
 
+    fn cmd_catch(&mut self, args: &[&str]) {
+        let enabled = args.first() != Some(&"off");
+        let kinds: Vec<String> = if enabled {
+            args.iter().map(|k| k.to_string()).collect()
+        } else {
+            Vec::new()
+        };
+
+        let response = self
+            .session
+            .process_request(DebugRequest::SetBreakOnError { enabled, kinds });
+
+        if let DebugResponse::BreakOnErrorSet { enabled, kinds } = response {
+            if !enabled {
+                println!("Break on error disabled.");
+            } else if kinds.is_empty() {
+                println!("Breaking on all runtime errors.");
+            } else {
+                println!("Breaking on runtime errors: {}", kinds.join(", "));
+            }
+        }
+    }
+
     fn cmd_breakpoints(&mut self) {
         let response = self.session.process_request(DebugRequest::ListBreakpoints);
 
@@ -318,6 +351,86 @@ impl DebugRepl {
         }
     }
 
+    fn cmd_watch(&mut self, args: &[&str]) {
+        let Some(name) = args.first() else {
+            println!("Usage: watch <variable>");
+            return;
+        };
+
+        // A local of the current frame shadows a global of the same name
+        let local = WatchTarget::Local {
+            frame_index: 0,
+            name: name.to_string(),
+        };
+        let response = match self
+            .session
+            .process_request(DebugRequest::SetWatchpoint { target: local })
+        {
+            DebugResponse::Error { .. } => {
+                let global = WatchTarget::Global {
+                    name: name.to_string(),
+                };
+                self.session
+                    .process_request(DebugRequest::SetWatchpoint { target: global })
+            }
+            response => response,
+        };
+
+        match response {
+            DebugResponse::WatchpointSet { watchpoint } => {
+                println!("Watchpoint {} set on {}", watchpoint.id, watchpoint.target);
+            }
+            DebugResponse::Error { message } => {
+                println!("\x1b[31mError:\x1b[0m {}", message);
+            }
+            _ => {}
+        }
+    }
+
+    fn cmd_unwatch(&mut self, args: &[&str]) {
+        let Some(arg) = args.first() else {
+            println!("Usage: unwatch <id>");
+            return;
+        };
+
+        let Ok(id) = arg.parse::<BreakpointId>() else {
+            println!("Invalid watchpoint ID: '{}'", arg);
+            return;
+        };
+
+        match self
+            .session
+            .process_request(DebugRequest::RemoveWatchpoint { id })
+        {
+            DebugResponse::WatchpointRemoved { id } => println!("Watchpoint {} deleted.", id),
+            DebugResponse::Error { message } => {
+                println!("\x1b[31mError:\x1b[0m {}", message);
+            }
+            _ => {}
+        }
+    }
+
+    fn cmd_watchpoints(&mut self) {
+        match self.session.process_request(DebugRequest::ListWatchpoints) {
+            DebugResponse::Watchpoints { watchpoints } => {
+                if watchpoints.is_empty() {
+                    println!("No watchpoints set.");
+                    return;
+                }
+
+                println!();
+                println!("\x1b[1mWatchpoints:\x1b[0m");
+                println!("{:<4} Variable", "ID");
+                println!("{}", "-".repeat(40));
+                for wp in &watchpoints {
+                    println!("{:<4} {}", wp.id, wp.target);
+                }
+                println!();
+            }
+            _ => println!("Failed to list watchpoints."),
+        }
+    }
+
     fn cmd_list(&mut self, args: &[&str]) {
         let center_line = if args.is_empty() {
             // Use current location
@@ -441,10 +554,20 @@ impl DebugRepl {
                     PauseReason::Breakpoint { id } => {
                         format!("Breakpoint {} hit", id)
                     }
+                    PauseReason::Watchpoint {
+                        id,
+                        old_value,
+                        new_value,
+                    } => format!(
+                        "Watchpoint {} hit: {} -> {}",
+                        id,
+                        old_value.as_deref().unwrap_or("<unset>"),
+                        new_value
+                    ),
                     PauseReason::Step => "Stepped".to_string(),
                     PauseReason::ManualPause => "Paused".to_string(),
-                    PauseReason::Exception { message } => {
-                        format!("Exception: {}", message)
+                    PauseReason::Exception { message, kind } => {
+                        format!("Exception ({}): {}", kind, message)
                     }
                 };
 
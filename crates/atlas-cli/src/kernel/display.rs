@@ -0,0 +1,393 @@
+//! Rich display of cell results
+//!
+//! A result is sent to the frontend as a MIME bundle, and the frontend shows
+//! the richest type it understands:
+//!
+//! - Arrays of maps, and JSON arrays of objects, become tables with one
+//!   column per key (`text/html`, plus an aligned `text/plain` version).
+//!   A single map becomes a key/value table.
+//! - JSON objects and arrays get `application/json` (an explorable tree in
+//!   JupyterLab) and pretty-printed `text/plain`.
+//! - Everything else is its `inspect()` rendering as `text/plain`.
+
+use atlas_runtime::stdlib::collections::hash::HashKey;
+use atlas_runtime::{JsonValue, PrettyConfig, Value};
+use serde_json::{Map, Value as Json};
+use std::collections::BTreeSet;
+
+/// Rows rendered before a table is cut short
+const MAX_TABLE_ROWS: usize = 500;
+
+/// MIME bundle (`data` of an `execute_result`) for `value`
+pub fn mime_bundle(value: &Value) -> Map<String, Json> {
+    let mut bundle = Map::new();
+    let mut plain = value.pretty(&PrettyConfig::default());
+
+    if let Value::JsonValue(json) = value {
+        let json = json_to_serde(json);
+        if json.is_object() || json.is_array() {
+            plain = serde_json::to_string_pretty(&json).expect("JSON values always serialize");
+            if let Some(table) = json_table(&json) {
+                bundle.insert("text/html".into(), Json::String(table.to_html()));
+            }
+            bundle.insert("application/json".into(), json);
+        }
+    } else if let Some(table) = value_table(value) {
+        plain = table.to_text();
+        bundle.insert("text/html".into(), Json::String(table.to_html()));
+    }
+
+    bundle.insert("text/plain".into(), Json::String(plain));
+    bundle
+}
+
+/// Table cells, already formatted as text
+#[derive(Debug)]
+struct Table {
+    columns: Vec<String>,
+    rows: Vec<Vec<String>>,
+    /// Row count before truncation to `MAX_TABLE_ROWS`
+    total_rows: usize,
+}
+
+impl Table {
+    /// Build a table from rows of `(column, cell)` pairs; columns are the
+    /// sorted union of every row's keys, and missing cells are left blank
+    fn from_records(records: Vec<Vec<(String, String)>>, total_rows: usize) -> Self {
+        let columns: Vec<String> = records
+            .iter()
+            .flatten()
+            .map(|(key, _)| key.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let rows = records
+            .into_iter()
+            .map(|record| {
+                columns
+                    .iter()
+                    .map(|column| {
+                        record
+                            .iter()
+                            .find(|(key, _)| key == column)
+                            .map(|(_, cell)| cell.clone())
+                            .unwrap_or_default()
+                    })
+                    .collect()
+            })
+            .collect();
+        Table {
+            columns,
+            rows,
+            total_rows,
+        }
+    }
+
+    fn truncation_note(&self) -> Option<String> {
+        (self.total_rows > self.rows.len())
+            .then(|| format!("{} of {} rows shown", self.rows.len(), self.total_rows))
+    }
+
+    fn to_html(&self) -> String {
+        let mut html = String::from("<table>\n<thead><tr>");
+        for column in &self.columns {
+            html.push_str("<th>");
+            push_escaped(&mut html, column);
+            html.push_str("</th>");
+        }
+        html.push_str("</tr></thead>\n<tbody>\n");
+        for row in &self.rows {
+            html.push_str("<tr>");
+            for cell in row {
+                html.push_str("<td>");
+                push_escaped(&mut html, cell);
+                html.push_str("</td>");
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</tbody>\n</table>");
+        if let Some(note) = self.truncation_note() {
+            html.push_str("\n<p>");
+            html.push_str(&note);
+            html.push_str("</p>");
+        }
+        html
+    }
+
+    fn to_text(&self) -> String {
+        let widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                self.rows
+                    .iter()
+                    .map(|row| row[i].chars().count())
+                    .chain(std::iter::once(column.chars().count()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let line = |cells: &[String]| {
+            cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join(" | ")
+                .trim_end()
+                .to_string()
+        };
+
+        let mut lines = vec![line(&self.columns)];
+        lines.push(
+            widths
+                .iter()
+                .map(|&width| "-".repeat(width))
+                .collect::<Vec<_>>()
+                .join("-+-"),
+        );
+        lines.extend(self.rows.iter().map(|row| line(row)));
+        lines.extend(self.truncation_note());
+        lines.join("\n")
+    }
+}
+
+fn value_table(value: &Value) -> Option<Table> {
+    match value {
+        Value::Array(items) => {
+            if items.is_empty() || !items.iter().all(|item| matches!(item, Value::Map(_))) {
+                return None;
+            }
+            let records = items
+                .iter()
+                .take(MAX_TABLE_ROWS)
+                .filter_map(|item| match item {
+                    Value::Map(map) => Some(
+                        map.entries()
+                            .into_iter()
+                            .map(|(key, cell)| (key_label(&key), cell.to_string()))
+                            .collect(),
+                    ),
+                    _ => None,
+                })
+                .collect();
+            Some(Table::from_records(records, items.len()))
+        }
+        Value::Map(map) if !map.is_empty() => {
+            let mut entries: Vec<(String, String)> = map
+                .entries()
+                .into_iter()
+                .map(|(key, cell)| (key_label(&key), cell.to_string()))
+                .collect();
+            entries.sort();
+            let total_rows = entries.len();
+            entries.truncate(MAX_TABLE_ROWS);
+            Some(Table {
+                columns: vec!["key".to_string(), "value".to_string()],
+                rows: entries.into_iter().map(|(k, v)| vec![k, v]).collect(),
+                total_rows,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn json_table(json: &Json) -> Option<Table> {
+    let items = json.as_array()?;
+    if items.is_empty() || !items.iter().all(Json::is_object) {
+        return None;
+    }
+    let records = items
+        .iter()
+        .take(MAX_TABLE_ROWS)
+        .filter_map(Json::as_object)
+        .map(|object| {
+            object
+                .iter()
+                .map(|(key, cell)| {
+                    let cell = match cell {
+                        Json::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    (key.clone(), cell)
+                })
+                .collect()
+        })
+        .collect();
+    Some(Table::from_records(records, items.len()))
+}
+
+fn key_label(key: &HashKey) -> String {
+    match key {
+        HashKey::String(s) => s.to_string(),
+        other => other.to_value().to_string(),
+    }
+}
+
+fn json_to_serde(json: &JsonValue) -> Json {
+    match json {
+        JsonValue::Null => Json::Null,
+        JsonValue::Bool(b) => Json::Bool(*b),
+        // Keep integral numbers integral so `1` doesn't display as `1.0`
+        JsonValue::Number(n) if n.fract() == 0.0 && n.abs() < 9_007_199_254_740_992.0 => {
+            Json::from(*n as i64)
+        }
+        JsonValue::Number(n) => serde_json::Number::from_f64(*n)
+            .map(Json::Number)
+            .unwrap_or(Json::Null),
+        JsonValue::String(s) => Json::String(s.clone()),
+        JsonValue::Array(items) => Json::Array(items.iter().map(json_to_serde).collect()),
+        JsonValue::Object(entries) => Json::Object(
+            entries
+                .iter()
+                .map(|(key, value)| (key.clone(), json_to_serde(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn push_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atlas_runtime::value::ValueHashMap;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn string(s: &str) -> Value {
+        Value::String(Arc::new(s.to_string()))
+    }
+
+    fn map(entries: &[(&str, Value)]) -> Value {
+        let mut map = ValueHashMap::new();
+        for (key, value) in entries {
+            map.insert(HashKey::String(Arc::new(key.to_string())), value.clone());
+        }
+        Value::Map(map)
+    }
+
+    fn json(value: Json) -> Value {
+        fn convert(value: Json) -> JsonValue {
+            match value {
+                Json::Null => JsonValue::Null,
+                Json::Bool(b) => JsonValue::Bool(b),
+                Json::Number(n) => JsonValue::Number(n.as_f64().unwrap()),
+                Json::String(s) => JsonValue::String(s),
+                Json::Array(items) => JsonValue::Array(items.into_iter().map(convert).collect()),
+                Json::Object(entries) => JsonValue::Object(
+                    entries
+                        .into_iter()
+                        .map(|(k, v)| (k, convert(v)))
+                        .collect::<HashMap<_, _>>(),
+                ),
+            }
+        }
+        Value::JsonValue(Arc::new(convert(value)))
+    }
+
+    #[test]
+    fn test_scalar_is_plain_text_only() {
+        let bundle = mime_bundle(&Value::Number(3.0));
+        assert_eq!(bundle.len(), 1);
+        assert_eq!(bundle["text/plain"], "3");
+    }
+
+    #[test]
+    fn test_array_of_maps_renders_table() {
+        let rows = Value::Array(
+            vec![
+                map(&[("name", string("Ada")), ("age", Value::Number(36.0))]),
+                map(&[("name", string("<Bob>")), ("city", string("Paris"))]),
+            ]
+            .into(),
+        );
+        let bundle = mime_bundle(&rows);
+
+        let html = bundle["text/html"].as_str().unwrap();
+        assert!(html.contains("<th>age</th><th>city</th><th>name</th>"));
+        assert!(html.contains("<tr><td>36</td><td></td><td>Ada</td></tr>"));
+        assert!(html.contains("<td>&lt;Bob&gt;</td>"));
+
+        assert_eq!(
+            bundle["text/plain"],
+            "age | city  | name\n----+-------+------\n36  |       | Ada\n    | Paris | <Bob>"
+        );
+    }
+
+    #[test]
+    fn test_mixed_array_is_not_a_table() {
+        let mixed = Value::Array(vec![map(&[("a", Value::Null)]), Value::Number(1.0)].into());
+        assert!(!mime_bundle(&mixed).contains_key("text/html"));
+    }
+
+    #[test]
+    fn test_map_renders_key_value_table() {
+        let bundle = mime_bundle(&map(&[("b", Value::Bool(true)), ("a", Value::Number(1.0))]));
+        assert_eq!(
+            bundle["text/plain"],
+            "key | value\n----+------\na   | 1\nb   | true"
+        );
+    }
+
+    #[test]
+    fn test_long_table_is_truncated() {
+        let rows: Vec<Value> = (0..MAX_TABLE_ROWS + 5)
+            .map(|i| map(&[("i", Value::Number(i as f64))]))
+            .collect();
+        let html = mime_bundle(&Value::Array(rows.into()))["text/html"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(html.matches("<tr><td>").count(), MAX_TABLE_ROWS);
+        assert!(html.ends_with("<p>500 of 505 rows shown</p>"));
+    }
+
+    #[test]
+    fn test_json_object_is_pretty_and_structured() {
+        let value = json(serde_json::json!({ "id": 1, "tags": ["x"] }));
+        let bundle = mime_bundle(&value);
+        assert_eq!(
+            bundle["application/json"],
+            serde_json::json!({ "id": 1, "tags": ["x"] })
+        );
+        assert_eq!(
+            bundle["text/plain"],
+            "{\n  \"id\": 1,\n  \"tags\": [\n    \"x\"\n  ]\n}"
+        );
+        assert!(!bundle.contains_key("text/html"));
+    }
+
+    #[test]
+    fn test_json_array_of_objects_renders_table() {
+        let value = json(serde_json::json!([
+            { "id": 1, "name": "a" },
+            { "id": 2.5, "name": "b", "extra": { "k": null } }
+        ]));
+        let html = mime_bundle(&value)["text/html"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert!(html.contains("<th>extra</th><th>id</th><th>name</th>"));
+        assert!(html.contains("<tr><td></td><td>1</td><td>a</td></tr>"));
+        assert!(html.contains("<td>{&quot;k&quot;:null}</td><td>2.5</td>"));
+    }
+
+    #[test]
+    fn test_json_scalar_is_plain_text_only() {
+        let bundle = mime_bundle(&json(serde_json::json!("hi")));
+        assert_eq!(bundle.len(), 1);
+    }
+}
@@ -0,0 +1,542 @@
+//! Jupyter kernel
+//!
+//! Speaks the Jupyter messaging protocol on top of [`ReplCore`], so a
+//! notebook keeps its variables and functions from cell to cell exactly like
+//! the REPL does. Jupyter starts one `atlas kernel --connection-file <file>`
+//! per notebook; the connection file names the five sockets to bind and the
+//! key used to sign messages.
+//!
+//! Cells run one at a time on the main thread. Requests from the shell and
+//! control sockets share one queue, so a shutdown request waits for the
+//! running cell. Input requests (`stdin`) are not supported.
+
+pub mod display;
+pub mod wire;
+pub mod zmtp;
+
+use anyhow::{anyhow, bail, Context, Result};
+use atlas_runtime::{Diagnostic, InputCompleteness, ReplCore};
+use serde_json::{json, Value as Json};
+use std::path::Path;
+use std::sync::mpsc;
+use wire::{Message, Session, Signer, PROTOCOL_VERSION};
+use zmtp::{Publisher, Router};
+
+/// Sockets and signing settings from a Jupyter connection file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionInfo {
+    pub ip: String,
+    pub shell_port: u16,
+    pub control_port: u16,
+    pub stdin_port: u16,
+    pub iopub_port: u16,
+    pub hb_port: u16,
+    pub signature_scheme: String,
+    pub key: String,
+}
+
+impl ConnectionInfo {
+    pub fn parse(text: &str) -> Result<Self> {
+        let json: Json = serde_json::from_str(text).context("invalid connection file")?;
+        let transport = json["transport"].as_str().unwrap_or("tcp");
+        if transport != "tcp" {
+            bail!(
+                "unsupported transport '{}' (only tcp is supported)",
+                transport
+            );
+        }
+        let port = |name: &str| {
+            json[name]
+                .as_u64()
+                .and_then(|port| u16::try_from(port).ok())
+                .ok_or_else(|| anyhow!("connection file has no valid '{}'", name))
+        };
+        let text_field =
+            |name: &str, default: &str| json[name].as_str().unwrap_or(default).to_string();
+        Ok(ConnectionInfo {
+            ip: text_field("ip", "127.0.0.1"),
+            shell_port: port("shell_port")?,
+            control_port: port("control_port")?,
+            stdin_port: port("stdin_port")?,
+            iopub_port: port("iopub_port")?,
+            hb_port: port("hb_port")?,
+            signature_scheme: text_field("signature_scheme", "hmac-sha256"),
+            key: text_field("key", ""),
+        })
+    }
+
+    fn address(&self, port: u16) -> String {
+        if self.ip.contains(':') {
+            format!("[{}]:{}", self.ip, port)
+        } else {
+            format!("{}:{}", self.ip, port)
+        }
+    }
+}
+
+/// Socket a request arrived on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Channel {
+    Shell,
+    Control,
+    Stdin,
+}
+
+/// Whether the kernel keeps serving after a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flow {
+    Continue,
+    Shutdown,
+}
+
+/// Bind the sockets in `connection_file` and serve until shutdown
+pub fn run(connection_file: &Path, verbose: bool) -> Result<()> {
+    let text = std::fs::read_to_string(connection_file)
+        .with_context(|| format!("cannot read {}", connection_file.display()))?;
+    let info = ConnectionInfo::parse(&text)?;
+    let signer = Signer::new(&info.signature_scheme, &info.key)?;
+
+    let bind_error =
+        |name: &str, port: u16| format!("cannot bind {} socket on port {}", name, port);
+    let (inbox, requests) = mpsc::channel();
+    let shell = Router::bind(
+        &info.address(info.shell_port),
+        Channel::Shell,
+        inbox.clone(),
+    )
+    .with_context(|| bind_error("shell", info.shell_port))?;
+    let control = Router::bind(
+        &info.address(info.control_port),
+        Channel::Control,
+        inbox.clone(),
+    )
+    .with_context(|| bind_error("control", info.control_port))?;
+    Router::bind(&info.address(info.stdin_port), Channel::Stdin, inbox)
+        .with_context(|| bind_error("stdin", info.stdin_port))?;
+    let iopub = Publisher::bind(&info.address(info.iopub_port))
+        .with_context(|| bind_error("iopub", info.iopub_port))?;
+    zmtp::serve_heartbeat(&info.address(info.hb_port))
+        .with_context(|| bind_error("heartbeat", info.hb_port))?;
+
+    if verbose {
+        eprintln!("Atlas kernel listening on {}", info.ip);
+    }
+
+    let publish_signer = signer.clone();
+    let mut kernel = Kernel::new(Box::new(move |message: Message| {
+        iopub.send(&message.encode(&publish_signer));
+    }));
+
+    for inbound in requests {
+        let socket = match inbound.label {
+            Channel::Shell => &shell,
+            Channel::Control => &control,
+            Channel::Stdin => continue,
+        };
+        let request = match Message::decode(inbound.frames, &signer) {
+            Ok(request) => request,
+            Err(e) => {
+                if verbose {
+                    eprintln!("Dropped message: {}", e);
+                }
+                continue;
+            }
+        };
+        if verbose {
+            eprintln!("{:?} {}", inbound.label, request.msg_type());
+        }
+        let flow = kernel.handle(&request, &mut |reply| {
+            let _ = socket.send(inbound.peer, &reply.encode(&signer));
+        });
+        if flow == Flow::Shutdown {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Request handling, independent of the sockets
+struct Kernel {
+    repl: ReplCore,
+    session: Session,
+    /// Sends a message on iopub
+    publish: Box<dyn FnMut(Message)>,
+    execution_count: u64,
+}
+
+impl Kernel {
+    fn new(publish: Box<dyn FnMut(Message)>) -> Self {
+        Kernel {
+            repl: ReplCore::new(),
+            session: Session::start(),
+            publish,
+            execution_count: 0,
+        }
+    }
+
+    fn broadcast(&mut self, parent: &Message, msg_type: &str, content: Json) {
+        let message = self.session.broadcast(parent, msg_type, content);
+        (self.publish)(message);
+    }
+
+    /// Handle one request: busy status, the work, the reply, idle status
+    fn handle(&mut self, request: &Message, reply: &mut dyn FnMut(Message)) -> Flow {
+        self.broadcast(request, "status", json!({ "execution_state": "busy" }));
+
+        let mut flow = Flow::Continue;
+        let content = match request.msg_type() {
+            "kernel_info_request" => Some(kernel_info()),
+            "execute_request" => Some(self.execute(request)),
+            "is_complete_request" => Some(is_complete(&request.content)),
+            "complete_request" => Some(self.complete(&request.content)),
+            "inspect_request" => Some(json!({
+                "status": "ok",
+                "found": false,
+                "data": {},
+                "metadata": {},
+            })),
+            "history_request" => Some(json!({ "status": "ok", "history": [] })),
+            "comm_info_request" => Some(json!({ "status": "ok", "comms": {} })),
+            "shutdown_request" => {
+                flow = Flow::Shutdown;
+                Some(json!({ "status": "ok", "restart": request.content["restart"] }))
+            }
+            _ => None,
+        };
+        if let Some(content) = content {
+            let reply_type = request.msg_type().replace("_request", "_reply");
+            reply(self.session.reply(request, &reply_type, content));
+        }
+
+        self.broadcast(request, "status", json!({ "execution_state": "idle" }));
+        flow
+    }
+
+    fn execute(&mut self, request: &Message) -> Json {
+        let code = request.content["code"].as_str().unwrap_or("");
+        let silent = request.content["silent"].as_bool().unwrap_or(false);
+        let store_history = request.content["store_history"]
+            .as_bool()
+            .unwrap_or(!silent);
+        if store_history {
+            self.execution_count += 1;
+        }
+        let execution_count = self.execution_count;
+        if !silent {
+            self.broadcast(
+                request,
+                "execute_input",
+                json!({ "code": code, "execution_count": execution_count }),
+            );
+        }
+
+        let result = self.repl.eval_line(code);
+        if !silent && !result.stdout.is_empty() {
+            self.broadcast(
+                request,
+                "stream",
+                json!({ "name": "stdout", "text": result.stdout }),
+            );
+        }
+
+        let (errors, warnings): (Vec<&Diagnostic>, Vec<&Diagnostic>) =
+            result.diagnostics.iter().partition(|d| d.is_error());
+        if !silent && !warnings.is_empty() {
+            let text: String = warnings
+                .iter()
+                .map(|d| format_diagnostic(d, code))
+                .collect();
+            self.broadcast(request, "stream", json!({ "name": "stderr", "text": text }));
+        }
+        if let Some(first) = errors.first() {
+            let error = json!({
+                "ename": first.code,
+                "evalue": first.message,
+                "traceback": errors.iter().map(|d| format_diagnostic(d, code)).collect::<Vec<_>>(),
+            });
+            if !silent {
+                self.broadcast(request, "error", error.clone());
+            }
+            let mut reply = error;
+            reply["status"] = json!("error");
+            reply["execution_count"] = json!(execution_count);
+            return reply;
+        }
+
+        match result.value {
+            Some(value) if !silent && !matches!(value, atlas_runtime::Value::Null) => {
+                self.broadcast(
+                    request,
+                    "execute_result",
+                    json!({
+                        "execution_count": execution_count,
+                        "data": display::mime_bundle(&value),
+                        "metadata": {},
+                    }),
+                );
+            }
+            _ => {}
+        }
+        json!({
+            "status": "ok",
+            "execution_count": execution_count,
+            "user_expressions": {},
+            "payload": [],
+        })
+    }
+
+    /// Complete the identifier before the cursor from the session's variables
+    fn complete(&self, content: &Json) -> Json {
+        let code = content["code"].as_str().unwrap_or("");
+        let chars: Vec<char> = code.chars().collect();
+        let cursor = content["cursor_pos"]
+            .as_u64()
+            .map_or(chars.len(), |pos| (pos as usize).min(chars.len()));
+        let start = chars[..cursor]
+            .iter()
+            .rposition(|c| !(c.is_alphanumeric() || *c == '_'))
+            .map_or(0, |i| i + 1);
+        let prefix: String = chars[start..cursor].iter().collect();
+
+        let matches: Vec<String> = self
+            .repl
+            .variables()
+            .into_iter()
+            .map(|binding| binding.name)
+            .filter(|name| name.starts_with(&prefix))
+            .collect();
+        json!({
+            "status": "ok",
+            "matches": matches,
+            "cursor_start": start,
+            "cursor_end": cursor,
+            "metadata": {},
+        })
+    }
+}
+
+fn kernel_info() -> Json {
+    json!({
+        "status": "ok",
+        "protocol_version": PROTOCOL_VERSION,
+        "implementation": "atlas",
+        "implementation_version": atlas_runtime::VERSION,
+        "language_info": {
+            "name": "atlas",
+            "version": atlas_runtime::VERSION,
+            "mimetype": "text/x-atlas",
+            "file_extension": ".atl",
+        },
+        "banner": format!("Atlas v{}", atlas_runtime::VERSION),
+        "help_links": [],
+    })
+}
+
+fn is_complete(content: &Json) -> Json {
+    let code = content["code"].as_str().unwrap_or("");
+    match atlas_runtime::is_input_complete(code) {
+        InputCompleteness::Complete => json!({ "status": "complete" }),
+        InputCompleteness::Incomplete { .. } => json!({ "status": "incomplete", "indent": "    " }),
+    }
+}
+
+fn format_diagnostic(diagnostic: &Diagnostic, code: &str) -> String {
+    crate::diagnostics::format_diagnostic_plain(diagnostic, Some(code), Some("<cell>"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// A kernel whose iopub messages are collected for inspection
+    fn kernel() -> (Kernel, Rc<RefCell<Vec<Message>>>) {
+        let published = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&published);
+        let kernel = Kernel::new(Box::new(move |message| sink.borrow_mut().push(message)));
+        (kernel, published)
+    }
+
+    fn request(msg_type: &str, content: Json) -> Message {
+        Message {
+            identities: vec![b"frontend".to_vec()],
+            header: json!({ "msg_id": "req", "msg_type": msg_type }),
+            parent_header: json!({}),
+            metadata: json!({}),
+            content,
+        }
+    }
+
+    /// Handle `request`, expecting exactly one reply
+    fn handle(kernel: &mut Kernel, request: &Message) -> (Message, Flow) {
+        let mut replies = Vec::new();
+        let flow = kernel.handle(request, &mut |reply| replies.push(reply));
+        assert_eq!(replies.len(), 1, "expected exactly one reply");
+        (replies.remove(0), flow)
+    }
+
+    fn execute(code: &str) -> Json {
+        json!({ "code": code, "silent": false, "store_history": true })
+    }
+
+    #[test]
+    fn test_parse_connection_file() {
+        let info = ConnectionInfo::parse(
+            r#"{
+                "shell_port": 50001, "iopub_port": 50002, "stdin_port": 50003,
+                "control_port": 50004, "hb_port": 50005, "ip": "127.0.0.1",
+                "key": "a0436f6c", "transport": "tcp",
+                "signature_scheme": "hmac-sha256", "kernel_name": "atlas"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(info.shell_port, 50001);
+        assert_eq!(info.hb_port, 50005);
+        assert_eq!(info.key, "a0436f6c");
+        assert_eq!(info.address(info.iopub_port), "127.0.0.1:50002");
+    }
+
+    #[test]
+    fn test_parse_connection_file_rejects_ipc_and_missing_ports() {
+        let ipc = ConnectionInfo::parse(r#"{ "transport": "ipc", "shell_port": 1 }"#);
+        assert!(ipc.unwrap_err().to_string().contains("transport"));
+        let missing = ConnectionInfo::parse(r#"{ "shell_port": 1 }"#);
+        assert!(missing.unwrap_err().to_string().contains("control_port"));
+    }
+
+    #[test]
+    fn test_kernel_info_reply_is_bracketed_by_status() {
+        let (mut kernel, published) = kernel();
+        let (reply, flow) = handle(&mut kernel, &request("kernel_info_request", json!({})));
+        assert_eq!(flow, Flow::Continue);
+        assert_eq!(reply.msg_type(), "kernel_info_reply");
+        assert_eq!(reply.content["language_info"]["name"], "atlas");
+        assert_eq!(reply.parent_header["msg_id"], "req");
+
+        let published = published.borrow();
+        let states: Vec<&Json> = published
+            .iter()
+            .map(|m| &m.content["execution_state"])
+            .collect();
+        assert_eq!(states, [&json!("busy"), &json!("idle")]);
+    }
+
+    #[test]
+    fn test_execute_publishes_output_and_result() {
+        let (mut kernel, published) = kernel();
+        let (reply, _) = handle(
+            &mut kernel,
+            &request("execute_request", execute("console.log(\"hi\");\n1 + 2;")),
+        );
+        assert_eq!(reply.content["status"], "ok");
+        assert_eq!(reply.content["execution_count"], 1);
+
+        let published = published.borrow();
+        let types: Vec<&str> = published.iter().map(Message::msg_type).collect();
+        assert_eq!(
+            types,
+            [
+                "status",
+                "execute_input",
+                "stream",
+                "execute_result",
+                "status"
+            ]
+        );
+        assert_eq!(published[2].content["text"], "hi\n");
+        assert_eq!(published[3].content["data"]["text/plain"], "3");
+        assert_eq!(published[3].content["execution_count"], 1);
+    }
+
+    #[test]
+    fn test_state_persists_across_cells() {
+        let (mut kernel, published) = kernel();
+        handle(
+            &mut kernel,
+            &request("execute_request", execute("let x = 20;")),
+        );
+        let (reply, _) = handle(&mut kernel, &request("execute_request", execute("x * 2;")));
+        assert_eq!(reply.content["execution_count"], 2);
+        let published = published.borrow();
+        let result = published
+            .iter()
+            .rfind(|m| m.msg_type() == "execute_result")
+            .unwrap();
+        assert_eq!(result.content["data"]["text/plain"], "40");
+    }
+
+    #[test]
+    fn test_execute_error_reply() {
+        let (mut kernel, published) = kernel();
+        let (reply, _) = handle(
+            &mut kernel,
+            &request("execute_request", execute("undefined_name + 1")),
+        );
+        assert_eq!(reply.content["status"], "error");
+        assert!(reply.content["ename"].as_str().unwrap().starts_with("AT"));
+        assert!(!reply.content["traceback"].as_array().unwrap().is_empty());
+        assert!(published.borrow().iter().any(|m| m.msg_type() == "error"));
+    }
+
+    #[test]
+    fn test_silent_execute_publishes_only_status() {
+        let (mut kernel, published) = kernel();
+        let (reply, _) = handle(
+            &mut kernel,
+            &request(
+                "execute_request",
+                json!({ "code": "1 + 1", "silent": true, "store_history": false }),
+            ),
+        );
+        assert_eq!(reply.content["execution_count"], 0);
+        let published = published.borrow();
+        assert!(published.iter().all(|m| m.msg_type() == "status"));
+    }
+
+    #[test]
+    fn test_is_complete() {
+        let (mut kernel, _) = kernel();
+        let (reply, _) = handle(
+            &mut kernel,
+            &request("is_complete_request", json!({ "code": "fn f() {" })),
+        );
+        assert_eq!(reply.content["status"], "incomplete");
+        let (reply, _) = handle(
+            &mut kernel,
+            &request("is_complete_request", json!({ "code": "1 + 1" })),
+        );
+        assert_eq!(reply.content["status"], "complete");
+    }
+
+    #[test]
+    fn test_complete_from_variables() {
+        let (mut kernel, _) = kernel();
+        handle(
+            &mut kernel,
+            &request("execute_request", execute("let total = 1; let tag = 2;")),
+        );
+        let (reply, _) = handle(
+            &mut kernel,
+            &request(
+                "complete_request",
+                json!({ "code": "1 + tot", "cursor_pos": 7 }),
+            ),
+        );
+        assert_eq!(reply.content["matches"], json!(["total"]));
+        assert_eq!(reply.content["cursor_start"], 4);
+        assert_eq!(reply.content["cursor_end"], 7);
+    }
+
+    #[test]
+    fn test_shutdown_request_stops_kernel() {
+        let (mut kernel, _) = kernel();
+        let (reply, flow) = handle(
+            &mut kernel,
+            &request("shutdown_request", json!({ "restart": false })),
+        );
+        assert_eq!(flow, Flow::Shutdown);
+        assert_eq!(reply.msg_type(), "shutdown_reply");
+        assert_eq!(reply.content["restart"], false);
+    }
+}
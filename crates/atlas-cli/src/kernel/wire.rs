@@ -0,0 +1,256 @@
+//! Jupyter messages on the wire
+//!
+//! Every message is a run of ZeroMQ frames:
+//!
+//! ```text
+//! <routing identities...> "<IDS|MSG>" <hmac> <header> <parent_header> <metadata> <content> <buffers...>
+//! ```
+//!
+//! The signature is the hex HMAC of the four JSON frames, keyed by the
+//! connection file's `key`. An empty key disables signing.
+
+use super::zmtp::Frames;
+use anyhow::{anyhow, bail, Result};
+use hmac::{Hmac, Mac};
+use serde_json::{json, Map, Value};
+use sha2::Sha256;
+
+/// Separates routing identities from the message proper
+const DELIMITER: &[u8] = b"<IDS|MSG>";
+
+/// Messaging protocol version implemented by the kernel
+pub const PROTOCOL_VERSION: &str = "5.3";
+
+/// Signs and verifies messages with the connection key
+#[derive(Clone)]
+pub struct Signer {
+    key: Option<Hmac<Sha256>>,
+}
+
+impl Signer {
+    pub fn new(scheme: &str, key: &str) -> Result<Self> {
+        if key.is_empty() {
+            return Ok(Signer { key: None });
+        }
+        if scheme != "hmac-sha256" {
+            bail!("unsupported signature scheme '{}'", scheme);
+        }
+        let mac = Hmac::<Sha256>::new_from_slice(key.as_bytes())
+            .map_err(|e| anyhow!("invalid signing key: {}", e))?;
+        Ok(Signer { key: Some(mac) })
+    }
+
+    fn mac(&self, parts: &[&[u8]]) -> Option<Hmac<Sha256>> {
+        let mut mac = self.key.clone()?;
+        for part in parts {
+            mac.update(part);
+        }
+        Some(mac)
+    }
+
+    /// Hex signature of `parts`, empty when signing is off
+    pub fn sign(&self, parts: &[&[u8]]) -> String {
+        self.mac(parts)
+            .map(|mac| hex::encode(mac.finalize().into_bytes()))
+            .unwrap_or_default()
+    }
+
+    /// Constant-time check of a hex signature
+    pub fn verify(&self, signature: &[u8], parts: &[&[u8]]) -> bool {
+        match self.mac(parts) {
+            None => true,
+            Some(mac) => hex::decode(signature)
+                .map(|expected| mac.verify_slice(&expected).is_ok())
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// A decoded Jupyter message
+#[derive(Debug, Clone)]
+pub struct Message {
+    /// Routing prefix, sent back unchanged on replies
+    pub identities: Vec<Vec<u8>>,
+    pub header: Value,
+    pub parent_header: Value,
+    pub metadata: Value,
+    pub content: Value,
+}
+
+impl Message {
+    pub fn msg_type(&self) -> &str {
+        self.header["msg_type"].as_str().unwrap_or("")
+    }
+
+    pub fn decode(frames: Frames, signer: &Signer) -> Result<Self> {
+        let split = frames
+            .iter()
+            .position(|frame| frame == DELIMITER)
+            .ok_or_else(|| anyhow!("message has no <IDS|MSG> delimiter"))?;
+        let mut frames = frames.into_iter();
+        let identities: Vec<Vec<u8>> = frames.by_ref().take(split).collect();
+        let body: Vec<Vec<u8>> = frames.skip(1).take(5).collect();
+        if body.len() < 5 {
+            bail!("message is missing frames");
+        }
+
+        let parts: Vec<&[u8]> = body[1..5].iter().map(Vec::as_slice).collect();
+        if !signer.verify(&body[0], &parts) {
+            bail!("message signature mismatch");
+        }
+
+        let parse = |frame: &[u8]| -> Result<Value> { Ok(serde_json::from_slice(frame)?) };
+        Ok(Message {
+            identities,
+            header: parse(&body[1])?,
+            parent_header: parse(&body[2])?,
+            metadata: parse(&body[3])?,
+            content: parse(&body[4])?,
+        })
+    }
+
+    pub fn encode(&self, signer: &Signer) -> Frames {
+        let json = [
+            &self.header,
+            &self.parent_header,
+            &self.metadata,
+            &self.content,
+        ]
+        .map(|value| serde_json::to_vec(value).expect("JSON values always serialize"));
+        let parts: Vec<&[u8]> = json.iter().map(Vec::as_slice).collect();
+        let signature = signer.sign(&parts);
+
+        let mut frames = self.identities.clone();
+        frames.push(DELIMITER.to_vec());
+        frames.push(signature.into_bytes());
+        frames.extend(json);
+        frames
+    }
+}
+
+/// Builds headers for outgoing messages
+pub struct Session {
+    id: String,
+    next_msg: u64,
+}
+
+impl Session {
+    pub fn start() -> Self {
+        let started = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        Session {
+            id: format!("{:x}-{:x}", std::process::id(), started.as_nanos()),
+            next_msg: 0,
+        }
+    }
+
+    fn header(&mut self, msg_type: &str) -> Value {
+        self.next_msg += 1;
+        json!({
+            "msg_id": format!("{}-{}", self.id, self.next_msg),
+            "session": self.id,
+            "username": "kernel",
+            "date": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+            "msg_type": msg_type,
+            "version": PROTOCOL_VERSION,
+        })
+    }
+
+    /// A reply to `parent` on the socket it arrived on
+    pub fn reply(&mut self, parent: &Message, msg_type: &str, content: Value) -> Message {
+        Message {
+            identities: parent.identities.clone(),
+            header: self.header(msg_type),
+            parent_header: parent.header.clone(),
+            metadata: Value::Object(Map::new()),
+            content,
+        }
+    }
+
+    /// An iopub broadcast caused by `parent`, published under its type as topic
+    pub fn broadcast(&mut self, parent: &Message, msg_type: &str, content: Value) -> Message {
+        Message {
+            identities: vec![msg_type.as_bytes().to_vec()],
+            header: self.header(msg_type),
+            parent_header: parent.header.clone(),
+            metadata: Value::Object(Map::new()),
+            content,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(msg_type: &str) -> Message {
+        Message {
+            identities: vec![b"client".to_vec()],
+            header: json!({ "msg_id": "1", "msg_type": msg_type }),
+            parent_header: json!({}),
+            metadata: json!({}),
+            content: json!({ "code": "1 + 1" }),
+        }
+    }
+
+    #[test]
+    fn test_signature_matches_reference_hmac() {
+        // HMAC-SHA256("key", "The quick brown fox jumps over the lazy dog")
+        let signer = Signer::new("hmac-sha256", "key").unwrap();
+        assert_eq!(
+            signer.sign(&[
+                b"The quick brown fox ".as_slice(),
+                b"jumps over the lazy dog"
+            ]),
+            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let signer = Signer::new("hmac-sha256", "secret").unwrap();
+        let frames = request("execute_request").encode(&signer);
+        assert_eq!(frames[0], b"client");
+        assert_eq!(frames[1], DELIMITER);
+        assert_eq!(frames[2].len(), 64);
+
+        let decoded = Message::decode(frames, &signer).unwrap();
+        assert_eq!(decoded.identities, vec![b"client".to_vec()]);
+        assert_eq!(decoded.msg_type(), "execute_request");
+        assert_eq!(decoded.content["code"], "1 + 1");
+    }
+
+    #[test]
+    fn test_decode_rejects_tampered_message() {
+        let signer = Signer::new("hmac-sha256", "secret").unwrap();
+        let mut frames = request("execute_request").encode(&signer);
+        *frames.last_mut().unwrap() = br#"{"code":"evil()"}"#.to_vec();
+        assert!(Message::decode(frames, &signer).is_err());
+
+        let unsigned = request("execute_request").encode(&Signer::new("", "").unwrap());
+        assert!(Message::decode(unsigned, &signer).is_err());
+    }
+
+    #[test]
+    fn test_empty_key_disables_signing() {
+        let signer = Signer::new("hmac-sha256", "").unwrap();
+        let frames = request("kernel_info_request").encode(&signer);
+        assert!(frames[2].is_empty());
+        assert!(Message::decode(frames, &signer).is_ok());
+    }
+
+    #[test]
+    fn test_reply_keeps_identities_and_parent() {
+        let mut session = Session::start();
+        let parent = request("execute_request");
+        let reply = session.reply(&parent, "execute_reply", json!({ "status": "ok" }));
+        assert_eq!(reply.identities, parent.identities);
+        assert_eq!(reply.parent_header["msg_id"], "1");
+        assert_eq!(reply.msg_type(), "execute_reply");
+
+        let status = session.broadcast(&parent, "status", json!({}));
+        assert_eq!(status.identities, vec![b"status".to_vec()]);
+        assert_ne!(status.header["msg_id"], reply.header["msg_id"]);
+    }
+}
@@ -0,0 +1,436 @@
+//! Minimal ZeroMQ transport for the kernel sockets (ZMTP 3.0, NULL security)
+//!
+//! The kernel binds every socket and frontends connect to it, so only the
+//! server side of the handshake is implemented, and each socket type only
+//! does what the Jupyter protocol relies on:
+//!
+//! - ROUTER (shell, control, stdin): inbound messages are tagged with the
+//!   connection they arrived on, and replies are written back to it.
+//! - PUB (iopub): every message goes to every connected subscriber.
+//!   Frontends subscribe to all topics, so subscriptions are read and dropped.
+//! - REP (heartbeat): every message is echoed back unchanged.
+
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A multipart message: one `Vec<u8>` per frame
+pub type Frames = Vec<Vec<u8>>;
+
+/// Connection a ROUTER message arrived on
+pub type PeerId = u64;
+
+/// Largest frame accepted from a peer
+const MAX_FRAME_BYTES: u64 = 256 * 1024 * 1024;
+
+const FLAG_MORE: u8 = 0x01;
+const FLAG_LONG: u8 = 0x02;
+const FLAG_COMMAND: u8 = 0x04;
+
+static NEXT_PEER: AtomicU64 = AtomicU64::new(1);
+
+type Peers = Arc<Mutex<HashMap<PeerId, TcpStream>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SocketType {
+    Router,
+    Pub,
+    Rep,
+}
+
+impl SocketType {
+    fn name(self) -> &'static str {
+        match self {
+            SocketType::Router => "ROUTER",
+            SocketType::Pub => "PUB",
+            SocketType::Rep => "REP",
+        }
+    }
+}
+
+/// A message received on a ROUTER socket
+#[derive(Debug)]
+pub struct Inbound<L> {
+    /// Which socket it arrived on
+    pub label: L,
+    /// Which connection it arrived on
+    pub peer: PeerId,
+    pub frames: Frames,
+}
+
+/// Bound ROUTER socket; cheap to clone
+#[derive(Clone)]
+pub struct Router {
+    peers: Peers,
+}
+
+impl Router {
+    /// Bind `address` and forward every inbound message to `inbox`
+    pub fn bind<L>(address: &str, label: L, inbox: Sender<Inbound<L>>) -> io::Result<Self>
+    where
+        L: Copy + Send + 'static,
+    {
+        let listener = TcpListener::bind(address)?;
+        let peers: Peers = Arc::default();
+        let router = Router {
+            peers: Arc::clone(&peers),
+        };
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let peers = Arc::clone(&peers);
+                let inbox = inbox.clone();
+                thread::spawn(move || {
+                    let _ = serve_router_peer(stream, label, &peers, &inbox);
+                });
+            }
+        });
+        Ok(router)
+    }
+
+    /// Send a message back to `peer`
+    pub fn send(&self, peer: PeerId, frames: &[Vec<u8>]) -> io::Result<()> {
+        let mut peers = self.peers.lock().expect("peer table lock poisoned");
+        let stream = peers
+            .get_mut(&peer)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "peer disconnected"))?;
+        write_message(stream, frames)
+    }
+}
+
+fn serve_router_peer<L: Copy>(
+    mut stream: TcpStream,
+    label: L,
+    peers: &Peers,
+    inbox: &Sender<Inbound<L>>,
+) -> io::Result<()> {
+    handshake(&mut stream, SocketType::Router)?;
+    let peer = NEXT_PEER.fetch_add(1, Ordering::Relaxed);
+    peers
+        .lock()
+        .expect("peer table lock poisoned")
+        .insert(peer, stream.try_clone()?);
+
+    let result = loop {
+        match read_message(&mut stream) {
+            Ok(frames) => {
+                if inbox
+                    .send(Inbound {
+                        label,
+                        peer,
+                        frames,
+                    })
+                    .is_err()
+                {
+                    break Ok(());
+                }
+            }
+            Err(e) => break Err(e),
+        }
+    };
+    peers
+        .lock()
+        .expect("peer table lock poisoned")
+        .remove(&peer);
+    result
+}
+
+/// Bound PUB socket; cheap to clone
+#[derive(Clone)]
+pub struct Publisher {
+    peers: Peers,
+}
+
+impl Publisher {
+    pub fn bind(address: &str) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let peers: Peers = Arc::default();
+        let publisher = Publisher {
+            peers: Arc::clone(&peers),
+        };
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let peers = Arc::clone(&peers);
+                thread::spawn(move || {
+                    let _ = serve_subscriber(stream, &peers);
+                });
+            }
+        });
+        Ok(publisher)
+    }
+
+    /// Send a message to every subscriber, dropping the ones that went away
+    pub fn send(&self, frames: &[Vec<u8>]) {
+        let mut peers = self.peers.lock().expect("peer table lock poisoned");
+        peers.retain(|_, stream| write_message(stream, frames).is_ok());
+    }
+}
+
+fn serve_subscriber(mut stream: TcpStream, peers: &Peers) -> io::Result<()> {
+    handshake(&mut stream, SocketType::Pub)?;
+    let peer = NEXT_PEER.fetch_add(1, Ordering::Relaxed);
+    peers
+        .lock()
+        .expect("peer table lock poisoned")
+        .insert(peer, stream.try_clone()?);
+
+    // Subscription messages: read until the subscriber disconnects
+    let result = loop {
+        if let Err(e) = read_message(&mut stream) {
+            break Err(e);
+        }
+    };
+    peers
+        .lock()
+        .expect("peer table lock poisoned")
+        .remove(&peer);
+    result
+}
+
+/// Bind a REP socket that echoes every message (the heartbeat channel)
+pub fn serve_heartbeat(address: &str) -> io::Result<()> {
+    let listener = TcpListener::bind(address)?;
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            thread::spawn(move || -> io::Result<()> {
+                handshake(&mut stream, SocketType::Rep)?;
+                loop {
+                    let frames = read_message(&mut stream)?;
+                    write_message(&mut stream, &frames)?;
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+// ============================================================================
+// Wire format
+// ============================================================================
+
+/// Exchange greetings and READY commands as the server
+fn handshake(stream: &mut TcpStream, socket_type: SocketType) -> io::Result<()> {
+    // Signature (0xFF, 8 padding bytes, 0x7F), version 3.0, mechanism,
+    // as-server flag and filler
+    let mut greeting = [0u8; 64];
+    greeting[0] = 0xFF;
+    greeting[9] = 0x7F;
+    greeting[10] = 3;
+    greeting[12..16].copy_from_slice(b"NULL");
+    greeting[32] = 1;
+    stream.write_all(&greeting)?;
+
+    let mut peer = [0u8; 64];
+    stream.read_exact(&mut peer)?;
+    if peer[0] != 0xFF || peer[9] & 0x01 == 0 {
+        return Err(protocol_error("not a ZMTP peer"));
+    }
+    if peer[10] < 3 {
+        return Err(protocol_error("ZMTP 3.0 or later required"));
+    }
+    if &peer[12..16] != b"NULL" || peer[16..32].iter().any(|&b| b != 0) {
+        return Err(protocol_error(
+            "only the NULL security mechanism is supported",
+        ));
+    }
+
+    let mut ready = command_body("READY");
+    push_property(&mut ready, "Socket-Type", socket_type.name().as_bytes());
+    write_frame(stream, &ready, FLAG_COMMAND)?;
+
+    let (flags, body) = read_frame(stream)?;
+    if flags & FLAG_COMMAND == 0 || command_name(&body) != Some(b"READY".as_slice()) {
+        return Err(protocol_error("expected READY command"));
+    }
+    Ok(())
+}
+
+fn command_body(name: &str) -> Vec<u8> {
+    let mut body = vec![name.len() as u8];
+    body.extend_from_slice(name.as_bytes());
+    body
+}
+
+fn push_property(body: &mut Vec<u8>, name: &str, value: &[u8]) {
+    body.push(name.len() as u8);
+    body.extend_from_slice(name.as_bytes());
+    body.extend_from_slice(&(value.len() as u32).to_be_bytes());
+    body.extend_from_slice(value);
+}
+
+fn command_name(body: &[u8]) -> Option<&[u8]> {
+    let len = *body.first()? as usize;
+    body.get(1..1 + len)
+}
+
+fn read_frame(stream: &mut impl Read) -> io::Result<(u8, Vec<u8>)> {
+    let mut flags = [0u8; 1];
+    stream.read_exact(&mut flags)?;
+    let flags = flags[0];
+    let len = if flags & FLAG_LONG != 0 {
+        let mut len = [0u8; 8];
+        stream.read_exact(&mut len)?;
+        u64::from_be_bytes(len)
+    } else {
+        let mut len = [0u8; 1];
+        stream.read_exact(&mut len)?;
+        u64::from(len[0])
+    };
+    if len > MAX_FRAME_BYTES {
+        return Err(protocol_error("frame too large"));
+    }
+    let mut body = vec![0u8; len as usize];
+    stream.read_exact(&mut body)?;
+    Ok((flags, body))
+}
+
+fn encode_frame(out: &mut Vec<u8>, body: &[u8], flags: u8) {
+    if body.len() > u8::MAX as usize {
+        out.push(flags | FLAG_LONG);
+        out.extend_from_slice(&(body.len() as u64).to_be_bytes());
+    } else {
+        out.push(flags);
+        out.push(body.len() as u8);
+    }
+    out.extend_from_slice(body);
+}
+
+fn write_frame(stream: &mut impl Write, body: &[u8], flags: u8) -> io::Result<()> {
+    let mut out = Vec::with_capacity(body.len() + 9);
+    encode_frame(&mut out, body, flags);
+    stream.write_all(&out)
+}
+
+/// Read one multipart message, skipping commands between messages (PING etc.)
+fn read_message(stream: &mut impl Read) -> io::Result<Frames> {
+    let mut frames = Vec::new();
+    loop {
+        let (flags, body) = read_frame(stream)?;
+        if flags & FLAG_COMMAND != 0 {
+            if frames.is_empty() {
+                continue;
+            }
+            return Err(protocol_error("command inside a multipart message"));
+        }
+        frames.push(body);
+        if flags & FLAG_MORE == 0 {
+            return Ok(frames);
+        }
+    }
+}
+
+/// Write a multipart message with a single `write_all`
+fn write_message(stream: &mut impl Write, frames: &[Vec<u8>]) -> io::Result<()> {
+    let mut out = Vec::new();
+    for (i, frame) in frames.iter().enumerate() {
+        let flags = if i + 1 < frames.len() { FLAG_MORE } else { 0 };
+        encode_frame(&mut out, frame, flags);
+    }
+    stream.write_all(&out)
+}
+
+fn protocol_error(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::sync::mpsc;
+
+    /// Client side of the handshake, as a frontend would do it
+    fn connect(address: &str, socket_type: &str) -> TcpStream {
+        let mut stream = TcpStream::connect(address).unwrap();
+        let mut greeting = [0u8; 64];
+        greeting[0] = 0xFF;
+        greeting[9] = 0x7F;
+        greeting[10] = 3;
+        greeting[11] = 1;
+        greeting[12..16].copy_from_slice(b"NULL");
+        stream.write_all(&greeting).unwrap();
+
+        let mut server = [0u8; 64];
+        stream.read_exact(&mut server).unwrap();
+        assert_eq!(server[0], 0xFF);
+        assert_eq!(&server[12..16], b"NULL");
+
+        let (flags, body) = read_frame(&mut stream).unwrap();
+        assert_ne!(flags & FLAG_COMMAND, 0);
+        assert_eq!(command_name(&body), Some(b"READY".as_slice()));
+
+        let mut ready = command_body("READY");
+        push_property(&mut ready, "Socket-Type", socket_type.as_bytes());
+        write_frame(&mut stream, &ready, FLAG_COMMAND).unwrap();
+        stream
+    }
+
+    fn free_address() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_frames_roundtrip_short_and_long() {
+        let frames = vec![b"<IDS|MSG>".to_vec(), vec![7u8; 300], Vec::new()];
+        let mut wire = Vec::new();
+        write_message(&mut wire, &frames).unwrap();
+        // short frame: flags + 1-byte length; long frame: flags + 8-byte length
+        assert_eq!(wire[0], FLAG_MORE);
+        assert_eq!(wire[11], FLAG_MORE | FLAG_LONG);
+        assert_eq!(read_message(&mut Cursor::new(wire)).unwrap(), frames);
+    }
+
+    #[test]
+    fn test_read_message_skips_leading_commands() {
+        let mut wire = Vec::new();
+        write_frame(&mut wire, &command_body("PING"), FLAG_COMMAND).unwrap();
+        write_message(&mut wire, &[b"hello".to_vec()]).unwrap();
+        assert_eq!(
+            read_message(&mut Cursor::new(wire)).unwrap(),
+            vec![b"hello".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_router_tags_and_replies_to_peer() {
+        let address = free_address();
+        let (tx, rx) = mpsc::channel();
+        let router = Router::bind(&address, "shell", tx).unwrap();
+
+        let mut client = connect(&address, "DEALER");
+        write_message(&mut client, &[b"a".to_vec(), b"b".to_vec()]).unwrap();
+        let inbound = rx.recv().unwrap();
+        assert_eq!(inbound.label, "shell");
+        assert_eq!(inbound.frames, vec![b"a".to_vec(), b"b".to_vec()]);
+
+        router.send(inbound.peer, &[b"reply".to_vec()]).unwrap();
+        assert_eq!(read_message(&mut client).unwrap(), vec![b"reply".to_vec()]);
+    }
+
+    #[test]
+    fn test_heartbeat_echoes() {
+        let address = free_address();
+        serve_heartbeat(&address).unwrap();
+        let mut client = connect(&address, "REQ");
+        let ping = vec![Vec::new(), b"ping".to_vec()];
+        write_message(&mut client, &ping).unwrap();
+        assert_eq!(read_message(&mut client).unwrap(), ping);
+    }
+
+    #[test]
+    fn test_handshake_rejects_non_zmtp_peer() {
+        let address = free_address();
+        let (tx, _rx) = mpsc::channel::<Inbound<()>>();
+        let _router = Router::bind(&address, (), tx).unwrap();
+        let mut stream = TcpStream::connect(&address).unwrap();
+        stream.write_all(&[b'G'; 64]).unwrap();
+        // The server hangs up after its greeting
+        let mut buf = Vec::new();
+        let _ = stream.read_to_end(&mut buf);
+        assert_eq!(buf.len(), 64);
+    }
+}
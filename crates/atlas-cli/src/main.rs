@@ -7,6 +7,7 @@ mod commands;
 mod config;
 mod debugger;
 mod diagnostics;
+mod kernel;
 mod templates;
 mod testing;
 
@@ -37,19 +38,84 @@ struct Cli {
     command: Commands,
 }
 
+/// Subcommands of `atlas api`
+#[derive(Subcommand)]
+enum ApiCommand {
+    /// List every exported function, type and value with its signature
+    Report {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Classify API changes since a release as breaking or additive
+    ///
+    /// Fails if the version in atlas.toml is too small for the changes.
+    Diff {
+        /// Version of the earlier release (its `v<version>` git tag is read)
+        old_version: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Subcommands of `atlas daemon`
+#[derive(Subcommand)]
+enum DaemonCommand {
+    /// Start a daemon in the background (the default)
+    Start {
+        /// Workspace root [default: the project enclosing the current directory]
+        #[arg(long)]
+        root: Option<std::path::PathBuf>,
+        /// Stop after this many minutes without a request (0 = never)
+        #[arg(long, default_value = "60")]
+        idle_timeout: u64,
+    },
+    /// Serve in the foreground until stopped
+    Serve {
+        /// Workspace root [default: the project enclosing the current directory]
+        #[arg(long)]
+        root: Option<std::path::PathBuf>,
+        /// Stop after this many minutes without a request (0 = never)
+        #[arg(long, default_value = "60")]
+        idle_timeout: u64,
+    },
+    /// Show what the running daemon holds
+    Status {
+        /// Workspace root [default: the project enclosing the current directory]
+        #[arg(long)]
+        root: Option<std::path::PathBuf>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Stop the running daemon
+    Stop {
+        /// Workspace root [default: the project enclosing the current directory]
+        #[arg(long)]
+        root: Option<std::path::PathBuf>,
+    },
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Run an Atlas source file
     ///
     /// Compiles and executes the specified Atlas file. Supports watch mode
     /// for automatic recompilation on file changes. Arguments after the file
-    /// are passed to the Atlas program via process.getProcessArgs().
+    /// are passed to `fn main(args: string[])` (and process.args()); main's
+    /// return value becomes the exit code.
     ///
     /// EXAMPLES:
     ///     atlas run main.atl              Run a program
     ///     atlas run main.atl foo bar      Run with arguments
     ///     atlas run main.atl --watch      Watch for changes
     ///     atlas run main.atl --json       Output diagnostics as JSON
+    ///     atlas run main.atl --timings    Show time spent per pipeline phase
+    ///     ./tool.atl                      Run a script with `#!/usr/bin/env atlas`
+    ///
+    /// A script may declare packages in a leading `//! dependencies:` comment;
+    /// they are installed into a temporary project the first time it runs.
     #[command(visible_alias = "r", trailing_var_arg = true)]
     Run {
         /// Path to the Atlas source file
@@ -66,6 +132,15 @@ enum Commands {
         /// Verbose output with timing information
         #[arg(long, short = 'v')]
         verbose: bool,
+        /// Compile installed packages from source instead of using cached bytecode
+        #[arg(long, env = "ATLAS_NO_BYTECODE_CACHE")]
+        no_cache: bool,
+        /// Print console.styled text, tables, and progress bars without color
+        #[arg(long)]
+        no_color: bool,
+        /// Print the time spent in each pipeline phase (as JSON with --json)
+        #[arg(long)]
+        timings: bool,
         /// Arguments to pass to the Atlas program
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         program_args: Vec<String>,
@@ -73,8 +148,9 @@ enum Commands {
 
     /// Type-check an Atlas source file without running
     ///
-    /// Analyzes the source file for type errors and reports diagnostics
-    /// without executing the code.
+    /// Analyzes the source file and every module it imports for errors and
+    /// reports diagnostics without executing the code. A running `atlas
+    /// daemon` for the project does the check from its warm cache.
     ///
     /// EXAMPLES:
     ///     atlas check main.atl         Check for errors
@@ -119,6 +195,58 @@ enum Commands {
         json: bool,
     },
 
+    /// Bundle a program into a standalone executable
+    ///
+    /// Compiles the entry file with all of its modules and locked packages,
+    /// and appends the bytecode to a copy of the Atlas launcher. The bundle
+    /// runs with the permissions in the project's `[security]` section.
+    ///
+    /// EXAMPLES:
+    ///     atlas bundle                      Bundle the project's entry point
+    ///     atlas bundle tool.atl -o tool     Bundle a single file
+    ///     atlas bundle --allow-all          Grant every permission
+    Bundle {
+        /// Entry file (defaults to the project's entry point)
+        file: Option<std::path::PathBuf>,
+        /// Output path (defaults to target/bundle/<name>)
+        #[arg(long, short = 'o')]
+        output: Option<std::path::PathBuf>,
+        /// Ignore [security] and grant every permission
+        #[arg(long)]
+        allow_all: bool,
+        /// Quiet output (errors only)
+        #[arg(long, short = 'q')]
+        quiet: bool,
+    },
+
+    /// Compile a program to bytecode ahead of time
+    ///
+    /// Checks and compiles the entry file with all of its modules and locked
+    /// packages into one `.atbc` file. Compiled packages are kept in the
+    /// package cache and reused by later compiles and `atlas run` until their
+    /// sources or the Atlas version change.
+    ///
+    /// EXAMPLES:
+    ///     atlas compile main.atl                Write main.atbc
+    ///     atlas compile main.atl -o app.atbc    Choose the output path
+    ///     atlas compile main.atl --no-cache     Recompile every package
+    Compile {
+        /// Entry file
+        file: std::path::PathBuf,
+        /// Output path (defaults to the entry file with an .atbc extension)
+        #[arg(long, short = 'o')]
+        output: Option<std::path::PathBuf>,
+        /// Compile installed packages from source instead of using cached bytecode
+        #[arg(long, env = "ATLAS_NO_BYTECODE_CACHE")]
+        no_cache: bool,
+        /// Output diagnostics in JSON format
+        #[arg(long, env = "ATLAS_JSON")]
+        json: bool,
+        /// Quiet output (errors only)
+        #[arg(long, short = 'q')]
+        quiet: bool,
+    },
+
     /// Start an interactive REPL
     ///
     /// Opens an interactive Read-Eval-Print Loop for exploring Atlas.
@@ -221,6 +349,8 @@ enum Commands {
     ///     atlas profile slow.atl          Profile execution
     ///     atlas profile slow.atl -o report.txt  Save report
     ///     atlas profile slow.atl --summary      Brief output
+    ///     atlas profile slow.atl --folded=out.folded  Flamegraph input
+    ///     atlas profile slow.atl --memory       Allocations and peak memory
     Profile {
         /// Path to the Atlas source file
         file: String,
@@ -233,6 +363,15 @@ enum Commands {
         /// Print summary only (no detailed report)
         #[arg(long)]
         summary: bool,
+        /// Write folded call stacks (flamegraph.pl, inferno, speedscope) to this file
+        #[arg(long)]
+        folded: Option<String>,
+        /// Weight folded stacks by instructions executed instead of self time
+        #[arg(long, requires = "folded")]
+        folded_instructions: bool,
+        /// Also report allocations by kind and site, and peak memory
+        #[arg(long)]
+        memory: bool,
     },
 
     /// Run tests in a directory
@@ -287,13 +426,18 @@ enum Commands {
     ///     atlas debug main.atl            Start debugging
     ///     atlas debug main.atl -b 10      Break at line 10
     ///     atlas debug main.atl -b 10 -b 20  Multiple breakpoints
+    ///     atlas debug --dap               Debug Adapter Protocol server (stdio)
     #[command(visible_alias = "d")]
     Debug {
         /// Path to the Atlas source file
-        file: String,
+        #[arg(required_unless_present = "dap")]
+        file: Option<String>,
         /// Set breakpoints at line numbers (can be repeated)
         #[arg(long, short = 'b')]
         breakpoint: Vec<u32>,
+        /// Serve the Debug Adapter Protocol on stdio for graphical debuggers
+        #[arg(long)]
+        dap: bool,
     },
 
     /// Start the Atlas Language Server
@@ -321,6 +465,78 @@ enum Commands {
         verbose: bool,
     },
 
+    /// Keep a workspace parsed and compiled in the background
+    ///
+    /// While a daemon serves the project, `atlas run`, `atlas check` and
+    /// `atlas test` get unchanged programs already compiled or checked from
+    /// it and the language server takes its workspace index from it, instead
+    /// of re-parsing the project each time.
+    /// Anything the daemon cannot serve is done locally as before.
+    ///
+    /// EXAMPLES:
+    ///     atlas daemon                    Start a daemon for this project
+    ///     atlas daemon status             Show cached modules and hit rate
+    ///     atlas daemon stop               Stop it
+    ///     atlas daemon serve              Run in the foreground
+    Daemon {
+        #[command(subcommand)]
+        command: Option<DaemonCommand>,
+    },
+
+    /// Start a local web playground
+    ///
+    /// Serves an editor in the browser. Each run is evaluated in a fresh
+    /// sandboxed runtime (no file, network, process or environment access)
+    /// with time and memory limits, and its output is streamed back live.
+    ///
+    /// EXAMPLES:
+    ///     atlas playground                    Serve on http://127.0.0.1:8765
+    ///     atlas playground --port=3000        Custom port
+    ///     atlas playground --timeout=10       Allow 10 seconds per run
+    Playground {
+        /// Port to listen on
+        #[arg(long, default_value = "8765")]
+        port: u16,
+        /// Bind address
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        /// Execution time limit per run, in seconds
+        #[arg(long, default_value = "5")]
+        timeout: u64,
+        /// Memory limit per run, in megabytes
+        #[arg(long, default_value = "16")]
+        max_memory: usize,
+        /// Log each request
+        #[arg(long, short = 'v')]
+        verbose: bool,
+    },
+
+    /// Run as a Jupyter kernel
+    ///
+    /// Jupyter starts the kernel itself with a connection file. Run once
+    /// with --install to register Atlas as a notebook language. Cells share
+    /// state like the REPL; arrays of maps render as tables and JSON values
+    /// as pretty, explorable JSON.
+    ///
+    /// EXAMPLES:
+    ///     atlas kernel --install                        Register the kernelspec
+    ///     atlas kernel --install --prefix=.venv         Install into an environment
+    ///     atlas kernel --connection-file=kernel.json    Serve (Jupyter does this)
+    Kernel {
+        /// Connection file written by Jupyter
+        #[arg(long, value_name = "FILE", required_unless_present = "install")]
+        connection_file: Option<std::path::PathBuf>,
+        /// Install the kernelspec instead of serving
+        #[arg(long, conflicts_with = "connection_file")]
+        install: bool,
+        /// Install under <PREFIX>/share/jupyter/kernels
+        #[arg(long, requires = "install")]
+        prefix: Option<std::path::PathBuf>,
+        /// Log each request
+        #[arg(long, short = 'v')]
+        verbose: bool,
+    },
+
     /// Generate shell completions
     ///
     /// Outputs shell completion scripts for bash, zsh, fish, or powershell.
@@ -413,6 +629,9 @@ enum Commands {
         /// Rename the dependency
         #[arg(long)]
         rename: Option<String>,
+        /// Track the nightly channel (allow pre-release versions)
+        #[arg(long)]
+        nightly: bool,
         /// Dry run (don't modify files)
         #[arg(long)]
         dry_run: bool,
@@ -466,6 +685,9 @@ enum Commands {
         /// Quiet output (errors only)
         #[arg(long, short = 'q')]
         quiet: bool,
+        /// Registry URL or local registry directory for registry dependencies
+        #[arg(long)]
+        registry: Option<String>,
     },
 
     /// Update project dependencies
@@ -477,6 +699,8 @@ enum Commands {
     ///     atlas update                   Update all dependencies
     ///     atlas update http              Update specific package
     ///     atlas update --dry-run         Show what would be updated
+    ///     atlas update --pre             Include pre-release versions
+    ///     atlas update --conservative    Only take patch releases of locked versions
     #[command(visible_alias = "up")]
     Update {
         /// Specific packages to update (empty = all)
@@ -490,6 +714,90 @@ enum Commands {
         /// Verbose output
         #[arg(long, short = 'v')]
         verbose: bool,
+        /// Consider pre-release versions (e.g. 1.0.0-beta.2)
+        #[arg(long)]
+        pre: bool,
+        /// Minimal bumps: newest patch release of each locked major.minor
+        #[arg(long, conflicts_with = "aggressive")]
+        conservative: bool,
+        /// Newest version each constraint allows (default)
+        #[arg(long)]
+        aggressive: bool,
+        /// Registry URL or local registry directory for registry dependencies
+        #[arg(long)]
+        registry: Option<String>,
+    },
+
+    /// Resolve dependencies and rewrite atlas.lock
+    ///
+    /// Re-resolves every dependency from atlas.toml, ignoring the existing
+    /// lockfile. With --minimal-versions the lowest matching versions are
+    /// locked instead, so library authors can check their lower bounds.
+    ///
+    /// EXAMPLES:
+    ///     atlas resolve                      Lock the newest matching versions
+    ///     atlas resolve --minimal-versions   Lock the oldest matching versions
+    Resolve {
+        /// Select the lowest versions satisfying each requirement
+        #[arg(long)]
+        minimal_versions: bool,
+        /// Verbose output
+        #[arg(long, short = 'v')]
+        verbose: bool,
+        /// Registry URL or local registry directory for registry dependencies
+        #[arg(long)]
+        registry: Option<String>,
+    },
+
+    /// Explain why a dependency is in the lockfile
+    ///
+    /// Shows which constraints pulled the package in and why the locked
+    /// version was chosen over the one given.
+    ///
+    /// EXAMPLES:
+    ///     atlas why json                 Who requires json and at what version
+    ///     atlas why json@1.2.0           Why json 1.2.0 was not used
+    Why {
+        /// Package name, optionally with a version (name@version)
+        package: String,
+    },
+
+    /// Search the package registry
+    ///
+    /// Matches every term against package names, keywords and descriptions.
+    ///
+    /// EXAMPLES:
+    ///     atlas search http              Packages mentioning http
+    ///     atlas search http client       Packages matching both terms
+    ///     atlas search json --page 2     Second page of results
+    Search {
+        /// Search terms
+        #[arg(required = true)]
+        query: Vec<String>,
+        /// Result page to show
+        #[arg(long, default_value_t = 1)]
+        page: usize,
+        /// Results per page
+        #[arg(long, default_value_t = 20)]
+        per_page: usize,
+        /// Registry URL or local registry directory
+        #[arg(long)]
+        registry: Option<String>,
+    },
+
+    /// Show registry details for a package
+    ///
+    /// Lists versions (marking yanked ones), license, features,
+    /// dependencies and download count.
+    ///
+    /// EXAMPLES:
+    ///     atlas info http                Details for the http package
+    Info {
+        /// Package name
+        package: String,
+        /// Registry URL or local registry directory
+        #[arg(long)]
+        registry: Option<String>,
     },
 
     /// Publish package to registry
@@ -501,6 +809,9 @@ enum Commands {
     ///     atlas publish                  Publish to default registry
     ///     atlas publish --dry-run        Validate without publishing
     ///     atlas publish --no-verify      Skip validation steps
+    ///     atlas publish --sign           Sign the release archive
+    ///     atlas publish --upload         Upload a tarball to the registry
+    ///     atlas publish --yank 1.2.0     Withdraw a published version
     Publish {
         /// Registry to publish to
         #[arg(long)]
@@ -517,6 +828,32 @@ enum Commands {
         /// Verbose output
         #[arg(long, short = 'v')]
         verbose: bool,
+        /// Yank a published version (new resolutions will skip it)
+        #[arg(long, value_name = "VERSION")]
+        yank: Option<String>,
+        /// With --yank: restore a previously yanked version
+        #[arg(long, requires = "yank")]
+        undo: bool,
+        /// Sign the release with the ed25519 key in ~/atlas/credentials.toml
+        #[arg(long, conflicts_with = "yank")]
+        sign: bool,
+        /// Upload a package tarball to the registry instead of tagging
+        #[arg(long, conflicts_with = "yank")]
+        upload: bool,
+    },
+
+    /// Inspect the package's public API
+    ///
+    /// Lists everything the package exports, or compares it with an earlier
+    /// release tag and checks the version bump in atlas.toml.
+    ///
+    /// EXAMPLES:
+    ///     atlas api report               List exported items
+    ///     atlas api diff 1.2.0           Changes since tag v1.2.0
+    ///     atlas api diff 1.2.0 --json    Machine-readable diff
+    Api {
+        #[command(subcommand)]
+        command: ApiCommand,
     },
 
     /// Explain an Atlas error code
@@ -538,6 +875,26 @@ enum Commands {
         list: bool,
     },
 
+    /// List `// atlas-ignore:` suppressions
+    ///
+    /// Scans Atlas files for inline warning suppressions and prints each one
+    /// with its rules and reason, followed by a count per rule.
+    ///
+    /// EXAMPLES:
+    ///     atlas suppressions              Audit the current directory
+    ///     atlas suppressions src/ --json  Machine-readable report
+    ///     atlas suppressions --strict     Fail on unexplained or unknown rules
+    Suppressions {
+        /// Files or directories to scan (default: current directory)
+        paths: Vec<std::path::PathBuf>,
+        /// Output the report as JSON
+        #[arg(long)]
+        json: bool,
+        /// Fail if a suppression has no reason or names an unknown rule
+        #[arg(long)]
+        strict: bool,
+    },
+
     /// Create a new Atlas project from a template
     ///
     /// Creates a new project directory with a complete project structure
@@ -591,8 +948,23 @@ enum Commands {
     },
 }
 
+/// Command-line arguments, reading `atlas script.atl ...` (how a
+/// `#!/usr/bin/env atlas` line invokes a script) as `atlas run script.atl ...`
+fn cli_args() -> Vec<std::ffi::OsString> {
+    let mut args: Vec<_> = std::env::args_os().collect();
+    if let Some(first) = args.get(1) {
+        let is_command = first
+            .to_str()
+            .is_some_and(|name| Cli::command().find_subcommand(name).is_some());
+        if !is_command && std::path::Path::new(first).is_file() {
+            args.insert(1, "run".into());
+        }
+    }
+    args
+}
+
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let cli = Cli::parse_from(cli_args());
     let cli_config = config::Config::from_env();
 
     // Load project configuration (atlas.toml) if in a project directory
@@ -608,14 +980,26 @@ fn main() -> Result<()> {
             watch,
             no_clear,
             verbose,
+            no_cache,
+            no_color,
+            timings,
             program_args,
         } => {
             // Command-line flag overrides environment variable
             let use_json = json || cli_config.default_json;
+            if no_color || cli_config.no_color {
+                atlas_runtime::stdlib::terminal::set_color_mode(
+                    atlas_runtime::diagnostic::formatter::ColorMode::Never,
+                );
+            }
 
             // Set program args as env vars for runtime's getProcessArgs()
             // _ATLAS_RUN marker distinguishes "atlas run with no args" from compiled binary
             std::env::set_var("_ATLAS_RUN", "1");
+            // Program name for process.parseArgs usage lines
+            if let Some(stem) = std::path::Path::new(&file).file_stem() {
+                std::env::set_var("ATLAS_PROGRAM_NAME", stem);
+            }
             if !program_args.is_empty() {
                 std::env::set_var("ATLAS_PROGRAM_ARGS", program_args.join("\n"));
             }
@@ -630,14 +1014,15 @@ fn main() -> Result<()> {
                 };
                 commands::watch::run_watch(&file, config)?;
             } else {
-                // Normal run
-                commands::run::run(&file, use_json)?;
+                // Normal run; temp files go with the runtime before any exit below
+                let code = commands::run::run(&file, use_json, !no_cache, timings)?;
+                if code != 0 {
+                    std::process::exit(code);
+                }
             }
         }
-        Commands::Check { file, .. } => {
-            eprintln!("error: `atlas check` is deprecated — it only analyzes a single file in isolation and misses cross-module errors.");
-            eprintln!("       Use `atlas run {}` instead.", file);
-            std::process::exit(1);
+        Commands::Check { file, json } => {
+            commands::check::run(&file, json || cli_config.default_json)?;
         }
         Commands::Build {
             profile,
@@ -660,6 +1045,37 @@ fn main() -> Result<()> {
             };
             commands::build::run(args)?;
         }
+        Commands::Bundle {
+            file,
+            output,
+            allow_all,
+            quiet,
+        } => {
+            let args = commands::bundle::BundleArgs {
+                file,
+                output,
+                allow_all,
+                project_dir: std::env::current_dir()?,
+                quiet,
+            };
+            commands::bundle::run(args)?;
+        }
+        Commands::Compile {
+            file,
+            output,
+            no_cache,
+            json,
+            quiet,
+        } => {
+            let args = commands::compile::CompileArgs {
+                file,
+                output,
+                no_cache,
+                json: json || cli_config.default_json,
+                quiet,
+            };
+            commands::compile::run(args)?;
+        }
         Commands::Repl { tui, no_history } => {
             // Command-line flag overrides environment variable
             let disable_history = no_history || cli_config.no_history;
@@ -706,11 +1122,19 @@ fn main() -> Result<()> {
             threshold,
             output,
             summary,
+            folded,
+            folded_instructions,
+            memory,
         } => {
             let mut args = commands::profile::ProfileArgs::new(file);
             args.hotspot_threshold = threshold;
             args.output_file = output.map(std::path::PathBuf::from);
             args.detailed = !summary;
+            args.folded_file = folded.map(std::path::PathBuf::from);
+            if folded_instructions {
+                args.folded_weight = atlas_runtime::profiler::StackWeight::Instructions;
+            }
+            args.memory = memory;
             commands::profile::run(args)?;
         }
         Commands::Test {
@@ -732,14 +1156,54 @@ fn main() -> Result<()> {
             };
             commands::test::run(args)?;
         }
-        Commands::Debug { file, breakpoint } => {
+        Commands::Debug {
+            file,
+            breakpoint,
+            dap,
+        } => {
             let args = commands::debug::DebugArgs {
-                file,
+                file: file.unwrap_or_default(),
                 breakpoints: breakpoint,
                 stop_at_entry: true,
+                dap,
             };
             commands::debug::run(args)?;
         }
+        Commands::Daemon { command } => {
+            let command = command.unwrap_or(DaemonCommand::Start {
+                root: None,
+                idle_timeout: commands::daemon::DaemonArgs::default().idle_timeout,
+            });
+            match command {
+                DaemonCommand::Start { root, idle_timeout } => {
+                    commands::daemon::start(commands::daemon::DaemonArgs {
+                        root,
+                        idle_timeout,
+                        json: false,
+                    })?
+                }
+                DaemonCommand::Serve { root, idle_timeout } => {
+                    commands::daemon::serve(commands::daemon::DaemonArgs {
+                        root,
+                        idle_timeout,
+                        json: false,
+                    })?
+                }
+                DaemonCommand::Status { root, json } => {
+                    commands::daemon::status(commands::daemon::DaemonArgs {
+                        root,
+                        json,
+                        ..Default::default()
+                    })?
+                }
+                DaemonCommand::Stop { root } => {
+                    commands::daemon::stop(commands::daemon::DaemonArgs {
+                        root,
+                        ..Default::default()
+                    })?
+                }
+            }
+        }
         Commands::Lsp {
             tcp,
             port,
@@ -754,6 +1218,36 @@ fn main() -> Result<()> {
             };
             commands::lsp::run(args)?;
         }
+        Commands::Playground {
+            port,
+            host,
+            timeout,
+            max_memory,
+            verbose,
+        } => {
+            let args = commands::playground::PlaygroundArgs {
+                host,
+                port,
+                timeout: std::time::Duration::from_secs(timeout),
+                max_memory: max_memory * 1024 * 1024,
+                verbose,
+            };
+            commands::playground::run(args)?;
+        }
+        Commands::Kernel {
+            connection_file,
+            install,
+            prefix,
+            verbose,
+        } => {
+            let args = commands::kernel::KernelArgs {
+                connection_file,
+                install,
+                prefix,
+                verbose,
+            };
+            commands::kernel::run(args)?;
+        }
         Commands::Completions { shell } => {
             let mut cmd = Cli::command();
             let name = cmd.get_name().to_string();
@@ -794,6 +1288,7 @@ fn main() -> Result<()> {
             no_default_features,
             optional,
             rename,
+            nightly,
             dry_run,
         } => {
             // Parse package@version syntax
@@ -817,6 +1312,7 @@ fn main() -> Result<()> {
                 no_default_features,
                 optional,
                 rename,
+                channel: nightly.then_some(atlas_package::ReleaseChannel::Nightly),
                 project_dir: std::env::current_dir()?,
                 dry_run,
                 verbose: false,
@@ -844,6 +1340,7 @@ fn main() -> Result<()> {
             dry_run,
             verbose,
             quiet,
+            registry,
         } => {
             let args = commands::install::InstallArgs {
                 packages: Vec::new(),
@@ -853,6 +1350,7 @@ fn main() -> Result<()> {
                 dry_run,
                 verbose,
                 quiet,
+                registry,
             };
             commands::install::run(args)?;
         }
@@ -861,6 +1359,10 @@ fn main() -> Result<()> {
             dev,
             dry_run,
             verbose,
+            pre,
+            conservative,
+            aggressive: _,
+            registry,
         } => {
             let args = commands::update::UpdateArgs {
                 packages,
@@ -868,15 +1370,64 @@ fn main() -> Result<()> {
                 project_dir: std::env::current_dir()?,
                 dry_run,
                 verbose,
+                pre,
+                mode: if conservative {
+                    atlas_package::UpdateMode::Conservative
+                } else {
+                    atlas_package::UpdateMode::Aggressive
+                },
+                registry,
             };
             commands::update::run(args)?;
         }
+        Commands::Resolve {
+            minimal_versions,
+            verbose,
+            registry,
+        } => {
+            let args = commands::resolve::ResolveArgs {
+                minimal_versions,
+                project_dir: std::env::current_dir()?,
+                verbose,
+                registry,
+            };
+            commands::resolve::run(args)?;
+        }
+        Commands::Why { package } => {
+            let args = commands::why::WhyArgs {
+                package,
+                project_dir: std::env::current_dir()?,
+            };
+            commands::why::run(args)?;
+        }
+        Commands::Search {
+            query,
+            page,
+            per_page,
+            registry,
+        } => {
+            let args = commands::search::SearchArgs {
+                query: query.join(" "),
+                page,
+                per_page,
+                registry,
+            };
+            commands::search::run(args)?;
+        }
+        Commands::Info { package, registry } => {
+            let args = commands::info::InfoArgs { package, registry };
+            commands::info::run(args)?;
+        }
         Commands::Publish {
             registry,
             no_verify,
             dry_run,
             allow_dirty,
             verbose,
+            yank,
+            undo,
+            sign,
+            upload,
         } => {
             let args = commands::publish::PublishArgs {
                 project_dir: std::env::current_dir()?,
@@ -885,9 +1436,25 @@ fn main() -> Result<()> {
                 dry_run,
                 allow_dirty,
                 verbose,
+                yank,
+                undo,
+                sign,
+                upload,
             };
             commands::publish::run(args)?;
         }
+        Commands::Api { command } => {
+            let project_dir = std::env::current_dir()?;
+            match command {
+                ApiCommand::Report { json } => {
+                    commands::api::report(commands::api::ApiArgs { project_dir, json })?
+                }
+                ApiCommand::Diff { old_version, json } => commands::api::diff_against(
+                    commands::api::ApiArgs { project_dir, json },
+                    &old_version,
+                )?,
+            }
+        }
         Commands::Explain { code, list } => {
             if list {
                 commands::explain::run_list()?;
@@ -901,6 +1468,18 @@ fn main() -> Result<()> {
                 commands::explain::run(&code)?;
             }
         }
+        Commands::Suppressions {
+            paths,
+            json,
+            strict,
+        } => {
+            let args = commands::suppressions::SuppressionsArgs {
+                paths,
+                json,
+                strict,
+            };
+            commands::suppressions::run(args)?;
+        }
         Commands::New {
             name,
             lib,
@@ -980,6 +1559,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_playground_flags() {
+        let cli = Cli::parse_from(["atlas", "playground", "--port=3000", "--timeout=10"]);
+        match cli.command {
+            Commands::Playground {
+                port,
+                timeout,
+                max_memory,
+                ..
+            } => {
+                assert_eq!(port, 3000);
+                assert_eq!(timeout, 10);
+                assert_eq!(max_memory, 16);
+            }
+            _ => panic!("Expected Playground command"),
+        }
+    }
+
+    #[test]
+    fn test_cli_kernel_flags() {
+        let cli = Cli::parse_from(["atlas", "kernel", "--connection-file", "conn.json"]);
+        match cli.command {
+            Commands::Kernel {
+                connection_file,
+                install,
+                ..
+            } => {
+                assert_eq!(connection_file, Some(std::path::PathBuf::from("conn.json")));
+                assert!(!install);
+            }
+            _ => panic!("Expected Kernel command"),
+        }
+        assert!(Cli::try_parse_from(["atlas", "kernel"]).is_err());
+        assert!(Cli::try_parse_from(["atlas", "kernel", "--install"]).is_ok());
+    }
+
     #[test]
     fn test_alias_r_for_run() {
         let cli = Cli::parse_from(["atlas", "r", "main.atl"]);
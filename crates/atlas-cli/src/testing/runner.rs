@@ -1,11 +1,15 @@
 //! Test runner - execute discovered tests
 
+use crate::commands::run::compile_with_daemon;
 use crate::testing::discovery::{TestFunction, TestSuite};
 use crate::testing::TEST_FILE_SUFFIX;
+use atlas_lsp::daemon::CompileResult;
 use atlas_runtime::api::Runtime;
-use atlas_runtime::SecurityContext;
+use atlas_runtime::{ModuleCache, SecurityContext};
 use rayon::prelude::*;
-use std::path::Path;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 /// Result of running a single test
@@ -57,6 +61,9 @@ pub struct TestRunner {
     /// Timeout for individual tests
     #[allow(dead_code)]
     timeout: Duration,
+    /// Parsed modules shared by every test's runtime, so a file with many
+    /// tests (and the modules it imports) is parsed once
+    module_cache: Arc<ModuleCache>,
 }
 
 impl Default for TestRunner {
@@ -71,6 +78,7 @@ impl TestRunner {
         Self {
             parallel: true,
             timeout: Duration::from_secs(30),
+            module_cache: Arc::new(ModuleCache::new()),
         }
     }
 
@@ -88,36 +96,56 @@ impl TestRunner {
     }
 
     /// Run all tests in the suite
+    ///
+    /// When an `atlas daemon` serves the project, each test file is checked
+    /// through it first; the tests of a file it rejects fail with its
+    /// diagnostics instead of each recompiling the file. Tests themselves
+    /// always run here, in a fresh runtime apiece.
     pub fn run(&self, suite: &TestSuite) -> Vec<TestRun> {
+        let rejected = rejected_by_daemon(suite);
         if self.parallel {
-            self.run_parallel(suite)
+            self.run_parallel(suite, &rejected)
         } else {
-            self.run_sequential(suite)
+            self.run_sequential(suite, &rejected)
         }
     }
 
     /// Run tests sequentially
-    fn run_sequential(&self, suite: &TestSuite) -> Vec<TestRun> {
+    fn run_sequential(
+        &self,
+        suite: &TestSuite,
+        rejected: &HashMap<PathBuf, String>,
+    ) -> Vec<TestRun> {
         suite
             .tests
             .iter()
-            .map(|test| self.run_single_test(test))
+            .map(|test| self.run_single_test(test, rejected))
             .collect()
     }
 
     /// Run tests in parallel using rayon
-    fn run_parallel(&self, suite: &TestSuite) -> Vec<TestRun> {
+    fn run_parallel(&self, suite: &TestSuite, rejected: &HashMap<PathBuf, String>) -> Vec<TestRun> {
         suite
             .tests
             .par_iter()
-            .map(|test| self.run_single_test(test))
+            .map(|test| self.run_single_test(test, rejected))
             .collect()
     }
 
     /// Run a single test
-    fn run_single_test(&self, test: &TestFunction) -> TestRun {
+    fn run_single_test(&self, test: &TestFunction, rejected: &HashMap<PathBuf, String>) -> TestRun {
         let start = Instant::now();
 
+        if let Some(error) = rejected.get(&test.file) {
+            return TestRun {
+                test: test.clone(),
+                result: TestResult::Fail {
+                    error: format!("Failed to load test file: {}", error),
+                    duration: start.elapsed(),
+                },
+            };
+        }
+
         let security = if is_test_file(&test.file) {
             SecurityContext::test_mode()
         } else {
@@ -126,6 +154,7 @@ impl TestRunner {
 
         // Create isolated runtime for this test (D-052: unified VM execution)
         let mut runtime = Runtime::new_with_security(security);
+        runtime.set_module_cache(Arc::clone(&self.module_cache));
 
         // Load the test file with import resolution (H-330 fix)
         // This uses load_file() which properly resolves imports relative to the file path,
@@ -161,6 +190,27 @@ impl TestRunner {
     }
 }
 
+/// Test files a running daemon reports errors in, with the error text
+///
+/// Files the daemon cannot be asked about, or declines, are left to
+/// `Runtime::load_file` like any other.
+fn rejected_by_daemon(suite: &TestSuite) -> HashMap<PathBuf, String> {
+    let files: BTreeSet<&PathBuf> = suite.tests.iter().map(|test| &test.file).collect();
+    files
+        .into_iter()
+        .filter_map(|file| match compile_with_daemon(file)? {
+            CompileResult::Diagnostics(diagnostics) => {
+                let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+                Some((
+                    file.clone(),
+                    format!("Parse error: {}", messages.join("\n  ")),
+                ))
+            }
+            CompileResult::Compiled(_) | CompileResult::Unsupported(_) => None,
+        })
+        .collect()
+}
+
 fn is_test_file(path: &Path) -> bool {
     path.file_name()
         .and_then(|name| name.to_str())
@@ -203,7 +253,7 @@ fn test_simple() : void {
         };
 
         let runner = TestRunner::new();
-        let result = runner.run_single_test(&test);
+        let result = runner.run_single_test(&test, &HashMap::new());
 
         assert!(result.result.is_pass());
     }
@@ -225,7 +275,7 @@ fn test_failing() : void {
         };
 
         let runner = TestRunner::new();
-        let result = runner.run_single_test(&test);
+        let result = runner.run_single_test(&test, &HashMap::new());
 
         assert!(result.result.is_fail());
     }
@@ -239,7 +289,7 @@ fn test_failing() : void {
         };
 
         let runner = TestRunner::new();
-        let result = runner.run_single_test(&test);
+        let result = runner.run_single_test(&test, &HashMap::new());
 
         assert!(result.result.is_fail());
         if let TestResult::Fail { error, .. } = result.result {
@@ -282,6 +332,9 @@ fn test_two() : void { test.assert(true, "ok"); }
 
         assert_eq!(results.len(), 2);
         assert!(results.iter().all(|r| r.result.is_pass()));
+        // The second test reuses the file parsed for the first
+        assert_eq!(runner.module_cache.misses(), 1);
+        assert_eq!(runner.module_cache.hits(), 1);
     }
 
     #[test]
@@ -363,7 +416,7 @@ fn test_process_env(): void {
         };
 
         let runner = TestRunner::new();
-        let result = runner.run_single_test(&test);
+        let result = runner.run_single_test(&test, &HashMap::new());
 
         match result.result {
             TestResult::Pass { .. } => {}
@@ -561,6 +561,195 @@ mod multi_command_workflow {
     }
 }
 
+// ══════════════════════════════════════════════════════════════════════════════
+// DAEMON WORKFLOW TESTS
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(unix)]
+mod daemon_workflow {
+    use super::*;
+    use std::path::Path;
+    use std::process::Child;
+    use std::time::{Duration, Instant};
+
+    /// Serve `root` from a daemon keeping its socket under `home`, once it answers
+    fn start_daemon(home: &Path, root: &Path) -> Child {
+        let daemon = atlas_cmd()
+            .args(["daemon", "serve", "--idle-timeout=0", "--root"])
+            .arg(root)
+            .env("ATLAS_HOME", home)
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .unwrap();
+        let started = Instant::now();
+        while !atlas_cmd()
+            .args(["daemon", "status", "--root"])
+            .arg(root)
+            .env("ATLAS_HOME", home)
+            .output()
+            .unwrap()
+            .status
+            .success()
+        {
+            assert!(
+                started.elapsed() < Duration::from_secs(10),
+                "daemon never answered"
+            );
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        daemon
+    }
+
+    fn daemon_status(home: &Path, root: &Path) -> serde_json::Value {
+        let status = atlas_cmd()
+            .args(["daemon", "status", "--json", "--root"])
+            .arg(root)
+            .env("ATLAS_HOME", home)
+            .output()
+            .unwrap();
+        serde_json::from_slice(&status.stdout).unwrap()
+    }
+
+    fn stop_daemon(home: &Path, root: &Path, mut daemon: Child) {
+        atlas_cmd()
+            .args(["daemon", "stop", "--root"])
+            .arg(root)
+            .env("ATLAS_HOME", home)
+            .assert()
+            .success();
+        assert!(daemon.wait().unwrap().success());
+    }
+
+    #[test]
+    fn test_run_through_daemon() {
+        let home = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+        fs::write(
+            project.path().join("lib.atl"),
+            "export fn twice(x: number): number { return x * 2; }",
+        )
+        .unwrap();
+        let main = project.path().join("main.atl");
+        fs::write(
+            &main,
+            "import { twice } from \"./lib\";\nconsole.log(twice(21));",
+        )
+        .unwrap();
+
+        let daemon = start_daemon(home.path(), project.path());
+
+        for _ in 0..2 {
+            atlas_cmd()
+                .arg("run")
+                .arg(&main)
+                .env("ATLAS_HOME", home.path())
+                .assert()
+                .success()
+                .stdout(predicate::str::contains("42"));
+        }
+        fs::write(
+            project.path().join("lib.atl"),
+            "export fn twice(x: number): number { return x * 3; }",
+        )
+        .unwrap();
+        atlas_cmd()
+            .arg("run")
+            .arg(&main)
+            .env("ATLAS_HOME", home.path())
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("63"));
+
+        let status = daemon_status(home.path(), project.path());
+        // main and lib parsed once, reused on the second run; lib re-parsed
+        // after its change, main reused
+        assert_eq!(status["module_misses"], 3);
+        assert_eq!(status["module_hits"], 3);
+
+        stop_daemon(home.path(), project.path(), daemon);
+        atlas_cmd()
+            .args(["daemon", "status", "--root"])
+            .arg(project.path())
+            .env("ATLAS_HOME", home.path())
+            .assert()
+            .failure();
+    }
+
+    /// A project whose `main.atl` and tests import `lib.atl`, plus one test
+    /// file that does not type-check
+    fn check_test_project() -> TempDir {
+        let project = TempDir::new().unwrap();
+        fs::write(
+            project.path().join("lib.atl"),
+            "export fn twice(x: number): number { return x * 2; }",
+        )
+        .unwrap();
+        fs::write(
+            project.path().join("main.atl"),
+            "import { twice } from \"./lib\";\nconsole.log(twice(21));",
+        )
+        .unwrap();
+        fs::write(
+            project.path().join("lib.test.atl"),
+            "import { twice } from \"./lib\";\nfn test_twice(): void { test.assert(twice(2) == 4, \"twice\"); }",
+        )
+        .unwrap();
+        fs::write(
+            project.path().join("broken.test.atl"),
+            "fn test_broken(): void { let x: number = \"s\"; }",
+        )
+        .unwrap();
+        project
+    }
+
+    /// `check` and `test` give the same verdicts with or without a daemon
+    fn assert_check_and_test(home: &Path, project: &Path) {
+        atlas_cmd()
+            .arg("check")
+            .arg(project.join("main.atl"))
+            .env("ATLAS_HOME", home)
+            .assert()
+            .success();
+        atlas_cmd()
+            .arg("check")
+            .arg(project.join("broken.test.atl"))
+            .env("ATLAS_HOME", home)
+            .assert()
+            .failure();
+        atlas_cmd()
+            .args(["test", "--json", "--dir"])
+            .arg(project)
+            .env("ATLAS_HOME", home)
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains(r#""passed":1"#))
+            .stdout(predicate::str::contains(r#""failed":1"#));
+    }
+
+    #[test]
+    fn test_check_and_test_through_daemon() {
+        let home = TempDir::new().unwrap();
+        let project = check_test_project();
+        let daemon = start_daemon(home.path(), project.path());
+
+        assert_check_and_test(home.path(), project.path());
+        let status = daemon_status(home.path(), project.path());
+        // check: main, lib, broken; test: lib.test (lib reused), broken reused
+        assert_eq!(status["module_misses"], 4);
+        assert_eq!(status["module_hits"], 2);
+
+        stop_daemon(home.path(), project.path(), daemon);
+    }
+
+    #[test]
+    fn test_check_and_test_without_daemon() {
+        let home = TempDir::new().unwrap();
+        let project = check_test_project();
+        assert_check_and_test(home.path(), project.path());
+        assert!(!home.path().join("daemon").exists());
+    }
+}
+
 // ══════════════════════════════════════════════════════════════════════════════
 // EDGE CASE TESTS
 // ══════════════════════════════════════════════════════════════════════════════
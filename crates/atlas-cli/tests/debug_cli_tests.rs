@@ -501,3 +501,41 @@ fn test_debug_location_aliases() {
         .assert()
         .success();
 }
+
+// ── Debug Adapter Protocol ────────────────────────────────────────────────────
+
+fn dap_message(seq: u32, command: &str, arguments: &str) -> String {
+    let body = format!(
+        r#"{{"seq":{seq},"type":"request","command":"{command}","arguments":{arguments}}}"#
+    );
+    format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+#[test]
+fn test_debug_dap_over_stdio() {
+    let file = create_test_file("let x = 1;\nconsole.log(\"hi from atlas\");\n");
+    let program = serde_json::to_string(file.path().to_str().unwrap()).unwrap();
+    let input = [
+        dap_message(1, "initialize", r#"{"adapterID":"atlas"}"#),
+        dap_message(2, "launch", &format!(r#"{{"program":{program}}}"#)),
+        dap_message(3, "configurationDone", "{}"),
+        dap_message(4, "disconnect", "{}"),
+    ]
+    .concat();
+
+    let mut cmd = atlas_cmd();
+    cmd.args(["debug", "--dap"])
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("Content-Length: "))
+        .stdout(predicate::str::contains(r#""event":"initialized""#))
+        .stdout(predicate::str::contains(r#""output":"hi from atlas""#))
+        .stdout(predicate::str::contains(r#""event":"terminated""#));
+}
+
+#[test]
+fn test_debug_requires_file_without_dap() {
+    let mut cmd = atlas_cmd();
+    cmd.arg("debug").assert().failure();
+}
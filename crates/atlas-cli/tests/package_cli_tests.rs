@@ -15,6 +15,14 @@ fn atlas() -> Command {
     assert_cmd::cargo::cargo_bin_cmd!("atlas")
 }
 
+/// `atlas install` against an empty local registry, so registry deps fail
+/// to resolve without touching the network
+fn install(registry: &TempDir) -> Command {
+    let mut cmd = atlas();
+    cmd.arg("install").arg("--registry").arg(registry.path());
+    cmd
+}
+
 fn create_test_project(dir: &std::path::Path) {
     let manifest = r#"[package]
 name = "test-project"
@@ -333,18 +341,17 @@ fn test_remove_dry_run() {
 // ============================================================================
 
 #[test]
-fn test_install_errors_without_registry() {
+fn test_install_errors_for_unknown_registry_package() {
     let temp = TempDir::new().unwrap();
+    let registry = TempDir::new().unwrap();
     create_test_project(temp.path());
 
-    atlas()
-        .args(["install", "--quiet"])
+    install(&registry)
+        .arg("--quiet")
         .current_dir(temp.path())
         .assert()
         .failure()
-        .stderr(predicate::str::contains(
-            "registry deps require a community index",
-        ));
+        .stderr(predicate::str::contains("Package not found"));
 
     assert!(!temp.path().join("atlas.lock").exists());
 }
@@ -352,16 +359,15 @@ fn test_install_errors_without_registry() {
 #[test]
 fn test_install_does_not_create_modules_dir() {
     let temp = TempDir::new().unwrap();
+    let registry = TempDir::new().unwrap();
     create_test_project(temp.path());
 
-    atlas()
-        .args(["install", "--quiet"])
+    install(&registry)
+        .arg("--quiet")
         .current_dir(temp.path())
         .assert()
         .failure()
-        .stderr(predicate::str::contains(
-            "registry deps require a community index",
-        ));
+        .stderr(predicate::str::contains("Package not found"));
 
     assert!(!temp.path().join("atlas_modules").exists());
 }
@@ -398,42 +404,38 @@ fn test_install_dry_run() {
 #[test]
 fn test_install_production_flag() {
     let temp = TempDir::new().unwrap();
+    let registry = TempDir::new().unwrap();
     create_test_project(temp.path());
 
-    atlas()
-        .args(["install", "--production", "--quiet"])
+    install(&registry)
+        .args(["--production", "--quiet"])
         .current_dir(temp.path())
         .assert()
         .failure()
-        .stderr(predicate::str::contains(
-            "registry deps require a community index",
-        ));
+        .stderr(predicate::str::contains("Package not found"));
 }
 
 #[test]
 fn test_install_force_reinstall() {
     let temp = TempDir::new().unwrap();
+    let registry = TempDir::new().unwrap();
     create_test_project(temp.path());
 
     // First install should fail
-    atlas()
-        .args(["install", "--quiet"])
+    install(&registry)
+        .arg("--quiet")
         .current_dir(temp.path())
         .assert()
         .failure()
-        .stderr(predicate::str::contains(
-            "registry deps require a community index",
-        ));
+        .stderr(predicate::str::contains("Package not found"));
 
     // Force reinstall should also fail
-    atlas()
-        .args(["install", "--force", "--quiet"])
+    install(&registry)
+        .args(["--force", "--quiet"])
         .current_dir(temp.path())
         .assert()
         .failure()
-        .stderr(predicate::str::contains(
-            "registry deps require a community index",
-        ));
+        .stderr(predicate::str::contains("Package not found"));
 }
 
 #[test]
@@ -451,17 +453,16 @@ fn test_install_no_manifest_fails() {
 #[test]
 fn test_install_verbose() {
     let temp = TempDir::new().unwrap();
+    let registry = TempDir::new().unwrap();
     create_test_project(temp.path());
 
-    atlas()
-        .args(["install", "--verbose"])
+    install(&registry)
+        .arg("--verbose")
         .current_dir(temp.path())
         .assert()
         .failure()
         .stdout(predicate::str::contains("Reading manifest"))
-        .stderr(predicate::str::contains(
-            "registry deps require a community index",
-        ));
+        .stderr(predicate::str::contains("Package not found"));
 }
 
 // ============================================================================
@@ -618,6 +619,7 @@ fn test_publish_verbose() {
 #[test]
 fn test_init_add_install_workflow() {
     let temp = TempDir::new().unwrap();
+    let registry = TempDir::new().unwrap();
     let project_dir = temp.path().join("workflow-test");
     fs::create_dir(&project_dir).unwrap();
 
@@ -636,14 +638,12 @@ fn test_init_add_install_workflow() {
         .success();
 
     // Install
-    atlas()
-        .args(["install", "--quiet"])
+    install(&registry)
+        .arg("--quiet")
         .current_dir(&project_dir)
         .assert()
         .failure()
-        .stderr(predicate::str::contains(
-            "registry deps require a community index",
-        ));
+        .stderr(predicate::str::contains("Package not found"));
 
     assert!(!project_dir.join("atlas.lock").exists());
 }
@@ -677,17 +677,16 @@ fn test_add_remove_workflow() {
 #[test]
 fn test_install_update_workflow() {
     let temp = TempDir::new().unwrap();
+    let registry = TempDir::new().unwrap();
     create_test_project(temp.path());
 
     // Install
-    atlas()
-        .args(["install", "--quiet"])
+    install(&registry)
+        .arg("--quiet")
         .current_dir(temp.path())
         .assert()
         .failure()
-        .stderr(predicate::str::contains(
-            "registry deps require a community index",
-        ));
+        .stderr(predicate::str::contains("Package not found"));
 
     assert!(!temp.path().join("atlas.lock").exists());
 
@@ -28,6 +28,10 @@ pub struct SecurityConfig {
     /// Environment variable permissions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub environment: Option<EnvironmentPermissions>,
+
+    /// Database (SQLite file) permissions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<DatabasePermissions>,
 }
 
 /// Filesystem permissions
@@ -89,12 +93,32 @@ pub struct EnvironmentPermissions {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub allow: Vec<String>,
 
+    /// Environment variables allowed to set and unset
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub write: Vec<String>,
+
     /// Environment variables explicitly denied
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub deny: Vec<String>,
 }
 
+/// Database permissions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DatabasePermissions {
+    /// Database files (or directories of them) allowed to be opened
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<PathBuf>,
+
+    /// Paths explicitly denied
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<PathBuf>,
+}
+
 impl SecurityConfig {
     /// Get the security mode (default: "standard")
     pub fn mode(&self) -> &str {
@@ -129,12 +153,18 @@ deny = ["*"]
 
 [environment]
 allow = ["PATH", "HOME"]
+write = ["APP_MODE"]
+
+[database]
+allow = ["./data"]
 "#;
 
         let config: SecurityConfig = toml::from_str(toml).unwrap();
         assert_eq!(config.mode(), "strict");
         assert_eq!(config.filesystem.as_ref().unwrap().read.len(), 2);
         assert_eq!(config.network.as_ref().unwrap().allow.len(), 1);
+        assert_eq!(config.database.as_ref().unwrap().allow.len(), 1);
+        assert_eq!(config.environment.as_ref().unwrap().write, vec!["APP_MODE"]);
     }
 
     #[test]
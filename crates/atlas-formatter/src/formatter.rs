@@ -90,12 +90,23 @@ impl Formatter {
 
         // Step 3: Parse source into AST (using a fresh lexer, since parser expects no comments)
         let mut parse_lexer = Lexer::new(source);
-        let (parse_tokens, _) = parse_lexer.tokenize();
+        let (parse_tokens, lex_diags) = parse_lexer.tokenize();
+        // The AST of a file using macros holds their expansions, not the
+        // source as written
+        if Parser::uses_macros(&parse_tokens) {
+            return FormatResult::Ok(source.to_string());
+        }
         let mut parser = Parser::new(parse_tokens);
         let (program, parse_diags) = parser.parse();
 
-        if !parse_diags.is_empty() {
-            let errors: Vec<String> = parse_diags.iter().map(|d| d.message.clone()).collect();
+        // The lexer closes an unterminated string so parsing can go on; the
+        // source still must not be rewritten as if the quote were there
+        if !lex_diags.is_empty() || !parse_diags.is_empty() {
+            let errors: Vec<String> = lex_diags
+                .iter()
+                .chain(&parse_diags)
+                .map(|d| d.message.clone())
+                .collect();
             return FormatResult::ParseError(errors);
         }
 
@@ -273,7 +273,7 @@ impl FormatVisitor {
     fn visit_statement(&mut self, stmt: &Stmt) {
         match stmt {
             Stmt::VarDecl(v) => self.visit_var_decl(v),
-            Stmt::LetDestructure(_) => { /* B15-P06 */ }
+            Stmt::LetDestructure(d) => self.visit_let_destructure(d),
             Stmt::FunctionDecl(f) => self.visit_function_decl(f),
             Stmt::Assign(a) => self.visit_assign(a),
             Stmt::CompoundAssign(c) => self.visit_compound_assign(c),
@@ -300,6 +300,7 @@ impl FormatVisitor {
                 self.visit_block(&d.body);
                 self.writeln();
             }
+            Stmt::TryCatch(t) => self.visit_try_catch(t),
         }
     }
 
@@ -324,6 +325,38 @@ impl FormatVisitor {
         self.writeln();
     }
 
+    fn visit_let_destructure(&mut self, d: &LetDestructure) {
+        self.write_indent();
+        if d.mutable {
+            self.write("let mut ");
+        } else {
+            self.write("let ");
+        }
+        let (open, close) = match d.kind {
+            DestructureKind::Tuple => ("(", ")"),
+            DestructureKind::Array { .. } => ("[", "]"),
+            DestructureKind::Map => ("{ ", " }"),
+        };
+        let rest = matches!(d.kind, DestructureKind::Array { rest: true });
+        self.write(open);
+        for (idx, name) in d.names.iter().enumerate() {
+            if idx > 0 {
+                self.write(", ");
+            }
+            if rest && idx == d.names.len() - 1 {
+                self.write("...");
+            }
+            self.write(&name.name);
+        }
+        self.write(close);
+
+        self.write(" = ");
+        self.visit_expr(&d.init);
+        self.write(";");
+        self.emit_trailing_comment(d.span.end);
+        self.writeln();
+    }
+
     fn visit_type_alias(&mut self, alias: &TypeAliasDecl) {
         self.write_indent();
         self.write("type ");
@@ -456,6 +489,20 @@ impl FormatVisitor {
         self.writeln();
     }
 
+    fn visit_try_catch(&mut self, t: &TryCatchStmt) {
+        self.write_indent();
+        self.write("try ");
+        self.visit_block(&t.body);
+        self.write(" catch ");
+        if let Some(ref binding) = t.error_binding {
+            self.write("(");
+            self.write(&binding.name);
+            self.write(") ");
+        }
+        self.visit_block(&t.catch_block);
+        self.writeln();
+    }
+
     fn visit_return(&mut self, r: &ReturnStmt) {
         self.write_indent();
         self.write("return");
@@ -591,6 +638,8 @@ impl FormatVisitor {
             Expr::Identifier(id) => self.write(&id.name),
             Expr::Unary(u) => self.visit_unary(u),
             Expr::Binary(b) => self.visit_binary(b),
+            Expr::Call(c) if c.is_pipeline() => self.visit_pipeline(expr),
+            Expr::Member(m) if m.is_pipeline() => self.visit_pipeline(expr),
             Expr::Call(c) => self.visit_call(c),
             Expr::Index(i) => self.visit_index(i),
             Expr::Member(m) => self.visit_member(m),
@@ -733,6 +782,12 @@ impl FormatVisitor {
             }
             Literal::Bool(b) => self.write(if *b { "true" } else { "false" }),
             Literal::Null => self.write("null"),
+            Literal::Regex { pattern, flags } => {
+                self.write("/");
+                self.write(pattern);
+                self.write("/");
+                self.write(flags);
+            }
         }
     }
 
@@ -760,6 +815,8 @@ impl FormatVisitor {
             BinaryOp::Ge => " >= ",
             BinaryOp::And => " && ",
             BinaryOp::Or => " || ",
+            BinaryOp::Coalesce => " ?? ",
+            BinaryOp::In => " in ",
         };
         self.write(op);
         self.visit_expr(&b.right);
@@ -767,16 +824,82 @@ impl FormatVisitor {
 
     fn visit_call(&mut self, c: &CallExpr) {
         self.visit_expr(&c.callee);
+        self.write_call_args(&c.args);
+    }
+
+    /// Write a parenthesized argument list, one argument per line if it is too wide
+    fn write_call_args(&mut self, args: &[Expr]) {
         self.write("(");
-        let args_str = self.format_args(&c.args);
-        if self.would_exceed_max_width(&args_str) && c.args.len() > 1 {
-            self.write_args_multiline(&c.args);
+        let args_str = self.format_args(args);
+        if self.would_exceed_max_width(&args_str) && args.len() > 1 {
+            self.write_args_multiline(args);
         } else {
             self.write(&args_str);
         }
         self.write(")");
     }
 
+    /// Write a pipeline `head |> stage |> stage`, given its last stage.
+    ///
+    /// The chain stays on one line if it fits; otherwise every stage goes on
+    /// its own line, one level deeper than the head, so the stages align.
+    fn visit_pipeline(&mut self, last: &Expr) {
+        let mut stages = Vec::new();
+        let mut head = last;
+        while let Some(piped) = piped_value(head) {
+            stages.push(head);
+            head = piped;
+        }
+        stages.reverse();
+
+        let mut measure = FormatVisitor::new(self.config.clone(), Vec::new(), self.source.clone());
+        measure.visit_expr(head);
+        for stage in &stages {
+            measure.write(" |> ");
+            measure.write_stage(stage);
+        }
+
+        let multiline = stages.len() > 1 && self.would_exceed_max_width(&measure.output);
+        self.visit_expr(head);
+        if multiline {
+            self.indent_level += 1;
+            for stage in stages {
+                self.writeln();
+                self.write_indent();
+                self.write("|> ");
+                self.write_stage(stage);
+            }
+            self.indent_level -= 1;
+        } else {
+            for stage in stages {
+                self.write(" |> ");
+                self.write_stage(stage);
+            }
+        }
+    }
+
+    /// Write a pipeline stage without its piped value: `f(y)` for `f(x, y)`.
+    /// A stage written without parentheses (`x |> f`) keeps that form.
+    fn write_stage(&mut self, stage: &Expr) {
+        match stage {
+            Expr::Call(c) => {
+                self.visit_expr(&c.callee);
+                if c.span.end > c.callee.span().end {
+                    self.write_call_args(&c.args[1..]);
+                }
+            }
+            Expr::Member(m) => {
+                self.visit_expr(&m.target);
+                self.write(if m.optional { "?." } else { "." });
+                self.write(&m.member.name);
+                if let Some(args) = &m.args {
+                    self.write_call_args(&args[1..]);
+                }
+            }
+            _ => self.visit_expr(stage),
+        }
+    }
+
     fn format_args(&self, args: &[Expr]) -> String {
         let mut visitor = FormatVisitor::new(self.config.clone(), Vec::new(), self.source.clone());
         let parts: Vec<String> = args
@@ -816,17 +939,10 @@ impl FormatVisitor {
 
     fn visit_member(&mut self, m: &MemberExpr) {
         self.visit_expr(&m.target);
-        self.write(".");
+        self.write(if m.optional { "?." } else { "." });
         self.write(&m.member.name);
         if let Some(ref args) = m.args {
-            self.write("(");
-            let args_str = self.format_args(args);
-            if self.would_exceed_max_width(&args_str) && args.len() > 1 {
-                self.write_args_multiline(args);
-            } else {
-                self.write(&args_str);
-            }
-            self.write(")");
+            self.write_call_args(args);
         }
     }
 
@@ -925,7 +1041,7 @@ impl FormatVisitor {
                     self.write(")");
                 }
             }
-            Pattern::Array { elements, .. } => {
+            Pattern::Array { elements, rest, .. } => {
                 self.write("[");
                 for (i, elem) in elements.iter().enumerate() {
                     if i > 0 {
@@ -933,11 +1049,28 @@ impl FormatVisitor {
                     }
                     self.visit_pattern(elem);
                 }
+                if let Some(rest) = rest {
+                    if !elements.is_empty() {
+                        self.write(", ");
+                    }
+                    self.write("...");
+                    self.visit_pattern(rest);
+                }
                 self.write("]");
             }
             Pattern::Tuple { .. } => {
                 // Tuple pattern formatting not yet implemented
             }
+            Pattern::Range {
+                start,
+                end,
+                inclusive,
+                ..
+            } => {
+                self.visit_literal(&Literal::Number(*start));
+                self.write(if *inclusive { "..=" } else { ".." });
+                self.visit_literal(&Literal::Number(*end));
+            }
             Pattern::Or(alternatives, _) => {
                 for (i, alt) in alternatives.iter().enumerate() {
                     if i > 0 {
@@ -992,7 +1125,14 @@ impl FormatVisitor {
                     if i > 0 {
                         self.write(", ");
                     }
-                    self.write(&field.name.name);
+                    // Keys that are not identifiers were written quoted
+                    if is_identifier(&field.name.name) {
+                        self.write(&field.name.name);
+                    } else {
+                        self.write("\"");
+                        self.write(&escape_string(&field.name.name));
+                        self.write("\"");
+                    }
                     if let Some(sub) = &field.pattern {
                         self.write(": ");
                         self.visit_pattern(sub);
@@ -1214,6 +1354,15 @@ impl FormatVisitor {
     }
 }
 
+/// The value piped into a pipeline stage, if `expr` is one
+fn piped_value(expr: &Expr) -> Option<&Expr> {
+    match expr {
+        Expr::Call(c) if c.is_pipeline() => c.args.first(),
+        Expr::Member(m) if m.is_pipeline() => m.args.as_ref().and_then(|args| args.first()),
+        _ => None,
+    }
+}
+
 fn extern_type_str(ty: &ExternTypeAnnotation) -> &'static str {
     match ty {
         ExternTypeAnnotation::CInt => "c_int",
@@ -1225,6 +1374,14 @@ fn extern_type_str(ty: &ExternTypeAnnotation) -> &'static str {
     }
 }
 
+fn is_identifier(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && atlas_runtime::TokenKind::is_keyword(name).is_none()
+}
+
 fn escape_string(s: &str) -> String {
     let mut result = String::with_capacity(s.len());
     for c in s.chars() {
@@ -45,6 +45,11 @@ fn test_string_literal() {
     );
 }
 
+#[test]
+fn test_regex_literal() {
+    assert_eq!(fmt("let r = /^a\\/b+$/im;"), "let r = /^a\\/b+$/im;\n");
+}
+
 #[test]
 fn test_boolean_literals() {
     assert_eq!(
@@ -315,6 +320,69 @@ fn test_match_constructor_patterns() {
     );
 }
 
+#[test]
+fn test_match_array_rest_pattern() {
+    assert_eq!(
+        fmt("let r = match xs { [] => 0, [h,...t] => h, };"),
+        "let r = match xs {\n    [] => 0,\n    [h, ...t] => h,\n};\n"
+    );
+}
+
+#[test]
+fn test_match_quoted_key_pattern() {
+    assert_eq!(
+        fmt("let r = match v { {\"type\":\"user\",\"name\":n} => n, _ => v, };"),
+        "let r = match v {\n    { \"type\": \"user\", name: n } => n,\n    _ => v,\n};\n"
+    );
+}
+
+#[test]
+fn test_null_safe_operators() {
+    assert_eq!(
+        fmt("let c = user?.address?.city(1)??\"none\";"),
+        "let c = user?.address?.city(1) ?? \"none\";\n"
+    );
+}
+
+#[test]
+fn test_membership_and_comparison_chain() {
+    assert_eq!(
+        fmt("let ok = x in xs&&0<=i<n;"),
+        "let ok = x in xs && 0 <= i < n;\n"
+    );
+}
+
+#[test]
+fn test_pipeline_stays_on_one_line_when_it_fits() {
+    assert_eq!(
+        fmt("let n = xs|>filter(isOdd)|>len;"),
+        "let n = xs |> filter(isOdd) |> len;\n"
+    );
+    assert_eq!(
+        fmt("let r = text |> Json.parse()?;"),
+        "let r = text |> Json.parse()?;\n"
+    );
+}
+
+#[test]
+fn test_long_pipeline_aligns_stages() {
+    let source = "let total = orders |> filter(isOpen) |> map(orderTotal) |> reduce(sum, 0) |> Math.round();";
+    let config = FormatConfig::default().with_max_width(60);
+    let expected = "let total = orders\n    |> filter(isOpen)\n    |> map(orderTotal)\n    |> reduce(sum, 0)\n    |> Math.round();\n";
+    assert_eq!(fmt_with(source, &config), expected);
+    assert_eq!(fmt_with(expected, &config), expected);
+}
+
+#[test]
+fn test_let_destructure() {
+    assert_eq!(
+        fmt("let mut [a,b,...rest] = xs;"),
+        "let mut [a, b, ...rest] = xs;\n"
+    );
+    assert_eq!(fmt("let {host,port} = cfg;"), "let { host, port } = cfg;\n");
+    assert_eq!(fmt("let (x,y) = pair;"), "let (x, y) = pair;\n");
+}
+
 // === Indentation Configuration ===
 
 #[test]
@@ -67,7 +67,7 @@ impl HotspotTracker {
             .filter(|(&offset, &count)| count >= self.threshold && !self.is_compiled(offset))
             .map(|(&offset, &count)| HotFunction { offset, count })
             .collect();
-        hot.sort_by(|a, b| b.count.cmp(&a.count));
+        hot.sort_by_key(|h| std::cmp::Reverse(h.count));
         hot
     }
 
@@ -156,6 +156,7 @@ pub fn extract_function_boundaries(bytecode: &Bytecode) -> Vec<FunctionBoundary>
                 | Opcode::Jump
                 | Opcode::JumpIfFalse
                 | Opcode::Loop
+                | Opcode::TryBegin
                 | Opcode::Array => {
                     ip += 3; // opcode + u16
                 }
@@ -3,19 +3,61 @@
 //! Runs multiple module bytecodes in dependency order on a single VM so that
 //! globals defined by dependency modules are visible to the entry point.
 
-use atlas_runtime::{Bytecode, SecurityContext, VM};
+use crate::self_path::EmbeddedProgram;
+use atlas_config::SecurityConfig;
+use atlas_runtime::{Bytecode, RunOutcome, RuntimeError, SecurityContext, Value, VM};
+use serde::Deserialize;
+
+/// Run settings written by `atlas bundle`.
+///
+/// Must stay field-for-field compatible with `atlas_build::binary_emit::BundleManifest`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BundleManifest {
+    /// The entry module declares `fn main`; its return value is the exit code
+    #[serde(default)]
+    pub main: bool,
+    /// Permissions the program runs with; `None` allows everything
+    #[serde(default)]
+    pub security: Option<SecurityConfig>,
+}
 
 /// Deserialize and execute all module bytecodes in order.
 ///
 /// `module_bytecodes` must be in dependency order: dependencies first, entry point last.
 /// Returns exit code: 0 on success, 1 on any error.
 pub fn run_bytecodes(module_bytecodes: &[Vec<u8>]) -> i32 {
+    run_program(&EmbeddedProgram {
+        modules: module_bytecodes.to_vec(),
+        manifest: None,
+    })
+}
+
+/// Execute an embedded program under its bundle manifest, if any.
+///
+/// Without a manifest the program runs with full permissions and exits 0 on
+/// success. With one, the baked security settings apply and a `fn main`
+/// program exits with main's return code. Any error exits 1.
+pub fn run_program(program: &EmbeddedProgram) -> i32 {
+    let module_bytecodes = &program.modules;
     if module_bytecodes.is_empty() {
         eprintln!("atlas: embedded program contains no modules");
         return 1;
     }
 
-    let security = SecurityContext::allow_all();
+    let manifest = match &program.manifest {
+        Some(bytes) => match serde_json::from_slice::<BundleManifest>(bytes) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                eprintln!("atlas: failed to read embedded bundle manifest: {e}");
+                return 1;
+            }
+        },
+        None => BundleManifest::default(),
+    };
+    let security = match &manifest.security {
+        Some(config) => SecurityContext::from_config(config),
+        None => SecurityContext::allow_all(),
+    };
 
     // Deserialize the first module and create the VM
     let first_bc = match Bytecode::from_bytes(&module_bytecodes[0]) {
@@ -26,10 +68,13 @@ pub fn run_bytecodes(module_bytecodes: &[Vec<u8>]) -> i32 {
         }
     };
     let mut vm = VM::new(first_bc);
-    if let Err(e) = vm.run(&security) {
-        eprintln!("atlas: runtime error in module 0: {e}");
-        return 1;
-    }
+    let mut last_value = match vm.run(&security) {
+        Ok(value) => value,
+        Err(e) => {
+            report_runtime_error(&mut vm, 0, &e);
+            return 1;
+        }
+    };
 
     // Load and run each subsequent module on the SAME VM (globals are preserved)
     for (i, bc_bytes) in module_bytecodes[1..].iter().enumerate() {
@@ -44,11 +89,36 @@ pub fn run_bytecodes(module_bytecodes: &[Vec<u8>]) -> i32 {
             }
         };
         vm.load_module(bc);
-        if let Err(e) = vm.run(&security) {
-            eprintln!("atlas: runtime error in module {}: {e}", i + 1);
-            return 1;
+        last_value = match vm.run(&security) {
+            Ok(value) => value,
+            Err(e) => {
+                report_runtime_error(&mut vm, i + 1, &e);
+                return 1;
+            }
+        };
+    }
+
+    match RunOutcome::new(last_value.unwrap_or(Value::Null), manifest.main) {
+        RunOutcome::Script(_) => 0,
+        RunOutcome::Exited { code, error } => {
+            if let Some(error) = error {
+                eprintln!("error: {error}");
+            }
+            code
         }
     }
+}
 
-    0
+/// Print a runtime error followed by the call stack that led to it.
+fn report_runtime_error(vm: &mut VM, module: usize, error: &RuntimeError) {
+    eprintln!("atlas: runtime error in module {module}: {error}");
+    let stack_trace = vm.take_error_stack_trace();
+    if stack_trace.len() > 1 {
+        for frame in stack_trace {
+            eprintln!(
+                "  at {} ({}:{}:{})",
+                frame.function, frame.file, frame.line, frame.column
+            );
+        }
+    }
 }
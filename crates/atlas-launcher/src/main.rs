@@ -1,13 +1,12 @@
 //! Atlas native binary launcher
 //!
-//! This binary is embedded into Atlas executables produced by `atlas build`.
+//! This binary is embedded into Atlas executables produced by `atlas build`
+//! and `atlas bundle`.
 //! At runtime it reads bytecode appended to itself (after a magic sentinel),
 //! then executes it using the Atlas VM.
 //!
 //! See B19 for the full design: self-appending launcher pattern.
 
-mod execute;
-
 fn main() {
     let exe_path = match std::env::current_exe() {
         Ok(p) => p,
@@ -17,8 +16,8 @@ fn main() {
         }
     };
 
-    let modules = match atlas_launcher::self_path::find_appended_bytecode(&exe_path) {
-        Ok(Some(modules)) => modules,
+    let program = match atlas_launcher::self_path::find_embedded_program(&exe_path) {
+        Ok(Some(program)) => program,
         Ok(None) => {
             eprintln!(
                 "atlas: this binary has no embedded Atlas program. It may be corrupted.\n\
@@ -32,6 +31,6 @@ fn main() {
         }
     };
 
-    let exit_code = execute::run_bytecodes(&modules);
+    let exit_code = atlas_launcher::execute::run_program(&program);
     std::process::exit(exit_code);
 }
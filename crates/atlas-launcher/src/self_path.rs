@@ -8,6 +8,9 @@
 //!   [payload_len: u64 LE]      ← byte count from module_count to before ATLAS_BC_MAGIC
 //!
 //! The launcher executes each module in order on a single VM so globals accumulate.
+//!
+//! Bundles add `[manifest_len: u32 LE][manifest_json]` after the last module,
+//! still inside the payload (see `atlas_build::binary_emit`).
 
 use std::io::{self, Read, Seek, SeekFrom};
 use std::path::PathBuf;
@@ -32,11 +35,26 @@ pub fn current_exe_path() -> io::Result<PathBuf> {
     std::env::current_exe()
 }
 
+/// Everything appended to a launcher binary
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddedProgram {
+    /// Module bytecodes in dependency order (dependencies first, entry point last)
+    pub modules: Vec<Vec<u8>>,
+    /// Bundle manifest JSON, present in archives written by `atlas bundle`
+    pub manifest: Option<Vec<u8>>,
+}
+
 /// Read the trailer from the last 24 bytes and extract all embedded module bytecodes.
 ///
 /// Returns the bytecodes in dependency order (dependencies first, entry point last).
 /// Returns None if the file has no valid Atlas trailer.
 pub fn find_appended_bytecode(exe_path: &std::path::Path) -> io::Result<Option<Vec<Vec<u8>>>> {
+    Ok(find_embedded_program(exe_path)?.map(|program| program.modules))
+}
+
+/// Like [`find_appended_bytecode`], also returning the bundle manifest if the
+/// archive has one.
+pub fn find_embedded_program(exe_path: &std::path::Path) -> io::Result<Option<EmbeddedProgram>> {
     let mut file = std::fs::File::open(exe_path)?;
     let file_len = file.seek(SeekFrom::End(0))?;
 
@@ -91,5 +109,18 @@ pub fn find_appended_bytecode(exe_path: &std::path::Path) -> io::Result<Option<V
         pos += bc_len;
     }
 
-    Ok(Some(modules))
+    // Optional trailing manifest section
+    let manifest = if pos + 4 <= payload.len() {
+        let manifest_len =
+            u32::from_le_bytes(payload[pos..pos + 4].try_into().expect("4 bytes")) as usize;
+        pos += 4;
+        if pos + manifest_len > payload.len() {
+            return Ok(None);
+        }
+        Some(payload[pos..pos + manifest_len].to_vec())
+    } else {
+        None
+    };
+
+    Ok(Some(EmbeddedProgram { modules, manifest }))
 }
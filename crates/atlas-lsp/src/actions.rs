@@ -1,14 +1,18 @@
 //! Code actions provider for LSP
 //!
 //! Provides:
-//! - Quick fixes for diagnostics (5+ types)
+//! - Quick fixes for diagnostics, chosen by error code (missing return type,
+//!   `let` to `let mut`, `_` prefix for unused names, missing imports, ...)
 //! - Refactoring actions (3+ types)
 //! - Source actions (organize imports, etc.)
 
 use atlas_runtime::ast::*;
 use atlas_runtime::diagnostic::error_codes;
+use atlas_runtime::span::Span;
 use atlas_runtime::symbol::SymbolTable;
-use atlas_runtime::Diagnostic;
+use atlas_runtime::typechecker::inference::{infer_return_type, InferredReturn};
+use atlas_runtime::{Diagnostic, Lexer, Parser, Type};
+use std::path::{Component, Path};
 use tower_lsp::lsp_types::*;
 
 /// Code action kind constants
@@ -91,6 +95,16 @@ fn generate_quick_fixes(
     let mut actions = Vec::new();
 
     match code {
+        // AT1000: Syntax error - fix return type annotations
+        c if c == error_codes::SYNTAX_ERROR.code => {
+            if let Some(action) = add_missing_return_type(uri, text, lsp_diag) {
+                actions.push(action);
+            }
+            if let Some(action) = replace_return_arrow(uri, text, lsp_diag) {
+                actions.push(action);
+            }
+        }
+
         // AT0002: Undefined symbol - suggest declaration
         c if c == error_codes::UNDEFINED_SYMBOL.code => {
             if let Some(action) = fix_undefined_symbol(uri, text, lsp_diag) {
@@ -105,7 +119,7 @@ fn generate_quick_fixes(
             }
         }
 
-        // AT3003: Immutable assignment - suggest var instead of let
+        // AT3003: Immutable assignment - suggest let mut instead of let
         c if c == error_codes::IMMUTABLE_ASSIGNMENT.code => {
             if let Some(action) = fix_immutable_assignment(uri, text, lsp_diag) {
                 actions.push(action);
@@ -238,40 +252,30 @@ fn fix_immutable_assignment(
     text: &str,
     diag: &tower_lsp::lsp_types::Diagnostic,
 ) -> Option<CodeActionOrCommand> {
-    // Find the variable declaration that needs to be changed
     let identifier = extract_identifier_from_message(&diag.message)?;
 
-    // Search for the declaration in the text
-    let lines: Vec<&str> = text.lines().collect();
-
-    for (line_num, line) in lines.iter().enumerate() {
-        // Look for "let <identifier>" (but not "let mut <identifier>")
-        if let Some(pos) = line.find(&format!("let {}", identifier)) {
-            // Insert "mut " after "let "
-            let insert_pos = Position {
-                line: line_num as u32,
-                character: (pos + 4) as u32, // after "let "
-            };
-
-            let edit = TextEdit {
-                range: Range {
-                    start: insert_pos,
-                    end: insert_pos,
-                },
-                new_text: "mut ".to_string(),
-            };
+    // The declaration in scope is the nearest `let <identifier>` above the assignment
+    let (line, character) = find_let_declaration(text, &identifier, diag.range.start.line)?;
+    let insert_pos = Position {
+        line,
+        character: character - identifier.len() as u32,
+    };
 
-            return Some(create_code_action(
-                format!("Change '{}' to mutable (let mut)", identifier),
-                uri.clone(),
-                vec![edit],
-                action_kinds::quick_fix(),
-                Some(diag.clone()),
-            ));
-        }
-    }
+    let edit = TextEdit {
+        range: Range {
+            start: insert_pos,
+            end: insert_pos,
+        },
+        new_text: "mut ".to_string(),
+    };
 
-    None
+    Some(create_code_action(
+        format!("Change '{}' to mutable (let mut)", identifier),
+        uri.clone(),
+        vec![edit],
+        action_kinds::quick_fix(),
+        Some(diag.clone()),
+    ))
 }
 
 /// Fix unused variable by prefixing with underscore
@@ -287,6 +291,22 @@ fn fix_unused_variable(
         return None;
     }
 
+    // The warning points at the name itself, which also covers parameters
+    // and loop variables
+    if extract_text_in_range(text, diag.range) == identifier {
+        let edit = TextEdit {
+            range: diag.range,
+            new_text: format!("_{}", identifier),
+        };
+        return Some(create_code_action(
+            format!("Prefix '{}' with underscore", identifier),
+            uri.clone(),
+            vec![edit],
+            action_kinds::quick_fix(),
+            Some(diag.clone()),
+        ));
+    }
+
     // Find the declaration
     let lines: Vec<&str> = text.lines().collect();
 
@@ -466,6 +486,268 @@ fn add_wildcard_arm(
     None
 }
 
+/// Add the return type a named function is missing, inferred from its
+/// `return` statements. Offered only when every returned value has a
+/// known type.
+fn add_missing_return_type(
+    uri: &Url,
+    text: &str,
+    diag: &tower_lsp::lsp_types::Diagnostic,
+) -> Option<CodeActionOrCommand> {
+    if !diag.message.contains("return type annotation required") {
+        return None;
+    }
+
+    // The error points at the body's `{`; the annotation goes right after `)`
+    let line = text.lines().nth(diag.range.start.line as usize)?;
+    let found_at = (diag.range.start.character as usize).min(line.len());
+    let params_end = line.get(..found_at)?.trim_end();
+    if !params_end.ends_with(')') {
+        return None;
+    }
+    let insert_pos = Position {
+        line: diag.range.start.line,
+        character: params_end.len() as u32,
+    };
+
+    let return_type = infer_missing_return_type(text, insert_pos)?;
+
+    let edit = TextEdit {
+        range: Range {
+            start: insert_pos,
+            end: insert_pos,
+        },
+        new_text: format!(": {}", return_type),
+    };
+
+    Some(create_code_action(
+        format!("Add return type '{}'", return_type),
+        uri.clone(),
+        vec![edit],
+        action_kinds::quick_fix(),
+        Some(diag.clone()),
+    ))
+}
+
+/// Parse `text` with a placeholder `: void` at `at` and infer the return
+/// type of the function that contains it
+fn infer_missing_return_type(text: &str, at: Position) -> Option<String> {
+    let offset = crate::convert::position_to_offset(text, at);
+    let patched = format!("{}: void{}", &text[..offset], &text[offset..]);
+
+    let (tokens, _) = Lexer::new(&patched).tokenize();
+    let (program, _) = Parser::new(tokens).parse();
+
+    let mut bodies: Vec<(Span, &Block)> = Vec::new();
+    for item in &program.items {
+        match item {
+            Item::Function(func) => bodies.push((func.span, &func.body)),
+            Item::Export(ExportDecl {
+                item: ExportItem::Function(func),
+                ..
+            }) => bodies.push((func.span, &func.body)),
+            Item::Impl(block) => {
+                bodies.extend(block.methods.iter().map(|m| (m.span, &m.body)));
+            }
+            _ => {}
+        }
+    }
+    let (_, body) = bodies
+        .into_iter()
+        .filter(|(span, _)| span.start <= offset && offset < span.end)
+        .min_by_key(|(span, _)| span.end - span.start)?;
+
+    let return_type = match infer_return_type(body) {
+        InferredReturn::Void => return Some("void".to_string()),
+        InferredReturn::Uniform(ty) => ty,
+        InferredReturn::Inconsistent { types, .. } => Type::union(types),
+    };
+    let members = match &return_type {
+        Type::Union(members) => members.clone(),
+        other => vec![other.clone()],
+    };
+    if members.iter().all(is_known_type) {
+        Some(return_type.display_name())
+    } else {
+        None
+    }
+}
+
+/// Whether an inferred type is fully known, so it can be written down
+fn is_known_type(ty: &Type) -> bool {
+    match ty {
+        Type::Unknown => false,
+        Type::Array(elem) => is_known_type(elem),
+        other => !Type::is_any_placeholder(other),
+    }
+}
+
+/// Replace a Rust-style `-> T` return type arrow with `:`
+fn replace_return_arrow(
+    uri: &Url,
+    text: &str,
+    diag: &tower_lsp::lsp_types::Diagnostic,
+) -> Option<CodeActionOrCommand> {
+    if !diag.message.contains("not `-> T`") || extract_text_in_range(text, diag.range) != "->" {
+        return None;
+    }
+
+    // `fn f() -> T` becomes `fn f(): T`
+    let line = text.lines().nth(diag.range.start.line as usize)?;
+    let arrow_at = (diag.range.start.character as usize).min(line.len());
+    let start = Position {
+        line: diag.range.start.line,
+        character: line.get(..arrow_at)?.trim_end().len() as u32,
+    };
+
+    let edit = TextEdit {
+        range: Range {
+            start,
+            end: diag.range.end,
+        },
+        new_text: ":".to_string(),
+    };
+
+    Some(create_code_action(
+        "Replace '->' with ':'".to_string(),
+        uri.clone(),
+        vec![edit],
+        action_kinds::quick_fix(),
+        Some(diag.clone()),
+    ))
+}
+
+/// Generate "add missing import" fixes for undefined symbols.
+///
+/// `modules` are the other documents the server has parsed. For each
+/// undefined name that one of them exports, this offers an edit that imports
+/// it, joining an existing `import { ... } from` line for that module if the
+/// file has one.
+pub fn generate_import_fixes(
+    uri: &Url,
+    text: &str,
+    context: &CodeActionContext,
+    modules: &[(Url, &Program)],
+) -> Vec<CodeActionOrCommand> {
+    let mut actions = Vec::new();
+    let Ok(from) = uri.to_file_path() else {
+        return actions;
+    };
+
+    for diag in &context.diagnostics {
+        let is_undefined = matches!(
+            &diag.code,
+            Some(NumberOrString::String(c)) if c == error_codes::UNDEFINED_SYMBOL.code
+        );
+        if !is_undefined {
+            continue;
+        }
+        let Some(identifier) = extract_identifier_from_message(&diag.message) else {
+            continue;
+        };
+
+        for (module_uri, program) in modules {
+            if module_uri == uri || !exported_names(program).contains(&identifier) {
+                continue;
+            }
+            let Some(source) = module_uri
+                .to_file_path()
+                .ok()
+                .and_then(|to| relative_import_path(&from, &to))
+            else {
+                continue;
+            };
+            actions.push(create_code_action(
+                format!("Import '{}' from \"{}\"", identifier, source),
+                uri.clone(),
+                vec![import_edit(text, &identifier, &source)],
+                action_kinds::quick_fix(),
+                Some(diag.clone()),
+            ));
+        }
+    }
+
+    actions
+}
+
+/// Names a module exports, re-exports included
+fn exported_names(program: &Program) -> Vec<String> {
+    let mut names = Vec::new();
+    for item in &program.items {
+        let Item::Export(export) = item else {
+            continue;
+        };
+        match &export.item {
+            ExportItem::Function(func) => names.push(func.name.name.clone()),
+            ExportItem::Variable(var) => names.push(var.name.name.clone()),
+            ExportItem::TypeAlias(alias) => names.push(alias.name.name.clone()),
+            ExportItem::Const(constant) => names.push(constant.name.name.clone()),
+            ExportItem::Struct(decl) => names.push(decl.name.name.clone()),
+            ExportItem::Enum(decl) => names.push(decl.name.name.clone()),
+            ExportItem::ReExport { names: specs, .. } => names.extend(
+                specs
+                    .iter()
+                    .map(|spec| spec.alias.as_ref().unwrap_or(&spec.name).name.clone()),
+            ),
+        }
+    }
+    names
+}
+
+/// Import source for `to` as written in `from`: `"./utils"`, `"../lib/math"`
+fn relative_import_path(from: &Path, to: &Path) -> Option<String> {
+    let from_dir: Vec<Component> = from.parent()?.components().collect();
+    let to_dir: Vec<Component> = to.parent()?.components().collect();
+    let shared = from_dir
+        .iter()
+        .zip(&to_dir)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from_dir.len() - shared];
+    if parts.is_empty() {
+        parts.push(".".to_string());
+    }
+    for component in &to_dir[shared..] {
+        parts.push(component.as_os_str().to_str()?.to_string());
+    }
+    parts.push(to.file_stem()?.to_str()?.to_string());
+    Some(parts.join("/"))
+}
+
+/// Edit that imports `name` from `source`: added to an existing import of
+/// that module, or as a new line after the last import
+fn import_edit(text: &str, name: &str, source: &str) -> TextEdit {
+    let from_clause = format!("}} from \"{}\"", source);
+    let mut last_import = None;
+    for (line_num, line) in text.lines().enumerate() {
+        if !line.trim_start().starts_with("import ") {
+            continue;
+        }
+        last_import = Some(line_num);
+        if let Some(close) = line.find(&from_clause) {
+            let before = line[..close].trim_end();
+            let at = Position {
+                line: line_num as u32,
+                character: before.len() as u32,
+            };
+            return TextEdit {
+                range: Range { start: at, end: at },
+                new_text: format!(", {}", name),
+            };
+        }
+    }
+
+    let at = Position {
+        line: last_import.map_or(0, |line| line as u32 + 1),
+        character: 0,
+    };
+    TextEdit {
+        range: Range { start: at, end: at },
+        new_text: format!("import {{ {} }} from \"{}\";\n", name, source),
+    }
+}
+
 /// Generate refactoring actions based on selection
 fn generate_refactoring_actions(
     uri: &Url,
@@ -775,6 +1057,28 @@ fn extract_identifier_from_message(message: &str) -> Option<String> {
     None
 }
 
+/// Position just past `let <name>` (or `let mut <name>`) on the nearest
+/// line at or above `before_line` that declares it
+fn find_let_declaration(text: &str, name: &str, before_line: u32) -> Option<(u32, u32)> {
+    let lines: Vec<&str> = text.lines().collect();
+    let last = (before_line as usize).min(lines.len().checked_sub(1)?);
+    for line_num in (0..=last).rev() {
+        let line = lines[line_num];
+        let mut from = 0;
+        while let Some(found) = line[from..].find("let ") {
+            let name_at = from + found + 4;
+            let rest = &line[name_at..];
+            let is_name = rest.starts_with(name)
+                && !rest[name.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_');
+            if is_name {
+                return Some((line_num as u32, (name_at + name.len()) as u32));
+            }
+            from = name_at;
+        }
+    }
+    None
+}
+
 /// Get indentation of a line
 fn get_line_indent(line: &str) -> String {
     let indent_len = line.len() - line.trim_start().len();
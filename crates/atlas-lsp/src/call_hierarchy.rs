@@ -1,430 +1,684 @@
 //! Call hierarchy navigation for functions
 //!
 //! Implements LSP call hierarchy requests:
-//! - prepare: Find function at cursor position
-//! - incoming calls: Find all callers of this function
-//! - outgoing calls: Find all functions called by this function
-
-use crate::index::{span_to_range, SymbolIndex};
-use crate::navigation::find_identifier_at_position;
+//! - prepare: the function declared or called at the cursor
+//! - incoming calls: the functions whose bodies call it
+//! - outgoing calls: the functions its body calls
+//!
+//! Call sites are the call expressions in each function body. A callee is
+//! bound the way the binder binds names: a function declared in the calling
+//! module is that declaration, and an imported name (or `ns.f` through a
+//! namespace import) is the declaration in the module it is imported from,
+//! so same-named functions in unrelated modules stay apart. Calls made by
+//! top-level statements belong to the module itself.
+
+use crate::hover::format_type_ref;
+use crate::index::{position_to_offset, span_to_range};
+use crate::references::import_targets;
 use atlas_runtime::ast::*;
-use std::collections::HashMap;
+use atlas_runtime::span::Span;
+use atlas_runtime::{Lexer, Parser};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use tower_lsp::lsp_types::{
-    CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
-    CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, Position, Range, SymbolKind, Url,
+    CallHierarchyIncomingCall, CallHierarchyItem, CallHierarchyOutgoingCall, Position, Range,
+    SymbolKind, Url,
 };
 
-/// Prepare call hierarchy by finding the function at the cursor position
+/// Source text and parsed program of every module calls are resolved across
+pub type Modules = HashMap<Url, (String, Option<Program>)>;
+
+/// Prepare call hierarchy for the function declared or called at `position`
 ///
-/// Returns the function definition as a CallHierarchyItem if found
+/// A call site can resolve to several methods when the receiver's type is
+/// not known.
 pub fn prepare_call_hierarchy(
     uri: &Url,
-    text: &str,
     position: Position,
-    ast: Option<&Program>,
+    modules: &Modules,
 ) -> Option<Vec<CallHierarchyItem>> {
-    let ast = ast?;
-
-    // Find the identifier at the cursor position
-    let identifier = find_identifier_at_position(text, position)?;
-
-    // Find the function definition with this name
-    for item in &ast.items {
-        if let Item::Function(func) = item {
-            if func.name.name == identifier {
-                // Convert function to CallHierarchyItem
-                let range = span_to_range(&func.span, text);
-                let selection_range = span_to_range(&func.name.span, text);
-
-                let item = CallHierarchyItem {
-                    name: func.name.name.clone(),
-                    kind: SymbolKind::FUNCTION,
-                    tags: None,
-                    detail: Some(format_function_signature(func)),
-                    uri: uri.clone(),
-                    range,
-                    selection_range,
-                    data: None,
-                };
+    let graph = CallGraph::new(modules);
+    let module = graph.module(uri)?;
+    let offset = position_to_offset(position, module.text);
 
-                return Some(vec![item]);
-            }
+    let items: Vec<CallHierarchyItem> = match module
+        .callables
+        .iter()
+        .find(|callable| contains(callable.name.span, offset))
+    {
+        Some(callable) => vec![module.item(callable)],
+        None => {
+            let call = module
+                .callers()
+                .flat_map(|(_, calls)| calls)
+                .find(|call| contains(call.name().span, offset))?;
+            graph
+                .targets(module, call)
+                .map(|(target, callable)| target.item(callable))
+                .collect()
         }
-    }
+    };
 
-    None
+    (!items.is_empty()).then_some(items)
 }
 
-/// Find all incoming calls to the function
-///
-/// Returns a list of functions that call this function and the call sites
+/// Find the functions that call `item`, and where
 pub fn find_incoming_calls(
-    params: CallHierarchyIncomingCallsParams,
-    index: &SymbolIndex,
-    documents: &HashMap<Url, (String, Option<Program>)>,
+    item: &CallHierarchyItem,
+    modules: &Modules,
 ) -> Option<Vec<CallHierarchyIncomingCall>> {
-    let item = &params.item;
-    let function_name = &item.name;
-
-    // Find all references to this function
-    let references = index.find_references(function_name, false);
-
-    if references.is_empty() {
-        return None;
-    }
-
-    // Group references by containing function
-    let mut callers: HashMap<String, Vec<Range>> = HashMap::new();
-
-    for location in references {
-        // Find the containing function for this call site
-        if let Some((text, Some(ast))) = documents.get(&location.uri) {
-            if let Some(caller_name) = find_containing_function(ast, text, location.range.start) {
-                callers.entry(caller_name).or_default().push(location.range);
+    let graph = CallGraph::new(modules);
+    let (home, target) = graph.find(item)?;
+    let target = target?;
+
+    let mut incoming = Vec::new();
+    for module in &graph.modules {
+        for (caller, calls) in module.callers() {
+            let from_ranges: Vec<Range> = calls
+                .iter()
+                .filter(|call| module.binds(call, home.uri, target))
+                .map(|call| span_to_range(&call.name().span, module.text))
+                .collect();
+            if from_ranges.is_empty() {
+                continue;
             }
+            let from = match caller {
+                Some(callable) => module.item(callable),
+                None => module.file_item(),
+            };
+            incoming.push(CallHierarchyIncomingCall { from, from_ranges });
         }
     }
 
-    // Convert to CallHierarchyIncomingCall
-    let mut incoming_calls = Vec::new();
-
-    for (caller_name, call_ranges) in callers {
-        // Find the caller function definition
-        for (uri, (text, ast)) in documents {
-            if let Some(ast) = ast {
-                if let Some(caller_func) = find_function_by_name(ast, &caller_name) {
-                    let from_range = span_to_range(&caller_func.span, text);
-                    let from_selection_range = span_to_range(&caller_func.name.span, text);
-
-                    let from = CallHierarchyItem {
-                        name: caller_name.clone(),
-                        kind: SymbolKind::FUNCTION,
-                        tags: None,
-                        detail: Some(format_function_signature(caller_func)),
-                        uri: uri.clone(),
-                        range: from_range,
-                        selection_range: from_selection_range,
-                        data: None,
-                    };
-
-                    incoming_calls.push(CallHierarchyIncomingCall {
-                        from,
-                        from_ranges: call_ranges.clone(),
-                    });
+    (!incoming.is_empty()).then_some(incoming)
+}
 
-                    break;
-                }
+/// Find the functions `item` calls, and where
+///
+/// Calls to builtins and to modules that are not available are left out.
+pub fn find_outgoing_calls(
+    item: &CallHierarchyItem,
+    modules: &Modules,
+) -> Option<Vec<CallHierarchyOutgoingCall>> {
+    let graph = CallGraph::new(modules);
+    let (module, caller) = graph.find(item)?;
+    let calls = match caller {
+        Some(callable) => &callable.calls,
+        None => &module.top_level,
+    };
+
+    // Grouped by callee, in order of each callee's first call
+    let mut outgoing: Vec<CallHierarchyOutgoingCall> = Vec::new();
+    for call in calls {
+        let range = span_to_range(&call.name().span, module.text);
+        for (target, callable) in graph.targets(module, call) {
+            let to = target.item(callable);
+            match outgoing.iter_mut().find(|out| same_item(&out.to, &to)) {
+                Some(out) => out.from_ranges.push(range),
+                None => outgoing.push(CallHierarchyOutgoingCall {
+                    to,
+                    from_ranges: vec![range],
+                }),
             }
         }
     }
 
-    if incoming_calls.is_empty() {
-        None
-    } else {
-        Some(incoming_calls)
-    }
+    (!outgoing.is_empty()).then_some(outgoing)
 }
 
-/// Find all outgoing calls from the function
+/// Read and parse the module at `path`
 ///
-/// Returns a list of functions called by this function and the call sites
-pub fn find_outgoing_calls(
-    params: CallHierarchyOutgoingCallsParams,
-    index: &SymbolIndex,
-    documents: &HashMap<Url, (String, Option<Program>)>,
-) -> Option<Vec<CallHierarchyOutgoingCall>> {
-    let item = &params.item;
+/// Parse errors are tolerated: the declarations that did parse take part in
+/// call resolution.
+pub fn read_module(path: &Path) -> Option<(Url, (String, Option<Program>))> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let uri = Url::from_file_path(path).ok()?;
+    let (tokens, _) = Lexer::new(&text).tokenize();
+    let (program, _) = Parser::new(tokens).parse();
+    Some((uri, (text, Some(program))))
+}
 
-    // Find the function definition
-    let (text, ast) = documents.get(&item.uri)?;
-    let ast = ast.as_ref()?;
-    let func = find_function_by_name(ast, &item.name)?;
+// ============================================================================
+// Call graph
+// ============================================================================
 
-    // Find all function calls in the body
-    let mut calls = Vec::new();
-    collect_function_calls(&func.body, &mut calls);
+/// Every module's functions and call sites
+struct CallGraph<'a> {
+    /// Sorted by URI so results come out in a stable order
+    modules: Vec<Module<'a>>,
+}
 
-    if calls.is_empty() {
-        return None;
+impl<'a> CallGraph<'a> {
+    fn new(modules: &'a Modules) -> Self {
+        let mut modules: Vec<Module<'a>> = modules
+            .iter()
+            .filter_map(|(uri, (text, program))| {
+                Some(Module::new(uri, text, program.as_ref()?, modules))
+            })
+            .collect();
+        modules.sort_by(|a, b| a.uri.as_str().cmp(b.uri.as_str()));
+        Self { modules }
     }
 
-    // Group calls by target function name
-    let mut callees: HashMap<String, Vec<Range>> = HashMap::new();
+    fn module(&self, uri: &Url) -> Option<&Module<'a>> {
+        self.modules.iter().find(|module| module.uri == uri)
+    }
 
-    for call_expr in calls {
-        if let Some(callee_name) = extract_function_name(&call_expr.callee) {
-            let range = span_to_range(&call_expr.span, text);
-            callees.entry(callee_name).or_default().push(range);
+    /// The module and function `item` stands for; `None` for the function
+    /// when the item is the module itself
+    fn find(&self, item: &CallHierarchyItem) -> Option<(&Module<'a>, Option<&Callable<'a>>)> {
+        let module = self.module(&item.uri)?;
+        if item.kind == SymbolKind::FILE {
+            return Some((module, None));
         }
+        // The selection range pins down the declaration; the name is the
+        // fallback when the document changed since the item was prepared
+        let callable = module
+            .callables
+            .iter()
+            .find(|callable| {
+                span_to_range(&callable.name.span, module.text) == item.selection_range
+            })
+            .or_else(|| {
+                module
+                    .callables
+                    .iter()
+                    .find(|callable| callable.display_name() == item.name)
+            })?;
+        Some((module, Some(callable)))
     }
 
-    // Convert to CallHierarchyOutgoingCall
-    let mut outgoing_calls = Vec::new();
-
-    for (callee_name, call_ranges) in callees {
-        // Find the callee function definition
-        let definitions = index.find_definitions(&callee_name);
-        let definition = definitions.first();
-
-        if let Some(def) = definition {
-            if let Some((callee_text, Some(callee_ast))) = documents.get(&def.location.uri) {
-                if let Some(callee_func) = find_function_by_name(callee_ast, &callee_name) {
-                    let to_range = span_to_range(&callee_func.span, callee_text);
-                    let to_selection_range = span_to_range(&callee_func.name.span, callee_text);
-
-                    let to = CallHierarchyItem {
-                        name: callee_name.clone(),
-                        kind: SymbolKind::FUNCTION,
-                        tags: None,
-                        detail: Some(format_function_signature(callee_func)),
-                        uri: def.location.uri.clone(),
-                        range: to_range,
-                        selection_range: to_selection_range,
-                        data: None,
-                    };
+    /// Every function `call`, written in `module`, may be calling
+    fn targets<'s>(
+        &'s self,
+        module: &'s Module<'a>,
+        call: &'s CallSite<'a>,
+    ) -> impl Iterator<Item = (&'s Module<'a>, &'s Callable<'a>)> + 's {
+        self.modules.iter().flat_map(move |target| {
+            target
+                .callables
+                .iter()
+                .filter(move |callable| module.binds(call, target.uri, callable))
+                .map(move |callable| (target, callable))
+        })
+    }
+}
 
-                    outgoing_calls.push(CallHierarchyOutgoingCall {
-                        to,
-                        from_ranges: call_ranges.clone(),
-                    });
+/// One module's functions, call sites and imports
+struct Module<'a> {
+    uri: &'a Url,
+    text: &'a str,
+    /// Functions, exported functions, impl methods and nested functions
+    callables: Vec<Callable<'a>>,
+    /// Calls made by top-level statements
+    top_level: Vec<CallSite<'a>>,
+    /// Type names declared here (structs, enums, impl targets)
+    types: HashSet<&'a str>,
+    /// Named imports, by name, to the module they come from
+    imports: HashMap<&'a str, &'a Url>,
+    /// Namespace imports, by alias, to the module they refer to
+    namespaces: HashMap<&'a str, &'a Url>,
+}
+
+impl<'a> Module<'a> {
+    fn new(uri: &'a Url, text: &'a str, program: &'a Program, modules: &'a Modules) -> Self {
+        let mut module = Module {
+            uri,
+            text,
+            callables: Vec::new(),
+            top_level: Vec::new(),
+            types: HashSet::new(),
+            imports: HashMap::new(),
+            namespaces: HashMap::new(),
+        };
+        let mut top_level = Body::default();
+
+        for item in &program.items {
+            match item {
+                Item::Function(func) => module.add_function(func),
+                Item::Statement(stmt) => top_level.stmt(stmt),
+                Item::Const(decl) => top_level.expr(&decl.init),
+                Item::Export(export) => match &export.item {
+                    ExportItem::Function(func) => module.add_function(func),
+                    ExportItem::Variable(decl) => top_level.expr(&decl.init),
+                    ExportItem::Const(decl) => top_level.expr(&decl.init),
+                    ExportItem::Struct(decl) => {
+                        module.types.insert(&decl.name.name);
+                    }
+                    ExportItem::Enum(decl) => {
+                        module.types.insert(&decl.name.name);
+                    }
+                    _ => {}
+                },
+                Item::Struct(decl) => {
+                    module.types.insert(&decl.name.name);
+                }
+                Item::Enum(decl) => {
+                    module.types.insert(&decl.name.name);
+                }
+                Item::Impl(block) => {
+                    module.types.insert(&block.type_name.name);
+                    for method in &block.methods {
+                        module.add_method(&block.type_name.name, method);
+                    }
+                }
+                Item::Import(import) => {
+                    let Some(home) = modules
+                        .keys()
+                        .find(|candidate| import_targets(uri, &import.source, candidate))
+                    else {
+                        continue;
+                    };
+                    for spec in &import.specifiers {
+                        match spec {
+                            ImportSpecifier::Named { name, .. } => {
+                                module.imports.insert(&name.name, home);
+                            }
+                            ImportSpecifier::Namespace { alias, .. } => {
+                                module.namespaces.insert(&alias.name, home);
+                            }
+                        }
+                    }
                 }
+                _ => {}
             }
         }
-    }
 
-    if outgoing_calls.is_empty() {
-        None
-    } else {
-        Some(outgoing_calls)
+        for func in top_level.functions {
+            module.add_function(func);
+        }
+        module.top_level = top_level.calls;
+        module
     }
-}
-
-// ============================================================================
-// Helper Functions
-// ============================================================================
 
-/// Format function signature for display
-fn format_function_signature(func: &FunctionDecl) -> String {
-    let mut sig = String::from("fn ");
-    sig.push_str(&func.name.name);
-
-    // Type parameters
-    if !func.type_params.is_empty() {
-        sig.push('<');
-        let params: Vec<String> = func.type_params.iter().map(|p| p.name.clone()).collect();
-        sig.push_str(&params.join(", "));
-        sig.push('>');
+    /// Add `func` and the functions nested in it
+    fn add_function(&mut self, func: &'a FunctionDecl) {
+        let body = Body::of(&func.body);
+        self.callables.push(Callable {
+            name: &func.name,
+            owner: None,
+            detail: signature(
+                &func.name.name,
+                &func.type_params,
+                &func.params,
+                func.return_type.as_ref(),
+            ),
+            span: func.span,
+            calls: body.calls,
+        });
+        for nested in body.functions {
+            self.add_function(nested);
+        }
     }
 
-    // Parameters
-    sig.push('(');
-    let params: Vec<String> = func
-        .params
-        .iter()
-        .map(|p| {
-            let ty = format_type(&p.type_ref);
-            format!("{}: {}", p.name.name, ty)
-        })
-        .collect();
-    sig.push_str(&params.join(", "));
-    sig.push(')');
-
-    // Return type (omitted when None or explicitly void)
-    if let Some(ref rt) = func.return_type {
-        if !matches!(rt, TypeRef::Named(ref name, _) if name == "void") {
-            sig.push_str(" -> ");
-            sig.push_str(&format_type(rt));
+    /// Add `method`, declared in an impl block for `owner`
+    fn add_method(&mut self, owner: &'a str, method: &'a ImplMethod) {
+        let body = Body::of(&method.body);
+        self.callables.push(Callable {
+            name: &method.name,
+            owner: Some(owner),
+            detail: signature(
+                &method.name.name,
+                &method.type_params,
+                &method.params,
+                Some(&method.return_type),
+            ),
+            span: method.span,
+            calls: body.calls,
+        });
+        for nested in body.functions {
+            self.add_function(nested);
         }
     }
 
-    sig
-}
+    /// Each function with its call sites, then the module's top-level calls
+    fn callers(&self) -> impl Iterator<Item = (Option<&Callable<'a>>, &Vec<CallSite<'a>>)> {
+        self.callables
+            .iter()
+            .map(|callable| (Some(callable), &callable.calls))
+            .chain(std::iter::once((None, &self.top_level)))
+    }
 
-/// Format a type reference for display
-fn format_type(type_ref: &TypeRef) -> String {
-    match type_ref {
-        TypeRef::Named(name, _) => name.clone(),
-        TypeRef::Array(elem, _) => format!("{}[]", format_type(elem)),
-        TypeRef::Union { members, .. } => {
-            let formatted: Vec<String> = members.iter().map(format_type).collect();
-            formatted.join(" | ")
+    /// Whether `call`, written in this module, binds to `target` declared in
+    /// the module at `home`
+    fn binds(&self, call: &CallSite<'a>, home: &Url, target: &Callable<'a>) -> bool {
+        if call.name().name != target.name.name {
+            return false;
         }
-        TypeRef::Intersection { members, .. } => {
-            let formatted: Vec<String> = members.iter().map(format_type).collect();
-            formatted.join(" & ")
+        match (call, target.owner) {
+            (CallSite::Function(name), None) => {
+                if self.declares_function(&name.name) {
+                    home == self.uri
+                } else {
+                    self.imports.get(name.name.as_str()) == Some(&home)
+                }
+            }
+            (CallSite::Function(_), Some(_)) => false,
+            (CallSite::Member { receiver, .. }, owner) => {
+                let receiver = receiver.map(|name| name.name.as_str());
+                if let Some(namespace) = receiver.and_then(|name| self.namespaces.get(name)) {
+                    return owner.is_none() && *namespace == home;
+                }
+                let Some(owner) = owner else {
+                    return false;
+                };
+                match receiver.and_then(|name| self.type_home(name).map(|h| (name, h))) {
+                    // `Type.method()`
+                    Some((type_name, type_home)) => owner == type_name && type_home == home,
+                    // A method called on a value: its type is not known here,
+                    // so any method of that name in this module or one it
+                    // imports from may be called
+                    None => home == self.uri || self.imports.values().any(|m| *m == home),
+                }
+            }
         }
-        TypeRef::Function {
-            params,
-            return_type,
-            ..
-        } => {
-            let param_types: Vec<String> = params.iter().map(format_type).collect();
-            format!(
-                "({}) => {}",
-                param_types.join(", "),
-                format_type(return_type)
-            )
+    }
+
+    fn declares_function(&self, name: &str) -> bool {
+        self.callables
+            .iter()
+            .any(|callable| callable.owner.is_none() && callable.name.name == name)
+    }
+
+    /// Module declaring the type `name` as seen from this module
+    fn type_home(&self, name: &str) -> Option<&'a Url> {
+        if self.types.contains(name) {
+            Some(self.uri)
+        } else {
+            self.imports.get(name).copied()
         }
-        TypeRef::Generic {
-            name, type_args, ..
-        } => {
-            let arg_types: Vec<String> = type_args.iter().map(format_type).collect();
-            format!("{}<{}>", name, arg_types.join(", "))
+    }
+
+    fn item(&self, callable: &Callable<'a>) -> CallHierarchyItem {
+        CallHierarchyItem {
+            name: callable.display_name(),
+            kind: match callable.owner {
+                Some(_) => SymbolKind::METHOD,
+                None => SymbolKind::FUNCTION,
+            },
+            tags: None,
+            detail: Some(callable.detail.clone()),
+            uri: self.uri.clone(),
+            range: span_to_range(&callable.span, self.text),
+            selection_range: span_to_range(&callable.name.span, self.text),
+            data: None,
         }
-        TypeRef::Structural { .. } => "{ ... }".to_string(),
-        TypeRef::Tuple { elements, .. } => {
-            let formatted: Vec<String> = elements.iter().map(format_type).collect();
-            format!("({})", formatted.join(", "))
+    }
+
+    /// The module itself, as the caller of its top-level calls
+    fn file_item(&self) -> CallHierarchyItem {
+        let name = self
+            .uri
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .unwrap_or(self.uri.as_str());
+        let whole = Span::new(0, self.text.len());
+        CallHierarchyItem {
+            name: name.to_string(),
+            kind: SymbolKind::FILE,
+            tags: None,
+            detail: None,
+            uri: self.uri.clone(),
+            range: span_to_range(&whole, self.text),
+            selection_range: Range::default(),
+            data: None,
         }
-        TypeRef::Future { inner, .. } => format!("Future<{}>", format_type(inner)),
-        TypeRef::SelfType(_) => "self".to_string(),
     }
 }
 
-/// Find a function by name in the AST
-fn find_function_by_name<'a>(ast: &'a Program, name: &str) -> Option<&'a FunctionDecl> {
-    for item in &ast.items {
-        if let Item::Function(func) = item {
-            if func.name.name == name {
-                return Some(func);
-            }
+/// A function or method that can appear in a call hierarchy
+struct Callable<'a> {
+    name: &'a Identifier,
+    /// Type whose impl block declares the method
+    owner: Option<&'a str>,
+    detail: String,
+    span: Span,
+    /// Calls in the body, excluding those in nested function declarations
+    calls: Vec<CallSite<'a>>,
+}
+
+impl Callable<'_> {
+    fn display_name(&self) -> String {
+        match self.owner {
+            Some(owner) => format!("{}.{}", owner, self.name.name),
+            None => self.name.name.clone(),
         }
     }
-    None
 }
 
-/// Find the containing function for a position
-fn find_containing_function(ast: &Program, text: &str, position: Position) -> Option<String> {
-    for item in &ast.items {
-        if let Item::Function(func) = item {
-            let func_range = span_to_range(&func.span, text);
-            if position_in_range(position, func_range) {
-                return Some(func.name.name.clone());
-            }
+/// The callee of a call expression
+enum CallSite<'a> {
+    /// `f(...)`
+    Function(&'a Identifier),
+    /// `target.f(...)`, with the target's name when it is a plain identifier
+    /// (a namespace, a type, or a variable)
+    Member {
+        receiver: Option<&'a Identifier>,
+        name: &'a Identifier,
+    },
+}
+
+impl<'a> CallSite<'a> {
+    fn name(&self) -> &'a Identifier {
+        match self {
+            CallSite::Function(name) | CallSite::Member { name, .. } => name,
         }
     }
-    None
 }
 
-/// Check if a position is within a range
-fn position_in_range(position: Position, range: Range) -> bool {
-    if position.line < range.start.line || position.line > range.end.line {
-        return false;
-    }
-    if position.line == range.start.line && position.character < range.start.character {
-        return false;
-    }
-    if position.line == range.end.line && position.character > range.end.character {
-        return false;
-    }
-    true
+/// Call sites and nested function declarations found in a body
+#[derive(Default)]
+struct Body<'a> {
+    calls: Vec<CallSite<'a>>,
+    functions: Vec<&'a FunctionDecl>,
 }
 
-/// Collect all function call expressions from a block
-fn collect_function_calls(block: &Block, calls: &mut Vec<CallExpr>) {
-    for stmt in &block.statements {
-        collect_calls_from_stmt(stmt, calls);
+impl<'a> Body<'a> {
+    fn of(block: &'a Block) -> Self {
+        let mut body = Body::default();
+        body.block(block);
+        body
     }
-}
 
-/// Collect function calls from a statement
-fn collect_calls_from_stmt(stmt: &Stmt, calls: &mut Vec<CallExpr>) {
-    match stmt {
-        Stmt::Expr(expr_stmt) => collect_calls_from_expr(&expr_stmt.expr, calls),
-        Stmt::Return(return_stmt) => {
-            if let Some(value) = &return_stmt.value {
-                collect_calls_from_expr(value, calls);
-            }
+    fn block(&mut self, block: &'a Block) {
+        for stmt in &block.statements {
+            self.stmt(stmt);
         }
-        Stmt::VarDecl(decl) => {
-            collect_calls_from_expr(&decl.init, calls);
+        if let Some(tail) = &block.tail_expr {
+            self.expr(tail);
         }
-        Stmt::If(if_stmt) => {
-            collect_calls_from_expr(&if_stmt.cond, calls);
-            collect_function_calls(&if_stmt.then_block, calls);
-            if let Some(else_block) = &if_stmt.else_block {
-                collect_function_calls(else_block, calls);
+    }
+
+    fn stmt(&mut self, stmt: &'a Stmt) {
+        match stmt {
+            Stmt::VarDecl(decl) => self.expr(&decl.init),
+            Stmt::LetDestructure(decl) => self.expr(&decl.init),
+            // A nested function is a caller of its own
+            Stmt::FunctionDecl(func) => self.functions.push(func),
+            Stmt::Assign(assign) => {
+                self.assign_target(&assign.target);
+                self.expr(&assign.value);
             }
+            Stmt::CompoundAssign(assign) => {
+                self.assign_target(&assign.target);
+                self.expr(&assign.value);
+            }
+            Stmt::If(if_stmt) => {
+                self.expr(&if_stmt.cond);
+                self.block(&if_stmt.then_block);
+                if let Some(else_block) = &if_stmt.else_block {
+                    self.block(else_block);
+                }
+            }
+            Stmt::While(while_stmt) => {
+                self.expr(&while_stmt.cond);
+                self.block(&while_stmt.body);
+            }
+            Stmt::ForIn(for_in) => {
+                self.expr(&for_in.iterable);
+                self.block(&for_in.body);
+            }
+            Stmt::Return(ret) => {
+                if let Some(value) = &ret.value {
+                    self.expr(value);
+                }
+            }
+            Stmt::Expr(expr_stmt) => self.expr(&expr_stmt.expr),
+            Stmt::Defer(defer) => self.block(&defer.body),
+            Stmt::TryCatch(try_catch) => {
+                self.block(&try_catch.body);
+                self.block(&try_catch.catch_block);
+            }
+            Stmt::Break(_) | Stmt::Continue(_) => {}
         }
-        Stmt::While(while_stmt) => {
-            collect_calls_from_expr(&while_stmt.cond, calls);
-            collect_function_calls(&while_stmt.body, calls);
-        }
-        Stmt::ForIn(for_in_stmt) => {
-            collect_calls_from_expr(&for_in_stmt.iterable, calls);
-            collect_function_calls(&for_in_stmt.body, calls);
-        }
-        _ => {}
     }
-}
 
-/// Collect function calls from an expression
-fn collect_calls_from_expr(expr: &Expr, calls: &mut Vec<CallExpr>) {
-    match expr {
-        Expr::Call(call_expr) => {
-            // Add this call
-            calls.push(call_expr.clone());
-            // Recursively check arguments
-            for arg in &call_expr.args {
-                collect_calls_from_expr(arg, calls);
+    fn assign_target(&mut self, target: &'a AssignTarget) {
+        match target {
+            AssignTarget::Name(_) => {}
+            AssignTarget::Index { target, index, .. } => {
+                self.expr(target);
+                self.expr(index);
             }
-            // Check callee (in case of nested calls like foo()())
-            collect_calls_from_expr(&call_expr.callee, calls);
-        }
-        Expr::Unary(unary) => {
-            collect_calls_from_expr(&unary.expr, calls);
+            AssignTarget::Member { target, .. } => self.expr(target),
         }
-        Expr::Binary(binary) => {
-            collect_calls_from_expr(&binary.left, calls);
-            collect_calls_from_expr(&binary.right, calls);
-        }
-        Expr::Index(index) => {
-            collect_calls_from_expr(&index.target, calls);
-            match &index.index {
-                IndexValue::Single(expr) => collect_calls_from_expr(expr, calls),
+    }
+
+    fn expr(&mut self, expr: &'a Expr) {
+        match expr {
+            Expr::Call(call) => {
+                match call.callee.as_ref() {
+                    Expr::Identifier(name) => self.calls.push(CallSite::Function(name)),
+                    callee => self.expr(callee),
+                }
+                for arg in &call.args {
+                    self.expr(arg);
+                }
             }
-        }
-        Expr::Member(member) => {
-            collect_calls_from_expr(&member.target, calls);
-        }
-        Expr::ArrayLiteral(array) => {
-            for elem in &array.elements {
-                collect_calls_from_expr(elem, calls);
+            Expr::Member(member) => {
+                if member.args.is_some() {
+                    let receiver = match member.target.as_ref() {
+                        Expr::Identifier(name) => Some(name),
+                        _ => None,
+                    };
+                    self.calls.push(CallSite::Member {
+                        receiver,
+                        name: &member.member,
+                    });
+                }
+                self.expr(&member.target);
+                for arg in member.args.iter().flatten() {
+                    self.expr(arg);
+                }
             }
-        }
-        Expr::Group(group) => {
-            collect_calls_from_expr(&group.expr, calls);
-        }
-        Expr::Range { start, end, .. } => {
-            if let Some(start) = start {
-                collect_calls_from_expr(start, calls);
+            Expr::TemplateString { parts, .. } => {
+                for part in parts {
+                    if let TemplatePart::Expression(expr) = part {
+                        self.expr(expr);
+                    }
+                }
             }
-            if let Some(end) = end {
-                collect_calls_from_expr(end, calls);
+            Expr::Unary(unary) => self.expr(&unary.expr),
+            Expr::Binary(binary) => {
+                self.expr(&binary.left);
+                self.expr(&binary.right);
             }
-        }
-        Expr::Match(match_expr) => {
-            collect_calls_from_expr(&match_expr.scrutinee, calls);
-            for arm in &match_expr.arms {
-                // Note: Atlas match arms don't have guards in the current AST
-                collect_calls_from_expr(&arm.body, calls);
+            Expr::Index(index) => {
+                self.expr(&index.target);
+                let IndexValue::Single(value) = &index.index;
+                self.expr(value);
             }
+            Expr::ArrayLiteral(array) => {
+                for elem in &array.elements {
+                    self.expr(elem);
+                }
+            }
+            Expr::ObjectLiteral(object) => {
+                for entry in &object.entries {
+                    self.expr(&entry.value);
+                }
+            }
+            Expr::StructExpr(init) => {
+                for field in &init.fields {
+                    self.expr(&field.value);
+                }
+            }
+            Expr::Range { start, end, .. } => {
+                for bound in [start, end].into_iter().flatten() {
+                    self.expr(bound);
+                }
+            }
+            Expr::Group(group) => self.expr(&group.expr),
+            Expr::TupleLiteral { elements, .. } => {
+                for elem in elements {
+                    self.expr(elem);
+                }
+            }
+            Expr::Match(match_expr) => {
+                self.expr(&match_expr.scrutinee);
+                for arm in &match_expr.arms {
+                    if let Some(guard) = &arm.guard {
+                        self.expr(guard);
+                    }
+                    self.expr(&arm.body);
+                }
+            }
+            Expr::Try(try_expr) => self.expr(&try_expr.expr),
+            // Calls inside a closure are made on behalf of the function
+            // creating it
+            Expr::AnonFn { body, .. } => self.expr(body),
+            Expr::Block(block) => self.block(block),
+            Expr::EnumVariant(variant) => {
+                for arg in variant.args.iter().flatten() {
+                    self.expr(arg);
+                }
+            }
+            Expr::Await { expr, .. } => self.expr(expr),
+            Expr::New { args, .. } => {
+                for arg in args {
+                    self.expr(arg);
+                }
+            }
+            Expr::Literal(..) | Expr::Identifier(_) => {}
         }
-        Expr::Try(try_expr) => {
-            collect_calls_from_expr(&try_expr.expr, calls);
-        }
-        _ => {}
     }
 }
 
-/// Extract function name from a callee expression
-fn extract_function_name(expr: &Expr) -> Option<String> {
-    match expr {
-        Expr::Identifier(ident) => Some(ident.name.clone()),
-        Expr::Member(member) => {
-            // For method calls like obj.method(), extract the member name
-            Some(member.member.name.clone())
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Format a function signature for display
+fn signature(
+    name: &str,
+    type_params: &[TypeParam],
+    params: &[Param],
+    return_type: Option<&TypeRef>,
+) -> String {
+    let mut sig = format!("fn {}", name);
+    if !type_params.is_empty() {
+        let names: Vec<&str> = type_params.iter().map(|p| p.name.as_str()).collect();
+        sig.push_str(&format!("<{}>", names.join(", ")));
+    }
+    let params: Vec<String> = params
+        .iter()
+        .map(|p| format!("{}: {}", p.name.name, format_type_ref(&p.type_ref)))
+        .collect();
+    sig.push_str(&format!("({})", params.join(", ")));
+    // Return type (omitted when None or explicitly void)
+    if let Some(rt) = return_type {
+        if !matches!(rt, TypeRef::Named(name, _) if name == "void") {
+            sig.push_str(&format!(": {}", format_type_ref(rt)));
         }
-        _ => None,
     }
+    sig
+}
+
+/// Whether the cursor at `offset` is on `span`, including just past its end
+fn contains(span: Span, offset: usize) -> bool {
+    span.start <= offset && offset <= span.end
+}
+
+fn same_item(a: &CallHierarchyItem, b: &CallHierarchyItem) -> bool {
+    a.uri == b.uri && a.selection_range == b.selection_range
 }
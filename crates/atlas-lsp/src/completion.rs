@@ -168,6 +168,14 @@ pub fn keyword_completions() -> Vec<CompletionItem> {
             insert_text_format: Some(InsertTextFormat::SNIPPET),
             ..Default::default()
         },
+        CompletionItem {
+            label: "try".to_string(),
+            kind: Some(CompletionItemKind::KEYWORD),
+            detail: Some("Try/catch statement".to_string()),
+            insert_text: Some("try {\n\t${1}\n} catch (${2:e}) {\n\t${3}\n}".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        },
         CompletionItem {
             label: "return".to_string(),
             kind: Some(CompletionItemKind::KEYWORD),
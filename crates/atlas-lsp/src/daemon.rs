@@ -0,0 +1,662 @@
+//! Background daemon keeping a workspace parsed, compiled and indexed
+//!
+//! `atlas daemon` serves one workspace root over a Unix socket. It holds a
+//! [`ModuleCache`] shared by every compile it performs, so `atlas run`,
+//! `atlas check` and `atlas test` get the bytecode (or the errors) of an
+//! unchanged program without re-parsing or re-checking any of its modules,
+//! and a workspace symbol index that the language server loads at startup
+//! instead of walking and parsing the tree itself.
+//! Both caches are revalidated against file contents on each request, so
+//! the daemon never serves stale results; it only saves the work.
+//!
+//! The socket lives at `$ATLAS_HOME/daemon/<hash of root>.sock` (or
+//! `~/atlas/daemon`), in a directory only the owner can open. Each message
+//! is a length-prefixed JSON frame; a compiled program is followed by one
+//! more frame holding its serialized bytecode. Clients that cannot reach a
+//! daemon, or get an error back, do the work themselves.
+
+use crate::symbols::{find_workspace_files, index_source, IndexedSymbol};
+use atlas_runtime::{
+    Atlas, BytecodeCache, CompiledProgram, Diagnostic, ModuleCache, SecurityContext,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tower_lsp::lsp_types::Url;
+
+/// How long a client waits for a reply before doing the work itself
+pub const CLIENT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Largest frame either side accepts
+const MAX_FRAME_LEN: usize = 1 << 30;
+
+/// Directory holding the daemon sockets: `$ATLAS_HOME/daemon`, else
+/// `~/atlas/daemon`
+pub fn socket_dir() -> PathBuf {
+    let atlas_home = std::env::var("ATLAS_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("atlas")
+        });
+    atlas_home.join("daemon")
+}
+
+/// Socket of the daemon serving `root`
+pub fn socket_path(root: &Path) -> PathBuf {
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    socket_dir().join(format!(
+        "{:016x}.sock",
+        fnv1a(root.to_string_lossy().as_bytes())
+    ))
+}
+
+/// 64-bit FNV-1a; stable across builds, unlike `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Protocol
+// ---------------------------------------------------------------------------
+
+/// A request to the daemon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Request {
+    /// Compile the program whose entry point is `path`
+    Compile { path: PathBuf },
+    /// Symbols of every Atlas file under the daemon's root
+    Index,
+    /// What the daemon is serving and how warm it is
+    Status,
+    /// Stop serving and remove the socket
+    Shutdown,
+}
+
+/// The daemon's reply to a [`Request`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Response {
+    /// The program compiled; its bytecode follows in the next frame
+    Compiled {
+        has_main: bool,
+    },
+    /// The program has errors
+    Diagnostics {
+        diagnostics: Vec<Diagnostic>,
+    },
+    /// The daemon cannot compile this program; the client compiles it itself
+    Unsupported {
+        reason: String,
+    },
+    Index {
+        files: Vec<(Url, Vec<IndexedSymbol>)>,
+    },
+    Status(DaemonStatus),
+    ShuttingDown,
+    /// The request could not be served at all
+    Error {
+        message: String,
+    },
+}
+
+/// Snapshot of a running daemon
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    pub root: PathBuf,
+    pub pid: u32,
+    pub version: String,
+    pub uptime_secs: u64,
+    /// Requests served, this one included
+    pub requests: u64,
+    /// Modules held in the module cache
+    pub modules: usize,
+    pub module_hits: usize,
+    pub module_misses: usize,
+    /// Files held in the symbol index
+    pub indexed_files: usize,
+}
+
+/// A request as sent, tagged with the sender's version so a daemon left
+/// running across an upgrade refuses it instead of misreading it
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    version: String,
+    request: Request,
+}
+
+fn write_frame(writer: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
+    let len = u32::try_from(bytes.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "frame too large"))?;
+    writer.write_all(&len.to_be_bytes())?;
+    writer.write_all(bytes)?;
+    writer.flush()
+}
+
+/// Next frame, or `None` when the peer closed the connection between frames
+fn read_frame(reader: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
+    let mut len = [0u8; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame of {} bytes exceeds the limit", len),
+        ));
+    }
+    let mut bytes = vec![0u8; len];
+    reader.read_exact(&mut bytes)?;
+    Ok(Some(bytes))
+}
+
+fn write_json(writer: &mut impl Write, value: &impl Serialize) -> io::Result<()> {
+    let bytes =
+        serde_json::to_vec(value).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    write_frame(writer, &bytes)
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(reader: &mut impl Read) -> io::Result<Option<T>> {
+    match read_frame(reader)? {
+        Some(bytes) => serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        None => Ok(None),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Daemon state
+// ---------------------------------------------------------------------------
+
+/// Result of compiling a program through the daemon
+#[derive(Debug)]
+pub enum CompileResult {
+    Compiled(CompiledProgram),
+    Diagnostics(Vec<Diagnostic>),
+    /// The daemon declined; compile locally instead
+    Unsupported(String),
+}
+
+/// Indexed symbols of one file, with the text they were extracted from
+struct IndexedFile {
+    text: String,
+    uri: Url,
+    symbols: Vec<IndexedSymbol>,
+}
+
+/// The warm caches behind a daemon, independent of its transport
+#[cfg_attr(not(unix), allow(dead_code))]
+struct State {
+    root: PathBuf,
+    started: std::time::Instant,
+    requests: std::sync::atomic::AtomicU64,
+    modules: std::sync::Arc<ModuleCache>,
+    index: std::sync::Mutex<HashMap<PathBuf, IndexedFile>>,
+}
+
+#[cfg_attr(not(unix), allow(dead_code))]
+impl State {
+    fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            started: std::time::Instant::now(),
+            requests: std::sync::atomic::AtomicU64::new(0),
+            modules: std::sync::Arc::new(ModuleCache::new()),
+            index: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Compile `path`, returning the response and the bytecode frame to
+    /// follow it
+    fn compile(&self, path: &Path) -> (Response, Option<Vec<u8>>) {
+        let path = match path.canonicalize() {
+            Ok(path) if path.starts_with(&self.root) => path,
+            Ok(path) => {
+                return (
+                    Response::Unsupported {
+                        reason: format!("{} is outside {}", path.display(), self.root.display()),
+                    },
+                    None,
+                )
+            }
+            Err(e) => {
+                return (
+                    Response::Error {
+                        message: format!("cannot open {}: {}", path.display(), e),
+                    },
+                    None,
+                )
+            }
+        };
+
+        // Same permissions as `atlas run`, which would otherwise compile it
+        let runtime = Atlas::new_with_security(SecurityContext::allow_all())
+            .with_bytecode_cache(BytecodeCache::for_packages())
+            .with_module_cache(std::sync::Arc::clone(&self.modules));
+        match runtime.compile_file(&path.to_string_lossy()) {
+            Ok(program) => (
+                Response::Compiled {
+                    has_main: program.has_main,
+                },
+                Some(program.bytecode.to_bytes()),
+            ),
+            Err(diagnostics)
+                if diagnostics.iter().any(|d| {
+                    d.code == atlas_runtime::diagnostic::error_codes::EXTERN_NOT_PRECOMPILABLE.code
+                }) =>
+            {
+                let reason = diagnostics
+                    .into_iter()
+                    .next()
+                    .map(|d| d.message)
+                    .unwrap_or_default();
+                (Response::Unsupported { reason }, None)
+            }
+            Err(diagnostics) => (Response::Diagnostics { diagnostics }, None),
+        }
+    }
+
+    /// Symbols of every Atlas file under the root, re-parsing only files
+    /// whose text changed since the last call
+    fn index(&self) -> Vec<(Url, Vec<IndexedSymbol>)> {
+        let paths = find_workspace_files(&self.root);
+        let present: HashSet<&PathBuf> = paths.iter().collect();
+        let mut index = self.index.lock().expect("daemon index lock poisoned");
+        index.retain(|path, _| present.contains(path));
+        for path in &paths {
+            let Ok(text) = std::fs::read_to_string(path) else {
+                index.remove(path);
+                continue;
+            };
+            if index.get(path).is_some_and(|file| file.text == text) {
+                continue;
+            }
+            let Ok(uri) = Url::from_file_path(path) else {
+                continue;
+            };
+            let symbols = index_source(&uri, &text);
+            index.insert(path.clone(), IndexedFile { text, uri, symbols });
+        }
+        paths
+            .iter()
+            .filter_map(|path| index.get(path))
+            .map(|file| (file.uri.clone(), file.symbols.clone()))
+            .collect()
+    }
+
+    fn status(&self) -> DaemonStatus {
+        DaemonStatus {
+            root: self.root.clone(),
+            pid: std::process::id(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            uptime_secs: self.started.elapsed().as_secs(),
+            requests: self.requests.load(std::sync::atomic::Ordering::Relaxed),
+            modules: self.modules.len(),
+            module_hits: self.modules.hits(),
+            module_misses: self.modules.misses(),
+            indexed_files: self.index.lock().expect("daemon index lock poisoned").len(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Server and client
+// ---------------------------------------------------------------------------
+
+#[cfg(unix)]
+pub use unix::{Daemon, DaemonClient};
+
+#[cfg(unix)]
+mod unix {
+    use super::*;
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    /// Create the socket directory, or make sure an existing one is a real
+    /// directory owned by us that nobody else can open
+    fn secure_socket_dir(dir: &Path) -> io::Result<()> {
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(dir)?;
+        let meta = std::fs::symlink_metadata(dir)?;
+        // SAFETY: geteuid has no preconditions and cannot fail
+        let uid = unsafe { libc::geteuid() };
+        if !meta.is_dir() || meta.uid() != uid {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!(
+                    "daemon socket directory {} is not a directory owned by the current user",
+                    dir.display()
+                ),
+            ));
+        }
+        if meta.mode() & 0o077 != 0 {
+            std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+        }
+        Ok(())
+    }
+
+    /// A daemon bound to its socket, ready to [`serve`](Daemon::serve)
+    pub struct Daemon {
+        listener: UnixListener,
+        socket: PathBuf,
+        state: Arc<State>,
+        stopping: Arc<AtomicBool>,
+        last_request: Arc<Mutex<Instant>>,
+        idle_timeout: Option<Duration>,
+    }
+
+    impl Daemon {
+        /// Bind the daemon for `root` to `socket`
+        ///
+        /// Fails if another daemon is already answering there; a socket
+        /// left behind by one that died is replaced.
+        pub fn bind(root: &Path, socket: &Path) -> io::Result<Self> {
+            let root = root.canonicalize()?;
+            if let Some(dir) = socket.parent() {
+                secure_socket_dir(dir)?;
+            }
+            if socket.exists() {
+                if UnixStream::connect(socket).is_ok() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::AddrInUse,
+                        format!(
+                            "a daemon for {} is already running at {}",
+                            root.display(),
+                            socket.display()
+                        ),
+                    ));
+                }
+                std::fs::remove_file(socket)?;
+            }
+            Ok(Self {
+                listener: UnixListener::bind(socket)?,
+                socket: socket.to_path_buf(),
+                state: Arc::new(State::new(root)),
+                stopping: Arc::new(AtomicBool::new(false)),
+                last_request: Arc::new(Mutex::new(Instant::now())),
+                idle_timeout: None,
+            })
+        }
+
+        /// Shut down after `timeout` without a request
+        pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+            self.idle_timeout = Some(timeout);
+            self
+        }
+
+        /// Root this daemon serves
+        pub fn root(&self) -> &Path {
+            &self.state.root
+        }
+
+        /// Answer connections until asked to shut down or idle too long,
+        /// then remove the socket
+        pub fn serve(self) -> io::Result<()> {
+            if let Some(timeout) = self.idle_timeout {
+                let stopping = Arc::clone(&self.stopping);
+                let last_request = Arc::clone(&self.last_request);
+                let socket = self.socket.clone();
+                std::thread::spawn(move || loop {
+                    std::thread::sleep(timeout.min(Duration::from_secs(1)));
+                    if stopping.load(Ordering::SeqCst) {
+                        return;
+                    }
+                    let idle = last_request.lock().expect("daemon lock poisoned").elapsed();
+                    if idle >= timeout {
+                        stop(&stopping, &socket);
+                        return;
+                    }
+                });
+            }
+
+            for stream in self.listener.incoming() {
+                if self.stopping.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let state = Arc::clone(&self.state);
+                let stopping = Arc::clone(&self.stopping);
+                let last_request = Arc::clone(&self.last_request);
+                let socket = self.socket.clone();
+                std::thread::spawn(move || {
+                    let _ = handle_connection(stream, &state, &last_request, || {
+                        stop(&stopping, &socket)
+                    });
+                });
+            }
+
+            match std::fs::remove_file(&self.socket) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Flag the daemon as stopping and wake its blocked `accept`
+    fn stop(stopping: &AtomicBool, socket: &Path) {
+        stopping.store(true, Ordering::SeqCst);
+        let _ = UnixStream::connect(socket);
+    }
+
+    fn handle_connection(
+        mut stream: UnixStream,
+        state: &State,
+        last_request: &Mutex<Instant>,
+        shutdown: impl Fn(),
+    ) -> io::Result<()> {
+        while let Some(envelope) = read_json::<Envelope>(&mut stream)? {
+            *last_request.lock().expect("daemon lock poisoned") = Instant::now();
+            state.requests.fetch_add(1, Ordering::Relaxed);
+
+            if envelope.version != env!("CARGO_PKG_VERSION") {
+                let message = format!(
+                    "daemon is version {}, client is version {}",
+                    env!("CARGO_PKG_VERSION"),
+                    envelope.version
+                );
+                write_json(&mut stream, &Response::Error { message })?;
+                continue;
+            }
+
+            match envelope.request {
+                Request::Compile { path } => {
+                    let (response, bytecode) = state.compile(&path);
+                    write_json(&mut stream, &response)?;
+                    if let Some(bytecode) = bytecode {
+                        write_frame(&mut stream, &bytecode)?;
+                    }
+                }
+                Request::Index => {
+                    let files = state.index();
+                    write_json(&mut stream, &Response::Index { files })?;
+                }
+                Request::Status => write_json(&mut stream, &Response::Status(state.status()))?,
+                Request::Shutdown => {
+                    write_json(&mut stream, &Response::ShuttingDown)?;
+                    shutdown();
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// A connection to a running daemon
+    pub struct DaemonClient {
+        stream: UnixStream,
+    }
+
+    impl DaemonClient {
+        /// Connect to the daemon listening on `socket`
+        pub fn connect(socket: &Path) -> io::Result<Self> {
+            let stream = UnixStream::connect(socket)?;
+            stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+            stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+            Ok(Self { stream })
+        }
+
+        /// Connect to the daemon serving the nearest root enclosing `path`
+        pub fn find(path: &Path) -> Option<Self> {
+            let path = path.canonicalize().ok()?;
+            path.ancestors().find_map(|root| {
+                let socket = socket_path(root);
+                if socket.exists() {
+                    Self::connect(&socket).ok()
+                } else {
+                    None
+                }
+            })
+        }
+
+        fn request(&mut self, request: Request) -> io::Result<Response> {
+            let envelope = Envelope {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                request,
+            };
+            write_json(&mut self.stream, &envelope)?;
+            match read_json(&mut self.stream)? {
+                Some(Response::Error { message }) => Err(io::Error::other(message)),
+                Some(response) => Ok(response),
+                None => Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "daemon closed the connection",
+                )),
+            }
+        }
+
+        /// Compile the program whose entry point is `path`
+        pub fn compile(&mut self, path: &Path) -> io::Result<CompileResult> {
+            let path = std::path::absolute(path)?;
+            match self.request(Request::Compile { path })? {
+                Response::Compiled { has_main } => {
+                    let bytes = read_frame(&mut self.stream)?.ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::UnexpectedEof, "missing bytecode")
+                    })?;
+                    let bytecode = atlas_runtime::bytecode::Bytecode::from_untrusted_bytes(&bytes)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                    Ok(CompileResult::Compiled(CompiledProgram {
+                        bytecode,
+                        has_main,
+                    }))
+                }
+                Response::Diagnostics { diagnostics } => {
+                    Ok(CompileResult::Diagnostics(diagnostics))
+                }
+                Response::Unsupported { reason } => Ok(CompileResult::Unsupported(reason)),
+                other => Err(unexpected(other)),
+            }
+        }
+
+        /// Symbols of every Atlas file under the daemon's root
+        pub fn index(&mut self) -> io::Result<Vec<(Url, Vec<IndexedSymbol>)>> {
+            match self.request(Request::Index)? {
+                Response::Index { files } => Ok(files),
+                other => Err(unexpected(other)),
+            }
+        }
+
+        pub fn status(&mut self) -> io::Result<DaemonStatus> {
+            match self.request(Request::Status)? {
+                Response::Status(status) => Ok(status),
+                other => Err(unexpected(other)),
+            }
+        }
+
+        /// Ask the daemon to stop; it removes its socket once it has
+        pub fn shutdown(&mut self) -> io::Result<()> {
+            match self.request(Request::Shutdown)? {
+                Response::ShuttingDown => Ok(()),
+                other => Err(unexpected(other)),
+            }
+        }
+    }
+
+    fn unexpected(response: Response) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unexpected daemon response: {:?}", response),
+        )
+    }
+}
+
+#[cfg(not(unix))]
+pub use unsupported::{Daemon, DaemonClient};
+
+/// Unix sockets are the only transport so far; elsewhere every client
+/// falls back to doing the work itself
+#[cfg(not(unix))]
+mod unsupported {
+    use super::*;
+
+    fn unsupported() -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            "atlas daemon is only supported on Unix",
+        )
+    }
+
+    pub struct Daemon {
+        state: State,
+    }
+
+    impl Daemon {
+        pub fn bind(_root: &Path, _socket: &Path) -> io::Result<Self> {
+            Err(unsupported())
+        }
+
+        pub fn with_idle_timeout(self, _timeout: Duration) -> Self {
+            self
+        }
+
+        pub fn root(&self) -> &Path {
+            &self.state.root
+        }
+
+        pub fn serve(self) -> io::Result<()> {
+            Err(unsupported())
+        }
+    }
+
+    pub struct DaemonClient;
+
+    impl DaemonClient {
+        pub fn connect(_socket: &Path) -> io::Result<Self> {
+            Err(unsupported())
+        }
+
+        pub fn find(_path: &Path) -> Option<Self> {
+            None
+        }
+
+        pub fn compile(&mut self, _path: &Path) -> io::Result<CompileResult> {
+            Err(unsupported())
+        }
+
+        pub fn index(&mut self) -> io::Result<Vec<(Url, Vec<IndexedSymbol>)>> {
+            Err(unsupported())
+        }
+
+        pub fn status(&mut self) -> io::Result<DaemonStatus> {
+            Err(unsupported())
+        }
+
+        pub fn shutdown(&mut self) -> io::Result<()> {
+            Err(unsupported())
+        }
+    }
+}
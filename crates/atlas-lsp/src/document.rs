@@ -1,9 +1,17 @@
 //! Document state management
 
+use crate::incremental::{Relocate, Relocation};
+use crate::modules::{ModuleContext, ModuleGraph};
 use atlas_runtime::{
-    ast::Program, symbol::SymbolTable, Binder, Diagnostic, Lexer, Parser, TypeChecker,
+    ast::Program,
+    diagnostic::suppression::apply_suppressions,
+    span::{intern_file, register_source, FileId},
+    symbol::SymbolTable,
+    Binder, Diagnostic, Lexer, Parser, Span, TypeChecker,
 };
-use tower_lsp::lsp_types::Url;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tower_lsp::lsp_types::{Range, TextDocumentContentChangeEvent, Url};
 
 /// State of a single document in the LSP server
 pub struct DocumentState {
@@ -13,11 +21,89 @@ pub struct DocumentState {
     pub diagnostics: Vec<Diagnostic>,
     pub ast: Option<Program>,
     pub symbols: Option<SymbolTable>,
+    /// Top-level items carried over from the previous parse by the last
+    /// update (0 when the whole document was re-parsed)
+    pub reused_items: usize,
+    /// Source file every span of this document points at
+    file: FileId,
+    /// Source extent of each item in `ast`, in the same order
+    item_spans: Vec<Span>,
+    /// Lex/parse diagnostics of the current text; incremental re-parsing
+    /// only builds on a clean parse
+    syntax_diagnostics: Vec<Diagnostic>,
+    /// Whether `diagnostics` and `symbols` predate the current text or
+    /// the modules it imports
+    stale: bool,
+    /// Graph that resolves this document's imports, with its key in it
+    modules: Option<(Arc<Mutex<ModuleGraph>>, PathBuf)>,
+}
+
+/// What binding and type-checking a document needs, taken with
+/// [`DocumentState::analysis_input`] so the work can run off the document lock
+pub struct AnalysisInput {
+    version: i32,
+    text: String,
+    ast: Option<Program>,
+    syntax_diagnostics: Vec<Diagnostic>,
+    modules: Option<(Arc<Mutex<ModuleGraph>>, PathBuf)>,
+}
+
+impl AnalysisInput {
+    /// Bind and type-check
+    pub fn run(self) -> Analysis {
+        let context = module_context(self.modules.as_ref(), self.ast.as_ref());
+        let (diagnostics, symbols) = analyze(
+            &self.text,
+            self.ast.as_ref(),
+            &self.syntax_diagnostics,
+            context.as_ref(),
+        );
+        Analysis {
+            version: self.version,
+            ast: self.ast,
+            diagnostics,
+            symbols,
+        }
+    }
+}
+
+/// Result of [`AnalysisInput::run`], stored with [`DocumentState::store_analysis`]
+pub struct Analysis {
+    version: i32,
+    /// The input AST, now carrying the types the checker inferred
+    ast: Option<Program>,
+    diagnostics: Vec<Diagnostic>,
+    symbols: Option<SymbolTable>,
 }
 
 impl DocumentState {
     /// Create a new document and analyze it
+    ///
+    /// Its imports are not resolved; see
+    /// [`with_module_graph`](Self::with_module_graph).
     pub fn new(uri: Url, text: String, version: i32) -> Self {
+        Self::create(uri, text, version, None)
+    }
+
+    /// Create a new document whose imports resolve through `modules`, and
+    /// analyze it
+    pub fn with_module_graph(
+        uri: Url,
+        text: String,
+        version: i32,
+        modules: Arc<Mutex<ModuleGraph>>,
+    ) -> Self {
+        let modules = ModuleGraph::module_path(&uri).map(|path| (modules, path));
+        Self::create(uri, text, version, modules)
+    }
+
+    fn create(
+        uri: Url,
+        text: String,
+        version: i32,
+        modules: Option<(Arc<Mutex<ModuleGraph>>, PathBuf)>,
+    ) -> Self {
+        let file = intern_file(format!("<lsp:{}>", uri));
         let mut doc = Self {
             uri,
             text,
@@ -25,7 +111,14 @@ impl DocumentState {
             diagnostics: Vec::new(),
             ast: None,
             symbols: None,
+            reused_items: 0,
+            file,
+            item_spans: Vec::new(),
+            syntax_diagnostics: Vec::new(),
+            stale: false,
+            modules,
         };
+        doc.parse_full();
         doc.analyze();
         doc
     }
@@ -34,55 +127,289 @@ impl DocumentState {
     pub fn update(&mut self, text: String, version: i32) {
         self.text = text;
         self.version = version;
+        self.parse_full();
         self.analyze();
     }
 
-    /// Analyze the document and update diagnostics
-    fn analyze(&mut self) {
-        self.diagnostics.clear();
+    /// Apply the content changes of one `didChange` notification, in order,
+    /// then re-analyze once.
+    ///
+    /// Ranged changes re-parse only the top-level items around the edit and
+    /// keep the rest of the tree; a change without a range replaces the text.
+    pub fn apply_changes(&mut self, changes: Vec<TextDocumentContentChangeEvent>, version: i32) {
+        self.apply_text_changes(changes, version);
+        self.analyze();
+    }
+
+    /// Apply the content changes of one `didChange` notification without
+    /// re-analyzing
+    ///
+    /// The AST follows the new text; `diagnostics` and `symbols` are stale
+    /// until [`analyze_if_stale`](Self::analyze_if_stale) or
+    /// [`store_analysis`](Self::store_analysis) catches up.
+    pub fn apply_text_changes(
+        &mut self,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        version: i32,
+    ) {
+        self.version = version;
+        self.stale = true;
+        for change in changes {
+            match change.range {
+                Some(range) => self.apply_edit(range, &change.text),
+                None => {
+                    self.text = change.text;
+                    self.parse_full();
+                }
+            }
+        }
+    }
+
+    /// Whether `diagnostics` and `symbols` predate the current text
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+
+    /// Mark the analysis out of date because a module this document
+    /// imports changed
+    pub fn mark_stale(&mut self) {
+        self.stale = true;
+    }
+
+    /// Re-analyze if an edit has not been analyzed yet
+    pub fn analyze_if_stale(&mut self) {
+        if self.stale {
+            self.analyze();
+        }
+    }
+
+    /// Snapshot of what an analysis of the current text needs, or `None`
+    /// when the document is already analyzed
+    pub fn analysis_input(&self) -> Option<AnalysisInput> {
+        self.stale.then(|| AnalysisInput {
+            version: self.version,
+            text: self.text.clone(),
+            ast: self.ast.clone(),
+            syntax_diagnostics: self.syntax_diagnostics.clone(),
+            modules: self.modules.clone(),
+        })
+    }
+
+    /// Store an analysis run off the lock; `false` (and nothing stored) when
+    /// the document has been edited since its input was taken
+    pub fn store_analysis(&mut self, analysis: Analysis) -> bool {
+        if analysis.version != self.version {
+            return false;
+        }
+        if self.stale {
+            self.ast = analysis.ast;
+            self.diagnostics = analysis.diagnostics;
+            self.symbols = analysis.symbols;
+            self.stale = false;
+        }
+        true
+    }
+
+    /// Splice one ranged edit into the text and re-parse what it touched
+    fn apply_edit(&mut self, range: Range, new_text: &str) {
+        let byte_start = crate::convert::position_to_offset(&self.text, range.start);
+        let byte_end = crate::convert::position_to_offset(&self.text, range.end).max(byte_start);
+
+        // Spans count characters, not bytes
+        let start = self.text[..byte_start].chars().count();
+        let end = start + self.text[byte_start..byte_end].chars().count();
+        let old_len = self.text.chars().count();
+        let delta = new_text.chars().count() as isize - (end - start) as isize;
+
+        self.text.replace_range(byte_start..byte_end, new_text);
+        if !self.reparse_items(start, end, old_len, delta) {
+            self.parse_full();
+        }
+    }
+
+    /// Re-lex and re-parse the whole text
+    fn parse_full(&mut self) {
+        self.reused_items = 0;
         self.ast = None;
-        self.symbols = None;
+        self.item_spans.clear();
+        self.syntax_diagnostics.clear();
 
-        // Lex the source code
-        let mut lexer = Lexer::new(&self.text);
+        let mut lexer = Lexer::new(&self.text).with_file(format!("<lsp:{}>", self.uri));
         let (tokens, lex_diagnostics) = lexer.tokenize();
 
-        if !lex_diagnostics.is_empty() {
-            self.diagnostics.extend(lex_diagnostics);
-            return;
+        // Store partial AST even on lex or parse errors — enables LSP features
+        // (hover, completion) for the portion of the document that parsed
+        // successfully. The lexer closes an unterminated string itself, so
+        // one missing quote mid-edit no longer costs the whole document; the
+        // parse errors that follow a lex error only echo it and are dropped.
+        let mut parser = Parser::new(tokens);
+        let (ast, item_spans, parse_diagnostics) = parser.parse_with_item_spans();
+        self.ast = Some(ast);
+        self.item_spans = item_spans;
+        self.syntax_diagnostics = if lex_diagnostics.is_empty() {
+            parse_diagnostics
+        } else {
+            lex_diagnostics
+        };
+    }
+
+    /// Re-parse the items overlapping the edited range `start..end` (in the
+    /// old text's offsets) plus one neighbor on each side, reusing every
+    /// other item. Returns `false` when a full parse is needed instead.
+    fn reparse_items(&mut self, start: usize, end: usize, old_len: usize, delta: isize) -> bool {
+        if !self.syntax_diagnostics.is_empty() {
+            return false;
+        }
+        let Some(ast) = self.ast.as_mut() else {
+            return false;
+        };
+        let count = ast.items.len();
+        if count == 0 || self.item_spans.len() != count {
+            return false;
         }
 
-        // Parse tokens into AST
-        let mut parser = Parser::new(tokens);
-        let (ast, parse_diagnostics) = parser.parse();
+        // Neighbors are included because an edit at an item's edge can change
+        // where it ends, e.g. deleting the `;` of the statement before it.
+        let first = self
+            .item_spans
+            .iter()
+            .take_while(|span| span.end < start)
+            .count()
+            .saturating_sub(1);
+        let last = (self
+            .item_spans
+            .iter()
+            .take_while(|span| span.start <= end)
+            .count()
+            + 1)
+        .min(count);
 
-        // Store partial AST even on parse errors — enables LSP features (hover, completion)
-        // for the portion of the document that parsed successfully.
-        self.ast = Some(ast.clone());
+        let region_start = if first == 0 {
+            0
+        } else {
+            self.item_spans[first - 1].end
+        };
+        let old_region_end = if last == count {
+            old_len
+        } else {
+            self.item_spans[last].start
+        };
+        let region_end = old_region_end.saturating_add_signed(delta);
+
+        let region: String = self
+            .text
+            .chars()
+            .skip(region_start)
+            .take(region_end.saturating_sub(region_start))
+            .collect();
+        let mut lexer = Lexer::new(region);
+        let (mut tokens, lex_diagnostics) = lexer.tokenize();
+        // A macro declared in the region may be expanded by reused items
+        if !lex_diagnostics.is_empty() || Parser::uses_macros(&tokens) {
+            return false;
+        }
+        let to_document = Relocation {
+            delta: region_start as isize,
+            file: self.file,
+        };
+        for token in &mut tokens {
+            token.span.relocate(to_document);
+        }
 
+        // An edit that leaves the region unbalanced (an open brace or
+        // string) may swallow later items, which only a full parse sees.
+        let mut parser = Parser::new(tokens);
+        let (region_ast, region_spans, parse_diagnostics) = parser.parse_with_item_spans();
         if !parse_diagnostics.is_empty() {
-            self.diagnostics.extend(parse_diagnostics);
-            return;
+            return false;
         }
 
-        // Bind symbols
-        let mut binder = Binder::new();
-        let (mut symbol_table, bind_diagnostics) = binder.bind(&ast);
+        let shift = Relocation {
+            delta,
+            file: self.file,
+        };
+        let mut tail_items = ast.items.split_off(last);
+        let mut tail_spans = self.item_spans.split_off(last);
+        tail_items.relocate(shift);
+        tail_spans.relocate(shift);
+        ast.items.truncate(first);
+        self.item_spans.truncate(first);
+
+        ast.items.extend(region_ast.items);
+        ast.items.extend(tail_items);
+        self.item_spans.extend(region_spans);
+        self.item_spans.extend(tail_spans);
 
-        if !bind_diagnostics.is_empty() {
-            self.diagnostics.extend(bind_diagnostics);
-            return;
-        }
+        // Reused spans resolve line numbers against the document's source
+        register_source(self.file, self.text.clone());
+        self.reused_items = first + (count - last);
+        true
+    }
+
+    /// Bind and type-check the current AST and update diagnostics
+    fn analyze(&mut self) {
+        let context = module_context(self.modules.as_ref(), self.ast.as_ref());
+        let (diagnostics, symbols) = analyze(
+            &self.text,
+            self.ast.as_ref(),
+            &self.syntax_diagnostics,
+            context.as_ref(),
+        );
+        self.diagnostics = diagnostics;
+        self.symbols = symbols;
+        self.stale = false;
+    }
+}
 
-        // Type check
-        let mut typechecker = TypeChecker::new(&mut symbol_table);
-        let typecheck_diagnostics = typechecker.check(&ast);
+/// Imports of `ast` resolved through the document's module graph
+fn module_context(
+    modules: Option<&(Arc<Mutex<ModuleGraph>>, PathBuf)>,
+    ast: Option<&Program>,
+) -> Option<ModuleContext> {
+    let (graph, path) = modules?;
+    graph
+        .lock()
+        .expect("module graph lock poisoned")
+        .context(path, ast?)
+}
 
-        // Store symbols for navigation (after type checking updates them)
-        self.symbols = Some(symbol_table.clone());
+/// Bind and type-check `ast`, returning its diagnostics and, when binding
+/// succeeds, the symbol table
+///
+/// Imports bind against `modules` when given; without it they are ignored.
+fn analyze(
+    text: &str,
+    ast: Option<&Program>,
+    syntax_diagnostics: &[Diagnostic],
+    modules: Option<&ModuleContext>,
+) -> (Vec<Diagnostic>, Option<SymbolTable>) {
+    let mut diagnostics = syntax_diagnostics.to_vec();
+    if !diagnostics.is_empty() {
+        return (diagnostics, None);
+    }
+    let Some(ast) = ast else {
+        return (diagnostics, None);
+    };
 
-        if !typecheck_diagnostics.is_empty() {
-            self.diagnostics.extend(typecheck_diagnostics);
+    // Bind symbols
+    let mut binder = Binder::new();
+    let (mut symbol_table, bind_diagnostics) = match modules {
+        Some(modules) => {
+            binder.set_resolved_imports(modules.resolved_imports.clone());
+            binder.bind_with_modules(ast, &modules.path, &modules.registry)
         }
+        None => binder.bind(ast),
+    };
+
+    if !bind_diagnostics.is_empty() {
+        diagnostics.extend(bind_diagnostics);
+        return (diagnostics, None);
     }
+
+    // Type check
+    let mut typechecker = TypeChecker::new(&mut symbol_table);
+    let typecheck_diagnostics = typechecker.check(ast);
+
+    diagnostics.extend(apply_suppressions(text, typecheck_diagnostics));
+    (diagnostics, Some(symbol_table))
 }
@@ -30,7 +30,7 @@ pub fn generate_folding_ranges(text: &str, ast: Option<&Program>) -> Vec<Folding
     }
 
     // Sort by start line and deduplicate
-    ranges.sort_by(|a, b| a.start_line.cmp(&b.start_line));
+    ranges.sort_by_key(|r| r.start_line);
     ranges.dedup_by(|a, b| a.start_line == b.start_line && a.end_line == b.end_line);
 
     ranges
@@ -221,6 +221,22 @@ fn extract_statement_folds(text: &str, stmt: &Stmt, ranges: &mut Vec<FoldingRang
             }
             extract_block_folds(text, &for_in.body, ranges);
         }
+        Stmt::TryCatch(try_catch) => {
+            for block in [&try_catch.body, &try_catch.catch_block] {
+                let block_range = span_to_range(text, block.span);
+                if block_range.end.line > block_range.start.line {
+                    ranges.push(FoldingRange {
+                        start_line: block_range.start.line,
+                        start_character: Some(block_range.start.character),
+                        end_line: block_range.end.line,
+                        end_character: Some(block_range.end.character),
+                        kind: Some(FoldingRangeKind::Region),
+                        collapsed_text: None,
+                    });
+                }
+                extract_block_folds(text, block, ranges);
+            }
+        }
         Stmt::VarDecl(var) => {
             // Large array literals can be folded
             extract_expression_folds(text, &var.init, ranges);
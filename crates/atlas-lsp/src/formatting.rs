@@ -1,84 +1,200 @@
 //! Code formatting helpers
+//!
+//! Full-document, range and on-type formatting all run the same
+//! atlas-formatter engine. Range and on-type requests reformat the whole
+//! top-level items they touch, since an item is the smallest unit the
+//! formatter can lay out on its own.
 
-use tower_lsp::lsp_types::{Position, Range, TextEdit};
+use crate::convert::{offset_to_position, position_to_offset};
+use atlas_formatter::{FormatConfig, FormatResult};
+use atlas_runtime::ast::{Item, Program};
+use atlas_runtime::span::Span;
+use atlas_runtime::{Lexer, Parser};
+use tower_lsp::lsp_types::{FormattingOptions, Position, Range, TextEdit};
+
+/// Characters that trigger on-type formatting
+pub const ON_TYPE_TRIGGERS: &[&str] = &["}", ";"];
 
 /// Format an Atlas source file
 ///
-/// This is a basic formatter that ensures consistent indentation and spacing.
-/// For now, it returns the source as-is since we don't have a full formatter implementation.
-/// In the future, this would be replaced with a proper AST-based formatter.
-pub fn format_document(source: &str) -> Vec<TextEdit> {
-    // For now, just return empty edits (no formatting changes)
-    // A full formatter would:
-    // 1. Parse the source to AST
-    // 2. Pretty-print the AST with consistent style
-    // 3. Generate TextEdits for the differences
-
-    // Calculate the range of the entire document
-    let lines: Vec<&str> = source.lines().collect();
-    let last_line = lines.len().saturating_sub(1);
-    let last_char = lines.last().map(|l| l.len()).unwrap_or(0);
-
-    let full_range = Range {
-        start: Position {
-            line: 0,
-            character: 0,
-        },
-        end: Position {
-            line: last_line as u32,
-            character: last_char as u32,
-        },
+/// Returns no edits when the file is already formatted or does not parse.
+pub fn format_document(source: &str, options: &FormattingOptions) -> Vec<TextEdit> {
+    match atlas_formatter::format_source_with_config(source, &config_for(options)) {
+        FormatResult::Ok(formatted) => minimal_edit(source, 0, source, &formatted)
+            .into_iter()
+            .collect(),
+        FormatResult::ParseError(_) => vec![],
+    }
+}
+
+/// Format the top-level items of an Atlas source file that overlap `range`
+///
+/// Text outside those items, including blank lines between them and the
+/// rest of the file, is left untouched.
+pub fn format_range(source: &str, range: Range, options: &FormattingOptions) -> Vec<TextEdit> {
+    let Some(program) = parse(source) else {
+        return vec![];
+    };
+    let start = position_to_offset(source, range.start);
+    let end = position_to_offset(source, range.end).max(start);
+    let Some((from, to)) = covering_lines(source, &program, start, end) else {
+        return vec![];
     };
 
-    // Return a single edit that replaces the entire document with itself
-    // This demonstrates the formatting capability without actually changing anything
-    vec![TextEdit {
-        range: full_range,
-        new_text: source.to_string(),
-    }]
+    let snippet = &source[from..to];
+    match atlas_formatter::format_source_with_config(snippet, &config_for(options)) {
+        FormatResult::Ok(formatted) => {
+            let formatted = formatted.trim_end_matches('\n');
+            minimal_edit(source, from, snippet, formatted)
+                .into_iter()
+                .collect()
+        }
+        FormatResult::ParseError(_) => vec![],
+    }
 }
 
-/// Format a range of an Atlas source file
-pub fn format_range(source: &str, range: Range) -> Vec<TextEdit> {
-    // For range formatting, we would:
-    // 1. Extract the text in the range
-    // 2. Parse and format just that portion
-    // 3. Generate edits for the changes
-    //
-    // For now, just return the range unchanged
-    let lines: Vec<&str> = source.lines().collect();
-    let start_line = range.start.line as usize;
-    let end_line = range.end.line as usize;
-
-    if start_line >= lines.len() {
+/// Re-format the item just edited after `ch` was typed at `position`
+pub fn format_on_type(
+    source: &str,
+    position: Position,
+    ch: &str,
+    options: &FormattingOptions,
+) -> Vec<TextEdit> {
+    if !ON_TYPE_TRIGGERS.contains(&ch) {
         return vec![];
     }
+    // The typed character sits just before the cursor
+    let line = Position::new(position.line, position.character.saturating_sub(1));
+    format_range(source, Range::new(line, line), options)
+}
 
-    let end_line = end_line.min(lines.len() - 1);
-    let range_text = lines[start_line..=end_line].join("\n");
+/// Formatter configuration for the client's formatting options
+fn config_for(options: &FormattingOptions) -> FormatConfig {
+    let config = FormatConfig::default();
+    match options.tab_size {
+        0 => config,
+        size => config.with_indent_size(size as usize),
+    }
+}
 
-    vec![TextEdit {
-        range,
-        new_text: range_text,
-    }]
+fn parse(source: &str) -> Option<Program> {
+    let mut lexer = Lexer::new(source);
+    let (tokens, lex_diags) = lexer.tokenize();
+    let mut parser = Parser::new(tokens);
+    let (program, parse_diags) = parser.parse();
+    (lex_diags.is_empty() && parse_diags.is_empty()).then_some(program)
+}
+
+/// Byte range of the whole lines holding every item that overlaps the
+/// lines of `start..end`, widened until no other item shares those lines
+fn covering_lines(
+    source: &str,
+    program: &Program,
+    start: usize,
+    end: usize,
+) -> Option<(usize, usize)> {
+    let spans: Vec<Span> = program.items.iter().map(item_span).collect();
+    let mut from = line_start(source, start);
+    let mut to = line_end(source, end);
+    let mut covered = None;
+
+    loop {
+        let overlapping = spans.iter().filter(|s| s.start <= to && s.end > from);
+        let lines = overlapping.fold(None, |lines: Option<(usize, usize)>, span| {
+            let item = (line_start(source, span.start), line_end(source, span.end));
+            Some(lines.map_or(item, |(a, b)| (a.min(item.0), b.max(item.1))))
+        });
+        if lines.is_none() || lines == covered {
+            return lines;
+        }
+        covered = lines;
+        (from, to) = lines?;
+    }
+}
+
+fn item_span(item: &Item) -> Span {
+    match item {
+        Item::Function(f) => f.span,
+        Item::Statement(s) => s.span(),
+        Item::Import(i) => i.span,
+        Item::Export(e) => e.span,
+        Item::Extern(e) => e.span,
+        Item::TypeAlias(t) => t.span,
+        Item::Const(c) => c.span,
+        Item::Trait(t) => t.span(),
+        Item::Impl(i) => i.span(),
+        Item::Struct(s) => s.span(),
+        Item::Enum(e) => e.span(),
+    }
+}
+
+fn line_start(source: &str, offset: usize) -> usize {
+    let offset = offset.min(source.len());
+    source[..offset].rfind('\n').map_or(0, |i| i + 1)
+}
+
+fn line_end(source: &str, offset: usize) -> usize {
+    let offset = offset.min(source.len());
+    source[offset..]
+        .find('\n')
+        .map_or(source.len(), |i| offset + i)
+}
+
+/// A single edit turning `old` (found at `base` in `source`) into `new`,
+/// trimmed to the part that actually changed so the cursor and unchanged
+/// text stay put
+fn minimal_edit(source: &str, base: usize, old: &str, new: &str) -> Option<TextEdit> {
+    if old == new {
+        return None;
+    }
+    let prefix: usize = old
+        .chars()
+        .zip(new.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum();
+    let suffix: usize = old[prefix..]
+        .chars()
+        .rev()
+        .zip(new[prefix..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum();
+
+    Some(TextEdit {
+        range: Range {
+            start: offset_to_position(source, base + prefix),
+            end: offset_to_position(source, base + old.len() - suffix),
+        },
+        new_text: new[prefix..new.len() - suffix].to_string(),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn options() -> FormattingOptions {
+        FormattingOptions {
+            tab_size: 4,
+            insert_spaces: true,
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_format_document() {
         let source = "let x: number = 42;";
-        let edits = format_document(source);
-        assert!(!edits.is_empty());
+        let edits = format_document(source, &options());
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].new_text, "\n");
     }
 
     #[test]
     fn test_format_empty_document() {
         let source = "";
-        let edits = format_document(source);
-        assert!(!edits.is_empty());
+        let edits = format_document(source, &options());
+        assert!(edits.is_empty());
     }
 
     #[test]
@@ -94,7 +210,15 @@ mod tests {
                 character: 18,
             },
         };
-        let edits = format_range(source, range);
-        assert!(!edits.is_empty());
+        let edits = format_range(source, range, &options());
+        assert!(edits.is_empty());
+    }
+
+    #[test]
+    fn test_minimal_edit_keeps_shared_text() {
+        let edit = minimal_edit("let x=1;", 0, "let x=1;", "let x = 1;").unwrap();
+        assert_eq!(edit.range.start, Position::new(0, 5));
+        assert_eq!(edit.range.end, Position::new(0, 6));
+        assert_eq!(edit.new_text, " = ");
     }
 }
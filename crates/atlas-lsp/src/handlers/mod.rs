@@ -11,7 +11,8 @@
 //! ## Code Intelligence
 //! - `hover` in hover.rs - Type info, documentation, builtin help
 //! - `completion` in completion.rs - Code completions
-//! - `code_action` in actions.rs - Quick fixes and refactorings
+//! - `code_action` in actions.rs - Quick fixes (by diagnostic code, including
+//!   imports from other open documents) and refactorings
 //!
 //! ## Syntax
 //! - `semantic_tokens_full`, `semantic_tokens_range` in semantic_tokens.rs
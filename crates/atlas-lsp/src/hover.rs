@@ -303,25 +303,52 @@ fn find_type_alias_hover(program: &Program, identifier: &str) -> Option<String>
     None
 }
 
+/// Describe what an ownership annotation means for the caller
+fn ownership_summary(ownership: &Option<OwnershipAnnotation>) -> Option<&'static str> {
+    match ownership {
+        Some(OwnershipAnnotation::Own) => Some(
+            "The argument is moved into this function — the caller's binding \
+             cannot be used after the call.",
+        ),
+        Some(OwnershipAnnotation::Borrow) => {
+            Some("The argument is borrowed read-only — the caller keeps ownership.")
+        }
+        Some(OwnershipAnnotation::Share) => Some(
+            "The argument is shared — caller and callee both hold valid references \
+             and neither may transfer ownership.",
+        ),
+        None => None,
+    }
+}
+
 /// Find hover information for a parameter (with ownership annotation)
 fn find_parameter_hover(program: &Program, identifier: &str) -> Option<String> {
-    for item in &program.items {
-        if let Item::Function(func) = item {
-            for param in &func.params {
-                if param.name.name == identifier {
-                    let label = ownership_label(&param.ownership);
-                    let mut hover = String::new();
-                    hover.push_str("```atlas\n");
-                    hover.push_str(&format!(
-                        "{}{}: {}",
-                        label,
-                        param.name.name,
-                        format_type_ref(&param.type_ref)
-                    ));
-                    hover.push_str("\n```");
-                    return Some(hover);
-                }
+    let params = program.items.iter().flat_map(|item| match item {
+        Item::Function(func) => func.params.iter().collect::<Vec<_>>(),
+        Item::Impl(impl_block) => impl_block
+            .methods
+            .iter()
+            .flat_map(|m| m.params.iter())
+            .collect(),
+        _ => Vec::new(),
+    });
+    for param in params {
+        if param.name.name == identifier {
+            let label = ownership_label(&param.ownership);
+            let mut hover = String::new();
+            hover.push_str("```atlas\n");
+            hover.push_str(&format!(
+                "{}{}: {}",
+                label,
+                param.name.name,
+                format_type_ref(&param.type_ref)
+            ));
+            hover.push_str("\n```");
+            if let Some(summary) = ownership_summary(&param.ownership) {
+                hover.push_str("\n\n");
+                hover.push_str(summary);
             }
+            return Some(hover);
         }
     }
     None
@@ -508,6 +535,10 @@ fn find_builtin_hover(identifier: &str) -> Option<String> {
             "fn typeof(value: any) -> string",
             "Returns the type of a value as a string",
         ),
+        "inspect" => (
+            "fn inspect(value: any, options?: Map<string, number>) -> string",
+            "Renders a value for debugging: quoted strings, nested collections, truncated output",
+        ),
         "is_number" => (
             "fn is_number(value: any) -> bool",
             "Checks if value is a number",
@@ -788,6 +819,8 @@ fn find_keyword_hover(identifier: &str) -> Option<String> {
         "return" => "Returns a value from a function.",
         "break" => "Exits the current loop.",
         "continue" => "Skips to the next iteration of a loop.",
+        "try" => "Runs a block and recovers from runtime errors in its `catch` block.",
+        "catch" => "Handles a runtime error raised in a `try` block; `catch (e)` binds `{ kind, message, code }`.",
         "match" => "Pattern matching expression.",
         "type" => "Declares a type alias.",
         "import" => "Imports symbols from another module.",
@@ -895,7 +928,7 @@ fn lookup_inferred_return_type(symbols: Option<&SymbolTable>, func_name: &str) -
 }
 
 /// Format a type reference for display
-fn format_type_ref(type_ref: &TypeRef) -> String {
+pub(crate) fn format_type_ref(type_ref: &TypeRef) -> String {
     match type_ref {
         TypeRef::Named(name, _) => name.clone(),
         TypeRef::Array(inner, _) => format!("{}[]", format_type_ref(inner)),
@@ -942,7 +975,7 @@ fn format_type_ref(type_ref: &TypeRef) -> String {
 }
 
 /// Extract doc comment preceding a position
-fn extract_doc_comment(source: &str, position: usize) -> Option<String> {
+pub(crate) fn extract_doc_comment(source: &str, position: usize) -> Option<String> {
     // Simple approach: look for /// comments in the lines preceding the position
     let lines: Vec<&str> = source.lines().collect();
 
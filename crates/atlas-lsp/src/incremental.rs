@@ -0,0 +1,577 @@
+//! Span relocation for incremental re-parsing
+//!
+//! When an edit only touches some top-level items, `DocumentState` keeps the
+//! parsed trees of the others. Items after the edit are unchanged apart from
+//! their position, so their spans are moved by the edit's length delta and
+//! pointed at the document's current source.
+
+use atlas_runtime::ast::*;
+use atlas_runtime::span::{FileId, Span};
+
+/// Offset delta and target file applied to every span of a reused item
+#[derive(Debug, Clone, Copy)]
+pub struct Relocation {
+    pub delta: isize,
+    pub file: FileId,
+}
+
+impl Relocation {
+    fn span(&self, span: &mut Span) {
+        span.start = span.start.saturating_add_signed(self.delta);
+        span.end = span.end.saturating_add_signed(self.delta);
+        span.file = self.file;
+    }
+}
+
+/// AST nodes whose spans can be relocated in place
+pub trait Relocate {
+    fn relocate(&mut self, by: Relocation);
+}
+
+impl Relocate for Span {
+    fn relocate(&mut self, by: Relocation) {
+        by.span(self);
+    }
+}
+
+impl<T: Relocate> Relocate for Vec<T> {
+    fn relocate(&mut self, by: Relocation) {
+        for node in self {
+            node.relocate(by);
+        }
+    }
+}
+
+impl<T: Relocate> Relocate for Option<T> {
+    fn relocate(&mut self, by: Relocation) {
+        if let Some(node) = self {
+            node.relocate(by);
+        }
+    }
+}
+
+impl<T: Relocate> Relocate for Box<T> {
+    fn relocate(&mut self, by: Relocation) {
+        (**self).relocate(by);
+    }
+}
+
+impl Relocate for Identifier {
+    fn relocate(&mut self, by: Relocation) {
+        by.span(&mut self.span);
+    }
+}
+
+impl Relocate for Attribute {
+    fn relocate(&mut self, by: Relocation) {
+        by.span(&mut self.span);
+    }
+}
+
+impl Relocate for Item {
+    fn relocate(&mut self, by: Relocation) {
+        match self {
+            Item::Function(func) => func.relocate(by),
+            Item::Statement(stmt) => stmt.relocate(by),
+            Item::Import(import) => {
+                for specifier in &mut import.specifiers {
+                    match specifier {
+                        ImportSpecifier::Named { name, span } => {
+                            name.relocate(by);
+                            by.span(span);
+                        }
+                        ImportSpecifier::Namespace { alias, span } => {
+                            alias.relocate(by);
+                            by.span(span);
+                        }
+                    }
+                }
+                by.span(&mut import.span);
+            }
+            Item::Export(export) => {
+                match &mut export.item {
+                    ExportItem::Function(func) => func.relocate(by),
+                    ExportItem::Variable(var) => var.relocate(by),
+                    ExportItem::TypeAlias(alias) => alias.relocate(by),
+                    ExportItem::Const(decl) => decl.relocate(by),
+                    ExportItem::Struct(decl) => decl.relocate(by),
+                    ExportItem::Enum(decl) => decl.relocate(by),
+                    ExportItem::ReExport { names, span, .. } => {
+                        for name in names {
+                            name.name.relocate(by);
+                            name.alias.relocate(by);
+                            by.span(&mut name.span);
+                        }
+                        by.span(span);
+                    }
+                }
+                by.span(&mut export.span);
+            }
+            Item::Extern(decl) => by.span(&mut decl.span),
+            Item::TypeAlias(alias) => alias.relocate(by),
+            Item::Const(decl) => decl.relocate(by),
+            Item::Trait(decl) => {
+                decl.name.relocate(by);
+                decl.attributes.relocate(by);
+                decl.type_params.relocate(by);
+                for method in &mut decl.methods {
+                    method.name.relocate(by);
+                    method.type_params.relocate(by);
+                    method.params.relocate(by);
+                    method.return_type.relocate(by);
+                    method.body.relocate(by);
+                    by.span(&mut method.span);
+                }
+                by.span(&mut decl.span);
+            }
+            Item::Impl(block) => {
+                block.trait_name.relocate(by);
+                block.trait_type_args.relocate(by);
+                block.type_name.relocate(by);
+                for method in &mut block.methods {
+                    method.name.relocate(by);
+                    method.type_params.relocate(by);
+                    method.params.relocate(by);
+                    method.return_type.relocate(by);
+                    method.body.relocate(by);
+                    by.span(&mut method.span);
+                }
+                by.span(&mut block.span);
+            }
+            Item::Struct(decl) => decl.relocate(by),
+            Item::Enum(decl) => decl.relocate(by),
+        }
+    }
+}
+
+impl Relocate for FunctionDecl {
+    fn relocate(&mut self, by: Relocation) {
+        self.name.relocate(by);
+        self.attributes.relocate(by);
+        self.type_params.relocate(by);
+        self.params.relocate(by);
+        self.return_type.relocate(by);
+        if let Some(predicate) = &mut self.predicate {
+            predicate.param.relocate(by);
+            predicate.target.relocate(by);
+            by.span(&mut predicate.span);
+        }
+        self.body.relocate(by);
+        by.span(&mut self.span);
+    }
+}
+
+impl Relocate for TypeAliasDecl {
+    fn relocate(&mut self, by: Relocation) {
+        self.name.relocate(by);
+        self.type_params.relocate(by);
+        self.type_ref.relocate(by);
+        by.span(&mut self.span);
+    }
+}
+
+impl Relocate for ConstDecl {
+    fn relocate(&mut self, by: Relocation) {
+        self.name.relocate(by);
+        self.type_ref.relocate(by);
+        self.init.relocate(by);
+        by.span(&mut self.span);
+    }
+}
+
+impl Relocate for StructField {
+    fn relocate(&mut self, by: Relocation) {
+        self.name.relocate(by);
+        self.type_ref.relocate(by);
+        by.span(&mut self.span);
+    }
+}
+
+impl Relocate for StructDecl {
+    fn relocate(&mut self, by: Relocation) {
+        self.attributes.relocate(by);
+        self.name.relocate(by);
+        self.type_params.relocate(by);
+        self.fields.relocate(by);
+        by.span(&mut self.span);
+    }
+}
+
+impl Relocate for EnumDecl {
+    fn relocate(&mut self, by: Relocation) {
+        self.name.relocate(by);
+        self.type_params.relocate(by);
+        for variant in &mut self.variants {
+            match variant {
+                EnumVariant::Unit { name, span } => {
+                    name.relocate(by);
+                    by.span(span);
+                }
+                EnumVariant::Tuple { name, fields, span } => {
+                    name.relocate(by);
+                    fields.relocate(by);
+                    by.span(span);
+                }
+                EnumVariant::Struct { name, fields, span } => {
+                    name.relocate(by);
+                    fields.relocate(by);
+                    by.span(span);
+                }
+            }
+        }
+        by.span(&mut self.span);
+    }
+}
+
+impl Relocate for TypeParam {
+    fn relocate(&mut self, by: Relocation) {
+        for bound in &mut self.trait_bounds {
+            by.span(&mut bound.span);
+        }
+        by.span(&mut self.span);
+    }
+}
+
+impl Relocate for Param {
+    fn relocate(&mut self, by: Relocation) {
+        self.name.relocate(by);
+        self.type_ref.relocate(by);
+        self.default_value.relocate(by);
+        by.span(&mut self.span);
+    }
+}
+
+impl Relocate for Block {
+    fn relocate(&mut self, by: Relocation) {
+        self.statements.relocate(by);
+        self.tail_expr.relocate(by);
+        by.span(&mut self.span);
+    }
+}
+
+impl Relocate for VarDecl {
+    fn relocate(&mut self, by: Relocation) {
+        self.name.relocate(by);
+        self.type_ref.relocate(by);
+        self.init.relocate(by);
+        by.span(&mut self.span);
+    }
+}
+
+impl Relocate for AssignTarget {
+    fn relocate(&mut self, by: Relocation) {
+        match self {
+            AssignTarget::Name(name) => name.relocate(by),
+            AssignTarget::Index {
+                target,
+                index,
+                span,
+            } => {
+                target.relocate(by);
+                index.relocate(by);
+                by.span(span);
+            }
+            AssignTarget::Member {
+                target,
+                member,
+                span,
+            } => {
+                target.relocate(by);
+                member.relocate(by);
+                by.span(span);
+            }
+        }
+    }
+}
+
+impl Relocate for Stmt {
+    fn relocate(&mut self, by: Relocation) {
+        match self {
+            Stmt::VarDecl(var) => var.relocate(by),
+            Stmt::LetDestructure(destructure) => {
+                destructure.names.relocate(by);
+                destructure.init.relocate(by);
+                by.span(&mut destructure.span);
+            }
+            Stmt::FunctionDecl(func) => func.relocate(by),
+            Stmt::Assign(assign) => {
+                assign.target.relocate(by);
+                assign.value.relocate(by);
+                by.span(&mut assign.span);
+            }
+            Stmt::CompoundAssign(assign) => {
+                assign.target.relocate(by);
+                assign.value.relocate(by);
+                by.span(&mut assign.span);
+            }
+            Stmt::If(stmt) => {
+                stmt.cond.relocate(by);
+                stmt.then_block.relocate(by);
+                stmt.else_block.relocate(by);
+                by.span(&mut stmt.span);
+            }
+            Stmt::While(stmt) => {
+                stmt.cond.relocate(by);
+                stmt.body.relocate(by);
+                by.span(&mut stmt.span);
+            }
+            Stmt::ForIn(stmt) => {
+                stmt.variable.relocate(by);
+                stmt.iterable.relocate(by);
+                stmt.body.relocate(by);
+                by.span(&mut stmt.span);
+            }
+            Stmt::Return(stmt) => {
+                stmt.value.relocate(by);
+                by.span(&mut stmt.span);
+            }
+            Stmt::Break(span) | Stmt::Continue(span) => by.span(span),
+            Stmt::Expr(stmt) => {
+                stmt.expr.relocate(by);
+                by.span(&mut stmt.span);
+            }
+            Stmt::Defer(stmt) => {
+                stmt.body.relocate(by);
+                by.span(&mut stmt.span);
+            }
+            Stmt::TryCatch(stmt) => {
+                stmt.body.relocate(by);
+                stmt.error_binding.relocate(by);
+                stmt.catch_block.relocate(by);
+                by.span(&mut stmt.span);
+            }
+        }
+    }
+}
+
+impl Relocate for Expr {
+    fn relocate(&mut self, by: Relocation) {
+        match self {
+            Expr::Literal(_, span) => by.span(span),
+            Expr::TemplateString { parts, span } => {
+                for part in parts {
+                    if let TemplatePart::Expression(expr) = part {
+                        expr.relocate(by);
+                    }
+                }
+                by.span(span);
+            }
+            Expr::Identifier(ident) => ident.relocate(by),
+            Expr::Unary(unary) => {
+                unary.expr.relocate(by);
+                by.span(&mut unary.span);
+            }
+            Expr::Binary(binary) => {
+                binary.left.relocate(by);
+                binary.right.relocate(by);
+                by.span(&mut binary.span);
+            }
+            Expr::Call(call) => {
+                call.callee.relocate(by);
+                call.args.relocate(by);
+                call.type_args.relocate(by);
+                by.span(&mut call.span);
+            }
+            Expr::Index(index) => {
+                index.target.relocate(by);
+                let IndexValue::Single(value) = &mut index.index;
+                value.relocate(by);
+                by.span(&mut index.span);
+            }
+            Expr::Member(member) => {
+                member.target.relocate(by);
+                member.member.relocate(by);
+                member.args.relocate(by);
+                member.type_args.relocate(by);
+                by.span(&mut member.span);
+            }
+            Expr::ArrayLiteral(array) => {
+                array.elements.relocate(by);
+                by.span(&mut array.span);
+            }
+            Expr::ObjectLiteral(object) => {
+                for entry in &mut object.entries {
+                    entry.key.relocate(by);
+                    entry.value.relocate(by);
+                    by.span(&mut entry.span);
+                }
+                by.span(&mut object.span);
+            }
+            Expr::StructExpr(expr) => {
+                expr.name.relocate(by);
+                for field in &mut expr.fields {
+                    field.name.relocate(by);
+                    field.value.relocate(by);
+                    by.span(&mut field.span);
+                }
+                by.span(&mut expr.span);
+            }
+            Expr::Range {
+                start, end, span, ..
+            } => {
+                start.relocate(by);
+                end.relocate(by);
+                by.span(span);
+            }
+            Expr::Group(group) => {
+                group.expr.relocate(by);
+                by.span(&mut group.span);
+            }
+            Expr::TupleLiteral { elements, span } => {
+                elements.relocate(by);
+                by.span(span);
+            }
+            Expr::Match(expr) => {
+                expr.scrutinee.relocate(by);
+                for arm in &mut expr.arms {
+                    arm.pattern.relocate(by);
+                    arm.guard.relocate(by);
+                    arm.body.relocate(by);
+                    by.span(&mut arm.span);
+                }
+                by.span(&mut expr.span);
+            }
+            Expr::Try(expr) => {
+                expr.expr.relocate(by);
+                by.span(&mut expr.span);
+            }
+            Expr::AnonFn {
+                params,
+                return_type,
+                body,
+                span,
+            } => {
+                params.relocate(by);
+                return_type.relocate(by);
+                body.relocate(by);
+                by.span(span);
+            }
+            Expr::Block(block) => block.relocate(by),
+            Expr::EnumVariant(expr) => {
+                expr.enum_name.relocate(by);
+                expr.variant_name.relocate(by);
+                expr.args.relocate(by);
+                by.span(&mut expr.span);
+            }
+            Expr::Await { expr, span } => {
+                expr.relocate(by);
+                by.span(span);
+            }
+            Expr::New {
+                type_name,
+                type_args,
+                args,
+                span,
+            } => {
+                type_name.relocate(by);
+                type_args.relocate(by);
+                args.relocate(by);
+                by.span(span);
+            }
+        }
+    }
+}
+
+impl Relocate for Pattern {
+    fn relocate(&mut self, by: Relocation) {
+        match self {
+            Pattern::Literal(_, span) | Pattern::Wildcard(span) | Pattern::Range { span, .. } => {
+                by.span(span)
+            }
+            Pattern::Variable(ident) => ident.relocate(by),
+            Pattern::Constructor { name, args, span }
+            | Pattern::BareVariant { name, args, span } => {
+                name.relocate(by);
+                args.relocate(by);
+                by.span(span);
+            }
+            Pattern::Array {
+                elements,
+                rest,
+                span,
+            } => {
+                elements.relocate(by);
+                rest.relocate(by);
+                by.span(span);
+            }
+            Pattern::Tuple { elements, span } => {
+                elements.relocate(by);
+                by.span(span);
+            }
+            Pattern::Or(alternatives, span) => {
+                alternatives.relocate(by);
+                by.span(span);
+            }
+            Pattern::EnumVariant {
+                enum_name,
+                variant_name,
+                args,
+                span,
+            } => {
+                enum_name.relocate(by);
+                variant_name.relocate(by);
+                args.relocate(by);
+                by.span(span);
+            }
+            Pattern::Struct {
+                type_name,
+                fields,
+                span,
+            } => {
+                type_name.relocate(by);
+                for field in fields {
+                    field.name.relocate(by);
+                    field.pattern.relocate(by);
+                    by.span(&mut field.span);
+                }
+                by.span(span);
+            }
+        }
+    }
+}
+
+impl Relocate for TypeRef {
+    fn relocate(&mut self, by: Relocation) {
+        match self {
+            TypeRef::Named(_, span) | TypeRef::SelfType(span) => by.span(span),
+            TypeRef::Array(inner, span) => {
+                inner.relocate(by);
+                by.span(span);
+            }
+            TypeRef::Function {
+                params,
+                return_type,
+                span,
+            } => {
+                params.relocate(by);
+                return_type.relocate(by);
+                by.span(span);
+            }
+            TypeRef::Structural { members, span } => {
+                for member in members {
+                    member.type_ref.relocate(by);
+                    by.span(&mut member.span);
+                }
+                by.span(span);
+            }
+            TypeRef::Generic {
+                type_args, span, ..
+            } => {
+                type_args.relocate(by);
+                by.span(span);
+            }
+            TypeRef::Union { members, span } | TypeRef::Intersection { members, span } => {
+                members.relocate(by);
+                by.span(span);
+            }
+            TypeRef::Future { inner, span } => {
+                inner.relocate(by);
+                by.span(span);
+            }
+            TypeRef::Tuple { elements, span } => {
+                elements.relocate(by);
+                by.span(span);
+            }
+        }
+    }
+}
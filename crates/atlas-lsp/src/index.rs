@@ -441,6 +441,19 @@ impl SymbolIndex {
             Stmt::Defer(defer) => {
                 self.index_block(&defer.body, ctx);
             }
+            Stmt::TryCatch(try_catch) => {
+                self.index_block(&try_catch.body, ctx);
+                if let Some(binding) = &try_catch.error_binding {
+                    self.add_definition(
+                        &binding.name,
+                        &binding.span,
+                        SymbolKind::Variable,
+                        ctx.current_scope.clone(),
+                        ctx,
+                    );
+                }
+                self.index_block(&try_catch.catch_block, ctx);
+            }
         }
     }
 
@@ -1,13 +1,21 @@
 //! Inlay hint provider
 //!
 //! Provides inlay hints for:
-//! - Type hints for variables with inferred types
-//! - Parameter name hints for function calls
+//! - Type hints for `let` bindings without an annotation, from the type the
+//!   typechecker inferred for the declaration
+//! - Parameter name hints for calls to functions declared in the document
+//! - Inferred return types on functions without an annotation
+//!
+//! Each kind can be switched off through `InlayHintConfig`, which the server
+//! fills from the client's `atlas.inlayHints` settings.
+
+use std::collections::HashMap;
 
 use atlas_runtime::ast::*;
 use atlas_runtime::symbol::{SymbolKind as AtlasSymbolKind, SymbolTable};
 use atlas_runtime::typechecker::inference::{infer_return_type, InferredReturn};
 use atlas_runtime::types::Type;
+use serde_json::Value as JsonValue;
 use tower_lsp::lsp_types::{InlayHint, InlayHintKind, InlayHintLabel, Position, Range};
 
 use crate::symbols::offset_to_position;
@@ -39,6 +47,42 @@ impl Default for InlayHintConfig {
     }
 }
 
+impl InlayHintConfig {
+    /// Apply client settings, leaving options the settings don't mention as they are
+    ///
+    /// Accepts the `inlayHints` object on its own or nested under `atlas`, as sent in
+    /// `initializationOptions` or `workspace/didChangeConfiguration`:
+    ///
+    /// ```json
+    /// { "atlas": { "inlayHints": { "typeHints": true, "parameterHints": false } } }
+    /// ```
+    ///
+    /// Keys: `typeHints`, `parameterHints`, `returnTypeHints`, `skipObviousTypes`
+    /// (booleans) and `maxTypeLength` (number).
+    pub fn apply_settings(&mut self, settings: &JsonValue) {
+        let settings = settings.get("atlas").unwrap_or(settings);
+        let Some(hints) = settings.get("inlayHints") else {
+            return;
+        };
+        let flag = |key: &str| hints.get(key).and_then(JsonValue::as_bool);
+        if let Some(on) = flag("typeHints") {
+            self.show_type_hints = on;
+        }
+        if let Some(on) = flag("parameterHints") {
+            self.show_parameter_hints = on;
+        }
+        if let Some(on) = flag("returnTypeHints") {
+            self.show_inferred_return = on;
+        }
+        if let Some(on) = flag("skipObviousTypes") {
+            self.skip_obvious_types = on;
+        }
+        if let Some(len) = hints.get("maxTypeLength").and_then(JsonValue::as_u64) {
+            self.max_type_length = len as usize;
+        }
+    }
+}
+
 /// Generate inlay hints for a document range
 pub fn generate_inlay_hints(
     text: &str,
@@ -47,501 +91,318 @@ pub fn generate_inlay_hints(
     symbols: Option<&SymbolTable>,
     config: &InlayHintConfig,
 ) -> Vec<InlayHint> {
-    let mut hints = Vec::new();
-
-    if let Some(program) = ast {
-        // Convert range to byte offsets for filtering
-        let start_offset = position_to_offset_simple(text, range.start);
-        let end_offset = position_to_offset_simple(text, range.end);
-
-        for item in &program.items {
-            extract_item_hints(
-                text,
-                item,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                &mut hints,
-            );
-        }
-    }
+    let Some(program) = ast else {
+        return Vec::new();
+    };
 
-    hints
+    let mut collector = HintCollector {
+        text,
+        symbols,
+        config,
+        start_offset: position_to_offset_simple(text, range.start),
+        end_offset: position_to_offset_simple(text, range.end),
+        param_names: collect_param_names(program),
+        hints: Vec::new(),
+    };
+    for item in &program.items {
+        collector.item(item);
+    }
+    collector.hints
 }
 
-/// Extract inlay hints from an AST item
-fn extract_item_hints(
-    text: &str,
-    item: &Item,
-    symbols: Option<&SymbolTable>,
-    config: &InlayHintConfig,
+/// Walks the AST and collects the hints that fall in the requested range
+struct HintCollector<'a> {
+    text: &'a str,
+    symbols: Option<&'a SymbolTable>,
+    config: &'a InlayHintConfig,
     start_offset: usize,
     end_offset: usize,
-    hints: &mut Vec<InlayHint>,
-) {
-    match item {
-        Item::Function(func) => {
-            // Inferred return type hint on unannotated functions
-            if config.show_inferred_return && func.return_type.is_none() {
-                emit_inferred_return_hint(text, func, symbols, start_offset, end_offset, hints);
-            }
-            // Hints for function body
-            extract_block_hints(
-                text,
-                &func.body,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
+    /// Parameter names of every function declared in the document
+    param_names: HashMap<String, Vec<String>>,
+    hints: Vec<InlayHint>,
+}
+
+impl HintCollector<'_> {
+    fn in_range(&self, offset: usize) -> bool {
+        offset >= self.start_offset && offset <= self.end_offset
+    }
+
+    fn item(&mut self, item: &Item) {
+        match item {
+            Item::Function(func)
+            | Item::Export(ExportDecl {
+                item: ExportItem::Function(func),
+                ..
+            }) => self.function(func),
+            Item::Export(ExportDecl {
+                item: ExportItem::Variable(var),
+                ..
+            }) => self.var_decl(var),
+            Item::Statement(stmt) => self.statement(stmt),
+            _ => {}
         }
-        Item::Statement(stmt) => {
-            extract_statement_hints(text, stmt, symbols, config, start_offset, end_offset, hints);
+    }
+
+    fn function(&mut self, func: &FunctionDecl) {
+        // Inferred return type hint on unannotated functions
+        if self.config.show_inferred_return && func.return_type.is_none() {
+            self.inferred_return(func);
         }
-        _ => {}
+        self.block(&func.body);
     }
-}
 
-/// Extract inlay hints from a block
-fn extract_block_hints(
-    text: &str,
-    block: &Block,
-    symbols: Option<&SymbolTable>,
-    config: &InlayHintConfig,
-    start_offset: usize,
-    end_offset: usize,
-    hints: &mut Vec<InlayHint>,
-) {
-    for stmt in &block.statements {
-        extract_statement_hints(text, stmt, symbols, config, start_offset, end_offset, hints);
+    fn block(&mut self, block: &Block) {
+        for stmt in &block.statements {
+            self.statement(stmt);
+        }
     }
-}
 
-/// Extract inlay hints from a statement
-fn extract_statement_hints(
-    text: &str,
-    stmt: &Stmt,
-    symbols: Option<&SymbolTable>,
-    config: &InlayHintConfig,
-    start_offset: usize,
-    end_offset: usize,
-    hints: &mut Vec<InlayHint>,
-) {
-    match stmt {
-        Stmt::VarDecl(var) => {
-            // Type hints for variables without explicit types
-            if config.show_type_hints && var.type_ref.is_none() {
-                // Check if in range
-                if var.span.start >= start_offset && var.span.start <= end_offset {
-                    // Skip obvious types if configured
-                    if !config.skip_obvious_types || !is_obvious_type(&var.init) {
-                        // Try to get type from symbol table
-                        if let Some(type_str) = get_variable_type(symbols, &var.name.name) {
-                            let truncated = truncate_type(&type_str, config.max_type_length);
-                            let position = offset_to_position(text, var.name.span.end);
-
-                            hints.push(InlayHint {
-                                position,
-                                label: InlayHintLabel::String(format!(": {}", truncated)),
-                                kind: Some(InlayHintKind::TYPE),
-                                text_edits: None,
-                                tooltip: if truncated != type_str {
-                                    Some(tower_lsp::lsp_types::InlayHintTooltip::String(type_str))
-                                } else {
-                                    None
-                                },
-                                padding_left: Some(false),
-                                padding_right: Some(true),
-                                data: None,
-                            });
-                        }
-                    }
+    fn statement(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::VarDecl(var) => self.var_decl(var),
+            Stmt::FunctionDecl(func) => self.function(func),
+            Stmt::If(if_stmt) => {
+                self.expression(&if_stmt.cond);
+                self.block(&if_stmt.then_block);
+                if let Some(else_block) = &if_stmt.else_block {
+                    self.block(else_block);
                 }
             }
-
-            // Check initializer for function calls
-            extract_expression_hints(
-                text,
-                &var.init,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
-        }
-        Stmt::FunctionDecl(func) => {
-            // Inferred return type hint on unannotated nested functions
-            if config.show_inferred_return && func.return_type.is_none() {
-                emit_inferred_return_hint(text, func, symbols, start_offset, end_offset, hints);
+            Stmt::While(while_stmt) => {
+                self.expression(&while_stmt.cond);
+                self.block(&while_stmt.body);
             }
-            extract_block_hints(
-                text,
-                &func.body,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
-        }
-        Stmt::If(if_stmt) => {
-            extract_expression_hints(
-                text,
-                &if_stmt.cond,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
-            extract_block_hints(
-                text,
-                &if_stmt.then_block,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
-            if let Some(else_block) = &if_stmt.else_block {
-                extract_block_hints(
-                    text,
-                    else_block,
-                    symbols,
-                    config,
-                    start_offset,
-                    end_offset,
-                    hints,
-                );
+            Stmt::ForIn(for_in) => {
+                self.expression(&for_in.iterable);
+                self.block(&for_in.body);
             }
-        }
-        Stmt::While(while_stmt) => {
-            extract_expression_hints(
-                text,
-                &while_stmt.cond,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
-            extract_block_hints(
-                text,
-                &while_stmt.body,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
-        }
-        Stmt::ForIn(for_in) => {
-            extract_expression_hints(
-                text,
-                &for_in.iterable,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
-            extract_block_hints(
-                text,
-                &for_in.body,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
-        }
-        Stmt::Return(ret) => {
-            if let Some(expr) = &ret.value {
-                extract_expression_hints(
-                    text,
-                    expr,
-                    symbols,
-                    config,
-                    start_offset,
-                    end_offset,
-                    hints,
-                );
+            Stmt::Return(ret) => {
+                if let Some(expr) = &ret.value {
+                    self.expression(expr);
+                }
+            }
+            Stmt::Expr(expr_stmt) => self.expression(&expr_stmt.expr),
+            Stmt::TryCatch(try_catch) => {
+                self.block(&try_catch.body);
+                self.block(&try_catch.catch_block);
             }
+            _ => {}
         }
-        Stmt::Expr(expr_stmt) => {
-            extract_expression_hints(
-                text,
-                &expr_stmt.expr,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
+    }
+
+    /// `: T` after the name of a `let` without an annotation
+    fn var_decl(&mut self, var: &VarDecl) {
+        if self.config.show_type_hints
+            && var.type_ref.is_none()
+            && self.in_range(var.span.start)
+            && !(self.config.skip_obvious_types && is_obvious_type(&var.init))
+        {
+            if let Some(type_str) = self.variable_type(var) {
+                let truncated = truncate_type(&type_str, self.config.max_type_length);
+                self.hints.push(InlayHint {
+                    position: offset_to_position(self.text, var.name.span.end),
+                    label: InlayHintLabel::String(format!(": {}", truncated)),
+                    kind: Some(InlayHintKind::TYPE),
+                    text_edits: None,
+                    tooltip: if truncated != type_str {
+                        Some(tower_lsp::lsp_types::InlayHintTooltip::String(type_str))
+                    } else {
+                        None
+                    },
+                    padding_left: Some(false),
+                    padding_right: Some(true),
+                    data: None,
+                });
+            }
         }
-        _ => {}
+
+        // Check initializer for function calls
+        self.expression(&var.init);
     }
-}
 
-/// Extract inlay hints from an expression
-fn extract_expression_hints(
-    text: &str,
-    expr: &Expr,
-    symbols: Option<&SymbolTable>,
-    config: &InlayHintConfig,
-    start_offset: usize,
-    end_offset: usize,
-    hints: &mut Vec<InlayHint>,
-) {
-    // Check if in range
-    let expr_span = expr.span();
-    if expr_span.start < start_offset || expr_span.start > end_offset {
-        return;
+    /// The type the typechecker inferred for a declaration; the symbol table
+    /// is the fallback when the declaration was never checked
+    fn variable_type(&self, var: &VarDecl) -> Option<String> {
+        let ty = match var.inferred_type.borrow().clone() {
+            Some(ty) => ty,
+            None => get_variable_type(self.symbols, &var.name.name)?,
+        };
+        if ty.normalized() == Type::Unknown {
+            return None;
+        }
+        Some(format_type(&ty))
     }
 
-    match expr {
-        Expr::Call(call) => {
-            // Parameter name hints
-            if config.show_parameter_hints && !call.args.is_empty() {
-                if let Some(param_names) = get_function_params(symbols, &call.callee) {
-                    for (i, arg) in call.args.iter().enumerate() {
-                        if let Some(param_name) = param_names.get(i) {
-                            // Skip if argument is already named or obvious
-                            if !is_obvious_argument(arg, param_name) {
-                                let position = offset_to_position(text, arg.span().start);
-
-                                hints.push(InlayHint {
-                                    position,
-                                    label: InlayHintLabel::String(format!("{}:", param_name)),
-                                    kind: Some(InlayHintKind::PARAMETER),
-                                    text_edits: None,
-                                    tooltip: None,
-                                    padding_left: Some(false),
-                                    padding_right: Some(true),
-                                    data: None,
-                                });
-                            }
-                        }
-                    }
-                }
-            }
+    fn expression(&mut self, expr: &Expr) {
+        if !self.in_range(expr.span().start) {
+            return;
+        }
 
-            // Recursively check arguments
-            for arg in &call.args {
-                extract_expression_hints(
-                    text,
-                    arg,
-                    symbols,
-                    config,
-                    start_offset,
-                    end_offset,
-                    hints,
-                );
+        match expr {
+            Expr::Call(call) => {
+                if self.config.show_parameter_hints {
+                    self.parameter_names(call);
+                }
+                for arg in &call.args {
+                    self.expression(arg);
+                }
             }
-        }
-        Expr::Member(member) => {
-            // Method call parameter hints
-            if config.show_parameter_hints {
+            Expr::Member(member) => {
                 if let Some(args) = &member.args {
                     for arg in args {
-                        extract_expression_hints(
-                            text,
-                            arg,
-                            symbols,
-                            config,
-                            start_offset,
-                            end_offset,
-                            hints,
-                        );
+                        self.expression(arg);
                     }
                 }
+                self.expression(&member.target);
             }
-            extract_expression_hints(
-                text,
-                &member.target,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
-        }
-        Expr::Binary(bin) => {
-            extract_expression_hints(
-                text,
-                &bin.left,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
-            extract_expression_hints(
-                text,
-                &bin.right,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
-        }
-        Expr::Unary(unary) => {
-            extract_expression_hints(
-                text,
-                &unary.expr,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
-        }
-        Expr::ArrayLiteral(arr) => {
-            for elem in &arr.elements {
-                extract_expression_hints(
-                    text,
-                    elem,
-                    symbols,
-                    config,
-                    start_offset,
-                    end_offset,
-                    hints,
-                );
+            Expr::Binary(bin) => {
+                self.expression(&bin.left);
+                self.expression(&bin.right);
             }
-        }
-        Expr::Index(index) => {
-            extract_expression_hints(
-                text,
-                &index.target,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
-            match &index.index {
-                IndexValue::Single(expr) => extract_expression_hints(
-                    text,
-                    expr,
-                    symbols,
-                    config,
-                    start_offset,
-                    end_offset,
-                    hints,
-                ),
+            Expr::Unary(unary) => self.expression(&unary.expr),
+            Expr::ArrayLiteral(arr) => {
+                for elem in &arr.elements {
+                    self.expression(elem);
+                }
             }
-        }
-        Expr::Range { start, end, .. } => {
-            if let Some(start) = start {
-                extract_expression_hints(
-                    text,
-                    start,
-                    symbols,
-                    config,
-                    start_offset,
-                    end_offset,
-                    hints,
-                );
+            Expr::Index(index) => {
+                self.expression(&index.target);
+                match &index.index {
+                    IndexValue::Single(expr) => self.expression(expr),
+                }
             }
-            if let Some(end) = end {
-                extract_expression_hints(
-                    text,
-                    end,
-                    symbols,
-                    config,
-                    start_offset,
-                    end_offset,
-                    hints,
-                );
+            Expr::Range { start, end, .. } => {
+                if let Some(start) = start {
+                    self.expression(start);
+                }
+                if let Some(end) = end {
+                    self.expression(end);
+                }
             }
+            Expr::Group(group) => self.expression(&group.expr),
+            Expr::Match(match_expr) => {
+                self.expression(&match_expr.scrutinee);
+                for arm in &match_expr.arms {
+                    self.expression(&arm.body);
+                }
+            }
+            _ => {}
         }
-        Expr::Group(group) => {
-            extract_expression_hints(
-                text,
-                &group.expr,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
-        }
-        Expr::Match(match_expr) => {
-            extract_expression_hints(
-                text,
-                &match_expr.scrutinee,
-                symbols,
-                config,
-                start_offset,
-                end_offset,
-                hints,
-            );
-            for arm in &match_expr.arms {
-                extract_expression_hints(
-                    text,
-                    &arm.body,
-                    symbols,
-                    config,
-                    start_offset,
-                    end_offset,
-                    hints,
-                );
+    }
+
+    /// `name:` before each argument whose parameter name it doesn't already spell
+    fn parameter_names(&mut self, call: &CallExpr) {
+        let Some(param_names) = self.callee_params(&call.callee) else {
+            return;
+        };
+        let hints: Vec<InlayHint> = call
+            .args
+            .iter()
+            .zip(param_names)
+            .filter(|(arg, param_name)| !is_obvious_argument(arg, param_name))
+            .map(|(arg, param_name)| InlayHint {
+                position: offset_to_position(self.text, arg.span().start),
+                label: InlayHintLabel::String(format!("{}:", param_name)),
+                kind: Some(InlayHintKind::PARAMETER),
+                text_edits: None,
+                tooltip: None,
+                padding_left: Some(false),
+                padding_right: Some(true),
+                data: None,
+            })
+            .collect();
+        self.hints.extend(hints);
+    }
+
+    /// Parameter names of a called function declared in this document
+    fn callee_params(&self, callee: &Expr) -> Option<&[String]> {
+        let Expr::Identifier(id) = callee else {
+            return None;
+        };
+        // A variable of the same name shadows the function
+        if let Some(symbol) = self.symbols.and_then(|s| s.lookup(&id.name)) {
+            if symbol.kind != AtlasSymbolKind::Function {
+                return None;
             }
         }
-        _ => {}
+        self.param_names.get(&id.name).map(Vec::as_slice)
     }
-}
 
-/// Emit a `→ T` inlay hint at the start of a function body for unannotated return types.
-///
-/// Uses `infer_return_type` directly on the function body so the hint reflects the actual
-/// inferred type regardless of whether the symbol table was updated by the typechecker.
-fn emit_inferred_return_hint(
-    text: &str,
-    func: &FunctionDecl,
-    _symbols: Option<&SymbolTable>,
-    start_offset: usize,
-    end_offset: usize,
-    hints: &mut Vec<InlayHint>,
-) {
-    // Only emit if the function body is within the requested range
-    if func.body.span.start < start_offset || func.body.span.start > end_offset {
-        return;
+    /// Emit a `→ T` inlay hint at the start of a function body for unannotated return types.
+    ///
+    /// Uses `infer_return_type` directly on the function body so the hint reflects the actual
+    /// inferred type regardless of whether the symbol table was updated by the typechecker.
+    fn inferred_return(&mut self, func: &FunctionDecl) {
+        // Only emit if the function body is within the requested range
+        if !self.in_range(func.body.span.start) {
+            return;
+        }
+
+        let ret_type_str = match infer_return_type(&func.body) {
+            InferredReturn::Uniform(ty) => {
+                let s = format_type(&ty);
+                if s == "?" || s == "void" || s == "unknown" {
+                    return;
+                }
+                s
+            }
+            _ => return,
+        };
+
+        let truncated = truncate_type(&ret_type_str, self.config.max_type_length);
+        // Place the hint at the opening brace of the function body
+        let position = offset_to_position(self.text, func.body.span.start);
+
+        self.hints.push(InlayHint {
+            position,
+            label: InlayHintLabel::String(format!("→ {} ", truncated)),
+            kind: Some(InlayHintKind::TYPE),
+            text_edits: None,
+            tooltip: if truncated != ret_type_str {
+                Some(tower_lsp::lsp_types::InlayHintTooltip::String(ret_type_str))
+            } else {
+                None
+            },
+            padding_left: Some(true),
+            padding_right: Some(false),
+            data: None,
+        });
     }
+}
 
-    let ret_type_str = match infer_return_type(&func.body) {
-        InferredReturn::Uniform(ty) => {
-            let s = format_type(&ty);
-            if s == "?" || s == "void" || s == "unknown" {
-                return;
+/// Parameter names of every function in the program, nested ones included
+fn collect_param_names(program: &Program) -> HashMap<String, Vec<String>> {
+    fn add(func: &FunctionDecl, names: &mut HashMap<String, Vec<String>>) {
+        let params = func
+            .params
+            .iter()
+            .map(|p| p.name.name.clone())
+            .filter(|name| name != "self")
+            .collect();
+        names.insert(func.name.name.clone(), params);
+        add_nested(&func.body, names);
+    }
+    fn add_nested(block: &Block, names: &mut HashMap<String, Vec<String>>) {
+        for stmt in &block.statements {
+            if let Stmt::FunctionDecl(func) = stmt {
+                add(func, names);
             }
-            s
         }
-        _ => return,
-    };
+    }
 
-    let truncated = truncate_type(&ret_type_str, 25);
-    // Place the hint at the opening brace of the function body
-    let position = offset_to_position(text, func.body.span.start);
-
-    hints.push(InlayHint {
-        position,
-        label: InlayHintLabel::String(format!("→ {} ", truncated)),
-        kind: Some(InlayHintKind::TYPE),
-        text_edits: None,
-        tooltip: if truncated != ret_type_str {
-            Some(tower_lsp::lsp_types::InlayHintTooltip::String(ret_type_str))
-        } else {
-            None
-        },
-        padding_left: Some(true),
-        padding_right: Some(false),
-        data: None,
-    });
+    let mut names = HashMap::new();
+    for item in &program.items {
+        match item {
+            Item::Function(func)
+            | Item::Statement(Stmt::FunctionDecl(func))
+            | Item::Export(ExportDecl {
+                item: ExportItem::Function(func),
+                ..
+            }) => add(func, &mut names),
+            _ => {}
+        }
+    }
+    names
 }
 
 /// Check if a type is obvious from the initializer
@@ -561,41 +422,17 @@ fn is_obvious_argument(arg: &Expr, param_name: &str) -> bool {
 }
 
 /// Get variable type from symbol table
-fn get_variable_type(symbols: Option<&SymbolTable>, name: &str) -> Option<String> {
+fn get_variable_type(symbols: Option<&SymbolTable>, name: &str) -> Option<Type> {
     let symbols = symbols?;
     let symbol = symbols.lookup(name)?;
 
     if symbol.kind == AtlasSymbolKind::Variable || symbol.kind == AtlasSymbolKind::Parameter {
-        Some(format_type(&symbol.ty))
+        Some(symbol.ty.clone())
     } else {
         None
     }
 }
 
-/// Get function parameter names
-fn get_function_params(symbols: Option<&SymbolTable>, callee: &Expr) -> Option<Vec<String>> {
-    // Extract function name from callee
-    let func_name = match callee {
-        Expr::Identifier(id) => &id.name,
-        _ => return None,
-    };
-
-    // Check symbol table for function
-    let symbols = symbols?;
-    let symbol = symbols.lookup(func_name)?;
-
-    if symbol.kind == AtlasSymbolKind::Function {
-        // Try to extract parameter names from the function type
-        if let Type::Function { params, .. } = &symbol.ty {
-            // The Type doesn't store parameter names, so we'd need to look at the AST
-            // For now, return generic names based on parameter count
-            return Some((0..params.len()).map(|i| format!("arg{}", i)).collect());
-        }
-    }
-
-    None
-}
-
 /// Format a type for display
 fn format_type(ty: &Type) -> String {
     match ty {
@@ -628,6 +465,7 @@ fn format_type(ty: &Type) -> String {
             }
         }
         Type::Alias { name, .. } => name.clone(),
+        Type::TypeParameter { .. } if Type::is_any_placeholder(ty) => "any".to_string(),
         Type::TypeParameter { name } => name.clone(),
         Type::JsonValue => "JsonValue".to_string(),
         Type::Union(types) => {
@@ -6,16 +6,21 @@ pub mod actions;
 pub mod call_hierarchy;
 pub mod completion;
 pub mod convert;
+pub mod daemon;
 pub mod document;
 pub mod folding;
 pub mod formatting;
 pub mod handlers;
 pub mod hover;
+pub mod incremental;
 pub mod index;
 pub mod inlay_hints;
+pub mod modules;
 pub mod navigation;
 pub mod refactor;
 pub mod references;
+pub mod scheduler;
 pub mod semantic_tokens;
 pub mod server;
+pub mod signature_help;
 pub mod symbols;
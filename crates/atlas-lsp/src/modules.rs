@@ -0,0 +1,225 @@
+//! Module graph of the documents being analyzed
+//!
+//! Resolves a document's imports so binding sees the exports of the modules
+//! it imports, and remembers who imports whom so that a change to a module
+//! — an edit in the editor, or a file changed on disk by a checkout or a
+//! code generator — invalidates exactly the modules that depend on it.
+//! Imported modules are bound once and cached until they or one of their
+//! own imports change; an open module is read from its editor text, any
+//! other from disk.
+
+use atlas_runtime::ast::{ExportItem, Item, Program};
+use atlas_runtime::module_loader::ModuleRegistry;
+use atlas_runtime::span::Span;
+use atlas_runtime::{Binder, Lexer, ModuleResolver, Parser, TypeChecker};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tower_lsp::lsp_types::Url;
+
+/// What binding a module with imports needs, from [`ModuleGraph::context`]
+pub struct ModuleContext {
+    /// The module's own path
+    pub path: PathBuf,
+    /// Symbol tables of the modules it imports
+    pub registry: ModuleRegistry,
+    /// Import source → resolved path
+    pub resolved_imports: HashMap<String, PathBuf>,
+}
+
+/// Import edges between modules plus the cache of bound imported modules
+#[derive(Default)]
+pub struct ModuleGraph {
+    /// Editor text of open modules, which wins over the file on disk
+    open: HashMap<PathBuf, String>,
+    /// Symbol tables of the imported modules bound so far
+    bound: HashMap<PathBuf, atlas_runtime::symbol::SymbolTable>,
+    /// Resolved imports of every module seen
+    imports: HashMap<PathBuf, Vec<PathBuf>>,
+    /// Modules with an import that did not resolve, which a file created
+    /// later may satisfy
+    unresolved: HashSet<PathBuf>,
+}
+
+impl ModuleGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Graph key of the document at `uri`; `None` when it is not a file
+    pub fn module_path(uri: &Url) -> Option<PathBuf> {
+        let path = uri.to_file_path().ok()?;
+        // Resolved imports are canonical; a deleted file still has a
+        // canonical directory
+        if let Ok(canonical) = path.canonicalize() {
+            return Some(canonical);
+        }
+        let canonical_dir = path.parent().and_then(|dir| dir.canonicalize().ok());
+        match (canonical_dir, path.file_name()) {
+            (Some(dir), Some(name)) => Some(dir.join(name)),
+            _ => Some(path),
+        }
+    }
+
+    /// Use `text` for the module at `path` until it is closed; returns the
+    /// modules that import it, directly or not
+    pub fn set_open(&mut self, path: PathBuf, text: String) -> HashSet<PathBuf> {
+        let dependents = self.invalidate(&path);
+        self.open.insert(path, text);
+        dependents
+    }
+
+    /// Go back to the file on disk for the module at `path`; returns the
+    /// modules that import it
+    pub fn close(&mut self, path: &Path) -> HashSet<PathBuf> {
+        self.open.remove(path);
+        self.invalidate(path)
+    }
+
+    /// Account for the file at `path` changing on disk; returns the modules
+    /// whose analysis may have changed
+    ///
+    /// Besides the modules importing `path`, that includes every module with
+    /// an unresolved import, as a created file may be the one it names.
+    pub fn file_changed(&mut self, path: &Path) -> HashSet<PathBuf> {
+        let mut affected = self.invalidate(path);
+        let unresolved: Vec<PathBuf> = self.unresolved.iter().cloned().collect();
+        for module in unresolved {
+            affected.extend(self.invalidate(&module));
+            affected.insert(module);
+        }
+        affected
+    }
+
+    /// Drop the cached binding of `path` and of every module importing it;
+    /// returns those importers
+    fn invalidate(&mut self, path: &Path) -> HashSet<PathBuf> {
+        let dependents = self.dependents(path);
+        self.bound.remove(path);
+        for dependent in &dependents {
+            self.bound.remove(dependent);
+        }
+        dependents
+    }
+
+    /// Modules importing `path`, directly or through other modules
+    pub fn dependents(&self, path: &Path) -> HashSet<PathBuf> {
+        let mut dependents = HashSet::new();
+        let mut pending = vec![path.to_path_buf()];
+        while let Some(module) = pending.pop() {
+            for (importer, imports) in &self.imports {
+                if imports.contains(&module) && dependents.insert(importer.clone()) {
+                    pending.push(importer.clone());
+                }
+            }
+        }
+        dependents.remove(path);
+        dependents
+    }
+
+    /// Resolve and bind the imports of the module at `path`, parsed as
+    /// `ast`; `None` when it imports nothing
+    pub fn context(&mut self, path: &Path, ast: &Program) -> Option<ModuleContext> {
+        let resolved_imports = self.resolve_imports(path, ast);
+        // A module without imports binds as before
+        import_sources(ast).next()?;
+        let mut registry = ModuleRegistry::new();
+        let mut loading = vec![path.to_path_buf()];
+        for dependency in resolved_imports.values() {
+            self.load(dependency, &mut registry, &mut loading);
+        }
+        Some(ModuleContext {
+            path: path.to_path_buf(),
+            registry,
+            resolved_imports,
+        })
+    }
+
+    /// Resolve the imports of `path`, recording the edges
+    fn resolve_imports(&mut self, path: &Path, ast: &Program) -> HashMap<String, PathBuf> {
+        let mut resolver = ModuleResolver::new(crate::navigation::project_root(path));
+        let mut resolved = HashMap::new();
+        let mut missing = false;
+        for (source, span) in import_sources(ast) {
+            // Binding reports the imports that do not resolve
+            match resolver.resolve_path(source, path, span) {
+                Ok(target) => {
+                    resolved.insert(source.to_string(), target);
+                }
+                Err(_) => missing = true,
+            }
+        }
+        self.imports
+            .insert(path.to_path_buf(), resolved.values().cloned().collect());
+        if missing {
+            self.unresolved.insert(path.to_path_buf());
+        } else {
+            self.unresolved.remove(path);
+        }
+        resolved
+    }
+
+    /// Bind the module at `path` after its own imports and register it;
+    /// a module already being loaded closes a cycle and is left out
+    fn load(&mut self, path: &Path, registry: &mut ModuleRegistry, loading: &mut Vec<PathBuf>) {
+        if registry.contains(path) || loading.iter().any(|module| module == path) {
+            return;
+        }
+        if let Some(symbols) = self.bound.get(path) {
+            registry.register(path.to_path_buf(), symbols.clone());
+            return;
+        }
+        let text = match self.open.get(path) {
+            Some(text) => text.clone(),
+            None => match std::fs::read_to_string(path) {
+                Ok(text) => text,
+                Err(_) => return,
+            },
+        };
+        let (tokens, _) = Lexer::new(&text)
+            .with_file(path.display().to_string())
+            .tokenize();
+        let (ast, _) = Parser::new(tokens).parse();
+
+        let resolved_imports = self.resolve_imports(path, &ast);
+        loading.push(path.to_path_buf());
+        for dependency in resolved_imports.values() {
+            self.load(dependency, registry, loading);
+        }
+        loading.pop();
+
+        // Bound and checked as `atlas run` does, so inherent methods of
+        // exported structs are exported too
+        let mut binder = Binder::new();
+        binder.set_resolved_imports(resolved_imports);
+        let (mut symbols, _) = binder.bind_with_modules(&ast, path, registry);
+        let exported_structs: HashSet<String> =
+            symbols.get_struct_exports().keys().cloned().collect();
+        let mut checker = TypeChecker::new(&mut symbols);
+        checker.check(&ast);
+        let methods: Vec<_> = checker
+            .inherent_registry
+            .iter()
+            .filter(|((struct_name, _), _)| exported_structs.contains(struct_name))
+            .map(|(key, method)| (key.clone(), method.clone()))
+            .collect();
+        drop(checker);
+        for ((struct_name, method_name), method) in methods {
+            symbols.add_impl_method_export(struct_name, method_name, method);
+        }
+
+        self.bound.insert(path.to_path_buf(), symbols.clone());
+        registry.register(path.to_path_buf(), symbols);
+    }
+}
+
+/// Source and span of every import and re-export of `ast`
+fn import_sources(ast: &Program) -> impl Iterator<Item = (&str, Span)> {
+    ast.items.iter().filter_map(|item| match item {
+        Item::Import(import) => Some((import.source.as_str(), import.span)),
+        Item::Export(export) => match &export.item {
+            ExportItem::ReExport { source, span, .. } => Some((source.as_str(), *span)),
+            _ => None,
+        },
+        _ => None,
+    })
+}
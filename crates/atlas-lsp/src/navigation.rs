@@ -2,9 +2,11 @@
 
 use atlas_runtime::ast::*;
 use atlas_runtime::symbol::SymbolTable;
+use atlas_runtime::{Lexer, ModuleResolver, Parser};
+use std::path::{Path, PathBuf};
 use tower_lsp::lsp_types::{DocumentSymbol, Location, Position, Range, SymbolKind, Url};
 
-use crate::index::SymbolIndex;
+use crate::index::{span_to_range, SymbolIndex};
 
 /// Find the identifier at a given position in the source
 pub fn find_identifier_at_position(text: &str, position: Position) -> Option<String> {
@@ -133,6 +135,81 @@ pub fn find_definition(
     Some(definitions[0].location.clone())
 }
 
+/// Go to the module an import refers to, using the same resolution as the
+/// module loader (relative paths, `/src/...`, and locked packages such as
+/// `"web"` or `"web/router"`).
+///
+/// On a named specifier this lands on the exported declaration; anywhere
+/// else in the import it lands at the top of the module.
+pub fn find_import_definition(
+    program: &Program,
+    uri: &Url,
+    text: &str,
+    position: Position,
+) -> Option<Location> {
+    let offset = crate::convert::position_to_offset(text, position);
+    let import = program.items.iter().find_map(|item| match item {
+        Item::Import(import) if import.span.start <= offset && offset <= import.span.end => {
+            Some(import)
+        }
+        _ => None,
+    })?;
+
+    let file = uri.to_file_path().ok()?;
+    let mut resolver = ModuleResolver::new(project_root(&file));
+    let target = resolver
+        .resolve_path(&import.source, &file, import.span)
+        .ok()?;
+    let target_uri = Url::from_file_path(&target).ok()?;
+
+    let specifier = import.specifiers.iter().find_map(|spec| match spec {
+        ImportSpecifier::Named { name, span } if span.start <= offset && offset <= span.end => {
+            Some(name.name.as_str())
+        }
+        _ => None,
+    });
+    let range = specifier
+        .and_then(|name| exported_name_range(&target, name))
+        .unwrap_or_default();
+
+    Some(Location {
+        uri: target_uri,
+        range,
+    })
+}
+
+/// Directory holding the nearest atlas.toml, else the file's own directory
+pub(crate) fn project_root(file: &Path) -> PathBuf {
+    let dir = file.parent().unwrap_or(Path::new("."));
+    dir.ancestors()
+        .find(|ancestor| ancestor.join("atlas.toml").exists())
+        .unwrap_or(dir)
+        .to_path_buf()
+}
+
+/// Range of the declaration `name` exported by the module at `path`
+fn exported_name_range(path: &Path, name: &str) -> Option<Range> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let (tokens, _) = Lexer::new(&text).tokenize();
+    let (program, _) = Parser::new(tokens).parse();
+
+    program.items.iter().find_map(|item| {
+        let Item::Export(export) = item else {
+            return None;
+        };
+        let ident = match &export.item {
+            ExportItem::Function(decl) => &decl.name,
+            ExportItem::Variable(decl) => &decl.name,
+            ExportItem::TypeAlias(decl) => &decl.name,
+            ExportItem::Const(decl) => &decl.name,
+            ExportItem::Struct(decl) => &decl.name,
+            ExportItem::Enum(decl) => &decl.name,
+            ExportItem::ReExport { .. } => return None,
+        };
+        (ident.name == name).then(|| span_to_range(&ident.span, &text))
+    })
+}
+
 /// Find all references to a symbol
 ///
 /// NOTE: This is a basic implementation that finds all identifier usages by name.
@@ -256,6 +333,17 @@ fn find_references_in_stmt(stmt: &Stmt, identifier: &str, references: &mut Vec<R
         Stmt::Defer(defer) => {
             find_references_in_block(&defer.body, identifier, references);
         }
+        Stmt::TryCatch(try_catch) => {
+            find_references_in_block(&try_catch.body, identifier, references);
+            if try_catch
+                .error_binding
+                .as_ref()
+                .is_some_and(|b| b.name == identifier)
+            {
+                references.push(Range::default());
+            }
+            find_references_in_block(&try_catch.catch_block, identifier, references);
+        }
     }
 }
 
@@ -61,7 +61,7 @@ pub fn extract_variable(
     ];
 
     // Sort edits by position (last to first) to avoid invalidating positions
-    edits.sort_by(|a, b| b.range.start.cmp(&a.range.start));
+    edits.sort_by_key(|e| std::cmp::Reverse(e.range.start));
 
     Ok(create_workspace_edit(uri, edits))
 }
@@ -271,6 +271,18 @@ fn collect_free_vars_stmt(
             collect_free_vars_block(&defer.body, scopes, free_vars);
             scopes.pop_back();
         }
+        Stmt::TryCatch(try_catch) => {
+            scopes.push_back(std::collections::HashSet::new());
+            collect_free_vars_block(&try_catch.body, scopes, free_vars);
+            scopes.pop_back();
+
+            scopes.push_back(std::collections::HashSet::new());
+            if let (Some(scope), Some(binding)) = (scopes.back_mut(), &try_catch.error_binding) {
+                scope.insert(binding.name.clone());
+            }
+            collect_free_vars_block(&try_catch.catch_block, scopes, free_vars);
+            scopes.pop_back();
+        }
     }
 }
 
@@ -422,10 +434,13 @@ fn collect_pattern_bindings(
                 collect_pattern_bindings(elem, scopes);
             }
         }
-        atlas_runtime::ast::Pattern::Array { elements, .. } => {
+        atlas_runtime::ast::Pattern::Array { elements, rest, .. } => {
             for elem in elements {
                 collect_pattern_bindings(elem, scopes);
             }
+            if let Some(rest) = rest {
+                collect_pattern_bindings(rest, scopes);
+            }
         }
         atlas_runtime::ast::Pattern::Or(patterns, _) => {
             for pat in patterns {
@@ -442,7 +457,9 @@ fn collect_pattern_bindings(
                 collect_pattern_bindings(arg, scopes);
             }
         }
-        atlas_runtime::ast::Pattern::Literal(_, _) | atlas_runtime::ast::Pattern::Wildcard(_) => {}
+        atlas_runtime::ast::Pattern::Literal(_, _)
+        | atlas_runtime::ast::Pattern::Range { .. }
+        | atlas_runtime::ast::Pattern::Wildcard(_) => {}
         atlas_runtime::ast::Pattern::Struct { fields, .. } => {
             for field in fields {
                 match &field.pattern {
@@ -59,7 +59,7 @@ pub fn inline_variable(
     });
 
     // Sort edits by position (last to first) to avoid invalidating positions
-    edits.sort_by(|a, b| b.range.start.cmp(&a.range.start));
+    edits.sort_by_key(|e| std::cmp::Reverse(e.range.start));
 
     Ok(create_workspace_edit(uri, edits))
 }
@@ -146,6 +146,13 @@ fn find_variable_in_block<'a>(block: &'a Block, name: &str) -> Option<&'a VarDec
                     return Some(decl);
                 }
             }
+            Stmt::TryCatch(try_catch) => {
+                for block in [&try_catch.body, &try_catch.catch_block] {
+                    if let Some(decl) = find_variable_in_block(block, name) {
+                        return Some(decl);
+                    }
+                }
+            }
             _ => {}
         }
     }
@@ -16,7 +16,7 @@ pub mod rename;
 
 pub use extract::{extract_function, extract_variable};
 pub use inline::{inline_function, inline_variable};
-pub use rename::rename_symbol;
+pub use rename::{prepare_rename, rename_in_workspace, rename_symbol};
 
 /// Refactoring action result
 pub type RefactorResult = Result<WorkspaceEdit, RefactorError>;
@@ -171,6 +171,13 @@ fn extract_names_from_stmt(stmt: &Stmt, names: &mut Vec<String>) {
         Stmt::Defer(defer) => {
             extract_names_from_block(&defer.body, names);
         }
+        Stmt::TryCatch(try_catch) => {
+            extract_names_from_block(&try_catch.body, names);
+            if let Some(binding) = &try_catch.error_binding {
+                names.push(binding.name.clone());
+            }
+            extract_names_from_block(&try_catch.catch_block, names);
+        }
     }
 }
 
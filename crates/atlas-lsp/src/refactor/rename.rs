@@ -1,8 +1,14 @@
 //! Rename symbol refactoring
 
-use super::{create_workspace_edit, validate_new_name, RefactorError, RefactorResult};
+use super::{
+    create_workspace_edit, extract_all_names, validate_new_name, RefactorError, RefactorResult,
+};
+use crate::index::SymbolIndex;
+use crate::navigation::find_identifier_at_position;
+use crate::references::resolve_occurrences;
 use atlas_runtime::ast::*;
-use atlas_runtime::symbol::SymbolTable;
+use atlas_runtime::symbol::{SymbolKind as BinderSymbolKind, SymbolTable};
+use std::collections::HashMap;
 use tower_lsp::lsp_types::*;
 
 /// Rename a symbol across the workspace
@@ -61,6 +67,128 @@ pub fn rename_symbol(
     Ok(create_workspace_edit(uri, edits))
 }
 
+/// Range of the identifier under the cursor, if a rename there is allowed
+///
+/// Only names the workspace index has a definition for in this document can
+/// be renamed; builtins and keywords are refused up front so the client can
+/// tell the user before they type a new name.
+pub fn prepare_rename(
+    uri: &Url,
+    text: &str,
+    position: Position,
+    symbols: Option<&SymbolTable>,
+    index: &SymbolIndex,
+) -> Option<Range> {
+    let name = find_identifier_at_position(text, position)?;
+    if is_builtin(&name, symbols) {
+        return None;
+    }
+    if !index
+        .find_definitions(&name)
+        .iter()
+        .any(|def| def.location.uri == *uri)
+    {
+        return None;
+    }
+
+    index
+        .find_references(&name, true)
+        .into_iter()
+        .find(|loc| loc.uri == *uri && range_contains(&loc.range, position))
+        .map(|loc| loc.range)
+}
+
+/// Rename the symbol under the cursor everywhere it is visible
+///
+/// A local (parameter or variable inside a function) is renamed within that
+/// function only. A top-level symbol is renamed in the module that defines it
+/// and in every open document importing it by name, skipping functions that
+/// shadow it with a local of the same name. `documents` maps each open URI to
+/// its text and parsed program.
+pub fn rename_in_workspace(
+    uri: &Url,
+    position: Position,
+    documents: &HashMap<Url, (String, Option<Program>)>,
+    symbols: Option<&SymbolTable>,
+    index: &SymbolIndex,
+    new_name: &str,
+) -> RefactorResult {
+    let (text, program) = documents
+        .get(uri)
+        .ok_or_else(|| RefactorError::AnalysisFailed("Document is not open".to_string()))?;
+    let program = program
+        .as_ref()
+        .ok_or_else(|| RefactorError::AnalysisFailed("Document failed to parse".to_string()))?;
+    let old_name = find_identifier_at_position(text, position)
+        .ok_or_else(|| RefactorError::InvalidSelection("No identifier at cursor".to_string()))?;
+
+    validate_new_name(new_name)?;
+
+    if is_builtin(&old_name, symbols) {
+        return Err(RefactorError::SemanticsViolation(format!(
+            "'{}' is a builtin and cannot be renamed",
+            old_name
+        )));
+    }
+
+    let occurrences =
+        resolve_occurrences(uri, position, text, program, &old_name, documents, index)
+            .map_err(RefactorError::AnalysisFailed)?;
+    if occurrences.declarations.is_empty() {
+        return Err(RefactorError::AnalysisFailed(format!(
+            "'{}' is declared in a module that must be open to rename it",
+            old_name
+        )));
+    }
+    if old_name == new_name {
+        return Ok(create_workspace_edit(uri, Vec::new()));
+    }
+
+    let mut changes = HashMap::new();
+    for (file, ranges) in occurrences.ranges {
+        let declares_new_name = index
+            .find_definitions(new_name)
+            .iter()
+            .any(|def| def.location.uri == file);
+        let names_new_name = documents
+            .get(&file)
+            .and_then(|(_, program)| program.as_ref())
+            .is_some_and(|program| extract_all_names(program).iter().any(|n| n == new_name));
+        if declares_new_name || names_new_name {
+            return Err(RefactorError::NameConflict(format!(
+                "Symbol '{}' already exists in {}",
+                new_name, file
+            )));
+        }
+
+        let edits: Vec<TextEdit> = ranges
+            .into_iter()
+            .map(|range| TextEdit {
+                range,
+                new_text: new_name.to_string(),
+            })
+            .collect();
+        changes.insert(file, edits);
+    }
+
+    Ok(WorkspaceEdit {
+        changes: Some(changes),
+        document_changes: None,
+        change_annotations: None,
+    })
+}
+
+/// Whether `name` resolves to a builtin rather than a user declaration
+fn is_builtin(name: &str, symbols: Option<&SymbolTable>) -> bool {
+    symbols
+        .and_then(|table| table.lookup(name))
+        .is_some_and(|symbol| symbol.kind == BinderSymbolKind::Builtin)
+}
+
+fn range_contains(range: &Range, position: Position) -> bool {
+    range.start <= position && position <= range.end
+}
+
 /// Check if a symbol exists in the program
 fn symbol_exists(program: &Program, name: &str) -> bool {
     for item in &program.items {
@@ -80,15 +208,11 @@ fn symbol_exists(program: &Program, name: &str) -> bool {
                     return true;
                 }
             }
-            Item::Statement(Stmt::VarDecl(var_decl)) => {
-                if var_decl.name.name == name {
-                    return true;
-                }
+            Item::Statement(Stmt::VarDecl(var_decl)) if var_decl.name.name == name => {
+                return true;
             }
-            Item::TypeAlias(alias) => {
-                if alias.name.name == name {
-                    return true;
-                }
+            Item::TypeAlias(alias) if alias.name.name == name => {
+                return true;
             }
             _ => {}
         }
@@ -100,10 +224,8 @@ fn symbol_exists(program: &Program, name: &str) -> bool {
 fn symbol_exists_in_block(block: &Block, name: &str) -> bool {
     for stmt in &block.statements {
         match stmt {
-            Stmt::VarDecl(var_decl) => {
-                if var_decl.name.name == name {
-                    return true;
-                }
+            Stmt::VarDecl(var_decl) if var_decl.name.name == name => {
+                return true;
             }
             Stmt::FunctionDecl(func) => {
                 if func.name.name == name {
@@ -128,10 +250,8 @@ fn symbol_exists_in_block(block: &Block, name: &str) -> bool {
                     }
                 }
             }
-            Stmt::While(while_stmt) => {
-                if symbol_exists_in_block(&while_stmt.body, name) {
-                    return true;
-                }
+            Stmt::While(while_stmt) if symbol_exists_in_block(&while_stmt.body, name) => {
+                return true;
             }
             Stmt::ForIn(for_in_stmt) => {
                 if for_in_stmt.variable.name == name {
@@ -141,6 +261,20 @@ fn symbol_exists_in_block(block: &Block, name: &str) -> bool {
                     return true;
                 }
             }
+            Stmt::TryCatch(try_catch) => {
+                if try_catch
+                    .error_binding
+                    .as_ref()
+                    .is_some_and(|b| b.name == name)
+                {
+                    return true;
+                }
+                if symbol_exists_in_block(&try_catch.body, name)
+                    || symbol_exists_in_block(&try_catch.catch_block, name)
+                {
+                    return true;
+                }
+            }
             _ => {}
         }
     }
@@ -1,85 +1,329 @@
 //! Find all references to a symbol
 //!
 //! Provides textDocument/references implementation for the LSP server.
+//!
+//! References are resolved through the symbol's binding rather than its
+//! name alone: a local is confined to the function declaring it, and a
+//! top-level symbol is followed into every open document importing it from
+//! its home module. Rename uses the same resolution.
 
-use crate::index::{position_to_offset, SymbolIndex};
+use crate::index::{position_to_offset, span_to_range, SymbolIndex};
 use crate::navigation::find_identifier_at_position;
-use atlas_runtime::ast::Program;
-use atlas_runtime::symbol::SymbolTable;
-use tower_lsp::lsp_types::{Location, Position, Url};
+use atlas_runtime::ast::*;
+use atlas_runtime::ModuleResolver;
+use std::collections::HashMap;
+use std::path::Path;
+use tower_lsp::lsp_types::{Location, Position, Range, Url};
+
+/// Every occurrence of one symbol across the open documents
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SymbolOccurrences {
+    /// Occurrence ranges per document, sorted and without duplicates
+    pub ranges: HashMap<Url, Vec<Range>>,
+    /// Declaration sites; empty when the declaring module is not open
+    pub declarations: Vec<Location>,
+}
 
 /// Find all references to the symbol at the given position
 ///
 /// # Arguments
 /// * `uri` - URI of the document
-/// * `text` - Source text of the document
 /// * `position` - Position of the symbol to find references for
-/// * `ast` - Parsed AST of the document (if available)
-/// * `symbols` - Symbol table (if available)
+/// * `documents` - Text and parsed program of every open document
 /// * `index` - Workspace symbol index
 /// * `include_declaration` - Whether to include the declaration in results
 ///
 /// # Returns
-/// Vector of locations where the symbol is referenced, or None if symbol not found
+/// Locations where the symbol occurs, or None if no symbol is declared there
 pub fn find_all_references(
     uri: &Url,
-    text: &str,
     position: Position,
-    ast: Option<&Program>,
-    _symbols: Option<&SymbolTable>,
+    documents: &HashMap<Url, (String, Option<Program>)>,
     index: &SymbolIndex,
     include_declaration: bool,
 ) -> Option<Vec<Location>> {
-    // Find the identifier at the cursor position
-    let identifier = find_identifier_at_position(text, position)?;
+    let (text, program) = documents.get(uri)?;
+    let name = find_identifier_at_position(text, position)?;
+    let occurrences = resolve_occurrences(
+        uri,
+        position,
+        text,
+        program.as_ref()?,
+        &name,
+        documents,
+        index,
+    )
+    .ok()?;
+
+    let mut locations: Vec<Location> = occurrences
+        .ranges
+        .into_iter()
+        .flat_map(|(file, ranges)| {
+            ranges.into_iter().map(move |range| Location {
+                uri: file.clone(),
+                range,
+            })
+        })
+        .filter(|loc| include_declaration || !occurrences.declarations.contains(loc))
+        .collect();
+    locations.sort_by(|a, b| (a.uri.as_str(), a.range.start).cmp(&(b.uri.as_str(), b.range.start)));
+    Some(locations)
+}
 
-    // Use the index to find all references
-    let locations = index.find_references(&identifier, include_declaration);
+/// Resolve `name`, written at `position` in `uri`, to its occurrences
+///
+/// The innermost function in `uri` declaring `name` owns it as a local.
+/// Otherwise the symbol is top-level in its home module (`uri`, or the module
+/// it is imported from) and occurs there and in every open document importing
+/// it by name, skipping functions that shadow it with a binding of their own.
+/// Fails if `uri` has no declaration or import of `name`.
+pub(crate) fn resolve_occurrences(
+    uri: &Url,
+    position: Position,
+    text: &str,
+    program: &Program,
+    name: &str,
+    documents: &HashMap<Url, (String, Option<Program>)>,
+    index: &SymbolIndex,
+) -> Result<SymbolOccurrences, String> {
+    let definitions = index.find_definitions(name);
+    let local_definitions: Vec<_> = definitions
+        .iter()
+        .filter(|def| def.location.uri == *uri)
+        .collect();
+    if local_definitions.is_empty() {
+        return Err(format!("Symbol '{}' not found", name));
+    }
 
-    if locations.is_empty() {
-        // Fallback to single-file search if index doesn't have results
-        // This handles cases where indexing isn't complete yet
-        return fallback_single_file_references(uri, text, &identifier, ast, include_declaration);
+    // Innermost function declaring the name decides whether it is a local
+    let offset = position_to_offset(position, text);
+    let mut enclosing = Vec::new();
+    for item in &program.items {
+        if let Some(func) = item_function(item) {
+            functions_containing(func, offset, &mut enclosing);
+        }
     }
+    let owner = enclosing.iter().rev().find(|func| {
+        local_definitions
+            .iter()
+            .any(|def| def.scope.as_deref() == Some(func.name.name.as_str()))
+    });
+
+    // Each affected file, optionally limited to one range of it
+    let (targets, declarations): (Vec<(&Url, Option<Range>)>, Vec<Location>) = match owner {
+        Some(func) => {
+            let body = span_to_range(&func.span, text);
+            let declarations = local_definitions
+                .iter()
+                .filter(|def| def.scope.as_deref() == Some(func.name.name.as_str()))
+                .filter(|def| range_within(&def.location.range, &body))
+                .map(|def| def.location.clone())
+                .collect();
+            (vec![(uri, Some(body))], declarations)
+        }
+        None => match home_module(uri, program, name, documents) {
+            Some(home) => {
+                let mut targets = vec![(home, None)];
+                for (other, (_, other_program)) in documents {
+                    let imports_it = other_program.as_ref().is_some_and(|other_program| {
+                        imports_name(other, other_program, name, home)
+                    });
+                    if other != home && imports_it {
+                        targets.push((other, None));
+                    }
+                }
+                let declarations = definitions
+                    .iter()
+                    .filter(|def| def.location.uri == *home && def.scope.is_none())
+                    .map(|def| def.location.clone())
+                    .collect();
+                (targets, declarations)
+            }
+            // Imported from a module that is not open: only this document
+            None => (vec![(uri, None)], Vec::new()),
+        },
+    };
 
-    Some(locations)
+    let mut ranges = HashMap::new();
+    for (file, restrict) in targets {
+        let Some((file_text, Some(file_program))) = documents.get(file) else {
+            continue;
+        };
+
+        // Functions with their own binding of the name keep it untouched
+        let shadowing: Vec<Range> = match restrict {
+            Some(_) => Vec::new(),
+            None => {
+                let scopes: Vec<&String> = definitions
+                    .iter()
+                    .filter(|def| def.location.uri == *file)
+                    .filter_map(|def| def.scope.as_ref())
+                    .collect();
+                let mut functions = Vec::new();
+                for item in &file_program.items {
+                    if let Some(func) = item_function(item) {
+                        collect_functions(func, &mut functions);
+                    }
+                }
+                functions
+                    .into_iter()
+                    .filter(|func| scopes.contains(&&func.name.name))
+                    .map(|func| span_to_range(&func.span, file_text))
+                    .collect()
+            }
+        };
+
+        let mut file_ranges: Vec<Range> = index
+            .find_references(name, true)
+            .into_iter()
+            .filter(|loc| loc.uri == *file)
+            .map(|loc| loc.range)
+            .filter(|range| {
+                restrict.is_none_or(|outer| range_within(range, &outer))
+                    && !shadowing.iter().any(|outer| range_within(range, outer))
+            })
+            .collect();
+        file_ranges.sort_by_key(|range| (range.start, range.end));
+        file_ranges.dedup();
+        if !file_ranges.is_empty() {
+            ranges.insert(file.clone(), file_ranges);
+        }
+    }
+
+    Ok(SymbolOccurrences {
+        ranges,
+        declarations,
+    })
 }
 
-/// Fallback implementation that searches only the current file
+/// Open document declaring the top-level symbol `name` seen from `uri`:
+/// `uri` itself, or the module it imports the name from if that is open
+fn home_module<'a>(
+    uri: &'a Url,
+    program: &Program,
+    name: &str,
+    documents: &'a HashMap<Url, (String, Option<Program>)>,
+) -> Option<&'a Url> {
+    let import = program.items.iter().find_map(|item| match item {
+        Item::Import(import) if import_names(import, name) => Some(import),
+        _ => None,
+    });
+    let Some(import) = import else {
+        return Some(uri);
+    };
+
+    documents
+        .keys()
+        .find(|candidate| import_targets(uri, &import.source, candidate))
+}
+
+/// Whether `program` (at `uri`) imports `name` from the module at `home`
+fn imports_name(uri: &Url, program: &Program, name: &str, home: &Url) -> bool {
+    program.items.iter().any(|item| match item {
+        Item::Import(import) => {
+            import_names(import, name) && import_targets(uri, &import.source, home)
+        }
+        _ => false,
+    })
+}
+
+fn import_names(import: &ImportDecl, name: &str) -> bool {
+    import.specifiers.iter().any(
+        |spec| matches!(spec, ImportSpecifier::Named { name: ident, .. } if ident.name == name),
+    )
+}
+
+/// Whether the import `source` written in `importer` refers to `target`
 ///
-/// Used when the workspace index doesn't have results (e.g., file just opened)
-fn fallback_single_file_references(
-    uri: &Url,
-    _text: &str,
-    identifier: &str,
-    ast: Option<&Program>,
-    include_declaration: bool,
-) -> Option<Vec<Location>> {
-    let ast = ast?;
+/// Relative imports are matched on the URL alone so that unsaved documents
+/// resolve; anything else goes through the module loader's resolution.
+pub(crate) fn import_targets(importer: &Url, source: &str, target: &Url) -> bool {
+    if source.starts_with("./") || source.starts_with("../") {
+        let Ok(base) = importer.join(source) else {
+            return false;
+        };
+        if Path::new(source).extension().is_some() {
+            return base == *target;
+        }
+        return ["atlas", "atl"]
+            .iter()
+            .any(|ext| format!("{}.{}", base, ext) == target.as_str());
+    }
 
-    // Use the existing navigation helper to find all references in this file
-    let ranges = crate::navigation::find_references(ast, &Default::default(), identifier);
+    let (Ok(file), Ok(target)) = (importer.to_file_path(), target.to_file_path()) else {
+        return false;
+    };
+    let mut resolver = ModuleResolver::new(crate::navigation::project_root(&file));
+    resolver
+        .resolve_path(source, &file, atlas_runtime::Span::dummy())
+        .is_ok_and(|resolved| resolved == target)
+}
 
-    if ranges.is_empty() && !include_declaration {
-        return None;
+fn item_function(item: &Item) -> Option<&FunctionDecl> {
+    match item {
+        Item::Function(func) => Some(func),
+        Item::Export(export) => match &export.item {
+            ExportItem::Function(func) => Some(func),
+            _ => None,
+        },
+        _ => None,
     }
+}
 
-    // Convert ranges to locations
-    let locations: Vec<Location> = ranges
-        .into_iter()
-        .map(|range| Location {
-            uri: uri.clone(),
-            range,
-        })
-        .collect();
+/// Push `func` and the functions nested in it that contain `offset`,
+/// outermost first
+fn functions_containing<'a>(
+    func: &'a FunctionDecl,
+    offset: usize,
+    out: &mut Vec<&'a FunctionDecl>,
+) {
+    if !func.span.contains(offset) {
+        return;
+    }
+    out.push(func);
+    let mut nested = Vec::new();
+    nested_functions(&func.body, &mut nested);
+    for inner in nested {
+        functions_containing(inner, offset, out);
+    }
+}
 
-    if locations.is_empty() {
-        None
-    } else {
-        Some(locations)
+/// `func` and every function declared anywhere inside it
+fn collect_functions<'a>(func: &'a FunctionDecl, out: &mut Vec<&'a FunctionDecl>) {
+    out.push(func);
+    let mut nested = Vec::new();
+    nested_functions(&func.body, &mut nested);
+    for inner in nested {
+        collect_functions(inner, out);
     }
 }
 
+/// Functions declared directly in `block` or its control-flow sub-blocks
+fn nested_functions<'a>(block: &'a Block, out: &mut Vec<&'a FunctionDecl>) {
+    for stmt in &block.statements {
+        match stmt {
+            Stmt::FunctionDecl(func) => out.push(func),
+            Stmt::If(if_stmt) => {
+                nested_functions(&if_stmt.then_block, out);
+                if let Some(else_block) = &if_stmt.else_block {
+                    nested_functions(else_block, out);
+                }
+            }
+            Stmt::While(while_stmt) => nested_functions(&while_stmt.body, out),
+            Stmt::ForIn(for_in_stmt) => nested_functions(&for_in_stmt.body, out),
+            Stmt::TryCatch(try_catch) => {
+                nested_functions(&try_catch.body, out);
+                nested_functions(&try_catch.catch_block, out);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn range_within(inner: &Range, outer: &Range) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
 /// Find the symbol at a given position for use in references/definition
 pub fn find_symbol_at_position(
     text: &str,
@@ -111,8 +355,6 @@ pub fn is_definition_position(text: &str, position: Position, ast: Option<&Progr
 
 /// Check if an identifier at a given offset is a definition in the AST
 fn is_definition_in_program(identifier: &str, offset: usize, program: &Program) -> bool {
-    use atlas_runtime::ast::Item;
-
     for item in &program.items {
         match item {
             Item::Function(func) => {
@@ -133,15 +375,13 @@ fn is_definition_in_program(identifier: &str, offset: usize, program: &Program)
                     return true;
                 }
             }
-            Item::Statement(stmt) => {
-                if is_definition_in_stmt(stmt, identifier, offset) {
-                    return true;
-                }
+            Item::Statement(stmt) if is_definition_in_stmt(stmt, identifier, offset) => {
+                return true;
             }
-            Item::TypeAlias(alias) => {
-                if alias.name.name == identifier && alias.name.span.contains(offset) {
-                    return true;
-                }
+            Item::TypeAlias(alias)
+                if alias.name.name == identifier && alias.name.span.contains(offset) =>
+            {
+                return true;
             }
             _ => {}
         }
@@ -150,11 +390,7 @@ fn is_definition_in_program(identifier: &str, offset: usize, program: &Program)
     false
 }
 
-fn is_definition_in_block(
-    block: &atlas_runtime::ast::Block,
-    identifier: &str,
-    offset: usize,
-) -> bool {
+fn is_definition_in_block(block: &Block, identifier: &str, offset: usize) -> bool {
     for stmt in &block.statements {
         if is_definition_in_stmt(stmt, identifier, offset) {
             return true;
@@ -163,9 +399,7 @@ fn is_definition_in_block(
     false
 }
 
-fn is_definition_in_stmt(stmt: &atlas_runtime::ast::Stmt, identifier: &str, offset: usize) -> bool {
-    use atlas_runtime::ast::Stmt;
-
+fn is_definition_in_stmt(stmt: &Stmt, identifier: &str, offset: usize) -> bool {
     match stmt {
         Stmt::VarDecl(var_decl) => {
             var_decl.name.name == identifier && var_decl.name.span.contains(offset)
@@ -198,6 +432,14 @@ fn is_definition_in_stmt(stmt: &atlas_runtime::ast::Stmt, identifier: &str, offs
                     .is_some_and(|block| is_definition_in_block(block, identifier, offset))
         }
         Stmt::While(while_stmt) => is_definition_in_block(&while_stmt.body, identifier, offset),
+        Stmt::TryCatch(try_catch) => {
+            try_catch
+                .error_binding
+                .as_ref()
+                .is_some_and(|b| b.name == identifier && b.span.contains(offset))
+                || is_definition_in_block(&try_catch.body, identifier, offset)
+                || is_definition_in_block(&try_catch.catch_block, identifier, offset)
+        }
         _ => false,
     }
 }
@@ -291,41 +533,49 @@ mod tests {
         ));
     }
 
+    fn open_documents(
+        files: &[(&Url, &str)],
+    ) -> (HashMap<Url, (String, Option<Program>)>, SymbolIndex) {
+        let mut documents = HashMap::new();
+        let mut index = SymbolIndex::new();
+        for (uri, source) in files {
+            let ast = parse_source(source);
+            index.index_document(uri, source, ast.as_ref());
+            documents.insert((*uri).clone(), (source.to_string(), ast));
+        }
+        (documents, index)
+    }
+
     #[test]
-    fn test_fallback_single_file_references() {
-        let source = r#"
-fn add(a: number, b: number) -> number {
-    return a + b;
-}
-var x: number = add(1, 2);
-var y: number = add(3, 4);
-"#;
-        let ast = parse_source(source);
+    fn test_local_references_stay_in_their_function() {
+        let source = "fn first(): number {\n    let x: number = 1;\n    return x;\n}\nfn second(): number {\n    let x: number = 2;\n    return x;\n}\n";
         let uri = Url::parse("file:///test.atl").unwrap();
-
-        // Find references to "add"
-        let refs = fallback_single_file_references(&uri, source, "add", ast.as_ref(), true);
-        assert!(refs.is_some());
-
-        let locations = refs.unwrap();
-        // Should find: 1 definition + 2 references = 3 total (but current implementation might vary)
-        assert!(!locations.is_empty());
+        let (documents, index) = open_documents(&[(&uri, source)]);
+        let at_x = Position {
+            line: 1,
+            character: 8,
+        };
+
+        let with_decl = find_all_references(&uri, at_x, &documents, &index, true).unwrap();
+        assert_eq!(with_decl.len(), 2);
+        assert!(with_decl.iter().all(|loc| loc.range.start.line < 3));
+
+        let without_decl = find_all_references(&uri, at_x, &documents, &index, false).unwrap();
+        assert_eq!(without_decl.len(), 1);
+        assert_eq!(without_decl[0].range.start.line, 2);
     }
 
     #[test]
-    fn test_fallback_with_no_references() {
-        let source = r#"
-fn unused(a: number) -> number {
-    return a + 1;
-}
-"#;
-        let ast = parse_source(source);
+    fn test_no_references_for_unknown_symbol() {
+        let source = "fn unused(a: number): number {\n    return a + 1;\n}\nprint(1);\n";
         let uri = Url::parse("file:///test.atl").unwrap();
-
-        // Find references to "nonexistent"
-        let refs =
-            fallback_single_file_references(&uri, source, "nonexistent", ast.as_ref(), false);
-        // Should return None since "nonexistent" doesn't exist
-        assert!(refs.is_none());
+        let (documents, index) = open_documents(&[(&uri, source)]);
+
+        // `print` is a builtin with no declaration in the workspace
+        let position = Position {
+            line: 3,
+            character: 2,
+        };
+        assert!(find_all_references(&uri, position, &documents, &index, true).is_none());
     }
 }
@@ -0,0 +1,235 @@
+//! Scheduling of document analysis and background work
+//!
+//! Edits are parsed as they arrive, but binding and type-checking a document
+//! is debounced: each edit replaces the analysis scheduled by the previous
+//! one, so a burst of keystrokes is analyzed once. Interactive requests
+//! (completion, hover, signature help) are marked in flight while they run,
+//! and background work — debounced analysis and workspace indexing — waits
+//! for them before taking locks or starting its next batch.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::task::AbortHandle;
+use tower_lsp::lsp_types::notification::Progress;
+use tower_lsp::lsp_types::request::WorkDoneProgressCreate;
+use tower_lsp::lsp_types::*;
+use tower_lsp::Client;
+
+/// Quiet period after an edit before its document is analyzed
+pub const ANALYSIS_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Per-document debounced tasks plus the count of interactive requests in flight
+pub struct Scheduler {
+    debounce: Duration,
+    pending: Mutex<HashMap<Url, AbortHandle>>,
+    interactive: AtomicUsize,
+    idle: Notify,
+}
+
+impl Scheduler {
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            debounce,
+            pending: Mutex::new(HashMap::new()),
+            interactive: AtomicUsize::new(0),
+            idle: Notify::new(),
+        }
+    }
+
+    /// Run `task` for `uri` once the debounce delay has passed and no
+    /// interactive request is in flight, cancelling the task an earlier
+    /// call scheduled for the same document
+    pub fn schedule<F>(self: &Arc<Self>, uri: Url, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let scheduler = Arc::clone(self);
+        let handle = tokio::spawn(async move {
+            tokio::time::sleep(scheduler.debounce).await;
+            scheduler.wait_for_interactive().await;
+            task.await;
+        });
+        let superseded = self
+            .pending
+            .lock()
+            .expect("scheduler lock poisoned")
+            .insert(uri, handle.abort_handle());
+        if let Some(superseded) = superseded {
+            superseded.abort();
+        }
+    }
+
+    /// Cancel the task scheduled for `uri`, if it has not finished
+    pub fn cancel(&self, uri: &Url) {
+        let pending = self
+            .pending
+            .lock()
+            .expect("scheduler lock poisoned")
+            .remove(uri);
+        if let Some(pending) = pending {
+            pending.abort();
+        }
+    }
+
+    /// Mark an interactive request in flight until the guard is dropped
+    pub fn interactive(&self) -> InteractiveGuard<'_> {
+        self.interactive.fetch_add(1, Ordering::SeqCst);
+        InteractiveGuard { scheduler: self }
+    }
+
+    /// Number of interactive requests in flight
+    pub fn interactive_in_flight(&self) -> usize {
+        self.interactive.load(Ordering::SeqCst)
+    }
+
+    /// Wait until no interactive request is in flight
+    pub async fn wait_for_interactive(&self) {
+        loop {
+            // Registered before the check so a guard dropped in between still wakes us
+            let idle = self.idle.notified();
+            if self.interactive_in_flight() == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+}
+
+/// Keeps an interactive request counted by its [`Scheduler`]
+pub struct InteractiveGuard<'a> {
+    scheduler: &'a Scheduler,
+}
+
+impl Drop for InteractiveGuard<'_> {
+    fn drop(&mut self) {
+        if self.scheduler.interactive.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.scheduler.idle.notify_waiters();
+        }
+    }
+}
+
+/// `$/progress` reporting for one long-running task
+///
+/// Reports nothing when the client does not support work done progress or
+/// refuses the token.
+pub struct ProgressReporter {
+    client: Client,
+    token: Option<ProgressToken>,
+}
+
+impl ProgressReporter {
+    /// Ask the client to create `token` and report its start
+    pub async fn begin(client: Client, supported: bool, token: &str, title: &str) -> Self {
+        let token = NumberOrString::String(token.to_string());
+        let created = supported
+            && client
+                .send_request::<WorkDoneProgressCreate>(WorkDoneProgressCreateParams {
+                    token: token.clone(),
+                })
+                .await
+                .is_ok();
+        let reporter = Self {
+            client,
+            token: created.then_some(token),
+        };
+        reporter
+            .send(WorkDoneProgress::Begin(WorkDoneProgressBegin {
+                title: title.to_string(),
+                cancellable: Some(false),
+                message: None,
+                percentage: Some(0),
+            }))
+            .await;
+        reporter
+    }
+
+    /// Report `done` of `total` steps
+    pub async fn report(&self, done: usize, total: usize, message: String) {
+        let percentage = (done * 100).checked_div(total).unwrap_or(100) as u32;
+        self.send(WorkDoneProgress::Report(WorkDoneProgressReport {
+            cancellable: Some(false),
+            message: Some(message),
+            percentage: Some(percentage),
+        }))
+        .await;
+    }
+
+    /// Report completion
+    pub async fn end(self, message: String) {
+        self.send(WorkDoneProgress::End(WorkDoneProgressEnd {
+            message: Some(message),
+        }))
+        .await;
+    }
+
+    async fn send(&self, progress: WorkDoneProgress) {
+        if let Some(token) = &self.token {
+            self.client
+                .send_notification::<Progress>(ProgressParams {
+                    token: token.clone(),
+                    value: ProgressParamsValue::WorkDone(progress),
+                })
+                .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+
+    fn uri() -> Url {
+        Url::parse("file:///test.atl").unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_later_edit_cancels_pending_task() {
+        let scheduler = Arc::new(Scheduler::new(Duration::from_millis(50)));
+        let runs = Arc::new(AtomicUsize::new(0));
+        for _ in 0..3 {
+            let runs = Arc::clone(&runs);
+            scheduler.schedule(uri(), async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+            });
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_drops_pending_task() {
+        let scheduler = Arc::new(Scheduler::new(Duration::from_millis(50)));
+        let ran = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&ran);
+        scheduler.schedule(uri(), async move {
+            flag.store(true, Ordering::SeqCst);
+        });
+        scheduler.cancel(&uri());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!ran.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_background_task_waits_for_interactive_requests() {
+        let scheduler = Arc::new(Scheduler::new(Duration::from_millis(10)));
+        let ran = Arc::new(AtomicBool::new(false));
+        let guard = scheduler.interactive();
+        let flag = Arc::clone(&ran);
+        scheduler.schedule(uri(), async move {
+            flag.store(true, Ordering::SeqCst);
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!ran.load(Ordering::SeqCst));
+
+        drop(guard);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(ran.load(Ordering::SeqCst));
+        assert_eq!(scheduler.interactive_in_flight(), 0);
+    }
+}
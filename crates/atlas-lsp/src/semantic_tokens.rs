@@ -64,6 +64,7 @@ mod token_type_idx {
     pub const COMMENT: u32 = 17;
     pub const STRING: u32 = 18;
     pub const NUMBER: u32 = 19;
+    pub const REGEXP: u32 = 20;
     pub const OPERATOR: u32 = 21;
 }
 
@@ -211,6 +212,7 @@ fn classify_token(
         | TokenKind::Static
         | TokenKind::Const
         | TokenKind::Defer
+        | TokenKind::Macro
         | TokenKind::New => (token_type_idx::KEYWORD, 0),
 
         // Boolean literals (also keywords semantically)
@@ -218,6 +220,7 @@ fn classify_token(
 
         // Literals
         TokenKind::Number => (token_type_idx::NUMBER, 0),
+        TokenKind::Regex => (token_type_idx::REGEXP, 0),
         TokenKind::String
         | TokenKind::TemplateString
         | TokenKind::InterpolationStart
@@ -244,7 +247,9 @@ fn classify_token(
         | TokenKind::PipePipe
         | TokenKind::Ampersand
         | TokenKind::At
+        | TokenKind::Dollar
         | TokenKind::Pipe
+        | TokenKind::PipeGreater
         | TokenKind::PlusEqual
         | TokenKind::MinusEqual
         | TokenKind::StarEqual
@@ -254,6 +259,8 @@ fn classify_token(
         | TokenKind::Arrow
         | TokenKind::FatArrow
         | TokenKind::Question
+        | TokenKind::QuestionDot
+        | TokenKind::QuestionQuestion
         | TokenKind::ColonColon
         | TokenKind::Range
         | TokenKind::RangeInclusive
@@ -421,6 +428,7 @@ fn get_builtin_names() -> std::collections::HashSet<&'static str> {
         "int",
         // Type checking
         "typeof",
+        "inspect",
         "is_number",
         "is_string",
         "is_bool",
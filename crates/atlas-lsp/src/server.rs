@@ -1,6 +1,8 @@
 //! Atlas LSP Server implementation
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tower_lsp::jsonrpc::Result;
@@ -10,6 +12,8 @@ use tower_lsp::{Client, LanguageServer};
 use crate::document::DocumentState;
 use crate::index::SymbolIndex;
 use crate::inlay_hints::InlayHintConfig;
+use crate::modules::ModuleGraph;
+use crate::scheduler::{ProgressReporter, Scheduler, ANALYSIS_DEBOUNCE};
 use crate::semantic_tokens;
 use crate::symbols::WorkspaceIndex;
 
@@ -19,9 +23,26 @@ pub struct AtlasLspServer {
     documents: Arc<Mutex<HashMap<Url, DocumentState>>>,
     workspace_index: Arc<Mutex<WorkspaceIndex>>,
     symbol_index: Arc<Mutex<SymbolIndex>>,
-    inlay_config: InlayHintConfig,
+    /// Import edges and bound imported modules, shared with the documents
+    modules: Arc<std::sync::Mutex<ModuleGraph>>,
+    /// Set from `initializationOptions` and `workspace/didChangeConfiguration`
+    inlay_config: Arc<Mutex<InlayHintConfig>>,
+    /// Whether the client accepts `workspace/inlayHint/refresh`
+    inlay_refresh_supported: AtomicBool,
+    /// Workspace folders (or the root) from `initialize`, indexed for workspace symbols
+    workspace_roots: Arc<Mutex<Vec<PathBuf>>>,
+    /// Whether the client lets the server register a file watcher
+    watch_registration_supported: AtomicBool,
+    /// Whether the client accepts `$/progress` for server-initiated work
+    work_done_progress_supported: AtomicBool,
+    /// Debounced analysis and interactive-request priority
+    scheduler: Arc<Scheduler>,
 }
 
+/// Files parsed per batch by the workspace indexer; interactive requests
+/// get the CPU between batches
+const INDEX_BATCH_SIZE: usize = 32;
+
 impl AtlasLspServer {
     /// Create a new Atlas LSP server
     pub fn new(client: Client) -> Self {
@@ -30,18 +51,272 @@ impl AtlasLspServer {
             documents: Arc::new(Mutex::new(HashMap::new())),
             workspace_index: Arc::new(Mutex::new(WorkspaceIndex::new())),
             symbol_index: Arc::new(Mutex::new(SymbolIndex::new())),
-            inlay_config: InlayHintConfig::default(),
+            modules: Arc::new(std::sync::Mutex::new(ModuleGraph::new())),
+            inlay_config: Arc::new(Mutex::new(InlayHintConfig::default())),
+            inlay_refresh_supported: AtomicBool::new(false),
+            workspace_roots: Arc::new(Mutex::new(Vec::new())),
+            watch_registration_supported: AtomicBool::new(false),
+            work_done_progress_supported: AtomicBool::new(false),
+            scheduler: Arc::new(Scheduler::new(ANALYSIS_DEBOUNCE)),
+        }
+    }
+
+    /// Index the Atlas files on disk under the workspace roots in the background.
+    ///
+    /// Files are parsed on a blocking thread in batches, each started only
+    /// when no interactive request is in flight, with progress reported
+    /// through `$/progress`. Documents opened in the meantime keep the
+    /// symbols from their editor text.
+    fn spawn_workspace_indexer(&self, roots: Vec<PathBuf>) {
+        let workspace_index = Arc::clone(&self.workspace_index);
+        let documents = Arc::clone(&self.documents);
+        let scheduler = Arc::clone(&self.scheduler);
+        let client = self.client.clone();
+        let progress_supported = self.work_done_progress_supported.load(Ordering::Relaxed);
+        tokio::spawn(async move {
+            // Roots an `atlas daemon` serves come back already indexed
+            let (from_daemon, paths) = tokio::task::spawn_blocking(move || {
+                let mut from_daemon = Vec::new();
+                let mut paths = Vec::new();
+                for root in &roots {
+                    let socket = crate::daemon::socket_path(root);
+                    match crate::daemon::DaemonClient::connect(&socket)
+                        .and_then(|mut daemon| daemon.index())
+                    {
+                        Ok(files) => from_daemon.extend(files),
+                        Err(_) => paths.extend(crate::symbols::find_workspace_files(root)),
+                    }
+                }
+                (from_daemon, paths)
+            })
+            .await
+            .unwrap_or_default();
+
+            let progress = ProgressReporter::begin(
+                client.clone(),
+                progress_supported,
+                "atlas/indexWorkspace",
+                "Indexing Atlas files",
+            )
+            .await;
+            let mut files = from_daemon;
+            files.reserve(paths.len());
+            let mut done = 0;
+            for batch in paths.chunks(INDEX_BATCH_SIZE) {
+                scheduler.wait_for_interactive().await;
+                done += batch.len();
+                let batch = batch.to_vec();
+                let indexed = tokio::task::spawn_blocking(move || {
+                    batch
+                        .iter()
+                        .filter_map(|path| crate::symbols::index_file(path))
+                        .collect::<Vec<_>>()
+                })
+                .await
+                .unwrap_or_default();
+                files.extend(indexed);
+                progress
+                    .report(done, paths.len(), format!("{}/{} files", done, paths.len()))
+                    .await;
+            }
+
+            // Hold the documents lock so a file opened meanwhile is not overwritten
+            let count = files.len();
+            {
+                let open = documents.lock().await;
+                let mut index = workspace_index.lock().await;
+                for (uri, symbols) in files {
+                    if !open.contains_key(&uri) {
+                        index.insert_symbols(uri, symbols);
+                    }
+                }
+            }
+            client
+                .log_message(
+                    MessageType::INFO,
+                    format!("Indexed {} Atlas files for workspace symbols", count),
+                )
+                .await;
+            progress.end(format!("Indexed {} files", count)).await;
+        });
+    }
+
+    /// Analyze `uri` once its edits settle, then publish its diagnostics
+    ///
+    /// A later edit cancels this; a result computed for a superseded version
+    /// is dropped.
+    fn schedule_analysis(&self, uri: Url) {
+        let documents = Arc::clone(&self.documents);
+        let client = self.client.clone();
+        self.scheduler.schedule(uri.clone(), async move {
+            let input = match documents.lock().await.get(&uri) {
+                Some(doc) => doc.analysis_input(),
+                None => return,
+            };
+            // `None` when a request already analyzed this version
+            let analysis = match input {
+                Some(input) => match tokio::task::spawn_blocking(move || input.run()).await {
+                    Ok(analysis) => Some(analysis),
+                    Err(_) => return,
+                },
+                None => None,
+            };
+
+            let (diagnostics, version) = {
+                let mut documents = documents.lock().await;
+                let Some(doc) = documents.get_mut(&uri) else {
+                    return;
+                };
+                if let Some(analysis) = analysis {
+                    if !doc.store_analysis(analysis) {
+                        return;
+                    }
+                }
+                let diagnostics: Vec<_> = doc
+                    .diagnostics
+                    .iter()
+                    .map(crate::convert::diagnostic_to_lsp)
+                    .collect();
+                (diagnostics, doc.version)
+            };
+            client
+                .publish_diagnostics(uri, diagnostics, Some(version))
+                .await;
+        });
+    }
+
+    /// Mark the open documents among `modules` stale and schedule their
+    /// analysis, which re-publishes their diagnostics
+    async fn reanalyze_modules(&self, modules: HashSet<PathBuf>) {
+        if modules.is_empty() {
+            return;
+        }
+        let uris: Vec<Url> = {
+            let mut documents = self.documents.lock().await;
+            documents
+                .iter_mut()
+                .filter(|(uri, _)| {
+                    ModuleGraph::module_path(uri).is_some_and(|path| modules.contains(&path))
+                })
+                .map(|(uri, doc)| {
+                    doc.mark_stale();
+                    uri.clone()
+                })
+                .collect()
+        };
+        for uri in uris {
+            self.schedule_analysis(uri);
+        }
+    }
+
+    /// Lock the documents, first analyzing `uri` if it has edits whose
+    /// analysis is still pending, so a request never sees stale symbols
+    async fn analyzed_documents(
+        &self,
+        uri: &Url,
+    ) -> tokio::sync::MutexGuard<'_, HashMap<Url, DocumentState>> {
+        let mut documents = self.documents.lock().await;
+        if let Some(doc) = documents.get_mut(uri) {
+            doc.analyze_if_stale();
+        }
+        documents
+    }
+
+    /// Re-index a file that is not open from its contents on disk, or drop it
+    /// from the workspace index if it no longer exists
+    async fn reindex_from_disk(&self, uri: &Url) {
+        let indexed = match uri.to_file_path() {
+            Ok(path) => tokio::task::spawn_blocking(move || crate::symbols::index_file(&path))
+                .await
+                .ok()
+                .flatten(),
+            Err(()) => None,
+        };
+        let mut index = self.workspace_index.lock().await;
+        match indexed {
+            Some((uri, symbols)) => index.insert_symbols(uri, symbols),
+            None => index.remove_document(uri),
         }
     }
+
+    /// Every open document plus the workspace files on disk that are not
+    /// open, so calls are resolved across modules
+    async fn call_hierarchy_modules(&self) -> crate::call_hierarchy::Modules {
+        let mut modules: crate::call_hierarchy::Modules = {
+            let documents = self.documents.lock().await;
+            documents
+                .iter()
+                .map(|(uri, doc)| (uri.clone(), (doc.text.clone(), doc.ast.clone())))
+                .collect()
+        };
+        let roots = self.workspace_roots.lock().await.clone();
+        let open: HashSet<Url> = modules.keys().cloned().collect();
+        let on_disk = tokio::task::spawn_blocking(move || {
+            roots
+                .iter()
+                .flat_map(|root| crate::symbols::find_workspace_files(root))
+                .filter(|path| Url::from_file_path(path).is_ok_and(|uri| !open.contains(&uri)))
+                .filter_map(|path| crate::call_hierarchy::read_module(&path))
+                .collect::<Vec<_>>()
+        })
+        .await
+        .unwrap_or_default();
+        modules.extend(on_disk);
+        modules
+    }
 }
 
 #[tower_lsp::async_trait]
 impl LanguageServer for AtlasLspServer {
-    async fn initialize(&self, _params: InitializeParams) -> Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        if let Some(options) = &params.initialization_options {
+            self.inlay_config.lock().await.apply_settings(options);
+        }
+        let refresh_supported = params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|w| w.inlay_hint.as_ref())
+            .and_then(|h| h.refresh_support)
+            .unwrap_or(false);
+        self.inlay_refresh_supported
+            .store(refresh_supported, Ordering::Relaxed);
+        let watch_supported = params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|w| w.did_change_watched_files.as_ref())
+            .and_then(|w| w.dynamic_registration)
+            .unwrap_or(false);
+        self.watch_registration_supported
+            .store(watch_supported, Ordering::Relaxed);
+        let progress_supported = params
+            .capabilities
+            .window
+            .as_ref()
+            .and_then(|w| w.work_done_progress)
+            .unwrap_or(false);
+        self.work_done_progress_supported
+            .store(progress_supported, Ordering::Relaxed);
+
+        #[allow(deprecated)]
+        let roots: Vec<PathBuf> = match &params.workspace_folders {
+            Some(folders) => folders
+                .iter()
+                .filter_map(|folder| folder.uri.to_file_path().ok())
+                .collect(),
+            None => params
+                .root_uri
+                .iter()
+                .filter_map(|uri| uri.to_file_path().ok())
+                .collect(),
+        };
+        *self.workspace_roots.lock().await = roots;
+
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::FULL,
+                    TextDocumentSyncKind::INCREMENTAL,
                 )),
                 diagnostic_provider: Some(DiagnosticServerCapabilities::Options(
                     DiagnosticOptions {
@@ -55,6 +330,10 @@ impl LanguageServer for AtlasLspServer {
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
                 definition_provider: Some(OneOf::Left(true)),
                 references_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Right(RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                })),
                 completion_provider: Some(CompletionOptions {
                     resolve_provider: Some(false),
                     trigger_characters: Some(vec![".".to_string()]),
@@ -62,8 +341,22 @@ impl LanguageServer for AtlasLspServer {
                     work_done_progress_options: WorkDoneProgressOptions::default(),
                     completion_item: None,
                 }),
+                signature_help_provider: Some(SignatureHelpOptions {
+                    trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
+                    retrigger_characters: None,
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                }),
                 document_formatting_provider: Some(OneOf::Left(true)),
                 document_range_formatting_provider: Some(OneOf::Left(true)),
+                document_on_type_formatting_provider: Some(DocumentOnTypeFormattingOptions {
+                    first_trigger_character: crate::formatting::ON_TYPE_TRIGGERS[0].to_string(),
+                    more_trigger_character: Some(
+                        crate::formatting::ON_TYPE_TRIGGERS[1..]
+                            .iter()
+                            .map(|c| c.to_string())
+                            .collect(),
+                    ),
+                }),
                 code_action_provider: Some(CodeActionProviderCapability::Options(
                     CodeActionOptions {
                         code_action_kinds: Some(vec![
@@ -111,19 +404,63 @@ impl LanguageServer for AtlasLspServer {
         self.client
             .log_message(MessageType::INFO, "Atlas LSP server initialized")
             .await;
+
+        let roots = self.workspace_roots.lock().await.clone();
+        if !roots.is_empty() {
+            self.spawn_workspace_indexer(roots);
+        }
+
+        if self.watch_registration_supported.load(Ordering::Relaxed) {
+            let options = DidChangeWatchedFilesRegistrationOptions {
+                watchers: vec![FileSystemWatcher {
+                    glob_pattern: GlobPattern::String("**/*.{atl,atlas}".to_string()),
+                    kind: None,
+                }],
+            };
+            let registration = Registration {
+                id: "atlas-source-watcher".to_string(),
+                method: "workspace/didChangeWatchedFiles".to_string(),
+                register_options: serde_json::to_value(options).ok(),
+            };
+            let client = self.client.clone();
+            tokio::spawn(async move {
+                let _ = client.register_capability(vec![registration]).await;
+            });
+        }
     }
 
     async fn shutdown(&self) -> Result<()> {
         Ok(())
     }
 
+    async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
+        self.inlay_config
+            .lock()
+            .await
+            .apply_settings(&params.settings);
+        if self.inlay_refresh_supported.load(Ordering::Relaxed) {
+            let _ = self.client.inlay_hint_refresh().await;
+        }
+    }
+
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
         let uri = params.text_document.uri;
         let text = params.text_document.text;
         let version = params.text_document.version;
 
+        // Importers of this module now see the editor text
+        let dependents = match ModuleGraph::module_path(&uri) {
+            Some(path) => self
+                .modules
+                .lock()
+                .expect("module graph lock poisoned")
+                .set_open(path, text.clone()),
+            None => HashSet::new(),
+        };
+
         // Create and analyze document
-        let doc = DocumentState::new(uri.clone(), text, version);
+        let doc =
+            DocumentState::with_module_graph(uri.clone(), text, version, Arc::clone(&self.modules));
 
         // Collect diagnostics before storing
         let diagnostics: Vec<_> = doc
@@ -154,32 +491,23 @@ impl LanguageServer for AtlasLspServer {
         self.client
             .publish_diagnostics(uri, diagnostics, Some(version))
             .await;
+
+        self.reanalyze_modules(dependents).await;
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
         let uri = params.text_document.uri;
         let version = params.text_document.version;
 
-        // Collect diagnostics and clone AST for indexing
-        let (diagnostics, ast_clone, text_clone) = {
+        // Re-parse now (ranged edits incrementally); binding and
+        // type-checking wait until the edits settle
+        let (ast_clone, text_clone) = {
             let mut documents = self.documents.lock().await;
-            if let Some(doc) = documents.get_mut(&uri) {
-                // Update document text (full sync)
-                for change in params.content_changes {
-                    doc.update(change.text, version);
-                }
-
-                // Collect diagnostics and clone AST
-                let diags: Vec<_> = doc
-                    .diagnostics
-                    .iter()
-                    .map(crate::convert::diagnostic_to_lsp)
-                    .collect();
-
-                (Some(diags), doc.ast.clone(), doc.text.clone())
-            } else {
-                (None, None, String::new())
-            }
+            let Some(doc) = documents.get_mut(&uri) else {
+                return;
+            };
+            doc.apply_text_changes(params.content_changes, version);
+            (doc.ast.clone(), doc.text.clone())
         };
 
         // Update workspace index with cloned AST
@@ -192,16 +520,21 @@ impl LanguageServer for AtlasLspServer {
             symbol_index.index_document(&uri, &text_clone, Some(&ast));
         }
 
-        // Publish diagnostics after releasing locks
-        if let Some(diags) = diagnostics {
-            self.client
-                .publish_diagnostics(uri, diags, Some(version))
-                .await;
-        }
+        let dependents = match ModuleGraph::module_path(&uri) {
+            Some(path) => self
+                .modules
+                .lock()
+                .expect("module graph lock poisoned")
+                .set_open(path, text_clone),
+            None => HashSet::new(),
+        };
+        self.schedule_analysis(uri);
+        self.reanalyze_modules(dependents).await;
     }
 
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
         let uri = params.text_document.uri;
+        self.scheduler.cancel(&uri);
 
         // Remove document from state
         {
@@ -215,10 +548,58 @@ impl LanguageServer for AtlasLspServer {
             symbol_index.remove_document(&uri);
         }
 
+        // Unsaved edits are discarded; workspace symbols and importers go
+        // back to the saved file
+        self.reindex_from_disk(&uri).await;
+        if let Some(path) = ModuleGraph::module_path(&uri) {
+            let dependents = self
+                .modules
+                .lock()
+                .expect("module graph lock poisoned")
+                .close(&path);
+            self.reanalyze_modules(dependents).await;
+        }
+
         // Clear diagnostics
         self.client.publish_diagnostics(uri, Vec::new(), None).await;
     }
 
+    async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        let open: HashSet<Url> = self.documents.lock().await.keys().cloned().collect();
+        let mut affected = HashSet::new();
+        for change in params.changes {
+            let is_source = change
+                .uri
+                .to_file_path()
+                .is_ok_and(|path| crate::symbols::is_atlas_source(&path));
+            if !is_source {
+                continue;
+            }
+            // Importers are re-analyzed, open or not in the editor
+            if let Some(path) = ModuleGraph::module_path(&change.uri) {
+                affected.extend(
+                    self.modules
+                        .lock()
+                        .expect("module graph lock poisoned")
+                        .file_changed(&path),
+                );
+            }
+            // Open documents are indexed from their editor text
+            if open.contains(&change.uri) {
+                continue;
+            }
+            if change.typ == FileChangeType::DELETED {
+                self.workspace_index
+                    .lock()
+                    .await
+                    .remove_document(&change.uri);
+            } else {
+                self.reindex_from_disk(&change.uri).await;
+            }
+        }
+        self.reanalyze_modules(affected).await;
+    }
+
     async fn document_symbol(
         &self,
         params: DocumentSymbolParams,
@@ -272,14 +653,15 @@ impl LanguageServer for AtlasLspServer {
         let uri = params.text_document.uri;
         let range = params.range;
 
-        let documents = self.documents.lock().await;
+        let config = self.inlay_config.lock().await.clone();
+        let documents = self.analyzed_documents(&uri).await;
         if let Some(doc) = documents.get(&uri) {
             let hints = crate::inlay_hints::generate_inlay_hints(
                 &doc.text,
                 range,
                 doc.ast.as_ref(),
                 doc.symbols.as_ref(),
-                &self.inlay_config,
+                &config,
             );
             if hints.is_empty() {
                 return Ok(None);
@@ -297,11 +679,22 @@ impl LanguageServer for AtlasLspServer {
         let uri = params.text_document_position_params.text_document.uri;
         let position = params.text_document_position_params.position;
 
-        let text = {
+        let (text, ast) = {
             let documents = self.documents.lock().await;
-            documents.get(&uri).map(|doc| doc.text.clone())
+            match documents.get(&uri) {
+                Some(doc) => (Some(doc.text.clone()), doc.ast.clone()),
+                None => (None, None),
+            }
         };
 
+        if let (Some(text), Some(ast)) = (&text, &ast) {
+            if let Some(location) =
+                crate::navigation::find_import_definition(ast, &uri, text, position)
+            {
+                return Ok(Some(GotoDefinitionResponse::Scalar(location)));
+            }
+        }
+
         if let Some(text) = text {
             let symbol_index = self.symbol_index.lock().await;
             if let Some(location) =
@@ -319,30 +712,80 @@ impl LanguageServer for AtlasLspServer {
         let position = params.text_document_position.position;
         let include_declaration = params.context.include_declaration;
 
-        let documents = self.documents.lock().await;
+        // Collect all documents for cross-file references
+        let documents_lock = self.documents.lock().await;
+        let mut documents = HashMap::new();
+        for (doc_uri, doc) in documents_lock.iter() {
+            documents.insert(doc_uri.clone(), (doc.text.clone(), doc.ast.clone()));
+        }
+        drop(documents_lock);
+
+        let symbol_index = self.symbol_index.lock().await;
+        Ok(crate::references::find_all_references(
+            &uri,
+            position,
+            &documents,
+            &symbol_index,
+            include_declaration,
+        ))
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        let uri = params.text_document.uri;
+        let position = params.position;
+
+        let documents = self.analyzed_documents(&uri).await;
         let symbol_index = self.symbol_index.lock().await;
 
         if let Some(doc) = documents.get(&uri) {
-            let locations = crate::references::find_all_references(
+            let range = crate::refactor::prepare_rename(
                 &uri,
                 &doc.text,
                 position,
-                doc.ast.as_ref(),
                 doc.symbols.as_ref(),
                 &symbol_index,
-                include_declaration,
             );
-            return Ok(locations);
+            return Ok(range.map(PrepareRenameResponse::Range));
         }
 
         Ok(None)
     }
 
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let uri = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+
+        // Collect all documents for cross-file edits
+        let documents_lock = self.analyzed_documents(&uri).await;
+        let mut documents = HashMap::new();
+        for (doc_uri, doc) in documents_lock.iter() {
+            documents.insert(doc_uri.clone(), (doc.text.clone(), doc.ast.clone()));
+        }
+        let symbols = documents_lock.get(&uri).and_then(|doc| doc.symbols.clone());
+        drop(documents_lock);
+
+        let symbol_index = self.symbol_index.lock().await;
+        crate::refactor::rename_in_workspace(
+            &uri,
+            position,
+            &documents,
+            symbols.as_ref(),
+            &symbol_index,
+            &params.new_name,
+        )
+        .map(Some)
+        .map_err(|e| tower_lsp::jsonrpc::Error::invalid_params(e.to_string()))
+    }
+
     async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let _interactive = self.scheduler.interactive();
         let uri = params.text_document_position_params.text_document.uri;
         let position = params.text_document_position_params.position;
 
-        let documents = self.documents.lock().await;
+        let documents = self.analyzed_documents(&uri).await;
         if let Some(doc) = documents.get(&uri) {
             // Use the enhanced hover provider
             return Ok(crate::hover::generate_hover(
@@ -356,14 +799,31 @@ impl LanguageServer for AtlasLspServer {
         Ok(None)
     }
 
+    async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
+        let _interactive = self.scheduler.interactive();
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        let documents = self.documents.lock().await;
+        if let Some(doc) = documents.get(&uri) {
+            return Ok(crate::signature_help::generate_signature_help(
+                &doc.text,
+                position,
+                doc.ast.as_ref(),
+            ));
+        }
+
+        Ok(None)
+    }
+
     async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
         let uri = params.text_document.uri;
         let range = params.range;
         let context = params.context;
 
-        let documents = self.documents.lock().await;
+        let documents = self.analyzed_documents(&uri).await;
         if let Some(doc) = documents.get(&uri) {
-            let actions = crate::actions::generate_code_actions(
+            let mut actions = crate::actions::generate_code_actions(
                 &uri,
                 range,
                 &context,
@@ -373,6 +833,15 @@ impl LanguageServer for AtlasLspServer {
                 &doc.diagnostics,
             );
 
+            // Undefined names may be exported by another open document
+            let modules: Vec<(Url, &atlas_runtime::ast::Program)> = documents
+                .iter()
+                .filter_map(|(other, state)| Some((other.clone(), state.ast.as_ref()?)))
+                .collect();
+            actions.extend(crate::actions::generate_import_fixes(
+                &uri, &doc.text, &context, &modules,
+            ));
+
             if actions.is_empty() {
                 return Ok(None);
             }
@@ -389,7 +858,7 @@ impl LanguageServer for AtlasLspServer {
     ) -> Result<Option<SemanticTokensResult>> {
         let uri = params.text_document.uri;
 
-        let documents = self.documents.lock().await;
+        let documents = self.analyzed_documents(&uri).await;
         if let Some(doc) = documents.get(&uri) {
             let result = semantic_tokens::generate_semantic_tokens(
                 &doc.text,
@@ -409,7 +878,7 @@ impl LanguageServer for AtlasLspServer {
         let uri = params.text_document.uri;
         let range = params.range;
 
-        let documents = self.documents.lock().await;
+        let documents = self.analyzed_documents(&uri).await;
         if let Some(doc) = documents.get(&uri) {
             let result = semantic_tokens::generate_semantic_tokens_range(
                 &doc.text,
@@ -424,9 +893,10 @@ impl LanguageServer for AtlasLspServer {
     }
 
     async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        let _interactive = self.scheduler.interactive();
         let uri = params.text_document_position.text_document.uri;
 
-        let documents = self.documents.lock().await;
+        let documents = self.analyzed_documents(&uri).await;
         if let Some(doc) = documents.get(&uri) {
             let position = params.text_document_position.position;
             let completions = crate::completion::generate_completions(
@@ -446,7 +916,7 @@ impl LanguageServer for AtlasLspServer {
 
         let documents = self.documents.lock().await;
         if let Some(doc) = documents.get(&uri) {
-            let edits = crate::formatting::format_document(&doc.text);
+            let edits = crate::formatting::format_document(&doc.text, &params.options);
             return Ok(Some(edits));
         }
 
@@ -462,50 +932,51 @@ impl LanguageServer for AtlasLspServer {
 
         let documents = self.documents.lock().await;
         if let Some(doc) = documents.get(&uri) {
-            let edits = crate::formatting::format_range(&doc.text, range);
+            let edits = crate::formatting::format_range(&doc.text, range, &params.options);
             return Ok(Some(edits));
         }
 
         Ok(None)
     }
 
-    async fn prepare_call_hierarchy(
+    async fn on_type_formatting(
         &self,
-        params: CallHierarchyPrepareParams,
-    ) -> Result<Option<Vec<CallHierarchyItem>>> {
-        let uri = params.text_document_position_params.text_document.uri;
-        let position = params.text_document_position_params.position;
+        params: DocumentOnTypeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        let uri = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
 
         let documents = self.documents.lock().await;
         if let Some(doc) = documents.get(&uri) {
-            return Ok(crate::call_hierarchy::prepare_call_hierarchy(
-                &uri,
-                &doc.text,
-                position,
-                doc.ast.as_ref(),
-            ));
+            let edits =
+                crate::formatting::format_on_type(&doc.text, position, &params.ch, &params.options);
+            return Ok(Some(edits));
         }
 
         Ok(None)
     }
 
+    async fn prepare_call_hierarchy(
+        &self,
+        params: CallHierarchyPrepareParams,
+    ) -> Result<Option<Vec<CallHierarchyItem>>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        let modules = self.call_hierarchy_modules().await;
+        Ok(crate::call_hierarchy::prepare_call_hierarchy(
+            &uri, position, &modules,
+        ))
+    }
+
     async fn incoming_calls(
         &self,
         params: CallHierarchyIncomingCallsParams,
     ) -> Result<Option<Vec<CallHierarchyIncomingCall>>> {
-        // Collect all documents for cross-file resolution
-        let documents_lock = self.documents.lock().await;
-        let mut documents = HashMap::new();
-        for (uri, doc) in documents_lock.iter() {
-            documents.insert(uri.clone(), (doc.text.clone(), doc.ast.clone()));
-        }
-        drop(documents_lock);
-
-        let symbol_index = self.symbol_index.lock().await;
+        let modules = self.call_hierarchy_modules().await;
         Ok(crate::call_hierarchy::find_incoming_calls(
-            params,
-            &symbol_index,
-            &documents,
+            &params.item,
+            &modules,
         ))
     }
 
@@ -513,19 +984,10 @@ impl LanguageServer for AtlasLspServer {
         &self,
         params: CallHierarchyOutgoingCallsParams,
     ) -> Result<Option<Vec<CallHierarchyOutgoingCall>>> {
-        // Collect all documents for cross-file resolution
-        let documents_lock = self.documents.lock().await;
-        let mut documents = HashMap::new();
-        for (uri, doc) in documents_lock.iter() {
-            documents.insert(uri.clone(), (doc.text.clone(), doc.ast.clone()));
-        }
-        drop(documents_lock);
-
-        let symbol_index = self.symbol_index.lock().await;
+        let modules = self.call_hierarchy_modules().await;
         Ok(crate::call_hierarchy::find_outgoing_calls(
-            params,
-            &symbol_index,
-            &documents,
+            &params.item,
+            &modules,
         ))
     }
 }
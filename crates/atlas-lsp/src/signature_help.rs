@@ -0,0 +1,907 @@
+//! Signature help provider
+//!
+//! Shows the signature of the call the cursor is in, with the argument being
+//! typed highlighted. The callee is looked up among:
+//! - functions declared in the document, including nested and exported ones
+//! - `extern` declarations
+//! - stdlib builtins, from `BUILTIN_SIGNATURES`. A builtin that accepts
+//!   different kinds of argument lists one signature per form, like overloads.
+
+use atlas_runtime::ast::*;
+use tower_lsp::lsp_types::{
+    Documentation, MarkupContent, MarkupKind, ParameterInformation, ParameterLabel, Position,
+    SignatureHelp, SignatureInformation,
+};
+
+use crate::convert::position_to_offset;
+use crate::hover::{extract_doc_comment, format_type_ref};
+
+/// A builtin signature: callee as written in source, parameters, return type
+/// and description. Callees listed more than once are offered as overloads.
+type BuiltinSignature = (
+    &'static str,
+    &'static [&'static str],
+    &'static str,
+    &'static str,
+);
+
+/// Signatures of the stdlib builtins, as documented in `docs/stdlib`.
+/// Namespaces match case-insensitively (`file.read` and `File.read`).
+const BUILTIN_SIGNATURES: &[BuiltinSignature] = &[
+    // Core
+    (
+        "str",
+        &["value: any"],
+        "string",
+        "Convert any value to its string representation.",
+    ),
+    (
+        "num",
+        &["value: string"],
+        "number",
+        "Parse a string as a number. Panics if it is not a valid number.",
+    ),
+    (
+        "bool",
+        &["value: any"],
+        "bool",
+        "Convert a value to its truthiness.",
+    ),
+    (
+        "len",
+        &["value: string"],
+        "number",
+        "Number of Unicode characters in the string.",
+    ),
+    (
+        "len",
+        &["value: T[]"],
+        "number",
+        "Number of elements in the array.",
+    ),
+    (
+        "type",
+        &["value: any"],
+        "string",
+        "Name of the value's runtime type.",
+    ),
+    (
+        "panic",
+        &["message: string"],
+        "void",
+        "Stop the program with an error message.",
+    ),
+    (
+        "assertEq",
+        &["actual: any", "expected: any"],
+        "void",
+        "Panic if the two values are not equal.",
+    ),
+    (
+        "Some",
+        &["value: T"],
+        "Option<T>",
+        "An `Option` holding `value`.",
+    ),
+    (
+        "Ok",
+        &["value: T"],
+        "Result<T, E>",
+        "A successful `Result`.",
+    ),
+    ("Err", &["error: E"], "Result<T, E>", "A failed `Result`."),
+    // Console
+    (
+        "console.log",
+        &["...args: any"],
+        "void",
+        "Print the arguments to stdout, followed by a newline.",
+    ),
+    (
+        "console.println",
+        &["...args: any"],
+        "void",
+        "Print the arguments to stdout, followed by a newline.",
+    ),
+    (
+        "console.print",
+        &["...args: any"],
+        "void",
+        "Print the arguments to stdout without a newline.",
+    ),
+    (
+        "console.error",
+        &["...args: any"],
+        "void",
+        "Print the arguments to stderr.",
+    ),
+    (
+        "console.warn",
+        &["...args: any"],
+        "void",
+        "Print the arguments to stderr as a warning.",
+    ),
+    (
+        "console.debug",
+        &["...args: any"],
+        "void",
+        "Print the arguments to stderr as debug output.",
+    ),
+    // Testing
+    (
+        "test.assert",
+        &["condition: bool"],
+        "void",
+        "Fail the test if `condition` is false.",
+    ),
+    (
+        "test.assert",
+        &["condition: bool", "message: string"],
+        "void",
+        "Fail the test with `message` if `condition` is false.",
+    ),
+    (
+        "test.assertEq",
+        &["actual: any", "expected: any"],
+        "void",
+        "Fail the test if the two values are not equal.",
+    ),
+    // Math
+    ("Math.abs", &["x: number"], "number", "Absolute value."),
+    (
+        "Math.floor",
+        &["x: number"],
+        "number",
+        "Round down to an integer.",
+    ),
+    (
+        "Math.ceil",
+        &["x: number"],
+        "number",
+        "Round up to an integer.",
+    ),
+    (
+        "Math.round",
+        &["x: number"],
+        "number",
+        "Round to the nearest integer.",
+    ),
+    (
+        "Math.trunc",
+        &["x: number"],
+        "number",
+        "Drop the fractional part.",
+    ),
+    (
+        "Math.min",
+        &["a: number", "b: number"],
+        "number",
+        "The smaller of two numbers.",
+    ),
+    (
+        "Math.max",
+        &["a: number", "b: number"],
+        "number",
+        "The larger of two numbers.",
+    ),
+    (
+        "Math.sign",
+        &["x: number"],
+        "number",
+        "-1, 0 or 1 depending on the sign of `x`.",
+    ),
+    (
+        "Math.sqrt",
+        &["x: number"],
+        "Result<number, string>",
+        "Square root; `Err` for negative input.",
+    ),
+    ("Math.cbrt", &["x: number"], "number", "Cube root."),
+    (
+        "Math.pow",
+        &["base: number", "exponent: number"],
+        "number",
+        "`base` raised to `exponent`.",
+    ),
+    ("Math.exp", &["x: number"], "number", "e raised to `x`."),
+    (
+        "Math.log",
+        &["x: number"],
+        "Result<number, string>",
+        "Natural logarithm; `Err` for non-positive input.",
+    ),
+    (
+        "Math.log2",
+        &["x: number"],
+        "Result<number, string>",
+        "Base-2 logarithm; `Err` for non-positive input.",
+    ),
+    (
+        "Math.log10",
+        &["x: number"],
+        "Result<number, string>",
+        "Base-10 logarithm; `Err` for non-positive input.",
+    ),
+    (
+        "Math.hypot",
+        &["x: number", "y: number"],
+        "number",
+        "Length of the vector (`x`, `y`).",
+    ),
+    ("Math.sin", &["x: number"], "number", "Sine of `x` radians."),
+    (
+        "Math.cos",
+        &["x: number"],
+        "number",
+        "Cosine of `x` radians.",
+    ),
+    (
+        "Math.tan",
+        &["x: number"],
+        "number",
+        "Tangent of `x` radians.",
+    ),
+    (
+        "Math.asin",
+        &["x: number"],
+        "Result<number, string>",
+        "Arcsine; `Err` outside [-1, 1].",
+    ),
+    (
+        "Math.acos",
+        &["x: number"],
+        "Result<number, string>",
+        "Arccosine; `Err` outside [-1, 1].",
+    ),
+    ("Math.atan", &["x: number"], "number", "Arctangent."),
+    (
+        "Math.atan2",
+        &["y: number", "x: number"],
+        "number",
+        "Angle of the point (`x`, `y`) from the positive x axis.",
+    ),
+    (
+        "Math.clamp",
+        &["value: number", "min: number", "max: number"],
+        "Result<number, string>",
+        "Limit `value` to [`min`, `max`]; `Err` if `min > max`.",
+    ),
+    ("Math.random", &[], "number", "Random number in [0, 1)."),
+    // Json
+    (
+        "Json.parse",
+        &["text: string"],
+        "Result<JsonValue, string>",
+        "Parse JSON text.",
+    ),
+    (
+        "Json.stringify",
+        &["value: any"],
+        "string",
+        "Serialize a value as compact JSON.",
+    ),
+    (
+        "Json.isValid",
+        &["text: string"],
+        "bool",
+        "Whether `text` is valid JSON.",
+    ),
+    (
+        "Json.prettify",
+        &["text: string", "indent: number"],
+        "string",
+        "Re-format JSON text with `indent` spaces.",
+    ),
+    (
+        "Json.minify",
+        &["text: string"],
+        "string",
+        "Remove insignificant whitespace from JSON text.",
+    ),
+    (
+        "Json.keys",
+        &["json: JsonValue"],
+        "string[]",
+        "Keys of a JSON object.",
+    ),
+    (
+        "Json.getString",
+        &["json: JsonValue", "key: string"],
+        "Option<string>",
+        "String field of a JSON object.",
+    ),
+    (
+        "Json.getNumber",
+        &["json: JsonValue", "key: string"],
+        "Option<number>",
+        "Number field of a JSON object.",
+    ),
+    (
+        "Json.getBool",
+        &["json: JsonValue", "key: string"],
+        "Option<bool>",
+        "Bool field of a JSON object.",
+    ),
+    (
+        "Json.getArray",
+        &["json: JsonValue", "key: string"],
+        "Option<JsonValue[]>",
+        "Array field of a JSON object.",
+    ),
+    (
+        "Json.getObject",
+        &["json: JsonValue", "key: string"],
+        "Option<JsonValue>",
+        "Object field of a JSON object.",
+    ),
+    (
+        "Json.isNull",
+        &["json: JsonValue", "key: string"],
+        "bool",
+        "Whether the field is missing or null.",
+    ),
+    (
+        "Json.getPath",
+        &["json: JsonValue", "path: string"],
+        "Result<JsonValue, string>",
+        "Value at a dotted path such as `\"user.tags[0]\"`.",
+    ),
+    (
+        "Json.setPath",
+        &["json: JsonValue", "path: string", "value: any"],
+        "Result<JsonValue, string>",
+        "Copy of `json` with the value at `path` replaced.",
+    ),
+    (
+        "Json.hasPath",
+        &["json: JsonValue", "path: string"],
+        "bool",
+        "Whether a value exists at `path`.",
+    ),
+    // Arrays and collections
+    (
+        "Array.isArray",
+        &["value: any"],
+        "bool",
+        "Whether `value` is an array.",
+    ),
+    (
+        "Array.range",
+        &["start: number", "end: number"],
+        "number[]",
+        "Numbers from `start` up to, not including, `end`.",
+    ),
+    (
+        "Array.range",
+        &["start: number", "end: number", "step: number"],
+        "number[]",
+        "Numbers from `start` up to, not including, `end`, `step` apart.",
+    ),
+    (
+        "Map.fromEntries",
+        &["entries: [K, V][]"],
+        "Map<K, V>",
+        "Build a map from key-value pairs.",
+    ),
+    (
+        "Set.fromArray",
+        &["arr: T[]"],
+        "Set<T>",
+        "Build a set from the elements of an array.",
+    ),
+    // Files and paths
+    (
+        "file.read",
+        &["path: string"],
+        "Result<string, string>",
+        "Read a whole file as UTF-8 text.",
+    ),
+    (
+        "file.write",
+        &["path: string", "content: string"],
+        "Result<null, string>",
+        "Write text to a file, replacing its contents.",
+    ),
+    (
+        "file.append",
+        &["path: string", "content: string"],
+        "Result<null, string>",
+        "Append text to a file.",
+    ),
+    (
+        "file.exists",
+        &["path: string"],
+        "bool",
+        "Whether a file or directory exists at `path`.",
+    ),
+    (
+        "file.remove",
+        &["path: string"],
+        "Result<null, string>",
+        "Delete a file.",
+    ),
+    (
+        "file.mkdir",
+        &["path: string"],
+        "Result<null, string>",
+        "Create a single directory.",
+    ),
+    (
+        "file.mkdirp",
+        &["path: string"],
+        "Result<null, string>",
+        "Create a directory and any missing parents.",
+    ),
+    (
+        "file.copy",
+        &["from: string", "to: string"],
+        "Result<null, string>",
+        "Copy a file.",
+    ),
+    (
+        "file.rename",
+        &["from: string", "to: string"],
+        "Result<null, string>",
+        "Rename or move a file.",
+    ),
+    (
+        "path.join",
+        &["...segments: string"],
+        "string",
+        "Join path segments with the platform separator.",
+    ),
+    (
+        "path.basename",
+        &["path: string"],
+        "string",
+        "Last component of the path.",
+    ),
+    (
+        "path.dirname",
+        &["path: string"],
+        "string",
+        "Path without its last component.",
+    ),
+    (
+        "path.extension",
+        &["path: string"],
+        "string",
+        "Extension of the last component, without the dot.",
+    ),
+    (
+        "path.relative",
+        &["from: string", "to: string"],
+        "string",
+        "Path of `to` relative to `from`.",
+    ),
+    (
+        "path.withExtension",
+        &["path: string", "ext: string"],
+        "string",
+        "Path with its extension replaced.",
+    ),
+    // Processes and environment
+    (
+        "process.exec",
+        &["command: string", "args: string[]", "options: record"],
+        "Result<ProcessOutput, string>",
+        "Run `command` with `args`; `options` may set `env`, `cwd` and `inherit`.",
+    ),
+    (
+        "process.exec",
+        &["command: string[]", "options: record"],
+        "Result<ProcessOutput, string>",
+        "Run `[program, ...args]`; `options` may set `env`, `cwd` and `inherit`.",
+    ),
+    (
+        "process.shell",
+        &["command: string", "options: object"],
+        "Result<ProcessOutput, string>",
+        "Run a command line through the system shell.",
+    ),
+    (
+        "process.shellOut",
+        &["command: string"],
+        "Result<string, string>",
+        "Run a command line through the shell and return its stdout.",
+    ),
+    (
+        "process.exit",
+        &["code: number"],
+        "never",
+        "End the program with an exit code.",
+    ),
+    (
+        "env.get",
+        &["name: string"],
+        "Option<string>",
+        "Value of an environment variable.",
+    ),
+    (
+        "env.set",
+        &["name: string", "value: string"],
+        "null",
+        "Set an environment variable.",
+    ),
+    (
+        "env.unset",
+        &["name: string"],
+        "null",
+        "Remove an environment variable.",
+    ),
+    // Compression
+    (
+        "Gzip.compress",
+        &["data: string", "level: number"],
+        "number[]",
+        "Gzip the UTF-8 bytes of a string at `level` 0–9.",
+    ),
+    (
+        "Gzip.compress",
+        &["data: number[]", "level: number"],
+        "number[]",
+        "Gzip raw bytes (values 0–255) at `level` 0–9.",
+    ),
+    (
+        "Gzip.decompress",
+        &["compressed: number[]"],
+        "number[]",
+        "Decompress gzip bytes.",
+    ),
+    (
+        "Gzip.decompressString",
+        &["compressed: number[]"],
+        "string",
+        "Decompress gzip bytes into a UTF-8 string.",
+    ),
+];
+
+/// Words that can precede `(` without making a call
+const NON_CALL_KEYWORDS: &[&str] = &["if", "while", "for", "match", "return", "catch", "fn", "in"];
+
+/// A call the cursor is inside
+#[derive(Debug, PartialEq)]
+struct CallContext {
+    /// Callee as written, e.g. `add` or `Math.pow`
+    callee: String,
+    /// Zero-based index of the argument under the cursor
+    active_parameter: u32,
+}
+
+/// A signature ready to be rendered
+struct Signature {
+    /// Text before the parameter list, including the opening parenthesis
+    head: String,
+    params: Vec<String>,
+    /// Text after the parameter list, including the closing parenthesis
+    tail: String,
+    doc: Option<String>,
+}
+
+impl Signature {
+    /// Whether the signature has a parameter at `index`
+    fn accepts(&self, index: u32) -> bool {
+        (index as usize) < self.params.len() || self.is_variadic()
+    }
+
+    fn is_variadic(&self) -> bool {
+        self.params.last().is_some_and(|p| p.starts_with("..."))
+    }
+
+    fn into_information(self, active_parameter: u32) -> SignatureInformation {
+        let active_parameter = if self.is_variadic() {
+            active_parameter.min(self.params.len() as u32 - 1)
+        } else {
+            active_parameter
+        };
+
+        // Parameter labels are offsets into the label, in UTF-16 code units
+        let mut label = self.head;
+        let mut parameters = Vec::with_capacity(self.params.len());
+        for (i, param) in self.params.iter().enumerate() {
+            if i > 0 {
+                label.push_str(", ");
+            }
+            let start = label.encode_utf16().count() as u32;
+            label.push_str(param);
+            let end = label.encode_utf16().count() as u32;
+            parameters.push(ParameterInformation {
+                label: ParameterLabel::LabelOffsets([start, end]),
+                documentation: None,
+            });
+        }
+        label.push_str(&self.tail);
+
+        SignatureInformation {
+            label,
+            documentation: self.doc.map(|value| {
+                Documentation::MarkupContent(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value,
+                })
+            }),
+            parameters: Some(parameters),
+            active_parameter: Some(active_parameter),
+        }
+    }
+}
+
+/// Generate signature help for the call at `position`
+pub fn generate_signature_help(
+    text: &str,
+    position: Position,
+    ast: Option<&Program>,
+) -> Option<SignatureHelp> {
+    let offset = position_to_offset(text, position);
+    let call = find_enclosing_call(&text[..offset])?;
+
+    let mut signatures = ast
+        .and_then(|program| declared_signature(text, program, &call.callee))
+        .map(|signature| vec![signature])
+        .unwrap_or_else(|| builtin_signatures(&call.callee));
+    if signatures.is_empty() {
+        return None;
+    }
+
+    let active_signature = signatures
+        .iter()
+        .position(|s| s.accepts(call.active_parameter))
+        .unwrap_or(0) as u32;
+
+    Some(SignatureHelp {
+        signatures: signatures
+            .drain(..)
+            .map(|s| s.into_information(call.active_parameter))
+            .collect(),
+        active_signature: Some(active_signature),
+        active_parameter: Some(call.active_parameter),
+    })
+}
+
+/// Find the innermost call whose argument list `prefix` ends inside.
+///
+/// Brackets inside strings and comments are ignored. Grouping parentheses
+/// and array literals are looked through, so `f([1, (2` is still a call to
+/// `f`; a `{` stops the search, since a block inside an argument is not
+/// part of the call's argument list.
+fn find_enclosing_call(prefix: &str) -> Option<CallContext> {
+    struct Frame {
+        open: char,
+        at: usize,
+        commas: u32,
+    }
+
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut chars = prefix.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' | '`' => {
+                while let Some((_, next)) = chars.next() {
+                    if next == '\\' {
+                        chars.next();
+                    } else if next == c {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek().is_some_and(|&(_, next)| next == '/') => {
+                for (_, next) in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+            }
+            '(' | '[' | '{' => stack.push(Frame {
+                open: c,
+                at: i,
+                commas: 0,
+            }),
+            ')' | ']' | '}' => {
+                stack.pop();
+            }
+            ',' => {
+                if let Some(frame) = stack.last_mut() {
+                    frame.commas += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for frame in stack.iter().rev() {
+        match frame.open {
+            '(' => {
+                if let Some(callee) = callee_before(&prefix[..frame.at]) {
+                    return Some(CallContext {
+                        callee,
+                        active_parameter: frame.commas,
+                    });
+                }
+            }
+            '[' => {}
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// The callee written right before an opening parenthesis, if the
+/// parenthesis starts a call rather than a group or a declaration
+fn callee_before(before: &str) -> Option<String> {
+    let before = before.trim_end();
+    let start = before
+        .char_indices()
+        .rev()
+        .take_while(|&(_, c)| c.is_alphanumeric() || c == '_' || c == '.')
+        .last()
+        .map(|(i, _)| i)?;
+    let callee = &before[start..];
+
+    if callee.starts_with(|c: char| c == '.' || c.is_ascii_digit())
+        || callee.ends_with('.')
+        || NON_CALL_KEYWORDS.contains(&callee)
+    {
+        return None;
+    }
+
+    // `fn name(` declares a function
+    let previous_word = before[..start]
+        .trim_end()
+        .rsplit(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .next();
+    if previous_word == Some("fn") {
+        return None;
+    }
+
+    Some(callee.to_string())
+}
+
+/// Signature of a function or extern declared in the document
+fn declared_signature(text: &str, program: &Program, callee: &str) -> Option<Signature> {
+    if callee.contains('.') {
+        return None;
+    }
+
+    let mut functions = Vec::new();
+    for item in &program.items {
+        match item {
+            Item::Function(func)
+            | Item::Statement(Stmt::FunctionDecl(func))
+            | Item::Export(ExportDecl {
+                item: ExportItem::Function(func),
+                ..
+            }) => collect_functions(func, &mut functions),
+            Item::Extern(decl) if decl.name == callee => return Some(extern_signature(decl)),
+            _ => {}
+        }
+    }
+
+    // Top-level declarations come first, so they win over nested ones
+    functions.sort_by_key(|(depth, _)| *depth);
+    functions
+        .into_iter()
+        .find(|(_, func)| func.name.name == callee)
+        .map(|(_, func)| function_signature(text, func))
+}
+
+/// Collect `func` and the functions nested in it, with their nesting depth
+fn collect_functions<'a>(func: &'a FunctionDecl, out: &mut Vec<(usize, &'a FunctionDecl)>) {
+    fn walk<'a>(func: &'a FunctionDecl, depth: usize, out: &mut Vec<(usize, &'a FunctionDecl)>) {
+        out.push((depth, func));
+        for stmt in &func.body.statements {
+            if let Stmt::FunctionDecl(nested) = stmt {
+                walk(nested, depth + 1, out);
+            }
+        }
+    }
+    walk(func, 0, out);
+}
+
+fn function_signature(text: &str, func: &FunctionDecl) -> Signature {
+    let type_params = if func.type_params.is_empty() {
+        String::new()
+    } else {
+        let names: Vec<&str> = func.type_params.iter().map(|p| p.name.as_str()).collect();
+        format!("<{}>", names.join(", "))
+    };
+
+    let params = func
+        .params
+        .iter()
+        .map(|p| {
+            let ownership = match (&p.ownership, p.ownership_explicit) {
+                (Some(OwnershipAnnotation::Own), true) => "own ",
+                (Some(OwnershipAnnotation::Borrow), true) => "borrow ",
+                (Some(OwnershipAnnotation::Share), true) => "share ",
+                _ => "",
+            };
+            if matches!(p.type_ref, TypeRef::SelfType(_)) {
+                format!("{}{}", ownership, p.name.name)
+            } else {
+                format!(
+                    "{}{}: {}",
+                    ownership,
+                    p.name.name,
+                    format_type_ref(&p.type_ref)
+                )
+            }
+        })
+        .collect();
+
+    let tail = match &func.return_type {
+        Some(return_type) => format!("): {}", format_type_ref(return_type)),
+        None => ")".to_string(),
+    };
+
+    Signature {
+        head: format!("fn {}{}(", func.name.name, type_params),
+        params,
+        tail,
+        doc: extract_doc_comment(text, func.name.span.start),
+    }
+}
+
+fn extern_signature(decl: &ExternDecl) -> Signature {
+    Signature {
+        head: format!("extern fn {}(", decl.name),
+        params: decl
+            .params
+            .iter()
+            .map(|(name, ty)| format!("{}: {:?}", name, ty))
+            .collect(),
+        tail: format!("): {:?}", decl.return_type),
+        doc: Some(format!("Foreign function from `{}`", decl.library)),
+    }
+}
+
+/// Builtin signatures matching `callee`, one per accepted form
+fn builtin_signatures(callee: &str) -> Vec<Signature> {
+    BUILTIN_SIGNATURES
+        .iter()
+        .filter(|(name, ..)| same_builtin(name, callee))
+        .map(|(name, params, returns, doc)| Signature {
+            head: format!("{}(", name),
+            params: params.iter().map(|p| p.to_string()).collect(),
+            tail: format!("): {}", returns),
+            doc: Some(doc.to_string()),
+        })
+        .collect()
+}
+
+fn same_builtin(name: &str, callee: &str) -> bool {
+    match (name.split_once('.'), callee.split_once('.')) {
+        (Some((namespace, method)), Some((callee_namespace, callee_method))) => {
+            namespace.eq_ignore_ascii_case(callee_namespace) && method == callee_method
+        }
+        (None, None) => name == callee,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(prefix: &str) -> Option<(String, u32)> {
+        find_enclosing_call(prefix).map(|c| (c.callee, c.active_parameter))
+    }
+
+    #[test]
+    fn test_find_enclosing_call() {
+        assert_eq!(call("add(1, "), Some(("add".to_string(), 1)));
+        assert_eq!(call("Math.pow("), Some(("Math.pow".to_string(), 0)));
+        assert_eq!(call("f(g(1, 2), "), Some(("f".to_string(), 1)));
+        assert_eq!(call("f([1, 2, "), Some(("f".to_string(), 0)));
+        assert_eq!(call("f((1 + "), Some(("f".to_string(), 0)));
+        assert_eq!(call("f(\"a, (b\", "), Some(("f".to_string(), 1)));
+    }
+
+    #[test]
+    fn test_find_enclosing_call_outside_calls() {
+        assert_eq!(call("add(1, 2);"), None);
+        assert_eq!(call("fn add("), None);
+        assert_eq!(call("if ("), None);
+        assert_eq!(call("f(fn(x: number): number { g"), None);
+        assert_eq!(call("(1 + "), None);
+    }
+}
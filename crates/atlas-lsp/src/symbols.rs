@@ -2,22 +2,26 @@
 //!
 //! Provides symbol extraction for:
 //! - Document outline (textDocument/documentSymbol)
-//! - Workspace symbol search (workspace/symbol)
+//! - Workspace symbol search (workspace/symbol), over open documents and
+//!   the Atlas files on disk under the workspace roots
 //! - Query caching and performance optimization
 //! - Memory-bounded indexing for large workspaces
 
 use atlas_runtime::ast::*;
 use atlas_runtime::span::Span;
+use atlas_runtime::{Lexer, Parser};
 use lru::LruCache;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 use tower_lsp::lsp_types::{
     DocumentSymbol, Location, Position, Range, SymbolInformation, SymbolKind, Url,
 };
 
 /// Symbol with location info for workspace indexing
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexedSymbol {
     pub name: String,
     pub kind: SymbolKind,
@@ -87,7 +91,11 @@ impl WorkspaceIndex {
     /// Index symbols from a document
     pub fn index_document(&mut self, uri: Url, text: &str, ast: &Program) {
         let symbols = extract_indexed_symbols(&uri, text, ast);
+        self.insert_symbols(uri, symbols);
+    }
 
+    /// Replace the symbols of one file, e.g. ones extracted by [`index_file`]
+    pub fn insert_symbols(&mut self, uri: Url, symbols: Vec<IndexedSymbol>) {
         // Check memory bounds
         let total_symbols: usize = self.symbols.values().map(|v| v.len()).sum();
         if total_symbols + symbols.len() > self.config.max_symbols {
@@ -162,28 +170,17 @@ impl WorkspaceIndex {
                         tags: None,
                         deprecated: None,
                     });
-
-                    if results.len() >= limit {
-                        break;
-                    }
                 }
             }
-
-            if results.len() >= limit {
-                break;
-            }
         }
 
-        // Sort by relevance (exact prefix matches first)
+        // Sort by relevance: exact, prefix, substring, then subsequence matches
         results.sort_by(|a, b| {
-            let a_starts = a.name.to_lowercase().starts_with(&query_lower);
-            let b_starts = b.name.to_lowercase().starts_with(&query_lower);
-            match (a_starts, b_starts) {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => a.name.cmp(&b.name),
-            }
+            match_rank(&a.name, &query_lower)
+                .cmp(&match_rank(&b.name, &query_lower))
+                .then_with(|| a.name.cmp(&b.name))
         });
+        results.truncate(limit);
 
         // Cache the results
         if let Ok(mut cache) = self.query_cache.write() {
@@ -551,6 +548,22 @@ fn extract_statement_symbols(
 
             extract_block_symbols(uri, text, &for_in.body, container, symbols);
         }
+        Stmt::TryCatch(try_catch) => {
+            extract_block_symbols(uri, text, &try_catch.body, container, symbols);
+            if let Some(binding) = &try_catch.error_binding {
+                let range = span_to_range(text, binding.span);
+                symbols.push(IndexedSymbol {
+                    name: binding.name.clone(),
+                    kind: SymbolKind::VARIABLE,
+                    location: Location {
+                        uri: uri.clone(),
+                        range,
+                    },
+                    container_name: container.map(String::from),
+                });
+            }
+            extract_block_symbols(uri, text, &try_catch.catch_block, container, symbols);
+        }
         // Other statements don't introduce new symbols
         _ => {}
     }
@@ -908,6 +921,10 @@ fn extract_block_children(text: &str, block: &Block, children: &mut Vec<Document
             Stmt::ForIn(for_in) => {
                 extract_block_children(text, &for_in.body, children);
             }
+            Stmt::TryCatch(try_catch) => {
+                extract_block_children(text, &try_catch.body, children);
+                extract_block_children(text, &try_catch.catch_block, children);
+            }
             _ => {}
         }
     }
@@ -971,6 +988,78 @@ pub fn position_to_offset(text: &str, position: Position) -> usize {
     offset
 }
 
+/// Directories the workspace indexer never descends into
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules", "build", "dist"];
+
+/// Every Atlas source file (`.atl`, `.atlas`) under `root`.
+///
+/// Hidden directories (`.git`, `.atlas`, ...) and build output are skipped.
+pub fn find_workspace_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref()) {
+                    pending.push(path);
+                }
+            } else if is_atlas_source(&path) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Whether `path` names an Atlas source file
+pub fn is_atlas_source(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("atl" | "atlas")
+    )
+}
+
+/// Read and parse a file from disk and extract its workspace symbols.
+///
+/// Parse errors are tolerated: the declarations that did parse are indexed.
+pub fn index_file(path: &Path) -> Option<(Url, Vec<IndexedSymbol>)> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let uri = Url::from_file_path(path).ok()?;
+    let symbols = index_source(&uri, &text);
+    Some((uri, symbols))
+}
+
+/// Parse `text` and extract its workspace symbols, located in `uri`
+pub fn index_source(uri: &Url, text: &str) -> Vec<IndexedSymbol> {
+    let (tokens, _) = Lexer::new(text).tokenize();
+    let (program, _) = Parser::new(tokens).parse();
+    extract_indexed_symbols(uri, text, &program)
+}
+
+/// How well `name` matches a lowercase query; lower is better
+fn match_rank(name: &str, query: &str) -> u8 {
+    let name_lower = name.to_lowercase();
+    if name_lower == query {
+        0
+    } else if name_lower.starts_with(query) {
+        1
+    } else if name_lower.contains(query) {
+        2
+    } else {
+        3
+    }
+}
+
 /// Fuzzy match for workspace symbol search
 fn fuzzy_match(name: &str, query: &str) -> bool {
     if query.is_empty() {
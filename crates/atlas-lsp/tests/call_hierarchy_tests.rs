@@ -942,3 +942,312 @@ fn deepRecursion(n: number): number {
     let calls = outgoing.unwrap();
     assert!(calls.iter().any(|c| c.to.name == "deepRecursion"));
 }
+
+// ============================================================================
+// Cross-Module Resolution Tests
+// ============================================================================
+
+async fn open(server: &AtlasLspServer, uri: &Url, text: &str) {
+    server
+        .did_open(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: uri.clone(),
+                language_id: "atlas".to_string(),
+                version: 1,
+                text: text.to_string(),
+            },
+        })
+        .await;
+}
+
+async fn prepare(
+    server: &AtlasLspServer,
+    uri: &Url,
+    line: u32,
+    character: u32,
+) -> Vec<CallHierarchyItem> {
+    server
+        .prepare_call_hierarchy(CallHierarchyPrepareParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position: Position { line, character },
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        })
+        .await
+        .unwrap()
+        .unwrap_or_default()
+}
+
+async fn incoming(
+    server: &AtlasLspServer,
+    item: &CallHierarchyItem,
+) -> Vec<CallHierarchyIncomingCall> {
+    server
+        .incoming_calls(CallHierarchyIncomingCallsParams {
+            item: item.clone(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        })
+        .await
+        .unwrap()
+        .unwrap_or_default()
+}
+
+async fn outgoing(
+    server: &AtlasLspServer,
+    item: &CallHierarchyItem,
+) -> Vec<CallHierarchyOutgoingCall> {
+    server
+        .outgoing_calls(CallHierarchyOutgoingCallsParams {
+            item: item.clone(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        })
+        .await
+        .unwrap()
+        .unwrap_or_default()
+}
+
+const MATH: &str = "export fn add(a: number, b: number): number {\n    return a + b;\n}\n";
+
+#[tokio::test]
+async fn test_calls_resolve_through_named_imports() {
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+
+    let math = test_uri("math");
+    let main = test_uri("main");
+    open(server, &math, MATH).await;
+    open(
+        server,
+        &main,
+        "import { add } from \"./math\";\n\nfn run(): number {\n    return add(1, 2);\n}\n",
+    )
+    .await;
+
+    let add = prepare(server, &math, 0, 10).await;
+    assert_eq!(add.len(), 1);
+    assert_eq!(
+        add[0].detail.as_deref(),
+        Some("fn add(a: number, b: number): number")
+    );
+
+    let callers = incoming(server, &add[0]).await;
+    assert_eq!(callers.len(), 1);
+    assert_eq!(callers[0].from.name, "run");
+    assert_eq!(callers[0].from.uri, main);
+    assert_eq!(callers[0].from_ranges[0].start, Position::new(3, 11));
+
+    let run = prepare(server, &main, 2, 4).await;
+    let callees = outgoing(server, &run[0]).await;
+    assert_eq!(callees.len(), 1);
+    assert_eq!(callees[0].to.name, "add");
+    assert_eq!(callees[0].to.uri, math);
+}
+
+#[tokio::test]
+async fn test_prepare_on_call_site_lands_on_imported_declaration() {
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+
+    let math = test_uri("math");
+    let main = test_uri("main");
+    open(server, &math, MATH).await;
+    open(
+        server,
+        &main,
+        "import { add } from \"./math\";\nlet total = add(1, 2);\n",
+    )
+    .await;
+
+    let items = prepare(server, &main, 1, 13).await;
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].uri, math);
+    assert_eq!(items[0].selection_range.start, Position::new(0, 10));
+}
+
+#[tokio::test]
+async fn test_same_named_functions_in_other_modules_stay_apart() {
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+
+    let a = test_uri("a");
+    let b = test_uri("b");
+    open(
+        server,
+        &a,
+        "fn helper(): number { return 1; }\nfn useA(): number { return helper(); }\n",
+    )
+    .await;
+    open(
+        server,
+        &b,
+        "fn helper(): number { return 2; }\nfn useB(): number { return helper(); }\n",
+    )
+    .await;
+
+    let helper = prepare(server, &a, 0, 3).await;
+    let callers = incoming(server, &helper[0]).await;
+    let names: Vec<&str> = callers.iter().map(|c| c.from.name.as_str()).collect();
+    assert_eq!(names, vec!["useA"]);
+}
+
+#[tokio::test]
+async fn test_namespace_import_calls() {
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+
+    let math = test_uri("math");
+    let main = test_uri("main");
+    open(server, &math, MATH).await;
+    open(
+        server,
+        &main,
+        "import * as m from \"./math\";\nfn run(): number { return m.add(1, 2); }\n",
+    )
+    .await;
+
+    let add = prepare(server, &math, 0, 10).await;
+    let callers = incoming(server, &add[0]).await;
+    assert_eq!(callers.len(), 1);
+    assert_eq!(callers[0].from.name, "run");
+}
+
+#[tokio::test]
+async fn test_top_level_calls_belong_to_the_module() {
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+
+    let uri = test_uri("script");
+    open(
+        server,
+        &uri,
+        "fn setup(): number { return 1; }\nlet ready = setup();\nconsole.log(setup());\n",
+    )
+    .await;
+
+    let setup = prepare(server, &uri, 0, 3).await;
+    let callers = incoming(server, &setup[0]).await;
+    assert_eq!(callers.len(), 1);
+    assert_eq!(callers[0].from.kind, SymbolKind::FILE);
+    assert_eq!(callers[0].from.name, "script.atl");
+    assert_eq!(callers[0].from_ranges.len(), 2);
+
+    // The module item lists the calls its top-level statements make
+    let callees = outgoing(server, &callers[0].from).await;
+    assert_eq!(callees.len(), 1);
+    assert_eq!(callees[0].to.name, "setup");
+}
+
+#[tokio::test]
+async fn test_calls_inside_nested_expressions() {
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+
+    let uri = test_uri("test");
+    let source = r#"fn a(): number { return 1; }
+fn b(): number { return 2; }
+fn c(): number { return 3; }
+fn d(): number { return 4; }
+fn driver(flag: boolean): string {
+    let f = fn(x: number): number { return x + a(); };
+    let n = match flag { true => b(), false => 0 };
+    defer { d(); }
+    return `value ${c() + f(n)}`;
+}
+"#;
+    open(server, &uri, source).await;
+
+    let driver = prepare(server, &uri, 4, 4).await;
+    let callees = outgoing(server, &driver[0]).await;
+    let mut names: Vec<&str> = callees.iter().map(|c| c.to.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, vec!["a", "b", "c", "d"]);
+}
+
+#[tokio::test]
+async fn test_nested_functions_are_their_own_callers() {
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+
+    let uri = test_uri("test");
+    let source = r#"fn leaf(): number { return 1; }
+fn outer(): number {
+    fn inner(): number { return leaf(); }
+    return inner();
+}
+"#;
+    open(server, &uri, source).await;
+
+    let leaf = prepare(server, &uri, 0, 3).await;
+    let callers = incoming(server, &leaf[0]).await;
+    assert_eq!(callers.len(), 1);
+    assert_eq!(callers[0].from.name, "inner");
+
+    let outer = prepare(server, &uri, 1, 3).await;
+    let callees = outgoing(server, &outer[0]).await;
+    let names: Vec<&str> = callees.iter().map(|c| c.to.name.as_str()).collect();
+    assert_eq!(names, vec!["inner"]);
+}
+
+#[tokio::test]
+async fn test_method_calls() {
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+
+    let uri = test_uri("test");
+    let source = r#"struct Counter { count: number }
+impl Counter {
+    static fn zero(): Counter { return Counter { count: 0 }; }
+    fn next(self: Counter): number { return self.count + 1; }
+}
+fn main(): number {
+    let c = Counter.zero();
+    return c.next();
+}
+"#;
+    open(server, &uri, source).await;
+
+    let main = prepare(server, &uri, 5, 3).await;
+    let callees = outgoing(server, &main[0]).await;
+    let names: Vec<&str> = callees.iter().map(|c| c.to.name.as_str()).collect();
+    assert_eq!(names, vec!["Counter.zero", "Counter.next"]);
+    assert!(callees.iter().all(|c| c.to.kind == SymbolKind::METHOD));
+
+    let next = prepare(server, &uri, 3, 7).await;
+    let callers = incoming(server, &next[0]).await;
+    assert_eq!(callers.len(), 1);
+    assert_eq!(callers[0].from.name, "main");
+}
+
+#[tokio::test]
+async fn test_callers_in_unopened_workspace_files() {
+    let project = tempfile::TempDir::new().unwrap();
+    let lib = project.path().join("lib.atl");
+    std::fs::write(&lib, MATH).unwrap();
+    std::fs::write(
+        project.path().join("app.atl"),
+        "import { add } from \"./lib\";\nfn total(): number { return add(2, 3); }\n",
+    )
+    .unwrap();
+
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+    server
+        .initialize(InitializeParams {
+            root_uri: Some(Url::from_file_path(project.path()).unwrap()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let lib_uri = Url::from_file_path(&lib).unwrap();
+    open(server, &lib_uri, MATH).await;
+
+    let add = prepare(server, &lib_uri, 0, 10).await;
+    let callers = incoming(server, &add[0]).await;
+    assert_eq!(callers.len(), 1);
+    assert_eq!(callers[0].from.name, "total");
+    assert!(callers[0].from.uri.path().ends_with("/app.atl"));
+}
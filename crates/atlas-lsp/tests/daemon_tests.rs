@@ -0,0 +1,175 @@
+//! Daemon Tests
+//!
+//! Tests for the workspace daemon shared by the CLI and the language server:
+//! compiling through its module cache, reporting diagnostics, declining
+//! programs it cannot precompile, serving the symbol index, and shutdown.
+
+#![cfg(unix)]
+
+use atlas_lsp::daemon::{CompileResult, Daemon, DaemonClient};
+use atlas_runtime::{Atlas, SecurityContext};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_daemon_round_trip() {
+    let root = TempDir::new().unwrap();
+    let sockets = TempDir::new().unwrap();
+    let socket = sockets.path().join("daemon.sock");
+    fs::write(
+        root.path().join("lib.atl"),
+        "export fn twice(x: number): number { return x * 2; }",
+    )
+    .unwrap();
+    let main = root.path().join("main.atl");
+    fs::write(
+        &main,
+        "import { twice } from \"./lib\";\nfn main(): number { return twice(21); }",
+    )
+    .unwrap();
+
+    let daemon = Daemon::bind(root.path(), &socket).unwrap();
+    assert!(
+        Daemon::bind(root.path(), &socket).is_err(),
+        "a second daemon must not take over a live socket"
+    );
+    let server = std::thread::spawn(move || daemon.serve());
+    let mut client = DaemonClient::connect(&socket).unwrap();
+
+    // Compiled programs run like locally compiled ones
+    let program = match client.compile(&main).unwrap() {
+        CompileResult::Compiled(program) => program,
+        other => panic!("expected bytecode, got {:?}", other),
+    };
+    assert!(program.has_main);
+    let outcome = Atlas::new_with_security(SecurityContext::allow_all())
+        .run_compiled(program)
+        .unwrap();
+    assert!(matches!(
+        outcome,
+        atlas_runtime::RunOutcome::Exited { code: 42, .. }
+    ));
+    assert!(matches!(
+        client.compile(&main).unwrap(),
+        CompileResult::Compiled(_)
+    ));
+    let status = client.status().unwrap();
+    assert_eq!(status.modules, 2);
+    assert_eq!((status.module_hits, status.module_misses), (2, 2));
+
+    // Errors come back as diagnostics
+    let bad = root.path().join("bad.atl");
+    fs::write(&bad, "let x: number = \"s\";").unwrap();
+    match client.compile(&bad).unwrap() {
+        CompileResult::Diagnostics(diagnostics) => {
+            assert!(diagnostics.iter().any(|d| d.code == "AT3001"));
+        }
+        other => panic!("expected diagnostics, got {:?}", other),
+    }
+
+    // Extern functions and files outside the root are left to the client
+    let native = root.path().join("native.atl");
+    fs::write(&native, "extern \"m\" fn sqrt(x: CDouble): CDouble;\n1;").unwrap();
+    assert!(matches!(
+        client.compile(&native).unwrap(),
+        CompileResult::Unsupported(_)
+    ));
+    let outside = TempDir::new().unwrap();
+    let stray = outside.path().join("stray.atl");
+    fs::write(&stray, "1;").unwrap();
+    assert!(matches!(
+        client.compile(&stray).unwrap(),
+        CompileResult::Unsupported(_)
+    ));
+    assert!(client.compile(&root.path().join("missing.atl")).is_err());
+
+    // The index follows file changes
+    let files = client.index().unwrap();
+    assert_eq!(files.len(), 4);
+    assert!(files
+        .iter()
+        .flat_map(|(_, symbols)| symbols)
+        .any(|symbol| symbol.name == "twice"));
+    fs::write(
+        root.path().join("lib.atl"),
+        "export fn thrice(x: number): number { return x * 3; }",
+    )
+    .unwrap();
+    let names: Vec<String> = client
+        .index()
+        .unwrap()
+        .into_iter()
+        .filter(|(uri, _)| uri.path().ends_with("/lib.atl"))
+        .flat_map(|(_, symbols)| symbols)
+        .map(|symbol| symbol.name)
+        .collect();
+    assert!(names.contains(&"thrice".to_string()));
+    assert!(!names.contains(&"twice".to_string()));
+    assert_eq!(client.status().unwrap().indexed_files, 4);
+
+    client.shutdown().unwrap();
+    server.join().unwrap().unwrap();
+    assert!(!socket.exists());
+    assert!(DaemonClient::connect(&socket).is_err());
+}
+
+#[test]
+fn test_daemon_replaces_stale_socket() {
+    let root = TempDir::new().unwrap();
+    let sockets = TempDir::new().unwrap();
+    let socket = sockets.path().join("daemon.sock");
+
+    // A socket file nobody listens on, as left by a daemon that was killed
+    drop(std::os::unix::net::UnixListener::bind(&socket).unwrap());
+    assert!(socket.exists());
+
+    let daemon = Daemon::bind(root.path(), &socket).unwrap();
+    assert_eq!(daemon.root(), root.path().canonicalize().unwrap());
+    let server = std::thread::spawn(move || daemon.serve());
+    let mut client = DaemonClient::connect(&socket).unwrap();
+    assert_eq!(client.status().unwrap().pid, std::process::id());
+    client.shutdown().unwrap();
+    server.join().unwrap().unwrap();
+}
+
+#[test]
+fn test_daemon_stops_when_idle() {
+    let root = TempDir::new().unwrap();
+    let sockets = TempDir::new().unwrap();
+    let socket = sockets.path().join("daemon.sock");
+
+    let daemon = Daemon::bind(root.path(), &socket)
+        .unwrap()
+        .with_idle_timeout(std::time::Duration::from_millis(200));
+    daemon.serve().unwrap();
+    assert!(!socket.exists());
+}
+
+#[test]
+fn test_daemon_tightens_socket_dir_mode() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let root = TempDir::new().unwrap();
+    let sockets = TempDir::new().unwrap();
+    let dir = sockets.path().join("daemon");
+    fs::create_dir(&dir).unwrap();
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let daemon = Daemon::bind(root.path(), &dir.join("daemon.sock")).unwrap();
+    let mode = fs::metadata(&dir).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o700);
+    drop(daemon);
+}
+
+#[test]
+fn test_daemon_refuses_symlinked_socket_dir() {
+    let root = TempDir::new().unwrap();
+    let sockets = TempDir::new().unwrap();
+    let dir = sockets.path().join("daemon");
+    std::os::unix::fs::symlink(sockets.path(), &dir).unwrap();
+
+    let err = Daemon::bind(root.path(), &dir.join("daemon.sock"))
+        .err()
+        .expect("a symlinked socket directory must be refused");
+    assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+}
@@ -270,3 +270,97 @@ async fn test_rapid_document_changes() {
 
     // Server should handle all changes without crashing
 }
+
+#[test]
+fn test_use_after_own_diagnostic_has_move_site_related_information() {
+    let source = r#"
+fn consume(own data: string): void {}
+fn main(): void {
+    let value: string = "hello";
+    consume(value);
+    let again: string = value;
+}
+"#;
+    let mut lexer = atlas_runtime::Lexer::new(source);
+    let (tokens, _) = lexer.tokenize();
+    let mut parser = atlas_runtime::Parser::new(tokens);
+    let (ast, _) = parser.parse();
+    let mut binder = atlas_runtime::Binder::new();
+    let (mut symbols, _) = binder.bind(&ast);
+    let mut typechecker = atlas_runtime::TypeChecker::new(&mut symbols);
+    let diags = typechecker.check(&ast);
+
+    let at3053 = diags
+        .iter()
+        .find(|d| d.code == "AT3053")
+        .expect("expected AT3053 use-after-own");
+    let lsp_diag = atlas_lsp::convert::diagnostic_to_lsp(at3053);
+
+    let related = lsp_diag
+        .related_information
+        .expect("use-after-own should carry the move site");
+    assert_eq!(related.len(), 1);
+    assert!(
+        related[0].message.contains("moved here"),
+        "got: {}",
+        related[0].message
+    );
+    assert_ne!(
+        related[0].location.range.start, lsp_diag.range.start,
+        "move site and use site should be distinct spans"
+    );
+}
+
+#[test]
+fn test_imports_resolve_through_module_graph() {
+    use atlas_lsp::document::DocumentState;
+    use atlas_lsp::modules::ModuleGraph;
+    use std::sync::{Arc, Mutex};
+
+    let project = tempfile::TempDir::new().unwrap();
+    let math = project.path().join("math.atl");
+    std::fs::write(
+        &math,
+        "export fn add(a: number, b: number): number { return a + b; }",
+    )
+    .unwrap();
+    let main = project.path().join("main.atl");
+    let uri = Url::from_file_path(&main).unwrap();
+    let graph = Arc::new(Mutex::new(ModuleGraph::new()));
+
+    let mut doc = DocumentState::with_module_graph(
+        uri.clone(),
+        "import { add } from \"./math\";\nlet total: number = add(1, 2);\n".to_string(),
+        1,
+        Arc::clone(&graph),
+    );
+    assert!(doc.diagnostics.is_empty(), "got: {:?}", doc.diagnostics);
+
+    // Deleting the imported file outside the editor affects its importer
+    std::fs::remove_file(&math).unwrap();
+    let main_path = ModuleGraph::module_path(&uri).unwrap();
+    let math_path = ModuleGraph::module_path(&Url::from_file_path(&math).unwrap()).unwrap();
+    let affected = graph.lock().unwrap().file_changed(&math_path);
+    assert!(affected.contains(&main_path));
+    doc.mark_stale();
+    doc.analyze_if_stale();
+    assert!(
+        doc.diagnostics
+            .iter()
+            .any(|d| d.message.contains("Cannot find module")),
+        "got: {:?}",
+        doc.diagnostics
+    );
+
+    // A created file may satisfy an import that did not resolve
+    std::fs::write(
+        &math,
+        "export fn add(a: number, b: number): number { return a + b; }",
+    )
+    .unwrap();
+    let affected = graph.lock().unwrap().file_changed(&math_path);
+    assert!(affected.contains(&main_path));
+    doc.mark_stale();
+    doc.analyze_if_stale();
+    assert!(doc.diagnostics.is_empty(), "got: {:?}", doc.diagnostics);
+}
@@ -147,3 +147,299 @@ async fn test_multiple_documents() {
     // All documents should be independently tracked
     // (If we had a way to query document state, we would verify here)
 }
+
+#[test]
+fn test_ranged_change_reuses_untouched_items() {
+    use atlas_lsp::document::DocumentState;
+
+    let uri = Url::parse("file:///incremental.atl").unwrap();
+    let source = "fn a(): number {\n    return 1;\n}\nfn b(): number {\n    return 2;\n}\n\
+                  fn c(): number {\n    return 3;\n}\nfn d(): number {\n    return 4;\n}\n\
+                  fn e(): number {\n    return 5;\n}\n";
+    let mut doc = DocumentState::new(uri.clone(), source.to_string(), 1);
+
+    // `return 3;` -> `return 30 + 3;` inside `c`
+    doc.apply_changes(
+        vec![TextDocumentContentChangeEvent {
+            range: Some(Range {
+                start: Position::new(7, 11),
+                end: Position::new(7, 12),
+            }),
+            range_length: None,
+            text: "30 + 3".to_string(),
+        }],
+        2,
+    );
+
+    // `a` and `e` are kept; `c` and its neighbors are re-parsed
+    assert_eq!(doc.reused_items, 2);
+    assert!(doc.text.contains("return 30 + 3;"));
+    assert!(
+        doc.diagnostics.iter().all(|d| !d.is_error()),
+        "{:?}",
+        doc.diagnostics
+    );
+
+    // The spliced tree, shifted spans included, matches a fresh parse
+    let fresh = DocumentState::new(uri, doc.text.clone(), 3);
+    assert_eq!(doc.ast, fresh.ast);
+}
+
+#[test]
+fn test_ranged_change_with_unbalanced_brace_reparses_everything() {
+    use atlas_lsp::document::DocumentState;
+
+    let uri = Url::parse("file:///incremental_brace.atl").unwrap();
+    let source = "fn a(): number {\n    return 1;\n}\nfn b(): number {\n    return 2;\n}\n\
+                  fn c(): number {\n    return 3;\n}\nfn d(): number {\n    return 4;\n}\n";
+    let mut doc = DocumentState::new(uri, source.to_string(), 1);
+
+    // Delete the closing brace of `a`
+    doc.apply_changes(
+        vec![TextDocumentContentChangeEvent {
+            range: Some(Range {
+                start: Position::new(2, 0),
+                end: Position::new(2, 1),
+            }),
+            range_length: None,
+            text: String::new(),
+        }],
+        2,
+    );
+
+    assert_eq!(doc.reused_items, 0);
+    assert!(!doc.diagnostics.is_empty());
+}
+
+#[test]
+fn test_unterminated_string_keeps_the_rest_of_the_document() {
+    use atlas_lsp::document::DocumentState;
+
+    let uri = Url::parse("file:///unterminated.atl").unwrap();
+    let source = "fn a(): number {\n    return 1;\n}\nlet s = \"oops\n\
+                  fn b(): number {\n    return 2;\n}\n";
+    let doc = DocumentState::new(uri, source.to_string(), 1);
+
+    // One diagnostic for the missing quote, not one per line that follows
+    assert_eq!(doc.diagnostics.len(), 1, "{:?}", doc.diagnostics);
+    assert_eq!(doc.diagnostics[0].code, "AT1002");
+    assert_eq!(doc.diagnostics[0].line, 4);
+
+    // The `let` missing its `;` is dropped, but `b` after it still parses
+    let ast = doc.ast.as_ref().expect("partial AST is kept");
+    let functions: Vec<&str> = ast
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            atlas_runtime::ast::Item::Function(f) => Some(f.name.name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(functions, ["a", "b"]);
+}
+
+#[test]
+fn test_unclosed_function_body_is_kept() {
+    use atlas_lsp::document::DocumentState;
+
+    let uri = Url::parse("file:///unclosed.atl").unwrap();
+    let source = "fn a(): number {\n    return 1;\n}\nfn b(): number {\n    let x = 2;\n";
+    let doc = DocumentState::new(uri, source.to_string(), 1);
+
+    assert_eq!(doc.diagnostics.len(), 1, "{:?}", doc.diagnostics);
+    assert_eq!(doc.diagnostics[0].code, "AT1021");
+    assert_eq!(doc.diagnostics[0].line, 4);
+    assert_eq!(doc.ast.as_ref().map(|ast| ast.items.len()), Some(2));
+}
+
+#[test]
+fn test_text_changes_defer_analysis() {
+    use atlas_lsp::document::DocumentState;
+
+    let uri = Url::parse("file:///deferred.atl").unwrap();
+    let mut doc = DocumentState::new(uri, "let x: number = 1;".to_string(), 1);
+    let change = |text: &str| {
+        vec![TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: text.to_string(),
+        }]
+    };
+
+    // The AST follows the edit at once; diagnostics wait for analysis
+    doc.apply_text_changes(change("let x: number = \"one\";"), 2);
+    assert!(doc.is_stale());
+    assert!(doc.ast.is_some());
+    assert!(doc.diagnostics.is_empty());
+
+    let input = doc.analysis_input().expect("stale document has input");
+    let analysis = input.run();
+    assert!(doc.store_analysis(analysis));
+    assert!(!doc.is_stale());
+    assert!(doc.diagnostics.iter().any(|d| d.is_error()));
+    assert!(doc.analysis_input().is_none());
+
+    // An analysis overtaken by a later edit is dropped
+    doc.apply_text_changes(change("let x: number = 2;"), 3);
+    let superseded = doc.analysis_input().unwrap().run();
+    doc.apply_text_changes(change("let x: number = 3;"), 4);
+    assert!(!doc.store_analysis(superseded));
+    assert!(doc.is_stale());
+
+    doc.analyze_if_stale();
+    assert!(!doc.is_stale());
+    assert!(doc.diagnostics.is_empty());
+}
+
+#[tokio::test]
+async fn test_request_after_change_sees_pending_edit() {
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+
+    let uri = Url::parse("file:///pending.atl").unwrap();
+    server
+        .did_open(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: uri.clone(),
+                language_id: "atlas".to_string(),
+                version: 1,
+                text: "let x: number = 42;".to_string(),
+            },
+        })
+        .await;
+
+    // Several edits in a row, with no time for the debounced analysis to run
+    for (version, text) in [
+        (2, "let y"),
+        (3, "let y = \"a\""),
+        (4, "let y = \"a\" + \"b\";"),
+    ] {
+        server
+            .did_change(DidChangeTextDocumentParams {
+                text_document: VersionedTextDocumentIdentifier {
+                    uri: uri.clone(),
+                    version,
+                },
+                content_changes: vec![TextDocumentContentChangeEvent {
+                    range: None,
+                    range_length: None,
+                    text: text.to_string(),
+                }],
+            })
+            .await;
+    }
+
+    // Type hints come from the type checker, so the request waits for no timer
+    let hints = server
+        .inlay_hint(InlayHintParams {
+            text_document: TextDocumentIdentifier { uri },
+            range: Range {
+                start: Position::default(),
+                end: Position {
+                    line: 1,
+                    character: 0,
+                },
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        })
+        .await
+        .unwrap()
+        .unwrap_or_default();
+    let labels: Vec<String> = hints
+        .iter()
+        .filter(|h| h.kind == Some(InlayHintKind::TYPE))
+        .map(|h| match &h.label {
+            InlayHintLabel::String(label) => label.clone(),
+            InlayHintLabel::LabelParts(parts) => parts.iter().map(|p| p.value.as_str()).collect(),
+        })
+        .collect();
+    assert_eq!(labels, vec![": string"]);
+}
+
+#[tokio::test]
+async fn test_module_changes_reanalyze_importers() {
+    let project = tempfile::TempDir::new().unwrap();
+    let shapes = project.path().join("shapes.atl");
+    std::fs::write(&shapes, "export fn label(): number { return 1; }").unwrap();
+    let shapes_uri = Url::from_file_path(&shapes).unwrap();
+    let uri = Url::from_file_path(project.path().join("main.atl")).unwrap();
+
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+    server
+        .did_open(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: uri.clone(),
+                language_id: "atlas".to_string(),
+                version: 1,
+                text: "import { label } from \"./shapes\";\nlet x = label();\n".to_string(),
+            },
+        })
+        .await;
+
+    let mut seen = Vec::new();
+    for step in 0..3 {
+        match step {
+            // A checkout rewrites the imported file on disk
+            1 => {
+                std::fs::write(&shapes, "export fn label(): string { return \"one\"; }").unwrap();
+                server
+                    .did_change_watched_files(DidChangeWatchedFilesParams {
+                        changes: vec![FileEvent {
+                            uri: shapes_uri.clone(),
+                            typ: FileChangeType::CHANGED,
+                        }],
+                    })
+                    .await;
+            }
+            // Unsaved editor text of the imported module wins over the disk
+            2 => {
+                server
+                    .did_open(DidOpenTextDocumentParams {
+                        text_document: TextDocumentItem {
+                            uri: shapes_uri.clone(),
+                            language_id: "atlas".to_string(),
+                            version: 1,
+                            text: "export fn label(): bool { return true; }".to_string(),
+                        },
+                    })
+                    .await;
+            }
+            _ => {}
+        }
+        let hints = server
+            .inlay_hint(InlayHintParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                range: Range {
+                    start: Position::default(),
+                    end: Position {
+                        line: 2,
+                        character: 0,
+                    },
+                },
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            })
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        let labels: Vec<String> = hints
+            .iter()
+            .filter(|h| h.kind == Some(InlayHintKind::TYPE))
+            .map(|h| match &h.label {
+                InlayHintLabel::String(label) => label.clone(),
+                InlayHintLabel::LabelParts(parts) => {
+                    parts.iter().map(|p| p.value.as_str()).collect()
+                }
+            })
+            .collect();
+        seen.push(labels);
+    }
+    assert_eq!(
+        seen,
+        vec![
+            vec![": number".to_string()],
+            vec![": string".to_string()],
+            vec![": bool".to_string()],
+        ]
+    );
+}
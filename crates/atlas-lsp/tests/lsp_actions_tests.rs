@@ -5,7 +5,7 @@
 //! - Refactoring actions
 //! - Source actions
 
-use atlas_lsp::actions::{action_kinds, generate_code_actions};
+use atlas_lsp::actions::{action_kinds, generate_code_actions, generate_import_fixes};
 use atlas_runtime::{Diagnostic, Lexer, Parser};
 use tower_lsp::lsp_types::*;
 
@@ -619,3 +619,173 @@ fn test_multiple_diagnostics_multiple_fixes() {
     // Should have multiple actions
     assert!(actions.len() >= 2);
 }
+
+// === Quick Fixes From Real Diagnostics ===
+
+/// First diagnostic with `code` that the pipeline reports for `source`
+fn lsp_diagnostic(source: &str, code: &str) -> tower_lsp::lsp_types::Diagnostic {
+    let (_, _, diags) = parse_source(source);
+    let diag = diags
+        .iter()
+        .find(|d| d.code == code)
+        .unwrap_or_else(|| panic!("no {} diagnostic in {:?}", code, diags));
+    atlas_lsp::convert::diagnostic_to_lsp(diag)
+}
+
+/// The single edit of the quick fix whose title contains `title`
+fn quick_fix_edit(actions: &[CodeActionOrCommand], title: &str) -> TextEdit {
+    let action = actions
+        .iter()
+        .find_map(|a| match a {
+            CodeActionOrCommand::CodeAction(ca) if ca.title.contains(title) => Some(ca),
+            _ => None,
+        })
+        .unwrap_or_else(|| panic!("no '{}' action in {:?}", title, actions));
+    let changes = action.edit.as_ref().unwrap().changes.as_ref().unwrap();
+    let edits = changes.values().next().unwrap();
+    assert_eq!(edits.len(), 1);
+    edits[0].clone()
+}
+
+#[test]
+fn test_quickfix_add_missing_return_type() {
+    let source = "fn double(x: number) {\n    return x * 2;\n}";
+    let diag = lsp_diagnostic(source, "AT1000");
+    let context = create_context_with_diagnostic(diag);
+    let actions = generate_code_actions(
+        &create_test_uri(),
+        Range::default(),
+        &context,
+        source,
+        None,
+        None,
+        &[],
+    );
+
+    let edit = quick_fix_edit(&actions, "Add return type");
+    assert_eq!(edit.new_text, ": number");
+    assert_eq!(edit.range.start, Position::new(0, 20));
+    assert_eq!(edit.range.end, edit.range.start);
+}
+
+#[test]
+fn test_quickfix_add_missing_void_return_type() {
+    let source = "fn greet(name: string) {\n    console.log(name);\n}";
+    let context = create_context_with_diagnostic(lsp_diagnostic(source, "AT1000"));
+    let actions = generate_code_actions(
+        &create_test_uri(),
+        Range::default(),
+        &context,
+        source,
+        None,
+        None,
+        &[],
+    );
+
+    assert_eq!(
+        quick_fix_edit(&actions, "Add return type").new_text,
+        ": void"
+    );
+}
+
+#[test]
+fn test_quickfix_replace_return_arrow() {
+    let source = "fn double(x: number) -> number {\n    return x * 2;\n}";
+    let context = create_context_with_diagnostic(lsp_diagnostic(source, "AT1000"));
+    let actions = generate_code_actions(
+        &create_test_uri(),
+        Range::default(),
+        &context,
+        source,
+        None,
+        None,
+        &[],
+    );
+
+    let edit = quick_fix_edit(&actions, "Replace '->'");
+    assert_eq!(edit.new_text, ":");
+    assert_eq!(edit.range.start, Position::new(0, 20));
+    assert_eq!(edit.range.end, Position::new(0, 23));
+}
+
+#[test]
+fn test_quickfix_immutable_assignment_targets_nearest_declaration() {
+    let source = "let count = 0;\nfn f(): void {\n    let count = 1;\n    count = 2;\n}";
+    let context = create_context_with_diagnostic(lsp_diagnostic(source, "AT3003"));
+    let actions = generate_code_actions(
+        &create_test_uri(),
+        Range::default(),
+        &context,
+        source,
+        None,
+        None,
+        &[],
+    );
+
+    let edit = quick_fix_edit(&actions, "let mut");
+    assert_eq!(edit.new_text, "mut ");
+    assert_eq!(edit.range.start, Position::new(2, 8));
+}
+
+#[test]
+fn test_quickfix_prefix_unused_parameter() {
+    let source = "fn f(unused: number): number {\n    return 1;\n}\nf(1);";
+    let context = create_context_with_diagnostic(lsp_diagnostic(source, "AT2001"));
+    let actions = generate_code_actions(
+        &create_test_uri(),
+        Range::default(),
+        &context,
+        source,
+        None,
+        None,
+        &[],
+    );
+
+    let edit = quick_fix_edit(&actions, "underscore");
+    assert_eq!(edit.new_text, "_unused");
+    assert_eq!(edit.range.start, Position::new(0, 5));
+    assert_eq!(edit.range.end, Position::new(0, 11));
+}
+
+#[test]
+fn test_quickfix_import_from_open_module() {
+    let utils = parse_source("export fn square(x: number): number {\n    return x * x;\n}")
+        .0
+        .unwrap();
+    let math = parse_source("export let tau = 6.28;").0.unwrap();
+    let utils_uri = Url::parse("file:///project/src/utils.atlas").unwrap();
+    let math_uri = Url::parse("file:///project/lib/math.atlas").unwrap();
+    let modules = vec![(utils_uri, &utils), (math_uri, &math)];
+    let uri = Url::parse("file:///project/src/main.atlas").unwrap();
+
+    let source = "let x = square(2);";
+    let context = create_context_with_diagnostic(lsp_diagnostic(source, "AT0002"));
+    let actions = generate_import_fixes(&uri, source, &context, &modules);
+    let edit = quick_fix_edit(&actions, "Import 'square' from \"./utils\"");
+    assert_eq!(edit.new_text, "import { square } from \"./utils\";\n");
+    assert_eq!(edit.range.start, Position::new(0, 0));
+
+    let source = "import { square } from \"./utils\";\nlet y = tau;";
+    let context = create_context_with_diagnostic(lsp_diagnostic(source, "AT0002"));
+    let actions = generate_import_fixes(&uri, source, &context, &modules);
+    let edit = quick_fix_edit(&actions, "Import 'tau' from \"../lib/math\"");
+    assert_eq!(edit.new_text, "import { tau } from \"../lib/math\";\n");
+    assert_eq!(edit.range.start, Position::new(1, 0));
+}
+
+#[test]
+fn test_quickfix_import_joins_existing_import() {
+    let utils = parse_source("export fn square(x: number): number {\n    return x * x;\n}\nexport fn cube(x: number): number {\n    return x * x * x;\n}")
+        .0
+        .unwrap();
+    let modules = vec![(Url::parse("file:///project/utils.atlas").unwrap(), &utils)];
+    let uri = Url::parse("file:///project/main.atlas").unwrap();
+
+    let source = "import { square } from \"./utils\";\nlet y = cube(square(2));";
+    let context = create_context_with_diagnostic(lsp_diagnostic(source, "AT0002"));
+    let actions = generate_import_fixes(&uri, source, &context, &modules);
+
+    let edit = quick_fix_edit(&actions, "Import 'cube'");
+    assert_eq!(edit.new_text, ", cube");
+    assert_eq!(edit.range.start, Position::new(0, 15));
+}
@@ -0,0 +1,240 @@
+//! Formatting tests
+//!
+//! Tests for LSP formatting including:
+//! - Full-document formatting through the atlas-formatter engine
+//! - Range formatting of the top-level items a selection touches
+//! - On-type formatting after `}` and `;`
+//! - Idempotency: formatting formatted code produces no edits
+
+use atlas_lsp::convert::position_to_offset;
+use atlas_lsp::formatting::{format_document, format_on_type, format_range};
+use tower_lsp::lsp_types::{FormattingOptions, Position, Range, TextEdit};
+
+fn options() -> FormattingOptions {
+    FormattingOptions {
+        tab_size: 4,
+        insert_spaces: true,
+        ..Default::default()
+    }
+}
+
+/// Apply edits to `source`, last edit first so earlier offsets stay valid
+fn apply(source: &str, edits: &[TextEdit]) -> String {
+    let mut text = source.to_string();
+    let mut edits = edits.to_vec();
+    edits.sort_by_key(|e| (e.range.start.line, e.range.start.character));
+    for edit in edits.iter().rev() {
+        let start = position_to_offset(&text, edit.range.start);
+        let end = position_to_offset(&text, edit.range.end);
+        text.replace_range(start..end, &edit.new_text);
+    }
+    text
+}
+
+fn lines(start: u32, end: u32) -> Range {
+    Range::new(Position::new(start, 0), Position::new(end, 0))
+}
+
+const MESSY: &str =
+    "fn add(a:number,b:number):number{\nreturn a+b;}\n\nlet   x=add(1,2);\nlet y  =  x*2;\n";
+
+// === Full document ===
+
+#[test]
+fn test_document_formatting_matches_formatter() {
+    let formatted = apply(MESSY, &format_document(MESSY, &options()));
+    let expected = match atlas_formatter::format_source(MESSY) {
+        atlas_formatter::FormatResult::Ok(text) => text,
+        other => panic!("formatter failed: {:?}", other),
+    };
+    assert_eq!(formatted, expected);
+}
+
+#[test]
+fn test_document_formatting_is_idempotent() {
+    let formatted = apply(MESSY, &format_document(MESSY, &options()));
+    assert!(format_document(&formatted, &options()).is_empty());
+}
+
+#[test]
+fn test_document_formatting_uses_client_tab_size() {
+    let options = FormattingOptions {
+        tab_size: 2,
+        insert_spaces: true,
+        ..Default::default()
+    };
+    let formatted = apply(MESSY, &format_document(MESSY, &options));
+    assert!(formatted.contains("\n  return a + b;\n"));
+}
+
+#[test]
+fn test_document_formatting_edit_is_trimmed() {
+    let source = "let a = 1;\nlet b=2;\nlet c = 3;\n";
+    let edits = format_document(source, &options());
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].range.start.line, 1);
+    assert_eq!(edits[0].range.end.line, 1);
+}
+
+#[test]
+fn test_no_edits_for_parse_errors() {
+    let source = "fn broken( {\nlet x=1;\n";
+    assert!(format_document(source, &options()).is_empty());
+    assert!(format_range(source, lines(1, 1), &options()).is_empty());
+    assert!(format_on_type(source, Position::new(1, 8), ";", &options()).is_empty());
+}
+
+// === Range ===
+
+#[test]
+fn test_range_formats_only_selected_items() {
+    let formatted = apply(MESSY, &format_range(MESSY, lines(3, 3), &options()));
+    assert_eq!(
+        formatted,
+        "fn add(a:number,b:number):number{\nreturn a+b;}\n\nlet x = add(1, 2);\nlet y  =  x*2;\n"
+    );
+}
+
+#[test]
+fn test_range_inside_function_formats_whole_function() {
+    let formatted = apply(MESSY, &format_range(MESSY, lines(1, 1), &options()));
+    assert!(formatted
+        .starts_with("fn add(a: number, b: number): number {\n    return a + b;\n}\n\nlet   x"));
+}
+
+#[test]
+fn test_range_over_every_item_matches_document_formatting() {
+    let ranged = apply(MESSY, &format_range(MESSY, lines(0, 5), &options()));
+    let full = apply(MESSY, &format_document(MESSY, &options()));
+    assert_eq!(ranged, full);
+}
+
+#[test]
+fn test_range_formatting_is_idempotent() {
+    let once = apply(MESSY, &format_range(MESSY, lines(0, 1), &options()));
+    assert_ne!(once, MESSY);
+    assert!(format_range(&once, lines(0, 2), &options()).is_empty());
+}
+
+#[test]
+fn test_range_keeps_comments_and_blank_lines_outside_items() {
+    let source = "// header\n\n\nlet a=1; // one\n\n\nlet b=2;\n";
+    let formatted = apply(source, &format_range(source, lines(3, 3), &options()));
+    assert_eq!(
+        formatted,
+        "// header\n\n\nlet a = 1; // one\n\n\nlet b=2;\n"
+    );
+}
+
+#[test]
+fn test_range_on_blank_lines_has_no_edits() {
+    let source = "let a=1;\n\n\nlet b=2;\n";
+    assert!(format_range(source, lines(1, 1), &options()).is_empty());
+}
+
+#[test]
+fn test_range_widens_to_items_sharing_a_line() {
+    let source = "let a=1; let b=2;\nlet c=3;\n";
+    let range = Range::new(Position::new(0, 10), Position::new(0, 12));
+    let formatted = apply(source, &format_range(source, range, &options()));
+    assert_eq!(formatted, "let a = 1;\nlet b = 2;\nlet c=3;\n");
+}
+
+// === On type ===
+
+#[test]
+fn test_closing_brace_reindents_block() {
+    let source = "let keep=1;\nfn f(): number {\nlet x=1;\nreturn x;\n}";
+    let edits = format_on_type(source, Position::new(4, 1), "}", &options());
+    let formatted = apply(source, &edits);
+    assert_eq!(
+        formatted,
+        "let keep=1;\nfn f(): number {\n    let x = 1;\n    return x;\n}"
+    );
+    assert!(format_on_type(&formatted, Position::new(4, 1), "}", &options()).is_empty());
+}
+
+#[test]
+fn test_semicolon_formats_statement() {
+    let source = "let a=1;\nlet b=a+1;";
+    let edits = format_on_type(source, Position::new(1, 10), ";", &options());
+    let formatted = apply(source, &edits);
+    assert_eq!(formatted, "let a=1;\nlet b = a + 1;");
+    assert!(format_on_type(&formatted, Position::new(1, 14), ";", &options()).is_empty());
+}
+
+#[test]
+fn test_other_characters_do_not_format() {
+    let source = "let b=a+1;";
+    assert!(format_on_type(source, Position::new(0, 10), "\n", &options()).is_empty());
+}
+
+#[tokio::test]
+async fn test_server_formatting_requests() {
+    use atlas_lsp::server::AtlasLspServer;
+    use tower_lsp::lsp_types::*;
+    use tower_lsp::{LanguageServer, LspService};
+
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+    let uri = Url::parse("file:///formatting.atlas").unwrap();
+
+    let init = server
+        .initialize(InitializeParams::default())
+        .await
+        .unwrap();
+    assert!(init
+        .capabilities
+        .document_range_formatting_provider
+        .is_some());
+    let on_type = init
+        .capabilities
+        .document_on_type_formatting_provider
+        .expect("on-type formatting");
+    assert_eq!(on_type.first_trigger_character, "}");
+    assert_eq!(on_type.more_trigger_character, Some(vec![";".to_string()]));
+
+    let text = "fn f(): number {\nreturn 1;\n}\nlet x=f();\n";
+    server
+        .did_open(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: uri.clone(),
+                language_id: "atlas".to_string(),
+                version: 1,
+                text: text.to_string(),
+            },
+        })
+        .await;
+
+    let edits = server
+        .range_formatting(DocumentRangeFormattingParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            range: lines(3, 3),
+            options: options(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        })
+        .await
+        .unwrap()
+        .expect("range edits");
+    assert_eq!(
+        apply(text, &edits),
+        "fn f(): number {\nreturn 1;\n}\nlet x = f();\n"
+    );
+
+    let edits = server
+        .on_type_formatting(DocumentOnTypeFormattingParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri },
+                position: Position::new(2, 1),
+            },
+            ch: "}".to_string(),
+            options: options(),
+        })
+        .await
+        .unwrap()
+        .expect("on-type edits");
+    assert_eq!(
+        apply(text, &edits),
+        "fn f(): number {\n    return 1;\n}\nlet x=f();\n"
+    );
+}
@@ -492,6 +492,39 @@ fn test_shared_param_shows_in_hover() {
     );
 }
 
+#[test]
+fn test_own_param_hover_explains_move_semantics() {
+    let source = "fn process(own data: number): number { return data; }";
+    let (ast, symbols) = parse_source(source);
+    let pos = Position {
+        line: 0,
+        character: 15, // 'data' in the param list
+    };
+    let hover = generate_hover(source, pos, ast.as_ref(), symbols.as_ref());
+    let contents = format!("{:?}", hover.expect("hover").contents);
+    assert!(
+        contents.contains("moved into this function"),
+        "Expected move explanation in: {contents}"
+    );
+}
+
+#[test]
+fn test_impl_method_param_shows_ownership_in_hover() {
+    let source = "trait Sink { fn take(self: Sink, own payload: string): void; }
+impl Sink for number { fn take(self: number, own payload: string): void {} }";
+    let (ast, symbols) = parse_source(source);
+    let pos = Position {
+        line: 1,
+        character: 51, // 'payload' in the impl method param list
+    };
+    let hover = generate_hover(source, pos, ast.as_ref(), symbols.as_ref());
+    let contents = format!("{:?}", hover.expect("hover").contents);
+    assert!(
+        contents.contains("(own parameter) payload"),
+        "Expected '(own parameter) payload' in: {contents}"
+    );
+}
+
 #[test]
 fn test_unannotated_param_hover_unchanged() {
     let source = "fn f(x: number): number { return x; }";
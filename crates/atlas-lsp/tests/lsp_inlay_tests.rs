@@ -421,3 +421,159 @@ fn test_no_inferred_return_hint_for_annotated_fn() {
         return_hints.iter().map(|h| &h.label).collect::<Vec<_>>()
     );
 }
+
+// === Inferred Types and Parameter Names ===
+
+#[test]
+fn test_type_hint_uses_inferred_type_of_local() {
+    // The local `x` shadows the top-level one with a different type
+    let source = "fn names(): string[] { return [\"a\"]; }\nfn count(): number { return 1; }\nlet x = count();\nfn test(): void { let x = names(); }";
+    let (ast, symbols) = parse_source(source);
+
+    let config = InlayHintConfig::default();
+    let hints = generate_inlay_hints(source, full_range(), Some(&ast), Some(&symbols), &config);
+
+    let labels: Vec<(u32, String)> = hints
+        .iter()
+        .filter(|h| h.kind == Some(InlayHintKind::TYPE))
+        .filter_map(|h| match &h.label {
+            tower_lsp::lsp_types::InlayHintLabel::String(s) => Some((h.position.line, s.clone())),
+            _ => None,
+        })
+        .collect();
+
+    assert!(
+        labels.contains(&(2, ": number".to_string())),
+        "{:?}",
+        labels
+    );
+    assert!(
+        labels.contains(&(3, ": string[]".to_string())),
+        "{:?}",
+        labels
+    );
+}
+
+#[test]
+fn test_parameter_hints_use_declared_names() {
+    let source = "fn clamp(value: number, low: number, high: number): number { return value; }\nlet n = 5;\nclamp(n * 2, n, n + 1);";
+    let (ast, symbols) = parse_source(source);
+
+    let config = InlayHintConfig::default();
+    let hints = generate_inlay_hints(source, full_range(), Some(&ast), Some(&symbols), &config);
+
+    let labels: Vec<String> = hints
+        .iter()
+        .filter(|h| h.kind == Some(InlayHintKind::PARAMETER))
+        .filter_map(|h| match &h.label {
+            tower_lsp::lsp_types::InlayHintLabel::String(s) => Some(s.clone()),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(labels, vec!["value:", "low:", "high:"]);
+}
+
+#[test]
+fn test_parameter_hint_skips_argument_named_like_parameter() {
+    let source = "fn area(width: number, height: number): number { return width * height; }\nlet width = 2;\nlet h = 3;\narea(width, h);";
+    let (ast, symbols) = parse_source(source);
+
+    let config = InlayHintConfig::default();
+    let hints = generate_inlay_hints(source, full_range(), Some(&ast), Some(&symbols), &config);
+
+    let labels: Vec<String> = hints
+        .iter()
+        .filter(|h| h.kind == Some(InlayHintKind::PARAMETER))
+        .filter_map(|h| match &h.label {
+            tower_lsp::lsp_types::InlayHintLabel::String(s) => Some(s.clone()),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(labels, vec!["height:"]);
+}
+
+#[test]
+fn test_apply_settings() {
+    let mut config = InlayHintConfig::default();
+    config.apply_settings(&serde_json::json!({
+        "atlas": {
+            "inlayHints": { "typeHints": false, "returnTypeHints": false, "maxTypeLength": 10 }
+        }
+    }));
+    assert!(!config.show_type_hints);
+    assert!(!config.show_inferred_return);
+    assert_eq!(config.max_type_length, 10);
+    // Options the settings don't mention keep their value
+    assert!(config.show_parameter_hints);
+
+    config.apply_settings(&serde_json::json!({ "inlayHints": { "typeHints": true } }));
+    assert!(config.show_type_hints);
+}
+
+#[tokio::test]
+async fn test_server_configuration_toggles_hints() {
+    use atlas_lsp::server::AtlasLspServer;
+    use tower_lsp::lsp_types::*;
+    use tower_lsp::{LanguageServer, LspService};
+
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+    let uri = Url::parse("file:///hints.atlas").unwrap();
+
+    server
+        .initialize(InitializeParams {
+            initialization_options: Some(serde_json::json!({
+                "inlayHints": { "parameterHints": false }
+            })),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    server
+        .did_open(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: uri.clone(),
+                language_id: "atlas".to_string(),
+                version: 1,
+                text: "fn twice(n: number): number { return n * 2; }\nlet k = 1;\nlet x = twice(k + 1);".to_string(),
+            },
+        })
+        .await;
+
+    let request = InlayHintParams {
+        text_document: TextDocumentIdentifier { uri: uri.clone() },
+        range: Range {
+            start: Position::new(0, 0),
+            end: Position::new(10, 0),
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+    };
+    let hints = server
+        .inlay_hint(request.clone())
+        .await
+        .unwrap()
+        .unwrap_or_default();
+    assert!(hints.iter().any(|h| h.kind == Some(InlayHintKind::TYPE)));
+    assert!(!hints
+        .iter()
+        .any(|h| h.kind == Some(InlayHintKind::PARAMETER)));
+
+    server
+        .did_change_configuration(DidChangeConfigurationParams {
+            settings: serde_json::json!({
+                "atlas": { "inlayHints": { "parameterHints": true, "typeHints": false } }
+            }),
+        })
+        .await;
+    let hints = server
+        .inlay_hint(request)
+        .await
+        .unwrap()
+        .unwrap_or_default();
+    assert!(!hints.iter().any(|h| h.kind == Some(InlayHintKind::TYPE)));
+    assert!(hints
+        .iter()
+        .any(|h| h.kind == Some(InlayHintKind::PARAMETER)));
+}
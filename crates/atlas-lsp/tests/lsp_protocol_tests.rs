@@ -194,7 +194,7 @@ async fn test_lifecycle_complete() {
 // ============================================================================
 
 #[tokio::test]
-async fn test_text_document_sync_full() {
+async fn test_text_document_sync_incremental() {
     let (service, _socket) = LspService::new(AtlasLspServer::new);
     let server = service.inner();
 
@@ -207,7 +207,7 @@ async fn test_text_document_sync_full() {
     assert!(result.capabilities.text_document_sync.is_some());
 
     if let Some(TextDocumentSyncCapability::Kind(kind)) = result.capabilities.text_document_sync {
-        assert_eq!(kind, TextDocumentSyncKind::FULL);
+        assert_eq!(kind, TextDocumentSyncKind::INCREMENTAL);
     }
 }
 
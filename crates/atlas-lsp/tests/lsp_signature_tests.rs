@@ -0,0 +1,228 @@
+//! Signature help tests
+//!
+//! Tests for LSP signature help including:
+//! - User functions, nested and exported functions, and externs
+//! - Stdlib builtins, including builtins with several signatures
+//! - Active parameter tracking as arguments are typed
+
+use atlas_lsp::signature_help::generate_signature_help;
+use atlas_runtime::{Lexer, Parser};
+use tower_lsp::lsp_types::{ParameterLabel, Position, SignatureHelp};
+
+/// Signature help with the cursor at the end of `source`
+fn help_at_end(source: &str) -> Option<SignatureHelp> {
+    let mut lexer = Lexer::new(source);
+    let (tokens, _) = lexer.tokenize();
+    let mut parser = Parser::new(tokens);
+    let (ast, _) = parser.parse();
+
+    let line = source.lines().count().saturating_sub(1) as u32;
+    let character = source.lines().last().unwrap_or("").chars().count() as u32;
+    generate_signature_help(source, Position::new(line, character), Some(&ast))
+}
+
+/// Label of the highlighted parameter of the active signature
+fn active_param_label(help: &SignatureHelp) -> String {
+    let signature = &help.signatures[help.active_signature.unwrap() as usize];
+    let index = signature.active_parameter.unwrap() as usize;
+    match &signature.parameters.as_ref().unwrap()[index].label {
+        ParameterLabel::LabelOffsets([start, end]) => signature
+            .label
+            .chars()
+            .skip(*start as usize)
+            .take((end - start) as usize)
+            .collect(),
+        ParameterLabel::Simple(label) => label.clone(),
+    }
+}
+
+// === User functions ===
+
+#[test]
+fn test_user_function_signature() {
+    let help = help_at_end("fn add(a: number, b: number): number { return a + b; }\nadd(");
+    let help = help.expect("signature help");
+    assert_eq!(help.signatures.len(), 1);
+    assert_eq!(
+        help.signatures[0].label,
+        "fn add(a: number, b: number): number"
+    );
+    assert_eq!(active_param_label(&help), "a: number");
+}
+
+#[test]
+fn test_active_parameter_follows_commas() {
+    let help = help_at_end("fn add(a: number, b: number): number { return a + b; }\nadd(1, ")
+        .expect("signature help");
+    assert_eq!(help.active_parameter, Some(1));
+    assert_eq!(active_param_label(&help), "b: number");
+}
+
+#[test]
+fn test_nested_call_arguments_do_not_count() {
+    let source = "fn add(a: number, b: number): number { return a + b; }\n\
+                  fn pair(x: number[], y: string): string { return y; }\n\
+                  pair([add(1, 2), 3], ";
+    let help = help_at_end(source).expect("signature help");
+    assert!(help.signatures[0].label.starts_with("fn pair("));
+    assert_eq!(active_param_label(&help), "y: string");
+}
+
+#[test]
+fn test_inner_call_wins_while_typing_its_arguments() {
+    let source = "fn add(a: number, b: number): number { return a + b; }\n\
+                  fn show(v: number): string { return v.toString(); }\n\
+                  show(add(1, ";
+    let help = help_at_end(source).expect("signature help");
+    assert!(help.signatures[0].label.starts_with("fn add("));
+    assert_eq!(active_param_label(&help), "b: number");
+}
+
+#[test]
+fn test_doc_comment_and_generics() {
+    let source =
+        "/// Returns the first element\nfn first<T>(items: T[]): T { return items[0]; }\nfirst(";
+    let help = help_at_end(source).expect("signature help");
+    let signature = &help.signatures[0];
+    assert_eq!(signature.label, "fn first<T>(items: T[]): T");
+    let doc = format!("{:?}", signature.documentation);
+    assert!(doc.contains("Returns the first element"));
+}
+
+#[test]
+fn test_exported_and_nested_functions() {
+    let help = help_at_end("export fn greet(name: string): string { return name; }\ngreet(")
+        .expect("signature help");
+    assert_eq!(active_param_label(&help), "name: string");
+
+    let source = "fn outer(): number {\n    fn inner(depth: number): number { return depth; }\n    return inner(";
+    let help = help_at_end(source).expect("signature help");
+    assert_eq!(help.signatures[0].label, "fn inner(depth: number): number");
+}
+
+#[test]
+fn test_extern_function_signature() {
+    let help =
+        help_at_end("extern \"m\" fn cbrt(x: CDouble): CDouble;\ncbrt(").expect("signature help");
+    assert_eq!(
+        help.signatures[0].label,
+        "extern fn cbrt(x: CDouble): CDouble"
+    );
+    assert_eq!(active_param_label(&help), "x: CDouble");
+}
+
+#[test]
+fn test_user_function_shadows_builtin() {
+    let help =
+        help_at_end("fn len(items: string[]): number { return 0; }\nlen(").expect("signature help");
+    assert_eq!(help.signatures.len(), 1);
+    assert!(help.signatures[0].label.starts_with("fn len("));
+}
+
+// === Builtins ===
+
+#[test]
+fn test_namespace_builtin_signature() {
+    let help = help_at_end("let p = Math.pow(2, ").expect("signature help");
+    assert_eq!(
+        help.signatures[0].label,
+        "Math.pow(base: number, exponent: number): number"
+    );
+    assert_eq!(active_param_label(&help), "exponent: number");
+}
+
+#[test]
+fn test_namespace_matches_case_insensitively() {
+    let help = help_at_end("let t = File.read(").expect("signature help");
+    assert!(help.signatures[0].label.starts_with("file.read("));
+}
+
+#[test]
+fn test_builtin_with_several_signatures() {
+    let help = help_at_end("let n = len(").expect("signature help");
+    let labels: Vec<&str> = help.signatures.iter().map(|s| s.label.as_str()).collect();
+    assert_eq!(
+        labels,
+        vec!["len(value: string): number", "len(value: T[]): number"]
+    );
+    assert!(help.signatures.iter().all(|s| s.documentation.is_some()));
+}
+
+#[test]
+fn test_active_signature_fits_argument_count() {
+    let help = help_at_end("let r = Array.range(0, 10, ").expect("signature help");
+    assert_eq!(help.active_signature, Some(1));
+    assert_eq!(active_param_label(&help), "step: number");
+}
+
+#[test]
+fn test_variadic_builtin_keeps_last_parameter_active() {
+    let help = help_at_end("console.log(\"a\", \"b\", ").expect("signature help");
+    assert_eq!(help.active_parameter, Some(2));
+    assert_eq!(active_param_label(&help), "...args: any");
+}
+
+// === No help ===
+
+#[test]
+fn test_no_help_outside_calls() {
+    assert!(help_at_end("let x = 1;").is_none());
+    assert!(
+        help_at_end("fn add(a: number, b: number): number { return a + b; }\nadd(1, 2);").is_none()
+    );
+    assert!(help_at_end("fn add(").is_none());
+    assert!(help_at_end("let s = \"len(").is_none());
+    assert!(help_at_end("unknownFunction(").is_none());
+}
+
+#[tokio::test]
+async fn test_server_signature_help() {
+    use atlas_lsp::server::AtlasLspServer;
+    use tower_lsp::lsp_types::*;
+    use tower_lsp::{LanguageServer, LspService};
+
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+    let uri = Url::parse("file:///signature.atlas").unwrap();
+
+    let init = server
+        .initialize(InitializeParams::default())
+        .await
+        .unwrap();
+    let triggers = init
+        .capabilities
+        .signature_help_provider
+        .and_then(|options| options.trigger_characters)
+        .unwrap_or_default();
+    assert!(triggers.contains(&"(".to_string()));
+    assert!(triggers.contains(&",".to_string()));
+
+    server
+        .did_open(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: uri.clone(),
+                language_id: "atlas".to_string(),
+                version: 1,
+                text: "fn scale(value: number, factor: number): number { return value * factor; }\nlet x = scale(3, ".to_string(),
+            },
+        })
+        .await;
+
+    let help = server
+        .signature_help(SignatureHelpParams {
+            context: None,
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri },
+                position: Position::new(1, 17),
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        })
+        .await
+        .unwrap()
+        .expect("signature help");
+    assert_eq!(
+        help.signatures[0].label,
+        "fn scale(value: number, factor: number): number"
+    );
+    assert_eq!(help.active_parameter, Some(1));
+}
@@ -155,6 +155,72 @@ let mut result: number = add(1, 2);
     assert_eq!(location.range.start.character, 3);
 }
 
+#[tokio::test]
+async fn test_goto_definition_into_dependency_package() {
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+
+    let project = tempfile::TempDir::new().unwrap();
+    let root = project.path();
+    std::fs::write(
+        root.join("atlas.toml"),
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("atlas.lock"),
+        "version = 1\n\n[[packages]]\nname = \"web\"\nversion = \"0.1.0\"\n\n[packages.source]\ntype = \"path\"\npath = \"vendor/web\"\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(root.join("vendor/web")).unwrap();
+    std::fs::write(
+        root.join("vendor/web/router.atlas"),
+        "export fn route(): string {\n    return \"ok\";\n}\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    let main = root.join("src/main.atlas");
+    let source = "import { route } from \"web/router\";\nlet r: string = route();\n";
+    std::fs::write(&main, source).unwrap();
+
+    let uri = Url::from_file_path(&main).unwrap();
+    server
+        .did_open(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: uri.clone(),
+                language_id: "atlas".to_string(),
+                version: 1,
+                text: source.to_string(),
+            },
+        })
+        .await;
+
+    // Cursor on `route` in the import list lands on the exported fn
+    let params = GotoDefinitionParams {
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            position: Position {
+                line: 0,
+                character: 11,
+            },
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+    };
+    let Some(GotoDefinitionResponse::Scalar(location)) =
+        server.goto_definition(params).await.unwrap()
+    else {
+        panic!("expected a single definition location");
+    };
+    assert!(
+        location.uri.path().ends_with("vendor/web/router.atlas"),
+        "{}",
+        location.uri
+    );
+    assert_eq!(location.range.start.line, 0);
+    assert_eq!(location.range.start.character, 10);
+}
+
 #[tokio::test]
 async fn test_references_placeholder() {
     let (service, _socket) = LspService::new(AtlasLspServer::new);
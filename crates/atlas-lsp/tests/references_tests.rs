@@ -500,6 +500,67 @@ async fn test_references_to_function_across_files() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn test_references_follow_imports() {
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+
+    let math_uri = test_uri("math");
+    let main_uri = test_uri("main");
+    let other_uri = test_uri("other");
+    let math = "export fn add(a: number, b: number): number {\n    return a + b;\n}\n";
+    let main = "import { add } from \"./math\";\n\nlet sum = add(1, 2);\n";
+    // Same name, unrelated declaration
+    let other = "fn add(): number {\n    return 0;\n}\n";
+
+    for (uri, text) in [(&math_uri, math), (&main_uri, main), (&other_uri, other)] {
+        server
+            .did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "atlas".to_string(),
+                    version: 1,
+                    text: text.to_string(),
+                },
+            })
+            .await;
+    }
+
+    let find = |include_declaration| ReferenceParams {
+        text_document_position: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier {
+                uri: main_uri.clone(),
+            },
+            position: Position {
+                line: 2,
+                character: 11,
+            },
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+        context: ReferenceContext {
+            include_declaration,
+        },
+    };
+
+    let with_decl = server.references(find(true)).await.unwrap().unwrap();
+    assert!(with_decl.iter().all(|loc| loc.uri != other_uri));
+    assert_eq!(
+        with_decl.iter().filter(|loc| loc.uri == math_uri).count(),
+        1,
+        "the declaration: {with_decl:?}"
+    );
+    assert_eq!(
+        with_decl.iter().filter(|loc| loc.uri == main_uri).count(),
+        2,
+        "import specifier and call: {with_decl:?}"
+    );
+
+    let without_decl = server.references(find(false)).await.unwrap().unwrap();
+    assert!(without_decl.iter().all(|loc| loc.uri == main_uri));
+    assert_eq!(without_decl.len(), 2);
+}
+
 // ============================================================================
 // Index Management Tests
 // ============================================================================
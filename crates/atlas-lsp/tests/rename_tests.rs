@@ -0,0 +1,159 @@
+//! Rename symbol tests
+//!
+//! Tests textDocument/rename and textDocument/prepareRename including:
+//! - Locals renamed only inside their function
+//! - Exported symbols renamed in importing documents
+//! - Builtins, invalid names and conflicts rejected
+
+use atlas_lsp::server::AtlasLspServer;
+use tower_lsp::lsp_types::*;
+use tower_lsp::{LanguageServer, LspService};
+
+/// Helper to create test URI
+fn test_uri(name: &str) -> Url {
+    Url::parse(&format!("file:///{}.atl", name)).unwrap()
+}
+
+#[tokio::test]
+async fn test_rename_local_stays_in_its_function() {
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+
+    let uri = test_uri("locals");
+    let source = r#"fn first(): number {
+    let x: number = 1;
+    return x + 1;
+}
+
+fn second(): number {
+    let x: number = 2;
+    return x;
+}
+"#;
+
+    server
+        .did_open(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: uri.clone(),
+                language_id: "atlas".to_string(),
+                version: 1,
+                text: source.to_string(),
+            },
+        })
+        .await;
+
+    let edit = server
+        .rename(RenameParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position: Position {
+                    line: 1,
+                    character: 8,
+                },
+            },
+            new_name: "total".to_string(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+    let changes = edit.changes.unwrap();
+    let edits = &changes[&uri];
+    assert_eq!(edits.len(), 2, "declaration and use in first(): {edits:?}");
+    assert!(edits.iter().all(|e| e.range.start.line < 4));
+    assert!(edits.iter().all(|e| e.new_text == "total"));
+}
+
+#[tokio::test]
+async fn test_rename_export_updates_importers() {
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+
+    let math_uri = test_uri("math");
+    let main_uri = test_uri("main");
+    let math = "export fn add(a: number, b: number): number {\n    return a + b;\n}\n";
+    let main = "import { add } from \"./math\";\n\nlet sum = add(1, 2);\n";
+
+    for (uri, text) in [(&math_uri, math), (&main_uri, main)] {
+        server
+            .did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "atlas".to_string(),
+                    version: 1,
+                    text: text.to_string(),
+                },
+            })
+            .await;
+    }
+
+    // Rename from the call site in the importing module
+    let edit = server
+        .rename(RenameParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: main_uri.clone(),
+                },
+                position: Position {
+                    line: 2,
+                    character: 11,
+                },
+            },
+            new_name: "plus".to_string(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+    let changes = edit.changes.unwrap();
+    assert_eq!(changes[&math_uri].len(), 1, "the declaration");
+    assert_eq!(changes[&main_uri].len(), 2, "import specifier and call");
+}
+
+#[tokio::test]
+async fn test_rename_rejects_builtins_and_bad_names() {
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+
+    let uri = test_uri("rejects");
+    let source = "let count = 1;\nlet other = 2;\nprint(count);\n";
+
+    server
+        .did_open(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: uri.clone(),
+                language_id: "atlas".to_string(),
+                version: 1,
+                text: source.to_string(),
+            },
+        })
+        .await;
+
+    let at = |line, character| TextDocumentPositionParams {
+        text_document: TextDocumentIdentifier { uri: uri.clone() },
+        position: Position { line, character },
+    };
+
+    // prepareRename accepts user symbols and refuses builtins
+    let prepared = server.prepare_rename(at(0, 5)).await.unwrap();
+    assert!(matches!(prepared, Some(PrepareRenameResponse::Range(_))));
+    assert!(server.prepare_rename(at(2, 2)).await.unwrap().is_none());
+
+    for (position, new_name) in [
+        (at(2, 2), "show"),   // builtin
+        (at(0, 5), "let"),    // keyword
+        (at(0, 5), "9count"), // not an identifier
+        (at(0, 5), "other"),  // already declared
+    ] {
+        let result = server
+            .rename(RenameParams {
+                text_document_position: position,
+                new_name: new_name.to_string(),
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            })
+            .await;
+        assert!(result.is_err(), "rename to '{new_name}' should fail");
+    }
+}
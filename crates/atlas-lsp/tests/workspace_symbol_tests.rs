@@ -605,3 +605,214 @@ fn main(): number {
 
     assert!(call_result.is_some());
 }
+
+// ============================================================================
+// On-disk Workspace Indexing
+// ============================================================================
+
+/// Names returned by a workspace symbol query
+async fn symbol_names(server: &AtlasLspServer, query: &str) -> Vec<String> {
+    server
+        .symbol(WorkspaceSymbolParams {
+            query: query.to_string(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        })
+        .await
+        .unwrap()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|s| s.name)
+        .collect()
+}
+
+/// Wait for the background indexer to make `name` searchable
+async fn wait_for_symbol(server: &AtlasLspServer, name: &str) -> bool {
+    for _ in 0..200 {
+        if symbol_names(server, name).await.iter().any(|n| n == name) {
+            return true;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+    }
+    false
+}
+
+#[tokio::test]
+async fn test_workspace_symbol_indexes_files_on_disk() {
+    let project = tempfile::TempDir::new().unwrap();
+    let root = project.path();
+    std::fs::create_dir_all(root.join("src/util")).unwrap();
+    std::fs::create_dir_all(root.join(".git")).unwrap();
+    std::fs::create_dir_all(root.join("target")).unwrap();
+    std::fs::write(
+        root.join("main.atl"),
+        "fn mainEntry(): number { return 1; }",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("src/util/strings.atl"),
+        "export fn padLeftDisk(s: string): string { return s; }\nlet defaultWidth = 4;",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("legacy.atlas"),
+        "fn legacyHelper(): number { return 0; }",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join(".git/hidden.atl"),
+        "fn hiddenFn(): number { return 0; }",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("target/built.atl"),
+        "fn builtFn(): number { return 0; }",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("notes.txt"),
+        "fn notAtlas(): number { return 0; }",
+    )
+    .unwrap();
+
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+    server
+        .initialize(InitializeParams {
+            workspace_folders: Some(vec![WorkspaceFolder {
+                uri: Url::from_file_path(root).unwrap(),
+                name: "project".to_string(),
+            }]),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    server.initialized(InitializedParams {}).await;
+
+    assert!(wait_for_symbol(server, "padLeftDisk").await);
+    assert!(symbol_names(server, "mainEntry")
+        .await
+        .contains(&"mainEntry".to_string()));
+    assert!(symbol_names(server, "defaultWidth")
+        .await
+        .contains(&"defaultWidth".to_string()));
+    assert!(symbol_names(server, "legacyHelper")
+        .await
+        .contains(&"legacyHelper".to_string()));
+    assert!(symbol_names(server, "hiddenFn").await.is_empty());
+    assert!(symbol_names(server, "builtFn").await.is_empty());
+    assert!(symbol_names(server, "notAtlas").await.is_empty());
+}
+
+#[tokio::test]
+async fn test_closing_document_restores_on_disk_symbols() {
+    let project = tempfile::TempDir::new().unwrap();
+    let path = project.path().join("shapes.atl");
+    std::fs::write(&path, "fn savedArea(): number { return 1; }").unwrap();
+    let uri = Url::from_file_path(&path).unwrap();
+
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+    server
+        .initialize(InitializeParams {
+            root_uri: Some(Url::from_file_path(project.path()).unwrap()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    server.initialized(InitializedParams {}).await;
+    assert!(wait_for_symbol(server, "savedArea").await);
+
+    // Unsaved editor text replaces the on-disk symbols while the file is open
+    server
+        .did_open(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: uri.clone(),
+                language_id: "atlas".to_string(),
+                version: 1,
+                text: "fn draftArea(): number { return 2; }".to_string(),
+            },
+        })
+        .await;
+    assert!(symbol_names(server, "draftArea")
+        .await
+        .contains(&"draftArea".to_string()));
+    assert!(symbol_names(server, "savedArea").await.is_empty());
+
+    // Closing without saving goes back to the file on disk
+    server
+        .did_close(DidCloseTextDocumentParams {
+            text_document: TextDocumentIdentifier { uri },
+        })
+        .await;
+    assert!(symbol_names(server, "draftArea").await.is_empty());
+    assert!(symbol_names(server, "savedArea")
+        .await
+        .contains(&"savedArea".to_string()));
+}
+
+#[tokio::test]
+async fn test_watched_file_changes_update_index() {
+    let project = tempfile::TempDir::new().unwrap();
+    let path = project.path().join("late.atl");
+
+    let (service, _socket) = LspService::new(AtlasLspServer::new);
+    let server = service.inner();
+    server
+        .initialize(InitializeParams::default())
+        .await
+        .unwrap();
+
+    std::fs::write(&path, "fn createdLater(): number { return 1; }").unwrap();
+    let uri = Url::from_file_path(&path).unwrap();
+    server
+        .did_change_watched_files(DidChangeWatchedFilesParams {
+            changes: vec![FileEvent {
+                uri: uri.clone(),
+                typ: FileChangeType::CREATED,
+            }],
+        })
+        .await;
+    assert!(symbol_names(server, "createdLater")
+        .await
+        .contains(&"createdLater".to_string()));
+
+    std::fs::remove_file(&path).unwrap();
+    server
+        .did_change_watched_files(DidChangeWatchedFilesParams {
+            changes: vec![FileEvent {
+                uri,
+                typ: FileChangeType::DELETED,
+            }],
+        })
+        .await;
+    assert!(symbol_names(server, "createdLater").await.is_empty());
+}
+
+#[test]
+fn test_search_ranks_matches_before_applying_limit() {
+    use atlas_lsp::symbols::{IndexedSymbol, WorkspaceIndex};
+
+    let mut index = WorkspaceIndex::new();
+    let uri = test_uri("ranking");
+    let symbols = ["parseHeaderLine", "reparse", "parseX", "parse", "p_a_r_s_e"]
+        .iter()
+        .map(|name| IndexedSymbol {
+            name: name.to_string(),
+            kind: SymbolKind::FUNCTION,
+            location: Location {
+                uri: uri.clone(),
+                range: Range::default(),
+            },
+            container_name: None,
+        })
+        .collect();
+    index.insert_symbols(uri, symbols);
+
+    let names: Vec<String> = index
+        .search("parse", 4, None)
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    assert_eq!(names, vec!["parse", "parseHeaderLine", "parseX", "reparse"]);
+}
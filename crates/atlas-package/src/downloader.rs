@@ -1,21 +1,118 @@
-use crate::registry::{Registry, RegistryError, RegistryResult};
+use crate::lockfile::{LockedSource, Lockfile};
+use crate::registry::{PackageMetadata, Registry, RegistryError, RegistryResult};
+use crate::signing::{self, TrustPolicy};
 use flate2::read::GzDecoder;
 use semver::Version;
 use sha2::{Digest, Sha256};
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 use tar::Archive;
 
-/// Package downloader with checksum verification
+/// Concurrent downloads when none is configured
+const MAX_DEFAULT_PARALLELISM: usize = 8;
+
+/// Package downloader with checksum and signature verification
 pub struct Downloader {
     cache_dir: PathBuf,
+    trust_policy: TrustPolicy,
+    parallelism: usize,
+}
+
+/// One registry package for `Downloader::download_all` to fetch
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DownloadRequest {
+    pub package: String,
+    pub version: Version,
+    /// Checksum pinned by a lockfile; the archive must match it as well as
+    /// the registry's published checksum
+    pub checksum: Option<String>,
+}
+
+impl DownloadRequest {
+    pub fn new(package: impl Into<String>, version: Version) -> Self {
+        Self {
+            package: package.into(),
+            version,
+            checksum: None,
+        }
+    }
+
+    /// The registry packages locked by the lockfiles of a workspace's
+    /// members, each `name@version` listed once
+    pub fn from_lockfiles<'a>(lockfiles: impl IntoIterator<Item = &'a Lockfile>) -> Vec<Self> {
+        let mut seen = HashSet::new();
+        lockfiles
+            .into_iter()
+            .flat_map(|lockfile| &lockfile.packages)
+            .filter(|p| matches!(p.source, LockedSource::Registry { .. }))
+            .filter(|p| seen.insert((p.name.clone(), p.version.clone())))
+            .map(|p| Self {
+                package: p.name.clone(),
+                version: p.version.clone(),
+                checksum: p.checksum.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Where a package in `Downloader::download_all` has got to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadStatus {
+    Started,
+    Finished,
+    Failed,
+}
+
+/// Progress report passed to the `download_all` callback
+#[derive(Debug, Clone, Copy)]
+pub struct DownloadProgress<'a> {
+    pub request: &'a DownloadRequest,
+    pub status: DownloadStatus,
+    /// Packages finished or failed so far, this one included
+    pub completed: usize,
+    pub total: usize,
+}
+
+/// Result of one package in `Downloader::download_all`
+#[derive(Debug)]
+pub struct DownloadOutcome {
+    pub request: DownloadRequest,
+    /// Extracted package directory
+    pub result: RegistryResult<PathBuf>,
 }
 
 impl Downloader {
     pub fn new(cache_dir: PathBuf) -> Self {
         // Ensure cache directory exists
         fs::create_dir_all(&cache_dir).ok();
-        Self { cache_dir }
+        let parallelism = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(MAX_DEFAULT_PARALLELISM);
+        Self {
+            cache_dir,
+            trust_policy: TrustPolicy::default(),
+            parallelism,
+        }
+    }
+
+    /// Apply a trust policy (e.g. from the manifest's `[trust]` table)
+    pub fn with_trust_policy(mut self, policy: TrustPolicy) -> Self {
+        self.trust_policy = policy;
+        self
+    }
+
+    /// Download at most `jobs` packages at a time in `download_all`
+    /// (at least one)
+    pub fn with_parallelism(mut self, jobs: usize) -> Self {
+        self.parallelism = jobs.max(1);
+        self
+    }
+
+    pub fn parallelism(&self) -> usize {
+        self.parallelism
     }
 
     /// Get cache directory
@@ -29,6 +126,91 @@ impl Downloader {
         registry: &dyn Registry,
         package: &str,
         version: &Version,
+    ) -> RegistryResult<PathBuf> {
+        self.fetch(registry, package, version, None)
+    }
+
+    /// Download and extract every requested package, running up to
+    /// `parallelism` downloads at once.
+    ///
+    /// Duplicate requests are fetched once. One package failing does not stop
+    /// the others; outcomes come back in request order. `progress` is called
+    /// from the worker threads as each package starts and completes.
+    pub fn download_all(
+        &self,
+        registry: &dyn Registry,
+        requests: &[DownloadRequest],
+        progress: &(dyn Fn(&DownloadProgress<'_>) + Sync),
+    ) -> Vec<DownloadOutcome> {
+        let mut seen = HashSet::new();
+        let requests: Vec<&DownloadRequest> = requests
+            .iter()
+            .filter(|r| seen.insert((&r.package, &r.version)))
+            .collect();
+        let total = requests.len();
+        let results: Mutex<Vec<Option<RegistryResult<PathBuf>>>> =
+            Mutex::new((0..total).map(|_| None).collect());
+        let next = AtomicUsize::new(0);
+        let completed = AtomicUsize::new(0);
+
+        let worker = || loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let Some(request) = requests.get(index).copied() else {
+                break;
+            };
+            progress(&DownloadProgress {
+                request,
+                status: DownloadStatus::Started,
+                completed: completed.load(Ordering::Relaxed),
+                total,
+            });
+
+            let result = self.fetch(
+                registry,
+                &request.package,
+                &request.version,
+                request.checksum.as_deref(),
+            );
+            let status = if result.is_ok() {
+                DownloadStatus::Finished
+            } else {
+                DownloadStatus::Failed
+            };
+            results.lock().unwrap_or_else(|e| e.into_inner())[index] = Some(result);
+            progress(&DownloadProgress {
+                request,
+                status,
+                completed: completed.fetch_add(1, Ordering::Relaxed) + 1,
+                total,
+            });
+        };
+
+        std::thread::scope(|scope| {
+            for _ in 1..self.parallelism.min(total) {
+                scope.spawn(worker);
+            }
+            worker();
+        });
+
+        let results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+        requests
+            .into_iter()
+            .zip(results)
+            .map(|(request, result)| DownloadOutcome {
+                request: request.clone(),
+                // Workers drain the queue before the scope ends
+                result: result.expect("every request is downloaded"),
+            })
+            .collect()
+    }
+
+    /// Download, verify and extract one package
+    fn fetch(
+        &self,
+        registry: &dyn Registry,
+        package: &str,
+        version: &Version,
+        pinned_checksum: Option<&str>,
     ) -> RegistryResult<PathBuf> {
         // Get metadata for checksum
         let metadata = registry.get_metadata(package, version)?;
@@ -40,6 +222,12 @@ impl Downloader {
         if !metadata.checksum.is_empty() && metadata.checksum != "mock_checksum" {
             self.verify_checksum(&archive_bytes, &metadata.checksum, package, version)?;
         }
+        // A republished archive must not slip past the lockfile
+        if let Some(expected) = pinned_checksum {
+            self.verify_checksum(&archive_bytes, expected, package, version)?;
+        }
+
+        self.verify_signature(registry, &metadata, &archive_bytes)?;
 
         // Extract to cache
         let extract_path = self.cache_dir.join(package).join(version.to_string());
@@ -70,6 +258,57 @@ impl Downloader {
         Ok(())
     }
 
+    /// Check the publisher's signature over the archive actually downloaded.
+    ///
+    /// A signature that is present must verify against one of the registry's
+    /// keys for the package. Unsigned packages, and signatures the registry
+    /// publishes no keys for, only pass when the trust policy allows it.
+    fn verify_signature(
+        &self,
+        registry: &dyn Registry,
+        metadata: &PackageMetadata,
+        data: &[u8],
+    ) -> RegistryResult<()> {
+        let package = &metadata.name;
+        let version = &metadata.version;
+        let required = self.trust_policy.require_signatures;
+
+        let Some(signature) = &metadata.signature else {
+            if required {
+                return Err(RegistryError::MissingSignature {
+                    package: package.clone(),
+                    version: version.to_string(),
+                });
+            }
+            return Ok(());
+        };
+
+        let invalid = |reason: String| RegistryError::SignatureInvalid {
+            package: package.clone(),
+            version: version.to_string(),
+            reason,
+        };
+
+        let keys = registry.public_keys(package)?;
+        if keys.is_empty() && !required {
+            return Ok(());
+        }
+        let key = keys
+            .iter()
+            .find(|key| {
+                hex::decode(key)
+                    .map(|bytes| signing::key_id(&bytes) == signature.key_id)
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| invalid(format!("unknown signing key {}", signature.key_id)))?;
+
+        let payload = signing::signed_payload(package, version, &Self::calculate_checksum(data));
+        if !signing::verify(key, &payload, &signature.signature) {
+            return Err(invalid("signature does not match archive".to_string()));
+        }
+        Ok(())
+    }
+
     /// Extract tar.gz archive
     fn extract_archive(&self, data: &[u8], dest: &Path) -> RegistryResult<()> {
         // Create destination directory
@@ -130,6 +369,239 @@ mod tests {
         );
     }
 
+    /// Local registry holding one gzip'd tarball of `http` 1.0.0
+    fn signed_registry(root: &Path, key: &signing::SigningKey, sign: bool) -> crate::LocalRegistry {
+        let dir = root.join("http").join("1.0.0");
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        let content = b"export fn get() {}";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "lib.atlas", &content[..])
+            .unwrap();
+        let archive = builder.into_inner().unwrap().finish().unwrap();
+        fs::write(dir.join("package.tar.gz"), &archive).unwrap();
+
+        let checksum = Downloader::calculate_checksum(&archive);
+        let mut metadata = format!("checksum = \"{}\"\n", checksum);
+        if sign {
+            let signature = key.sign_package("http", &Version::new(1, 0, 0), &checksum);
+            metadata.push_str(&format!(
+                "\n[signature]\nkey-id = \"{}\"\nsignature = \"{}\"\n",
+                signature.key_id, signature.signature
+            ));
+        }
+        fs::write(dir.join("metadata.toml"), metadata).unwrap();
+        fs::write(
+            root.join("http").join("keys.toml"),
+            format!("keys = [\"{}\"]\n", key.public_key_hex()),
+        )
+        .unwrap();
+        crate::LocalRegistry::new(root.to_path_buf())
+    }
+
+    #[test]
+    fn test_signed_package_verifies() {
+        let temp_dir = TempDir::new().unwrap();
+        let key = signing::SigningKey::generate().unwrap();
+        let registry = signed_registry(&temp_dir.path().join("registry"), &key, true);
+        let downloader = Downloader::new(temp_dir.path().join("cache"))
+            .with_trust_policy(TrustPolicy::require_signatures());
+
+        let path = downloader
+            .download_package(&registry, "http", &Version::new(1, 0, 0))
+            .unwrap();
+        assert!(path.join("lib.atlas").exists());
+    }
+
+    #[test]
+    fn test_signature_from_unknown_key_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let key = signing::SigningKey::generate().unwrap();
+        let root = temp_dir.path().join("registry");
+        let registry = signed_registry(&root, &key, true);
+        // The registry now publishes a different key for the package
+        let other = signing::SigningKey::generate().unwrap();
+        fs::write(
+            root.join("http").join("keys.toml"),
+            format!("keys = [\"{}\"]\n", other.public_key_hex()),
+        )
+        .unwrap();
+
+        let result = Downloader::new(temp_dir.path().join("cache")).download_package(
+            &registry,
+            "http",
+            &Version::new(1, 0, 0),
+        );
+        assert!(matches!(
+            result,
+            Err(RegistryError::SignatureInvalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unsigned_package_obeys_trust_policy() {
+        let temp_dir = TempDir::new().unwrap();
+        let key = signing::SigningKey::generate().unwrap();
+        let registry = signed_registry(&temp_dir.path().join("registry"), &key, false);
+        let version = Version::new(1, 0, 0);
+
+        let permissive = Downloader::new(temp_dir.path().join("cache"));
+        assert!(permissive
+            .download_package(&registry, "http", &version)
+            .is_ok());
+
+        let strict = Downloader::new(temp_dir.path().join("strict"))
+            .with_trust_policy(TrustPolicy::require_signatures());
+        assert!(matches!(
+            strict.download_package(&registry, "http", &version),
+            Err(RegistryError::MissingSignature { .. })
+        ));
+    }
+
+    /// Add `name@version` (unsigned) to a local registry rooted at `root`;
+    /// returns the archive checksum
+    fn publish(root: &Path, name: &str, version: &str) -> String {
+        let dir = root.join(name).join(version);
+        fs::create_dir_all(&dir).unwrap();
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        let content = format!("export fn version(): string {{ return \"{}\"; }}", version);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "lib.atlas", content.as_bytes())
+            .unwrap();
+        let archive = builder.into_inner().unwrap().finish().unwrap();
+        fs::write(dir.join("package.tar.gz"), &archive).unwrap();
+        let checksum = Downloader::calculate_checksum(&archive);
+        fs::write(
+            dir.join("metadata.toml"),
+            format!("checksum = \"{}\"\n", checksum),
+        )
+        .unwrap();
+        checksum
+    }
+
+    #[test]
+    fn test_download_all_fetches_in_parallel() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("registry");
+        let names: Vec<String> = (0..12).map(|i| format!("pkg{}", i)).collect();
+        for name in &names {
+            publish(&root, name, "1.0.0");
+        }
+        let registry = crate::LocalRegistry::new(root);
+        let requests: Vec<DownloadRequest> = names
+            .iter()
+            .map(|name| DownloadRequest::new(name.clone(), Version::new(1, 0, 0)))
+            .collect();
+
+        let events = Mutex::new(Vec::new());
+        let outcomes = Downloader::new(temp_dir.path().join("cache"))
+            .with_parallelism(4)
+            .download_all(&registry, &requests, &|p| {
+                events
+                    .lock()
+                    .unwrap()
+                    .push((p.status, p.completed, p.total));
+            });
+
+        assert_eq!(outcomes.len(), 12);
+        for (outcome, name) in outcomes.iter().zip(&names) {
+            assert_eq!(&outcome.request.package, name);
+            assert!(outcome.result.as_ref().unwrap().join("lib.atlas").exists());
+        }
+        let events = events.into_inner().unwrap();
+        let finished: Vec<usize> = events
+            .iter()
+            .filter(|(status, _, _)| *status == DownloadStatus::Finished)
+            .map(|(_, completed, _)| *completed)
+            .collect();
+        assert_eq!(events.len(), 24);
+        assert_eq!(finished.iter().max(), Some(&12));
+        assert!(events.iter().all(|(_, _, total)| *total == 12));
+    }
+
+    #[test]
+    fn test_download_all_verifies_pinned_checksum() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("registry");
+        let good = publish(&root, "http", "1.0.0");
+        publish(&root, "json", "2.0.0");
+        let registry = crate::LocalRegistry::new(root);
+
+        let mut http = DownloadRequest::new("http", Version::new(1, 0, 0));
+        http.checksum = Some(good);
+        let mut json = DownloadRequest::new("json", Version::new(2, 0, 0));
+        json.checksum = Some("0".repeat(64));
+
+        let outcomes = Downloader::new(temp_dir.path().join("cache")).download_all(
+            &registry,
+            &[http, json],
+            &|_| {},
+        );
+        assert!(outcomes[0].result.is_ok());
+        assert!(matches!(
+            outcomes[1].result,
+            Err(RegistryError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_requests_from_workspace_lockfiles_are_deduplicated() {
+        use crate::lockfile::LockedPackage;
+        use std::collections::HashMap;
+
+        let locked = |name: &str, version: &str, source: LockedSource| LockedPackage {
+            name: name.to_string(),
+            version: version.parse().unwrap(),
+            source,
+            checksum: Some(format!("sum-{}", version)),
+            dependencies: HashMap::new(),
+        };
+        let registry = || LockedSource::Registry { registry: None };
+
+        let mut app = Lockfile::new();
+        app.add_package(locked("http", "1.2.0", registry()));
+        app.add_package(locked("json", "0.3.0", registry()));
+        let mut cli = Lockfile::new();
+        cli.add_package(locked("http", "1.2.0", registry()));
+        cli.add_package(locked("json", "0.4.0", registry()));
+        cli.add_package(locked(
+            "utils",
+            "0.1.0",
+            LockedSource::Path {
+                path: PathBuf::from("../utils"),
+            },
+        ));
+
+        let mut requests: Vec<String> = DownloadRequest::from_lockfiles([&app, &cli])
+            .iter()
+            .map(|r| format!("{}@{}", r.package, r.version))
+            .collect();
+        requests.sort();
+        assert_eq!(requests, ["http@1.2.0", "json@0.3.0", "json@0.4.0"]);
+    }
+
+    #[test]
+    fn test_parallelism_is_at_least_one() {
+        let temp_dir = TempDir::new().unwrap();
+        let downloader = Downloader::new(temp_dir.path().to_path_buf()).with_parallelism(0);
+        assert_eq!(downloader.parallelism(), 1);
+    }
+
     #[test]
     fn test_cache_dir_created() {
         let temp_dir = TempDir::new().unwrap();
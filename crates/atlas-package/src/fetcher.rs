@@ -246,7 +246,6 @@ fn compute_dir_checksum(dir: &Path) -> Result<String, FetchError> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Write;
     use tempfile::TempDir;
 
     // ── url_to_cache_subpath ──────────────────────────────────────────────────
@@ -1,19 +1,26 @@
 //! Package installer for Atlas.
 //!
-//! Reads `atlas.toml`, fetches all dependencies via git (or resolves path deps),
-//! and writes `atlas.lock`.
+//! Reads `atlas.toml`, fetches all dependencies via git or the configured
+//! registry (or resolves path deps), and writes `atlas.lock`.
 
+use crate::downloader::{DownloadRequest, Downloader};
 use crate::fetcher::GitFetcher;
 use crate::lockfile::{LockedPackage, LockedSource, Lockfile};
-use crate::manifest::{Dependency, DetailedDependency, PackageManifest};
+use crate::manifest::{req_matches, Dependency, DetailedDependency, PackageManifest};
+use crate::registry::Registry;
+use crate::resolver::ResolutionMode;
+use crate::target::BuildContext;
 use crate::{PackageError, Result};
-use std::collections::HashSet;
-use std::path::PathBuf;
+use semver::{Version, VersionReq};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 /// Installer: resolves and fetches all dependencies in an `atlas.toml`.
 pub struct Installer {
     cache_dir: PathBuf,
     project_dir: PathBuf,
+    context: BuildContext,
+    registry: Option<Box<dyn Registry>>,
 }
 
 /// High-level description of what `install()` would do, without executing it.
@@ -72,9 +79,25 @@ impl Installer {
         Self {
             cache_dir,
             project_dir,
+            context: BuildContext::default(),
+            registry: None,
         }
     }
 
+    /// Only install the dependency sections that apply to `context`
+    /// (defaults to a development build for the host platform).
+    pub fn with_context(mut self, context: BuildContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Fetch registry dependencies from `registry`. Without one, a manifest
+    /// with registry deps fails to install.
+    pub fn with_registry(mut self, registry: Box<dyn Registry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
     /// Build an install plan without fetching anything.
     pub fn plan(&self, manifest: &PackageManifest) -> Result<InstallPlan> {
         let fetcher = GitFetcher::new(self.cache_dir.clone());
@@ -82,11 +105,7 @@ impl Installer {
         let mut to_fetch = Vec::new();
         let mut already_cached = Vec::new();
 
-        for (name, dep) in manifest
-            .dependencies
-            .iter()
-            .chain(manifest.dev_dependencies.iter())
-        {
+        for (name, dep) in &manifest.dependencies_for(&self.context) {
             match dep_kind(dep) {
                 DepKind::Git { url, tag } => {
                     let source = format!("git: {}@{}", url, tag);
@@ -108,7 +127,7 @@ impl Installer {
                 DepKind::Registry => {
                     to_fetch.push(PlannedFetch {
                         name: name.clone(),
-                        source: "registry: (not yet supported)".to_string(),
+                        source: format!("registry: {}", registry_req(dep).unwrap_or("*")),
                     });
                 }
             }
@@ -126,6 +145,7 @@ impl Installer {
     /// If a valid, up-to-date lockfile already exists and `force` is false, the
     /// lockfile is reused and only missing cache entries are populated.
     pub fn install(&self, manifest: &PackageManifest, force: bool) -> Result<Lockfile> {
+        self.check_trust_policy(manifest)?;
         let lockfile_path = self.project_dir.join("atlas.lock");
 
         // Try reusing an existing lockfile.
@@ -133,7 +153,7 @@ impl Installer {
             if let Ok(existing) = Lockfile::from_file(&lockfile_path) {
                 if self.lockfile_is_fresh(&existing, manifest) {
                     // Ensure cache is populated for every locked entry.
-                    self.populate_cache_from_lockfile(&existing)?;
+                    self.populate_cache_from_lockfile(&existing, manifest)?;
                     return Ok(existing);
                 }
             }
@@ -143,15 +163,9 @@ impl Installer {
         let mut lockfile = Lockfile::new();
         let fetcher = GitFetcher::new(self.cache_dir.clone());
 
-        // Merge regular + dev deps (dev deps get the same treatment for now).
-        let all_deps: Vec<(&String, &Dependency)> = manifest
-            .dependencies
-            .iter()
-            .chain(manifest.dev_dependencies.iter())
-            .collect();
-
-        for (name, dep) in all_deps {
-            let locked = self.resolve_dep(name, dep, &fetcher)?;
+        for (name, dep) in &manifest.dependencies_for(&self.context) {
+            let locked =
+                self.resolve_dep(name, dep, ResolutionMode::Highest, manifest, &fetcher)?;
             lockfile.add_package(locked);
         }
 
@@ -159,24 +173,181 @@ impl Installer {
         Ok(lockfile)
     }
 
+    /// Resolve every dependency afresh and return the lockfile, without
+    /// writing it.
+    ///
+    /// Git deps that give a `version` requirement instead of a fixed tag, rev
+    /// or branch are matched against the repository's semver tags; `mode`
+    /// decides whether the highest or the lowest matching tag is locked. The
+    /// same goes for registry deps and the versions the registry publishes.
+    pub fn resolve(&self, manifest: &PackageManifest, mode: ResolutionMode) -> Result<Lockfile> {
+        self.check_trust_policy(manifest)?;
+        let mut lockfile = Lockfile::new();
+        let fetcher = GitFetcher::new(self.cache_dir.clone());
+
+        for (name, dep) in &manifest.dependencies_for(&self.context) {
+            let locked = match dep {
+                Dependency::Detailed(d) if tracks_version_tags(d) => {
+                    self.resolve_git_version(name, d, mode, &fetcher)?
+                }
+                _ => self.resolve_dep(name, dep, mode, manifest, &fetcher)?,
+            };
+            lockfile.add_package(locked);
+        }
+
+        Ok(lockfile)
+    }
+
     // ── private helpers ───────────────────────────────────────────────────────
 
-    /// Returns `true` when every dep name in `manifest` appears in `lockfile`.
+    /// Pick a registry version matching the dep's requirement and download it,
+    /// verifying its signature under the manifest's `[trust]` policy.
+    fn resolve_registry_dep(
+        &self,
+        name: &str,
+        dep: &Dependency,
+        mode: ResolutionMode,
+        manifest: &PackageManifest,
+    ) -> Result<LockedPackage> {
+        let registry = self.registry()?;
+        let field = || format!("dependencies.{}.version", name);
+        let req = registry_req(dep)
+            .unwrap_or("*")
+            .parse::<VersionReq>()
+            .map_err(|e| PackageError::InvalidField {
+                field: field(),
+                reason: e.to_string(),
+            })?;
+        let allow_pre = match dep {
+            Dependency::Detailed(d) => d.channel.unwrap_or_default().allows_prerelease(),
+            Dependency::Simple(_) => false,
+        };
+
+        let candidates = registry
+            .query_index(name)?
+            .into_iter()
+            .filter(|entry| !entry.yanked && req_matches(&req, &entry.version, allow_pre))
+            .map(|entry| entry.version);
+        let version = match mode {
+            ResolutionMode::Highest => candidates.max(),
+            ResolutionMode::Minimal => candidates.min(),
+        }
+        .ok_or_else(|| PackageError::InvalidField {
+            field: field(),
+            reason: format!("no published version of {} satisfies {}", name, req),
+        })?;
+
+        let path = self
+            .downloader(manifest)
+            .download_package(registry, name, &version)?;
+        let checksum = registry.get_metadata(name, &version)?.checksum;
+        Ok(LockedPackage {
+            name: name.to_string(),
+            version,
+            source: LockedSource::Registry { registry: None },
+            checksum: (!checksum.is_empty()).then_some(checksum),
+            dependencies: locked_dependencies(&path),
+        })
+    }
+
+    fn registry(&self) -> Result<&dyn Registry> {
+        self.registry.as_deref().ok_or_else(|| {
+            PackageError::Unsupported(
+                "registry deps need a configured registry — use git deps or set one".to_string(),
+            )
+        })
+    }
+
+    /// Registry downloads land under `<cache>/registry/<name>/<version>/`.
+    fn downloader(&self, manifest: &PackageManifest) -> Downloader {
+        Downloader::new(self.cache_dir.join("registry"))
+            .with_trust_policy(manifest.trust.clone().unwrap_or_default())
+    }
+
+    /// Pick a tag for a git dep from its `version` requirement and fetch it.
+    fn resolve_git_version(
+        &self,
+        name: &str,
+        dep: &DetailedDependency,
+        mode: ResolutionMode,
+        fetcher: &GitFetcher,
+    ) -> Result<LockedPackage> {
+        let (Some(url), Some(version)) = (&dep.git, &dep.version) else {
+            unreachable!("checked by tracks_version_tags");
+        };
+        let req = version
+            .parse::<VersionReq>()
+            .map_err(|e| PackageError::InvalidField {
+                field: format!("dependencies.{}.version", name),
+                reason: e.to_string(),
+            })?;
+
+        let tags = fetcher.list_remote_tags(url)?;
+        let allow_pre = dep.channel.unwrap_or_default().allows_prerelease();
+        let Some((tag, version)) = select_tag(&tags, &req, mode, allow_pre) else {
+            return Err(PackageError::InvalidField {
+                field: format!("dependencies.{}.version", name),
+                reason: format!("no tag in {} satisfies {}", url, req),
+            });
+        };
+
+        let result = fetcher.fetch(name, url, &tag)?;
+        Ok(LockedPackage {
+            name: name.to_string(),
+            version,
+            source: LockedSource::Git {
+                url: url.clone(),
+                rev: result.rev,
+                tag: Some(tag),
+            },
+            checksum: Some(result.checksum),
+            dependencies: locked_dependencies(&result.path),
+        })
+    }
+
+    /// Returns `true` when every dep this build needs appears in `lockfile`
+    /// and every locked package is still declared somewhere in `manifest`.
+    ///
+    /// A lockfile written by a development install stays valid for a
+    /// production install, which just skips the dev-only entries.
     fn lockfile_is_fresh(&self, lockfile: &Lockfile, manifest: &PackageManifest) -> bool {
         let locked_names: HashSet<&str> =
             lockfile.packages.iter().map(|p| p.name.as_str()).collect();
-        let manifest_names: HashSet<&str> = manifest
-            .dependencies
+        let needed = manifest.dependencies_for(&self.context);
+        needed
             .keys()
-            .chain(manifest.dev_dependencies.keys())
-            .map(|s| s.as_str())
-            .collect();
-        manifest_names == locked_names
+            .all(|name| locked_names.contains(name.as_str()))
+            && locked_names
+                .iter()
+                .all(|name| manifest.find_dependency(name).is_some())
     }
 
     /// For a valid cached lockfile, ensure git deps are still present in the
-    /// local cache (someone might have cleared it).
-    fn populate_cache_from_lockfile(&self, lockfile: &Lockfile) -> Result<()> {
+    /// local cache (someone might have cleared it). Missing registry packages
+    /// are downloaded again, pinned to their locked checksum.
+    fn populate_cache_from_lockfile(
+        &self,
+        lockfile: &Lockfile,
+        manifest: &PackageManifest,
+    ) -> Result<()> {
+        let downloader = self.downloader(manifest);
+        let missing: Vec<DownloadRequest> = DownloadRequest::from_lockfiles([lockfile])
+            .into_iter()
+            .filter(|request| {
+                !downloader
+                    .cache_dir()
+                    .join(&request.package)
+                    .join(request.version.to_string())
+                    .exists()
+            })
+            .collect();
+        if !missing.is_empty() {
+            let registry = self.registry()?;
+            for outcome in downloader.download_all(registry, &missing, &|_| {}) {
+                outcome.result?;
+            }
+        }
+
         let fetcher = GitFetcher::new(self.cache_dir.clone());
         for pkg in &lockfile.packages {
             if let LockedSource::Git { url, rev: _, tag } = &pkg.source {
@@ -191,11 +362,44 @@ impl Installer {
         Ok(())
     }
 
+    /// Enforce `[trust] require-signatures`.
+    ///
+    /// Signatures are published per registry release, so a git dependency has
+    /// nothing to verify and is refused. Registry deps are checked by the
+    /// `Downloader` as they are fetched. Path dependencies are local source
+    /// and always allowed.
+    fn check_trust_policy(&self, manifest: &PackageManifest) -> Result<()> {
+        let required = manifest
+            .trust
+            .as_ref()
+            .is_some_and(|trust| trust.require_signatures);
+        if !required {
+            return Ok(());
+        }
+
+        let mut unverifiable: Vec<String> = manifest
+            .dependencies_for(&self.context)
+            .iter()
+            .filter(|(_, dep)| matches!(dep_kind(dep), DepKind::Git { .. }))
+            .map(|(name, _)| name.clone())
+            .collect();
+        if unverifiable.is_empty() {
+            return Ok(());
+        }
+        unverifiable.sort();
+        Err(PackageError::Untrusted(format!(
+            "require-signatures is set but git dependencies cannot be signature-verified: {}",
+            unverifiable.join(", ")
+        )))
+    }
+
     /// Resolve a single dependency to a `LockedPackage`.
     fn resolve_dep(
         &self,
         name: &str,
         dep: &Dependency,
+        mode: ResolutionMode,
+        manifest: &PackageManifest,
         fetcher: &GitFetcher,
     ) -> Result<LockedPackage> {
         match dep_kind(dep) {
@@ -212,7 +416,7 @@ impl Installer {
                         tag: Some(tag.clone()),
                     },
                     checksum: Some(result.checksum),
-                    dependencies: Default::default(),
+                    dependencies: locked_dependencies(&result.path),
                 })
             }
             DepKind::Path(relative) => {
@@ -233,18 +437,67 @@ impl Installer {
                 Ok(LockedPackage {
                     name: name.to_string(),
                     version,
+                    dependencies: locked_dependencies(&abs),
                     source: LockedSource::Path { path: abs },
                     checksum: None,
-                    dependencies: Default::default(),
                 })
             }
-            DepKind::Registry => Err(PackageError::Unsupported(
-                "registry deps require a community index — use git deps for now".to_string(),
-            )),
+            DepKind::Registry => self.resolve_registry_dep(name, dep, mode, manifest),
         }
     }
 }
 
+/// Versions a fetched package was built against, from its own `atlas.lock`.
+///
+/// These become the edges of the locked graph that the duplicate-version
+/// report walks. A package without a lockfile contributes no edges.
+fn locked_dependencies(package_dir: &Path) -> HashMap<String, Version> {
+    Lockfile::from_file(&package_dir.join("atlas.lock"))
+        .map(|lockfile| {
+            lockfile
+                .packages
+                .into_iter()
+                .map(|p| (p.name, p.version))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The version requirement of a registry dep, if it gives one.
+fn registry_req(dep: &Dependency) -> Option<&str> {
+    match dep {
+        Dependency::Simple(v) => Some(v.as_str()),
+        Dependency::Detailed(d) => d.version.as_deref(),
+    }
+}
+
+/// A git dep pinned only by a `version` requirement (no tag, rev or branch).
+fn tracks_version_tags(d: &DetailedDependency) -> bool {
+    d.path.is_none()
+        && d.git.is_some()
+        && d.version.is_some()
+        && d.tag.is_none()
+        && d.rev.is_none()
+        && d.branch.is_none()
+}
+
+/// Choose the highest or lowest semver tag (`v` prefix optional) matching `req`.
+fn select_tag(
+    tags: &[String],
+    req: &VersionReq,
+    mode: ResolutionMode,
+    allow_pre: bool,
+) -> Option<(String, Version)> {
+    let candidates = tags.iter().filter_map(|tag| {
+        let version = Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()?;
+        req_matches(req, &version, allow_pre).then(|| (tag.clone(), version))
+    });
+    match mode {
+        ResolutionMode::Highest => candidates.max_by(|a, b| a.1.cmp(&b.1)),
+        ResolutionMode::Minimal => candidates.min_by(|a, b| a.1.cmp(&b.1)),
+    }
+}
+
 /// Extract semver from a dep, or return 0.0.0 when unavailable (path/git deps).
 fn dep_version_or_zero(dep: &Dependency) -> semver::Version {
     let s = match dep {
@@ -369,6 +622,32 @@ local-lib = {{ path = "{}" }}
         assert!(plan.already_cached.is_empty());
     }
 
+    #[test]
+    fn test_production_plan_skips_dev_dependencies() {
+        let project = TempDir::new().expect("tempdir");
+        let cache = TempDir::new().expect("tempdir");
+
+        let toml = make_manifest_toml(
+            r#"web = { git = "https://github.com/org/web", tag = "v1.0.0" }
+
+[dev-dependencies]
+mocks = { git = "https://github.com/org/mocks", tag = "v0.1.0" }"#,
+        );
+        write_manifest(project.path(), &toml);
+
+        let manifest =
+            PackageManifest::from_file(&project.path().join("atlas.toml")).expect("parse manifest");
+        let installer = Installer::new(cache.path().to_path_buf(), project.path().to_path_buf());
+        assert_eq!(installer.plan(&manifest).expect("plan").to_fetch.len(), 2);
+
+        let installer = installer.with_context(BuildContext::production(
+            crate::target::TargetPlatform::host(),
+        ));
+        let plan = installer.plan(&manifest).expect("plan");
+        assert_eq!(plan.to_fetch.len(), 1);
+        assert_eq!(plan.to_fetch[0].name, "web");
+    }
+
     // ── test_lockfile_reused_when_valid ───────────────────────────────────────
 
     #[test]
@@ -424,6 +703,71 @@ utils = {{ path = "{}" }}
         assert_eq!(mtime1, mtime2, "lockfile should not be rewritten");
     }
 
+    // ── test_select_tag_modes ────────────────────────────────────────────────
+
+    #[test]
+    fn test_select_tag_modes() {
+        let tags: Vec<String> = [
+            "v1.0.0",
+            "v1.2.0",
+            "1.4.0",
+            "v2.0.0",
+            "v1.5.0-rc.1",
+            "latest",
+        ]
+        .iter()
+        .map(|t| t.to_string())
+        .collect();
+        let req: VersionReq = "^1.1".parse().unwrap();
+
+        let (tag, _) = select_tag(&tags, &req, ResolutionMode::Highest, false).unwrap();
+        assert_eq!(tag, "1.4.0");
+        let (tag, version) = select_tag(&tags, &req, ResolutionMode::Minimal, false).unwrap();
+        assert_eq!(tag, "v1.2.0");
+        assert_eq!(version, Version::new(1, 2, 0));
+
+        let (tag, _) = select_tag(&tags, &req, ResolutionMode::Highest, true).unwrap();
+        assert_eq!(tag, "v1.5.0-rc.1");
+        assert!(select_tag(
+            &tags,
+            &"^3".parse().unwrap(),
+            ResolutionMode::Minimal,
+            false
+        )
+        .is_none());
+    }
+
+    // ── test_resolve_does_not_write_lockfile ─────────────────────────────────
+
+    #[test]
+    fn test_resolve_does_not_write_lockfile() {
+        let project = TempDir::new().expect("tempdir");
+        let cache = TempDir::new().expect("tempdir");
+
+        let sibling = TempDir::new().expect("tempdir");
+        let toml = format!(
+            r#"[package]
+name = "test-pkg"
+version = "0.1.0"
+
+[dependencies]
+utils = {{ path = "{}" }}
+"#,
+            sibling.path().display()
+        );
+        write_manifest(project.path(), &toml);
+
+        let manifest =
+            PackageManifest::from_file(&project.path().join("atlas.toml")).expect("parse manifest");
+        let installer = Installer::new(cache.path().to_path_buf(), project.path().to_path_buf());
+
+        let lockfile = installer
+            .resolve(&manifest, ResolutionMode::Minimal)
+            .expect("resolve");
+        assert!(lockfile.get_package("utils").is_some());
+        assert!(!project.path().join("atlas.lock").exists());
+    }
+
     // ── test_registry_dep_returns_error ──────────────────────────────────────
 
     #[test]
@@ -444,8 +788,196 @@ utils = {{ path = "{}" }}
             .expect_err("registry dep must fail");
         let msg = err.to_string();
         assert!(
-            msg.contains("registry") || msg.contains("community index"),
+            msg.contains("configured registry"),
             "error should mention registry, got: {msg}"
         );
     }
+
+    #[test]
+    fn test_require_signatures_refuses_git_deps() {
+        let project = TempDir::new().expect("tempdir");
+        let cache = TempDir::new().expect("tempdir");
+        std::fs::create_dir_all(project.path().join("local")).expect("mkdir");
+
+        let toml = make_manifest_toml(
+            r#"web = { git = "https://github.com/org/web", tag = "v1.0.0" }
+local = { path = "local" }
+
+[trust]
+require-signatures = true"#,
+        );
+        write_manifest(project.path(), &toml);
+
+        let manifest =
+            PackageManifest::from_file(&project.path().join("atlas.toml")).expect("parse manifest");
+        let installer = Installer::new(cache.path().to_path_buf(), project.path().to_path_buf());
+        let err = installer
+            .install(&manifest, false)
+            .expect_err("git dep must be refused");
+        assert!(matches!(err, PackageError::Untrusted(ref msg) if msg.contains("web")));
+        assert!(!err.to_string().contains("local"), "{err}");
+        assert!(!project.path().join("atlas.lock").exists());
+    }
+
+    /// Publish `name@version` to a local registry at `root`, signed by `key`
+    /// when given.
+    fn publish(root: &Path, name: &str, version: &str, key: Option<&crate::SigningKey>) {
+        let dir = root.join(name).join(version);
+        fs::create_dir_all(&dir).expect("mkdir");
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        let content = b"export fn get() {}";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "lib.atlas", &content[..])
+            .expect("append");
+        let archive = builder.into_inner().unwrap().finish().unwrap();
+        fs::write(dir.join("package.tar.gz"), &archive).expect("write archive");
+
+        let checksum = Downloader::calculate_checksum(&archive);
+        let mut metadata = format!("checksum = \"{}\"\n", checksum);
+        if let Some(key) = key {
+            let signature = key.sign_package(name, &version.parse().unwrap(), &checksum);
+            metadata.push_str(&format!(
+                "\n[signature]\nkey-id = \"{}\"\nsignature = \"{}\"\n",
+                signature.key_id, signature.signature
+            ));
+            fs::write(
+                root.join(name).join("keys.toml"),
+                format!("keys = [\"{}\"]\n", key.public_key_hex()),
+            )
+            .expect("write keys");
+        }
+        fs::write(dir.join("metadata.toml"), metadata).expect("write metadata");
+    }
+
+    fn registry_installer(cache: &Path, project: &Path, root: &Path) -> Installer {
+        Installer::new(cache.to_path_buf(), project.to_path_buf())
+            .with_registry(Box::new(crate::LocalRegistry::new(root.to_path_buf())))
+    }
+
+    #[test]
+    fn test_registry_dep_installs_signed_package() {
+        let project = TempDir::new().expect("tempdir");
+        let cache = TempDir::new().expect("tempdir");
+        let registry = TempDir::new().expect("tempdir");
+        let key = crate::SigningKey::generate().expect("key");
+        for version in ["1.0.0", "1.2.0", "2.0.0"] {
+            publish(registry.path(), "http", version, Some(&key));
+        }
+        write_manifest(
+            project.path(),
+            &make_manifest_toml("http = \"^1.0\"\n\n[trust]\nrequire-signatures = true"),
+        );
+        let manifest =
+            PackageManifest::from_file(&project.path().join("atlas.toml")).expect("parse manifest");
+        let installer = registry_installer(cache.path(), project.path(), registry.path());
+
+        let lockfile = installer.install(&manifest, false).expect("install");
+        let locked = lockfile.get_package("http").expect("http locked");
+        assert_eq!(locked.version, Version::new(1, 2, 0));
+        assert!(matches!(locked.source, LockedSource::Registry { .. }));
+        assert!(locked.checksum.is_some());
+        assert!(cache.path().join("registry/http/1.2.0/lib.atlas").exists());
+
+        let minimal = installer
+            .resolve(&manifest, ResolutionMode::Minimal)
+            .expect("resolve");
+        assert_eq!(
+            minimal.get_package("http").expect("http").version,
+            Version::new(1, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_require_signatures_refuses_unsigned_registry_dep() {
+        let project = TempDir::new().expect("tempdir");
+        let cache = TempDir::new().expect("tempdir");
+        let registry = TempDir::new().expect("tempdir");
+        publish(registry.path(), "http", "1.0.0", None);
+        let toml = make_manifest_toml("http = \"1.0.0\"");
+        write_manifest(project.path(), &toml);
+        let manifest =
+            PackageManifest::from_file(&project.path().join("atlas.toml")).expect("parse manifest");
+        let installer = registry_installer(cache.path(), project.path(), registry.path());
+        installer
+            .install(&manifest, false)
+            .expect("unsigned package installs without [trust]");
+
+        write_manifest(
+            project.path(),
+            &format!("{}\n\n[trust]\nrequire-signatures = true\n", toml),
+        );
+        let manifest =
+            PackageManifest::from_file(&project.path().join("atlas.toml")).expect("parse manifest");
+        let err = installer
+            .install(&manifest, true)
+            .expect_err("unsigned package must be refused");
+        assert!(matches!(
+            err,
+            PackageError::Registry(crate::RegistryError::MissingSignature { .. })
+        ));
+    }
+
+    #[test]
+    fn test_cleared_registry_cache_is_refetched_from_lockfile() {
+        let project = TempDir::new().expect("tempdir");
+        let cache = TempDir::new().expect("tempdir");
+        let registry = TempDir::new().expect("tempdir");
+        publish(registry.path(), "http", "1.0.0", None);
+        write_manifest(project.path(), &make_manifest_toml("http = \"1.0.0\""));
+        let manifest =
+            PackageManifest::from_file(&project.path().join("atlas.toml")).expect("parse manifest");
+        let installer = registry_installer(cache.path(), project.path(), registry.path());
+
+        installer.install(&manifest, false).expect("first install");
+        let package_dir = cache.path().join("registry/http/1.0.0");
+        fs::remove_dir_all(&package_dir).expect("clear cache");
+        installer.install(&manifest, false).expect("second install");
+        assert!(package_dir.join("lib.atlas").exists());
+    }
+
+    #[test]
+    fn test_path_dep_lockfile_becomes_locked_edges() {
+        let project = TempDir::new().expect("tempdir");
+        let cache = TempDir::new().expect("tempdir");
+        let lib = project.path().join("lib");
+        fs::create_dir_all(&lib).expect("mkdir");
+        fs::write(
+            lib.join("atlas.toml"),
+            "[package]\nname = \"lib\"\nversion = \"0.1.0\"\n",
+        )
+        .expect("write lib manifest");
+        let mut lib_lock = Lockfile::new();
+        lib_lock.add_package(LockedPackage {
+            name: "json".to_string(),
+            version: Version::new(1, 4, 0),
+            source: LockedSource::Registry { registry: None },
+            checksum: None,
+            dependencies: Default::default(),
+        });
+        lib_lock
+            .write_to_file(&lib.join("atlas.lock"))
+            .expect("write lib lock");
+
+        write_manifest(
+            project.path(),
+            &make_manifest_toml(r#"lib = { path = "lib" }"#),
+        );
+        let manifest =
+            PackageManifest::from_file(&project.path().join("atlas.toml")).expect("parse manifest");
+        let installer = Installer::new(cache.path().to_path_buf(), project.path().to_path_buf());
+
+        let lockfile = installer.install(&manifest, false).expect("install");
+        let locked = lockfile.get_package("lib").expect("lib locked");
+        assert_eq!(
+            locked.dependencies.get("json"),
+            Some(&Version::new(1, 4, 0))
+        );
+    }
 }
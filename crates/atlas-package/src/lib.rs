@@ -13,25 +13,37 @@ pub mod lockfile;
 pub mod manifest;
 pub mod registry;
 pub mod resolver;
+pub mod signing;
+pub mod target;
 pub mod validator;
 
 pub use build_order::{BuildOrderComputer, BuildOrderError, BuildOrderResult};
 pub use cache::PackageCache;
-pub use downloader::Downloader;
+pub use downloader::{
+    DownloadOutcome, DownloadProgress, DownloadRequest, DownloadStatus, Downloader,
+};
 pub use fetcher::{url_to_cache_subpath, FetchError, FetchResult, GitFetcher};
 pub use installer::{InstallPlan, Installer, PlannedFetch};
-pub use lockfile::{LockedPackage, LockedSource, Lockfile, LockfileMetadata};
+pub use lockfile::{
+    LockChange, LockedPackage, LockedSource, Lockfile, LockfileDiff, LockfileMetadata,
+};
 pub use manifest::{
-    Dependency, DependencySource, Feature, PackageManifest, VersionConstraint, Workspace,
+    Assets, Dependency, DependencySource, Feature, LintLevel, Lints, PackageManifest,
+    ReleaseChannel, TargetDependencies, VersionConstraint, Workspace,
 };
 pub use registry::{
-    LocalRegistry, PackageMetadata, Registry, RegistryError, RegistryManager, RegistryResult,
-    RemoteRegistry,
+    CachePolicy, LocalRegistry, MetadataCache, PackageInfo, PackageMetadata, PackageUpload,
+    Registry, RegistryError, RegistryManager, RegistryResult, RemoteRegistry, SearchHit,
+    SearchQuery, SearchResults, VersionEntry,
 };
 pub use resolver::{
-    Conflict, ConflictResolver, ConflictingConstraint, DependencyGraph, Resolution,
-    ResolvedPackage, Resolver, ResolverError, ResolverResult, VersionSolver,
+    Conflict, ConflictResolver, ConflictingConstraint, DependencyGraph, DuplicateLint,
+    DuplicatePackage, DuplicateReport, DuplicateVersion, LockfileUpdate, RejectedVersion,
+    Resolution, ResolutionExplanation, ResolutionMode, ResolvedPackage, Resolver, ResolverError,
+    ResolverResult, UpdateMode, VersionSolver,
 };
+pub use signing::{CredentialsStore, PackageSignature, SigningError, SigningKey, TrustPolicy};
+pub use target::{BuildContext, CfgExpr, DependencyKind, TargetPlatform};
 pub use validator::{ValidationError, Validator};
 
 /// Package management errors
@@ -64,8 +76,14 @@ pub enum PackageError {
     #[error("Fetch error: {0}")]
     Fetch(#[from] fetcher::FetchError),
 
+    #[error("Registry error: {0}")]
+    Registry(#[from] registry::RegistryError),
+
     #[error("Unsupported: {0}")]
     Unsupported(String),
+
+    #[error("Untrusted dependency: {0}")]
+    Untrusted(String),
 }
 
 pub type Result<T> = std::result::Result<T, PackageError>;
@@ -47,9 +47,12 @@ impl Lockfile {
     }
 
     /// Write lockfile to file
+    ///
+    /// The write is atomic (temp file + fsync + rename): a crash mid-write leaves
+    /// the previous lockfile intact instead of a truncated one.
     pub fn write_to_file(&self, path: &Path) -> crate::Result<()> {
         let content = self.to_string()?;
-        std::fs::write(path, content)?;
+        write_atomic(path, content.as_bytes())?;
         Ok(())
     }
 
@@ -94,6 +97,32 @@ impl Lockfile {
 
         Ok(())
     }
+
+    /// Packages whose locked version differs in `updated`, sorted by name
+    pub fn diff(&self, updated: &Lockfile) -> LockfileDiff {
+        let mut names: Vec<&str> = self
+            .packages
+            .iter()
+            .chain(&updated.packages)
+            .map(|p| p.name.as_str())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+
+        let changes = names
+            .into_iter()
+            .filter_map(|name| {
+                let old = self.get_package(name).map(|p| p.version.clone());
+                let new = updated.get_package(name).map(|p| p.version.clone());
+                (old != new).then(|| LockChange {
+                    name: name.to_string(),
+                    old,
+                    new,
+                })
+            })
+            .collect();
+        LockfileDiff { changes }
+    }
 }
 
 impl Default for Lockfile {
@@ -157,6 +186,113 @@ pub struct LockfileMetadata {
     pub atlas_version: Option<String>,
 }
 
+/// Changed locked versions between two lockfiles
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LockfileDiff {
+    pub changes: Vec<LockChange>,
+}
+
+impl LockfileDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// One line per change, e.g. `Updating http v1.2.0 -> v1.3.0`
+    pub fn report(&self) -> String {
+        let mut report = String::new();
+        for change in &self.changes {
+            report.push_str(&change.describe());
+            report.push('\n');
+        }
+        report
+    }
+}
+
+/// A package added to, removed from, or moved to another version in the lockfile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockChange {
+    pub name: String,
+    /// Previously locked version (`None` when added)
+    pub old: Option<semver::Version>,
+    /// Newly locked version (`None` when removed)
+    pub new: Option<semver::Version>,
+}
+
+impl LockChange {
+    /// Whether the new version is semver-incompatible with the old one
+    pub fn is_breaking(&self) -> bool {
+        use crate::resolver::duplicates::compatibility_class;
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => compatibility_class(old) != compatibility_class(new),
+            _ => false,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match (&self.old, &self.new) {
+            (None, Some(new)) => format!("Adding {} v{}", self.name, new),
+            (Some(old), None) => format!("Removing {} v{}", self.name, old),
+            (Some(old), Some(new)) => {
+                let verb = if new > old { "Updating" } else { "Downgrading" };
+                let breaking = if self.is_breaking() {
+                    " (breaking)"
+                } else {
+                    ""
+                };
+                format!("{} {} v{} -> v{}{}", verb, self.name, old, new, breaking)
+            }
+            (None, None) => format!("Unchanged {}", self.name),
+        }
+    }
+}
+
+/// Atomically replace `path` with `contents`, syncing data and directory entry.
+///
+/// The temp file name is unique per process and call, and is created with
+/// `create_new`, so concurrent writers never share or clobber a temp file.
+fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    let file_name = path.file_name().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("'{}' has no file name", path.display()),
+        )
+    })?;
+    let tmp_path = parent.join(format!(
+        ".{}.{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let result = (|| {
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        drop(file);
+        std::fs::rename(&tmp_path, path)?;
+        #[cfg(unix)]
+        std::fs::File::open(parent)?.sync_all()?;
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp_path);
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,6 +304,43 @@ mod tests {
         assert!(lockfile.packages.is_empty());
     }
 
+    fn locked(name: &str, version: &str) -> LockedPackage {
+        LockedPackage {
+            name: name.to_string(),
+            version: version.parse().unwrap(),
+            source: LockedSource::Registry { registry: None },
+            checksum: None,
+            dependencies: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff_lists_added_removed_and_moved_packages() {
+        let mut old = Lockfile::new();
+        old.add_package(locked("http", "1.2.0"));
+        old.add_package(locked("json", "0.3.1"));
+        old.add_package(locked("log", "1.0.0"));
+        let mut new = Lockfile::new();
+        new.add_package(locked("http", "1.3.0"));
+        new.add_package(locked("json", "0.4.0"));
+        new.add_package(locked("log", "1.0.0"));
+        new.add_package(locked("tls", "2.0.0"));
+
+        let diff = old.diff(&new);
+        let names: Vec<&str> = diff.changes.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["http", "json", "tls"]);
+        assert!(!diff.changes[0].is_breaking());
+        assert!(diff.changes[1].is_breaking());
+        assert_eq!(
+            diff.report(),
+            "Updating http v1.2.0 -> v1.3.0\n\
+             Updating json v0.3.1 -> v0.4.0 (breaking)\n\
+             Adding tls v2.0.0\n"
+        );
+        assert!(new.diff(&new).is_empty());
+        assert_eq!(new.diff(&old).changes[2].describe(), "Removing tls v2.0.0");
+    }
+
     #[test]
     fn test_add_package() {
         let mut lockfile = Lockfile::new();
@@ -306,4 +479,51 @@ mod tests {
         assert!(toml.contains("type = \"path\""));
         assert!(toml.contains("path = \"../local-package\""));
     }
+
+    #[test]
+    fn test_write_to_file_replaces_atomically() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("atlas.lock");
+        std::fs::write(&path, "stale").unwrap();
+
+        let mut lockfile = Lockfile::new();
+        lockfile.add_package(LockedPackage {
+            name: "pkg".to_string(),
+            version: semver::Version::new(0, 1, 0),
+            source: LockedSource::Registry { registry: None },
+            checksum: None,
+            dependencies: HashMap::new(),
+        });
+        lockfile.write_to_file(&path).unwrap();
+
+        assert_eq!(Lockfile::from_file(&path).unwrap(), lockfile);
+        // Only the lockfile remains — no leftover temp files
+        let entries: Vec<_> = std::fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn test_concurrent_writes_use_separate_temp_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("atlas.lock");
+
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let mut lockfile = Lockfile::new();
+                    lockfile.add_package(locked(&format!("pkg{}", i), "1.0.0"));
+                    lockfile.write_to_file(&path)
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap().unwrap();
+        }
+
+        // Whichever write landed last, the file is a whole lockfile
+        assert_eq!(Lockfile::from_file(&path).unwrap().packages.len(), 1);
+        let entries: Vec<_> = std::fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+    }
 }
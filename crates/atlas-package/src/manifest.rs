@@ -1,5 +1,7 @@
 //! Package manifest parsing and types (atlas.toml)
 
+use crate::signing::TrustPolicy;
+use crate::target::{BuildContext, DependencyKind};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -26,6 +28,15 @@ pub struct PackageManifest {
     pub dependencies: HashMap<String, Dependency>,
     #[serde(default, rename = "dev-dependencies")]
     pub dev_dependencies: HashMap<String, Dependency>,
+    #[serde(
+        default,
+        rename = "build-dependencies",
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub build_dependencies: HashMap<String, Dependency>,
+    /// Platform-specific sections keyed by `cfg(...)`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub target: HashMap<String, TargetDependencies>,
     #[serde(default)]
     pub build: Option<BuildConfig>,
     #[serde(default)]
@@ -36,6 +47,15 @@ pub struct PackageManifest {
     pub features: HashMap<String, Feature>,
     #[serde(default)]
     pub workspace: Option<Workspace>,
+    /// `[trust]`: signature requirements for dependencies
+    #[serde(default)]
+    pub trust: Option<TrustPolicy>,
+    /// `[lints]`: dependency graph lints
+    #[serde(default)]
+    pub lints: Option<Lints>,
+    /// `[assets]`: files `includeStr`/`includeBytes` may embed
+    #[serde(default)]
+    pub assets: Option<Assets>,
 }
 
 impl PackageManifest {
@@ -55,6 +75,87 @@ impl PackageManifest {
     pub fn to_string(&self) -> Result<String, toml::ser::Error> {
         toml::to_string_pretty(self)
     }
+
+    /// Dependencies that apply to a build context
+    ///
+    /// Merges the base sections selected by the context's kinds with every
+    /// `[target.'cfg(...)']` section matching its platform. A target entry
+    /// overrides a base entry of the same name.
+    pub fn dependencies_for(&self, context: &BuildContext) -> HashMap<String, Dependency> {
+        let mut deps = HashMap::new();
+        let mut add = |section: &HashMap<String, Dependency>| {
+            deps.extend(section.iter().map(|(k, v)| (k.clone(), v.clone())));
+        };
+
+        for kind in SECTION_ORDER {
+            if context.includes(kind) {
+                add(self.section(kind));
+            }
+        }
+        for (key, target) in &self.target {
+            if !context.matches_target(key) {
+                continue;
+            }
+            for kind in SECTION_ORDER {
+                if context.includes(kind) {
+                    add(target.section(kind));
+                }
+            }
+        }
+        deps
+    }
+
+    /// Look a dependency up in any section, for any platform
+    pub fn find_dependency(&self, name: &str) -> Option<&Dependency> {
+        self.dependencies
+            .get(name)
+            .or_else(|| self.dev_dependencies.get(name))
+            .or_else(|| self.build_dependencies.get(name))
+            .or_else(|| self.target.values().find_map(|t| t.find_dependency(name)))
+    }
+
+    fn section(&self, kind: DependencyKind) -> &HashMap<String, Dependency> {
+        match kind {
+            DependencyKind::Normal => &self.dependencies,
+            DependencyKind::Dev => &self.dev_dependencies,
+            DependencyKind::Build => &self.build_dependencies,
+        }
+    }
+}
+
+/// Later sections win when a name appears in several
+const SECTION_ORDER: [DependencyKind; 3] = [
+    DependencyKind::Build,
+    DependencyKind::Dev,
+    DependencyKind::Normal,
+];
+
+/// Dependency sections under `[target.'cfg(...)']`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TargetDependencies {
+    #[serde(default)]
+    pub dependencies: HashMap<String, Dependency>,
+    #[serde(default, rename = "dev-dependencies")]
+    pub dev_dependencies: HashMap<String, Dependency>,
+    #[serde(default, rename = "build-dependencies")]
+    pub build_dependencies: HashMap<String, Dependency>,
+}
+
+impl TargetDependencies {
+    fn section(&self, kind: DependencyKind) -> &HashMap<String, Dependency> {
+        match kind {
+            DependencyKind::Normal => &self.dependencies,
+            DependencyKind::Dev => &self.dev_dependencies,
+            DependencyKind::Build => &self.build_dependencies,
+        }
+    }
+
+    fn find_dependency(&self, name: &str) -> Option<&Dependency> {
+        self.dependencies
+            .get(name)
+            .or_else(|| self.dev_dependencies.get(name))
+            .or_else(|| self.build_dependencies.get(name))
+    }
 }
 
 /// Package metadata
@@ -104,6 +205,34 @@ impl Dependency {
             Dependency::Detailed(d) => d.optional.unwrap_or(false),
         }
     }
+
+    /// Release channel this dependency tracks
+    pub fn channel(&self) -> ReleaseChannel {
+        match self {
+            Dependency::Simple(_) => ReleaseChannel::Stable,
+            Dependency::Detailed(d) => d.channel.unwrap_or_default(),
+        }
+    }
+}
+
+/// Release channel a dependency tracks
+///
+/// `stable` only selects pre-release versions the constraint names explicitly;
+/// `nightly` lets any pre-release within the constraint be chosen, so a team can
+/// opt one dependency into pre-releases without affecting the rest.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseChannel {
+    #[default]
+    Stable,
+    Nightly,
+}
+
+impl ReleaseChannel {
+    /// Whether pre-release versions may be selected on this channel
+    pub fn allows_prerelease(self) -> bool {
+        matches!(self, ReleaseChannel::Nightly)
+    }
 }
 
 /// Detailed dependency specification
@@ -131,6 +260,8 @@ pub struct DetailedDependency {
     pub default_features: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "package")]
     pub rename: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<ReleaseChannel>,
 }
 
 /// Dependency source type
@@ -211,6 +342,36 @@ pub struct Workspace {
     pub dependencies: HashMap<String, Dependency>,
 }
 
+/// Dependency graph lints (`[lints]`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Lints {
+    /// How to treat semver-incompatible duplicates of one package
+    #[serde(default)]
+    pub duplicate_versions: LintLevel,
+    /// Duplicates tolerated before `deny` fails the build
+    #[serde(default)]
+    pub duplicate_budget: usize,
+}
+
+/// Compile-time embeddable files (`[assets]`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Assets {
+    /// Glob patterns relative to the package root, e.g. `templates/**/*.html`
+    #[serde(default)]
+    pub include: Vec<String>,
+}
+
+/// Severity of a lint
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    Allow,
+    #[default]
+    Warn,
+    Deny,
+}
+
 /// Version constraint
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum VersionConstraint {
@@ -252,21 +413,84 @@ impl VersionConstraint {
     }
 
     /// Check if version satisfies constraint
+    ///
+    /// Pre-release versions only match when the constraint itself names a
+    /// pre-release of the same `major.minor.patch` (e.g. `^1.0.0-beta.1` matches
+    /// `1.0.0-beta.2` but `^1.0.0` does not). Build metadata is ignored.
     pub fn matches(&self, version: &semver::Version) -> bool {
+        self.matches_with(version, false)
+    }
+
+    /// Check if version satisfies constraint, optionally admitting any
+    /// pre-release within the constraint's range (`--pre`, nightly channel)
+    pub fn matches_with(&self, version: &semver::Version, allow_prerelease: bool) -> bool {
+        let version = &without_build(version);
+        if !version.pre.is_empty() && !allow_prerelease && !self.names_prerelease_of(version) {
+            return false;
+        }
         match self {
-            VersionConstraint::Exact(v) => version == v,
-            VersionConstraint::Range(req) => req.matches(version),
+            VersionConstraint::Exact(v) => version == &without_build(v),
+            VersionConstraint::Range(req) => req_matches(req, version, allow_prerelease),
             VersionConstraint::Caret(v) => {
                 // ^1.2.3 := >=1.2.3, <2.0.0
-                version >= v && version.major == v.major
+                version >= &without_build(v) && version.major == v.major
             }
             VersionConstraint::Tilde(v) => {
                 // ~1.2.3 := >=1.2.3, <1.3.0
-                version >= v && version.major == v.major && version.minor == v.minor
+                version >= &without_build(v) && version.major == v.major && version.minor == v.minor
             }
             VersionConstraint::Wildcard | VersionConstraint::Any => true,
         }
     }
+
+    /// Whether the constraint explicitly names a pre-release of `version`'s
+    /// `major.minor.patch`
+    fn names_prerelease_of(&self, version: &semver::Version) -> bool {
+        let same_release = |v: &semver::Version| {
+            !v.pre.is_empty()
+                && v.major == version.major
+                && v.minor == version.minor
+                && v.patch == version.patch
+        };
+        match self {
+            VersionConstraint::Exact(v)
+            | VersionConstraint::Caret(v)
+            | VersionConstraint::Tilde(v) => same_release(v),
+            // semver's own matching already applies the rule to requirements
+            VersionConstraint::Range(_) => true,
+            VersionConstraint::Wildcard | VersionConstraint::Any => false,
+        }
+    }
+}
+
+/// Check a semver requirement against a version.
+///
+/// Without `allow_prerelease` this is plain semver matching: a pre-release only
+/// matches a comparator that names a pre-release of the same release. With it,
+/// a pre-release matches whenever the release it leads up to falls inside the
+/// requirement — so `^1.0` admits `1.4.0-beta.1` but not `2.0.0-rc.1`.
+pub fn req_matches(
+    req: &semver::VersionReq,
+    version: &semver::Version,
+    allow_prerelease: bool,
+) -> bool {
+    if req.matches(version) {
+        return true;
+    }
+    if !allow_prerelease || version.pre.is_empty() {
+        return false;
+    }
+    let mut release = version.clone();
+    release.pre = semver::Prerelease::EMPTY;
+    release.build = semver::BuildMetadata::EMPTY;
+    req.matches(&release)
+}
+
+/// Drop build metadata, which never affects version matching
+fn without_build(version: &semver::Version) -> semver::Version {
+    let mut v = version.clone();
+    v.build = semver::BuildMetadata::EMPTY;
+    v
 }
 
 #[cfg(test)]
@@ -326,6 +550,64 @@ mod tests {
         assert!(!constraint.matches(&semver::Version::new(2, 0, 0)));
     }
 
+    #[test]
+    fn test_version_constraint_prerelease_requires_opt_in() {
+        let beta = semver::Version::parse("1.0.0-beta.2").unwrap();
+        let next_beta = semver::Version::parse("1.3.0-beta.1").unwrap();
+
+        assert!(!VersionConstraint::parse("^1.0.0")
+            .unwrap()
+            .matches(&next_beta));
+        assert!(!VersionConstraint::parse("*").unwrap().matches(&beta));
+        assert!(VersionConstraint::parse("^1.0.0-beta.1")
+            .unwrap()
+            .matches(&beta));
+        assert!(VersionConstraint::parse(">=1.0.0-beta.1")
+            .unwrap()
+            .matches(&beta));
+        assert!(!VersionConstraint::parse("^1.0.0-beta.1")
+            .unwrap()
+            .matches(&next_beta));
+
+        // --pre / nightly channel admits pre-releases inside the range
+        let caret = VersionConstraint::parse("^1.0.0").unwrap();
+        assert!(caret.matches_with(&next_beta, true));
+        let range = VersionConstraint::parse(">=1.0, <2.0").unwrap();
+        assert!(range.matches_with(&next_beta, true));
+        assert!(!range.matches_with(&semver::Version::parse("2.0.0-rc.1").unwrap(), true));
+    }
+
+    #[test]
+    fn test_version_constraint_ignores_build_metadata() {
+        let built = semver::Version::parse("1.2.3+build.7").unwrap();
+        assert!(VersionConstraint::parse("1.2.3").unwrap().matches(&built));
+        assert!(VersionConstraint::parse("^1.2.0").unwrap().matches(&built));
+    }
+
+    #[test]
+    fn test_dependency_channel() {
+        let toml = r#"
+            [package]
+            name = "my-package"
+            version = "1.0.0"
+
+            [dependencies]
+            stable-dep = "1.0"
+            edge = { version = "^2.0", channel = "nightly" }
+        "#;
+
+        let manifest = PackageManifest::from_str(toml).unwrap();
+        assert_eq!(
+            manifest.dependencies["stable-dep"].channel(),
+            ReleaseChannel::Stable
+        );
+        assert_eq!(
+            manifest.dependencies["edge"].channel(),
+            ReleaseChannel::Nightly
+        );
+        assert!(ReleaseChannel::Nightly.allows_prerelease());
+    }
+
     #[test]
     fn test_version_constraint_tilde() {
         let constraint = VersionConstraint::parse("~1.2.3").unwrap();
@@ -348,8 +630,55 @@ mod tests {
             features: None,
             default_features: None,
             rename: None,
+            channel: None,
         });
 
         assert!(dep.is_optional());
     }
+
+    #[test]
+    fn test_dependencies_for_build_context() {
+        use crate::target::TargetPlatform;
+
+        let toml = r#"
+            [package]
+            name = "app"
+            version = "1.0.0"
+
+            [dependencies]
+            json = "1.0"
+            fs = "1.0"
+
+            [dev-dependencies]
+            test-utils = "0.1"
+
+            [build-dependencies]
+            codegen = "0.3"
+
+            [target.'cfg(windows)'.dependencies]
+            winapi = "0.3"
+
+            [target.'cfg(os = "linux")'.dependencies]
+            fs = "2.0"
+        "#;
+        let manifest = PackageManifest::from_str(toml).unwrap();
+        let linux = TargetPlatform::new("linux", "x86_64", "unix");
+
+        let prod = manifest.dependencies_for(&BuildContext::production(linux.clone()));
+        assert!(prod.contains_key("json"));
+        assert!(prod.contains_key("codegen"));
+        assert!(!prod.contains_key("test-utils"));
+        assert!(!prod.contains_key("winapi"));
+        assert_eq!(prod["fs"].version_constraint(), Some("2.0"));
+
+        let dev = manifest.dependencies_for(&BuildContext::development(linux));
+        assert!(dev.contains_key("test-utils"));
+
+        let windows = TargetPlatform::new("windows", "x86_64", "windows");
+        let win = manifest.dependencies_for(&BuildContext::production(windows));
+        assert!(win.contains_key("winapi"));
+        assert_eq!(win["fs"].version_constraint(), Some("1.0"));
+
+        assert!(manifest.find_dependency("winapi").is_some());
+    }
 }
@@ -1,10 +1,21 @@
+use crate::manifest::PackageManifest;
+use crate::signing::PackageSignature;
+use crate::validator::Validator;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use semver::Version;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
 use thiserror::Error;
 
 pub mod local;
+pub mod metadata_cache;
 pub mod remote;
 
 pub use local::LocalRegistry;
+pub use metadata_cache::{CacheLookup, CachePolicy, MetadataCache};
 pub use remote::RemoteRegistry;
 
 #[derive(Debug, Error)]
@@ -18,9 +29,28 @@ pub enum RegistryError {
     #[error("Checksum mismatch for {package}@{version}")]
     ChecksumMismatch { package: String, version: String },
 
+    #[error("Signature verification failed for {package}@{version}: {reason}")]
+    SignatureInvalid {
+        package: String,
+        version: String,
+        reason: String,
+    },
+
+    #[error("{package}@{version} is not signed and the trust policy requires signatures")]
+    MissingSignature { package: String, version: String },
+
     #[error("Registry unavailable: {0}")]
     Unavailable(String),
 
+    #[error("Invalid package: {0}")]
+    InvalidPackage(String),
+
+    #[error("Not authorized: {0}")]
+    Unauthorized(String),
+
+    #[error("{package}@{version} is already published")]
+    AlreadyPublished { package: String, version: String },
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -38,6 +68,19 @@ pub struct PackageMetadata {
     pub checksum: String,
     pub download_url: String,
     pub dependencies: Vec<String>,
+    /// Withdrawn by the publisher: never picked by a new resolution, but an
+    /// existing lockfile that pins it stays valid
+    pub yanked: bool,
+    /// Publisher's signature over name, version and checksum
+    pub signature: Option<PackageSignature>,
+}
+
+/// One entry in a package's version index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VersionEntry {
+    pub version: Version,
+    #[serde(default)]
+    pub yanked: bool,
 }
 
 impl PackageMetadata {
@@ -48,6 +91,8 @@ impl PackageMetadata {
             checksum: String::new(),
             download_url: String::new(),
             dependencies: Vec::new(),
+            yanked: false,
+            signature: None,
         }
     }
 
@@ -65,13 +110,339 @@ impl PackageMetadata {
         self.dependencies = deps;
         self
     }
+
+    pub fn with_yanked(mut self, yanked: bool) -> Self {
+        self.yanked = yanked;
+        self
+    }
+
+    pub fn with_signature(mut self, signature: Option<PackageSignature>) -> Self {
+        self.signature = signature;
+        self
+    }
+}
+
+/// Full-text search request over package names, keywords and descriptions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery {
+    pub text: String,
+    /// 1-based page number
+    pub page: usize,
+    pub per_page: usize,
+}
+
+impl SearchQuery {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            page: 1,
+            per_page: 20,
+        }
+    }
+
+    pub fn with_page(mut self, page: usize) -> Self {
+        self.page = page.max(1);
+        self
+    }
+
+    pub fn with_per_page(mut self, per_page: usize) -> Self {
+        self.per_page = per_page.max(1);
+        self
+    }
+
+    /// Whether every whitespace-separated term appears (case-insensitively)
+    /// in the hit's name, one of its keywords, or its description
+    pub fn matches(&self, hit: &SearchHit) -> bool {
+        let name = hit.name.to_lowercase();
+        let description = hit.description.as_deref().unwrap_or("").to_lowercase();
+        self.text.split_whitespace().all(|term| {
+            let term = term.to_lowercase();
+            name.contains(&term)
+                || description.contains(&term)
+                || hit
+                    .keywords
+                    .iter()
+                    .any(|k| k.to_lowercase().contains(&term))
+        })
+    }
+}
+
+/// One package in a search result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub name: String,
+    /// Latest non-yanked version
+    pub version: Version,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub downloads: u64,
+}
+
+/// One page of search results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchResults {
+    pub hits: Vec<SearchHit>,
+    /// Matches across all pages
+    pub total: usize,
+    pub page: usize,
+    pub per_page: usize,
+}
+
+impl SearchResults {
+    /// Rank every match (exact name first, then by downloads) and cut out the
+    /// requested page
+    pub fn paginate(mut matches: Vec<SearchHit>, query: &SearchQuery) -> Self {
+        let text = query.text.trim().to_lowercase();
+        matches.sort_by(|a, b| {
+            (b.name == text)
+                .cmp(&(a.name == text))
+                .then(b.downloads.cmp(&a.downloads))
+                .then(a.name.cmp(&b.name))
+        });
+        let total = matches.len();
+        let hits = matches
+            .into_iter()
+            .skip((query.page - 1) * query.per_page)
+            .take(query.per_page)
+            .collect();
+        Self {
+            hits,
+            total,
+            page: query.page,
+            per_page: query.per_page,
+        }
+    }
+
+    /// Number of pages needed for every match
+    pub fn total_pages(&self) -> usize {
+        self.total.div_ceil(self.per_page.max(1))
+    }
+}
+
+/// Everything `atlas info` shows about a package
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackageInfo {
+    pub name: String,
+    /// Latest non-yanked version (the newest version if all are yanked)
+    pub latest: Version,
+    /// Every published version, oldest first
+    pub versions: Vec<VersionEntry>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub repository: Option<String>,
+    #[serde(default)]
+    pub homepage: Option<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Feature names of the latest version
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// Dependencies of the latest version
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    /// All-time downloads, if the registry tracks them
+    #[serde(default)]
+    pub downloads: Option<u64>,
+}
+
+/// Largest archive `PackageUpload::from_dir` will produce
+pub const MAX_UPLOAD_SIZE: usize = 10 * 1024 * 1024;
+
+/// A package directory packed for `Registry::publish`
+#[derive(Debug, Clone)]
+pub struct PackageUpload {
+    pub manifest: PackageManifest,
+    /// Gzipped tarball of the package directory
+    pub archive: Vec<u8>,
+    /// Hex SHA-256 of `archive`
+    pub checksum: String,
+    /// Publisher's signature over name, version and checksum
+    pub signature: Option<PackageSignature>,
+}
+
+impl PackageUpload {
+    /// Validate `dir/atlas.toml` for publishing and pack the directory.
+    ///
+    /// Hidden entries and `target/` are left out. The archive is
+    /// reproducible: entries are sorted and carry no timestamps or owners, so
+    /// packing the same tree twice gives the same checksum.
+    pub fn from_dir(dir: &Path) -> RegistryResult<Self> {
+        let manifest_path = dir.join("atlas.toml");
+        let content = fs::read_to_string(&manifest_path).map_err(|e| {
+            RegistryError::InvalidPackage(format!("{}: {}", manifest_path.display(), e))
+        })?;
+        let manifest = PackageManifest::from_str(&content)
+            .map_err(|e| RegistryError::InvalidPackage(format!("atlas.toml: {}", e)))?;
+        if let Err(errors) = Validator::validate_for_publish(&manifest) {
+            let reasons: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+            return Err(RegistryError::InvalidPackage(reasons.join("; ")));
+        }
+
+        let archive = package_archive(dir)?;
+        if archive.len() > MAX_UPLOAD_SIZE {
+            return Err(RegistryError::InvalidPackage(format!(
+                "archive is {} bytes; the limit is {} bytes",
+                archive.len(),
+                MAX_UPLOAD_SIZE
+            )));
+        }
+        let checksum = format!("{:x}", Sha256::digest(&archive));
+        Ok(Self {
+            manifest,
+            archive,
+            checksum,
+            signature: None,
+        })
+    }
+
+    pub fn with_signature(mut self, signature: PackageSignature) -> Self {
+        self.signature = Some(signature);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.manifest.package.name
+    }
+
+    pub fn version(&self) -> &Version {
+        &self.manifest.package.version
+    }
+
+    /// Names of the runtime dependencies, sorted
+    pub fn dependency_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.manifest.dependencies.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+/// Pack `dir` into a gzipped tarball with paths relative to `dir`
+pub fn package_archive(dir: &Path) -> RegistryResult<Vec<u8>> {
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    let walker = walkdir::WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0 || {
+                let name = entry.file_name().to_string_lossy();
+                !(name.starts_with('.') || (entry.depth() == 1 && name == "target"))
+            }
+        });
+
+    for entry in walker {
+        let entry = entry.map_err(|e| RegistryError::IoError(e.into()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(dir)
+            .expect("walkdir yields paths under its root");
+        let data = fs::read(entry.path())?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(0);
+        builder.append_data(&mut header, relative, data.as_slice())?;
+    }
+
+    Ok(builder.into_inner()?.finish()?)
+}
+
+/// Latest non-yanked version in an index, falling back to the newest
+pub(crate) fn latest_version(index: &[VersionEntry]) -> Option<&Version> {
+    index
+        .iter()
+        .filter(|e| !e.yanked)
+        .map(|e| &e.version)
+        .max()
+        .or_else(|| index.iter().map(|e| &e.version).max())
 }
 
 /// Registry trait - implemented by remote, local, git registries
 pub trait Registry: Send + Sync {
-    /// Query available versions for a package
+    /// Query available versions for a package (yanked versions included)
     fn query_versions(&self, package: &str) -> RegistryResult<Vec<Version>>;
 
+    /// Query the version index, including yank status.
+    ///
+    /// Registries without yank support report every version as live.
+    fn query_index(&self, package: &str) -> RegistryResult<Vec<VersionEntry>> {
+        Ok(self
+            .query_versions(package)?
+            .into_iter()
+            .map(|version| VersionEntry {
+                version,
+                yanked: false,
+            })
+            .collect())
+    }
+
+    /// Mark a published version as yanked (or restore it with `yanked = false`)
+    fn yank(&self, package: &str, _version: &Version, _yanked: bool) -> RegistryResult<()> {
+        Err(RegistryError::Unavailable(format!(
+            "registry does not support yanking ({})",
+            package
+        )))
+    }
+
+    /// Search packages by name, keyword and description
+    fn search(&self, _query: &SearchQuery) -> RegistryResult<SearchResults> {
+        Err(RegistryError::Unavailable(
+            "registry does not support search".to_string(),
+        ))
+    }
+
+    /// Describe a package: versions, license, features, dependencies.
+    ///
+    /// The default builds what it can from the version index and the latest
+    /// version's metadata; registries that store more override it.
+    fn package_info(&self, package: &str) -> RegistryResult<PackageInfo> {
+        let versions = self.query_index(package)?;
+        let latest = latest_version(&versions)
+            .cloned()
+            .ok_or_else(|| RegistryError::PackageNotFound(package.to_string()))?;
+        let metadata = self.get_metadata(package, &latest)?;
+        Ok(PackageInfo {
+            name: package.to_string(),
+            latest,
+            versions,
+            description: None,
+            license: None,
+            repository: None,
+            homepage: None,
+            keywords: Vec::new(),
+            features: Vec::new(),
+            dependencies: metadata.dependencies,
+            downloads: None,
+        })
+    }
+
+    /// Hex-encoded ed25519 public keys the package's publishers sign with.
+    ///
+    /// Registries that do not publish keys report none, which leaves
+    /// signatures unverifiable.
+    fn public_keys(&self, _package: &str) -> RegistryResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Upload a packed package as a new version.
+    ///
+    /// Versions are immutable: publishing one that exists fails with
+    /// `AlreadyPublished`. Returns the stored version's metadata.
+    fn publish(&self, upload: &PackageUpload) -> RegistryResult<PackageMetadata> {
+        Err(RegistryError::Unavailable(format!(
+            "registry does not accept uploads ({})",
+            upload.name()
+        )))
+    }
+
     /// Get package metadata for specific version
     fn get_metadata(&self, package: &str, version: &Version) -> RegistryResult<PackageMetadata>;
 
@@ -114,6 +485,50 @@ impl RegistryManager {
         Err(RegistryError::PackageNotFound(package.to_string()))
     }
 
+    /// Query the version index (with yank status) from the first registry that has it
+    pub fn query_index(&self, package: &str) -> RegistryResult<Vec<VersionEntry>> {
+        for registry in &self.registries {
+            if let Ok(entries) = registry.query_index(package) {
+                return Ok(entries);
+            }
+        }
+        Err(RegistryError::PackageNotFound(package.to_string()))
+    }
+
+    /// Search the first registry that supports search
+    pub fn search(&self, query: &SearchQuery) -> RegistryResult<SearchResults> {
+        let mut last_err = RegistryError::Unavailable("no registries configured".to_string());
+        for registry in &self.registries {
+            match registry.search(query) {
+                Ok(results) => return Ok(results),
+                Err(e) => last_err = e,
+            }
+        }
+        Err(last_err)
+    }
+
+    /// Describe a package from the first registry that has it
+    pub fn package_info(&self, package: &str) -> RegistryResult<PackageInfo> {
+        for registry in &self.registries {
+            if let Ok(info) = registry.package_info(package) {
+                return Ok(info);
+            }
+        }
+        Err(RegistryError::PackageNotFound(package.to_string()))
+    }
+
+    /// Public keys from the first registry that publishes any for the package
+    pub fn public_keys(&self, package: &str) -> RegistryResult<Vec<String>> {
+        for registry in &self.registries {
+            if let Ok(keys) = registry.public_keys(package) {
+                if !keys.is_empty() {
+                    return Ok(keys);
+                }
+            }
+        }
+        Ok(Vec::new())
+    }
+
     /// Get metadata from first registry that has it
     pub fn get_metadata(
         &self,
@@ -186,6 +601,13 @@ mod tests {
         assert_eq!(metadata.dependencies.len(), 1);
     }
 
+    #[test]
+    fn test_package_metadata_yanked() {
+        let metadata = PackageMetadata::new("test".to_string(), Version::new(1, 0, 0));
+        assert!(!metadata.yanked);
+        assert!(metadata.with_yanked(true).yanked);
+    }
+
     #[test]
     fn test_registry_manager_cache_control() {
         let mut manager = RegistryManager::new();
@@ -197,4 +619,56 @@ mod tests {
         manager.set_cache_enabled(true);
         assert!(manager.is_cache_enabled());
     }
+
+    fn hit(name: &str, description: &str, downloads: u64) -> SearchHit {
+        SearchHit {
+            name: name.to_string(),
+            version: Version::new(1, 0, 0),
+            description: Some(description.to_string()),
+            keywords: vec!["web".to_string()],
+            downloads,
+        }
+    }
+
+    #[test]
+    fn test_search_query_matches_all_terms() {
+        let http = hit("http", "An HTTP client", 10);
+        assert!(SearchQuery::new("HTTP client").matches(&http));
+        assert!(SearchQuery::new("web").matches(&http));
+        assert!(!SearchQuery::new("http server").matches(&http));
+    }
+
+    #[test]
+    fn test_search_results_rank_and_paginate() {
+        let matches = vec![
+            hit("http-extra", "", 5),
+            hit("http", "", 1),
+            hit("http-router", "", 50),
+        ];
+        let query = SearchQuery::new("http").with_per_page(2);
+        let first = SearchResults::paginate(matches.clone(), &query);
+        assert_eq!(first.total, 3);
+        assert_eq!(first.total_pages(), 2);
+        let names: Vec<&str> = first.hits.iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, vec!["http", "http-router"]);
+
+        let second = SearchResults::paginate(matches, &query.with_page(2));
+        assert_eq!(second.hits.len(), 1);
+        assert_eq!(second.hits[0].name, "http-extra");
+    }
+
+    #[test]
+    fn test_latest_version_skips_yanked() {
+        let index = vec![
+            VersionEntry {
+                version: Version::new(1, 0, 0),
+                yanked: false,
+            },
+            VersionEntry {
+                version: Version::new(1, 1, 0),
+                yanked: true,
+            },
+        ];
+        assert_eq!(latest_version(&index), Some(&Version::new(1, 0, 0)));
+    }
 }
@@ -1,4 +1,8 @@
-use super::{PackageMetadata, Registry, RegistryError, RegistryResult};
+use super::{
+    latest_version, PackageInfo, PackageMetadata, PackageUpload, Registry, RegistryError,
+    RegistryResult, SearchHit, SearchQuery, SearchResults, VersionEntry,
+};
+use crate::signing::PackageSignature;
 use semver::Version;
 use std::fs;
 use std::path::PathBuf;
@@ -37,6 +41,68 @@ impl LocalRegistry {
     fn archive_path(&self, package: &str, version: &Version) -> PathBuf {
         self.version_dir(package, version).join("package.tar.gz")
     }
+
+    /// Read metadata.toml for a version (empty table if absent)
+    fn read_metadata_table(&self, package: &str, version: &Version) -> RegistryResult<toml::Table> {
+        let metadata_path = self.metadata_path(package, version);
+        if !metadata_path.exists() {
+            return Ok(toml::Table::new());
+        }
+        let content = fs::read_to_string(&metadata_path)?;
+        toml::from_str(&content).map_err(|e| RegistryError::ParseError(e.to_string()))
+    }
+
+    /// Whether metadata.toml marks the version as yanked
+    fn is_yanked(&self, package: &str, version: &Version) -> RegistryResult<bool> {
+        Ok(self
+            .read_metadata_table(package, version)?
+            .get("yanked")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false))
+    }
+
+    /// Version index, latest version and its metadata.toml
+    fn latest(&self, package: &str) -> RegistryResult<(Vec<VersionEntry>, Version, toml::Table)> {
+        let index = self.query_index(package)?;
+        let latest = latest_version(&index)
+            .cloned()
+            .ok_or_else(|| RegistryError::PackageNotFound(package.to_string()))?;
+        let table = self.read_metadata_table(package, &latest)?;
+        Ok((index, latest, table))
+    }
+}
+
+fn str_field(table: &toml::Table, key: &str) -> Option<String> {
+    table.get(key).and_then(|v| v.as_str()).map(str::to_string)
+}
+
+fn str_list(table: &toml::Table, key: &str) -> Vec<String> {
+    table
+        .get(key)
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Feature names, whether stored as a `[features]` table or a list
+fn feature_names(table: &toml::Table) -> Vec<String> {
+    match table.get("features") {
+        Some(toml::Value::Table(features)) => features.keys().cloned().collect(),
+        Some(_) => str_list(table, "features"),
+        None => Vec::new(),
+    }
+}
+
+fn downloads(table: &toml::Table) -> Option<u64> {
+    table
+        .get("downloads")
+        .and_then(|v| v.as_integer())
+        .and_then(|n| u64::try_from(n).ok())
 }
 
 impl Registry for LocalRegistry {
@@ -95,11 +161,153 @@ impl Registry for LocalRegistry {
             .unwrap_or("")
             .to_string();
 
+        let yanked = metadata_toml
+            .get("yanked")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        // `[signature]` table: key-id and signature
+        let signature = metadata_toml
+            .get("signature")
+            .cloned()
+            .map(|v| v.try_into::<PackageSignature>())
+            .transpose()
+            .map_err(|e| RegistryError::ParseError(e.to_string()))?;
+
         let download_url = format!("file://{}", self.archive_path(package, version).display());
 
         Ok(PackageMetadata::new(name, version.clone())
             .with_checksum(checksum)
-            .with_download_url(download_url))
+            .with_download_url(download_url)
+            .with_yanked(yanked)
+            .with_signature(signature))
+    }
+
+    /// Keys listed in `<package>/keys.toml` as `keys = ["<hex>", ...]`
+    fn public_keys(&self, package: &str) -> RegistryResult<Vec<String>> {
+        let keys_path = self.package_dir(package).join("keys.toml");
+        if !keys_path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&keys_path)?;
+        let table: toml::Table =
+            toml::from_str(&content).map_err(|e| RegistryError::ParseError(e.to_string()))?;
+        Ok(str_list(&table, "keys"))
+    }
+
+    fn query_index(&self, package: &str) -> RegistryResult<Vec<VersionEntry>> {
+        self.query_versions(package)?
+            .into_iter()
+            .map(|version| {
+                let yanked = self.is_yanked(package, &version)?;
+                Ok(VersionEntry { version, yanked })
+            })
+            .collect()
+    }
+
+    fn search(&self, query: &SearchQuery) -> RegistryResult<SearchResults> {
+        let mut matches = Vec::new();
+        if self.root.exists() {
+            for entry in fs::read_dir(&self.root)? {
+                let entry = entry?;
+                if !entry.file_type()?.is_dir() {
+                    continue;
+                }
+                let name = entry.file_name().to_string_lossy().to_string();
+                // Directories without any version are not packages
+                let Ok((_, version, table)) = self.latest(&name) else {
+                    continue;
+                };
+                let hit = SearchHit {
+                    name,
+                    version,
+                    description: str_field(&table, "description"),
+                    keywords: str_list(&table, "keywords"),
+                    downloads: downloads(&table).unwrap_or(0),
+                };
+                if query.matches(&hit) {
+                    matches.push(hit);
+                }
+            }
+        }
+        Ok(SearchResults::paginate(matches, query))
+    }
+
+    fn package_info(&self, package: &str) -> RegistryResult<PackageInfo> {
+        let (versions, latest, table) = self.latest(package)?;
+        let mut features = feature_names(&table);
+        features.sort();
+        Ok(PackageInfo {
+            name: package.to_string(),
+            latest,
+            versions,
+            description: str_field(&table, "description"),
+            license: str_field(&table, "license"),
+            repository: str_field(&table, "repository"),
+            homepage: str_field(&table, "homepage"),
+            keywords: str_list(&table, "keywords"),
+            features,
+            dependencies: str_list(&table, "dependencies"),
+            downloads: downloads(&table),
+        })
+    }
+
+    fn yank(&self, package: &str, version: &Version, yanked: bool) -> RegistryResult<()> {
+        if !self.version_dir(package, version).exists() {
+            return Err(RegistryError::PackageNotFound(format!(
+                "{}@{}",
+                package, version
+            )));
+        }
+        let mut table = self.read_metadata_table(package, version)?;
+        table.insert("yanked".to_string(), toml::Value::Boolean(yanked));
+        let content =
+            toml::to_string(&table).map_err(|e| RegistryError::ParseError(e.to_string()))?;
+        fs::write(self.metadata_path(package, version), content)?;
+        Ok(())
+    }
+
+    fn publish(&self, upload: &PackageUpload) -> RegistryResult<PackageMetadata> {
+        let (package, version) = (upload.name(), upload.version());
+        let version_dir = self.version_dir(package, version);
+        if version_dir.exists() {
+            return Err(RegistryError::AlreadyPublished {
+                package: package.to_string(),
+                version: version.to_string(),
+            });
+        }
+
+        let info = &upload.manifest.package;
+        let mut table = toml::Table::new();
+        table.insert("name".to_string(), package.into());
+        table.insert("checksum".to_string(), upload.checksum.as_str().into());
+        for (key, value) in [
+            ("description", &info.description),
+            ("license", &info.license),
+            ("repository", &info.repository),
+            ("homepage", &info.homepage),
+        ] {
+            if let Some(value) = value {
+                table.insert(key.to_string(), value.as_str().into());
+            }
+        }
+        table.insert("keywords".to_string(), info.keywords.clone().into());
+        table.insert("dependencies".to_string(), upload.dependency_names().into());
+        let mut features: Vec<String> = upload.manifest.features.keys().cloned().collect();
+        features.sort();
+        table.insert("features".to_string(), features.into());
+        if let Some(signature) = &upload.signature {
+            let signature = toml::Value::try_from(signature)
+                .map_err(|e| RegistryError::ParseError(e.to_string()))?;
+            table.insert("signature".to_string(), signature);
+        }
+        let content =
+            toml::to_string(&table).map_err(|e| RegistryError::ParseError(e.to_string()))?;
+
+        fs::create_dir_all(&version_dir)?;
+        fs::write(self.archive_path(package, version), &upload.archive)?;
+        fs::write(self.metadata_path(package, version), content)?;
+        self.get_metadata(package, version)
     }
 
     fn download(&self, package: &str, version: &Version) -> RegistryResult<Vec<u8>> {
@@ -161,4 +369,160 @@ mod tests {
             PathBuf::from("/tmp/registry/test-package/1.0.0/package.tar.gz")
         );
     }
+
+    #[test]
+    fn test_yank_marks_version_in_index() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let registry = LocalRegistry::new(temp.path().to_path_buf());
+        for v in ["1.0.0", "1.1.0"] {
+            fs::create_dir_all(temp.path().join("json").join(v)).unwrap();
+        }
+
+        registry.yank("json", &Version::new(1, 1, 0), true).unwrap();
+        let index = registry.query_index("json").unwrap();
+        assert!(!index[0].yanked);
+        assert!(index[1].yanked);
+        assert!(
+            registry
+                .get_metadata("json", &Version::new(1, 1, 0))
+                .unwrap()
+                .yanked
+        );
+
+        registry
+            .yank("json", &Version::new(1, 1, 0), false)
+            .unwrap();
+        assert!(!registry.query_index("json").unwrap()[1].yanked);
+    }
+
+    #[test]
+    fn test_yank_unknown_version_errors() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let registry = LocalRegistry::new(temp.path().to_path_buf());
+        assert!(matches!(
+            registry.yank("json", &Version::new(9, 0, 0), true),
+            Err(RegistryError::PackageNotFound(_))
+        ));
+    }
+
+    fn publish_fixture(root: &std::path::Path, name: &str, version: &str, metadata: &str) {
+        let dir = root.join(name).join(version);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("metadata.toml"), metadata).unwrap();
+    }
+
+    #[test]
+    fn test_search_matches_keywords_and_descriptions() {
+        let temp = tempfile::TempDir::new().unwrap();
+        publish_fixture(
+            temp.path(),
+            "http",
+            "1.2.0",
+            "description = \"HTTP client\"\nkeywords = [\"web\"]\ndownloads = 40\n",
+        );
+        publish_fixture(
+            temp.path(),
+            "json",
+            "1.0.0",
+            "description = \"JSON codec\"\n",
+        );
+        let registry = LocalRegistry::new(temp.path().to_path_buf());
+
+        let results = registry.search(&SearchQuery::new("web")).unwrap();
+        assert_eq!(results.total, 1);
+        assert_eq!(results.hits[0].name, "http");
+        assert_eq!(results.hits[0].version, Version::new(1, 2, 0));
+        assert_eq!(results.hits[0].downloads, 40);
+
+        assert_eq!(
+            registry.search(&SearchQuery::new("codec")).unwrap().total,
+            1
+        );
+        assert_eq!(registry.search(&SearchQuery::new("yaml")).unwrap().total, 0);
+    }
+
+    #[test]
+    fn test_package_info_reads_latest_metadata() {
+        let temp = tempfile::TempDir::new().unwrap();
+        publish_fixture(temp.path(), "http", "1.0.0", "license = \"MIT\"\n");
+        publish_fixture(
+            temp.path(),
+            "http",
+            "1.1.0",
+            "license = \"MIT\"\ndependencies = [\"url\"]\n\n[features]\ntls = []\n",
+        );
+        publish_fixture(temp.path(), "http", "2.0.0", "yanked = true\n");
+        let registry = LocalRegistry::new(temp.path().to_path_buf());
+
+        let info = registry.package_info("http").unwrap();
+        assert_eq!(info.latest, Version::new(1, 1, 0));
+        assert_eq!(info.versions.len(), 3);
+        assert_eq!(info.license.as_deref(), Some("MIT"));
+        assert_eq!(info.features, vec!["tls".to_string()]);
+        assert_eq!(info.dependencies, vec!["url".to_string()]);
+        assert_eq!(info.downloads, None);
+    }
+
+    #[test]
+    fn test_publish_packs_and_stores_version() {
+        let project = tempfile::TempDir::new().unwrap();
+        fs::write(
+            project.path().join("atlas.toml"),
+            "[package]\nname = \"http\"\nversion = \"1.0.0\"\nlicense = \"MIT\"\ndescription = \"HTTP client\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(project.path().join("src")).unwrap();
+        fs::write(project.path().join("src/main.atl"), "export fn get() {}").unwrap();
+        fs::create_dir_all(project.path().join(".git")).unwrap();
+        fs::write(project.path().join(".git/HEAD"), "ref").unwrap();
+
+        let upload = PackageUpload::from_dir(project.path()).unwrap();
+        assert_eq!(
+            PackageUpload::from_dir(project.path()).unwrap().checksum,
+            upload.checksum,
+            "packing is reproducible"
+        );
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let registry = LocalRegistry::new(temp.path().to_path_buf());
+        let metadata = registry.publish(&upload).unwrap();
+        assert_eq!(metadata.checksum, upload.checksum);
+        assert_eq!(
+            registry
+                .package_info("http")
+                .unwrap()
+                .description
+                .as_deref(),
+            Some("HTTP client")
+        );
+
+        let archive = registry.download("http", &Version::new(1, 0, 0)).unwrap();
+        let mut entries: Vec<String> =
+            tar::Archive::new(flate2::read::GzDecoder::new(archive.as_slice()))
+                .entries()
+                .unwrap()
+                .map(|e| e.unwrap().path().unwrap().display().to_string())
+                .collect();
+        entries.sort();
+        assert_eq!(entries, vec!["atlas.toml", "src/main.atl"]);
+
+        assert!(matches!(
+            registry.publish(&upload),
+            Err(RegistryError::AlreadyPublished { .. })
+        ));
+    }
+
+    #[test]
+    fn test_publish_rejects_invalid_manifest() {
+        let project = tempfile::TempDir::new().unwrap();
+        fs::write(
+            project.path().join("atlas.toml"),
+            "[package]\nname = \"http\"\nversion = \"1.0.0\"\n",
+        )
+        .unwrap();
+        match PackageUpload::from_dir(project.path()) {
+            Err(RegistryError::InvalidPackage(reason)) => assert!(reason.contains("license")),
+            other => panic!("unexpected {:?}", other.map(|u| u.checksum)),
+        }
+    }
 }
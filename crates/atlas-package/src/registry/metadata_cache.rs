@@ -0,0 +1,210 @@
+//! On-disk cache for registry metadata responses
+//!
+//! Each response body is stored with the time it was fetched and the server's
+//! `ETag`. A `CachePolicy` decides whether a stored body can be used as-is or
+//! must be revalidated with `If-None-Match`.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long cached metadata is trusted before it is revalidated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Revalidate on every request (cheap when the server answers 304)
+    Revalidate,
+    /// Use cached metadata without contacting the registry until it is older than this
+    MaxAge(Duration),
+    /// Never contact the registry; serve whatever is cached
+    Offline,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        // Yanks must propagate reasonably quickly, so keep the window short.
+        CachePolicy::MaxAge(Duration::from_secs(5 * 60))
+    }
+}
+
+/// Result of looking a key up in the cache
+#[derive(Debug, Clone, PartialEq)]
+pub enum CacheLookup {
+    /// Usable without contacting the registry
+    Fresh(String),
+    /// Present but must be revalidated; send `etag` as `If-None-Match`
+    Stale { body: String, etag: Option<String> },
+    /// Nothing cached
+    Miss,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedEntry {
+    etag: Option<String>,
+    /// Seconds since the Unix epoch
+    fetched_at: u64,
+    body: String,
+}
+
+/// Registry metadata cache rooted at a directory
+#[derive(Debug, Clone)]
+pub struct MetadataCache {
+    dir: PathBuf,
+    policy: CachePolicy,
+}
+
+impl MetadataCache {
+    pub fn new(dir: PathBuf, policy: CachePolicy) -> Self {
+        Self { dir, policy }
+    }
+
+    /// Get the freshness policy
+    pub fn policy(&self) -> CachePolicy {
+        self.policy
+    }
+
+    /// Look up a cached response
+    pub fn lookup(&self, key: &str) -> CacheLookup {
+        let Some(entry) = self.read_entry(key) else {
+            return CacheLookup::Miss;
+        };
+
+        let fresh = match self.policy {
+            CachePolicy::Revalidate => false,
+            CachePolicy::MaxAge(max_age) => {
+                now_secs().saturating_sub(entry.fetched_at) < max_age.as_secs()
+            }
+            CachePolicy::Offline => true,
+        };
+
+        if fresh {
+            CacheLookup::Fresh(entry.body)
+        } else {
+            CacheLookup::Stale {
+                body: entry.body,
+                etag: entry.etag,
+            }
+        }
+    }
+
+    /// Store a response body along with its `ETag`
+    pub fn store(&self, key: &str, etag: Option<String>, body: &str) -> std::io::Result<()> {
+        self.write_entry(
+            key,
+            &CachedEntry {
+                etag,
+                fetched_at: now_secs(),
+                body: body.to_string(),
+            },
+        )
+    }
+
+    /// Mark a cached response as fresh again (the server answered 304)
+    pub fn touch(&self, key: &str) -> std::io::Result<()> {
+        match self.read_entry(key) {
+            Some(mut entry) => {
+                entry.fetched_at = now_secs();
+                self.write_entry(key, &entry)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Drop a cached response (e.g. after yanking, so the change is seen at once)
+    pub fn invalidate(&self, key: &str) {
+        let _ = fs::remove_file(self.entry_path(key));
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        let file: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!("{}.json", file))
+    }
+
+    fn read_entry(&self, key: &str) -> Option<CachedEntry> {
+        let content = fs::read_to_string(self.entry_path(key)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    fn write_entry(&self, key: &str, entry: &CachedEntry) -> std::io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let content = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+        fs::write(self.entry_path(key), content)
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_miss_then_fresh() {
+        let temp = TempDir::new().unwrap();
+        let cache = MetadataCache::new(temp.path().to_path_buf(), CachePolicy::default());
+        assert_eq!(cache.lookup("json/versions"), CacheLookup::Miss);
+
+        cache
+            .store("json/versions", Some("\"abc\"".to_string()), "[]")
+            .unwrap();
+        assert_eq!(
+            cache.lookup("json/versions"),
+            CacheLookup::Fresh("[]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_revalidate_policy_returns_stale_with_etag() {
+        let temp = TempDir::new().unwrap();
+        let cache = MetadataCache::new(temp.path().to_path_buf(), CachePolicy::Revalidate);
+        cache
+            .store("json/versions", Some("\"abc\"".to_string()), "[]")
+            .unwrap();
+
+        assert_eq!(
+            cache.lookup("json/versions"),
+            CacheLookup::Stale {
+                body: "[]".to_string(),
+                etag: Some("\"abc\"".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_zero_max_age_is_always_stale() {
+        let temp = TempDir::new().unwrap();
+        let cache = MetadataCache::new(
+            temp.path().to_path_buf(),
+            CachePolicy::MaxAge(Duration::ZERO),
+        );
+        cache.store("json/versions", None, "[]").unwrap();
+        assert!(matches!(
+            cache.lookup("json/versions"),
+            CacheLookup::Stale { etag: None, .. }
+        ));
+    }
+
+    #[test]
+    fn test_invalidate_removes_entry() {
+        let temp = TempDir::new().unwrap();
+        let cache = MetadataCache::new(temp.path().to_path_buf(), CachePolicy::Offline);
+        cache.store("json/versions", None, "[]").unwrap();
+        cache.invalidate("json/versions");
+        assert_eq!(cache.lookup("json/versions"), CacheLookup::Miss);
+    }
+}
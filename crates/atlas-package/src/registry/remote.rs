@@ -1,12 +1,73 @@
-use super::{PackageMetadata, Registry, RegistryError, RegistryResult};
+use super::metadata_cache::{CacheLookup, CachePolicy, MetadataCache};
+use super::{
+    latest_version, PackageInfo, PackageMetadata, PackageUpload, Registry, RegistryError,
+    RegistryResult, SearchHit, SearchQuery, SearchResults, VersionEntry,
+};
+use crate::signing::PackageSignature;
 use reqwest::blocking::Client;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use reqwest::StatusCode;
 use semver::Version;
+use serde::Deserialize;
 use std::time::Duration;
 
 /// Remote HTTP registry
 pub struct RemoteRegistry {
     base_url: String,
     client: Client,
+    /// Cache for version index responses
+    metadata_cache: Option<MetadataCache>,
+    /// Bearer token for write operations (publish, yank)
+    token: Option<String>,
+}
+
+/// Per-version metadata document served by the registry
+#[derive(Debug, Deserialize)]
+struct MetadataDocument {
+    #[serde(default)]
+    checksum: String,
+    #[serde(default)]
+    dependencies: Vec<String>,
+    #[serde(default)]
+    yanked: bool,
+    #[serde(default)]
+    signature: Option<PackageSignature>,
+}
+
+/// Publisher keys document (`/packages/<name>/keys`)
+#[derive(Debug, Deserialize)]
+struct KeysDocument {
+    #[serde(default)]
+    keys: Vec<String>,
+}
+
+/// Search response: one page of hits plus the overall match count
+#[derive(Debug, Deserialize)]
+struct SearchDocument {
+    total: usize,
+    #[serde(default)]
+    packages: Vec<SearchHit>,
+}
+
+/// Package-level document (`/packages/<name>`) describing the latest version
+#[derive(Debug, Deserialize)]
+struct InfoDocument {
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    license: Option<String>,
+    #[serde(default)]
+    repository: Option<String>,
+    #[serde(default)]
+    homepage: Option<String>,
+    #[serde(default)]
+    keywords: Vec<String>,
+    #[serde(default)]
+    features: Vec<String>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+    #[serde(default)]
+    downloads: Option<u64>,
 }
 
 impl RemoteRegistry {
@@ -16,7 +77,12 @@ impl RemoteRegistry {
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { base_url, client }
+        Self {
+            base_url,
+            client,
+            metadata_cache: None,
+            token: None,
+        }
     }
 
     /// Create with custom timeout
@@ -26,56 +92,334 @@ impl RemoteRegistry {
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { base_url, client }
+        Self {
+            base_url,
+            client,
+            metadata_cache: None,
+            token: None,
+        }
+    }
+
+    /// Cache metadata responses on disk (see `MetadataCache`)
+    pub fn with_metadata_cache(mut self, cache: MetadataCache) -> Self {
+        self.metadata_cache = Some(cache);
+        self
+    }
+
+    /// Authenticate write operations with a bearer token
+    pub fn with_token(mut self, token: String) -> Self {
+        self.token = Some(token);
+        self
     }
 
     /// Get base URL
     pub fn base_url(&self) -> &str {
         &self.base_url
     }
+
+    /// Fetch a metadata document, honouring the cache policy and revalidating
+    /// stale entries with `If-None-Match`.
+    fn fetch_cached(&self, url: &str, key: &str, not_found: &str) -> RegistryResult<String> {
+        let lookup = match &self.metadata_cache {
+            Some(cache) => cache.lookup(key),
+            None => CacheLookup::Miss,
+        };
+
+        let (stale_body, etag) = match lookup {
+            CacheLookup::Fresh(body) => return Ok(body),
+            CacheLookup::Stale { body, etag } => (Some(body), etag),
+            CacheLookup::Miss => (None, None),
+        };
+
+        if let Some(cache) = &self.metadata_cache {
+            if cache.policy() == CachePolicy::Offline {
+                return stale_body.ok_or_else(|| {
+                    RegistryError::Unavailable(format!("{} is not cached (offline)", key))
+                });
+            }
+        }
+
+        let mut request = self.client.get(url);
+        if let (Some(etag), Some(_)) = (&etag, &stale_body) {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        let response = request
+            .send()
+            .map_err(|e| RegistryError::NetworkError(e.to_string()))?;
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let (Some(cache), Some(body)) = (&self.metadata_cache, stale_body) {
+                let _ = cache.touch(key);
+                return Ok(body);
+            }
+        }
+        if !response.status().is_success() {
+            return Err(RegistryError::PackageNotFound(not_found.to_string()));
+        }
+
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let body = response
+            .text()
+            .map_err(|e| RegistryError::NetworkError(e.to_string()))?;
+        if let Some(cache) = &self.metadata_cache {
+            let _ = cache.store(key, etag, &body);
+        }
+        Ok(body)
+    }
+}
+
+/// Percent-encode a query-string value
+fn encode_query_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+/// Parse a search response, keeping the caller's page and page size
+pub(crate) fn parse_search_results(
+    body: &str,
+    query: &SearchQuery,
+) -> RegistryResult<SearchResults> {
+    let document: SearchDocument =
+        serde_json::from_str(body).map_err(|e| RegistryError::ParseError(e.to_string()))?;
+    Ok(SearchResults {
+        hits: document.packages,
+        total: document.total,
+        page: query.page,
+        per_page: query.per_page,
+    })
+}
+
+/// Map a rejected upload's status and body to an error
+pub(crate) fn publish_error(
+    status: StatusCode,
+    body: &str,
+    upload: &PackageUpload,
+) -> RegistryError {
+    let reason = if body.trim().is_empty() {
+        status.to_string()
+    } else {
+        body.trim().to_string()
+    };
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => RegistryError::Unauthorized(reason),
+        StatusCode::CONFLICT => RegistryError::AlreadyPublished {
+            package: upload.name().to_string(),
+            version: upload.version().to_string(),
+        },
+        StatusCode::BAD_REQUEST
+        | StatusCode::PAYLOAD_TOO_LARGE
+        | StatusCode::UNPROCESSABLE_ENTITY => RegistryError::InvalidPackage(reason),
+        _ => RegistryError::NetworkError(format!(
+            "publish {}@{} failed: HTTP {}",
+            upload.name(),
+            upload.version(),
+            status
+        )),
+    }
+}
+
+/// Parse a version index: a JSON array of `{ "version": ..., "yanked": ... }`
+pub(crate) fn parse_version_index(body: &str) -> RegistryResult<Vec<VersionEntry>> {
+    let mut entries: Vec<VersionEntry> =
+        serde_json::from_str(body).map_err(|e| RegistryError::ParseError(e.to_string()))?;
+    entries.sort_by(|a, b| a.version.cmp(&b.version));
+    Ok(entries)
 }
 
 impl Registry for RemoteRegistry {
     fn query_versions(&self, package: &str) -> RegistryResult<Vec<Version>> {
+        Ok(self
+            .query_index(package)?
+            .into_iter()
+            .map(|entry| entry.version)
+            .collect())
+    }
+
+    fn query_index(&self, package: &str) -> RegistryResult<Vec<VersionEntry>> {
         let url = format!("{}/packages/{}/versions", self.base_url, package);
+        let body = self.fetch_cached(&url, &format!("{}/versions", package), package)?;
+        parse_version_index(&body)
+    }
+
+    fn get_metadata(&self, package: &str, version: &Version) -> RegistryResult<PackageMetadata> {
+        let url = format!("{}/packages/{}/{}", self.base_url, package, version);
+        let spec = format!("{}@{}", package, version);
+        let body = self.fetch_cached(&url, &spec, &spec)?;
+        let document: MetadataDocument =
+            serde_json::from_str(&body).map_err(|e| RegistryError::ParseError(e.to_string()))?;
+
+        Ok(PackageMetadata::new(package.to_string(), version.clone())
+            .with_checksum(document.checksum)
+            .with_dependencies(document.dependencies)
+            .with_yanked(document.yanked)
+            .with_signature(document.signature)
+            .with_download_url(format!(
+                "{}/downloads/{}/{}",
+                self.base_url, package, version
+            )))
+    }
+
+    fn public_keys(&self, package: &str) -> RegistryResult<Vec<String>> {
+        let url = format!("{}/packages/{}/keys", self.base_url, package);
         let response = self
             .client
             .get(&url)
             .send()
             .map_err(|e| RegistryError::NetworkError(e.to_string()))?;
-
+        // A package without registered keys is unsigned, not an error
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
         if !response.status().is_success() {
-            return Err(RegistryError::PackageNotFound(package.to_string()));
+            return Err(RegistryError::NetworkError(format!(
+                "HTTP {} fetching keys for {}",
+                response.status(),
+                package
+            )));
         }
-
-        // For now, return mock versions
-        // In a real implementation, this would parse JSON response
-        Ok(vec![
-            Version::new(1, 0, 0),
-            Version::new(1, 1, 0),
-            Version::new(2, 0, 0),
-        ])
+        let body = response
+            .text()
+            .map_err(|e| RegistryError::NetworkError(e.to_string()))?;
+        let document: KeysDocument =
+            serde_json::from_str(&body).map_err(|e| RegistryError::ParseError(e.to_string()))?;
+        Ok(document.keys)
     }
 
-    fn get_metadata(&self, package: &str, version: &Version) -> RegistryResult<PackageMetadata> {
-        let url = format!("{}/packages/{}/{}", self.base_url, package, version);
+    fn search(&self, query: &SearchQuery) -> RegistryResult<SearchResults> {
+        // Search results change constantly, so they bypass the metadata cache.
+        let url = format!(
+            "{}/search?q={}&page={}&per_page={}",
+            self.base_url,
+            encode_query_value(&query.text),
+            query.page,
+            query.per_page
+        );
         let response = self
             .client
             .get(&url)
             .send()
             .map_err(|e| RegistryError::NetworkError(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(RegistryError::NetworkError(format!(
+                "search failed: HTTP {}",
+                response.status()
+            )));
+        }
+        let body = response
+            .text()
+            .map_err(|e| RegistryError::NetworkError(e.to_string()))?;
+        parse_search_results(&body, query)
+    }
+
+    fn package_info(&self, package: &str) -> RegistryResult<PackageInfo> {
+        let versions = self.query_index(package)?;
+        let latest = latest_version(&versions)
+            .cloned()
+            .ok_or_else(|| RegistryError::PackageNotFound(package.to_string()))?;
+        let url = format!("{}/packages/{}", self.base_url, package);
+        let body = self.fetch_cached(&url, package, package)?;
+        let document: InfoDocument =
+            serde_json::from_str(&body).map_err(|e| RegistryError::ParseError(e.to_string()))?;
+
+        Ok(PackageInfo {
+            name: package.to_string(),
+            latest,
+            versions,
+            description: document.description,
+            license: document.license,
+            repository: document.repository,
+            homepage: document.homepage,
+            keywords: document.keywords,
+            features: document.features,
+            dependencies: document.dependencies,
+            downloads: document.downloads,
+        })
+    }
+
+    fn yank(&self, package: &str, version: &Version, yanked: bool) -> RegistryResult<()> {
+        let token = self.token.as_ref().ok_or_else(|| {
+            RegistryError::Unavailable("yanking requires a registry token".to_string())
+        })?;
+        let url = format!("{}/packages/{}/{}/yank", self.base_url, package, version);
+        let request = if yanked {
+            self.client.put(&url)
+        } else {
+            self.client.delete(&url)
+        };
+        let response = request
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .send()
+            .map_err(|e| RegistryError::NetworkError(e.to_string()))?;
 
         if !response.status().is_success() {
-            return Err(RegistryError::PackageNotFound(format!(
-                "{}@{}",
-                package, version
+            return Err(RegistryError::NetworkError(format!(
+                "yank {}@{} failed: HTTP {}",
+                package,
+                version,
+                response.status()
             )));
         }
 
-        // For now, return mock metadata
-        // In a real implementation, this would parse JSON response
+        // Drop cached metadata so the new yank state is seen immediately.
+        if let Some(cache) = &self.metadata_cache {
+            cache.invalidate(&format!("{}/versions", package));
+            cache.invalidate(&format!("{}@{}", package, version));
+        }
+        Ok(())
+    }
+
+    /// `PUT /packages/<name>/<version>` with the tarball as the body; the
+    /// checksum and signature travel in `X-Atlas-*` headers so the registry
+    /// can verify the archive before storing it.
+    fn publish(&self, upload: &PackageUpload) -> RegistryResult<PackageMetadata> {
+        let token = self.token.as_ref().ok_or_else(|| {
+            RegistryError::Unauthorized("publishing requires a registry token".to_string())
+        })?;
+        let (package, version) = (upload.name(), upload.version());
+        let url = format!("{}/packages/{}/{}", self.base_url, package, version);
+        let mut request = self
+            .client
+            .put(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .header(CONTENT_TYPE, "application/gzip")
+            .header("X-Atlas-Checksum", &upload.checksum);
+        if let Some(signature) = &upload.signature {
+            request = request
+                .header("X-Atlas-Key-Id", &signature.key_id)
+                .header("X-Atlas-Signature", &signature.signature);
+        }
+        let response = request
+            .body(upload.archive.clone())
+            .send()
+            .map_err(|e| RegistryError::NetworkError(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().unwrap_or_default();
+            return Err(publish_error(status, &body, upload));
+        }
+
+        // The new version must show up in the next index lookup
+        if let Some(cache) = &self.metadata_cache {
+            cache.invalidate(&format!("{}/versions", package));
+            cache.invalidate(package);
+        }
         Ok(PackageMetadata::new(package.to_string(), version.clone())
-            .with_checksum("mock_checksum".to_string())
+            .with_checksum(upload.checksum.clone())
+            .with_dependencies(upload.dependency_names())
+            .with_signature(upload.signature.clone())
             .with_download_url(format!(
                 "{}/downloads/{}/{}",
                 self.base_url, package, version
@@ -113,6 +457,114 @@ mod tests {
         assert_eq!(registry.base_url(), "https://registry.example.com");
     }
 
+    #[test]
+    fn test_parse_version_index_with_yanks() {
+        let entries =
+            parse_version_index(r#"[{"version":"1.1.0","yanked":true},{"version":"1.0.0"}]"#)
+                .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].version, Version::new(1, 0, 0));
+        assert!(!entries[0].yanked);
+        assert!(entries[1].yanked);
+    }
+
+    #[test]
+    fn test_parse_version_index_rejects_garbage() {
+        assert!(matches!(
+            parse_version_index("not json"),
+            Err(RegistryError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_search_results_keeps_page() {
+        let query = SearchQuery::new("http").with_page(3).with_per_page(5);
+        let results = parse_search_results(
+            r#"{"total":12,"packages":[{"name":"http","version":"1.2.0","downloads":7}]}"#,
+            &query,
+        )
+        .unwrap();
+        assert_eq!(results.total, 12);
+        assert_eq!(results.page, 3);
+        assert_eq!(results.total_pages(), 3);
+        assert_eq!(results.hits[0].version, Version::new(1, 2, 0));
+    }
+
+    #[test]
+    fn test_encode_query_value() {
+        assert_eq!(encode_query_value("http client"), "http%20client");
+        assert_eq!(encode_query_value("a&b=c"), "a%26b%3Dc");
+    }
+
+    #[test]
+    fn test_yank_without_token_errors() {
+        let registry = RemoteRegistry::new("https://registry.example.com".to_string());
+        let err = registry
+            .yank("json", &Version::new(1, 0, 0), true)
+            .unwrap_err();
+        assert!(matches!(err, RegistryError::Unavailable(_)));
+    }
+
+    fn upload() -> PackageUpload {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("atlas.toml"),
+            "[package]\nname = \"json\"\nversion = \"1.0.0\"\nlicense = \"MIT\"\ndescription = \"JSON\"\n",
+        )
+        .unwrap();
+        PackageUpload::from_dir(dir.path()).unwrap()
+    }
+
+    #[test]
+    fn test_publish_without_token_errors() {
+        let registry = RemoteRegistry::new("https://registry.example.com".to_string());
+        assert!(matches!(
+            registry.publish(&upload()),
+            Err(RegistryError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_publish_error_maps_status() {
+        let upload = upload();
+        assert!(matches!(
+            publish_error(StatusCode::FORBIDDEN, "", &upload),
+            RegistryError::Unauthorized(_)
+        ));
+        assert!(matches!(
+            publish_error(StatusCode::CONFLICT, "", &upload),
+            RegistryError::AlreadyPublished { .. }
+        ));
+        match publish_error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "checksum mismatch\n",
+            &upload,
+        ) {
+            RegistryError::InvalidPackage(reason) => assert_eq!(reason, "checksum mismatch"),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_offline_cache_serves_without_network() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let cache = MetadataCache::new(temp.path().to_path_buf(), CachePolicy::Offline);
+        cache
+            .store("json/versions", None, r#"[{"version":"1.0.0"}]"#)
+            .unwrap();
+        let registry =
+            RemoteRegistry::new("http://127.0.0.1:9".to_string()).with_metadata_cache(cache);
+
+        assert_eq!(
+            registry.query_versions("json").unwrap(),
+            vec![Version::new(1, 0, 0)]
+        );
+        assert!(matches!(
+            registry.query_versions("missing"),
+            Err(RegistryError::Unavailable(_))
+        ));
+    }
+
     #[test]
     fn test_remote_registry_with_timeout() {
         let registry = RemoteRegistry::with_timeout("https://registry.example.com".to_string(), 10);
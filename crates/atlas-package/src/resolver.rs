@@ -1,14 +1,21 @@
 use crate::manifest::{Dependency, PackageManifest};
+use crate::target::{BuildContext, TargetPlatform};
 use semver::{Version, VersionReq};
 use std::collections::HashMap;
 use thiserror::Error;
 
 pub mod conflict;
+pub mod duplicates;
+pub mod explain;
 mod graph;
+pub mod update;
 mod version_solver;
 
 pub use conflict::{Conflict, ConflictResolver, ConflictingConstraint};
+pub use duplicates::{DuplicateLint, DuplicatePackage, DuplicateReport, DuplicateVersion};
+pub use explain::{RejectedVersion, ResolutionExplanation};
 pub use graph::DependencyGraph;
+pub use update::{LockfileUpdate, UpdateMode};
 pub use version_solver::VersionSolver;
 
 #[derive(Debug, Error)]
@@ -47,6 +54,23 @@ pub struct Resolver {
 
     /// Version solver
     solver: VersionSolver,
+
+    /// Whether to pick the highest or lowest satisfying version
+    mode: ResolutionMode,
+
+    /// Which dependency sections and target platform apply
+    context: BuildContext,
+}
+
+/// Which satisfying version the resolver selects for each package
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResolutionMode {
+    /// Newest version allowed by every constraint (normal installs)
+    #[default]
+    Highest,
+    /// Oldest version allowed by every constraint, used to check that
+    /// declared lower bounds actually work (`--minimal-versions`)
+    Minimal,
 }
 
 /// Version constraint with source tracking
@@ -62,9 +86,22 @@ impl Resolver {
             graph: DependencyGraph::new(),
             constraints: HashMap::new(),
             solver: VersionSolver::new(),
+            mode: ResolutionMode::default(),
+            context: BuildContext::production(TargetPlatform::host()),
         }
     }
 
+    /// Set whether the highest or lowest satisfying versions are selected
+    pub fn set_mode(&mut self, mode: ResolutionMode) {
+        self.mode = mode;
+    }
+
+    /// Set the build context whose dependency sections are resolved
+    /// (defaults to a production build for the host platform)
+    pub fn set_context(&mut self, context: BuildContext) {
+        self.context = context;
+    }
+
     /// Resolve dependencies from a manifest
     pub fn resolve(&mut self, manifest: &PackageManifest) -> ResolverResult<Resolution> {
         // Add root package to graph
@@ -72,8 +109,8 @@ impl Resolver {
         let root_version = manifest.package.version.clone();
         self.graph.add_package(root_name.clone(), root_version);
 
-        // Add direct dependencies from manifest
-        for (name, dep) in &manifest.dependencies {
+        // Add direct dependencies that apply to this build
+        for (name, dep) in &manifest.dependencies_for(&self.context) {
             self.add_constraint(&root_name, name, dep)?;
         }
 
@@ -110,6 +147,10 @@ impl Resolver {
             .or_default()
             .push(constraint);
 
+        if dep.channel().allows_prerelease() {
+            self.solver.allow_prerelease_for(package);
+        }
+
         Ok(())
     }
 
@@ -132,20 +173,41 @@ impl Resolver {
     /// Run constraint solver to find compatible versions
     fn solve(&mut self) -> ResolverResult<Resolution> {
         let mut resolved_packages = HashMap::new();
+        let mut explanations = HashMap::new();
 
         // For each package with constraints, find compatible version
         for (package, constraints) in &self.constraints {
             let requirements: Vec<VersionReq> =
                 constraints.iter().map(|c| c.requirement.clone()).collect();
 
-            // Find maximum version satisfying all constraints
-            let version = self
+            let selected = match self.mode {
+                ResolutionMode::Highest => {
+                    self.solver.max_satisfying_version(package, &requirements)
+                }
+                ResolutionMode::Minimal => {
+                    self.solver.min_satisfying_version(package, &requirements)
+                }
+            };
+            let version = selected.ok_or_else(|| ResolverError::NoSatisfyingVersion {
+                package: package.clone(),
+                constraints: format!("{:?}", requirements),
+            })?;
+
+            let available = self
                 .solver
-                .max_satisfying_version(package, &requirements)
-                .ok_or_else(|| ResolverError::NoSatisfyingVersion {
-                    package: package.clone(),
-                    constraints: format!("{:?}", requirements),
-                })?;
+                .get_versions(package)
+                .cloned()
+                .unwrap_or_default();
+            explanations.insert(
+                package.clone(),
+                ResolutionExplanation::with_yanked(
+                    package,
+                    version.clone(),
+                    constraints,
+                    &available,
+                    |v| self.solver.is_yanked(package, v),
+                ),
+            );
 
             resolved_packages.insert(
                 package.clone(),
@@ -159,9 +221,26 @@ impl Resolver {
 
         Ok(Resolution {
             packages: resolved_packages,
+            explanations,
         })
     }
 
+    /// Allow pre-release versions of every package to be selected (`--pre`).
+    ///
+    /// Without this, a pre-release is only chosen when a constraint names it
+    /// explicitly or the dependency tracks the nightly channel.
+    pub fn set_allow_prereleases(&mut self, allow: bool) {
+        self.solver.set_allow_prereleases(allow);
+    }
+
+    /// Mark versions of a package as yanked.
+    ///
+    /// Yanked versions are never chosen by a fresh resolution, but a lockfile
+    /// that already pins one stays valid (see `resolve_with_lockfile`).
+    pub fn mark_yanked(&mut self, package: &str, versions: impl IntoIterator<Item = Version>) {
+        self.solver.mark_yanked(package, versions);
+    }
+
     /// Check if constraints are compatible
     pub fn check_compatibility(&self, package: &str) -> ResolverResult<bool> {
         if let Some(constraints) = self.constraints.get(package) {
@@ -214,7 +293,7 @@ impl Resolver {
         // If lockfile exists and is valid, use it
         if let Some(lock) = lockfile {
             if self.lockfile_is_valid(manifest, lock)? {
-                return self.resolution_from_lockfile(lock);
+                return Ok(Resolution::from_lockfile(manifest, lock));
             }
         }
 
@@ -234,7 +313,7 @@ impl Resolver {
         }
 
         // Check that all manifest dependencies are in lockfile with compatible versions
-        for (name, dep) in &manifest.dependencies {
+        for (name, dep) in &manifest.dependencies_for(&self.context) {
             let locked_pkg = match lockfile.get_package(name) {
                 Some(pkg) => pkg,
                 None => return Ok(false), // Missing dependency
@@ -256,26 +335,6 @@ impl Resolver {
         Ok(true)
     }
 
-    /// Create resolution from lockfile
-    fn resolution_from_lockfile(
-        &self,
-        lockfile: &crate::lockfile::Lockfile,
-    ) -> ResolverResult<Resolution> {
-        let mut resolution = Resolution::new();
-
-        for locked_pkg in &lockfile.packages {
-            let dependencies: Vec<String> = locked_pkg.dependencies.keys().cloned().collect();
-
-            resolution.add_package(ResolvedPackage {
-                name: locked_pkg.name.clone(),
-                version: locked_pkg.version.clone(),
-                dependencies,
-            });
-        }
-
-        Ok(resolution)
-    }
-
     /// Generate lockfile from resolution
     pub fn generate_lockfile(&self, resolution: &Resolution) -> crate::lockfile::Lockfile {
         use crate::lockfile::{LockedPackage, LockedSource, Lockfile, LockfileMetadata};
@@ -321,13 +380,73 @@ impl Default for Resolver {
 pub struct Resolution {
     /// Resolved packages with exact versions
     pub packages: HashMap<String, ResolvedPackage>,
+
+    /// Decision trail for each resolved package (see `explain`)
+    pub explanations: HashMap<String, ResolutionExplanation>,
 }
 
 impl Resolution {
     pub fn new() -> Self {
         Self {
             packages: HashMap::new(),
+            explanations: HashMap::new(),
+        }
+    }
+
+    /// Rebuild a resolution from a lockfile.
+    ///
+    /// The lockfile does not record which versions were available, so the
+    /// explanations list only the constraints that pull each package in: the
+    /// manifest's requirement and an exact pin from every locked dependent.
+    pub fn from_lockfile(manifest: &PackageManifest, lockfile: &crate::lockfile::Lockfile) -> Self {
+        let mut resolution = Resolution::new();
+        let root = &manifest.package.name;
+
+        for locked_pkg in &lockfile.packages {
+            let dependencies: Vec<String> = locked_pkg.dependencies.keys().cloned().collect();
+
+            let mut required_by = Vec::new();
+            if let Some(dep) = manifest.find_dependency(&locked_pkg.name) {
+                if let Ok(requirement) = dep.version_constraint().unwrap_or("*").parse() {
+                    required_by.push(VersionConstraint {
+                        requirement,
+                        source: root.clone(),
+                    });
+                }
+            }
+            for dependent in &lockfile.packages {
+                if let Some(version) = dependent.dependencies.get(&locked_pkg.name) {
+                    if let Ok(requirement) = format!("={}", version).parse() {
+                        required_by.push(VersionConstraint {
+                            requirement,
+                            source: format!("{} v{}", dependent.name, dependent.version),
+                        });
+                    }
+                }
+            }
+
+            resolution.explanations.insert(
+                locked_pkg.name.clone(),
+                ResolutionExplanation::new(
+                    &locked_pkg.name,
+                    locked_pkg.version.clone(),
+                    &required_by,
+                    &[],
+                ),
+            );
+            resolution.add_package(ResolvedPackage {
+                name: locked_pkg.name.clone(),
+                version: locked_pkg.version.clone(),
+                dependencies,
+            });
         }
+
+        resolution
+    }
+
+    /// Why `name` was resolved to its version, if it is part of the resolution
+    pub fn explain(&self, name: &str) -> Option<&ResolutionExplanation> {
+        self.explanations.get(name)
     }
 
     pub fn add_package(&mut self, package: ResolvedPackage) {
@@ -400,6 +519,178 @@ mod tests {
         assert_eq!(resolution.package_count(), 1);
     }
 
+    #[test]
+    fn test_resolve_records_explanations() {
+        let manifest = PackageManifest::from_str(
+            r#"
+            [package]
+            name = "app"
+            version = "0.1.0"
+
+            [dependencies]
+            dep = "^1.0"
+            "#,
+        )
+        .unwrap();
+        let resolution = Resolver::new().resolve(&manifest).unwrap();
+
+        let explanation = resolution.explain("dep").unwrap();
+        assert_eq!(explanation.version, Version::new(1, 2, 0));
+        assert_eq!(explanation.required_by[0].source, "app");
+        let newer = explanation.why_not(&Version::new(2, 0, 0)).unwrap();
+        assert_eq!(newer.excluded_by.len(), 1);
+        assert!(resolution.explain("missing").is_none());
+    }
+
+    #[test]
+    fn test_minimal_mode_selects_lowest_version() {
+        let manifest = PackageManifest::from_str(
+            r#"
+            [package]
+            name = "app"
+            version = "0.1.0"
+
+            [dependencies]
+            dep = "^1.1"
+            "#,
+        )
+        .unwrap();
+        let mut resolver = Resolver::new();
+        resolver.set_mode(ResolutionMode::Minimal);
+        let resolution = resolver.resolve(&manifest).unwrap();
+
+        assert_eq!(
+            resolution.get_package("dep").unwrap().version,
+            Version::new(1, 1, 0)
+        );
+        let newer = resolution
+            .explain("dep")
+            .unwrap()
+            .why_not(&Version::new(1, 2, 0))
+            .unwrap();
+        assert!(newer.is_superseded());
+    }
+
+    #[test]
+    fn test_dev_dependencies_only_resolved_in_development_context() {
+        let manifest = PackageManifest::from_str(
+            r#"
+            [package]
+            name = "app"
+            version = "0.1.0"
+
+            [dependencies]
+            dep = "^1.0"
+
+            [dev-dependencies]
+            helper = "^1.0"
+            "#,
+        )
+        .unwrap();
+
+        let resolution = Resolver::new().resolve(&manifest).unwrap();
+        assert!(resolution.get_package("dep").is_some());
+        assert!(resolution.get_package("helper").is_none());
+
+        let mut resolver = Resolver::new();
+        resolver.set_context(BuildContext::development(TargetPlatform::host()));
+        let resolution = resolver.resolve(&manifest).unwrap();
+        assert!(resolution.get_package("helper").is_some());
+    }
+
+    #[test]
+    fn test_fresh_resolution_skips_yanked_but_lockfile_keeps_it() {
+        use crate::lockfile::{LockedPackage, LockedSource, Lockfile};
+
+        let manifest = PackageManifest::from_str(
+            r#"
+            [package]
+            name = "app"
+            version = "0.1.0"
+
+            [dependencies]
+            dep = "^1.0"
+            "#,
+        )
+        .unwrap();
+
+        let mut resolver = Resolver::new();
+        resolver.mark_yanked("dep", [Version::new(1, 2, 0)]);
+        let fresh = resolver.resolve(&manifest).unwrap();
+        assert_eq!(
+            fresh.get_package("dep").unwrap().version,
+            Version::new(1, 1, 0)
+        );
+        assert!(
+            fresh
+                .explain("dep")
+                .unwrap()
+                .why_not(&Version::new(1, 2, 0))
+                .unwrap()
+                .yanked
+        );
+
+        let mut lockfile = Lockfile::new();
+        lockfile.add_package(LockedPackage {
+            name: "dep".to_string(),
+            version: Version::new(1, 2, 0),
+            source: LockedSource::Registry { registry: None },
+            checksum: None,
+            dependencies: HashMap::new(),
+        });
+        let mut resolver = Resolver::new();
+        resolver.mark_yanked("dep", [Version::new(1, 2, 0)]);
+        let locked = resolver
+            .resolve_with_lockfile(&manifest, Some(&lockfile))
+            .unwrap();
+        assert_eq!(
+            locked.get_package("dep").unwrap().version,
+            Version::new(1, 2, 0)
+        );
+    }
+
+    #[test]
+    fn test_resolution_from_lockfile_explains_dependents() {
+        use crate::lockfile::{LockedPackage, LockedSource, Lockfile};
+
+        let manifest = PackageManifest::from_str(
+            r#"
+            [package]
+            name = "app"
+            version = "0.1.0"
+
+            [dependencies]
+            web = "^2.0"
+            "#,
+        )
+        .unwrap();
+        let mut lockfile = Lockfile::new();
+        lockfile.add_package(LockedPackage {
+            name: "web".to_string(),
+            version: Version::new(2, 1, 0),
+            source: LockedSource::Registry { registry: None },
+            checksum: None,
+            dependencies: HashMap::from([("json".to_string(), Version::new(1, 4, 0))]),
+        });
+        lockfile.add_package(LockedPackage {
+            name: "json".to_string(),
+            version: Version::new(1, 4, 0),
+            source: LockedSource::Registry { registry: None },
+            checksum: None,
+            dependencies: HashMap::new(),
+        });
+
+        let resolution = Resolution::from_lockfile(&manifest, &lockfile);
+        let json = resolution.explain("json").unwrap();
+        assert_eq!(json.required_by.len(), 1);
+        assert_eq!(json.required_by[0].source, "web v2.1.0");
+        assert_eq!(json.required_by[0].requirement.to_string(), "=1.4.0");
+        assert_eq!(
+            resolution.explain("web").unwrap().required_by[0].source,
+            "app"
+        );
+    }
+
     #[test]
     fn test_resolved_package_new() {
         let pkg = ResolvedPackage::new("test".to_string(), Version::new(1, 0, 0));
@@ -0,0 +1,276 @@
+//! Duplicate-version report (`[lints] duplicate-versions`)
+//!
+//! Walks the locked dependency graph from the root package and finds packages
+//! that are reached at more than one semver-incompatible version, recording
+//! every path that leads to each version.
+
+use crate::lockfile::Lockfile;
+use crate::manifest::{LintLevel, Lints, PackageManifest};
+use semver::Version;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Packages present at more than one semver-incompatible version
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DuplicateReport {
+    /// Sorted by package name
+    pub packages: Vec<DuplicatePackage>,
+}
+
+/// One package and the incompatible versions of it in the graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicatePackage {
+    pub name: String,
+    /// Ascending by version
+    pub versions: Vec<DuplicateVersion>,
+}
+
+/// A version of a duplicated package and how the graph reaches it
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateVersion {
+    pub version: Version,
+    /// Each path is `root -> dep vX -> ... -> name vY`
+    pub paths: Vec<String>,
+}
+
+/// Outcome of checking a report against `[lints]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateLint {
+    /// No duplicates, or the lint is set to `allow`
+    Clean,
+    /// Duplicates to report; the build may continue
+    Warn,
+    /// `deny` and the duplicate count exceeds the budget
+    Deny,
+}
+
+/// Versions that can be unified: same major, or same minor below 1.0,
+/// or the exact patch below 0.1
+pub(crate) fn compatibility_class(version: &Version) -> (u64, u64, u64) {
+    match (version.major, version.minor) {
+        (0, 0) => (0, 0, version.patch),
+        (0, minor) => (0, minor, 0),
+        (major, _) => (major, 0, 0),
+    }
+}
+
+impl DuplicateReport {
+    /// Analyse a lockfile whose root is `manifest`.
+    ///
+    /// Direct dependencies are the locked entries the manifest declares. Each
+    /// locked entry's `dependencies` map records the version it was built
+    /// against; an edge whose version differs from the locked entry of that
+    /// name is a separate copy, and the walk stops there.
+    pub fn from_lockfile(manifest: &PackageManifest, lockfile: &Lockfile) -> Self {
+        let root = manifest.package.name.clone();
+        let mut reached: BTreeMap<String, BTreeMap<Version, Vec<String>>> = BTreeMap::new();
+
+        let mut direct: Vec<_> = lockfile
+            .packages
+            .iter()
+            .filter(|p| manifest.find_dependency(&p.name).is_some())
+            .collect();
+        direct.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for package in direct {
+            let mut path = vec![root.clone()];
+            walk(
+                lockfile,
+                &package.name,
+                &package.version,
+                &mut path,
+                &mut reached,
+            );
+        }
+
+        let packages = reached
+            .into_iter()
+            .filter(|(_, versions)| {
+                versions
+                    .keys()
+                    .map(compatibility_class)
+                    .collect::<BTreeSet<_>>()
+                    .len()
+                    > 1
+            })
+            .map(|(name, versions)| DuplicatePackage {
+                name,
+                versions: versions
+                    .into_iter()
+                    .map(|(version, paths)| DuplicateVersion { version, paths })
+                    .collect(),
+            })
+            .collect();
+        Self { packages }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+
+    /// Incompatible copies beyond one per package. Compatible versions of
+    /// one package (1.2 and 1.4) count once.
+    pub fn duplicate_count(&self) -> usize {
+        self.packages
+            .iter()
+            .map(|p| {
+                p.versions
+                    .iter()
+                    .map(|v| compatibility_class(&v.version))
+                    .collect::<BTreeSet<_>>()
+                    .len()
+                    - 1
+            })
+            .sum()
+    }
+
+    /// Apply the `[lints]` settings to this report
+    pub fn check(&self, lints: &Lints) -> DuplicateLint {
+        if self.is_empty() || lints.duplicate_versions == LintLevel::Allow {
+            return DuplicateLint::Clean;
+        }
+        if lints.duplicate_versions == LintLevel::Deny
+            && self.duplicate_count() > lints.duplicate_budget
+        {
+            return DuplicateLint::Deny;
+        }
+        DuplicateLint::Warn
+    }
+
+    /// Human-readable report, one block per duplicated package
+    pub fn report(&self) -> String {
+        let mut report = String::new();
+        for package in &self.packages {
+            report.push_str(&format!(
+                "{} has {} semver-incompatible versions:\n",
+                package.name,
+                package.versions.len()
+            ));
+            for version in &package.versions {
+                report.push_str(&format!("  v{}\n", version.version));
+                for path in &version.paths {
+                    report.push_str(&format!("    {}\n", path));
+                }
+            }
+        }
+        report
+    }
+}
+
+/// Depth-first walk recording the path to every (name, version) reached
+fn walk(
+    lockfile: &Lockfile,
+    name: &str,
+    version: &Version,
+    path: &mut Vec<String>,
+    reached: &mut BTreeMap<String, BTreeMap<Version, Vec<String>>>,
+) {
+    let label = format!("{} v{}", name, version);
+    if path.contains(&label) {
+        return;
+    }
+    path.push(label);
+    reached
+        .entry(name.to_string())
+        .or_default()
+        .entry(version.clone())
+        .or_default()
+        .push(path.join(" -> "));
+
+    if let Some(locked) = lockfile
+        .get_package(name)
+        .filter(|locked| &locked.version == version)
+    {
+        let mut edges: Vec<_> = locked.dependencies.iter().collect();
+        edges.sort();
+        for (dep, dep_version) in edges {
+            walk(lockfile, dep, dep_version, path, reached);
+        }
+    }
+    path.pop();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lockfile::{LockedPackage, LockedSource};
+
+    fn locked(name: &str, version: &str, deps: &[(&str, &str)]) -> LockedPackage {
+        LockedPackage {
+            name: name.to_string(),
+            version: version.parse().unwrap(),
+            source: LockedSource::Registry { registry: None },
+            checksum: None,
+            dependencies: deps
+                .iter()
+                .map(|(n, v)| (n.to_string(), v.parse().unwrap()))
+                .collect(),
+        }
+    }
+
+    fn fixture() -> (PackageManifest, Lockfile) {
+        let manifest = PackageManifest::from_str(
+            r#"[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+http = "1.0"
+json = "2.0"
+log = "0.4"
+"#,
+        )
+        .unwrap();
+        let mut lockfile = Lockfile::new();
+        lockfile.add_package(locked(
+            "http",
+            "1.0.0",
+            &[("json", "1.4.0"), ("log", "0.4.2")],
+        ));
+        lockfile.add_package(locked("json", "2.0.0", &[]));
+        lockfile.add_package(locked("log", "0.4.5", &[]));
+        (manifest, lockfile)
+    }
+
+    #[test]
+    fn test_reports_incompatible_versions_with_paths() {
+        let (manifest, lockfile) = fixture();
+        let report = DuplicateReport::from_lockfile(&manifest, &lockfile);
+
+        // log 0.4.2 and 0.4.5 are compatible, so only json is reported
+        assert_eq!(report.packages.len(), 1);
+        assert_eq!(report.duplicate_count(), 1);
+        let json = &report.packages[0];
+        assert_eq!(json.name, "json");
+        assert_eq!(
+            json.versions[0].paths,
+            vec!["app -> http v1.0.0 -> json v1.4.0".to_string()]
+        );
+        assert_eq!(
+            json.versions[1].paths,
+            vec!["app -> json v2.0.0".to_string()]
+        );
+        assert!(report
+            .report()
+            .starts_with("json has 2 semver-incompatible versions:\n  v1.4.0\n"));
+    }
+
+    #[test]
+    fn test_lint_levels_and_budget() {
+        let (manifest, lockfile) = fixture();
+        let report = DuplicateReport::from_lockfile(&manifest, &lockfile);
+
+        let mut lints = Lints::default();
+        assert_eq!(report.check(&lints), DuplicateLint::Warn);
+        lints.duplicate_versions = LintLevel::Deny;
+        assert_eq!(report.check(&lints), DuplicateLint::Deny);
+        lints.duplicate_budget = 1;
+        assert_eq!(report.check(&lints), DuplicateLint::Warn);
+        lints.duplicate_versions = LintLevel::Allow;
+        assert_eq!(report.check(&lints), DuplicateLint::Clean);
+
+        assert_eq!(
+            DuplicateReport::default().check(&Lints::default()),
+            DuplicateLint::Clean
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Resolver decision trail (`atlas why`)
+//!
+//! Records, for every resolved package, which constraints pulled it in and why
+//! each other known version was not selected.
+
+use super::VersionConstraint;
+use semver::Version;
+
+/// Why a package was resolved to its version
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolutionExplanation {
+    pub package: String,
+    pub version: Version,
+    /// Constraints that pulled the package in, in the order they were added
+    pub required_by: Vec<VersionConstraint>,
+    /// Other known versions and why each was passed over
+    pub alternatives: Vec<RejectedVersion>,
+}
+
+/// A candidate version that was not selected
+#[derive(Debug, Clone, PartialEq)]
+pub struct RejectedVersion {
+    pub version: Version,
+    /// Constraints this version fails; empty when it was compatible but
+    /// another compatible version was preferred
+    pub excluded_by: Vec<VersionConstraint>,
+    /// Withdrawn by the publisher
+    pub yanked: bool,
+}
+
+impl RejectedVersion {
+    /// The version satisfied every constraint but another one was picked
+    pub fn is_superseded(&self) -> bool {
+        self.excluded_by.is_empty() && !self.yanked
+    }
+
+    /// One-line reason this version was not selected
+    pub fn reason(&self) -> String {
+        if self.is_superseded() {
+            return "compatible, but another matching version was preferred".to_string();
+        }
+        let mut reasons: Vec<String> = Vec::new();
+        if self.yanked {
+            reasons.push("yanked".to_string());
+        }
+        reasons.extend(
+            self.excluded_by
+                .iter()
+                .map(|c| format!("{} requires {}", c.source, c.requirement)),
+        );
+        format!("excluded: {}", reasons.join(", "))
+    }
+}
+
+impl ResolutionExplanation {
+    /// Build the explanation for `chosen` given the constraints on `package`
+    /// and every version the resolver knew about.
+    pub fn new(
+        package: &str,
+        chosen: Version,
+        constraints: &[VersionConstraint],
+        available: &[Version],
+    ) -> Self {
+        Self::with_yanked(package, chosen, constraints, available, |_| false)
+    }
+
+    /// Like [`ResolutionExplanation::new`], flagging versions for which
+    /// `is_yanked` returns `true`.
+    pub fn with_yanked(
+        package: &str,
+        chosen: Version,
+        constraints: &[VersionConstraint],
+        available: &[Version],
+        is_yanked: impl Fn(&Version) -> bool,
+    ) -> Self {
+        let mut alternatives: Vec<RejectedVersion> = available
+            .iter()
+            .filter(|v| **v != chosen)
+            .map(|v| RejectedVersion {
+                version: v.clone(),
+                excluded_by: constraints
+                    .iter()
+                    .filter(|c| !c.requirement.matches(v))
+                    .cloned()
+                    .collect(),
+                yanked: is_yanked(v),
+            })
+            .collect();
+        // Newest first: the versions a user most often asks about
+        alternatives.sort_by(|a, b| b.version.cmp(&a.version));
+
+        Self {
+            package: package.to_string(),
+            version: chosen,
+            required_by: constraints.to_vec(),
+            alternatives,
+        }
+    }
+
+    /// Why a specific version was not selected, if the resolver knew about it
+    pub fn why_not(&self, version: &Version) -> Option<&RejectedVersion> {
+        self.alternatives.iter().find(|r| &r.version == version)
+    }
+
+    /// Generate human-readable explanation
+    pub fn report(&self) -> String {
+        let mut report = format!("{} v{}\n", self.package, self.version);
+
+        if self.required_by.is_empty() {
+            report.push_str("  not required by any package\n");
+        } else {
+            report.push_str("  required by:\n");
+            for constraint in &self.required_by {
+                report.push_str(&format!(
+                    "    {} requires {}\n",
+                    constraint.source, constraint.requirement
+                ));
+            }
+        }
+
+        if !self.alternatives.is_empty() {
+            report.push_str("  other versions:\n");
+            for rejected in &self.alternatives {
+                report.push_str(&format!(
+                    "    {} — {}\n",
+                    rejected.version,
+                    rejected.reason()
+                ));
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constraint(req: &str, source: &str) -> VersionConstraint {
+        VersionConstraint {
+            requirement: req.parse().unwrap(),
+            source: source.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_explanation_marks_newer_versions_as_excluded() {
+        let constraints = vec![constraint("^1.0", "app"), constraint("<1.2.0", "lib")];
+        let available = vec![
+            Version::new(1, 0, 0),
+            Version::new(1, 1, 0),
+            Version::new(1, 2, 0),
+            Version::new(2, 0, 0),
+        ];
+        let explanation =
+            ResolutionExplanation::new("dep", Version::new(1, 1, 0), &constraints, &available);
+
+        assert_eq!(explanation.required_by.len(), 2);
+        let v2 = explanation.why_not(&Version::new(2, 0, 0)).unwrap();
+        assert_eq!(v2.excluded_by.len(), 2);
+        let v12 = explanation.why_not(&Version::new(1, 2, 0)).unwrap();
+        assert_eq!(v12.excluded_by, vec![constraint("<1.2.0", "lib")]);
+        assert!(explanation
+            .why_not(&Version::new(1, 0, 0))
+            .unwrap()
+            .is_superseded());
+        assert!(explanation.why_not(&Version::new(1, 1, 0)).is_none());
+    }
+
+    #[test]
+    fn test_explanation_reports_yanked_versions() {
+        let constraints = vec![constraint("^1.0", "app")];
+        let available = vec![Version::new(1, 0, 0), Version::new(1, 1, 0)];
+        let explanation = ResolutionExplanation::with_yanked(
+            "dep",
+            Version::new(1, 0, 0),
+            &constraints,
+            &available,
+            |v| *v == Version::new(1, 1, 0),
+        );
+
+        let rejected = explanation.why_not(&Version::new(1, 1, 0)).unwrap();
+        assert!(rejected.yanked);
+        assert!(!rejected.is_superseded());
+        assert_eq!(rejected.reason(), "excluded: yanked");
+    }
+
+    #[test]
+    fn test_explanation_report_lists_sources() {
+        let constraints = vec![constraint("^1.0", "app")];
+        let available = vec![Version::new(1, 0, 0), Version::new(2, 0, 0)];
+        let report =
+            ResolutionExplanation::new("dep", Version::new(1, 0, 0), &constraints, &available)
+                .report();
+
+        assert!(report.starts_with("dep v1.0.0"));
+        assert!(report.contains("app requires ^1.0"));
+        assert!(report.contains("2.0.0 — excluded: app requires ^1.0"));
+    }
+}
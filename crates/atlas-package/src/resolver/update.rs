@@ -0,0 +1,359 @@
+//! Lockfile updates (`atlas update --conservative` / `--aggressive`)
+//!
+//! Unlike a fresh resolution, an update starts from the existing lockfile:
+//! packages that are not being updated keep their locked version as long as
+//! the manifest still allows it, so the diff only shows what was asked for
+//! (plus whatever a changed manifest forces).
+
+use super::{Resolver, ResolverError, ResolverResult};
+use crate::lockfile::{LockedPackage, LockedSource, Lockfile, LockfileDiff};
+use crate::manifest::{Dependency, PackageManifest};
+use crate::registry::RegistryManager;
+use semver::{Version, VersionReq};
+use std::collections::HashMap;
+
+/// How far `Resolver::update` moves the packages it updates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UpdateMode {
+    /// Minimal bumps: the newest patch release of the locked `major.minor`.
+    /// A locked version the manifest no longer allows moves to the oldest
+    /// version that satisfies it.
+    Conservative,
+    /// The newest version the manifest's constraints allow
+    #[default]
+    Aggressive,
+}
+
+/// A recomputed lockfile and how it differs from the one it started from
+#[derive(Debug, Clone)]
+pub struct LockfileUpdate {
+    pub lockfile: Lockfile,
+    pub diff: LockfileDiff,
+}
+
+impl Resolver {
+    /// Re-resolve the registry dependencies of `manifest` against the
+    /// versions `registry` offers now, starting from `lockfile`.
+    ///
+    /// Only the named `packages` are updated (every registry dependency when
+    /// empty); git and path dependencies keep their locked entries. Yanked
+    /// versions are never selected, though an unchanged package may stay on
+    /// one it already pins.
+    pub fn update(
+        &mut self,
+        manifest: &PackageManifest,
+        lockfile: &Lockfile,
+        registry: &RegistryManager,
+        mode: UpdateMode,
+        packages: &[String],
+    ) -> ResolverResult<LockfileUpdate> {
+        if let Some(unknown) = packages
+            .iter()
+            .find(|name| manifest.find_dependency(name).is_none())
+        {
+            return Err(ResolverError::PackageNotFound(unknown.clone()));
+        }
+
+        let mut dependencies: Vec<(String, Dependency)> = manifest
+            .dependencies_for(&self.context)
+            .into_iter()
+            .filter(|(_, dep)| is_registry_dependency(dep))
+            .collect();
+        dependencies.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let root = manifest.package.name.clone();
+        let mut updated = lockfile.clone();
+        for (name, dep) in &dependencies {
+            self.add_constraint(&root, name, dep)?;
+
+            let index = registry
+                .query_index(name)
+                .map_err(|e| ResolverError::ResolutionFailed(format!("{}: {}", name, e)))?;
+            self.solver
+                .add_package_versions(name, index.iter().map(|e| e.version.clone()).collect());
+            self.solver.mark_yanked(
+                name,
+                index.iter().filter(|e| e.yanked).map(|e| e.version.clone()),
+            );
+
+            let locked = lockfile.get_package(name).map(|p| &p.version);
+            let selected = packages.is_empty() || packages.contains(name);
+            let version = self.select_update(name, locked, selected, mode)?;
+            if locked == Some(&version) {
+                continue;
+            }
+
+            let checksum = registry
+                .get_metadata(name, &version)
+                .ok()
+                .map(|m| m.checksum)
+                .filter(|c| !c.is_empty());
+            let source = LockedSource::Registry {
+                registry: match dep {
+                    Dependency::Detailed(d) => d.registry.clone(),
+                    Dependency::Simple(_) => None,
+                },
+            };
+            updated.add_package(LockedPackage {
+                name: name.clone(),
+                version,
+                source,
+                checksum,
+                dependencies: HashMap::new(),
+            });
+        }
+
+        // Registry entries the manifest dropped, unless another package
+        // still depends on them
+        let stale: Vec<String> = updated
+            .packages
+            .iter()
+            .filter(|p| matches!(p.source, LockedSource::Registry { .. }))
+            .filter(|p| manifest.find_dependency(&p.name).is_none())
+            .filter(|p| {
+                !updated
+                    .packages
+                    .iter()
+                    .any(|other| other.dependencies.contains_key(&p.name))
+            })
+            .map(|p| p.name.clone())
+            .collect();
+        for name in &stale {
+            updated.remove_package(name);
+        }
+
+        let diff = lockfile.diff(&updated);
+        Ok(LockfileUpdate {
+            lockfile: updated,
+            diff,
+        })
+    }
+
+    /// Version to lock for `package`, given its currently locked version
+    fn select_update(
+        &self,
+        package: &str,
+        locked: Option<&Version>,
+        selected: bool,
+        mode: UpdateMode,
+    ) -> ResolverResult<Version> {
+        let requirements: Vec<VersionReq> = self
+            .constraints
+            .get(package)
+            .map(|cs| cs.iter().map(|c| c.requirement.clone()).collect())
+            .unwrap_or_default();
+        let allowed = locked.filter(|v| self.solver.satisfies_all(package, &requirements, v));
+        let candidates: Vec<Version> = self
+            .solver
+            .find_all_satisfying(package, &requirements)
+            .into_iter()
+            .filter(|v| !self.solver.is_yanked(package, v))
+            .collect();
+
+        let version = match (allowed, mode) {
+            (Some(current), _) if !selected => Some(current.clone()),
+            (Some(current), UpdateMode::Conservative) => candidates
+                .iter()
+                .filter(|v| v.major == current.major && v.minor == current.minor)
+                .filter(|v| *v > current)
+                .max()
+                .cloned()
+                .or_else(|| Some(current.clone())),
+            (None, UpdateMode::Conservative) => locked
+                .and_then(|old| candidates.iter().filter(|v| *v > old).min().cloned())
+                .or_else(|| candidates.iter().max().cloned()),
+            (_, UpdateMode::Aggressive) => candidates.iter().max().cloned(),
+        };
+
+        version.ok_or_else(|| ResolverError::NoSatisfyingVersion {
+            package: package.to_string(),
+            constraints: format!("{:?}", requirements),
+        })
+    }
+}
+
+/// Dependencies resolved through a registry rather than git or a path
+fn is_registry_dependency(dep: &Dependency) -> bool {
+    match dep {
+        Dependency::Simple(_) => true,
+        Dependency::Detailed(d) => d.git.is_none() && d.path.is_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::LocalRegistry;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Local registry offering the given versions; `!` marks a yanked one
+    fn registry(dir: &TempDir, packages: &[(&str, &[&str])]) -> RegistryManager {
+        for (name, versions) in packages {
+            for version in *versions {
+                let (version, yanked) = match version.strip_suffix('!') {
+                    Some(v) => (v, true),
+                    None => (*version, false),
+                };
+                let version_dir = dir.path().join(name).join(version);
+                fs::create_dir_all(&version_dir).unwrap();
+                fs::write(
+                    version_dir.join("metadata.toml"),
+                    format!("checksum = \"sum-{}\"\nyanked = {}\n", version, yanked),
+                )
+                .unwrap();
+            }
+        }
+        let mut manager = RegistryManager::new();
+        manager.add_registry(Box::new(LocalRegistry::new(dir.path().to_path_buf())));
+        manager
+    }
+
+    fn manifest(deps: &str) -> PackageManifest {
+        PackageManifest::from_str(&format!(
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\n{}",
+            deps
+        ))
+        .unwrap()
+    }
+
+    fn lockfile(packages: &[(&str, &str)]) -> Lockfile {
+        let mut lockfile = Lockfile::new();
+        for (name, version) in packages {
+            lockfile.add_package(LockedPackage {
+                name: name.to_string(),
+                version: version.parse().unwrap(),
+                source: LockedSource::Registry { registry: None },
+                checksum: None,
+                dependencies: HashMap::new(),
+            });
+        }
+        lockfile
+    }
+
+    fn locked_version(update: &LockfileUpdate, name: &str) -> String {
+        update
+            .lockfile
+            .get_package(name)
+            .unwrap()
+            .version
+            .to_string()
+    }
+
+    const HTTP: (&str, &[&str]) = ("http", &["1.2.0", "1.2.1", "1.2.3", "1.4.0", "2.0.0"]);
+
+    #[test]
+    fn test_aggressive_takes_latest_compatible() {
+        let dir = TempDir::new().unwrap();
+        let registry = registry(&dir, &[HTTP]);
+        let update = Resolver::new()
+            .update(
+                &manifest("http = \"^1.2\"\n"),
+                &lockfile(&[("http", "1.2.0")]),
+                &registry,
+                UpdateMode::Aggressive,
+                &[],
+            )
+            .unwrap();
+
+        assert_eq!(locked_version(&update, "http"), "1.4.0");
+        assert_eq!(
+            update
+                .lockfile
+                .get_package("http")
+                .unwrap()
+                .checksum
+                .as_deref(),
+            Some("sum-1.4.0")
+        );
+        assert_eq!(update.diff.report(), "Updating http v1.2.0 -> v1.4.0\n");
+    }
+
+    #[test]
+    fn test_conservative_stays_on_locked_minor() {
+        let dir = TempDir::new().unwrap();
+        let registry = registry(&dir, &[HTTP]);
+        let update = Resolver::new()
+            .update(
+                &manifest("http = \"^1.2\"\n"),
+                &lockfile(&[("http", "1.2.0")]),
+                &registry,
+                UpdateMode::Conservative,
+                &[],
+            )
+            .unwrap();
+
+        assert_eq!(locked_version(&update, "http"), "1.2.3");
+    }
+
+    #[test]
+    fn test_conservative_makes_minimal_bump_when_manifest_changes() {
+        let dir = TempDir::new().unwrap();
+        let registry = registry(&dir, &[HTTP]);
+        let update = Resolver::new()
+            .update(
+                &manifest("http = \"^1.3\"\n"),
+                &lockfile(&[("http", "1.2.0")]),
+                &registry,
+                UpdateMode::Conservative,
+                &[],
+            )
+            .unwrap();
+
+        assert_eq!(locked_version(&update, "http"), "1.4.0");
+    }
+
+    #[test]
+    fn test_update_only_touches_named_packages() {
+        let dir = TempDir::new().unwrap();
+        let registry = registry(&dir, &[HTTP, ("json", &["0.3.0", "0.3.5"])]);
+        let update = Resolver::new()
+            .update(
+                &manifest("http = \"^1\"\njson = \"^0.3\"\n"),
+                &lockfile(&[("http", "1.2.0"), ("json", "0.3.0")]),
+                &registry,
+                UpdateMode::Aggressive,
+                &["json".to_string()],
+            )
+            .unwrap();
+
+        assert_eq!(locked_version(&update, "http"), "1.2.0");
+        assert_eq!(locked_version(&update, "json"), "0.3.5");
+        assert_eq!(update.diff.changes.len(), 1);
+    }
+
+    #[test]
+    fn test_update_skips_yanked_and_drops_removed_dependencies() {
+        let dir = TempDir::new().unwrap();
+        let registry = registry(&dir, &[("http", &["1.2.0", "1.3.0!"])]);
+        let update = Resolver::new()
+            .update(
+                &manifest("http = \"^1\"\n"),
+                &lockfile(&[("http", "1.2.0"), ("old", "1.0.0")]),
+                &registry,
+                UpdateMode::Aggressive,
+                &[],
+            )
+            .unwrap();
+
+        assert_eq!(locked_version(&update, "http"), "1.2.0");
+        assert!(update.lockfile.get_package("old").is_none());
+        assert_eq!(update.diff.report(), "Removing old v1.0.0\n");
+    }
+
+    #[test]
+    fn test_update_rejects_unknown_package() {
+        let dir = TempDir::new().unwrap();
+        let registry = registry(&dir, &[HTTP]);
+        let err = Resolver::new()
+            .update(
+                &manifest("http = \"^1\"\n"),
+                &Lockfile::new(),
+                &registry,
+                UpdateMode::Aggressive,
+                &["tls".to_string()],
+            )
+            .unwrap_err();
+        assert!(matches!(err, ResolverError::PackageNotFound(name) if name == "tls"));
+    }
+}
@@ -1,5 +1,6 @@
+use crate::manifest::req_matches;
 use semver::{Version, VersionReq};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Version solver for finding compatible package versions
 #[derive(Debug, Clone)]
@@ -7,15 +8,71 @@ pub struct VersionSolver {
     /// Available package versions (mock data for phase-08a)
     /// In phase-08b, this will query the registry
     available_versions: HashMap<String, Vec<Version>>,
+
+    /// Yanked versions per package: never selected by a new resolution
+    yanked_versions: HashMap<String, HashSet<Version>>,
+
+    /// Packages whose pre-releases may be selected (nightly channel)
+    prerelease_packages: HashSet<String>,
+
+    /// Allow pre-releases for every package (`--pre`)
+    allow_all_prereleases: bool,
 }
 
 impl VersionSolver {
     pub fn new() -> Self {
         Self {
             available_versions: HashMap::new(),
+            yanked_versions: HashMap::new(),
+            prerelease_packages: HashSet::new(),
+            allow_all_prereleases: false,
         }
     }
 
+    /// Allow pre-release versions of one package to be selected
+    pub fn allow_prerelease_for(&mut self, package: &str) {
+        self.prerelease_packages.insert(package.to_string());
+    }
+
+    /// Allow pre-release versions of every package to be selected
+    pub fn set_allow_prereleases(&mut self, allow: bool) {
+        self.allow_all_prereleases = allow;
+    }
+
+    /// Whether pre-releases of `package` may be selected without being named
+    /// explicitly by a constraint
+    pub fn allows_prerelease(&self, package: &str) -> bool {
+        self.allow_all_prereleases || self.prerelease_packages.contains(package)
+    }
+
+    /// Check a version against every constraint, honouring pre-release opt-in
+    pub(super) fn satisfies_all(
+        &self,
+        package: &str,
+        constraints: &[VersionReq],
+        version: &Version,
+    ) -> bool {
+        let allow_pre = self.allows_prerelease(package);
+        constraints
+            .iter()
+            .all(|req| req_matches(req, version, allow_pre))
+    }
+
+    /// Mark versions of a package as yanked
+    pub fn mark_yanked(&mut self, package: &str, versions: impl IntoIterator<Item = Version>) {
+        self.yanked_versions
+            .entry(package.to_string())
+            .or_default()
+            .extend(versions);
+    }
+
+    /// Check whether a version has been yanked
+    pub fn is_yanked(&self, package: &str, version: &Version) -> bool {
+        self.yanked_versions
+            .get(package)
+            .is_some_and(|yanked| yanked.contains(version))
+    }
+
     /// Register available versions for a package
     pub fn add_package_versions(&mut self, package: &str, mut versions: Vec<Version>) {
         // Sort versions in ascending order
@@ -32,10 +89,11 @@ impl VersionSolver {
     ) -> Option<Version> {
         let versions = self.available_versions.get(package)?;
 
-        // Filter versions that satisfy all constraints
+        // Filter versions that satisfy all constraints (yanked versions are never picked)
         let mut satisfying: Vec<&Version> = versions
             .iter()
-            .filter(|v| constraints.iter().all(|req| req.matches(v)))
+            .filter(|v| !self.is_yanked(package, v))
+            .filter(|v| self.satisfies_all(package, constraints, v))
             .collect();
 
         // Return maximum version
@@ -61,7 +119,7 @@ impl VersionSolver {
 
         versions
             .iter()
-            .filter(|v| constraints.iter().all(|req| req.matches(v)))
+            .filter(|v| self.satisfies_all(package, constraints, v))
             .cloned()
             .collect()
     }
@@ -87,10 +145,11 @@ impl VersionSolver {
     ) -> Option<Version> {
         let versions = self.available_versions.get(package)?;
 
-        // Filter versions that satisfy all constraints
+        // Filter versions that satisfy all constraints (yanked versions are never picked)
         let mut satisfying: Vec<&Version> = versions
             .iter()
-            .filter(|v| constraints.iter().all(|req| req.matches(v)))
+            .filter(|v| !self.is_yanked(package, v))
+            .filter(|v| self.satisfies_all(package, constraints, v))
             .collect();
 
         // Return minimum version
@@ -325,6 +384,61 @@ mod tests {
         assert_eq!(version, Some(Version::new(1, 0, 0)));
     }
 
+    #[test]
+    fn test_yanked_versions_are_skipped() {
+        let mut solver = VersionSolver::new();
+        solver.add_package_versions(
+            "test",
+            vec![
+                Version::new(1, 0, 0),
+                Version::new(1, 1, 0),
+                Version::new(1, 2, 0),
+            ],
+        );
+        solver.mark_yanked("test", [Version::new(1, 2, 0), Version::new(1, 0, 0)]);
+
+        let req = create_version_req("^1.0.0");
+        assert_eq!(
+            solver.max_satisfying_version("test", std::slice::from_ref(&req)),
+            Some(Version::new(1, 1, 0))
+        );
+        assert_eq!(
+            solver.min_satisfying_version("test", &[req]),
+            Some(Version::new(1, 1, 0))
+        );
+        assert!(solver.is_yanked("test", &Version::new(1, 2, 0)));
+    }
+
+    #[test]
+    fn test_prereleases_require_opt_in() {
+        let mut solver = VersionSolver::new();
+        solver.add_package_versions(
+            "test",
+            vec![
+                Version::new(1, 0, 0),
+                Version::parse("1.1.0-beta.1").unwrap(),
+                Version::parse("2.0.0-rc.1").unwrap(),
+            ],
+        );
+        let caret = create_version_req("^1.0.0");
+
+        assert_eq!(
+            solver.max_satisfying_version("test", std::slice::from_ref(&caret)),
+            Some(Version::new(1, 0, 0))
+        );
+        // Explicitly requested pre-release
+        assert_eq!(
+            solver.max_satisfying_version("test", &[create_version_req("=1.1.0-beta.1")]),
+            Some(Version::parse("1.1.0-beta.1").unwrap())
+        );
+
+        solver.allow_prerelease_for("test");
+        assert_eq!(
+            solver.max_satisfying_version("test", &[caret]),
+            Some(Version::parse("1.1.0-beta.1").unwrap())
+        );
+    }
+
     #[test]
     fn test_wildcard_version() {
         let mut solver = VersionSolver::new();
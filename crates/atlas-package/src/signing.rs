@@ -0,0 +1,337 @@
+//! Package signing and signature verification
+//!
+//! Publishers sign packages with an ed25519 key kept in the local credentials
+//! store (`~/atlas/credentials.toml`). The signature covers the package name,
+//! version and archive checksum, so it cannot be replayed onto another release.
+//! Installers verify it against the public keys the registry publishes for the
+//! package, and a `[trust]` policy can refuse unsigned packages outright.
+
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Signing and credentials store errors
+#[derive(Debug, thiserror::Error)]
+pub enum SigningError {
+    #[error("Invalid signing key: {0}")]
+    InvalidKey(String),
+
+    #[error("Key generation failed")]
+    KeyGeneration,
+
+    #[error("Failed to parse credentials: {0}")]
+    ParseError(String),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+/// Detached signature attached to a published version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PackageSignature {
+    /// Identifies which of the publisher's keys made the signature
+    pub key_id: String,
+    /// Hex-encoded ed25519 signature
+    pub signature: String,
+}
+
+/// Trust policy from the manifest's `[trust]` table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TrustPolicy {
+    /// Refuse any dependency whose signature cannot be verified
+    #[serde(default)]
+    pub require_signatures: bool,
+}
+
+impl TrustPolicy {
+    pub fn require_signatures() -> Self {
+        Self {
+            require_signatures: true,
+        }
+    }
+}
+
+/// Bytes covered by a package signature
+pub fn signed_payload(package: &str, version: &semver::Version, checksum: &str) -> Vec<u8> {
+    format!(
+        "atlas-package-signature-v1\n{}\n{}\n{}\n",
+        package, version, checksum
+    )
+    .into_bytes()
+}
+
+/// Short identifier for a public key: the first 16 hex digits of its SHA-256
+pub fn key_id(public_key: &[u8]) -> String {
+    let digest = Sha256::digest(public_key);
+    hex::encode(&digest[..8])
+}
+
+/// Check `signature_hex` over `data` against a hex-encoded ed25519 public key
+pub fn verify(public_key_hex: &str, data: &[u8], signature_hex: &str) -> bool {
+    let (Ok(public_key), Ok(signature)) = (hex::decode(public_key_hex), hex::decode(signature_hex))
+    else {
+        return false;
+    };
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(data, &signature)
+        .is_ok()
+}
+
+/// Ed25519 key pair used to sign packages at publish time
+pub struct SigningKey {
+    pair: Ed25519KeyPair,
+    pkcs8: Vec<u8>,
+}
+
+impl SigningKey {
+    /// Generate a fresh key pair
+    pub fn generate() -> Result<Self, SigningError> {
+        let document = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+            .map_err(|_| SigningError::KeyGeneration)?;
+        Self::from_pkcs8(document.as_ref())
+    }
+
+    /// Load a key pair from its PKCS#8 encoding
+    pub fn from_pkcs8(bytes: &[u8]) -> Result<Self, SigningError> {
+        let pair = Ed25519KeyPair::from_pkcs8(bytes)
+            .map_err(|e| SigningError::InvalidKey(e.to_string()))?;
+        Ok(Self {
+            pair,
+            pkcs8: bytes.to_vec(),
+        })
+    }
+
+    /// Hex-encoded public key, as registered with the registry
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.pair.public_key().as_ref())
+    }
+
+    pub fn key_id(&self) -> String {
+        key_id(self.pair.public_key().as_ref())
+    }
+
+    /// Sign a release identified by name, version and archive checksum
+    pub fn sign_package(
+        &self,
+        package: &str,
+        version: &semver::Version,
+        checksum: &str,
+    ) -> PackageSignature {
+        let signature = self.pair.sign(&signed_payload(package, version, checksum));
+        PackageSignature {
+            key_id: self.key_id(),
+            signature: hex::encode(signature.as_ref()),
+        }
+    }
+}
+
+impl std::fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SigningKey")
+            .field("key_id", &self.key_id())
+            .finish_non_exhaustive()
+    }
+}
+
+/// On-disk store for publisher credentials
+///
+/// The `[signing]` table is managed here and the registry token is read from
+/// `[registry] token`; other tables in the file are preserved when the key is
+/// written.
+pub struct CredentialsStore {
+    path: PathBuf,
+}
+
+impl CredentialsStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// `$ATLAS_HOME/credentials.toml`, falling back to `~/atlas/credentials.toml`
+    pub fn default_location() -> Self {
+        let root = std::env::var("ATLAS_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| {
+                dirs::home_dir()
+                    .unwrap_or_else(|| PathBuf::from("."))
+                    .join("atlas")
+            });
+        Self::new(root.join("credentials.toml"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The stored signing key, if one has been created
+    pub fn signing_key(&self) -> Result<Option<SigningKey>, SigningError> {
+        let table = self.read()?;
+        let Some(encoded) = table
+            .get("signing")
+            .and_then(|s| s.get("key"))
+            .and_then(|k| k.as_str())
+        else {
+            return Ok(None);
+        };
+        let bytes = hex::decode(encoded).map_err(|e| SigningError::InvalidKey(e.to_string()))?;
+        SigningKey::from_pkcs8(&bytes).map(Some)
+    }
+
+    /// The API token for registry uploads, from `[registry] token`
+    pub fn registry_token(&self) -> Result<Option<String>, SigningError> {
+        Ok(self
+            .read()?
+            .get("registry")
+            .and_then(|r| r.get("token"))
+            .and_then(|t| t.as_str())
+            .map(str::to_string))
+    }
+
+    /// Store `key` as the signing key, replacing any previous one
+    pub fn save_signing_key(&self, key: &SigningKey) -> Result<(), SigningError> {
+        let mut table = self.read()?;
+        let mut signing = toml::map::Map::new();
+        signing.insert("key".to_string(), hex::encode(&key.pkcs8).into());
+        signing.insert("public-key".to_string(), key.public_key_hex().into());
+        table.insert("signing".to_string(), toml::Value::Table(signing));
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content =
+            toml::to_string_pretty(&table).map_err(|e| SigningError::ParseError(e.to_string()))?;
+        write_private(&self.path, &content)?;
+        Ok(())
+    }
+
+    /// The stored key, generating and saving one on first use.
+    /// Returns whether the key was newly created.
+    pub fn signing_key_or_generate(&self) -> Result<(SigningKey, bool), SigningError> {
+        if let Some(key) = self.signing_key()? {
+            return Ok((key, false));
+        }
+        let key = SigningKey::generate()?;
+        self.save_signing_key(&key)?;
+        Ok((key, true))
+    }
+
+    fn read(&self) -> Result<toml::map::Map<String, toml::Value>, SigningError> {
+        if !self.path.exists() {
+            return Ok(toml::map::Map::new());
+        }
+        let content = fs::read_to_string(&self.path)?;
+        toml::from_str(&content).map_err(|e| SigningError::ParseError(e.to_string()))
+    }
+}
+
+/// The credentials file holds a private key: write it to an owner-only
+/// temp file and rename it into place, so it is never readable by others
+fn write_private(path: &Path, content: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp = path.with_file_name(temp_name);
+    match fs::remove_file(&temp) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let result = options
+        .open(&temp)
+        .and_then(|mut file| {
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use semver::Version;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_sign_and_verify_roundtrip() {
+        let key = SigningKey::generate().unwrap();
+        let version = Version::new(1, 2, 0);
+        let signature = key.sign_package("http", &version, "abc123");
+        assert_eq!(signature.key_id, key.key_id());
+        assert_eq!(signature.key_id.len(), 16);
+
+        let public_key = key.public_key_hex();
+        let payload = signed_payload("http", &version, "abc123");
+        assert!(verify(&public_key, &payload, &signature.signature));
+
+        // The signature is bound to name, version and checksum
+        for tampered in [
+            signed_payload("https", &version, "abc123"),
+            signed_payload("http", &Version::new(1, 2, 1), "abc123"),
+            signed_payload("http", &version, "abc124"),
+        ] {
+            assert!(!verify(&public_key, &tampered, &signature.signature));
+        }
+        assert!(!verify("not hex", &payload, &signature.signature));
+    }
+
+    #[test]
+    fn test_credentials_store_persists_key() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("credentials.toml");
+        std::fs::write(&path, "[registry]\ntoken = \"secret\"\n").unwrap();
+        let store = CredentialsStore::new(path.clone());
+
+        assert!(store.signing_key().unwrap().is_none());
+        let (key, created) = store.signing_key_or_generate().unwrap();
+        assert!(created);
+
+        let (reloaded, created) = store.signing_key_or_generate().unwrap();
+        assert!(!created);
+        assert_eq!(reloaded.public_key_hex(), key.public_key_hex());
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("token = \"secret\""), "{content}");
+        assert_eq!(store.registry_token().unwrap().as_deref(), Some("secret"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_credentials_file_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("credentials.toml");
+        std::fs::write(&path, "").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let store = CredentialsStore::new(path.clone());
+        store.signing_key_or_generate().unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(!temp.path().join("credentials.toml.tmp").exists());
+    }
+
+    #[test]
+    fn test_trust_policy_parses_kebab_case() {
+        let policy: TrustPolicy = toml::from_str("require-signatures = true").unwrap();
+        assert_eq!(policy, TrustPolicy::require_signatures());
+        assert!(!TrustPolicy::default().require_signatures);
+    }
+}
@@ -0,0 +1,354 @@
+//! Build contexts and target-specific dependencies
+//!
+//! A `BuildContext` says which dependency sections apply (normal, dev, build)
+//! and which platform is being built for. `[target.'cfg(...)'.dependencies]`
+//! sections are selected by evaluating their `cfg` key against the platform.
+
+/// Platform a package is being built for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetPlatform {
+    /// Operating system (`linux`, `macos`, `windows`, ...)
+    pub os: String,
+    /// CPU architecture (`x86_64`, `aarch64`, ...)
+    pub arch: String,
+    /// OS family (`unix` or `windows`)
+    pub family: String,
+}
+
+impl TargetPlatform {
+    pub fn new(os: impl Into<String>, arch: impl Into<String>, family: impl Into<String>) -> Self {
+        Self {
+            os: os.into(),
+            arch: arch.into(),
+            family: family.into(),
+        }
+    }
+
+    /// The platform this binary is running on
+    pub fn host() -> Self {
+        Self::new(
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            std::env::consts::FAMILY,
+        )
+    }
+}
+
+/// Parsed `cfg(...)` predicate from a `[target.'cfg(...)']` key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CfgExpr {
+    /// `key = "value"` where key is `os`, `arch` or `family`
+    Equal {
+        key: String,
+        value: String,
+    },
+    /// Bare `unix` / `windows`, shorthand for `family = "..."`
+    Family(String),
+    Not(Box<CfgExpr>),
+    All(Vec<CfgExpr>),
+    Any(Vec<CfgExpr>),
+}
+
+impl CfgExpr {
+    /// Parse a target key such as `cfg(any(os = "linux", os = "macos"))`
+    pub fn parse(key: &str) -> Result<Self, String> {
+        let tokens = tokenize(key)?;
+        let mut parser = CfgParser { tokens, pos: 0 };
+        if parser.next() != Some(Token::Ident("cfg".to_string())) {
+            return Err(format!("expected 'cfg(...)', found '{}'", key));
+        }
+        parser.expect(Token::LParen)?;
+        let expr = parser.expr()?;
+        parser.expect(Token::RParen)?;
+        if parser.pos != parser.tokens.len() {
+            return Err(format!("unexpected trailing input in '{}'", key));
+        }
+        Ok(expr)
+    }
+
+    /// Evaluate the predicate against a platform
+    pub fn matches(&self, platform: &TargetPlatform) -> bool {
+        match self {
+            CfgExpr::Equal { key, value } => match key.as_str() {
+                "os" => platform.os == *value,
+                "arch" => platform.arch == *value,
+                "family" => platform.family == *value,
+                _ => false,
+            },
+            CfgExpr::Family(family) => platform.family == *family,
+            CfgExpr::Not(inner) => !inner.matches(platform),
+            CfgExpr::All(exprs) => exprs.iter().all(|e| e.matches(platform)),
+            CfgExpr::Any(exprs) => exprs.iter().any(|e| e.matches(platform)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    LParen,
+    RParen,
+    Comma,
+    Eq,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' | '\t' => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            ',' => {
+                chars.next();
+                tokens.push(Token::Comma);
+            }
+            '=' => {
+                chars.next();
+                tokens.push(Token::Eq);
+            }
+            '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(ch) => value.push(ch),
+                        None => return Err("unterminated string in cfg".to_string()),
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_ascii_alphanumeric() || ch == '_' {
+                        ident.push(ch);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            other => return Err(format!("unexpected character '{}' in cfg", other)),
+        }
+    }
+    Ok(tokens)
+}
+
+struct CfgParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl CfgParser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!("expected {:?}, found {:?}", expected, token)),
+            None => Err(format!("expected {:?}, found end of input", expected)),
+        }
+    }
+
+    fn expr(&mut self) -> Result<CfgExpr, String> {
+        let name = match self.next() {
+            Some(Token::Ident(name)) => name,
+            Some(token) => return Err(format!("expected predicate, found {:?}", token)),
+            None => return Err("expected predicate, found end of input".to_string()),
+        };
+
+        match self.peek() {
+            Some(Token::Eq) => {
+                self.pos += 1;
+                if !matches!(name.as_str(), "os" | "arch" | "family") {
+                    return Err(format!("unknown cfg key '{}'", name));
+                }
+                match self.next() {
+                    Some(Token::Str(value)) => Ok(CfgExpr::Equal { key: name, value }),
+                    _ => Err(format!("expected string value for '{}'", name)),
+                }
+            }
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let args = self.list()?;
+                match name.as_str() {
+                    "not" => {
+                        let mut args = args;
+                        if args.len() != 1 {
+                            return Err("not() takes exactly one predicate".to_string());
+                        }
+                        Ok(CfgExpr::Not(Box::new(args.remove(0))))
+                    }
+                    "all" => Ok(CfgExpr::All(args)),
+                    "any" => Ok(CfgExpr::Any(args)),
+                    _ => Err(format!("unknown cfg operator '{}'", name)),
+                }
+            }
+            _ => match name.as_str() {
+                "unix" | "windows" => Ok(CfgExpr::Family(name)),
+                _ => Err(format!("unknown cfg predicate '{}'", name)),
+            },
+        }
+    }
+
+    /// Comma-separated predicates up to and including the closing paren
+    fn list(&mut self) -> Result<Vec<CfgExpr>, String> {
+        let mut exprs = Vec::new();
+        if self.peek() == Some(&Token::RParen) {
+            self.pos += 1;
+            return Ok(exprs);
+        }
+        loop {
+            exprs.push(self.expr()?);
+            match self.next() {
+                Some(Token::Comma) if self.peek() == Some(&Token::RParen) => {
+                    self.pos += 1;
+                    return Ok(exprs);
+                }
+                Some(Token::Comma) => continue,
+                Some(Token::RParen) => return Ok(exprs),
+                _ => return Err("expected ',' or ')' in cfg".to_string()),
+            }
+        }
+    }
+}
+
+/// Manifest section a dependency was declared in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DependencyKind {
+    /// `[dependencies]`: shipped to every consumer
+    Normal,
+    /// `[dev-dependencies]`: tests and benches of this package only
+    Dev,
+    /// `[build-dependencies]`: build scripts only
+    Build,
+}
+
+/// Which dependency sections apply to a build, and for which platform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildContext {
+    pub platform: TargetPlatform,
+    pub kinds: Vec<DependencyKind>,
+}
+
+impl BuildContext {
+    /// Building the package for consumers: no dev-dependencies
+    pub fn production(platform: TargetPlatform) -> Self {
+        Self {
+            platform,
+            kinds: vec![DependencyKind::Normal, DependencyKind::Build],
+        }
+    }
+
+    /// Working on the package itself, including its tests and benches
+    pub fn development(platform: TargetPlatform) -> Self {
+        Self {
+            platform,
+            kinds: vec![
+                DependencyKind::Normal,
+                DependencyKind::Dev,
+                DependencyKind::Build,
+            ],
+        }
+    }
+
+    /// Running build scripts: build-dependencies only
+    pub fn build_script(platform: TargetPlatform) -> Self {
+        Self {
+            platform,
+            kinds: vec![DependencyKind::Build],
+        }
+    }
+
+    /// Whether dependencies of `kind` are part of this build
+    pub fn includes(&self, kind: DependencyKind) -> bool {
+        self.kinds.contains(&kind)
+    }
+
+    /// Whether a `[target.'...']` key applies to this build's platform.
+    /// Keys that fail to parse never match; the validator reports them.
+    pub fn matches_target(&self, key: &str) -> bool {
+        CfgExpr::parse(key)
+            .map(|cfg| cfg.matches(&self.platform))
+            .unwrap_or(false)
+    }
+}
+
+impl Default for BuildContext {
+    fn default() -> Self {
+        Self::development(TargetPlatform::host())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn linux() -> TargetPlatform {
+        TargetPlatform::new("linux", "x86_64", "unix")
+    }
+
+    #[test]
+    fn test_cfg_simple_predicates() {
+        let cfg = CfgExpr::parse(r#"cfg(os = "linux")"#).unwrap();
+        assert!(cfg.matches(&linux()));
+        assert!(!cfg.matches(&TargetPlatform::new("windows", "x86_64", "windows")));
+
+        assert!(CfgExpr::parse("cfg(unix)").unwrap().matches(&linux()));
+        assert!(!CfgExpr::parse("cfg(windows)").unwrap().matches(&linux()));
+    }
+
+    #[test]
+    fn test_cfg_combinators() {
+        let cfg = CfgExpr::parse(
+            r#"cfg(all(unix, not(os = "macos"), any(arch = "x86_64", arch = "aarch64")))"#,
+        )
+        .unwrap();
+        assert!(cfg.matches(&linux()));
+        assert!(!cfg.matches(&TargetPlatform::new("macos", "aarch64", "unix")));
+    }
+
+    #[test]
+    fn test_cfg_parse_errors() {
+        assert!(CfgExpr::parse(r#"os = "linux""#).is_err());
+        assert!(CfgExpr::parse(r#"cfg(target = "linux")"#).is_err());
+        assert!(CfgExpr::parse(r#"cfg(os = "linux""#).is_err());
+        assert!(CfgExpr::parse("cfg(not(unix, windows))").is_err());
+    }
+
+    #[test]
+    fn test_build_context_kinds() {
+        let production = BuildContext::production(linux());
+        assert!(production.includes(DependencyKind::Normal));
+        assert!(!production.includes(DependencyKind::Dev));
+
+        let development = BuildContext::development(linux());
+        assert!(development.includes(DependencyKind::Dev));
+
+        let script = BuildContext::build_script(linux());
+        assert!(!script.includes(DependencyKind::Normal));
+        assert!(script.matches_target("cfg(unix)"));
+        assert!(!script.matches_target("not a cfg"));
+    }
+}
@@ -22,6 +22,8 @@ pub enum ValidationError {
     WorkspaceError(String),
     /// Conflicting dependency sources
     ConflictingSource { name: String, reason: String },
+    /// Unparseable `[target.'cfg(...)']` key
+    InvalidTarget { key: String, reason: String },
 }
 
 impl std::fmt::Display for ValidationError {
@@ -51,6 +53,9 @@ impl std::fmt::Display for ValidationError {
             ValidationError::ConflictingSource { name, reason } => {
                 write!(f, "Conflicting source for '{}': {}", name, reason)
             }
+            ValidationError::InvalidTarget { key, reason } => {
+                write!(f, "Invalid target '{}': {}", key, reason)
+            }
         }
     }
 }
@@ -73,6 +78,18 @@ impl Validator {
         // Validate dependencies
         errors.extend(Self::validate_dependencies(&manifest.dependencies));
         errors.extend(Self::validate_dependencies(&manifest.dev_dependencies));
+        errors.extend(Self::validate_dependencies(&manifest.build_dependencies));
+        for (key, target) in &manifest.target {
+            if let Err(reason) = crate::target::CfgExpr::parse(key) {
+                errors.push(ValidationError::InvalidTarget {
+                    key: key.clone(),
+                    reason,
+                });
+            }
+            errors.extend(Self::validate_dependencies(&target.dependencies));
+            errors.extend(Self::validate_dependencies(&target.dev_dependencies));
+            errors.extend(Self::validate_dependencies(&target.build_dependencies));
+        }
 
         // Validate features
         errors.extend(Self::validate_features(
@@ -523,6 +540,7 @@ mod tests {
                 features: None,
                 default_features: None,
                 rename: None,
+                channel: None,
             }),
         );
 
@@ -551,6 +569,7 @@ mod tests {
                 features: None,
                 default_features: None,
                 rename: None,
+                channel: None,
             }),
         );
 
@@ -575,4 +594,24 @@ mod tests {
         let errors = Validator::validate_features(&features, &dependencies);
         assert!(!errors.is_empty());
     }
+
+    #[test]
+    fn test_invalid_target_key() {
+        let manifest = PackageManifest::from_str(
+            r#"
+            [package]
+            name = "app"
+            version = "1.0.0"
+
+            [target.'cfg(platform = "linux")'.dependencies]
+            sys = "1.0"
+            "#,
+        )
+        .unwrap();
+
+        let errors = Validator::validate(&manifest).unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, ValidationError::InvalidTarget { .. })));
+    }
 }
@@ -169,7 +169,7 @@ fn test_install_git_dep_populates_cache() {
     // Cache should contain something under the package name.
     let fetcher = GitFetcher::new(cache.path().to_path_buf());
     assert!(
-        fetcher.is_cached("logger", "v1.0.0"),
+        fetcher.is_cached(&file_url(pkg.path()), "logger", "v1.0.0"),
         "cache should be populated after install"
     );
 }
@@ -673,7 +673,7 @@ fn test_parse_ls_remote_tags_no_tags_returns_empty() {
 fn test_is_cached_false_before_fetch() {
     let cache = cache_dir();
     let fetcher = GitFetcher::new(cache.path().to_path_buf());
-    assert!(!fetcher.is_cached("never-fetched", "v1.0.0"));
+    assert!(!fetcher.is_cached("file:///never-fetched", "never-fetched", "v1.0.0"));
 }
 
 #[test]
@@ -685,7 +685,7 @@ fn test_is_cached_true_after_fetch() {
     fetcher
         .fetch("cache-check", &file_url(pkg.path()), "v1.0.0")
         .expect("fetch");
-    assert!(fetcher.is_cached("cache-check", "v1.0.0"));
+    assert!(fetcher.is_cached(&file_url(pkg.path()), "cache-check", "v1.0.0"));
 }
 
 #[test]
@@ -699,5 +699,5 @@ fn test_is_cached_false_for_different_tag() {
         .expect("fetch");
 
     // v1.1.0 was never fetched.
-    assert!(!fetcher.is_cached("tag-check", "v1.1.0"));
+    assert!(!fetcher.is_cached(&file_url(pkg.path()), "tag-check", "v1.1.0"));
 }
@@ -35,11 +35,16 @@ fn create_test_manifest(name: &str, version: &str, deps: Vec<(&str, &str)>) -> P
         entry: None,
         dependencies,
         dev_dependencies: HashMap::new(),
+        build_dependencies: HashMap::new(),
+        target: HashMap::new(),
         build: None,
         lib: None,
         bin: vec![],
         workspace: None,
         features: HashMap::new(),
+        trust: None,
+        lints: None,
+        assets: None,
     }
 }
 
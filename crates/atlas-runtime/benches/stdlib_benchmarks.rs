@@ -4,6 +4,7 @@
 //! Benchmarks cover:
 //! - String operations (split, join, replace)
 //! - Array operations (map, filter, reduce, sort, indexOf)
+//! - Callback sorts (`sort` with a comparator, `sortBy`) at increasing sizes
 //! - Math operations (arithmetic, functions)
 //! - JSON operations (parse, stringify, nested access)
 //! - File I/O operations (read, write)
@@ -11,7 +12,7 @@
 //! Run with: cargo bench --bench stdlib_benchmarks
 
 use atlas_runtime::{Atlas, SecurityContext};
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use std::fs;
 use tempfile::TempDir;
 
@@ -173,6 +174,84 @@ fn bench_array_sort(c: &mut Criterion) {
     });
 }
 
+fn bench_array_sort_callbacks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("array_sort_callbacks");
+    group.sample_size(10);
+
+    for n in [1_000, 10_000, 100_000] {
+        // A scrambled permutation of 0..n
+        let items = format!(
+            "let items = map(Array.range(0, {n}), fn(i: number): number {{ return (i * 7919) % {n}; }});"
+        );
+        let comparator =
+            format!("{items}\nsort(items, fn(a: number, b: number): number {{ return a - b; }});");
+        let key = format!("{items}\nsortBy(items, fn(x: number): number {{ return x % 100; }});");
+
+        group.bench_with_input(BenchmarkId::new("comparator", n), &comparator, |b, code| {
+            let runtime = Atlas::new();
+            b.iter(|| {
+                let _ = runtime.eval(black_box(code));
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("sort_by_key", n), &key, |b, code| {
+            let runtime = Atlas::new();
+            b.iter(|| {
+                let _ = runtime.eval(black_box(code));
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_array_intrinsics_1m(c: &mut Criterion) {
+    let mut group = c.benchmark_group("array_intrinsics_1m");
+    group.sample_size(10);
+
+    // Callback intrinsics walk the shared array in place rather than copying
+    // it first; `build` is the cost of creating the array alone
+    let items = "let items = Array.range(0, 1000000);";
+    let cases = [
+        ("build", String::new()),
+        (
+            "map",
+            "map(items, fn(x: number): number { return x + 1; });".to_string(),
+        ),
+        (
+            "filter",
+            "filter(items, fn(x: number): bool { return x % 2 == 0; });".to_string(),
+        ),
+        (
+            "reduce",
+            "reduce(items, fn(acc: number, x: number): number { return acc + x; }, 0);".to_string(),
+        ),
+        (
+            "forEach",
+            "forEach(items, fn(x: number): void { });".to_string(),
+        ),
+        (
+            "every",
+            "every(items, fn(x: number): bool { return x >= 0; });".to_string(),
+        ),
+        (
+            "findIndex",
+            "findIndex(items, fn(x: number): bool { return x < 0; });".to_string(),
+        ),
+    ];
+
+    for (name, call) in cases {
+        let code = format!("{items}\n{call}");
+        group.bench_with_input(BenchmarkId::from_parameter(name), &code, |b, code| {
+            let runtime = Atlas::new();
+            b.iter(|| {
+                let _ = runtime.eval(black_box(code));
+            });
+        });
+    }
+
+    group.finish();
+}
+
 fn bench_array_indexof(c: &mut Criterion) {
     c.bench_function("array_indexOf_search", |b| {
         let runtime = Atlas::new();
@@ -352,6 +431,8 @@ criterion_group!(
     bench_array_filter,
     bench_array_reduce,
     bench_array_sort,
+    bench_array_sort_callbacks,
+    bench_array_intrinsics_1m,
     bench_array_indexof
 );
 
@@ -0,0 +1,328 @@
+//! VM Dispatch Benchmarks
+//!
+//! Instruction-dispatch-bound workloads: each program is compiled once up
+//! front and only `VM::run` is timed, so the numbers track the cost of the
+//! execute loop rather than the frontend.
+//!
+//! - Tight arithmetic loops (GetLocal / Constant / Add / SetLocal / Loop)
+//! - Branch-heavy loops (comparisons, JumpIfFalse, And / Or)
+//! - Call-heavy loops (Call / Return)
+//! - Global access (GetGlobal / SetGlobal through functions)
+//! - Array indexing (GetIndex / SetIndex)
+//! - Builtin calls (stdlib fast path, per-run security setup)
+//! - String concatenation (Add on strings)
+//!
+//! Run with: cargo bench --bench vm_dispatch_benches
+//!
+//! To compare dispatch strategies, save a baseline on one revision and
+//! compare on the other:
+//!
+//! ```text
+//! cargo bench --bench vm_dispatch_benches -- --save-baseline before
+//! cargo bench --bench vm_dispatch_benches -- --baseline before
+//! ```
+
+use atlas_runtime::bytecode::Bytecode;
+use atlas_runtime::compiler::Compiler;
+use atlas_runtime::lexer::Lexer;
+use atlas_runtime::parser::Parser;
+use atlas_runtime::security::SecurityContext;
+use atlas_runtime::vm::VM;
+use criterion::{
+    black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput,
+};
+
+fn compile(source: &str) -> Bytecode {
+    let mut lexer = Lexer::new(source.to_string());
+    let (tokens, _) = lexer.tokenize();
+    let mut parser = Parser::new(tokens);
+    let (program, _) = parser.parse();
+    let mut compiler = Compiler::new();
+    compiler.compile(&program).expect("Compilation failed")
+}
+
+/// Time `VM::run` alone over a fresh VM for each iteration
+fn bench_program(c: &mut Criterion, group: &str, name: &str, source: &str) {
+    let bytecode = compile(source);
+    let security = SecurityContext::allow_all();
+    let mut group = c.benchmark_group(group);
+    group.bench_function(BenchmarkId::from_parameter(name), |b| {
+        b.iter_batched(
+            || VM::new(bytecode.clone()),
+            |mut vm| black_box(vm.run(&security)),
+            BatchSize::SmallInput,
+        );
+    });
+    group.finish();
+}
+
+// ============================================================================
+// Straight-line dispatch
+// ============================================================================
+
+fn bench_arithmetic_loop(c: &mut Criterion) {
+    bench_program(
+        c,
+        "dispatch/arithmetic",
+        "sum_10000",
+        "let mut sum = 0; let mut i = 0; while i < 10000 { sum = sum + i * 2 - 1; i = i + 1; } sum;",
+    );
+}
+
+fn bench_branch_loop(c: &mut Criterion) {
+    bench_program(
+        c,
+        "dispatch/branches",
+        "classify_10000",
+        r#"
+        let mut evens = 0;
+        let mut big = 0;
+        let mut i = 0;
+        while i < 10000 {
+            if i % 2 == 0 && i > 10 {
+                evens = evens + 1;
+            } else if i >= 5000 || i == 3 {
+                big = big + 1;
+            }
+            i = i + 1;
+        }
+        evens + big;
+        "#,
+    );
+}
+
+// ============================================================================
+// Calls and globals
+// ============================================================================
+
+fn bench_call_loop(c: &mut Criterion) {
+    bench_program(
+        c,
+        "dispatch/calls",
+        "add_5000",
+        r#"
+        fn add(borrow a: number, borrow b: number): number {
+            return a + b;
+        }
+        let mut sum = 0;
+        let mut i = 0;
+        while i < 5000 {
+            sum = add(sum, i);
+            i = i + 1;
+        }
+        sum;
+        "#,
+    );
+}
+
+fn bench_recursive_calls(c: &mut Criterion) {
+    bench_program(
+        c,
+        "dispatch/calls",
+        "fib_18",
+        r#"
+        fn fib(borrow n: number): number {
+            if n < 2 {
+                return n;
+            }
+            return fib(n - 1) + fib(n - 2);
+        }
+        fib(18);
+        "#,
+    );
+}
+
+fn bench_global_access(c: &mut Criterion) {
+    bench_program(
+        c,
+        "dispatch/globals",
+        "counter_5000",
+        r#"
+        let mut counter = 0;
+        fn bump(): void {
+            counter = counter + 1;
+        }
+        let mut i = 0;
+        while i < 5000 {
+            bump();
+            i = i + 1;
+        }
+        counter;
+        "#,
+    );
+}
+
+// ============================================================================
+// Arrays
+// ============================================================================
+
+fn bench_array_indexing(c: &mut Criterion) {
+    bench_program(
+        c,
+        "dispatch/arrays",
+        "index_5000",
+        r#"
+        let mut arr = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let mut i = 0;
+        while i < 5000 {
+            arr[i % 10] = arr[i % 10] + i;
+            i = i + 1;
+        }
+        arr[3];
+        "#,
+    );
+}
+
+// ============================================================================
+// Builtin calls
+// ============================================================================
+
+/// `len` needs no I/O or allocation, so these time the call path itself
+const BUILTIN_LOOP: &str =
+    "let xs = [1, 2, 3]; let mut sum = 0; let mut i = 0; while i < 10000 { sum = sum + len(xs); i = i + 1; } sum;";
+
+fn bench_builtin_calls(c: &mut Criterion) {
+    bench_program(c, "dispatch/builtins", "len_10000", BUILTIN_LOOP);
+
+    // A builtin call looks up its source span, so a larger program must not
+    // make each call slower
+    let filler: String = (0..2000)
+        .map(|i| format!("let v{i} = {i} + 1;\n"))
+        .collect();
+    bench_program(
+        c,
+        "dispatch/builtins",
+        "len_10000_large_program",
+        &format!("{filler}{BUILTIN_LOOP}"),
+    );
+}
+
+/// Fixed cost of `VM::run` for a trivial program under a security context
+/// with many grants
+fn bench_run_setup(c: &mut Criterion) {
+    let bytecode = compile("len([1]);");
+    let mut security = SecurityContext::new();
+    for i in 0..200 {
+        security.grant_filesystem_read(std::path::Path::new(&format!("/data/{i}")), true);
+        security.grant_network(format!("host{i}.example.com"));
+    }
+    let security = std::sync::Arc::new(security);
+
+    let mut group = c.benchmark_group("dispatch/builtins");
+    group.bench_function(BenchmarkId::from_parameter("run_setup"), |b| {
+        b.iter_batched(
+            || {
+                let mut vm = VM::new(bytecode.clone());
+                vm.set_security(security.clone());
+                vm
+            },
+            |mut vm| black_box(vm.run(&security)),
+            BatchSize::SmallInput,
+        );
+    });
+    group.finish();
+}
+
+// ============================================================================
+// Strings
+// ============================================================================
+
+/// Concatenation throughput, reported as bytes of string built per second
+fn bench_string_concat(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dispatch/strings");
+    let security = SecurityContext::allow_all();
+    let cases: [(&str, u64, String); 3] = [
+        // Many small results: allocation cost dominates
+        (
+            "short_10000",
+            10_000 * 8,
+            r#"
+            let a = "abcd";
+            let b = "efgh";
+            let mut n = 0;
+            let mut i = 0;
+            while i < 10000 {
+                let s = a + b;
+                n = n + len(s);
+                i = i + 1;
+            }
+            n;
+            "#
+            .to_string(),
+        ),
+        // Growing accumulator: copy cost dominates
+        (
+            "accumulate_2000",
+            (1..=2000u64).map(|i| i * 5).sum(),
+            r#"
+            let mut s = "";
+            let mut i = 0;
+            while i < 2000 {
+                s = s + "hello";
+                i = i + 1;
+            }
+            len(s);
+            "#
+            .to_string(),
+        ),
+        // Large operands
+        (
+            "large_64k_100",
+            100 * 2 * 65_536,
+            format!(
+                r#"
+                let a = "{big}";
+                let b = "{big}";
+                let mut n = 0;
+                let mut i = 0;
+                while i < 100 {{
+                    let s = a + b;
+                    n = n + len(s);
+                    i = i + 1;
+                }}
+                n;
+                "#,
+                big = "x".repeat(65_536)
+            ),
+        ),
+    ];
+    for (name, bytes, source) in cases {
+        let bytecode = compile(&source);
+        group.throughput(Throughput::Bytes(bytes));
+        group.bench_function(BenchmarkId::from_parameter(name), |b| {
+            b.iter_batched(
+                || VM::new(bytecode.clone()),
+                |mut vm| black_box(vm.run(&security)),
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    straight_line_benches,
+    bench_arithmetic_loop,
+    bench_branch_loop,
+);
+
+criterion_group!(
+    call_benches,
+    bench_call_loop,
+    bench_recursive_calls,
+    bench_global_access,
+);
+
+criterion_group!(array_benches, bench_array_indexing,);
+
+criterion_group!(builtin_benches, bench_builtin_calls, bench_run_setup);
+
+criterion_group!(string_benches, bench_string_concat);
+
+criterion_main!(
+    straight_line_benches,
+    call_benches,
+    array_benches,
+    builtin_benches,
+    string_benches
+);
@@ -3,11 +3,44 @@
 //! Provides configuration options for controlling Atlas runtime behavior,
 //! including execution limits, memory constraints, and capability restrictions.
 
+use crate::profile::LanguageProfile;
+use crate::security::RegexLimits;
 use crate::stdlib::{stdout_writer, OutputWriter};
 use crate::value::RuntimeError;
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use crate::vm::gc::GcConfig;
+use crate::vm::VmLimits;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+/// Stops a running evaluation from another thread
+///
+/// Obtained from [`Runtime::interrupt_handle`](super::Runtime::interrupt_handle).
+/// The VM checks the flag between instructions and long-running builtins
+/// check it at their interrupt points, so the evaluation in progress fails
+/// with `RuntimeError::Interrupted` shortly after [`interrupt`](Self::interrupt)
+/// is called. Each evaluation starts with the flag cleared.
+#[derive(Debug, Clone, Default)]
+pub struct InterruptHandle {
+    flag: Arc<AtomicBool>,
+}
+
+impl InterruptHandle {
+    /// Ask the running evaluation to stop
+    pub fn interrupt(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether an interrupt is pending
+    pub fn is_interrupted(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+
+    fn clear(&self) {
+        self.flag.store(false, Ordering::Relaxed);
+    }
+}
+
 /// Execution limits for sandbox enforcement
 ///
 /// Tracks execution time and memory usage, providing methods to check if limits are exceeded.
@@ -16,6 +49,8 @@ use std::time::{Duration, Instant};
 pub struct ExecutionLimits {
     /// Maximum execution time before timeout (None = unlimited)
     pub max_time: Option<Duration>,
+    /// Maximum time a single builtin call may take (None = unlimited)
+    pub max_call_time: Option<Duration>,
     /// Execution start time (set when limits are activated)
     start_time: Option<Instant>,
     /// Instruction counter for amortized time checks in VM
@@ -26,6 +61,10 @@ pub struct ExecutionLimits {
     max_memory: Option<usize>,
     /// Current memory usage in bytes (tracked via track_allocation/track_deallocation)
     current_memory: AtomicUsize,
+    /// Host interrupt flag, shared with every clone of these limits
+    interrupt: InterruptHandle,
+    /// Whether an [`InterruptHandle`] has been handed out
+    interruptible: bool,
 }
 
 impl ExecutionLimits {
@@ -33,12 +72,15 @@ impl ExecutionLimits {
     pub fn from_config(config: &RuntimeConfig) -> Self {
         Self {
             max_time: config.max_execution_time,
+            max_call_time: config.max_builtin_call_time,
             start_time: None,
             instruction_count: AtomicU64::new(0),
             // Check every 10000 instructions to amortize syscall overhead
             check_interval: 10000,
             max_memory: config.max_memory_bytes,
             current_memory: AtomicUsize::new(0),
+            interrupt: InterruptHandle::default(),
+            interruptible: false,
         }
     }
 
@@ -46,19 +88,54 @@ impl ExecutionLimits {
     pub fn unlimited() -> Self {
         Self {
             max_time: None,
+            max_call_time: None,
             start_time: None,
             instruction_count: AtomicU64::new(0),
             check_interval: 10000,
             max_memory: None,
             current_memory: AtomicUsize::new(0),
+            interrupt: InterruptHandle::default(),
+            interruptible: false,
         }
     }
 
-    /// Start the execution timer and reset memory tracking
+    /// Start the execution timer and reset memory tracking and interrupts
     pub fn start(&mut self) {
         self.start_time = Some(Instant::now());
         self.instruction_count.store(0, Ordering::Relaxed);
         self.current_memory.store(0, Ordering::Relaxed);
+        self.interrupt.clear();
+    }
+
+    /// A handle that interrupts evaluations run under these limits
+    ///
+    /// Interrupt checks are only made once a handle has been requested.
+    pub fn interrupt_handle(&mut self) -> InterruptHandle {
+        self.interruptible = true;
+        self.interrupt.clone()
+    }
+
+    /// Fail if the host has interrupted execution
+    ///
+    /// The interrupt is consumed, so it stops the evaluation once.
+    #[inline]
+    pub fn check_interrupt(&self) -> Result<(), RuntimeError> {
+        if self.interruptible && self.interrupt.flag.swap(false, Ordering::Relaxed) {
+            return Err(RuntimeError::Interrupted);
+        }
+        Ok(())
+    }
+
+    /// Check the per-call limit for a builtin that started at `started`
+    pub fn check_call_time(&self, started: Instant) -> Result<(), RuntimeError> {
+        let Some(limit) = self.max_call_time else {
+            return Ok(());
+        };
+        let elapsed = started.elapsed();
+        if elapsed > limit {
+            return Err(RuntimeError::Timeout { elapsed, limit });
+        }
+        Ok(())
     }
 
     /// Check if execution time limit has been exceeded
@@ -90,6 +167,9 @@ impl ExecutionLimits {
     /// Returns Ok(()) if within limits, Err if timeout exceeded.
     #[inline]
     pub fn tick_and_check(&self) -> Result<(), RuntimeError> {
+        if self.interruptible && self.interrupt.is_interrupted() {
+            return self.check_interrupt();
+        }
         if self.max_time.is_none() {
             return Ok(()); // Fast path: no limit configured
         }
@@ -107,9 +187,12 @@ impl ExecutionLimits {
         self.start_time.map(|s| s.elapsed())
     }
 
-    /// Check if limits are active (has a timeout or memory limit configured)
+    /// Check if limits are active (a timeout, memory limit or interrupt handle)
     pub fn is_active(&self) -> bool {
-        self.max_time.is_some() || self.max_memory.is_some()
+        self.max_time.is_some()
+            || self.max_call_time.is_some()
+            || self.max_memory.is_some()
+            || self.interruptible
     }
 
     /// Check if memory limit is configured
@@ -175,11 +258,14 @@ impl Clone for ExecutionLimits {
     fn clone(&self) -> Self {
         Self {
             max_time: self.max_time,
+            max_call_time: self.max_call_time,
             start_time: self.start_time,
             instruction_count: AtomicU64::new(self.instruction_count.load(Ordering::Relaxed)),
             check_interval: self.check_interval,
             max_memory: self.max_memory,
             current_memory: AtomicUsize::new(self.current_memory.load(Ordering::Relaxed)),
+            interrupt: self.interrupt.clone(),
+            interruptible: self.interruptible,
         }
     }
 }
@@ -204,6 +290,7 @@ impl Clone for ExecutionLimits {
 /// // Custom config
 /// let config = RuntimeConfig::new()
 ///     .with_max_execution_time(Duration::from_secs(10))
+///     .with_max_builtin_call_time(Duration::from_secs(1))
 ///     .with_max_memory_bytes(50_000_000) // 50MB
 ///     .with_io_allowed(false)
 ///     .with_network_allowed(false);
@@ -213,6 +300,9 @@ pub struct RuntimeConfig {
     /// Maximum execution time before timeout (None = unlimited)
     pub max_execution_time: Option<Duration>,
 
+    /// Maximum time a single builtin call may take (None = unlimited)
+    pub max_builtin_call_time: Option<Duration>,
+
     /// Maximum memory allocation in bytes (None = unlimited)
     pub max_memory_bytes: Option<usize>,
 
@@ -222,18 +312,42 @@ pub struct RuntimeConfig {
     /// Whether network operations are allowed
     pub allow_network: bool,
 
+    /// Programs scripts may run with `process.exec` and friends
+    ///
+    /// `None` grants every program when IO is allowed and none otherwise;
+    /// `Some` grants exactly the listed programs either way.
+    pub allowed_commands: Option<Vec<String>>,
+
     /// Output destination for print(). Defaults to stdout.
     pub output: OutputWriter,
+
+    /// Cycle collector strategy and tuning
+    pub gc: GcConfig,
+
+    /// Limits on regex patterns compiled by scripts
+    pub regex_limits: RegexLimits,
+
+    /// Limits on call depth and callbacks nested inside builtins
+    pub vm_limits: VmLimits,
+
+    /// Grammar and stdlib restrictions for scripts (default: the full language)
+    pub language_profile: LanguageProfile,
 }
 
 impl std::fmt::Debug for RuntimeConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RuntimeConfig")
             .field("max_execution_time", &self.max_execution_time)
+            .field("max_builtin_call_time", &self.max_builtin_call_time)
             .field("max_memory_bytes", &self.max_memory_bytes)
             .field("allow_io", &self.allow_io)
             .field("allow_network", &self.allow_network)
+            .field("allowed_commands", &self.allowed_commands)
             .field("output", &"<output writer>")
+            .field("gc", &self.gc)
+            .field("regex_limits", &self.regex_limits)
+            .field("vm_limits", &self.vm_limits)
+            .field("language_profile", &self.language_profile)
             .finish()
     }
 }
@@ -259,10 +373,16 @@ impl RuntimeConfig {
     pub fn new() -> Self {
         Self {
             max_execution_time: None,
+            max_builtin_call_time: None,
             max_memory_bytes: None,
             allow_io: true,
             allow_network: true,
+            allowed_commands: None,
             output: stdout_writer(),
+            gc: GcConfig::default(),
+            regex_limits: RegexLimits::standard(),
+            vm_limits: VmLimits::standard(),
+            language_profile: LanguageProfile::default(),
         }
     }
 
@@ -271,6 +391,8 @@ impl RuntimeConfig {
     /// Sandboxed settings:
     /// - 5 second execution timeout
     /// - 10MB memory limit
+    /// - Strict regex limits ([`RegexLimits::strict`])
+    /// - Strict recursion limits ([`VmLimits::strict`])
     /// - IO disabled
     /// - Network disabled
     ///
@@ -289,10 +411,16 @@ impl RuntimeConfig {
     pub fn sandboxed() -> Self {
         Self {
             max_execution_time: Some(Duration::from_secs(5)),
+            max_builtin_call_time: None,
             max_memory_bytes: Some(10_000_000), // 10MB
             allow_io: false,
             allow_network: false,
+            allowed_commands: None,
             output: stdout_writer(),
+            gc: GcConfig::default(),
+            regex_limits: RegexLimits::strict(),
+            vm_limits: VmLimits::strict(),
+            language_profile: LanguageProfile::default(),
         }
     }
 
@@ -332,6 +460,26 @@ impl RuntimeConfig {
         self
     }
 
+    /// Limit how long any one builtin call may run
+    ///
+    /// Expensive builtins (regex over large input, big file reads) check this
+    /// at their interrupt points, so a single call cannot use up the whole
+    /// execution budget unnoticed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atlas_runtime::api::RuntimeConfig;
+    /// use std::time::Duration;
+    ///
+    /// let config = RuntimeConfig::new()
+    ///     .with_max_builtin_call_time(Duration::from_millis(500));
+    /// ```
+    pub fn with_max_builtin_call_time(mut self, duration: Duration) -> Self {
+        self.max_builtin_call_time = Some(duration);
+        self
+    }
+
     /// Set maximum memory allocation in bytes
     ///
     /// # Examples
@@ -376,6 +524,105 @@ impl RuntimeConfig {
         self.allow_network = allowed;
         self
     }
+
+    /// Allow scripts to run only the listed programs
+    ///
+    /// Names are matched exactly against the program a script passes to
+    /// `process.exec`, so `"git"` does not allow `"/usr/bin/git"`. Granting a
+    /// shell such as `"sh"` also allows `process.shell`, and with it any
+    /// command line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atlas_runtime::api::RuntimeConfig;
+    ///
+    /// let config = RuntimeConfig::sandboxed()
+    ///     .with_allowed_commands(["git", "ls"]);
+    /// assert_eq!(config.allowed_commands.unwrap(), vec!["git", "ls"]);
+    /// ```
+    pub fn with_allowed_commands<I, S>(mut self, commands: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_commands = Some(commands.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Configure the cycle collector
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atlas_runtime::api::RuntimeConfig;
+    /// use atlas_runtime::vm::gc::{GcConfig, GcStrategy};
+    ///
+    /// let config = RuntimeConfig::new()
+    ///     .with_gc(GcConfig::new(GcStrategy::Automatic).with_threshold(10_000));
+    /// ```
+    pub fn with_gc(mut self, gc: GcConfig) -> Self {
+        self.gc = gc;
+        self
+    }
+
+    /// Limit the regex patterns scripts may compile
+    ///
+    /// A pattern over any limit fails with `RuntimeError::RegexLimitExceeded`
+    /// instead of compiling.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atlas_runtime::api::RuntimeConfig;
+    /// use atlas_runtime::RegexLimits;
+    ///
+    /// let config = RuntimeConfig::new()
+    ///     .with_regex_limits(RegexLimits::strict().with_max_pattern_len(256));
+    /// ```
+    pub fn with_regex_limits(mut self, limits: RegexLimits) -> Self {
+        self.regex_limits = limits;
+        self
+    }
+
+    /// Limit how deeply scripts may recurse
+    ///
+    /// A call past either limit fails with `RuntimeError::RecursionLimit`
+    /// instead of exhausting memory or overflowing the host stack.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atlas_runtime::api::RuntimeConfig;
+    /// use atlas_runtime::vm::VmLimits;
+    ///
+    /// let config = RuntimeConfig::new()
+    ///     .with_vm_limits(VmLimits::standard().with_max_call_depth(500));
+    /// ```
+    pub fn with_vm_limits(mut self, limits: VmLimits) -> Self {
+        self.vm_limits = limits;
+        self
+    }
+
+    /// Restrict the grammar and stdlib scripts may use
+    ///
+    /// Code outside the profile fails to compile with `AT1025`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atlas_runtime::api::{LanguageProfile, Production, RuntimeConfig};
+    ///
+    /// let config = RuntimeConfig::new().with_language_profile(
+    ///     LanguageProfile::new()
+    ///         .without(Production::Loops)
+    ///         .hide_builtin("file"),
+    /// );
+    /// ```
+    pub fn with_language_profile(mut self, profile: LanguageProfile) -> Self {
+        self.language_profile = profile;
+        self
+    }
 }
 
 impl Default for RuntimeConfig {
@@ -107,20 +107,29 @@ fn type_name(value: &Value) -> &'static str {
         Value::Queue(_) => "queue",
         Value::Stack(_) => "stack",
         Value::Range { .. } => "range",
+        #[cfg(feature = "regex")]
         Value::Regex(_) => "regex",
         Value::DateTime(_) => "datetime",
+        Value::Bytes(_) => "bytes",
+        #[cfg(feature = "http")]
         Value::HttpRequest(_) => "HttpRequest",
+        #[cfg(feature = "http")]
         Value::HttpResponse(_) => "HttpResponse",
         #[cfg(feature = "http")]
         Value::HttpServerRequest(_) => "HttpServerRequest",
         Value::ProcessOutput(_) => "ProcessOutput",
         Value::SqliteConnection(_) => "SqliteConnection",
+        Value::KvStore(_) => "KvStore",
+        Value::FileHandle(_) => "FileHandle",
+        Value::ProgressBar(_) => "ProgressBar",
         Value::Future(_) => "Future",
         Value::TaskHandle(_) => "TaskHandle",
         Value::ChannelSender(_) => "ChannelSender",
         Value::ChannelReceiver(_) => "ChannelReceiver",
         Value::AsyncMutex(_) => "AsyncMutex",
+        #[cfg(feature = "fs")]
         Value::Watcher(_) => "Watcher",
+        Value::Resource(_) => "Resource",
         Value::Closure(_) => "closure",
         Value::SharedValue(_) => "shared",
         Value::Tuple(_) => "tuple",
@@ -320,6 +329,26 @@ impl<T: ToAtlas> ToAtlas for HashMap<String, T> {
     }
 }
 
+// Implementations for host resources
+
+impl FromAtlas for super::resource::Resource {
+    fn from_atlas(value: &Value) -> Result<Self, ConversionError> {
+        match value {
+            Value::Resource(r) => Ok(r.clone()),
+            _ => Err(ConversionError::TypeMismatch {
+                expected: "Resource".to_string(),
+                found: type_name(value).to_string(),
+            }),
+        }
+    }
+}
+
+impl ToAtlas for super::resource::Resource {
+    fn to_atlas(self) -> Value {
+        Value::Resource(self)
+    }
+}
+
 // Convenience implementations for reference types
 
 impl ToAtlas for &str {
@@ -5,6 +5,7 @@
 //! - Runtime execution via Compiler+VM (D-052)
 //! - Value conversion between Rust and Atlas types
 //! - Native function registration
+//! - Host resources released when scripts drop them
 //! - Function calling and global variable management
 //! - Comprehensive error handling
 //!
@@ -24,14 +25,25 @@
 //! runtime.eval("fn add(x: number, y: number): number { return x + y; }").expect("eval failed");
 //! let result = runtime.call("add", vec![Value::Number(1.0), Value::Number(2.0)]).expect("call failed");
 //! ```
+//!
+//! # Cargo features
+//!
+//! Everything is enabled by default. A minimal embed can depend on
+//! `atlas-runtime` with `default-features = false` and opt back in to `http`,
+//! `ffi`, `fs`, `regex`, `collections`, `repl` or `debugger`; builtins of a
+//! disabled feature are absent from the stdlib registry.
 
 pub mod config;
 pub mod conversion;
 pub mod native;
+pub mod resource;
 pub mod runtime;
 
 // Re-export main types for convenience
-pub use config::{ExecutionLimits, RuntimeConfig};
+pub use crate::profile::{LanguageProfile, Production, ProfileError};
+pub use crate::vm::GlobalResolver;
+pub use config::{ExecutionLimits, InterruptHandle, RuntimeConfig};
 pub use conversion::{ConversionError, FromAtlas, ToAtlas};
 pub use native::{BuildError, NativeFunctionBuilder};
+pub use resource::{Resource, WeakResource};
 pub use runtime::{EvalError, Runtime};
@@ -0,0 +1,196 @@
+//! Host-owned resources with a drop callback
+//!
+//! A [`Resource`] wraps a native object (a database connection, a file
+//! handle, a socket) that an embedder hands to scripts as an opaque value.
+//! Scripts can store and pass it around but cannot look inside; native
+//! functions registered by the host get the object back with
+//! [`Resource::with`].
+//!
+//! When the last script reference is dropped the host's finalizer runs with
+//! the object, so connections are closed without the script having to
+//! remember. [`WeakResource`] lets the host observe a resource without
+//! keeping it alive.
+//!
+//! # Examples
+//!
+//! ```
+//! use atlas_runtime::api::{Resource, Runtime};
+//! use atlas_runtime::Value;
+//! use std::sync::atomic::{AtomicBool, Ordering};
+//! use std::sync::Arc;
+//!
+//! struct Connection;
+//!
+//! let closed = Arc::new(AtomicBool::new(false));
+//! let flag = closed.clone();
+//! let db = Resource::new("db", Connection).on_drop(move |_conn: Connection| {
+//!     flag.store(true, Ordering::SeqCst);
+//! });
+//! let weak = db.downgrade();
+//!
+//! let mut runtime = Runtime::new();
+//! runtime.set_global("db", Value::Resource(db));
+//! assert!(weak.is_alive());
+//!
+//! drop(runtime);
+//! assert!(!weak.is_alive());
+//! assert!(closed.load(Ordering::SeqCst));
+//! ```
+
+use std::any::Any;
+use std::fmt;
+use std::sync::{Arc, Mutex, Weak};
+
+type Payload = Box<dyn Any + Send>;
+type Finalizer = Box<dyn FnOnce(Payload) + Send>;
+
+/// An opaque native object owned by the host, released through a callback
+///
+/// Cloning shares the same object; the finalizer runs once, when the last
+/// clone is dropped or [`close`](Self::close) is called.
+#[derive(Clone)]
+pub struct Resource {
+    inner: Arc<ResourceInner>,
+}
+
+/// A handle that observes a [`Resource`] without keeping it alive
+#[derive(Clone)]
+pub struct WeakResource {
+    inner: Weak<ResourceInner>,
+}
+
+struct ResourceInner {
+    kind: String,
+    /// `None` once the resource has been closed
+    payload: Mutex<Option<Payload>>,
+    finalizer: Mutex<Option<Finalizer>>,
+}
+
+impl Resource {
+    /// Wrap `payload` as a resource of the given kind
+    ///
+    /// `kind` names the resource in `typeof`-style output and error messages,
+    /// e.g. `"postgres"` or `"socket"`.
+    pub fn new<T: Any + Send>(kind: impl Into<String>, payload: T) -> Self {
+        Self {
+            inner: Arc::new(ResourceInner {
+                kind: kind.into(),
+                payload: Mutex::new(Some(Box::new(payload))),
+                finalizer: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Run `finalizer` with the payload when the resource is released
+    ///
+    /// The finalizer receives the payload by value. It is not called if the
+    /// payload is not a `T`.
+    pub fn on_drop<T: Any + Send>(self, finalizer: impl FnOnce(T) + Send + 'static) -> Self {
+        let finalizer: Finalizer = Box::new(move |payload: Payload| {
+            if let Ok(payload) = payload.downcast::<T>() {
+                finalizer(*payload);
+            }
+        });
+        *lock(&self.inner.finalizer) = Some(finalizer);
+        self
+    }
+
+    /// The kind given at construction
+    pub fn kind(&self) -> &str {
+        &self.inner.kind
+    }
+
+    /// Borrow the payload as a `T`
+    ///
+    /// Returns `None` if the resource has been closed or holds another type.
+    pub fn with<T: Any, R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        let mut payload = lock(&self.inner.payload);
+        let payload = payload.as_mut()?.downcast_mut::<T>()?;
+        Some(f(payload))
+    }
+
+    /// Release the payload now instead of waiting for the last reference
+    ///
+    /// Returns `false` if the resource was already closed. Other references
+    /// stay valid but see a closed resource.
+    pub fn close(&self) -> bool {
+        self.inner.release()
+    }
+
+    /// Whether the payload has been released
+    pub fn is_closed(&self) -> bool {
+        lock(&self.inner.payload).is_none()
+    }
+
+    /// A weak handle to this resource
+    pub fn downgrade(&self) -> WeakResource {
+        WeakResource {
+            inner: Arc::downgrade(&self.inner),
+        }
+    }
+
+    /// Whether two handles refer to the same resource
+    pub fn ptr_eq(&self, other: &Resource) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl WeakResource {
+    /// The resource, if any strong reference to it is still alive
+    pub fn upgrade(&self) -> Option<Resource> {
+        self.inner.upgrade().map(|inner| Resource { inner })
+    }
+
+    /// Whether a strong reference is still alive and the payload not closed
+    pub fn is_alive(&self) -> bool {
+        self.upgrade().is_some_and(|r| !r.is_closed())
+    }
+}
+
+impl ResourceInner {
+    fn release(&self) -> bool {
+        let Some(payload) = lock(&self.payload).take() else {
+            return false;
+        };
+        if let Some(finalizer) = lock(&self.finalizer).take() {
+            finalizer(payload);
+        }
+        true
+    }
+}
+
+impl Drop for ResourceInner {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.is_closed() { "closed" } else { "open" };
+        write!(f, "<Resource {} {}>", self.kind(), status)
+    }
+}
+
+impl fmt::Debug for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Resource")
+            .field("kind", &self.inner.kind)
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+impl fmt::Debug for WeakResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakResource")
+            .field("alive", &self.is_alive())
+            .finish()
+    }
+}
+
+/// Lock ignoring poisoning: a panicking finalizer must not make the
+/// resource unusable for the rest of the program
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
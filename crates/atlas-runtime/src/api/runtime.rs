@@ -19,10 +19,6 @@
 //! let result = runtime.eval("x").unwrap();
 //! ```
 
-use crate::ast::{
-    Expr, Identifier, ImportSpecifier, Item, ObjectEntry, ObjectLiteral, Program, Stmt, VarDecl,
-};
-
 /// Emit warnings through the proper diagnostic formatter to stderr (H-196).
 /// Replaces raw `eprintln!("{}", diag.to_human_string())` calls.
 fn emit_warnings_via_formatter(warnings: &[Diagnostic]) {
@@ -41,22 +37,22 @@ fn emit_warnings_via_formatter(warnings: &[Diagnostic]) {
         let _ = formatter.write_diagnostic(&mut stream, diag);
     }
 }
+use super::config::InterruptHandle;
 use crate::binder::Binder;
 use crate::compiler::Compiler;
-use crate::diagnostic::error_codes::IMPORT_RESOLUTION_FAILED;
-use crate::diagnostic::Diagnostic;
+use crate::diagnostic::{Diagnostic, StackTraceFrame};
 use crate::lexer::Lexer;
-use crate::module_loader::ModuleLoader;
+use crate::module_loader::{ModuleCache, ModuleLoader};
 use crate::parser::Parser;
-use crate::resolver::ModuleResolver;
+use crate::profile::LanguageProfile;
 use crate::security::SecurityContext;
 use crate::span::Span;
 use crate::typechecker::TypeChecker;
 use crate::value::{RuntimeError, Value};
-use crate::vm::VM;
-use std::cell::RefCell;
+use crate::vm::{GlobalResolver, VM};
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
 
 /// Unified error type for runtime evaluation
 #[derive(Debug)]
@@ -92,6 +88,11 @@ impl std::error::Error for EvalError {}
 /// Maintains global variables and function definitions across multiple
 /// evaluations. Uses Compiler + VM for execution (D-052: unified execution path).
 ///
+/// `Runtime` is `Send + Sync`. Evaluating takes `&mut self`, so one thread
+/// drives it at a time; `&self` methods such as [`get_global`](Self::get_global)
+/// and [`interrupt_handle`](Self::interrupt_handle) may be called from any thread
+/// that can see it.
+///
 /// # Examples
 ///
 /// ```rust,no_run
@@ -108,19 +109,39 @@ impl std::error::Error for EvalError {}
 pub struct Runtime {
     /// Global variables and functions (name -> (value, is_mutable))
     /// Replaces interpreter.globals after D-052 unification
-    globals: RefCell<HashMap<String, (Value, bool)>>,
+    globals: Mutex<HashMap<String, (Value, bool)>>,
     /// Security context for permission checks
-    security: SecurityContext,
+    security: Arc<SecurityContext>,
     /// Execution limits (timeout, memory) for sandbox enforcement
-    execution_limits: RefCell<super::config::ExecutionLimits>,
+    execution_limits: Mutex<super::config::ExecutionLimits>,
     /// Accumulated bytecode (persists across eval() calls)
-    accumulated_bytecode: RefCell<crate::bytecode::Bytecode>,
+    accumulated_bytecode: Mutex<crate::bytecode::Bytecode>,
     /// Output writer for print() (threaded to VM)
     output: crate::stdlib::OutputWriter,
     /// Native function arities (None = variadic)
-    native_signatures: RefCell<HashMap<String, Option<usize>>>,
+    native_signatures: Mutex<HashMap<String, Option<usize>>>,
+    /// Cycle collector configuration (threaded to VM)
+    gc: crate::vm::gc::GcConfig,
+    /// Recursion limits (threaded to VM)
+    vm_limits: crate::vm::VmLimits,
+    /// Call stack of the last runtime error, innermost frame first
+    last_stack_trace: Mutex<Vec<StackTraceFrame>>,
+    /// Grammar and stdlib restrictions applied to every evaluation
+    profile: LanguageProfile,
+    /// Lazily supplies globals not in `globals` (threaded to VM)
+    global_resolver: Option<Arc<dyn GlobalResolver>>,
+    /// Parsed modules shared with other runtimes (threaded to ModuleLoader)
+    module_cache: Option<Arc<ModuleCache>>,
 }
 
+// Compile-time assertion: `Runtime` can move to and be shared with other threads.
+const _: () = {
+    fn _assert_send_sync<T: Send + Sync>() {}
+    fn _check() {
+        _assert_send_sync::<Runtime>();
+    }
+};
+
 impl Default for Runtime {
     fn default() -> Self {
         Self::new()
@@ -140,12 +161,18 @@ impl Runtime {
     pub fn new() -> Self {
         let output = crate::stdlib::stdout_writer();
         Self {
-            globals: RefCell::new(HashMap::new()),
-            security: SecurityContext::new(),
-            execution_limits: RefCell::new(super::config::ExecutionLimits::unlimited()),
-            accumulated_bytecode: RefCell::new(crate::bytecode::Bytecode::new()),
+            globals: Mutex::new(HashMap::new()),
+            security: Arc::new(SecurityContext::new()),
+            execution_limits: Mutex::new(super::config::ExecutionLimits::unlimited()),
+            accumulated_bytecode: Mutex::new(crate::bytecode::Bytecode::new()),
             output,
-            native_signatures: RefCell::new(HashMap::new()),
+            native_signatures: Mutex::new(HashMap::new()),
+            gc: crate::vm::gc::GcConfig::default(),
+            vm_limits: crate::vm::VmLimits::default(),
+            last_stack_trace: Mutex::new(Vec::new()),
+            profile: LanguageProfile::default(),
+            global_resolver: None,
+            module_cache: None,
         }
     }
 
@@ -163,12 +190,18 @@ impl Runtime {
     pub fn new_with_security(security: SecurityContext) -> Self {
         let output = crate::stdlib::stdout_writer();
         Self {
-            globals: RefCell::new(HashMap::new()),
-            security,
-            execution_limits: RefCell::new(super::config::ExecutionLimits::unlimited()),
-            accumulated_bytecode: RefCell::new(crate::bytecode::Bytecode::new()),
+            globals: Mutex::new(HashMap::new()),
+            security: Arc::new(security),
+            execution_limits: Mutex::new(super::config::ExecutionLimits::unlimited()),
+            accumulated_bytecode: Mutex::new(crate::bytecode::Bytecode::new()),
             output,
-            native_signatures: RefCell::new(HashMap::new()),
+            native_signatures: Mutex::new(HashMap::new()),
+            gc: crate::vm::gc::GcConfig::default(),
+            vm_limits: crate::vm::VmLimits::default(),
+            last_stack_trace: Mutex::new(Vec::new()),
+            profile: LanguageProfile::default(),
+            global_resolver: None,
+            module_cache: None,
         }
     }
 
@@ -195,6 +228,7 @@ impl Runtime {
             {
                 security.grant_filesystem_read(std::path::Path::new("/"), true);
                 security.grant_filesystem_write(std::path::Path::new("/"), true);
+                security.grant_database(std::path::Path::new("/"), true);
             }
             #[cfg(windows)]
             {
@@ -203,28 +237,44 @@ impl Runtime {
                 let root = format!("{}\\", system_drive);
                 security.grant_filesystem_read(std::path::Path::new(&root), true);
                 security.grant_filesystem_write(std::path::Path::new(&root), true);
+                security.grant_database(std::path::Path::new(&root), true);
             }
             // Also grant process and environment access with IO
-            security.grant_process("*");
+            if config.allowed_commands.is_none() {
+                security.grant_process("*");
+            }
             security.grant_environment("*");
+            security.grant_environment_write("*");
+        }
+
+        for command in config.allowed_commands.iter().flatten() {
+            security.grant_process(command.clone());
         }
 
         if config.allow_network {
             // Grant network permissions separately
             security.grant_network("*");
         }
+        security.set_regex_limits(config.regex_limits);
 
         // Create execution limits from config (timeout enforcement)
         let execution_limits = super::config::ExecutionLimits::from_config(&config);
 
         let output = config.output.clone();
+        let profile = config.language_profile.clone();
         Self {
-            globals: RefCell::new(HashMap::new()),
-            security,
-            execution_limits: RefCell::new(execution_limits),
-            accumulated_bytecode: RefCell::new(crate::bytecode::Bytecode::new()),
+            globals: Mutex::new(HashMap::new()),
+            security: Arc::new(security),
+            execution_limits: Mutex::new(execution_limits),
+            accumulated_bytecode: Mutex::new(crate::bytecode::Bytecode::new()),
             output,
-            native_signatures: RefCell::new(HashMap::new()),
+            native_signatures: Mutex::new(HashMap::new()),
+            gc: config.gc,
+            vm_limits: config.vm_limits,
+            last_stack_trace: Mutex::new(Vec::new()),
+            profile,
+            global_resolver: None,
+            module_cache: None,
         }
     }
 
@@ -244,6 +294,153 @@ impl Runtime {
         Self::from_config(super::config::RuntimeConfig::sandboxed())
     }
 
+    /// Call stack of the most recent runtime error, innermost frame first
+    ///
+    /// `EvalError::RuntimeError` carries only the error and its span; this
+    /// holds the function names and file/line of each frame that led to it.
+    /// Empty until an evaluation fails at runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atlas_runtime::api::Runtime;
+    ///
+    /// let mut runtime = Runtime::new();
+    /// let source = "fn inner(): void { let arr = [1, 2]; arr[5]; }
+    ///               fn outer(): void { inner(); }
+    ///               outer();";
+    /// runtime.eval(source).unwrap_err();
+    /// let functions: Vec<_> = runtime
+    ///     .last_stack_trace()
+    ///     .into_iter()
+    ///     .map(|frame| frame.function)
+    ///     .collect();
+    /// assert_eq!(functions, ["inner", "outer"]);
+    /// ```
+    pub fn last_stack_trace(&self) -> Vec<StackTraceFrame> {
+        lock(&self.last_stack_trace).clone()
+    }
+
+    /// Handle for interrupting this runtime's evaluations from another thread
+    ///
+    /// Calling [`InterruptHandle::interrupt`] makes the evaluation in progress
+    /// fail with `RuntimeError::Interrupted` at its next check: between VM
+    /// instructions or at an interrupt point inside a long-running builtin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atlas_runtime::api::{EvalError, Runtime};
+    /// use atlas_runtime::value::RuntimeError;
+    /// use std::time::Duration;
+    ///
+    /// let mut runtime = Runtime::new();
+    /// let handle = runtime.interrupt_handle();
+    /// std::thread::spawn(move || {
+    ///     std::thread::sleep(Duration::from_millis(50));
+    ///     handle.interrupt();
+    /// });
+    /// let result = runtime.eval("let mut x: number = 0; while (true) { x = x + 1; }");
+    /// assert!(matches!(
+    ///     result,
+    ///     Err(EvalError::RuntimeError(RuntimeError::Interrupted))
+    /// ));
+    /// ```
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        lock(&self.execution_limits).interrupt_handle()
+    }
+
+    /// Restrict the grammar and stdlib of later evaluations
+    ///
+    /// Code already evaluated is unaffected. Functions registered with
+    /// [`register_function`](Self::register_function) stay visible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atlas_runtime::api::{LanguageProfile, Runtime};
+    ///
+    /// let mut runtime = Runtime::new();
+    /// runtime.set_language_profile(LanguageProfile::expressions_only());
+    /// assert!(runtime.eval("1 + 2").is_ok());
+    /// assert!(runtime.eval("let x = 1;").is_err());
+    /// ```
+    pub fn set_language_profile(&mut self, profile: LanguageProfile) {
+        self.profile = profile;
+    }
+
+    /// Supply globals on demand instead of setting them up front
+    ///
+    /// Scripts may use every name in [`GlobalResolver::names`]; a name is
+    /// resolved the first time a script reads it and then kept like a value
+    /// set with [`set_global`](Self::set_global), which takes precedence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atlas_runtime::api::{GlobalResolver, Runtime};
+    /// use atlas_runtime::Value;
+    ///
+    /// struct Orders;
+    ///
+    /// impl GlobalResolver for Orders {
+    ///     fn names(&self) -> Vec<String> {
+    ///         vec!["orderCount".to_string()]
+    ///     }
+    ///     fn resolve(&self, name: &str) -> Option<Value> {
+    ///         (name == "orderCount").then(|| Value::Number(3.0))
+    ///     }
+    /// }
+    ///
+    /// let mut runtime = Runtime::new();
+    /// runtime.set_global_resolver(Orders);
+    /// let result = runtime.eval("orderCount * 2").unwrap();
+    /// assert_eq!(result, Value::Number(6.0));
+    /// ```
+    pub fn set_global_resolver(&mut self, resolver: impl GlobalResolver + 'static) {
+        self.global_resolver = Some(Arc::new(resolver));
+    }
+
+    /// Share parsed modules with every runtime using `cache`
+    ///
+    /// `load_file` and `eval_file` then skip re-parsing files that have not
+    /// changed since another runtime loaded them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atlas_runtime::api::Runtime;
+    /// use atlas_runtime::ModuleCache;
+    /// use std::sync::Arc;
+    ///
+    /// let cache = Arc::new(ModuleCache::new());
+    /// let mut runtime = Runtime::new();
+    /// runtime.set_module_cache(Arc::clone(&cache));
+    /// ```
+    pub fn set_module_cache(&mut self, cache: Arc<ModuleCache>) {
+        self.module_cache = Some(cache);
+    }
+
+    /// Restart the execution limits and attach them, and the recursion
+    /// limits, to `vm`
+    fn start_limits(&self, vm: &mut VM) {
+        vm.set_limits(self.vm_limits);
+        let execution_limits = {
+            let mut limits = lock(&self.execution_limits);
+            limits.start();
+            std::sync::Arc::new(limits.clone())
+        };
+        if execution_limits.is_active() {
+            vm.set_execution_limits(execution_limits);
+        }
+    }
+
+    /// Record the failed VM's call stack and wrap its error
+    fn runtime_error(&self, vm: &mut VM, error: RuntimeError) -> EvalError {
+        *lock(&self.last_stack_trace) = vm.take_error_stack_trace();
+        EvalError::RuntimeError(error)
+    }
+
     /// Evaluate Atlas source code
     ///
     /// Runs the full compilation pipeline (lex → parse → bind → typecheck → execute)
@@ -279,7 +476,8 @@ impl Runtime {
 
         // Lex the source code
         let mut lexer = Lexer::new(&source_with_semi);
-        let (tokens, lex_diagnostics) = lexer.tokenize();
+        let (mut tokens, lex_diagnostics) = lexer.tokenize();
+        self.profile.rewrite_keywords(&mut tokens);
 
         if !lex_diagnostics.is_empty() {
             return Err(EvalError::ParseError(lex_diagnostics));
@@ -300,7 +498,7 @@ impl Runtime {
         // Create initial symbol table with registered globals
         let mut initial_symbol_table = crate::symbol::SymbolTable::new();
         {
-            let globals = self.globals.borrow();
+            let globals = lock(&self.globals);
             for (name, (value, is_mutable)) in globals.iter() {
                 // Determine symbol kind based on value type
                 let kind = match value {
@@ -311,9 +509,7 @@ impl Runtime {
                 };
 
                 let ty = match value {
-                    Value::NativeFunction(_) => self
-                        .native_signatures
-                        .borrow()
+                    Value::NativeFunction(_) => lock(&self.native_signatures)
                         .get(name)
                         .and_then(|arity| {
                             arity.as_ref().map(|count| crate::types::Type::Function {
@@ -344,10 +540,25 @@ impl Runtime {
                     let _ = initial_symbol_table.define(symbol);
                 }
             }
+
+            // Resolver names not yet materialized into globals
+            let resolver_names = self.global_resolver.iter().flat_map(|r| r.names());
+            for name in resolver_names.filter(|name| !globals.contains_key(name)) {
+                let _ = initial_symbol_table.define(crate::symbol::Symbol {
+                    name,
+                    ty: crate::types::Type::Unknown,
+                    mutable: false,
+                    kind: crate::symbol::SymbolKind::Variable,
+                    span: crate::span::Span::dummy(),
+                    exported: false,
+                    visibility: crate::ast::Visibility::Private,
+                });
+            }
         }
 
         // Bind symbols with pre-populated symbol table
         let mut binder = Binder::with_symbol_table(initial_symbol_table);
+        binder.set_language_profile(self.profile.clone());
         let (mut symbol_table, bind_diagnostics) = binder.bind(&ast);
 
         if !bind_diagnostics.is_empty() {
@@ -371,13 +582,6 @@ impl Runtime {
             .filter(|d| d.is_warning())
             .collect();
 
-        // Start execution timer and prepare limits for sharing
-        let execution_limits = {
-            let mut limits = self.execution_limits.borrow_mut();
-            limits.start();
-            std::sync::Arc::new(limits.clone())
-        };
-
         // Compile AST to bytecode (D-052: unified VM execution path)
         let mut compiler = Compiler::new();
         let new_bytecode = match compiler.compile(&ast) {
@@ -386,53 +590,58 @@ impl Runtime {
         };
 
         // Get the start offset of new code (before appending)
-        let new_code_start = self.accumulated_bytecode.borrow().instructions.len();
+        let new_code_start = lock(&self.accumulated_bytecode).instructions.len();
 
         // Append to accumulated bytecode
-        self.accumulated_bytecode.borrow_mut().append(new_bytecode);
+        lock(&self.accumulated_bytecode).append(new_bytecode);
 
         // Create VM with the accumulated bytecode
-        let accumulated = self.accumulated_bytecode.borrow().clone();
+        let accumulated = lock(&self.accumulated_bytecode).clone();
         let mut vm = VM::new(accumulated);
         vm.set_output_writer(self.output.clone());
+        vm.set_gc_config(self.gc);
 
-        // Set execution limits for timeout enforcement
-        if execution_limits.is_active() {
-            vm.set_execution_limits(execution_limits);
-        }
+        // Start execution limits (timeout, memory, interrupts)
+        self.start_limits(&mut vm);
 
         // Set IP to start of new code (so we don't re-execute old code)
         vm.set_ip(new_code_start);
 
         // Copy runtime globals to VM (for natives and other complex types)
         {
-            let globals = self.globals.borrow();
+            let globals = lock(&self.globals);
             for (name, (value, _mutable)) in globals.iter() {
                 vm.set_global(name.clone(), value.clone());
             }
         }
+        if let Some(resolver) = &self.global_resolver {
+            vm.set_global_resolver(Arc::clone(resolver));
+        }
 
         // Load extern function declarations (FFI bindings)
         vm.load_extern_declarations(&ast)
             .map_err(EvalError::RuntimeError)?;
 
+        vm.set_security(Arc::clone(&self.security));
         let result = match vm.run(&self.security) {
             Ok(Some(value)) => Ok(value),
             Ok(None) => Ok(Value::Null),
-            Err(e) => Err(EvalError::RuntimeError(e)),
+            Err(e) => Err(self.runtime_error(&mut vm, e)),
         };
 
         // Collect and emit all warnings via the proper formatter (H-196)
         let mut all_warnings = typecheck_warnings;
         all_warnings.extend(vm.take_runtime_warnings());
+        let all_warnings =
+            crate::diagnostic::suppression::apply_suppressions(&source_with_semi, all_warnings);
         emit_warnings_via_formatter(&all_warnings);
 
         // Copy VM globals back to runtime for persistence across eval() calls
         // Note: VM doesn't track mutability, so we default to mutable for copied-back values
         {
-            let mut globals = self.globals.borrow_mut();
+            let mut globals = lock(&self.globals);
             for (name, value) in vm.get_globals() {
-                globals.insert(name.clone(), (value.clone(), true));
+                globals.insert(name, (value, true));
             }
         }
 
@@ -474,6 +683,10 @@ impl Runtime {
         {
             // Step 1: Load all modules in dependency order
             let mut loader = ModuleLoader::new(project_root.clone());
+            loader.set_language_profile(self.profile.clone());
+            if let Some(cache) = &self.module_cache {
+                loader.set_module_cache(Arc::clone(cache));
+            }
             let modules = loader.load_module(path).map_err(EvalError::ParseError)?;
 
             // Step 2: Compile ALL modules to bytecode in dependency order
@@ -481,40 +694,49 @@ impl Runtime {
             // so when module B imports from A, A's code will have already run
             // and defined its globals before B tries to access them.
             let mut combined_bytecode = crate::bytecode::Bytecode::new();
-
-            let exports_by_path: HashMap<std::path::PathBuf, Vec<String>> = modules
-                .iter()
-                .map(|module| (module.path.clone(), module.exports.clone()))
-                .collect();
-            let mut resolver = ModuleResolver::new(project_root.clone());
+            let linker = crate::module_loader::ModuleLinker::new(&project_root, &modules);
 
             // Pass 1: Run binder + typechecker to populate AST annotations (type_tag, etc.)
             // Without this, MemberExpr.type_tag is None and compile_member falls back to
             // the GetField structural path, which fails for builtin namespaces (console, etc.).
             let mut module_registry = crate::module_loader::ModuleRegistry::new();
-            let mut expanded_modules: Vec<Program> = Vec::new();
+            let mut profile_errors = Vec::new();
             for module in &modules {
-                let expanded = self
-                    .expand_namespace_imports(module, &exports_by_path, &mut resolver)
-                    .map_err(|diag| EvalError::ParseError(vec![*diag]))?;
                 let mut binder = Binder::new();
                 binder.set_resolved_imports(module.resolved_imports.clone());
-                let (mut symbol_table, _) =
-                    binder.bind_with_modules(&expanded, &module.path, &module_registry);
+                binder.set_language_profile(self.profile.clone());
+                let (mut symbol_table, bind_diags) =
+                    binder.bind_with_modules(&module.ast, &module.path, &module_registry);
+                // Other binder errors surface at runtime, but code outside the
+                // language profile must never run
+                profile_errors.extend(bind_diags.into_iter().filter(|d| {
+                    d.code == crate::diagnostic::error_codes::NOT_IN_LANGUAGE_PROFILE.code
+                }));
                 let mut type_checker = TypeChecker::new(&mut symbol_table);
-                let _ = type_checker.check(&expanded); // populate annotations; ignore errors
+                let _ = type_checker.check(&module.ast); // populate annotations; ignore errors
                 module_registry.register(module.path.clone(), symbol_table);
-                expanded_modules.push(expanded);
+            }
+            if !profile_errors.is_empty() {
+                return Err(EvalError::ParseError(profile_errors));
             }
 
-            for (i, (module, expanded)) in modules.iter().zip(expanded_modules.iter()).enumerate() {
+            for (i, module) in modules.iter().enumerate() {
                 let is_last = i == modules.len() - 1;
 
                 // Compile this module (AST already has type_tag annotations from Pass 1)
                 let mut compiler = Compiler::new();
                 compiler.register_imported_enums(&module.imports, &module.path, &module_registry);
-                let mut module_bytecode =
-                    compiler.compile(expanded).map_err(EvalError::ParseError)?;
+                compiler.register_namespace_imports(
+                    &module.imports,
+                    &module.path,
+                    &module_registry,
+                );
+                if let Some(dir) = module.path.parent() {
+                    compiler.set_source_dir(dir);
+                }
+                let mut module_bytecode = compiler
+                    .compile(&module.ast)
+                    .map_err(EvalError::ParseError)?;
 
                 // Strip trailing Halt from non-final modules
                 // (compiler adds Halt at end of each module, but we need
@@ -533,12 +755,14 @@ impl Runtime {
                 }
 
                 // Append to combined bytecode (this adjusts function offsets)
+                linker.link(module, &mut module_bytecode);
                 combined_bytecode.append(module_bytecode);
             }
 
             // Step 3: Create VM and run combined bytecode
             let mut vm = VM::new(combined_bytecode);
             vm.set_output_writer(self.output.clone());
+            self.start_limits(&mut vm);
 
             // Load extern function declarations from all modules (FFI bindings)
             for module in &modules {
@@ -547,10 +771,11 @@ impl Runtime {
             }
 
             // Step 4: Execute via VM
+            vm.set_security(Arc::clone(&self.security));
             match vm.run(&self.security) {
                 Ok(Some(value)) => Ok(value),
                 Ok(None) => Ok(Value::Null),
-                Err(e) => Err(EvalError::RuntimeError(e)),
+                Err(e) => Err(self.runtime_error(&mut vm, e)),
             }
         }
     }
@@ -592,13 +817,13 @@ impl Runtime {
 
         // Step 1: Load all modules in dependency order
         let mut loader = ModuleLoader::new(project_root.clone());
+        loader.set_language_profile(self.profile.clone());
+        if let Some(cache) = &self.module_cache {
+            loader.set_module_cache(Arc::clone(cache));
+        }
         let modules = loader.load_module(path).map_err(EvalError::ParseError)?;
 
-        let exports_by_path: HashMap<std::path::PathBuf, Vec<String>> = modules
-            .iter()
-            .map(|module| (module.path.clone(), module.exports.clone()))
-            .collect();
-        let mut resolver = ModuleResolver::new(project_root.clone());
+        let linker = crate::module_loader::ModuleLinker::new(&project_root, &modules);
 
         // Build module registry for cross-module import resolution
         let mut module_registry = crate::module_loader::ModuleRegistry::new();
@@ -608,25 +833,14 @@ impl Runtime {
         // This ensures type_tags are set for namespace calls like test.assert().
         // ═══════════════════════════════════════════════════════════════════════
         let mut all_errors: Vec<Diagnostic> = Vec::new();
-        let mut expanded_modules: Vec<Program> = Vec::new();
 
         for module in &modules {
-            // Expand namespace imports (import * as foo)
-            let expanded =
-                match self.expand_namespace_imports(module, &exports_by_path, &mut resolver) {
-                    Ok(e) => e,
-                    Err(diag) => {
-                        all_errors.push(*diag);
-                        expanded_modules.push(Program { items: vec![] });
-                        continue;
-                    }
-                };
-
             // Bind symbols with cross-module import support
             let mut binder = Binder::new();
             binder.set_resolved_imports(module.resolved_imports.clone());
+            binder.set_language_profile(self.profile.clone());
             let (mut symbol_table, bind_diags) =
-                binder.bind_with_modules(&expanded, &module.path, &module_registry);
+                binder.bind_with_modules(&module.ast, &module.path, &module_registry);
             let bind_errors: Vec<_> = bind_diags
                 .iter()
                 .filter(|d| d.is_error())
@@ -636,7 +850,7 @@ impl Runtime {
 
             // Type-check even if bind had errors — collect ALL diagnostics
             let mut type_checker = TypeChecker::new(&mut symbol_table);
-            let type_diags = type_checker.check(&expanded);
+            let type_diags = type_checker.check(&module.ast);
             let type_errors: Vec<_> = type_diags
                 .iter()
                 .filter(|d| d.is_error())
@@ -646,7 +860,6 @@ impl Runtime {
 
             // Register this module's symbol table for subsequent imports
             module_registry.register(module.path.clone(), symbol_table);
-            expanded_modules.push(expanded);
         }
 
         // If ANY module had errors, return ALL errors now (before compilation)
@@ -659,12 +872,18 @@ impl Runtime {
         // ═══════════════════════════════════════════════════════════════════════
         let mut combined_bytecode = crate::bytecode::Bytecode::new();
 
-        for (i, expanded) in expanded_modules.iter().enumerate() {
+        for (i, module) in modules.iter().enumerate() {
             let is_last = i == modules.len() - 1;
 
             // Compile this module
             let mut compiler = Compiler::new();
-            let mut module_bytecode = compiler.compile(expanded).map_err(EvalError::ParseError)?;
+            compiler.register_namespace_imports(&module.imports, &module.path, &module_registry);
+            if let Some(dir) = module.path.parent() {
+                compiler.set_source_dir(dir);
+            }
+            let mut module_bytecode = compiler
+                .compile(&module.ast)
+                .map_err(EvalError::ParseError)?;
 
             // Strip trailing Halt from non-final modules
             if !is_last
@@ -679,6 +898,7 @@ impl Runtime {
                 }
             }
 
+            linker.link(module, &mut module_bytecode);
             combined_bytecode.append(module_bytecode);
         }
 
@@ -690,24 +910,26 @@ impl Runtime {
         }
 
         // Step 3: Get start offset and append to accumulated bytecode
-        let new_code_start = self.accumulated_bytecode.borrow().instructions.len();
-        self.accumulated_bytecode
-            .borrow_mut()
-            .append(combined_bytecode);
+        let new_code_start = lock(&self.accumulated_bytecode).instructions.len();
+        lock(&self.accumulated_bytecode).append(combined_bytecode);
 
         // Step 4: Create VM with accumulated bytecode, set IP to new code
-        let accumulated = self.accumulated_bytecode.borrow().clone();
+        let accumulated = lock(&self.accumulated_bytecode).clone();
         let mut vm = VM::new(accumulated);
         vm.set_output_writer(self.output.clone());
+        self.start_limits(&mut vm);
         vm.set_ip(new_code_start);
 
         // Copy runtime globals to VM (for natives and other complex types)
         {
-            let globals = self.globals.borrow();
+            let globals = lock(&self.globals);
             for (name, (value, _mutable)) in globals.iter() {
                 vm.set_global(name.clone(), value.clone());
             }
         }
+        if let Some(resolver) = &self.global_resolver {
+            vm.set_global_resolver(Arc::clone(resolver));
+        }
 
         // Load extern function declarations from all modules (FFI bindings)
         for module in &modules {
@@ -716,88 +938,22 @@ impl Runtime {
         }
 
         // Step 5: Execute the loaded code
+        vm.set_security(Arc::clone(&self.security));
         if let Err(e) = vm.run(&self.security) {
-            return Err(EvalError::RuntimeError(e));
+            return Err(self.runtime_error(&mut vm, e));
         }
 
         // Step 6: Copy VM globals back to runtime for subsequent eval() calls
         {
-            let mut globals = self.globals.borrow_mut();
+            let mut globals = lock(&self.globals);
             for (name, value) in vm.get_globals() {
-                globals.insert(name.clone(), (value.clone(), true));
+                globals.insert(name, (value, true));
             }
         }
 
         Ok(())
     }
 
-    fn expand_namespace_imports(
-        &self,
-        module: &crate::module_loader::LoadedModule,
-        exports_by_path: &HashMap<std::path::PathBuf, Vec<String>>,
-        resolver: &mut ModuleResolver,
-    ) -> Result<Program, Box<Diagnostic>> {
-        let mut items = Vec::new();
-
-        for item in &module.ast.items {
-            items.push(item.clone());
-
-            let Item::Import(import_decl) = item else {
-                continue;
-            };
-
-            for specifier in &import_decl.specifiers {
-                let ImportSpecifier::Namespace { alias, span } = specifier else {
-                    continue;
-                };
-
-                let import_path = resolver
-                    .resolve_path(&import_decl.source, &module.path, import_decl.span)
-                    .map_err(Box::new)?;
-                let exports = exports_by_path.get(&import_path).ok_or_else(|| {
-                    Box::new(
-                        IMPORT_RESOLUTION_FAILED
-                            .emit(import_decl.span)
-                            .arg("path", &import_decl.source)
-                            .arg("detail", "module not found")
-                            .build()
-                            .with_label("namespace import"),
-                    )
-                })?;
-
-                let mut entries = Vec::with_capacity(exports.len());
-                for name in exports {
-                    let ident = Identifier {
-                        name: name.clone(),
-                        span: *span,
-                    };
-                    entries.push(ObjectEntry {
-                        key: ident.clone(),
-                        value: Expr::Identifier(ident),
-                        span: *span,
-                    });
-                }
-
-                let obj = ObjectLiteral {
-                    entries,
-                    span: *span,
-                };
-                let var = VarDecl {
-                    mutable: false,
-                    uses_deprecated_var: false,
-                    name: alias.clone(),
-                    type_ref: None,
-                    init: Expr::ObjectLiteral(obj),
-                    span: *span,
-                    needs_drop: std::cell::RefCell::new(None),
-                };
-                items.push(Item::Statement(Stmt::VarDecl(var)));
-            }
-        }
-
-        Ok(Program { items })
-    }
-
     /// Call an Atlas function by name with arguments
     ///
     /// Looks up the function in global scope and executes it with provided arguments.
@@ -872,7 +1028,7 @@ impl Runtime {
     /// ```
     pub fn set_global(&mut self, name: &str, value: Value) {
         // Store in runtime globals - eval() copies these to VM before execution
-        let mut globals = self.globals.borrow_mut();
+        let mut globals = lock(&self.globals);
         globals.insert(name.to_string(), (value, true));
     }
 
@@ -902,7 +1058,7 @@ impl Runtime {
     /// ```
     pub fn get_global(&self, name: &str) -> Option<Value> {
         // Read from runtime globals - eval() copies VM globals back here after execution
-        let globals = self.globals.borrow();
+        let globals = lock(&self.globals);
         globals.get(name).map(|(v, _)| v.clone())
     }
 
@@ -973,9 +1129,7 @@ impl Runtime {
             }
         };
 
-        self.native_signatures
-            .borrow_mut()
-            .insert(name.to_string(), Some(arity));
+        lock(&self.native_signatures).insert(name.to_string(), Some(arity));
         self.set_global(name, native_fn);
     }
 
@@ -1038,9 +1192,13 @@ impl Runtime {
             }
         };
 
-        self.native_signatures
-            .borrow_mut()
-            .insert(name.to_string(), None);
+        lock(&self.native_signatures).insert(name.to_string(), None);
         self.set_global(name, native_fn);
     }
 }
+
+/// Lock ignoring poisoning: a native function that panics mid-`eval` must
+/// not leave the runtime's globals permanently locked
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
@@ -537,6 +537,8 @@ pub enum Stmt {
     Expr(ExprStmt),
     /// Deferred execution: runs on scope exit in LIFO order
     Defer(DeferStmt),
+    /// Runtime error recovery: `try { ... } catch (e) { ... }`
+    TryCatch(TryCatchStmt),
 }
 
 /// Variable declaration
@@ -554,17 +556,37 @@ pub struct VarDecl {
     /// Set by typechecker when variable's type implements Drop trait.
     #[serde(skip)]
     pub needs_drop: std::cell::RefCell<Option<String>>,
+    /// Type inferred for a declaration without an annotation.
+    /// Set by typechecker; read by tooling (inlay hints).
+    #[serde(skip)]
+    pub inferred_type: std::cell::RefCell<Option<crate::types::Type>>,
 }
 
-/// Tuple destructuring declaration: `let (a, b) = expr;`
+/// Destructuring declaration: `let (a, b) = expr;`, `let [first, ...rest] = expr;`
+/// or `let { key } = expr;`
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LetDestructure {
     pub mutable: bool,
+    pub kind: DestructureKind,
+    /// Every bound name in source order (for an array with a rest binding,
+    /// the rest name is last)
     pub names: Vec<Identifier>,
     pub init: Expr,
     pub span: Span,
 }
 
+/// Shape of a [`LetDestructure`] pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DestructureKind {
+    /// `(a, b)`: tuple elements by position
+    Tuple,
+    /// `[a, b]` or `[a, b, ...rest]`: array elements by index, plus the
+    /// remaining slice when `rest` is set
+    Array { rest: bool },
+    /// `{ a, b }`: HashMap or record entries keyed by each name
+    Map,
+}
+
 /// Assignment statement
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Assign {
@@ -662,6 +684,20 @@ pub struct DeferStmt {
     pub span: Span,
 }
 
+/// Try/catch statement — recovers from runtime errors raised in `body`
+///
+/// Syntax: `try { body } catch (e) { handler }` or `try { body } catch { handler }`
+///
+/// Not to be confused with [`Expr::Try`], the `?` propagation operator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TryCatchStmt {
+    pub body: Block,
+    /// Name bound to the error record inside the catch block
+    pub error_binding: Option<Identifier>,
+    pub catch_block: Block,
+    pub span: Span,
+}
+
 /// Expression
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Expr {
@@ -748,6 +784,37 @@ pub struct BinaryExpr {
     pub span: Span,
 }
 
+impl BinaryExpr {
+    /// Operands and operators of a comparison chain such as `0 <= i < n`
+    ///
+    /// A chain is an ordering comparison whose left operand is itself an
+    /// unparenthesized ordering comparison; `(a < b) < c` is not one. Returns
+    /// `None` for a single comparison.
+    pub fn comparison_chain(&self) -> Option<(Vec<&Expr>, Vec<BinaryOp>)> {
+        if !self.op.is_ordering() {
+            return None;
+        }
+        let mut operands = vec![self.right.as_ref()];
+        let mut ops = vec![self.op];
+        let mut left = self.left.as_ref();
+        while let Expr::Binary(inner) = left {
+            if !inner.op.is_ordering() {
+                break;
+            }
+            operands.push(inner.right.as_ref());
+            ops.push(inner.op);
+            left = inner.left.as_ref();
+        }
+        if ops.len() < 2 {
+            return None;
+        }
+        operands.push(left);
+        operands.reverse();
+        ops.reverse();
+        Some((operands, ops))
+    }
+}
+
 /// Function call expression
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CallExpr {
@@ -758,6 +825,14 @@ pub struct CallExpr {
     pub span: Span,
 }
 
+impl CallExpr {
+    /// Whether the call was written as a pipeline stage: the parser desugars
+    /// `x |> f(y)` to `f(x, y)`, so the piped value precedes the callee.
+    pub fn is_pipeline(&self) -> bool {
+        is_piped(self.args.first(), self.callee.span(), self.span)
+    }
+}
+
 /// Array index expression
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct IndexExpr {
@@ -774,7 +849,7 @@ pub enum IndexValue {
 
 /// Member access expression (method call or property access)
 ///
-/// Syntax: `expr.member` or `expr.method(args)`
+/// Syntax: `expr.member` or `expr.method(args)`, or `expr?.member` for null-safe access
 /// This is sugar for function calls: `Type::method(expr, args)`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemberExpr {
@@ -786,6 +861,13 @@ pub struct MemberExpr {
     pub args: Option<Vec<Expr>>,
     /// Type arguments for generic method calls: `obj.method<T>(x)`
     pub type_args: Vec<TypeRef>,
+    /// Written `?.`: a null (or None) target skips the access
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    /// What a `?.` access unwraps its target from.
+    /// Set by the typechecker, read by the compiler.
+    #[serde(skip)]
+    pub null_safe: Cell<Option<NullSafeKind>>,
     /// Type tag for method dispatch (set by typechecker, used by interpreter/compiler)
     #[serde(skip)]
     pub type_tag: Cell<Option<TypeTag>>,
@@ -802,15 +884,34 @@ pub struct MemberExpr {
 
 impl PartialEq for MemberExpr {
     fn eq(&self, other: &Self) -> bool {
-        // type_tag, trait_dispatch, static_dispatch are ephemeral annotations — exclude from equality
+        // null_safe, type_tag, trait_dispatch, static_dispatch are ephemeral annotations — exclude from equality
         self.target == other.target
             && self.member == other.member
             && self.args == other.args
             && self.type_args == other.type_args
+            && self.optional == other.optional
             && self.span == other.span
     }
 }
 
+impl MemberExpr {
+    /// Whether the method call was written as a pipeline stage
+    /// (`x |> Math.max(y)`, desugared to `Math.max(x, y)`)
+    pub fn is_pipeline(&self) -> bool {
+        let first = self.args.as_ref().and_then(|args| args.first());
+        is_piped(first, self.target.span(), self.span)
+    }
+}
+
+/// Whether `first` is a value piped into a call: it starts the call and ends
+/// before the callee. Synthesized arguments (dummy spans) never qualify.
+fn is_piped(first: Option<&Expr>, callee: Span, call: Span) -> bool {
+    first.is_some_and(|arg| {
+        let arg = arg.span();
+        arg.start < arg.end && arg.start == call.start && arg.end <= callee.start
+    })
+}
+
 /// Array literal expression
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ArrayLiteral {
@@ -883,6 +984,21 @@ pub enum TryTargetKind {
     Option,
 }
 
+/// What the target of a `?.` access is, which decides how it short-circuits.
+/// Set by the typechecker, read by the compiler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NullSafeKind {
+    /// `T | null` (or `any`): a null target gives null
+    Nullable,
+    /// `json`: property reads index the value, so a missing key or json null
+    /// gives json null; a method call on json null gives null
+    Json,
+    /// `Option<T>`: None gives None, `Some(v)` gives `Some(v.member)`
+    Option,
+    /// `Result<T, E>`: `?` propagation followed by a plain access
+    Result,
+}
+
 /// Try expression (error propagation operator ?)
 ///
 /// Unwraps Ok/Some value or returns Err/None early from current function
@@ -948,10 +1064,25 @@ pub enum Pattern {
         args: Vec<Pattern>,
         span: Span,
     },
-    /// Array pattern: [], [x], [x, y]
-    Array { elements: Vec<Pattern>, span: Span },
+    /// Array pattern: [], [x], [x, y], [first, ...rest]
+    ///
+    /// Without `rest` the array length must equal `elements.len()`; with it the
+    /// array needs at least that many elements and `rest` (a variable or `_`)
+    /// matches the remaining slice.
+    Array {
+        elements: Vec<Pattern>,
+        rest: Option<Box<Pattern>>,
+        span: Span,
+    },
     /// Tuple pattern: (p1, p2, ...)
     Tuple { elements: Vec<Pattern>, span: Span },
+    /// Number range pattern: `0..10` (end excluded) or `0..=10` (end included)
+    Range {
+        start: f64,
+        end: f64,
+        inclusive: bool,
+        span: Span,
+    },
     /// OR pattern: pat1 | pat2 | pat3
     Or(Vec<Pattern>, Span),
     /// Enum variant pattern: State::Running, Color::Rgb(r, g, b)
@@ -986,6 +1117,11 @@ pub enum Literal {
     String(String),
     Bool(bool),
     Null,
+    /// `/pattern/flags`, both as written in the source
+    Regex {
+        pattern: String,
+        flags: String,
+    },
 }
 
 /// Template string part
@@ -1082,6 +1218,20 @@ pub enum BinaryOp {
     // Logical
     And,
     Or,
+    /// `??`: the left value unless it is null, None or json null; `Some(v)` unwraps to `v`
+    Coalesce,
+    /// `in`: membership of the left value in an array, map, set, string, range or json object
+    In,
+}
+
+impl BinaryOp {
+    /// Whether this is one of `<`, `<=`, `>`, `>=`, the operators that chain
+    pub fn is_ordering(self) -> bool {
+        matches!(
+            self,
+            BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge
+        )
+    }
 }
 
 // Helper methods for getting spans from AST nodes
@@ -1140,6 +1290,7 @@ impl Stmt {
             Stmt::Break(s) | Stmt::Continue(s) => *s,
             Stmt::Expr(e) => e.span,
             Stmt::Defer(d) => d.span,
+            Stmt::TryCatch(t) => t.span,
         }
     }
 }
@@ -1172,6 +1323,7 @@ impl Pattern {
             Pattern::Constructor { span, .. } => *span,
             Pattern::Array { span, .. } => *span,
             Pattern::Tuple { span, .. } => *span,
+            Pattern::Range { span, .. } => *span,
             Pattern::Or(_, span) => *span,
             Pattern::EnumVariant { span, .. } => *span,
             Pattern::BareVariant { span, .. } => *span,
@@ -116,6 +116,17 @@ impl AtlasFuture {
         matches!(inner.state, FutureState::Rejected(_))
     }
 
+    /// `true` if this is the only handle to the future, so nothing else can
+    /// ever resolve or reject it.
+    pub(crate) fn is_orphaned(&self) -> bool {
+        Arc::strong_count(&self.inner) == 1
+    }
+
+    /// `true` if both handles refer to the same future.
+    pub(crate) fn same_as(&self, other: &AtlasFuture) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
     /// Get the current state (cloned).
     pub fn get_state(&self) -> FutureState {
         let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
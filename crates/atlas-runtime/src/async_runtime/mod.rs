@@ -7,8 +7,11 @@
 //! - Channels for message passing
 //! - Async primitives (sleep, timers, mutex, timeout)
 //!
-//! The async runtime enables non-blocking I/O operations without requiring
-//! language-level async/await syntax (reserved for future versions).
+//! Language-level `await` on a pending future inside an `async fn` suspends
+//! that function's frame; the VM resumes it once the future settles, running
+//! its other suspended functions in the meantime. Only when nothing is left to
+//! run does the VM thread park until the task, timer or worker that owns one
+//! of the awaited futures settles it.
 
 pub mod channel;
 pub mod future;
@@ -24,7 +27,9 @@ pub use future::{future_all, future_race, AtlasFuture, FutureState};
 pub use primitives::{interval, retry_with_timeout, sleep, timeout, timer, AsyncMutex};
 pub use task::spawn_blocking_task;
 pub use task::{join_all, spawn_and_await, spawn_task, TaskHandle, TaskStatus};
-pub use worker::{init_worker_pool, worker_pool, Worker, WorkerPool, WorkerTask};
+pub use worker::{
+    ensure_worker_pool, init_worker_pool, worker_pool, Worker, WorkerPool, WorkerTask,
+};
 
 use std::sync::OnceLock;
 use tokio::runtime::Runtime;
@@ -154,3 +159,99 @@ where
     let local_set = LocalSet::new();
     runtime().block_on(local_set.run_until(future))
 }
+
+/// Wait for a future to settle, parking the calling thread until it does.
+///
+/// The future is settled elsewhere (a worker task, the blocking pool, a timer
+/// on the global runtime) and its waker unparks this thread. Used by code that
+/// has no frame to suspend, such as the `await_future` builtin.
+///
+/// Errors instead of waiting when nothing can ever settle the future, or when
+/// this thread drives a `current_thread` runtime whose own tasks would be
+/// starved by blocking.
+pub fn wait_for(
+    future: &AtlasFuture,
+) -> Result<Result<crate::value::Value, crate::value::Value>, String> {
+    if let Some(result) = settled(future) {
+        return Ok(result);
+    }
+    if future.is_orphaned() {
+        // The last other handle may have settled the future and been dropped
+        // after the state was read; settling happens before the drop.
+        return settled(future).ok_or_else(|| {
+            "future can never settle: nothing holds a handle to resolve it".to_string()
+        });
+    }
+    ensure_may_block()?;
+
+    let waker = std::task::Waker::from(std::sync::Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = std::task::Context::from_waker(&waker);
+    let mut pending = future.clone();
+    loop {
+        match std::future::Future::poll(std::pin::Pin::new(&mut pending), &mut cx) {
+            std::task::Poll::Ready(result) => return Ok(result),
+            std::task::Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+/// Park the calling thread until at least one of `futures` has settled
+///
+/// The VM's scheduler calls this when every suspended `async fn` is waiting:
+/// whichever future settles first decides which one runs next.
+pub(crate) fn wait_for_any(futures: &[AtlasFuture]) -> Result<(), String> {
+    if futures.is_empty() || futures.iter().any(|f| settled(f).is_some()) {
+        return Ok(());
+    }
+    ensure_may_block()?;
+
+    let waker = std::task::Waker::from(std::sync::Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = std::task::Context::from_waker(&waker);
+    for future in futures {
+        let mut pending = future.clone();
+        if std::future::Future::poll(std::pin::Pin::new(&mut pending), &mut cx).is_ready() {
+            return Ok(());
+        }
+    }
+    // Every future now holds the waker until it settles; unparks without a
+    // settled future are spurious
+    while !futures.iter().any(|f| settled(f).is_some()) {
+        std::thread::park();
+    }
+    Ok(())
+}
+
+/// Refuse to park a thread that drives a `current_thread` runtime, whose own
+/// tasks would be starved by blocking
+fn ensure_may_block() -> Result<(), String> {
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::CurrentThread {
+            return Err("cannot block on a pending future from inside a worker task".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// The outcome of `future`, or `None` while it is still pending
+pub(crate) fn settled(
+    future: &AtlasFuture,
+) -> Option<Result<crate::value::Value, crate::value::Value>> {
+    match future.get_state() {
+        FutureState::Resolved(v) => Some(Ok(v)),
+        FutureState::Rejected(e) => Some(Err(e)),
+        FutureState::Pending => None,
+    }
+}
+
+/// Waker that unparks the thread blocked in [`wait_for`] or [`wait_for_any`]
+struct ThreadWaker(std::thread::Thread);
+
+impl std::task::Wake for ThreadWaker {
+    fn wake(self: std::sync::Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &std::sync::Arc<Self>) {
+        self.0.unpark();
+    }
+}
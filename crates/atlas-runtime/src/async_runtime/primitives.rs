@@ -9,8 +9,8 @@
 //!
 //! - **Inside Tokio:** `sleep`/`timeout` spawn a background task that resolves
 //!   the returned `AtlasFuture` after the delay; the caller is not blocked.
-//! - **Outside Tokio:** `sleep`/`timeout` drive the timer synchronously via
-//!   `std::thread::sleep`, so the returned future is always already resolved.
+//! - **Outside Tokio:** `sleep` drives its timer on the global runtime and
+//!   `timeout` drives the wrapped operation synchronously.
 
 use crate::async_runtime::AtlasFuture;
 use crate::value::Value;
@@ -26,26 +26,22 @@ use tokio::sync::Mutex as TokioMutex;
 ///
 /// Returns an `AtlasFuture` that resolves to `null` after `milliseconds` ms.
 ///
-/// - **Inside a Tokio async context:** a background task is spawned to drive
-///   the timer; the returned future resolves asynchronously without blocking
-///   the calling thread.
-/// - **Outside a Tokio context:** the current thread sleeps synchronously and
-///   the returned future is already resolved when this function returns.
+/// A background task drives the timer — on the current Tokio runtime when
+/// there is one, otherwise on the global runtime — so the calling thread is
+/// never blocked and an `async fn` awaiting the future can be suspended.
 pub fn sleep(milliseconds: u64) -> AtlasFuture {
     let dur = Duration::from_millis(milliseconds);
     let fut = AtlasFuture::new_pending();
     let resolver = fut.clone();
 
+    let timer = async move {
+        tokio::time::sleep(dur).await;
+        resolver.resolve(Value::Null);
+    };
     if let Ok(handle) = tokio::runtime::Handle::try_current() {
-        // Inside Tokio — spawn a background task so we don't block the executor.
-        handle.spawn(async move {
-            tokio::time::sleep(dur).await;
-            resolver.resolve(Value::Null);
-        });
+        handle.spawn(timer);
     } else {
-        // Outside Tokio — simple thread sleep is fine.
-        std::thread::sleep(dur);
-        resolver.resolve(Value::Null);
+        crate::async_runtime::runtime().spawn(timer);
     }
 
     fut
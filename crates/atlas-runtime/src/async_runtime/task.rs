@@ -66,6 +66,19 @@ pub(crate) struct TaskState {
     status: StdMutex<TaskStatus>,
     cancelled: AtomicBool,
     result: StdMutex<Option<Result<Value, String>>>,
+    /// Settled together with `result`, so `await`ing a join wakes on completion
+    completion: AtlasFuture,
+}
+
+impl TaskState {
+    /// Record the task's outcome and settle its completion future.
+    fn set_result(&self, result: Result<Value, String>) {
+        match &result {
+            Ok(value) => self.completion.resolve(value.clone()),
+            Err(error) => self.completion.reject(Value::string(error.clone())),
+        }
+        *self.result.lock().unwrap_or_else(|e| e.into_inner()) = Some(result);
+    }
 }
 
 // ── TaskHandle ───────────────────────────────────────────────────────────────
@@ -91,6 +104,7 @@ impl TaskHandle {
                 status: StdMutex::new(TaskStatus::Running),
                 cancelled: AtomicBool::new(false),
                 result: StdMutex::new(None),
+                completion: AtlasFuture::new_pending(),
             }),
             _marker: std::marker::PhantomData,
         }
@@ -138,6 +152,9 @@ impl TaskHandle {
         let mut status = self.state.status.lock().unwrap_or_else(|e| e.into_inner());
         if *status == TaskStatus::Running {
             *status = TaskStatus::Cancelled;
+            self.state
+                .completion
+                .reject(Value::string("task cancelled"));
         }
     }
 
@@ -146,10 +163,10 @@ impl TaskHandle {
         self.state.cancelled.load(Ordering::SeqCst)
     }
 
-    /// Non-blocking result check.
+    /// Future for the task's result.
     ///
-    /// Returns a resolved `AtlasFuture` if done, or a pending future if the
-    /// task is still running.  For a blocking wait, use [`spawn_and_await`].
+    /// Returns a settled `AtlasFuture` if done; otherwise a pending future that
+    /// settles when the task finishes, so `await` can wait on it.
     pub fn join(&self) -> AtlasFuture {
         let result = self
             .state
@@ -160,7 +177,7 @@ impl TaskHandle {
         match result {
             Some(Ok(value)) => AtlasFuture::resolved(value),
             Some(Err(error)) => AtlasFuture::rejected(Value::string(error)),
-            None => AtlasFuture::new_pending(),
+            None => self.state.completion.clone(),
         }
     }
 
@@ -279,10 +296,7 @@ where
                         .unwrap_or_else(|e| e.into_inner());
                     if *status == TaskStatus::Running {
                         *status = TaskStatus::Completed;
-                        *state_for_task
-                            .result
-                            .lock()
-                            .unwrap_or_else(|e| e.into_inner()) = Some(Ok(value));
+                        state_for_task.set_result(Ok(value));
                     }
                 }
                 Err(panic_payload) => {
@@ -299,10 +313,7 @@ where
                         .unwrap_or_else(|e| e.into_inner());
                     if *status == TaskStatus::Running {
                         *status = TaskStatus::Failed;
-                        *state_for_task
-                            .result
-                            .lock()
-                            .unwrap_or_else(|e| e.into_inner()) = Some(Err(msg));
+                        state_for_task.set_result(Err(msg));
                     }
                 }
             }
@@ -316,8 +327,7 @@ where
                 let mut status = state.status.lock().unwrap_or_else(|e| e.into_inner());
                 if *status == TaskStatus::Running {
                     *status = TaskStatus::Failed;
-                    *state.result.lock().unwrap_or_else(|e| e.into_inner()) =
-                        Some(Err("worker pool channel full — task dropped".to_string()));
+                    state.set_result(Err("worker pool channel full — task dropped".to_string()));
                 }
             }
         }
@@ -327,7 +337,7 @@ where
             let mut status = state.status.lock().unwrap_or_else(|e| e.into_inner());
             if *status == TaskStatus::Running {
                 *status = TaskStatus::Failed;
-                *state.result.lock().unwrap_or_else(|e| e.into_inner()) = Some(Err(
+                state.set_result(Err(
                     "worker pool not initialised — call init_worker_pool() at startup".to_string(),
                 ));
             }
@@ -419,26 +429,17 @@ pub fn spawn_function_task(
             match result {
                 Some(Ok(value)) => {
                     *status = TaskStatus::Completed;
-                    *state_bridge
-                        .result
-                        .lock()
-                        .unwrap_or_else(|e| e.into_inner()) = Some(Ok(value));
+                    state_bridge.set_result(Ok(value));
                 }
                 Some(Err(msg)) => {
                     *status = TaskStatus::Failed;
-                    *state_bridge
-                        .result
-                        .lock()
-                        .unwrap_or_else(|e| e.into_inner()) = Some(Err(msg));
+                    state_bridge.set_result(Err(msg));
                 }
                 None => {
                     // Channel closed without sending — worker shut down.
                     *status = TaskStatus::Failed;
-                    *state_bridge
-                        .result
-                        .lock()
-                        .unwrap_or_else(|e| e.into_inner()) =
-                        Some(Err("worker shut down before task completed".to_string()));
+                    state_bridge
+                        .set_result(Err("worker shut down before task completed".to_string()));
                 }
             }
         }
@@ -451,8 +452,8 @@ pub fn spawn_function_task(
                 let mut status = state.status.lock().unwrap_or_else(|e| e.into_inner());
                 if *status == TaskStatus::Running {
                     *status = TaskStatus::Failed;
-                    *state.result.lock().unwrap_or_else(|e| e.into_inner()) = Some(Err(
-                        "worker pool channel full — function task dropped".to_string(),
+                    state.set_result(Err(
+                        "worker pool channel full — function task dropped".to_string()
                     ));
                 }
             }
@@ -461,7 +462,7 @@ pub fn spawn_function_task(
             let mut status = state.status.lock().unwrap_or_else(|e| e.into_inner());
             if *status == TaskStatus::Running {
                 *status = TaskStatus::Failed;
-                *state.result.lock().unwrap_or_else(|e| e.into_inner()) = Some(Err(
+                state.set_result(Err(
                     "worker pool not initialised — call init_worker_pool() at startup".to_string(),
                 ));
             }
@@ -506,7 +507,7 @@ pub fn spawn_blocking_task(
         None => {
             let mut status = state.status.lock().unwrap_or_else(|e| e.into_inner());
             *status = TaskStatus::Failed;
-            *state.result.lock().unwrap_or_else(|e| e.into_inner()) = Some(Err(
+            state.set_result(Err(
                 "blocking pool not initialised — call init_blocking_pool() at startup".to_string(),
             ));
             return handle;
@@ -529,16 +530,15 @@ pub fn spawn_blocking_task(
             match task_result {
                 Ok(Ok(value)) => {
                     *status = TaskStatus::Completed;
-                    *state_clone.result.lock().unwrap_or_else(|e| e.into_inner()) = Some(Ok(value));
+                    state_clone.set_result(Ok(value));
                 }
                 Ok(Err(msg)) => {
                     *status = TaskStatus::Failed;
-                    *state_clone.result.lock().unwrap_or_else(|e| e.into_inner()) = Some(Err(msg));
+                    state_clone.set_result(Err(msg));
                 }
                 Err(join_err) => {
                     *status = TaskStatus::Failed;
-                    *state_clone.result.lock().unwrap_or_else(|e| e.into_inner()) =
-                        Some(Err(format!("blocking task panicked: {join_err}")));
+                    state_clone.set_result(Err(format!("blocking task panicked: {join_err}")));
                 }
             }
         }
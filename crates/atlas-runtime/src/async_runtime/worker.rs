@@ -237,6 +237,20 @@ pub fn init_worker_pool(n: usize, base_vm: &VM) {
         .expect("init_worker_pool called more than once");
 }
 
+/// Initialize the global worker pool unless it already exists.
+///
+/// Unlike [`init_worker_pool`] this may race: runtimes starting on several
+/// threads at once create exactly one pool. Returns `true` for the caller
+/// whose `base_vm` seeded it.
+pub fn ensure_worker_pool(n: usize, base_vm: &VM) -> bool {
+    let mut created = false;
+    WORKER_POOL.get_or_init(|| {
+        created = true;
+        WorkerPool::new(n, base_vm)
+    });
+    created
+}
+
 /// Access the global worker pool.
 ///
 /// Returns `None` before [`init_worker_pool`] is called.
@@ -8,6 +8,7 @@ use crate::ast::*;
 use crate::diagnostic::error_codes;
 use crate::diagnostic::Diagnostic;
 use crate::module_loader::ModuleRegistry;
+use crate::profile::{LanguageProfile, Production};
 use crate::span::Span;
 use crate::symbol::{Symbol, SymbolKind, SymbolTable};
 use crate::types::{StructuralMemberType, Type, TypeParamDef};
@@ -37,6 +38,8 @@ pub struct Binder {
     /// Populated before binding so that bare package names (e.g. "web") resolve to
     /// the correct registry key (e.g. /path/to/web/lib.atlas) rather than a naive local path.
     resolved_imports: HashMap<String, PathBuf>,
+    /// Grammar and builtin restrictions set by the embedder (None = full language)
+    profile: Option<LanguageProfile>,
 }
 
 impl Binder {
@@ -51,6 +54,7 @@ impl Binder {
             enum_names: HashSet::new(),
             enum_variants: HashMap::new(),
             resolved_imports: HashMap::new(),
+            profile: None,
         }
     }
 
@@ -65,6 +69,7 @@ impl Binder {
             enum_names: HashSet::new(),
             enum_variants: HashMap::new(),
             resolved_imports: HashMap::new(),
+            profile: None,
         }
     }
 
@@ -74,6 +79,90 @@ impl Binder {
         self.resolved_imports = map;
     }
 
+    /// Report constructs and builtins that `profile` excludes (AT1025)
+    pub fn set_language_profile(&mut self, profile: LanguageProfile) {
+        self.profile = (!profile.is_unrestricted()).then_some(profile);
+    }
+
+    fn check_production(&mut self, production: Production, span: Span) {
+        if self.profile.as_ref().is_some_and(|p| !p.allows(production)) {
+            self.diagnostics.push(
+                error_codes::NOT_IN_LANGUAGE_PROFILE
+                    .emit(span)
+                    .arg("what", production.describe())
+                    .build()
+                    .with_label("excluded by the language profile"),
+            );
+        }
+    }
+
+    fn check_item_production(&mut self, item: &Item) {
+        let (production, span) = match item {
+            Item::Function(func) => (Production::Functions, func.span),
+            Item::Statement(_) => return, // checked statement by statement
+            Item::Import(decl) => (Production::Modules, decl.span),
+            Item::Export(decl) => (Production::Modules, decl.span),
+            Item::Extern(decl) => (Production::Extern, decl.span),
+            Item::Const(decl) => (Production::Variables, decl.span),
+            Item::TypeAlias(decl) => (Production::TypeDeclarations, decl.span),
+            Item::Trait(decl) => (Production::TypeDeclarations, decl.span),
+            Item::Impl(block) => (Production::TypeDeclarations, block.span),
+            Item::Struct(decl) => (Production::TypeDeclarations, decl.span),
+            Item::Enum(decl) => (Production::TypeDeclarations, decl.span),
+        };
+        self.check_production(production, span);
+    }
+
+    /// Walk trait and impl method bodies for builtins the profile hides.
+    ///
+    /// The binder otherwise leaves these bodies to the typechecker, so every
+    /// other diagnostic from the walk is dropped.
+    fn check_method_builtins<'a>(
+        &mut self,
+        methods: impl Iterator<Item = (&'a [Param], &'a Block)>,
+    ) {
+        if self.profile.is_none() {
+            return;
+        }
+        let before = self.diagnostics.len();
+        for (params, body) in methods {
+            self.symbol_table.enter_scope();
+            for param in params {
+                let _ = self.symbol_table.define(Symbol {
+                    name: param.name.name.clone(),
+                    ty: Type::Unknown,
+                    mutable: false,
+                    kind: SymbolKind::Parameter,
+                    span: param.name.span,
+                    exported: false,
+                    visibility: Visibility::Private,
+                });
+            }
+            self.bind_block(body);
+            self.symbol_table.exit_scope();
+        }
+        let walked = self.diagnostics.split_off(before);
+        self.diagnostics.extend(
+            walked
+                .into_iter()
+                .filter(|d| d.code == error_codes::NOT_IN_LANGUAGE_PROFILE.code),
+        );
+    }
+
+    fn check_trait_impl_builtins(&mut self, item: &Item) {
+        match item {
+            Item::Trait(decl) => self.check_method_builtins(
+                decl.methods
+                    .iter()
+                    .filter_map(|m| m.body.as_ref().map(|body| (m.params.as_slice(), body))),
+            ),
+            Item::Impl(block) => self.check_method_builtins(
+                block.methods.iter().map(|m| (m.params.as_slice(), &m.body)),
+            ),
+            _ => {}
+        }
+    }
+
     /// Bind a program (two-pass: hoist functions, then bind everything)
     pub fn bind(&mut self, program: &Program) -> (SymbolTable, Vec<Diagnostic>) {
         // Phase 0a: Collect struct declarations (so named struct types resolve in signatures)
@@ -207,6 +296,30 @@ impl Binder {
                             span: *span,
                         };
                         self.bind_import(&synthetic, module_path, registry);
+
+                        // `export { a as b } from "..."` exports the imported `a` as `b`
+                        for spec in names {
+                            let Some(alias) = &spec.alias else {
+                                continue;
+                            };
+                            let Some(mut aliased) =
+                                self.symbol_table.lookup(&spec.name.name).cloned()
+                            else {
+                                continue;
+                            };
+                            aliased.name = alias.name.clone();
+                            aliased.span = alias.span;
+                            if let Err(err) = self.symbol_table.define(aliased) {
+                                let (msg, _) = *err;
+                                self.diagnostics.push(
+                                    error_codes::DUPLICATE_DECLARATION
+                                        .emit(alias.span)
+                                        .arg("detail", &msg)
+                                        .build()
+                                        .with_label("re-export alias"),
+                                );
+                            }
+                        }
                     }
                 }
                 _ => {}
@@ -571,6 +684,7 @@ impl Binder {
 
     /// Bind a top-level item
     fn bind_item(&mut self, item: &Item) {
+        self.check_item_production(item);
         match item {
             Item::Function(func) => self.bind_function(func),
             Item::Statement(stmt) => self.bind_statement(stmt),
@@ -612,6 +726,7 @@ impl Binder {
             }
             Item::Trait(_) | Item::Impl(_) => {
                 // Trait/impl binding handled in Block 3 (trait system)
+                self.check_trait_impl_builtins(item);
             }
             Item::Struct(_) | Item::Enum(_) => {
                 // Struct/enum type declarations are handled by the type system
@@ -627,6 +742,7 @@ impl Binder {
         _module_path: &Path,
         _registry: &ModuleRegistry,
     ) {
+        self.check_item_production(item);
         match item {
             Item::Function(func) => self.bind_function(func),
             Item::Statement(stmt) => self.bind_statement(stmt),
@@ -667,6 +783,7 @@ impl Binder {
             }
             Item::Trait(_) | Item::Impl(_) => {
                 // Trait/impl binding handled in Block 3 (trait system)
+                self.check_trait_impl_builtins(item);
             }
             Item::Struct(_) | Item::Enum(_) => {
                 // Struct/enum type declarations are handled by the type system
@@ -1272,6 +1389,17 @@ impl Binder {
 
     /// Bind a statement
     fn bind_statement(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::VarDecl(_)
+            | Stmt::LetDestructure(_)
+            | Stmt::Assign(_)
+            | Stmt::CompoundAssign(_) => self.check_production(Production::Variables, stmt.span()),
+            Stmt::While(_) | Stmt::ForIn(_) | Stmt::Break(_) | Stmt::Continue(_) => {
+                self.check_production(Production::Loops, stmt.span())
+            }
+            Stmt::FunctionDecl(_) => self.check_production(Production::Functions, stmt.span()),
+            _ => {}
+        }
         match stmt {
             Stmt::VarDecl(var) => {
                 // Check for global shadowing of prelude builtins
@@ -1453,6 +1581,27 @@ impl Binder {
                 // Bind the deferred block - it executes in the same scope
                 self.bind_block(&defer.body);
             }
+            Stmt::TryCatch(try_catch) => {
+                self.bind_block(&try_catch.body);
+
+                // The error binding lives in its own scope around the catch block
+                self.symbol_table.enter_scope();
+                if let Some(binding) = &try_catch.error_binding {
+                    let symbol = Symbol {
+                        name: binding.name.clone(),
+                        ty: crate::value::RuntimeError::record_type(),
+                        mutable: false,
+                        kind: SymbolKind::Variable,
+                        span: binding.span,
+                        exported: false,
+                        visibility: Visibility::Private,
+                    };
+                    // A fresh scope holds nothing else, so this cannot collide
+                    let _ = self.symbol_table.define(symbol);
+                }
+                self.bind_block(&try_catch.catch_block);
+                self.symbol_table.exit_scope();
+            }
         }
     }
 
@@ -1505,15 +1654,32 @@ impl Binder {
             Expr::Identifier(id) => {
                 // H-305: Check symbol table FIRST to allow user variables to shadow deprecated
                 // bare global names. A user declaring `let log = ...` should be valid.
-                let is_defined = self.symbol_table.lookup(&id.name).is_some()
-                    || crate::stdlib::is_array_intrinsic(&id.name)
-                    || crate::method_dispatch::is_static_namespace(&id.name)
-                    || crate::method_dispatch::is_allowed_bare_global(&id.name)
+                let is_user_defined = self.symbol_table.lookup(&id.name).is_some()
                     || self.enum_variants.contains_key(&id.name)
                     // B39-P02: struct type names are valid for static method calls (Type.method())
                     || self.struct_decls.contains_key(&id.name);
+                let is_builtin = crate::stdlib::is_array_intrinsic(&id.name)
+                    || crate::method_dispatch::is_static_namespace(&id.name)
+                    || crate::method_dispatch::is_allowed_bare_global(&id.name);
+
+                if !is_user_defined
+                    && is_builtin
+                    && self
+                        .profile
+                        .as_ref()
+                        .is_some_and(|p| !p.is_builtin_visible(&id.name))
+                {
+                    self.diagnostics.push(
+                        error_codes::NOT_IN_LANGUAGE_PROFILE
+                            .emit(id.span)
+                            .arg("what", format!("`{}`", id.name))
+                            .build()
+                            .with_label("hidden by the language profile"),
+                    );
+                    return;
+                }
 
-                if is_defined {
+                if is_user_defined || is_builtin {
                     // Identifier is valid — nothing to do
                     return;
                 }
@@ -1667,7 +1833,10 @@ impl Binder {
                 // Bind the expression being tried
                 self.bind_expr(&try_expr.expr);
             }
-            Expr::AnonFn { params, body, .. } => {
+            Expr::AnonFn {
+                params, body, span, ..
+            } => {
+                self.check_production(Production::Closures, *span);
                 // Enter a new scope for the anonymous function body
                 self.symbol_table.enter_scope();
                 // Define params as symbols (they are definitions, not references)
@@ -1732,7 +1901,7 @@ impl Binder {
         let mut vars = Vec::new();
 
         match pattern {
-            Pattern::Literal(_, _) | Pattern::Wildcard(_) => {
+            Pattern::Literal(_, _) | Pattern::Range { .. } | Pattern::Wildcard(_) => {
                 // No variables
             }
             Pattern::Variable(id) => {
@@ -1744,11 +1913,14 @@ impl Binder {
                     vars.extend(self.collect_pattern_variables(arg));
                 }
             }
-            Pattern::Array { elements, .. } => {
-                // Collect from all element patterns
+            Pattern::Array { elements, rest, .. } => {
+                // Collect from all element patterns, then the rest binding
                 for elem in elements {
                     vars.extend(self.collect_pattern_variables(elem));
                 }
+                if let Some(rest) = rest {
+                    vars.extend(self.collect_pattern_variables(rest));
+                }
             }
             Pattern::Tuple { elements, .. } => {
                 // Collect from all tuple element patterns
@@ -1812,6 +1984,7 @@ impl Binder {
                 "null" => Type::Null,
                 "any" => Type::any_placeholder(),
                 "json" => Type::JsonValue,
+                "range" => Type::Range,
                 "array" => Type::Array(Box::new(Type::any_placeholder())),
                 "Comparable" | "Numeric" => Type::Number,
                 "Iterable" => Type::Array(Box::new(Type::any_placeholder())),
@@ -0,0 +1,126 @@
+//! On-disk cache of compiled modules
+//!
+//! Installed packages never change under a given version, so recompiling them
+//! on every run is wasted work. [`BytecodeCache`] keeps each module's compiled
+//! bytecode in a `.atbc` file named by a key over the compiler version, the
+//! module's path and source, and the keys of the modules it imports: editing
+//! a file (or upgrading Atlas) produces a new key, and the stale entry is
+//! simply never read again.
+
+use super::Bytecode;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Identifies the compiler that produced a cache entry. Part of every key, so
+/// entries written by another Atlas release are ignored.
+pub const COMPILER_VERSION: &str = concat!("atlas-", env!("CARGO_PKG_VERSION"));
+
+/// Compiled-module cache rooted at one directory
+#[derive(Debug)]
+pub struct BytecodeCache {
+    /// Where entries are written
+    dir: PathBuf,
+    /// Only modules under this directory are cached
+    scope: PathBuf,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl BytecodeCache {
+    /// Cache modules under `scope` in `dir`
+    pub fn new(dir: impl Into<PathBuf>, scope: impl Into<PathBuf>) -> Self {
+        let scope = scope.into();
+        Self {
+            dir: dir.into(),
+            scope: scope.canonicalize().unwrap_or(scope),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// Cache for installed packages, kept in the package cache itself
+    /// (`<package cache>/.bytecode`). The project's own modules are always
+    /// compiled from source.
+    pub fn for_packages() -> Self {
+        let root = crate::resolver::package_cache_root();
+        Self::new(root.join(".bytecode"), root)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Whether the module at `path` is cached at all
+    pub fn covers(&self, path: &Path) -> bool {
+        path.starts_with(&self.scope)
+    }
+
+    /// Key of a module with the given source whose imports have the given keys
+    pub fn key(path: &Path, source: &str, dependency_keys: &[&str]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(COMPILER_VERSION.as_bytes());
+        hasher.update(super::BYTECODE_VERSION.to_be_bytes());
+        // Spans in the bytecode name the file, so the path is part of the key
+        for part in [path.to_string_lossy().as_bytes(), source.as_bytes()] {
+            hasher.update((part.len() as u64).to_be_bytes());
+            hasher.update(part);
+        }
+        let mut dependency_keys = dependency_keys.to_vec();
+        dependency_keys.sort_unstable();
+        for key in dependency_keys {
+            hasher.update(key.as_bytes());
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    /// The entry stored under `key`, if there is a valid one
+    pub fn load(&self, key: &str) -> Option<Bytecode> {
+        match Bytecode::read_from_file(&self.entry_path(key)) {
+            Ok(bytecode) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(bytecode)
+            }
+            Err(_) => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Store `bytecode` under `key`. The entry is written to a temporary file
+    /// and renamed into place, so a concurrent reader never sees half of it.
+    pub fn store(&self, key: &str, bytecode: &Bytecode) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.entry_path(key);
+        let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+        bytecode.write_to_file(&tmp)?;
+        std::fs::rename(&tmp, &path).inspect_err(|_| {
+            let _ = std::fs::remove_file(&tmp);
+        })
+    }
+
+    /// Remove every entry
+    pub fn clear(&self) -> std::io::Result<()> {
+        match std::fs::remove_dir_all(&self.dir) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Modules loaded from the cache so far
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Cached-scope modules that had to be compiled so far
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir
+            .join(key)
+            .with_extension(super::BYTECODE_FILE_EXTENSION)
+    }
+}
@@ -14,10 +14,13 @@ use std::fmt::Write;
 /// 0: 42.0
 /// 1: "hello"
 ///
+/// === Globals ===
+/// 0: total
+///
 /// === Instructions ===
 /// 0000  Constant 0
-/// 0003  Add
-/// 0004  Halt
+/// 0003  SetGlobal 0 (total)
+/// 0006  Halt
 /// ```
 pub fn disassemble(bytecode: &Bytecode) -> String {
     let mut output = String::new();
@@ -31,6 +34,15 @@ pub fn disassemble(bytecode: &Bytecode) -> String {
         writeln!(output).unwrap();
     }
 
+    // Global name table
+    if !bytecode.global_names.is_empty() {
+        writeln!(output, "=== Globals ===").unwrap();
+        for (idx, name) in bytecode.global_names.iter().enumerate() {
+            writeln!(output, "{}: {}", idx, name).unwrap();
+        }
+        writeln!(output).unwrap();
+    }
+
     // Instructions section
     writeln!(output, "=== Instructions ===").unwrap();
     let mut offset = 0;
@@ -108,19 +120,34 @@ fn disassemble_instruction(bytecode: &Bytecode, offset: &mut usize) -> String {
         | Opcode::CheckEnumVariant
         | Opcode::ExtractEnumData
         | Opcode::IsStruct
+        | Opcode::ForInPrepare
+        | Opcode::IsNullish
+        | Opcode::Contains
         | Opcode::Await
         | Opcode::WrapFuture
         | Opcode::DeferExec
+        | Opcode::TryEnd
+        | Opcode::PushError
         | Opcode::Halt => {
             format!("{:04}  {:?}", start_offset, opcode)
         }
 
-        // u16 operands (constants, locals, globals, upvalues)
+        // Global slot, annotated with its name
+        Opcode::GetGlobal | Opcode::SetGlobal => {
+            let slot = read_u16(bytecode, offset);
+            match bytecode.global_names.get(slot as usize) {
+                Some(name) => format!("{:04}  {:?} {} ({})", start_offset, opcode, slot, name),
+                None => format!(
+                    "{:04}  {:?} {} <unknown global>",
+                    start_offset, opcode, slot
+                ),
+            }
+        }
+
+        // u16 operands (constants, locals, upvalues)
         Opcode::Constant
         | Opcode::GetLocal
         | Opcode::SetLocal
-        | Opcode::GetGlobal
-        | Opcode::SetGlobal
         | Opcode::GetUpvalue
         | Opcode::SetUpvalue
         | Opcode::Array
@@ -181,7 +208,7 @@ fn disassemble_instruction(bytecode: &Bytecode, offset: &mut usize) -> String {
         }
 
         // i16 operands (jumps)
-        Opcode::Jump | Opcode::JumpIfFalse | Opcode::Loop => {
+        Opcode::Jump | Opcode::JumpIfFalse | Opcode::Loop | Opcode::TryBegin => {
             let jump_offset = read_i16(bytecode, offset);
             let target = (*offset as i32 + jump_offset as i32) as usize;
             format!(
@@ -224,14 +251,7 @@ fn format_value(value: &crate::value::Value) -> String {
     match value {
         Value::Null => "null".to_string(),
         Value::Bool(b) => b.to_string(),
-        Value::Number(n) => {
-            // Show integers without decimal point
-            if n.fract() == 0.0 && n.is_finite() {
-                format!("{:.0}", n)
-            } else {
-                n.to_string()
-            }
-        }
+        Value::Number(n) => crate::value::format_number(*n),
         Value::String(s) => format!("\"{}\"", s),
         Value::Function(f) => format!("<fn {}({})>", f.name, f.arity),
         Value::Builtin(name) => format!("<builtin {}>", name),
@@ -245,9 +265,13 @@ fn format_value(value: &crate::value::Value) -> String {
         Value::Set(_) => "<hashset>".to_string(),
         Value::Queue(_) => "<queue>".to_string(),
         Value::Stack(_) => "<stack>".to_string(),
+        #[cfg(feature = "regex")]
         Value::Regex(r) => format!("<regex /{}/>", r.as_str()),
         Value::DateTime(dt) => format!("<datetime {}>", dt.to_rfc3339()),
+        Value::Bytes(b) => format!("<bytes len={}>", b.len()),
+        #[cfg(feature = "http")]
         Value::HttpRequest(req) => format!("<HttpRequest {} {}>", req.method(), req.url()),
+        #[cfg(feature = "http")]
         Value::HttpResponse(res) => format!("<HttpResponse {}>", res.status()),
         #[cfg(feature = "http")]
         Value::HttpServerRequest(req) => {
@@ -258,6 +282,15 @@ fn format_value(value: &crate::value::Value) -> String {
             let status = if c.is_closed() { "closed" } else { "open" };
             format!("<SqliteConnection {}>", status)
         }
+        Value::KvStore(k) => {
+            let status = if k.is_closed() { "closed" } else { "open" };
+            format!("<KvStore {}>", status)
+        }
+        Value::FileHandle(h) => {
+            let status = if h.is_closed() { "closed" } else { "open" };
+            format!("<FileHandle {} {}>", h.path(), status)
+        }
+        Value::ProgressBar(b) => format!("<{:?}>", b),
         Value::Future(f) => format!("<{}>", f.as_ref()),
         Value::TaskHandle(h) => {
             let id = h.lock().map(|h| h.id()).unwrap_or(0);
@@ -266,7 +299,9 @@ fn format_value(value: &crate::value::Value) -> String {
         Value::ChannelSender(_) => "<ChannelSender>".to_string(),
         Value::ChannelReceiver(_) => "<ChannelReceiver>".to_string(),
         Value::AsyncMutex(_) => "<AsyncMutex>".to_string(),
+        #[cfg(feature = "fs")]
         Value::Watcher(_) => "<Watcher>".to_string(),
+        Value::Resource(r) => r.to_string(),
         Value::Closure(c) => format!("<fn {}>", c.func.name),
         Value::SharedValue(_) => "<shared>".to_string(),
         Value::Tuple(elems) => format!("<tuple({} elements)>", elems.len()),
@@ -3,12 +3,14 @@
 //! Stack-based bytecode with 37 opcodes organized by category.
 //! Operands are encoded separately in the instruction stream.
 
+mod cache;
 mod disasm;
 mod opcode;
 mod optimizer;
 mod serialize;
 pub mod validator;
 
+pub use cache::{BytecodeCache, COMPILER_VERSION};
 pub use disasm::disassemble;
 pub use opcode::Opcode;
 pub use optimizer::{
@@ -16,12 +18,14 @@ pub use optimizer::{
     PeepholePass,
 };
 use serialize::{
-    compute_checksum, deserialize_span, deserialize_value, serialize_span, serialize_value,
+    compute_checksum, deserialize_local_debug_info, deserialize_span, deserialize_value,
+    serialize_local_debug_info, serialize_span, serialize_value,
 };
 pub use validator::{validate, ValidationError, ValidationErrorKind};
 
 use crate::span::Span;
 use crate::value::Value;
+use std::path::Path;
 
 /// Current bytecode format version
 ///
@@ -33,7 +37,14 @@ use crate::value::Value;
 /// - Version 1: Initial bytecode format (Phase 10)
 /// - Version 2: Added CRC32 checksum, extended value serialization (H-002)
 /// - Version 3: Added source file paths to debug spans (H-044)
-pub const BYTECODE_VERSION: u16 = 4; // H-288: added top_level_local_count serialization
+/// - Version 4: Added top_level_local_count serialization (H-288)
+/// - Version 5: Added statement start offsets to the debug info section
+/// - Version 6: Added local variable names to the debug info section
+/// - Version 7: Added the global name table; `GetGlobal`/`SetGlobal` index it
+pub const BYTECODE_VERSION: u16 = 7;
+
+/// File extension of serialized bytecode (`main.atbc`)
+pub const BYTECODE_FILE_EXTENSION: &str = "atbc";
 
 /// Debug information for bytecode
 ///
@@ -46,6 +57,21 @@ pub struct DebugSpan {
     pub span: Span,
 }
 
+/// Debug record naming a local slot (or captured upvalue) over a range of instructions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalDebugInfo {
+    /// Source-level variable name
+    pub name: String,
+    /// Frame-relative local slot, or upvalue index when `captured` is set
+    pub slot: usize,
+    /// First instruction offset at which the name is in scope
+    pub start_offset: usize,
+    /// Instruction offset at which the name goes out of scope (exclusive)
+    pub end_offset: usize,
+    /// Whether this is a closure upvalue captured from an enclosing function
+    pub captured: bool,
+}
+
 /// Bytecode container
 ///
 /// Contains raw instruction bytes, constant pool, and debug information.
@@ -58,8 +84,20 @@ pub struct Bytecode {
     pub instructions: Vec<u8>,
     /// Constant pool (referenced by index)
     pub constants: Vec<Value>,
+    /// Interned global variable names. `GetGlobal`/`SetGlobal` operands index
+    /// this table, and the VM keeps one value slot per entry.
+    pub global_names: Vec<String>,
     /// Debug information (instruction offset -> source span)
     pub debug_info: Vec<DebugSpan>,
+    /// Instruction offsets at which a source statement begins, in ascending order.
+    /// Used by the debugger to step one statement at a time. The optimizer moves
+    /// each start along with its instruction, so optimized bytecode keeps them;
+    /// with an empty list the debugger falls back to depth-only stepping.
+    pub statement_offsets: Vec<usize>,
+    /// Local variable and upvalue names with their live instruction ranges.
+    /// A name's range never covers the body of a nested function, so the records
+    /// live at an offset all belong to the function executing there.
+    pub local_names: Vec<LocalDebugInfo>,
     /// Number of local slots required by top-level code (set by compiler).
     /// Used by the VM to initialize the main frame's local_count so that
     /// SetLocal in top-level for-in loops and other constructs works correctly.
@@ -72,7 +110,10 @@ impl Bytecode {
         Self {
             instructions: Vec::new(),
             constants: Vec::new(),
+            global_names: Vec::new(),
             debug_info: Vec::new(),
+            statement_offsets: Vec::new(),
+            local_names: Vec::new(),
             top_level_local_count: 0,
         }
     }
@@ -108,11 +149,67 @@ impl Bytecode {
         (self.constants.len() - 1) as u16
     }
 
+    /// Return the global slot for `name`, adding it to the name table if new
+    ///
+    /// This scans the table; the compiler keeps its own index so interning
+    /// stays constant-time while compiling.
+    pub fn intern_global(&mut self, name: &str) -> u16 {
+        match self.global_names.iter().position(|n| n == name) {
+            Some(slot) => slot as u16,
+            None => {
+                self.global_names.push(name.to_string());
+                (self.global_names.len() - 1) as u16
+            }
+        }
+    }
+
     /// Get current instruction offset (for jump targets)
     pub fn current_offset(&self) -> usize {
         self.instructions.len()
     }
 
+    /// Record that a source statement begins at the current instruction offset
+    pub fn mark_statement_start(&mut self) {
+        let offset = self.instructions.len();
+        if self.statement_offsets.last() != Some(&offset) {
+            self.statement_offsets.push(offset);
+        }
+    }
+
+    /// Check whether a source statement begins at `offset`
+    pub fn is_statement_start(&self, offset: usize) -> bool {
+        self.statement_offsets.binary_search(&offset).is_ok()
+    }
+
+    /// Open a live range for a local name at the current offset, returning its index
+    ///
+    /// The range stays open until closed with [`end_local_range`](Self::end_local_range).
+    pub fn begin_local_range(&mut self, name: impl Into<String>, slot: usize) -> usize {
+        self.local_names.push(LocalDebugInfo {
+            name: name.into(),
+            slot,
+            start_offset: self.instructions.len(),
+            end_offset: usize::MAX,
+            captured: false,
+        });
+        self.local_names.len() - 1
+    }
+
+    /// Close a live range opened by [`begin_local_range`](Self::begin_local_range)
+    pub fn end_local_range(&mut self, index: usize) {
+        let end = self.instructions.len();
+        if let Some(info) = self.local_names.get_mut(index) {
+            info.end_offset = end;
+        }
+    }
+
+    /// Local and upvalue names in scope at `offset`
+    pub fn locals_at(&self, offset: usize) -> impl Iterator<Item = &LocalDebugInfo> {
+        self.local_names
+            .iter()
+            .filter(move |info| info.start_offset <= offset && offset < info.end_offset)
+    }
+
     /// Patch a jump instruction with the correct offset
     ///
     /// Used for forward jumps where the target isn't known yet
@@ -127,21 +224,25 @@ impl Bytecode {
     /// Returns the span of the instruction at or before the given offset.
     /// This is useful for error reporting in the VM.
     pub fn get_span_for_offset(&self, offset: usize) -> Option<Span> {
-        // Find the most recent debug info entry at or before the offset
-        self.debug_info
-            .iter()
-            .rev()
-            .find(|debug_span| debug_span.instruction_offset <= offset)
-            .map(|debug_span| debug_span.span)
+        // Find the most recent debug info entry at or before the offset.
+        // Entries are recorded in emission order, so offsets never decrease.
+        let after = self
+            .debug_info
+            .partition_point(|debug_span| debug_span.instruction_offset <= offset);
+        after
+            .checked_sub(1)
+            .map(|index| self.debug_info[index].span)
     }
 
-    /// Serialize bytecode to binary format (.atb file)
+    /// Serialize bytecode to binary format (.atbc file)
     ///
     /// Format:
     /// - Header: Magic "ATB\0" + version u16 + flags u16
     /// - Constants: count u32 + serialized values
+    /// - Globals: count u32 + names (length u32 + UTF-8 bytes each)
     /// - Instructions: length u32 + bytecode bytes
-    /// - Debug info (optional): count u32 + debug spans
+    /// - Debug info (optional): count u32 + debug spans, count u32 + statement offsets,
+    ///   count u32 + local names
     /// - Checksum: CRC32 u32 (over all preceding bytes)
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut bytes = Vec::new();
@@ -162,6 +263,13 @@ impl Bytecode {
             serialize_value(value, &mut bytes);
         }
 
+        // Global name table
+        bytes.extend_from_slice(&(self.global_names.len() as u32).to_be_bytes());
+        for name in &self.global_names {
+            bytes.extend_from_slice(&(name.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(name.as_bytes());
+        }
+
         // Top-level local count (H-288 fix: must serialize for for..in loops to work)
         bytes.extend_from_slice(&(self.top_level_local_count as u32).to_be_bytes());
 
@@ -176,6 +284,14 @@ impl Bytecode {
                 bytes.extend_from_slice(&(debug_span.instruction_offset as u32).to_be_bytes());
                 serialize_span(&debug_span.span, &mut bytes);
             }
+            bytes.extend_from_slice(&(self.statement_offsets.len() as u32).to_be_bytes());
+            for offset in &self.statement_offsets {
+                bytes.extend_from_slice(&(*offset as u32).to_be_bytes());
+            }
+            bytes.extend_from_slice(&(self.local_names.len() as u32).to_be_bytes());
+            for info in &self.local_names {
+                serialize_local_debug_info(info, &mut bytes);
+            }
         }
 
         // Checksum (CRC32 over all preceding bytes)
@@ -185,7 +301,7 @@ impl Bytecode {
         bytes
     }
 
-    /// Deserialize bytecode from binary format (.atb file)
+    /// Deserialize bytecode from binary format (.atbc file)
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
         // Minimum size: header (8) + checksum (4)
         if bytes.len() < 12 {
@@ -246,6 +362,39 @@ impl Bytecode {
             offset += consumed;
         }
 
+        // Read global name table
+        if offset + 4 > data_len {
+            return Err("Invalid bytecode: global names truncated".to_string());
+        }
+        let global_count = u32::from_be_bytes([
+            bytes[offset],
+            bytes[offset + 1],
+            bytes[offset + 2],
+            bytes[offset + 3],
+        ]) as usize;
+        offset += 4;
+
+        let mut global_names = Vec::with_capacity(global_count.min(data_len));
+        for _ in 0..global_count {
+            if offset + 4 > data_len {
+                return Err("Invalid bytecode: global name truncated".to_string());
+            }
+            let len = u32::from_be_bytes([
+                bytes[offset],
+                bytes[offset + 1],
+                bytes[offset + 2],
+                bytes[offset + 3],
+            ]) as usize;
+            offset += 4;
+            if offset + len > data_len {
+                return Err("Invalid bytecode: global name truncated".to_string());
+            }
+            let name = std::str::from_utf8(&bytes[offset..offset + len])
+                .map_err(|_| "Invalid bytecode: global name is not UTF-8".to_string())?;
+            global_names.push(name.to_string());
+            offset += len;
+        }
+
         // Read top-level local count (H-288 fix)
         if offset + 4 > data_len {
             return Err("Invalid bytecode: top_level_local_count truncated".to_string());
@@ -278,6 +427,8 @@ impl Bytecode {
 
         // Read debug info (optional)
         let mut debug_info = Vec::new();
+        let mut statement_offsets = Vec::new();
+        let mut local_names = Vec::new();
         if has_debug_info {
             if offset + 4 > data_len {
                 return Err("Invalid bytecode: debug info section truncated".to_string());
@@ -309,6 +460,48 @@ impl Bytecode {
                 });
                 offset += consumed;
             }
+
+            if offset + 4 > data_len {
+                return Err("Invalid bytecode: statement offsets truncated".to_string());
+            }
+            let statement_count = u32::from_be_bytes([
+                bytes[offset],
+                bytes[offset + 1],
+                bytes[offset + 2],
+                bytes[offset + 3],
+            ]) as usize;
+            offset += 4;
+
+            if offset + statement_count * 4 > data_len {
+                return Err("Invalid bytecode: statement offsets truncated".to_string());
+            }
+            statement_offsets.reserve(statement_count);
+            for _ in 0..statement_count {
+                statement_offsets.push(u32::from_be_bytes([
+                    bytes[offset],
+                    bytes[offset + 1],
+                    bytes[offset + 2],
+                    bytes[offset + 3],
+                ]) as usize);
+                offset += 4;
+            }
+
+            if offset + 4 > data_len {
+                return Err("Invalid bytecode: local names truncated".to_string());
+            }
+            let local_count = u32::from_be_bytes([
+                bytes[offset],
+                bytes[offset + 1],
+                bytes[offset + 2],
+                bytes[offset + 3],
+            ]) as usize;
+            offset += 4;
+
+            for _ in 0..local_count {
+                let (info, consumed) = deserialize_local_debug_info(&bytes[offset..data_len])?;
+                local_names.push(info);
+                offset += consumed;
+            }
         }
 
         // Verify we consumed exactly the data portion (checksum already verified)
@@ -322,23 +515,74 @@ impl Bytecode {
         Ok(Bytecode {
             instructions,
             constants,
+            global_names,
             debug_info,
+            statement_offsets,
+            local_names,
             top_level_local_count,
         })
     }
 
+    /// Write the serialized bytecode to a `.atbc` file
+    pub fn write_to_file(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_bytes())
+    }
+
+    /// Read a `.atbc` file written by [`write_to_file`](Self::write_to_file)
+    ///
+    /// The bytes are checked with [`from_untrusted_bytes`](Self::from_untrusted_bytes),
+    /// since a file on disk may come from anywhere.
+    pub fn read_from_file(path: &Path) -> Result<Self, String> {
+        let bytes = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Self::from_untrusted_bytes(&bytes).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Deserialize bytecode that did not come from this process
+    ///
+    /// Besides the version and checksum checks of [`from_bytes`](Self::from_bytes),
+    /// the instruction stream is validated before anything can execute it.
+    pub fn from_untrusted_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let bytecode = Self::from_bytes(bytes)?;
+        validate(&bytecode).map_err(|errors| {
+            let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+            format!("invalid bytecode: {}", errors.join("; "))
+        })?;
+        Ok(bytecode)
+    }
+
     /// Append another bytecode chunk to this one
     ///
     /// This adjusts:
     /// - Instruction offsets in debug info
     /// - Bytecode offsets in Function values in constants
     /// - Constant indices in the new instructions (opcodes that reference constants)
+    /// - Global slots in the new instructions, after interning the other chunk's
+    ///   global names into this one's table
     ///
     /// Used by Runtime to accumulate bytecode across multiple eval() calls.
     pub fn append(&mut self, other: Bytecode) {
         let instruction_offset = self.instructions.len();
         let constant_offset = self.constants.len() as u16;
 
+        // Intern the other chunk's globals; `global_slots[i]` is the slot its
+        // global `i` has in the combined table
+        let mut known: std::collections::HashMap<String, u16> = self
+            .global_names
+            .iter()
+            .enumerate()
+            .map(|(slot, name)| (name.clone(), slot as u16))
+            .collect();
+        let global_slots: Vec<u16> = other
+            .global_names
+            .into_iter()
+            .map(|name| {
+                *known.entry(name).or_insert_with_key(|name| {
+                    self.global_names.push(name.clone());
+                    (self.global_names.len() - 1) as u16
+                })
+            })
+            .collect();
+
         // Append constants FIRST, adjusting function bytecode offsets
         for constant in other.constants {
             match constant {
@@ -406,13 +650,25 @@ impl Bytecode {
                 self.instructions.push(other.instructions[i]);
                 self.instructions.push(other.instructions[i + 1]);
                 i += 2;
+            } else if (opcode_byte == Opcode::GetGlobal as u8
+                || opcode_byte == Opcode::SetGlobal as u8)
+                && i + 1 < other.instructions.len()
+            {
+                // Global slot: map into the combined name table
+                let old_slot =
+                    ((other.instructions[i] as u16) << 8) | other.instructions[i + 1] as u16;
+                let new_slot = global_slots
+                    .get(old_slot as usize)
+                    .copied()
+                    .unwrap_or(old_slot);
+                self.instructions.push((new_slot >> 8) as u8);
+                self.instructions.push((new_slot & 0xFF) as u8);
+                i += 2;
             } else {
                 // Check if this opcode uses a constant index (u16 operand)
                 let uses_constant = matches!(
                     opcode_byte,
                     x if x == Opcode::Constant as u8
-                        || x == Opcode::GetGlobal as u8
-                        || x == Opcode::SetGlobal as u8
                         || x == Opcode::CheckStructType as u8
                 );
 
@@ -451,7 +707,8 @@ impl Bytecode {
                             || x == Opcode::TupleGet as u8
                             || x == Opcode::Tuple as u8
                             || x == Opcode::HashMap as u8
-                            || x == Opcode::DeferPush as u8 =>
+                            || x == Opcode::DeferPush as u8
+                            || x == Opcode::TryBegin as u8 =>
                         {
                             2 // u16 operand
                         }
@@ -482,6 +739,18 @@ impl Bytecode {
             debug_span.instruction_offset += instruction_offset;
             self.debug_info.push(debug_span);
         }
+        self.statement_offsets.extend(
+            other
+                .statement_offsets
+                .into_iter()
+                .map(|offset| offset + instruction_offset),
+        );
+        self.local_names
+            .extend(other.local_names.into_iter().map(|mut info| {
+                info.start_offset += instruction_offset;
+                info.end_offset = info.end_offset.saturating_add(instruction_offset);
+                info
+            }));
 
         // Accumulate top_level_local_count: each module's top-level locals need stack space
         // (H-290: B36 regression fix — append() was leaving this at 0)
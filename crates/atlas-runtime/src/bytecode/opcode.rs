@@ -163,6 +163,15 @@ pub enum Opcode {
     /// [u16 name_const_idx] Pop value, push true if it is a struct whose registered
     /// type name equals the string constant at name_const_idx.
     CheckStructType = 0x9C,
+    /// Pop a for-in iterable, push something GetArrayLen/GetIndex can walk:
+    /// arrays and ranges as-is, a HashMap as an array of (key, value) tuples,
+    /// a HashSet as an array of its elements
+    ForInPrepare = 0x9D,
+    /// Pop value, push true if it is null, Option::None or a json null
+    IsNullish = 0x9E,
+    /// Pop container, pop value, push whether the value is in the container
+    /// (array element, map key, set element, substring, range number or json object key)
+    Contains = 0x9F,
 
     // ===== Async (0xA0-0xAF) =====
     /// Call async function [u16 fn_const_idx, u8 arg_count]
@@ -187,6 +196,17 @@ pub enum Opcode {
     /// Execute all deferred blocks for current scope (LIFO order)
     DeferExec = 0xB1,
 
+    // ===== Error handling (0xC0-0xCF) =====
+    /// Install a runtime-error handler for the current frame [i16 catch_offset]
+    /// A catchable error raised before the matching TryEnd unwinds to the
+    /// catch block at IP + catch_offset (relative to the end of the operand)
+    TryBegin = 0xC0,
+    /// Remove the innermost handler installed by TryBegin [no operands]
+    TryEnd = 0xC1,
+    /// Push the record of the error caught by the last unwind [no operands]
+    /// Emitted at the start of a catch block that binds the error
+    PushError = 0xC2,
+
     // ===== Special (0xF0-0xFF) =====
     /// End of bytecode
     Halt = 0xFF,
@@ -261,12 +281,18 @@ impl TryFrom<u8> for Opcode {
             0x9A => Ok(Opcode::ExtractEnumData),
             0x9B => Ok(Opcode::IsStruct),
             0x9C => Ok(Opcode::CheckStructType),
+            0x9D => Ok(Opcode::ForInPrepare),
+            0x9E => Ok(Opcode::IsNullish),
+            0x9F => Ok(Opcode::Contains),
             0xA0 => Ok(Opcode::AsyncCall),
             0xA1 => Ok(Opcode::Await),
             0xA2 => Ok(Opcode::WrapFuture),
             0xA3 => Ok(Opcode::SpawnTask),
             0xB0 => Ok(Opcode::DeferPush),
             0xB1 => Ok(Opcode::DeferExec),
+            0xC0 => Ok(Opcode::TryBegin),
+            0xC1 => Ok(Opcode::TryEnd),
+            0xC2 => Ok(Opcode::PushError),
             0xFF => Ok(Opcode::Halt),
             _ => Err(()),
         }
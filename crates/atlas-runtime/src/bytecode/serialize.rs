@@ -25,6 +25,7 @@
 //! HttpRequest, HttpResponse, Future, TaskHandle, Channel*, AsyncMutex, Watcher,
 //! Closure, SharedValue.
 
+use super::LocalDebugInfo;
 use crate::span::{file_path, Span};
 use crate::stdlib::collections::hash::HashKey;
 use crate::stdlib::collections::hashmap::AtlasHashMap;
@@ -52,6 +53,7 @@ mod tags {
     pub const DATETIME: u8 = 0x0E;
     pub const RANGE: u8 = 0x0F;
     pub const TUPLE: u8 = 0x10;
+    pub const BYTES: u8 = 0x11;
 }
 
 /// Serialize a Value to bytes
@@ -189,6 +191,7 @@ pub(super) fn serialize_value(value: &Value, bytes: &mut Vec<u8>) {
                 bytes.extend_from_slice(&end.to_be_bytes());
             }
         }
+        #[cfg(feature = "regex")]
         Value::Regex(re) => {
             bytes.push(tags::REGEX);
             serialize_string(re.as_str(), bytes);
@@ -199,6 +202,11 @@ pub(super) fn serialize_value(value: &Value, bytes: &mut Vec<u8>) {
             let s = dt.to_rfc3339();
             serialize_string(&s, bytes);
         }
+        Value::Bytes(data) => {
+            bytes.push(tags::BYTES);
+            bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(data);
+        }
         // Runtime-only types cannot be serialized
         Value::NativeFunction(_) => {
             panic!("Cannot serialize native functions in bytecode constants");
@@ -206,9 +214,11 @@ pub(super) fn serialize_value(value: &Value, bytes: &mut Vec<u8>) {
         Value::JsonValue(_) => {
             panic!("Cannot serialize JSON values in bytecode constants");
         }
+        #[cfg(feature = "http")]
         Value::HttpRequest(_) => {
             panic!("Cannot serialize HttpRequest values in bytecode constants");
         }
+        #[cfg(feature = "http")]
         Value::HttpResponse(_) => {
             panic!("Cannot serialize HttpResponse values in bytecode constants");
         }
@@ -218,6 +228,15 @@ pub(super) fn serialize_value(value: &Value, bytes: &mut Vec<u8>) {
         Value::SqliteConnection(_) => {
             panic!("Cannot serialize SqliteConnection values in bytecode constants");
         }
+        Value::KvStore(_) => {
+            panic!("Cannot serialize KvStore values in bytecode constants");
+        }
+        Value::FileHandle(_) => {
+            panic!("Cannot serialize FileHandle values in bytecode constants");
+        }
+        Value::ProgressBar(_) => {
+            panic!("Cannot serialize ProgressBar values in bytecode constants");
+        }
         Value::Future(_) => {
             panic!("Cannot serialize Future values in bytecode constants");
         }
@@ -233,9 +252,13 @@ pub(super) fn serialize_value(value: &Value, bytes: &mut Vec<u8>) {
         Value::AsyncMutex(_) => {
             panic!("Cannot serialize AsyncMutex values in bytecode constants");
         }
+        #[cfg(feature = "fs")]
         Value::Watcher(_) => {
             panic!("Cannot serialize Watcher values in bytecode constants");
         }
+        Value::Resource(_) => {
+            panic!("Cannot serialize Resource values in bytecode constants");
+        }
         Value::Closure(_) => {
             panic!("Cannot serialize Closure values in bytecode constants");
         }
@@ -611,6 +634,9 @@ pub(super) fn deserialize_value(bytes: &[u8]) -> Result<(Value, usize), String>
             Ok((Value::Stack(ValueStack::from_atlas(stack)), 1 + cursor))
         }
 
+        #[cfg(not(feature = "regex"))]
+        tags::REGEX => Err(crate::stdlib::REGEX_DISABLED.to_string()),
+        #[cfg(feature = "regex")]
         tags::REGEX => {
             let (pattern, consumed) = deserialize_string(rest)?;
             let re =
@@ -626,6 +652,18 @@ pub(super) fn deserialize_value(bytes: &[u8]) -> Result<(Value, usize), String>
             Ok((Value::DateTime(std::sync::Arc::new(dt)), 1 + consumed))
         }
 
+        tags::BYTES => {
+            if rest.len() < 4 {
+                return Err("Truncated bytes length".to_string());
+            }
+            let len = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+            if rest.len() < 4 + len {
+                return Err("Truncated bytes data".to_string());
+            }
+            let data = rest[4..4 + len].to_vec();
+            Ok((Value::Bytes(std::sync::Arc::new(data)), 1 + 4 + len))
+        }
+
         tags::TUPLE => {
             if rest.len() < 4 {
                 return Err("Truncated tuple length".to_string());
@@ -673,6 +711,40 @@ pub(super) fn deserialize_span(bytes: &[u8]) -> Result<(Span, usize), String> {
     ))
 }
 
+/// Serialize a local-variable debug record to bytes
+pub(super) fn serialize_local_debug_info(info: &LocalDebugInfo, bytes: &mut Vec<u8>) {
+    serialize_string(&info.name, bytes);
+    bytes.extend_from_slice(&(info.slot as u32).to_be_bytes());
+    bytes.extend_from_slice(&(info.start_offset as u32).to_be_bytes());
+    bytes.extend_from_slice(&(info.end_offset as u32).to_be_bytes());
+    bytes.push(info.captured as u8);
+}
+
+/// Deserialize a local-variable debug record, returns (LocalDebugInfo, bytes_consumed)
+pub(super) fn deserialize_local_debug_info(
+    bytes: &[u8],
+) -> Result<(LocalDebugInfo, usize), String> {
+    let (name, consumed) = deserialize_string(bytes)?;
+    let rest = &bytes[consumed..];
+    if rest.len() < 13 {
+        return Err("Truncated local debug info".to_string());
+    }
+    let slot = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+    let start_offset = u32::from_be_bytes([rest[4], rest[5], rest[6], rest[7]]) as usize;
+    let end_offset = u32::from_be_bytes([rest[8], rest[9], rest[10], rest[11]]) as usize;
+    let captured = rest[12] != 0;
+    Ok((
+        LocalDebugInfo {
+            name,
+            slot,
+            start_offset,
+            end_offset,
+            captured,
+        },
+        consumed + 13,
+    ))
+}
+
 /// Compute CRC32 checksum for bytecode integrity verification
 pub(super) fn compute_checksum(data: &[u8]) -> u32 {
     crc32fast::hash(data)
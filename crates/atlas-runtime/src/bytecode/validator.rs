@@ -244,7 +244,7 @@ fn read_operand(opcode: Opcode, code: &[u8], ip: usize) -> Result<(usize, i64),
             Ok((4, ((hi << 8) | lo) as i64))
         }
         // 2-byte signed operand (i16)
-        Opcode::Jump | Opcode::JumpIfFalse | Opcode::Loop => {
+        Opcode::Jump | Opcode::JumpIfFalse | Opcode::Loop | Opcode::TryBegin => {
             if ip + 1 >= code.len() {
                 return Err(opcode_name(opcode));
             }
@@ -343,6 +343,9 @@ fn opcode_name(opcode: Opcode) -> &'static str {
         Opcode::ExtractEnumData => "ExtractEnumData",
         Opcode::IsStruct => "IsStruct",
         Opcode::CheckStructType => "CheckStructType",
+        Opcode::ForInPrepare => "ForInPrepare",
+        Opcode::IsNullish => "IsNullish",
+        Opcode::Contains => "Contains",
         Opcode::Halt => "Halt",
         Opcode::MakeClosure => "MakeClosure",
         Opcode::GetUpvalue => "GetUpvalue",
@@ -355,6 +358,9 @@ fn opcode_name(opcode: Opcode) -> &'static str {
         Opcode::TupleGet => "TupleGet",
         Opcode::DeferPush => "DeferPush",
         Opcode::DeferExec => "DeferExec",
+        Opcode::TryBegin => "TryBegin",
+        Opcode::TryEnd => "TryEnd",
+        Opcode::PushError => "PushError",
     }
 }
 
@@ -373,7 +379,10 @@ fn check_jump_targets(
     for instr in decoded {
         let is_jump = matches!(
             instr.opcode,
-            Some(Opcode::Jump) | Some(Opcode::JumpIfFalse) | Some(Opcode::Loop)
+            Some(Opcode::Jump)
+                | Some(Opcode::JumpIfFalse)
+                | Some(Opcode::Loop)
+                | Some(Opcode::TryBegin)
         );
         if !is_jump {
             continue;
@@ -415,20 +424,14 @@ fn check_constant_refs(
     decoded: &[DecodedInstruction],
     errors: &mut Vec<ValidationError>,
 ) {
-    let pool_size = bytecode.constants.len();
-
     for instr in decoded {
-        let needs_pool = matches!(
-            instr.opcode,
-            Some(Opcode::Constant)
-                | Some(Opcode::GetGlobal)
-                | Some(Opcode::SetGlobal)
-                | Some(Opcode::TraitDispatch)
-                | Some(Opcode::Struct)
-        );
-        if !needs_pool {
-            continue;
-        }
+        let pool_size = match instr.opcode {
+            Some(Opcode::Constant) | Some(Opcode::TraitDispatch) | Some(Opcode::Struct) => {
+                bytecode.constants.len()
+            }
+            Some(Opcode::GetGlobal) | Some(Opcode::SetGlobal) => bytecode.global_names.len(),
+            _ => continue,
+        };
 
         let index = instr.operand as usize;
         if index >= pool_size {
@@ -483,6 +486,8 @@ fn stack_delta(instr: &DecodedInstruction) -> Option<i32> {
         | Opcode::GetArrayLen
         | Opcode::ExtractEnumData
         | Opcode::IsStruct
+        | Opcode::ForInPrepare
+        | Opcode::IsNullish
         | Opcode::Halt => Some(0),
 
         // CheckStructType: pop 1 (value), push 1 (bool) — net 0
@@ -505,6 +510,7 @@ fn stack_delta(instr: &DecodedInstruction) -> Option<i32> {
         | Opcode::GreaterEqual
         | Opcode::And
         | Opcode::Or
+        | Opcode::Contains
         | Opcode::GetIndex
         | Opcode::GetField
         | Opcode::Range
@@ -527,6 +533,11 @@ fn stack_delta(instr: &DecodedInstruction) -> Option<i32> {
         // Defer: DeferPush records body location (net 0), DeferExec runs defers (net 0)
         Opcode::DeferPush | Opcode::DeferExec => Some(0),
 
+        // TryBegin/TryEnd install and remove a handler (net 0);
+        // PushError pushes the caught error record at the start of a catch block
+        Opcode::TryBegin | Opcode::TryEnd => Some(0),
+        Opcode::PushError => Some(1),
+
         // Variable-arity — skip (MakeClosure pops n_upvalues, push 1; net depends on operand)
         // EnumVariant pops enum_name, variant_name, and args; pushes 1
         // AsyncCall/SpawnTask pop args + fn, push Future (arg_count varies)
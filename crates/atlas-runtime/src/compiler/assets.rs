@@ -0,0 +1,213 @@
+//! Compile-time asset embedding (`includeStr`, `includeBytes`)
+//!
+//! Calls to these builtins never reach the VM: the compiler reads the file
+//! and emits its contents as a constant, so templates and SQL snippets ship
+//! inside the bytecode and a bundled program needs no filesystem access to
+//! use them. Only files matched by the `[assets] include` patterns of the
+//! project's `atlas.toml` can be embedded.
+
+use super::Compiler;
+use crate::ast::{CallExpr, Expr, Literal};
+use crate::bytecode::Opcode;
+use crate::diagnostic::error_codes::ASSET_INCLUDE_ERROR;
+use crate::diagnostic::Diagnostic;
+use crate::span::Span;
+use crate::value::Value;
+use std::path::{Path, PathBuf};
+
+/// Largest file `includeStr`/`includeBytes` will embed
+pub const MAX_ASSET_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Which files a program may embed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetPolicy {
+    /// Package root; patterns and embedded paths are relative to it
+    root: PathBuf,
+    /// Glob patterns: `*` and `?` within a path segment, `**` across segments
+    patterns: Vec<String>,
+}
+
+impl AssetPolicy {
+    pub fn new(root: impl Into<PathBuf>, patterns: Vec<String>) -> Self {
+        let root = root.into();
+        let root = root.canonicalize().unwrap_or(root);
+        Self { root, patterns }
+    }
+
+    /// The policy declared by the nearest `atlas.toml` at or above `dir`.
+    ///
+    /// `Ok(None)` when there is no manifest or it has no `[assets]` table.
+    pub fn discover(dir: &Path) -> Result<Option<Self>, String> {
+        let Some(manifest) = dir
+            .ancestors()
+            .map(|d| d.join("atlas.toml"))
+            .find(|p| p.is_file())
+        else {
+            return Ok(None);
+        };
+        let content = std::fs::read_to_string(&manifest)
+            .map_err(|e| format!("{}: {}", manifest.display(), e))?;
+        let table: toml::Table =
+            toml::from_str(&content).map_err(|e| format!("{}: {}", manifest.display(), e))?;
+        let Some(assets) = table.get("assets") else {
+            return Ok(None);
+        };
+
+        let patterns = assets
+            .get("include")
+            .and_then(|v| v.as_array())
+            .and_then(|items| {
+                items
+                    .iter()
+                    .map(|v| v.as_str().map(str::to_string))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or_else(|| {
+                format!(
+                    "{}: `[assets] include` must be a list of glob patterns",
+                    manifest.display()
+                )
+            })?;
+        let root = manifest.parent().unwrap_or(Path::new("."));
+        Ok(Some(Self::new(root, patterns)))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resolve `request`, relative to `from_dir`, to a file the policy allows
+    pub fn resolve(&self, from_dir: &Path, request: &str) -> Result<PathBuf, String> {
+        let path = from_dir
+            .join(request)
+            .canonicalize()
+            .map_err(|e| e.to_string())?;
+        let relative = path
+            .strip_prefix(&self.root)
+            .map_err(|_| format!("it is outside the package root {}", self.root.display()))?;
+        let relative: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        let relative = relative.join("/");
+
+        if !self.patterns.iter().any(|p| glob_match(p, &relative)) {
+            return Err(format!(
+                "`{}` is not listed in `[assets] include` of atlas.toml",
+                relative
+            ));
+        }
+        Ok(path)
+    }
+}
+
+/// Match a `/`-separated path against a glob pattern
+pub(crate) fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.trim_start_matches("./").split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+    match_segments(&pattern, &path)
+}
+
+fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| match_segments(rest, &path[skip..])),
+        Some((segment, rest)) => path.split_first().is_some_and(|(name, path)| {
+            match_segment(segment.as_bytes(), name.as_bytes()) && match_segments(rest, path)
+        }),
+    }
+}
+
+fn match_segment(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| match_segment(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && match_segment(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && match_segment(rest, &name[1..]),
+    }
+}
+
+impl Compiler {
+    /// Embed the file named by `includeStr(path)` / `includeBytes(path)` as a
+    /// constant: a string, or an array of byte values
+    pub(super) fn compile_include(
+        &mut self,
+        builtin: &str,
+        call: &CallExpr,
+    ) -> Result<(), Vec<Diagnostic>> {
+        let request = match call.args.as_slice() {
+            [Expr::Literal(Literal::String(path), _)] => path.clone(),
+            _ => {
+                return Err(vec![include_error(
+                    call.span,
+                    builtin,
+                    format!("`{}` takes a single string literal path", builtin),
+                )])
+            }
+        };
+
+        // Without an explicit policy, use the manifest nearest the module
+        if self.asset_policy.is_none() {
+            if let Some(dir) = &self.source_dir {
+                self.asset_policy = AssetPolicy::discover(dir)
+                    .map_err(|detail| vec![include_error(call.span, &request, detail)])?;
+            }
+        }
+        let Some(policy) = &self.asset_policy else {
+            return Err(vec![include_error(
+                call.span,
+                &request,
+                "no assets are declared; list the file under `[assets] include` in atlas.toml"
+                    .to_string(),
+            )]);
+        };
+        let from_dir = self.source_dir.as_deref().unwrap_or(policy.root());
+        let path = policy
+            .resolve(from_dir, &request)
+            .map_err(|detail| vec![include_error(call.span, &request, detail)])?;
+
+        let size = std::fs::metadata(&path)
+            .map_err(|e| vec![include_error(call.span, &request, e.to_string())])?
+            .len();
+        if size > MAX_ASSET_SIZE {
+            return Err(vec![include_error(
+                call.span,
+                &request,
+                format!(
+                    "it is {} bytes; the limit is {} bytes",
+                    size, MAX_ASSET_SIZE
+                ),
+            )]);
+        }
+        let bytes = std::fs::read(&path)
+            .map_err(|e| vec![include_error(call.span, &request, e.to_string())])?;
+        self.embedded_files.push(path);
+
+        let value = if builtin == "includeStr" {
+            let text = String::from_utf8(bytes).map_err(|_| {
+                vec![include_error(
+                    call.span,
+                    &request,
+                    "it is not valid UTF-8; use `includeBytes` for binary files".to_string(),
+                )]
+            })?;
+            Value::string(text)
+        } else {
+            Value::array(bytes.into_iter().map(|b| Value::Number(b as f64)).collect())
+        };
+
+        let idx = self.bytecode.add_constant(value);
+        self.bytecode.emit(Opcode::Constant, call.span);
+        self.bytecode.emit_u16(idx);
+        Ok(())
+    }
+}
+
+fn include_error(span: Span, path: &str, detail: String) -> Diagnostic {
+    ASSET_INCLUDE_ERROR
+        .emit(span)
+        .arg("path", path)
+        .arg("detail", detail)
+        .build()
+        .with_label("embedded here")
+}
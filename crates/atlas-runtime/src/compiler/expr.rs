@@ -5,7 +5,7 @@
 use crate::ast::*;
 use crate::bytecode::Opcode;
 use crate::compiler::{Compiler, Local, UpvalueCapture, UpvalueContext};
-use crate::diagnostic::error_codes::INTERNAL_ERROR;
+use crate::diagnostic::error_codes::{INTERNAL_ERROR, INVALID_REGEX_LITERAL};
 use crate::diagnostic::Diagnostic;
 use crate::span::Span;
 use crate::value::Value;
@@ -195,9 +195,7 @@ impl Compiler {
         if let Expr::Identifier(id) = call.callee.as_ref() {
             if self.constructor_types.contains(&id.name) {
                 let mangled = format!("__static__{}__new", id.name);
-                let name_idx = self
-                    .bytecode
-                    .add_constant(crate::value::Value::string(mangled));
+                let name_idx = self.global_slot(&mangled);
                 self.bytecode.emit(Opcode::GetGlobal, call.span);
                 self.bytecode.emit_u16(name_idx);
                 for arg in &call.args {
@@ -209,6 +207,17 @@ impl Compiler {
             }
         }
 
+        // includeStr/includeBytes are resolved at compile time unless shadowed
+        if let Expr::Identifier(id) = call.callee.as_ref() {
+            if matches!(id.name.as_str(), "includeStr" | "includeBytes")
+                && self.resolve_local(&id.name).is_none()
+                && !self.global_mutability.contains_key(&id.name)
+                && !self.function_names.contains(&id.name)
+            {
+                return self.compile_include(&id.name, call);
+            }
+        }
+
         // Bare user-defined enum variant constructor: `Unknown(raw)` without `EnumName::`.
         // Short-circuit BEFORE callee lookup so variant names never hit GetGlobal.
         // Skip stdlib constructors (Ok, Err, Some, None) — they have dedicated Value types.
@@ -269,11 +278,9 @@ impl Compiler {
 
                 // Check if this local is from current function's scope or parent scope
                 if local.depth < self.scope_depth {
-                    if let Some(name_to_use) = local.scoped_name.as_ref() {
+                    if let Some(name_to_use) = local.scoped_name.clone() {
                         // Nested function in parent scope — accessible via global scoped name
-                        let name_idx = self
-                            .bytecode
-                            .add_constant(crate::value::Value::string(name_to_use));
+                        let name_idx = self.global_slot(&name_to_use);
                         self.bytecode.emit(Opcode::GetGlobal, call.span);
                         self.bytecode.emit_u16(name_idx);
                     } else if !self.upvalue_stack.is_empty() {
@@ -283,9 +290,7 @@ impl Compiler {
                         self.bytecode.emit_u16(upvalue_idx as u16);
                     } else {
                         // Fallback: GetGlobal
-                        let name_idx = self
-                            .bytecode
-                            .add_constant(crate::value::Value::string(func_name));
+                        let name_idx = self.global_slot(func_name);
                         self.bytecode.emit(Opcode::GetGlobal, call.span);
                         self.bytecode.emit_u16(name_idx);
                     }
@@ -297,9 +302,7 @@ impl Compiler {
                 }
             } else {
                 // Load from global
-                let name_idx = self
-                    .bytecode
-                    .add_constant(crate::value::Value::string(func_name));
+                let name_idx = self.global_slot(func_name);
                 self.bytecode.emit(Opcode::GetGlobal, call.span);
                 self.bytecode.emit_u16(name_idx);
             }
@@ -432,9 +435,7 @@ impl Compiler {
             }
         } else {
             // Global variable (or doesn't exist — silently skip)
-            let name_idx = self
-                .bytecode
-                .add_constant(crate::value::Value::string(var_name));
+            let name_idx = self.global_slot(var_name);
             self.bytecode.emit(Opcode::SetGlobal, span);
             self.bytecode.emit_u16(name_idx);
         }
@@ -446,6 +447,26 @@ impl Compiler {
     /// The function name is determined from the method name using a standard mapping:
     ///   value.as_string() → jsonAsString(value)
     fn compile_member(&mut self, member: &MemberExpr) -> Result<(), Vec<Diagnostic>> {
+        // Module namespace member (`math.add`, `math.add(1, 2)`): read the
+        // exporting module's global directly so the binding stays live
+        if let Some(alias) = self.namespace_alias(&member.target) {
+            let name_idx = self.global_slot(&format!("{}.{}", alias, member.member.name));
+            self.bytecode.emit(Opcode::GetGlobal, member.span);
+            self.bytecode.emit_u16(name_idx);
+            if let Some(args) = &member.args {
+                for arg in args {
+                    self.compile_expr(arg)?;
+                }
+                self.bytecode.emit(Opcode::Call, member.span);
+                self.bytecode.emit_u8(args.len() as u8);
+            }
+            return Ok(());
+        }
+
+        if member.optional {
+            return self.compile_null_safe_member(member);
+        }
+
         if member.args.is_none() {
             self.compile_expr(&member.target)?;
             // Tuple element access: .0, .1, ... → TupleGet with numeric index
@@ -470,9 +491,7 @@ impl Compiler {
             let mangled_name = format!("__static__{}__{}", type_name, member.member.name);
 
             // Push the mangled function by name from globals
-            let name_idx = self
-                .bytecode
-                .add_constant(crate::value::Value::string(&mangled_name));
+            let name_idx = self.global_slot(&mangled_name);
             self.bytecode.emit(Opcode::GetGlobal, member.span);
             self.bytecode.emit_u16(name_idx);
 
@@ -530,9 +549,7 @@ impl Compiler {
                 };
 
                 // Push the mangled function by name from globals
-                let name_idx = self
-                    .bytecode
-                    .add_constant(crate::value::Value::string(&mangled_name));
+                let name_idx = self.global_slot(&mangled_name);
                 self.bytecode.emit(Opcode::GetGlobal, member.span);
                 self.bytecode.emit_u16(name_idx);
 
@@ -559,8 +576,7 @@ impl Compiler {
         // a diagnostic rather than panicking (fuzzer safety, and graceful error for callers).
         if let Some(type_tag) = member.type_tag.get() {
             // For HashMap targets, if resolve_method fails, fall back to treating the member
-            // as a callable field. This handles namespace imports (`import * as ns`) which are
-            // stored as Value::Map but whose fields are user-defined functions.
+            // as a callable field (a map whose values are user-defined functions).
             if matches!(type_tag, crate::method_dispatch::TypeTag::Map)
                 && member.args.is_some()
                 && crate::method_dispatch::resolve_method(type_tag, &member.member.name).is_none()
@@ -661,11 +677,14 @@ impl Compiler {
                     | crate::method_dispatch::TypeTag::HttpNs
                     | crate::method_dispatch::TypeTag::NetNs
                     | crate::method_dispatch::TypeTag::CryptoNs
+                    | crate::method_dispatch::TypeTag::EncodingNs
                     | crate::method_dispatch::TypeTag::RegexNs
                     | crate::method_dispatch::TypeTag::IoNs
                     | crate::method_dispatch::TypeTag::ConsoleNs
                     | crate::method_dispatch::TypeTag::ReflectNs
                     | crate::method_dispatch::TypeTag::SqliteNs
+                    | crate::method_dispatch::TypeTag::KvNs
+                    | crate::method_dispatch::TypeTag::CsvNs
                     | crate::method_dispatch::TypeTag::GzipNs
                     | crate::method_dispatch::TypeTag::TarNs
                     | crate::method_dispatch::TypeTag::ZipNs
@@ -782,10 +801,61 @@ impl Compiler {
             Literal::Null => {
                 self.bytecode.emit(Opcode::Null, span);
             }
+            #[cfg(not(feature = "regex"))]
+            Literal::Regex { .. } => {
+                return Err(vec![INVALID_REGEX_LITERAL
+                    .emit(span)
+                    .arg("detail", crate::stdlib::REGEX_DISABLED)
+                    .build()]);
+            }
+            #[cfg(feature = "regex")]
+            Literal::Regex { pattern, flags } => {
+                let regex =
+                    crate::stdlib::regex::compile_literal(pattern, flags).map_err(|detail| {
+                        vec![INVALID_REGEX_LITERAL
+                            .emit(span)
+                            .arg("detail", detail)
+                            .build()]
+                    })?;
+                let idx = self
+                    .bytecode
+                    .add_constant(Value::Regex(std::sync::Arc::new(regex)));
+                self.bytecode.emit(Opcode::Constant, span);
+                self.bytecode.emit_u16(idx);
+            }
         }
         Ok(())
     }
 
+    /// Alias of the namespace import `expr` names, unless a local shadows it
+    fn namespace_alias(&self, expr: &Expr) -> Option<String> {
+        let Expr::Identifier(id) = expr else {
+            return None;
+        };
+        (self.namespace_imports.contains_key(&id.name) && self.resolve_local(&id.name).is_none())
+            .then(|| id.name.clone())
+    }
+
+    /// Compile a namespace import used as a value into a record of its exports
+    fn compile_namespace_value(&mut self, ident: &Identifier) -> Result<(), Vec<Diagnostic>> {
+        let members = self.namespace_imports[&ident.name].clone();
+        let name_idx = self
+            .bytecode
+            .add_constant(Value::string("<anonymous>".to_string()));
+        for member in &members {
+            let key_idx = self.bytecode.add_constant(Value::string(member.clone()));
+            self.bytecode.emit(Opcode::Constant, ident.span);
+            self.bytecode.emit_u16(key_idx);
+            let global_idx = self.global_slot(&format!("{}.{}", ident.name, member));
+            self.bytecode.emit(Opcode::GetGlobal, ident.span);
+            self.bytecode.emit_u16(global_idx);
+        }
+        self.bytecode.emit(Opcode::Struct, ident.span);
+        self.bytecode.emit_u16(name_idx);
+        self.bytecode.emit_u16(members.len() as u16);
+        Ok(())
+    }
+
     /// Compile an identifier (variable access)
     fn compile_identifier(&mut self, ident: &Identifier) -> Result<(), Vec<Diagnostic>> {
         // Check for compile-time constant first — inline the value directly
@@ -829,9 +899,9 @@ impl Compiler {
                 let function_relative_idx = local_idx - self.current_function_base;
                 self.bytecode.emit(Opcode::GetLocal, ident.span);
                 self.bytecode.emit_u16(function_relative_idx as u16);
-            } else if let Some(name_to_use) = local.scoped_name.as_ref() {
+            } else if let Some(name_to_use) = local.scoped_name.clone() {
                 // Nested function in parent scope — accessible via its global scoped name
-                let name_idx = self.bytecode.add_constant(Value::string(name_to_use));
+                let name_idx = self.global_slot(&name_to_use);
                 self.bytecode.emit(Opcode::GetGlobal, ident.span);
                 self.bytecode.emit_u16(name_idx);
             } else if !self.upvalue_stack.is_empty() {
@@ -841,21 +911,122 @@ impl Compiler {
                 self.bytecode.emit_u16(upvalue_idx as u16);
             } else {
                 // Outer scope but not in a nested function — use GetGlobal fallback
-                let name_idx = self.bytecode.add_constant(Value::string(&ident.name));
+                let name_idx = self.global_slot(&ident.name);
                 self.bytecode.emit(Opcode::GetGlobal, ident.span);
                 self.bytecode.emit_u16(name_idx);
             }
+        } else if self.namespace_imports.contains_key(&ident.name) {
+            return self.compile_namespace_value(ident);
         } else {
             // Global variable
-            let name_idx = self.bytecode.add_constant(Value::string(&ident.name));
+            let name_idx = self.global_slot(&ident.name);
             self.bytecode.emit(Opcode::GetGlobal, ident.span);
             self.bytecode.emit_u16(name_idx);
         }
         Ok(())
     }
 
+    /// Compile a `?.` access as a plain access guarded by a check on its
+    /// target, which is parked in a temp global unless it is a plain name:
+    ///
+    /// ```text
+    /// Nullable / json method:  missing target → null
+    /// Option:                  None → None, Some(v) → Some(v.member)
+    /// json property:           indexing, which already gives json null
+    /// Result:                  target? then the access
+    /// ```
+    fn compile_null_safe_member(&mut self, member: &MemberExpr) -> Result<(), Vec<Diagnostic>> {
+        let span = member.span;
+        let kind = member.null_safe.get().unwrap_or(NullSafeKind::Nullable);
+        let mut access = member.clone();
+        access.optional = false;
+
+        if kind == NullSafeKind::Result {
+            *access.target = Expr::Try(TryExpr {
+                expr: member.target.clone(),
+                target_kind: std::cell::RefCell::new(Some(TryTargetKind::Result)),
+                span: member.target.span(),
+            });
+            return self.compile_member(&access);
+        }
+
+        if kind == NullSafeKind::Json && member.args.is_none() {
+            self.compile_expr(&member.target)?;
+            let key_idx = self
+                .bytecode
+                .add_constant(Value::string(&member.member.name));
+            self.bytecode.emit(Opcode::Constant, span);
+            self.bytecode.emit_u16(key_idx);
+            self.bytecode.emit(Opcode::GetIndex, span);
+            return Ok(());
+        }
+
+        // A plain name can be read twice; anything else is evaluated once.
+        // Option targets are always parked, since the access reads the unwrapped value.
+        let temp = match member.target.as_ref() {
+            Expr::Identifier(_) if kind != NullSafeKind::Option => None,
+            target => {
+                self.compile_expr(target)?;
+                let slot = self.global_slot("$null_safe");
+                self.bytecode.emit(Opcode::SetGlobal, span);
+                self.bytecode.emit_u16(slot);
+                self.bytecode.emit(Opcode::Pop, span);
+                *access.target = Expr::Identifier(Identifier {
+                    name: "$null_safe".to_string(),
+                    span: target.span(),
+                });
+                Some(slot)
+            }
+        };
+
+        self.compile_expr(&access.target)?;
+        if kind == NullSafeKind::Option {
+            self.bytecode.emit(Opcode::IsOptionSome, span);
+        } else {
+            self.bytecode.emit(Opcode::IsNullish, span);
+            self.bytecode.emit(Opcode::Not, span);
+        }
+        self.bytecode.emit(Opcode::JumpIfFalse, span);
+        let missing_jump = self.bytecode.current_offset();
+        self.bytecode.emit_u16(0xFFFF); // Placeholder
+
+        if let (NullSafeKind::Option, Some(slot)) = (kind, temp) {
+            // Some(<access of the unwrapped value>)
+            let some_idx = self.global_slot("Some");
+            self.bytecode.emit(Opcode::GetGlobal, span);
+            self.bytecode.emit_u16(some_idx);
+            self.bytecode.emit(Opcode::GetGlobal, span);
+            self.bytecode.emit_u16(slot);
+            self.bytecode.emit(Opcode::ExtractOptionValue, span);
+            self.bytecode.emit(Opcode::SetGlobal, span);
+            self.bytecode.emit_u16(slot);
+            self.bytecode.emit(Opcode::Pop, span);
+            self.compile_member(&access)?;
+            self.bytecode.emit(Opcode::Call, span);
+            self.bytecode.emit_u8(1);
+        } else {
+            self.compile_member(&access)?;
+        }
+        self.bytecode.emit(Opcode::Jump, span);
+        let end_jump = self.bytecode.current_offset();
+        self.bytecode.emit_u16(0xFFFF); // Placeholder
+
+        // Missing target: None stays None, everything else becomes null
+        self.bytecode.patch_jump(missing_jump);
+        if kind == NullSafeKind::Option {
+            self.compile_expr(&access.target)?;
+        } else {
+            self.bytecode.emit(Opcode::Null, span);
+        }
+        self.bytecode.patch_jump(end_jump);
+        Ok(())
+    }
+
     /// Compile a binary expression
     fn compile_binary(&mut self, bin: &BinaryExpr) -> Result<(), Vec<Diagnostic>> {
+        if let Some((operands, ops)) = bin.comparison_chain() {
+            return self.compile_comparison_chain(&operands, &ops, bin.span);
+        }
         // Handle short-circuit evaluation for && and ||
         match bin.op {
             BinaryOp::And => {
@@ -901,6 +1072,37 @@ impl Compiler {
                 self.bytecode.patch_jump(end_jump);
                 Ok(())
             }
+            BinaryOp::Coalesce => {
+                // For ??: Some(v) gives v, null/None/json null give right (evaluated
+                // only then), anything else is kept
+                self.compile_expr(&bin.left)?;
+                self.bytecode.emit(Opcode::Dup, bin.span);
+                self.bytecode.emit(Opcode::IsOptionSome, bin.span);
+                self.bytecode.emit(Opcode::JumpIfFalse, bin.span);
+                let not_some_jump = self.bytecode.current_offset();
+                self.bytecode.emit_u16(0xFFFF); // Placeholder
+
+                self.bytecode.emit(Opcode::ExtractOptionValue, bin.span);
+                self.bytecode.emit(Opcode::Jump, bin.span);
+                let some_jump = self.bytecode.current_offset();
+                self.bytecode.emit_u16(0xFFFF); // Placeholder
+
+                // Not nullish: jump to end, keeping left
+                self.bytecode.patch_jump(not_some_jump);
+                self.bytecode.emit(Opcode::Dup, bin.span);
+                self.bytecode.emit(Opcode::IsNullish, bin.span);
+                self.bytecode.emit(Opcode::JumpIfFalse, bin.span);
+                let keep_jump = self.bytecode.current_offset();
+                self.bytecode.emit_u16(0xFFFF); // Placeholder
+
+                // Left is missing, pop it and eval right
+                self.bytecode.emit(Opcode::Pop, bin.span);
+                self.compile_expr(&bin.right)?;
+
+                self.bytecode.patch_jump(some_jump);
+                self.bytecode.patch_jump(keep_jump);
+                Ok(())
+            }
             _ => {
                 // For all other operators, evaluate both sides
                 self.compile_expr(&bin.left)?;
@@ -919,7 +1121,8 @@ impl Compiler {
                     BinaryOp::Le => Opcode::LessEqual,
                     BinaryOp::Gt => Opcode::Greater,
                     BinaryOp::Ge => Opcode::GreaterEqual,
-                    BinaryOp::And | BinaryOp::Or => unreachable!(), // Handled above
+                    BinaryOp::In => Opcode::Contains,
+                    BinaryOp::And | BinaryOp::Or | BinaryOp::Coalesce => unreachable!(), // Handled above
                 };
                 self.bytecode.emit(opcode, bin.span);
                 Ok(())
@@ -927,6 +1130,52 @@ impl Compiler {
         }
     }
 
+    /// Compile `a < b <= c ...` as `a < b && b <= c ...` with each operand
+    /// evaluated once
+    fn compile_comparison_chain(
+        &mut self,
+        operands: &[&Expr],
+        ops: &[BinaryOp],
+        span: Span,
+    ) -> Result<(), Vec<Diagnostic>> {
+        let mut fail_jumps = Vec::new();
+        self.compile_expr(operands[0])?;
+        for (i, op) in ops.iter().enumerate() {
+            self.compile_expr(operands[i + 1])?;
+            let is_last = i + 1 == ops.len();
+            if !is_last {
+                // [a, b] -> [b, a, b]: keep b for the next comparison
+                self.bytecode.emit(Opcode::Dup, span);
+                self.bytecode.emit(Opcode::Rot3, span);
+                self.bytecode.emit(Opcode::Rot3, span);
+            }
+            let opcode = match op {
+                BinaryOp::Lt => Opcode::Less,
+                BinaryOp::Le => Opcode::LessEqual,
+                BinaryOp::Gt => Opcode::Greater,
+                _ => Opcode::GreaterEqual,
+            };
+            self.bytecode.emit(opcode, span);
+            if !is_last {
+                self.bytecode.emit(Opcode::JumpIfFalse, span);
+                fail_jumps.push(self.bytecode.current_offset());
+                self.bytecode.emit_u16(0xFFFF); // Placeholder
+            }
+        }
+        self.bytecode.emit(Opcode::Jump, span);
+        let end_jump = self.bytecode.current_offset();
+        self.bytecode.emit_u16(0xFFFF); // Placeholder
+
+        // A link failed: drop the operand kept for the next one
+        for jump in fail_jumps {
+            self.bytecode.patch_jump(jump);
+        }
+        self.bytecode.emit(Opcode::Pop, span);
+        self.bytecode.emit(Opcode::False, span);
+        self.bytecode.patch_jump(end_jump);
+        Ok(())
+    }
+
     /// Compile a unary expression
     fn compile_unary(&mut self, un: &UnaryExpr) -> Result<(), Vec<Diagnostic>> {
         // Compile the operand
@@ -1011,21 +1260,21 @@ impl Compiler {
                 self.bytecode.emit_u16(0);
             }
             "Set" => {
-                let name_idx = self.bytecode.add_constant(Value::string("setNew"));
+                let name_idx = self.global_slot("setNew");
                 self.bytecode.emit(Opcode::GetGlobal, span);
                 self.bytecode.emit_u16(name_idx);
                 self.bytecode.emit(Opcode::Call, span);
                 self.bytecode.emit_u8(args.len() as u8);
             }
             "Queue" => {
-                let name_idx = self.bytecode.add_constant(Value::string("queueNew"));
+                let name_idx = self.global_slot("queueNew");
                 self.bytecode.emit(Opcode::GetGlobal, span);
                 self.bytecode.emit_u16(name_idx);
                 self.bytecode.emit(Opcode::Call, span);
                 self.bytecode.emit_u8(args.len() as u8);
             }
             "Stack" => {
-                let name_idx = self.bytecode.add_constant(Value::string("stackNew"));
+                let name_idx = self.global_slot("stackNew");
                 self.bytecode.emit(Opcode::GetGlobal, span);
                 self.bytecode.emit_u16(name_idx);
                 self.bytecode.emit(Opcode::Call, span);
@@ -1099,7 +1348,7 @@ impl Compiler {
 
         // Temp global name for saving the match result during cleanup
         let temp_name = "$match_result";
-        let temp_name_idx = self.bytecode.add_constant(Value::string(temp_name));
+        let temp_name_idx = self.global_slot(temp_name);
 
         let mut arm_end_jumps = Vec::new();
 
@@ -1219,7 +1468,7 @@ impl Compiler {
             }
 
             // Clean up locals tracking
-            self.locals.truncate(locals_before);
+            self.truncate_locals(locals_before);
         }
 
         // Patch all end jumps to point here
@@ -1228,7 +1477,7 @@ impl Compiler {
         }
 
         // Remove the hidden scrutinee placeholder local added before the loop.
-        self.locals.truncate(pre_match_locals);
+        self.truncate_locals(pre_match_locals);
 
         // Stack: [body_result] — match expression produces exactly one value
         Ok(())
@@ -1285,6 +1534,11 @@ impl Compiler {
                 Ok(None) // No jump needed, always matches
             }
 
+            // Literal inside a `{ key: pattern }` field, which may be json
+            Pattern::Literal(lit, lit_span) if self.record_field_depth > 0 => {
+                self.compile_record_literal_pattern(lit, *lit_span)
+            }
+
             // Literal: check equality
             Pattern::Literal(lit, lit_span) => {
                 // Scrutinee copy is on stack. Push literal value for comparison.
@@ -1308,6 +1562,12 @@ impl Compiler {
                     Literal::Null => {
                         self.bytecode.emit(Opcode::Null, *lit_span);
                     }
+                    Literal::Regex { .. } => {
+                        return Err(vec![INTERNAL_ERROR
+                            .emit(*lit_span)
+                            .arg("detail", "regex literals cannot be used as patterns")
+                            .build()]);
+                    }
                 }
 
                 // Compare: pops both (scrutinee copy + literal), pushes bool
@@ -1325,15 +1585,60 @@ impl Compiler {
                 Ok(Some(jump_offset))
             }
 
+            // Range: start <= value < end (or <= end when inclusive)
+            Pattern::Range {
+                start,
+                end,
+                inclusive,
+                span: range_span,
+            } => {
+                // The value is compared twice, so park it in a temp global
+                let name_idx = self.match_temp_slot("$match_range");
+                self.bytecode.emit(Opcode::SetGlobal, *range_span);
+                self.bytecode.emit_u16(name_idx);
+                self.bytecode.emit(Opcode::Pop, *range_span);
+
+                let bounds = [
+                    (*start, Opcode::GreaterEqual),
+                    (
+                        *end,
+                        if *inclusive {
+                            Opcode::LessEqual
+                        } else {
+                            Opcode::Less
+                        },
+                    ),
+                ];
+                for (bound, compare) in bounds {
+                    self.bytecode.emit(Opcode::GetGlobal, *range_span);
+                    self.bytecode.emit_u16(name_idx);
+                    let const_idx = self.bytecode.add_constant(Value::Number(bound));
+                    self.bytecode.emit(Opcode::Constant, *range_span);
+                    self.bytecode.emit_u16(const_idx);
+                    self.bytecode.emit(compare, *range_span);
+                }
+                self.bytecode.emit(Opcode::And, span);
+
+                // Same contract as a literal: jump away on failure, push True on success
+                self.bytecode.emit(Opcode::JumpIfFalse, span);
+                let jump_offset = self.bytecode.current_offset();
+                self.bytecode.emit_u16(0xFFFF);
+                self.bytecode.emit(Opcode::True, span);
+
+                Ok(Some(jump_offset))
+            }
+
             // Constructor: Some(x), None, Ok(x), Err(e)
             Pattern::Constructor { name, args, span } => {
                 self.compile_constructor_pattern(name, args, *span, locals_before)
             }
 
             // Array: [x, y, z]
-            Pattern::Array { elements, span } => {
-                self.compile_array_pattern(elements, *span, locals_before)
-            }
+            Pattern::Array {
+                elements,
+                rest,
+                span,
+            } => self.compile_array_pattern(elements, rest.as_deref(), *span, locals_before),
 
             // Tuple: (x, y, z) — not yet compiled; emit diagnostic stub
             Pattern::Tuple { elements, span } => {
@@ -1562,25 +1867,27 @@ impl Compiler {
         Ok(Some(fail_exit))
     }
 
-    /// Compile array pattern [x, y, z]
+    /// Compile array pattern [x, y, z] or [x, y, ...rest]
     ///
     /// Stack protocol (same as compile_pattern_check):
     /// - INPUT: [copy] (array value) on stack
     /// - SUCCESS: copy consumed, [element_locals...] [True] on stack
     /// - FAILURE: copy consumed, stack clean, jumps to returned offset
     ///
-    /// The array is stored in a temp global ("$match_array") so it can be
+    /// The array is stored in a temp global ("$match_array<depth>") so it can be
     /// accessed for each element without interfering with stack/local positions.
+    /// With a rest pattern the length check becomes `len >= elements.len()` and
+    /// the rest is matched against `SliceFrom(elements.len())`.
     fn compile_array_pattern(
         &mut self,
         elements: &[Pattern],
+        rest: Option<&Pattern>,
         span: Span,
         locals_before: usize,
     ) -> Result<Option<usize>, Vec<Diagnostic>> {
         use crate::bytecode::Opcode;
 
-        let array_global_name = "$match_array";
-        let array_name_idx = self.bytecode.add_constant(Value::string(array_global_name));
+        let array_name_idx = self.match_temp_slot("$match_array");
 
         // Stack: [copy] (the array)
         // Store array to temp global for repeated access
@@ -1609,16 +1916,22 @@ impl Compiler {
         self.bytecode.emit(Opcode::Constant, span);
         self.bytecode.emit_u16(const_idx);
 
-        self.bytecode.emit(Opcode::Equal, span);
+        let length_check = if rest.is_some() {
+            Opcode::GreaterEqual
+        } else {
+            Opcode::Equal
+        };
+        self.bytecode.emit(length_check, span);
 
         self.bytecode.emit(Opcode::JumpIfFalse, span);
         let wrong_length_jump = self.bytecode.current_offset();
         self.bytecode.emit_u16(0xFFFF);
 
-        // Array type and length match. Match each element.
+        // Array type and length match. Match each element, then the rest slice.
         // Track element failure info: (jump_offset, element_locals_above_baseline)
         let mut elem_fail_info: Vec<(usize, usize)> = Vec::new();
 
+        self.pattern_depth += 1;
         for (idx, elem_pattern) in elements.iter().enumerate() {
             // Get array from temp global
             self.bytecode.emit(Opcode::GetGlobal, span);
@@ -1644,6 +1957,20 @@ impl Compiler {
                 elem_fail_info.push((jump, elem_locals));
             }
         }
+        self.pattern_depth -= 1;
+
+        if let Some(rest) = rest {
+            // Stack: [] — push array[elements.len()..] and match it
+            self.bytecode.emit(Opcode::GetGlobal, span);
+            self.bytecode.emit_u16(array_name_idx);
+            self.bytecode.emit(Opcode::Constant, span);
+            self.bytecode.emit_u16(const_idx);
+            self.bytecode.emit(Opcode::SliceFrom, span);
+
+            // Variable or wildcard (the parser allows nothing else): never fails
+            self.compile_pattern_check(rest, span, locals_before)?;
+            self.bytecode.emit(Opcode::Pop, span);
+        }
 
         // All elements matched! Push True (contract).
         self.bytecode.emit(Opcode::True, span);
@@ -1704,8 +2031,7 @@ impl Compiler {
         use crate::bytecode::Opcode;
 
         // Store the tuple in a temp global so we can get each element without consuming it.
-        let tuple_global_name = "$match_tuple";
-        let name_idx = self.bytecode.add_constant(Value::string(tuple_global_name));
+        let name_idx = self.match_temp_slot("$match_tuple");
 
         // INPUT: tuple on TOS
         self.bytecode.emit(Opcode::SetGlobal, span);
@@ -1730,6 +2056,7 @@ impl Compiler {
 
         let mut elem_fail_info: Vec<(usize, usize)> = Vec::new();
 
+        self.pattern_depth += 1;
         for (idx, elem_pattern) in elements.iter().enumerate() {
             // Load tuple from temp global, extract element idx
             self.bytecode.emit(Opcode::GetGlobal, span);
@@ -1748,6 +2075,7 @@ impl Compiler {
                 elem_fail_info.push((jump, elem_locals));
             }
         }
+        self.pattern_depth -= 1;
 
         // All elements matched — push True
         self.bytecode.emit(Opcode::True, span);
@@ -1792,9 +2120,11 @@ impl Compiler {
     /// - FAILURE: copy consumed, stack clean, jumps to returned offset
     ///
     /// Strategy:
-    ///   1. Store scrutinee in `$match_struct` temp global.
-    ///   2. Check `IsStruct`; if named, also `CheckStructType`.
-    ///   3. For each field: GetField from global, compile sub-pattern, Pop True.
+    ///   1. Store scrutinee in `$match_struct<depth>` temp global.
+    ///   2. Named: check `IsStruct` and `CheckStructType`. Anonymous `{ x }`:
+    ///      check the value is any Map (struct, record or HashMap) via `isType`.
+    ///   3. For each field: (anonymous only) fail unless `mapHas`, then GetField
+    ///      from global, compile sub-pattern, Pop True.
     ///   4. All failure paths (type mismatch, field failures) clean up committed
     ///      field locals and converge at `fail_exit`.
     fn compile_struct_pattern(
@@ -1807,8 +2137,7 @@ impl Compiler {
         use crate::ast::Pattern;
         use crate::bytecode::Opcode;
 
-        let struct_global = "$match_struct";
-        let global_name_idx = self.bytecode.add_constant(Value::string(struct_global));
+        let global_name_idx = self.match_temp_slot("$match_struct");
 
         // Stack: [copy]  — move struct to temp global so we can GetField multiple times.
         self.bytecode.emit(Opcode::SetGlobal, span);
@@ -1816,10 +2145,27 @@ impl Compiler {
         self.bytecode.emit(Opcode::Pop, span);
         // Stack: []
 
-        // ── IsStruct check ───────────────────────────────────────────────────────
-        self.bytecode.emit(Opcode::GetGlobal, span);
-        self.bytecode.emit_u16(global_name_idx);
-        self.bytecode.emit(Opcode::IsStruct, span);
+        // ── IsStruct check (named) / map or JSON object check (anonymous) ───────
+        if type_name.is_some() {
+            self.bytecode.emit(Opcode::GetGlobal, span);
+            self.bytecode.emit_u16(global_name_idx);
+            self.bytecode.emit(Opcode::IsStruct, span);
+        } else {
+            // isType(value, "map") || isType(value, "object")
+            let is_type_idx = self.global_slot("isType");
+            for kind in ["map", "object"] {
+                self.bytecode.emit(Opcode::GetGlobal, span);
+                self.bytecode.emit_u16(is_type_idx);
+                self.bytecode.emit(Opcode::GetGlobal, span);
+                self.bytecode.emit_u16(global_name_idx);
+                let kind_idx = self.bytecode.add_constant(Value::string(kind));
+                self.bytecode.emit(Opcode::Constant, span);
+                self.bytecode.emit_u16(kind_idx);
+                self.bytecode.emit(Opcode::Call, span);
+                self.bytecode.emit_u8(2);
+            }
+            self.bytecode.emit(Opcode::Or, span);
+        }
         // Stack: [bool]
         self.bytecode.emit(Opcode::JumpIfFalse, span);
         let not_struct_fail = self.bytecode.current_offset();
@@ -1849,13 +2195,33 @@ impl Compiler {
         // field_fail_info: (fail_jump_offset, locals_committed_count_at_that_point)
         let mut field_fail_info: Vec<(usize, usize)> = Vec::new();
 
+        self.pattern_depth += 1;
         for field in fields {
-            // Load struct, push field value
-            self.bytecode.emit(Opcode::GetGlobal, span);
-            self.bytecode.emit_u16(global_name_idx);
             let field_key_idx = self
                 .bytecode
                 .add_constant(Value::string(field.name.name.clone()));
+
+            // A HashMap or JSON object need not have the key: check before
+            // GetField would fail
+            if type_name.is_none() {
+                let has_field_idx = self.global_slot("hasField");
+                self.bytecode.emit(Opcode::GetGlobal, span);
+                self.bytecode.emit_u16(has_field_idx);
+                self.bytecode.emit(Opcode::GetGlobal, span);
+                self.bytecode.emit_u16(global_name_idx);
+                self.bytecode.emit(Opcode::Constant, span);
+                self.bytecode.emit_u16(field_key_idx);
+                self.bytecode.emit(Opcode::Call, span);
+                self.bytecode.emit_u8(2);
+                self.bytecode.emit(Opcode::JumpIfFalse, span);
+                let missing = self.bytecode.current_offset();
+                self.bytecode.emit_u16(0xFFFF);
+                field_fail_info.push((missing, self.locals.len() - locals_before));
+            }
+
+            // Load struct, push field value
+            self.bytecode.emit(Opcode::GetGlobal, span);
+            self.bytecode.emit_u16(global_name_idx);
             self.bytecode.emit(Opcode::Constant, span);
             self.bytecode.emit_u16(field_key_idx);
             self.bytecode.emit(Opcode::GetField, span);
@@ -1866,7 +2232,14 @@ impl Compiler {
                 .clone()
                 .unwrap_or_else(|| Pattern::Variable(field.name.clone()));
 
-            let field_failed = self.compile_pattern_check(&sub_pattern, span, locals_before)?;
+            if type_name.is_none() {
+                self.record_field_depth += 1;
+            }
+            let field_failed = self.compile_pattern_check(&sub_pattern, span, locals_before);
+            if type_name.is_none() {
+                self.record_field_depth -= 1;
+            }
+            let field_failed = field_failed?;
 
             // Pop the True that a successful sub-pattern pushes
             self.bytecode.emit(Opcode::Pop, span);
@@ -1878,6 +2251,7 @@ impl Compiler {
                 field_fail_info.push((jump, committed));
             }
         }
+        self.pattern_depth -= 1;
 
         // ── Success ───────────────────────────────────────────────────────────────
         self.bytecode.emit(Opcode::True, span);
@@ -1929,6 +2303,113 @@ impl Compiler {
         Ok(Some(fail_exit))
     }
 
+    /// Compile a literal pattern in an anonymous record field: `{ "type": "user" }`.
+    ///
+    /// The field may hold a native value (a Map entry) or a json scalar (a
+    /// JSON object key), so this checks `typeof` first and unwraps json with
+    /// the matching `jsonAs*` before comparing. A json `"1"` never matches `1`.
+    ///
+    /// Contract same as compile_pattern_check.
+    fn compile_record_literal_pattern(
+        &mut self,
+        lit: &Literal,
+        span: Span,
+    ) -> Result<Option<usize>, Vec<Diagnostic>> {
+        use crate::bytecode::Opcode;
+
+        let (kind, unwrap, literal) = match lit {
+            Literal::Number(n) => ("number", "jsonAsNumber", Some(Value::Number(*n))),
+            Literal::String(s) => ("string", "jsonAsString", Some(Value::string(s.clone()))),
+            Literal::Bool(b) => ("boolean", "jsonAsBool", Some(Value::Bool(*b))),
+            // typeof is "null" for both null and json null: nothing left to compare
+            Literal::Null => ("null", "", None),
+            Literal::Regex { .. } => {
+                return Err(vec![INTERNAL_ERROR
+                    .emit(span)
+                    .arg("detail", "regex literals cannot be used as patterns")
+                    .build()]);
+            }
+        };
+
+        let leaf_idx = self.match_temp_slot("$match_leaf");
+        self.bytecode.emit(Opcode::SetGlobal, span);
+        self.bytecode.emit_u16(leaf_idx);
+        self.bytecode.emit(Opcode::Pop, span);
+
+        // typeof(leaf) == kind
+        let typeof_idx = self.global_slot("typeof");
+        self.bytecode.emit(Opcode::GetGlobal, span);
+        self.bytecode.emit_u16(typeof_idx);
+        self.bytecode.emit(Opcode::GetGlobal, span);
+        self.bytecode.emit_u16(leaf_idx);
+        self.bytecode.emit(Opcode::Call, span);
+        self.bytecode.emit_u8(1);
+        let kind_idx = self.bytecode.add_constant(Value::string(kind));
+        self.bytecode.emit(Opcode::Constant, span);
+        self.bytecode.emit_u16(kind_idx);
+        self.bytecode.emit(Opcode::Equal, span);
+        self.bytecode.emit(Opcode::JumpIfFalse, span);
+        let mut fail_jumps = vec![self.bytecode.current_offset()];
+        self.bytecode.emit_u16(0xFFFF);
+
+        if let Some(literal) = literal {
+            // isType(leaf, "json") ? jsonAs*(leaf) : leaf
+            let is_type_idx = self.global_slot("isType");
+            self.bytecode.emit(Opcode::GetGlobal, span);
+            self.bytecode.emit_u16(is_type_idx);
+            self.bytecode.emit(Opcode::GetGlobal, span);
+            self.bytecode.emit_u16(leaf_idx);
+            let json_idx = self.bytecode.add_constant(Value::string("json"));
+            self.bytecode.emit(Opcode::Constant, span);
+            self.bytecode.emit_u16(json_idx);
+            self.bytecode.emit(Opcode::Call, span);
+            self.bytecode.emit_u8(2);
+            self.bytecode.emit(Opcode::JumpIfFalse, span);
+            let native = self.bytecode.current_offset();
+            self.bytecode.emit_u16(0xFFFF);
+
+            let unwrap_idx = self.global_slot(unwrap);
+            self.bytecode.emit(Opcode::GetGlobal, span);
+            self.bytecode.emit_u16(unwrap_idx);
+            self.bytecode.emit(Opcode::GetGlobal, span);
+            self.bytecode.emit_u16(leaf_idx);
+            self.bytecode.emit(Opcode::Call, span);
+            self.bytecode.emit_u8(1);
+            self.bytecode.emit(Opcode::Jump, span);
+            let compare = self.bytecode.current_offset();
+            self.bytecode.emit_u16(0xFFFF);
+
+            self.bytecode.patch_jump(native);
+            self.bytecode.emit(Opcode::GetGlobal, span);
+            self.bytecode.emit_u16(leaf_idx);
+
+            self.bytecode.patch_jump(compare);
+            let const_idx = self.bytecode.add_constant(literal);
+            self.bytecode.emit(Opcode::Constant, span);
+            self.bytecode.emit_u16(const_idx);
+            self.bytecode.emit(Opcode::Equal, span);
+            self.bytecode.emit(Opcode::JumpIfFalse, span);
+            fail_jumps.push(self.bytecode.current_offset());
+            self.bytecode.emit_u16(0xFFFF);
+        }
+
+        self.bytecode.emit(Opcode::True, span);
+        self.bytecode.emit(Opcode::Jump, span);
+        let success_exit = self.bytecode.current_offset();
+        self.bytecode.emit_u16(0xFFFF);
+
+        for jump in fail_jumps {
+            self.bytecode.patch_jump(jump);
+        }
+        self.bytecode.emit(Opcode::Jump, span);
+        let fail_exit = self.bytecode.current_offset();
+        self.bytecode.emit_u16(0xFFFF);
+
+        self.bytecode.patch_jump(success_exit);
+
+        Ok(Some(fail_exit))
+    }
+
     /// Compile enum variant pattern: State::Running, Color::Rgb(r, g, b)
     ///
     /// Contract (same as compile_pattern_check):
@@ -2009,8 +2490,7 @@ impl Compiler {
 
             // Now we treat this like an array pattern match
             // Store data array to temp global
-            let data_global_name = "$match_enum_data";
-            let data_name_idx = self.bytecode.add_constant(Value::string(data_global_name));
+            let data_name_idx = self.match_temp_slot("$match_enum_data");
 
             self.bytecode.emit(Opcode::SetGlobal, span);
             self.bytecode.emit_u16(data_name_idx);
@@ -2035,6 +2515,7 @@ impl Compiler {
             // Match each argument pattern against data elements
             let mut inner_fails = Vec::new();
 
+            self.pattern_depth += 1;
             for (i, arg_pattern) in args.iter().enumerate() {
                 // Get element from data array
                 self.bytecode.emit(Opcode::GetGlobal, span);
@@ -2057,6 +2538,7 @@ impl Compiler {
                 // Pop the True from sub-pattern (we'll emit our own at the end)
                 self.bytecode.emit(Opcode::Pop, span);
             }
+            self.pattern_depth -= 1;
 
             // All patterns matched - emit success True
             self.bytecode.emit(Opcode::True, span);
@@ -2261,6 +2743,7 @@ impl Compiler {
         self.bytecode.emit_u16(0xFFFF); // placeholder
 
         let function_offset = self.bytecode.current_offset();
+        self.suspend_local_ranges();
 
         // --- Compile function body with upvalue tracking ---
         let old_scope = self.scope_depth;
@@ -2268,6 +2751,7 @@ impl Compiler {
         self.scope_depth += 1;
 
         let prev_watermark = std::mem::replace(&mut self.locals_watermark, local_base);
+        let prev_local_base = std::mem::replace(&mut self.current_function_base, local_base);
 
         for param in params {
             self.push_local(Local {
@@ -2279,8 +2763,6 @@ impl Compiler {
             });
         }
 
-        let prev_local_base = std::mem::replace(&mut self.current_function_base, local_base);
-
         self.upvalue_stack.push(UpvalueContext {
             parent_base: prev_local_base,
             captures: Vec::new(),
@@ -2313,13 +2795,15 @@ impl Compiler {
                 .build()]
         })?;
         let upvalues = upvalue_ctx.captures;
+        self.record_upvalue_names(&upvalues, function_offset);
 
         self.current_function_base = prev_local_base;
         let total_local_count = self.locals_watermark - local_base;
         self.locals_watermark = prev_watermark;
 
         self.scope_depth = old_scope;
-        self.locals.truncate(local_base);
+        self.truncate_locals(local_base);
+        self.resume_local_ranges();
 
         // Patch the skip jump to land after the function body.
         self.bytecode.patch_jump(skip_jump);
@@ -2422,7 +2906,7 @@ impl Compiler {
             }
         }
 
-        self.locals.truncate(local_base);
+        self.truncate_locals(local_base);
         self.scope_depth = old_scope;
 
         Ok(())
@@ -0,0 +1,420 @@
+//! Top-level initialization order
+//!
+//! A module's top-level items run once, in declaration order, after every
+//! module it imports has run (the module loader orders modules by their
+//! imports). Functions are defined where they are declared, so a `let`
+//! initializer or top-level statement that reaches a binding declared at or
+//! after it, directly or through the functions it calls, would read an unset
+//! global. Both are rejected before any code runs:
+//! - AT5010 when the chain leads back to the binding being initialized
+//! - AT5011 when it reaches a binding declared further down
+//!
+//! Closures created by top-level code are not followed: they usually run
+//! after initialization has finished.
+
+use crate::ast::*;
+use crate::diagnostic::error_codes::{CYCLIC_INITIALIZATION, USED_BEFORE_INITIALIZATION};
+use crate::diagnostic::Diagnostic;
+use crate::span::Span;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// One diagnostic per top-level item that depends on a binding that is not
+/// yet initialized when the item runs
+pub(super) fn check(program: &Program) -> Vec<Diagnostic> {
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    let mut functions: HashMap<&str, &FunctionDecl> = HashMap::new();
+    for (i, item) in program.items.iter().enumerate() {
+        for name in bound_names(item) {
+            positions.entry(name).or_insert(i);
+        }
+        if let Some(func) = function_of(item) {
+            functions.insert(&func.name.name, func);
+        }
+    }
+
+    let graph = Graph {
+        positions,
+        functions,
+    };
+    program
+        .items
+        .iter()
+        .enumerate()
+        .filter_map(|(i, item)| graph.check_item(i, item))
+        .collect()
+}
+
+/// Top-level bindings and the function bodies that read them
+struct Graph<'a> {
+    /// Index of the item that first declares each top-level name
+    positions: HashMap<&'a str, usize>,
+    functions: HashMap<&'a str, &'a FunctionDecl>,
+}
+
+impl<'a> Graph<'a> {
+    fn check_item(&self, position: usize, item: &'a Item) -> Option<Diagnostic> {
+        let roots = eager_reads(item)?;
+        let own = bound_names(item);
+
+        // Breadth-first over everything the item reads, following calls
+        // into top-level functions wherever they are declared
+        let mut parent: HashMap<&str, Option<&str>> = HashMap::new();
+        let mut root_span: HashMap<&str, Span> = HashMap::new();
+        let mut queue = VecDeque::new();
+        for id in roots {
+            let name = id.name.as_str();
+            if self.positions.contains_key(name) && !parent.contains_key(name) {
+                parent.insert(name, None);
+                root_span.insert(name, id.span);
+                queue.push_back(name);
+            }
+        }
+
+        let mut late = None;
+        while let Some(name) = queue.pop_front() {
+            let declared_at = self.positions[name];
+            if declared_at == position && own.contains(&name) {
+                let cycle = self.chain(&own, &parent, name);
+                return Some(
+                    CYCLIC_INITIALIZATION
+                        .emit(root_span[name])
+                        .arg("name", name)
+                        .arg("cycle", cycle)
+                        .build()
+                        .with_label(format!("leads back to `{}`", name)),
+                );
+            }
+            if late.is_none() && declared_at >= position {
+                late = Some(name);
+            }
+            if let Some(func) = self.functions.get(name) {
+                let span = root_span[name];
+                for id in function_reads(func) {
+                    let next = id.name.as_str();
+                    if self.positions.contains_key(next) && !parent.contains_key(next) {
+                        parent.insert(next, Some(name));
+                        root_span.insert(next, span);
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        late.map(|name| {
+            USED_BEFORE_INITIALIZATION
+                .emit(root_span[name])
+                .arg("name", name)
+                .arg("chain", self.chain(&own, &parent, name))
+                .build()
+                .with_label("used here")
+        })
+    }
+
+    /// `own -> first -> ... -> name`, the path that reached `name`
+    fn chain(&self, own: &[&str], parent: &HashMap<&str, Option<&str>>, name: &str) -> String {
+        let mut path = vec![name];
+        let mut current = name;
+        while let Some(Some(prev)) = parent.get(current) {
+            path.push(prev);
+            current = prev;
+        }
+        path.extend(own.first());
+        path.reverse();
+        path.join(" -> ")
+    }
+}
+
+/// Names an item binds at top level
+fn bound_names(item: &Item) -> Vec<&str> {
+    match item {
+        Item::Function(func) => vec![func.name.name.as_str()],
+        Item::Statement(Stmt::FunctionDecl(func)) => vec![func.name.name.as_str()],
+        Item::Statement(Stmt::VarDecl(var)) => vec![var.name.name.as_str()],
+        Item::Statement(Stmt::LetDestructure(decl)) => {
+            decl.names.iter().map(|id| id.name.as_str()).collect()
+        }
+        Item::Export(export) => match &export.item {
+            ExportItem::Function(func) => vec![func.name.name.as_str()],
+            ExportItem::Variable(var) => vec![var.name.name.as_str()],
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+fn function_of(item: &Item) -> Option<&FunctionDecl> {
+    match item {
+        Item::Function(func) | Item::Statement(Stmt::FunctionDecl(func)) => Some(func),
+        Item::Export(export) => match &export.item {
+            ExportItem::Function(func) => Some(func),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Identifiers read while the item itself runs, or `None` for declarations
+fn eager_reads(item: &Item) -> Option<Vec<&Identifier>> {
+    let mut refs = References::new(false);
+    match item {
+        Item::Statement(Stmt::FunctionDecl(_)) => return None,
+        Item::Statement(Stmt::VarDecl(var)) => refs.expr(&var.init),
+        Item::Statement(Stmt::LetDestructure(decl)) => refs.expr(&decl.init),
+        Item::Statement(stmt) => refs.stmt(stmt),
+        Item::Export(ExportDecl {
+            item: ExportItem::Variable(var),
+            ..
+        }) => refs.expr(&var.init),
+        _ => return None,
+    }
+    Some(refs.finish())
+}
+
+/// Identifiers a function may read when called, including from its closures
+fn function_reads(func: &FunctionDecl) -> Vec<&Identifier> {
+    let mut refs = References::new(true);
+    refs.function(func);
+    refs.finish()
+}
+
+/// Identifier reads in a piece of code, minus the names it binds locally.
+///
+/// Locals are tracked per walk rather than per scope, so a local anywhere in
+/// the code hides a top-level binding of the same name throughout it. That
+/// can miss a dependency but never reports one that is not there.
+struct References<'a> {
+    reads: Vec<&'a Identifier>,
+    locals: HashSet<&'a str>,
+    into_closures: bool,
+}
+
+impl<'a> References<'a> {
+    fn new(into_closures: bool) -> Self {
+        Self {
+            reads: Vec::new(),
+            locals: HashSet::new(),
+            into_closures,
+        }
+    }
+
+    fn finish(self) -> Vec<&'a Identifier> {
+        let locals = self.locals;
+        self.reads
+            .into_iter()
+            .filter(|id| !locals.contains(id.name.as_str()))
+            .collect()
+    }
+
+    fn function(&mut self, func: &'a FunctionDecl) {
+        self.params(&func.params);
+        self.block(&func.body);
+    }
+
+    fn params(&mut self, params: &'a [Param]) {
+        for param in params {
+            self.locals.insert(&param.name.name);
+            if let Some(default) = &param.default_value {
+                self.expr(default);
+            }
+        }
+    }
+
+    fn block(&mut self, block: &'a Block) {
+        for stmt in &block.statements {
+            self.stmt(stmt);
+        }
+        if let Some(tail) = &block.tail_expr {
+            self.expr(tail);
+        }
+    }
+
+    fn stmt(&mut self, stmt: &'a Stmt) {
+        match stmt {
+            Stmt::VarDecl(var) => {
+                self.locals.insert(&var.name.name);
+                self.expr(&var.init);
+            }
+            Stmt::LetDestructure(decl) => {
+                self.locals
+                    .extend(decl.names.iter().map(|id| id.name.as_str()));
+                self.expr(&decl.init);
+            }
+            Stmt::FunctionDecl(func) => {
+                self.locals.insert(&func.name.name);
+                if self.into_closures {
+                    self.function(func);
+                }
+            }
+            Stmt::Assign(assign) => {
+                self.assign_target(&assign.target);
+                self.expr(&assign.value);
+            }
+            Stmt::CompoundAssign(assign) => {
+                self.assign_target(&assign.target);
+                self.expr(&assign.value);
+            }
+            Stmt::If(if_stmt) => {
+                self.expr(&if_stmt.cond);
+                self.block(&if_stmt.then_block);
+                if let Some(else_block) = &if_stmt.else_block {
+                    self.block(else_block);
+                }
+            }
+            Stmt::While(while_stmt) => {
+                self.expr(&while_stmt.cond);
+                self.block(&while_stmt.body);
+            }
+            Stmt::ForIn(for_in) => {
+                self.locals.insert(&for_in.variable.name);
+                self.expr(&for_in.iterable);
+                self.block(&for_in.body);
+            }
+            Stmt::Return(ret) => {
+                if let Some(value) = &ret.value {
+                    self.expr(value);
+                }
+            }
+            Stmt::Break(_) | Stmt::Continue(_) => {}
+            Stmt::Expr(expr_stmt) => self.expr(&expr_stmt.expr),
+            Stmt::Defer(defer) => self.block(&defer.body),
+            Stmt::TryCatch(try_catch) => {
+                self.block(&try_catch.body);
+                if let Some(binding) = &try_catch.error_binding {
+                    self.locals.insert(&binding.name);
+                }
+                self.block(&try_catch.catch_block);
+            }
+        }
+    }
+
+    fn assign_target(&mut self, target: &'a AssignTarget) {
+        match target {
+            AssignTarget::Name(id) => self.reads.push(id),
+            AssignTarget::Index { target, index, .. } => {
+                self.expr(target);
+                self.expr(index);
+            }
+            AssignTarget::Member { target, .. } => self.expr(target),
+        }
+    }
+
+    fn exprs(&mut self, exprs: &'a [Expr]) {
+        for expr in exprs {
+            self.expr(expr);
+        }
+    }
+
+    fn expr(&mut self, expr: &'a Expr) {
+        match expr {
+            Expr::Literal(..) => {}
+            Expr::TemplateString { parts, .. } => {
+                for part in parts {
+                    if let TemplatePart::Expression(expr) = part {
+                        self.expr(expr);
+                    }
+                }
+            }
+            Expr::Identifier(id) => self.reads.push(id),
+            Expr::Unary(unary) => self.expr(&unary.expr),
+            Expr::Binary(binary) => {
+                self.expr(&binary.left);
+                self.expr(&binary.right);
+            }
+            Expr::Call(call) => {
+                self.expr(&call.callee);
+                self.exprs(&call.args);
+            }
+            Expr::Index(index) => {
+                self.expr(&index.target);
+                let IndexValue::Single(value) = &index.index;
+                self.expr(value);
+            }
+            Expr::Member(member) => {
+                self.expr(&member.target);
+                if let Some(args) = &member.args {
+                    self.exprs(args);
+                }
+            }
+            Expr::ArrayLiteral(array) => self.exprs(&array.elements),
+            Expr::ObjectLiteral(object) => {
+                for entry in &object.entries {
+                    self.expr(&entry.value);
+                }
+            }
+            Expr::StructExpr(struct_expr) => {
+                for field in &struct_expr.fields {
+                    self.expr(&field.value);
+                }
+            }
+            Expr::Range { start, end, .. } => {
+                for bound in start.iter().chain(end) {
+                    self.expr(bound);
+                }
+            }
+            Expr::Group(group) => self.expr(&group.expr),
+            Expr::TupleLiteral { elements, .. } => self.exprs(elements),
+            Expr::Match(match_expr) => {
+                self.expr(&match_expr.scrutinee);
+                for arm in &match_expr.arms {
+                    self.pattern(&arm.pattern);
+                    if let Some(guard) = &arm.guard {
+                        self.expr(guard);
+                    }
+                    self.expr(&arm.body);
+                }
+            }
+            Expr::Try(try_expr) => self.expr(&try_expr.expr),
+            Expr::AnonFn { params, body, .. } => {
+                if self.into_closures {
+                    self.params(params);
+                    self.expr(body);
+                }
+            }
+            Expr::Block(block) => self.block(block),
+            Expr::EnumVariant(variant) => {
+                if let Some(args) = &variant.args {
+                    self.exprs(args);
+                }
+            }
+            Expr::Await { expr, .. } => self.expr(expr),
+            Expr::New { args, .. } => self.exprs(args),
+        }
+    }
+
+    /// Record the names a match pattern binds
+    fn pattern(&mut self, pattern: &'a Pattern) {
+        match pattern {
+            Pattern::Literal(..) | Pattern::Wildcard(_) | Pattern::Range { .. } => {}
+            Pattern::Variable(id) => {
+                self.locals.insert(&id.name);
+            }
+            Pattern::Constructor { args, .. }
+            | Pattern::EnumVariant { args, .. }
+            | Pattern::BareVariant { args, .. } => {
+                for arg in args {
+                    self.pattern(arg);
+                }
+            }
+            Pattern::Array { elements, rest, .. } => {
+                for element in elements.iter().chain(rest.as_deref()) {
+                    self.pattern(element);
+                }
+            }
+            Pattern::Tuple { elements, .. } | Pattern::Or(elements, _) => {
+                for element in elements {
+                    self.pattern(element);
+                }
+            }
+            Pattern::Struct { fields, .. } => {
+                for field in fields {
+                    match &field.pattern {
+                        Some(pattern) => self.pattern(pattern),
+                        None => {
+                            self.locals.insert(&field.name.name);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
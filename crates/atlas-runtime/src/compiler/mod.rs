@@ -6,11 +6,15 @@
 //! - Locals are tracked by index (stack slots)
 //! - Globals are tracked by name (string constants)
 
+mod assets;
 mod expr;
+mod init_order;
 mod stmt;
 
+pub use assets::{AssetPolicy, MAX_ASSET_SIZE};
+
 use crate::ast::*;
-use crate::bytecode::{Bytecode, Opcode, Optimizer};
+use crate::bytecode::{Bytecode, LocalDebugInfo, Opcode, Optimizer};
 use crate::diagnostic::Diagnostic;
 use crate::optimizer::{ConstantFoldingPass, DeadCodeEliminationPass, PeepholePass};
 use crate::span::Span;
@@ -31,6 +35,12 @@ pub(super) struct Local {
     pub(super) drop_type: Option<String>,
 }
 
+/// Whether a local is a compiler-introduced temporary (`$match_scrutinee`, `__for_idx`, ...)
+/// that should not be surfaced to debugger clients.
+fn is_compiler_temporary(name: &str) -> bool {
+    name.starts_with('$') || name.starts_with("__")
+}
+
 /// Loop context for break/continue
 #[derive(Debug, Clone)]
 pub(super) struct LoopContext {
@@ -43,6 +53,9 @@ pub(super) struct LoopContext {
     /// has been consumed at runtime (e.g. `$match_scrutinee` for last-arm
     /// consuming patterns like `None => continue`).
     pub(super) phantom_locals: usize,
+    /// Number of enclosing `try` bodies when the loop started.
+    /// break/continue emit a TryEnd for each `try` they jump out of.
+    pub(super) try_depth: usize,
 }
 
 /// How an upvalue is sourced when building a closure.
@@ -75,12 +88,25 @@ pub struct Compiler {
     pub(super) bytecode: Bytecode,
     /// Local variables (stack slots)
     pub(super) locals: Vec<Local>,
+    /// Open debug live range for each entry in `locals` (index into `Bytecode::local_names`).
+    /// `None` for compiler temporaries and for locals suspended inside a nested function body.
+    pub(super) local_ranges: Vec<Option<usize>>,
     /// Current scope depth
     pub(super) scope_depth: usize,
     /// Loop context stack (for break/continue)
     pub(super) loops: Vec<LoopContext>,
+    /// Number of `try` bodies currently being compiled
+    pub(super) try_depth: usize,
+    /// Nesting depth of destructuring match patterns being compiled; keeps
+    /// each level's temp global (`$match_array0`, `$match_array1`, ...) apart
+    pub(super) pattern_depth: usize,
+    /// Number of anonymous `{ key: pattern }` fields being compiled; literals
+    /// inside them also match the json scalars a JSON object holds
+    pub(super) record_field_depth: usize,
     /// Bytecode optimizer (optional)
     optimizer: Option<Optimizer>,
+    /// Time the optimizer took on the last `compile`
+    optimize_time: std::time::Duration,
     /// Monomorphizer for generic functions
     #[allow(dead_code)] // Will be used when generic runtime support is fully integrated
     pub(super) monomorphizer: crate::typechecker::generics::Monomorphizer,
@@ -91,6 +117,8 @@ pub struct Compiler {
     pub(super) current_function_base: usize,
     /// Global variable mutability tracking (true = mutable, false = immutable)
     pub(super) global_mutability: std::collections::HashMap<String, bool>,
+    /// Slot of each name interned into `bytecode.global_names`
+    global_slots: std::collections::HashMap<String, u16>,
     /// High-water mark: maximum self.locals.len() seen within the current function.
     /// Updated whenever a local is pushed. Reset at function start.
     /// Used to compute accurate `local_count` even after match arm truncation.
@@ -116,6 +144,19 @@ pub struct Compiler {
     /// Struct types with a static `new` method: type_name -> true.
     /// Enables `Foo(args)` constructor sugar in compile_call.
     pub(super) constructor_types: std::collections::HashSet<String>,
+    /// Names of the program's top-level functions
+    pub(super) function_names: std::collections::HashSet<String>,
+    /// Files `includeStr`/`includeBytes` may embed; discovered from the
+    /// nearest `atlas.toml` on first use when not set
+    pub(super) asset_policy: Option<AssetPolicy>,
+    /// Directory of the module being compiled; embedded paths are relative to it
+    pub(super) source_dir: Option<std::path::PathBuf>,
+    /// Files embedded by `includeStr`/`includeBytes` so far
+    pub(super) embedded_files: Vec<std::path::PathBuf>,
+    /// Namespace imports: alias -> the source module's exported value names.
+    /// `ns.member` compiles to the global `"ns.member"`, which the module
+    /// linker points at the exporting module's global.
+    pub(super) namespace_imports: std::collections::HashMap<String, Vec<String>>,
 }
 
 impl Compiler {
@@ -124,13 +165,19 @@ impl Compiler {
         Self {
             bytecode: Bytecode::new(),
             locals: Vec::new(),
+            local_ranges: Vec::new(),
             scope_depth: 0,
             loops: Vec::new(),
+            try_depth: 0,
+            pattern_depth: 0,
+            record_field_depth: 0,
             optimizer: None, // Optimization disabled by default
+            optimize_time: std::time::Duration::ZERO,
             monomorphizer: crate::typechecker::generics::Monomorphizer::new(),
             next_func_id: 0,
             current_function_base: 0,
             global_mutability: std::collections::HashMap::new(),
+            global_slots: std::collections::HashMap::new(),
             locals_watermark: 0,
             upvalue_stack: Vec::new(),
             trait_default_methods: std::collections::HashMap::new(),
@@ -139,6 +186,11 @@ impl Compiler {
             enum_variants: std::collections::HashMap::new(),
             const_values: std::collections::HashMap::new(),
             constructor_types: std::collections::HashSet::new(),
+            function_names: std::collections::HashSet::new(),
+            asset_policy: None,
+            source_dir: None,
+            embedded_files: Vec::new(),
+            namespace_imports: std::collections::HashMap::new(),
         }
     }
 
@@ -154,13 +206,19 @@ impl Compiler {
         Self {
             bytecode: Bytecode::new(),
             locals: Vec::new(),
+            local_ranges: Vec::new(),
             scope_depth: 0,
             loops: Vec::new(),
+            try_depth: 0,
+            pattern_depth: 0,
+            record_field_depth: 0,
             optimizer: Some(optimizer),
+            optimize_time: std::time::Duration::ZERO,
             monomorphizer: crate::typechecker::generics::Monomorphizer::new(),
             next_func_id: 0,
             current_function_base: 0,
             global_mutability: std::collections::HashMap::new(),
+            global_slots: std::collections::HashMap::new(),
             locals_watermark: 0,
             upvalue_stack: Vec::new(),
             trait_default_methods: std::collections::HashMap::new(),
@@ -169,6 +227,11 @@ impl Compiler {
             enum_variants: std::collections::HashMap::new(),
             const_values: std::collections::HashMap::new(),
             constructor_types: std::collections::HashSet::new(),
+            function_names: std::collections::HashSet::new(),
+            asset_policy: None,
+            source_dir: None,
+            embedded_files: Vec::new(),
+            namespace_imports: std::collections::HashMap::new(),
         }
     }
 
@@ -177,6 +240,29 @@ impl Compiler {
         self.optimizer = optimizer;
     }
 
+    /// Time the optimizer took on the last `compile`, included in its total
+    pub fn optimize_time(&self) -> std::time::Duration {
+        self.optimize_time
+    }
+
+    /// Files `includeStr`/`includeBytes` embedded into the bytecode, whose
+    /// contents it depends on besides the source
+    pub fn embedded_files(&self) -> &[std::path::PathBuf] {
+        &self.embedded_files
+    }
+
+    /// Set which files `includeStr`/`includeBytes` may embed, instead of
+    /// discovering them from the nearest `atlas.toml`
+    pub fn set_asset_policy(&mut self, policy: Option<AssetPolicy>) {
+        self.asset_policy = policy;
+    }
+
+    /// Set the directory embedded paths are resolved against (the directory
+    /// of the source file being compiled)
+    pub fn set_source_dir(&mut self, dir: impl Into<std::path::PathBuf>) {
+        self.source_dir = Some(dir.into());
+    }
+
     /// Register all variants of an enum declaration for bare constructor resolution.
     pub fn register_enum_variants(&mut self, decl: &crate::ast::EnumDecl) {
         for variant in &decl.variants {
@@ -232,6 +318,45 @@ impl Compiler {
         }
     }
 
+    /// Register namespace imports (`import ns from "..."` / `import * as ns`)
+    ///
+    /// Called before compile() so `ns.member` and bare `ns` compile against
+    /// the source module's exports.
+    pub fn register_namespace_imports(
+        &mut self,
+        imports: &[crate::ast::ImportDecl],
+        module_path: &std::path::Path,
+        registry: &crate::module_loader::ModuleRegistry,
+    ) {
+        use crate::ast::ImportSpecifier;
+        use crate::binder::Binder;
+
+        for import_decl in imports {
+            let source_path = Binder::resolve_import_path(&import_decl.source, module_path);
+            let source_sym = registry
+                .get(&source_path)
+                .or_else(|| registry.get(&source_path.with_extension("atlas")))
+                .or_else(|| registry.get(&source_path.with_extension("atl")));
+            let Some(source_symbols) = source_sym else {
+                continue;
+            };
+
+            for specifier in &import_decl.specifiers {
+                if let ImportSpecifier::Namespace { alias, .. } = specifier {
+                    // Consts are inlined at their use sites and have no global
+                    let mut members: Vec<String> = source_symbols
+                        .get_exports()
+                        .into_iter()
+                        .filter(|(_, symbol)| symbol.kind != crate::symbol::SymbolKind::Const)
+                        .map(|(name, _)| name)
+                        .collect();
+                    members.sort();
+                    self.namespace_imports.insert(alias.name.clone(), members);
+                }
+            }
+        }
+    }
+
     /// Register const values from the program for inlining.
     /// Called before compile() to evaluate const expressions at compile time.
     pub fn register_consts(&mut self, program: &Program) {
@@ -266,6 +391,13 @@ impl Compiler {
                 Literal::String(s) => Some(Value::string(s)),
                 Literal::Bool(b) => Some(Value::Bool(*b)),
                 Literal::Null => Some(Value::Null),
+                #[cfg(not(feature = "regex"))]
+                Literal::Regex { .. } => None,
+                #[cfg(feature = "regex")]
+                Literal::Regex { pattern, flags } => {
+                    let regex = crate::stdlib::regex::compile_literal(pattern, flags).ok()?;
+                    Some(Value::Regex(std::sync::Arc::new(regex)))
+                }
             },
             Expr::Identifier(id) => {
                 // Reference to another const
@@ -319,13 +451,35 @@ impl Compiler {
 
     /// Compile an AST to bytecode
     pub fn compile(&mut self, program: &Program) -> Result<Bytecode, Vec<Diagnostic>> {
-        // Check if program defines a zero-arg fn main()
-        let has_main = program.items.iter().any(|item| {
-            matches!(item,
-                Item::Function(f) if f.name.name == "main" && f.params.is_empty()
-            )
+        // Top-level code that would read a global before it is set never runs
+        let init_errors = init_order::check(program);
+        if !init_errors.is_empty() {
+            return Err(init_errors);
+        }
+
+        // Arity of the entry point, if the program defines `fn main()` or
+        // `fn main(args: string[])`
+        let main_arity = program.items.iter().find_map(|item| match item {
+            Item::Function(f) if f.name.name == "main" && f.params.len() <= 1 => {
+                Some(f.params.len())
+            }
+            _ => None,
         });
 
+        // Top-level functions may shadow compile-time builtins like includeStr
+        self.function_names = program
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Function(f) => Some(f.name.name.clone()),
+                Item::Export(export) => match &export.item {
+                    crate::ast::ExportItem::Function(f) => Some(f.name.name.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+
         self.trait_default_methods.clear();
         for item in &program.items {
             if let Item::Trait(trait_decl) = item {
@@ -343,39 +497,92 @@ impl Compiler {
         // Collect const values for inlining
         self.register_consts(program);
 
+        // Locals carried over from a previous compile are live again in this chunk
+        self.resume_local_ranges();
+
         // Compile all top-level items
         for item in &program.items {
             self.compile_item(item)?;
         }
 
-        // H-068: Auto-call fn main() if defined (zero-arg)
-        if has_main {
-            let name_idx = self
-                .bytecode
-                .add_constant(crate::value::Value::string("main"));
+        // H-068: Auto-call fn main() if defined. `main(args)` receives the
+        // program arguments, the same array `process.args()` returns.
+        if let Some(arity) = main_arity {
+            let name_idx = self.global_slot("main");
             self.bytecode.emit(Opcode::GetGlobal, Span::dummy());
             self.bytecode.emit_u16(name_idx);
+            if arity == 1 {
+                let args_idx =
+                    self.bytecode
+                        .add_constant(crate::value::Value::Builtin(std::sync::Arc::from(
+                            "getProcessArgs",
+                        )));
+                self.bytecode.emit(Opcode::Constant, Span::dummy());
+                self.bytecode.emit_u16(args_idx);
+                self.bytecode.emit(Opcode::Call, Span::dummy());
+                self.bytecode.emit_u8(0);
+            }
             self.bytecode.emit(Opcode::Call, Span::dummy());
-            self.bytecode.emit_u8(0);
+            self.bytecode.emit_u8(arity as u8);
         }
 
         // Emit halt at the end
         self.bytecode.emit(Opcode::Halt, Span::dummy());
+        self.suspend_local_ranges();
 
-        // Take ownership of the bytecode
+        // Take ownership of the bytecode; its global slots go with it
         let mut bytecode = std::mem::take(&mut self.bytecode);
+        self.global_slots.clear();
 
         // Record peak local count so the VM can initialize the main frame correctly.
         bytecode.top_level_local_count = self.locals_watermark;
 
         // Apply optimization if enabled
         if let Some(ref optimizer) = self.optimizer {
+            let start = std::time::Instant::now();
             bytecode = optimizer.optimize(bytecode);
+            self.optimize_time = start.elapsed();
         }
 
         Ok(bytecode)
     }
 
+    /// Operand for `GetGlobal`/`SetGlobal` naming `name`
+    pub(super) fn global_slot(&mut self, name: &str) -> u16 {
+        if let Some(&slot) = self.global_slots.get(name) {
+            return slot;
+        }
+        // Every name goes through `global_slots`, so an unknown one is new
+        let slot = self.bytecode.global_names.len() as u16;
+        self.bytecode.global_names.push(name.to_string());
+        self.global_slots.insert(name.to_string(), slot);
+        slot
+    }
+
+    /// Slot of the temp global a destructuring pattern parks its scrutinee
+    /// in, distinct per nesting level so inner patterns leave it intact
+    pub(super) fn match_temp_slot(&mut self, base: &str) -> u16 {
+        let name = format!("{}{}", base, self.pattern_depth);
+        self.global_slot(&name)
+    }
+
+    /// Compile an AST and write the bytecode to a `.atbc` file, which
+    /// [`VM::from_file`](crate::vm::VM::from_file) runs without the source
+    pub fn compile_to_file(
+        &mut self,
+        program: &Program,
+        path: &std::path::Path,
+    ) -> Result<Bytecode, Vec<Diagnostic>> {
+        let bytecode = self.compile(program)?;
+        bytecode.write_to_file(path).map_err(|e| {
+            vec![crate::diagnostic::error_codes::IO_ERROR
+                .emit(Span::dummy())
+                .arg("detail", format!("{}: {}", path.display(), e))
+                .build()]
+        })?;
+        Ok(bytecode)
+    }
+
     /// Compile a top-level item
     fn compile_item(&mut self, item: &Item) -> Result<(), Vec<Diagnostic>> {
         match item {
@@ -493,9 +700,7 @@ impl Compiler {
         self.bytecode.emit_u16(const_idx);
 
         // Store function as a global variable (so it can be called)
-        let name_idx = self
-            .bytecode
-            .add_constant(crate::value::Value::string(&func.name.name));
+        let name_idx = self.global_slot(&func.name.name);
         self.bytecode.emit(Opcode::SetGlobal, func.span);
         self.bytecode.emit_u16(name_idx);
         self.bytecode.emit(Opcode::Pop, func.span);
@@ -507,6 +712,7 @@ impl Compiler {
 
         // NOW record the function body offset (after all setup code)
         let function_offset = self.bytecode.current_offset();
+        self.suspend_local_ranges();
 
         // Now compile the function body at function_offset
         // The function body is compiled inline in the bytecode
@@ -523,6 +729,9 @@ impl Compiler {
         // Reset watermark for this function. Save previous value for nesting.
         let prev_watermark = std::mem::replace(&mut self.locals_watermark, old_locals_len);
 
+        // Track function base for nested function support
+        let prev_function_base = std::mem::replace(&mut self.current_function_base, old_locals_len);
+
         // Add parameters as locals
         for param in &func.params {
             self.push_local(Local {
@@ -534,9 +743,6 @@ impl Compiler {
             });
         }
 
-        // Track function base for nested function support
-        let prev_function_base = std::mem::replace(&mut self.current_function_base, old_locals_len);
-
         // Set async context so Stmt::Return and implicit Returns emit WrapFuture first.
         let prev_in_async = std::mem::replace(&mut self.in_async_fn, func.is_async);
 
@@ -602,7 +808,8 @@ impl Compiler {
 
         // Restore scope and locals
         self.scope_depth = old_scope;
-        self.locals.truncate(old_locals_len);
+        self.truncate_locals(old_locals_len);
+        self.resume_local_ranges();
 
         // Update the FunctionRef in constants with accurate local_count and ownership metadata
         let updated_ref = crate::value::FunctionRef {
@@ -728,9 +935,7 @@ impl Compiler {
         self.bytecode.emit(Opcode::Constant, span);
         self.bytecode.emit_u16(const_idx);
 
-        let name_idx = self
-            .bytecode
-            .add_constant(crate::value::Value::string(mangled_name));
+        let name_idx = self.global_slot(mangled_name);
         self.bytecode.emit(Opcode::SetGlobal, span);
         self.bytecode.emit_u16(name_idx);
         self.bytecode.emit(Opcode::Pop, span);
@@ -741,11 +946,13 @@ impl Compiler {
         self.bytecode.emit_u16(0xFFFF);
 
         let function_offset = self.bytecode.current_offset();
+        self.suspend_local_ranges();
 
         let old_locals_len = self.locals.len();
         let old_scope = self.scope_depth;
         self.scope_depth += 1;
         let prev_watermark = std::mem::replace(&mut self.locals_watermark, old_locals_len);
+        let prev_function_base = std::mem::replace(&mut self.current_function_base, old_locals_len);
 
         for param in &method.params {
             self.push_local(Local {
@@ -757,8 +964,6 @@ impl Compiler {
             });
         }
 
-        let prev_function_base = std::mem::replace(&mut self.current_function_base, old_locals_len);
-
         self.compile_block(&method.body)?;
 
         self.current_function_base = prev_function_base;
@@ -777,7 +982,8 @@ impl Compiler {
         self.bytecode.emit(Opcode::Return, span);
 
         self.scope_depth = old_scope;
-        self.locals.truncate(old_locals_len);
+        self.truncate_locals(old_locals_len);
+        self.resume_local_ranges();
 
         let updated_ref = crate::value::FunctionRef {
             name: mangled_name.to_string(),
@@ -801,12 +1007,70 @@ impl Compiler {
 
     /// Push a local variable, updating the high-water mark for accurate `local_count`.
     pub(super) fn push_local(&mut self, local: Local) {
+        let range = if is_compiler_temporary(&local.name) {
+            None
+        } else {
+            let slot = self.locals.len() - self.current_function_base;
+            Some(self.bytecode.begin_local_range(local.name.clone(), slot))
+        };
         self.locals.push(local);
+        self.local_ranges.push(range);
         if self.locals.len() > self.locals_watermark {
             self.locals_watermark = self.locals.len();
         }
     }
 
+    /// Drop locals above `len`, closing their debug live ranges at the current offset.
+    pub(super) fn truncate_locals(&mut self, len: usize) {
+        if len < self.local_ranges.len() {
+            for index in self.local_ranges.drain(len..).flatten() {
+                self.bytecode.end_local_range(index);
+            }
+        }
+        self.locals.truncate(len);
+    }
+
+    /// Close every open local live range. Called on entering a nested function body,
+    /// whose instructions belong to a different frame.
+    pub(super) fn suspend_local_ranges(&mut self) {
+        for range in &mut self.local_ranges {
+            if let Some(index) = range.take() {
+                self.bytecode.end_local_range(index);
+            }
+        }
+    }
+
+    /// Reopen live ranges for the current function's locals after a nested function body.
+    pub(super) fn resume_local_ranges(&mut self) {
+        let base = self.current_function_base;
+        for (abs_idx, local) in self.locals.iter().enumerate().skip(base) {
+            if self.local_ranges[abs_idx].is_none() && !is_compiler_temporary(&local.name) {
+                self.local_ranges[abs_idx] = Some(
+                    self.bytecode
+                        .begin_local_range(local.name.clone(), abs_idx - base),
+                );
+            }
+        }
+    }
+
+    /// Record the names of a closure's captured upvalues over its body.
+    pub(super) fn record_upvalue_names(
+        &mut self,
+        upvalues: &[(String, UpvalueCapture)],
+        function_offset: usize,
+    ) {
+        let end_offset = self.bytecode.current_offset();
+        for (index, (name, _)) in upvalues.iter().enumerate() {
+            self.bytecode.local_names.push(LocalDebugInfo {
+                name: name.clone(),
+                slot: index,
+                start_offset: function_offset,
+                end_offset,
+                captured: true,
+            });
+        }
+    }
+
     /// B37-P02: Emit drop calls for locals going out of scope.
     /// Iterates from `end` down to `start` (exclusive) in LIFO order.
     /// For each local with drop_type, emits: GetLocal(idx), Call(__impl__Type__Drop__drop)
@@ -827,9 +1091,7 @@ impl Compiler {
 
                     // Call mangled drop function: __impl__TypeName__Drop__drop
                     let mangled = format!("__impl__{type_name}__Drop__drop");
-                    let name_idx = self
-                        .bytecode
-                        .add_constant(crate::value::Value::string(&mangled));
+                    let name_idx = self.global_slot(&mangled);
                     self.bytecode.emit(Opcode::GetGlobal, span);
                     self.bytecode.emit_u16(name_idx);
                     self.bytecode.emit(Opcode::Call, span);
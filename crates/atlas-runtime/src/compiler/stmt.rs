@@ -32,6 +32,7 @@ impl Compiler {
         self.bytecode.emit_u16(0xFFFF); // Placeholder
 
         let function_offset = self.bytecode.current_offset();
+        self.suspend_local_ranges();
 
         // --- Phase 2: Compile function body with upvalue tracking ---
         let old_scope = self.scope_depth;
@@ -39,6 +40,7 @@ impl Compiler {
         self.scope_depth += 1;
 
         let prev_watermark = std::mem::replace(&mut self.locals_watermark, local_base);
+        let prev_local_base = std::mem::replace(&mut self.current_function_base, local_base);
 
         for param in &func.params {
             self.push_local(Local {
@@ -50,8 +52,6 @@ impl Compiler {
             });
         }
 
-        let prev_local_base = std::mem::replace(&mut self.current_function_base, local_base);
-
         // Register as async fn so call sites emit AsyncCall.
         if func.is_async {
             self.async_fn_names.insert(func.name.name.clone());
@@ -78,13 +78,15 @@ impl Compiler {
                 .build()]
         })?;
         let upvalues = upvalue_ctx.captures;
+        self.record_upvalue_names(&upvalues, function_offset);
 
         self.current_function_base = prev_local_base;
         let total_local_count = self.locals_watermark - local_base;
         self.locals_watermark = prev_watermark;
 
         self.scope_depth = old_scope;
-        self.locals.truncate(local_base);
+        self.truncate_locals(local_base);
+        self.resume_local_ranges();
 
         // --- Phase 3: Patch the skip jump ---
         self.bytecode.patch_jump(skip_jump);
@@ -159,16 +161,12 @@ impl Compiler {
         // Store globally (for sibling access) and as a local in the outer function's scope
         if self.scope_depth == 0 {
             // Top-level fallback (compile_nested_function normally not called at scope 0)
-            let name_idx = self
-                .bytecode
-                .add_constant(crate::value::Value::string(&func.name.name));
+            let name_idx = self.global_slot(&func.name.name);
             self.bytecode.emit(Opcode::SetGlobal, func.span);
             self.bytecode.emit_u16(name_idx);
             self.bytecode.emit(Opcode::Pop, func.span);
         } else {
-            let scoped_name_idx = self
-                .bytecode
-                .add_constant(crate::value::Value::string(&scoped_name));
+            let scoped_name_idx = self.global_slot(&scoped_name);
             self.bytecode.emit(Opcode::SetGlobal, func.span);
             self.bytecode.emit_u16(scoped_name_idx);
 
@@ -187,6 +185,7 @@ impl Compiler {
 
     /// Compile a statement
     pub(super) fn compile_stmt(&mut self, stmt: &Stmt) -> Result<(), Vec<Diagnostic>> {
+        self.bytecode.mark_statement_start();
         match stmt {
             Stmt::VarDecl(decl) => self.compile_var_decl(decl),
             Stmt::LetDestructure(d) => self.compile_let_destructure(d),
@@ -228,6 +227,7 @@ impl Compiler {
             Stmt::Continue(span) => self.compile_continue(*span),
             Stmt::CompoundAssign(compound) => self.compile_compound_assign(compound),
             Stmt::Defer(defer) => self.compile_defer(defer),
+            Stmt::TryCatch(try_catch) => self.compile_try_catch(try_catch),
         }
     }
 
@@ -241,7 +241,7 @@ impl Compiler {
             // SetGlobal uses peek() to support assignment expressions like x = y = 5,
             // but for variable declarations we need to pop the value to avoid polluting
             // the stack (which would corrupt local variable indices)
-            let name_idx = self.bytecode.add_constant(Value::string(&decl.name.name));
+            let name_idx = self.global_slot(&decl.name.name);
             self.bytecode.emit(Opcode::SetGlobal, decl.span);
             self.bytecode.emit_u16(name_idx);
             self.bytecode.emit(Opcode::Pop, decl.span);
@@ -266,32 +266,61 @@ impl Compiler {
         Ok(())
     }
 
-    /// Compile a tuple destructuring declaration: `let (a, b) = expr;`
+    /// Compile a destructuring declaration: `let (a, b) = expr;`,
+    /// `let [a, ...rest] = expr;` or `let { a } = expr;`
+    ///
+    /// Mismatches surface as the usual runtime errors: a too-short array fails
+    /// with OutOfBounds, a missing key with a field error. Extra elements are
+    /// ignored.
     fn compile_let_destructure(&mut self, d: &LetDestructure) -> Result<(), Vec<Diagnostic>> {
-        // Evaluate RHS — leaves tuple on stack
+        // Evaluate RHS — leaves the value on stack
         self.compile_expr(&d.init)?;
 
-        // Register the tuple as a hidden temp local so we can GetLocal it repeatedly.
-        // Stack: [tuple]  — tuple is at index `tuple_local_idx` from the frame base.
-        let tuple_local_idx = self.locals.len();
+        // Register the value as a hidden temp local so we can GetLocal it repeatedly.
+        // Stack: [value]  — value is at index `temp_local_idx` from the frame base.
+        let temp_local_idx = self.locals.len();
         self.push_local(Local {
-            name: "__tuple_destructure_temp__".to_string(),
+            name: "__destructure_temp__".to_string(),
             depth: self.scope_depth,
             mutable: false,
             scoped_name: None,
-            drop_type: None, // temp local, tuple itself doesn't need drop
+            drop_type: None, // temp local, the value itself doesn't need drop
         });
 
         // GetLocal index must be function-relative (offset from current_function_base).
-        let tuple_rel_idx = (tuple_local_idx - self.current_function_base) as u16;
+        let temp_rel_idx = (temp_local_idx - self.current_function_base) as u16;
 
-        // For each binding: load tuple via GetLocal, extract element, register as local.
-        // Stack after all iterations: [tuple, elem0, elem1, ...]
+        // For each binding: load the value via GetLocal, extract the part, register as local.
+        // Stack after all iterations: [value, part0, part1, ...]
+        let rest = matches!(d.kind, DestructureKind::Array { rest: true });
+        let positional = d.names.len() - usize::from(rest);
         for (idx, name) in d.names.iter().enumerate() {
             self.bytecode.emit(Opcode::GetLocal, d.span);
-            self.bytecode.emit_u16(tuple_rel_idx);
-            self.bytecode.emit(Opcode::TupleGet, d.span);
-            self.bytecode.emit_u16(idx as u16);
+            self.bytecode.emit_u16(temp_rel_idx);
+            match d.kind {
+                DestructureKind::Tuple => {
+                    self.bytecode.emit(Opcode::TupleGet, d.span);
+                    self.bytecode.emit_u16(idx as u16);
+                }
+                DestructureKind::Array { .. } => {
+                    let const_idx = self.bytecode.add_constant(Value::Number(idx as f64));
+                    self.bytecode.emit(Opcode::Constant, d.span);
+                    self.bytecode.emit_u16(const_idx);
+                    // The rest binding (always last) takes value[positional..]
+                    let op = if idx < positional {
+                        Opcode::GetIndex
+                    } else {
+                        Opcode::SliceFrom
+                    };
+                    self.bytecode.emit(op, d.span);
+                }
+                DestructureKind::Map => {
+                    let key_idx = self.bytecode.add_constant(Value::string(name.name.clone()));
+                    self.bytecode.emit(Opcode::Constant, d.span);
+                    self.bytecode.emit_u16(key_idx);
+                    self.bytecode.emit(Opcode::GetField, d.span);
+                }
+            }
             self.push_local(Local {
                 name: name.name.clone(),
                 depth: self.scope_depth,
@@ -371,7 +400,7 @@ impl Compiler {
 
                     // Compile value and emit SetGlobal
                     self.compile_expr(&assign.value)?;
-                    let name_idx = self.bytecode.add_constant(Value::string(&ident.name));
+                    let name_idx = self.global_slot(&ident.name);
                     self.bytecode.emit(Opcode::SetGlobal, assign.span);
                     self.bytecode.emit_u16(name_idx);
                 }
@@ -442,7 +471,7 @@ impl Compiler {
         for _ in 0..then_locals_to_pop {
             self.bytecode.emit(Opcode::Pop, if_stmt.span);
         }
-        self.locals.truncate(locals_before_then);
+        self.truncate_locals(locals_before_then);
 
         if let Some(else_block) = &if_stmt.else_block {
             // Jump over else branch
@@ -464,7 +493,7 @@ impl Compiler {
             for _ in 0..else_locals_to_pop {
                 self.bytecode.emit(Opcode::Pop, if_stmt.span);
             }
-            self.locals.truncate(locals_before_else);
+            self.truncate_locals(locals_before_else);
 
             // Patch the else jump
             self.bytecode.patch_jump(else_jump);
@@ -547,6 +576,10 @@ impl Compiler {
         match stmt {
             Stmt::Return(_) => true,
             Stmt::If(if_stmt) => self.if_always_returns(if_stmt),
+            // A try/catch discards tail expressions, so only explicit returns count
+            Stmt::TryCatch(try_catch) => [&try_catch.body, &try_catch.catch_block]
+                .iter()
+                .all(|b| b.tail_expr.is_none() && self.block_always_returns(b)),
             // H-278: Expression statements that call functions returning `never` terminate
             Stmt::Expr(expr_stmt) => Self::expr_returns_never(&expr_stmt.expr),
             _ => false,
@@ -582,6 +615,7 @@ impl Compiler {
             break_jumps: Vec::new(),
             locals_at_body_start: locals_before,
             phantom_locals: 0,
+            try_depth: self.try_depth,
         });
 
         // Compile condition
@@ -603,7 +637,7 @@ impl Compiler {
             self.bytecode.emit(Opcode::Pop, while_stmt.span);
         }
         // Remove locals from compile-time tracking so next iteration re-declares them
-        self.locals.truncate(locals_before);
+        self.truncate_locals(locals_before);
 
         // Loop back to condition
         // Offset needs to account for the Loop instruction (1 byte) + offset operand (2 bytes) = 3 bytes
@@ -651,8 +685,9 @@ impl Compiler {
 
         // ── Init: Push 4 values; each stays on stack as its local slot ─────────
 
-        // __for_arr = iterable
+        // __for_arr = iterable (maps and sets are snapshotted into arrays)
         self.compile_expr(&for_in_stmt.iterable)?;
+        self.bytecode.emit(Opcode::ForInPrepare, span);
         let arr_rel = (self.locals.len() - self.current_function_base) as u16;
         self.push_local(Local {
             name: "__for_arr".to_string(),
@@ -729,6 +764,7 @@ impl Compiler {
             break_jumps: Vec::new(),
             locals_at_body_start: 0, // updated below
             phantom_locals: 0,
+            try_depth: self.try_depth,
         });
 
         // if idx < len → continue; else jump to cleanup
@@ -767,7 +803,7 @@ impl Compiler {
         for _ in 0..body_locals_to_pop {
             self.bytecode.emit(Opcode::Pop, span);
         }
-        self.locals.truncate(locals_before_body);
+        self.truncate_locals(locals_before_body);
 
         // ── Loop back to increment ────────────────────────────────────────────
         let offset = increment_start as i32 - (self.bytecode.current_offset() as i32 + 3);
@@ -801,7 +837,7 @@ impl Compiler {
         self.bytecode.emit(Opcode::Pop, span); // sentinel
 
         // Remove hidden locals from compile-time tracking
-        self.locals.truncate(locals_before);
+        self.truncate_locals(locals_before);
 
         Ok(())
     }
@@ -854,7 +890,7 @@ impl Compiler {
                     }
 
                     // Get current value
-                    let name_idx = self.bytecode.add_constant(Value::string(&ident.name));
+                    let name_idx = self.global_slot(&ident.name);
                     self.bytecode.emit(Opcode::GetGlobal, compound.span);
                     self.bytecode.emit_u16(name_idx);
                 }
@@ -877,7 +913,7 @@ impl Compiler {
                     self.bytecode.emit(Opcode::SetLocal, compound.span);
                     self.bytecode.emit_u16(local_idx as u16);
                 } else {
-                    let name_idx = self.bytecode.add_constant(Value::string(&ident.name));
+                    let name_idx = self.global_slot(&ident.name);
                     self.bytecode.emit(Opcode::SetGlobal, compound.span);
                     self.bytecode.emit_u16(name_idx);
                 }
@@ -993,7 +1029,7 @@ impl Compiler {
                 self.bytecode.emit(Opcode::SetLocal, span);
                 self.bytecode.emit_u16(local_idx as u16);
             } else {
-                let name_idx = self.bytecode.add_constant(Value::string(&ident.name));
+                let name_idx = self.global_slot(&ident.name);
                 self.bytecode.emit(Opcode::SetGlobal, span);
                 self.bytecode.emit_u16(name_idx);
             }
@@ -1019,7 +1055,7 @@ impl Compiler {
                     self.bytecode.emit(Opcode::SetLocal, span);
                     self.bytecode.emit_u16(local_idx as u16);
                 } else {
-                    let name_idx = self.bytecode.add_constant(Value::string(&ident.name));
+                    let name_idx = self.global_slot(&ident.name);
                     self.bytecode.emit(Opcode::SetGlobal, span);
                     self.bytecode.emit_u16(name_idx);
                 }
@@ -1136,6 +1172,7 @@ impl Compiler {
         for _ in 0..body_locals {
             self.bytecode.emit(Opcode::Pop, span);
         }
+        self.emit_try_exits(span);
         if let Some(loop_ctx) = self.loops.last_mut() {
             self.bytecode.emit(Opcode::Jump, span);
             let jump_offset = self.bytecode.current_offset();
@@ -1161,6 +1198,7 @@ impl Compiler {
             for _ in 0..body_locals {
                 self.bytecode.emit(Opcode::Pop, span);
             }
+            self.emit_try_exits(span);
             // Offset accounts for Loop instruction (1 byte) + operand (2 bytes) = 3 bytes
             let offset = start as i32 - (self.bytecode.current_offset() as i32 + 3);
             self.bytecode.emit(Opcode::Loop, span);
@@ -1169,6 +1207,76 @@ impl Compiler {
         Ok(())
     }
 
+    /// Remove the handlers of the `try` bodies a break/continue jumps out of
+    fn emit_try_exits(&mut self, span: Span) {
+        let loop_try_depth = self.loops.last().map_or(self.try_depth, |l| l.try_depth);
+        for _ in loop_try_depth..self.try_depth {
+            self.bytecode.emit(Opcode::TryEnd, span);
+        }
+    }
+
+    /// Compile a try/catch statement
+    ///
+    /// Layout:
+    ///   [TryBegin] [catch_offset:i16]   <- install handler
+    ///   [body bytecode...]              <- body locals popped at the end
+    ///   [TryEnd]                        <- remove handler
+    ///   [Jump] [end_offset:i16]
+    ///   [catch:]
+    ///   [PushError]                     <- only with `catch (e)`: binds e
+    ///   [catch bytecode...]
+    ///   [Pop]                           <- only with `catch (e)`
+    ///   [end:]
+    ///
+    /// On a catchable error the VM unwinds to the handler's frame, truncates
+    /// the stack to its height at TryBegin and jumps to `catch`.
+    fn compile_try_catch(&mut self, try_catch: &TryCatchStmt) -> Result<(), Vec<Diagnostic>> {
+        let span = try_catch.span;
+
+        self.bytecode.emit(Opcode::TryBegin, span);
+        let catch_jump = self.bytecode.current_offset();
+        self.bytecode.emit_u16(0xFFFF); // Placeholder — patched to catch
+
+        let locals_before = self.locals.len();
+        self.try_depth += 1;
+        let body = self.compile_block_as_statement(&try_catch.body);
+        self.try_depth -= 1;
+        body?;
+        for _ in locals_before..self.locals.len() {
+            self.bytecode.emit(Opcode::Pop, span);
+        }
+        self.truncate_locals(locals_before);
+
+        self.bytecode.emit(Opcode::TryEnd, span);
+        self.bytecode.emit(Opcode::Jump, span);
+        let end_jump = self.bytecode.current_offset();
+        self.bytecode.emit_u16(0xFFFF); // Placeholder — patched to end
+
+        self.bytecode.patch_jump(catch_jump);
+        if let Some(binding) = &try_catch.error_binding {
+            self.bytecode.emit(Opcode::PushError, binding.span);
+            self.push_local(Local {
+                name: binding.name.clone(),
+                depth: self.scope_depth + 1,
+                mutable: false,
+                scoped_name: None,
+                drop_type: None,
+            });
+        }
+        self.compile_block_as_statement(&try_catch.catch_block)?;
+        for _ in locals_before..self.locals.len() {
+            self.bytecode.emit(Opcode::Pop, span);
+        }
+        self.truncate_locals(locals_before);
+
+        self.bytecode.patch_jump(end_jump);
+        // Sentinel, as in for-in: keeps the binding's Pop from being the
+        // last instruction before Halt, which Pop skips
+        self.bytecode.emit(Opcode::Null, span);
+        self.bytecode.emit(Opcode::Pop, span);
+        Ok(())
+    }
+
     /// Compile a defer statement
     ///
     /// Defer pushes a block onto the defer stack. When the scope exits (via return
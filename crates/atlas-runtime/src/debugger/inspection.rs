@@ -7,6 +7,7 @@ use crate::compiler::Compiler;
 use crate::debugger::protocol::Variable;
 use crate::lexer::Lexer;
 use crate::parser::Parser;
+use crate::pretty::PrettyConfig;
 use crate::security::SecurityContext;
 use crate::value::Value;
 use crate::vm::VM;
@@ -18,6 +19,8 @@ use crate::vm::VM;
 pub enum VariableScope {
     /// Local variable in the current function frame.
     Local,
+    /// Variable captured by a closure from an enclosing function.
+    Captured,
     /// Global variable.
     Global,
 }
@@ -98,14 +101,19 @@ impl Inspector {
         let mut vars = Vec::new();
 
         // Locals from the requested frame
-        for (slot, value) in vm.get_locals_for_frame(frame_index) {
+        for local in vm.get_named_locals_for_frame(frame_index) {
+            let scope = if local.captured {
+                VariableScope::Captured
+            } else {
+                VariableScope::Local
+            };
             vars.push(ScopedVariable::new(
                 Variable::new(
-                    format!("local_{slot}"),
-                    format_value_with_depth(value, self.max_format_depth),
-                    value.type_name(),
+                    local.name,
+                    format_value_with_depth(local.value, self.max_format_depth),
+                    local.value.type_name(),
                 ),
-                VariableScope::Local,
+                scope,
             ));
         }
 
@@ -127,13 +135,13 @@ impl Inspector {
 
     /// Collect only local variables for a frame.
     pub fn collect_locals(&self, vm: &VM, frame_index: usize) -> Vec<Variable> {
-        vm.get_locals_for_frame(frame_index)
+        vm.get_named_locals_for_frame(frame_index)
             .into_iter()
-            .map(|(slot, value)| {
+            .map(|local| {
                 Variable::new(
-                    format!("local_{slot}"),
-                    format_value_with_depth(value, self.max_format_depth),
-                    value.type_name(),
+                    local.name,
+                    format_value_with_depth(local.value, self.max_format_depth),
+                    local.value.type_name(),
                 )
             })
             .collect()
@@ -143,7 +151,6 @@ impl Inspector {
     pub fn collect_globals(&self, vm: &VM) -> Vec<Variable> {
         let mut vars: Vec<Variable> = vm
             .get_global_variables()
-            .iter()
             .map(|(name, value)| {
                 Variable::new(
                     name.clone(),
@@ -255,54 +262,14 @@ pub struct WatchResult {
 // ── Formatting helpers ───────────────────────────────────────────────────────
 
 /// Format a `Value` for display with depth control.
+///
+/// Single-line [`Value::pretty`] output; collections nested deeper than
+/// `max_depth` collapse to a summary such as `[Array(3)]`.
 pub fn format_value_with_depth(value: &Value, max_depth: usize) -> String {
-    format_value_recursive(value, 0, max_depth)
-}
-
-fn format_value_recursive(value: &Value, depth: usize, max_depth: usize) -> String {
-    if depth > max_depth {
-        return "...".to_string();
-    }
-    match value {
-        Value::Number(n) => {
-            if n.fract() == 0.0 && n.abs() < 1e15 {
-                format!("{}", *n as i64)
-            } else {
-                format!("{n}")
-            }
-        }
-        Value::Bool(b) => b.to_string(),
-        Value::Null => "null".to_string(),
-        Value::String(s) => format!("\"{}\"", s.as_ref()),
-        Value::Array(arr) => {
-            if depth >= max_depth {
-                return format!("[{} items]", arr.len());
-            }
-            let items: Vec<String> = arr
-                .as_slice()
-                .iter()
-                .take(10)
-                .map(|v| format_value_recursive(v, depth + 1, max_depth))
-                .collect();
-            if arr.len() > 10 {
-                format!("[{}, ... +{} more]", items.join(", "), arr.len() - 10)
-            } else {
-                format!("[{}]", items.join(", "))
-            }
-        }
-        Value::Map(m) => format!("{{HashMap, {} entries}}", m.len()),
-        Value::Set(s) => {
-            format!("{{HashSet, {} items}}", s.inner().len())
-        }
-        Value::Queue(q) => {
-            format!("[Queue, {} items]", q.inner().len())
-        }
-        Value::Stack(s) => {
-            format!("[Stack, {} items]", s.inner().len())
-        }
-        Value::Function(f) => format!("<fn {}>", f.name),
-        _ => format!("{:?}", value),
-    }
+    value.pretty(&PrettyConfig {
+        max_depth,
+        ..PrettyConfig::compact()
+    })
 }
 
 /// Check if a string is a valid Atlas identifier.
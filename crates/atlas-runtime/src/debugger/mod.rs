@@ -40,7 +40,7 @@ pub mod stepping;
 // Re-export the most commonly used types at the `debugger` crate level.
 pub use protocol::{
     Breakpoint, BreakpointId, DebugEvent, DebugRequest, DebugResponse, DebugStackFrame,
-    PauseReason, SourceLocation, Variable,
+    PauseReason, SourceLocation, Variable, WatchTarget, Watchpoint,
 };
 pub use source_map::SourceMap;
 pub use state::{DebuggerState, ExecutionMode, StepMode};
@@ -50,9 +50,7 @@ pub use inspection::{EvalResult, Inspector, ScopedVariable, VariableScope, Watch
 pub use stepping::{StepRequest, StepTracker};
 
 use crate::bytecode::Bytecode;
-use crate::compiler::Compiler;
-use crate::lexer::Lexer;
-use crate::parser::Parser;
+use crate::pretty::PrettyConfig;
 use crate::security::SecurityContext;
 use crate::value::Value;
 use crate::vm::{VmRunResult, VM};
@@ -76,12 +74,12 @@ pub struct DebuggerSession {
 impl DebuggerSession {
     /// Create a new debugger session.
     ///
-    /// * `bytecode` – compiled Atlas bytecode.
-    /// * `source`   – original source text (used to compute line/column and evaluate expressions).
-    /// * `file`     – source file name shown in debug output.
+    /// * `bytecode` – compiled Atlas bytecode, possibly linked from several modules.
+    /// * `source`   – entry file's source text (used to compute line/column and evaluate expressions).
+    /// * `file`     – entry file name shown in debug output; locations in imported
+    ///   modules are reported under their own paths.
     pub fn new(bytecode: Bytecode, source: &str, file: &str) -> Self {
-        let source_map =
-            SourceMap::from_debug_spans(&bytecode.debug_info.clone(), file, Some(source));
+        let source_map = SourceMap::from_linked_spans(&bytecode.debug_info, file, source);
         let vm = VM::new(bytecode);
         Self {
             vm,
@@ -127,6 +125,32 @@ impl DebuggerSession {
                 DebugResponse::BreakpointsCleared
             }
 
+            // ── Watchpoints ───────────────────────────────────────────────────
+            DebugRequest::SetWatchpoint { target } => match self.bind_watch_target(&target) {
+                Ok((slot, frame_depth)) => {
+                    let id = self.state.add_watchpoint(target, slot, frame_depth);
+                    match self.state.get_watchpoint(id) {
+                        Some(wp) => DebugResponse::WatchpointSet {
+                            watchpoint: wp.clone(),
+                        },
+                        None => DebugResponse::error(format!("no watchpoint with id {id}")),
+                    }
+                }
+                Err(message) => DebugResponse::error(message),
+            },
+
+            DebugRequest::RemoveWatchpoint { id } => {
+                if self.state.remove_watchpoint(id).is_some() {
+                    DebugResponse::WatchpointRemoved { id }
+                } else {
+                    DebugResponse::error(format!("no watchpoint with id {id}"))
+                }
+            }
+
+            DebugRequest::ListWatchpoints => DebugResponse::Watchpoints {
+                watchpoints: self.state.watchpoints_owned(),
+            },
+
             // ── Execution control ─────────────────────────────────────────────
             DebugRequest::Continue => {
                 // Resume – state.resume() is called internally in run_until_pause.
@@ -163,6 +187,11 @@ impl DebuggerSession {
                 DebugResponse::Resumed
             }
 
+            DebugRequest::SetBreakOnError { enabled, kinds } => {
+                self.state.set_break_on_error(enabled, kinds.clone());
+                DebugResponse::BreakOnErrorSet { enabled, kinds }
+            }
+
             // ── Inspection ────────────────────────────────────────────────────
             DebugRequest::GetVariables { frame_index } => {
                 let variables = self.collect_variables(frame_index);
@@ -247,18 +276,56 @@ impl DebuggerSession {
         &self.source_map
     }
 
+    /// Send the program's output (`console.log` and friends) to `writer`
+    /// instead of stdout.
+    pub fn set_output_writer(&mut self, writer: crate::stdlib::OutputWriter) {
+        self.vm.set_output_writer(writer);
+    }
+
+    /// Variables visible in `frame_index`, tagged with the scope each one
+    /// belongs to.
+    pub fn scoped_variables(&self, frame_index: usize) -> Vec<ScopedVariable> {
+        Inspector::new().collect_scoped_variables(&self.vm, frame_index)
+    }
+
     // ── Private helpers ───────────────────────────────────────────────────────
 
+    /// Resolve a watch target to its storage: the global slot, or the
+    /// value-stack index of the local and the depth of the frame owning it.
+    fn bind_watch_target(&self, target: &WatchTarget) -> Result<(usize, Option<usize>), String> {
+        match target {
+            WatchTarget::Global { name } => self
+                .vm
+                .global_slot(name)
+                .map(|slot| (slot, None))
+                .ok_or_else(|| format!("no global named `{name}`")),
+            WatchTarget::Local { frame_index, name } => {
+                let frame = self
+                    .vm
+                    .get_frame_at(*frame_index)
+                    .ok_or_else(|| format!("no frame {frame_index}"))?;
+                let local = self
+                    .vm
+                    .get_named_locals_for_frame(*frame_index)
+                    .into_iter()
+                    .find(|var| var.name == *name && !var.captured)
+                    .ok_or_else(|| format!("no local named `{name}` in frame {frame_index}"))?;
+                let frame_depth = self.vm.frame_depth() - frame_index;
+                Ok((frame.stack_base + local.slot, Some(frame_depth)))
+            }
+        }
+    }
+
     /// Collect variables visible in `frame_index` (locals + globals).
     fn collect_variables(&self, frame_index: usize) -> Vec<Variable> {
         let mut vars = Vec::new();
 
         // Locals from the requested frame
-        for (slot, value) in self.vm.get_locals_for_frame(frame_index) {
+        for local in self.vm.get_named_locals_for_frame(frame_index) {
             vars.push(Variable::new(
-                format!("local_{slot}"),
-                format_value(value),
-                value.type_name(),
+                local.name,
+                format_value(local.value),
+                local.value.type_name(),
             ));
         }
 
@@ -281,12 +348,14 @@ impl DebuggerSession {
         (0..depth)
             .filter_map(|i| {
                 let frame = self.vm.get_frame_at(i)?;
-                // Determine location: use the instruction pointer relative to frame.
-                // For the innermost frame (i=0) we use the current IP.
-                let ip = if i == 0 {
-                    self.vm.current_ip()
-                } else {
-                    frame.return_ip.saturating_sub(1)
+                // The innermost frame is at the current IP; an outer frame is
+                // at its call, which the frame it called returns past.
+                let ip = match i
+                    .checked_sub(1)
+                    .and_then(|inner| self.vm.get_frame_at(inner))
+                {
+                    Some(inner) => inner.return_ip.saturating_sub(1),
+                    None => self.vm.current_ip(),
                 };
                 let location = self.source_map.location_for_offset(ip).cloned();
                 Some(DebugStackFrame {
@@ -302,65 +371,22 @@ impl DebuggerSession {
 
     /// Evaluate `expression` in the context of `frame_index`.
     ///
-    /// Builds a small Atlas snippet that pre-defines the visible variables as
-    /// constants, then evaluates the expression using the tree-walking interpreter.
+    /// See [`VM::evaluate`]: the paused program is left untouched.
     fn evaluate_in_context(&self, expression: &str, frame_index: usize) -> DebugResponse {
-        // Collect variables for the frame
-        let vars = self.collect_variables(frame_index);
-
-        // Build a snippet that injects visible variables as `let` bindings
-        let mut snippet = String::new();
-        for var in &vars {
-            // Only inject variables whose names are valid identifiers
-            if var.name.chars().all(|c| c.is_alphanumeric() || c == '_')
-                && var
-                    .name
-                    .chars()
-                    .next()
-                    .map(|c| c.is_alphabetic() || c == '_')
-                    .unwrap_or(false)
-            {
-                // Try to produce a valid Atlas literal.
-                let literal = value_to_literal(&var.type_name, &var.value);
-                if let Some(lit) = literal {
-                    snippet.push_str(&format!("let {} = {};\n", var.name, lit));
-                }
-            }
-        }
-        snippet.push_str(expression);
-        // Atlas requires a semicolon at the end of statements.
-        let trimmed = expression.trim();
-        if !trimmed.ends_with(';') && !trimmed.ends_with('}') {
-            snippet.push(';');
+        if frame_index >= self.vm.frame_depth() {
+            return DebugResponse::error(format!("no stack frame {}", frame_index));
         }
-
-        // Run through Compiler + VM (D-052: unified execution path)
-        let tokens = Lexer::new(&snippet).tokenize().0;
-        let (ast, errors) = Parser::new(tokens).parse();
-        if !errors.is_empty() {
-            return DebugResponse::error(format!("parse error: {:?}", errors[0]));
-        }
-
-        let mut compiler = Compiler::new();
-        let bytecode = match compiler.compile(&ast) {
-            Ok(bc) => bc,
-            Err(diags) => {
-                return DebugResponse::error(format!("compile error: {:?}", diags[0]));
-            }
-        };
-
-        let security = SecurityContext::allow_all();
-        let mut vm = VM::new(bytecode);
-        match vm.run(&security) {
-            Ok(Some(value)) => DebugResponse::EvalResult {
+        match self.vm.evaluate(expression, frame_index) {
+            Ok(value) => DebugResponse::EvalResult {
                 value: format_value(&value),
                 type_name: value.type_name().to_string(),
             },
-            Ok(None) => DebugResponse::EvalResult {
-                value: "null".to_string(),
-                type_name: "null".to_string(),
-            },
-            Err(e) => DebugResponse::error(format!("{:?}", e)),
+            Err(diagnostics) => DebugResponse::error(
+                diagnostics
+                    .first()
+                    .map(|d| d.message.clone())
+                    .unwrap_or_else(|| "evaluation failed".to_string()),
+            ),
         }
     }
 }
@@ -369,48 +395,5 @@ impl DebuggerSession {
 
 /// Format a `Value` for display in the debugger.
 fn format_value(value: &Value) -> String {
-    match value {
-        Value::Number(n) => {
-            if n.fract() == 0.0 && n.abs() < 1e15 {
-                format!("{}", *n as i64)
-            } else {
-                format!("{n}")
-            }
-        }
-        Value::Bool(b) => b.to_string(),
-        Value::Null => "null".to_string(),
-        Value::String(s) => format!("\"{}\"", s.as_ref()),
-        Value::Array(arr) => {
-            format!("[{} items]", arr.len())
-        }
-        Value::Map(m) => format!("{{HashMap, {} entries}}", m.len()),
-        Value::Set(s) => {
-            format!("{{HashSet, {} items}}", s.inner().len())
-        }
-        Value::Queue(q) => {
-            format!("[Queue, {} items]", q.inner().len())
-        }
-        Value::Stack(s) => {
-            format!("[Stack, {} items]", s.inner().len())
-        }
-        Value::Function(f) => format!("<fn {}>", f.name),
-        _ => format!("{:?}", value),
-    }
-}
-
-/// Try to produce an Atlas literal string from type_name + display value.
-///
-/// Returns `None` if we can't safely re-parse the value as a literal.
-fn value_to_literal(type_name: &str, display: &str) -> Option<String> {
-    match type_name {
-        "number" => {
-            // display is already a number string like "42" or "3.14"
-            display.parse::<f64>().ok()?;
-            Some(display.to_string())
-        }
-        "bool" => Some(display.to_string()),
-        "null" => Some("null".to_string()),
-        "string" => Some(display.to_string()), // already includes quotes
-        _ => None,                             // Complex types can't be trivially re-created
-    }
+    value.pretty(&PrettyConfig::compact())
 }
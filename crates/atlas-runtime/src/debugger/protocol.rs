@@ -95,6 +95,50 @@ impl Breakpoint {
     }
 }
 
+// ── Watchpoint ────────────────────────────────────────────────────────────────
+
+/// The variable a watchpoint observes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum WatchTarget {
+    /// A global variable.
+    Global { name: String },
+    /// A local variable of a live stack frame; the watchpoint is removed
+    /// when that frame returns.
+    Local {
+        /// Frame that owns the local (0 = innermost) when the watchpoint is set.
+        frame_index: usize,
+        /// Local variable name.
+        name: String,
+    },
+}
+
+impl std::fmt::Display for WatchTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Global { name } => write!(f, "{name}"),
+            Self::Local { frame_index, name } => write!(f, "{name} (frame {frame_index})"),
+        }
+    }
+}
+
+/// A registered watchpoint (data breakpoint): pauses execution right after
+/// a write changes the watched variable's value.
+///
+/// Watchpoints share the breakpoint ID space.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Watchpoint {
+    /// Unique ID assigned by the debugger.
+    pub id: BreakpointId,
+    /// Requested variable.
+    pub target: WatchTarget,
+    /// Global slot, or value-stack index of the local.
+    pub slot: usize,
+    /// Call-frame depth (`frames.len()` while the frame is innermost) that
+    /// owns a watched local; `None` for globals.
+    pub frame_depth: Option<usize>,
+}
+
 // ── Stack frame ───────────────────────────────────────────────────────────────
 
 /// A frame in the call stack (for stack traces).
@@ -150,6 +194,15 @@ pub enum PauseReason {
         /// ID of the breakpoint that triggered the pause.
         id: BreakpointId,
     },
+    /// A watched variable changed value.
+    Watchpoint {
+        /// ID of the watchpoint that triggered the pause.
+        id: BreakpointId,
+        /// Value before the write (`None` if the variable was unassigned).
+        old_value: Option<String>,
+        /// Value after the write.
+        new_value: String,
+    },
     /// A step operation completed (step-over, step-into, step-out).
     Step,
     /// Execution was paused manually (e.g. via Pause request).
@@ -158,6 +211,10 @@ pub enum PauseReason {
     Exception {
         /// Error message.
         message: String,
+        /// Runtime error kind (e.g. `"OutOfBounds"`); serialized as
+        /// `error_kind` since `kind` is the enum tag.
+        #[serde(default, rename = "error_kind")]
+        kind: String,
     },
 }
 
@@ -176,6 +233,12 @@ pub enum DebugRequest {
     ListBreakpoints,
     /// Remove all registered breakpoints.
     ClearBreakpoints,
+    /// Pause when a variable's value changes.
+    SetWatchpoint { target: WatchTarget },
+    /// Remove a previously registered watchpoint.
+    RemoveWatchpoint { id: BreakpointId },
+    /// List all registered watchpoints.
+    ListWatchpoints,
 
     // ── Execution control ───────────────────────────────────────────────────
     /// Resume execution from a paused state.
@@ -188,6 +251,14 @@ pub enum DebugRequest {
     StepOut,
     /// Pause execution at the next instruction.
     Pause,
+    /// Pause when a runtime error is about to propagate out of the program,
+    /// leaving the faulting frame inspectable.
+    SetBreakOnError {
+        enabled: bool,
+        /// Error kinds to break on (e.g. `"OutOfBounds"`); empty means all kinds.
+        #[serde(default)]
+        kinds: Vec<String>,
+    },
 
     // ── Inspection ─────────────────────────────────────────────────────────
     /// Get all variables visible in a stack frame.
@@ -221,8 +292,16 @@ pub enum DebugResponse {
     Breakpoints { breakpoints: Vec<Breakpoint> },
     /// All breakpoints were cleared.
     BreakpointsCleared,
+    /// A watchpoint was registered.
+    WatchpointSet { watchpoint: Watchpoint },
+    /// A watchpoint was removed.
+    WatchpointRemoved { id: BreakpointId },
+    /// All registered watchpoints.
+    Watchpoints { watchpoints: Vec<Watchpoint> },
     /// Execution has been resumed.
     Resumed,
+    /// Break-on-error settings were updated.
+    BreakOnErrorSet { enabled: bool, kinds: Vec<String> },
     /// Execution is paused.
     Paused {
         reason: PauseReason,
@@ -2,11 +2,17 @@
 //!
 //! Converts `DebugSpan` entries from bytecode into (file, line, column) source locations
 //! and maintains both forward (offset → location) and reverse (location → offset) indexes.
+//!
+//! Bytecode linked from several modules carries spans from every module's file.
+//! [`SourceMap::from_linked_spans`] resolves each span against its own file so
+//! locations in imported modules are not reported against the entry file.
 
 use std::collections::HashMap;
+use std::fs;
 
 use crate::bytecode::DebugSpan;
 use crate::debugger::protocol::SourceLocation;
+use crate::span::{source_for_file, FileId, Span};
 
 // ── Line offset computation ───────────────────────────────────────────────────
 
@@ -49,6 +55,8 @@ pub fn byte_offset_to_line_column(offset: usize, line_offsets: &[usize]) -> (u32
 pub struct SourceMap {
     /// offset → source location
     offset_to_location: HashMap<usize, SourceLocation>,
+    /// offset → original span (only for maps built from linked spans)
+    offset_to_span: HashMap<usize, Span>,
     /// (file, line) → sorted list of instruction offsets
     line_to_offsets: HashMap<(String, u32), Vec<usize>>,
     /// (file, line, column) → first matching instruction offset
@@ -93,6 +101,26 @@ impl SourceMap {
         map
     }
 
+    /// Build a source map from the spans of bytecode linked from several modules.
+    ///
+    /// Each span is resolved against the file it was lexed from, using the
+    /// source text registered by the lexer or, failing that, the file on disk.
+    /// Spans from anonymous source or from the entry file itself are reported
+    /// as `entry_file` and resolved against `entry_source`.
+    pub fn from_linked_spans(spans: &[DebugSpan], entry_file: &str, entry_source: &str) -> Self {
+        let mut map = Self::new();
+        let mut files = SpanFiles::new(entry_file, entry_source);
+
+        for debug_span in spans {
+            let location = files.location(debug_span.span);
+            map.insert(debug_span.instruction_offset, location);
+            map.offset_to_span
+                .insert(debug_span.instruction_offset, debug_span.span);
+        }
+
+        map
+    }
+
     /// Insert a single offset → location mapping.
     pub fn insert(&mut self, offset: usize, location: SourceLocation) {
         // line → offsets  (allow multiple instructions on the same line)
@@ -131,6 +159,23 @@ impl SourceMap {
         self.offset_to_location.get(&best)
     }
 
+    /// Get the original span for an instruction offset, falling back to the
+    /// closest preceding instruction like [`location_for_offset`](Self::location_for_offset).
+    ///
+    /// Only maps built with [`from_linked_spans`](Self::from_linked_spans) record spans.
+    pub fn span_for_offset(&self, offset: usize) -> Option<Span> {
+        if let Some(span) = self.offset_to_span.get(&offset) {
+            return Some(*span);
+        }
+        let best = self
+            .offset_to_span
+            .keys()
+            .copied()
+            .filter(|&o| o <= offset)
+            .max()?;
+        self.offset_to_span.get(&best).copied()
+    }
+
     /// Get the first instruction offset for an exact `(file, line, column)` match.
     pub fn offset_for_location(&self, file: &str, line: u32, column: u32) -> Option<usize> {
         let key = (file.to_string(), line, column);
@@ -178,3 +223,75 @@ impl SourceMap {
         files
     }
 }
+
+// ── Per-file resolution ───────────────────────────────────────────────────────
+
+/// A file referenced by linked spans: its display name and, when its text
+/// could be found, the line table used to compute line/column.
+#[derive(Debug, Clone)]
+pub(crate) struct ResolvedFile {
+    pub name: String,
+    pub source: Option<String>,
+    pub line_offsets: Option<Vec<usize>>,
+}
+
+/// Resolves span files to display names and line tables, caching per file id.
+pub(crate) struct SpanFiles<'a> {
+    entry_file: &'a str,
+    entry_source: Option<&'a str>,
+    cache: HashMap<FileId, ResolvedFile>,
+}
+
+impl<'a> SpanFiles<'a> {
+    pub(crate) fn new(entry_file: &'a str, entry_source: &'a str) -> Self {
+        Self::with_optional_source(entry_file, Some(entry_source))
+    }
+
+    pub(crate) fn with_optional_source(entry_file: &'a str, entry_source: Option<&'a str>) -> Self {
+        Self {
+            entry_file,
+            entry_source,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// The file a span belongs to.
+    pub(crate) fn resolve(&mut self, span: Span) -> &ResolvedFile {
+        let (entry_file, entry_source) = (self.entry_file, self.entry_source);
+        self.cache.entry(span.file).or_insert_with(|| {
+            let path = span.file();
+            let registered = source_for_file(span.file);
+            let is_entry = path.starts_with('<')
+                || path.as_ref() == entry_file
+                || (registered.is_some() && registered.as_deref() == entry_source);
+            let (name, source) = if is_entry {
+                (entry_file.to_string(), entry_source.map(str::to_string))
+            } else {
+                let source = registered
+                    .map(|s| s.to_string())
+                    .or_else(|| fs::read_to_string(path.as_ref()).ok());
+                (path.to_string(), source)
+            };
+            let line_offsets = source.as_deref().map(compute_line_offsets);
+            ResolvedFile {
+                name,
+                source,
+                line_offsets,
+            }
+        })
+    }
+
+    /// The 1-based source location of a span's start.
+    pub(crate) fn location(&mut self, span: Span) -> SourceLocation {
+        let file = self.resolve(span);
+        let (line, column) = match &file.line_offsets {
+            Some(offsets) => byte_offset_to_line_column(span.start, offsets),
+            None => (1, 1),
+        };
+        SourceLocation {
+            file: file.name.clone(),
+            line,
+            column,
+        }
+    }
+}
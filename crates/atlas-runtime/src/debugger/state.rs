@@ -6,7 +6,9 @@
 
 use std::collections::HashMap;
 
-use crate::debugger::protocol::{Breakpoint, BreakpointId, PauseReason, SourceLocation};
+use crate::debugger::protocol::{
+    Breakpoint, BreakpointId, PauseReason, SourceLocation, WatchTarget, Watchpoint,
+};
 
 // ── ExecutionMode ─────────────────────────────────────────────────────────────
 
@@ -51,7 +53,10 @@ pub struct DebuggerState {
     /// All registered breakpoints keyed by their ID.
     breakpoints: HashMap<BreakpointId, Breakpoint>,
 
-    /// Monotonically increasing ID for new breakpoints.
+    /// All registered watchpoints keyed by their ID.
+    watchpoints: HashMap<BreakpointId, Watchpoint>,
+
+    /// Monotonically increasing ID for new breakpoints and watchpoints.
     next_id: BreakpointId,
 
     /// Active step mode (None means run freely).
@@ -70,6 +75,12 @@ pub struct DebuggerState {
 
     /// Instruction pointer at the most recent pause.
     pub pause_ip: usize,
+
+    /// Pause when a runtime error is about to propagate.
+    pub break_on_error: bool,
+
+    /// Error kinds that trigger break-on-error; empty means every kind.
+    pub break_on_error_kinds: Vec<String>,
 }
 
 impl DebuggerState {
@@ -78,12 +89,15 @@ impl DebuggerState {
         Self {
             mode: ExecutionMode::Running,
             breakpoints: HashMap::new(),
+            watchpoints: HashMap::new(),
             next_id: 1,
             step_mode: StepMode::None,
             step_start_frame_depth: 0,
             pause_reason: None,
             pause_location: None,
             pause_ip: 0,
+            break_on_error: false,
+            break_on_error_kinds: Vec::new(),
         }
     }
 
@@ -156,6 +170,54 @@ impl DebuggerState {
         self.breakpoints.len()
     }
 
+    // ── Watchpoint management ─────────────────────────────────────────────────
+
+    /// Register a watchpoint bound to `slot` and return its assigned ID.
+    ///
+    /// `frame_depth` is the depth of the frame owning a watched local, `None`
+    /// for a global.
+    pub fn add_watchpoint(
+        &mut self,
+        target: WatchTarget,
+        slot: usize,
+        frame_depth: Option<usize>,
+    ) -> BreakpointId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.watchpoints.insert(
+            id,
+            Watchpoint {
+                id,
+                target,
+                slot,
+                frame_depth,
+            },
+        );
+        id
+    }
+
+    /// Remove a watchpoint by ID, returning the removed entry.
+    pub fn remove_watchpoint(&mut self, id: BreakpointId) -> Option<Watchpoint> {
+        self.watchpoints.remove(&id)
+    }
+
+    /// Remove all registered watchpoints.
+    pub fn clear_watchpoints(&mut self) {
+        self.watchpoints.clear();
+    }
+
+    /// Look up a single watchpoint by ID.
+    pub fn get_watchpoint(&self, id: BreakpointId) -> Option<&Watchpoint> {
+        self.watchpoints.get(&id)
+    }
+
+    /// Return all watchpoints as owned values, sorted by ID.
+    pub fn watchpoints_owned(&self) -> Vec<Watchpoint> {
+        let mut wps: Vec<Watchpoint> = self.watchpoints.values().cloned().collect();
+        wps.sort_by_key(|wp| wp.id);
+        wps
+    }
+
     // ── Execution-mode control ────────────────────────────────────────────────
 
     /// Transition to Paused mode and record the pause context.
@@ -211,6 +273,21 @@ impl DebuggerState {
         self.step_mode = StepMode::None;
     }
 
+    // ── Break-on-error ────────────────────────────────────────────────────────
+
+    /// Configure break-on-error. An empty `kinds` list matches every error kind.
+    pub fn set_break_on_error(&mut self, enabled: bool, kinds: Vec<String>) {
+        self.break_on_error = enabled;
+        self.break_on_error_kinds = kinds;
+    }
+
+    /// Returns `true` if an error of `kind` should pause execution.
+    pub fn breaks_on_error(&self, kind: &str) -> bool {
+        self.break_on_error
+            && (self.break_on_error_kinds.is_empty()
+                || self.break_on_error_kinds.iter().any(|k| k == kind))
+    }
+
     // ── Step-pause logic ──────────────────────────────────────────────────────
 
     /// Decide whether to pause at `ip` for the current step mode.
@@ -7,6 +7,7 @@ pub mod descriptor;
 pub mod error_codes;
 pub mod formatter;
 pub mod normalizer;
+pub mod suppression;
 pub mod warnings;
 
 use crate::span::{source_for_file, Span};
@@ -90,6 +91,16 @@ impl RelatedLocation {
             is_occurrence: false,
         }
     }
+
+    /// Construct a semantic related location pointing at `span`.
+    ///
+    /// Line and column are resolved through the registered source for the span's file.
+    pub fn from_span(span: Span, message: impl Into<String>) -> Self {
+        let (line, column) = source_for_file(span.file)
+            .map(|src| crate::diagnostic::formatter::offset_to_line_col(src.as_ref(), span.start))
+            .unwrap_or((1, span.start + 1));
+        Self::semantic(span.file().to_string(), line, column, span.len(), message)
+    }
 }
 
 /// Stack trace frame for runtime errors.
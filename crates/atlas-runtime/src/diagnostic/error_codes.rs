@@ -127,6 +127,16 @@ pub const UNHASHABLE_TYPE: DiagnosticDescriptor = DiagnosticDescriptor {
     domain: DiagnosticDomain::Runtime,
 };
 
+pub const CIRCULAR_REFERENCE: DiagnosticDescriptor = DiagnosticDescriptor {
+    code: "AT0141",
+    level: DiagnosticLevel::Error,
+    title: "Circular reference",
+    message_template: "circular reference: {detail}",
+    static_help: Some("a `shared` value reachable from itself cannot be serialized, compared or cloned — break the cycle first"),
+    static_note: None,
+    domain: DiagnosticDomain::Runtime,
+};
+
 // ── AT03xx: Permission Errors ──────────────────────────────────────────────────
 
 pub const FILESYSTEM_PERMISSION_DENIED: DiagnosticDescriptor = DiagnosticDescriptor {
@@ -190,6 +200,18 @@ pub const FFI_PERMISSION_DENIED: DiagnosticDescriptor = DiagnosticDescriptor {
     domain: DiagnosticDomain::Runtime,
 };
 
+pub const DATABASE_PERMISSION_DENIED: DiagnosticDescriptor = DiagnosticDescriptor {
+    code: "AT0305",
+    level: DiagnosticLevel::Error,
+    title: "Database permission denied",
+    message_template: "database access denied: {detail}",
+    static_help: Some(
+        "allow the database path under `[security.database]` in atlas.toml or adjust the security settings",
+    ),
+    static_note: None,
+    domain: DiagnosticDomain::Runtime,
+};
+
 // ── AT04xx: I/O Errors ─────────────────────────────────────────────────────────
 
 pub const IO_ERROR: DiagnosticDescriptor = DiagnosticDescriptor {
@@ -226,6 +248,47 @@ pub const MEMORY_LIMIT_EXCEEDED: DiagnosticDescriptor = DiagnosticDescriptor {
     domain: DiagnosticDomain::Runtime,
 };
 
+/// Execution stopped by the embedding host through an interrupt handle.
+pub const EXECUTION_INTERRUPTED: DiagnosticDescriptor = DiagnosticDescriptor {
+    code: "AT0502",
+    level: DiagnosticLevel::Error,
+    title: "Execution interrupted",
+    message_template: "execution was interrupted by the host",
+    static_help: Some(
+        "the embedding application stopped this script; run it again once the host allows it",
+    ),
+    static_note: Some(
+        "interrupts are delivered between instructions and at builtin interrupt points",
+    ),
+    domain: DiagnosticDomain::Runtime,
+};
+
+/// A script compiled a regex pattern over the configured `RegexLimits`.
+pub const REGEX_LIMIT_EXCEEDED: DiagnosticDescriptor = DiagnosticDescriptor {
+    code: "AT0503",
+    level: DiagnosticLevel::Error,
+    title: "Regex limit exceeded",
+    message_template: "regex pattern exceeds the {limit} limit",
+    static_help: Some("simplify the pattern, or escape untrusted input with `Regex.escape`"),
+    static_note: Some(
+        "limits cover pattern length, compiled size, nesting depth and the match cache",
+    ),
+    domain: DiagnosticDomain::Runtime,
+};
+
+/// Calls nested deeper than the configured `VmLimits` allow.
+pub const RECURSION_LIMIT: DiagnosticDescriptor = DiagnosticDescriptor {
+    code: "AT0504",
+    level: DiagnosticLevel::Error,
+    title: "Recursion limit exceeded",
+    message_template: "recursion limit exceeded at call depth {depth}",
+    static_help: Some("check that the recursion reaches its base case, or rewrite it as a loop"),
+    static_note: Some(
+        "the limit covers nested calls and callbacks nested inside builtins such as `map` and `sort`",
+    ),
+    domain: DiagnosticDomain::Runtime,
+};
+
 // ── AT1xxx: Syntax / Lexer Errors ─────────────────────────────────────────────
 
 pub const SYNTAX_ERROR: DiagnosticDescriptor = DiagnosticDescriptor {
@@ -485,6 +548,41 @@ pub const RESERVED_KEYWORD_AS_IDENTIFIER: DiagnosticDescriptor = DiagnosticDescr
     domain: DiagnosticDomain::Parser,
 };
 
+/// Parse error: a `macro` declaration or invocation could not be expanded.
+pub const MACRO_EXPANSION_ERROR: DiagnosticDescriptor = DiagnosticDescriptor {
+    code: "AT1023",
+    level: DiagnosticLevel::Error,
+    title: "Macro expansion error",
+    message_template: "macro expansion failed: {detail}",
+    static_help: Some("macros are declared as `macro name(param: kind, ...) { ... }` and invoked as `name!(args)`"),
+    static_note: None,
+    domain: DiagnosticDomain::Parser,
+};
+
+/// Parse error: a `/pattern/flags` literal does not compile.
+pub const INVALID_REGEX_LITERAL: DiagnosticDescriptor = DiagnosticDescriptor {
+    code: "AT1024",
+    level: DiagnosticLevel::Error,
+    title: "Invalid regex literal",
+    message_template: "invalid regex literal: {detail}",
+    static_help: Some("regex literals use the `regex` crate syntax; valid flags are `i`, `m`, `s` and `x`"),
+    static_note: Some("regex literals are compiled when the program is parsed, so the error is reported before it runs"),
+    domain: DiagnosticDomain::Parser,
+};
+
+/// A construct or builtin that the runtime's language profile excludes.
+pub const NOT_IN_LANGUAGE_PROFILE: DiagnosticDescriptor = DiagnosticDescriptor {
+    code: "AT1025",
+    level: DiagnosticLevel::Error,
+    title: "Not available in this language profile",
+    message_template: "this language profile does not allow {what}",
+    static_help: Some(
+        "the application running this script restricts the language; rewrite this without it",
+    ),
+    static_note: None,
+    domain: DiagnosticDomain::Parser,
+};
+
 // ── AT2xxx: Warnings ───────────────────────────────────────────────────────────
 
 pub const UNUSED_VARIABLE: DiagnosticDescriptor = DiagnosticDescriptor {
@@ -1356,6 +1454,50 @@ pub const DUPLICATE_EXPORT: DiagnosticDescriptor = DiagnosticDescriptor {
     domain: DiagnosticDomain::Parser,
 };
 
+pub const ASSET_INCLUDE_ERROR: DiagnosticDescriptor = DiagnosticDescriptor {
+    code: "AT5009",
+    level: DiagnosticLevel::Error,
+    title: "Asset cannot be embedded",
+    message_template: "cannot embed `{path}`: {detail}",
+    static_help: Some(
+        "embedded files must be listed in atlas.toml, e.g. `[assets] include = [\"templates/*.html\"]`",
+    ),
+    static_note: None,
+    domain: DiagnosticDomain::Parser,
+};
+
+pub const CYCLIC_INITIALIZATION: DiagnosticDescriptor = DiagnosticDescriptor {
+    code: "AT5010",
+    level: DiagnosticLevel::Error,
+    title: "Cyclic initialization",
+    message_template: "`{name}` depends on its own value during initialization: {cycle}",
+    static_help: Some(
+        "a top-level initializer must not call a function that reads the value being initialized",
+    ),
+    static_note: Some("top-level items run once, in declaration order"),
+    domain: DiagnosticDomain::Parser,
+};
+
+pub const USED_BEFORE_INITIALIZATION: DiagnosticDescriptor = DiagnosticDescriptor {
+    code: "AT5011",
+    level: DiagnosticLevel::Error,
+    title: "Used before initialization",
+    message_template: "`{name}` is used before it is initialized: {chain}",
+    static_help: Some("move the declaration above the top-level code that uses it"),
+    static_note: Some("top-level items run once, in declaration order"),
+    domain: DiagnosticDomain::Parser,
+};
+
+pub const EXTERN_NOT_PRECOMPILABLE: DiagnosticDescriptor = DiagnosticDescriptor {
+    code: "AT5012",
+    level: DiagnosticLevel::Error,
+    title: "Extern functions cannot be precompiled",
+    message_template: "{path} declares extern functions, which cannot be compiled ahead of time",
+    static_help: Some("run the program from source with `atlas run` instead"),
+    static_note: Some("native bindings are loaded from the source, not stored in bytecode"),
+    domain: DiagnosticDomain::Parser,
+};
+
 // ── AT9xxx: Internal ───────────────────────────────────────────────────────────
 // AT9000 (DEPRECATED_STDLIB_GLOBAL) removed — bare globals deleted, no backward compat (B35).
 
@@ -1454,6 +1596,19 @@ pub const NO_NEW_CONSTRUCTOR: DiagnosticDescriptor = DiagnosticDescriptor {
     domain: DiagnosticDomain::Typechecker,
 };
 
+/// Fired when a top-level `fn main` does not match the entry-point contract.
+pub const INVALID_MAIN_SIGNATURE: DiagnosticDescriptor = DiagnosticDescriptor {
+    code: "AT3066",
+    level: DiagnosticLevel::Error,
+    title: "Invalid `main` signature",
+    message_template: "invalid entry point: {detail}",
+    static_help: Some(
+        "declare `fn main()` or `fn main(args: string[])`, returning nothing, `number`, or `Result<T, E>`",
+    ),
+    static_note: Some("`atlas run` calls `main` with the program arguments and uses its return value as the exit code"),
+    domain: DiagnosticDomain::Typechecker,
+};
+
 // ── Descriptor Registry ────────────────────────────────────────────────────────
 
 /// Lookup a descriptor by error code.  O(n) scan — only used by `atlas explain`
@@ -1489,14 +1644,19 @@ pub static DESCRIPTOR_REGISTRY: &[&DiagnosticDescriptor] = &[
     &STDLIB_ARG_ERROR,
     &STDLIB_VALUE_ERROR,
     &UNHASHABLE_TYPE,
+    &CIRCULAR_REFERENCE,
     &FILESYSTEM_PERMISSION_DENIED,
     &NETWORK_PERMISSION_DENIED,
     &PROCESS_PERMISSION_DENIED,
     &ENVIRONMENT_PERMISSION_DENIED,
     &FFI_PERMISSION_DENIED,
+    &DATABASE_PERMISSION_DENIED,
     &IO_ERROR,
     &EXECUTION_TIMEOUT,
     &MEMORY_LIMIT_EXCEEDED,
+    &EXECUTION_INTERRUPTED,
+    &REGEX_LIMIT_EXCEEDED,
+    &RECURSION_LIMIT,
     &SYNTAX_ERROR,
     &UNEXPECTED_TOKEN,
     &UNTERMINATED_STRING,
@@ -1520,6 +1680,9 @@ pub static DESCRIPTOR_REGISTRY: &[&DiagnosticDescriptor] = &[
     &MISSING_SEMICOLON,
     &MISSING_CLOSING_DELIMITER,
     &RESERVED_KEYWORD_AS_IDENTIFIER,
+    &MACRO_EXPANSION_ERROR,
+    &INVALID_REGEX_LITERAL,
+    &NOT_IN_LANGUAGE_PROFILE,
     &UNUSED_VARIABLE,
     &UNREACHABLE_CODE,
     &DUPLICATE_DECLARATION,
@@ -1593,6 +1756,10 @@ pub static DESCRIPTOR_REGISTRY: &[&DiagnosticDescriptor] = &[
     &MODULE_NOT_EXPORTED,
     &NAMESPACE_IMPORT_UNSUPPORTED,
     &DUPLICATE_EXPORT,
+    &ASSET_INCLUDE_ERROR,
+    &CYCLIC_INITIALIZATION,
+    &USED_BEFORE_INITIALIZATION,
+    &EXTERN_NOT_PRECOMPILABLE,
     &INTERNAL_ERROR,
     &STACK_UNDERFLOW,
     &UNKNOWN_OPCODE,
@@ -1602,4 +1769,5 @@ pub static DESCRIPTOR_REGISTRY: &[&DiagnosticDescriptor] = &[
     &REQUIRED_PARAM_AFTER_DEFAULT,
     &DEFAULT_ON_OWNERSHIP_PARAM,
     &NO_NEW_CONSTRUCTOR,
+    &INVALID_MAIN_SIGNATURE,
 ];
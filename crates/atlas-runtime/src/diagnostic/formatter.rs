@@ -109,8 +109,9 @@ impl DiagnosticFormatter {
             }
         }
 
-        // Stack trace (shows execution path before fix guidance)
-        if !diag.stack_trace.is_empty() {
+        // Stack trace (shows execution path before fix guidance). A single
+        // frame only repeats the primary location, so it is left out.
+        if diag.stack_trace.len() > 1 {
             self.write_stack_trace(w, diag)?;
         }
 
@@ -299,12 +300,27 @@ impl DiagnosticFormatter {
     }
 
     fn write_stack_trace(&self, w: &mut impl WriteColor, diag: &Diagnostic) -> std::io::Result<()> {
-        for frame in &diag.stack_trace {
+        w.set_color(ColorSpec::new().set_bold(true))?;
+        write!(w, "stack trace")?;
+        w.reset()?;
+        writeln!(w, ":")?;
+        // Deep recursion repeats one frame thousands of times; print it once
+        let mut frames = diag.stack_trace.iter().peekable();
+        while let Some(frame) = frames.next() {
             writeln!(
                 w,
                 "  at {} ({}:{}:{})",
                 frame.function, frame.file, frame.line, frame.column
             )?;
+            let mut repeats = 0;
+            while frames.next_if_eq(&frame).is_some() {
+                repeats += 1;
+            }
+            match repeats {
+                0 => {}
+                1 => writeln!(w, "  ... 1 more identical frame")?,
+                n => writeln!(w, "  ... {} more identical frames", n)?,
+            }
         }
         Ok(())
     }
@@ -0,0 +1,173 @@
+//! Inline suppression of warnings with `// atlas-ignore:` comments
+//!
+//! ```atlas
+//! // atlas-ignore: unused-variable -- kept for the debugger
+//! let scratch = compute();
+//!
+//! let legacy = old_api(); // atlas-ignore: AT2009
+//! ```
+//!
+//! A directive on a line of its own applies to the next line of code; one
+//! trailing code applies to its own line. Rules are named either by their
+//! kebab-case name (see [`RULES`]) or by diagnostic code, several separated
+//! by commas, and anything after `--` is a free-form reason kept for
+//! `atlas suppressions`. Only warnings can be suppressed — errors, including
+//! warnings promoted by `deny`, are never hidden.
+
+use crate::diagnostic::{Diagnostic, DiagnosticLevel};
+use crate::lexer::Lexer;
+use crate::token::TokenKind;
+
+/// Comment prefix that introduces a directive
+pub const DIRECTIVE: &str = "atlas-ignore:";
+
+/// Rule names accepted by `atlas-ignore`, with the warning code each covers
+///
+/// Covers the checker's warnings and the ownership warnings the VM reports
+/// while running.
+pub const RULES: &[(&str, &str)] = &[
+    ("unused-variable", "AT2001"),
+    ("unreachable-code", "AT2002"),
+    ("duplicate-declaration", "AT2003"),
+    ("unused-function", "AT2004"),
+    ("shadowing", "AT2005"),
+    ("constant-condition", "AT2006"),
+    ("unnecessary-annotation", "AT2007"),
+    ("unused-import", "AT2008"),
+    ("deprecated-type-alias", "AT2009"),
+    ("own-on-primitive", "AT2010"),
+    ("borrow-on-shared", "AT2011"),
+    ("borrow-to-own", "AT2012"),
+    ("missing-ownership-annotation", "AT2013"),
+    ("deprecated-var", "AT2014"),
+    ("discarded-cow-result", "AT2015"),
+    ("inherent-shadow", "AW3059"),
+    ("share-to-non-share", "AW3060"),
+];
+
+/// One `atlas-ignore` directive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suppression {
+    /// Rules as written, names or codes
+    pub rules: Vec<String>,
+    /// Text after `--`, if any
+    pub reason: Option<String>,
+    /// Line of the directive comment (1-based)
+    pub line: usize,
+    /// Line whose warnings it suppresses (1-based)
+    pub target_line: usize,
+}
+
+impl Suppression {
+    /// Whether this directive silences warning `code`
+    pub fn covers(&self, code: &str) -> bool {
+        self.rules.iter().any(|rule| rule_code(rule) == Some(code))
+    }
+
+    /// Rules that name no known warning
+    pub fn unknown_rules(&self) -> Vec<&str> {
+        self.rules
+            .iter()
+            .map(String::as_str)
+            .filter(|rule| rule_code(rule).is_none())
+            .collect()
+    }
+}
+
+/// Warning code for a rule name or code, ignoring case
+pub fn rule_code(rule: &str) -> Option<&'static str> {
+    RULES
+        .iter()
+        .find(|(name, code)| name.eq_ignore_ascii_case(rule) || code.eq_ignore_ascii_case(rule))
+        .map(|(_, code)| *code)
+}
+
+/// Rule name for a warning code
+pub fn rule_name(code: &str) -> Option<&'static str> {
+    RULES
+        .iter()
+        .find(|(_, c)| *c == code)
+        .map(|(name, _)| *name)
+}
+
+/// Find every directive in `source`
+///
+/// Only real line comments count: `atlas-ignore:` inside a string literal
+/// or a block comment is ignored.
+pub fn parse_suppressions(source: &str) -> Vec<Suppression> {
+    let (tokens, _) = Lexer::new(source).tokenize_with_comments();
+    let chars: Vec<char> = source.chars().collect();
+    let lines: Vec<&str> = source.lines().collect();
+
+    tokens
+        .iter()
+        .filter(|token| token.kind == TokenKind::LineComment)
+        .filter_map(|token| {
+            let body = token.lexeme.strip_prefix("//")?.trim_start();
+            let body = body.strip_prefix(DIRECTIVE)?;
+            let (rules, reason) = match body.split_once("--") {
+                Some((rules, reason)) => (rules, Some(reason.trim().to_string())),
+                None => (body, None),
+            };
+            let rules: Vec<String> = rules
+                .split(',')
+                .map(str::trim)
+                .filter(|rule| !rule.is_empty())
+                .map(str::to_string)
+                .collect();
+
+            let start = token.span.start.min(chars.len());
+            let line = 1 + chars[..start].iter().filter(|&&c| c == '\n').count();
+            let line_start = chars[..start]
+                .iter()
+                .rposition(|&c| c == '\n')
+                .map_or(0, |i| i + 1);
+            let standalone = chars[line_start..start].iter().all(|c| c.is_whitespace());
+            let target_line = if standalone {
+                next_code_line(&lines, line)
+            } else {
+                line
+            };
+
+            Some(Suppression {
+                rules,
+                reason: reason.filter(|r| !r.is_empty()),
+                line,
+                target_line,
+            })
+        })
+        .collect()
+}
+
+/// First line after `line` holding code rather than blanks or comments
+fn next_code_line(lines: &[&str], line: usize) -> usize {
+    lines
+        .iter()
+        .enumerate()
+        .skip(line)
+        .find(|(_, text)| {
+            let text = text.trim();
+            !text.is_empty() && !text.starts_with("//")
+        })
+        .map_or(line + 1, |(index, _)| index + 1)
+}
+
+/// Drop the warnings in `diagnostics` that a directive in `source` covers
+pub fn apply_suppressions(source: &str, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+    if !source.contains(DIRECTIVE) {
+        return diagnostics;
+    }
+    let suppressions = parse_suppressions(source);
+    diagnostics
+        .into_iter()
+        .filter(|diag| !is_suppressed(diag, &suppressions))
+        .collect()
+}
+
+/// Whether one of `suppressions` covers `diag`
+pub fn is_suppressed(diag: &Diagnostic, suppressions: &[Suppression]) -> bool {
+    diag.level == DiagnosticLevel::Warning
+        && suppressions
+            .iter()
+            .any(|s| s.target_line == diag.line && s.covers(&diag.code))
+}
@@ -163,14 +163,7 @@ impl fmt::Display for JsonValue {
         match self {
             JsonValue::Null => write!(f, "null"),
             JsonValue::Bool(b) => write!(f, "{}", b),
-            JsonValue::Number(n) => {
-                // Format numbers without trailing .0 for integers
-                if n.fract() == 0.0 && n.is_finite() {
-                    write!(f, "{}", *n as i64)
-                } else {
-                    write!(f, "{}", n)
-                }
-            }
+            JsonValue::Number(n) => write!(f, "{}", crate::value::format_number(*n)),
             JsonValue::String(s) => write!(f, "\"{}\"", s),
             JsonValue::Array(arr) => {
                 write!(f, "[")?;
@@ -11,6 +11,15 @@ enum StringScan {
     Error(Token),
 }
 
+/// Lexer position at the first newline inside a string
+#[derive(Clone, Copy)]
+struct LineEnd {
+    pos: usize,
+    line: u32,
+    column: u32,
+    value_len: usize,
+}
+
 impl Lexer {
     /// Scan a string literal
     pub(super) fn string(&mut self) -> Token {
@@ -117,6 +126,7 @@ impl Lexer {
         let mut value = String::new();
         let mut has_error = false;
         let mut error_token = None;
+        let mut first_line_end = None;
 
         while !self.is_at_end() {
             let current_char = self.peek();
@@ -143,6 +153,14 @@ impl Lexer {
             }
 
             if current_char == '\n' {
+                if first_line_end.is_none() {
+                    first_line_end = Some(LineEnd {
+                        pos: self.current,
+                        line: self.line,
+                        column: self.column,
+                        value_len: value.len(),
+                    });
+                }
                 self.line += 1;
                 self.column = 1;
             }
@@ -150,7 +168,12 @@ impl Lexer {
             if current_char == '\\' {
                 self.advance(); // consume backslash
                 if self.is_at_end() {
-                    return StringScan::Error(self.error_unterminated_string());
+                    return self.close_unterminated(
+                        TokenKind::String,
+                        part_start_pos,
+                        value,
+                        first_line_end,
+                    );
                 }
 
                 let escape_char = self.peek();
@@ -219,7 +242,7 @@ impl Lexer {
             }
         }
 
-        StringScan::Error(self.error_unterminated_string())
+        self.close_unterminated(TokenKind::String, part_start_pos, value, first_line_end)
     }
 
     fn scan_template_string_part(&mut self, part_start_pos: usize) -> StringScan {
@@ -259,7 +282,12 @@ impl Lexer {
             if current_char == '\\' {
                 self.advance(); // consume backslash
                 if self.is_at_end() {
-                    return StringScan::Error(self.error_unterminated_string());
+                    return self.close_unterminated(
+                        TokenKind::TemplateString,
+                        part_start_pos,
+                        value,
+                        None,
+                    );
                 }
 
                 let escape_char = self.peek();
@@ -340,7 +368,32 @@ impl Lexer {
             }
         }
 
-        StringScan::Error(self.error_unterminated_string())
+        self.close_unterminated(TokenKind::TemplateString, part_start_pos, value, None)
+    }
+
+    /// Close a string that runs into end of file without its closing quote
+    ///
+    /// Reports AT1002 once and hands the parser a complete token, so the
+    /// code before it keeps parsing. A `"` string that spans lines has most
+    /// likely lost its quote on the first one, so it is cut back to the end
+    /// of that line (`first_line_end`) and lexing resumes there; template
+    /// strings span lines by design and end at EOF instead.
+    fn close_unterminated(
+        &mut self,
+        kind: TokenKind,
+        part_start_pos: usize,
+        mut value: String,
+        first_line_end: Option<LineEnd>,
+    ) -> StringScan {
+        if let Some(end) = first_line_end {
+            self.current = end.pos;
+            self.line = end.line;
+            self.column = end.column;
+            value.truncate(end.value_len);
+        }
+        self.report_unterminated_string();
+        let span = Span::new_in(part_start_pos, self.current, self.file);
+        StringScan::Complete(Token::new(kind, value, span))
     }
 
     /// Scan exactly `count` hex digits and return the corresponding Unicode character.
@@ -360,13 +413,14 @@ impl Lexer {
     }
 
     /// Scan a number literal (integer, float, or scientific notation)
+    ///
+    /// `_` may separate digits (`10_000_000`); the separators are dropped from
+    /// the token's lexeme.
     pub(super) fn number(&mut self) -> Token {
         let start = self.current - 1; // -1 because we already advanced past first digit
 
         // Consume all digits
-        while !self.is_at_end() && self.peek().is_ascii_digit() {
-            self.advance();
-        }
+        self.digits();
 
         // Check for decimal point
         if !self.is_at_end() && self.peek() == '.' {
@@ -376,9 +430,7 @@ impl Lexer {
                     self.advance(); // consume .
 
                     // Consume fractional digits
-                    while !self.is_at_end() && self.peek().is_ascii_digit() {
-                        self.advance();
-                    }
+                    self.digits();
                 }
             }
         }
@@ -398,15 +450,95 @@ impl Lexer {
             }
 
             // Consume exponent digits
-            while !self.is_at_end() && self.peek().is_ascii_digit() {
-                self.advance();
-            }
+            self.digits();
         }
 
-        let lexeme: String = self.chars[start..self.current].iter().collect();
+        let lexeme: String = self.chars[start..self.current]
+            .iter()
+            .filter(|c| **c != '_')
+            .collect();
         self.make_token(TokenKind::Number, &lexeme)
     }
 
+    /// Consume a run of digits, allowing a single `_` between two digits
+    fn digits(&mut self) {
+        while !self.is_at_end() {
+            let separator =
+                self.peek() == '_' && self.peek_next().is_some_and(|c| c.is_ascii_digit());
+            if !self.peek().is_ascii_digit() && !separator {
+                break;
+            }
+            self.advance();
+        }
+    }
+
+    /// Whether a `/` here starts a regex literal rather than a division
+    ///
+    /// After something that ends an operand — a literal, a name, a closing
+    /// bracket, or a postfix `?` — a slash divides; everywhere else it opens
+    /// a regex.
+    pub(super) fn regex_allowed(&self) -> bool {
+        !matches!(
+            self.last_token_kind,
+            Some(
+                TokenKind::Number
+                    | TokenKind::String
+                    | TokenKind::TemplateString
+                    | TokenKind::Regex
+                    | TokenKind::True
+                    | TokenKind::False
+                    | TokenKind::Null
+                    | TokenKind::Identifier
+                    | TokenKind::RightParen
+                    | TokenKind::RightBracket
+                    | TokenKind::RightBrace
+                    | TokenKind::Question
+            )
+        )
+    }
+
+    /// Scan a regex literal `/pattern/flags` (opening `/` already consumed)
+    ///
+    /// The pattern ends at the first unescaped `/` outside a character class
+    /// and must close on the same line; otherwise nothing is consumed and the
+    /// caller lexes the slash as an operator. Flags are the letters that
+    /// follow — the parser validates them along with the pattern.
+    pub(super) fn regex(&mut self) -> Option<Token> {
+        let mut end = self.current;
+        let mut in_class = false;
+        loop {
+            match self.chars.get(end) {
+                None | Some('\n') => return None,
+                Some('\\') => {
+                    if matches!(self.chars.get(end + 1), None | Some('\n')) {
+                        return None;
+                    }
+                    end += 2;
+                    continue;
+                }
+                Some('[') => in_class = true,
+                Some(']') => in_class = false,
+                Some('/') if !in_class => break,
+                Some(_) => {}
+            }
+            end += 1;
+        }
+        // An empty pattern would be the start of a `//` comment
+        if end == self.current {
+            return None;
+        }
+
+        while self.current <= end {
+            self.advance();
+        }
+        while self.peek().is_ascii_alphabetic() {
+            self.advance();
+        }
+
+        let lexeme: String = self.chars[self.start_pos..self.current].iter().collect();
+        Some(self.make_token(TokenKind::Regex, &lexeme))
+    }
+
     /// Scan an identifier or keyword
     pub(super) fn identifier(&mut self) -> Token {
         let start = self.current - 1; // -1 because we already advanced past first char
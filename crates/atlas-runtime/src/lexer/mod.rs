@@ -195,7 +195,15 @@ impl Lexer {
                     self.make_token(TokenKind::Colon, ":")
                 }
             }
-            '?' => self.make_token(TokenKind::Question, "?"),
+            '?' => {
+                if self.match_char('.') {
+                    self.make_token(TokenKind::QuestionDot, "?.")
+                } else if self.match_char('?') {
+                    self.make_token(TokenKind::QuestionQuestion, "??")
+                } else {
+                    self.make_token(TokenKind::Question, "?")
+                }
+            }
 
             // Operators with potential compound forms
             '+' => {
@@ -222,6 +230,11 @@ impl Lexer {
                 }
             }
             '/' => {
+                if self.regex_allowed() {
+                    if let Some(token) = self.regex() {
+                        return token;
+                    }
+                }
                 if self.match_char('=') {
                     self.make_token(TokenKind::SlashEqual, "/=")
                 } else {
@@ -268,6 +281,7 @@ impl Lexer {
                 }
             }
             '@' => self.make_token(TokenKind::At, "@"),
+            '$' => self.make_token(TokenKind::Dollar, "$"),
             '&' => {
                 if self.match_char('&') {
                     self.make_token(TokenKind::AmpAmp, "&&")
@@ -278,6 +292,8 @@ impl Lexer {
             '|' => {
                 if self.match_char('|') {
                     self.make_token(TokenKind::PipePipe, "||")
+                } else if self.match_char('>') {
+                    self.make_token(TokenKind::PipeGreater, "|>")
                 } else {
                     self.make_token(TokenKind::Pipe, "|")
                 }
@@ -333,6 +349,19 @@ impl Lexer {
                     self.line += 1;
                     self.column = 1;
                 }
+                // `#!/usr/bin/env atlas` on the very first line makes a
+                // script directly executable; it is not part of the program
+                '#' if self.current == 0 && self.peek_next() == Some('!') => {
+                    while !self.is_at_end() && self.peek() != '\n' {
+                        self.advance();
+                    }
+                    if self.emit_comments {
+                        let text: String = self.chars[..self.current].iter().collect();
+                        let span = Span::new_in(0, self.current, self.file);
+                        self.pending_comments
+                            .push(Token::new(TokenKind::LineComment, text, span));
+                    }
+                }
                 '/' => {
                     if self.peek_next() == Some('/') {
                         let comment_start = self.current;
@@ -508,8 +537,8 @@ impl Lexer {
         self.push_descriptor_error(diag, message)
     }
 
-    /// Create an error token for unterminated strings (AT1002)
-    pub(super) fn error_unterminated_string(&mut self) -> Token {
+    /// Report an unterminated string (AT1002); the caller closes it
+    pub(super) fn report_unterminated_string(&mut self) {
         let (span, snippet) = self.current_error_span_and_snippet();
         let diag = UNTERMINATED_STRING
             .emit(span)
@@ -517,7 +546,7 @@ impl Lexer {
             .with_line(self.start_line as usize)
             .with_snippet(snippet)
             .with_label("string starts here");
-        self.push_descriptor_error(diag, "unterminated string literal")
+        self.diagnostics.push(diag);
     }
 
     /// Create an error token for invalid escape sequences (AT1003)
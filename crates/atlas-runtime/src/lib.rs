@@ -16,6 +16,7 @@ pub mod async_runtime;
 pub mod binder;
 pub mod bytecode;
 pub mod compiler;
+#[cfg(feature = "debugger")]
 pub mod debugger;
 pub mod diagnostic;
 #[cfg(feature = "ffi")]
@@ -27,17 +28,22 @@ pub mod method_dispatch;
 pub mod module_loader;
 pub mod optimizer;
 pub mod parser;
+pub mod pretty;
+pub mod profile;
 pub mod profiler;
 pub mod reflect;
+#[cfg(feature = "repl")]
 pub mod repl;
 pub mod resolver;
 pub mod runtime;
 pub mod security;
+#[cfg(feature = "debugger")]
 pub mod sourcemap;
 pub mod span;
 pub(crate) mod stack_trace;
 pub mod stdlib;
 pub mod symbol;
+pub mod timings;
 pub mod token;
 pub mod typecheck_dump;
 pub mod typechecker;
@@ -51,24 +57,28 @@ pub mod test_utils;
 
 // Re-export commonly used types
 pub use binder::Binder;
-pub use bytecode::{Bytecode, Opcode};
-pub use compiler::Compiler;
+pub use bytecode::{Bytecode, BytecodeCache, Opcode};
+pub use compiler::{AssetPolicy, Compiler};
 pub use diagnostic::{
     error_codes, formatter, normalizer, sort_diagnostics, warnings, Diagnostic, DiagnosticLevel,
     RelatedLocation, SuggestionDiff, DIAG_VERSION,
 };
 pub use json_value::JsonValue;
 pub use lexer::Lexer;
-pub use module_loader::{LoadedModule, ModuleLoader};
+pub use module_loader::{LoadedModule, ModuleCache, ModuleLoader};
 pub use parser::Parser;
+pub use pretty::PrettyConfig;
+#[cfg(feature = "repl")]
 pub use repl::{
     is_input_complete, IncompleteReason, InputCompleteness, MultilineInput, ReplCore, ReplResult,
 };
 pub use resolver::ModuleResolver;
-pub use runtime::{runtime_error_to_diagnostic, Atlas, RuntimeResult};
+pub use runtime::{
+    runtime_error_to_diagnostic, Atlas, CompiledProgram, ExprLimits, RunOutcome, RuntimeResult,
+};
 pub use security::{
     AuditEntry, AuditEvent, AuditLogger, MemoryAuditLogger, NullAuditLogger, Permission,
-    PermissionSet, SecurityContext, SecurityError,
+    PermissionSet, RegexLimits, SecurityContext, SecurityError,
 };
 pub use span::Span;
 pub use symbol::{Symbol, SymbolKind, SymbolTable};
@@ -53,12 +53,24 @@ pub enum TypeTag {
     ReflectNs,
     /// Static namespace: sqlite.open(), etc.
     SqliteNs,
-    /// Instance methods on SqliteConnection values (execute, query, close)
+    /// Instance methods on SqliteConnection values (execute, query, close, transactions)
     SqliteConnection,
+    /// Static namespace: kv.open()
+    KvNs,
+    /// Static namespace: csv.parse(), csv.parseRecords(), csv.stringify()
+    CsvNs,
+    /// Instance methods on KvStore values (get, set, delete, transaction, etc.)
+    KvStore,
+    /// Instance methods on ProgressBar values (advance, set, setLabel, finish)
+    ProgressBar,
     /// Instance methods on DateTime values (year, month, day, format, etc.)
     DateTime,
     /// Instance methods on Regex values (test, find, findAll, replace, etc.)
     RegexValue,
+    /// Instance methods on Bytes values (len, slice, toHex, toBase64, etc.)
+    Bytes,
+    /// Instance methods on FileHandle values (readLine, readChunk, writeChunk, close)
+    FileHandle,
     /// Instance methods on ProcessOutput values (stdout, stderr, exitCode, success)
     ProcessOutput,
     /// Static namespace: Gzip.compress(), Gzip.decompress(), etc.
@@ -151,12 +163,22 @@ pub fn resolve_method(
         TypeTag::SqliteConnection => {
             resolve_sqlite_connection_method(method_name).map(std::borrow::Cow::Borrowed)
         }
+        TypeTag::KvNs => resolve_kv_ns_method(method_name).map(std::borrow::Cow::Borrowed),
+        TypeTag::KvStore => resolve_kv_store_method(method_name).map(std::borrow::Cow::Borrowed),
+        TypeTag::CsvNs => resolve_csv_ns_method(method_name).map(std::borrow::Cow::Borrowed),
+        TypeTag::ProgressBar => {
+            resolve_progress_bar_method(method_name).map(std::borrow::Cow::Borrowed)
+        }
         TypeTag::DateTime => {
             resolve_datetime_instance_method(method_name).map(std::borrow::Cow::Borrowed)
         }
         TypeTag::RegexValue => {
             resolve_regex_instance_method(method_name).map(std::borrow::Cow::Borrowed)
         }
+        TypeTag::Bytes => resolve_bytes_method(method_name).map(std::borrow::Cow::Borrowed),
+        TypeTag::FileHandle => {
+            resolve_file_handle_method(method_name).map(std::borrow::Cow::Borrowed)
+        }
         TypeTag::ProcessOutput => {
             resolve_process_output_method(method_name).map(std::borrow::Cow::Borrowed)
         }
@@ -194,6 +216,8 @@ fn resolve_array_ns_method(method_name: &str) -> Option<&'static str> {
     let func_name = match method_name {
         // D-062: Array.isArray(x) — mirrors TypeScript, acts as typeof(x) == 'array'
         "isArray" => "arrayIsArray",
+        // Array.range(start, end, step?) — end is exclusive, step defaults to 1
+        "range" => "arrayRange",
         _ => return None,
     };
     Some(func_name)
@@ -249,7 +273,9 @@ pub fn is_allowed_bare_global(name: &str) -> bool {
         | "is_ok" | "is_err" | "is_some" | "is_none"
         | "isOk" | "isErr" | "isSome" | "isNone"
         // Core utilities (print is console.log, not a bare global)
-        | "len" | "typeof" | "type_of" | "toString" | "str"
+        | "len" | "typeof" | "type_of" | "toString" | "str" | "inspect"
+        // Compile-time asset embedding (the compiler replaces these calls)
+        | "includeStr" | "includeBytes"
         // Type guard predicates (both snake_case and camelCase; registered in VM stdlib)
         | "is_string" | "isString"
         | "is_number" | "isNumber"
@@ -322,6 +348,8 @@ const STATIC_NAMESPACES: &[(&str, TypeTag)] = &[
     ("console", TypeTag::ConsoleNs),
     ("reflect", TypeTag::ReflectNs),
     ("sqlite", TypeTag::SqliteNs),
+    ("kv", TypeTag::KvNs),
+    ("csv", TypeTag::CsvNs),
     ("gzip", TypeTag::GzipNs),
     ("tar", TypeTag::TarNs),
     ("zip", TypeTag::ZipNs),
@@ -358,6 +386,7 @@ fn resolve_http_response_method(method_name: &str) -> Option<&'static str> {
         "header" => "httpHeader",
         "url" => "httpUrl",
         "isSuccess" => "httpIsSuccess",
+        "toMap" => "httpResponseToMap",
         _ => return None,
     };
     Some(func_name)
@@ -406,6 +435,7 @@ fn resolve_string_method(method_name: &str) -> Option<&'static str> {
         "toNumber" => "parseFloat",
         "toInt" => "parseInt",
         "toBool" => "toBool",
+        "toBytes" => "stringToBytes",
         _ => return None,
     };
     Some(func_name)
@@ -448,6 +478,32 @@ fn resolve_array_method(method_name: &str) -> Option<&'static str> {
     Some(func_name)
 }
 
+/// Resolve a Bytes method call to its stdlib function name.
+fn resolve_bytes_method(method_name: &str) -> Option<&'static str> {
+    let func_name = match method_name {
+        "len" | "length" => "bytesLen",
+        "slice" => "bytesSlice",
+        "toHex" => "bytesToHex",
+        "toBase64" => "bytesToBase64",
+        "toArray" => "bytesToArray",
+        "toUtf8" => "bytesToUtf8",
+        _ => return None,
+    };
+    Some(func_name)
+}
+
+/// Resolve a FileHandle method call to its stdlib function name.
+fn resolve_file_handle_method(method_name: &str) -> Option<&'static str> {
+    let func_name = match method_name {
+        "readLine" => "fileHandleReadLine",
+        "readChunk" => "fileHandleReadChunk",
+        "writeChunk" => "fileHandleWriteChunk",
+        "close" => "fileHandleClose",
+        _ => return None,
+    };
+    Some(func_name)
+}
+
 /// Resolve a HashMap method call to its stdlib function name.
 /// HashMap uses CoW semantics — mutating methods return a new map (write-back required).
 fn resolve_hashmap_method(method_name: &str) -> Option<&'static str> {
@@ -581,6 +637,10 @@ fn resolve_json_ns_method(method_name: &str) -> Option<&'static str> {
         "getArray" => "jsonNsGetArray",
         "getObject" => "jsonNsGetObject",
         "isNull" => "jsonNsIsNull",
+        // Same functions as the JsonValue methods, with the json value first
+        "getPath" => "jsonGetPath",
+        "setPath" => "jsonSetPath",
+        "hasPath" => "jsonHasPath",
         _ => return None,
     };
     Some(func_name)
@@ -631,7 +691,7 @@ fn resolve_env_ns_method(method_name: &str) -> Option<&'static str> {
         "get" => "getEnv",
         "set" => "setEnv",
         "unset" => "unsetEnv",
-        "list" => "listEnv",
+        "list" | "all" => "listEnv",
         _ => return None,
     };
     Some(func_name)
@@ -644,11 +704,18 @@ fn resolve_file_ns_method(method_name: &str) -> Option<&'static str> {
         // Core I/O
         "read" => "fileNsRead",
         "write" => "fileNsWrite",
+        "readBytes" => "fileNsReadBytes",
+        "writeBytes" => "fileNsWriteBytes",
+        "open" => "fileNsOpen",
         "append" => "fileNsAppend",
+        "writeAtomic" => "fileNsWriteAtomic",
         "exists" => "fileNsExists",
         "remove" => "fileNsRemove",
         "rename" => "fileNsRename",
         "copy" => "fileNsCopy",
+        "move" => "fileNsMove",
+        "touch" => "fileNsTouch",
+        "setPermissions" => "fileNsSetPermissions",
         // Directory operations
         "createDir" => "fileNsCreateDir",
         "removeDir" => "fileNsRemoveDir",
@@ -702,9 +769,13 @@ fn resolve_process_ns_method(method_name: &str) -> Option<&'static str> {
         "exit" => "processExit",
         "spawn" => "processNsSpawn",
         "exec" => "exec",
+        "execCapture" => "execCapture",
         "shell" => "shell",
         "shellOut" => "shellOut",
         "args" | "getProcessArgs" => "getProcessArgs",
+        "parseArgs" => "processParseArgs",
+        "argsHelp" => "processArgsHelp",
+        "loadConfig" => "processLoadConfig",
         "run" => "processRun",
         "waitFor" => "processNsWaitFor",
         "kill" => "processNsKill",
@@ -713,6 +784,7 @@ fn resolve_process_ns_method(method_name: &str) -> Option<&'static str> {
         "stdout" => "processNsStdout",
         "stderr" => "processNsStderr",
         "output" => "processNsOutput",
+        "gc" => "processNsGc",
         _ => return None,
     };
     Some(func_name)
@@ -742,7 +814,16 @@ fn resolve_datetime_ns_method(method_name: &str) -> Option<&'static str> {
         "parseRfc2822" => "dateTimeParseRfc2822",
         "tryParse" => "dateTimeTryParse",
         "utc" => "dateTimeUtc",
-        _ => return None,
+        "timestamp" => "dateTimeTimestamp",
+        "formatDate" => "dateTimeFormatDate",
+        "parseDate" => "dateTimeParseDate",
+        "durationFromSeconds" => "durationFromSeconds",
+        "durationFromMinutes" => "durationFromMinutes",
+        "durationFromHours" => "durationFromHours",
+        "durationFromDays" => "durationFromDays",
+        "durationFormat" => "durationFormat",
+        // Instance methods called with the DateTime first: DateTime.addDays(dt, 1)
+        _ => return resolve_datetime_instance_method(method_name),
     };
     Some(func_name)
 }
@@ -754,6 +835,17 @@ fn resolve_path_ns_method(method_name: &str) -> Option<&'static str> {
         "dirname" => "pathDirname",
         "basename" => "pathBasename",
         "extension" | "extname" => "pathExtension", // extname is Node.js alias
+        "stem" => "pathStem",
+        "withExtension" => "pathWithExtension",
+        "expandHome" => "pathExpandHome",
+        "parse" => "pathParse",
+        "relative" => "pathRelative",
+        "equals" => "pathEquals",
+        "drive" => "pathDrive",
+        "toPosix" => "pathToPosix",
+        "toWindows" => "pathToWindows",
+        "toPlatform" => "pathToPlatform",
+        "delimiter" => "pathDelimiter",
         "exists" => "pathExists",
         "isAbsolute" => "pathIsAbsolute",
         "isRelative" => "pathIsRelative",
@@ -779,6 +871,7 @@ fn resolve_http_ns_method(method_name: &str) -> Option<&'static str> {
         "put" => "httpNsPut",
         "delete" => "httpNsDelete",
         "patch" => "httpNsPatch",
+        "request" => "httpNsRequest",
         "checkPermission" => "httpCheckPermission",
         "serve" => "httpServe",
         _ => return None,
@@ -805,6 +898,7 @@ fn resolve_net_ns_method(method_name: &str) -> Option<&'static str> {
         "tcpListenerClose" => "tcpListenerClose",
         // UDP
         "udpBind" => "udpBind",
+        "udpConnect" => "udpConnect",
         "udpSend" => "udpSend",
         "udpReceive" => "udpReceive",
         "udpClose" => "udpClose",
@@ -815,6 +909,10 @@ fn resolve_net_ns_method(method_name: &str) -> Option<&'static str> {
         "tlsRead" => "tlsRead",
         "tlsWrite" => "tlsWrite",
         "tlsClose" => "tlsClose",
+        // Any socket
+        "send" => "netSend",
+        "recv" => "netRecv",
+        "close" => "netClose",
         // WebSocket
         "wsConnect" => "wsConnect",
         "wsSend" => "wsSend",
@@ -854,6 +952,10 @@ fn resolve_encoding_ns_method(method_name: &str) -> Option<&'static str> {
         "hexDecode" => "encodingNsHexDecode",
         "urlEncode" => "encodingNsUrlEncode",
         "urlDecode" => "encodingNsUrlDecode",
+        // Bytes constructors
+        "fromHex" => "encodingNsFromHex",
+        "fromBase64" => "encodingNsFromBase64",
+        "fromArray" => "encodingNsFromArray",
         _ => return None,
     };
     Some(func_name)
@@ -974,6 +1076,7 @@ pub fn namespace_hint_for_bare_global(name: &str) -> Option<&'static str> {
         "httpPost" | "httpNsPost" => Some("http.post(url, body?, options?)"),
         "httpPut" | "httpNsPut" => Some("http.put(url, body?, options?)"),
         "httpDelete" | "httpNsDelete" => Some("http.delete(url, options?)"),
+        "httpRequest" | "httpNsRequest" => Some("http.request(options)"),
         // net
         "tcpConnect" => Some("net.tcpConnect(addr)"),
         "udpBind" => Some("net.udpBind(addr)"),
@@ -1052,7 +1155,9 @@ pub fn namespace_hint_for_bare_global(name: &str) -> Option<&'static str> {
         | "jsonNsKeys" | "jsonAsString" | "jsonAsNumber" | "jsonAsBool" | "jsonGetString"
         | "jsonGetNumber" | "jsonGetBool" | "jsonGetArray" | "jsonGetObject" | "jsonIsNull"
         | "jsonNsGetString" | "jsonNsGetNumber" | "jsonNsGetBool" | "jsonNsGetArray"
-        | "jsonNsGetObject" | "jsonNsIsNull" => Some("Json.method() — use namespace syntax"),
+        | "jsonNsGetObject" | "jsonNsIsNull" | "jsonGetPath" | "jsonSetPath" | "jsonHasPath" => {
+            Some("Json.method() — use namespace syntax")
+        }
         // Additional prefixed bare globals (only names NOT matched earlier in this function)
         "fsRead" | "fsWrite" | "fsAppend" | "fsExists" | "fsRemove" | "fsCopy" | "fsMove"
         | "fsIsDir" | "fsIsFile" => Some("file.method() — use namespace syntax"),
@@ -1176,6 +1281,21 @@ fn resolve_console_ns_method(method_name: &str) -> Option<&'static str> {
         "error" => "consoleError",
         "warn" => "consoleWarn",
         "debug" => "consoleDebug",
+        "printTable" => "consolePrintTable",
+        "progressBar" => "consoleProgressBar",
+        "styled" => "consoleStyled",
+        _ => return None,
+    };
+    Some(func_name)
+}
+
+/// Resolve ProgressBar instance method → stdlib function name.
+fn resolve_progress_bar_method(method_name: &str) -> Option<&'static str> {
+    let func_name = match method_name {
+        "advance" => "progressBarAdvance",
+        "set" => "progressBarSet",
+        "setLabel" => "progressBarSetLabel",
+        "finish" => "progressBarFinish",
         _ => return None,
     };
     Some(func_name)
@@ -1218,6 +1338,45 @@ fn resolve_sqlite_connection_method(method_name: &str) -> Option<&'static str> {
         "execute" => "sqlite_execute",
         "query" => "sqlite_query",
         "close" => "sqlite_close",
+        "begin" => "sqlite_begin",
+        "commit" => "sqlite_commit",
+        "rollback" => "sqlite_rollback",
+        "inTransaction" => "sqlite_in_transaction",
+        _ => return None,
+    };
+    Some(func_name)
+}
+
+/// Resolve kv.method() → stdlib function name.
+fn resolve_kv_ns_method(method_name: &str) -> Option<&'static str> {
+    let func_name = match method_name {
+        "open" => "kv_open",
+        _ => return None,
+    };
+    Some(func_name)
+}
+
+/// Resolve csv.method() → stdlib function name.
+fn resolve_csv_ns_method(method_name: &str) -> Option<&'static str> {
+    let func_name = match method_name {
+        "parse" => "csvNsParse",
+        "parseRecords" => "csvNsParseRecords",
+        "stringify" => "csvNsStringify",
+        _ => return None,
+    };
+    Some(func_name)
+}
+
+/// Resolve KvStore instance method → stdlib function name.
+fn resolve_kv_store_method(method_name: &str) -> Option<&'static str> {
+    let func_name = match method_name {
+        "get" => "kv_get",
+        "set" => "kv_set",
+        "delete" => "kv_delete",
+        "has" => "kv_has",
+        "keys" => "kv_keys",
+        "transaction" => "kv_transaction",
+        "close" => "kv_close",
         _ => return None,
     };
     Some(func_name)
@@ -1253,7 +1412,7 @@ fn resolve_datetime_instance_method(method_name: &str) -> Option<&'static str> {
         "addMinutes" => "dateTimeAddMinutes",
         "addHours" => "dateTimeAddHours",
         "addDays" => "dateTimeAddDays",
-        "diff" => "dateTimeDiff",
+        "diff" | "diffSeconds" => "dateTimeDiff",
         "compare" => "dateTimeCompare",
         "toIso" => "dateTimeToIso",
         "format" => "dateTimeFormat",
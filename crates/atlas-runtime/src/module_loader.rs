@@ -9,12 +9,16 @@ use crate::diagnostic::error_codes::{CIRCULAR_DEPENDENCY, MODULE_NOT_FOUND};
 use crate::diagnostic::Diagnostic;
 use crate::lexer::Lexer;
 use crate::parser::Parser;
+use crate::profile::LanguageProfile;
 use crate::resolver::ModuleResolver;
 use crate::span::Span;
 use crate::symbol::SymbolTable;
-use std::collections::{HashMap, HashSet, VecDeque};
+use crate::timings::{Phase, PhaseTimings};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
 
 /// A loaded module with its AST and metadata
 #[derive(Debug, Clone)]
@@ -77,6 +81,259 @@ impl Default for ModuleRegistry {
     }
 }
 
+/// Parsed and compiled modules kept in memory from one load to the next
+///
+/// A long-lived process such as `atlas daemon` shares one cache between all
+/// the programs it loads, so a file is lexed, parsed and compiled again only
+/// when it — or, for bytecode, one of its imports — changes. Entries are
+/// checked against the file's current text, never against timestamps.
+/// Every module is still bound and type-checked on each load.
+#[derive(Debug, Default)]
+pub struct ModuleCache {
+    /// Text of each module with what parsing it produced
+    parsed: Mutex<HashMap<PathBuf, ParsedModule>>,
+    /// Bytecode of each module with the key it was compiled under
+    compiled: Mutex<HashMap<PathBuf, (String, crate::bytecode::Bytecode)>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct ParsedModule {
+    source: String,
+    module: LoadedModule,
+    errors: Vec<Diagnostic>,
+}
+
+impl ModuleCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The module parsed from `source` at `path`, with its parse errors, if
+    /// the file has not changed since
+    fn parsed(&self, path: &Path, source: &str) -> Option<(LoadedModule, Vec<Diagnostic>)> {
+        let parsed = lock(&self.parsed);
+        match parsed.get(path).filter(|entry| entry.source == source) {
+            Some(entry) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some((entry.module.clone(), entry.errors.clone()))
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    fn store_parsed(&self, source: String, module: &LoadedModule, errors: &[Diagnostic]) {
+        lock(&self.parsed).insert(
+            module.path.clone(),
+            ParsedModule {
+                source,
+                module: module.clone(),
+                errors: errors.to_vec(),
+            },
+        );
+    }
+
+    /// Key the bytecode of `module` is cached under, or `None` when the
+    /// module or one of its imports did not go through this cache. `keys`
+    /// holds the keys of the modules loaded before it.
+    pub(crate) fn key(
+        &self,
+        module: &LoadedModule,
+        keys: &HashMap<PathBuf, String>,
+    ) -> Option<String> {
+        let parsed = lock(&self.parsed);
+        let source = &parsed.get(&module.path)?.source;
+        let dependency_keys = module
+            .resolved_imports
+            .values()
+            .map(|path| keys.get(path).map(String::as_str))
+            .collect::<Option<Vec<_>>>()?;
+        Some(crate::bytecode::BytecodeCache::key(
+            &module.path,
+            source,
+            &dependency_keys,
+        ))
+    }
+
+    /// Bytecode of the module at `path` compiled under `key`
+    pub(crate) fn compiled(&self, path: &Path, key: &str) -> Option<crate::bytecode::Bytecode> {
+        lock(&self.compiled)
+            .get(path)
+            .filter(|(compiled_key, _)| compiled_key == key)
+            .map(|(_, bytecode)| bytecode.clone())
+    }
+
+    /// Remember the bytecode of the module at `path`, replacing the one
+    /// compiled from an earlier version
+    pub(crate) fn store_compiled(
+        &self,
+        path: &Path,
+        key: String,
+        bytecode: &crate::bytecode::Bytecode,
+    ) {
+        lock(&self.compiled).insert(path.to_path_buf(), (key, bytecode.clone()));
+    }
+
+    /// Number of modules held
+    pub fn len(&self) -> usize {
+        lock(&self.parsed).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Loads that reused a parsed module
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Loads that had to parse the file
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Forget every module
+    pub fn clear(&self) {
+        lock(&self.parsed).clear();
+        lock(&self.compiled).clear();
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Gives every module its own global namespace when modules are linked
+///
+/// Each module compiles with plain global names. Before its bytecode is
+/// appended to the program, the linker renames the globals a non-entry module
+/// defines to `module::name`, points imported names (including `ns.member`
+/// references from namespace imports and names reached through re-exports) at
+/// the defining module's qualified global, and leaves builtins alone. Two
+/// modules can then declare the same private name without clobbering each
+/// other. The entry module keeps bare names so embedders still see `main` and
+/// friends under their declared names.
+pub struct ModuleLinker {
+    root: PathBuf,
+    entry: PathBuf,
+    /// Module path → exported name → global that holds the value
+    exports: HashMap<PathBuf, HashMap<String, String>>,
+}
+
+impl ModuleLinker {
+    /// Build the export tables for `modules`, which must be in load order
+    /// (dependencies first, entry last)
+    pub fn new(root: &Path, modules: &[LoadedModule]) -> Self {
+        let mut linker = Self {
+            root: root.to_path_buf(),
+            entry: modules.last().map(|m| m.path.clone()).unwrap_or_default(),
+            exports: HashMap::new(),
+        };
+        for module in modules {
+            let mut table = HashMap::new();
+            for item in &module.ast.items {
+                let Item::Export(export_decl) = item else {
+                    continue;
+                };
+                if let crate::ast::ExportItem::ReExport { names, source, .. } = &export_decl.item {
+                    let source_exports = module
+                        .resolved_imports
+                        .get(source)
+                        .and_then(|path| linker.exports.get(path));
+                    for spec in names {
+                        let exported = spec.alias.as_ref().unwrap_or(&spec.name);
+                        if let Some(global) =
+                            source_exports.and_then(|table| table.get(&spec.name.name))
+                        {
+                            table.insert(exported.name.clone(), global.clone());
+                        }
+                    }
+                }
+            }
+            for name in &module.exports {
+                if !table.contains_key(name) {
+                    table.insert(name.clone(), linker.qualify(&module.path, name));
+                }
+            }
+            linker.exports.insert(module.path.clone(), table);
+        }
+        linker
+    }
+
+    /// Global name `name` declared in the module at `path` links to
+    fn qualify(&self, path: &Path, name: &str) -> String {
+        if path == self.entry {
+            return name.to_string();
+        }
+        let id = path
+            .strip_prefix(&self.root)
+            .unwrap_or(path)
+            .with_extension("");
+        let id = id
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        format!("{}::{}", id, name)
+    }
+
+    /// Rename `bytecode`'s globals (compiled from `module`) to their linked names
+    pub fn link(&self, module: &LoadedModule, bytecode: &mut crate::bytecode::Bytecode) {
+        use crate::ast::ImportSpecifier;
+
+        let mut imported: HashMap<String, String> = HashMap::new();
+        for import in &module.imports {
+            let Some(table) = module
+                .resolved_imports
+                .get(&import.source)
+                .and_then(|path| self.exports.get(path))
+            else {
+                continue;
+            };
+            for specifier in &import.specifiers {
+                match specifier {
+                    ImportSpecifier::Named { name, .. } => {
+                        if let Some(global) = table.get(&name.name) {
+                            imported.insert(name.name.clone(), global.clone());
+                        }
+                    }
+                    ImportSpecifier::Namespace { alias, .. } => {
+                        for (member, global) in table {
+                            imported.insert(format!("{}.{}", alias.name, member), global.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        // Slots this module assigns are the globals it owns
+        let defined: HashSet<u16> = crate::optimizer::decode_instructions(bytecode)
+            .iter()
+            .filter(|instr| instr.opcode == crate::bytecode::Opcode::SetGlobal)
+            .map(|instr| instr.read_u16())
+            .collect();
+
+        let is_entry = module.path == self.entry;
+        for (slot, name) in bytecode.global_names.iter_mut().enumerate() {
+            if let Some(global) = imported.get(name.as_str()) {
+                *name = global.clone();
+            } else if !is_entry
+                && defined.contains(&(slot as u16))
+                // Impl methods are found by their mangled name at runtime
+                && !name.starts_with("__impl__")
+                && !name.starts_with("__static__")
+            {
+                *name = self.qualify(&module.path, name);
+            }
+        }
+    }
+}
+
 /// Module loader - loads files, builds dependency graphs, performs topological sort
 pub struct ModuleLoader {
     /// Module resolver for path resolution
@@ -85,8 +342,14 @@ pub struct ModuleLoader {
     cache: HashMap<PathBuf, LoadedModule>,
     /// Dependency graph (module -> its dependencies)
     dependencies: HashMap<PathBuf, Vec<PathBuf>>,
-    /// Modules currently being loaded (for cycle detection during loading)
-    loading: HashSet<PathBuf>,
+    /// Import chain currently being loaded, entry first (for cycle detection)
+    loading: Vec<PathBuf>,
+    /// Time spent lexing and parsing the loaded files
+    timings: PhaseTimings,
+    /// Keyword hiding and aliases applied to every loaded file
+    profile: LanguageProfile,
+    /// Modules parsed by earlier loads
+    module_cache: Option<Arc<ModuleCache>>,
 }
 
 impl ModuleLoader {
@@ -96,10 +359,31 @@ impl ModuleLoader {
             resolver: ModuleResolver::new(root),
             cache: HashMap::new(),
             dependencies: HashMap::new(),
-            loading: HashSet::new(),
+            loading: Vec::new(),
+            timings: PhaseTimings::new(),
+            profile: LanguageProfile::default(),
+            module_cache: None,
         }
     }
 
+    /// Reuse the modules `cache` holds, and add the ones parsed here
+    ///
+    /// Only used while no language profile is set, since a profile changes
+    /// what a file parses to.
+    pub fn set_module_cache(&mut self, cache: Arc<ModuleCache>) {
+        self.module_cache = Some(cache);
+    }
+
+    /// Lex every loaded file with `profile`'s keyword hiding and aliases
+    pub fn set_language_profile(&mut self, profile: LanguageProfile) {
+        self.profile = profile;
+    }
+
+    /// Lex and parse time of every file loaded so far
+    pub fn timings(&self) -> &PhaseTimings {
+        &self.timings
+    }
+
     /// Load a module and all its dependencies
     ///
     /// Returns modules in topological order (dependencies first).
@@ -118,7 +402,7 @@ impl ModuleLoader {
     ) -> Result<Vec<LoadedModule>, Vec<Diagnostic>> {
         // Load the entry module and all dependencies, collecting all errors.
         let mut all_errors: Vec<Diagnostic> = Vec::new();
-        self.load_recursive(entry_point, &mut all_errors);
+        self.load_recursive(entry_point, None, &mut all_errors);
 
         // If any module had parse errors, report them all now.
         if !all_errors.is_empty() {
@@ -152,7 +436,13 @@ impl ModuleLoader {
     /// Errors are accumulated into `all_errors` rather than returned immediately.
     /// This ensures ALL files in the import graph are visited and ALL parse errors
     /// are collected in a single pass — not just the first file that fails.
-    fn load_recursive(&mut self, module_path: &Path, all_errors: &mut Vec<Diagnostic>) {
+    /// `imported_at` is the import that reached this module, if any.
+    fn load_recursive(
+        &mut self,
+        module_path: &Path,
+        imported_at: Option<(&Path, Span)>,
+        all_errors: &mut Vec<Diagnostic>,
+    ) {
         let abs_path = module_path.to_path_buf();
 
         // Check cache - if already loaded (successfully or with errors noted), skip
@@ -161,19 +451,27 @@ impl ModuleLoader {
         }
 
         // Check if currently being loaded (circular dependency) — fatal, stop this branch
-        if self.loading.contains(&abs_path) {
+        if let Some(start) = self.loading.iter().position(|p| p == &abs_path) {
+            let cycle = self.loading[start..]
+                .iter()
+                .chain(std::iter::once(&abs_path))
+                .map(|p| self.display_path(p))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            let (importer, span) = imported_at.unwrap_or((module_path, Span::dummy()));
             all_errors.push(
                 CIRCULAR_DEPENDENCY
-                    .emit(Span::dummy())
-                    .arg("cycle", abs_path.display().to_string())
+                    .emit(span)
+                    .arg("cycle", &cycle)
                     .build()
-                    .with_label(format!("module: {}", abs_path.display())),
+                    .with_file(importer.display().to_string())
+                    .with_label("this import closes the cycle"),
             );
             return;
         }
 
         // Mark as currently loading
-        self.loading.insert(abs_path.clone());
+        self.loading.push(abs_path.clone());
 
         // Load and parse the module file — returns (partial_module, parse_errors).
         // Even on parse errors we get the partial AST so we can follow its imports.
@@ -213,13 +511,21 @@ impl ModuleLoader {
                 .add_dependency(abs_path.clone(), dep_path.clone());
 
             // Recurse — errors accumulate, never short-circuit
-            self.load_recursive(&dep_path, all_errors);
+            self.load_recursive(&dep_path, Some((&abs_path, import.span)), all_errors);
         }
 
         self.dependencies.insert(abs_path.clone(), deps);
         loaded.resolved_imports = resolved_import_map;
         self.cache.insert(abs_path.clone(), loaded);
-        self.loading.remove(&abs_path);
+        self.loading.pop();
+    }
+
+    /// `path` relative to the project root, for diagnostics
+    fn display_path(&self, path: &Path) -> String {
+        path.strip_prefix(self.resolver.root())
+            .unwrap_or(path)
+            .display()
+            .to_string()
     }
 
     /// Load and parse a single module file, always returning a partial module.
@@ -228,7 +534,7 @@ impl ModuleLoader {
     /// It returns `(module, errors)` — the module may have an incomplete AST when
     /// errors are present, but it always has the import declarations extracted so
     /// `load_recursive` can follow the import graph even for broken files.
-    fn load_and_parse_partial(&self, path: &Path) -> (LoadedModule, Vec<Diagnostic>) {
+    fn load_and_parse_partial(&mut self, path: &Path) -> (LoadedModule, Vec<Diagnostic>) {
         // Read file contents — a missing file is fatal for this module only
         let source = match fs::read_to_string(path) {
             Ok(s) => s,
@@ -254,19 +560,38 @@ impl ModuleLoader {
             }
         };
 
-        // Lex — lex errors are returned alongside an empty token stream
+        let module_cache = self
+            .module_cache
+            .clone()
+            .filter(|_| self.profile == LanguageProfile::default());
+        if let Some(hit) = module_cache
+            .as_ref()
+            .and_then(|cache| cache.parsed(path, &source))
+        {
+            self.timings.modules += 1;
+            return hit;
+        }
+
+        // Lex — lex errors are returned alongside the recovered token stream
         let mut lexer = Lexer::new(&source).with_file(path.display().to_string());
-        let (tokens, lex_diags) = lexer.tokenize();
+        let (mut tokens, lex_diags) = self.timings.time(Phase::Lex, || lexer.tokenize());
+        self.profile.rewrite_keywords(&mut tokens);
         let lex_errors: Vec<_> = lex_diags.into_iter().filter(|d| d.is_error()).collect();
 
         // Parse — always returns the partial AST and any parse errors
         let mut parser = Parser::new(tokens);
-        let (ast, parse_diags) = parser.parse();
+        let (ast, parse_diags) = self.timings.time(Phase::Parse, || parser.parse());
+        self.timings.modules += 1;
         let parse_errors: Vec<_> = parse_diags.into_iter().filter(|d| d.is_error()).collect();
 
-        // Collect all errors from this file
-        let mut errors = lex_errors;
-        errors.extend(parse_errors);
+        // Collect the errors from this file. The lexer closes what it cannot
+        // finish (an unterminated string) so the AST stays usable, but the
+        // parser's complaints about the result only echo the lex error.
+        let errors = if lex_errors.is_empty() {
+            parse_errors
+        } else {
+            lex_errors
+        };
 
         // Extract exports and imports from whatever the parser produced
         let mut exports = Vec::new();
@@ -284,7 +609,7 @@ impl ModuleLoader {
                                 specifiers: names
                                     .iter()
                                     .map(|s| crate::ast::ImportSpecifier::Named {
-                                        name: s.alias.as_ref().unwrap_or(&s.name).clone(),
+                                        name: s.name.clone(),
                                         span: s.span,
                                     })
                                     .collect(),
@@ -322,118 +647,61 @@ impl ModuleLoader {
             }
         }
 
-        (
-            LoadedModule {
-                path: path.to_path_buf(),
-                ast,
-                exports,
-                imports,
-                resolved_imports: std::collections::HashMap::new(), // populated in load_recursive
-            },
-            errors,
-        )
+        let module = LoadedModule {
+            path: path.to_path_buf(),
+            ast,
+            exports,
+            imports,
+            resolved_imports: std::collections::HashMap::new(), // populated in load_recursive
+        };
+        if let Some(cache) = module_cache {
+            cache.store_parsed(source, &module, &errors);
+        }
+        (module, errors)
     }
 
     /// Perform topological sort to get initialization order
     ///
     /// Returns modules in dependency order (dependencies before dependents).
-    /// Uses Kahn's algorithm.
+    /// The order is a depth-first post-order that follows each module's
+    /// imports in declaration order, so it depends only on the source text:
+    /// a module's first import initializes (with its own dependencies) before
+    /// its second, and a module shared by several importers initializes once,
+    /// ahead of the first of them.
     /// Only includes modules reachable from the entry point.
     fn topological_sort(&self, entry: &Path) -> Result<Vec<PathBuf>, Vec<Diagnostic>> {
-        // First, find all modules reachable from entry using DFS
-        let reachable = self.find_reachable(entry);
-
-        // Build in-degree map (count of incoming edges) for reachable nodes only
-        let mut in_degree: HashMap<PathBuf, usize> = HashMap::new();
-
-        // Initialize in-degrees for reachable nodes
-        for node in &reachable {
-            in_degree.insert(node.clone(), 0);
-        }
-
-        // Calculate in-degrees (only for reachable nodes)
-        for from in &reachable {
-            if let Some(deps) = self.dependencies.get(from) {
-                for _dep in deps {
-                    if reachable.contains(_dep) {
-                        *in_degree
-                            .get_mut(from)
-                            .expect("in_degree should contain all reachable nodes") += 1;
-                    }
-                }
-            }
-        }
-
-        // Queue of nodes with no incoming edges
-        // Sort for deterministic ordering (HashMap iteration order is random)
-        let mut queue: VecDeque<PathBuf> = VecDeque::new();
-        let mut zero_degree: Vec<_> = in_degree
-            .iter()
-            .filter(|(_, &degree)| degree == 0)
-            .map(|(node, _)| node.clone())
-            .collect();
-        zero_degree.sort();
-        queue.extend(zero_degree);
-
-        // Process nodes in topological order
         let mut sorted = Vec::new();
-        while let Some(node) = queue.pop_front() {
-            sorted.push(node.clone());
-
-            // Collect all nodes whose in-degree reaches 0 after processing this node
-            let mut newly_zero: Vec<PathBuf> = Vec::new();
-
-            // For each dependent of this node (in reachable set)
-            for from in &reachable {
-                if let Some(deps) = self.dependencies.get(from) {
-                    if deps.contains(&node) {
-                        // Decrease in-degree
-                        let degree = in_degree
-                            .get_mut(from)
-                            .expect("in_degree should contain all reachable nodes");
-                        *degree -= 1;
-
-                        // If no more dependencies, collect for later sorting
-                        if *degree == 0 {
-                            newly_zero.push(from.clone());
-                        }
-                    }
-                }
-            }
+        let mut visited = HashSet::new();
+        let mut in_progress = HashSet::new();
+        self.visit_in_import_order(entry, &mut in_progress, &mut visited, &mut sorted)?;
+        Ok(sorted)
+    }
 
-            // Sort and add to queue for deterministic ordering
-            newly_zero.sort();
-            queue.extend(newly_zero);
+    /// Append `node`'s dependencies, then `node`, to `sorted`
+    fn visit_in_import_order(
+        &self,
+        node: &Path,
+        in_progress: &mut HashSet<PathBuf>,
+        visited: &mut HashSet<PathBuf>,
+        sorted: &mut Vec<PathBuf>,
+    ) -> Result<(), Vec<Diagnostic>> {
+        if visited.contains(node) {
+            return Ok(());
         }
-
-        // Check if all reachable nodes were processed (no cycles)
-        if sorted.len() != reachable.len() {
+        // `load_recursive` rejects import cycles first; this guards the invariant
+        if !in_progress.insert(node.to_path_buf()) {
             return Err(vec![CIRCULAR_DEPENDENCY
                 .emit(Span::dummy())
                 .arg("cycle", "detected during topological sort")
                 .build()]);
         }
-
-        Ok(sorted)
-    }
-
-    /// Find all modules reachable from a given entry point using DFS
-    fn find_reachable(&self, entry: &Path) -> HashSet<PathBuf> {
-        let mut reachable = HashSet::new();
-        let mut stack = vec![entry.to_path_buf()];
-
-        while let Some(node) = stack.pop() {
-            if reachable.insert(node.clone()) {
-                // If this is a new node, explore its dependencies
-                if let Some(deps) = self.dependencies.get(&node) {
-                    for dep in deps {
-                        stack.push(dep.clone());
-                    }
-                }
-            }
+        for dep in self.dependencies.get(node).into_iter().flatten() {
+            self.visit_in_import_order(dep, in_progress, visited, sorted)?;
         }
-
-        reachable
+        in_progress.remove(node);
+        visited.insert(node.to_path_buf());
+        sorted.push(node.to_path_buf());
+        Ok(())
     }
 
     /// Get a loaded module from cache
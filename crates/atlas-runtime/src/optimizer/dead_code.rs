@@ -72,9 +72,23 @@ impl OptimizationPass for DeadCodeEliminationPass {
             .collect();
 
         let mut constants = bytecode.constants;
-        fix_all_references(&mut live, &mut constants);
-
-        let result = encode_instructions(&live, constants, top_level_local_count);
+        let mut statement_offsets = bytecode.statement_offsets;
+        let mut local_names = bytecode.local_names;
+        fix_all_references(
+            &mut live,
+            &mut constants,
+            &mut statement_offsets,
+            &mut local_names,
+        );
+
+        let result = encode_instructions(
+            &live,
+            constants,
+            bytecode.global_names,
+            statement_offsets,
+            local_names,
+            top_level_local_count,
+        );
         stats.bytecode_size_after = result.instructions.len();
         (result, stats)
     }
@@ -139,8 +153,10 @@ fn compute_reachable(
                 // NO fallthrough
             }
 
-            // Conditional jump: both fallthrough and jump target are successors
-            Opcode::JumpIfFalse => {
+            // Conditional jump: both fallthrough and jump target are successors.
+            // TryBegin's catch block is entered by unwinding, so it is
+            // reachable exactly like a branch target.
+            Opcode::JumpIfFalse | Opcode::TryBegin => {
                 if instr.operands.len() == 2 {
                     let relative = instr.read_i16();
                     let target = (offset as isize + 3 + relative as isize) as usize;
@@ -23,7 +23,7 @@ pub use constant_folding::ConstantFoldingPass;
 pub use dead_code::DeadCodeEliminationPass;
 pub use peephole::PeepholePass;
 
-use crate::bytecode::{Bytecode, DebugSpan, Opcode};
+use crate::bytecode::{Bytecode, DebugSpan, LocalDebugInfo, Opcode};
 use crate::span::Span;
 use crate::value::Value;
 
@@ -316,7 +316,8 @@ pub(crate) fn operand_size(opcode: Opcode) -> usize {
         | Opcode::TupleGet
         | Opcode::Jump
         | Opcode::JumpIfFalse
-        | Opcode::Loop => 2,
+        | Opcode::Loop
+        | Opcode::TryBegin => 2,
 
         // 1-byte operands (u8)
         Opcode::Call | Opcode::EnumVariant | Opcode::Range => 1,
@@ -336,8 +337,13 @@ pub(crate) fn operand_size(opcode: Opcode) -> usize {
 }
 
 /// Returns true if this opcode is a jump instruction (has an i16 relative offset)
+///
+/// `TryBegin` counts: its operand is the relative offset of the catch block.
 pub(crate) fn is_jump_opcode(opcode: Opcode) -> bool {
-    matches!(opcode, Opcode::Jump | Opcode::JumpIfFalse | Opcode::Loop)
+    matches!(
+        opcode,
+        Opcode::Jump | Opcode::JumpIfFalse | Opcode::Loop | Opcode::TryBegin
+    )
 }
 
 /// Returns true if this opcode terminates a basic block unconditionally.
@@ -418,6 +424,9 @@ pub(crate) fn decode_instructions(bytecode: &Bytecode) -> Vec<DecodedInstruction
 pub(crate) fn encode_instructions(
     decoded: &[DecodedInstruction],
     constants: Vec<Value>,
+    global_names: Vec<String>,
+    statement_offsets: Vec<usize>,
+    local_names: Vec<LocalDebugInfo>,
     top_level_local_count: usize,
 ) -> Bytecode {
     let mut instructions = Vec::new();
@@ -437,7 +446,10 @@ pub(crate) fn encode_instructions(
     Bytecode {
         instructions,
         constants,
+        global_names,
         debug_info,
+        statement_offsets,
+        local_names,
         top_level_local_count,
     }
 }
@@ -449,7 +461,15 @@ pub(crate) fn encode_instructions(
 /// 1. Assigns new byte offsets to each instruction in `decoded`
 /// 2. For each jump instruction, recalculates the relative i16 offset
 /// 3. Updates `Function` values in `constants` if their bytecode_offset changed
-pub(crate) fn fix_all_references(decoded: &mut [DecodedInstruction], constants: &mut [Value]) {
+/// 4. Moves each statement start in `statement_offsets` with its instruction,
+///    or to the next surviving instruction if it was removed
+/// 5. Moves the live range of each entry in `local_names` the same way
+pub(crate) fn fix_all_references(
+    decoded: &mut [DecodedInstruction],
+    constants: &mut [Value],
+    statement_offsets: &mut Vec<usize>,
+    local_names: &mut [LocalDebugInfo],
+) {
     // Build old_offset → new_offset mapping
     let mut new_offsets = Vec::with_capacity(decoded.len());
     let mut current = 0usize;
@@ -489,6 +509,32 @@ pub(crate) fn fix_all_references(decoded: &mut [DecodedInstruction], constants:
         }
     }
 
+    // Fix statement starts; `decoded` keeps the original instruction order
+    let mut surviving: Vec<(usize, usize)> = decoded
+        .iter()
+        .zip(new_offsets.iter())
+        .map(|(instr, &new_off)| (instr.offset, new_off))
+        .collect();
+    surviving.sort_unstable();
+    let remap = |old: usize| {
+        let next = surviving.partition_point(|&(offset, _)| offset < old);
+        surviving.get(next).map(|&(_, new_off)| new_off)
+    };
+    let mut remapped: Vec<usize> = statement_offsets
+        .iter()
+        .filter_map(|&old| remap(old))
+        .collect();
+    remapped.sort_unstable();
+    remapped.dedup();
+    *statement_offsets = remapped;
+
+    // Fix local live ranges; a range running past the last surviving
+    // instruction now ends with the code
+    for local in local_names.iter_mut() {
+        local.start_offset = remap(local.start_offset).unwrap_or(current);
+        local.end_offset = remap(local.end_offset).unwrap_or(current);
+    }
+
     // Update offsets in decoded list
     for (instr, &new_off) in decoded.iter_mut().zip(new_offsets.iter()) {
         instr.offset = new_off;
@@ -162,9 +162,23 @@ impl OptimizationPass for PeepholePass {
             decoded = new_decoded;
         }
 
-        fix_all_references(&mut decoded, &mut constants);
-
-        let result = encode_instructions(&decoded, constants, top_level_local_count);
+        let mut statement_offsets = bytecode.statement_offsets;
+        let mut local_names = bytecode.local_names;
+        fix_all_references(
+            &mut decoded,
+            &mut constants,
+            &mut statement_offsets,
+            &mut local_names,
+        );
+
+        let result = encode_instructions(
+            &decoded,
+            constants,
+            bytecode.global_names,
+            statement_offsets,
+            local_names,
+            top_level_local_count,
+        );
         stats.bytecode_size_after = result.instructions.len();
         (result, stats)
     }
@@ -1,7 +1,9 @@
 //! Expression parsing (Pratt parsing)
 
 use crate::ast::*;
-use crate::diagnostic::error_codes::{GENERIC_WARNING, INVALID_NUMBER, SYNTAX_ERROR};
+use crate::diagnostic::error_codes::{
+    GENERIC_WARNING, INVALID_NUMBER, INVALID_REGEX_LITERAL, SYNTAX_ERROR,
+};
 use crate::diagnostic::Diagnostic;
 use crate::parser::{Parser, Precedence};
 use crate::span::Span;
@@ -30,6 +32,7 @@ impl Parser {
             TokenKind::Number => self.parse_number(),
             TokenKind::String => self.parse_string(),
             TokenKind::TemplateString => self.parse_template_string(),
+            TokenKind::Regex => self.parse_regex(),
             TokenKind::True | TokenKind::False => self.parse_bool(),
             TokenKind::Null => self.parse_null(),
             TokenKind::Identifier => self.parse_identifier(),
@@ -73,10 +76,13 @@ impl Parser {
             | TokenKind::Greater
             | TokenKind::GreaterEqual
             | TokenKind::AmpAmp
-            | TokenKind::PipePipe => self.parse_binary(left),
+            | TokenKind::PipePipe
+            | TokenKind::QuestionQuestion
+            | TokenKind::In => self.parse_binary(left),
+            TokenKind::PipeGreater => self.parse_pipeline(left),
             TokenKind::LeftParen => self.parse_call(left),
             TokenKind::LeftBracket => self.parse_index(left),
-            TokenKind::Dot => self.parse_member(left),
+            TokenKind::Dot | TokenKind::QuestionDot => self.parse_member(left),
             TokenKind::Question => self.parse_try(left),
             TokenKind::Range | TokenKind::RangeInclusive => self.parse_range_infix(left),
             _ => Ok(left),
@@ -92,19 +98,23 @@ impl Parser {
     /// Get precedence for a token kind
     pub(super) fn token_precedence_kind(&self, kind: TokenKind) -> Precedence {
         match kind {
+            TokenKind::QuestionQuestion => Precedence::Coalesce,
             TokenKind::PipePipe => Precedence::Or,
             TokenKind::AmpAmp => Precedence::And,
             TokenKind::EqualEqual | TokenKind::BangEqual => Precedence::Equality,
             TokenKind::Less
             | TokenKind::LessEqual
             | TokenKind::Greater
-            | TokenKind::GreaterEqual => Precedence::Comparison,
+            | TokenKind::GreaterEqual
+            | TokenKind::In => Precedence::Comparison,
+            TokenKind::PipeGreater => Precedence::Pipeline,
             TokenKind::Range | TokenKind::RangeInclusive => Precedence::Range,
             TokenKind::Plus | TokenKind::Minus => Precedence::Term,
             TokenKind::Star | TokenKind::Slash | TokenKind::Percent => Precedence::Factor,
             TokenKind::LeftParen
             | TokenKind::LeftBracket
             | TokenKind::Dot
+            | TokenKind::QuestionDot
             | TokenKind::Question => Precedence::Call,
             _ => Precedence::Lowest,
         }
@@ -127,6 +137,24 @@ impl Parser {
         Ok(Expr::Literal(Literal::Number(value), span))
     }
 
+    /// Parse a `/pattern/flags` regex literal, compiling it to check it
+    fn parse_regex(&mut self) -> Result<Expr, ()> {
+        let token = self.advance();
+        let span = token.span;
+        let lexeme = token.lexeme.clone();
+        let close = lexeme.rfind('/').unwrap_or(0);
+        let pattern = lexeme[1..close].to_string();
+        let flags = lexeme[close + 1..].to_string();
+        #[cfg(feature = "regex")]
+        let checked = crate::stdlib::regex::compile_literal(&pattern, &flags).map(drop);
+        #[cfg(not(feature = "regex"))]
+        let checked: Result<(), String> = Err(crate::stdlib::REGEX_DISABLED.to_string());
+        if let Err(detail) = checked {
+            self.emit_descriptor(INVALID_REGEX_LITERAL.emit(span).arg("detail", detail));
+        }
+        Ok(Expr::Literal(Literal::Regex { pattern, flags }, span))
+    }
+
     /// Parse string literal
     fn parse_string(&mut self) -> Result<Expr, ()> {
         let token = self.advance();
@@ -588,18 +616,22 @@ impl Parser {
             TokenKind::GreaterEqual => BinaryOp::Ge,
             TokenKind::AmpAmp => BinaryOp::And,
             TokenKind::PipePipe => BinaryOp::Or,
+            TokenKind::QuestionQuestion => BinaryOp::Coalesce,
+            TokenKind::In => BinaryOp::In,
             _ => unreachable!(),
         };
 
         // Get precedence from the operator kind
         let precedence = match op_kind {
+            TokenKind::QuestionQuestion => Precedence::Coalesce,
             TokenKind::PipePipe => Precedence::Or,
             TokenKind::AmpAmp => Precedence::And,
             TokenKind::EqualEqual | TokenKind::BangEqual => Precedence::Equality,
             TokenKind::Less
             | TokenKind::LessEqual
             | TokenKind::Greater
-            | TokenKind::GreaterEqual => Precedence::Comparison,
+            | TokenKind::GreaterEqual
+            | TokenKind::In => Precedence::Comparison,
             TokenKind::Plus | TokenKind::Minus => Precedence::Term,
             TokenKind::Star | TokenKind::Slash | TokenKind::Percent => Precedence::Factor,
             _ => Precedence::Lowest,
@@ -616,6 +648,17 @@ impl Parser {
         }))
     }
 
+    /// Parse a pipeline stage: `value |> f(args)` desugars to `f(value, args)`
+    /// and `value |> f` to `f(value)`.
+    ///
+    /// The stage is a primary expression with its postfix operators, so
+    /// `xs |> len() > 2` compares the result. Stages chain left to right.
+    fn parse_pipeline(&mut self, value: Expr) -> Result<Expr, ()> {
+        self.advance(); // consume '|>'
+        let stage = self.parse_precedence(Precedence::Unary)?;
+        Ok(pipe_into(value, stage))
+    }
+
     /// Parse call expression
     fn parse_call(&mut self, callee: Expr) -> Result<Expr, ()> {
         let callee_span = callee.span();
@@ -764,19 +807,46 @@ impl Parser {
         )
     }
 
-    /// Parse member expression (method call or property access)
-    fn parse_member(&mut self, target: Expr) -> Result<Expr, ()> {
+    /// Parse member expression (method call or property access), `.` or `?.`
+    fn parse_member(&mut self, mut target: Expr) -> Result<Expr, ()> {
         let target_span = target.span();
-        self.consume(TokenKind::Dot, "Expected '.'")?;
+        let mut optional = self.match_token(TokenKind::QuestionDot);
+        if !optional {
+            self.consume(TokenKind::Dot, "Expected '.'")?;
+        }
 
         // Member name: identifier OR integer literal (for tuple element access: .0, .1, ...)
         let member = if self.check(TokenKind::Number) {
             let tok = self.advance();
             // Only accept non-negative integer literals as tuple indices
-            let lexeme = tok.lexeme.clone();
+            let mut lexeme = tok.lexeme.clone();
+            let mut span = tok.span;
+            // `t.1.0` lexes the indices as the number `1.0`: split it into `.1` then `.0`
+            if let Some((outer, inner)) = lexeme.split_once('.') {
+                if outer.parse::<u64>().is_ok() && inner.parse::<u64>().is_ok() {
+                    let outer_end = span.start + outer.len();
+                    target = Expr::Member(MemberExpr {
+                        target: Box::new(target),
+                        member: Identifier {
+                            name: outer.to_string(),
+                            span: Span::new_in(span.start, outer_end, span.file),
+                        },
+                        args: None,
+                        type_args: vec![],
+                        optional,
+                        null_safe: std::cell::Cell::new(None),
+                        type_tag: std::cell::Cell::new(None),
+                        trait_dispatch: std::cell::RefCell::new(None),
+                        static_dispatch: std::cell::RefCell::new(None),
+                        span: target_span.merge(Span::new_in(span.start, outer_end, span.file)),
+                    });
+                    span = Span::new_in(outer_end + 1, span.end, span.file);
+                    lexeme = inner.to_string();
+                    optional = false;
+                }
+            }
             let is_integer = lexeme.parse::<u64>().is_ok();
             if !is_integer {
-                let span = tok.span;
                 self.emit_descriptor(
                     SYNTAX_ERROR
                         .emit(span)
@@ -793,10 +863,7 @@ impl Parser {
                 );
                 return Err(());
             }
-            Identifier {
-                name: lexeme,
-                span: tok.span,
-            }
+            Identifier { name: lexeme, span }
         } else {
             let member_token = self.consume_member_name()?;
             Identifier {
@@ -840,6 +907,8 @@ impl Parser {
             member,
             args,
             type_args,
+            optional,
+            null_safe: std::cell::Cell::new(None),
             type_tag: std::cell::Cell::new(None),
             trait_dispatch: std::cell::RefCell::new(None),
             static_dispatch: std::cell::RefCell::new(None),
@@ -1604,24 +1673,45 @@ impl Parser {
         Ok(Pattern::Or(alternatives, start_span.merge(end_span)))
     }
 
+    /// Parse an optionally negated number literal inside a pattern
+    fn parse_pattern_number(&mut self) -> Result<(f64, Span), ()> {
+        let start_span = self.peek().span;
+        let negative = self.match_token(TokenKind::Minus);
+        let token = self.consume(TokenKind::Number, "Expected a number in pattern")?;
+        let span = start_span.merge(token.span);
+        let lexeme = token.lexeme.clone();
+        let value: f64 = match lexeme.parse::<f64>() {
+            Ok(value) if value.is_finite() => value,
+            _ => {
+                self.emit_descriptor(INVALID_NUMBER.emit(span).arg("literal", &lexeme));
+                0.0
+            }
+        };
+        Ok((if negative { -value } else { value }, span))
+    }
+
     /// Parse pattern
     fn parse_pattern(&mut self) -> Result<crate::ast::Pattern, ()> {
         use crate::ast::Pattern;
 
         match self.peek().kind {
             // Literal patterns: numbers, strings, bools, null
-            TokenKind::Number => {
-                let token = self.advance();
-                let span = token.span;
-                let lexeme = token.lexeme.clone();
-                let value: f64 = match lexeme.parse::<f64>() {
-                    Ok(value) if value.is_finite() => value,
-                    _ => {
-                        self.emit_descriptor(INVALID_NUMBER.emit(span).arg("literal", &lexeme));
-                        0.0
-                    }
+            // A number may be followed by `..`/`..=` to form a range pattern
+            TokenKind::Number | TokenKind::Minus => {
+                let (start, start_span) = self.parse_pattern_number()?;
+                let inclusive = match self.peek().kind {
+                    TokenKind::Range => false,
+                    TokenKind::RangeInclusive => true,
+                    _ => return Ok(Pattern::Literal(Literal::Number(start), start_span)),
                 };
-                Ok(Pattern::Literal(Literal::Number(value), span))
+                self.advance(); // consume `..` / `..=`
+                let (end, end_span) = self.parse_pattern_number()?;
+                Ok(Pattern::Range {
+                    start,
+                    end,
+                    inclusive,
+                    span: start_span.merge(end_span),
+                })
             }
             TokenKind::String => {
                 let token = self.advance();
@@ -1673,6 +1763,9 @@ impl Parser {
             // Array pattern: [...]
             TokenKind::LeftBracket => self.parse_array_pattern(),
 
+            // Anonymous struct/map pattern: { field, field: sub_pattern, ... }
+            TokenKind::LeftBrace => self.parse_struct_pattern(None),
+
             // Constructor pattern or variable binding: Identifier or Identifier(...)
             // Also handles enum variant patterns: EnumName::VariantName
             TokenKind::Identifier => {
@@ -1796,19 +1889,35 @@ impl Parser {
         }
     }
 
-    /// Parse array pattern: [pattern, pattern, ...]
+    /// Parse array pattern: [pattern, pattern, ...] with an optional trailing `...rest`
     fn parse_array_pattern(&mut self) -> Result<crate::ast::Pattern, ()> {
         use crate::ast::Pattern;
 
         let start_span = self.consume(TokenKind::LeftBracket, "Expected '['")?.span;
         let mut elements = Vec::new();
-
-        if !self.check(TokenKind::RightBracket) {
-            loop {
-                elements.push(self.parse_pattern()?);
-                if !self.match_token(TokenKind::Comma) {
-                    break;
+        let mut rest = None;
+
+        while !self.check(TokenKind::RightBracket) {
+            if self.check(TokenKind::DotDotDot) {
+                let dots_span = self.advance().span;
+                let binding = self.parse_pattern()?;
+                if !matches!(binding, Pattern::Variable(_) | Pattern::Wildcard(_)) {
+                    self.emit_descriptor(
+                        SYNTAX_ERROR
+                            .emit(dots_span.merge(binding.span()))
+                            .arg("detail", "a rest pattern must be a name or `_`")
+                            .with_help("bind the remaining elements with `...rest`"),
+                    );
+                    return Err(());
                 }
+                rest = Some(Box::new(binding));
+                // Nothing may follow the rest pattern
+                self.match_token(TokenKind::Comma);
+                break;
+            }
+            elements.push(self.parse_pattern()?);
+            if !self.match_token(TokenKind::Comma) {
+                break;
             }
         }
 
@@ -1816,6 +1925,7 @@ impl Parser {
 
         Ok(Pattern::Array {
             elements,
+            rest,
             span: start_span.merge(end_span),
         })
     }
@@ -1823,6 +1933,7 @@ impl Parser {
     /// Parse struct pattern: `TypeName { field, field: sub_pattern }` or anonymous `{ field }`.
     ///
     /// `type_name` is `Some(id)` for named structs, `None` for anonymous record patterns.
+    /// Keys may also be quoted (`{ "type": "user" }`), which requires a sub-pattern.
     fn parse_struct_pattern(
         &mut self,
         type_name: Option<Identifier>,
@@ -1840,15 +1951,26 @@ impl Parser {
 
         if !self.check(TokenKind::RightBrace) {
             loop {
-                let field_tok = self.consume_identifier("a struct field name in pattern")?;
+                // A quoted key (`"content-type": v`) can name any key, keywords included
+                let quoted = self.check(TokenKind::String);
+                let field_tok = if quoted {
+                    self.advance().clone()
+                } else {
+                    self.consume_identifier("a struct field name in pattern")?
+                        .clone()
+                };
                 let field_name = Identifier {
                     name: field_tok.lexeme.clone(),
                     span: field_tok.span,
                 };
                 let field_span = field_tok.span;
 
-                // `field: sub_pattern` or shorthand `field`
-                let sub_pattern = if self.check(TokenKind::Colon) {
+                // `field: sub_pattern` or shorthand `field`; a quoted key is
+                // no variable name, so it always takes a sub-pattern
+                let sub_pattern = if quoted {
+                    self.consume(TokenKind::Colon, "Expected ':' after quoted key in pattern")?;
+                    Some(self.parse_or_pattern()?)
+                } else if self.check(TokenKind::Colon) {
                     self.advance(); // consume ':'
                     Some(self.parse_or_pattern()?)
                 } else {
@@ -1909,3 +2031,34 @@ impl Parser {
         })
     }
 }
+
+/// Pass `value` as the first argument of a pipeline stage
+fn pipe_into(value: Expr, stage: Expr) -> Expr {
+    let span = value.span().merge(stage.span());
+    match stage {
+        Expr::Call(mut call) => {
+            call.args.insert(0, value);
+            call.span = span;
+            Expr::Call(call)
+        }
+        Expr::Member(mut member) if member.args.is_some() => {
+            if let Some(args) = member.args.as_mut() {
+                args.insert(0, value);
+            }
+            member.span = span;
+            Expr::Member(member)
+        }
+        // `xs |> parse()?` applies `?` to the stage's result
+        Expr::Try(mut try_expr) => {
+            *try_expr.expr = pipe_into(value, *try_expr.expr);
+            try_expr.span = span;
+            Expr::Try(try_expr)
+        }
+        stage => Expr::Call(CallExpr {
+            callee: Box::new(stage),
+            args: vec![value],
+            type_args: vec![],
+            span,
+        }),
+    }
+}
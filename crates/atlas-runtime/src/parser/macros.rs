@@ -0,0 +1,670 @@
+//! Macro expansion (tokens to tokens)
+//!
+//! Macros are hygienic token templates, expanded before parsing so every
+//! later phase only ever sees ordinary syntax:
+//!
+//! ```atlas
+//! macro to_json(T: ident, fields: ident...) {
+//!     fn $concat(to_json_, $T)(value: $T): string {
+//!         let parts: string[] = [$( $stringify($fields) + ": " + str(value.$fields) ),*];
+//!         return "{" + parts.join(", ") + "}";
+//!     }
+//! }
+//!
+//! to_json!(User, name, age);
+//! ```
+//!
+//! Parameters have a fragment kind — `ident` (one identifier), `expr`
+//! (parenthesized when substituted), `type`, or `tokens` (the default) —
+//! and the last one may be variadic (`...`). Inside the body:
+//!
+//! - `$name` substitutes an argument; a variadic one expands to its items
+//!   separated by commas.
+//! - `$( ... )sep*` repeats the group once per item of the variadic parameter
+//!   it mentions, with an optional single-token separator.
+//! - `$concat(a, $b, ...)` pastes pieces into one identifier and
+//!   `$stringify(...)` turns tokens into a string literal.
+//!
+//! Restrictions: macros are declared at the top level of the file that uses
+//! them, take effect for the whole file, and cannot be exported. Variables a
+//! body declares with `let` or `for` are renamed per expansion, so they never
+//! capture or shadow names at the call site. Invocations in expression
+//! position are wrapped in parentheses.
+
+use crate::diagnostic::error_codes::MACRO_EXPANSION_ERROR;
+use crate::diagnostic::Diagnostic;
+use crate::span::Span;
+use crate::token::{Token, TokenKind};
+use std::collections::HashMap;
+
+/// Deepest chain of macros expanding into further invocations
+const MAX_EXPANSION_DEPTH: usize = 64;
+
+/// Body directives; not usable as parameter names
+const DIRECTIVES: [&str; 2] = ["concat", "stringify"];
+
+/// What an argument must look like
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FragmentKind {
+    Ident,
+    Expr,
+    Type,
+    Tokens,
+}
+
+#[derive(Debug, Clone)]
+struct MacroParam {
+    name: String,
+    kind: FragmentKind,
+    variadic: bool,
+}
+
+#[derive(Debug, Clone)]
+struct MacroDef {
+    name: String,
+    params: Vec<MacroParam>,
+    body: Vec<Token>,
+}
+
+enum Binding {
+    One(Vec<Token>),
+    Many(Vec<Vec<Token>>),
+}
+
+type ExpandResult<T> = Result<T, (String, Span)>;
+
+/// Whether `tokens` declare or invoke a macro
+pub(super) fn uses_macros(tokens: &[Token]) -> bool {
+    tokens
+        .iter()
+        .any(|t| matches!(t.kind, TokenKind::Macro | TokenKind::Dollar))
+        || (0..tokens.len()).any(|i| is_invocation(tokens, i))
+}
+
+/// Strip `macro` declarations from `tokens` and replace every invocation
+/// with its expansion
+pub(super) fn expand(tokens: Vec<Token>) -> (Vec<Token>, Vec<Diagnostic>) {
+    if !uses_macros(&tokens) {
+        return (tokens, Vec::new());
+    }
+
+    let mut expander = Expander {
+        macros: HashMap::new(),
+        diagnostics: Vec::new(),
+        expansions: 0,
+    };
+    let tokens = expander.collect_definitions(&tokens);
+    let tokens = expander.expand_stream(&tokens, 0);
+    (tokens, expander.diagnostics)
+}
+
+struct Expander {
+    macros: HashMap<String, MacroDef>,
+    diagnostics: Vec<Diagnostic>,
+    /// Expansions so far; numbers the hygienic renames
+    expansions: usize,
+}
+
+impl Expander {
+    fn error(&mut self, detail: impl Into<String>, span: Span) {
+        self.diagnostics.push(
+            MACRO_EXPANSION_ERROR
+                .emit(span)
+                .arg("detail", detail)
+                .build()
+                .with_label("macro expansion"),
+        );
+    }
+
+    /// Register and remove every `macro` declaration
+    fn collect_definitions(&mut self, tokens: &[Token]) -> Vec<Token> {
+        let mut out = Vec::with_capacity(tokens.len());
+        let mut depth = 0usize;
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = &tokens[i];
+            if token.kind == TokenKind::Macro {
+                let next = definition_end(tokens, i);
+                match parse_definition(&tokens[i..next]) {
+                    Ok(def) if depth > 0 => self.error(
+                        format!("macro `{}` must be declared at the top level", def.name),
+                        token.span,
+                    ),
+                    Ok(def) if self.macros.contains_key(&def.name) => self.error(
+                        format!("macro `{}` is already declared", def.name),
+                        token.span,
+                    ),
+                    Ok(def) => {
+                        self.macros.insert(def.name.clone(), def);
+                    }
+                    Err((detail, span)) => self.error(detail, span),
+                }
+                i = next;
+                continue;
+            }
+
+            if is_open(token.kind) {
+                depth += 1;
+            } else if is_close(token.kind) {
+                depth = depth.saturating_sub(1);
+            }
+            out.push(token.clone());
+            i += 1;
+        }
+        out
+    }
+
+    /// Replace the invocations in `tokens`, expanding their output in turn
+    fn expand_stream(&mut self, tokens: &[Token], depth: usize) -> Vec<Token> {
+        let mut out: Vec<Token> = Vec::with_capacity(tokens.len());
+        let mut i = 0;
+        while i < tokens.len() {
+            if !is_invocation(tokens, i) {
+                out.push(tokens[i].clone());
+                i += 1;
+                continue;
+            }
+
+            let name = tokens[i].lexeme.clone();
+            let Some(close) = matching_close(tokens, i + 2) else {
+                self.error(
+                    format!("unclosed invocation of `{}!`", name),
+                    tokens[i].span,
+                );
+                out.extend(tokens[i..].iter().cloned());
+                break;
+            };
+            let call_span = tokens[i].span.merge(tokens[close].span);
+            let statement = out.last().is_none_or(|prev| {
+                matches!(
+                    prev.kind,
+                    TokenKind::Semicolon | TokenKind::LeftBrace | TokenKind::RightBrace
+                )
+            });
+            let mut next = close + 1;
+
+            let expansion = match self.macros.get(&name).cloned() {
+                None => Err((format!("unknown macro `{}`", name), tokens[i].span)),
+                Some(_) if depth >= MAX_EXPANSION_DEPTH => Err((
+                    format!(
+                        "expansion of `{}!` nests more than {} levels deep (is it recursive?)",
+                        name, MAX_EXPANSION_DEPTH
+                    ),
+                    call_span,
+                )),
+                Some(def) => self.instantiate(&def, &tokens[i + 3..close], call_span),
+            };
+
+            match expansion {
+                Ok(expansion) => {
+                    let expansion = self.expand_stream(&expansion, depth + 1);
+                    if statement {
+                        // Declarations and blocks need no `;` after the call
+                        let complete = expansion.last().is_some_and(|t| {
+                            matches!(t.kind, TokenKind::RightBrace | TokenKind::Semicolon)
+                        });
+                        if complete
+                            && tokens
+                                .get(next)
+                                .is_some_and(|t| t.kind == TokenKind::Semicolon)
+                        {
+                            next += 1;
+                        }
+                        out.extend(expansion);
+                    } else if !expansion.is_empty() {
+                        out.push(Token::new(TokenKind::LeftParen, "(", call_span));
+                        out.extend(expansion);
+                        out.push(Token::new(TokenKind::RightParen, ")", call_span));
+                    }
+                }
+                Err((detail, span)) => {
+                    self.error(detail, span);
+                    if !statement {
+                        // Keep the surrounding expression parseable
+                        out.push(Token::new(TokenKind::Null, "null", call_span));
+                    }
+                }
+            }
+            i = next;
+        }
+        out
+    }
+
+    /// Bind `args` to the parameters of `def` and transcribe its body
+    fn instantiate(
+        &mut self,
+        def: &MacroDef,
+        args: &[Token],
+        call_span: Span,
+    ) -> ExpandResult<Vec<Token>> {
+        let args = split_args(args, &def.params);
+        let fixed = def.params.iter().filter(|p| !p.variadic).count();
+        let variadic = def.params.last().is_some_and(|p| p.variadic);
+        if args.len() < fixed || (!variadic && args.len() > fixed) {
+            return Err((
+                format!(
+                    "`{}!` takes {}{} argument(s) but {} were given",
+                    def.name,
+                    if variadic { "at least " } else { "" },
+                    fixed,
+                    args.len()
+                ),
+                call_span,
+            ));
+        }
+
+        let mut bindings = HashMap::new();
+        for (param, arg) in def.params.iter().zip(&args) {
+            if param.variadic {
+                break;
+            }
+            bindings.insert(
+                param.name.clone(),
+                Binding::One(bind_fragment(param, arg, call_span)?),
+            );
+        }
+        if let Some(param) = def.params.last().filter(|p| p.variadic) {
+            let items = args[fixed..]
+                .iter()
+                .map(|arg| bind_fragment(param, arg, call_span))
+                .collect::<ExpandResult<Vec<_>>>()?;
+            bindings.insert(param.name.clone(), Binding::Many(items));
+        }
+
+        // Hygiene: locals the body declares get a name unique to this expansion
+        self.expansions += 1;
+        let mut renames = HashMap::new();
+        for (i, token) in def.body.iter().enumerate() {
+            if !matches!(token.kind, TokenKind::Let | TokenKind::For) {
+                continue;
+            }
+            let mut j = i + 1;
+            if def.body.get(j).is_some_and(|t| t.kind == TokenKind::Mut) {
+                j += 1;
+            }
+            if let Some(name) = def.body.get(j).filter(|t| t.kind == TokenKind::Identifier) {
+                renames.insert(
+                    name.lexeme.clone(),
+                    format!("{}__{}{}", name.lexeme, def.name, self.expansions),
+                );
+            }
+        }
+
+        let mut out = Vec::new();
+        let transcriber = Transcriber {
+            bindings: &bindings,
+            renames: &renames,
+            call_span,
+        };
+        transcriber.transcribe(&def.body, None, &mut out)?;
+        Ok(out)
+    }
+}
+
+/// Substitution state for one expansion
+struct Transcriber<'a> {
+    bindings: &'a HashMap<String, Binding>,
+    renames: &'a HashMap<String, String>,
+    call_span: Span,
+}
+
+impl Transcriber<'_> {
+    /// Append `body` to `out`; `item` is the variadic parameter being
+    /// repeated over and its current argument
+    fn transcribe(
+        &self,
+        body: &[Token],
+        item: Option<(&str, &[Token])>,
+        out: &mut Vec<Token>,
+    ) -> ExpandResult<()> {
+        let mut i = 0;
+        while i < body.len() {
+            let token = &body[i];
+            if token.kind != TokenKind::Dollar {
+                let mut token = token.clone();
+                token.span = self.call_span;
+                let after_dot = i > 0 && body[i - 1].kind == TokenKind::Dot;
+                if token.kind == TokenKind::Identifier && !after_dot {
+                    if let Some(renamed) = self.renames.get(&token.lexeme) {
+                        token.lexeme = renamed.clone();
+                    }
+                }
+                out.push(token);
+                i += 1;
+                continue;
+            }
+
+            let next = body.get(i + 1);
+            let directive = next
+                .filter(|t| t.kind == TokenKind::Identifier)
+                .map(|t| t.lexeme.as_str())
+                .filter(|name| DIRECTIVES.contains(name))
+                .filter(|_| body.get(i + 2).map(|t| t.kind) == Some(TokenKind::LeftParen));
+
+            if let Some(directive) = directive {
+                let close = matching_close(body, i + 2)
+                    .ok_or_else(|| (format!("unclosed `${}(`", directive), token.span))?;
+                let mut inner = Vec::new();
+                self.transcribe(&body[i + 3..close], item, &mut inner)?;
+                out.push(if directive == "concat" {
+                    self.concat(&inner, token.span)?
+                } else {
+                    let text: Vec<&str> = inner.iter().map(|t| t.lexeme.as_str()).collect();
+                    Token::new(TokenKind::String, text.join(" "), self.call_span)
+                });
+                i = close + 1;
+            } else if next.is_some_and(|t| t.kind == TokenKind::LeftParen) {
+                i = self.repeat(body, i, out)?;
+            } else if let Some(name) = next.filter(|t| t.kind == TokenKind::Identifier) {
+                match (item, self.bindings.get(&name.lexeme)) {
+                    (Some((var, arg)), _) if var == name.lexeme => out.extend_from_slice(arg),
+                    (_, Some(Binding::One(arg))) => out.extend_from_slice(arg),
+                    (_, Some(Binding::Many(args))) => {
+                        for (n, arg) in args.iter().enumerate() {
+                            if n > 0 {
+                                out.push(Token::new(TokenKind::Comma, ",", self.call_span));
+                            }
+                            out.extend_from_slice(arg);
+                        }
+                    }
+                    (_, None) => {
+                        return Err((
+                            format!("`${}` is not a parameter of this macro", name.lexeme),
+                            name.span,
+                        ))
+                    }
+                }
+                i += 2;
+            } else {
+                return Err((
+                    "expected a parameter, `$(`, `$concat(` or `$stringify(` after `$`".to_string(),
+                    token.span,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Transcribe the `$( ... )sep*` group starting at `body[start]`,
+    /// returning the index after it
+    fn repeat(&self, body: &[Token], start: usize, out: &mut Vec<Token>) -> ExpandResult<usize> {
+        let span = body[start].span;
+        let close =
+            matching_close(body, start + 1).ok_or_else(|| ("unclosed `$(`".to_string(), span))?;
+        let group = &body[start + 2..close];
+
+        let mut end = close + 1;
+        let separator = match body.get(end) {
+            Some(t) if t.kind == TokenKind::Star => None,
+            Some(t) => {
+                end += 1;
+                Some(t.clone())
+            }
+            None => None,
+        };
+        if body.get(end).map(|t| t.kind) != Some(TokenKind::Star) {
+            return Err(("expected `*` after `$( ... )`".to_string(), span));
+        }
+
+        let repeated = group.windows(2).find_map(|pair| match pair {
+            [dollar, name] if dollar.kind == TokenKind::Dollar => {
+                match self.bindings.get(&name.lexeme) {
+                    Some(Binding::Many(args)) => Some((name.lexeme.as_str(), args)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        });
+        let Some((var, args)) = repeated else {
+            return Err((
+                "`$( ... )*` must mention a variadic parameter".to_string(),
+                span,
+            ));
+        };
+
+        for (n, arg) in args.iter().enumerate() {
+            if n > 0 {
+                if let Some(separator) = &separator {
+                    let mut separator = separator.clone();
+                    separator.span = self.call_span;
+                    out.push(separator);
+                }
+            }
+            self.transcribe(group, Some((var, arg)), out)?;
+        }
+        Ok(end + 1)
+    }
+
+    /// Paste `pieces` (separated by commas) into one identifier
+    fn concat(&self, pieces: &[Token], span: Span) -> ExpandResult<Token> {
+        let name: String = pieces
+            .iter()
+            .filter(|t| t.kind != TokenKind::Comma)
+            .map(|t| t.lexeme.as_str())
+            .collect();
+        let valid = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+            && TokenKind::is_keyword(&name).is_none();
+        if !valid {
+            return Err((
+                format!("`$concat` produced `{}`, which is not an identifier", name),
+                span,
+            ));
+        }
+        Ok(Token::new(TokenKind::Identifier, name, self.call_span))
+    }
+}
+
+/// Parse `macro name(params) { body }` from `tokens`
+fn parse_definition(tokens: &[Token]) -> ExpandResult<MacroDef> {
+    let keyword_span = tokens[0].span;
+    let expected = |what: &str, at: Option<&Token>| {
+        (
+            format!("expected {} in macro declaration", what),
+            at.map_or(keyword_span, |t| t.span),
+        )
+    };
+
+    let name = tokens
+        .get(1)
+        .filter(|t| t.kind == TokenKind::Identifier)
+        .ok_or_else(|| expected("a macro name", tokens.get(1)))?;
+    if tokens.get(2).map(|t| t.kind) != Some(TokenKind::LeftParen) {
+        return Err(expected("`(`", tokens.get(2)));
+    }
+
+    let mut params: Vec<MacroParam> = Vec::new();
+    let mut i = 3;
+    while tokens.get(i).map(|t| t.kind) != Some(TokenKind::RightParen) {
+        if params.last().is_some_and(|p| p.variadic) {
+            return Err((
+                "only the last macro parameter can be variadic".to_string(),
+                tokens[i].span,
+            ));
+        }
+        let param = tokens
+            .get(i)
+            .filter(|t| t.kind == TokenKind::Identifier)
+            .ok_or_else(|| expected("a parameter name", tokens.get(i)))?;
+        if DIRECTIVES.contains(&param.lexeme.as_str())
+            || params.iter().any(|p| p.name == param.lexeme)
+        {
+            return Err((
+                format!(
+                    "`{}` cannot be used as a macro parameter name here",
+                    param.lexeme
+                ),
+                param.span,
+            ));
+        }
+        i += 1;
+
+        let mut kind = FragmentKind::Tokens;
+        if tokens.get(i).map(|t| t.kind) == Some(TokenKind::Colon) {
+            let kind_token = tokens
+                .get(i + 1)
+                .ok_or_else(|| expected("a fragment kind", None))?;
+            kind = match kind_token.lexeme.as_str() {
+                "ident" => FragmentKind::Ident,
+                "expr" => FragmentKind::Expr,
+                "type" => FragmentKind::Type,
+                "tokens" => FragmentKind::Tokens,
+                other => {
+                    return Err((
+                        format!(
+                            "unknown fragment kind `{}` (expected ident, expr, type or tokens)",
+                            other
+                        ),
+                        kind_token.span,
+                    ))
+                }
+            };
+            i += 2;
+        }
+        let variadic = tokens.get(i).map(|t| t.kind) == Some(TokenKind::DotDotDot);
+        if variadic {
+            i += 1;
+        }
+        params.push(MacroParam {
+            name: param.lexeme.clone(),
+            kind,
+            variadic,
+        });
+
+        match tokens.get(i).map(|t| t.kind) {
+            Some(TokenKind::Comma) => i += 1,
+            Some(TokenKind::RightParen) => {}
+            _ => return Err(expected("`,` or `)`", tokens.get(i))),
+        }
+    }
+    i += 1;
+
+    if tokens.get(i).map(|t| t.kind) != Some(TokenKind::LeftBrace) {
+        return Err(expected("a `{` body", tokens.get(i)));
+    }
+    let close = matching_close(tokens, i)
+        .ok_or_else(|| ("unclosed macro body".to_string(), tokens[i].span))?;
+
+    Ok(MacroDef {
+        name: name.lexeme.clone(),
+        params,
+        body: tokens[i + 1..close].to_vec(),
+    })
+}
+
+/// Index just past the declaration starting at `tokens[start]`: after the
+/// body's closing brace, or after the keyword if there is no body to find
+fn definition_end(tokens: &[Token], start: usize) -> usize {
+    let body = tokens[start + 1..]
+        .iter()
+        .position(|t| matches!(t.kind, TokenKind::LeftBrace | TokenKind::Semicolon))
+        .map(|offset| start + 1 + offset)
+        .filter(|&i| tokens[i].kind == TokenKind::LeftBrace);
+    body.and_then(|open| matching_close(tokens, open))
+        .map_or(start + 1, |close| close + 1)
+}
+
+/// Check an argument against its parameter's kind, parenthesizing
+/// compound expressions so they substitute as a unit
+fn bind_fragment(param: &MacroParam, arg: &[Token], call_span: Span) -> ExpandResult<Vec<Token>> {
+    let span = arg.first().map_or(call_span, |t| t.span);
+    match param.kind {
+        FragmentKind::Ident if arg.len() != 1 || arg[0].kind != TokenKind::Identifier => {
+            Err((format!("`${}` expects an identifier", param.name), span))
+        }
+        FragmentKind::Expr | FragmentKind::Type if arg.is_empty() => {
+            Err((format!("missing argument for `${}`", param.name), span))
+        }
+        FragmentKind::Expr if arg.len() > 1 => {
+            let mut wrapped = Vec::with_capacity(arg.len() + 2);
+            wrapped.push(Token::new(TokenKind::LeftParen, "(", arg[0].span));
+            wrapped.extend_from_slice(arg);
+            wrapped.push(Token::new(
+                TokenKind::RightParen,
+                ")",
+                arg[arg.len() - 1].span,
+            ));
+            Ok(wrapped)
+        }
+        _ => Ok(arg.to_vec()),
+    }
+}
+
+/// Split invocation arguments on top-level commas. Commas inside `<...>`
+/// belong to the argument while a `type` parameter is being filled.
+fn split_args(tokens: &[Token], params: &[MacroParam]) -> Vec<Vec<Token>> {
+    let mut args = Vec::new();
+    let mut current = Vec::new();
+    let mut depth = 0usize;
+    let mut angle_depth = 0usize;
+    for token in tokens {
+        let kind = params.get(args.len()).or(params.last()).map(|p| p.kind);
+        match token.kind {
+            k if is_open(k) => depth += 1,
+            k if is_close(k) => depth = depth.saturating_sub(1),
+            TokenKind::Less if kind == Some(FragmentKind::Type) => angle_depth += 1,
+            TokenKind::Greater if angle_depth > 0 => angle_depth -= 1,
+            TokenKind::Comma if depth == 0 && angle_depth == 0 => {
+                args.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(token.clone());
+    }
+    // A trailing comma does not start another argument
+    let trailing_comma = tokens.last().map(|t| t.kind) == Some(TokenKind::Comma);
+    if !current.is_empty() || (!args.is_empty() && !trailing_comma) {
+        args.push(current);
+    }
+    args
+}
+
+fn is_invocation(tokens: &[Token], i: usize) -> bool {
+    tokens
+        .get(i)
+        .is_some_and(|t| t.kind == TokenKind::Identifier)
+        && tokens.get(i + 1).is_some_and(|t| t.kind == TokenKind::Bang)
+        && tokens
+            .get(i + 2)
+            .is_some_and(|t| t.kind == TokenKind::LeftParen)
+}
+
+fn is_open(kind: TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::LeftParen
+            | TokenKind::LeftBracket
+            | TokenKind::LeftBrace
+            | TokenKind::InterpolationStart
+    )
+}
+
+fn is_close(kind: TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::RightParen
+            | TokenKind::RightBracket
+            | TokenKind::RightBrace
+            | TokenKind::InterpolationEnd
+    )
+}
+
+/// Index of the token closing the bracket at `tokens[open]`
+fn matching_close(tokens: &[Token], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        if is_open(token.kind) {
+            depth += 1;
+        } else if is_close(token.kind) {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
@@ -4,6 +4,7 @@
 //! Uses Pratt parsing for expressions and recursive descent for statements.
 
 mod expr;
+mod macros;
 mod stmt;
 
 use crate::ast::*;
@@ -13,6 +14,7 @@ use crate::diagnostic::error_codes::{
     UNEXPECTED_TOKEN,
 };
 use crate::diagnostic::Diagnostic;
+use crate::span::Span;
 use crate::token::{Token, TokenKind};
 
 /// Parser state for building AST from tokens
@@ -36,10 +38,12 @@ pub struct Parser {
 pub(super) enum Precedence {
     Lowest,
     Range,      // .. ..=
+    Coalesce,   // ??
     Or,         // ||
     And,        // &&
     Equality,   // == !=
-    Comparison, // < <= > >=
+    Comparison, // < <= > >= in
+    Pipeline,   // |>
     Term,       // + -
     Factor,     // * / %
     Unary,      // ! -
@@ -63,10 +67,11 @@ impl Parser {
             .into_iter()
             .filter(|token| !matches!(token.kind, TokenKind::LineComment | TokenKind::BlockComment))
             .collect();
+        let (tokens, diagnostics) = macros::expand(tokens);
         Self {
             tokens,
             current: 0,
-            diagnostics: Vec::new(),
+            diagnostics,
             no_struct_literal: false,
             pending_attributes: Vec::new(),
             in_panic_mode: false,
@@ -75,20 +80,49 @@ impl Parser {
 
     /// Parse tokens into an AST
     pub fn parse(&mut self) -> (Program, Vec<Diagnostic>) {
+        let (program, _, diagnostics) = self.parse_with_item_spans();
+        (program, diagnostics)
+    }
+
+    /// Whether `tokens` declare or invoke a macro.
+    ///
+    /// Such a file parses to its expansion, so tools that print source back
+    /// out from the AST should leave it alone.
+    pub fn uses_macros(tokens: &[Token]) -> bool {
+        macros::uses_macros(tokens)
+    }
+
+    /// Parse tokens into an AST, also returning the source extent of each
+    /// top-level item, leading doc comments included.
+    ///
+    /// The extents line up with `Program::items` and let the LSP re-parse
+    /// only the items an edit touches.
+    pub fn parse_with_item_spans(&mut self) -> (Program, Vec<Span>, Vec<Diagnostic>) {
         let mut items = Vec::new();
+        let mut item_spans = Vec::new();
 
         while !self.is_at_end_raw() {
+            let start = self.peek_raw().span;
             let doc_comment = self.collect_doc_comments();
             if self.is_at_end() {
                 break;
             }
+            let first_token = self.current;
             match self.parse_item(doc_comment) {
-                Ok(item) => items.push(item),
-                Err(_) => self.synchronize(),
+                Ok(item) => {
+                    let end = self.tokens[self.current - 1].span;
+                    items.push(item);
+                    item_spans.push(start.merge(end));
+                }
+                Err(_) => self.recover(first_token),
             }
         }
 
-        (Program { items }, std::mem::take(&mut self.diagnostics))
+        (
+            Program { items },
+            item_spans,
+            std::mem::take(&mut self.diagnostics),
+        )
     }
 
     // === Top-level parsing ===
@@ -518,6 +552,17 @@ impl Parser {
                 alias,
                 span: import_span,
             });
+        } else if self.check(TokenKind::Identifier) {
+            // Module import: import ns from "./path" (same as `* as ns`)
+            let alias_token = self.consume_identifier("module name")?;
+            let alias = Identifier {
+                name: alias_token.lexeme.clone(),
+                span: alias_token.span,
+            };
+            specifiers.push(ImportSpecifier::Namespace {
+                alias,
+                span: import_span,
+            });
         } else {
             // Named imports: import { x, y } from "./path"
             self.consume(TokenKind::LeftBrace, "Expected '{' for named imports")?;
@@ -980,16 +1025,16 @@ impl Parser {
             }
         }
 
-        self.consume(TokenKind::LeftBrace, "Expected '{' after trait name")?;
+        let open = self
+            .consume(TokenKind::LeftBrace, "Expected '{' after trait name")?
+            .span;
 
         let mut methods = Vec::new();
         while !self.check(TokenKind::RightBrace) && !self.is_at_end() {
             methods.push(self.parse_trait_method_sig()?);
         }
 
-        let end_span = self
-            .consume(TokenKind::RightBrace, "Expected '}' after trait body")?
-            .span;
+        let end_span = self.close_brace(open, "Expected '}' after trait body")?;
 
         let attributes = std::mem::take(&mut self.pending_attributes);
         Ok(TraitDecl {
@@ -1087,18 +1132,18 @@ impl Parser {
                 span: type_name_tok.span,
             };
 
-            self.consume(
-                TokenKind::LeftBrace,
-                "Expected '{' after type name in impl block",
-            )?;
+            let open = self
+                .consume(
+                    TokenKind::LeftBrace,
+                    "Expected '{' after type name in impl block",
+                )?
+                .span;
             let self_type = TypeRef::Named(type_name.name.clone(), type_name.span);
             let mut methods = Vec::new();
             while !self.check(TokenKind::RightBrace) && !self.is_at_end() {
                 methods.push(self.parse_impl_method(&self_type)?);
             }
-            let end_span = self
-                .consume(TokenKind::RightBrace, "Expected '}' after impl body")?
-                .span;
+            let end_span = self.close_brace(open, "Expected '}' after impl body")?;
 
             Ok(ImplBlock {
                 trait_name: Some(first_name),
@@ -1109,18 +1154,18 @@ impl Parser {
             })
         } else {
             // Inherent impl: `impl TypeName { ... }`
-            self.consume(
-                TokenKind::LeftBrace,
-                "Expected '{' after type name in impl block",
-            )?;
+            let open = self
+                .consume(
+                    TokenKind::LeftBrace,
+                    "Expected '{' after type name in impl block",
+                )?
+                .span;
             let self_type = TypeRef::Named(first_name.name.clone(), first_name.span);
             let mut methods = Vec::new();
             while !self.check(TokenKind::RightBrace) && !self.is_at_end() {
                 methods.push(self.parse_impl_method(&self_type)?);
             }
-            let end_span = self
-                .consume(TokenKind::RightBrace, "Expected '}' after impl body")?
-                .span;
+            let end_span = self.close_brace(open, "Expected '}' after impl body")?;
 
             Ok(ImplBlock {
                 trait_name: None,
@@ -1373,6 +1418,27 @@ impl Parser {
         }
     }
 
+    /// Consume the `}` closing a body opened at `open`, closing it virtually
+    /// when the file ends first.
+    ///
+    /// Files are usually cut short mid-edit, so rather than failing the
+    /// whole enclosing item this reports one AT1021 at the unclosed `{` and
+    /// keeps what was parsed. Enclosing bodies that end at the same EOF stay
+    /// quiet through the usual cascade suppression.
+    pub(super) fn close_brace(&mut self, open: Span, message: &str) -> Result<Span, ()> {
+        if !self.is_at_end() {
+            return self.consume(TokenKind::RightBrace, message).map(|t| t.span);
+        }
+        self.emit_descriptor(
+            MISSING_CLOSING_DELIMITER
+                .emit(open)
+                .arg("delimiter", "}")
+                .with_help("add `}` to close this `{` — the file ends before it is closed")
+                .with_note("the body was closed at end of file so the rest of it still parses"),
+        );
+        Ok(self.peek().span)
+    }
+
     /// Check if at end of token stream
     pub(super) fn is_at_end(&mut self) -> bool {
         self.skip_trivia();
@@ -1536,6 +1602,34 @@ impl Parser {
         }
     }
 
+    /// Recover from an item or statement that began at token `start` and failed
+    ///
+    /// If it failed on a token that opens a new declaration — typically a
+    /// missing `;` at the end of a line, as after a string the lexer had to
+    /// close — parsing resumes right there rather than skipping that
+    /// declaration too. Otherwise this is [`synchronize`](Self::synchronize).
+    pub(super) fn recover(&mut self, start: usize) {
+        let at_declaration = matches!(
+            self.peek().kind,
+            TokenKind::Fn
+                | TokenKind::Let
+                | TokenKind::Const
+                | TokenKind::Type
+                | TokenKind::Struct
+                | TokenKind::Enum
+                | TokenKind::Trait
+                | TokenKind::Impl
+                | TokenKind::Import
+                | TokenKind::Export
+                | TokenKind::Extern
+        );
+        if self.current > start && at_declaration {
+            self.in_panic_mode = false;
+        } else {
+            self.synchronize();
+        }
+    }
+
     /// Synchronize after error — clears `in_panic_mode` to re-enable error reporting.
     ///
     /// Tracks brace depth so it skips nested `{ }` blocks entirely before stopping at
@@ -5,7 +5,7 @@ use crate::diagnostic::error_codes::{
     FOREIGN_SYNTAX_CLASS, FOREIGN_SYNTAX_ECHO, FOREIGN_SYNTAX_FUNCTION_KW,
     FOREIGN_SYNTAX_IMPORT_FROM, FOREIGN_SYNTAX_INCREMENT, FOREIGN_SYNTAX_VAR,
     INVALID_ASSIGN_TARGET, INVALID_ASSIGN_TARGET_CALL, INVALID_ASSIGN_TARGET_MEMBER,
-    INVALID_ASSIGN_TARGET_RANGE,
+    INVALID_ASSIGN_TARGET_RANGE, UNEXPECTED_TOKEN,
 };
 use crate::diagnostic::Diagnostic;
 use crate::parser::Parser;
@@ -37,6 +37,13 @@ impl Parser {
                     return Err(());
                 }
                 // "console" is a valid Atlas namespace (B21) — no foreign syntax trap
+                // `try` is contextual: only `try {` starts a try/catch statement,
+                // so existing identifiers named `try` keep working.
+                "try"
+                    if self.peek_nth_nontrivia(1).map(|t| t.kind) == Some(TokenKind::LeftBrace) =>
+                {
+                    return self.parse_try_catch_stmt();
+                }
                 _ => {}
             }
 
@@ -102,43 +109,15 @@ impl Parser {
             false // let x = ... (immutable)
         };
 
-        // Tuple destructuring: `let (a, b) = expr;` or `let mut (a, b) = expr;`
-        if self.check(TokenKind::LeftParen) {
-            self.advance(); // consume '('
-            let mut names = Vec::new();
-            loop {
-                if self.check(TokenKind::RightParen) {
-                    break;
-                }
-                let tok = self.consume_identifier("a variable name in tuple pattern")?;
-                names.push(Identifier {
-                    name: tok.lexeme.clone(),
-                    span: tok.span,
-                });
-                if self.check(TokenKind::Comma) {
-                    self.advance();
-                } else {
-                    break;
-                }
-            }
-            self.consume(TokenKind::RightParen, "Expected ')' after tuple pattern")?;
-            self.consume(
-                TokenKind::Equal,
-                "Expected '=' in destructuring declaration",
-            )?;
-            let init = self.parse_expression()?;
-            let end_span = self
-                .consume(
-                    TokenKind::Semicolon,
-                    "Expected ';' after destructuring declaration",
-                )?
-                .span;
-            return Ok(Stmt::LetDestructure(LetDestructure {
-                mutable,
-                names,
-                init,
-                span: keyword_span.merge(end_span),
-            }));
+        // Destructuring: `let (a, b) = expr;`, `let [a, ...rest] = expr;`, `let { a } = expr;`
+        let destructure = match self.peek().kind {
+            TokenKind::LeftParen => Some(DestructureKind::Tuple),
+            TokenKind::LeftBracket => Some(DestructureKind::Array { rest: false }),
+            TokenKind::LeftBrace => Some(DestructureKind::Map),
+            _ => None,
+        };
+        if let Some(kind) = destructure {
+            return self.parse_let_destructure(keyword_span, mutable, kind);
         }
 
         let name_token = self.consume_identifier("a variable name")?;
@@ -170,6 +149,65 @@ impl Parser {
             init,
             span: keyword_span.merge(end_span),
             needs_drop: std::cell::RefCell::new(None),
+            inferred_type: std::cell::RefCell::new(None),
+        }))
+    }
+
+    /// Parse the pattern, initializer and `;` of a destructuring `let`; the
+    /// opening `(`, `[` or `{` is the current token
+    fn parse_let_destructure(
+        &mut self,
+        keyword_span: crate::span::Span,
+        mutable: bool,
+        mut kind: DestructureKind,
+    ) -> Result<Stmt, ()> {
+        let (close, what) = match kind {
+            DestructureKind::Tuple => (TokenKind::RightParen, "tuple"),
+            DestructureKind::Array { .. } => (TokenKind::RightBracket, "array"),
+            DestructureKind::Map => (TokenKind::RightBrace, "map"),
+        };
+        self.advance(); // consume the opening delimiter
+
+        let mut names = Vec::new();
+        while !self.check(close) {
+            let is_rest = matches!(kind, DestructureKind::Array { .. })
+                && self.match_token(TokenKind::DotDotDot);
+            let tok = self.consume_identifier(&format!("a variable name in {} pattern", what))?;
+            names.push(Identifier {
+                name: tok.lexeme.clone(),
+                span: tok.span,
+            });
+            if is_rest {
+                // The rest binding takes everything left, so it must come last
+                kind = DestructureKind::Array { rest: true };
+                self.match_token(TokenKind::Comma);
+                break;
+            }
+            if !self.match_token(TokenKind::Comma) {
+                break;
+            }
+        }
+        self.consume(
+            close,
+            &format!("Expected '{}' after {} pattern", close.as_str(), what),
+        )?;
+        self.consume(
+            TokenKind::Equal,
+            "Expected '=' in destructuring declaration",
+        )?;
+        let init = self.parse_expression()?;
+        let end_span = self
+            .consume(
+                TokenKind::Semicolon,
+                "Expected ';' after destructuring declaration",
+            )?
+            .span;
+        Ok(Stmt::LetDestructure(LetDestructure {
+            mutable,
+            kind,
+            names,
+            init,
+            span: keyword_span.merge(end_span),
         }))
     }
 
@@ -250,6 +288,13 @@ impl Parser {
             Expr::Identifier(ident) => Ok(AssignTarget::Name(ident)),
             Expr::Index(idx) => match idx.index {
                 IndexValue::Single(index) => {
+                    if has_null_safe_access(&idx.target) {
+                        let span = self.peek().span;
+                        self.emit_descriptor(INVALID_ASSIGN_TARGET.emit(span).with_help(
+                            "`?.` only reads; check the value for null, then assign with `.`",
+                        ));
+                        return Err(());
+                    }
                     if matches!(index.as_ref(), Expr::Range { .. }) {
                         let span = self.peek().span;
                         self.emit_descriptor(INVALID_ASSIGN_TARGET_RANGE.emit(span));
@@ -268,6 +313,13 @@ impl Parser {
                     self.emit_descriptor(INVALID_ASSIGN_TARGET_CALL.emit(span));
                     return Err(());
                 }
+                if member.optional || has_null_safe_access(&member.target) {
+                    let span = self.peek().span;
+                    self.emit_descriptor(INVALID_ASSIGN_TARGET.emit(span).with_help(
+                        "`?.` only reads; check the value for null, then assign with `.`",
+                    ));
+                    return Err(());
+                }
                 if !matches!(
                     member.target.as_ref(),
                     Expr::Identifier(_) | Expr::Member(_) | Expr::Index(_)
@@ -492,6 +544,48 @@ impl Parser {
         }))
     }
 
+    /// Parse try/catch statement
+    ///
+    /// Syntax: `try { body } catch (e) { handler }` or `try { body } catch { handler }`
+    pub(super) fn parse_try_catch_stmt(&mut self) -> Result<Stmt, ()> {
+        let try_span = self.advance().span; // consume contextual 'try'
+        let body = self.parse_block()?;
+
+        if !(self.check(TokenKind::Identifier) && self.peek().lexeme == "catch") {
+            let found = self.peek().kind;
+            let span = self.peek().span;
+            self.emit_descriptor(
+                UNEXPECTED_TOKEN
+                    .emit(span)
+                    .arg("token", found.as_str())
+                    .with_help("add a `catch { ... }` block after the `try` block"),
+            );
+            return Err(());
+        }
+        self.advance(); // consume contextual 'catch'
+
+        let error_binding = if self.match_token(TokenKind::LeftParen) {
+            let tok = self.consume_identifier("an error variable name")?;
+            let binding = Identifier {
+                name: tok.lexeme.clone(),
+                span: tok.span,
+            };
+            self.consume(TokenKind::RightParen, "Expected ')' after catch variable")?;
+            Some(binding)
+        } else {
+            None
+        };
+
+        let catch_block = self.parse_block()?;
+        let span = try_span.merge(catch_block.span);
+        Ok(Stmt::TryCatch(TryCatchStmt {
+            body,
+            error_binding,
+            catch_block,
+            span,
+        }))
+    }
+
     /// Parse a block with support for implicit returns (Rust-style tail expressions)
     ///
     /// If the last item in a block is an expression without a trailing semicolon,
@@ -510,13 +604,14 @@ impl Parser {
             }
 
             // Otherwise, parse a regular statement
+            let first_token = self.current;
             match self.parse_statement() {
                 Ok(stmt) => statements.push(stmt),
-                Err(_) => self.synchronize(),
+                Err(_) => self.recover(first_token),
             }
         }
 
-        let end_span = self.consume(TokenKind::RightBrace, "Expected '}'")?.span;
+        let end_span = self.close_brace(start_span, "Expected '}'")?;
 
         Ok(Block {
             statements,
@@ -561,8 +656,9 @@ impl Parser {
 
         // Check what follows the expression
         match self.peek().kind {
-            TokenKind::RightBrace => {
-                // Expression followed by `}` = tail expression (implicit return)
+            TokenKind::RightBrace | TokenKind::Eof => {
+                // Expression followed by `}` = tail expression (implicit return).
+                // At EOF the block is unclosed and `parse_block` closes it.
                 Ok(Some(expr))
             }
             TokenKind::Semicolon => {
@@ -591,3 +687,12 @@ impl Parser {
         }
     }
 }
+
+/// Whether an assignment target reads through `?.` anywhere along its path
+fn has_null_safe_access(expr: &Expr) -> bool {
+    match expr {
+        Expr::Member(member) => member.optional || has_null_safe_access(&member.target),
+        Expr::Index(index) => has_null_safe_access(&index.target),
+        _ => false,
+    }
+}
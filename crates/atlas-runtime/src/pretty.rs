@@ -0,0 +1,307 @@
+//! Structured pretty-printing of runtime values
+//!
+//! `Display` for [`Value`] is the terse form `print` uses. [`Value::pretty`]
+//! is the inspecting form: strings are quoted, collections are expanded
+//! element by element, and output is cut off by depth, element count and
+//! string length so a huge or deeply nested value never floods a terminal.
+//! Collections that fit within the configured width stay on one line;
+//! longer ones are broken one element per line.
+//!
+//! A `shared<T>` cell that contains itself prints as `<cycle>` where it
+//! repeats.
+
+use crate::json_value::JsonValue;
+use crate::value::{CycleGuard, Value};
+
+/// Limits and layout for [`Value::pretty`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrettyConfig {
+    /// Nesting depth to expand; deeper collections print as a summary
+    /// such as `[Array(3)]`
+    pub max_depth: usize,
+    /// Elements shown per collection before `... N more`
+    pub max_items: usize,
+    /// Characters shown per string before it is cut
+    pub max_string: usize,
+    /// Line width a collection must fit in to stay on one line
+    pub width: usize,
+    /// Spaces per nesting level in multi-line output
+    pub indent: usize,
+}
+
+impl Default for PrettyConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 4,
+            max_items: 100,
+            max_string: 1000,
+            width: 80,
+            indent: 2,
+        }
+    }
+}
+
+impl PrettyConfig {
+    /// Default limits, always rendered on a single line
+    pub fn compact() -> Self {
+        Self {
+            width: usize::MAX,
+            ..Self::default()
+        }
+    }
+}
+
+impl Value {
+    /// Render this value for a human reader, within `config`'s limits
+    pub fn pretty(&self, config: &PrettyConfig) -> String {
+        let mut printer = Printer {
+            config,
+            guard: CycleGuard::default(),
+        };
+        let doc = printer.value(self, 0);
+        let mut out = String::new();
+        printer.render(&doc, 0, 0, &mut out);
+        out
+    }
+}
+
+/// Layout tree: leaves are final text, groups are bracketed element lists
+/// that are laid out flat or broken depending on the available width
+enum Doc {
+    Text(String),
+    Group {
+        open: String,
+        close: &'static str,
+        items: Vec<Doc>,
+        /// Elements dropped by `max_items`
+        more: usize,
+    },
+    /// `key: value` inside a map or object
+    Entry(String, Box<Doc>),
+}
+
+struct Printer<'a> {
+    config: &'a PrettyConfig,
+    guard: CycleGuard,
+}
+
+impl Printer<'_> {
+    fn value(&mut self, value: &Value, depth: usize) -> Doc {
+        match value {
+            Value::String(s) => Doc::Text(self.string(s)),
+            Value::Array(arr) => self.seq("[", "]", "Array", arr.as_slice(), depth),
+            Value::Tuple(elems) => {
+                let close = if elems.len() == 1 { ",)" } else { ")" };
+                self.seq("(", close, "Tuple", elems, depth)
+            }
+            Value::Queue(q) => self.seq("Queue [", "]", "Queue", &q.inner().to_vec(), depth),
+            Value::Stack(s) => self.seq("Stack [", "]", "Stack", &s.inner().to_vec(), depth),
+            Value::Set(set) => {
+                let mut elems: Vec<Value> =
+                    set.inner().to_vec().iter().map(|k| k.to_value()).collect();
+                elems.sort_by_cached_key(|v| v.to_string());
+                self.seq("Set {", "}", "Set", &elems, depth)
+            }
+            Value::Map(map) => {
+                if depth > self.config.max_depth && !map.is_empty() {
+                    return Doc::Text(format!("[Map({})]", map.len()));
+                }
+                let mut entries: Vec<(String, Value)> = map
+                    .entries()
+                    .into_iter()
+                    .map(|(k, v)| (self.key(&k.to_value()), v))
+                    .collect();
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
+                let more = entries.len().saturating_sub(self.config.max_items);
+                let items = entries
+                    .into_iter()
+                    .take(self.config.max_items)
+                    .map(|(k, v)| Doc::Entry(k, Box::new(self.value(&v, depth + 1))))
+                    .collect();
+                group("{", "}", items, more)
+            }
+            Value::JsonValue(json) => self.json(json, depth),
+            Value::Option(Some(v)) => group("Some(", ")", vec![self.value(v, depth)], 0),
+            Value::Result(Ok(v)) => group("Ok(", ")", vec![self.value(v, depth)], 0),
+            Value::Result(Err(e)) => group("Err(", ")", vec![self.value(e, depth)], 0),
+            Value::EnumValue {
+                enum_name,
+                variant_name,
+                data,
+            } if !data.is_empty() => {
+                let open = format!("{}::{}(", enum_name, variant_name);
+                let items = data.iter().map(|v| self.value(v, depth + 1)).collect();
+                group(open, ")", items, 0)
+            }
+            Value::SharedValue(cell) => {
+                if !self.guard.enter(cell) {
+                    return Doc::Text("<cycle>".to_string());
+                }
+                let inner = cell.with(|v| self.value(v, depth));
+                self.guard.exit(cell);
+                group("shared(", ")", vec![inner], 0)
+            }
+            other => Doc::Text(other.to_string()),
+        }
+    }
+
+    fn seq(
+        &mut self,
+        open: &str,
+        close: &'static str,
+        kind: &str,
+        elems: &[Value],
+        depth: usize,
+    ) -> Doc {
+        if depth > self.config.max_depth && !elems.is_empty() {
+            return Doc::Text(format!("[{}({})]", kind, elems.len()));
+        }
+        let more = elems.len().saturating_sub(self.config.max_items);
+        let items = elems
+            .iter()
+            .take(self.config.max_items)
+            .map(|v| self.value(v, depth + 1))
+            .collect();
+        group(open, close, items, more)
+    }
+
+    fn json(&mut self, json: &JsonValue, depth: usize) -> Doc {
+        match json {
+            JsonValue::String(s) => Doc::Text(self.string(s)),
+            JsonValue::Array(arr) => {
+                if depth > self.config.max_depth && !arr.is_empty() {
+                    return Doc::Text(format!("[Array({})]", arr.len()));
+                }
+                let more = arr.len().saturating_sub(self.config.max_items);
+                let items = arr
+                    .iter()
+                    .take(self.config.max_items)
+                    .map(|v| self.json(v, depth + 1))
+                    .collect();
+                group("[", "]", items, more)
+            }
+            JsonValue::Object(obj) => {
+                if depth > self.config.max_depth && !obj.is_empty() {
+                    return Doc::Text(format!("[Object({})]", obj.len()));
+                }
+                let mut keys: Vec<&String> = obj.keys().collect();
+                keys.sort();
+                let more = keys.len().saturating_sub(self.config.max_items);
+                let items = keys
+                    .into_iter()
+                    .take(self.config.max_items)
+                    .map(|k| {
+                        let key = self.string(k);
+                        Doc::Entry(key, Box::new(self.json(&obj[k], depth + 1)))
+                    })
+                    .collect();
+                group("{", "}", items, more)
+            }
+            other => Doc::Text(other.to_string()),
+        }
+    }
+
+    /// Quoted, escaped and truncated to `max_string` characters
+    fn string(&self, s: &str) -> String {
+        let total = s.chars().count();
+        let shown: String = s.chars().take(self.config.max_string).collect();
+        let mut out = String::with_capacity(shown.len() + 2);
+        out.push('"');
+        for c in shown.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_control() => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+        if total > self.config.max_string {
+            out.push_str(&format!("... (+{} chars)", total - self.config.max_string));
+        }
+        out
+    }
+
+    fn key(&self, key: &Value) -> String {
+        match key {
+            Value::String(s) => self.string(s),
+            other => other.to_string(),
+        }
+    }
+
+    fn render(&self, doc: &Doc, level: usize, used: usize, out: &mut String) {
+        match doc {
+            Doc::Text(text) => out.push_str(text),
+            Doc::Entry(key, value) => {
+                out.push_str(key);
+                out.push_str(": ");
+                self.render(value, level, used + key.chars().count() + 2, out);
+            }
+            Doc::Group {
+                open,
+                close,
+                items,
+                more,
+            } => {
+                let flat = flat(doc);
+                let column = level * self.config.indent + used;
+                if items.is_empty()
+                    || column.saturating_add(flat.chars().count()) <= self.config.width
+                {
+                    out.push_str(&flat);
+                    return;
+                }
+                let pad = " ".repeat((level + 1) * self.config.indent);
+                out.push_str(open);
+                for item in items {
+                    out.push('\n');
+                    out.push_str(&pad);
+                    self.render(item, level + 1, 0, out);
+                    out.push(',');
+                }
+                if *more > 0 {
+                    out.push('\n');
+                    out.push_str(&pad);
+                    out.push_str(&format!("... {} more", more));
+                } else {
+                    out.pop();
+                }
+                out.push('\n');
+                out.push_str(&" ".repeat(level * self.config.indent));
+                out.push_str(close);
+            }
+        }
+    }
+}
+
+fn group(open: impl Into<String>, close: &'static str, items: Vec<Doc>, more: usize) -> Doc {
+    Doc::Group {
+        open: open.into(),
+        close,
+        items,
+        more,
+    }
+}
+
+/// Single-line rendering of a layout tree
+fn flat(doc: &Doc) -> String {
+    match doc {
+        Doc::Text(text) => text.clone(),
+        Doc::Entry(key, value) => format!("{}: {}", key, flat(value)),
+        Doc::Group {
+            open,
+            close,
+            items,
+            more,
+        } => {
+            let mut parts: Vec<String> = items.iter().map(flat).collect();
+            if *more > 0 {
+                parts.push(format!("... {} more", more));
+            }
+            format!("{}{}{}", open, parts.join(", "), close)
+        }
+    }
+}
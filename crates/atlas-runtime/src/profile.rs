@@ -0,0 +1,312 @@
+//! Language profiles: per-runtime restrictions on grammar and stdlib
+//!
+//! An application embedding Atlas as a rules or configuration language can
+//! narrow what scripts may use. A [`LanguageProfile`] can
+//!
+//! - disable grammar [`Production`]s, e.g. forbid `fn` declarations and
+//!   loops so scripts are plain expressions,
+//! - hide keywords (the word becomes an ordinary identifier) and add
+//!   aliases that spell a keyword differently (`when` for `if`),
+//! - hide stdlib builtins and namespaces, or allow only a listed subset.
+//!
+//! The profile is set on [`RuntimeConfig`](crate::api::RuntimeConfig) or
+//! [`Runtime::set_language_profile`](crate::api::Runtime::set_language_profile).
+//! Keywords are rewritten between lexing and parsing; productions and
+//! builtins are checked by the binder, which reports a use outside the
+//! profile as `AT1025` before anything runs. Functions the host registers
+//! with `register_function` are always visible.
+//!
+//! ```
+//! use atlas_runtime::api::{LanguageProfile, Runtime, RuntimeConfig};
+//!
+//! let profile = LanguageProfile::expressions_only()
+//!     .only_builtins(["math", "len"])
+//!     .with_keyword_alias("when", "if")
+//!     .unwrap();
+//! let mut runtime = Runtime::from_config(RuntimeConfig::new().with_language_profile(profile));
+//!
+//! assert!(runtime.eval("math.max(len([1, 2]), 1)").is_ok());
+//! assert!(runtime.eval("fn f(): number { return 1; }").is_err());
+//! assert!(runtime.eval(r#"file.read("secrets.txt")"#).is_err());
+//! ```
+
+use crate::token::{Token, TokenKind};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A grammar production that a profile can disable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Production {
+    /// Named `fn` declarations, top-level and nested
+    Functions,
+    /// Anonymous functions and closures
+    Closures,
+    /// `let`, `var` and `const` declarations, and assignment
+    Variables,
+    /// `while` and `for` loops, with `break` and `continue`
+    Loops,
+    /// `struct`, `enum`, `type`, `trait` and `impl` declarations
+    TypeDeclarations,
+    /// `import` and `export`
+    Modules,
+    /// `extern` (FFI) declarations
+    Extern,
+}
+
+impl Production {
+    /// Every production, in declaration order
+    pub const ALL: [Production; 7] = [
+        Production::Functions,
+        Production::Closures,
+        Production::Variables,
+        Production::Loops,
+        Production::TypeDeclarations,
+        Production::Modules,
+        Production::Extern,
+    ];
+
+    /// Description used in diagnostics
+    pub fn describe(self) -> &'static str {
+        match self {
+            Production::Functions => "function declarations",
+            Production::Closures => "anonymous functions",
+            Production::Variables => "variable declarations and assignment",
+            Production::Loops => "loops",
+            Production::TypeDeclarations => "type declarations",
+            Production::Modules => "imports and exports",
+            Production::Extern => "extern declarations",
+        }
+    }
+}
+
+/// Error building a [`LanguageProfile`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileError {
+    /// The word is not an Atlas keyword
+    UnknownKeyword(String),
+    /// An alias must be a plain identifier that is not already a keyword
+    InvalidAlias(String),
+}
+
+impl std::fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProfileError::UnknownKeyword(word) => write!(f, "'{}' is not a keyword", word),
+            ProfileError::InvalidAlias(alias) => write!(
+                f,
+                "'{}' cannot be a keyword alias: it must be an identifier that is not a keyword",
+                alias
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {}
+
+/// Restrictions on the language a runtime accepts
+///
+/// The default profile is the full language.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LanguageProfile {
+    disabled: BTreeSet<Production>,
+    hidden_keywords: BTreeSet<String>,
+    /// alias → keyword it stands for
+    keyword_aliases: BTreeMap<String, String>,
+    hidden_builtins: BTreeSet<String>,
+    /// When set, only these builtins are visible
+    visible_builtins: Option<BTreeSet<String>>,
+}
+
+impl LanguageProfile {
+    /// The full language
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expressions only: every [`Production`] disabled
+    ///
+    /// Scripts are a sequence of expressions — calls, operators, `if`,
+    /// `match` and blocks — ending in the value they evaluate to.
+    pub fn expressions_only() -> Self {
+        Production::ALL
+            .into_iter()
+            .fold(Self::new(), |profile, production| {
+                profile.without(production)
+            })
+    }
+
+    /// Side-effect-free expressions: [`expressions_only`](Self::expressions_only)
+    /// with only the pure builtins visible
+    ///
+    /// Math, JSON, regex, encoding, date arithmetic, collections and the
+    /// conversion and type-guard helpers stay; `console`, `file`, `io`,
+    /// `process`, `env`, `http`, `net`, storage and task APIs are hidden.
+    /// This is the profile [`Atlas::eval_expr`](crate::Atlas::eval_expr) uses.
+    pub fn pure_expressions() -> Self {
+        Self::expressions_only().only_builtins(PURE_BUILTINS)
+    }
+
+    /// Disable `production`
+    pub fn without(mut self, production: Production) -> Self {
+        self.disabled.insert(production);
+        self
+    }
+
+    /// Whether `production` is enabled
+    pub fn allows(&self, production: Production) -> bool {
+        !self.disabled.contains(&production)
+    }
+
+    /// Turn `keyword` into an ordinary identifier
+    ///
+    /// Together with [`with_keyword_alias`](Self::with_keyword_alias) this
+    /// renames a keyword.
+    pub fn hide_keyword(mut self, keyword: &str) -> Result<Self, ProfileError> {
+        if TokenKind::is_keyword(keyword).is_none() {
+            return Err(ProfileError::UnknownKeyword(keyword.to_string()));
+        }
+        self.hidden_keywords.insert(keyword.to_string());
+        Ok(self)
+    }
+
+    /// Accept `alias` wherever `keyword` is accepted
+    ///
+    /// `alias` must not be a keyword itself unless that keyword is hidden.
+    pub fn with_keyword_alias(mut self, alias: &str, keyword: &str) -> Result<Self, ProfileError> {
+        if TokenKind::is_keyword(keyword).is_none() {
+            return Err(ProfileError::UnknownKeyword(keyword.to_string()));
+        }
+        let is_identifier = alias
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && alias.chars().all(|c| c.is_alphanumeric() || c == '_');
+        let is_live_keyword =
+            TokenKind::is_keyword(alias).is_some() && !self.hidden_keywords.contains(alias);
+        if !is_identifier || is_live_keyword {
+            return Err(ProfileError::InvalidAlias(alias.to_string()));
+        }
+        self.keyword_aliases
+            .insert(alias.to_string(), keyword.to_string());
+        Ok(self)
+    }
+
+    /// Hide a builtin function (`len`) or stdlib namespace (`file`)
+    pub fn hide_builtin(mut self, name: &str) -> Self {
+        self.hidden_builtins.insert(builtin_key(name));
+        self
+    }
+
+    /// Make only the listed builtins and namespaces visible
+    ///
+    /// `Ok`, `Err`, `Some` and `None` are part of the language rather than
+    /// the stdlib and stay visible.
+    pub fn only_builtins<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.visible_builtins = Some(names.into_iter().map(|n| builtin_key(n.as_ref())).collect());
+        self
+    }
+
+    /// Whether scripts may use the builtin or namespace `name`
+    pub fn is_builtin_visible(&self, name: &str) -> bool {
+        if matches!(name, "Ok" | "Err" | "Some" | "None") {
+            return true;
+        }
+        let key = builtin_key(name);
+        !self.hidden_builtins.contains(&key)
+            && self
+                .visible_builtins
+                .as_ref()
+                .is_none_or(|visible| visible.contains(&key))
+    }
+
+    /// Whether this profile restricts nothing
+    pub fn is_unrestricted(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Apply hidden keywords and aliases to a token stream
+    pub fn rewrite_keywords(&self, tokens: &mut [Token]) {
+        if self.hidden_keywords.is_empty() && self.keyword_aliases.is_empty() {
+            return;
+        }
+        for token in tokens {
+            if TokenKind::is_keyword(&token.lexeme) == Some(token.kind)
+                && self.hidden_keywords.contains(&token.lexeme)
+            {
+                token.kind = TokenKind::Identifier;
+            }
+            if token.kind == TokenKind::Identifier {
+                if let Some(keyword) = self.keyword_aliases.get(&token.lexeme) {
+                    token.kind = TokenKind::is_keyword(keyword).unwrap_or(TokenKind::Identifier);
+                }
+            }
+        }
+    }
+}
+
+/// Builtins and namespaces visible under [`LanguageProfile::pure_expressions`]
+const PURE_BUILTINS: &[&str] = &[
+    "math",
+    "json",
+    "regex",
+    "encoding",
+    "csv",
+    "datetime",
+    "array",
+    "reflect",
+    "len",
+    "str",
+    "toString",
+    "inspect",
+    "typeof",
+    "type_of",
+    "unwrap",
+    "unwrap_or",
+    "expect",
+    "is_ok",
+    "is_err",
+    "is_some",
+    "is_none",
+    "isOk",
+    "isErr",
+    "isSome",
+    "isNone",
+    "is_string",
+    "isString",
+    "is_number",
+    "isNumber",
+    "is_bool",
+    "isBool",
+    "is_null",
+    "isNull",
+    "is_array",
+    "isArray",
+    "is_function",
+    "isFunction",
+    "is_object",
+    "isObject",
+    "is_type",
+    "isType",
+    "has_field",
+    "hasField",
+    "has_method",
+    "hasMethod",
+    "has_tag",
+    "hasTag",
+    "Map",
+    "Set",
+    "Queue",
+    "Stack",
+];
+
+/// Namespaces resolve case-insensitively (`Math` and `math`), bare builtins do not
+fn builtin_key(name: &str) -> String {
+    if crate::method_dispatch::is_static_namespace(name) {
+        name.to_lowercase()
+    } else {
+        name.to_string()
+    }
+}
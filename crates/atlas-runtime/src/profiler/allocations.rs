@@ -0,0 +1,472 @@
+//! Allocation and memory profiling
+//!
+//! Counts the heap values a program creates, by kind and by the instruction
+//! (function and source span) that created them, and samples the memory
+//! reachable from the VM roots to find the peak.
+//!
+//! An allocation is seen when an instruction that can create a heap value —
+//! arithmetic (string concatenation), collection, struct, tuple, enum and
+//! closure construction, slicing, `ToString`, builtin calls — leaves one on
+//! top of the stack that nothing else references yet. Values a builtin
+//! creates and stores without returning them, and copy-on-write copies made
+//! by in-place updates (`a[i] = x`), are not counted. Sizes are estimates:
+//! the payload of the value itself, not allocator overhead.
+
+use crate::bytecode::Opcode;
+use crate::span::Span;
+use crate::value::Value;
+use std::collections::{HashMap, HashSet};
+use std::mem::size_of;
+use std::sync::Arc;
+
+/// Instructions between memory samples at the least; the interval grows
+/// with the cost of the previous sample
+const MIN_SAMPLE_INTERVAL: u64 = 4096;
+
+/// Kind of heap value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AllocationKind {
+    String,
+    Array,
+    Map,
+    Set,
+    Struct,
+    Tuple,
+    Closure,
+    Enum,
+    /// Queues, stacks and byte buffers
+    Other,
+}
+
+impl AllocationKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            AllocationKind::String => "string",
+            AllocationKind::Array => "array",
+            AllocationKind::Map => "hashmap",
+            AllocationKind::Set => "hashset",
+            AllocationKind::Struct => "struct",
+            AllocationKind::Tuple => "tuple",
+            AllocationKind::Closure => "closure",
+            AllocationKind::Enum => "enum",
+            AllocationKind::Other => "other",
+        }
+    }
+}
+
+/// Allocation count and estimated bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocationStats {
+    pub count: u64,
+    pub bytes: u64,
+}
+
+impl AllocationStats {
+    fn add(&mut self, bytes: usize) {
+        self.count += 1;
+        self.bytes += bytes as u64;
+    }
+}
+
+/// Allocations made by one instruction
+#[derive(Debug, Clone)]
+pub struct AllocationSite {
+    /// Instruction offset
+    pub ip: usize,
+    /// Function the instruction belongs to
+    pub function: String,
+    /// Source span of the instruction, when the bytecode has one
+    pub span: Option<Span>,
+    pub stats: AllocationStats,
+    /// Count per kind
+    pub kinds: HashMap<AllocationKind, u64>,
+}
+
+/// Allocation tracking state; see the [module docs](self)
+#[derive(Debug, Clone)]
+pub struct AllocationProfiler {
+    by_kind: HashMap<AllocationKind, AllocationStats>,
+    sites: HashMap<usize, AllocationSite>,
+    peak_bytes: usize,
+    /// Instructions left before the next memory sample
+    until_sample: u64,
+    /// Instructions whose result is still to be looked at: frame depth,
+    /// opcode and offset, innermost frame last
+    pending: Vec<(usize, Opcode, usize)>,
+}
+
+impl AllocationProfiler {
+    pub fn new() -> Self {
+        Self {
+            by_kind: HashMap::new(),
+            sites: HashMap::new(),
+            peak_bytes: 0,
+            until_sample: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Whether `opcode` can leave a newly created heap value on the stack
+    pub fn can_allocate(opcode: Opcode) -> bool {
+        matches!(
+            opcode,
+            Opcode::Add
+                | Opcode::MakeClosure
+                | Opcode::Call
+                | Opcode::Array
+                | Opcode::HashMap
+                | Opcode::Slice
+                | Opcode::SliceFrom
+                | Opcode::SliceTo
+                | Opcode::SliceFull
+                | Opcode::Struct
+                | Opcode::Tuple
+                | Opcode::ToString
+                | Opcode::EnumVariant
+        )
+    }
+
+    /// Note that the instruction at `ip`, about to run in the frame at
+    /// `depth`, may leave a new heap value on the stack
+    pub fn expect_result(&mut self, opcode: Opcode, ip: usize, depth: usize) {
+        self.pending.push((depth, opcode, ip));
+    }
+
+    /// The instruction whose result the frame at `depth` has produced, if
+    /// one is expected
+    ///
+    /// Called before each instruction. Expected results of frames that have
+    /// returned since are dropped; those of callers, still waiting on a
+    /// builtin that called back into the program, are kept.
+    pub fn take_result(&mut self, depth: usize) -> Option<(Opcode, usize)> {
+        while let Some(&(pending_depth, opcode, ip)) = self.pending.last() {
+            if pending_depth < depth {
+                return None;
+            }
+            self.pending.pop();
+            if pending_depth == depth {
+                return Some((opcode, ip));
+            }
+        }
+        None
+    }
+
+    /// Record `value`, left on the stack by the instruction at `ip`, if it
+    /// is a heap value nothing else references
+    pub fn record_result(
+        &mut self,
+        opcode: Opcode,
+        value: &Value,
+        ip: usize,
+        function: &str,
+        span: impl FnOnce() -> Option<Span>,
+    ) {
+        let Some((kind, bytes)) = new_allocation(value) else {
+            return;
+        };
+        // Structs are maps built by their own opcode
+        let kind = match (opcode, kind) {
+            (Opcode::Struct, AllocationKind::Map) => AllocationKind::Struct,
+            _ => kind,
+        };
+        self.by_kind.entry(kind).or_default().add(bytes);
+        let site = self.sites.entry(ip).or_insert_with(|| AllocationSite {
+            ip,
+            function: function.to_string(),
+            span: span(),
+            stats: AllocationStats::default(),
+            kinds: HashMap::new(),
+        });
+        site.stats.add(bytes);
+        *site.kinds.entry(kind).or_default() += 1;
+    }
+
+    /// Whether the memory reachable from the roots is due for a sample;
+    /// counts down one instruction
+    pub fn sample_due(&mut self) -> bool {
+        if self.until_sample == 0 {
+            return true;
+        }
+        self.until_sample -= 1;
+        false
+    }
+
+    /// Measure the memory reachable from `roots` and keep the peak
+    ///
+    /// The next sample waits for more instructions the larger the heap, so
+    /// walking it costs a bounded share of the run.
+    pub fn sample<'a>(&mut self, roots: impl IntoIterator<Item = &'a Value>) {
+        let (bytes, visited) = reachable_bytes(roots);
+        self.peak_bytes = self.peak_bytes.max(bytes);
+        self.until_sample = MIN_SAMPLE_INTERVAL.max(visited as u64 * 16);
+    }
+
+    /// Largest reachable memory seen by a sample, in bytes
+    pub fn peak_bytes(&self) -> usize {
+        self.peak_bytes
+    }
+
+    /// Totals over every kind
+    pub fn total(&self) -> AllocationStats {
+        self.by_kind
+            .values()
+            .fold(AllocationStats::default(), |mut total, stats| {
+                total.count += stats.count;
+                total.bytes += stats.bytes;
+                total
+            })
+    }
+
+    /// Count and bytes of one kind
+    pub fn stats(&self, kind: AllocationKind) -> AllocationStats {
+        self.by_kind.get(&kind).copied().unwrap_or_default()
+    }
+
+    /// Per-kind totals, most bytes first
+    pub fn by_kind(&self) -> Vec<(AllocationKind, AllocationStats)> {
+        let mut kinds: Vec<_> = self.by_kind.iter().map(|(k, s)| (*k, *s)).collect();
+        kinds.sort_by(|a, b| b.1.bytes.cmp(&a.1.bytes).then(a.0.cmp(&b.0)));
+        kinds
+    }
+
+    /// Allocation sites, most allocations first
+    pub fn sites(&self) -> Vec<&AllocationSite> {
+        let mut sites: Vec<_> = self.sites.values().collect();
+        sites.sort_by(|a, b| {
+            b.stats
+                .count
+                .cmp(&a.stats.count)
+                .then(b.stats.bytes.cmp(&a.stats.bytes))
+                .then(a.ip.cmp(&b.ip))
+        });
+        sites
+    }
+
+    /// Snapshot for a [`ProfileReport`](super::ProfileReport), keeping the
+    /// `max_sites` busiest sites
+    pub fn report(&self, max_sites: usize) -> AllocationReport {
+        AllocationReport {
+            total: self.total(),
+            peak_bytes: self.peak_bytes,
+            by_kind: self.by_kind(),
+            sites: self
+                .sites()
+                .into_iter()
+                .take(max_sites)
+                .map(|site| {
+                    let location = site.span.map(|span| {
+                        let frame =
+                            crate::stack_trace::stack_frame_from_span(&site.function, span, None);
+                        format!("{}:{}:{}", frame.file, frame.line, frame.column)
+                    });
+                    SiteSummary {
+                        function: site.function.clone(),
+                        location,
+                        ip: site.ip,
+                        stats: site.stats,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Default for AllocationProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One row of [`AllocationReport::sites`]
+#[derive(Debug, Clone)]
+pub struct SiteSummary {
+    pub function: String,
+    /// `file:line:column`, when the instruction has a span
+    pub location: Option<String>,
+    pub ip: usize,
+    pub stats: AllocationStats,
+}
+
+/// Allocation section of a profile report
+#[derive(Debug, Clone)]
+pub struct AllocationReport {
+    pub total: AllocationStats,
+    /// Largest sampled memory reachable from the VM roots, in bytes
+    pub peak_bytes: usize,
+    /// Per-kind totals, most bytes first
+    pub by_kind: Vec<(AllocationKind, AllocationStats)>,
+    /// Busiest allocation sites first
+    pub sites: Vec<SiteSummary>,
+}
+
+impl AllocationReport {
+    /// Tables for [`ProfileReport::format_detailed`](super::ProfileReport::format_detailed)
+    pub fn format(&self) -> String {
+        let mut out = String::new();
+        out.push_str("[ Allocations ]\n");
+        out.push_str(&format!("  Total allocations:     {}\n", self.total.count));
+        out.push_str(&format!(
+            "  Allocated (estimated): {}\n",
+            format_bytes(self.total.bytes)
+        ));
+        out.push_str(&format!(
+            "  Peak reachable memory: {}\n\n",
+            format_bytes(self.peak_bytes as u64)
+        ));
+        if self.by_kind.is_empty() {
+            out.push_str("  No allocations recorded.\n\n");
+            return out;
+        }
+
+        out.push_str(&format!(
+            "  {:<12} {:>12} {:>12}\n",
+            "Kind", "Count", "Bytes"
+        ));
+        out.push_str("  ");
+        out.push_str(&"-".repeat(38));
+        out.push('\n');
+        for (kind, stats) in &self.by_kind {
+            out.push_str(&format!(
+                "  {:<12} {:>12} {:>12}\n",
+                kind.label(),
+                stats.count,
+                format_bytes(stats.bytes)
+            ));
+        }
+        out.push('\n');
+
+        out.push_str("[ Allocation Sites ]\n");
+        out.push_str(&format!(
+            "  {:<24} {:<28} {:>10} {:>12}\n",
+            "Function", "Location", "Count", "Bytes"
+        ));
+        out.push_str("  ");
+        out.push_str(&"-".repeat(77));
+        out.push('\n');
+        for site in &self.sites {
+            let location = site
+                .location
+                .clone()
+                .unwrap_or_else(|| format!("ip {}", site.ip));
+            out.push_str(&format!(
+                "  {:<24} {:<28} {:>10} {:>12}\n",
+                site.function,
+                location,
+                site.stats.count,
+                format_bytes(site.stats.bytes)
+            ));
+        }
+        out.push('\n');
+        out
+    }
+}
+
+/// `1536` → `1.5 KiB`
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Kind and estimated size of `value` if it is a heap value no other value
+/// references
+fn new_allocation(value: &Value) -> Option<(AllocationKind, usize)> {
+    let kind = match value {
+        Value::String(s) if Arc::strong_count(s) == 1 => AllocationKind::String,
+        Value::Array(arr) if arr.is_exclusively_owned() => AllocationKind::Array,
+        Value::Map(map) if map.is_exclusively_owned() => AllocationKind::Map,
+        Value::Set(set) if set.is_exclusively_owned() => AllocationKind::Set,
+        Value::Tuple(items) if Arc::strong_count(items) == 1 => AllocationKind::Tuple,
+        Value::Closure(closure) if Arc::strong_count(&closure.upvalues) == 1 => {
+            AllocationKind::Closure
+        }
+        Value::EnumValue { .. } => AllocationKind::Enum,
+        Value::Queue(queue) if queue.is_exclusively_owned() => AllocationKind::Other,
+        Value::Stack(stack) if stack.is_exclusively_owned() => AllocationKind::Other,
+        Value::Bytes(bytes) if Arc::strong_count(bytes) == 1 => AllocationKind::Other,
+        _ => return None,
+    };
+    Some((kind, payload_bytes(value)))
+}
+
+/// Estimated heap payload of `value` alone, not the values it holds
+fn payload_bytes(value: &Value) -> usize {
+    let slot = size_of::<Value>();
+    let key = size_of::<crate::stdlib::collections::hash::HashKey>();
+    match value {
+        Value::String(s) => size_of::<String>() + s.len(),
+        Value::Array(arr) => size_of::<Vec<Value>>() + arr.len() * slot,
+        Value::Map(map) => {
+            size_of::<crate::stdlib::collections::hashmap::AtlasHashMap>()
+                + map.len() * (key + slot)
+        }
+        Value::Set(set) => {
+            size_of::<crate::stdlib::collections::hashset::AtlasHashSet>() + set.inner().len() * key
+        }
+        Value::Tuple(items) => size_of::<Vec<Value>>() + items.len() * slot,
+        Value::Closure(closure) => size_of::<Vec<Value>>() + closure.upvalues.len() * slot,
+        Value::EnumValue {
+            enum_name,
+            variant_name,
+            data,
+        } => enum_name.len() + variant_name.len() + data.len() * slot,
+        Value::Queue(queue) => size_of::<Vec<Value>>() + queue.inner().len() * slot,
+        Value::Stack(stack) => size_of::<Vec<Value>>() + stack.inner().len() * slot,
+        Value::Bytes(bytes) => size_of::<Vec<u8>>() + bytes.len(),
+        Value::Option(Some(_)) | Value::Result(_) => slot,
+        _ => 0,
+    }
+}
+
+/// Estimated bytes of the heap values reachable from `roots`, each shared
+/// value counted once, and the number of values visited
+fn reachable_bytes<'a>(roots: impl IntoIterator<Item = &'a Value>) -> (usize, usize) {
+    let mut seen: HashSet<usize> = HashSet::new();
+    let mut pending: Vec<Value> = roots.into_iter().cloned().collect();
+    let mut bytes = 0;
+    let mut visited = 0;
+    while let Some(value) = pending.pop() {
+        visited += 1;
+        // Values behind one `Arc` are one allocation however often they appear
+        let address = match &value {
+            Value::String(s) => Some(Arc::as_ptr(s) as usize),
+            Value::Array(arr) => Some(Arc::as_ptr(arr.arc()) as usize),
+            Value::Map(map) => Some(Arc::as_ptr(map.arc()) as usize),
+            Value::Tuple(items) => Some(Arc::as_ptr(items) as usize),
+            Value::Closure(closure) => Some(Arc::as_ptr(&closure.upvalues) as usize),
+            Value::Bytes(b) => Some(Arc::as_ptr(b) as usize),
+            Value::SharedValue(cell) => Some(cell.as_ptr() as usize),
+            _ => None,
+        };
+        if address.is_some_and(|address| !seen.insert(address)) {
+            continue;
+        }
+        bytes += payload_bytes(&value);
+        match &value {
+            Value::Array(arr) => pending.extend(arr.iter().cloned()),
+            Value::Tuple(items) => pending.extend(items.iter().cloned()),
+            Value::Closure(closure) => pending.extend(closure.upvalues.iter().cloned()),
+            Value::EnumValue { data, .. } => pending.extend(data.iter().cloned()),
+            Value::Option(Some(inner)) => pending.push((**inner).clone()),
+            Value::Result(Ok(inner)) | Value::Result(Err(inner)) => pending.push((**inner).clone()),
+            Value::Map(map) => pending.extend(map.values()),
+            Value::Queue(queue) => pending.extend(queue.inner().to_vec()),
+            Value::Stack(stack) => pending.extend(stack.inner().to_vec()),
+            Value::SharedValue(cell) => {
+                bytes += size_of::<Value>();
+                pending.push(cell.with(|inner| (**inner).clone()));
+            }
+            _ => {}
+        }
+    }
+    (bytes, visited)
+}
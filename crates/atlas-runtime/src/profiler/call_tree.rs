@@ -0,0 +1,292 @@
+//! Call-tree profiling
+//!
+//! Builds a tree of call paths while the VM runs: every distinct chain of
+//! calls (`<main>` → `render` → `layout`) is one node, carrying its call
+//! count, the instructions executed in the function itself, and its
+//! inclusive wall-clock time. Exclusive (self) figures are derived from a
+//! node's children. The tree can be printed or exported as folded stacks,
+//! the one-line-per-stack format read by `flamegraph.pl`, inferno and
+//! speedscope.
+
+use std::time::{Duration, Instant};
+
+/// Index of a node in a [`CallTree`]
+pub type CallNodeId = usize;
+
+/// One call path: a function reached through the functions above it
+#[derive(Debug, Clone)]
+pub struct CallNode {
+    /// Function name
+    pub name: String,
+    /// Caller's node (`None` for the root)
+    pub parent: Option<CallNodeId>,
+    /// Callee nodes, in first-call order
+    pub children: Vec<CallNodeId>,
+    /// Calls made along this path
+    pub calls: u64,
+    /// Instructions executed in this function itself, not its callees
+    pub self_instructions: u64,
+    /// Wall-clock time of the calls, callees included
+    pub inclusive_time: Duration,
+}
+
+/// What a folded stack line is weighted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StackWeight {
+    /// Self time in microseconds
+    #[default]
+    Time,
+    /// Instructions executed in the function itself (deterministic)
+    Instructions,
+}
+
+/// Hierarchical call profile; see the [module docs](self)
+#[derive(Debug, Clone)]
+pub struct CallTree {
+    /// `nodes[0]` is a synthetic root above the outermost frames
+    nodes: Vec<CallNode>,
+    /// Open calls, innermost last, with the time each was entered
+    open: Vec<(CallNodeId, Instant)>,
+}
+
+impl CallTree {
+    /// Create an empty tree
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![CallNode {
+                name: String::new(),
+                parent: None,
+                children: Vec::new(),
+                calls: 0,
+                self_instructions: 0,
+                inclusive_time: Duration::ZERO,
+            }],
+            open: Vec::new(),
+        }
+    }
+
+    /// Enter a call to `name` from the innermost open call
+    pub fn enter(&mut self, name: &str, now: Instant) {
+        let parent = self.current();
+        let existing = self.nodes[parent]
+            .children
+            .iter()
+            .copied()
+            .find(|&child| self.nodes[child].name == name);
+        let id = match existing {
+            Some(id) => id,
+            None => {
+                let id = self.nodes.len();
+                self.nodes.push(CallNode {
+                    name: name.to_string(),
+                    parent: Some(parent),
+                    children: Vec::new(),
+                    calls: 0,
+                    self_instructions: 0,
+                    inclusive_time: Duration::ZERO,
+                });
+                self.nodes[parent].children.push(id);
+                id
+            }
+        };
+        self.nodes[id].calls += 1;
+        self.open.push((id, now));
+    }
+
+    /// Leave the innermost open call
+    pub fn exit(&mut self, now: Instant) {
+        if let Some((id, entered)) = self.open.pop() {
+            self.nodes[id].inclusive_time += now.saturating_duration_since(entered);
+        }
+    }
+
+    /// Leave every open call
+    pub fn exit_all(&mut self, now: Instant) {
+        while !self.open.is_empty() {
+            self.exit(now);
+        }
+    }
+
+    /// Number of open calls
+    pub fn depth(&self) -> usize {
+        self.open.len()
+    }
+
+    /// Node of the innermost open call (the root when none is open)
+    pub fn current(&self) -> CallNodeId {
+        self.open.last().map(|&(id, _)| id).unwrap_or(0)
+    }
+
+    /// Attribute one instruction to the innermost open call
+    pub fn record_instruction(&mut self) {
+        let id = self.current();
+        self.nodes[id].self_instructions += 1;
+    }
+
+    /// Discard everything recorded
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    // --- Queries ---
+
+    /// The synthetic root; its children are the outermost calls
+    pub fn root(&self) -> CallNodeId {
+        0
+    }
+
+    /// A node by id
+    pub fn node(&self, id: CallNodeId) -> &CallNode {
+        &self.nodes[id]
+    }
+
+    /// Child of `id` named `name`
+    pub fn child(&self, id: CallNodeId, name: &str) -> Option<CallNodeId> {
+        self.nodes[id]
+            .children
+            .iter()
+            .copied()
+            .find(|&child| self.nodes[child].name == name)
+    }
+
+    /// Node reached from the root through `path`, e.g. `["<main>", "fib"]`
+    pub fn find(&self, path: &[&str]) -> Option<CallNodeId> {
+        path.iter()
+            .try_fold(self.root(), |id, name| self.child(id, name))
+    }
+
+    /// Wall-clock time of `id`, callees included; for the root, the
+    /// outermost calls together
+    pub fn inclusive_time(&self, id: CallNodeId) -> Duration {
+        if id == self.root() {
+            self.children_time(id)
+        } else {
+            self.nodes[id].inclusive_time
+        }
+    }
+
+    /// Wall-clock time of `id` minus the time of its callees
+    pub fn exclusive_time(&self, id: CallNodeId) -> Duration {
+        self.inclusive_time(id)
+            .saturating_sub(self.children_time(id))
+    }
+
+    /// Instructions executed in `id` and everything it called
+    pub fn inclusive_instructions(&self, id: CallNodeId) -> u64 {
+        self.inclusive_instruction_counts()[id]
+    }
+
+    /// [`inclusive_instructions`](Self::inclusive_instructions) of every node
+    fn inclusive_instruction_counts(&self) -> Vec<u64> {
+        let mut counts: Vec<u64> = self.nodes.iter().map(|n| n.self_instructions).collect();
+        // A node is always created after its parent
+        for id in (1..self.nodes.len()).rev() {
+            if let Some(parent) = self.nodes[id].parent {
+                counts[parent] += counts[id];
+            }
+        }
+        counts
+    }
+
+    fn children_time(&self, id: CallNodeId) -> Duration {
+        self.nodes[id]
+            .children
+            .iter()
+            .map(|&child| self.nodes[child].inclusive_time)
+            .sum()
+    }
+
+    /// Every node below the root in depth-first order, with its depth
+    /// (outermost calls at 0); `order` picks the order of siblings
+    fn walk(&self, order: impl Fn(&[CallNodeId]) -> Vec<CallNodeId>) -> Vec<(CallNodeId, usize)> {
+        let mut visited = Vec::with_capacity(self.nodes.len());
+        let mut pending: Vec<(CallNodeId, usize)> = order(&self.nodes[self.root()].children)
+            .into_iter()
+            .rev()
+            .map(|id| (id, 0))
+            .collect();
+        // Iterative: recursion may be as deep as the VM's call depth limit
+        while let Some((id, depth)) = pending.pop() {
+            visited.push((id, depth));
+            pending.extend(
+                order(&self.nodes[id].children)
+                    .into_iter()
+                    .rev()
+                    .map(|child| (child, depth + 1)),
+            );
+        }
+        visited
+    }
+
+    // --- Output ---
+
+    /// Folded stacks: one `outer;inner;leaf weight` line per call path
+    /// with a non-zero weight
+    pub fn folded_stacks(&self, weight: StackWeight) -> String {
+        let mut out = String::new();
+        let mut path: Vec<String> = Vec::new();
+        for (id, depth) in self.walk(|children| children.to_vec()) {
+            path.truncate(depth);
+            // `;` separates frames, so it cannot appear inside one
+            path.push(self.nodes[id].name.replace(';', ":"));
+            let value = match weight {
+                StackWeight::Time => self.exclusive_time(id).as_micros() as u64,
+                StackWeight::Instructions => self.nodes[id].self_instructions,
+            };
+            if value > 0 {
+                out.push_str(&path.join(";"));
+                out.push_str(&format!(" {}\n", value));
+            }
+        }
+        out
+    }
+
+    /// Indented call tree, hottest callees first, with inclusive and self
+    /// time, the share of total time, instructions (callees included) and
+    /// calls
+    pub fn format_tree(&self) -> String {
+        let total = self.inclusive_time(self.root());
+        let instructions = self.inclusive_instruction_counts();
+        let mut out = format!(
+            "  {:<40} {:>11} {:>7} {:>11} {:>12} {:>8}\n",
+            "Function", "Total", "Pct", "Self", "Instrs", "Calls"
+        );
+        out.push_str("  ");
+        out.push_str(&"-".repeat(94));
+        out.push('\n');
+
+        // Hottest first: by inclusive time, then instructions
+        let hottest_first = |children: &[CallNodeId]| {
+            let mut children = children.to_vec();
+            children.sort_by_key(|&child| {
+                std::cmp::Reverse((self.nodes[child].inclusive_time, instructions[child]))
+            });
+            children
+        };
+        for (id, depth) in self.walk(hottest_first) {
+            let node = &self.nodes[id];
+            let pct = if total.is_zero() {
+                0.0
+            } else {
+                node.inclusive_time.as_secs_f64() / total.as_secs_f64() * 100.0
+            };
+            let label = format!("{}{}", "  ".repeat(depth), node.name);
+            out.push_str(&format!(
+                "  {:<40} {:>9.3}ms {:>6.2}% {:>9.3}ms {:>12} {:>8}\n",
+                label,
+                node.inclusive_time.as_secs_f64() * 1000.0,
+                pct,
+                self.exclusive_time(id).as_secs_f64() * 1000.0,
+                instructions[id],
+                node.calls
+            ));
+        }
+        out
+    }
+}
+
+impl Default for CallTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
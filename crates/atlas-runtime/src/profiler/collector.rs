@@ -152,7 +152,7 @@ impl ProfileCollector {
             .iter()
             .filter_map(|(&byte, &count)| Opcode::try_from(byte).ok().map(|op| (op, count)))
             .collect();
-        pairs.sort_by(|a, b| b.1.cmp(&a.1));
+        pairs.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
         pairs.truncate(n);
         pairs
     }
@@ -164,7 +164,7 @@ impl ProfileCollector {
             .iter()
             .map(|(&ip, &c)| (ip, c))
             .collect();
-        pairs.sort_by(|a, b| b.1.cmp(&a.1));
+        pairs.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
         pairs.truncate(n);
         pairs
     }
@@ -92,7 +92,7 @@ impl HotspotDetector {
             })
             .collect();
 
-        hotspots.sort_by(|a, b| b.count.cmp(&a.count));
+        hotspots.sort_by_key(|h| std::cmp::Reverse(h.count));
         hotspots.truncate(self.max_hotspots);
         hotspots
     }
@@ -1,9 +1,10 @@
 //! VM profiler — comprehensive performance analysis
 //!
-//! Provides instruction counting, timing, hotspot detection, and formatted
+//! Provides instruction counting, timing, hotspot detection, a call tree
+//! with folded-stack export, optional allocation tracking, and formatted
 //! reports. The `Profiler` struct is the primary public interface; it wraps
-//! the lower-level [`ProfileCollector`], [`HotspotDetector`], and
-//! [`ProfileReport`] types.
+//! the lower-level [`ProfileCollector`], [`CallTree`],
+//! [`AllocationProfiler`], [`HotspotDetector`], and [`ProfileReport`] types.
 //!
 //! # Quick start
 //!
@@ -21,15 +22,23 @@
 //! println!("{}", report.format_detailed());
 //! ```
 
+pub mod allocations;
+pub mod call_tree;
 pub mod collector;
 pub mod hotspots;
 pub mod report;
 
+pub use allocations::{
+    AllocationKind, AllocationProfiler, AllocationReport, AllocationSite, AllocationStats,
+    SiteSummary,
+};
+pub use call_tree::{CallNode, CallNodeId, CallTree, StackWeight};
 pub use collector::ProfileCollector;
 pub use hotspots::{HotOpcode, Hotspot, HotspotDetector};
 pub use report::ProfileReport;
 
 use crate::bytecode::Opcode;
+use crate::vm::CallFrame;
 use std::collections::HashMap;
 use std::time::Instant;
 
@@ -44,6 +53,10 @@ pub struct Profiler {
     enabled: bool,
     /// Data collector
     collector: ProfileCollector,
+    /// Call paths with their timings
+    call_tree: CallTree,
+    /// Allocation tracking, when that mode is on
+    allocations: Option<AllocationProfiler>,
     /// When timing started
     start_time: Option<Instant>,
     /// Captured elapsed duration in seconds
@@ -56,6 +69,8 @@ impl Profiler {
         Self {
             enabled: false,
             collector: ProfileCollector::new(),
+            call_tree: CallTree::new(),
+            allocations: None,
             start_time: None,
             elapsed_secs: None,
         }
@@ -66,6 +81,8 @@ impl Profiler {
         Self {
             enabled: true,
             collector: ProfileCollector::new(),
+            call_tree: CallTree::new(),
+            allocations: None,
             start_time: None,
             elapsed_secs: None,
         }
@@ -94,10 +111,13 @@ impl Profiler {
     }
 
     /// Stop the wall-clock timer and record elapsed time
+    ///
+    /// Calls still open in the call tree are closed here.
     pub fn stop_timing(&mut self) {
         if let Some(start) = self.start_time.take() {
             self.elapsed_secs = Some(start.elapsed().as_secs_f64());
         }
+        self.call_tree.exit_all(Instant::now());
     }
 
     /// Elapsed time in seconds (None if timing was not started/stopped)
@@ -108,6 +128,10 @@ impl Profiler {
     /// Reset all collected data and timing
     pub fn reset(&mut self) {
         self.collector.reset();
+        self.call_tree.reset();
+        if self.allocations.is_some() {
+            self.allocations = Some(AllocationProfiler::new());
+        }
         self.start_time = None;
         self.elapsed_secs = None;
     }
@@ -120,6 +144,7 @@ impl Profiler {
     pub fn record_instruction_at(&mut self, opcode: Opcode, ip: usize) {
         if self.enabled {
             self.collector.record_instruction(opcode, ip);
+            self.call_tree.record_instruction();
         }
     }
 
@@ -129,6 +154,7 @@ impl Profiler {
     pub fn record_instruction(&mut self, opcode: Opcode) {
         if self.enabled {
             self.collector.record_instruction_opcode(opcode);
+            self.call_tree.record_instruction();
         }
     }
 
@@ -155,6 +181,38 @@ impl Profiler {
         }
     }
 
+    /// Record the call whose frame was just pushed, the last of `frames`
+    pub fn record_frame_push(&mut self, frames: &[CallFrame]) {
+        if !self.enabled {
+            return;
+        }
+        let Some((callee, callers)) = frames.split_last() else {
+            return;
+        };
+        self.collector.record_function_call(&callee.function_name);
+        // A call that returned just before this one is still open in the tree
+        self.sync_frames(callers);
+        self.call_tree.enter(&callee.function_name, Instant::now());
+    }
+
+    /// Bring the call tree in line with the VM's frames, innermost last
+    ///
+    /// Called before each profiled instruction: calls that returned or were
+    /// unwound since the last one are closed, and frames the tree has not
+    /// seen (the main frame) are opened.
+    pub fn sync_frames(&mut self, frames: &[CallFrame]) {
+        if !self.enabled || self.call_tree.depth() == frames.len() {
+            return;
+        }
+        let now = Instant::now();
+        while self.call_tree.depth() > frames.len() {
+            self.call_tree.exit(now);
+        }
+        for frame in &frames[self.call_tree.depth()..] {
+            self.call_tree.enter(&frame.function_name, now);
+        }
+    }
+
     // --- Basic accessors (backward compat with vm/profiler.rs API) ---
 
     /// Total instructions executed
@@ -187,6 +245,28 @@ impl Profiler {
         &self.collector
     }
 
+    /// Call paths recorded so far, with calls, instructions and timings
+    pub fn call_tree(&self) -> &CallTree {
+        &self.call_tree
+    }
+
+    /// Also track allocations and peak memory, at some cost in speed
+    pub fn enable_allocation_tracking(&mut self) {
+        if self.allocations.is_none() {
+            self.allocations = Some(AllocationProfiler::new());
+        }
+    }
+
+    /// Allocations recorded so far, when allocation tracking is on
+    pub fn allocations(&self) -> Option<&AllocationProfiler> {
+        self.allocations.as_ref()
+    }
+
+    /// Mutable allocation state, for the VM's instruction hook
+    pub fn allocations_mut(&mut self) -> Option<&mut AllocationProfiler> {
+        self.allocations.as_mut()
+    }
+
     // --- Reports ---
 
     /// Basic text report (backward compat with vm/profiler.rs)
@@ -254,6 +334,10 @@ impl Profiler {
             function_calls: self.collector.function_calls(),
             top_opcodes,
             hotspots,
+            allocations: self
+                .allocations
+                .as_ref()
+                .map(|allocations| allocations.report(20)),
         }
     }
 
@@ -279,6 +363,8 @@ impl Clone for Profiler {
         Self {
             enabled: self.enabled,
             collector: self.collector.clone(),
+            call_tree: self.call_tree.clone(),
+            allocations: self.allocations.clone(),
             start_time: None, // Instant is not Clone-able in a meaningful way
             elapsed_secs: self.elapsed_secs,
         }
@@ -2,6 +2,7 @@
 //!
 //! Formats collected profiling data into human-readable performance reports.
 
+use crate::profiler::allocations::AllocationReport;
 use crate::profiler::hotspots::Hotspot;
 
 /// Comprehensive profile report
@@ -23,6 +24,8 @@ pub struct ProfileReport {
     pub top_opcodes: Vec<(String, u64, f64)>,
     /// Hotspot locations above the threshold
     pub hotspots: Vec<Hotspot>,
+    /// Allocations and peak memory (None unless allocation tracking was on)
+    pub allocations: Option<AllocationReport>,
 }
 
 impl ProfileReport {
@@ -111,6 +114,11 @@ impl ProfileReport {
             out.push_str("[ Hotspots ]\n  No hotspots detected above threshold.\n\n");
         }
 
+        // --- Allocations ---
+        if let Some(allocations) = &self.allocations {
+            out.push_str(&allocations.format());
+        }
+
         out
     }
 
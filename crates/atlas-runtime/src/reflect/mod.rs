@@ -181,6 +181,7 @@ pub fn get_value_type_info(value: &Value) -> TypeInfo {
             }
         }
 
+        #[cfg(feature = "regex")]
         Value::Regex(_) => {
             // Regex type
             TypeInfo {
@@ -208,6 +209,17 @@ pub fn get_value_type_info(value: &Value) -> TypeInfo {
                 alias_target: None,
             }
         }
+        Value::Bytes(_) => TypeInfo {
+            name: "Bytes".to_string(),
+            kind: TypeKind::Generic,
+            fields: vec![],
+            parameters: vec![],
+            return_type: None,
+            element_type: None,
+            type_args: vec![],
+            alias_target: None,
+        },
+        #[cfg(feature = "http")]
         Value::HttpRequest(_) => TypeInfo {
             name: "HttpRequest".to_string(),
             kind: TypeKind::Generic,
@@ -218,6 +230,7 @@ pub fn get_value_type_info(value: &Value) -> TypeInfo {
             type_args: vec![],
             alias_target: None,
         },
+        #[cfg(feature = "http")]
         Value::HttpResponse(_) => TypeInfo {
             name: "HttpResponse".to_string(),
             kind: TypeKind::Generic,
@@ -248,6 +261,36 @@ pub fn get_value_type_info(value: &Value) -> TypeInfo {
             type_args: vec![],
             alias_target: None,
         },
+        Value::KvStore(_) => TypeInfo {
+            name: "KvStore".to_string(),
+            kind: TypeKind::Generic,
+            fields: vec![],
+            parameters: vec![],
+            return_type: None,
+            element_type: None,
+            type_args: vec![],
+            alias_target: None,
+        },
+        Value::FileHandle(_) => TypeInfo {
+            name: "FileHandle".to_string(),
+            kind: TypeKind::Generic,
+            fields: vec![],
+            parameters: vec![],
+            return_type: None,
+            element_type: None,
+            type_args: vec![],
+            alias_target: None,
+        },
+        Value::ProgressBar(_) => TypeInfo {
+            name: "ProgressBar".to_string(),
+            kind: TypeKind::Generic,
+            fields: vec![],
+            parameters: vec![],
+            return_type: None,
+            element_type: None,
+            type_args: vec![],
+            alias_target: None,
+        },
         Value::Future(_) => TypeInfo {
             name: "Future".to_string(),
             kind: TypeKind::Generic,
@@ -298,6 +341,7 @@ pub fn get_value_type_info(value: &Value) -> TypeInfo {
             type_args: vec![],
             alias_target: None,
         },
+        #[cfg(feature = "fs")]
         Value::Watcher(_) => TypeInfo {
             name: "Watcher".to_string(),
             kind: TypeKind::Generic,
@@ -308,6 +352,16 @@ pub fn get_value_type_info(value: &Value) -> TypeInfo {
             type_args: vec![],
             alias_target: None,
         },
+        Value::Resource(_) => TypeInfo {
+            name: "Resource".to_string(),
+            kind: TypeKind::Generic,
+            fields: vec![],
+            parameters: vec![],
+            return_type: None,
+            element_type: None,
+            type_args: vec![],
+            alias_target: None,
+        },
         Value::SharedValue(_) => TypeInfo {
             name: "share".to_string(),
             kind: TypeKind::Generic,
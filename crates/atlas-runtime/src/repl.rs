@@ -346,7 +346,7 @@ impl ReplCore {
 
         // Copy globals back from VM
         for (name, value) in vm.get_globals() {
-            self.globals.insert(name.clone(), value.clone());
+            self.globals.insert(name, value);
         }
 
         match eval_result {
@@ -358,7 +358,8 @@ impl ReplCore {
                 bindings: self.collect_bindings(&declared_vars),
             },
             Err(e) => {
-                let diag = crate::runtime::runtime_error_to_diagnostic(e, Vec::new(), None);
+                let stack_trace = vm.take_error_stack_trace();
+                let diag = crate::runtime::runtime_error_to_diagnostic(e, stack_trace, None);
                 diagnostics.push(diag);
                 ReplResult {
                     value: None,
@@ -37,6 +37,22 @@ fn resolver_url_to_cache_subpath(url: &str, name: &str, tag: &str) -> std::path:
     std::path::PathBuf::from(format!("{}@{}", stripped, tag))
 }
 
+/// Directory installed packages live in: `ATLAS_CACHE_DIR` (compat), else
+/// `ATLAS_HOME/pkg`, else `~/atlas/pkg`
+pub fn package_cache_root() -> PathBuf {
+    if let Ok(dir) = std::env::var("ATLAS_CACHE_DIR") {
+        return PathBuf::from(dir);
+    }
+    let atlas_home = std::env::var("ATLAS_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("atlas")
+        });
+    atlas_home.join("pkg")
+}
+
 // ---------------------------------------------------------------------------
 // Minimal inline lockfile reader (avoids pulling in atlas-package + reqwest)
 // ---------------------------------------------------------------------------
@@ -48,7 +64,7 @@ struct MinLockfile {
     packages: Vec<MinLockedPackage>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 struct MinLockedSource {
     #[serde(default)]
     tag: Option<String>,
@@ -59,7 +75,7 @@ struct MinLockedSource {
     path: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 struct MinLockedPackage {
     name: String,
     version: String,
@@ -123,6 +139,11 @@ impl ModuleResolver {
         }
     }
 
+    /// Root directory that absolute imports resolve from
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
     /// Resolve a module path to an absolute file path
     ///
     /// # Arguments
@@ -149,13 +170,20 @@ impl ModuleResolver {
         } else if source.starts_with("./") || source.starts_with("../") {
             // Relative path: resolve from importing file's directory
             self.resolve_relative(source, importing_file)?
-        } else if !source.contains('/') {
-            // Bare package name: 'web', 'http-router', etc.
-            return self.resolve_package(source, span);
+        } else if let Some((package, subpath)) = split_package_specifier(source) {
+            // Bare package name ('web') or a module inside one ('web/router')
+            let resolved = match subpath {
+                None => self.resolve_package(package, importing_file, span)?,
+                Some(subpath) => {
+                    self.resolve_package_module(package, subpath, importing_file, span)?
+                }
+            };
+            self.path_cache.insert(cache_key, resolved.clone());
+            return Ok(resolved);
         } else {
             return Err(INVALID_MODULE_PATH.emit(span)
                 .arg("path", source)
-                .with_help("use './file' for same directory, '../file' for parent, or '/src/file' for absolute paths")
+                .with_help("use './file' for same directory, '../file' for parent, '/src/file' for absolute paths, or 'package/module' for a dependency")
                 .build());
         };
 
@@ -321,24 +349,110 @@ impl ModuleResolver {
     }
 
     /// Resolve a bare package specifier (e.g., `'web'`, `'http-router'`) using atlas.lock
-    fn resolve_package(&self, name: &str, span: Span) -> Result<PathBuf, Diagnostic> {
-        // 1. Find atlas.lock by walking up from project root
-        let lockfile_path = self.find_lockfile(span)?;
+    fn resolve_package(
+        &self,
+        name: &str,
+        importing_file: &Path,
+        span: Span,
+    ) -> Result<PathBuf, Diagnostic> {
+        let pkg_dir = self.package_dir(name, importing_file, span)?;
 
-        // 2. Parse the lockfile
-        let lockfile = MinLockfile::from_file(&lockfile_path).map_err(|e| {
-            MODULE_NOT_FOUND
+        // Try entry point candidates: lib.atlas, index.atlas, mod.atlas
+        let candidates = ["lib.atlas", "index.atlas", "mod.atlas"];
+        for candidate in &candidates {
+            let path = pkg_dir.join(candidate);
+            if path.exists() {
+                return Ok(path.canonicalize().unwrap_or(path));
+            }
+        }
+
+        // Package is in lockfile but not in cache
+        Err(MODULE_NOT_FOUND
+            .emit(span)
+            .arg("path", name)
+            .with_help(format!(
+                "package \"{}\" is in atlas.lock but not in cache — run: atlas install",
+                name
+            ))
+            .build())
+    }
+
+    /// Resolve a module inside a package (`'web/router'`): `<pkg>/router.atlas`,
+    /// falling back to the package's `src/` directory
+    fn resolve_package_module(
+        &self,
+        name: &str,
+        subpath: &str,
+        importing_file: &Path,
+        span: Span,
+    ) -> Result<PathBuf, Diagnostic> {
+        let pkg_dir = self.package_dir(name, importing_file, span)?;
+        let source = format!("{}/{}", name, subpath);
+
+        let candidates: Vec<PathBuf> = [pkg_dir.clone(), pkg_dir.join("src")]
+            .iter()
+            .flat_map(|base| self.build_candidates(&base.join(subpath), subpath))
+            .collect();
+        match candidates.iter().find(|path| path.exists()) {
+            Some(path) => path.canonicalize().map_err(|e| {
+                MODULE_NOT_FOUND
+                    .emit(span)
+                    .arg("path", path.display().to_string())
+                    .with_help(format!("OS error: {e}"))
+                    .build()
+            }),
+            None => Err(MODULE_NOT_FOUND
                 .emit(span)
-                .arg("path", name)
+                .arg("path", &source)
                 .with_help(format!(
-                    "failed to read atlas.lock: {} — run: atlas install",
-                    e
+                    "package \"{}\" has no module \"{}\"",
+                    name, subpath
                 ))
                 .build()
-        })?;
+                .with_label(format!("tried: {}", pkg_dir.join(subpath).display()))),
+        }
+    }
 
-        // 3. Look up package in lockfile
-        let locked_pkg = lockfile.get_package(name).ok_or_else(|| {
+    /// Directory holding the locked contents of package `name`.
+    ///
+    /// The lockfile nearest the importing file wins, so a dependency's own
+    /// imports resolve against the atlas.lock it was installed with; the
+    /// project's lockfile is the fallback.
+    fn package_dir(
+        &self,
+        name: &str,
+        importing_file: &Path,
+        span: Span,
+    ) -> Result<PathBuf, Diagnostic> {
+        // 1. Candidate lockfiles: nearest the importing file, then the project's
+        let mut lockfile_paths = Vec::new();
+        if let Some(nearest) = importing_file.parent().and_then(find_lockfile_from) {
+            lockfile_paths.push(nearest);
+        }
+        let project_lockfile = self.find_lockfile(span)?;
+        if !lockfile_paths.contains(&project_lockfile) {
+            lockfile_paths.push(project_lockfile);
+        }
+
+        // 2. First lockfile that locks the package
+        let mut found = None;
+        for lockfile_path in lockfile_paths {
+            let lockfile = MinLockfile::from_file(&lockfile_path).map_err(|e| {
+                MODULE_NOT_FOUND
+                    .emit(span)
+                    .arg("path", name)
+                    .with_help(format!(
+                        "failed to read atlas.lock: {} — run: atlas install",
+                        e
+                    ))
+                    .build()
+            })?;
+            if let Some(locked) = lockfile.get_package(name) {
+                found = Some((lockfile_path, locked.clone()));
+                break;
+            }
+        }
+        let (lockfile_path, locked_pkg) = found.ok_or_else(|| {
             MODULE_NOT_FOUND
                 .emit(span)
                 .arg("path", name)
@@ -349,23 +463,12 @@ impl ModuleResolver {
                 .build()
         })?;
 
-        // 4. Determine pkg dir root: ATLAS_CACHE_DIR (compat) → ATLAS_HOME/pkg → ~/atlas/pkg
-        let pkg_root = if let Ok(dir) = std::env::var("ATLAS_CACHE_DIR") {
-            std::path::PathBuf::from(dir)
-        } else {
-            let atlas_home = std::env::var("ATLAS_HOME")
-                .map(std::path::PathBuf::from)
-                .unwrap_or_else(|_| {
-                    dirs::home_dir()
-                        .unwrap_or_else(|| std::path::PathBuf::from("."))
-                        .join("atlas")
-                });
-            atlas_home.join("pkg")
-        };
+        // 3. Determine pkg dir root
+        let pkg_root = package_cache_root();
 
-        // 5. Build namespaced path: <pkg_root>/<host>/<org>/<name>@<tag>
+        // 4. Build namespaced path: <pkg_root>/<host>/<org>/<name>@<tag>
         //    Path deps resolve directly to their declared path.
-        let pkg_dir = if let Some(local) = locked_pkg.local_path() {
+        Ok(if let Some(local) = locked_pkg.local_path() {
             // Path dep: resolve relative to lockfile location
             if local.is_absolute() {
                 local
@@ -381,48 +484,49 @@ impl ModuleResolver {
                 Some(url) => pkg_root.join(resolver_url_to_cache_subpath(&url, name, &cache_key)),
                 None => pkg_root.join(name).join(&cache_key),
             }
-        };
-
-        // 6. Try entry point candidates: lib.atlas, index.atlas, mod.atlas
-        let candidates = ["lib.atlas", "index.atlas", "mod.atlas"];
-        for candidate in &candidates {
-            let path = pkg_dir.join(candidate);
-            if path.exists() {
-                return Ok(path);
-            }
-        }
-
-        // 7. Package is in lockfile but not in cache
-        Err(MODULE_NOT_FOUND
-            .emit(span)
-            .arg("path", name)
-            .with_help(format!(
-                "package \"{}\" is in atlas.lock but not in cache — run: atlas install",
-                name
-            ))
-            .build())
+        })
     }
 
     /// Walk up from self.root looking for atlas.lock
     fn find_lockfile(&self, span: Span) -> Result<std::path::PathBuf, Diagnostic> {
-        let mut current = self.root.clone();
-        loop {
-            let candidate = current.join("atlas.lock");
-            if candidate.exists() {
-                return Ok(candidate);
-            }
-            if !current.pop() {
-                break;
-            }
-        }
-        Err(MODULE_NOT_FOUND
-            .emit(span)
-            .arg("path", "atlas.lock")
-            .with_help("no atlas.lock found — run: atlas install")
-            .build())
+        find_lockfile_from(&self.root).ok_or_else(|| {
+            MODULE_NOT_FOUND
+                .emit(span)
+                .arg("path", "atlas.lock")
+                .with_help("no atlas.lock found — run: atlas install")
+                .build()
+        })
     }
 }
 
+/// Nearest atlas.lock in `dir` or one of its ancestors
+fn find_lockfile_from(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|ancestor| ancestor.join("atlas.lock"))
+        .find(|candidate| candidate.exists())
+}
+
+/// Split a bare specifier into package name and optional module subpath:
+/// `"web"` → `("web", None)`, `"web/router/http"` → `("web", Some("router/http"))`.
+/// Returns `None` for anything that is not a bare package specifier.
+fn split_package_specifier(source: &str) -> Option<(&str, Option<&str>)> {
+    let (package, subpath) = match source.split_once('/') {
+        Some((package, subpath)) => (package, Some(subpath)),
+        None => (source, None),
+    };
+    let valid_name = !package.is_empty()
+        && package
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    let valid_subpath = subpath.is_none_or(|sub| {
+        !sub.is_empty()
+            && sub
+                .split('/')
+                .all(|seg| !seg.is_empty() && seg != "." && seg != "..")
+    });
+    (valid_name && valid_subpath).then_some((package, subpath))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -442,7 +546,8 @@ mod tests {
     fn test_bare_specifier_no_lockfile() {
         let dir = TempDir::new().expect("tempdir");
         let resolver = make_resolver(dir.path());
-        let result = resolver.resolve_package("web", dummy_span());
+        let importing = dir.path().join("main.atlas");
+        let result = resolver.resolve_package("web", &importing, dummy_span());
         assert!(result.is_err());
         let err = result.expect_err("expected error");
         let msg = format!("{:?}", err);
@@ -490,4 +595,84 @@ mod tests {
         let resolved = result.expect("resolved path");
         assert!(resolved.ends_with("foo.atlas"));
     }
+
+    fn write_path_lock(dir: &std::path::Path, name: &str, path: &str) {
+        fs::write(
+            dir.join("atlas.lock"),
+            format!(
+                "version = 1\n\n[[packages]]\nname = \"{}\"\nversion = \"0.1.0\"\n\n[packages.source]\ntype = \"path\"\npath = \"{}\"\n",
+                name, path
+            ),
+        )
+        .expect("write atlas.lock");
+    }
+
+    /// `package/module` resolves inside the locked package, including its src/ dir
+    #[test]
+    fn test_package_subpath_import() {
+        let dir = TempDir::new().expect("tempdir");
+        let pkg = dir.path().join("vendor/web");
+        fs::create_dir_all(pkg.join("src/router")).expect("create package dirs");
+        fs::write(pkg.join("lib.atlas"), "// lib").expect("write lib");
+        fs::write(pkg.join("utils.atlas"), "// utils").expect("write utils");
+        fs::write(pkg.join("src/router/http.atlas"), "// http").expect("write http");
+        write_path_lock(dir.path(), "web", "vendor/web");
+
+        let mut resolver = make_resolver(dir.path());
+        let importing = dir.path().join("main.atlas");
+
+        let utils = resolver
+            .resolve_path("web/utils", &importing, dummy_span())
+            .expect("web/utils");
+        assert!(utils.ends_with("vendor/web/utils.atlas"), "{:?}", utils);
+        let http = resolver
+            .resolve_path("web/router/http", &importing, dummy_span())
+            .expect("web/router/http");
+        assert!(
+            http.ends_with("vendor/web/src/router/http.atlas"),
+            "{:?}",
+            http
+        );
+
+        let missing = resolver
+            .resolve_path("web/nope", &importing, dummy_span())
+            .expect_err("missing module");
+        assert!(
+            format!("{:?}", missing).contains("has no module"),
+            "{:?}",
+            missing
+        );
+        assert!(resolver
+            .resolve_path("web/../secret", &importing, dummy_span())
+            .is_err());
+    }
+
+    /// A dependency's own imports resolve against the atlas.lock shipped with it
+    #[test]
+    fn test_dependency_imports_use_nearest_lockfile() {
+        let dir = TempDir::new().expect("tempdir");
+        let web = dir.path().join("vendor/web");
+        let json = dir.path().join("vendor/json");
+        fs::create_dir_all(&web).expect("create web");
+        fs::create_dir_all(&json).expect("create json");
+        fs::write(web.join("lib.atlas"), "// web").expect("write web");
+        fs::write(json.join("lib.atlas"), "// json").expect("write json");
+        write_path_lock(dir.path(), "web", "vendor/web");
+        write_path_lock(&web, "json", "../json");
+
+        let mut resolver = make_resolver(dir.path());
+        let resolved = resolver
+            .resolve_path("json", &web.join("lib.atlas"), dummy_span())
+            .expect("transitive package");
+        assert!(
+            resolved.ends_with("vendor/json/lib.atlas"),
+            "{:?}",
+            resolved
+        );
+
+        // The project itself does not depend on json
+        assert!(resolver
+            .resolve_path("json", &dir.path().join("main.atlas"), dummy_span())
+            .is_err());
+    }
 }
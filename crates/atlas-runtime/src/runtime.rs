@@ -1,19 +1,27 @@
 //! Atlas runtime API for embedding
 
+use crate::api::config::{ExecutionLimits, RuntimeConfig};
+use crate::ast::{Item, Stmt, Visibility};
 use crate::binder::Binder;
+use crate::bytecode::BytecodeCache;
 use crate::compiler::Compiler;
+use crate::diagnostic::error_codes;
 use crate::diagnostic::{Diagnostic, StackTraceFrame};
 use crate::lexer::Lexer;
-use crate::module_loader::ModuleLoader;
+use crate::module_loader::{ModuleCache, ModuleLoader};
 use crate::parser::Parser;
-use crate::resolver::ModuleResolver;
+use crate::profile::LanguageProfile;
 use crate::security::SecurityContext;
 use crate::span::Span;
+use crate::symbol::{Symbol, SymbolKind, SymbolTable};
+use crate::timings::{Phase, PhaseTimings};
 use crate::typechecker::TypeChecker;
+use crate::types::Type;
 use crate::value::{RuntimeError, Value};
-use crate::vm::VM;
-use std::cell::RefCell;
+use crate::vm::{VmLimits, VM};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 
 /// Result type for runtime operations
 pub type RuntimeResult<T> = Result<T, Vec<Diagnostic>>;
@@ -48,11 +56,128 @@ fn emit_warnings_via_formatter(warnings: &[Diagnostic], source: &str, file: &str
     }
 }
 
+/// Limits on a single [`Atlas::eval_expr`] call
+///
+/// Expressions usually come from end users rather than the embedder, so by
+/// default they get one second, 10 MB of tracked allocations and
+/// [`VmLimits::strict`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExprLimits {
+    /// Longest the expression, or any builtin it calls, may run (None = unlimited)
+    pub max_time: Option<Duration>,
+    /// Maximum memory the expression may allocate, in bytes (None = unlimited)
+    pub max_memory_bytes: Option<usize>,
+    /// Limits on call depth and callbacks nested inside builtins
+    pub vm_limits: VmLimits,
+}
+
+impl ExprLimits {
+    /// No time or memory limit, and the recursion limits of trusted scripts
+    pub fn unlimited() -> Self {
+        Self {
+            max_time: None,
+            max_memory_bytes: None,
+            vm_limits: VmLimits::standard(),
+        }
+    }
+}
+
+impl Default for ExprLimits {
+    fn default() -> Self {
+        Self {
+            max_time: Some(Duration::from_secs(1)),
+            max_memory_bytes: Some(10_000_000), // 10MB
+            vm_limits: VmLimits::strict(),
+        }
+    }
+}
+
+/// How a program run through [`Atlas::run_file`] finished
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunOutcome {
+    /// The file declares no `fn main`; the value of its last expression
+    Script(Value),
+    /// `fn main` returned; `error` holds the payload of an `Err` return
+    Exited { code: i32, error: Option<Value> },
+}
+
+impl RunOutcome {
+    /// Outcome of a program whose final value is `value`: `main`'s return
+    /// value when `has_main`, else the script's last expression
+    pub fn new(value: Value, has_main: bool) -> Self {
+        if !has_main {
+            return RunOutcome::Script(value);
+        }
+        match value {
+            Value::Number(n) => RunOutcome::Exited {
+                code: n as i32,
+                error: None,
+            },
+            Value::Result(Err(error)) => RunOutcome::Exited {
+                code: 1,
+                error: Some(*error),
+            },
+            _ => RunOutcome::Exited {
+                code: 0,
+                error: None,
+            },
+        }
+    }
+}
+
+/// A file and everything it imports, checked and compiled into one program
+/// (see [`Atlas::compile_file`])
+#[derive(Debug, Clone)]
+pub struct CompiledProgram {
+    /// Bytecode of every module in dependency order, entry point last
+    pub bytecode: crate::bytecode::Bytecode,
+    /// Whether the entry module declares `fn main`
+    pub has_main: bool,
+}
+
+/// Whether the entry module (loaded last) declares a `fn main` entry point
+fn entry_has_main(modules: &[crate::module_loader::LoadedModule]) -> bool {
+    modules.last().is_some_and(|entry| {
+        entry.ast.items.iter().any(|item| {
+            matches!(item,
+                crate::ast::Item::Function(f) if f.name.name == "main" && f.params.len() <= 1
+            )
+        })
+    })
+}
+
+/// Cache key of `module`, or `None` when it is not cacheable: outside the
+/// cache's scope, unreadable, or importing a module that is not cacheable.
+/// `keys` holds the keys of the modules loaded before it.
+fn module_cache_key(
+    cache: &BytecodeCache,
+    module: &crate::module_loader::LoadedModule,
+    keys: &HashMap<std::path::PathBuf, String>,
+) -> Option<String> {
+    if !cache.covers(&module.path) {
+        return None;
+    }
+    let source = std::fs::read_to_string(&module.path).ok()?;
+    let dependency_keys = module
+        .resolved_imports
+        .values()
+        .map(|path| keys.get(path).map(String::as_str))
+        .collect::<Option<Vec<_>>>()?;
+    Some(BytecodeCache::key(&module.path, &source, &dependency_keys))
+}
+
 /// Atlas runtime instance
 ///
 /// Provides a high-level API for embedding Atlas in host applications.
 /// Uses Compiler + VM for execution (D-052: single execution path).
 ///
+/// # Concurrency
+///
+/// `Atlas` is `Send + Sync`: it can be created on one thread and used on
+/// another, or shared through an `Arc`. Evaluations on one instance run one
+/// at a time — a call made while another thread is evaluating waits for it
+/// to finish. For parallel evaluation, give each thread its own `Atlas`.
+///
 /// # Examples
 ///
 /// ```
@@ -61,12 +186,33 @@ fn emit_warnings_via_formatter(warnings: &[Diagnostic], source: &str, file: &str
 /// let runtime = Atlas::new();
 /// let result = runtime.eval("1 + 2");
 /// ```
+///
+/// ```
+/// use atlas_runtime::{Atlas, Value};
+/// use std::sync::Arc;
+///
+/// let runtime = Arc::new(Atlas::new());
+///
+/// let shared = Arc::clone(&runtime);
+/// let result = std::thread::spawn(move || shared.eval("40 + 2").unwrap())
+///     .join()
+///     .unwrap();
+/// assert_eq!(result, Value::Number(42.0));
+/// ```
 pub struct Atlas {
     /// VM for executing bytecode (using interior mutability)
     /// None until first eval() call initializes it
-    vm: RefCell<Option<VM>>,
+    vm: Mutex<Option<VM>>,
     /// Security context for permission checks
-    security: SecurityContext,
+    security: Arc<SecurityContext>,
+    /// Compiled modules reused across runs (see [`Atlas::with_bytecode_cache`])
+    bytecode_cache: Option<BytecodeCache>,
+    /// Parsed and compiled modules kept in memory (see [`Atlas::with_module_cache`])
+    module_cache: Option<Arc<ModuleCache>>,
+    /// Phase timings of the most recent run (see [`Atlas::timings`])
+    timings: Mutex<PhaseTimings>,
+    /// Limits on each [`Atlas::eval_expr`] call
+    expr_limits: ExprLimits,
 }
 
 impl Atlas {
@@ -81,8 +227,12 @@ impl Atlas {
     /// ```
     pub fn new() -> Self {
         Self {
-            vm: RefCell::new(None),
-            security: SecurityContext::new(),
+            vm: Mutex::new(None),
+            security: Arc::new(SecurityContext::new()),
+            bytecode_cache: None,
+            module_cache: None,
+            timings: Mutex::new(PhaseTimings::new()),
+            expr_limits: ExprLimits::default(),
         }
     }
 
@@ -98,11 +248,105 @@ impl Atlas {
     /// ```
     pub fn new_with_security(security: SecurityContext) -> Self {
         Self {
-            vm: RefCell::new(None),
-            security,
+            vm: Mutex::new(None),
+            security: Arc::new(security),
+            bytecode_cache: None,
+            module_cache: None,
+            timings: Mutex::new(PhaseTimings::new()),
+            expr_limits: ExprLimits::default(),
         }
     }
 
+    /// Reuse compiled modules from `cache` when running or compiling files
+    ///
+    /// Modules the cache covers are compiled once and loaded from disk on
+    /// later runs, for as long as neither they nor anything they import
+    /// changes. Every module is still type-checked.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use atlas_runtime::{Atlas, BytecodeCache, SecurityContext};
+    ///
+    /// let runtime = Atlas::new_with_security(SecurityContext::allow_all())
+    ///     .with_bytecode_cache(BytecodeCache::for_packages());
+    /// let result = runtime.eval_file("main.atlas");
+    /// ```
+    pub fn with_bytecode_cache(mut self, cache: BytecodeCache) -> Self {
+        self.bytecode_cache = Some(cache);
+        self
+    }
+
+    /// The cache set by [`with_bytecode_cache`](Self::with_bytecode_cache)
+    pub fn bytecode_cache(&self) -> Option<&BytecodeCache> {
+        self.bytecode_cache.as_ref()
+    }
+
+    /// Share parsed and compiled modules with every runtime using `cache`
+    ///
+    /// Files that have not changed since another runtime loaded them are not
+    /// parsed again, and their bytecode is reused for as long as nothing
+    /// they import changes either. Every module is still type-checked.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use atlas_runtime::{Atlas, ModuleCache, SecurityContext};
+    /// use std::sync::Arc;
+    ///
+    /// let cache = Arc::new(ModuleCache::new());
+    /// for _ in 0..2 {
+    ///     let runtime = Atlas::new_with_security(SecurityContext::allow_all())
+    ///         .with_module_cache(Arc::clone(&cache));
+    ///     runtime.compile_file("main.atlas").unwrap();
+    /// }
+    /// assert!(cache.hits() > 0);
+    /// ```
+    pub fn with_module_cache(mut self, cache: Arc<ModuleCache>) -> Self {
+        self.module_cache = Some(cache);
+        self
+    }
+
+    /// Limit the time, memory and recursion each [`eval_expr`](Self::eval_expr)
+    /// call may use
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atlas_runtime::{Atlas, ExprLimits};
+    /// use std::time::Duration;
+    ///
+    /// let runtime = Atlas::new().with_expr_limits(ExprLimits {
+    ///     max_time: Some(Duration::from_millis(100)),
+    ///     ..ExprLimits::default()
+    /// });
+    /// ```
+    pub fn with_expr_limits(mut self, limits: ExprLimits) -> Self {
+        self.expr_limits = limits;
+        self
+    }
+
+    /// Time each pipeline phase took during the most recent `eval`, `run_file`
+    /// or `compile_file`, summed over all modules
+    ///
+    /// Phases a run never reached (because an earlier one failed) read zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atlas_runtime::timings::Phase;
+    /// use atlas_runtime::Atlas;
+    ///
+    /// let runtime = Atlas::new();
+    /// runtime.eval("1 + 2").unwrap();
+    /// let timings = runtime.timings();
+    /// assert_eq!(timings.modules, 1);
+    /// assert!(timings.total() >= timings.get(Phase::Execute));
+    /// ```
+    pub fn timings(&self) -> PhaseTimings {
+        lock(&self.timings).clone()
+    }
+
     /// Evaluate Atlas source code
     ///
     /// Returns the result of evaluating the source code, or diagnostics if there are errors.
@@ -128,6 +372,134 @@ impl Atlas {
         self.eval_source(source, "<input>")
     }
 
+    /// Evaluate a single expression against host-provided variables
+    ///
+    /// For user-supplied formulas: `source` must be one expression, checked
+    /// under [`LanguageProfile::pure_expressions`] — no declarations, loops,
+    /// closures or I/O builtins. Each entry in `vars` is readable by name.
+    /// The expression runs on a fresh VM with deny-all security, so nothing
+    /// it does outlives the call, whatever security this runtime was given,
+    /// and under [`ExprLimits`] (see [`with_expr_limits`](Self::with_expr_limits)).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use atlas_runtime::{Atlas, Value};
+    /// use std::collections::HashMap;
+    ///
+    /// let runtime = Atlas::new();
+    /// let vars = HashMap::from([
+    ///     ("price".to_string(), Value::Number(20.0)),
+    ///     ("qty".to_string(), Value::Number(3.0)),
+    /// ]);
+    /// let total = runtime.eval_expr("math.max(price * qty - 5, 0)", &vars).unwrap();
+    /// assert_eq!(total, Value::Number(55.0));
+    ///
+    /// assert!(runtime.eval_expr("let x = 1;", &vars).is_err());
+    /// assert!(runtime.eval_expr(r#"file.read("secrets.txt")"#, &vars).is_err());
+    /// ```
+    pub fn eval_expr(&self, source: &str, vars: &HashMap<String, Value>) -> RuntimeResult<Value> {
+        let source = source.trim();
+        let source_with_semi = if source.ends_with(';') || source.ends_with('}') {
+            source.to_string()
+        } else {
+            format!("{};", source)
+        };
+
+        let mut lexer = Lexer::new(&source_with_semi).with_file("<expr>");
+        let (tokens, lex_diagnostics) = lexer.tokenize();
+        if !lex_diagnostics.is_empty() {
+            return Err(lex_diagnostics);
+        }
+
+        let mut parser = Parser::new(tokens);
+        let (ast, parse_diagnostics) = parser.parse();
+        let parse_errors: Vec<_> = parse_diagnostics
+            .into_iter()
+            .filter(|d| d.is_error())
+            .collect();
+        if !parse_errors.is_empty() {
+            return Err(parse_errors);
+        }
+
+        // Host variables are immutable globals typed from their current value
+        let mut symbol_table = SymbolTable::new();
+        for (name, value) in vars {
+            let _ = symbol_table.define(Symbol {
+                name: name.clone(),
+                ty: value_type(value),
+                mutable: false,
+                kind: SymbolKind::Variable,
+                span: Span::dummy(),
+                exported: false,
+                visibility: Visibility::Private,
+            });
+        }
+
+        let mut binder = Binder::with_symbol_table(symbol_table);
+        binder.set_language_profile(LanguageProfile::pure_expressions());
+        let (mut symbol_table, bind_diagnostics) = binder.bind(&ast);
+        if bind_diagnostics.iter().any(|d| d.is_error()) {
+            return Err(bind_diagnostics);
+        }
+
+        // The profile rejects declarations but not statements such as
+        // `return`, nor a sequence of several expressions
+        if !matches!(ast.items.as_slice(), [Item::Statement(Stmt::Expr(_))]) {
+            let stray = ast
+                .items
+                .iter()
+                .enumerate()
+                .find_map(|(i, item)| match item {
+                    Item::Statement(Stmt::Expr(_)) if i == 0 => None,
+                    Item::Statement(stmt) => Some(stmt.span()),
+                    _ => None,
+                })
+                .unwrap_or_else(Span::dummy);
+            return Err(vec![error_codes::NOT_IN_LANGUAGE_PROFILE
+                .emit(stray)
+                .arg("what", "statements")
+                .build()
+                .with_label("expected a single expression")]);
+        }
+
+        let mut type_checker = TypeChecker::new(&mut symbol_table);
+        let type_diagnostics = type_checker.check(&ast);
+        if type_diagnostics.iter().any(|d| d.is_error()) {
+            return Err(type_diagnostics);
+        }
+
+        let mut compiler = Compiler::new();
+        let bytecode = compiler.compile(&ast)?;
+
+        let mut vm = VM::new(bytecode);
+        for (name, value) in vars {
+            vm.set_global(name.clone(), value.clone());
+        }
+        vm.set_limits(self.expr_limits.vm_limits);
+        let mut limits = ExecutionLimits::from_config(&RuntimeConfig {
+            max_execution_time: self.expr_limits.max_time,
+            max_builtin_call_time: self.expr_limits.max_time,
+            max_memory_bytes: self.expr_limits.max_memory_bytes,
+            ..RuntimeConfig::new()
+        });
+        limits.start();
+        if limits.is_active() {
+            vm.set_execution_limits(Arc::new(limits));
+        }
+        match vm.run(&SecurityContext::new()) {
+            Ok(value) => Ok(value.unwrap_or(Value::Null)),
+            Err(runtime_error) => {
+                let stack_trace = vm.take_error_stack_trace();
+                Err(vec![runtime_error_to_diagnostic(
+                    runtime_error,
+                    stack_trace,
+                    None,
+                )])
+            }
+        }
+    }
+
     fn eval_source(&self, source: &str, file: &str) -> RuntimeResult<Value> {
         // For REPL-style usage, if the source doesn't end with a semicolon,
         // treat it as an expression statement by appending one
@@ -139,9 +511,13 @@ impl Atlas {
                 source.to_string()
             };
 
+        let mut timings = lock(&self.timings);
+        *timings = PhaseTimings::new();
+        timings.modules = 1;
+
         // Lex the source code
         let mut lexer = Lexer::new(&source_with_semi).with_file(file);
-        let (tokens, lex_diagnostics) = lexer.tokenize();
+        let (tokens, lex_diagnostics) = timings.time(Phase::Lex, || lexer.tokenize());
 
         if !lex_diagnostics.is_empty() {
             return Err(lex_diagnostics);
@@ -149,7 +525,7 @@ impl Atlas {
 
         // Parse tokens into AST
         let mut parser = Parser::new(tokens);
-        let (ast, parse_diagnostics) = parser.parse();
+        let (ast, parse_diagnostics) = timings.time(Phase::Parse, || parser.parse());
 
         let parse_errors: Vec<_> = parse_diagnostics
             .into_iter()
@@ -161,13 +537,13 @@ impl Atlas {
 
         // Bind symbols
         let mut binder = Binder::new();
-        let (mut symbol_table, bind_diagnostics) = binder.bind(&ast);
+        let (mut symbol_table, bind_diagnostics) = timings.time(Phase::Bind, || binder.bind(&ast));
         let bind_has_errors = bind_diagnostics.iter().any(|d| d.is_error());
 
         // Type check — always run even when bind produced errors, so all phase
         // diagnostics are collected together (H-193: stops at first failing phase).
         let mut type_checker = TypeChecker::new(&mut symbol_table);
-        let type_diagnostics = type_checker.check(&ast);
+        let type_diagnostics = timings.time(Phase::Typecheck, || type_checker.check(&ast));
         let type_has_errors = type_diagnostics.iter().any(|d| d.is_error());
 
         if bind_has_errors || type_has_errors {
@@ -187,10 +563,12 @@ impl Atlas {
 
         // Compile AST to bytecode (D-052: single execution path via Compiler+VM)
         let mut compiler = Compiler::new();
-        let bytecode = compiler.compile(&ast)?;
+        let bytecode = timings.time(Phase::Compile, || compiler.compile(&ast))?;
+        charge_optimizer(&mut timings, &compiler);
 
         // Execute on VM
-        let mut vm_ref = self.vm.borrow_mut();
+        let mut vm_ref = lock(&self.vm);
+        let execute_start = std::time::Instant::now();
 
         let result = if vm_ref.is_none() {
             // First eval: create VM with this bytecode
@@ -200,11 +578,11 @@ impl Atlas {
                 .map_err(|e| vec![runtime_error_to_diagnostic(e, vec![], None)])?;
             // Lazily initialise the worker pool on first VM creation so that
             // task.spawn() / task.sleep() work without manual setup.
-            // Guard: OnceLock inside init_worker_pool prevents double-init.
-            if crate::async_runtime::worker_pool().is_none() {
-                crate::async_runtime::init_worker_pool(0, &vm);
+            // ensure_worker_pool is race-free when runtimes start on several threads.
+            if crate::async_runtime::ensure_worker_pool(0, &vm) {
                 crate::async_runtime::init_blocking_pool(&vm);
             }
+            vm.set_security(Arc::clone(&self.security));
             let run_result = vm.run(&self.security);
             *vm_ref = Some(vm);
             run_result
@@ -217,6 +595,7 @@ impl Atlas {
                 .map_err(|e| vec![runtime_error_to_diagnostic(e, vec![], None)])?;
             vm.run(&self.security)
         };
+        timings.add(Phase::Execute, execute_start.elapsed());
 
         match result {
             Ok(value) => {
@@ -225,15 +604,21 @@ impl Atlas {
                 if let Some(vm) = vm_ref.as_mut() {
                     all_warnings.extend(vm.take_runtime_warnings());
                 }
+                let all_warnings = crate::diagnostic::suppression::apply_suppressions(
+                    &source_with_semi,
+                    all_warnings,
+                );
                 emit_warnings_via_formatter(&all_warnings, source_with_semi.as_str(), file);
                 Ok(value.unwrap_or(Value::Null))
             }
             Err(runtime_error) => {
-                // VM doesn't have stack_trace_frames like interpreter, so we create
-                // a minimal diagnostic. Stack trace support will be added in a future phase.
+                let stack_trace = vm_ref
+                    .as_mut()
+                    .map(|vm| vm.take_error_stack_trace())
+                    .unwrap_or_default();
                 Err(vec![runtime_error_to_diagnostic(
                     runtime_error,
-                    vec![],
+                    stack_trace,
                     None,
                 )])
             }
@@ -258,6 +643,120 @@ impl Atlas {
     /// let result = runtime.eval_file("program.atlas");
     /// ```
     pub fn eval_file(&self, path: &str) -> RuntimeResult<Value> {
+        self.execute_file(path).map(|(value, _)| value)
+    }
+
+    /// Run an Atlas program file through its entry point
+    ///
+    /// Like [`eval_file`](Self::eval_file), but distinguishes programs that
+    /// declare `fn main` from plain scripts. `main`'s return value becomes the
+    /// exit code: a `number` is used as-is, nothing or `Ok(_)` exits 0, and
+    /// `Err(e)` exits 1 carrying `e`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use atlas_runtime::{Atlas, RunOutcome};
+    ///
+    /// let runtime = Atlas::new();
+    /// if let Ok(RunOutcome::Exited { code, .. }) = runtime.run_file("main.atlas") {
+    ///     std::process::exit(code);
+    /// }
+    /// ```
+    pub fn run_file(&self, path: &str) -> RuntimeResult<RunOutcome> {
+        let (value, has_main) = self.execute_file(path)?;
+        Ok(RunOutcome::new(value, has_main))
+    }
+
+    /// Check and compile a file and all of its imports (including locked
+    /// packages) into a single program without running it
+    ///
+    /// The result can be serialized with [`Bytecode::to_bytes`] and run later
+    /// on a fresh VM, which is how `atlas bundle` produces standalone
+    /// executables. Modules declaring `extern` functions are rejected, since
+    /// their native bindings are not part of the bytecode.
+    ///
+    /// [`Bytecode::to_bytes`]: crate::bytecode::Bytecode::to_bytes
+    pub fn compile_file(&self, path: &str) -> RuntimeResult<CompiledProgram> {
+        let (bytecode, modules) = self.load_and_compile(path)?;
+
+        if let Some(module) = modules.iter().find(|module| {
+            module
+                .ast
+                .items
+                .iter()
+                .any(|item| matches!(item, crate::ast::Item::Extern(_)))
+        }) {
+            return Err(vec![error_codes::EXTERN_NOT_PRECOMPILABLE
+                .emit(Span::dummy())
+                .arg("path", module.path.display().to_string())
+                .build()
+                .with_file(path)]);
+        }
+
+        Ok(CompiledProgram {
+            bytecode,
+            has_main: entry_has_main(&modules),
+        })
+    }
+
+    /// Run a program from [`compile_file`](Self::compile_file) the way
+    /// [`run_file`](Self::run_file) runs its source
+    ///
+    /// This is how `atlas run` executes a program compiled by `atlas daemon`.
+    pub fn run_compiled(&self, program: CompiledProgram) -> RuntimeResult<RunOutcome> {
+        *lock(&self.timings) = PhaseTimings::new();
+        let value = self.execute(VM::new(program.bytecode))?;
+        Ok(RunOutcome::new(value, program.has_main))
+    }
+
+    /// Load, check, compile and run a file with its imports. Also reports
+    /// whether the entry module declares `fn main`.
+    fn execute_file(&self, path: &str) -> RuntimeResult<(Value, bool)> {
+        let (combined_bytecode, modules) = self.load_and_compile(path)?;
+
+        // Create VM and run combined bytecode
+        let mut vm = VM::new(combined_bytecode);
+
+        // Load extern function declarations from all modules (FFI bindings)
+        for module in &modules {
+            vm.load_extern_declarations(&module.ast)
+                .map_err(|e| vec![runtime_error_to_diagnostic(e, Vec::new(), None)])?;
+        }
+
+        let has_main = entry_has_main(&modules);
+        Ok((self.execute(vm)?, has_main))
+    }
+
+    /// Run a VM holding a whole program under this runtime's security
+    fn execute(&self, mut vm: VM) -> RuntimeResult<Value> {
+        // Lazily initialise the worker pool so task.spawn() works.
+        // ensure_worker_pool is race-free when runtimes start on several threads.
+        if crate::async_runtime::ensure_worker_pool(0, &vm) {
+            crate::async_runtime::init_blocking_pool(&vm);
+        }
+
+        vm.set_security(Arc::clone(&self.security));
+        let result = lock(&self.timings).time(Phase::Execute, || vm.run(&self.security));
+        match result {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) => Ok(Value::Null),
+            Err(e) => {
+                let stack_trace = vm.take_error_stack_trace();
+                Err(vec![runtime_error_to_diagnostic(e, stack_trace, None)])
+            }
+        }
+    }
+
+    /// Load a file with its imports, type-check every module, and compile
+    /// them into one bytecode in dependency order
+    fn load_and_compile(
+        &self,
+        path: &str,
+    ) -> RuntimeResult<(
+        crate::bytecode::Bytecode,
+        Vec<crate::module_loader::LoadedModule>,
+    )> {
         use std::path::Path;
 
         let file_path = Path::new(path);
@@ -292,16 +791,19 @@ impl Atlas {
             .unwrap_or_else(|| std::path::PathBuf::from("."));
 
         // Load all modules in dependency order
+        let mut timings = lock(&self.timings);
+        *timings = PhaseTimings::new();
         let mut loader = ModuleLoader::new(project_root.clone());
-        let modules = loader.load_module(&abs_path)?;
+        if let Some(cache) = &self.module_cache {
+            loader.set_module_cache(Arc::clone(cache));
+        }
+        let loaded = loader.load_module(&abs_path);
+        timings.merge(loader.timings());
+        let modules = loaded?;
 
         // Compile all modules to bytecode in dependency order
         let mut combined_bytecode = crate::bytecode::Bytecode::new();
-        let exports_by_path: HashMap<std::path::PathBuf, Vec<String>> = modules
-            .iter()
-            .map(|module| (module.path.clone(), module.exports.clone()))
-            .collect();
-        let mut resolver = ModuleResolver::new(project_root.clone());
+        let linker = crate::module_loader::ModuleLinker::new(&project_root, &modules);
 
         // Build module registry for cross-module import resolution
         let mut module_registry = crate::module_loader::ModuleRegistry::new();
@@ -312,26 +814,14 @@ impl Atlas {
         // every file in one pass so they can fix their entire program at once.
         // ═══════════════════════════════════════════════════════════════════════
         let mut all_errors: Vec<Diagnostic> = Vec::new();
-        let mut expanded_modules: Vec<crate::ast::Program> = Vec::new();
 
         for module in &modules {
-            // Expand namespace imports (import * as foo)
-            let expanded =
-                match self.expand_namespace_imports(module, &exports_by_path, &mut resolver) {
-                    Ok(e) => e,
-                    Err(mut errs) => {
-                        all_errors.append(&mut errs);
-                        // Use empty AST as placeholder so indices align
-                        expanded_modules.push(crate::ast::Program { items: vec![] });
-                        continue;
-                    }
-                };
-
             // Bind symbols with cross-module import support
             let mut binder = Binder::new();
             binder.set_resolved_imports(module.resolved_imports.clone());
-            let (mut symbol_table, bind_diags) =
-                binder.bind_with_modules(&expanded, &module.path, &module_registry);
+            let (mut symbol_table, bind_diags) = timings.time(Phase::Bind, || {
+                binder.bind_with_modules(&module.ast, &module.path, &module_registry)
+            });
             let bind_errors: Vec<_> = bind_diags
                 .iter()
                 .filter(|d| d.is_error())
@@ -345,7 +835,7 @@ impl Atlas {
 
             // Type-check even if bind had errors — collect ALL diagnostics
             let mut type_checker = TypeChecker::new(&mut symbol_table);
-            let type_diags = type_checker.check(&expanded);
+            let type_diags = timings.time(Phase::Typecheck, || type_checker.check(&module.ast));
             let type_errors: Vec<_> = type_diags
                 .iter()
                 .filter(|d| d.is_error())
@@ -369,7 +859,6 @@ impl Atlas {
             // Register this module's symbol table for subsequent imports
             // (even if it had errors — partial symbols help downstream modules)
             module_registry.register(module.path.clone(), symbol_table);
-            expanded_modules.push(expanded);
         }
 
         // If ANY module had errors, return ALL errors now (before compilation)
@@ -380,16 +869,73 @@ impl Atlas {
         // ═══════════════════════════════════════════════════════════════════════
         // PASS 2: Compile + Execute — only runs if Pass 1 found no errors.
         // ═══════════════════════════════════════════════════════════════════════
-        for (i, (module, expanded)) in modules.iter().zip(expanded_modules.iter()).enumerate() {
+        let mut cache_keys: HashMap<std::path::PathBuf, String> = HashMap::new();
+        let mut memory_keys: HashMap<std::path::PathBuf, String> = HashMap::new();
+        for (i, module) in modules.iter().enumerate() {
             let is_last = i == modules.len() - 1;
 
-            // Compile this module
-            let mut compiler = Compiler::new();
-            // H-296: Register imported enum variants for bare constructor calls.
-            // Must happen before compile() so the compiler knows about variants
-            // from imported enums (e.g., CommandResult::Ok -> Ok).
-            compiler.register_imported_enums(&module.imports, &module.path, &module_registry);
-            let mut module_bytecode = compiler.compile(expanded)?;
+            let memory_key = self
+                .module_cache
+                .as_ref()
+                .and_then(|cache| cache.key(module, &memory_keys));
+            let in_memory = match (&self.module_cache, &memory_key) {
+                (Some(cache), Some(key)) => cache.compiled(&module.path, key),
+                _ => None,
+            };
+            let from_memory = in_memory.is_some();
+            // Bytecode that embeds files is out of date when they change
+            let mut embeds_files = false;
+
+            let cache_key = self
+                .bytecode_cache
+                .as_ref()
+                .and_then(|cache| module_cache_key(cache, module, &cache_keys));
+            let cached = in_memory.or_else(|| match (&self.bytecode_cache, &cache_key) {
+                (Some(cache), Some(key)) => cache.load(key),
+                _ => None,
+            });
+
+            let mut module_bytecode = match cached {
+                Some(bytecode) => bytecode,
+                None => {
+                    // Compile this module
+                    let mut compiler = Compiler::new();
+                    // H-296: Register imported enum variants for bare constructor calls.
+                    // Must happen before compile() so the compiler knows about variants
+                    // from imported enums (e.g., CommandResult::Ok -> Ok).
+                    compiler.register_imported_enums(
+                        &module.imports,
+                        &module.path,
+                        &module_registry,
+                    );
+                    compiler.register_namespace_imports(
+                        &module.imports,
+                        &module.path,
+                        &module_registry,
+                    );
+                    if let Some(dir) = module.path.parent() {
+                        compiler.set_source_dir(dir);
+                    }
+                    let bytecode =
+                        timings.time(Phase::Compile, || compiler.compile(&module.ast))?;
+                    charge_optimizer(&mut timings, &compiler);
+                    embeds_files = !compiler.embedded_files().is_empty();
+                    if let (Some(cache), Some(key)) = (&self.bytecode_cache, &cache_key) {
+                        // Best effort: a read-only cache only costs the speedup
+                        let _ = cache.store(key, &bytecode);
+                    }
+                    bytecode
+                }
+            };
+            if let Some(key) = cache_key {
+                cache_keys.insert(module.path.clone(), key);
+            }
+            if let (Some(cache), Some(key)) = (&self.module_cache, memory_key) {
+                if !from_memory && !embeds_files {
+                    cache.store_compiled(&module.path, key.clone(), &module_bytecode);
+                }
+                memory_keys.insert(module.path.clone(), key);
+            }
 
             // Strip trailing Halt from non-final modules
             if !is_last
@@ -404,105 +950,46 @@ impl Atlas {
                 }
             }
 
+            linker.link(module, &mut module_bytecode);
             combined_bytecode.append(module_bytecode);
         }
 
-        // Create VM and run combined bytecode
-        let mut vm = VM::new(combined_bytecode);
-
-        // Load extern function declarations from all modules (FFI bindings)
-        for module in &modules {
-            vm.load_extern_declarations(&module.ast)
-                .map_err(|e| vec![runtime_error_to_diagnostic(e, Vec::new(), None)])?;
-        }
-
-        // Lazily initialise the worker pool so task.spawn() works.
-        // Guard: OnceLock inside init_worker_pool prevents double-init.
-        if crate::async_runtime::worker_pool().is_none() {
-            crate::async_runtime::init_worker_pool(0, &vm);
-            crate::async_runtime::init_blocking_pool(&vm);
-        }
-
-        match vm.run(&self.security) {
-            Ok(Some(value)) => Ok(value),
-            Ok(None) => Ok(Value::Null),
-            Err(e) => Err(vec![runtime_error_to_diagnostic(e, Vec::new(), None)]),
-        }
+        Ok((combined_bytecode, modules))
     }
+}
 
-    /// Expand namespace imports (import * as foo) into object literals
-    fn expand_namespace_imports(
-        &self,
-        module: &crate::module_loader::LoadedModule,
-        exports_by_path: &HashMap<std::path::PathBuf, Vec<String>>,
-        resolver: &mut ModuleResolver,
-    ) -> RuntimeResult<crate::ast::Program> {
-        use crate::ast::{
-            Expr, Identifier, ImportSpecifier, Item, ObjectEntry, ObjectLiteral, Program, Stmt,
-            VarDecl,
-        };
-        use crate::diagnostic::error_codes::IMPORT_RESOLUTION_FAILED;
-
-        let mut items = Vec::new();
-
-        for item in &module.ast.items {
-            items.push(item.clone());
-
-            let Item::Import(import_decl) = item else {
-                continue;
-            };
-
-            for specifier in &import_decl.specifiers {
-                let ImportSpecifier::Namespace { alias, span } = specifier else {
-                    continue;
-                };
-
-                let import_path = resolver
-                    .resolve_path(&import_decl.source, &module.path, import_decl.span)
-                    .map_err(|e| vec![e])?;
-                let exports = exports_by_path.get(&import_path).ok_or_else(|| {
-                    vec![IMPORT_RESOLUTION_FAILED
-                        .emit(import_decl.span)
-                        .arg("path", &import_decl.source)
-                        .arg("detail", "module not found")
-                        .build()
-                        .with_label("namespace import")]
-                })?;
-
-                let mut entries = Vec::with_capacity(exports.len());
-                for name in exports {
-                    let ident = Identifier {
-                        name: name.clone(),
-                        span: *span,
-                    };
-                    entries.push(ObjectEntry {
-                        key: ident.clone(),
-                        value: Expr::Identifier(ident),
-                        span: *span,
-                    });
-                }
-
-                let obj = ObjectLiteral {
-                    entries,
-                    span: *span,
-                };
-                let var = VarDecl {
-                    mutable: false,
-                    uses_deprecated_var: false,
-                    name: alias.clone(),
-                    type_ref: None,
-                    init: Expr::ObjectLiteral(obj),
-                    span: *span,
-                    needs_drop: std::cell::RefCell::new(None),
-                };
-                items.push(Item::Statement(Stmt::VarDecl(var)));
-            }
-        }
+/// Static type of a host variable passed to [`Atlas::eval_expr`] or [`VM::evaluate`]
+///
+/// Only primitives are typed precisely; anything else is `Unknown` and
+/// checked at run time.
+pub(crate) fn value_type(value: &Value) -> Type {
+    match value {
+        Value::Number(_) => Type::Number,
+        Value::String(_) => Type::String,
+        Value::Bool(_) => Type::Bool,
+        Value::Null => Type::Null,
+        _ => Type::Unknown,
+    }
+}
 
-        Ok(Program { items })
+/// Move the optimizer's share of a compile from `Compile` to `Optimize`
+fn charge_optimizer(timings: &mut PhaseTimings, compiler: &Compiler) {
+    let optimize = compiler.optimize_time();
+    if !optimize.is_zero() {
+        let compile = timings.get(Phase::Compile);
+        timings.add(Phase::Optimize, optimize);
+        timings.set(Phase::Compile, compile.saturating_sub(optimize));
     }
 }
 
+// Compile-time assertion: the concurrency contract documented on `Atlas`.
+const _: () = {
+    fn _assert_send_sync<T: Send + Sync>() {}
+    fn _check() {
+        _assert_send_sync::<Atlas>();
+    }
+};
+
 impl Default for Atlas {
     fn default() -> Self {
         Self::new()
@@ -574,6 +1061,10 @@ pub fn runtime_error_to_diagnostic(
             "AT0303",
             format!("Permission denied: environment variable {}", var),
         ),
+        RuntimeError::DatabasePermissionDenied { path, .. } => (
+            "AT0305",
+            format!("Permission denied: database access to {}", path),
+        ),
         RuntimeError::IoError { message, .. } => ("AT0400", message.clone()),
         RuntimeError::UnhashableType { type_name, .. } => (
             "AT0140",
@@ -582,6 +1073,9 @@ pub fn runtime_error_to_diagnostic(
                 type_name
             ),
         ),
+        RuntimeError::CircularReference { msg, .. } => {
+            ("AT0141", format!("circular reference: {}", msg))
+        }
         RuntimeError::Timeout { elapsed, limit } => (
             "AT0500",
             format!(
@@ -589,6 +1083,7 @@ pub fn runtime_error_to_diagnostic(
                 elapsed, limit
             ),
         ),
+        RuntimeError::Interrupted => ("AT0502", "Execution interrupted by the host".to_string()),
         RuntimeError::FfiPermissionDenied { function } => (
             "AT0304",
             format!("Permission denied: FFI call to {}", function),
@@ -604,6 +1099,13 @@ pub fn runtime_error_to_diagnostic(
                 requested, limit, used
             ),
         ),
+        RuntimeError::RegexLimitExceeded { msg, .. } => {
+            ("AT0503", format!("Regex limit exceeded: {}", msg))
+        }
+        RuntimeError::RecursionLimit { depth, .. } => (
+            "AT0504",
+            format!("Recursion limit exceeded at call depth {}", depth),
+        ),
         RuntimeError::InternalError { msg, .. } => ("AT9995", format!("Internal error: {}", msg)),
     };
 
@@ -650,6 +1152,9 @@ pub fn runtime_error_to_diagnostic(
         RuntimeError::EnvironmentPermissionDenied { .. } => {
             "enable environment permissions with --allow-env or adjust security settings"
         }
+        RuntimeError::DatabasePermissionDenied { .. } => {
+            "allow the database path under [security.database] in atlas.toml or adjust security settings"
+        }
         RuntimeError::TypeError { .. } => {
             "check the value's type before this operation; use `type_of(value)` to inspect it at runtime"
         }
@@ -660,12 +1165,24 @@ pub fn runtime_error_to_diagnostic(
             let _ = type_name; // named in the message
             "use a hashable type (number, string, bool, or null) as the map key"
         }
+        RuntimeError::CircularReference { .. } => {
+            "break the cycle before this operation, e.g. store an id instead of the shared value itself"
+        }
         RuntimeError::Timeout { .. } => {
             "optimize the computation to reduce execution time, or increase the limit with --timeout <ms>"
         }
+        RuntimeError::Interrupted => {
+            "the embedding application stopped this script; run it again once the host allows it"
+        }
         RuntimeError::MemoryLimitExceeded { .. } => {
             "reduce memory usage or increase the limit with --memory <bytes>"
         }
+        RuntimeError::RegexLimitExceeded { .. } => {
+            "simplify the pattern, or use Regex.escape(text) when matching user input literally"
+        }
+        RuntimeError::RecursionLimit { .. } => {
+            "check that the recursion reaches its base case, or rewrite it as a loop"
+        }
         RuntimeError::UnknownOpcode { .. } | RuntimeError::StackUnderflow { .. } => {
             "this is a bug in the Atlas compiler; please report it at https://github.com/anthropics/atlas/issues"
         }
@@ -721,3 +1238,9 @@ pub fn runtime_error_to_diagnostic(
 
     diag
 }
+
+/// Lock ignoring poisoning: like the `RefCell`s these replaced, a panic
+/// during one evaluation must not make the runtime unusable afterwards
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
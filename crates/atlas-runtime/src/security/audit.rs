@@ -26,6 +26,10 @@ pub enum AuditEvent {
     ProcessDenied { command: String },
     /// Environment variable access denied
     EnvironmentDenied { var: String },
+    /// Setting an environment variable denied
+    EnvironmentWriteDenied { var: String },
+    /// Database file access denied
+    DatabaseDenied { path: PathBuf },
     /// Sandbox created
     SandboxCreated {
         sandbox_id: String,
@@ -91,6 +95,16 @@ impl fmt::Display for AuditEvent {
             AuditEvent::EnvironmentDenied { var } => {
                 write!(f, "Permission denied: environment variable {}", var)
             }
+            AuditEvent::EnvironmentWriteDenied { var } => {
+                write!(f, "Permission denied: setting environment variable {}", var)
+            }
+            AuditEvent::DatabaseDenied { path } => {
+                write!(
+                    f,
+                    "Permission denied: database access to {}",
+                    path.display()
+                )
+            }
             AuditEvent::SandboxCreated {
                 sandbox_id,
                 memory_limit,
@@ -0,0 +1,74 @@
+//! Resource limits for user-supplied regular expressions
+//!
+//! Atlas uses the `regex` crate, which matches in linear time and never
+//! backtracks, so a pattern cannot blow up at match time the way it can in a
+//! backtracking engine. What a hostile pattern *can* do is make compilation
+//! expensive: huge repetitions (`a{1000}{1000}`), deep nesting, or a very long
+//! pattern string. [`RegexLimits`] bounds each of those, plus the lazy DFA
+//! cache a compiled pattern may grow while matching. Wall-clock time spent
+//! matching is bounded separately by the per-builtin call limit.
+
+/// Limits applied whenever a script compiles a regex
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegexLimits {
+    /// Maximum length of the pattern source, in bytes
+    pub max_pattern_len: usize,
+    /// Maximum size of the compiled program, in bytes
+    pub max_compiled_size: usize,
+    /// Maximum size of the lazy DFA cache used while matching, in bytes
+    pub max_dfa_cache: usize,
+    /// Maximum nesting depth of groups, classes and repetitions
+    pub max_nesting: u32,
+}
+
+impl RegexLimits {
+    /// The `regex` crate's own defaults, with no cap on pattern length
+    pub const fn standard() -> Self {
+        Self {
+            max_pattern_len: usize::MAX,
+            max_compiled_size: 10 * (1 << 20),
+            max_dfa_cache: 2 * (1 << 20),
+            max_nesting: 250,
+        }
+    }
+
+    /// Tighter limits for untrusted scripts
+    pub const fn strict() -> Self {
+        Self {
+            max_pattern_len: 4096,
+            max_compiled_size: 1 << 20,
+            max_dfa_cache: 1 << 20,
+            max_nesting: 32,
+        }
+    }
+
+    /// Set the maximum pattern length in bytes
+    pub fn with_max_pattern_len(mut self, bytes: usize) -> Self {
+        self.max_pattern_len = bytes;
+        self
+    }
+
+    /// Set the maximum compiled program size in bytes
+    pub fn with_max_compiled_size(mut self, bytes: usize) -> Self {
+        self.max_compiled_size = bytes;
+        self
+    }
+
+    /// Set the maximum lazy DFA cache size in bytes
+    pub fn with_max_dfa_cache(mut self, bytes: usize) -> Self {
+        self.max_dfa_cache = bytes;
+        self
+    }
+
+    /// Set the maximum nesting depth
+    pub fn with_max_nesting(mut self, depth: u32) -> Self {
+        self.max_nesting = depth;
+        self
+    }
+}
+
+impl Default for RegexLimits {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
@@ -31,11 +31,13 @@
 //! ```
 
 pub mod audit;
+pub mod limits;
 pub mod permissions;
 pub mod policy;
 pub mod sandbox;
 
 pub use audit::{AuditEntry, AuditEvent, AuditLogger, MemoryAuditLogger, NullAuditLogger};
+pub use limits::RegexLimits;
 pub use permissions::{Permission, PermissionSet, SecurityContext, SecurityError};
 pub use policy::{PolicyError, PolicyManager, SecurityPolicy};
 pub use sandbox::{ResourceQuotas, ResourceUsage, Sandbox, SandboxError};
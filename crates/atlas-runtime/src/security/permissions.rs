@@ -3,6 +3,8 @@
 //! Defines the permission system for controlling I/O operations.
 
 use crate::security::audit::{AuditEvent, AuditLogger, NullAuditLogger};
+use crate::security::limits::RegexLimits;
+use crate::stdlib::fs::TempRoot;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -26,6 +28,12 @@ pub enum SecurityError {
     #[error("Permission denied: environment variable {var}")]
     EnvironmentDenied { var: String },
 
+    #[error("Permission denied: setting environment variable {var}")]
+    EnvironmentWriteDenied { var: String },
+
+    #[error("Permission denied: database access to {path}")]
+    DatabaseDenied { path: PathBuf },
+
     #[error("Invalid path: {0}")]
     InvalidPath(String),
 
@@ -50,6 +58,9 @@ pub enum Permission {
 
     /// Environment variable access
     Environment { var: String },
+
+    /// Database file access (open, read, and write through `sqlite`)
+    Database { path: PathBuf, recursive: bool },
 }
 
 impl Permission {
@@ -144,6 +155,21 @@ impl Permission {
                 Permission::Environment { var: requested_var },
             ) => allowed_var == requested_var || allowed_var == "*",
 
+            // Database: same path rules as the filesystem
+            (
+                Permission::Database {
+                    path: allowed_path,
+                    recursive: allowed_recursive,
+                },
+                Permission::Database {
+                    path: requested_path,
+                    ..
+                },
+            ) => {
+                allowed_path == requested_path
+                    || (*allowed_recursive && requested_path.starts_with(allowed_path))
+            }
+
             // Different permission types never match
             _ => false,
         }
@@ -205,7 +231,11 @@ pub struct SecurityContext {
     network: PermissionSet,
     process: PermissionSet,
     environment: PermissionSet,
+    environment_write: PermissionSet,
+    database: PermissionSet,
+    regex_limits: RegexLimits,
     audit_logger: Arc<dyn AuditLogger>,
+    temp_root: Arc<TempRoot>,
 }
 
 impl Default for SecurityContext {
@@ -216,7 +246,11 @@ impl Default for SecurityContext {
             network: PermissionSet::new(),
             process: PermissionSet::new(),
             environment: PermissionSet::new(),
+            environment_write: PermissionSet::new(),
+            database: PermissionSet::new(),
+            regex_limits: RegexLimits::default(),
             audit_logger: Arc::new(NullAuditLogger::new()),
+            temp_root: Arc::new(TempRoot::new()),
         }
     }
 }
@@ -235,18 +269,58 @@ impl SecurityContext {
             network: PermissionSet::new(),
             process: PermissionSet::new(),
             environment: PermissionSet::new(),
+            environment_write: PermissionSet::new(),
+            database: PermissionSet::new(),
+            regex_limits: RegexLimits::default(),
             audit_logger: logger,
+            temp_root: Arc::new(TempRoot::new()),
         }
     }
 
-    /// Create from security configuration
+    /// Create from a `[security]` configuration section
     ///
-    /// Note: This requires atlas-config to be available. For now, this is a placeholder
-    /// that will be implemented when integrating with the runtime.
-    #[allow(dead_code)] // TODO: Implement full config integration (future phase)
-    pub fn from_config(_config: &atlas_config::SecurityConfig) -> Self {
-        // TODO: Implement full config integration
-        Self::new()
+    /// Mode `"none"` disables checks entirely ([`allow_all`](Self::allow_all)).
+    /// Otherwise everything is denied except the `allow` / `read` / `write`
+    /// entries; an entry that is also listed under `deny` is not granted.
+    /// Filesystem and database paths are granted recursively.
+    pub fn from_config(config: &atlas_config::SecurityConfig) -> Self {
+        if config.mode() == "none" {
+            return Self::allow_all();
+        }
+
+        let mut ctx = Self::new();
+        if let Some(fs) = &config.filesystem {
+            for path in fs.read.iter().filter(|p| !fs.deny.contains(p)) {
+                ctx.grant_filesystem_read(path, true);
+            }
+            for path in fs.write.iter().filter(|p| !fs.deny.contains(p)) {
+                ctx.grant_filesystem_write(path, true);
+            }
+        }
+        if let Some(network) = &config.network {
+            for host in network.allow.iter().filter(|h| !network.deny.contains(h)) {
+                ctx.grant_network(host.clone());
+            }
+        }
+        if let Some(process) = &config.process {
+            for command in process.allow.iter().filter(|c| !process.deny.contains(c)) {
+                ctx.grant_process(command.clone());
+            }
+        }
+        if let Some(env) = &config.environment {
+            for var in env.allow.iter().filter(|v| !env.deny.contains(v)) {
+                ctx.grant_environment(var.clone());
+            }
+            for var in env.write.iter().filter(|v| !env.deny.contains(v)) {
+                ctx.grant_environment_write(var.clone());
+            }
+        }
+        if let Some(database) = &config.database {
+            for path in database.allow.iter().filter(|p| !database.deny.contains(p)) {
+                ctx.grant_database(path, true);
+            }
+        }
+        ctx
     }
 
     /// Create a permissive context that allows all operations
@@ -266,6 +340,10 @@ impl SecurityContext {
                 path: PathBuf::from("/"),
                 recursive: true,
             });
+            ctx.database.grant(Permission::Database {
+                path: PathBuf::from("/"),
+                recursive: true,
+            });
         }
         #[cfg(windows)]
         {
@@ -288,6 +366,12 @@ impl SecurityContext {
                 path: PathBuf::from(&verbatim_root),
                 recursive: true,
             });
+            for path in [&root, &verbatim_root] {
+                ctx.database.grant(Permission::Database {
+                    path: PathBuf::from(path),
+                    recursive: true,
+                });
+            }
         }
         ctx.network.grant(Permission::Network {
             host: "*".to_string(),
@@ -295,14 +379,13 @@ impl SecurityContext {
         ctx.process.grant(Permission::Process {
             command: "*".to_string(),
         });
-        ctx.environment.grant(Permission::Environment {
-            var: "*".to_string(),
-        });
+        ctx.grant_environment("*");
+        ctx.grant_environment_write("*");
 
         ctx
     }
 
-    /// Create a test-friendly context that allows filesystem, process, and env access
+    /// Create a test-friendly context that allows filesystem, database, process, and env access
     ///
     /// Network access remains denied to keep a minimal sandbox during tests.
     pub fn test_mode() -> Self {
@@ -318,6 +401,10 @@ impl SecurityContext {
                 path: PathBuf::from("/"),
                 recursive: true,
             });
+            ctx.database.grant(Permission::Database {
+                path: PathBuf::from("/"),
+                recursive: true,
+            });
         }
         #[cfg(windows)]
         {
@@ -340,14 +427,19 @@ impl SecurityContext {
                 path: PathBuf::from(&verbatim_root),
                 recursive: true,
             });
+            for path in [&root, &verbatim_root] {
+                ctx.database.grant(Permission::Database {
+                    path: PathBuf::from(path),
+                    recursive: true,
+                });
+            }
         }
 
         ctx.process.grant(Permission::Process {
             command: "*".to_string(),
         });
-        ctx.environment.grant(Permission::Environment {
-            var: "*".to_string(),
-        });
+        ctx.grant_environment("*");
+        ctx.grant_environment_write("*");
 
         ctx
     }
@@ -381,23 +473,43 @@ impl SecurityContext {
         });
     }
 
-    /// Grant environment variable access permission
+    /// Grant permission to read an environment variable
     pub fn grant_environment(&mut self, var: impl Into<String>) {
         self.environment
             .grant(Permission::Environment { var: var.into() });
     }
 
+    /// Grant permission to set and unset an environment variable
+    ///
+    /// Reading it needs [`grant_environment`](Self::grant_environment) as well.
+    pub fn grant_environment_write(&mut self, var: impl Into<String>) {
+        self.environment_write
+            .grant(Permission::Environment { var: var.into() });
+    }
+
+    /// Grant database access to the file at `path` (or every file under it
+    /// when `recursive`)
+    pub fn grant_database(&mut self, path: &Path, recursive: bool) {
+        let path = canonicalize_path_safe(path);
+        self.database
+            .grant(Permission::Database { path, recursive });
+    }
+
     // Permission checking methods
 
     /// Check filesystem read permission
+    ///
+    /// Paths inside this context's temp root (`file.tempFile()` / `file.tempDir()`)
+    /// are always allowed.
     pub fn check_filesystem_read(&self, path: &Path) -> Result<(), SecurityError> {
+        let in_temp_root = self.temp_root.contains(path);
         let path = canonicalize_path_safe(path);
         let requested = Permission::FilesystemRead {
             path: path.clone(),
             recursive: false,
         };
 
-        if self.filesystem_read.is_granted(&requested) {
+        if self.filesystem_read.is_granted(&requested) || in_temp_root {
             self.audit_logger.log(AuditEvent::PermissionCheck {
                 operation: "file read".to_string(),
                 target: path.display().to_string(),
@@ -412,14 +524,17 @@ impl SecurityContext {
     }
 
     /// Check filesystem write permission
+    ///
+    /// Paths inside this context's temp root are always allowed.
     pub fn check_filesystem_write(&self, path: &Path) -> Result<(), SecurityError> {
+        let in_temp_root = self.temp_root.contains(path);
         let path = canonicalize_path_safe(path);
         let requested = Permission::FilesystemWrite {
             path: path.clone(),
             recursive: false,
         };
 
-        if self.filesystem_write.is_granted(&requested) {
+        if self.filesystem_write.is_granted(&requested) || in_temp_root {
             self.audit_logger.log(AuditEvent::PermissionCheck {
                 operation: "file write".to_string(),
                 target: path.display().to_string(),
@@ -479,7 +594,7 @@ impl SecurityContext {
         }
     }
 
-    /// Check environment variable access permission
+    /// Check permission to read an environment variable
     pub fn check_environment(&self, var: &str) -> Result<(), SecurityError> {
         let requested = Permission::Environment {
             var: var.to_string(),
@@ -502,6 +617,86 @@ impl SecurityContext {
         }
     }
 
+    /// Check permission to set or unset an environment variable
+    pub fn check_environment_write(&self, var: &str) -> Result<(), SecurityError> {
+        let requested = Permission::Environment {
+            var: var.to_string(),
+        };
+
+        if self.environment_write.is_granted(&requested) {
+            self.audit_logger.log(AuditEvent::PermissionCheck {
+                operation: "environment write".to_string(),
+                target: var.to_string(),
+                granted: true,
+            });
+            Ok(())
+        } else {
+            self.audit_logger.log(AuditEvent::EnvironmentWriteDenied {
+                var: var.to_string(),
+            });
+            Err(SecurityError::EnvironmentWriteDenied {
+                var: var.to_string(),
+            })
+        }
+    }
+
+    /// Whether an environment variable may be read, without auditing
+    ///
+    /// Used to filter listings, where logging a check per variable would
+    /// flood the audit log.
+    pub fn can_read_environment(&self, var: &str) -> bool {
+        self.environment.is_granted(&Permission::Environment {
+            var: var.to_string(),
+        })
+    }
+
+    /// Check database access permission
+    ///
+    /// In-memory databases (`:memory:`, or an empty path for a private temp
+    /// database) touch no file and are always allowed.
+    pub fn check_database(&self, path: &str) -> Result<(), SecurityError> {
+        if path.is_empty() || path == ":memory:" {
+            return Ok(());
+        }
+
+        let path = canonicalize_path_safe(Path::new(path));
+        let requested = Permission::Database {
+            path: path.clone(),
+            recursive: false,
+        };
+
+        if self.database.is_granted(&requested) {
+            self.audit_logger.log(AuditEvent::PermissionCheck {
+                operation: "database".to_string(),
+                target: path.display().to_string(),
+                granted: true,
+            });
+            Ok(())
+        } else {
+            self.audit_logger
+                .log(AuditEvent::DatabaseDenied { path: path.clone() });
+            Err(SecurityError::DatabaseDenied { path })
+        }
+    }
+
+    /// Set the limits applied when scripts compile a regex
+    pub fn set_regex_limits(&mut self, limits: RegexLimits) {
+        self.regex_limits = limits;
+    }
+
+    /// Limits applied when scripts compile a regex
+    pub fn regex_limits(&self) -> RegexLimits {
+        self.regex_limits
+    }
+
+    /// Scratch directory backing `file.tempFile()` / `file.tempDir()`
+    ///
+    /// Shared by clones of this context and removed when the last one is
+    /// dropped.
+    pub fn temp_root(&self) -> &TempRoot {
+        &self.temp_root
+    }
+
     /// Get the audit logger (for testing)
     pub fn audit_logger(&self) -> Arc<dyn AuditLogger> {
         Arc::clone(&self.audit_logger)
@@ -103,6 +103,8 @@ pub enum ResourceType {
     Process,
     #[serde(rename = "environment")]
     Environment,
+    #[serde(rename = "database")]
+    Database,
     #[serde(rename = "reflection")]
     Reflection,
 }
@@ -190,24 +192,24 @@ impl SecurityPolicy {
 
         // Validate resource-specific patterns
         match rule.resource {
-            ResourceType::FileRead | ResourceType::FileWrite | ResourceType::FileDelete => {
-                // File paths should be absolute or contain wildcards
-                if !rule.pattern.starts_with('/') && !rule.pattern.contains('*') {
-                    return Err(PolicyError::InvalidField {
-                        field: "pattern".to_string(),
-                        reason: "File path must be absolute or contain wildcards".to_string(),
-                    });
-                }
+            // File paths should be absolute or contain wildcards
+            ResourceType::FileRead | ResourceType::FileWrite | ResourceType::FileDelete
+                if !rule.pattern.starts_with('/') && !rule.pattern.contains('*') =>
+            {
+                return Err(PolicyError::InvalidField {
+                    field: "pattern".to_string(),
+                    reason: "File path must be absolute or contain wildcards".to_string(),
+                });
             }
-            ResourceType::NetworkConnect | ResourceType::NetworkListen => {
-                // Network patterns should be valid domain or IP
-                // Basic validation - in production would use more sophisticated checks
-                if rule.pattern.contains("..") {
-                    return Err(PolicyError::InvalidField {
-                        field: "pattern".to_string(),
-                        reason: "Invalid network pattern".to_string(),
-                    });
-                }
+            // Network patterns should be valid domain or IP
+            // Basic validation - in production would use more sophisticated checks
+            ResourceType::NetworkConnect | ResourceType::NetworkListen
+                if rule.pattern.contains("..") =>
+            {
+                return Err(PolicyError::InvalidField {
+                    field: "pattern".to_string(),
+                    reason: "Invalid network pattern".to_string(),
+                });
             }
             _ => {
                 // Other resource types - basic validation
@@ -278,6 +280,10 @@ impl SecurityPolicy {
             ResourceType::Environment => Some(Permission::Environment {
                 var: rule.pattern.clone(),
             }),
+            ResourceType::Database => Some(Permission::Database {
+                path: PathBuf::from(&rule.pattern),
+                recursive: rule.scope.as_deref() == Some("recursive"),
+            }),
             // TODO: Add support for other resource types when they're added to Permission enum
             _ => None,
         }
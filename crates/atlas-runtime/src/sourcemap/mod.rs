@@ -15,7 +15,7 @@ pub mod vlq;
 pub use encoder::{decode_mappings, MappingEntry, OriginalLocation, SourceMapBuilder, SourceMapV3};
 
 use crate::bytecode::{Bytecode, DebugSpan};
-use crate::debugger::source_map::compute_line_offsets;
+use crate::debugger::source_map::SpanFiles;
 
 /// Options for source map generation.
 #[derive(Debug, Clone, Default)]
@@ -46,9 +46,13 @@ impl SourceMapOptions {
 /// a single line (the bytecode stream), and the original positions come from
 /// the AST spans embedded during compilation.
 ///
+/// Bytecode linked from several modules maps into several sources: spans
+/// from an imported module's file get their own `sources` entry, while spans
+/// from anonymous source are attributed to `source_file`.
+///
 /// # Arguments
 /// - `bytecode` — compiled bytecode with debug_info
-/// - `source_file` — name of the original source file
+/// - `source_file` — name of the original (entry) source file
 /// - `source_text` — optional source code for line/column computation and inlining
 /// - `options` — source map generation options
 pub fn generate_source_map(
@@ -66,37 +70,49 @@ pub fn generate_source_map(
         builder.set_source_root(root);
     }
 
+    // The entry file is always the first source, even if it has no mappings
     let content = if options.include_sources {
         source_text.map(|s| s.to_string())
     } else {
         None
     };
-    let source_idx = builder.add_source(source_file, content);
-
-    let line_offsets = source_text
-        .map(compute_line_offsets)
-        .unwrap_or_else(|| vec![0]);
+    builder.add_source(source_file, content);
+    let mut files = SpanFiles::with_optional_source(source_file, source_text);
 
     // Convert debug spans to source map entries.
     // Each instruction offset → generated position (line 0, column = offset).
-    // Original positions come from the span's byte offset → line/column.
-    let mut entries: Vec<(usize, u32, u32)> = Vec::new();
+    // Original positions come from the span's byte offset → line/column in its file.
+    let mut entries: Vec<(usize, u32, u32, u32)> = Vec::new();
     for debug_span in &bytecode.debug_info {
         if debug_span.span.start == 0 && debug_span.span.end == 0 {
             continue; // Skip dummy spans
         }
-        let (orig_line, orig_col) = byte_offset_to_zero_based(debug_span.span.start, &line_offsets);
-        entries.push((debug_span.instruction_offset, orig_line, orig_col));
+        let file = files.resolve(debug_span.span);
+        let content = if options.include_sources {
+            file.source.clone()
+        } else {
+            None
+        };
+        let (orig_line, orig_col) = match &file.line_offsets {
+            Some(offsets) => byte_offset_to_zero_based(debug_span.span.start, offsets),
+            None => (0, debug_span.span.start as u32),
+        };
+        let source_idx = builder.add_source(file.name.clone(), content);
+        entries.push((
+            debug_span.instruction_offset,
+            source_idx,
+            orig_line,
+            orig_col,
+        ));
     }
-
     // Remove redundant entries (same original position as previous)
-    entries.dedup_by(|b, a| a.1 == b.1 && a.2 == b.2);
+    entries.dedup_by(|b, a| a.1 == b.1 && a.2 == b.2 && a.3 == b.3);
 
-    for (offset, orig_line, orig_col) in &entries {
+    for (offset, source_idx, orig_line, orig_col) in &entries {
         builder.add_mapping(
             0,              // generated line (bytecode is a flat stream)
             *offset as u32, // generated column = instruction offset
-            source_idx,
+            *source_idx,
             *orig_line,
             *orig_col,
             None,
@@ -116,7 +132,10 @@ pub fn generate_from_debug_spans(
     let bytecode = Bytecode {
         instructions: Vec::new(),
         constants: Vec::new(),
+        global_names: Vec::new(),
         debug_info: spans.to_vec(),
+        statement_offsets: Vec::new(),
+        local_names: Vec::new(),
         top_level_local_count: 0,
     };
     generate_source_map(&bytecode, source_file, source_text, options)
@@ -2,14 +2,15 @@
 
 use crate::diagnostic::formatter::offset_to_line_col;
 use crate::diagnostic::StackTraceFrame;
-use crate::span::Span;
+use crate::span::{source_for_file, Span};
 use std::fs;
 
 /// Convert a span into a stack trace frame.
 ///
 /// If `source_override` is provided and the span's file matches, line/column
-/// are computed from the provided source text. Otherwise, the helper attempts
-/// to read the file from disk. When neither is available, it falls back to
+/// are computed from the provided source text. Otherwise, the helper uses the
+/// text the lexer registered for the span's file, then attempts to read the
+/// file from disk. When neither is available, it falls back to
 /// line 1 with a column based on the byte offset.
 pub fn stack_frame_from_span(
     function: impl Into<String>,
@@ -34,6 +35,10 @@ fn line_column_for_span(span: Span, source_override: Option<(&str, &str)>) -> (u
         }
     }
 
+    if let Some(source) = source_for_file(span.file) {
+        return offset_to_line_col(&source, span.start);
+    }
+
     if file.starts_with('<') {
         return (1, span.start + 1);
     }
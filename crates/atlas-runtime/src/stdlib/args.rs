@@ -0,0 +1,686 @@
+//! Declarative command-line argument parsing
+//!
+//! - process.parseArgs(spec) -> Result<record, string>
+//! - process.parseArgs(spec, argv) -> Result<record, string>
+//! - process.argsHelp(spec) -> string
+//!
+//! A spec is a `string[]` with one declaration per argument:
+//!
+//! ```text
+//! "service: string               # Service to deploy"
+//! "tag?: string                  # Image tag"
+//! "--env, -e: string = staging   # Target environment"
+//! "--port, -p: number = 8080     # Port to listen on"
+//! "--verbose, -v                 # Print every step"
+//! "--label: string[]             # May be repeated"
+//! ```
+//!
+//! Names starting with `-` are options, anything else is a positional.
+//! Options without a type are `bool` flags. `T[]` options collect every
+//! occurrence; a `T[]` positional takes the remaining arguments. Positionals
+//! are required unless marked `?` or given a default.
+//!
+//! The parsed record has one field per declaration, named after the long
+//! option (`--dry-run` becomes `dryRun`) or the positional. `--help`/`-h`
+//! prints the generated help and exits, unless the spec declares its own.
+//! A bad spec is a runtime error; bad user input is an `Err` whose message
+//! ends with the usage line.
+
+use super::{stdlib_arg_error, stdlib_arity_error, OutputWriter};
+use crate::security::SecurityContext;
+use crate::span::Span;
+use crate::stdlib::collections::hash::HashKey;
+use crate::stdlib::collections::hashmap::AtlasHashMap;
+use crate::value::{RuntimeError, Value, ValueHashMap};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum ArgType {
+    String,
+    Number,
+    Bool,
+}
+
+impl ArgType {
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            ArgType::String => "string",
+            ArgType::Number => "number",
+            ArgType::Bool => "bool",
+        }
+    }
+}
+
+/// One declaration from the spec
+#[derive(Debug)]
+struct ArgDecl {
+    /// Field name in the parsed record
+    key: String,
+    /// `--long` name without dashes (options only)
+    long: Option<String>,
+    short: Option<char>,
+    positional: bool,
+    ty: ArgType,
+    /// Repeatable option / rest positional
+    many: bool,
+    /// Positional marked `?`
+    optional: bool,
+    default: Option<Value>,
+    /// Default as written in the spec, for the help text
+    default_text: Option<String>,
+    help: String,
+}
+
+impl ArgDecl {
+    /// How the argument is shown in usage and help
+    fn display_name(&self) -> String {
+        if self.positional {
+            let dots = if self.many { "..." } else { "" };
+            if self.is_required() {
+                format!("<{}>{}", self.key, dots)
+            } else {
+                format!("[{}]{}", self.key, dots)
+            }
+        } else {
+            match &self.long {
+                Some(long) => format!("--{}", long),
+                None => format!("-{}", self.short.unwrap_or('?')),
+            }
+        }
+    }
+
+    fn is_required(&self) -> bool {
+        self.positional && !self.optional && !self.many && self.default.is_none()
+    }
+
+    fn takes_value(&self) -> bool {
+        self.ty != ArgType::Bool
+    }
+}
+
+struct Spec {
+    decls: Vec<ArgDecl>,
+    /// Whether `--help`/`-h` are ours to handle
+    auto_help: bool,
+}
+
+// ============================================================================
+// Spec parsing
+// ============================================================================
+
+fn spec_error(msg: String, span: Span) -> RuntimeError {
+    RuntimeError::TypeError {
+        msg: format!("process.parseArgs: invalid spec: {}", msg),
+        span,
+    }
+}
+
+fn parse_spec(value: &Value, span: Span) -> Result<Spec, RuntimeError> {
+    let lines = match value {
+        Value::Array(arr) => arr.as_slice().to_vec(),
+        other => {
+            return Err(stdlib_arg_error(
+                "process.parseArgs",
+                "string[]",
+                other,
+                span,
+            ))
+        }
+    };
+    let mut decls: Vec<ArgDecl> = Vec::with_capacity(lines.len());
+    for line in &lines {
+        let line = match line {
+            Value::String(s) => s.as_ref(),
+            other => {
+                return Err(stdlib_arg_error(
+                    "process.parseArgs",
+                    "string[]",
+                    other,
+                    span,
+                ))
+            }
+        };
+        let decl = parse_decl(line).map_err(|msg| spec_error(msg, span))?;
+        let clash = decls
+            .iter()
+            .find(|d| d.key == decl.key || (decl.short.is_some() && d.short == decl.short));
+        if let Some(existing) = clash {
+            return Err(spec_error(
+                format!(
+                    "'{}' is declared twice (also as {})",
+                    line.trim(),
+                    existing.display_name()
+                ),
+                span,
+            ));
+        }
+        decls.push(decl);
+    }
+
+    let positionals: Vec<&ArgDecl> = decls.iter().filter(|d| d.positional).collect();
+    for pair in positionals.windows(2) {
+        if pair[0].many {
+            return Err(spec_error(
+                format!("{} must be the last positional", pair[0].display_name()),
+                span,
+            ));
+        }
+        if !pair[0].is_required() && pair[1].is_required() {
+            return Err(spec_error(
+                format!(
+                    "required {} cannot follow optional {}",
+                    pair[1].display_name(),
+                    pair[0].display_name()
+                ),
+                span,
+            ));
+        }
+    }
+
+    let auto_help = !decls
+        .iter()
+        .any(|d| d.long.as_deref() == Some("help") || d.short == Some('h'));
+    Ok(Spec { decls, auto_help })
+}
+
+/// Parse `names [: type] [= default] [# help]`
+fn parse_decl(line: &str) -> Result<ArgDecl, String> {
+    let (decl, help) = match line.split_once('#') {
+        Some((decl, help)) => (decl, help.trim().to_string()),
+        None => (line, String::new()),
+    };
+    let (decl, default_text) = match decl.split_once('=') {
+        Some((decl, default)) => (decl, Some(unquote(default.trim()).to_string())),
+        None => (decl, None),
+    };
+    let (names, ty_text) = match decl.split_once(':') {
+        Some((names, ty)) => (names.trim(), Some(ty.trim())),
+        None => (decl.trim(), None),
+    };
+    if names.is_empty() {
+        return Err(format!("'{}' has no name", line.trim()));
+    }
+
+    let (ty, many) = match ty_text {
+        None => (ArgType::Bool, false),
+        Some(text) => {
+            let (base, many) = match text.strip_suffix("[]") {
+                Some(base) => (base, true),
+                None => (text, false),
+            };
+            let ty = match base {
+                "string" => ArgType::String,
+                "number" => ArgType::Number,
+                "bool" if !many => ArgType::Bool,
+                _ => return Err(format!("unknown type '{}' in '{}'", text, line.trim())),
+            };
+            (ty, many)
+        }
+    };
+
+    let mut decl = if names.starts_with('-') {
+        parse_option_names(names, line)?
+    } else {
+        parse_positional_name(names, line)?
+    };
+    // Untyped options are flags; untyped positionals are strings
+    decl.ty = match ty_text {
+        None if decl.positional => ArgType::String,
+        _ => ty,
+    };
+    decl.many |= many;
+    decl.help = help;
+
+    if let Some(text) = default_text {
+        let default = if decl.many {
+            let items: Result<Vec<Value>, String> = text
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| convert(item, decl.ty).map_err(|_| item.to_string()))
+                .collect();
+            items.map(Value::array)
+        } else {
+            convert(&text, decl.ty).map_err(|_| text.clone())
+        };
+        let default =
+            default.map_err(|bad| format!("default '{}' is not a {}", bad, decl.ty.name()))?;
+        decl.default = Some(default);
+        decl.default_text = Some(text);
+    }
+    Ok(decl)
+}
+
+fn blank_decl(key: String) -> ArgDecl {
+    ArgDecl {
+        key,
+        long: None,
+        short: None,
+        positional: false,
+        ty: ArgType::Bool,
+        many: false,
+        optional: false,
+        default: None,
+        default_text: None,
+        help: String::new(),
+    }
+}
+
+fn parse_option_names(names: &str, line: &str) -> Result<ArgDecl, String> {
+    let mut long = None;
+    let mut short = None;
+    for name in names.split(',').map(str::trim) {
+        if let Some(rest) = name.strip_prefix("--") {
+            let valid = !rest.is_empty()
+                && rest.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                && !rest.starts_with('-');
+            if !valid || long.is_some() {
+                return Err(format!("bad option name '{}' in '{}'", name, line.trim()));
+            }
+            long = Some(rest.to_string());
+        } else if let Some(rest) = name.strip_prefix('-') {
+            let mut chars = rest.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii_alphabetic() && short.is_none() => short = Some(c),
+                _ => return Err(format!("bad short option '{}' in '{}'", name, line.trim())),
+            }
+        } else {
+            return Err(format!("bad option name '{}' in '{}'", name, line.trim()));
+        }
+    }
+    let key = match (&long, short) {
+        (Some(long), _) => camel_case(long),
+        (None, Some(short)) => short.to_string(),
+        (None, None) => unreachable!("names is non-empty"),
+    };
+    let mut decl = blank_decl(key);
+    decl.long = long;
+    decl.short = short;
+    Ok(decl)
+}
+
+fn parse_positional_name(name: &str, line: &str) -> Result<ArgDecl, String> {
+    let (name, optional, many) = if let Some(base) = name.strip_suffix("...") {
+        (base, true, true)
+    } else if let Some(base) = name.strip_suffix('?') {
+        (base, true, false)
+    } else {
+        (name, false, false)
+    };
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(format!("bad argument name '{}' in '{}'", name, line.trim()));
+    }
+    let mut decl = blank_decl(name.to_string());
+    decl.positional = true;
+    decl.optional = optional;
+    decl.many = many;
+    Ok(decl)
+}
+
+pub(super) fn unquote(text: &str) -> &str {
+    text.strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .unwrap_or(text)
+}
+
+/// `dry-run` → `dryRun`
+pub(super) fn camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '-' {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+pub(super) fn convert(text: &str, ty: ArgType) -> Result<Value, ()> {
+    match ty {
+        ArgType::String => Ok(Value::string(text)),
+        ArgType::Number => match text.trim().parse::<f64>() {
+            Ok(n) if n.is_finite() => Ok(Value::Number(n)),
+            _ => Err(()),
+        },
+        ArgType::Bool => match text {
+            "true" | "yes" | "1" => Ok(Value::Bool(true)),
+            "false" | "no" | "0" => Ok(Value::Bool(false)),
+            _ => Err(()),
+        },
+    }
+}
+
+// ============================================================================
+// Help
+// ============================================================================
+
+pub(super) fn program_name() -> String {
+    if let Ok(name) = std::env::var("ATLAS_PROGRAM_NAME") {
+        return name;
+    }
+    std::env::args()
+        .next()
+        .and_then(|arg0| {
+            std::path::Path::new(&arg0)
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| "program".to_string())
+}
+
+fn usage_line(spec: &Spec) -> String {
+    let mut usage = format!("Usage: {}", program_name());
+    if spec.decls.iter().any(|d| !d.positional) || spec.auto_help {
+        usage.push_str(" [options]");
+    }
+    for decl in spec.decls.iter().filter(|d| d.positional) {
+        usage.push(' ');
+        usage.push_str(&decl.display_name());
+    }
+    usage
+}
+
+fn help_text(spec: &Spec) -> String {
+    let mut rows: Vec<(String, String, bool)> = Vec::new();
+    for decl in &spec.decls {
+        let mut left = if decl.positional {
+            decl.display_name()
+        } else {
+            let short = decl.short.map(|c| format!("-{}", c));
+            let long = decl.long.as_ref().map(|l| format!("--{}", l));
+            match (short, long) {
+                (Some(s), Some(l)) => format!("{}, {}", s, l),
+                (Some(s), None) => s,
+                (None, Some(l)) => format!("    {}", l),
+                (None, None) => unreachable!("options have a name"),
+            }
+        };
+        if !decl.positional && decl.takes_value() {
+            left.push_str(&format!(" <{}>", decl.ty.name()));
+            if decl.many {
+                left.push_str("...");
+            }
+        }
+        let mut right = decl.help.clone();
+        if let Some(default) = &decl.default_text {
+            if !right.is_empty() {
+                right.push(' ');
+            }
+            right.push_str(&format!("(default: {})", default));
+        }
+        rows.push((left, right, decl.positional));
+    }
+    if spec.auto_help {
+        rows.push((
+            "-h, --help".to_string(),
+            "Show this help".to_string(),
+            false,
+        ));
+    }
+
+    let width = rows
+        .iter()
+        .map(|(l, _, _)| l.chars().count())
+        .max()
+        .unwrap_or(0);
+    let section = |positional: bool| -> String {
+        rows.iter()
+            .filter(|(_, _, p)| *p == positional)
+            .map(|(left, right, _)| {
+                if right.is_empty() {
+                    format!("  {}\n", left)
+                } else {
+                    format!("  {:width$}  {}\n", left, right, width = width)
+                }
+            })
+            .collect()
+    };
+
+    let mut text = usage_line(spec);
+    text.push('\n');
+    let arguments = section(true);
+    if !arguments.is_empty() {
+        text.push_str("\nArguments:\n");
+        text.push_str(&arguments);
+    }
+    let options = section(false);
+    if !options.is_empty() {
+        text.push_str("\nOptions:\n");
+        text.push_str(&options);
+    }
+    text
+}
+
+// ============================================================================
+// Argument parsing
+// ============================================================================
+
+enum Outcome {
+    Parsed(AtlasHashMap),
+    Help,
+}
+
+fn parse_argv(spec: &Spec, argv: &[String]) -> Result<Outcome, String> {
+    let mut values: Vec<Option<Value>> = spec.decls.iter().map(|_| None).collect();
+    let mut positionals: Vec<&str> = Vec::new();
+
+    let set = |values: &mut Vec<Option<Value>>, i: usize, text: &str| -> Result<(), String> {
+        let decl = &spec.decls[i];
+        let value = convert(text, decl.ty).map_err(|_| {
+            format!(
+                "{} expects a {}, got '{}'",
+                decl.display_name(),
+                decl.ty.name(),
+                text
+            )
+        })?;
+        if decl.many {
+            let mut items = match values[i].take() {
+                Some(Value::Array(items)) => items.as_slice().to_vec(),
+                _ => Vec::new(),
+            };
+            items.push(value);
+            values[i] = Some(Value::array(items));
+        } else {
+            values[i] = Some(value);
+        }
+        Ok(())
+    };
+
+    let mut iter = argv.iter().map(String::as_str);
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            positionals.extend(iter.by_ref());
+            break;
+        }
+        if let Some(body) = arg.strip_prefix("--") {
+            let (name, inline) = match body.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (body, None),
+            };
+            if spec.auto_help && name == "help" {
+                return Ok(Outcome::Help);
+            }
+            let found = spec
+                .decls
+                .iter()
+                .position(|d| d.long.as_deref() == Some(name));
+            let negated = name.strip_prefix("no-").and_then(|base| {
+                spec.decls
+                    .iter()
+                    .position(|d| d.long.as_deref() == Some(base) && !d.takes_value())
+            });
+            match (found, negated) {
+                (Some(i), _) => {
+                    if spec.decls[i].takes_value() {
+                        let value = match inline {
+                            Some(value) => value,
+                            None => iter
+                                .next()
+                                .ok_or_else(|| format!("--{} requires a value", name))?,
+                        };
+                        set(&mut values, i, value)?;
+                    } else {
+                        set(&mut values, i, inline.unwrap_or("true"))?;
+                    }
+                }
+                (None, Some(i)) if inline.is_none() => values[i] = Some(Value::Bool(false)),
+                _ => return Err(format!("unknown option '--{}'", name)),
+            }
+        } else if arg.len() > 1 && arg.starts_with('-') && !looks_numeric(arg) {
+            let body = &arg[1..];
+            let (flags, inline) = match body.split_once('=') {
+                Some((flags, value)) => (flags, Some(value)),
+                None => (body, None),
+            };
+            for (pos, c) in flags.char_indices() {
+                if spec.auto_help && c == 'h' {
+                    return Ok(Outcome::Help);
+                }
+                let i = spec
+                    .decls
+                    .iter()
+                    .position(|d| d.short == Some(c))
+                    .ok_or_else(|| format!("unknown option '-{}'", c))?;
+                if !spec.decls[i].takes_value() {
+                    set(&mut values, i, "true")?;
+                    continue;
+                }
+                // A value option ends the bundle: `-p8080`, `-p=8080`, `-p 8080`
+                let rest = &flags[pos + c.len_utf8()..];
+                let value = if !rest.is_empty() {
+                    rest
+                } else if let Some(value) = inline {
+                    value
+                } else {
+                    iter.next()
+                        .ok_or_else(|| format!("-{} requires a value", c))?
+                };
+                set(&mut values, i, value)?;
+                break;
+            }
+        } else {
+            positionals.push(arg);
+        }
+    }
+
+    let mut rest = positionals.into_iter();
+    for (i, decl) in spec.decls.iter().enumerate().filter(|(_, d)| d.positional) {
+        if decl.many {
+            for arg in rest.by_ref() {
+                set(&mut values, i, arg)?;
+            }
+        } else if let Some(arg) = rest.next() {
+            set(&mut values, i, arg)?;
+        } else if decl.is_required() {
+            return Err(format!("missing required argument {}", decl.display_name()));
+        }
+    }
+    if let Some(extra) = rest.next() {
+        return Err(format!("unexpected argument '{}'", extra));
+    }
+
+    let mut map = AtlasHashMap::with_capacity(spec.decls.len());
+    for (decl, value) in spec.decls.iter().zip(values) {
+        let value = value.or_else(|| decl.default.clone()).unwrap_or_else(|| {
+            if decl.many {
+                Value::array(Vec::new())
+            } else if decl.ty == ArgType::Bool {
+                Value::Bool(false)
+            } else {
+                Value::Null
+            }
+        });
+        map.insert(HashKey::String(Arc::new(decl.key.clone())), value);
+    }
+    Ok(Outcome::Parsed(map))
+}
+
+/// `-5` and `-.5` are values, not options
+fn looks_numeric(arg: &str) -> bool {
+    arg.parse::<f64>().is_ok()
+}
+
+pub(super) fn string_list(
+    value: &Value,
+    func: &str,
+    span: Span,
+) -> Result<Vec<String>, RuntimeError> {
+    match value {
+        Value::Array(arr) => arr
+            .as_slice()
+            .iter()
+            .map(|v| match v {
+                Value::String(s) => Ok(s.as_ref().clone()),
+                other => Err(stdlib_arg_error(func, "string[]", other, span)),
+            })
+            .collect(),
+        other => Err(stdlib_arg_error(func, "string[]", other, span)),
+    }
+}
+
+/// Parse command-line arguments against a spec.
+///
+/// Atlas signature: `process.parseArgs(spec: string[], argv?: string[]) -> Result<record, string>`
+///
+/// `argv` defaults to `process.args()`.
+pub fn parse_args(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+    output: &OutputWriter,
+) -> Result<Value, RuntimeError> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(stdlib_arity_error("process.parseArgs", 1, args.len(), span));
+    }
+    let spec = parse_spec(&args[0], span)?;
+    let argv = match args.get(1) {
+        Some(argv) => string_list(argv, "process.parseArgs", span)?,
+        None => string_list(
+            &super::process::get_process_args(&[], span, security)?,
+            "process.parseArgs",
+            span,
+        )?,
+    };
+
+    match parse_argv(&spec, &argv) {
+        Ok(Outcome::Parsed(map)) => Ok(Value::Result(Ok(Box::new(Value::Map(
+            ValueHashMap::from_atlas(map),
+        ))))),
+        Ok(Outcome::Help) => {
+            use std::io::Write;
+            if let Ok(mut w) = output.lock() {
+                let _ = w.write_all(help_text(&spec).as_bytes());
+                let _ = w.flush();
+            }
+            security.temp_root().cleanup();
+            std::process::exit(0);
+        }
+        Err(msg) => Ok(Value::Result(Err(Box::new(Value::string(format!(
+            "error: {}\n\n{}\n\nFor more information, try '--help'.",
+            msg,
+            usage_line(&spec)
+        )))))),
+    }
+}
+
+/// Help text generated from a spec, as `--help` prints it.
+///
+/// Atlas signature: `process.argsHelp(spec: string[]) -> string`
+pub fn args_help(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error("process.argsHelp", 1, args.len(), span));
+    }
+    let spec = parse_spec(&args[0], span)?;
+    Ok(Value::string(help_text(&spec)))
+}
@@ -212,6 +212,36 @@ pub fn enumerate(arr: &[Value]) -> Value {
     Value::array(pairs)
 }
 
+// ============================================================================
+// Construction
+// ============================================================================
+
+/// Largest array `Array.range` will build
+const MAX_RANGE_LEN: f64 = 10_000_000.0;
+
+/// Numbers from `start` up to (not including) `end`, `step` apart
+///
+/// `Array.range(0, 10, 3)` → `[0, 3, 6, 9]`. A negative step counts down:
+/// `Array.range(5, 0, -2)` → `[5, 3, 1]`. Step defaults to 1.
+pub fn range(start: f64, end: f64, step: f64, span: Span) -> Result<Value, RuntimeError> {
+    if step == 0.0 || !step.is_finite() || !start.is_finite() || !end.is_finite() {
+        return Err(RuntimeError::TypeError {
+            msg: "Array.range() requires finite bounds and a non-zero step".to_string(),
+            span,
+        });
+    }
+
+    let len = ((end - start) / step).ceil().max(0.0);
+    if len > MAX_RANGE_LEN {
+        return Err(RuntimeError::InvalidNumericResult { span });
+    }
+
+    let values = (0..len as usize)
+        .map(|i| Value::Number(start + i as f64 * step))
+        .collect();
+    Ok(Value::array(values))
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
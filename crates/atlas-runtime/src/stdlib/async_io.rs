@@ -7,11 +7,16 @@ use super::{stdlib_arg_error, stdlib_arity_error};
 use crate::async_runtime::{block_on, AtlasFuture};
 use crate::security::SecurityContext;
 use crate::span::Span;
-use crate::stdlib::http::{HttpRequest, HttpResponse};
+#[cfg(feature = "http")]
+use crate::stdlib::http::{
+    checked_redirects, extract_host, redirect_denied, HttpRequest, HttpResponse,
+};
 use crate::value::{RuntimeError, Value};
+#[cfg(feature = "http")]
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+#[cfg(feature = "http")]
 use std::time::Duration;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
@@ -469,7 +474,7 @@ pub fn copy_file_async(
 }
 
 // ============================================================================
-// Async HTTP Operations
+// Async HTTP Operations (feature = "http")
 // ============================================================================
 
 /// Execute HTTP request asynchronously
@@ -481,7 +486,12 @@ pub fn copy_file_async(
 ///
 /// Uses reqwest's async client for non-blocking network I/O.
 /// Supports all HTTP methods, headers, body, timeout, and redirects.
-pub fn http_send_async(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+#[cfg(feature = "http")]
+pub fn http_send_async(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(RuntimeError::TypeError {
             msg: "httpSendAsync: expected 1 argument (request)".to_string(),
@@ -499,6 +509,21 @@ pub fn http_send_async(args: &[Value], span: Span) -> Result<Value, RuntimeError
         }
     };
 
+    // Network permission is checked synchronously, like the blocking client
+    let url = request.build_url();
+    let host = extract_host(&url).ok_or_else(|| RuntimeError::TypeError {
+        msg: format!("httpSendAsync: URL has no host: {}", url),
+        span,
+    })?;
+    security
+        .check_network(&host)
+        .map_err(|_| RuntimeError::NetworkPermissionDenied { host, span })?;
+    let redirects = if request.follow_redirects() {
+        checked_redirects(request.max_redirects() as usize, security)
+    } else {
+        reqwest::redirect::Policy::none()
+    };
+
     let future = AtlasFuture::new_pending();
     let future_clone = future.clone();
 
@@ -506,11 +531,7 @@ pub fn http_send_async(args: &[Value], span: Span) -> Result<Value, RuntimeError
         // Build reqwest client (async version)
         let client = match reqwest::Client::builder()
             .timeout(Duration::from_secs(request.timeout_secs()))
-            .redirect(if request.follow_redirects() {
-                reqwest::redirect::Policy::limited(request.max_redirects() as usize)
-            } else {
-                reqwest::redirect::Policy::none()
-            })
+            .redirect(redirects)
             .build()
         {
             Ok(c) => c,
@@ -556,7 +577,9 @@ pub fn http_send_async(args: &[Value], span: Span) -> Result<Value, RuntimeError
         let response = match req_builder.send().await {
             Ok(r) => r,
             Err(e) => {
-                let error_msg = if e.is_timeout() {
+                let error_msg = if let Some(host) = redirect_denied(&e) {
+                    format!("Permission denied: network access to {}", host)
+                } else if e.is_timeout() {
                     format!("Request timeout after {} seconds", request.timeout_secs())
                 } else if e.is_connect() {
                     format!("Connection error: {}", e)
@@ -604,7 +627,12 @@ pub fn http_send_async(args: &[Value], span: Span) -> Result<Value, RuntimeError
 /// - url: string
 ///
 /// Returns: Future<HttpResponse>
-pub fn http_get_async(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+#[cfg(feature = "http")]
+pub fn http_get_async(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(RuntimeError::TypeError {
             msg: "httpGetAsync: expected 1 argument (url)".to_string(),
@@ -624,7 +652,7 @@ pub fn http_get_async(args: &[Value], span: Span) -> Result<Value, RuntimeError>
 
     // Create GET request
     let request = HttpRequest::new("GET".to_string(), url);
-    http_send_async(&[Value::HttpRequest(Arc::new(request))], span)
+    http_send_async(&[Value::HttpRequest(Arc::new(request))], span, security)
 }
 
 /// POST request asynchronously
@@ -634,7 +662,12 @@ pub fn http_get_async(args: &[Value], span: Span) -> Result<Value, RuntimeError>
 /// - body: string
 ///
 /// Returns: Future<HttpResponse>
-pub fn http_post_async(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+#[cfg(feature = "http")]
+pub fn http_post_async(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
     if args.len() != 2 {
         return Err(RuntimeError::TypeError {
             msg: "httpPostAsync: expected 2 arguments (url, body)".to_string(),
@@ -663,7 +696,7 @@ pub fn http_post_async(args: &[Value], span: Span) -> Result<Value, RuntimeError
     };
 
     let request = HttpRequest::new("POST".to_string(), url).with_body(body);
-    http_send_async(&[Value::HttpRequest(Arc::new(request))], span)
+    http_send_async(&[Value::HttpRequest(Arc::new(request))], span, security)
 }
 
 /// PUT request asynchronously
@@ -673,7 +706,12 @@ pub fn http_post_async(args: &[Value], span: Span) -> Result<Value, RuntimeError
 /// - body: string
 ///
 /// Returns: Future<HttpResponse>
-pub fn http_put_async(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+#[cfg(feature = "http")]
+pub fn http_put_async(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
     if args.len() != 2 {
         return Err(RuntimeError::TypeError {
             msg: "httpPutAsync: expected 2 arguments (url, body)".to_string(),
@@ -702,7 +740,7 @@ pub fn http_put_async(args: &[Value], span: Span) -> Result<Value, RuntimeError>
     };
 
     let request = HttpRequest::new("PUT".to_string(), url).with_body(body);
-    http_send_async(&[Value::HttpRequest(Arc::new(request))], span)
+    http_send_async(&[Value::HttpRequest(Arc::new(request))], span, security)
 }
 
 /// DELETE request asynchronously
@@ -711,7 +749,12 @@ pub fn http_put_async(args: &[Value], span: Span) -> Result<Value, RuntimeError>
 /// - url: string
 ///
 /// Returns: Future<HttpResponse>
-pub fn http_delete_async(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+#[cfg(feature = "http")]
+pub fn http_delete_async(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(RuntimeError::TypeError {
             msg: "httpDeleteAsync: expected 1 argument (url)".to_string(),
@@ -730,7 +773,7 @@ pub fn http_delete_async(args: &[Value], span: Span) -> Result<Value, RuntimeErr
     };
 
     let request = HttpRequest::new("DELETE".to_string(), url);
-    http_send_async(&[Value::HttpRequest(Arc::new(request))], span)
+    http_send_async(&[Value::HttpRequest(Arc::new(request))], span, security)
 }
 
 // ============================================================================
@@ -739,9 +782,7 @@ pub fn http_delete_async(args: &[Value], span: Span) -> Result<Value, RuntimeErr
 
 /// Poll a future to drive it to completion (for testing/development)
 ///
-/// This is a utility function to help work with pending futures in the
-/// current synchronous execution model. Future phases will provide proper
-/// async/await syntax.
+/// Blocks until a pending future is settled by the task or timer that owns it.
 ///
 /// Args:
 /// - future: Future
@@ -765,22 +806,16 @@ pub fn await_future(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
         }
     };
 
-    // Check the future state - with synchronous primitives, futures should
-    // already be resolved when await is called
-    match future.get_state() {
-        crate::async_runtime::FutureState::Resolved(value) => Ok(value),
-        crate::async_runtime::FutureState::Rejected(error) => Err(RuntimeError::TypeError {
+    // Pending futures park this thread until their task or timer settles them
+    match crate::async_runtime::wait_for(&future) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(error)) => Err(RuntimeError::TypeError {
             msg: format!("Future rejected: {}", error),
             span,
         }),
-        crate::async_runtime::FutureState::Pending => {
-            // With synchronous primitives, this shouldn't happen
-            // but handle it gracefully
-            Err(RuntimeError::TypeError {
-                msg: "Future is still pending (async operations are not yet fully supported)"
-                    .to_string(),
-                span,
-            })
-        }
+        Err(reason) => Err(RuntimeError::TypeError {
+            msg: format!("Future is still pending: {}", reason),
+            span,
+        }),
     }
 }
@@ -609,6 +609,9 @@ pub fn async_mutex_new(args: &[Value], span: Span) -> Result<Value, RuntimeError
 
     let value = args[0].clone();
     let mutex = Arc::new(tokio::sync::Mutex::new(value));
+    // A mutex can end up holding itself (asyncMutexSet(m, [m])); let the
+    // cycle collector see it.
+    crate::vm::gc::track_async_mutex(&mutex);
 
     Ok(Value::AsyncMutex(mutex))
 }
@@ -0,0 +1,121 @@
+//! Byte buffer functions for Atlas stdlib.
+//!
+//! `Bytes` values hold raw binary data — file contents read with
+//! `file.readBytes`, or data decoded with `encoding.fromHex` / `encoding.fromBase64`.
+//! They are immutable: `slice` returns a new buffer.
+
+use super::{stdlib_arg_error, stdlib_arity_error};
+use crate::span::Span;
+use crate::value::{RuntimeError, Value};
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine as _;
+use std::sync::Arc;
+
+/// Wrap raw bytes in a `Bytes` value
+pub fn bytes_value(data: Vec<u8>) -> Value {
+    Value::Bytes(Arc::new(data))
+}
+
+/// bytes.len() -> number
+pub fn bytes_len(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error("bytes.len", 1, args.len(), span));
+    }
+    let data = extract_bytes(&args[0], "bytes.len", span)?;
+    Ok(Value::Number(data.len() as f64))
+}
+
+/// bytes.slice(start, end) -> Bytes
+///
+/// Indices are clamped to the buffer like `arr.slice`; `start > end` is an error.
+pub fn bytes_slice(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 3 {
+        return Err(stdlib_arity_error("bytes.slice", 3, args.len(), span));
+    }
+    let data = extract_bytes(&args[0], "bytes.slice", span)?;
+    let start = extract_index(&args[1], "bytes.slice", span)?.min(data.len());
+    let end = extract_index(&args[2], "bytes.slice", span)?.min(data.len());
+    if start > end {
+        return Err(RuntimeError::InvalidStdlibArgument {
+            msg: format!("bytes.slice(): start {} > end {}", start, end),
+            span,
+        });
+    }
+    Ok(bytes_value(data[start..end].to_vec()))
+}
+
+/// bytes.toHex() -> string (lowercase)
+pub fn bytes_to_hex(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error("bytes.toHex", 1, args.len(), span));
+    }
+    let data = extract_bytes(&args[0], "bytes.toHex", span)?;
+    Ok(Value::string(hex::encode(data)))
+}
+
+/// bytes.toBase64() -> string (standard alphabet, padded)
+pub fn bytes_to_base64(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error("bytes.toBase64", 1, args.len(), span));
+    }
+    let data = extract_bytes(&args[0], "bytes.toBase64", span)?;
+    Ok(Value::string(BASE64_STANDARD.encode(data)))
+}
+
+/// bytes.toArray() -> number[] (one number 0-255 per byte)
+pub fn bytes_to_array(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error("bytes.toArray", 1, args.len(), span));
+    }
+    let data = extract_bytes(&args[0], "bytes.toArray", span)?;
+    Ok(Value::array(
+        data.iter().map(|b| Value::Number(*b as f64)).collect(),
+    ))
+}
+
+/// bytes.toUtf8() -> Result<string, string>
+pub fn bytes_to_utf8(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error("bytes.toUtf8", 1, args.len(), span));
+    }
+    let data = extract_bytes(&args[0], "bytes.toUtf8", span)?;
+    Ok(match std::str::from_utf8(data) {
+        Ok(s) => Value::Result(Ok(Box::new(Value::string(s)))),
+        Err(e) => Value::Result(Err(Box::new(Value::string(format!(
+            "bytes.toUtf8: invalid UTF-8: {}",
+            e
+        ))))),
+    })
+}
+
+/// string.toBytes() -> Bytes (UTF-8 encoding)
+pub fn string_to_bytes(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error("string.toBytes", 1, args.len(), span));
+    }
+    match &args[0] {
+        Value::String(s) => Ok(bytes_value(s.as_bytes().to_vec())),
+        other => Err(stdlib_arg_error("string.toBytes", "string", other, span)),
+    }
+}
+
+// ── Helpers ──────────────────────────────────────────────────────────
+
+fn extract_bytes<'a>(
+    value: &'a Value,
+    func_name: &str,
+    span: Span,
+) -> Result<&'a [u8], RuntimeError> {
+    match value {
+        Value::Bytes(b) => Ok(b.as_slice()),
+        other => Err(stdlib_arg_error(func_name, "Bytes", other, span)),
+    }
+}
+
+/// A byte offset; like `arr.slice`, fractions truncate and negatives clamp to 0
+fn extract_index(value: &Value, func_name: &str, span: Span) -> Result<usize, RuntimeError> {
+    match value {
+        Value::Number(n) => Ok((*n as i64).max(0) as usize),
+        other => Err(stdlib_arg_error(func_name, "number", other, span)),
+    }
+}
@@ -0,0 +1,543 @@
+//! Layered configuration loading
+//!
+//! - process.loadConfig(spec) -> Result<record, string>
+//! - process.loadConfig(spec, options) -> Result<record, string>
+//!
+//! A spec is a `string[]` with one declaration per setting, in the same
+//! shape `process.parseArgs` uses for options, minus the dashes:
+//!
+//! ```text
+//! "region: string = us-east-1   # Deployment region"
+//! "replicas: number = 2"
+//! "token: string                # Required: no default"
+//! "dry-run: bool"
+//! "tags?: string[]"
+//! ```
+//!
+//! Each setting is resolved from these sources, later ones winning:
+//!
+//! 1. the spec default
+//! 2. `[tool.<name>]` in the nearest `atlas.toml`
+//! 3. the config file from `options.file` (`.json`, anything else is TOML)
+//! 4. the environment variable `<PREFIX>_<SETTING>` (`DEPLOY_DRY_RUN`)
+//! 5. the command-line flag `--<setting>` (`--dry-run`, `--no-dry-run`)
+//!
+//! Options (all optional): `name` (defaults to the program name), `file`,
+//! `envPrefix` (defaults to the upper-cased name) and `args` (defaults to
+//! `process.args()`). Flags that are not settings are left alone so the
+//! same argv can also go through `process.parseArgs`.
+//!
+//! Values are validated against the declared type in every layer. Bool
+//! settings default to `false` and lists to `[]`; other settings without a
+//! default are required unless marked `?`. A bad spec is a runtime error;
+//! a bad or missing value is an `Err` naming the source it came from.
+
+use super::args::{camel_case, convert, program_name, string_list, unquote, ArgType};
+use super::{stdlib_arg_error, stdlib_arity_error};
+use crate::security::SecurityContext;
+use crate::span::Span;
+use crate::stdlib::collections::hash::HashKey;
+use crate::stdlib::collections::hashmap::AtlasHashMap;
+use crate::value::{RuntimeError, Value, ValueHashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// One declaration from the spec
+#[derive(Debug)]
+struct Setting {
+    /// Name as written in the spec (`dry-run`)
+    name: String,
+    /// Field name in the result record (`dryRun`)
+    key: String,
+    ty: ArgType,
+    many: bool,
+    optional: bool,
+    default: Option<Value>,
+}
+
+impl Setting {
+    fn type_name(&self) -> String {
+        if self.many {
+            format!("{}[]", self.ty.name())
+        } else {
+            self.ty.name().to_string()
+        }
+    }
+
+    /// Whether `key` (from a file) refers to this setting. `dry-run`,
+    /// `dry_run` and `dryRun` are all accepted.
+    fn matches(&self, key: &str) -> bool {
+        key == self.key || key.replace('_', "-") == self.name.replace('_', "-")
+    }
+
+    fn env_var(&self, prefix: &str) -> String {
+        let name = self.name.replace('-', "_").to_uppercase();
+        if prefix.is_empty() {
+            name
+        } else {
+            format!("{}_{}", prefix, name)
+        }
+    }
+
+    fn flag(&self) -> String {
+        format!("--{}", self.name.replace('_', "-"))
+    }
+}
+
+struct Options {
+    name: String,
+    file: Option<PathBuf>,
+    env_prefix: String,
+    args: Vec<String>,
+}
+
+// ============================================================================
+// Spec and options
+// ============================================================================
+
+fn parse_spec(value: &Value, span: Span) -> Result<Vec<Setting>, RuntimeError> {
+    let lines = string_list(value, "process.loadConfig", span)?;
+    let mut settings: Vec<Setting> = Vec::with_capacity(lines.len());
+    for line in &lines {
+        let setting = parse_setting(line).map_err(|msg| RuntimeError::TypeError {
+            msg: format!("process.loadConfig: invalid spec: {}", msg),
+            span,
+        })?;
+        if settings.iter().any(|s| s.key == setting.key) {
+            return Err(RuntimeError::TypeError {
+                msg: format!(
+                    "process.loadConfig: invalid spec: '{}' is declared twice",
+                    setting.name
+                ),
+                span,
+            });
+        }
+        settings.push(setting);
+    }
+    Ok(settings)
+}
+
+/// Parse `name[?] [: type] [= default] [# help]`
+fn parse_setting(line: &str) -> Result<Setting, String> {
+    let decl = line.split_once('#').map_or(line, |(decl, _)| decl);
+    let (decl, default_text) = match decl.split_once('=') {
+        Some((decl, default)) => (decl, Some(unquote(default.trim()).to_string())),
+        None => (decl, None),
+    };
+    let (name, ty_text) = match decl.split_once(':') {
+        Some((name, ty)) => (name.trim(), Some(ty.trim())),
+        None => (decl.trim(), None),
+    };
+    let (name, optional) = match name.strip_suffix('?') {
+        Some(name) => (name, true),
+        None => (name, false),
+    };
+    let valid = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("bad setting name '{}' in '{}'", name, line.trim()));
+    }
+
+    let text = ty_text.unwrap_or("string");
+    let (base, many) = match text.strip_suffix("[]") {
+        Some(base) => (base, true),
+        None => (text, false),
+    };
+    let ty = match base {
+        "string" => ArgType::String,
+        "number" => ArgType::Number,
+        "bool" if !many => ArgType::Bool,
+        _ => return Err(format!("unknown type '{}' in '{}'", text, line.trim())),
+    };
+
+    let mut setting = Setting {
+        name: name.to_string(),
+        key: camel_case(&name.replace('_', "-")),
+        ty,
+        many,
+        optional,
+        default: None,
+    };
+    if let Some(text) = default_text {
+        let default = parse_text(&text, &setting)
+            .map_err(|_| format!("default '{}' is not a {}", text, setting.type_name()))?;
+        setting.default = Some(default);
+    }
+    Ok(setting)
+}
+
+/// Convert text from the spec or the environment; lists are comma-separated
+fn parse_text(text: &str, setting: &Setting) -> Result<Value, ()> {
+    if setting.many {
+        let items: Result<Vec<Value>, ()> = text
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| convert(item, setting.ty))
+            .collect();
+        items.map(Value::array)
+    } else {
+        convert(text, setting.ty)
+    }
+}
+
+fn parse_options(
+    value: Option<&Value>,
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Options, RuntimeError> {
+    let mut options = Options {
+        name: program_name(),
+        file: None,
+        env_prefix: String::new(),
+        args: Vec::new(),
+    };
+    let mut env_prefix = None;
+    let mut args = None;
+    match value {
+        None => {}
+        Some(Value::Map(map)) => {
+            for (key, value) in map.entries() {
+                let field = match &key {
+                    HashKey::String(s) => s.as_str(),
+                    _ => "",
+                };
+                let text = match &value {
+                    Value::String(s) => Some(s.as_ref().clone()),
+                    _ => None,
+                };
+                match (field, text) {
+                    ("name", Some(name)) => options.name = name,
+                    ("file", Some(file)) => options.file = Some(PathBuf::from(file)),
+                    ("envPrefix", Some(prefix)) => env_prefix = Some(prefix),
+                    ("args", _) => args = Some(string_list(&value, "process.loadConfig", span)?),
+                    ("name" | "file" | "envPrefix", None) => {
+                        return Err(RuntimeError::TypeError {
+                            msg: format!(
+                                "process.loadConfig: option '{}' must be a string, got {}",
+                                field,
+                                value.type_name()
+                            ),
+                            span,
+                        })
+                    }
+                    _ => {
+                        return Err(RuntimeError::TypeError {
+                            msg: format!("process.loadConfig: unknown option '{}'", field),
+                            span,
+                        })
+                    }
+                }
+            }
+        }
+        Some(other) => {
+            return Err(stdlib_arg_error(
+                "process.loadConfig",
+                "record",
+                other,
+                span,
+            ))
+        }
+    }
+    options.env_prefix =
+        env_prefix.unwrap_or_else(|| options.name.replace('-', "_").to_uppercase());
+    options.args = match args {
+        Some(args) => args,
+        None => {
+            let argv = super::process::get_process_args(&[], span, security)?;
+            string_list(&argv, "process.loadConfig", span)?
+        }
+    };
+    Ok(options)
+}
+
+// ============================================================================
+// Layers
+// ============================================================================
+
+/// Apply a table from atlas.toml or a config file. Unknown keys are errors
+/// so that typos do not silently fall back to defaults.
+fn apply_table(
+    settings: &[Setting],
+    values: &mut [Option<Value>],
+    table: &serde_json::Map<String, serde_json::Value>,
+    source: &str,
+) -> Result<(), String> {
+    for (key, json) in table {
+        let i = settings
+            .iter()
+            .position(|s| s.matches(key))
+            .ok_or_else(|| format!("{}: unknown setting '{}'", source, key))?;
+        let setting = &settings[i];
+        let value = from_json(json, setting).ok_or_else(|| {
+            format!(
+                "{}: '{}' expects a {}, got {}",
+                source,
+                key,
+                setting.type_name(),
+                json_type_name(json)
+            )
+        })?;
+        values[i] = Some(value);
+    }
+    Ok(())
+}
+
+fn from_json(json: &serde_json::Value, setting: &Setting) -> Option<Value> {
+    let scalar = |json: &serde_json::Value| match (json, setting.ty) {
+        (serde_json::Value::String(s), ArgType::String) => Some(Value::string(s.clone())),
+        (serde_json::Value::Number(n), ArgType::Number) => n.as_f64().map(Value::Number),
+        (serde_json::Value::Bool(b), ArgType::Bool) => Some(Value::Bool(*b)),
+        _ => None,
+    };
+    if setting.many {
+        match json {
+            serde_json::Value::Array(items) => items
+                .iter()
+                .map(scalar)
+                .collect::<Option<Vec<_>>>()
+                .map(Value::array),
+            _ => None,
+        }
+    } else {
+        scalar(json)
+    }
+}
+
+fn json_type_name(json: &serde_json::Value) -> &'static str {
+    match json {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "bool",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "table",
+    }
+}
+
+/// Read a TOML (or, by extension, JSON) file into a JSON object
+fn read_table(path: &Path) -> Result<serde_json::Map<String, serde_json::Value>, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let json = if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))?
+    } else {
+        let table: toml::Table =
+            toml::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+        serde_json::to_value(table).map_err(|e| format!("{}: {}", path.display(), e))?
+    };
+    match json {
+        serde_json::Value::Object(map) => Ok(map),
+        _ => Err(format!(
+            "{}: expected a table at the top level",
+            path.display()
+        )),
+    }
+}
+
+/// `[tool.<name>]` from the nearest atlas.toml, if there is one the
+/// security context lets us read
+fn manifest_layer(
+    settings: &[Setting],
+    values: &mut [Option<Value>],
+    name: &str,
+    security: &SecurityContext,
+) -> Result<(), String> {
+    let Ok(cwd) = std::env::current_dir() else {
+        return Ok(());
+    };
+    let Some(manifest) = cwd
+        .ancestors()
+        .map(|d| d.join("atlas.toml"))
+        .find(|p| p.is_file())
+    else {
+        return Ok(());
+    };
+    if security.check_filesystem_read(&manifest).is_err() {
+        return Ok(());
+    }
+    let table = read_table(&manifest)?;
+    match table.get("tool").and_then(|tool| tool.get(name)) {
+        Some(serde_json::Value::Object(section)) => apply_table(
+            settings,
+            values,
+            section,
+            &format!("{} [tool.{}]", manifest.display(), name),
+        ),
+        Some(_) => Err(format!(
+            "{}: [tool.{}] must be a table",
+            manifest.display(),
+            name
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Environment variables the security context allows us to read
+fn env_layer(
+    settings: &[Setting],
+    values: &mut [Option<Value>],
+    prefix: &str,
+    security: &SecurityContext,
+) -> Result<(), String> {
+    for (i, setting) in settings.iter().enumerate() {
+        let var = setting.env_var(prefix);
+        if security.check_environment(&var).is_err() {
+            continue;
+        }
+        if let Ok(text) = std::env::var(&var) {
+            let value = parse_text(&text, setting).map_err(|_| {
+                format!(
+                    "environment variable {} expects a {}, got '{}'",
+                    var,
+                    setting.type_name(),
+                    text
+                )
+            })?;
+            values[i] = Some(value);
+        }
+    }
+    Ok(())
+}
+
+/// `--setting value`, `--setting=value`, `--flag`, `--no-flag`; list settings
+/// collect every occurrence. Anything else is skipped.
+fn flag_layer(
+    settings: &[Setting],
+    values: &mut [Option<Value>],
+    argv: &[String],
+) -> Result<(), String> {
+    let mut collected: Vec<Option<Vec<Value>>> = settings.iter().map(|_| None).collect();
+    let mut iter = argv.iter().map(String::as_str);
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            break;
+        }
+        let Some(body) = arg.strip_prefix("--") else {
+            continue;
+        };
+        let (name, inline) = match body.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (body, None),
+        };
+        let flag = format!("--{}", name);
+        if let Some(i) = settings.iter().position(|s| s.flag() == flag) {
+            let setting = &settings[i];
+            let text = match (inline, setting.ty) {
+                (Some(text), _) => text,
+                (None, ArgType::Bool) => "true",
+                (None, _) => iter
+                    .next()
+                    .ok_or_else(|| format!("{} requires a value", flag))?,
+            };
+            let value = convert(text, setting.ty)
+                .map_err(|_| format!("{} expects a {}, got '{}'", flag, setting.ty.name(), text))?;
+            if setting.many {
+                collected[i].get_or_insert_with(Vec::new).push(value);
+            } else {
+                values[i] = Some(value);
+            }
+        } else if let Some(base) = name.strip_prefix("no-") {
+            let negated = format!("--{}", base);
+            let found = settings
+                .iter()
+                .position(|s| s.ty == ArgType::Bool && s.flag() == negated);
+            if let (Some(i), None) = (found, inline) {
+                values[i] = Some(Value::Bool(false));
+            }
+        }
+    }
+    for (i, items) in collected.into_iter().enumerate() {
+        if let Some(items) = items {
+            values[i] = Some(Value::array(items));
+        }
+    }
+    Ok(())
+}
+
+fn load(
+    settings: &[Setting],
+    options: &Options,
+    security: &SecurityContext,
+    span: Span,
+) -> Result<Result<AtlasHashMap, String>, RuntimeError> {
+    let mut values: Vec<Option<Value>> = settings.iter().map(|s| s.default.clone()).collect();
+
+    if let Err(msg) = manifest_layer(settings, &mut values, &options.name, security) {
+        return Ok(Err(msg));
+    }
+    if let Some(file) = &options.file {
+        if file.exists() {
+            let abs = file.canonicalize().unwrap_or_else(|_| file.clone());
+            security.check_filesystem_read(&abs).map_err(|_| {
+                RuntimeError::FilesystemPermissionDenied {
+                    operation: "read".to_string(),
+                    path: abs.display().to_string(),
+                    span,
+                }
+            })?;
+            let layer = read_table(file).and_then(|table| {
+                apply_table(settings, &mut values, &table, &file.display().to_string())
+            });
+            if let Err(msg) = layer {
+                return Ok(Err(msg));
+            }
+        }
+    }
+    if let Err(msg) = env_layer(settings, &mut values, &options.env_prefix, security) {
+        return Ok(Err(msg));
+    }
+    if let Err(msg) = flag_layer(settings, &mut values, &options.args) {
+        return Ok(Err(msg));
+    }
+
+    let mut map = AtlasHashMap::with_capacity(settings.len());
+    for (setting, value) in settings.iter().zip(values) {
+        let value = match value {
+            Some(value) => value,
+            None if setting.many => Value::array(Vec::new()),
+            None if setting.ty == ArgType::Bool => Value::Bool(false),
+            None if setting.optional => Value::Null,
+            None => {
+                return Ok(Err(format!(
+                    "missing required setting '{}' (set it in [tool.{}], {} or {})",
+                    setting.name,
+                    options.name,
+                    setting.env_var(&options.env_prefix),
+                    setting.flag()
+                )))
+            }
+        };
+        map.insert(HashKey::String(Arc::new(setting.key.clone())), value);
+    }
+    Ok(Ok(map))
+}
+
+/// Load settings from defaults, atlas.toml, a config file, the environment
+/// and command-line flags.
+///
+/// Atlas signature: `process.loadConfig(spec: string[], options?: record) -> Result<record, string>`
+pub fn load_config(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(stdlib_arity_error(
+            "process.loadConfig",
+            1,
+            args.len(),
+            span,
+        ));
+    }
+    let settings = parse_spec(&args[0], span)?;
+    let options = parse_options(args.get(1), span, security)?;
+    Ok(Value::Result(
+        match load(&settings, &options, security, span)? {
+            Ok(map) => Ok(Box::new(Value::Map(ValueHashMap::from_atlas(map)))),
+            Err(msg) => Err(Box::new(Value::string(format!("config: {}", msg)))),
+        },
+    ))
+}
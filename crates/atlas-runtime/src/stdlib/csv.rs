@@ -0,0 +1,401 @@
+//! CSV parsing and serialization
+//!
+//! Splitting lines on `,` breaks as soon as a field is quoted. These follow
+//! RFC 4180 instead:
+//! - csv.parse(text, options?) -> Result<string[][], string>
+//! - csv.parseRecords(text, options?) -> Result<Map<string, string>[], string>
+//! - csv.stringify(rows, options?) -> string
+//!
+//! Quoted fields may contain the delimiter, line breaks and doubled quotes
+//! (`""`). Records end at `\n` or `\r\n`; blank lines and a leading byte
+//! order mark are skipped. `parseRecords` takes the first row as the header
+//! and keys every following row by it.
+//!
+//! Options (all optional): `delimiter` (one character, default `","`) for
+//! every function; `columns` (string[]) and `header` (bool, default true)
+//! for `stringify`.
+
+use super::stdlib_arity_error;
+use crate::span::Span;
+use crate::stdlib::collections::hash::HashKey;
+use crate::stdlib::collections::hashmap::AtlasHashMap;
+use crate::value::{format_number, RuntimeError, Value, ValueHashMap};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+// ============================================================================
+// Namespace Functions (csv.*)
+// ============================================================================
+
+/// Parse CSV text into rows of fields.
+///
+/// Atlas signature: `csv.parse(text: string, options?) -> Result<string[][], string>`
+pub fn parse(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    let (text, delimiter) = parse_args("csv.parse", args, span)?;
+    Ok(Value::Result(match parse_rows(text, delimiter) {
+        Ok(rows) => Ok(Box::new(Value::array(
+            rows.into_iter()
+                .map(|(_, fields)| Value::array(fields.into_iter().map(Value::string).collect()))
+                .collect(),
+        ))),
+        Err(msg) => Err(Box::new(Value::string(format!("csv.parse: {}", msg)))),
+    }))
+}
+
+/// Parse CSV text with a header row into maps keyed by column name.
+///
+/// Atlas signature:
+/// `csv.parseRecords(text: string, options?) -> Result<Map<string, string>[], string>`
+/// - Every row must have as many fields as the header
+/// - Header names must be unique
+pub fn parse_records(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    let (text, delimiter) = parse_args("csv.parseRecords", args, span)?;
+    Ok(Value::Result(
+        match parse_rows(text, delimiter).and_then(rows_to_records) {
+            Ok(records) => Ok(Box::new(Value::array(records))),
+            Err(msg) => Err(Box::new(Value::string(format!(
+                "csv.parseRecords: {}",
+                msg
+            )))),
+        },
+    ))
+}
+
+/// Serialize rows to CSV text.
+///
+/// Atlas signature: `csv.stringify(rows: any[], options?) -> string`
+/// - Rows are arrays of fields, or maps written in `columns` order (sorted
+///   keys when `columns` is not given)
+/// - A header row is written for map rows, or for array rows when `columns`
+///   is given, unless `header` is false
+/// - Fields are strings, numbers, bools or null (an empty field)
+/// - Every record ends with `\n`
+pub fn stringify(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    const NAME: &str = "csv.stringify";
+    if args.is_empty() || args.len() > 2 {
+        return Err(stdlib_arity_error(NAME, 1, args.len(), span));
+    }
+    let Value::Array(rows) = &args[0] else {
+        return Err(type_error(
+            NAME,
+            format!("rows must be an array, got {}", args[0].type_name()),
+            span,
+        ));
+    };
+    let options = Options::from_arg(NAME, args.get(1), span)?;
+
+    let rows_are_maps = matches!(rows.get(0), Some(Value::Map(_)));
+    let columns = match options.columns {
+        Some(columns) => Some(columns),
+        None if rows_are_maps => Some(sorted_keys(rows.as_slice())),
+        None => None,
+    };
+
+    let mut out = String::new();
+    if let Some(columns) = columns.as_ref().filter(|_| options.header) {
+        let names: Vec<Value> = columns.iter().map(|c| Value::string(c.as_str())).collect();
+        write_record(&mut out, &names, options.delimiter, span)?;
+    }
+    for (i, row) in rows.iter().enumerate() {
+        match (row, rows_are_maps) {
+            (Value::Array(fields), false) => {
+                write_record(&mut out, fields.as_slice(), options.delimiter, span)?
+            }
+            (Value::Map(map), true) => {
+                let fields: Vec<Value> = columns
+                    .iter()
+                    .flatten()
+                    .map(|c| map.get(&string_key(c)).cloned().unwrap_or(Value::Null))
+                    .collect();
+                write_record(&mut out, &fields, options.delimiter, span)?
+            }
+            (other, _) => {
+                return Err(type_error(
+                    NAME,
+                    format!(
+                        "row {} is {}, expected {} like the first row",
+                        i,
+                        other.type_name(),
+                        if rows_are_maps { "a map" } else { "an array" }
+                    ),
+                    span,
+                ))
+            }
+        }
+    }
+    Ok(Value::string(out))
+}
+
+// ============================================================================
+// Options
+// ============================================================================
+
+struct Options {
+    delimiter: char,
+    columns: Option<Vec<String>>,
+    header: bool,
+}
+
+impl Options {
+    fn from_arg(name: &str, arg: Option<&Value>, span: Span) -> Result<Self, RuntimeError> {
+        let mut options = Options {
+            delimiter: ',',
+            columns: None,
+            header: true,
+        };
+        let Some(arg) = arg else {
+            return Ok(options);
+        };
+        let Value::Map(map) = arg else {
+            return Err(type_error(
+                name,
+                format!("options must be a record, got {}", arg.type_name()),
+                span,
+            ));
+        };
+        if let Some(value) = map.get(&string_key("delimiter")) {
+            let mut chars = match value {
+                Value::String(s) => s.chars(),
+                _ => "".chars(),
+            };
+            options.delimiter = match (chars.next(), chars.next()) {
+                (Some(c), None) if !matches!(c, '"' | '\n' | '\r') => c,
+                _ => {
+                    return Err(RuntimeError::InvalidStdlibArgument {
+                        msg: format!(
+                            "{}(): delimiter must be one character other than a quote or \
+                             line break, got {}",
+                            name, value
+                        ),
+                        span,
+                    })
+                }
+            };
+        }
+        if let Some(value) = map.get(&string_key("columns")) {
+            let columns = match value {
+                Value::Array(items) => items
+                    .iter()
+                    .map(|item| match item {
+                        Value::String(s) => Some(s.as_ref().clone()),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>(),
+                _ => None,
+            };
+            let Some(columns) = columns else {
+                return Err(type_error(
+                    name,
+                    "option 'columns' must be an array of strings".to_string(),
+                    span,
+                ));
+            };
+            options.columns = Some(columns);
+        }
+        match map.get(&string_key("header")) {
+            None => {}
+            Some(Value::Bool(b)) => options.header = *b,
+            Some(other) => {
+                return Err(type_error(
+                    name,
+                    format!("option 'header' must be a bool, got {}", other.type_name()),
+                    span,
+                ))
+            }
+        }
+        Ok(options)
+    }
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// Parsed records paired with the line each one starts on
+type Rows = Vec<(usize, Vec<String>)>;
+
+fn parse_rows(text: &str, delimiter: char) -> Result<Rows, String> {
+    let text = text.strip_prefix('\u{FEFF}').unwrap_or(text);
+    let mut rows = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+
+    while chars.peek().is_some() {
+        let start_line = line;
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        loop {
+            match chars.next() {
+                Some('"') if field.is_empty() && !quoted => {
+                    quoted = true;
+                    loop {
+                        match chars.next() {
+                            Some('"') if chars.peek() == Some(&'"') => {
+                                chars.next();
+                                field.push('"');
+                            }
+                            Some('"') => break,
+                            Some(c) => {
+                                if c == '\n' {
+                                    line += 1;
+                                }
+                                field.push(c);
+                            }
+                            None => {
+                                return Err(format!(
+                                    "unterminated quoted field starting on line {}",
+                                    start_line
+                                ))
+                            }
+                        }
+                    }
+                    match chars.peek() {
+                        None | Some('\n') | Some('\r') => {}
+                        Some(&c) if c == delimiter => {}
+                        Some(&c) => {
+                            return Err(format!(
+                                "unexpected '{}' after closing quote on line {}",
+                                c, line
+                            ))
+                        }
+                    }
+                }
+                Some(c) if c == delimiter => {
+                    fields.push(std::mem::take(&mut field));
+                    quoted = false;
+                }
+                Some('\r') if chars.peek() == Some(&'\n') => {}
+                Some('\n') | None => {
+                    line += 1;
+                    break;
+                }
+                Some(c) => field.push(c),
+            }
+        }
+        // A blank line is not a record
+        if fields.is_empty() && field.is_empty() && !quoted {
+            continue;
+        }
+        fields.push(field);
+        rows.push((start_line, fields));
+    }
+    Ok(rows)
+}
+
+fn rows_to_records(rows: Rows) -> Result<Vec<Value>, String> {
+    let mut rows = rows.into_iter();
+    let Some((_, header)) = rows.next() else {
+        return Ok(Vec::new());
+    };
+    let mut seen = BTreeSet::new();
+    if let Some(dup) = header.iter().find(|name| !seen.insert(name.as_str())) {
+        return Err(format!("duplicate column '{}' in header", dup));
+    }
+    let keys: Vec<HashKey> = header.iter().map(|name| string_key(name)).collect();
+    rows.map(|(line, fields)| {
+        if fields.len() != keys.len() {
+            return Err(format!(
+                "line {} has {} fields, expected {} from the header",
+                line,
+                fields.len(),
+                keys.len()
+            ));
+        }
+        let mut map = AtlasHashMap::with_capacity(keys.len());
+        for (key, field) in keys.iter().zip(fields) {
+            map.insert(key.clone(), Value::string(field));
+        }
+        Ok(Value::Map(ValueHashMap::from_atlas(map)))
+    })
+    .collect()
+}
+
+// ============================================================================
+// Serialization
+// ============================================================================
+
+fn write_record(
+    out: &mut String,
+    fields: &[Value],
+    delimiter: char,
+    span: Span,
+) -> Result<(), RuntimeError> {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push(delimiter);
+        }
+        let text = match field {
+            Value::String(s) => s.as_ref().clone(),
+            Value::Number(n) => format_number(*n),
+            Value::Bool(b) => b.to_string(),
+            Value::Null => String::new(),
+            other => {
+                return Err(type_error(
+                    "csv.stringify",
+                    format!("cannot write {} as a CSV field", other.type_name()),
+                    span,
+                ))
+            }
+        };
+        // A lone empty field is quoted so the record is not read back as a blank line
+        if text.contains([delimiter, '"', '\n', '\r']) || (fields.len() == 1 && text.is_empty()) {
+            out.push('"');
+            out.push_str(&text.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(&text);
+        }
+    }
+    out.push('\n');
+    Ok(())
+}
+
+/// Every string key across map rows, sorted so the column order is stable
+fn sorted_keys(rows: &[Value]) -> Vec<String> {
+    let mut keys = BTreeSet::new();
+    for row in rows {
+        if let Value::Map(map) = row {
+            for key in map.keys() {
+                if let HashKey::String(s) = key {
+                    keys.insert(s.as_ref().clone());
+                }
+            }
+        }
+    }
+    keys.into_iter().collect()
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn parse_args<'a>(
+    name: &str,
+    args: &'a [Value],
+    span: Span,
+) -> Result<(&'a str, char), RuntimeError> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(stdlib_arity_error(name, 1, args.len(), span));
+    }
+    let Value::String(text) = &args[0] else {
+        return Err(type_error(
+            name,
+            format!("text must be a string, got {}", args[0].type_name()),
+            span,
+        ));
+    };
+    let options = Options::from_arg(name, args.get(1), span)?;
+    Ok((text.as_str(), options.delimiter))
+}
+
+fn string_key(s: &str) -> HashKey {
+    HashKey::String(Arc::new(s.to_string()))
+}
+
+fn type_error(name: &str, msg: String, span: Span) -> RuntimeError {
+    RuntimeError::TypeError {
+        msg: format!("{}: {}", name, msg),
+        span,
+    }
+}
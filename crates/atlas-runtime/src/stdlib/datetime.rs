@@ -984,6 +984,148 @@ pub fn date_time_in_timezone(args: &[Value], span: Span) -> Result<Value, Runtim
     }
 }
 
+// ============================================================================
+// Timestamp Operations
+// ============================================================================
+
+/// Get the current time as a Unix timestamp
+///
+/// Returns: number (whole seconds since Unix epoch)
+///
+/// Example:
+/// ```atlas
+/// let ts = DateTime.timestamp();
+/// ```atlas
+pub fn date_time_timestamp(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if !args.is_empty() {
+        return Err(RuntimeError::TypeError {
+            msg: "dateTimeTimestamp: expected 0 arguments".to_string(),
+            span,
+        });
+    }
+
+    Ok(Value::Number(Utc::now().timestamp() as f64))
+}
+
+/// Format a Unix timestamp, optionally in a named timezone
+///
+/// Args:
+/// - timestamp: number (seconds since Unix epoch)
+/// - format: string (strftime format)
+/// - tz: string (optional IANA timezone name, default UTC)
+///
+/// Returns: string (wall-clock time in `tz`; `%z` and `%Z` show its offset)
+///
+/// Example:
+/// ```atlas
+/// let s = DateTime.formatDate(1718448000, "%Y-%m-%d %H:%M %Z", "Asia/Tokyo");
+/// // "2024-06-15 19:40 JST"
+/// ```atlas
+pub fn date_time_format_date(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if !(2..=3).contains(&args.len()) {
+        return Err(RuntimeError::TypeError {
+            msg: "dateTimeFormatDate: expected 2 or 3 arguments (timestamp, format, timezone?)"
+                .to_string(),
+            span,
+        });
+    }
+
+    let timestamp = expect_number(&args[0], "timestamp", span)?;
+    let format = expect_string(&args[1], "format", span)?;
+    let dt = match Utc.timestamp_opt(timestamp as i64, 0) {
+        chrono::LocalResult::Single(dt) => dt,
+        _ => {
+            return Err(RuntimeError::TypeError {
+                msg: format!("dateTimeFormatDate: invalid timestamp: {}", timestamp),
+                span,
+            })
+        }
+    };
+
+    let formatted = match args.get(2) {
+        Some(tz) => {
+            let tz = expect_timezone(tz, "dateTimeFormatDate", span)?;
+            format_checked(&dt.with_timezone(&tz), &format)
+        }
+        None => format_checked(&dt, &format),
+    };
+    formatted
+        .map(Value::string)
+        .ok_or_else(|| RuntimeError::TypeError {
+            msg: format!("dateTimeFormatDate: invalid format string '{}'", format),
+            span,
+        })
+}
+
+/// Parse a date string into a Unix timestamp, optionally in a named timezone
+///
+/// Date-only formats are read as midnight. A format with an offset (`%z`)
+/// takes the offset from the text and ignores `tz`.
+///
+/// Args:
+/// - text: string
+/// - format: string (strftime format)
+/// - tz: string (optional IANA timezone the text is in, default UTC)
+///
+/// Returns: number (seconds since Unix epoch)
+///
+/// Example:
+/// ```atlas
+/// let ts = DateTime.parseDate("2024-06-15 09:00", "%Y-%m-%d %H:%M", "Europe/Paris");
+/// ```atlas
+pub fn date_time_parse_date(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if !(2..=3).contains(&args.len()) {
+        return Err(RuntimeError::TypeError {
+            msg: "dateTimeParseDate: expected 2 or 3 arguments (text, format, timezone?)"
+                .to_string(),
+            span,
+        });
+    }
+
+    let text = expect_string(&args[0], "text", span)?;
+    let format = expect_string(&args[1], "format", span)?;
+    let parse_error = |e: chrono::ParseError| RuntimeError::TypeError {
+        msg: format!(
+            "dateTimeParseDate: failed to parse '{}' with format '{}': {}",
+            text, format, e
+        ),
+        span,
+    };
+
+    if format.contains("%z") || format.contains("%:z") || format.contains("%#z") {
+        let dt = chrono::DateTime::parse_from_str(&text, &format).map_err(parse_error)?;
+        return Ok(Value::Number(dt.timestamp() as f64));
+    }
+
+    let naive = match chrono::NaiveDateTime::parse_from_str(&text, &format) {
+        Ok(naive) => naive,
+        Err(e) if e.kind() == chrono::format::ParseErrorKind::NotEnough => {
+            chrono::NaiveDate::parse_from_str(&text, &format)
+                .map_err(parse_error)?
+                .and_time(chrono::NaiveTime::MIN)
+        }
+        Err(e) => return Err(parse_error(e)),
+    };
+
+    let timestamp = match args.get(2) {
+        Some(tz) => {
+            let tz = expect_timezone(tz, "dateTimeParseDate", span)?;
+            let Some(dt) = tz.from_local_datetime(&naive).single() else {
+                return Err(RuntimeError::TypeError {
+                    msg: format!(
+                        "dateTimeParseDate: '{}' is ambiguous or does not exist in '{}'",
+                        text, tz
+                    ),
+                    span,
+                });
+            };
+            dt.timestamp()
+        }
+        None => naive.and_utc().timestamp(),
+    };
+    Ok(Value::Number(timestamp as f64))
+}
+
 // ============================================================================
 // Duration Operations
 // ============================================================================
@@ -1189,6 +1331,26 @@ fn expect_datetime(
     }
 }
 
+/// Expect an IANA timezone name
+fn expect_timezone(value: &Value, func_name: &str, span: Span) -> Result<Tz, RuntimeError> {
+    let name = expect_string(value, "timezone", span)?;
+    name.parse().map_err(|_| RuntimeError::TypeError {
+        msg: format!("{}: invalid timezone name: '{}'", func_name, name),
+        span,
+    })
+}
+
+/// Format `dt`, or `None` if `format` has an invalid specifier
+fn format_checked<T: TimeZone>(dt: &chrono::DateTime<T>, format: &str) -> Option<String>
+where
+    T::Offset: std::fmt::Display,
+{
+    use std::fmt::Write;
+    let mut out = String::new();
+    write!(out, "{}", dt.format(format)).ok()?;
+    Some(out)
+}
+
 /// Expect an Array value
 fn expect_array<'a>(
     value: &'a Value,
@@ -1,6 +1,7 @@
 //! Encoding/decoding functions for Atlas stdlib.
 //!
-//! Provides base64, hex, and URL encoding/decoding.
+//! Provides base64, hex, and URL encoding/decoding, and the `Bytes`
+//! constructors `fromHex`, `fromBase64` and `fromArray`.
 
 use crate::span::Span;
 use crate::value::{RuntimeError, Value};
@@ -118,6 +119,79 @@ pub fn hex_decode(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
         })
 }
 
+// ── Bytes constructors ───────────────────────────────────────────────
+
+/// fromHex(hex_string: string) -> Bytes
+///
+/// Unlike `hexDecode`, the decoded data does not have to be UTF-8.
+pub fn from_hex(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(super::stdlib_arity_error("fromHex", 1, args.len(), span));
+    }
+    let s = extract_str(&args[0], "fromHex", span)?;
+    hex::decode(s)
+        .map(super::bytes::bytes_value)
+        .map_err(|e| RuntimeError::InvalidStdlibArgument {
+            msg: format!("fromHex(): invalid hex: {}", e),
+            span,
+        })
+}
+
+/// fromBase64(encoded: string) -> Bytes
+///
+/// Unlike `base64Decode`, the decoded data does not have to be UTF-8.
+pub fn from_base64(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(super::stdlib_arity_error("fromBase64", 1, args.len(), span));
+    }
+    let s = extract_str(&args[0], "fromBase64", span)?;
+    BASE64_STANDARD
+        .decode(s)
+        .map(super::bytes::bytes_value)
+        .map_err(|e| RuntimeError::InvalidStdlibArgument {
+            msg: format!("fromBase64(): invalid base64: {}", e),
+            span,
+        })
+}
+
+/// fromArray(values: number[]) -> Bytes
+///
+/// Every element must be a whole number from 0 to 255.
+pub fn from_array(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(super::stdlib_arity_error("fromArray", 1, args.len(), span));
+    }
+    let arr = match &args[0] {
+        Value::Array(arr) => arr,
+        other => {
+            return Err(super::stdlib_arg_error(
+                "fromArray",
+                "number[]",
+                other,
+                span,
+            ))
+        }
+    };
+    let mut data = Vec::with_capacity(arr.len());
+    for (i, value) in arr.iter().enumerate() {
+        match value {
+            Value::Number(n) if n.fract() == 0.0 && (0.0..=255.0).contains(n) => {
+                data.push(*n as u8)
+            }
+            other => {
+                return Err(RuntimeError::InvalidStdlibArgument {
+                    msg: format!(
+                        "fromArray(): element {} is not a byte (0-255): {}",
+                        i, other
+                    ),
+                    span,
+                })
+            }
+        }
+    }
+    Ok(super::bytes::bytes_value(data))
+}
+
 // ── URL Encoding (feature = "http") ──────────────────────────────────
 
 /// urlEncode(data: string) -> string (percent-encoded)
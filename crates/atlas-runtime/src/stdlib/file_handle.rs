@@ -0,0 +1,322 @@
+//! Streaming file handles
+//!
+//! `file.read` and `file.readBytes` load a whole file into memory. A handle
+//! reads or writes it a piece at a time instead:
+//! - file.open(path, mode?) -> Result<FileHandle, string>
+//! - handle.readLine() -> Option<string>
+//! - handle.readChunk(size) -> Option<Bytes>
+//! - handle.writeChunk(data) -> null
+//! - handle.close() -> null
+//!
+//! `mode` is `"r"` (the default), `"w"` (create or truncate) or `"a"`
+//! (create or append). Reads and writes are buffered; a write handle is
+//! flushed when it is closed.
+//!
+//! A handle is also closed when the last reference to it goes away: when the
+//! scope holding it exits, or when an uncaught error abandons that scope. A
+//! script that forgets `close()` does not leak the descriptor.
+
+use super::io::{check_write_path, resolve_read_path};
+use super::{stdlib_arg_error, stdlib_arity_error};
+use crate::security::SecurityContext;
+use crate::span::Span;
+use crate::value::{RuntimeError, Value};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+// ============================================================================
+// FileHandle
+// ============================================================================
+
+/// An open file. Closing it (or dropping the last reference) releases the
+/// descriptor.
+pub struct FileHandle {
+    path: String,
+    stream: Mutex<Option<Stream>>,
+}
+
+enum Stream {
+    Reader(BufReader<File>),
+    Writer(BufWriter<File>),
+}
+
+impl FileHandle {
+    fn new(path: String, stream: Stream) -> Self {
+        FileHandle {
+            path,
+            stream: Mutex::new(Some(stream)),
+        }
+    }
+
+    /// The path the handle was opened with
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.stream.lock().map(|g| g.is_none()).unwrap_or(true)
+    }
+
+    fn lock(
+        &self,
+        method: &str,
+        span: Span,
+    ) -> Result<MutexGuard<'_, Option<Stream>>, RuntimeError> {
+        self.stream.lock().map_err(|_| RuntimeError::IoError {
+            message: format!("{}: file lock poisoned", method),
+            span,
+        })
+    }
+
+    fn reader<T>(
+        &self,
+        method: &str,
+        span: Span,
+        f: impl FnOnce(&mut BufReader<File>) -> std::io::Result<T>,
+    ) -> Result<T, RuntimeError> {
+        let mut guard = self.lock(method, span)?;
+        match guard.as_mut() {
+            Some(Stream::Reader(reader)) => f(reader).map_err(|e| self.io_error(method, e, span)),
+            Some(Stream::Writer(_)) => Err(RuntimeError::IoError {
+                message: format!("{}: '{}' was opened for writing", method, self.path),
+                span,
+            }),
+            None => Err(self.closed_error(method, span)),
+        }
+    }
+
+    fn writer(
+        &self,
+        method: &str,
+        span: Span,
+        f: impl FnOnce(&mut BufWriter<File>) -> std::io::Result<()>,
+    ) -> Result<(), RuntimeError> {
+        let mut guard = self.lock(method, span)?;
+        match guard.as_mut() {
+            Some(Stream::Writer(writer)) => f(writer).map_err(|e| self.io_error(method, e, span)),
+            Some(Stream::Reader(_)) => Err(RuntimeError::IoError {
+                message: format!("{}: '{}' was opened for reading", method, self.path),
+                span,
+            }),
+            None => Err(self.closed_error(method, span)),
+        }
+    }
+
+    fn io_error(&self, method: &str, e: std::io::Error, span: Span) -> RuntimeError {
+        RuntimeError::IoError {
+            message: format!("{}: '{}': {}", method, self.path, e),
+            span,
+        }
+    }
+
+    fn closed_error(&self, method: &str, span: Span) -> RuntimeError {
+        RuntimeError::IoError {
+            message: format!("{}: '{}' is closed", method, self.path),
+            span,
+        }
+    }
+}
+
+impl std::fmt::Debug for FileHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let closed = self.is_closed();
+        write!(
+            f,
+            "FileHandle({}, {})",
+            self.path,
+            if closed { "closed" } else { "open" }
+        )
+    }
+}
+
+// ============================================================================
+// Namespace Functions (file.*)
+// ============================================================================
+
+/// Open a file for streaming reads or writes.
+///
+/// Atlas signature: `file.open(path: string, mode?: string) -> Result<FileHandle, string>`
+/// - Reading requires read permission on the file
+/// - Writing and appending require write permission, checked on the parent
+///   directory when the file does not exist yet
+pub fn open(args: &[Value], span: Span, security: &SecurityContext) -> Result<Value, RuntimeError> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(stdlib_arity_error("file.open", 1, args.len(), span));
+    }
+    let path_str = match &args[0] {
+        Value::String(s) => s.as_ref(),
+        other => return Err(stdlib_arg_error("file.open", "string", other, span)),
+    };
+    let mode = match args.get(1) {
+        None => "r",
+        Some(Value::String(s)) => s.as_ref(),
+        Some(other) => return Err(stdlib_arg_error("file.open", "string", other, span)),
+    };
+
+    let opened = match mode {
+        "r" => resolve_read_path(path_str, "file.open", security).and_then(|path| {
+            File::open(&path)
+                .map(|file| Stream::Reader(BufReader::new(file)))
+                .map_err(|e| format!("file.open: failed to open '{}': {}", path_str, e))
+        }),
+        "w" | "a" => check_write_path(path_str, "file.open", security).and_then(|path| {
+            OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(mode == "w")
+                .append(mode == "a")
+                .open(&path)
+                .map(|file| Stream::Writer(BufWriter::new(file)))
+                .map_err(|e| format!("file.open: failed to open '{}': {}", path_str, e))
+        }),
+        other => {
+            return Err(RuntimeError::InvalidStdlibArgument {
+                msg: format!(
+                    "file.open(): unknown mode '{}', expected \"r\", \"w\" or \"a\"",
+                    other
+                ),
+                span,
+            })
+        }
+    };
+
+    Ok(Value::Result(match opened {
+        Ok(stream) => Ok(Box::new(Value::FileHandle(Arc::new(FileHandle::new(
+            path_str.to_string(),
+            stream,
+        ))))),
+        Err(msg) => Err(Box::new(Value::string(msg))),
+    }))
+}
+
+// ============================================================================
+// Instance Methods (FileHandle.*)
+// ============================================================================
+
+/// Read the next line, without its line ending. `None` at end of file.
+///
+/// Atlas signature: `handle.readLine() -> Option<string>`
+pub fn read_line(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error("handle.readLine", 1, args.len(), span));
+    }
+    let handle = expect_handle(&args[0], "readLine", span)?;
+    let mut line = Vec::new();
+    let read = handle.reader("readLine", span, |r| r.read_until(b'\n', &mut line))?;
+    if read == 0 {
+        return Ok(Value::Option(None));
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+    }
+    let line = String::from_utf8(line).map_err(|e| RuntimeError::IoError {
+        message: format!("readLine: '{}' is not valid UTF-8: {}", handle.path, e),
+        span,
+    })?;
+    Ok(Value::Option(Some(Box::new(Value::string(line)))))
+}
+
+/// Read up to `size` bytes. `None` at end of file.
+///
+/// Atlas signature: `handle.readChunk(size: number) -> Option<Bytes>`
+pub fn read_chunk(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 2 {
+        return Err(stdlib_arity_error("handle.readChunk", 2, args.len(), span));
+    }
+    let handle = expect_handle(&args[0], "readChunk", span)?;
+    let size = match &args[1] {
+        Value::Number(n) if *n >= 1.0 && n.fract() == 0.0 => *n as u64,
+        Value::Number(n) => {
+            return Err(RuntimeError::InvalidStdlibArgument {
+                msg: format!(
+                    "readChunk(): size must be a positive whole number, got {}",
+                    n
+                ),
+                span,
+            })
+        }
+        other => return Err(stdlib_arg_error("readChunk", "number", other, span)),
+    };
+    let mut chunk = Vec::new();
+    handle.reader("readChunk", span, |r| r.take(size).read_to_end(&mut chunk))?;
+    if chunk.is_empty() {
+        return Ok(Value::Option(None));
+    }
+    Ok(Value::Option(Some(Box::new(super::bytes::bytes_value(
+        chunk,
+    )))))
+}
+
+/// Write a string (as UTF-8) or Bytes.
+///
+/// Atlas signature: `handle.writeChunk(data: string | Bytes) -> null`
+pub fn write_chunk(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 2 {
+        return Err(stdlib_arity_error("handle.writeChunk", 2, args.len(), span));
+    }
+    let handle = expect_handle(&args[0], "writeChunk", span)?;
+    let data: &[u8] = match &args[1] {
+        Value::String(s) => s.as_bytes(),
+        Value::Bytes(b) => b.as_slice(),
+        other => {
+            return Err(stdlib_arg_error(
+                "writeChunk",
+                "string or Bytes",
+                other,
+                span,
+            ))
+        }
+    };
+    handle.writer("writeChunk", span, |w| w.write_all(data))?;
+    Ok(Value::Null)
+}
+
+/// Flush and close the file.
+///
+/// Atlas signature: `handle.close() -> null`
+pub fn close(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error("handle.close", 1, args.len(), span));
+    }
+    let handle = expect_handle(&args[0], "close", span)?;
+    let stream = handle.lock("close", span)?.take();
+    match stream {
+        Some(Stream::Writer(mut writer)) => writer
+            .flush()
+            .map_err(|e| handle.io_error("close", e, span))?,
+        Some(Stream::Reader(_)) => {}
+        None => {
+            return Err(RuntimeError::IoError {
+                message: format!("close: '{}' already closed", handle.path),
+                span,
+            })
+        }
+    }
+    Ok(Value::Null)
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn expect_handle<'a>(
+    value: &'a Value,
+    method: &str,
+    span: Span,
+) -> Result<&'a Arc<FileHandle>, RuntimeError> {
+    match value {
+        Value::FileHandle(handle) => Ok(handle),
+        other => Err(RuntimeError::TypeError {
+            msg: format!(
+                "{}() requires FileHandle, got {}",
+                method,
+                other.type_name()
+            ),
+            span,
+        }),
+    }
+}
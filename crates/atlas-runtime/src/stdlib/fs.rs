@@ -3,15 +3,13 @@
 //! Advanced file system operations including metadata, symlinks, temporary files,
 //! and directory walking. Complements basic I/O operations in io.rs.
 
-use crate::async_runtime::channel::{channel_unbounded, ChannelReceiver, ChannelSender};
-use crate::json_value::JsonValue;
+use crate::security::SecurityContext;
 use crate::span::Span;
 use crate::value::{RuntimeError, Value};
-use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
-use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
-use std::sync::{Arc, Mutex};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 use std::time::SystemTime;
 
 #[cfg(unix)]
@@ -381,50 +379,159 @@ pub fn inode(_path: &str, span: Span) -> Result<Value, RuntimeError> {
 // Temporary Files and Directories
 // ============================================================================
 
-/// Create a temporary file
+/// Scratch directory backing `file.tempFile()` / `file.tempDir()`.
 ///
-/// Returns path to the temporary file. File is NOT automatically deleted.
-pub fn tmpfile(span: Span) -> Result<Value, RuntimeError> {
-    use std::env;
-    use std::time::{SystemTime, UNIX_EPOCH};
+/// Owned by a `SecurityContext` (shared by its clones) and created lazily on
+/// first use. Paths under it are implicitly readable and writable by that
+/// context, so scripts can use scratch space without broad filesystem grants.
+/// The directory is removed by [`TempRoot::cleanup`] or when the last context
+/// holding it is dropped.
+#[derive(Debug, Default)]
+pub struct TempRoot {
+    path: Mutex<Option<PathBuf>>,
+}
+
+/// Monotonic counter keeping temp names unique within one process.
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+impl TempRoot {
+    /// Create a temp root; the directory itself is made on first use
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    let temp_dir = env::temp_dir();
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_err(|e| RuntimeError::IoError {
-            message: format!("SystemTime error: {}", e),
+    /// Return the directory, creating it on first use.
+    fn get_or_create(&self, span: Span) -> Result<PathBuf, RuntimeError> {
+        let mut guard = self.path.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(root) = guard.as_ref() {
+            if root.is_dir() {
+                return Ok(root.clone());
+            }
+        }
+
+        let root =
+            std::env::temp_dir().join(format!("atlas-{}-{}", std::process::id(), unique_suffix()));
+        fs::create_dir_all(&root).map_err(|e| RuntimeError::IoError {
+            message: format!("Failed to create temporary root: {}", e),
             span,
-        })?
-        .as_nanos();
-    let filename = format!("atlas_tmp_{}.tmp", timestamp);
-    let temp_path = temp_dir.join(filename);
+        })?;
+        // Canonicalize so prefix checks compare resolved paths on both sides.
+        let root = root.canonicalize().unwrap_or(root);
+        *guard = Some(root.clone());
+        Ok(root)
+    }
 
-    // Create the file
-    fs::File::create(&temp_path).map_err(|e| RuntimeError::IoError {
-        message: format!("Failed to create temporary file: {}", e),
-        span,
-    })?;
+    /// The directory, if it has been created.
+    pub fn path(&self) -> Option<PathBuf> {
+        self.path.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
 
-    Ok(Value::string(temp_path.to_string_lossy().to_string()))
+    /// Check whether `path` lives inside the temp root.
+    ///
+    /// Paths with `..` components are rejected outright. The deepest existing
+    /// ancestor is canonicalized, so symlinks cannot point out of the root; a
+    /// dangling symlink is rejected because writing through it would create
+    /// its target wherever it points.
+    pub fn contains(&self, path: &Path) -> bool {
+        let Some(root) = self.path() else {
+            return false;
+        };
+        match resolve_for_containment(path) {
+            Some(resolved) => resolved.starts_with(&root),
+            None => false,
+        }
+    }
+
+    /// Remove the directory and everything created inside it.
+    ///
+    /// Hosts call this before exiting without unwinding (`process.exit`).
+    /// Safe to call multiple times; a later `file.tempFile()` creates a fresh root.
+    pub fn cleanup(&self) {
+        let mut guard = self.path.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(root) = guard.take() {
+            let _ = fs::remove_dir_all(&root);
+        }
+    }
 }
 
-/// Create a temporary directory
+impl Drop for TempRoot {
+    fn drop(&mut self) {
+        self.cleanup();
+    }
+}
+
+/// Resolve `path` for a temp-root containment check.
 ///
-/// Returns path to the temporary directory. Directory is NOT automatically deleted.
-pub fn tmpdir(span: Span) -> Result<Value, RuntimeError> {
-    use std::env;
-    use std::time::{SystemTime, UNIX_EPOCH};
+/// Returns `None` for paths that must not be treated as inside any root:
+/// those with `..` components and those whose first missing component is a
+/// dangling symlink.
+fn resolve_for_containment(path: &Path) -> Option<PathBuf> {
+    use std::path::Component;
+
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().ok()?.join(path)
+    };
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => return None,
+            other => normalized.push(other),
+        }
+    }
 
-    let temp_dir = env::temp_dir();
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_err(|e| RuntimeError::IoError {
-            message: format!("SystemTime error: {}", e),
-            span,
-        })?
-        .as_nanos();
-    let dirname = format!("atlas_tmp_dir_{}", timestamp);
-    let temp_path = temp_dir.join(dirname);
+    // Canonicalize the deepest existing ancestor and re-append the rest
+    let mut existing = normalized.as_path();
+    let mut missing = Vec::new();
+    let canonical = loop {
+        match existing.canonicalize() {
+            Ok(canonical) => break canonical,
+            Err(_) => {
+                if existing.symlink_metadata().is_ok() {
+                    return None;
+                }
+                missing.push(existing.file_name()?.to_os_string());
+                existing = existing.parent()?;
+            }
+        }
+    };
+    Some(
+        missing
+            .iter()
+            .rev()
+            .fold(canonical, |acc, name| acc.join(name)),
+    )
+}
+
+/// Unique suffix for temp names: nanosecond timestamp + per-process counter.
+fn unique_suffix() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let seq = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("{}_{}", nanos, seq)
+}
+
+/// Create a temporary file
+///
+/// Returns path to an empty file inside the context's temp root.
+/// The file is deleted when the temp root is cleaned up.
+pub fn tmpfile(security: &SecurityContext, span: Span) -> Result<Value, RuntimeError> {
+    tmpfile_named("atlas_tmp", security, span)
+}
+
+/// Create a temporary directory
+///
+/// Returns path to a fresh directory inside the context's temp root.
+/// The directory is deleted when the temp root is cleaned up.
+pub fn tmpdir(security: &SecurityContext, span: Span) -> Result<Value, RuntimeError> {
+    let temp_path = security
+        .temp_root()
+        .get_or_create(span)?
+        .join(format!("atlas_tmp_dir_{}", unique_suffix()));
 
     // Create the directory
     fs::create_dir(&temp_path).map_err(|e| RuntimeError::IoError {
@@ -437,21 +544,23 @@ pub fn tmpdir(span: Span) -> Result<Value, RuntimeError> {
 
 /// Create a named temporary file
 ///
-/// Creates a temporary file with a specific name prefix.
-pub fn tmpfile_named(prefix: &str, span: Span) -> Result<Value, RuntimeError> {
-    use std::env;
-    use std::time::{SystemTime, UNIX_EPOCH};
-
-    let temp_dir = env::temp_dir();
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_err(|e| RuntimeError::IoError {
-            message: format!("SystemTime error: {}", e),
+/// Creates a temporary file with a specific name prefix inside the context's temp root.
+pub fn tmpfile_named(
+    prefix: &str,
+    security: &SecurityContext,
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    if prefix.contains('/') || prefix.contains('\\') {
+        return Err(RuntimeError::IoError {
+            message: format!(
+                "Invalid temporary file prefix '{}': must not contain path separators",
+                prefix
+            ),
             span,
-        })?
-        .as_nanos();
-    let filename = format!("{}_{}.tmp", prefix, timestamp);
-    let temp_path = temp_dir.join(filename);
+        });
+    }
+    let filename = format!("{}_{}.tmp", prefix, unique_suffix());
+    let temp_path = security.temp_root().get_or_create(span)?.join(filename);
 
     // Create the file
     fs::File::create(&temp_path).map_err(|e| RuntimeError::IoError {
@@ -565,121 +674,138 @@ pub fn resolve_symlink(path: &str, span: Span) -> Result<Value, RuntimeError> {
 // File Watching
 // ============================================================================
 
-/// File system watcher handle
-pub struct FsWatcher {
-    receiver: ChannelReceiver,
-    _watcher: RecommendedWatcher,
-}
+// Watching needs the `notify` crate, which only comes with the `fs` feature
+#[cfg(feature = "fs")]
+pub use watch::{watch, watch_next, FsWatcher};
+
+#[cfg(feature = "fs")]
+mod watch {
+    use super::*;
+    use crate::async_runtime::channel::{channel_unbounded, ChannelReceiver, ChannelSender};
+    use crate::json_value::JsonValue;
+    use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    /// File system watcher handle
+    pub struct FsWatcher {
+        receiver: ChannelReceiver,
+        _watcher: RecommendedWatcher,
+    }
 
-impl FsWatcher {
-    fn next(&self) -> crate::async_runtime::AtlasFuture {
-        self.receiver.receive()
+    impl FsWatcher {
+        fn next(&self) -> crate::async_runtime::AtlasFuture {
+            self.receiver.receive()
+        }
     }
-}
 
-fn event_kind_label(kind: &EventKind) -> &'static str {
-    match kind {
-        EventKind::Create(_) => "create",
-        EventKind::Modify(_) => "modify",
-        EventKind::Remove(_) => "remove",
-        EventKind::Access(_) => "access",
-        EventKind::Any => "any",
-        _ => "other",
+    fn event_kind_label(kind: &EventKind) -> &'static str {
+        match kind {
+            EventKind::Create(_) => "create",
+            EventKind::Modify(_) => "modify",
+            EventKind::Remove(_) => "remove",
+            EventKind::Access(_) => "access",
+            EventKind::Any => "any",
+            _ => "other",
+        }
     }
-}
 
-fn event_to_value(event: &notify::Event) -> Value {
-    let paths = event
-        .paths
-        .iter()
-        .map(|path| JsonValue::String(path.to_string_lossy().to_string()))
-        .collect::<Vec<_>>();
+    fn event_to_value(event: &notify::Event) -> Value {
+        let paths = event
+            .paths
+            .iter()
+            .map(|path| JsonValue::String(path.to_string_lossy().to_string()))
+            .collect::<Vec<_>>();
+
+        let mut obj = HashMap::new();
+        obj.insert(
+            "kind".to_string(),
+            JsonValue::String(event_kind_label(&event.kind).to_string()),
+        );
+        obj.insert(
+            "detail".to_string(),
+            JsonValue::String(format!("{:?}", event.kind)),
+        );
+        obj.insert("paths".to_string(), JsonValue::Array(paths));
+
+        Value::JsonValue(Arc::new(JsonValue::Object(obj)))
+    }
 
-    let mut obj = HashMap::new();
-    obj.insert(
-        "kind".to_string(),
-        JsonValue::String(event_kind_label(&event.kind).to_string()),
-    );
-    obj.insert(
-        "detail".to_string(),
-        JsonValue::String(format!("{:?}", event.kind)),
-    );
-    obj.insert("paths".to_string(), JsonValue::Array(paths));
+    fn error_to_value(error: &notify::Error) -> Value {
+        let mut obj = HashMap::new();
+        obj.insert("kind".to_string(), JsonValue::String("error".to_string()));
+        obj.insert("message".to_string(), JsonValue::String(error.to_string()));
+        Value::JsonValue(Arc::new(JsonValue::Object(obj)))
+    }
 
-    Value::JsonValue(Arc::new(JsonValue::Object(obj)))
-}
+    /// Watch a file or directory for changes
+    ///
+    /// Atlas signature: `fsWatch(path: string) -> Watcher`
+    pub fn watch(path: &str, span: Span) -> Result<Value, RuntimeError> {
+        let watch_path = Path::new(path);
+        if !watch_path.exists() {
+            return Err(RuntimeError::IoError {
+                message: format!("Failed to watch '{}': path does not exist", path),
+                span,
+            });
+        }
 
-fn error_to_value(error: &notify::Error) -> Value {
-    let mut obj = HashMap::new();
-    obj.insert("kind".to_string(), JsonValue::String("error".to_string()));
-    obj.insert("message".to_string(), JsonValue::String(error.to_string()));
-    Value::JsonValue(Arc::new(JsonValue::Object(obj)))
-}
+        let (sender, receiver) = channel_unbounded();
+        let mut watcher = create_watcher(sender, span)?;
 
-/// Watch a file or directory for changes
-///
-/// Atlas signature: `fsWatch(path: string) -> Watcher`
-pub fn watch(path: &str, span: Span) -> Result<Value, RuntimeError> {
-    let watch_path = Path::new(path);
-    if !watch_path.exists() {
-        return Err(RuntimeError::IoError {
-            message: format!("Failed to watch '{}': path does not exist", path),
-            span,
-        });
+        let mode = if watch_path.is_dir() {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+
+        watcher
+            .watch(watch_path, mode)
+            .map_err(|e| RuntimeError::IoError {
+                message: format!("Failed to watch '{}': {}", watch_path.display(), e),
+                span,
+            })?;
+
+        Ok(Value::Watcher(Arc::new(Mutex::new(FsWatcher {
+            receiver,
+            _watcher: watcher,
+        }))))
     }
 
-    let (sender, receiver) = channel_unbounded();
-    let mut watcher = create_watcher(sender, span)?;
+    /// Receive the next change event for a watcher
+    ///
+    /// Atlas signature: `fsWatchNext(watcher: Watcher) -> Future<object>`
+    pub fn watch_next(watcher: &Value, span: Span) -> Result<Value, RuntimeError> {
+        let watcher = match watcher {
+            Value::Watcher(w) => w.lock().unwrap(),
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: format!("Expected Watcher, got {}", watcher.type_name()),
+                    span,
+                })
+            }
+        };
 
-    let mode = if watch_path.is_dir() {
-        RecursiveMode::Recursive
-    } else {
-        RecursiveMode::NonRecursive
-    };
+        let future = watcher.next();
+        Ok(Value::Future(Arc::new(future)))
+    }
 
-    watcher
-        .watch(watch_path, mode)
+    fn create_watcher(
+        sender: ChannelSender,
+        span: Span,
+    ) -> Result<RecommendedWatcher, RuntimeError> {
+        let sender_for_errors = sender.clone();
+        notify::recommended_watcher(move |res| match res {
+            Ok(event) => {
+                sender.send(event_to_value(&event));
+            }
+            Err(err) => {
+                sender_for_errors.send(error_to_value(&err));
+            }
+        })
         .map_err(|e| RuntimeError::IoError {
-            message: format!("Failed to watch '{}': {}", watch_path.display(), e),
+            message: format!("Failed to create watcher: {}", e),
             span,
-        })?;
-
-    Ok(Value::Watcher(Arc::new(Mutex::new(FsWatcher {
-        receiver,
-        _watcher: watcher,
-    }))))
-}
-
-/// Receive the next change event for a watcher
-///
-/// Atlas signature: `fsWatchNext(watcher: Watcher) -> Future<object>`
-pub fn watch_next(watcher: &Value, span: Span) -> Result<Value, RuntimeError> {
-    let watcher = match watcher {
-        Value::Watcher(w) => w.lock().unwrap(),
-        _ => {
-            return Err(RuntimeError::TypeError {
-                msg: format!("Expected Watcher, got {}", watcher.type_name()),
-                span,
-            })
-        }
-    };
-
-    let future = watcher.next();
-    Ok(Value::Future(Arc::new(future)))
-}
-
-fn create_watcher(sender: ChannelSender, span: Span) -> Result<RecommendedWatcher, RuntimeError> {
-    let sender_for_errors = sender.clone();
-    notify::recommended_watcher(move |res| match res {
-        Ok(event) => {
-            sender.send(event_to_value(&event));
-        }
-        Err(err) => {
-            sender_for_errors.send(error_to_value(&err));
-        }
-    })
-    .map_err(|e| RuntimeError::IoError {
-        message: format!("Failed to create watcher: {}", e),
-        span,
-    })
+        })
+    }
 }
@@ -13,15 +13,24 @@ use std::sync::Arc;
 use std::time::Duration;
 
 /// Extract the hostname from a URL string for security checking.
-fn extract_host(url: &str) -> Option<String> {
-    // Strip scheme (http:// or https://)
-    let rest = url
-        .strip_prefix("https://")
-        .or_else(|| url.strip_prefix("http://"))?;
-    // Take everything before the first '/' or end of string
-    let host_and_port = rest.split('/').next()?;
-    // Strip port if present
-    let host = host_and_port.split(':').next()?;
+///
+/// The URL is parsed properly so userinfo (`http://allowed.com:x@evil.com/`)
+/// cannot masquerade as the host the request actually goes to.
+pub(crate) fn extract_host(url: &str) -> Option<String> {
+    let parsed = reqwest::Url::parse(url).ok()?;
+    host_of(&parsed)
+}
+
+/// Hostname of an already-parsed http(s) URL, without IPv6 brackets.
+fn host_of(url: &reqwest::Url) -> Option<String> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = url.host_str()?;
+    let host = host
+        .strip_prefix('[')
+        .and_then(|h| h.strip_suffix(']'))
+        .unwrap_or(host);
     if host.is_empty() {
         None
     } else {
@@ -29,6 +38,51 @@ fn extract_host(url: &str) -> Option<String> {
     }
 }
 
+/// Redirect refused because its target host is not on the network allow-list.
+#[derive(Debug)]
+struct RedirectDenied(String);
+
+impl std::fmt::Display for RedirectDenied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "redirect to {} denied", self.0)
+    }
+}
+
+impl std::error::Error for RedirectDenied {}
+
+/// Redirect policy that re-checks network permission on every hop.
+pub(crate) fn checked_redirects(
+    max_redirects: usize,
+    security: &SecurityContext,
+) -> reqwest::redirect::Policy {
+    let security = security.clone();
+    reqwest::redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() >= max_redirects {
+            return attempt.error("too many redirects");
+        }
+        match host_of(attempt.url()) {
+            Some(host) if security.check_network(&host).is_ok() => attempt.follow(),
+            Some(host) => attempt.error(RedirectDenied(host)),
+            None => {
+                let target = attempt.url().to_string();
+                attempt.error(RedirectDenied(target))
+            }
+        }
+    })
+}
+
+/// Find a denied redirect anywhere in a request error's source chain.
+pub(crate) fn redirect_denied(error: &reqwest::Error) -> Option<String> {
+    let mut source = std::error::Error::source(error);
+    while let Some(err) = source {
+        if let Some(denied) = err.downcast_ref::<RedirectDenied>() {
+            return Some(denied.0.clone());
+        }
+        source = err.source();
+    }
+    None
+}
+
 /// HTTP Request configuration
 #[derive(Debug, Clone, PartialEq)]
 pub struct HttpRequest {
@@ -535,6 +589,43 @@ pub fn http_url(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
     Ok(Value::string(response.url().to_string()))
 }
 
+/// Convert a response into a plain HashMap
+///
+/// Args:
+/// - response: HttpResponse
+///
+/// Returns: HashMap with `status`, `headers`, `body` and `url` keys
+///
+/// Example:
+/// ```atlas
+/// let data = response.toMap();
+/// console.log(json.stringify(data));
+/// ```atlas
+pub fn http_response_to_map(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(RuntimeError::TypeError {
+            msg: "httpResponseToMap: expected 1 argument (response)".to_string(),
+            span,
+        });
+    }
+
+    let response = expect_http_response(&args[0], "response", span)?;
+    let headers = http_headers(args, span)?;
+
+    let mut atlas_map = AtlasHashMap::new();
+    let mut put = |key: &str, value: Value| {
+        atlas_map.insert(HashKey::String(Arc::new(key.to_string())), value);
+    };
+    put("status", Value::Number(response.status() as f64));
+    put("headers", headers);
+    put("body", Value::string(response.body().to_string()));
+    put("url", Value::string(response.url().to_string()));
+
+    Ok(Value::Map(crate::value::ValueHashMap::from_atlas(
+        atlas_map,
+    )))
+}
+
 /// Check if response is success (status 200-299)
 ///
 /// Args:
@@ -587,22 +678,22 @@ pub fn http_send(
 
     let request = expect_http_request(&args[0], "request", span)?;
 
-    // Enforce network permission before making the request
+    // Enforce network permission before making the request. A URL whose
+    // host cannot be determined is refused rather than sent unchecked.
     let url = request.build_url();
-    if let Some(host) = extract_host(&url) {
-        security
-            .check_network(&host)
-            .map_err(|e| RuntimeError::TypeError {
-                msg: format!("httpSend: {}", e),
-                span,
-            })?;
-    }
+    let host = extract_host(&url).ok_or_else(|| RuntimeError::TypeError {
+        msg: format!("httpSend: URL has no host: {}", url),
+        span,
+    })?;
+    security
+        .check_network(&host)
+        .map_err(|_| RuntimeError::NetworkPermissionDenied { host, span })?;
 
     // Build reqwest client
     let client = match reqwest::blocking::Client::builder()
         .timeout(Duration::from_secs(request.timeout_secs()))
         .redirect(if request.follow_redirects {
-            reqwest::redirect::Policy::limited(request.max_redirects as usize)
+            checked_redirects(request.max_redirects as usize, security)
         } else {
             reqwest::redirect::Policy::none()
         })
@@ -620,20 +711,17 @@ pub fn http_send(
     // Build URL with query parameters
     let url = request.build_url();
 
-    // Build request with proper method
-    let mut req_builder = match request.method() {
-        "GET" => client.get(&url),
-        "POST" => client.post(&url),
-        "PUT" => client.put(&url),
-        "DELETE" => client.delete(&url),
-        "PATCH" => client.patch(&url),
-        method => {
+    // Any method token is accepted (HEAD, OPTIONS, WebDAV verbs, ...)
+    let method = match reqwest::Method::from_bytes(request.method().as_bytes()) {
+        Ok(method) => method,
+        Err(_) => {
             return Ok(Value::Result(Err(Box::new(Value::string(format!(
                 "httpSend: unsupported method: {}",
-                method
+                request.method()
             ))))));
         }
     };
+    let mut req_builder = client.request(method, &url);
 
     // Add headers
     for (key, value) in request.headers() {
@@ -649,6 +737,9 @@ pub fn http_send(
     let response = match req_builder.send() {
         Ok(r) => r,
         Err(e) => {
+            if let Some(host) = redirect_denied(&e) {
+                return Err(RuntimeError::NetworkPermissionDenied { host, span });
+            }
             let error_msg = if e.is_timeout() {
                 format!(
                     "httpSend: request timeout after {} seconds",
@@ -1300,6 +1391,67 @@ fn apply_options_to_request(
     Ok(req)
 }
 
+/// http.request(options: map) → Result<HttpResponse>
+///
+/// General form of the verb helpers: `options.url` is required, `method`
+/// defaults to GET and `body` is sent as-is. All other options are the same
+/// as for `http.get`.
+pub fn http_ns_request(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(RuntimeError::TypeError {
+            msg: "http.request: expected 1 argument (options)".to_string(),
+            span,
+        });
+    }
+    let map = match &args[0] {
+        Value::Map(m) => m,
+        other => {
+            return Err(RuntimeError::TypeError {
+                msg: format!(
+                    "http.request: options must be a map, got {}",
+                    other.type_name()
+                ),
+                span,
+            })
+        }
+    };
+    let field = |key: &str| map.get(&HashKey::String(Arc::new(key.to_string())));
+
+    let url = match field("url") {
+        Some(value) => expect_string(value, "url", span)?,
+        None => {
+            return Err(RuntimeError::TypeError {
+                msg: "http.request: options.url is required".to_string(),
+                span,
+            })
+        }
+    };
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(RuntimeError::TypeError {
+            msg: format!(
+                "http.request: URL must start with http:// or https://, got: {}",
+                url
+            ),
+            span,
+        });
+    }
+    let method = match field("method") {
+        Some(value) => expect_string(value, "method", span)?.to_ascii_uppercase(),
+        None => "GET".to_string(),
+    };
+
+    let mut req = HttpRequest::new(method, url);
+    if let Some(body) = field("body") {
+        req = req.with_body(expect_string(body, "body", span)?);
+    }
+    req = apply_options_to_request(req, &args[0], span)?;
+    http_send(&[Value::HttpRequest(Arc::new(req))], span, security)
+}
+
 /// http.get(url: str, options?: map) → Result<HttpResponse>
 pub fn http_ns_get(
     args: &[Value],
@@ -0,0 +1,100 @@
+//! Interrupt points for long-running builtins
+//!
+//! The VM checks its execution limits between instructions, but a single
+//! builtin call — a regex over a huge string, reading a large file — never
+//! returns to the dispatch loop until it is done. While a builtin runs the VM
+//! publishes its limits here, and expensive builtins call [`checkpoint`] (or
+//! [`Ticker::tick`] inside loops) so the script timeout, the per-call limit
+//! and a host interrupt stop them as well.
+//!
+//! Outside a VM call, or when no limits are configured, checkpoints are
+//! no-ops.
+
+use crate::api::config::ExecutionLimits;
+use crate::value::RuntimeError;
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Loop iterations between two checks made by [`Ticker`]
+pub const CHECK_INTERVAL: usize = 1024;
+
+thread_local! {
+    static ACTIVE: RefCell<Option<ActiveCall>> = const { RefCell::new(None) };
+}
+
+/// The limits of the VM whose builtin is running on this thread
+struct ActiveCall {
+    limits: Arc<ExecutionLimits>,
+    started: Instant,
+}
+
+/// Restores the enclosing call's limits when a builtin returns
+///
+/// Builtins can re-enter the VM (callbacks), which can call further
+/// builtins, so calls nest.
+pub(crate) struct CallGuard {
+    previous: Option<ActiveCall>,
+}
+
+impl Drop for CallGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        ACTIVE.with(|active| *active.borrow_mut() = previous);
+    }
+}
+
+/// Publish `limits` for the duration of one builtin call
+///
+/// Returns `None` (and publishes nothing) when the VM has no limits.
+pub(crate) fn enter_call(limits: Option<&Arc<ExecutionLimits>>) -> Option<CallGuard> {
+    let limits = limits?.clone();
+    let call = ActiveCall {
+        limits,
+        started: Instant::now(),
+    };
+    let previous = ACTIVE.with(|active| active.borrow_mut().replace(call));
+    Some(CallGuard { previous })
+}
+
+/// Fail if the running script was interrupted or ran out of time
+///
+/// Checks, in order, a host interrupt, the whole-script timeout and the
+/// per-builtin-call limit.
+pub fn checkpoint() -> Result<(), RuntimeError> {
+    ACTIVE.with(|active| match &*active.borrow() {
+        Some(call) => {
+            call.limits.check_interrupt()?;
+            call.limits.check_timeout()?;
+            call.limits.check_call_time(call.started)
+        }
+        None => Ok(()),
+    })
+}
+
+/// Amortizes [`checkpoint`] over the iterations of a loop
+///
+/// ```ignore
+/// let mut ticker = Ticker::default();
+/// for item in huge_iter {
+///     ticker.tick()?;
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Ticker {
+    count: usize,
+}
+
+impl Ticker {
+    /// Count one iteration, checking every [`CHECK_INTERVAL`]
+    #[inline]
+    pub fn tick(&mut self) -> Result<(), RuntimeError> {
+        self.count += 1;
+        if self.count.is_multiple_of(CHECK_INTERVAL) {
+            checkpoint()
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -4,14 +4,18 @@
 //! plus stdin reading for interactive CLI programs.
 //! All operations respect the SecurityContext permission model.
 
+use super::interrupt;
 use super::{stdlib_arg_error, stdlib_arity_error};
 use crate::security::SecurityContext;
 use crate::span::Span;
 use crate::value::{RuntimeError, Value};
 use std::fs;
-use std::io::BufRead;
+use std::io::{BufRead, ErrorKind, Read};
 use std::path::{Path, PathBuf};
 
+/// Bytes read between interrupt checks in [`read_to_string_interruptible`]
+const READ_CHUNK: usize = 64 * 1024;
+
 /// Read one line from stdin (strips trailing newline).
 ///
 /// Blocks until the user presses Enter. Returns `Some(line)` on success,
@@ -122,27 +126,13 @@ pub fn read_file(
         _ => return Err(stdlib_arg_error("readFile", "string", &args[0], span)),
     };
 
-    let path = PathBuf::from(path_str);
-    let abs_path = match path.canonicalize() {
+    let abs_path = match resolve_read_path(path_str, "File.read", security) {
         Ok(p) => p,
-        Err(e) => {
-            return Ok(Value::Result(Err(Box::new(Value::string(format!(
-                "File.read: path '{}' not found or inaccessible: {}",
-                path_str, e
-            ))))));
-        }
+        Err(msg) => return Ok(Value::Result(Err(Box::new(Value::string(msg))))),
     };
 
-    // Check permission
-    if security.check_filesystem_read(&abs_path).is_err() {
-        return Ok(Value::Result(Err(Box::new(Value::string(format!(
-            "File.read: permission denied for '{}'",
-            abs_path.display()
-        ))))));
-    }
-
     // Read file
-    match fs::read_to_string(&abs_path) {
+    match read_to_string_interruptible(&abs_path)? {
         Ok(contents) => Ok(Value::Result(Ok(Box::new(Value::string(contents))))),
         Err(e) => Ok(Value::Result(Err(Box::new(Value::string(format!(
             "File.read: failed to read '{}': {}",
@@ -152,6 +142,97 @@ pub fn read_file(
     }
 }
 
+/// Read entire file as raw bytes
+///
+/// Like [`read_file`] but with no UTF-8 validation, so binary files can be read.
+pub fn read_file_bytes(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error("file.readBytes", 1, args.len(), span));
+    }
+
+    let path_str = match &args[0] {
+        Value::String(s) => s.as_ref(),
+        _ => return Err(stdlib_arg_error("file.readBytes", "string", &args[0], span)),
+    };
+
+    let abs_path = match resolve_read_path(path_str, "File.readBytes", security) {
+        Ok(p) => p,
+        Err(msg) => return Ok(Value::Result(Err(Box::new(Value::string(msg))))),
+    };
+
+    match read_bytes_interruptible(&abs_path)? {
+        Ok(bytes) => Ok(Value::Result(Ok(Box::new(Value::Bytes(
+            std::sync::Arc::new(bytes),
+        ))))),
+        Err(e) => Ok(Value::Result(Err(Box::new(Value::string(format!(
+            "File.readBytes: failed to read '{}': {}",
+            abs_path.display(),
+            e
+        )))))),
+    }
+}
+
+/// Canonicalize `path_str` and check read permission
+///
+/// The error is the message for the `Err(..)` result handed back to the script.
+pub(super) fn resolve_read_path(
+    path_str: &str,
+    func_name: &str,
+    security: &SecurityContext,
+) -> Result<PathBuf, String> {
+    let path = PathBuf::from(path_str);
+    let abs_path = path.canonicalize().map_err(|e| {
+        format!(
+            "{}: path '{}' not found or inaccessible: {}",
+            func_name, path_str, e
+        )
+    })?;
+
+    // Check permission
+    if security.check_filesystem_read(&abs_path).is_err() {
+        return Err(format!(
+            "{}: permission denied for '{}'",
+            func_name,
+            abs_path.display()
+        ));
+    }
+    Ok(abs_path)
+}
+
+/// Read a file as UTF-8 in chunks, with an interrupt point after each one
+///
+/// The outer error stops the script (timeout or interrupt); the inner one is
+/// an ordinary I/O failure for the caller to report.
+fn read_to_string_interruptible(path: &Path) -> Result<std::io::Result<String>, RuntimeError> {
+    Ok(read_bytes_interruptible(path)?.and_then(|bytes| {
+        String::from_utf8(bytes).map_err(|e| std::io::Error::new(ErrorKind::InvalidData, e))
+    }))
+}
+
+/// Read a file in chunks, with an interrupt point after each one
+fn read_bytes_interruptible(path: &Path) -> Result<std::io::Result<Vec<u8>>, RuntimeError> {
+    let mut file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) => return Ok(Err(e)),
+    };
+    let mut bytes = Vec::new();
+    let mut chunk = vec![0u8; READ_CHUNK];
+    loop {
+        match file.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => bytes.extend_from_slice(&chunk[..n]),
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Ok(Err(e)),
+        }
+        interrupt::checkpoint()?;
+    }
+    Ok(Ok(bytes))
+}
+
 /// Write string to file (create or overwrite)
 ///
 /// Checks write permission. Creates file if it doesn't exist.
@@ -174,50 +255,230 @@ pub fn write_file(
         _ => return Err(stdlib_arg_error("writeFile", "string", &args[1], span)),
     };
 
+    Ok(write_checked(
+        path_str,
+        contents.as_bytes(),
+        "File.write",
+        security,
+    ))
+}
+
+/// Write raw bytes to file (create or overwrite)
+///
+/// Checks write permission. Creates file if it doesn't exist.
+pub fn write_file_bytes(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
+    if args.len() != 2 {
+        return Err(stdlib_arity_error("file.writeBytes", 2, args.len(), span));
+    }
+
+    let path_str = match &args[0] {
+        Value::String(s) => s.as_ref(),
+        _ => {
+            return Err(stdlib_arg_error(
+                "file.writeBytes",
+                "string",
+                &args[0],
+                span,
+            ))
+        }
+    };
+
+    let contents = match &args[1] {
+        Value::Bytes(b) => b.as_slice(),
+        _ => return Err(stdlib_arg_error("file.writeBytes", "Bytes", &args[1], span)),
+    };
+
+    Ok(write_checked(
+        path_str,
+        contents,
+        "File.writeBytes",
+        security,
+    ))
+}
+
+/// Check write permission for `path_str` and write `contents` to it
+///
+/// Returns the `Result<null, string>` value handed back to the script.
+fn write_checked(
+    path_str: &str,
+    contents: &[u8],
+    func_name: &str,
+    security: &SecurityContext,
+) -> Value {
+    let path = match check_write_path(path_str, func_name, security) {
+        Ok(path) => path,
+        Err(msg) => return Value::Result(Err(Box::new(Value::string(msg)))),
+    };
+
+    // Write file
+    match fs::write(&path, contents) {
+        Ok(()) => Value::Result(Ok(Box::new(Value::Null))),
+        Err(e) => Value::Result(Err(Box::new(Value::string(format!(
+            "{}: failed to write '{}': {}",
+            func_name, path_str, e
+        ))))),
+    }
+}
+
+/// Check write permission for `path_str`, which need not exist yet
+///
+/// A missing file is checked through its parent directory. The error is the
+/// message for the `Err(..)` result handed back to the script.
+pub(super) fn check_write_path(
+    path_str: &str,
+    func_name: &str,
+    security: &SecurityContext,
+) -> Result<PathBuf, String> {
     let path = PathBuf::from(path_str);
 
-    // For write operations, check permission on the parent directory if file doesn't exist
     let check_path = if path.exists() {
-        match path.canonicalize() {
-            Ok(p) => p,
-            Err(e) => {
-                return Ok(Value::Result(Err(Box::new(Value::string(format!(
-                    "File.write: cannot resolve path '{}': {}",
-                    path_str, e
-                ))))));
-            }
-        }
+        path.canonicalize()
+            .map_err(|e| format!("{}: cannot resolve path '{}': {}", func_name, path_str, e))?
     } else {
-        let parent = path.parent().unwrap_or_else(|| Path::new("."));
-        match parent.canonicalize() {
-            Ok(p) => p,
-            Err(e) => {
-                return Ok(Value::Result(Err(Box::new(Value::string(format!(
-                    "File.write: cannot resolve parent path: {}",
-                    e
-                ))))));
-            }
-        }
+        // A bare file name has an empty parent, meaning the current directory
+        let parent = match path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p,
+            _ => Path::new("."),
+        };
+        parent
+            .canonicalize()
+            .map_err(|e| format!("{}: cannot resolve parent path: {}", func_name, e))?
     };
 
-    // Check permission
     if security.check_filesystem_write(&check_path).is_err() {
-        return Ok(Value::Result(Err(Box::new(Value::string(format!(
-            "File.write: permission denied for '{}'",
+        return Err(format!(
+            "{}: permission denied for '{}'",
+            func_name,
             check_path.display()
-        ))))));
+        ));
     }
+    Ok(path)
+}
 
-    // Write file
-    match fs::write(&path, contents) {
+/// Write string to file atomically (temp file + rename)
+///
+/// `file.writeAtomic(path, contents, fsync?)`. The contents are written to a
+/// sibling temp file which is then renamed over `path`, so readers never see a
+/// half-written file and a crash leaves either the old or the new contents.
+/// With `fsync = true` the data and the directory entry are flushed to disk
+/// before returning. Checks write permission like `file.write`.
+pub fn write_file_atomic(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
+    if args.len() != 2 && args.len() != 3 {
+        return Err(stdlib_arity_error("file.writeAtomic", 2, args.len(), span));
+    }
+
+    let path_str = match &args[0] {
+        Value::String(s) => s.as_ref(),
+        _ => {
+            return Err(stdlib_arg_error(
+                "file.writeAtomic",
+                "string",
+                &args[0],
+                span,
+            ))
+        }
+    };
+
+    let contents = match &args[1] {
+        Value::String(s) => s.as_ref(),
+        _ => {
+            return Err(stdlib_arg_error(
+                "file.writeAtomic",
+                "string",
+                &args[1],
+                span,
+            ))
+        }
+    };
+
+    let fsync = match args.get(2) {
+        None => false,
+        Some(Value::Bool(b)) => *b,
+        Some(other) => return Err(stdlib_arg_error("file.writeAtomic", "bool", other, span)),
+    };
+
+    let path = match check_write_path(path_str, "file.writeAtomic", security) {
+        Ok(path) => path,
+        Err(msg) => return Ok(Value::Result(Err(Box::new(Value::string(msg))))),
+    };
+
+    match write_atomic(&path, contents.as_bytes(), fsync) {
         Ok(()) => Ok(Value::Result(Ok(Box::new(Value::Null)))),
         Err(e) => Ok(Value::Result(Err(Box::new(Value::string(format!(
-            "File.write: failed to write '{}': {}",
+            "file.writeAtomic: failed to write '{}': {}",
             path_str, e
         )))))),
     }
 }
 
+/// Atomically replace `path` with `contents`.
+///
+/// Writes a uniquely named temp file in the same directory, copies the target's
+/// existing permissions onto it, then renames it over the target. The temp file
+/// is removed if any step fails. When `fsync` is set, both the file data and
+/// (on Unix) the parent directory are synced so the rename survives a crash.
+pub fn write_atomic(path: &Path, contents: &[u8], fsync: bool) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    let file_name = path.file_name().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("'{}' has no file name", path.display()),
+        )
+    })?;
+    let tmp_path = parent.join(format!(
+        ".{}.{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let result = (|| {
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp_path)?;
+        file.write_all(contents)?;
+        if fsync {
+            file.sync_all()?;
+        }
+        drop(file);
+
+        // Keep the target's mode (e.g. an executable script stays executable)
+        if let Ok(meta) = fs::metadata(path) {
+            fs::set_permissions(&tmp_path, meta.permissions())?;
+        }
+
+        fs::rename(&tmp_path, path)?;
+
+        #[cfg(unix)]
+        if fsync {
+            fs::File::open(parent)?.sync_all()?;
+        }
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
 /// Append string to end of file (create if doesn't exist)
 ///
 /// Checks write permission. Creates file if it doesn't exist.
@@ -665,9 +926,264 @@ pub fn copy_file(
     }
 }
 
-/// Get file metadata (size, modified time, is_file, is_dir)
+/// Move a file or directory
+///
+/// Like `file.rename`, but falls back to copy + remove when the destination is on a
+/// different filesystem (rename fails with a cross-device error). The fallback only
+/// applies to regular files. Checks write permission on both source and destination.
+pub fn move_file(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
+    if args.len() != 2 {
+        return Err(stdlib_arity_error("file.move", 2, args.len(), span));
+    }
+
+    let src_str = match &args[0] {
+        Value::String(s) => s.as_ref(),
+        _ => return Err(stdlib_arg_error("file.move", "string", &args[0], span)),
+    };
+
+    let dst_str = match &args[1] {
+        Value::String(s) => s.as_ref(),
+        _ => return Err(stdlib_arg_error("file.move", "string", &args[1], span)),
+    };
+
+    let src_path = PathBuf::from(src_str);
+    let dst_path = PathBuf::from(dst_str);
+
+    // Source must exist
+    let abs_src = match src_path.canonicalize() {
+        Ok(p) => p,
+        Err(e) => {
+            return Ok(Value::Result(Err(Box::new(Value::string(format!(
+                "file.move: source '{}' not found: {}",
+                src_str, e
+            ))))));
+        }
+    };
+
+    // Check write permission on source (it is removed)
+    if security.check_filesystem_write(&abs_src).is_err() {
+        return Ok(Value::Result(Err(Box::new(Value::string(format!(
+            "file.move: permission denied for source '{}'",
+            abs_src.display()
+        ))))));
+    }
+
+    // Check write permission on destination parent
+    let dst_parent = dst_path.parent().unwrap_or_else(|| Path::new("."));
+    let abs_dst_parent = match dst_parent.canonicalize() {
+        Ok(p) => p,
+        Err(e) => {
+            return Ok(Value::Result(Err(Box::new(Value::string(format!(
+                "file.move: destination parent not found: {}",
+                e
+            ))))));
+        }
+    };
+
+    if security.check_filesystem_write(&abs_dst_parent).is_err() {
+        return Ok(Value::Result(Err(Box::new(Value::string(format!(
+            "file.move: permission denied for destination '{}'",
+            abs_dst_parent.display()
+        ))))));
+    }
+
+    // Fast path: same-filesystem rename
+    let rename_err = match fs::rename(&src_path, &dst_path) {
+        Ok(()) => return Ok(Value::Result(Ok(Box::new(Value::Null)))),
+        Err(e) => e,
+    };
+
+    // Cross-device fallback: copy then remove (regular files only); any other
+    // rename failure is the answer
+    if rename_err.kind() != ErrorKind::CrossesDevices || !abs_src.is_file() {
+        return Ok(Value::Result(Err(Box::new(Value::string(format!(
+            "file.move: failed to move '{}' to '{}': {}",
+            src_str, dst_str, rename_err
+        ))))));
+    }
+    if let Err(e) = fs::copy(&src_path, &dst_path) {
+        return Ok(Value::Result(Err(Box::new(Value::string(format!(
+            "file.move: failed to copy '{}' to '{}': {}",
+            src_str, dst_str, e
+        ))))));
+    }
+    match fs::remove_file(&src_path) {
+        Ok(()) => Ok(Value::Result(Ok(Box::new(Value::Null)))),
+        Err(e) => Ok(Value::Result(Err(Box::new(Value::string(format!(
+            "file.move: copied to '{}' but failed to remove source '{}': {}",
+            dst_str, src_str, e
+        )))))),
+    }
+}
+
+/// Set file permissions
 ///
-/// Checks read permission. Returns object with metadata fields.
+/// Checks write permission. On Unix `mode` is applied as the permission bits
+/// (e.g. `0o644` = 420). On other platforms only the owner-write bit is honoured:
+/// clearing it makes the file read-only.
+pub fn set_permissions(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
+    if args.len() != 2 {
+        return Err(stdlib_arity_error(
+            "file.setPermissions",
+            2,
+            args.len(),
+            span,
+        ));
+    }
+
+    let path_str = match &args[0] {
+        Value::String(s) => s.as_ref(),
+        _ => {
+            return Err(stdlib_arg_error(
+                "file.setPermissions",
+                "string",
+                &args[0],
+                span,
+            ))
+        }
+    };
+
+    let mode = match &args[1] {
+        Value::Number(n) if n.fract() == 0.0 && *n >= 0.0 && *n <= 0o7777 as f64 => *n as u32,
+        _ => {
+            return Err(stdlib_arg_error(
+                "file.setPermissions",
+                "mode number between 0 and 0o7777",
+                &args[1],
+                span,
+            ))
+        }
+    };
+
+    let path = PathBuf::from(path_str);
+    let abs_path = match path.canonicalize() {
+        Ok(p) => p,
+        Err(e) => {
+            return Ok(Value::Result(Err(Box::new(Value::string(format!(
+                "file.setPermissions: path '{}' not found: {}",
+                path_str, e
+            ))))));
+        }
+    };
+
+    // Check permission
+    if security.check_filesystem_write(&abs_path).is_err() {
+        return Ok(Value::Result(Err(Box::new(Value::string(format!(
+            "file.setPermissions: permission denied for '{}'",
+            abs_path.display()
+        ))))));
+    }
+
+    let metadata = match fs::metadata(&abs_path) {
+        Ok(m) => m,
+        Err(e) => {
+            return Ok(Value::Result(Err(Box::new(Value::string(format!(
+                "file.setPermissions: failed to read metadata for '{}': {}",
+                abs_path.display(),
+                e
+            ))))));
+        }
+    };
+
+    let mut perms = metadata.permissions();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        perms.set_mode(mode);
+    }
+    #[cfg(not(unix))]
+    {
+        perms.set_readonly(mode & 0o200 == 0);
+    }
+
+    match fs::set_permissions(&abs_path, perms) {
+        Ok(()) => Ok(Value::Result(Ok(Box::new(Value::Null)))),
+        Err(e) => Ok(Value::Result(Err(Box::new(Value::string(format!(
+            "file.setPermissions: failed to update '{}': {}",
+            abs_path.display(),
+            e
+        )))))),
+    }
+}
+
+/// Touch a file
+///
+/// Creates an empty file if it does not exist, otherwise updates its
+/// modified time to now. Checks write permission on the file (or its parent
+/// when creating).
+pub fn touch(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error("file.touch", 1, args.len(), span));
+    }
+
+    let path_str = match &args[0] {
+        Value::String(s) => s.as_ref(),
+        _ => return Err(stdlib_arg_error("file.touch", "string", &args[0], span)),
+    };
+
+    let path = PathBuf::from(path_str);
+
+    // Existing file: check the file itself. New file: check its parent directory.
+    let check_path = match path.canonicalize() {
+        Ok(p) => p,
+        Err(_) => {
+            let parent = path.parent().unwrap_or_else(|| Path::new("."));
+            match parent.canonicalize() {
+                Ok(p) => p,
+                Err(e) => {
+                    return Ok(Value::Result(Err(Box::new(Value::string(format!(
+                        "file.touch: parent directory not found: {}",
+                        e
+                    ))))));
+                }
+            }
+        }
+    };
+
+    if security.check_filesystem_write(&check_path).is_err() {
+        return Ok(Value::Result(Err(Box::new(Value::string(format!(
+            "file.touch: permission denied for '{}'",
+            check_path.display()
+        ))))));
+    }
+
+    let file = match fs::OpenOptions::new().create(true).append(true).open(&path) {
+        Ok(f) => f,
+        Err(e) => {
+            return Ok(Value::Result(Err(Box::new(Value::string(format!(
+                "file.touch: failed to open '{}': {}",
+                path_str, e
+            ))))));
+        }
+    };
+
+    match file.set_modified(std::time::SystemTime::now()) {
+        Ok(()) => Ok(Value::Result(Ok(Box::new(Value::Null)))),
+        Err(e) => Ok(Value::Result(Err(Box::new(Value::string(format!(
+            "file.touch: failed to update modified time of '{}': {}",
+            path_str, e
+        )))))),
+    }
+}
+
+/// Get file metadata as a typed record
+///
+/// Checks read permission. Returns a record with fields:
+/// `size`, `modified`, `accessed`, `created` (Unix seconds, 0 when unavailable),
+/// `isFile`, `isDir`, `isSymlink`, `readonly`, and `permissions` (mode bits).
+/// Type: see [`file_info_type`].
 pub fn file_info(
     args: &[Value],
     span: Span,
@@ -703,24 +1219,72 @@ pub fn file_info(
         span,
     })?;
 
-    // Convert modified time to seconds since Unix epoch
-    let modified = metadata
-        .modified()
-        .ok()
-        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
-        .map(|dur| dur.as_secs() as f64)
-        .unwrap_or(0.0);
-
-    use crate::json_value::JsonValue;
-    let mut info = std::collections::HashMap::new();
-    info.insert("size".to_string(), JsonValue::Number(metadata.len() as f64));
-    info.insert("modified".to_string(), JsonValue::Number(modified));
-    info.insert("isFile".to_string(), JsonValue::Bool(metadata.is_file()));
-    info.insert("isDir".to_string(), JsonValue::Bool(metadata.is_dir()));
-
-    Ok(Value::JsonValue(std::sync::Arc::new(JsonValue::Object(
-        info,
-    ))))
+    // Convert a timestamp to seconds since Unix epoch (0 when the platform lacks it)
+    let seconds = |time: std::io::Result<std::time::SystemTime>| {
+        time.ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|dur| dur.as_secs() as f64)
+            .unwrap_or(0.0)
+    };
+
+    #[cfg(unix)]
+    let mode = {
+        use std::os::unix::fs::PermissionsExt;
+        (metadata.permissions().mode() & 0o7777) as f64
+    };
+    #[cfg(not(unix))]
+    let mode = if metadata.permissions().readonly() {
+        0o444 as f64
+    } else {
+        0o666 as f64
+    };
+
+    let is_symlink = fs::symlink_metadata(&path)
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false);
+
+    use crate::stdlib::collections::hash::HashKey;
+    use crate::stdlib::collections::hashmap::AtlasHashMap;
+    let mut info = AtlasHashMap::new();
+    let mut field = |name: &str, value: Value| {
+        info.insert(
+            HashKey::String(std::sync::Arc::new(name.to_string())),
+            value,
+        );
+    };
+    field("size", Value::Number(metadata.len() as f64));
+    field("modified", Value::Number(seconds(metadata.modified())));
+    field("accessed", Value::Number(seconds(metadata.accessed())));
+    field("created", Value::Number(seconds(metadata.created())));
+    field("isFile", Value::Bool(metadata.is_file()));
+    field("isDir", Value::Bool(metadata.is_dir()));
+    field("isSymlink", Value::Bool(is_symlink));
+    field("readonly", Value::Bool(metadata.permissions().readonly()));
+    field("permissions", Value::Number(mode));
+
+    Ok(Value::Map(crate::value::ValueHashMap::from_atlas(info)))
+}
+
+/// Static type of the record returned by `file.info()`.
+pub fn file_info_type() -> crate::types::Type {
+    use crate::types::{StructuralMemberType, Type};
+    let member = |name: &str, ty: Type| StructuralMemberType {
+        name: name.to_string(),
+        ty,
+    };
+    Type::Structural {
+        members: vec![
+            member("size", Type::Number),
+            member("modified", Type::Number),
+            member("accessed", Type::Number),
+            member("created", Type::Number),
+            member("isFile", Type::Bool),
+            member("isDir", Type::Bool),
+            member("isSymlink", Type::Bool),
+            member("readonly", Type::Bool),
+            member("permissions", Type::Number),
+        ],
+    }
 }
 
 /// Join path components with OS-specific separator
@@ -800,7 +1364,6 @@ pub fn io_read_all(
     if !args.is_empty() {
         return Err(stdlib_arity_error("io.readAll", 0, args.len(), span));
     }
-    use std::io::Read;
     let mut buf = String::new();
     std::io::stdin()
         .lock()
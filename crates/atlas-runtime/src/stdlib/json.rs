@@ -3,7 +3,7 @@
 use super::stdlib_arity_error;
 use crate::json_value::JsonValue;
 use crate::span::Span;
-use crate::value::{RuntimeError, Value};
+use crate::value::{format_number, RuntimeError, Value};
 use std::collections::HashSet;
 use std::sync::Arc;
 
@@ -879,6 +879,269 @@ pub fn json_ns_is_null(args: &[Value], span: Span) -> Result<Value, RuntimeError
     Ok(Value::Bool(is_null))
 }
 
+// ============================================================================
+// JSON Path Queries
+// ============================================================================
+// Deep access into a JsonValue with a path like `a.b[2].c` instead of a chain
+// of getObject/getArray calls. Called via method syntax or the Json namespace:
+//   data.getPath("a.b[2].c")         → jsonGetPath(data, path)
+//   data.setPath("a.b[2].c", value)  → jsonSetPath(data, path, value)
+//   data.hasPath("a.b[2].c")         → jsonHasPath(data, path)
+//
+// Path syntax: an optional leading `$`, then `.key` or `key` segments and
+// `[index]` array indices. Keys containing `.`, `[` or spaces are written
+// quoted: `["content-type"]` or `['a.b']`. An empty path (or `$`) is the
+// value itself.
+
+/// One step of a parsed JSON path
+#[derive(Debug, Clone, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Read the value at `path`.
+///
+/// Atlas signature: `jsonGetPath(json: json, path: string) -> Result<json, string>`
+/// - `Err` names the first segment that is missing or of the wrong kind
+pub fn json_get_path(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 2 {
+        return Err(stdlib_arity_error("jsonGetPath", 2, args.len(), span));
+    }
+    let (json, segments) = path_args("jsonGetPath", args, span)?;
+    Ok(Value::Result(match lookup_path(json, &segments) {
+        Ok(found) => Ok(Box::new(Value::JsonValue(Arc::new(found.clone())))),
+        Err(msg) => Err(Box::new(Value::string(format!("getPath: {}", msg)))),
+    }))
+}
+
+/// Return a copy of the value with `path` set to `value`.
+///
+/// Atlas signature: `jsonSetPath(json: json, path: string, value: any) -> Result<json, string>`
+/// - Every segment but the last must already exist
+/// - The last segment may add a key to an object, or append to an array
+///   when its index equals the array length
+/// - `value` is converted like `Json.stringify` would
+pub fn json_set_path(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 3 {
+        return Err(stdlib_arity_error("jsonSetPath", 3, args.len(), span));
+    }
+    let (json, segments) = path_args("jsonSetPath", args, span)?;
+    let value = match &args[2] {
+        Value::JsonValue(value) => value.as_ref().clone(),
+        other => {
+            let text = value_to_json(other, &mut HashSet::new(), span)?;
+            serde_json::from_str(&text)
+                .map(serde_to_atlas_json)
+                .map_err(|e| RuntimeError::TypeError {
+                    msg: format!("jsonSetPath(): cannot convert value to json: {}", e),
+                    span,
+                })?
+        }
+    };
+    let mut root = json.clone();
+    Ok(Value::Result(
+        match set_path(&mut root, &segments, value, &mut String::from("$")) {
+            Ok(()) => Ok(Box::new(Value::JsonValue(Arc::new(root)))),
+            Err(msg) => Err(Box::new(Value::string(format!("setPath: {}", msg)))),
+        },
+    ))
+}
+
+/// Whether a value exists at `path`.
+///
+/// Atlas signature: `jsonHasPath(json: json, path: string) -> bool`
+pub fn json_has_path(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 2 {
+        return Err(stdlib_arity_error("jsonHasPath", 2, args.len(), span));
+    }
+    let (json, segments) = path_args("jsonHasPath", args, span)?;
+    Ok(Value::Bool(lookup_path(json, &segments).is_ok()))
+}
+
+/// The json receiver and parsed path shared by the path functions
+fn path_args<'a>(
+    name: &str,
+    args: &'a [Value],
+    span: Span,
+) -> Result<(&'a JsonValue, Vec<PathSegment>), RuntimeError> {
+    let Value::JsonValue(json) = &args[0] else {
+        return Err(RuntimeError::TypeError {
+            msg: format!("{}() requires json argument", name),
+            span,
+        });
+    };
+    let Value::String(path) = &args[1] else {
+        return Err(RuntimeError::TypeError {
+            msg: format!("{}() requires string path", name),
+            span,
+        });
+    };
+    let segments = parse_path(path).map_err(|detail| RuntimeError::InvalidStdlibArgument {
+        msg: format!("{}(): invalid path '{}': {}", name, path, detail),
+        span,
+    })?;
+    Ok((json.as_ref(), segments))
+}
+
+fn parse_path(path: &str) -> Result<Vec<PathSegment>, String> {
+    let chars: Vec<char> = path.chars().collect();
+    let first = usize::from(chars.first() == Some(&'$'));
+    let mut i = first;
+    let mut segments = Vec::new();
+    while i < chars.len() {
+        if chars[i] == '[' {
+            i += 1;
+            let segment = match chars.get(i) {
+                Some(&quote) if quote == '"' || quote == '\'' => {
+                    i += 1;
+                    let mut key = String::new();
+                    loop {
+                        match chars.get(i) {
+                            None => return Err("unterminated quoted key".to_string()),
+                            Some(&c) if c == quote => break,
+                            Some('\\') if i + 1 < chars.len() => {
+                                key.push(chars[i + 1]);
+                                i += 1;
+                            }
+                            Some(&c) => key.push(c),
+                        }
+                        i += 1;
+                    }
+                    i += 1;
+                    PathSegment::Key(key)
+                }
+                _ => {
+                    let start = i;
+                    while chars.get(i).is_some_and(|c| c.is_ascii_digit()) {
+                        i += 1;
+                    }
+                    let digits: String = chars[start..i].iter().collect();
+                    PathSegment::Index(digits.parse().map_err(|_| {
+                        format!("expected an index or quoted key at position {}", start)
+                    })?)
+                }
+            };
+            if chars.get(i) != Some(&']') {
+                return Err(format!("expected ']' at position {}", i));
+            }
+            i += 1;
+            segments.push(segment);
+            continue;
+        }
+        // `.key`, or a bare key at the start of the path
+        if chars[i] == '.' {
+            i += 1;
+        } else if i != first {
+            return Err(format!("expected '.' or '[' at position {}", i));
+        }
+        let start = i;
+        while i < chars.len() && chars[i] != '.' && chars[i] != '[' {
+            i += 1;
+        }
+        if i == start {
+            return Err(format!("empty key at position {}", start));
+        }
+        segments.push(PathSegment::Key(chars[start..i].iter().collect()));
+    }
+    Ok(segments)
+}
+
+fn lookup_path<'a>(root: &'a JsonValue, segments: &[PathSegment]) -> Result<&'a JsonValue, String> {
+    let mut current = root;
+    let mut at = String::from("$");
+    for segment in segments {
+        current = match (segment, current) {
+            (PathSegment::Key(key), JsonValue::Object(map)) => {
+                map.get(key).ok_or_else(|| missing_key(key, &at))?
+            }
+            (PathSegment::Index(index), JsonValue::Array(items)) => items
+                .get(*index)
+                .ok_or_else(|| out_of_bounds(*index, items.len(), &at))?,
+            (segment, other) => return Err(wrong_kind(segment, other, &at)),
+        };
+        push_segment(&mut at, segment);
+    }
+    Ok(current)
+}
+
+fn set_path(
+    target: &mut JsonValue,
+    segments: &[PathSegment],
+    value: JsonValue,
+    at: &mut String,
+) -> Result<(), String> {
+    let Some((segment, rest)) = segments.split_first() else {
+        *target = value;
+        return Ok(());
+    };
+    let child = match (segment, target) {
+        (PathSegment::Key(key), JsonValue::Object(map)) => {
+            if rest.is_empty() {
+                map.insert(key.clone(), value);
+                return Ok(());
+            }
+            map.get_mut(key).ok_or_else(|| missing_key(key, at))?
+        }
+        (PathSegment::Index(index), JsonValue::Array(items)) => {
+            if rest.is_empty() && *index == items.len() {
+                items.push(value);
+                return Ok(());
+            }
+            let len = items.len();
+            items
+                .get_mut(*index)
+                .ok_or_else(|| out_of_bounds(*index, len, at))?
+        }
+        (segment, other) => return Err(wrong_kind(segment, other, at)),
+    };
+    push_segment(at, segment);
+    set_path(child, rest, value, at)
+}
+
+/// Append `segment` to a path as it would be written
+fn push_segment(at: &mut String, segment: &PathSegment) {
+    match segment {
+        PathSegment::Key(key)
+            if key.chars().next().is_some_and(|c| !c.is_ascii_digit())
+                && key.chars().all(|c| c.is_alphanumeric() || c == '_') =>
+        {
+            at.push('.');
+            at.push_str(key);
+        }
+        PathSegment::Key(key) => at.push_str(&format!("[{:?}]", key)),
+        PathSegment::Index(index) => at.push_str(&format!("[{}]", index)),
+    }
+}
+
+fn missing_key(key: &str, at: &str) -> String {
+    format!("no key '{}' in object at {}", key, at)
+}
+
+fn out_of_bounds(index: usize, len: usize, at: &str) -> String {
+    format!(
+        "index {} out of bounds for array of length {} at {}",
+        index, len, at
+    )
+}
+
+fn wrong_kind(segment: &PathSegment, found: &JsonValue, at: &str) -> String {
+    match segment {
+        PathSegment::Key(key) => format!(
+            "cannot read key '{}' from {} at {}",
+            key,
+            json_type_name(found),
+            at
+        ),
+        PathSegment::Index(index) => format!(
+            "cannot read index {} from {} at {}",
+            index,
+            json_type_name(found),
+            at
+        ),
+    }
+}
+
 // ============================================================================
 // Typed JSON Deserialization (H-293)
 // ============================================================================
@@ -968,7 +1231,7 @@ pub fn json_parse_typed(args: &[Value], span: Span) -> Result<Value, RuntimeErro
 }
 
 /// Convert a JSON value to an Atlas Value (for typed deserialization)
-fn json_to_atlas_value(value: serde_json::Value) -> Value {
+pub(crate) fn json_to_atlas_value(value: serde_json::Value) -> Value {
     match value {
         serde_json::Value::Null => Value::Null,
         serde_json::Value::Bool(b) => Value::Bool(b),
@@ -1046,12 +1309,7 @@ fn value_to_json(
                     span,
                 });
             }
-            // Format number (remove unnecessary decimals)
-            if n.fract() == 0.0 && n.abs() < 1e15 {
-                Ok(format!("{:.0}", n))
-            } else {
-                Ok(n.to_string())
-            }
+            Ok(format_number(*n))
         }
         Value::String(s) => {
             // Use serde_json to properly escape the string
@@ -1068,8 +1326,8 @@ fn value_to_json(
             // Check for circular reference using pointer address
             let ptr = Arc::as_ptr(arr_ref.arc()) as usize;
             if !visited.insert(ptr) {
-                return Err(RuntimeError::TypeError {
-                    msg: "Circular reference detected in array".to_string(),
+                return Err(RuntimeError::CircularReference {
+                    msg: "array contains itself".to_string(),
                     span,
                 });
             }
@@ -1113,8 +1371,8 @@ fn value_to_json(
             // Check for circular reference using pointer address
             let ptr = Arc::as_ptr(map_ref.arc()) as usize;
             if !visited.insert(ptr) {
-                return Err(RuntimeError::TypeError {
-                    msg: "Circular reference detected in HashMap".to_string(),
+                return Err(RuntimeError::CircularReference {
+                    msg: "HashMap contains itself".to_string(),
                     span,
                 });
             }
@@ -1126,14 +1384,7 @@ fn value_to_json(
                 // Convert HashKey to string for JSON object key
                 let key_str = match &key {
                     HashKey::String(s) => s.as_ref().clone(),
-                    HashKey::Number(n) => {
-                        let f = n.into_inner();
-                        if f.fract() == 0.0 && f.abs() < 1e15 {
-                            format!("{:.0}", f)
-                        } else {
-                            f.to_string()
-                        }
-                    }
+                    HashKey::Number(n) => format_number(n.into_inner()),
                     HashKey::Bool(b) => b.to_string(),
                     HashKey::Null => "null".to_string(),
                 };
@@ -1167,6 +1418,7 @@ fn value_to_json(
             msg: "Cannot serialize Stack to JSON".to_string(),
             span,
         }),
+        #[cfg(feature = "regex")]
         Value::Regex(_) => Err(RuntimeError::TypeError {
             msg: "Cannot serialize Regex to JSON".to_string(),
             span,
@@ -1175,6 +1427,11 @@ fn value_to_json(
             // Serialize DateTime as ISO 8601 string
             Ok(serde_json::to_string(&dt.to_rfc3339()).unwrap())
         }
+        Value::Bytes(_) => Err(RuntimeError::TypeError {
+            msg: "Cannot serialize Bytes to JSON (use toBase64() or toHex())".to_string(),
+            span,
+        }),
+        #[cfg(feature = "http")]
         Value::HttpRequest(_) | Value::HttpResponse(_) => Err(RuntimeError::TypeError {
             msg: "Cannot serialize HttpRequest/HttpResponse to JSON".to_string(),
             span,
@@ -1187,6 +1444,18 @@ fn value_to_json(
             msg: "Cannot serialize SqliteConnection to JSON".to_string(),
             span,
         }),
+        Value::KvStore(_) => Err(RuntimeError::TypeError {
+            msg: "Cannot serialize KvStore to JSON".to_string(),
+            span,
+        }),
+        Value::FileHandle(_) => Err(RuntimeError::TypeError {
+            msg: "Cannot serialize FileHandle to JSON".to_string(),
+            span,
+        }),
+        Value::ProgressBar(_) => Err(RuntimeError::TypeError {
+            msg: "Cannot serialize ProgressBar to JSON".to_string(),
+            span,
+        }),
         Value::Future(_) => Err(RuntimeError::TypeError {
             msg: "Cannot serialize Future to JSON".to_string(),
             span,
@@ -1207,19 +1476,34 @@ fn value_to_json(
             msg: "Cannot serialize AsyncMutex to JSON".to_string(),
             span,
         }),
+        #[cfg(feature = "fs")]
         Value::Watcher(_) => Err(RuntimeError::TypeError {
             msg: "Cannot serialize Watcher to JSON".to_string(),
             span,
         }),
-        Value::SharedValue(_) => Err(RuntimeError::TypeError {
-            msg: "Cannot serialize SharedValue to JSON".to_string(),
+        Value::Resource(r) => Err(RuntimeError::TypeError {
+            msg: format!("Cannot serialize Resource ({}) to JSON", r.kind()),
             span,
         }),
+        Value::SharedValue(cell) => {
+            // The only way a value can contain itself: serialize the contents,
+            // but refuse to re-enter a cell that is already being serialized
+            let ptr = cell.as_ptr() as usize;
+            if !visited.insert(ptr) {
+                return Err(RuntimeError::CircularReference {
+                    msg: "shared value contains itself; cannot serialize to JSON".to_string(),
+                    span,
+                });
+            }
+            let result = cell.with(|inner| value_to_json(inner, visited, span));
+            visited.remove(&ptr);
+            result
+        }
         Value::Tuple(elems) => {
             let ptr = std::sync::Arc::as_ptr(elems) as usize;
             if !visited.insert(ptr) {
-                return Err(RuntimeError::TypeError {
-                    msg: "Circular reference detected in Tuple".to_string(),
+                return Err(RuntimeError::CircularReference {
+                    msg: "Tuple contains itself".to_string(),
                     span,
                 });
             }
@@ -1269,11 +1553,7 @@ fn json_value_to_string(json: &JsonValue, span: Span) -> Result<String, RuntimeE
                     span,
                 });
             }
-            if n.fract() == 0.0 && n.abs() < 1e15 {
-                Ok(format!("{:.0}", n))
-            } else {
-                Ok(n.to_string())
-            }
+            Ok(format_number(*n))
         }
         JsonValue::String(s) => Ok(serde_json::to_string(s).unwrap()),
         JsonValue::Array(arr) => {
@@ -0,0 +1,330 @@
+//! Persistent key-value store
+//!
+//! A small embedded store for settings, caches, and other state that has to
+//! survive a restart without the ceremony of a SQL schema:
+//! - kv.open(path) -> KvStore
+//! - store.get(key) -> Option<any>
+//! - store.set(key, value) -> null
+//! - store.delete(key) -> bool
+//! - store.has(key) -> bool
+//! - store.keys() -> string[]
+//! - store.transaction(fn) -> any
+//! - store.close() -> null
+//!
+//! Values are stored as JSON, so anything `Json.stringify` accepts can be
+//! stored and comes back as the equivalent Atlas value. The store is a
+//! single-table SQLite database with a rollback journal and `synchronous =
+//! FULL`: every `set`/`delete` (or every `transaction`) is durable once it
+//! returns, and a crash mid-write leaves the previous contents intact.
+//!
+//! Opening a store file requires the database capability
+//! (`[security.database]`); ":memory:" stores are always allowed.
+
+use super::stdlib_arity_error;
+use crate::security::SecurityContext;
+use crate::span::Span;
+use crate::value::{RuntimeError, Value};
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+/// How long a write waits for another process holding the store's lock
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+const SCHEMA: &str = "PRAGMA journal_mode = DELETE;
+PRAGMA synchronous = FULL;
+CREATE TABLE IF NOT EXISTS kv (
+    key TEXT PRIMARY KEY NOT NULL,
+    value TEXT NOT NULL
+) WITHOUT ROWID;";
+
+// ============================================================================
+// KvStore
+// ============================================================================
+
+/// An open key-value store. Closing it (or dropping the last reference)
+/// releases the underlying database file.
+pub struct KvStore {
+    conn: Mutex<Option<Connection>>,
+}
+
+impl KvStore {
+    fn new(conn: Connection) -> Self {
+        KvStore {
+            conn: Mutex::new(Some(conn)),
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.conn.lock().map(|g| g.is_none()).unwrap_or(true)
+    }
+
+    /// Whether `transaction` is running on this store
+    pub fn in_transaction(&self) -> bool {
+        self.conn
+            .lock()
+            .ok()
+            .and_then(|guard| guard.as_ref().map(|c| !c.is_autocommit()))
+            .unwrap_or(false)
+    }
+
+    /// Start the transaction `store.transaction(fn)` runs `fn` in
+    pub(crate) fn begin(&self, span: Span) -> Result<(), RuntimeError> {
+        if self.in_transaction() {
+            return Err(RuntimeError::TypeError {
+                msg: "transaction: a transaction is already running on this store".to_string(),
+                span,
+            });
+        }
+        self.with_conn("transaction", span, |c| c.execute_batch("BEGIN IMMEDIATE"))
+    }
+
+    /// Make the running transaction's writes durable
+    pub(crate) fn commit(&self, span: Span) -> Result<(), RuntimeError> {
+        self.with_conn("transaction", span, |c| c.execute_batch("COMMIT"))
+    }
+
+    /// Discard the running transaction's writes. A store closed inside the
+    /// callback has already rolled back.
+    pub(crate) fn rollback(&self, span: Span) -> Result<(), RuntimeError> {
+        if !self.in_transaction() {
+            return Ok(());
+        }
+        self.with_conn("transaction", span, |c| c.execute_batch("ROLLBACK"))
+    }
+
+    fn lock(
+        &self,
+        method: &str,
+        span: Span,
+    ) -> Result<MutexGuard<'_, Option<Connection>>, RuntimeError> {
+        self.conn.lock().map_err(|_| RuntimeError::IoError {
+            message: format!("{}: store lock poisoned", method),
+            span,
+        })
+    }
+
+    fn with_conn<T>(
+        &self,
+        method: &str,
+        span: Span,
+        f: impl FnOnce(&Connection) -> rusqlite::Result<T>,
+    ) -> Result<T, RuntimeError> {
+        let guard = self.lock(method, span)?;
+        let Some(conn) = guard.as_ref() else {
+            return Err(RuntimeError::IoError {
+                message: format!("{}: store is closed", method),
+                span,
+            });
+        };
+        f(conn).map_err(|e| RuntimeError::IoError {
+            message: format!("{}: {}", method, e),
+            span,
+        })
+    }
+}
+
+impl std::fmt::Debug for KvStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let closed = self.is_closed();
+        write!(f, "KvStore({})", if closed { "closed" } else { "open" })
+    }
+}
+
+// ============================================================================
+// Namespace Functions (kv.*)
+// ============================================================================
+
+/// Open (or create) a key-value store.
+///
+/// Atlas signature: `kv.open(path: string) -> KvStore`
+/// - ":memory:" creates a store that lives until it is closed
+/// - A file path requires database permission
+pub fn open(args: &[Value], span: Span, security: &SecurityContext) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error("kv.open", 1, args.len(), span));
+    }
+    let path = match &args[0] {
+        Value::String(s) => s.as_ref().to_string(),
+        _ => {
+            return Err(RuntimeError::TypeError {
+                msg: "kv.open() requires string path argument".to_string(),
+                span,
+            })
+        }
+    };
+
+    security
+        .check_database(&path)
+        .map_err(|_| RuntimeError::DatabasePermissionDenied {
+            path: path.clone(),
+            span,
+        })?;
+
+    let flags = OpenFlags::SQLITE_OPEN_READ_WRITE
+        | OpenFlags::SQLITE_OPEN_CREATE
+        | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+    let conn = Connection::open_with_flags(&path, flags)
+        .and_then(|conn| {
+            conn.busy_timeout(BUSY_TIMEOUT)?;
+            conn.execute_batch(SCHEMA)?;
+            Ok(conn)
+        })
+        .map_err(|e| RuntimeError::IoError {
+            message: format!("kv.open: failed to open '{}': {}", path, e),
+            span,
+        })?;
+    Ok(Value::KvStore(Arc::new(KvStore::new(conn))))
+}
+
+// ============================================================================
+// Instance Methods (KvStore.*)
+// ============================================================================
+
+/// Look up a key.
+///
+/// Atlas signature: `store.get(key: string) -> Option<any>`
+pub fn get(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    let (store, key) = store_and_key(args, "get", 2, span)?;
+    let text: Option<String> = store.with_conn("get", span, |c| {
+        c.query_row("SELECT value FROM kv WHERE key = ?1", params![key], |row| {
+            row.get(0)
+        })
+        .optional()
+    })?;
+    match text {
+        Some(text) => {
+            let json = serde_json::from_str(&text).map_err(|e| RuntimeError::IoError {
+                message: format!("get: stored value for '{}' is corrupt: {}", key, e),
+                span,
+            })?;
+            Ok(Value::Option(Some(Box::new(
+                super::json::json_to_atlas_value(json),
+            ))))
+        }
+        None => Ok(Value::Option(None)),
+    }
+}
+
+/// Store a value under a key, replacing any previous value.
+///
+/// Atlas signature: `store.set(key: string, value: any) -> null`
+pub fn set(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    let (store, key) = store_and_key(args, "set", 3, span)?;
+    let text = match super::json::to_json(&args[2..], span)? {
+        Value::String(s) => s.as_ref().to_string(),
+        other => unreachable!("to_json returned {}", other.type_name()),
+    };
+    store.with_conn("set", span, |c| {
+        c.execute(
+            "INSERT INTO kv (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, text],
+        )
+    })?;
+    Ok(Value::Null)
+}
+
+/// Remove a key. Returns whether it was present.
+///
+/// Atlas signature: `store.delete(key: string) -> bool`
+pub fn delete(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    let (store, key) = store_and_key(args, "delete", 2, span)?;
+    let removed = store.with_conn("delete", span, |c| {
+        c.execute("DELETE FROM kv WHERE key = ?1", params![key])
+    })?;
+    Ok(Value::Bool(removed > 0))
+}
+
+/// Whether a key is present.
+///
+/// Atlas signature: `store.has(key: string) -> bool`
+pub fn has(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    let (store, key) = store_and_key(args, "has", 2, span)?;
+    let found = store.with_conn("has", span, |c| {
+        c.query_row("SELECT 1 FROM kv WHERE key = ?1", params![key], |_| Ok(()))
+            .optional()
+    })?;
+    Ok(Value::Bool(found.is_some()))
+}
+
+/// All keys, in ascending order.
+///
+/// Atlas signature: `store.keys() -> string[]`
+pub fn keys(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error("store.keys", 1, args.len(), span));
+    }
+    let store = expect_store(&args[0], "keys", span)?;
+    let keys: Vec<String> = store.with_conn("keys", span, |c| {
+        let mut stmt = c.prepare("SELECT key FROM kv ORDER BY key")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect()
+    })?;
+    Ok(Value::array(keys.into_iter().map(Value::string).collect()))
+}
+
+/// Close the store. A transaction still running is rolled back.
+///
+/// Atlas signature: `store.close() -> null`
+pub fn close(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error("store.close", 1, args.len(), span));
+    }
+    let store = expect_store(&args[0], "close", span)?;
+    let mut guard = store.lock("close", span)?;
+    if guard.take().is_none() {
+        return Err(RuntimeError::IoError {
+            message: "close: store already closed".to_string(),
+            span,
+        });
+    }
+    Ok(Value::Null)
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+pub(crate) fn expect_store<'a>(
+    value: &'a Value,
+    method: &str,
+    span: Span,
+) -> Result<&'a Arc<KvStore>, RuntimeError> {
+    match value {
+        Value::KvStore(store) => Ok(store),
+        other => Err(RuntimeError::TypeError {
+            msg: format!("{}() requires KvStore, got {}", method, other.type_name()),
+            span,
+        }),
+    }
+}
+
+/// Check arity and extract the receiver and its string key argument
+fn store_and_key<'a>(
+    args: &'a [Value],
+    method: &str,
+    arity: usize,
+    span: Span,
+) -> Result<(&'a Arc<KvStore>, &'a str), RuntimeError> {
+    if args.len() != arity {
+        return Err(stdlib_arity_error(
+            &format!("store.{}", method),
+            arity,
+            args.len(),
+            span,
+        ));
+    }
+    let store = expect_store(&args[0], method, span)?;
+    match &args[1] {
+        Value::String(key) => Ok((store, key.as_ref())),
+        other => Err(RuntimeError::TypeError {
+            msg: format!(
+                "{}() requires string key, got {}",
+                method,
+                other.type_name()
+            ),
+            span,
+        }),
+    }
+}
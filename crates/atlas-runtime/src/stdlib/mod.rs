@@ -1,28 +1,42 @@
 //! Standard library functions
 
+pub mod args;
 pub mod array;
-#[cfg(feature = "http")]
 pub mod async_io;
 pub mod async_primitives;
+pub mod bytes;
 pub mod collections;
 pub mod compression;
+pub mod config;
 pub mod console;
+pub mod csv;
 pub mod datetime;
+pub mod file_handle;
 pub mod fs;
 pub mod future;
 #[cfg(feature = "http")]
 pub mod http;
 #[cfg(feature = "http")]
 pub mod http_server;
+pub mod interrupt;
 pub mod io;
 pub mod json;
+pub mod kv;
 pub mod math;
 pub mod path;
 pub mod process;
 pub mod reflect;
+#[cfg(feature = "regex")]
 pub mod regex;
+
+/// Why a regex literal is rejected when the `regex` feature is off
+#[cfg(not(feature = "regex"))]
+pub(crate) const REGEX_DISABLED: &str =
+    "regex support is not enabled (build with the `regex` feature)";
+pub mod sort;
 pub mod sqlite;
 pub mod string;
+pub mod terminal;
 pub mod test;
 pub mod types;
 
@@ -45,13 +59,17 @@ use std::sync::{Arc, Mutex, OnceLock};
 /// Default implementation writes to stdout.
 pub type OutputWriter = Arc<Mutex<Box<dyn Write + Send>>>;
 
-/// Construct a writer that goes to real stdout (the default).
+/// The writer that goes to real stdout (the default).
+///
+/// Every call returns the same shared writer, which is how
+/// `terminal::is_terminal` tells stdout apart from captured output.
 pub fn stdout_writer() -> OutputWriter {
-    Arc::new(Mutex::new(Box::new(std::io::stdout())))
+    static STDOUT: OnceLock<OutputWriter> = OnceLock::new();
+    Arc::clone(STDOUT.get_or_init(|| Arc::new(Mutex::new(Box::new(std::io::stdout())))))
 }
 
 /// A builtin dispatch function: takes args, span, security, output → Result<Value, RuntimeError>
-type BuiltinFn =
+pub(crate) type BuiltinFn =
     fn(&[Value], crate::span::Span, &SecurityContext, &OutputWriter) -> Result<Value, RuntimeError>;
 
 /// Look up the documented signature for a stdlib function.
@@ -68,6 +86,7 @@ fn stdlib_signature(func_name: &str) -> Option<&'static str> {
         // Core builtins
         "len" => Some("len(value: string | []any): number"),
         "str" => Some("str(value: any): string"),
+        "inspect" => Some("inspect(value: any, options?: Map<string, number>): string"),
         "num" => Some("num(value: string): number"),
         "bool" => Some("bool(value: any): bool"),
         "type" => Some("type(value: any): string"),
@@ -114,6 +133,7 @@ fn stdlib_signature(func_name: &str) -> Option<&'static str> {
         "arrayEnumerate" => Some("arr.enumerate(): (number, T)[]  [use method syntax]"),
         "arrayFlat" => Some("arr.flat(): T[]  [use method syntax]"),
         "arrayFlatMap" => Some("arr.flatMap(fn: (T): U[]): U[]  [use method syntax]"),
+        "arrayRange" => Some("Array.range(start: number, end: number, step?: number): number[]"),
         "arrayFill" => {
             Some("arr.fill(value: T, start: number, end: number): T[]  [use method syntax]")
         }
@@ -257,6 +277,24 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
         m.insert("consoleDebug", |args, span, _, output| {
             console::console_debug(args, span, output)
         });
+        m.insert("consolePrintTable", |args, span, _, output| {
+            terminal::print_table(args, span, output)
+        });
+        m.insert("consoleProgressBar", |args, span, _, output| {
+            terminal::progress_bar(args, span, output)
+        });
+        m.insert("consoleStyled", |args, span, _, output| {
+            terminal::styled(args, span, output)
+        });
+        // ProgressBar instance methods
+        m.insert("progressBarAdvance", |a, s, _, _| {
+            terminal::bar_advance(a, s)
+        });
+        m.insert("progressBarSet", |a, s, _, _| terminal::bar_set(a, s));
+        m.insert("progressBarSetLabel", |a, s, _, _| {
+            terminal::bar_set_label(a, s)
+        });
+        m.insert("progressBarFinish", |a, s, _, _| terminal::bar_finish(a, s));
         m.insert("len", |args, span, _, _| {
             if args.len() != 1 {
                 return Err(stdlib_arity_error("len", 1, args.len(), span));
@@ -667,6 +705,9 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
         m.insert("jsonGetArray", |a, s, _, _| json::json_get_array(a, s));
         m.insert("jsonGetObject", |a, s, _, _| json::json_get_object(a, s));
         m.insert("jsonIsNull", |a, s, _, _| json::json_is_null(a, s));
+        m.insert("jsonGetPath", |a, s, _, _| json::json_get_path(a, s));
+        m.insert("jsonSetPath", |a, s, _, _| json::json_set_path(a, s));
+        m.insert("jsonHasPath", |a, s, _, _| json::json_has_path(a, s));
         // Json namespace string-based methods (B23) — used by Json.* dispatch
         m.insert("jsonNsMinify", |a, s, _, _| json::json_ns_minify(a, s));
         m.insert("jsonNsKeys", |a, s, _, _| json::json_ns_keys(a, s));
@@ -696,6 +737,18 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
         m.insert("isNull", |a, s, _, _| types::is_null(a, s));
         m.insert("isArray", |a, s, _, _| types::is_array(a, s));
         m.insert("arrayIsArray", |a, s, _, _| types::is_array(a, s));
+        m.insert("arrayRange", |args, span, _, _| {
+            if !(2..=3).contains(&args.len()) {
+                return Err(stdlib_arity_error("arrayRange", 2, args.len(), span));
+            }
+            let start = extract_number(&args[0], "arrayRange", span)?;
+            let end = extract_number(&args[1], "arrayRange", span)?;
+            let step = match args.get(2) {
+                Some(step) => extract_number(step, "arrayRange", span)?,
+                None => 1.0,
+            };
+            array::range(start, end, step, span)
+        });
         m.insert("isFunction", |a, s, _, _| types::is_function(a, s));
         m.insert("isObject", |a, s, _, _| types::is_object(a, s));
         m.insert("isType", |a, s, _, _| types::is_type(a, s));
@@ -707,6 +760,7 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
         // Type conversion functions
         // ====================================================================
         m.insert("toString", |a, s, _, _| types::to_string(a, s));
+        m.insert("inspect", |a, s, _, _| types::inspect(a, s));
         m.insert("toNumber", |a, s, _, _| types::to_number(a, s));
         m.insert("toBool", |a, s, _, _| types::to_bool(a, s));
         m.insert("parseInt", |a, s, _, _| types::parse_int(a, s));
@@ -917,35 +971,53 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
         m.insert("ioNsWriteLine", |a, s, sc, _| io::io_write_line(a, s, sc));
         m.insert("ioNsReadAll", |a, s, sc, _| io::io_read_all(a, s, sc));
         m.insert("ioNsFlush", |a, s, sc, _| io::io_flush(a, s, sc));
-        // B24: bare globals removed — all file.* calls route through fileNs* keys.
-        m.insert("fileNsRead", |a, s, sc, _| io::read_file(a, s, sc));
-        m.insert("fileNsWrite", |a, s, sc, _| io::write_file(a, s, sc));
-        m.insert("fileNsAppend", |a, s, sc, _| io::append_file(a, s, sc));
-        m.insert("fileNsExists", |a, s, sc, _| io::file_exists(a, s, sc));
-        m.insert("fileNsRemove", |a, s, sc, _| io::remove_file(a, s, sc));
-        m.insert("fileNsRename", |a, s, sc, _| io::rename_file(a, s, sc));
-        m.insert("fileNsCopy", |a, s, sc, _| io::copy_file(a, s, sc));
-        // B40-P07: Async file operations via file.* namespace
-        m.insert("fileNsReadAsync", |a, s, sc, _| {
-            async_io::read_file_async(a, s, sc)
-        });
-        m.insert("fileNsWriteAsync", |a, s, sc, _| {
-            async_io::write_file_async(a, s, sc)
-        });
-        m.insert("fileNsAppendAsync", |a, s, sc, _| {
-            async_io::append_file_async(a, s, sc)
-        });
-        m.insert("fileNsRenameAsync", |a, s, sc, _| {
-            async_io::rename_file_async(a, s, sc)
-        });
-        m.insert("fileNsCopyAsync", |a, s, sc, _| {
-            async_io::copy_file_async(a, s, sc)
-        });
-        m.insert("fileNsCreateDir", |a, s, sc, _| io::create_dir(a, s, sc));
-        m.insert("fileNsRemoveDir", |a, s, sc, _| io::remove_dir(a, s, sc));
-        m.insert("fileInfo", |a, s, sc, _| io::file_info(a, s, sc));
-        m.insert("fileNsInfo", |a, s, sc, _| io::file_info(a, s, sc));
-        m.insert("pathJoin", |a, s, sc, _| io::path_join(a, s, sc));
+        #[cfg(feature = "fs")]
+        {
+            // B24: bare globals removed — all file.* calls route through fileNs* keys.
+            m.insert("fileNsRead", |a, s, sc, _| io::read_file(a, s, sc));
+            m.insert("fileNsWrite", |a, s, sc, _| io::write_file(a, s, sc));
+            m.insert("fileNsReadBytes", |a, s, sc, _| {
+                io::read_file_bytes(a, s, sc)
+            });
+            m.insert("fileNsWriteBytes", |a, s, sc, _| {
+                io::write_file_bytes(a, s, sc)
+            });
+            m.insert("fileNsOpen", |a, s, sc, _| file_handle::open(a, s, sc));
+            m.insert("fileNsAppend", |a, s, sc, _| io::append_file(a, s, sc));
+            m.insert("fileNsWriteAtomic", |a, s, sc, _| {
+                io::write_file_atomic(a, s, sc)
+            });
+            m.insert("fileNsExists", |a, s, sc, _| io::file_exists(a, s, sc));
+            m.insert("fileNsRemove", |a, s, sc, _| io::remove_file(a, s, sc));
+            m.insert("fileNsRename", |a, s, sc, _| io::rename_file(a, s, sc));
+            m.insert("fileNsCopy", |a, s, sc, _| io::copy_file(a, s, sc));
+            m.insert("fileNsMove", |a, s, sc, _| io::move_file(a, s, sc));
+            m.insert("fileNsTouch", |a, s, sc, _| io::touch(a, s, sc));
+            m.insert("fileNsSetPermissions", |a, s, sc, _| {
+                io::set_permissions(a, s, sc)
+            });
+            // B40-P07: Async file operations via file.* namespace
+            m.insert("fileNsReadAsync", |a, s, sc, _| {
+                async_io::read_file_async(a, s, sc)
+            });
+            m.insert("fileNsWriteAsync", |a, s, sc, _| {
+                async_io::write_file_async(a, s, sc)
+            });
+            m.insert("fileNsAppendAsync", |a, s, sc, _| {
+                async_io::append_file_async(a, s, sc)
+            });
+            m.insert("fileNsRenameAsync", |a, s, sc, _| {
+                async_io::rename_file_async(a, s, sc)
+            });
+            m.insert("fileNsCopyAsync", |a, s, sc, _| {
+                async_io::copy_file_async(a, s, sc)
+            });
+            m.insert("fileNsCreateDir", |a, s, sc, _| io::create_dir(a, s, sc));
+            m.insert("fileNsRemoveDir", |a, s, sc, _| io::remove_dir(a, s, sc));
+            m.insert("fileInfo", |a, s, sc, _| io::file_info(a, s, sc));
+            m.insert("fileNsInfo", |a, s, sc, _| io::file_info(a, s, sc));
+            m.insert("pathJoin", |a, s, sc, _| io::path_join(a, s, sc));
+        }
 
         // ====================================================================
         // Reflection functions
@@ -1020,10 +1092,34 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
         // ====================================================================
         // SQLite functions (B40-P05)
         // ====================================================================
-        m.insert("sqlite_open", |a, s, _, _| sqlite::open(a, s));
+        m.insert("sqlite_open", |a, s, sec, _| sqlite::open(a, s, sec));
         m.insert("sqlite_execute", |a, s, _, _| sqlite::execute(a, s));
         m.insert("sqlite_query", |a, s, _, _| sqlite::query(a, s));
         m.insert("sqlite_close", |a, s, _, _| sqlite::close(a, s));
+        m.insert("sqlite_begin", |a, s, _, _| sqlite::begin(a, s));
+        m.insert("sqlite_commit", |a, s, _, _| sqlite::commit(a, s));
+        m.insert("sqlite_rollback", |a, s, _, _| sqlite::rollback(a, s));
+        m.insert("sqlite_in_transaction", |a, s, _, _| {
+            sqlite::in_transaction(a, s)
+        });
+
+        // ====================================================================
+        // Key-value store functions (kv_transaction is a VM intrinsic)
+        // ====================================================================
+        m.insert("kv_open", |a, s, sec, _| kv::open(a, s, sec));
+        m.insert("kv_get", |a, s, _, _| kv::get(a, s));
+        m.insert("kv_set", |a, s, _, _| kv::set(a, s));
+        m.insert("kv_delete", |a, s, _, _| kv::delete(a, s));
+        m.insert("kv_has", |a, s, _, _| kv::has(a, s));
+        m.insert("kv_keys", |a, s, _, _| kv::keys(a, s));
+        m.insert("kv_close", |a, s, _, _| kv::close(a, s));
+
+        // ====================================================================
+        // CSV functions
+        // ====================================================================
+        m.insert("csvNsParse", |a, s, _, _| csv::parse(a, s));
+        m.insert("csvNsParseRecords", |a, s, _, _| csv::parse_records(a, s));
+        m.insert("csvNsStringify", |a, s, _, _| csv::stringify(a, s));
 
         // ====================================================================
         // HashMap functions
@@ -1048,102 +1144,112 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
             collections::hashmap::entries(a, s)
         });
 
-        // ====================================================================
-        // HashSet functions
-        // ====================================================================
-        m.insert("setNew", |a, s, _, _| collections::hashset::new_set(a, s));
-        m.insert("setFromArray", |a, s, _, _| {
-            collections::hashset::from_array(a, s)
-        });
-        m.insert("setAdd", |a, s, _, _| collections::hashset::add(a, s));
-        m.insert("setRemove", |a, s, _, _| collections::hashset::remove(a, s));
-        m.insert("setHas", |a, s, _, _| collections::hashset::has(a, s));
-        m.insert("setSize", |a, s, _, _| collections::hashset::size(a, s));
-        m.insert("setIsEmpty", |a, s, _, _| {
-            collections::hashset::is_empty(a, s)
-        });
-        m.insert("setClear", |a, s, _, _| collections::hashset::clear(a, s));
-        m.insert("setUnion", |a, s, _, _| collections::hashset::union(a, s));
-        m.insert("setIntersection", |a, s, _, _| {
-            collections::hashset::intersection(a, s)
-        });
-        m.insert("setDifference", |a, s, _, _| {
-            collections::hashset::difference(a, s)
-        });
-        m.insert("setSymmetricDifference", |a, s, _, _| {
-            collections::hashset::symmetric_difference(a, s)
-        });
-        m.insert("setIsSubset", |a, s, _, _| {
-            collections::hashset::is_subset(a, s)
-        });
-        m.insert("setIsSuperset", |a, s, _, _| {
-            collections::hashset::is_superset(a, s)
-        });
-        m.insert("setToArray", |a, s, _, _| {
-            collections::hashset::to_array(a, s)
-        });
+        #[cfg(feature = "collections")]
+        {
+            // ====================================================================
+            // HashSet functions
+            // ====================================================================
+            m.insert("setNew", |a, s, _, _| collections::hashset::new_set(a, s));
+            m.insert("setFromArray", |a, s, _, _| {
+                collections::hashset::from_array(a, s)
+            });
+            m.insert("setAdd", |a, s, _, _| collections::hashset::add(a, s));
+            m.insert("setRemove", |a, s, _, _| collections::hashset::remove(a, s));
+            m.insert("setHas", |a, s, _, _| collections::hashset::has(a, s));
+            m.insert("setSize", |a, s, _, _| collections::hashset::size(a, s));
+            m.insert("setIsEmpty", |a, s, _, _| {
+                collections::hashset::is_empty(a, s)
+            });
+            m.insert("setClear", |a, s, _, _| collections::hashset::clear(a, s));
+            m.insert("setUnion", |a, s, _, _| collections::hashset::union(a, s));
+            m.insert("setIntersection", |a, s, _, _| {
+                collections::hashset::intersection(a, s)
+            });
+            m.insert("setDifference", |a, s, _, _| {
+                collections::hashset::difference(a, s)
+            });
+            m.insert("setSymmetricDifference", |a, s, _, _| {
+                collections::hashset::symmetric_difference(a, s)
+            });
+            m.insert("setIsSubset", |a, s, _, _| {
+                collections::hashset::is_subset(a, s)
+            });
+            m.insert("setIsSuperset", |a, s, _, _| {
+                collections::hashset::is_superset(a, s)
+            });
+            m.insert("setToArray", |a, s, _, _| {
+                collections::hashset::to_array(a, s)
+            });
 
-        // ====================================================================
-        // Queue functions
-        // ====================================================================
-        m.insert("queueNew", |a, s, _, _| collections::queue::new_queue(a, s));
-        m.insert("queueEnqueue", |a, s, _, _| {
-            collections::queue::enqueue(a, s)
-        });
-        m.insert("queueDequeue", |a, s, _, _| {
-            collections::queue::dequeue(a, s)
-        });
-        m.insert("queuePeek", |a, s, _, _| collections::queue::peek(a, s));
-        m.insert("queueSize", |a, s, _, _| collections::queue::size(a, s));
-        m.insert("queueIsEmpty", |a, s, _, _| {
-            collections::queue::is_empty(a, s)
-        });
-        m.insert("queueClear", |a, s, _, _| collections::queue::clear(a, s));
-        m.insert("queueToArray", |a, s, _, _| {
-            collections::queue::to_array(a, s)
-        });
+            // ====================================================================
+            // Queue functions
+            // ====================================================================
+            m.insert("queueNew", |a, s, _, _| collections::queue::new_queue(a, s));
+            m.insert("queueEnqueue", |a, s, _, _| {
+                collections::queue::enqueue(a, s)
+            });
+            m.insert("queueDequeue", |a, s, _, _| {
+                collections::queue::dequeue(a, s)
+            });
+            m.insert("queuePeek", |a, s, _, _| collections::queue::peek(a, s));
+            m.insert("queueSize", |a, s, _, _| collections::queue::size(a, s));
+            m.insert("queueIsEmpty", |a, s, _, _| {
+                collections::queue::is_empty(a, s)
+            });
+            m.insert("queueClear", |a, s, _, _| collections::queue::clear(a, s));
+            m.insert("queueToArray", |a, s, _, _| {
+                collections::queue::to_array(a, s)
+            });
 
-        // ====================================================================
-        // Stack functions
-        // ====================================================================
-        m.insert("stackNew", |a, s, _, _| collections::stack::new_stack(a, s));
-        m.insert("stackPush", |a, s, _, _| collections::stack::push(a, s));
-        m.insert("stackPop", |a, s, _, _| collections::stack::pop(a, s));
-        m.insert("stackPeek", |a, s, _, _| collections::stack::peek(a, s));
-        m.insert("stackSize", |a, s, _, _| collections::stack::size(a, s));
-        m.insert("stackIsEmpty", |a, s, _, _| {
-            collections::stack::is_empty(a, s)
-        });
-        m.insert("stackClear", |a, s, _, _| collections::stack::clear(a, s));
-        m.insert("stackToArray", |a, s, _, _| {
-            collections::stack::to_array(a, s)
-        });
+            // ====================================================================
+            // Stack functions
+            // ====================================================================
+            m.insert("stackNew", |a, s, _, _| collections::stack::new_stack(a, s));
+            m.insert("stackPush", |a, s, _, _| collections::stack::push(a, s));
+            m.insert("stackPop", |a, s, _, _| collections::stack::pop(a, s));
+            m.insert("stackPeek", |a, s, _, _| collections::stack::peek(a, s));
+            m.insert("stackSize", |a, s, _, _| collections::stack::size(a, s));
+            m.insert("stackIsEmpty", |a, s, _, _| {
+                collections::stack::is_empty(a, s)
+            });
+            m.insert("stackClear", |a, s, _, _| collections::stack::clear(a, s));
+            m.insert("stackToArray", |a, s, _, _| {
+                collections::stack::to_array(a, s)
+            });
+        }
 
-        // ====================================================================
-        // Regex functions
-        // ====================================================================
-        m.insert("regexNew", |a, s, _, _| regex::regex_new(a, s));
-        m.insert("regexNewWithFlags", |a, s, _, _| {
-            regex::regex_new_with_flags(a, s)
-        });
-        m.insert("regexEscape", |a, s, _, _| regex::regex_escape(a, s));
-        m.insert("regexIsMatch", |a, s, _, _| regex::regex_is_match(a, s));
-        m.insert("regexFind", |a, s, _, _| regex::regex_find(a, s));
-        m.insert("regexFindAll", |a, s, _, _| regex::regex_find_all(a, s));
-        m.insert("regexCaptures", |a, s, _, _| regex::regex_captures(a, s));
-        m.insert("regexCapturesNamed", |a, s, _, _| {
-            regex::regex_captures_named(a, s)
-        });
-        m.insert("regexReplace", |a, s, _, _| regex::regex_replace(a, s));
-        m.insert("regexReplaceAll", |a, s, _, _| {
-            regex::regex_replace_all(a, s)
-        });
-        m.insert("regexSplit", |a, s, _, _| regex::regex_split(a, s));
-        m.insert("regexSplitN", |a, s, _, _| regex::regex_split_n(a, s));
-        m.insert("regexMatchIndices", |a, s, _, _| {
-            regex::regex_match_indices(a, s)
-        });
-        m.insert("regexTest", |a, s, _, _| regex::regex_test(a, s));
+        #[cfg(feature = "regex")]
+        {
+            // ====================================================================
+            // Regex functions
+            // ====================================================================
+            m.insert("regexNew", |a, s, sec, _| {
+                regex::regex_new(a, s, sec.regex_limits())
+            });
+            m.insert("regexNewWithFlags", |a, s, sec, _| {
+                regex::regex_new_with_flags(a, s, sec.regex_limits())
+            });
+            m.insert("regexEscape", |a, s, _, _| regex::regex_escape(a, s));
+            m.insert("regexIsMatch", |a, s, _, _| regex::regex_is_match(a, s));
+            m.insert("regexFind", |a, s, _, _| regex::regex_find(a, s));
+            m.insert("regexFindAll", |a, s, _, _| regex::regex_find_all(a, s));
+            m.insert("regexCaptures", |a, s, _, _| regex::regex_captures(a, s));
+            m.insert("regexCapturesNamed", |a, s, _, _| {
+                regex::regex_captures_named(a, s)
+            });
+            m.insert("regexReplace", |a, s, _, _| regex::regex_replace(a, s));
+            m.insert("regexReplaceAll", |a, s, _, _| {
+                regex::regex_replace_all(a, s)
+            });
+            m.insert("regexSplit", |a, s, _, _| regex::regex_split(a, s));
+            m.insert("regexSplitN", |a, s, _, _| regex::regex_split_n(a, s));
+            m.insert("regexMatchIndices", |a, s, _, _| {
+                regex::regex_match_indices(a, s)
+            });
+            m.insert("regexTest", |a, s, sec, _| {
+                regex::regex_test(a, s, sec.regex_limits())
+            });
+        }
 
         // ====================================================================
         // DateTime functions
@@ -1159,6 +1265,15 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
             datetime::date_time_parse_iso(a, s)
         });
         m.insert("dateTimeUtc", |a, s, _, _| datetime::date_time_utc(a, s));
+        m.insert("dateTimeTimestamp", |a, s, _, _| {
+            datetime::date_time_timestamp(a, s)
+        });
+        m.insert("dateTimeFormatDate", |a, s, _, _| {
+            datetime::date_time_format_date(a, s)
+        });
+        m.insert("dateTimeParseDate", |a, s, _, _| {
+            datetime::date_time_parse_date(a, s)
+        });
         m.insert("dateTimeYear", |a, s, _, _| datetime::date_time_year(a, s));
         m.insert("dateTimeMonth", |a, s, _, _| {
             datetime::date_time_month(a, s)
@@ -1270,12 +1385,19 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
                 http::http_ns_delete(a, s, sec)
             });
             m.insert("httpNsPatch", |a, s, sec, _| http::http_ns_patch(a, s, sec));
+            m.insert("httpNsRequest", |a, s, sec, _| {
+                http::http_ns_request(a, s, sec)
+            });
             // HttpResponse instance method helpers (used by method_dispatch)
             m.insert("httpStatus", |a, s, _, _| http::http_status(a, s));
             m.insert("httpBody", |a, s, _, _| http::http_body(a, s));
+            m.insert("httpHeader", |a, s, _, _| http::http_header(a, s));
             m.insert("httpHeaders", |a, s, _, _| http::http_headers(a, s));
             m.insert("httpUrl", |a, s, _, _| http::http_url(a, s));
             m.insert("httpIsSuccess", |a, s, _, _| http::http_is_success(a, s));
+            m.insert("httpResponseToMap", |a, s, _, _| {
+                http::http_response_to_map(a, s)
+            });
             m.insert("httpCheckPermission", |a, s, sec, _| {
                 http::http_check_permission(a, s, sec)
             });
@@ -1355,16 +1477,20 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
         // ====================================================================
         #[cfg(feature = "http")]
         {
-            m.insert("httpSendAsync", |a, s, _, _| {
-                async_io::http_send_async(a, s)
+            m.insert("httpSendAsync", |a, s, sc, _| {
+                async_io::http_send_async(a, s, sc)
+            });
+            m.insert("httpGetAsync", |a, s, sc, _| {
+                async_io::http_get_async(a, s, sc)
             });
-            m.insert("httpGetAsync", |a, s, _, _| async_io::http_get_async(a, s));
-            m.insert("httpPostAsync", |a, s, _, _| {
-                async_io::http_post_async(a, s)
+            m.insert("httpPostAsync", |a, s, sc, _| {
+                async_io::http_post_async(a, s, sc)
             });
-            m.insert("httpPutAsync", |a, s, _, _| async_io::http_put_async(a, s));
-            m.insert("httpDeleteAsync", |a, s, _, _| {
-                async_io::http_delete_async(a, s)
+            m.insert("httpPutAsync", |a, s, sc, _| {
+                async_io::http_put_async(a, s, sc)
+            });
+            m.insert("httpDeleteAsync", |a, s, sc, _| {
+                async_io::http_delete_async(a, s, sc)
             });
             m.insert("await", |a, s, _, _| async_io::await_future(a, s));
         }
@@ -1488,6 +1614,7 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
         // Process management
         // ====================================================================
         m.insert("exec", |a, s, sc, _| process::exec(a, s, sc));
+        m.insert("execCapture", |a, s, sc, _| process::exec_capture(a, s, sc));
         m.insert("shell", |a, s, sc, _| process::shell(a, s, sc));
         m.insert("shellOut", |a, s, sc, _| process::shell_out(a, s, sc));
         m.insert("getEnv", |a, s, sc, _| process::get_env(a, s, sc));
@@ -1499,9 +1626,26 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
         m.insert("getPlatform", |a, s, sc, _| process::get_platform(a, s, sc));
         m.insert("getArch", |a, s, sc, _| process::get_arch(a, s, sc));
         m.insert("processExit", |a, s, sc, _| process::process_exit(a, s, sc));
+        // process.gc(): the VM intercepts this to pass its own roots. Called
+        // without a VM, the collector still frees cycles nothing else holds.
+        m.insert("processNsGc", |a, s, _, _| {
+            if !a.is_empty() {
+                return Err(stdlib_arity_error("process.gc", 0, a.len(), s));
+            }
+            Ok(Value::Number(
+                crate::vm::gc::collect(std::iter::empty()) as f64
+            ))
+        });
         m.insert("getProcessArgs", |a, s, sc, _| {
             process::get_process_args(a, s, sc)
         });
+        m.insert("processParseArgs", |a, s, sc, output| {
+            args::parse_args(a, s, sc, output)
+        });
+        m.insert("processArgsHelp", |a, s, _, _| args::args_help(a, s));
+        m.insert("processLoadConfig", |a, s, sc, _| {
+            config::load_config(a, s, sc)
+        });
         m.insert("processRun", |a, s, sc, _| process::process_run(a, s, sc));
         m.insert("processOutputStdout", |a, s, sc, _| {
             process::process_output_stdout(a, s, sc)
@@ -1541,362 +1685,393 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
             process::process_output(a, s, sc)
         });
 
-        // ====================================================================
-        // Path manipulation
-        // ====================================================================
-        m.insert("pathJoinArray", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathJoinArray", 1, args.len(), span));
-            }
-            let segments = extract_array(&args[0], "pathJoinArray", span)?;
-            let result = path::path_join(&segments, span)?;
-            Ok(Value::string(result))
-        });
-        m.insert("pathParse", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathParse", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathParse", span)?;
-            path::path_parse(path_str, span)
-        });
-        m.insert("pathNormalize", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathNormalize", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathNormalize", span)?;
-            Ok(Value::string(path::path_normalize(path_str, span)?))
-        });
-        m.insert("pathAbsolute", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathAbsolute", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathAbsolute", span)?;
-            Ok(Value::string(path::path_absolute(path_str, span)?))
-        });
-        m.insert("pathRelative", |args, span, _, _| {
-            if args.len() != 2 {
-                return Err(stdlib_arity_error("pathRelative", 2, args.len(), span));
-            }
-            let from = extract_string(&args[0], "pathRelative", span)?;
-            let to = extract_string(&args[1], "pathRelative", span)?;
-            Ok(Value::string(path::path_relative(from, to, span)?))
-        });
-        m.insert("pathParent", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathParent", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathParent", span)?;
-            path::path_parent(path_str, span)
-        });
-        m.insert("pathResolve", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathResolve", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathResolve", span)?;
-            Ok(Value::string(path::path_resolve(path_str, span)?))
-        });
-        m.insert("pathBasename", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathBasename", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathBasename", span)?;
-            Ok(Value::string(path::path_basename(path_str, span)?))
-        });
-        m.insert("pathDirname", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathDirname", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathDirname", span)?;
-            Ok(Value::string(path::path_dirname(path_str, span)?))
-        });
-        m.insert("pathExtension", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathExtension", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathExtension", span)?;
-            Ok(Value::string(path::path_extension(path_str, span)?))
-        });
-        m.insert("pathIsAbsolute", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathIsAbsolute", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathIsAbsolute", span)?;
-            Ok(Value::Bool(path::path_is_absolute(path_str, span)?))
-        });
-        m.insert("pathIsRelative", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathIsRelative", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathIsRelative", span)?;
-            Ok(Value::Bool(path::path_is_relative(path_str, span)?))
-        });
-        m.insert("pathExists", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathExists", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathExists", span)?;
-            Ok(Value::Bool(path::path_exists(path_str, span)?))
-        });
-        m.insert("pathCanonical", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathCanonical", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathCanonical", span)?;
-            Ok(Value::string(path::path_canonical(path_str, span)?))
-        });
-        m.insert("pathEquals", |args, span, _, _| {
-            if args.len() != 2 {
-                return Err(stdlib_arity_error("pathEquals", 2, args.len(), span));
-            }
-            let path1 = extract_string(&args[0], "pathEquals", span)?;
-            let path2 = extract_string(&args[1], "pathEquals", span)?;
-            Ok(Value::Bool(path::path_equals(path1, path2, span)?))
-        });
-        m.insert("pathHomedir", |args, span, _, _| {
-            if !args.is_empty() {
-                return Err(stdlib_arity_error("pathHomedir", 0, args.len(), span));
-            }
-            Ok(Value::string(path::path_homedir(span)?))
-        });
-        m.insert("pathCwd", |args, span, _, _| {
-            if !args.is_empty() {
-                return Err(stdlib_arity_error("pathCwd", 0, args.len(), span));
-            }
-            Ok(Value::string(path::path_cwd(span)?))
-        });
-        m.insert("pathTempdir", |args, span, _, _| {
-            if !args.is_empty() {
-                return Err(stdlib_arity_error("pathTempdir", 0, args.len(), span));
-            }
-            Ok(Value::string(path::path_tempdir(span)?))
-        });
-        m.insert("pathSeparator", |args, span, _, _| {
-            if !args.is_empty() {
-                return Err(stdlib_arity_error("pathSeparator", 0, args.len(), span));
-            }
-            Ok(Value::string(path::path_separator(span)?))
-        });
-        m.insert("pathDelimiter", |args, span, _, _| {
-            if !args.is_empty() {
-                return Err(stdlib_arity_error("pathDelimiter", 0, args.len(), span));
-            }
-            Ok(Value::string(path::path_delimiter(span)?))
-        });
-        m.insert("pathExtSeparator", |args, span, _, _| {
-            if !args.is_empty() {
-                return Err(stdlib_arity_error("pathExtSeparator", 0, args.len(), span));
-            }
-            Ok(Value::string(path::path_ext_separator(span)?))
-        });
-        m.insert("pathDrive", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathDrive", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathDrive", span)?;
-            Ok(Value::string(path::path_drive(path_str, span)?))
-        });
-        m.insert("pathToPlatform", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathToPlatform", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathToPlatform", span)?;
-            Ok(Value::string(path::path_to_platform(path_str, span)?))
-        });
-        m.insert("pathToPosix", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathToPosix", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathToPosix", span)?;
-            Ok(Value::string(path::path_to_posix(path_str, span)?))
-        });
-        m.insert("pathToWindows", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("pathToWindows", 1, args.len(), span));
-            }
-            let path_str = extract_string(&args[0], "pathToWindows", span)?;
-            Ok(Value::string(path::path_to_windows(path_str, span)?))
-        });
+        #[cfg(feature = "fs")]
+        {
+            // ====================================================================
+            // Path manipulation
+            // ====================================================================
+            m.insert("pathJoinArray", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathJoinArray", 1, args.len(), span));
+                }
+                let segments = extract_array(&args[0], "pathJoinArray", span)?;
+                let result = path::path_join(&segments, span)?;
+                Ok(Value::string(result))
+            });
+            m.insert("pathParse", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathParse", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathParse", span)?;
+                path::path_parse(path_str, span)
+            });
+            m.insert("pathNormalize", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathNormalize", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathNormalize", span)?;
+                Ok(Value::string(path::path_normalize(path_str, span)?))
+            });
+            m.insert("pathAbsolute", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathAbsolute", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathAbsolute", span)?;
+                Ok(Value::string(path::path_absolute(path_str, span)?))
+            });
+            m.insert("pathRelative", |args, span, _, _| {
+                if args.len() != 2 {
+                    return Err(stdlib_arity_error("pathRelative", 2, args.len(), span));
+                }
+                let from = extract_string(&args[0], "pathRelative", span)?;
+                let to = extract_string(&args[1], "pathRelative", span)?;
+                Ok(Value::string(path::path_relative(from, to, span)?))
+            });
+            m.insert("pathParent", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathParent", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathParent", span)?;
+                path::path_parent(path_str, span)
+            });
+            m.insert("pathResolve", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathResolve", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathResolve", span)?;
+                Ok(Value::string(path::path_resolve(path_str, span)?))
+            });
+            m.insert("pathBasename", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathBasename", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathBasename", span)?;
+                Ok(Value::string(path::path_basename(path_str, span)?))
+            });
+            m.insert("pathDirname", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathDirname", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathDirname", span)?;
+                Ok(Value::string(path::path_dirname(path_str, span)?))
+            });
+            m.insert("pathExtension", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathExtension", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathExtension", span)?;
+                Ok(Value::string(path::path_extension(path_str, span)?))
+            });
+            m.insert("pathStem", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathStem", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathStem", span)?;
+                Ok(Value::string(path::path_stem(path_str, span)?))
+            });
+            m.insert("pathWithExtension", |args, span, _, _| {
+                if args.len() != 2 {
+                    return Err(stdlib_arity_error("pathWithExtension", 2, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathWithExtension", span)?;
+                let ext = extract_string(&args[1], "pathWithExtension", span)?;
+                Ok(Value::string(path::path_with_extension(
+                    path_str, ext, span,
+                )?))
+            });
+            m.insert("pathExpandHome", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathExpandHome", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathExpandHome", span)?;
+                Ok(Value::string(path::path_expand_home(path_str, span)?))
+            });
+            m.insert("pathIsAbsolute", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathIsAbsolute", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathIsAbsolute", span)?;
+                Ok(Value::Bool(path::path_is_absolute(path_str, span)?))
+            });
+            m.insert("pathIsRelative", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathIsRelative", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathIsRelative", span)?;
+                Ok(Value::Bool(path::path_is_relative(path_str, span)?))
+            });
+            m.insert("pathExists", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathExists", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathExists", span)?;
+                Ok(Value::Bool(path::path_exists(path_str, span)?))
+            });
+            m.insert("pathCanonical", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathCanonical", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathCanonical", span)?;
+                Ok(Value::string(path::path_canonical(path_str, span)?))
+            });
+            m.insert("pathEquals", |args, span, _, _| {
+                if args.len() != 2 {
+                    return Err(stdlib_arity_error("pathEquals", 2, args.len(), span));
+                }
+                let path1 = extract_string(&args[0], "pathEquals", span)?;
+                let path2 = extract_string(&args[1], "pathEquals", span)?;
+                Ok(Value::Bool(path::path_equals(path1, path2, span)?))
+            });
+            m.insert("pathHomedir", |args, span, _, _| {
+                if !args.is_empty() {
+                    return Err(stdlib_arity_error("pathHomedir", 0, args.len(), span));
+                }
+                Ok(Value::string(path::path_homedir(span)?))
+            });
+            m.insert("pathCwd", |args, span, _, _| {
+                if !args.is_empty() {
+                    return Err(stdlib_arity_error("pathCwd", 0, args.len(), span));
+                }
+                Ok(Value::string(path::path_cwd(span)?))
+            });
+            m.insert("pathTempdir", |args, span, _, _| {
+                if !args.is_empty() {
+                    return Err(stdlib_arity_error("pathTempdir", 0, args.len(), span));
+                }
+                Ok(Value::string(path::path_tempdir(span)?))
+            });
+            m.insert("pathSeparator", |args, span, _, _| {
+                if !args.is_empty() {
+                    return Err(stdlib_arity_error("pathSeparator", 0, args.len(), span));
+                }
+                Ok(Value::string(path::path_separator(span)?))
+            });
+            m.insert("pathDelimiter", |args, span, _, _| {
+                if !args.is_empty() {
+                    return Err(stdlib_arity_error("pathDelimiter", 0, args.len(), span));
+                }
+                Ok(Value::string(path::path_delimiter(span)?))
+            });
+            m.insert("pathExtSeparator", |args, span, _, _| {
+                if !args.is_empty() {
+                    return Err(stdlib_arity_error("pathExtSeparator", 0, args.len(), span));
+                }
+                Ok(Value::string(path::path_ext_separator(span)?))
+            });
+            m.insert("pathDrive", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathDrive", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathDrive", span)?;
+                Ok(Value::string(path::path_drive(path_str, span)?))
+            });
+            m.insert("pathToPlatform", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathToPlatform", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathToPlatform", span)?;
+                Ok(Value::string(path::path_to_platform(path_str, span)?))
+            });
+            m.insert("pathToPosix", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathToPosix", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathToPosix", span)?;
+                Ok(Value::string(path::path_to_posix(path_str, span)?))
+            });
+            m.insert("pathToWindows", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("pathToWindows", 1, args.len(), span));
+                }
+                let path_str = extract_string(&args[0], "pathToWindows", span)?;
+                Ok(Value::string(path::path_to_windows(path_str, span)?))
+            });
 
-        // ====================================================================
-        // File system operations — B24: all registered under fileNs* keys.
-        // Bare fs* globals removed; file.* dispatch routes here.
-        // ====================================================================
-        m.insert("fileNsMkdir", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsMkdir", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsMkdir", span)?;
-            fs::mkdir(path, span)
-        });
-        m.insert("fileNsMkdirp", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsMkdirp", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsMkdirp", span)?;
-            fs::mkdirp(path, span)
-        });
-        m.insert("fileNsRmdir", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsRmdir", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsRmdir", span)?;
-            fs::rmdir(path, span)
-        });
-        m.insert("fileNsRmdirRecursive", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error(
-                    "fileNsRmdirRecursive",
-                    1,
-                    args.len(),
-                    span,
-                ));
-            }
-            let path = extract_string(&args[0], "fileNsRmdirRecursive", span)?;
-            fs::rmdir_recursive(path, span)
-        });
-        m.insert("fileNsReadDir", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsReadDir", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsReadDir", span)?;
-            fs::readdir(path, span)
-        });
-        m.insert("fileNsWalk", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsWalk", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsWalk", span)?;
-            fs::walk(path, span)
-        });
-        m.insert("fileNsFilterEntries", |args, span, _, _| {
-            if args.len() != 2 {
-                return Err(stdlib_arity_error(
-                    "fileNsFilterEntries",
-                    2,
-                    args.len(),
-                    span,
-                ));
-            }
-            let entries = extract_array(&args[0], "fileNsFilterEntries", span)?;
-            let pattern = extract_string(&args[1], "fileNsFilterEntries", span)?;
-            fs::filter_entries(&entries, pattern, span)
-        });
-        m.insert("fileNsSortEntries", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsSortEntries", 1, args.len(), span));
-            }
-            let entries = extract_array(&args[0], "fileNsSortEntries", span)?;
-            fs::sort_entries(&entries, span)
-        });
+            // ====================================================================
+            // File system operations — B24: all registered under fileNs* keys.
+            // Bare fs* globals removed; file.* dispatch routes here.
+            // ====================================================================
+            m.insert("fileNsMkdir", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsMkdir", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsMkdir", span)?;
+                fs::mkdir(path, span)
+            });
+            m.insert("fileNsMkdirp", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsMkdirp", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsMkdirp", span)?;
+                fs::mkdirp(path, span)
+            });
+            m.insert("fileNsRmdir", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsRmdir", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsRmdir", span)?;
+                fs::rmdir(path, span)
+            });
+            m.insert("fileNsRmdirRecursive", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error(
+                        "fileNsRmdirRecursive",
+                        1,
+                        args.len(),
+                        span,
+                    ));
+                }
+                let path = extract_string(&args[0], "fileNsRmdirRecursive", span)?;
+                fs::rmdir_recursive(path, span)
+            });
+            m.insert("fileNsReadDir", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsReadDir", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsReadDir", span)?;
+                fs::readdir(path, span)
+            });
+            m.insert("fileNsWalk", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsWalk", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsWalk", span)?;
+                fs::walk(path, span)
+            });
+            m.insert("fileNsFilterEntries", |args, span, _, _| {
+                if args.len() != 2 {
+                    return Err(stdlib_arity_error(
+                        "fileNsFilterEntries",
+                        2,
+                        args.len(),
+                        span,
+                    ));
+                }
+                let entries = extract_array(&args[0], "fileNsFilterEntries", span)?;
+                let pattern = extract_string(&args[1], "fileNsFilterEntries", span)?;
+                fs::filter_entries(&entries, pattern, span)
+            });
+            m.insert("fileNsSortEntries", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsSortEntries", 1, args.len(), span));
+                }
+                let entries = extract_array(&args[0], "fileNsSortEntries", span)?;
+                fs::sort_entries(&entries, span)
+            });
 
-        // File system operations - metadata
-        m.insert("fileNsSize", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsSize", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsSize", span)?;
-            fs::size(path, span)
-        });
-        m.insert("fileNsMtime", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsMtime", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsMtime", span)?;
-            fs::mtime(path, span)
-        });
-        m.insert("fileNsCtime", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsCtime", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsCtime", span)?;
-            fs::ctime(path, span)
-        });
-        m.insert("fileNsAtime", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsAtime", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsAtime", span)?;
-            fs::atime(path, span)
-        });
-        m.insert("fileNsPermissions", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsPermissions", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsPermissions", span)?;
-            fs::permissions(path, span)
-        });
-        m.insert("fileNsIsDir", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsIsDir", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsIsDir", span)?;
-            fs::is_dir(path, span)
-        });
-        m.insert("fileNsIsFile", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsIsFile", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsIsFile", span)?;
-            fs::is_file(path, span)
-        });
-        m.insert("fileNsIsSymlink", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsIsSymlink", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsIsSymlink", span)?;
-            fs::is_symlink(path, span)
-        });
-        m.insert("fileNsInode", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsInode", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsInode", span)?;
-            fs::inode(path, span)
-        });
+            // File system operations - metadata
+            m.insert("fileNsSize", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsSize", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsSize", span)?;
+                fs::size(path, span)
+            });
+            m.insert("fileNsMtime", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsMtime", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsMtime", span)?;
+                fs::mtime(path, span)
+            });
+            m.insert("fileNsCtime", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsCtime", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsCtime", span)?;
+                fs::ctime(path, span)
+            });
+            m.insert("fileNsAtime", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsAtime", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsAtime", span)?;
+                fs::atime(path, span)
+            });
+            m.insert("fileNsPermissions", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsPermissions", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsPermissions", span)?;
+                fs::permissions(path, span)
+            });
+            m.insert("fileNsIsDir", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsIsDir", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsIsDir", span)?;
+                fs::is_dir(path, span)
+            });
+            m.insert("fileNsIsFile", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsIsFile", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsIsFile", span)?;
+                fs::is_file(path, span)
+            });
+            m.insert("fileNsIsSymlink", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsIsSymlink", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsIsSymlink", span)?;
+                fs::is_symlink(path, span)
+            });
+            m.insert("fileNsInode", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsInode", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsInode", span)?;
+                fs::inode(path, span)
+            });
 
-        // File system operations - temporary files
-        m.insert("fileNsTempFile", |args, span, _, _| {
-            if !args.is_empty() {
-                return Err(stdlib_arity_error("fileNsTempFile", 0, args.len(), span));
-            }
-            fs::tmpfile(span)
-        });
-        m.insert("fileNsTempDir", |args, span, _, _| {
-            if !args.is_empty() {
-                return Err(stdlib_arity_error("fileNsTempDir", 0, args.len(), span));
-            }
-            fs::tmpdir(span)
-        });
+            // File system operations - temporary files
+            m.insert("fileNsTempFile", |args, span, security, _| {
+                match args.len() {
+                    0 => fs::tmpfile(security, span),
+                    1 => {
+                        let prefix = extract_string(&args[0], "fileNsTempFile", span)?;
+                        fs::tmpfile_named(prefix, security, span)
+                    }
+                    n => Err(stdlib_arity_error("fileNsTempFile", 1, n, span)),
+                }
+            });
+            m.insert("fileNsTempDir", |args, span, security, _| {
+                if !args.is_empty() {
+                    return Err(stdlib_arity_error("fileNsTempDir", 0, args.len(), span));
+                }
+                fs::tmpdir(security, span)
+            });
 
-        // File system operations - symlinks
-        m.insert("fileNsSymlink", |args, span, _, _| {
-            if args.len() != 2 {
-                return Err(stdlib_arity_error("fileNsSymlink", 2, args.len(), span));
-            }
-            let target = extract_string(&args[0], "fileNsSymlink", span)?;
-            let link = extract_string(&args[1], "fileNsSymlink", span)?;
-            fs::symlink(target, link, span)
-        });
-        m.insert("fileNsReadLink", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsReadLink", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsReadLink", span)?;
-            fs::readlink(path, span)
-        });
-        m.insert("fileNsWatch", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsWatch", 1, args.len(), span));
-            }
-            let path = extract_string(&args[0], "fileNsWatch", span)?;
-            fs::watch(path, span)
-        });
-        m.insert("fileNsWatchNext", |args, span, _, _| {
-            if args.len() != 1 {
-                return Err(stdlib_arity_error("fileNsWatchNext", 1, args.len(), span));
-            }
-            fs::watch_next(&args[0], span)
-        });
+            // File system operations - symlinks
+            m.insert("fileNsSymlink", |args, span, _, _| {
+                if args.len() != 2 {
+                    return Err(stdlib_arity_error("fileNsSymlink", 2, args.len(), span));
+                }
+                let target = extract_string(&args[0], "fileNsSymlink", span)?;
+                let link = extract_string(&args[1], "fileNsSymlink", span)?;
+                fs::symlink(target, link, span)
+            });
+            m.insert("fileNsReadLink", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsReadLink", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsReadLink", span)?;
+                fs::readlink(path, span)
+            });
+            m.insert("fileNsWatch", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsWatch", 1, args.len(), span));
+                }
+                let path = extract_string(&args[0], "fileNsWatch", span)?;
+                fs::watch(path, span)
+            });
+            m.insert("fileNsWatchNext", |args, span, _, _| {
+                if args.len() != 1 {
+                    return Err(stdlib_arity_error("fileNsWatchNext", 1, args.len(), span));
+                }
+                fs::watch_next(&args[0], span)
+            });
+        }
 
         // ====================================================================
         // Compression - gzip
@@ -2116,6 +2291,42 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
         m.insert("encodingNsHexDecode", |a, s, _, _| {
             encoding::hex_decode(a, s)
         });
+        m.insert("encodingNsFromHex", |a, s, _, _| encoding::from_hex(a, s));
+        m.insert("encodingNsFromBase64", |a, s, _, _| {
+            encoding::from_base64(a, s)
+        });
+        m.insert("encodingNsFromArray", |a, s, _, _| {
+            encoding::from_array(a, s)
+        });
+
+        // ====================================================================
+        // Bytes instance methods
+        // ====================================================================
+        m.insert("bytesLen", |a, s, _, _| bytes::bytes_len(a, s));
+        m.insert("bytesSlice", |a, s, _, _| bytes::bytes_slice(a, s));
+        m.insert("bytesToHex", |a, s, _, _| bytes::bytes_to_hex(a, s));
+        m.insert("bytesToBase64", |a, s, _, _| bytes::bytes_to_base64(a, s));
+        m.insert("bytesToArray", |a, s, _, _| bytes::bytes_to_array(a, s));
+        m.insert("bytesToUtf8", |a, s, _, _| bytes::bytes_to_utf8(a, s));
+        m.insert("stringToBytes", |a, s, _, _| bytes::string_to_bytes(a, s));
+
+        #[cfg(feature = "fs")]
+        {
+            // ====================================================================
+            // FileHandle instance methods
+            // ====================================================================
+            m.insert("fileHandleReadLine", |a, s, _, _| {
+                file_handle::read_line(a, s)
+            });
+            m.insert("fileHandleReadChunk", |a, s, _, _| {
+                file_handle::read_chunk(a, s)
+            });
+            m.insert("fileHandleWriteChunk", |a, s, _, _| {
+                file_handle::write_chunk(a, s)
+            });
+            m.insert("fileHandleClose", |a, s, _, _| file_handle::close(a, s));
+        }
+
         #[cfg(feature = "http")]
         m.insert("encodingNsUrlEncode", |a, s, _, _| {
             encoding::url_encode(a, s)
@@ -2150,7 +2361,8 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
             });
             // UDP
             m.insert("udpBind", |a, s, sec, _| net::udp_bind(a, s, sec));
-            m.insert("udpSend", |a, s, _, _| net::udp_send(a, s));
+            m.insert("udpConnect", |a, s, sec, _| net::udp_connect(a, s, sec));
+            m.insert("udpSend", |a, s, sec, _| net::udp_send(a, s, sec));
             m.insert("udpReceive", |a, s, _, _| net::udp_receive(a, s));
             m.insert("udpSetTimeout", |a, s, _, _| net::udp_set_timeout(a, s));
             m.insert("udpClose", |a, s, _, _| net::udp_close(a, s));
@@ -2160,6 +2372,10 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
             m.insert("tlsWrite", |a, s, _, _| net::tls_write(a, s));
             m.insert("tlsRead", |a, s, _, _| net::tls_read(a, s));
             m.insert("tlsClose", |a, s, _, _| net::tls_close(a, s));
+            // Any socket
+            m.insert("netSend", |a, s, _, _| net::send(a, s));
+            m.insert("netRecv", |a, s, _, _| net::recv(a, s));
+            m.insert("netClose", |a, s, _, _| net::close(a, s));
             // WebSocket
             m.insert("wsConnect", |a, s, sec, _| websocket::ws_connect(a, s, sec));
             m.insert("wsSend", |a, s, _, _| websocket::ws_send(a, s));
@@ -2248,6 +2464,9 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
             ("jsonGetArray", "json_get_array"),
             ("jsonGetObject", "json_get_object"),
             ("jsonIsNull", "json_is_null"),
+            ("jsonGetPath", "json_get_path"),
+            ("jsonSetPath", "json_set_path"),
+            ("jsonHasPath", "json_has_path"),
             // Type functions
             ("isString", "is_string"),
             ("isNumber", "is_number"),
@@ -2281,11 +2500,18 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
             // B24: bare globals removed — fileNs* keys only
             ("fileNsRead", "file_ns_read"),
             ("fileNsWrite", "file_ns_write"),
+            ("fileNsReadBytes", "file_ns_read_bytes"),
+            ("fileNsWriteBytes", "file_ns_write_bytes"),
+            ("fileNsOpen", "file_ns_open"),
             ("fileNsAppend", "file_ns_append"),
+            ("fileNsWriteAtomic", "file_ns_write_atomic"),
             ("fileNsExists", "file_ns_exists"),
             ("fileNsRemove", "file_ns_remove"),
             ("fileNsRename", "file_ns_rename"),
             ("fileNsCopy", "file_ns_copy"),
+            ("fileNsMove", "file_ns_move"),
+            ("fileNsTouch", "file_ns_touch"),
+            ("fileNsSetPermissions", "file_ns_set_permissions"),
             ("fileNsCreateDir", "file_ns_create_dir"),
             ("fileNsRemoveDir", "file_ns_remove_dir"),
             ("fileInfo", "file_info"),
@@ -2389,6 +2615,9 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
             ("dateTimeGetTimezone", "date_time_get_timezone"),
             ("dateTimeGetOffset", "date_time_get_offset"),
             ("dateTimeInTimezone", "date_time_in_timezone"),
+            ("dateTimeTimestamp", "date_time_timestamp"),
+            ("dateTimeFormatDate", "date_time_format_date"),
+            ("dateTimeParseDate", "date_time_parse_date"),
             // Duration
             ("durationFromSeconds", "duration_from_seconds"),
             ("durationFromMinutes", "duration_from_minutes"),
@@ -2491,6 +2720,9 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
             ("pathBasename", "path_basename"),
             ("pathDirname", "path_dirname"),
             ("pathExtension", "path_extension"),
+            ("pathStem", "path_stem"),
+            ("pathWithExtension", "path_with_extension"),
+            ("pathExpandHome", "path_expand_home"),
             ("pathIsAbsolute", "path_is_absolute"),
             ("pathIsRelative", "path_is_relative"),
             ("pathExists", "path_exists"),
@@ -2576,6 +2808,22 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
             ("encodingNsHexDecode", "hex_decode"),
             ("encodingNsUrlEncode", "url_encode"),
             ("encodingNsUrlDecode", "url_decode"),
+            ("encodingNsFromHex", "from_hex"),
+            ("encodingNsFromBase64", "from_base64"),
+            ("encodingNsFromArray", "from_array"),
+            // Bytes instance methods
+            ("bytesLen", "bytes_len"),
+            ("bytesSlice", "bytes_slice"),
+            ("bytesToHex", "bytes_to_hex"),
+            ("bytesToBase64", "bytes_to_base64"),
+            ("bytesToArray", "bytes_to_array"),
+            ("bytesToUtf8", "bytes_to_utf8"),
+            ("stringToBytes", "string_to_bytes"),
+            // FileHandle instance methods
+            ("fileHandleReadLine", "file_handle_read_line"),
+            ("fileHandleReadChunk", "file_handle_read_chunk"),
+            ("fileHandleWriteChunk", "file_handle_write_chunk"),
+            ("fileHandleClose", "file_handle_close"),
             // Networking
             ("tcpConnect", "tcp_connect"),
             ("tcpWrite", "tcp_write"),
@@ -2591,6 +2839,7 @@ fn builtin_registry() -> &'static HashMap<&'static str, BuiltinFn> {
             ("tcpListenerAddr", "tcp_listener_addr"),
             ("tcpListenerClose", "tcp_listener_close"),
             ("udpBind", "udp_bind"),
+            ("udpConnect", "udp_connect"),
             ("udpSend", "udp_send"),
             ("udpReceive", "udp_receive"),
             ("udpSetTimeout", "udp_set_timeout"),
@@ -2644,6 +2893,11 @@ pub fn is_builtin(name: &str) -> bool {
     builtin_registry().contains_key(name)
 }
 
+/// Dispatch function for a builtin, for callers that cache it (the VM's call-site cache)
+pub(crate) fn lookup_builtin(name: &str) -> Option<BuiltinFn> {
+    builtin_registry().get(name).copied()
+}
+
 /// Check if a function name is an array intrinsic (handled in interpreter/VM)
 pub fn is_array_intrinsic(name: &str) -> bool {
     matches!(
@@ -2680,6 +2934,8 @@ pub fn is_array_intrinsic(name: &str) -> bool {
             // Regex intrinsics (callback-based)
             | "regexReplaceWith" | "regex_replace_with"
             | "regexReplaceAllWith" | "regex_replace_all_with"
+            // Key-value store intrinsics (callback-based)
+            | "kv_transaction"
             // Test intrinsics (callback-based) — B34: bare globals removed, testNs keys remain
             | "testNsThrows" | "testNsNoThrow"
     )
@@ -2757,7 +3013,7 @@ pub fn print(
     Ok(())
 }
 
-/// Get the length of a string or array
+/// Get the length of a string, array or byte buffer
 ///
 /// For strings, returns Unicode scalar count (not byte length).
 /// For arrays, returns element count.
@@ -2765,7 +3021,13 @@ pub fn len(value: &Value, span: crate::span::Span) -> Result<f64, RuntimeError>
     match value {
         Value::String(s) => Ok(s.chars().count() as f64), // Unicode scalar count
         Value::Array(arr) => Ok(arr.len() as f64),
-        _ => Err(stdlib_arg_error("len", "string or array", value, span)),
+        Value::Bytes(data) => Ok(data.len() as f64),
+        _ => Err(stdlib_arg_error(
+            "len",
+            "string, array or bytes",
+            value,
+            span,
+        )),
     }
 }
 
@@ -1,7 +1,11 @@
 //! Networking primitives for Atlas stdlib.
 //!
-//! Provides TCP (client + server), UDP, and TLS support.
-//! All operations respect the SecurityContext network permission model.
+//! Provides TCP (client + server), UDP, and TLS support, plus `send`,
+//! `recv` and `close` that work on any connected socket handle.
+//! Opening a socket (`tcpConnect`, `tcpListen`, `tcpAccept`, `udpBind`,
+//! `udpConnect`, `tlsConnect`) returns `Result<handle, string>`; an address
+//! whose host the SecurityContext does not grant raises
+//! `NetworkPermissionDenied` instead, as does a UDP datagram to such a host.
 
 use crate::security::SecurityContext;
 use crate::span::Span;
@@ -14,24 +18,48 @@ use std::time::Duration;
 
 // ── Security helper ──────────────────────────────────────────────────
 
+/// Check the host of `address` (`host:port`, `[v6]:port` or a bare host)
+/// against the network grants. The port is not part of the grant, matching
+/// how `http` checks URLs.
 fn check_net_permission(
     security: &SecurityContext,
-    host: &str,
+    address: &str,
     span: Span,
 ) -> Result<(), RuntimeError> {
+    let host = address_host(address);
     security
         .check_network(host)
-        .map_err(|e| RuntimeError::TypeError {
-            msg: format!("network permission denied: {}", e),
+        .map_err(|_| RuntimeError::NetworkPermissionDenied {
+            host: host.to_string(),
             span,
         })
 }
 
+fn address_host(address: &str) -> &str {
+    if let Some(rest) = address.strip_prefix('[') {
+        return rest.split(']').next().unwrap_or(rest);
+    }
+    match address.rsplit_once(':') {
+        // More than one `:` is a bare IPv6 address, which has no port
+        Some((host, _)) if !host.contains(':') => host,
+        _ => address,
+    }
+}
+
+/// Wrap the outcome of opening a socket as an Atlas `Result`
+fn open_result(opened: Result<Value, String>, func_name: &str) -> Value {
+    match opened {
+        Ok(handle) => Value::Result(Ok(Box::new(handle))),
+        Err(e) => Value::Result(Err(Box::new(Value::string(format!(
+            "{}(): {}",
+            func_name, e
+        ))))),
+    }
+}
+
 // ── TCP Client ───────────────────────────────────────────────────────
 
-/// tcpConnect(address: string, timeout_ms?: number) -> TcpStream handle (as opaque Value)
-///
-/// Returns a HashMap with a `_handle` key containing the stream.
+/// tcpConnect(address: string, timeout_ms?: number) -> Result<stream handle, string>
 pub fn tcp_connect(
     args: &[Value],
     span: Span,
@@ -49,19 +77,16 @@ pub fn tcp_connect(
         None
     };
 
-    let addr: SocketAddr = resolve_addr(addr_str, "tcpConnect", span)?;
-
-    let stream = if let Some(ms) = timeout_ms {
-        TcpStream::connect_timeout(&addr, Duration::from_millis(ms))
-    } else {
-        TcpStream::connect(addr)
-    }
-    .map_err(|e| RuntimeError::IoError {
-        message: format!("tcpConnect(): {}", e),
-        span,
-    })?;
-
-    Ok(wrap_tcp_stream(stream))
+    let opened = resolve_addr(addr_str).and_then(|addr| {
+        if let Some(ms) = timeout_ms {
+            TcpStream::connect_timeout(&addr, Duration::from_millis(ms))
+        } else {
+            TcpStream::connect(addr)
+        }
+        .map(wrap_tcp_stream)
+        .map_err(|e| e.to_string())
+    });
+    Ok(open_result(opened, "tcpConnect"))
 }
 
 /// tcpWrite(stream: handle, data: string) -> number (bytes written)
@@ -73,17 +98,7 @@ pub fn tcp_write(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
     let data = extract_str(&args[1], "tcpWrite", span)?;
 
     let mut stream = stream_mutex.lock().unwrap();
-    let n = stream
-        .write(data.as_bytes())
-        .map_err(|e| RuntimeError::IoError {
-            message: format!("tcpWrite(): {}", e),
-            span,
-        })?;
-    stream.flush().map_err(|e| RuntimeError::IoError {
-        message: format!("tcpWrite(): flush failed: {}", e),
-        span,
-    })?;
-    Ok(Value::Number(n as f64))
+    write_flush(&mut *stream, data, "tcpWrite", span)
 }
 
 /// tcpRead(stream: handle, max_bytes: number) -> string
@@ -94,20 +109,8 @@ pub fn tcp_read(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
     let stream_mutex = extract_tcp_stream(&args[0], "tcpRead", span)?;
     let max_bytes = extract_number(&args[1], "tcpRead", span)? as usize;
 
-    let mut buf = vec![0u8; max_bytes.min(1024 * 1024)]; // Cap at 1MB per read
     let mut stream = stream_mutex.lock().unwrap();
-    let n = stream.read(&mut buf).map_err(|e| RuntimeError::IoError {
-        message: format!("tcpRead(): {}", e),
-        span,
-    })?;
-    buf.truncate(n);
-
-    String::from_utf8(buf)
-        .map(Value::string)
-        .map_err(|e| RuntimeError::InvalidStdlibArgument {
-            msg: format!("tcpRead(): received non-UTF-8 data: {}", e),
-            span,
-        })
+    read_string(&mut *stream, max_bytes, "tcpRead", span)
 }
 
 /// tcpReadBytes(stream: handle, max_bytes: number) -> array of numbers (raw bytes)
@@ -139,15 +142,8 @@ pub fn tcp_close(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(super::stdlib_arity_error("tcpClose", 1, args.len(), span));
     }
-    let stream_mutex = extract_tcp_stream(&args[0], "tcpClose", span)?;
-    let stream = stream_mutex.lock().unwrap();
-    stream
-        .shutdown(std::net::Shutdown::Both)
-        .map_err(|e| RuntimeError::IoError {
-            message: format!("tcpClose(): {}", e),
-            span,
-        })?;
-    Ok(Value::Null)
+    let id = extract_handle_id(&args[0], TCP_STREAM_TAG, "tcpClose", span)?;
+    close_handle(TCP_STREAM_TAG, id, "tcpClose", span)
 }
 
 /// tcpSetTimeout(stream: handle, read_ms: number, write_ms: number) -> null
@@ -243,7 +239,7 @@ pub fn tcp_remote_addr(args: &[Value], span: Span) -> Result<Value, RuntimeError
 
 // ── TCP Server ───────────────────────────────────────────────────────
 
-/// tcpListen(address: string) -> listener handle
+/// tcpListen(address: string) -> Result<listener handle, string>
 pub fn tcp_listen(
     args: &[Value],
     span: Span,
@@ -255,25 +251,24 @@ pub fn tcp_listen(
     let addr_str = extract_str(&args[0], "tcpListen", span)?;
     check_net_permission(security, addr_str, span)?;
 
-    let listener = TcpListener::bind(addr_str).map_err(|e| RuntimeError::IoError {
-        message: format!("tcpListen(): {}", e),
-        span,
-    })?;
-    Ok(wrap_tcp_listener(listener))
+    let opened = TcpListener::bind(addr_str)
+        .map(wrap_tcp_listener)
+        .map_err(|e| e.to_string());
+    Ok(open_result(opened, "tcpListen"))
 }
 
-/// tcpAccept(listener: handle) -> stream handle
+/// tcpAccept(listener: handle) -> Result<stream handle, string>
 pub fn tcp_accept(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(super::stdlib_arity_error("tcpAccept", 1, args.len(), span));
     }
     let listener_mutex = extract_tcp_listener(&args[0], "tcpAccept", span)?;
     let listener = listener_mutex.lock().unwrap();
-    let (stream, _addr) = listener.accept().map_err(|e| RuntimeError::IoError {
-        message: format!("tcpAccept(): {}", e),
-        span,
-    })?;
-    Ok(wrap_tcp_stream(stream))
+    let opened = listener
+        .accept()
+        .map(|(stream, _addr)| wrap_tcp_stream(stream))
+        .map_err(|e| e.to_string());
+    Ok(open_result(opened, "tcpAccept"))
 }
 
 /// tcpListenerAddr(listener: handle) -> string
@@ -305,14 +300,13 @@ pub fn tcp_listener_close(args: &[Value], span: Span) -> Result<Value, RuntimeEr
             span,
         ));
     }
-    // Drop the listener by extracting and dropping the inner value
-    let _ = extract_tcp_listener(&args[0], "tcpListenerClose", span)?;
-    Ok(Value::Null)
+    let id = extract_handle_id(&args[0], TCP_LISTENER_TAG, "tcpListenerClose", span)?;
+    close_handle(TCP_LISTENER_TAG, id, "tcpListenerClose", span)
 }
 
 // ── UDP ──────────────────────────────────────────────────────────────
 
-/// udpBind(address: string) -> socket handle
+/// udpBind(address: string) -> Result<socket handle, string>
 pub fn udp_bind(
     args: &[Value],
     span: Span,
@@ -324,21 +318,54 @@ pub fn udp_bind(
     let addr_str = extract_str(&args[0], "udpBind", span)?;
     check_net_permission(security, addr_str, span)?;
 
-    let socket = UdpSocket::bind(addr_str).map_err(|e| RuntimeError::IoError {
-        message: format!("udpBind(): {}", e),
-        span,
-    })?;
-    Ok(wrap_udp_socket(socket))
+    let opened = UdpSocket::bind(addr_str)
+        .map(wrap_udp_socket)
+        .map_err(|e| e.to_string());
+    Ok(open_result(opened, "udpBind"))
+}
+
+/// udpConnect(address: string) -> Result<socket handle, string>
+///
+/// Binds an ephemeral local port and fixes the peer, so the socket works
+/// with `send`/`recv` like a TCP stream.
+pub fn udp_connect(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(super::stdlib_arity_error("udpConnect", 1, args.len(), span));
+    }
+    let addr_str = extract_str(&args[0], "udpConnect", span)?;
+    check_net_permission(security, addr_str, span)?;
+
+    let opened = resolve_addr(addr_str).and_then(|addr| {
+        let local = if addr.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        UdpSocket::bind(local)
+            .and_then(|socket| socket.connect(addr).map(|()| socket))
+            .map(wrap_udp_socket)
+            .map_err(|e| e.to_string())
+    });
+    Ok(open_result(opened, "udpConnect"))
 }
 
 /// udpSend(socket: handle, data: string, target: string) -> number (bytes sent)
-pub fn udp_send(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+pub fn udp_send(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
     if args.len() != 3 {
         return Err(super::stdlib_arity_error("udpSend", 3, args.len(), span));
     }
     let socket_mutex = extract_udp_socket(&args[0], "udpSend", span)?;
     let data = extract_str(&args[1], "udpSend", span)?;
     let target = extract_str(&args[2], "udpSend", span)?;
+    check_net_permission(security, target, span)?;
 
     let socket = socket_mutex.lock().unwrap();
     let n = socket
@@ -404,8 +431,8 @@ pub fn udp_close(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(super::stdlib_arity_error("udpClose", 1, args.len(), span));
     }
-    let _ = extract_udp_socket(&args[0], "udpClose", span)?;
-    Ok(Value::Null)
+    let id = extract_handle_id(&args[0], UDP_SOCKET_TAG, "udpClose", span)?;
+    close_handle(UDP_SOCKET_TAG, id, "udpClose", span)
 }
 
 /// udpLocalAddr(socket: handle) -> string
@@ -429,7 +456,7 @@ pub fn udp_local_addr(args: &[Value], span: Span) -> Result<Value, RuntimeError>
 
 // ── TLS Client ───────────────────────────────────────────────────────
 
-/// tlsConnect(host: string, port: number) -> TLS stream handle
+/// tlsConnect(host: string, port: number) -> Result<TLS stream handle, string>
 pub fn tls_connect(
     args: &[Value],
     span: Span,
@@ -442,11 +469,22 @@ pub fn tls_connect(
     let port = extract_number(&args[1], "tlsConnect", span)? as u16;
     check_net_permission(security, host, span)?;
 
-    let addr_str = format!("{}:{}", host, port);
-    let tcp_stream = TcpStream::connect(&addr_str).map_err(|e| RuntimeError::IoError {
-        message: format!("tlsConnect(): TCP connection failed: {}", e),
-        span,
+    let server_name = rustls::pki_types::ServerName::try_from(host.to_string()).map_err(|e| {
+        RuntimeError::InvalidStdlibArgument {
+            msg: format!("tlsConnect(): invalid hostname: {}", e),
+            span,
+        }
     })?;
+    Ok(open_result(tls_open(host, port, server_name), "tlsConnect"))
+}
+
+fn tls_open(
+    host: &str,
+    port: u16,
+    server_name: rustls::pki_types::ServerName<'static>,
+) -> Result<Value, String> {
+    let tcp_stream =
+        TcpStream::connect((host, port)).map_err(|e| format!("TCP connection failed: {}", e))?;
 
     let root_store =
         rustls::RootCertStore::from_iter(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
@@ -455,26 +493,14 @@ pub fn tls_connect(
         .with_root_certificates(root_store)
         .with_no_client_auth();
 
-    let server_name = rustls::pki_types::ServerName::try_from(host.to_string()).map_err(|e| {
-        RuntimeError::InvalidStdlibArgument {
-            msg: format!("tlsConnect(): invalid hostname: {}", e),
-            span,
-        }
-    })?;
-
-    let conn = rustls::ClientConnection::new(Arc::new(config), server_name).map_err(|e| {
-        RuntimeError::IoError {
-            message: format!("tlsConnect(): TLS handshake failed: {}", e),
-            span,
-        }
-    })?;
+    let conn = rustls::ClientConnection::new(Arc::new(config), server_name)
+        .map_err(|e| format!("TLS handshake failed: {}", e))?;
 
     let mut tls_stream = rustls::StreamOwned::new(conn, tcp_stream);
     // Force handshake completion
-    tls_stream.flush().map_err(|e| RuntimeError::IoError {
-        message: format!("tlsConnect(): TLS handshake failed: {}", e),
-        span,
-    })?;
+    tls_stream
+        .flush()
+        .map_err(|e| format!("TLS handshake failed: {}", e))?;
 
     Ok(wrap_tls_stream(tls_stream))
 }
@@ -488,31 +514,214 @@ pub fn tls_write(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
     let data = extract_str(&args[1], "tlsWrite", span)?;
 
     let mut stream = stream_mutex.lock().unwrap();
+    write_flush(&mut *stream, data, "tlsWrite", span)
+}
+
+/// tlsRead(stream: handle, max_bytes: number) -> string
+pub fn tls_read(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 2 {
+        return Err(super::stdlib_arity_error("tlsRead", 2, args.len(), span));
+    }
+    let stream_mutex = extract_tls_stream(&args[0], "tlsRead", span)?;
+    let max_bytes = extract_number(&args[1], "tlsRead", span)? as usize;
+
+    let mut stream = stream_mutex.lock().unwrap();
+    read_string(&mut *stream, max_bytes, "tlsRead", span)
+}
+
+/// tlsClose(stream: handle) -> null
+pub fn tls_close(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(super::stdlib_arity_error("tlsClose", 1, args.len(), span));
+    }
+    let id = extract_handle_id(&args[0], TLS_STREAM_TAG, "tlsClose", span)?;
+    close_handle(TLS_STREAM_TAG, id, "tlsClose", span)
+}
+
+// ── Any Socket ───────────────────────────────────────────────────────
+
+/// send(socket: handle, data: string) -> number (bytes sent)
+///
+/// Accepts TCP and TLS streams and connected UDP sockets (`udpConnect`).
+pub fn send(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 2 {
+        return Err(super::stdlib_arity_error("send", 2, args.len(), span));
+    }
+    let data = extract_str(&args[1], "send", span)?;
+    match handle_parts(&args[0], "send", span)?.0 {
+        TCP_STREAM_TAG => {
+            let stream_mutex = extract_tcp_stream(&args[0], "send", span)?;
+            let mut stream = stream_mutex.lock().unwrap();
+            write_flush(&mut *stream, data, "send", span)
+        }
+        TLS_STREAM_TAG => {
+            let stream_mutex = extract_tls_stream(&args[0], "send", span)?;
+            let mut stream = stream_mutex.lock().unwrap();
+            write_flush(&mut *stream, data, "send", span)
+        }
+        UDP_SOCKET_TAG => {
+            let socket_mutex = extract_udp_socket(&args[0], "send", span)?;
+            let socket = socket_mutex.lock().unwrap();
+            if socket.peer_addr().is_err() {
+                return Err(RuntimeError::InvalidStdlibArgument {
+                    msg: "send(): UDP socket has no peer — open it with net.udpConnect, \
+                          or use net.udpSend(socket, data, target)"
+                        .to_string(),
+                    span,
+                });
+            }
+            let n = socket
+                .send(data.as_bytes())
+                .map_err(|e| RuntimeError::IoError {
+                    message: format!("send(): {}", e),
+                    span,
+                })?;
+            Ok(Value::Number(n as f64))
+        }
+        tag => Err(wrong_handle("send", tag, span)),
+    }
+}
+
+/// recv(socket: handle, max_bytes: number) -> string
+///
+/// Reads from a TCP or TLS stream, or takes one datagram from a UDP socket.
+pub fn recv(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 2 {
+        return Err(super::stdlib_arity_error("recv", 2, args.len(), span));
+    }
+    let max_bytes = extract_number(&args[1], "recv", span)? as usize;
+    match handle_parts(&args[0], "recv", span)?.0 {
+        TCP_STREAM_TAG => {
+            let stream_mutex = extract_tcp_stream(&args[0], "recv", span)?;
+            let mut stream = stream_mutex.lock().unwrap();
+            read_string(&mut *stream, max_bytes, "recv", span)
+        }
+        TLS_STREAM_TAG => {
+            let stream_mutex = extract_tls_stream(&args[0], "recv", span)?;
+            let mut stream = stream_mutex.lock().unwrap();
+            read_string(&mut *stream, max_bytes, "recv", span)
+        }
+        UDP_SOCKET_TAG => {
+            let socket_mutex = extract_udp_socket(&args[0], "recv", span)?;
+            let socket = socket_mutex.lock().unwrap();
+            let mut buf = vec![0u8; max_bytes.min(65536)];
+            let n = socket.recv(&mut buf).map_err(|e| RuntimeError::IoError {
+                message: format!("recv(): {}", e),
+                span,
+            })?;
+            Ok(Value::string(
+                String::from_utf8_lossy(&buf[..n]).into_owned(),
+            ))
+        }
+        tag => Err(wrong_handle("recv", tag, span)),
+    }
+}
+
+/// close(socket: handle) -> null
+///
+/// Closes any network handle: streams, listeners and UDP sockets.
+pub fn close(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(super::stdlib_arity_error("close", 1, args.len(), span));
+    }
+    let (tag, id) = handle_parts(&args[0], "close", span)?;
+    close_handle(tag, id, "close", span)
+}
+
+/// Release the socket behind handle `id`; later use of the handle fails
+fn close_handle(tag: &str, id: u64, func_name: &str, span: Span) -> Result<Value, RuntimeError> {
+    let closed = || RuntimeError::InvalidStdlibArgument {
+        msg: format!("{}(): handle has already been closed", func_name),
+        span,
+    };
+    match tag {
+        TCP_STREAM_TAG => {
+            let stream_mutex = tcp_streams()
+                .lock()
+                .unwrap()
+                .remove(&id)
+                .ok_or_else(closed)?;
+            let stream = stream_mutex.lock().unwrap();
+            // The peer may have hung up first, which leaves nothing to shut down
+            match stream.shutdown(std::net::Shutdown::Both) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotConnected => {
+                    return Err(RuntimeError::IoError {
+                        message: format!("{}(): {}", func_name, e),
+                        span,
+                    });
+                }
+                _ => {}
+            }
+        }
+        TCP_LISTENER_TAG => {
+            tcp_listeners()
+                .lock()
+                .unwrap()
+                .remove(&id)
+                .ok_or_else(closed)?;
+        }
+        UDP_SOCKET_TAG => {
+            udp_sockets()
+                .lock()
+                .unwrap()
+                .remove(&id)
+                .ok_or_else(closed)?;
+        }
+        TLS_STREAM_TAG => {
+            let stream_mutex = tls_streams()
+                .lock()
+                .unwrap()
+                .remove(&id)
+                .ok_or_else(closed)?;
+            let mut stream = stream_mutex.lock().unwrap();
+            stream.conn.send_close_notify();
+            let _ = stream.flush();
+        }
+        tag => return Err(wrong_handle(func_name, tag, span)),
+    }
+    Ok(Value::Null)
+}
+
+fn wrong_handle(func_name: &str, tag: &str, span: Span) -> RuntimeError {
+    RuntimeError::InvalidStdlibArgument {
+        msg: format!(
+            "{}(): expected a socket handle, got {} handle",
+            func_name, tag
+        ),
+        span,
+    }
+}
+
+/// Write `data` and flush, returning the number of bytes written
+fn write_flush(
+    stream: &mut impl Write,
+    data: &str,
+    func_name: &str,
+    span: Span,
+) -> Result<Value, RuntimeError> {
     let n = stream
         .write(data.as_bytes())
         .map_err(|e| RuntimeError::IoError {
-            message: format!("tlsWrite(): {}", e),
+            message: format!("{}(): {}", func_name, e),
             span,
         })?;
     stream.flush().map_err(|e| RuntimeError::IoError {
-        message: format!("tlsWrite(): flush failed: {}", e),
+        message: format!("{}(): flush failed: {}", func_name, e),
         span,
     })?;
     Ok(Value::Number(n as f64))
 }
 
-/// tlsRead(stream: handle, max_bytes: number) -> string
-pub fn tls_read(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
-    if args.len() != 2 {
-        return Err(super::stdlib_arity_error("tlsRead", 2, args.len(), span));
-    }
-    let stream_mutex = extract_tls_stream(&args[0], "tlsRead", span)?;
-    let max_bytes = extract_number(&args[1], "tlsRead", span)? as usize;
-
+/// Read up to `max_bytes` (capped at 1MB) as UTF-8
+fn read_string(
+    stream: &mut impl Read,
+    max_bytes: usize,
+    func_name: &str,
+    span: Span,
+) -> Result<Value, RuntimeError> {
     let mut buf = vec![0u8; max_bytes.min(1024 * 1024)];
-    let mut stream = stream_mutex.lock().unwrap();
     let n = stream.read(&mut buf).map_err(|e| RuntimeError::IoError {
-        message: format!("tlsRead(): {}", e),
+        message: format!("{}(): {}", func_name, e),
         span,
     })?;
     buf.truncate(n);
@@ -520,23 +729,11 @@ pub fn tls_read(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
     String::from_utf8(buf)
         .map(Value::string)
         .map_err(|e| RuntimeError::InvalidStdlibArgument {
-            msg: format!("tlsRead(): received non-UTF-8 data: {}", e),
+            msg: format!("{}(): received non-UTF-8 data: {}", func_name, e),
             span,
         })
 }
 
-/// tlsClose(stream: handle) -> null
-pub fn tls_close(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
-    if args.len() != 1 {
-        return Err(super::stdlib_arity_error("tlsClose", 1, args.len(), span));
-    }
-    let stream_mutex = extract_tls_stream(&args[0], "tlsClose", span)?;
-    let mut stream = stream_mutex.lock().unwrap();
-    stream.conn.send_close_notify();
-    let _ = stream.flush();
-    Ok(Value::Null)
-}
-
 // ── Handle types ─────────────────────────────────────────────────────
 //
 // Network handles are stored as SharedValue(Arc<Mutex<Value>>), where
@@ -621,35 +818,50 @@ fn wrap_tls_stream(stream: rustls::StreamOwned<rustls::ClientConnection, TcpStre
     make_handle(TLS_STREAM_TAG, id)
 }
 
+/// The tag and id of a handle value, whatever kind of handle it is
+fn handle_parts(
+    value: &Value,
+    func_name: &str,
+    span: Span,
+) -> Result<(&'static str, u64), RuntimeError> {
+    let invalid = || super::stdlib_arg_error(func_name, "handle", value, span);
+    let Value::Array(arr) = value else {
+        return Err(invalid());
+    };
+    match arr.as_slice() {
+        [Value::String(tag), Value::Number(id)] => {
+            let tag = [
+                TCP_STREAM_TAG,
+                TCP_LISTENER_TAG,
+                UDP_SOCKET_TAG,
+                TLS_STREAM_TAG,
+            ]
+            .into_iter()
+            .find(|known| *known == tag.as_str())
+            .ok_or_else(invalid)?;
+            Ok((tag, *id as u64))
+        }
+        _ => Err(invalid()),
+    }
+}
+
 fn extract_handle_id(
     value: &Value,
     expected_tag: &str,
     func_name: &str,
     span: Span,
 ) -> Result<u64, RuntimeError> {
-    match value {
-        Value::Array(arr) if arr.len() == 2 => {
-            let tag = match &arr.as_slice()[0] {
-                Value::String(s) => s.as_str(),
-                _ => return Err(super::stdlib_arg_error(func_name, "handle", value, span)),
-            };
-            let id = match &arr.as_slice()[1] {
-                Value::Number(n) => *n as u64,
-                _ => return Err(super::stdlib_arg_error(func_name, "handle", value, span)),
-            };
-            if tag == expected_tag {
-                Ok(id)
-            } else {
-                Err(RuntimeError::InvalidStdlibArgument {
-                    msg: format!(
-                        "{}(): expected {} handle, got {} handle",
-                        func_name, expected_tag, tag
-                    ),
-                    span,
-                })
-            }
-        }
-        _ => Err(super::stdlib_arg_error(func_name, "handle", value, span)),
+    let (tag, id) = handle_parts(value, func_name, span)?;
+    if tag == expected_tag {
+        Ok(id)
+    } else {
+        Err(RuntimeError::InvalidStdlibArgument {
+            msg: format!(
+                "{}(): expected {} handle, got {} handle",
+                func_name, expected_tag, tag
+            ),
+            span,
+        })
     }
 }
 
@@ -744,16 +956,10 @@ fn extract_bool(value: &Value, func_name: &str, span: Span) -> Result<bool, Runt
     }
 }
 
-fn resolve_addr(addr_str: &str, func_name: &str, span: Span) -> Result<SocketAddr, RuntimeError> {
+fn resolve_addr(addr_str: &str) -> Result<SocketAddr, String> {
     addr_str
         .to_socket_addrs()
-        .map_err(|e| RuntimeError::InvalidStdlibArgument {
-            msg: format!("{}(): invalid address '{}': {}", func_name, addr_str, e),
-            span,
-        })?
+        .map_err(|e| format!("invalid address '{}': {}", addr_str, e))?
         .next()
-        .ok_or_else(|| RuntimeError::InvalidStdlibArgument {
-            msg: format!("{}(): could not resolve address '{}'", func_name, addr_str),
-            span,
-        })
+        .ok_or_else(|| format!("could not resolve address '{}'", addr_str))
 }
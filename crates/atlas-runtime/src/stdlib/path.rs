@@ -39,8 +39,8 @@ fn expect_array(value: &Value, param_name: &str, span: Span) -> Result<Vec<Value
 
 /// Join path components
 ///
-/// Takes an array of path segments and joins them with the platform-specific separator.
-/// Handles edge cases like empty segments, relative paths, and normalization.
+/// Takes an array of path segments and joins them, rendering the result with
+/// forward slashes. Handles edge cases like empty segments and relative paths.
 pub fn path_join(segments: &[Value], span: Span) -> Result<String, RuntimeError> {
     if segments.is_empty() {
         return Ok(".".to_string());
@@ -58,7 +58,7 @@ pub fn path_join(segments: &[Value], span: Span) -> Result<String, RuntimeError>
     if path.as_os_str().is_empty() {
         Ok(".".to_string())
     } else {
-        Ok(path.to_string_lossy().to_string())
+        Ok(forward_slashes(&path))
     }
 }
 
@@ -160,7 +160,20 @@ pub fn path_normalize(path_str: &str, _span: Span) -> Result<String, RuntimeErro
         Ok(".".to_string())
     } else {
         // Always use forward slashes for cross-platform consistency
-        Ok(normalized.to_string_lossy().replace('\\', "/"))
+        Ok(forward_slashes(&normalized))
+    }
+}
+
+/// Render a computed path with forward slashes on every platform
+///
+/// Only Windows separators are rewritten; on Unix a backslash is an ordinary
+/// file-name character and is left alone.
+fn forward_slashes(path: &Path) -> String {
+    let rendered = path.to_string_lossy();
+    if cfg!(windows) {
+        rendered.replace('\\', "/")
+    } else {
+        rendered.into_owned()
     }
 }
 
@@ -171,7 +184,7 @@ pub fn path_absolute(path_str: &str, span: Span) -> Result<String, RuntimeError>
     let path = Path::new(path_str);
 
     if path.is_absolute() {
-        return Ok(path.to_string_lossy().to_string());
+        return Ok(forward_slashes(path));
     }
 
     // Get current working directory
@@ -181,7 +194,7 @@ pub fn path_absolute(path_str: &str, span: Span) -> Result<String, RuntimeError>
     })?;
 
     let absolute = cwd.join(path);
-    Ok(absolute.to_string_lossy().to_string())
+    Ok(forward_slashes(&absolute))
 }
 
 /// Compute relative path from 'from' to 'to'
@@ -214,10 +227,12 @@ pub fn path_relative(from: &str, to: &str, span: Span) -> Result<String, Runtime
             .join(to_path)
     };
 
-    // Use pathdiff for computing relative path
+    // Use pathdiff for computing relative path.
+    // Output uses forward slashes (like path_normalize) so results match across platforms.
     match pathdiff::diff_paths(&to_abs, &from_abs) {
-        Some(rel) => Ok(rel.to_string_lossy().to_string()),
-        None => Ok(to_abs.to_string_lossy().to_string()),
+        Some(rel) if rel.as_os_str().is_empty() => Ok(".".to_string()),
+        Some(rel) => Ok(forward_slashes(&rel)),
+        None => Ok(forward_slashes(&to_abs)),
     }
 }
 
@@ -228,7 +243,7 @@ pub fn path_parent(path_str: &str, _span: Span) -> Result<Value, RuntimeError> {
     let path = Path::new(path_str);
     match path.parent() {
         Some(p) if !p.as_os_str().is_empty() => Ok(Value::Option(Some(Box::new(Value::string(
-            p.to_string_lossy().to_string(),
+            forward_slashes(p),
         ))))),
         _ => Ok(Value::Option(None)),
     }
@@ -241,7 +256,7 @@ pub fn path_resolve(path_str: &str, span: Span) -> Result<String, RuntimeError>
     let path = Path::new(path_str);
 
     if path.is_absolute() {
-        return Ok(path.to_string_lossy().to_string());
+        return Ok(forward_slashes(path));
     }
 
     // Get current working directory and join
@@ -251,7 +266,7 @@ pub fn path_resolve(path_str: &str, span: Span) -> Result<String, RuntimeError>
     })?;
 
     let absolute = cwd.join(path);
-    Ok(absolute.to_string_lossy().to_string())
+    Ok(forward_slashes(&absolute))
 }
 
 /// Get base filename (with extension)
@@ -266,10 +281,7 @@ pub fn path_basename(path_str: &str, _span: Span) -> Result<String, RuntimeError
 /// Get directory name (everything except filename)
 pub fn path_dirname(path_str: &str, _span: Span) -> Result<String, RuntimeError> {
     let path = Path::new(path_str);
-    Ok(path
-        .parent()
-        .map(|p| p.to_string_lossy().to_string())
-        .unwrap_or_default())
+    Ok(path.parent().map(forward_slashes).unwrap_or_default())
 }
 
 /// Get file extension (without dot)
@@ -281,6 +293,46 @@ pub fn path_extension(path_str: &str, _span: Span) -> Result<String, RuntimeErro
         .unwrap_or_default())
 }
 
+/// Get file name without its extension ("archive.tar.gz" → "archive.tar")
+pub fn path_stem(path_str: &str, _span: Span) -> Result<String, RuntimeError> {
+    let path = Path::new(path_str);
+    Ok(path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default())
+}
+
+/// Replace (or add) the file extension
+///
+/// `ext` may be given with or without the leading dot. An empty `ext`
+/// removes the extension. Paths without a file name are returned unchanged.
+pub fn path_with_extension(path_str: &str, ext: &str, _span: Span) -> Result<String, RuntimeError> {
+    let path = Path::new(path_str);
+    if path.file_name().is_none() {
+        return Ok(path_str.to_string());
+    }
+    let ext = ext.strip_prefix('.').unwrap_or(ext);
+    Ok(forward_slashes(&path.with_extension(ext)))
+}
+
+/// Expand a leading `~` to the user's home directory
+///
+/// Handles `~`, `~/rest` and `~\rest`. Any other path (including `~user/...`)
+/// is returned unchanged.
+pub fn path_expand_home(path_str: &str, span: Span) -> Result<String, RuntimeError> {
+    let rest = match path_str.strip_prefix('~') {
+        Some("") => "",
+        Some(rest) if rest.starts_with('/') || rest.starts_with('\\') => &rest[1..],
+        _ => return Ok(path_str.to_string()),
+    };
+    let home = path_homedir(span)?;
+    if rest.is_empty() {
+        Ok(forward_slashes(Path::new(&home)))
+    } else {
+        Ok(forward_slashes(&Path::new(&home).join(rest)))
+    }
+}
+
 // ============================================================================
 // Path Comparison and Validation
 // ============================================================================
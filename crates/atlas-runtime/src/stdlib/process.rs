@@ -30,10 +30,12 @@
 use super::stdlib_arity_error;
 use crate::security::SecurityContext;
 use crate::span::Span;
+use crate::stdlib::collections::hash::HashKey;
 use crate::value::{RuntimeError, Value, ValueArray};
 use std::collections::HashMap;
 use std::env;
 use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::process::{Child, ChildStderr, ChildStdin, ChildStdout, Command, Output, Stdio};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
@@ -235,40 +237,102 @@ const PROCESS_STDERR_TAG: &str = "__process_stderr__";
 
 /// Execute a command and wait for completion
 ///
-/// Atlas signature: `exec(command: string | []string, options?: object) -> Result<object, string>`
+/// Atlas signature:
+/// `exec(command: string | []string, args?: []string, options?: record) -> Result<ProcessOutput, string>`
 ///
 /// Options:
-/// - env: object - Custom environment variables
-/// - cwd: string - Working directory
+/// - env: object - Custom environment variables, each needing environment
+///   write permission (a `PATH` here does not change which program runs;
+///   see `resolve_program`)
+/// - cwd: string - Working directory, needing filesystem read permission
 /// - inherit: bool - Inherit parent stdio (default: false)
 ///
-/// Returns: { exitCode: number, stdout: string, stderr: string }
+/// Returns a `ProcessOutput` (stdout, stderr, exitCode, success)
 pub fn exec(args: &[Value], span: Span, security: &SecurityContext) -> Result<Value, RuntimeError> {
-    if args.is_empty() || args.len() > 2 {
-        return Err(stdlib_arity_error("exec", 1, args.len(), span));
+    let (program, command_args, options) = parse_exec_call("exec", args, span)?;
+    run_command(&program, &command_args, &options, span, security)
+}
+
+/// Execute a command and capture its output, never inheriting stdio
+///
+/// Atlas signature:
+/// `execCapture(command: string | []string, args?: []string, options?: record) -> Result<ProcessOutput, string>`
+///
+/// Same as `exec`, except that the `inherit` option is rejected, so stdout
+/// and stderr always end up in the returned `ProcessOutput`.
+pub fn exec_capture(
+    args: &[Value],
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
+    let (program, command_args, options) = parse_exec_call("execCapture", args, span)?;
+    if options.inherit {
+        return Err(RuntimeError::InvalidStdlibArgument {
+            msg: "execCapture(): the inherit option is not supported; use exec() instead"
+                .to_string(),
+            span,
+        });
     }
+    run_command(&program, &command_args, &options, span, security)
+}
 
-    // Parse command
-    let (program, command_args) = parse_command(&args[0], span)?;
+/// Split `exec`-style arguments into program, argument list and options
+///
+/// The second argument is either the argument array or, for the older
+/// `exec(command, options)` form, the options object.
+fn parse_exec_call(
+    name: &str,
+    args: &[Value],
+    span: Span,
+) -> Result<(String, Vec<String>, ExecOptions), RuntimeError> {
+    if args.is_empty() || args.len() > 3 {
+        return Err(stdlib_arity_error(name, 1, args.len(), span));
+    }
 
-    // Check permission
+    let (program, mut command_args) = parse_command(&args[0], span)?;
+    let options = match &args[1..] {
+        [] => ExecOptions::default(),
+        [extra @ Value::Array(_), rest @ ..] => {
+            command_args.extend(parse_string_args(extra, name, span)?);
+            match rest {
+                [options] => parse_exec_options(options, span)?,
+                _ => ExecOptions::default(),
+            }
+        }
+        [options] => parse_exec_options(options, span)?,
+        _ => {
+            return Err(RuntimeError::TypeError {
+                msg: format!("{}(): second argument must be an array of arguments", name),
+                span,
+            })
+        }
+    };
+    Ok((program, command_args, options))
+}
+
+/// Run a permitted command to completion
+fn run_command(
+    program: &str,
+    command_args: &[String],
+    options: &ExecOptions,
+    span: Span,
+    security: &SecurityContext,
+) -> Result<Value, RuntimeError> {
     security
-        .check_process(&program)
+        .check_process(program)
         .map_err(|_| RuntimeError::ProcessPermissionDenied {
-            command: program.clone(),
+            command: program.to_string(),
             span,
         })?;
+    check_exec_options(options, span, security)?;
 
-    // Parse options
-    let options = if args.len() == 2 {
-        parse_exec_options(&args[1], span)?
-    } else {
-        ExecOptions::default()
+    // A program that cannot be started is an Err value, not a runtime error
+    let program_path = match resolve_program(program, options) {
+        Ok(path) => path,
+        Err(msg) => return Ok(Value::Result(Err(Box::new(Value::string(msg))))),
     };
-
-    // Build command
-    let mut cmd = Command::new(&program);
-    cmd.args(&command_args);
+    let mut cmd = Command::new(program_path);
+    cmd.args(command_args);
 
     // Set environment if provided
     if let Some(env_vars) = &options.env {
@@ -293,11 +357,15 @@ pub fn exec(args: &[Value], span: Span, security: &SecurityContext) -> Result<Va
             .stdin(Stdio::null());
     }
 
-    // Execute command
-    let output = cmd.output().map_err(|e| RuntimeError::IoError {
-        message: format!("Failed to execute command: {}", e),
-        span,
-    })?;
+    let output = match cmd.output() {
+        Ok(output) => output,
+        Err(e) => {
+            return Ok(Value::Result(Err(Box::new(Value::string(format!(
+                "failed to execute '{}': {}",
+                program, e
+            ))))))
+        }
+    };
 
     // Return typed ProcessOutput
     let process_out = ProcessOutput::from_std_output(&output);
@@ -306,6 +374,80 @@ pub fn exec(args: &[Value], span: Span, security: &SecurityContext) -> Result<Va
     ))))))
 }
 
+/// Check the `env` and `cwd` options against the script's permissions
+///
+/// The child sees every variable in `env`, so each one needs environment
+/// write permission: `LD_PRELOAD` or `GIT_SSH_COMMAND` would otherwise let a
+/// script allowed only `git` run code of its choosing. The working directory
+/// needs read permission.
+fn check_exec_options(
+    options: &ExecOptions,
+    span: Span,
+    security: &SecurityContext,
+) -> Result<(), RuntimeError> {
+    if let Some(env_vars) = &options.env {
+        for key in env_vars.keys() {
+            security.check_environment_write(key).map_err(|_| {
+                RuntimeError::EnvironmentPermissionDenied {
+                    var: key.clone(),
+                    span,
+                }
+            })?;
+        }
+    }
+    if let Some(cwd) = &options.cwd {
+        security
+            .check_filesystem_read(Path::new(cwd))
+            .map_err(|_| RuntimeError::FilesystemPermissionDenied {
+                operation: "working directory".to_string(),
+                path: cwd.clone(),
+                span,
+            })?;
+    }
+    Ok(())
+}
+
+/// Locate the program a permission-checked command name refers to
+///
+/// `Command` looks bare names up in the child's `PATH`, which the `env`
+/// option can replace; a script allowed only `git` could then run its own
+/// `git` from a directory it writes to. When `env` sets `PATH`, bare names
+/// are resolved against this process's `PATH` instead.
+fn resolve_program(program: &str, options: &ExecOptions) -> Result<PathBuf, String> {
+    let overrides_path = options
+        .env
+        .as_ref()
+        .is_some_and(|vars| vars.keys().any(|key| key.eq_ignore_ascii_case("PATH")));
+    if !overrides_path || Path::new(program).components().count() != 1 {
+        return Ok(PathBuf::from(program));
+    }
+
+    let candidates: Vec<String> = if cfg!(windows) && Path::new(program).extension().is_none() {
+        vec![format!("{}.exe", program), program.to_string()]
+    } else {
+        vec![program.to_string()]
+    };
+    env::var_os("PATH")
+        .and_then(|paths| {
+            env::split_paths(&paths)
+                .flat_map(|dir| candidates.iter().map(move |name| dir.join(name)))
+                .find(|candidate| is_executable(candidate))
+        })
+        .ok_or_else(|| format!("failed to execute '{}': not found in PATH", program))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
 /// Execute a shell command and return stdout as a string
 ///
 /// Simpler variant of `shell()` for cases where only stdout is needed.
@@ -415,9 +557,12 @@ pub fn shell(
     } else {
         ExecOptions::default()
     };
+    check_exec_options(&options, span, security)?;
 
     // Build command
-    let mut cmd = Command::new(shell_cmd);
+    let shell_path = resolve_program(shell_cmd, &options)
+        .map_err(|message| RuntimeError::IoError { message, span })?;
+    let mut cmd = Command::new(shell_path);
     cmd.arg(shell_arg).arg(&command_str);
 
     // Set environment if provided
@@ -539,7 +684,7 @@ pub fn set_env(
     };
 
     // Check permission
-    security.check_environment(&var_name).map_err(|_| {
+    security.check_environment_write(&var_name).map_err(|_| {
         RuntimeError::EnvironmentPermissionDenied {
             var: var_name.clone(),
             span,
@@ -578,7 +723,7 @@ pub fn unset_env(
     };
 
     // Check permission
-    security.check_environment(&var_name).map_err(|_| {
+    security.check_environment_write(&var_name).map_err(|_| {
         RuntimeError::EnvironmentPermissionDenied {
             var: var_name.clone(),
             span,
@@ -591,20 +736,23 @@ pub fn unset_env(
     Ok(Value::Null)
 }
 
-/// List all environment variables
+/// List the environment variables the script may read
 ///
 /// Atlas signature: `listEnv() -> object`
+///
+/// Variables without read permission are left out rather than failing the
+/// call, so a script granted `HOME` and `PATH` sees exactly those two.
 pub fn list_env(
     args: &[Value],
     span: Span,
-    _security: &SecurityContext,
+    security: &SecurityContext,
 ) -> Result<Value, RuntimeError> {
     if !args.is_empty() {
         return Err(stdlib_arity_error("listEnv", 0, args.len(), span));
     }
 
-    // Get all environment variables
     let env_vars: HashMap<String, crate::json_value::JsonValue> = env::vars()
+        .filter(|(key, _)| security.can_read_environment(key))
         .map(|(key, value)| (key, crate::json_value::JsonValue::String(value)))
         .collect();
 
@@ -695,7 +843,7 @@ pub fn get_arch(
 pub fn process_exit(
     args: &[Value],
     span: Span,
-    _security: &SecurityContext,
+    security: &SecurityContext,
 ) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(stdlib_arity_error("process.exit", 1, args.len(), span));
@@ -711,6 +859,8 @@ pub fn process_exit(
         }
     };
 
+    // Normal exit: remove runtime-managed temp files before the process goes away.
+    security.temp_root().cleanup();
     std::process::exit(code);
 }
 
@@ -1308,6 +1458,29 @@ fn parse_command_array(
     }
 }
 
+/// Parse an argument list: an array of strings, possibly empty
+fn parse_string_args(
+    value: &Value,
+    func_name: &str,
+    span: Span,
+) -> Result<Vec<String>, RuntimeError> {
+    let Value::Array(arr) = value else {
+        return Err(RuntimeError::TypeError {
+            msg: format!("{}(): arguments must be an array of strings", func_name),
+            span,
+        });
+    };
+    arr.iter()
+        .map(|arg| match arg {
+            Value::String(s) => Ok(s.as_ref().clone()),
+            _ => Err(RuntimeError::TypeError {
+                msg: format!("{}(): command arguments must be strings", func_name),
+                span,
+            }),
+        })
+        .collect()
+}
+
 fn extract_process_handle(value: &Value, func_name: &str, span: Span) -> Result<u32, RuntimeError> {
     match value {
         Value::Number(n) if *n >= 0.0 => Ok(*n as u32),
@@ -1351,10 +1524,11 @@ struct ExecOptions {
     inherit: bool,
 }
 
-/// Parse execution options from object
+/// Parse execution options from a `record { ... }` or JSON object
 fn parse_exec_options(value: &Value, span: Span) -> Result<ExecOptions, RuntimeError> {
     let json_obj = match value {
         Value::JsonValue(j) => j,
+        Value::Map(map) => return parse_exec_options_record(map.entries(), span),
         Value::Null => return Ok(ExecOptions::default()),
         _ => {
             return Err(RuntimeError::TypeError {
@@ -1410,3 +1584,54 @@ fn parse_exec_options(value: &Value, span: Span) -> Result<ExecOptions, RuntimeE
 
     Ok(options)
 }
+
+fn parse_exec_options_record(
+    entries: Vec<(HashKey, Value)>,
+    span: Span,
+) -> Result<ExecOptions, RuntimeError> {
+    let mut options = ExecOptions::default();
+    for (key, value) in entries {
+        let field = match &key {
+            HashKey::String(s) => s.as_str(),
+            _ => "",
+        };
+        match (field, value) {
+            ("env", Value::Map(env_map)) => {
+                let mut vars = HashMap::new();
+                for (name, val) in env_map.entries() {
+                    match (name, val) {
+                        (HashKey::String(name), Value::String(s)) => {
+                            vars.insert(name.as_ref().clone(), s.as_ref().clone());
+                        }
+                        _ => {
+                            return Err(RuntimeError::TypeError {
+                                msg: "Environment variable values must be strings".to_string(),
+                                span,
+                            })
+                        }
+                    }
+                }
+                options.env = Some(vars);
+            }
+            ("cwd", Value::String(s)) => options.cwd = Some(s.as_ref().clone()),
+            ("inherit", Value::Bool(b)) => options.inherit = b,
+            ("env" | "cwd" | "inherit", other) => {
+                return Err(RuntimeError::TypeError {
+                    msg: format!(
+                        "exec option '{}' has the wrong type: {}",
+                        field,
+                        other.type_name()
+                    ),
+                    span,
+                })
+            }
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: format!("unknown exec option '{}'", field),
+                    span,
+                })
+            }
+        }
+    }
+    Ok(options)
+}
@@ -7,7 +7,13 @@ use crate::reflect::{
     get_type_name, get_value_type_info, is_callable, is_primitive_value, same_type,
 };
 use crate::span::Span;
-use crate::value::{RuntimeError, Value};
+use crate::stdlib::collections::hashmap::AtlasHashMap;
+use crate::stdlib::collections::queue::AtlasQueue;
+use crate::stdlib::collections::stack::AtlasStack;
+use crate::value::{
+    format_number, CycleGuard, RuntimeError, Value, ValueHashMap, ValueQueue, ValueStack,
+};
+use std::sync::Arc;
 
 /// Get the type name of a value as a string
 ///
@@ -139,6 +145,10 @@ pub fn type_describe_fn(args: &[Value], span: Span) -> Result<Value, RuntimeErro
 
 /// Clone a value (deep copy for arrays, shallow for primitives)
 ///
+/// `shared` cells inside the value are copied into fresh cells, so mutating
+/// the clone never affects the original. A cell that contains itself cannot
+/// be copied and is reported as a `CircularReference`.
+///
 /// # Atlas Usage
 /// ```atlas
 /// let arr = [1, 2, 3];
@@ -149,8 +159,69 @@ pub fn clone_fn(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
         return Err(stdlib_arity_error("clone", 1, args.len(), span));
     }
 
-    // Value already implements Clone, so this is straightforward
-    Ok(args[0].clone())
+    deep_clone(&args[0], &mut CycleGuard::default(), span)
+}
+
+/// Copy `value`, giving every `shared` cell reachable from it a new cell.
+/// Everything else is copy-on-write already, so `Value::clone` suffices.
+fn deep_clone(value: &Value, seen: &mut CycleGuard, span: Span) -> Result<Value, RuntimeError> {
+    Ok(match value {
+        Value::SharedValue(cell) => {
+            if !seen.enter(cell) {
+                return Err(RuntimeError::CircularReference {
+                    msg: "shared value contains itself; cannot clone".to_string(),
+                    span,
+                });
+            }
+            let inner = cell.with(|v| deep_clone(v, seen, span));
+            seen.exit(cell);
+            Value::shared(inner?)
+        }
+        Value::Array(arr) => Value::array(deep_clone_all(arr.as_slice(), seen, span)?),
+        Value::Tuple(elems) => Value::Tuple(Arc::new(deep_clone_all(elems, seen, span)?)),
+        Value::Map(map) => {
+            let mut copy = AtlasHashMap::new();
+            for (key, v) in map.entries() {
+                copy.insert(key, deep_clone(&v, seen, span)?);
+            }
+            Value::Map(ValueHashMap::from_atlas(copy))
+        }
+        Value::Queue(queue) => {
+            let mut copy = AtlasQueue::new();
+            for v in deep_clone_all(&queue.inner().to_vec(), seen, span)? {
+                copy.enqueue(v);
+            }
+            Value::Queue(ValueQueue::from_atlas(copy))
+        }
+        Value::Stack(stack) => {
+            let mut copy = AtlasStack::new();
+            for v in deep_clone_all(&stack.inner().to_vec(), seen, span)? {
+                copy.push(v);
+            }
+            Value::Stack(ValueStack::from_atlas(copy))
+        }
+        Value::Option(Some(v)) => Value::Option(Some(Box::new(deep_clone(v, seen, span)?))),
+        Value::Result(Ok(v)) => Value::Result(Ok(Box::new(deep_clone(v, seen, span)?))),
+        Value::Result(Err(e)) => Value::Result(Err(Box::new(deep_clone(e, seen, span)?))),
+        Value::EnumValue {
+            enum_name,
+            variant_name,
+            data,
+        } => Value::EnumValue {
+            enum_name: enum_name.clone(),
+            variant_name: variant_name.clone(),
+            data: deep_clone_all(data, seen, span)?,
+        },
+        other => other.clone(),
+    })
+}
+
+fn deep_clone_all(
+    values: &[Value],
+    seen: &mut CycleGuard,
+    span: Span,
+) -> Result<Vec<Value>, RuntimeError> {
+    values.iter().map(|v| deep_clone(v, seen, span)).collect()
 }
 
 /// Convert any value to its string representation
@@ -185,13 +256,23 @@ pub fn deep_equals_fn(args: &[Value], span: Span) -> Result<Value, RuntimeError>
         return Err(stdlib_arity_error("deepEquals", 2, args.len(), span));
     }
 
-    let result = deep_equals_impl(&args[0], &args[1]);
+    let mut seen = [CycleGuard::default(), CycleGuard::default()];
+    let result = deep_equals_impl(&args[0], &args[1], &mut seen, span)?;
     Ok(Value::Bool(result))
 }
 
 /// Deep equality implementation
-fn deep_equals_impl(a: &Value, b: &Value) -> bool {
-    match (a, b) {
+///
+/// Distinct `shared` cells compare by contents; `seen` holds the cells
+/// entered on each side so a self-containing cell is reported, not walked
+/// forever.
+fn deep_equals_impl(
+    a: &Value,
+    b: &Value,
+    seen: &mut [CycleGuard; 2],
+    span: Span,
+) -> Result<bool, RuntimeError> {
+    Ok(match (a, b) {
         (Value::Number(x), Value::Number(y)) => x == y,
         (Value::String(x), Value::String(y)) => x == y,
         (Value::Bool(x), Value::Bool(y)) => x == y,
@@ -202,30 +283,52 @@ fn deep_equals_impl(a: &Value, b: &Value) -> bool {
             let b_borrowed = arr_b.as_slice();
 
             if a_borrowed.len() != b_borrowed.len() {
-                return false;
+                return Ok(false);
             }
 
-            a_borrowed
-                .iter()
-                .zip(b_borrowed.iter())
-                .all(|(x, y)| deep_equals_impl(x, y))
+            for (x, y) in a_borrowed.iter().zip(b_borrowed.iter()) {
+                if !deep_equals_impl(x, y, seen, span)? {
+                    return Ok(false);
+                }
+            }
+            true
         }
 
         (Value::Function(f_a), Value::Function(f_b)) => f_a.name == f_b.name,
         (Value::JsonValue(j_a), Value::JsonValue(j_b)) => j_a == j_b,
         (Value::Option(o_a), Value::Option(o_b)) => match (o_a, o_b) {
-            (Some(x), Some(y)) => deep_equals_impl(x, y),
+            (Some(x), Some(y)) => deep_equals_impl(x, y, seen, span)?,
             (None, None) => true,
             _ => false,
         },
         (Value::Result(r_a), Value::Result(r_b)) => match (r_a, r_b) {
-            (Ok(x), Ok(y)) => deep_equals_impl(x, y),
-            (Err(x), Err(y)) => deep_equals_impl(x, y),
+            (Ok(x), Ok(y)) => deep_equals_impl(x, y, seen, span)?,
+            (Err(x), Err(y)) => deep_equals_impl(x, y, seen, span)?,
             _ => false,
         },
 
+        (Value::SharedValue(s_a), Value::SharedValue(s_b)) => {
+            if s_a == s_b {
+                return Ok(true);
+            }
+            if !seen[0].enter(s_a) || !seen[1].enter(s_b) {
+                return Err(RuntimeError::CircularReference {
+                    msg: "shared value contains itself; cannot compare".to_string(),
+                    span,
+                });
+            }
+            // Walk copies: holding both locks could deadlock when one cell
+            // is reachable from the other
+            let inner_a = s_a.with(|v| (**v).clone());
+            let inner_b = s_b.with(|v| (**v).clone());
+            let equal = deep_equals_impl(&inner_a, &inner_b, seen, span);
+            seen[0].exit(s_a);
+            seen[1].exit(s_b);
+            equal?
+        }
+
         _ => false,
-    }
+    })
 }
 
 /// Get the function name (for function values)
@@ -306,7 +409,7 @@ pub fn fields_fn(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
                 .iter()
                 .map(|k| match k {
                     HashKey::String(s) => Value::string(s.as_ref().clone()),
-                    HashKey::Number(n) => Value::string(n.to_string()),
+                    HashKey::Number(n) => Value::string(format_number(n.into_inner())),
                     HashKey::Bool(b) => Value::string(b.to_string()),
                     HashKey::Null => Value::string("null".to_string()),
                 })
@@ -364,10 +467,15 @@ pub fn has_method_fn(args: &[Value], span: Span) -> Result<Value, RuntimeError>
         Value::Option(_) => Some(TypeTag::Option),
         Value::Result(_) => Some(TypeTag::Result),
         Value::JsonValue(_) => Some(TypeTag::JsonValue),
+        #[cfg(feature = "http")]
         Value::HttpResponse(_) => Some(TypeTag::HttpResponse),
+        #[cfg(feature = "http")]
         Value::HttpServerRequest(_) => Some(TypeTag::HttpServerRequest),
         Value::DateTime(_) => Some(TypeTag::DateTime),
+        #[cfg(feature = "regex")]
         Value::Regex(_) => Some(TypeTag::RegexValue),
+        Value::Bytes(_) => Some(TypeTag::Bytes),
+        Value::FileHandle(_) => Some(TypeTag::FileHandle),
         Value::ProcessOutput(_) => Some(TypeTag::ProcessOutput),
         _ => None,
     };
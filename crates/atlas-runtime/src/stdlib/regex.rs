@@ -13,7 +13,9 @@
 //! - `regexCaptures(regex: Regex, text: string) -> Option<Array>` - Extract capture groups by index
 //! - `regexCapturesNamed(regex: Regex, text: string) -> Option<HashMap>` - Extract named capture groups
 
+use super::interrupt::Ticker;
 use super::stdlib_arity_error;
+use crate::security::RegexLimits;
 use crate::span::Span;
 use crate::stdlib::collections::hash::HashKey;
 use crate::stdlib::collections::hashmap::AtlasHashMap;
@@ -34,18 +36,22 @@ use std::sync::Arc;
 /// - `Ok(Regex)` if the pattern compiles successfully
 /// - `Err(string)` with error message if the pattern is invalid
 ///
+/// # Errors
+/// `RegexLimitExceeded` if the pattern is over the security context's
+/// [`RegexLimits`].
+///
 /// # Example
 /// ```atlas
 /// let pattern = regexNew("\\d+");
 /// ```
-pub fn regex_new(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+pub fn regex_new(args: &[Value], span: Span, limits: RegexLimits) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(stdlib_arity_error("regexNew", 1, args.len(), span));
     }
 
     let pattern_str = expect_string(&args[0], span, "pattern")?;
 
-    match Regex::new(pattern_str) {
+    match build_limited(RegexBuilder::new(pattern_str), pattern_str, limits, span)? {
         Ok(regex) => {
             let regex_value = Value::Regex(Arc::new(regex));
             // Return Result::Ok(regex)
@@ -77,7 +83,11 @@ pub fn regex_new(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
 /// ```atlas
 /// let pattern = regexNewWithFlags("hello", "i"); // Case-insensitive
 /// ```
-pub fn regex_new_with_flags(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+pub fn regex_new_with_flags(
+    args: &[Value],
+    span: Span,
+    limits: RegexLimits,
+) -> Result<Value, RuntimeError> {
     if args.len() != 2 {
         return Err(stdlib_arity_error("regexNewWithFlags", 2, args.len(), span));
     }
@@ -109,7 +119,7 @@ pub fn regex_new_with_flags(args: &[Value], span: Span) -> Result<Value, Runtime
         }
     }
 
-    match builder.build() {
+    match build_limited(builder, pattern_str, limits, span)? {
         Ok(regex) => {
             let regex_value = Value::Regex(Arc::new(regex));
             Ok(Value::Result(Ok(Box::new(regex_value))))
@@ -249,8 +259,10 @@ pub fn regex_find_all(args: &[Value], span: Span) -> Result<Value, RuntimeError>
     let text = expect_string(&args[1], span, "text")?;
 
     let mut matches = Vec::new();
+    let mut ticker = Ticker::default();
 
     for mat in regex.find_iter(text) {
+        ticker.tick()?;
         let mut map = AtlasHashMap::new();
         map.insert(
             HashKey::String(Arc::new("text".to_string())),
@@ -439,9 +451,20 @@ pub fn regex_replace_all(args: &[Value], span: Span) -> Result<Value, RuntimeErr
     let text = expect_string(&args[1], span, "text")?;
     let replacement = expect_string(&args[2], span, "replacement")?;
 
-    let result = regex.replace_all(text, replacement);
+    // Equivalent to `Regex::replace_all`, with an interrupt point per match
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    let mut ticker = Ticker::default();
+    for caps in regex.captures_iter(text) {
+        ticker.tick()?;
+        let Some(mat) = caps.get(0) else { continue };
+        result.push_str(&text[last..mat.start()]);
+        caps.expand(replacement, &mut result);
+        last = mat.end();
+    }
+    result.push_str(&text[last..]);
 
-    Ok(Value::string(result.into_owned()))
+    Ok(Value::string(result))
 }
 
 // NOTE: regexReplaceWith and regexReplaceAllWith are implemented as intrinsics
@@ -473,7 +496,12 @@ pub fn regex_split(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
     let regex = expect_regex(&args[0], span)?;
     let text = expect_string(&args[1], span, "text")?;
 
-    let parts: Vec<Value> = regex.split(text).map(Value::string).collect();
+    let mut ticker = Ticker::default();
+    let mut parts = Vec::new();
+    for part in regex.split(text) {
+        ticker.tick()?;
+        parts.push(Value::string(part));
+    }
 
     Ok(Value::array(parts))
 }
@@ -515,7 +543,12 @@ pub fn regex_split_n(args: &[Value], span: Span) -> Result<Value, RuntimeError>
         return Ok(Value::array(vec![]));
     }
 
-    let parts: Vec<Value> = regex.splitn(text, limit + 1).map(Value::string).collect();
+    let mut ticker = Ticker::default();
+    let mut parts = Vec::new();
+    for part in regex.splitn(text, limit + 1) {
+        ticker.tick()?;
+        parts.push(Value::string(part));
+    }
 
     Ok(Value::array(parts))
 }
@@ -546,15 +579,15 @@ pub fn regex_match_indices(args: &[Value], span: Span) -> Result<Value, RuntimeE
     let regex = expect_regex(&args[0], span)?;
     let text = expect_string(&args[1], span, "text")?;
 
-    let indices: Vec<Value> = regex
-        .find_iter(text)
-        .map(|mat| {
-            Value::array(vec![
-                Value::Number(mat.start() as f64),
-                Value::Number(mat.end() as f64),
-            ])
-        })
-        .collect();
+    let mut ticker = Ticker::default();
+    let mut indices = Vec::new();
+    for mat in regex.find_iter(text) {
+        ticker.tick()?;
+        indices.push(Value::array(vec![
+            Value::Number(mat.start() as f64),
+            Value::Number(mat.end() as f64),
+        ]));
+    }
 
     Ok(Value::array(indices))
 }
@@ -567,13 +600,14 @@ pub fn regex_match_indices(args: &[Value], span: Span) -> Result<Value, RuntimeE
 ///
 /// # Returns
 /// - `true` if pattern matches, `false` if no match or compile error
+/// - `RegexLimitExceeded` if the pattern is over the configured limits
 ///
 /// # Example
 /// ```atlas
 /// regexTest("\\d+", "hello123") // Returns true
 /// regexTest("[invalid", "test") // Returns false (compile error)
 /// ```
-pub fn regex_test(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+pub fn regex_test(args: &[Value], span: Span, limits: RegexLimits) -> Result<Value, RuntimeError> {
     if args.len() != 2 {
         return Err(stdlib_arity_error("regexTest", 2, args.len(), span));
     }
@@ -581,7 +615,7 @@ pub fn regex_test(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
     let pattern_str = expect_string(&args[0], span, "pattern")?;
     let text = expect_string(&args[1], span, "text")?;
 
-    match Regex::new(pattern_str) {
+    match build_limited(RegexBuilder::new(pattern_str), pattern_str, limits, span)? {
         Ok(regex) => Ok(Value::Bool(regex.is_match(text))),
         Err(_) => Ok(Value::Bool(false)), // Return false on compile error
     }
@@ -591,6 +625,73 @@ pub fn regex_test(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
 // Helper Functions
 // ============================================================================
 
+/// Compile `builder` under `limits`
+///
+/// An invalid pattern is the script's to handle, so it comes back as the
+/// inner `Err(message)`; a pattern over a limit is a `RegexLimitExceeded`
+/// error.
+/// Compile a `/pattern/flags` regex literal
+///
+/// The flags are folded into the pattern as an inline group (`(?i)...`) so
+/// the compiled regex carries them in `as_str()`, which is what bytecode
+/// serialization stores. Errors are reduced to a single line for display
+/// as a parse diagnostic.
+pub fn compile_literal(pattern: &str, flags: &str) -> Result<Regex, String> {
+    if let Some(flag) = flags.chars().find(|c| !matches!(c, 'i' | 'm' | 's' | 'x')) {
+        return Err(format!("unknown flag `{}`", flag));
+    }
+    let source = if flags.is_empty() {
+        pattern.to_string()
+    } else {
+        format!("(?{}){}", flags, pattern)
+    };
+    Regex::new(&source).map_err(|err| {
+        let text = err.to_string();
+        text.lines()
+            .rev()
+            .find_map(|line| line.strip_prefix("error: "))
+            .unwrap_or(&text)
+            .to_string()
+    })
+}
+
+fn build_limited(
+    mut builder: RegexBuilder,
+    pattern: &str,
+    limits: RegexLimits,
+    span: Span,
+) -> Result<Result<Regex, String>, RuntimeError> {
+    let exceeded = |msg: String| RuntimeError::RegexLimitExceeded { msg, span };
+
+    if pattern.len() > limits.max_pattern_len {
+        return Err(exceeded(format!(
+            "pattern is {} bytes, limit is {}",
+            pattern.len(),
+            limits.max_pattern_len
+        )));
+    }
+
+    builder
+        .size_limit(limits.max_compiled_size)
+        .dfa_size_limit(limits.max_dfa_cache)
+        .nest_limit(limits.max_nesting);
+
+    match builder.build() {
+        Ok(regex) => Ok(Ok(regex)),
+        Err(regex::Error::CompiledTooBig(limit)) => Err(exceeded(format!(
+            "compiled pattern is larger than {} bytes",
+            limit
+        ))),
+        // The nesting limit is enforced by the parser, which reports it as an
+        // ordinary syntax error; its message is the only way to tell it apart.
+        Err(err) if err.to_string().contains("nested") => Err(exceeded(format!(
+            "pattern nests deeper than {} levels",
+            limits.max_nesting
+        ))),
+        Err(err) => Ok(Err(err.to_string())),
+    }
+}
+
 /// Expect a string argument
 fn expect_string<'a>(
     value: &'a Value,
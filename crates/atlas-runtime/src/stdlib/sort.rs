@@ -0,0 +1,173 @@
+//! Stable sorting for callback-driven sorts
+//!
+//! `sort` and `sortBy` compare through user code that may fail, so they cannot
+//! use `slice::sort_by`: the comparator has to be fallible, and a comparator
+//! that is not a total order must not panic. Every comparison is a call into
+//! the script, so the algorithm is chosen for few comparisons rather than few
+//! moves.
+//!
+//! [`sort_stable_by`] is a natural merge sort. It splits the input into runs
+//! that are already in order, reversing strictly descending ones, extends
+//! short runs with binary insertion sort, and merges neighbouring runs
+//! bottom-up. Sorted or reversed input takes n - 1 comparisons and any input
+//! takes O(n log n). It sorts a permutation of indices and never recurses.
+//!
+//! Both entry points borrow their input and clone each element once, into the
+//! sorted result, so sorting a shared array never copies it up front.
+//!
+//! Ties keep their input order: two elements the comparator reports as equal
+//! come out in the order they went in.
+
+use std::cmp::Ordering;
+
+/// Runs shorter than this are extended with binary insertion sort
+const MIN_RUN: usize = 32;
+
+/// A sorted copy of `items`, ordered by `cmp`, with equal items in their
+/// original order
+///
+/// `cmp(a, b)` returns the ordering of `a` relative to `b`. The first error
+/// it returns aborts the sort.
+pub fn sort_stable_by<T: Clone, E>(
+    items: &[T],
+    mut cmp: impl FnMut(&T, &T) -> Result<Ordering, E>,
+) -> Result<Vec<T>, E> {
+    let order = merge_sort(items.len(), &mut |a, b| {
+        Ok(cmp(&items[a], &items[b])? == Ordering::Less)
+    })?;
+    Ok(permute(items, order))
+}
+
+/// A sorted copy of `items`, ordered by the key `key` extracts, with items of
+/// equal keys in their original order
+///
+/// Each key is extracted exactly once (decorate-sort-undecorate), so `key`
+/// runs n times however many comparisons the sort makes. The first error it
+/// returns aborts the sort.
+pub fn sort_by_cached_key<T: Clone, K, E>(
+    items: &[T],
+    key: impl FnMut(&T) -> Result<K, E>,
+    mut cmp: impl FnMut(&K, &K) -> Ordering,
+) -> Result<Vec<T>, E> {
+    let keys = items.iter().map(key).collect::<Result<Vec<K>, E>>()?;
+    let order = merge_sort(keys.len(), &mut |a, b| {
+        Ok::<_, E>(cmp(&keys[a], &keys[b]) == Ordering::Less)
+    })?;
+    Ok(permute(items, order))
+}
+
+/// A copy of `items` rearranged so that position `i` holds `items[order[i]]`
+fn permute<T: Clone>(items: &[T], order: Vec<usize>) -> Vec<T> {
+    order
+        .into_iter()
+        .map(|index| items[index].clone())
+        .collect()
+}
+
+/// Stable sort of the indices `0..len` with the strict ordering `less`
+fn merge_sort<E>(
+    len: usize,
+    less: &mut impl FnMut(usize, usize) -> Result<bool, E>,
+) -> Result<Vec<usize>, E> {
+    let mut v: Vec<usize> = (0..len).collect();
+
+    // Split into runs, each sorted, covering `v` in order
+    let mut runs = Vec::new();
+    let mut start = 0;
+    while start < len {
+        let mut end = start + 1;
+        if end < len {
+            // Only strictly descending runs may be reversed without
+            // reordering equal elements
+            let descending = less(v[end], v[start])?;
+            end += 1;
+            while end < len && less(v[end], v[end - 1])? == descending {
+                end += 1;
+            }
+            if descending {
+                v[start..end].reverse();
+            }
+        }
+        let min_end = (start + MIN_RUN).min(len);
+        if end < min_end {
+            binary_insertion_sort(&mut v[start..min_end], end - start, less)?;
+            end = min_end;
+        }
+        runs.push((start, end));
+        start = end;
+    }
+
+    // Merge neighbouring runs pairwise until one is left
+    let mut buf = Vec::new();
+    while runs.len() > 1 {
+        let mut merged = Vec::with_capacity(runs.len().div_ceil(2));
+        for pair in runs.chunks(2) {
+            match *pair {
+                [(start, mid), (_, end)] => {
+                    merge(&mut v[start..end], mid - start, &mut buf, less)?;
+                    merged.push((start, end));
+                }
+                [run] => merged.push(run),
+                _ => unreachable!("chunks(2) yields one or two runs"),
+            }
+        }
+        runs = merged;
+    }
+    Ok(v)
+}
+
+/// Insert `v[sorted..]` one by one into the sorted prefix `v[..sorted]`
+fn binary_insertion_sort<E>(
+    v: &mut [usize],
+    sorted: usize,
+    less: &mut impl FnMut(usize, usize) -> Result<bool, E>,
+) -> Result<(), E> {
+    for i in sorted.max(1)..v.len() {
+        let item = v[i];
+        // Insert after every element not greater than `item`, so it lands
+        // behind its equals
+        let (mut lo, mut hi) = (0, i);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if less(item, v[mid])? {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        v[lo..=i].rotate_right(1);
+    }
+    Ok(())
+}
+
+/// Merge the sorted halves `v[..mid]` and `v[mid..]`
+fn merge<E>(
+    v: &mut [usize],
+    mid: usize,
+    buf: &mut Vec<usize>,
+    less: &mut impl FnMut(usize, usize) -> Result<bool, E>,
+) -> Result<(), E> {
+    // The halves are already in order, as when the input was nearly sorted
+    if !less(v[mid], v[mid - 1])? {
+        return Ok(());
+    }
+
+    buf.clear();
+    buf.extend_from_slice(&v[..mid]);
+    let (mut left, mut right, mut out) = (0, mid, 0);
+    while left < buf.len() && right < v.len() {
+        // Ties take from the left half, which keeps the sort stable
+        if less(v[right], buf[left])? {
+            v[out] = v[right];
+            right += 1;
+        } else {
+            v[out] = buf[left];
+            left += 1;
+        }
+        out += 1;
+    }
+    // Whatever is left of the right half is already in place
+    let rest = &buf[left..];
+    v[out..out + rest.len()].copy_from_slice(rest);
+    Ok(())
+}
@@ -5,12 +5,18 @@
 //! - conn.execute(sql, params) -> Result<number, string>
 //! - conn.query(sql, params) -> Result<Row[], string>
 //! - conn.close() -> Result<null, string>
+//! - conn.begin() / conn.commit() / conn.rollback() -> Result<null, string>
+//! - conn.inTransaction() -> bool
+//!
+//! Opening a database file requires the database capability
+//! (`[security.database]`); in-memory databases are always allowed.
 
 use super::stdlib_arity_error;
 use crate::json_value::JsonValue;
+use crate::security::SecurityContext;
 use crate::span::Span;
 use crate::value::{RuntimeError, Value};
-use rusqlite::{params_from_iter, Connection, ToSql};
+use rusqlite::{params_from_iter, Connection, OpenFlags, ToSql};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
@@ -55,8 +61,11 @@ impl std::fmt::Debug for SqliteConnection {
 ///
 /// Atlas signature: `sqlite.open(path: string) -> Connection`
 /// - ":memory:" creates an in-memory database
-/// - File path creates or opens a file-based database
-pub fn open(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+/// - File path creates or opens a file-based database (requires database permission)
+///
+/// URI filenames (`file:...?mode=...`) are not interpreted, so the permission
+/// check always sees the file that is actually opened.
+pub fn open(args: &[Value], span: Span, security: &SecurityContext) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(stdlib_arity_error("sqlite.open", 1, args.len(), span));
     }
@@ -71,7 +80,17 @@ pub fn open(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
         }
     };
 
-    match Connection::open(&path) {
+    security
+        .check_database(&path)
+        .map_err(|_| RuntimeError::DatabasePermissionDenied {
+            path: path.clone(),
+            span,
+        })?;
+
+    let flags = OpenFlags::SQLITE_OPEN_READ_WRITE
+        | OpenFlags::SQLITE_OPEN_CREATE
+        | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+    match Connection::open_with_flags(&path, flags) {
         Ok(conn) => Ok(Value::SqliteConnection(Arc::new(SqliteConnection::new(
             conn,
         )))),
@@ -315,10 +334,116 @@ pub fn close(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
     Ok(Value::Result(Ok(Box::new(Value::Null))))
 }
 
+/// Start a transaction.
+///
+/// Atlas signature: `conn.begin() -> Result<null, string>`
+/// Statements run until `commit()` or `rollback()` form one atomic unit;
+/// closing the connection with a transaction open rolls it back.
+pub fn begin(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    transaction_statement(args, "begin", "BEGIN", span)
+}
+
+/// Commit the open transaction.
+///
+/// Atlas signature: `conn.commit() -> Result<null, string>`
+pub fn commit(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    transaction_statement(args, "commit", "COMMIT", span)
+}
+
+/// Discard the open transaction.
+///
+/// Atlas signature: `conn.rollback() -> Result<null, string>`
+pub fn rollback(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    transaction_statement(args, "rollback", "ROLLBACK", span)
+}
+
+/// Whether a transaction is open.
+///
+/// Atlas signature: `conn.inTransaction() -> bool`
+/// A closed connection has no open transaction.
+pub fn in_transaction(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error(
+            "conn.inTransaction",
+            1,
+            args.len(),
+            span,
+        ));
+    }
+    let conn = expect_connection(&args[0], "inTransaction", span)?;
+    let open = conn
+        .conn
+        .lock()
+        .ok()
+        .and_then(|guard| guard.as_ref().map(|c| !c.is_autocommit()))
+        .unwrap_or(false);
+    Ok(Value::Bool(open))
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
+/// Run a transaction control statement (`BEGIN`, `COMMIT`, `ROLLBACK`)
+fn transaction_statement(
+    args: &[Value],
+    method: &str,
+    sql: &str,
+    span: Span,
+) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error(
+            &format!("conn.{}", method),
+            1,
+            args.len(),
+            span,
+        ));
+    }
+    let conn = expect_connection(&args[0], method, span)?;
+
+    let guard = match conn.conn.lock() {
+        Ok(g) => g,
+        Err(_) => {
+            return Ok(Value::Result(Err(Box::new(Value::string(format!(
+                "{}: connection lock poisoned",
+                method
+            ))))))
+        }
+    };
+    let Some(connection) = guard.as_ref() else {
+        return Ok(Value::Result(Err(Box::new(Value::string(format!(
+            "{}: connection is closed",
+            method
+        ))))));
+    };
+
+    match connection.execute_batch(sql) {
+        Ok(()) => Ok(Value::Result(Ok(Box::new(Value::Null)))),
+        Err(e) => Ok(Value::Result(Err(Box::new(Value::string(format!(
+            "{}: {}",
+            method, e
+        )))))),
+    }
+}
+
+fn expect_connection<'a>(
+    value: &'a Value,
+    method: &str,
+    span: Span,
+) -> Result<&'a Arc<SqliteConnection>, RuntimeError> {
+    match value {
+        Value::SqliteConnection(c) => Ok(c),
+        other => Err(RuntimeError::TypeError {
+            msg: format!(
+                "{}() requires SqliteConnection, got {}",
+                method,
+                other.type_name()
+            ),
+            span,
+        }),
+    }
+}
+
 /// Convert Atlas Value array to SQL parameter values.
 /// Supports: null, bool, number, string.
 fn value_array_to_sql_params(arr: &[Value]) -> Vec<Box<dyn ToSql>> {
@@ -0,0 +1,634 @@
+//! Terminal output: tables, progress bars, and styled text
+//!
+//! - console.printTable(rows, headers) -> null
+//! - console.progressBar(total) -> ProgressBar
+//! - console.styled(text, style) -> string
+//! - bar.advance(n) / bar.set(n) / bar.setLabel(text) / bar.finish()
+//!
+//! Everything is written through the runtime's [`OutputWriter`], so embedders
+//! that capture output capture these too. Only the process's real stdout
+//! counts as a terminal: there tables use box-drawing characters, progress
+//! bars redraw in place, and styles emit ANSI escapes. Any other writer gets
+//! plain text. Colors are also off when `NO_COLOR` is set or the host chose
+//! [`ColorMode::Never`] with [`set_color_mode`].
+
+use super::{stdlib_arg_error, stdlib_arity_error, stdout_writer, OutputWriter};
+use crate::diagnostic::formatter::ColorMode;
+use crate::span::Span;
+use crate::value::{RuntimeError, Value};
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Width of a progress bar's track, in cells
+const BAR_WIDTH: usize = 30;
+
+static COLOR_MODE: AtomicU8 = AtomicU8::new(0);
+
+/// Choose when styled output uses color. Defaults to [`ColorMode::Auto`].
+pub fn set_color_mode(mode: ColorMode) {
+    let raw = match mode {
+        ColorMode::Auto => 0,
+        ColorMode::Always => 1,
+        ColorMode::Never => 2,
+    };
+    COLOR_MODE.store(raw, Ordering::Relaxed);
+}
+
+fn color_mode() -> ColorMode {
+    match COLOR_MODE.load(Ordering::Relaxed) {
+        1 => ColorMode::Always,
+        2 => ColorMode::Never,
+        _ => ColorMode::Auto,
+    }
+}
+
+/// Whether `output` is the process's stdout and stdout is a terminal
+pub fn is_terminal(output: &OutputWriter) -> bool {
+    Arc::ptr_eq(output, &stdout_writer()) && std::io::stdout().is_terminal()
+}
+
+/// Whether text written to `output` should carry ANSI styles
+pub fn use_color(output: &OutputWriter) -> bool {
+    // https://no-color.org: any non-empty value disables color
+    if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        return false;
+    }
+    match color_mode() {
+        ColorMode::Always => true,
+        ColorMode::Never => false,
+        ColorMode::Auto => is_terminal(output),
+    }
+}
+
+fn write_output(output: &OutputWriter, text: &str, span: Span) -> Result<(), RuntimeError> {
+    let mut w = output.lock().map_err(|_| RuntimeError::TypeError {
+        msg: "output lock poisoned".into(),
+        span,
+    })?;
+    w.write_all(text.as_bytes())
+        .and_then(|_| w.flush())
+        .map_err(|_| RuntimeError::TypeError {
+            msg: "write failed".into(),
+            span,
+        })
+}
+
+// ============================================================================
+// Styles
+// ============================================================================
+
+/// Apply a dot-separated style such as `"bold.red"` or `"underline.bgBlue"`.
+///
+/// The style is validated even when `color` is false, so a typo fails the
+/// same way on a terminal and in CI.
+pub fn style(text: &str, spec: &str, color: bool) -> Result<String, String> {
+    let mut codes = Vec::new();
+    for part in spec.split('.').filter(|p| !p.is_empty()) {
+        codes.push(style_code(part).ok_or_else(|| format!("unknown style '{}'", part))?);
+    }
+    if !color || codes.is_empty() {
+        return Ok(text.to_string());
+    }
+    let codes: Vec<String> = codes.iter().map(u8::to_string).collect();
+    Ok(format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text))
+}
+
+fn style_code(name: &str) -> Option<u8> {
+    let modifier = match name {
+        "bold" => Some(1),
+        "dim" => Some(2),
+        "italic" => Some(3),
+        "underline" => Some(4),
+        "inverse" => Some(7),
+        "strikethrough" => Some(9),
+        _ => None,
+    };
+    if modifier.is_some() {
+        return modifier;
+    }
+    let (base, name) = match name.strip_prefix("bg") {
+        Some(rest) => (40, rest),
+        None => (30, name),
+    };
+    let (bright, name) = match name.strip_prefix("bright") {
+        Some(rest) => (60, rest),
+        None => (0, name),
+    };
+    let offset = match name.to_ascii_lowercase().as_str() {
+        "black" => 0,
+        "red" => 1,
+        "green" => 2,
+        "yellow" => 3,
+        "blue" => 4,
+        "magenta" => 5,
+        "cyan" => 6,
+        "white" => 7,
+        // gray is bright black
+        "gray" | "grey" if bright == 0 => return Some(base + 60),
+        _ => return None,
+    };
+    Some(base + bright + offset)
+}
+
+/// Number of terminal columns `text` occupies, ignoring ANSI escapes
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // CSI sequence: ESC [ params final-byte
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+/// Style text for the output it will be printed to.
+///
+/// Atlas signature: `console.styled(text: string, style: string) -> string`
+pub fn styled(args: &[Value], span: Span, output: &OutputWriter) -> Result<Value, RuntimeError> {
+    if args.len() != 2 {
+        return Err(stdlib_arity_error("console.styled", 2, args.len(), span));
+    }
+    let text = match &args[0] {
+        Value::String(s) => s.as_ref(),
+        other => return Err(stdlib_arg_error("console.styled", "string", other, span)),
+    };
+    let spec = match &args[1] {
+        Value::String(s) => s.as_ref(),
+        other => return Err(stdlib_arg_error("console.styled", "string", other, span)),
+    };
+    style(text, spec, use_color(output))
+        .map(Value::string)
+        .map_err(|msg| RuntimeError::TypeError {
+            msg: format!("console.styled: {}", msg),
+            span,
+        })
+}
+
+// ============================================================================
+// Tables
+// ============================================================================
+
+/// Border characters: corners/junctions per row kind, then the verticals
+struct Borders {
+    top: [&'static str; 3],
+    middle: [&'static str; 3],
+    bottom: [&'static str; 3],
+    horizontal: &'static str,
+    vertical: &'static str,
+}
+
+const BOX_BORDERS: Borders = Borders {
+    top: ["┌", "┬", "┐"],
+    middle: ["├", "┼", "┤"],
+    bottom: ["└", "┴", "┘"],
+    horizontal: "─",
+    vertical: "│",
+};
+
+const ASCII_BORDERS: Borders = Borders {
+    top: ["+", "+", "+"],
+    middle: ["+", "+", "+"],
+    bottom: ["+", "+", "+"],
+    horizontal: "-",
+    vertical: "|",
+};
+
+/// Render a table. Columns where every cell is a number are right-aligned;
+/// styled cells keep their escapes and are aligned by visible width.
+pub fn render_table(
+    headers: &[String],
+    rows: &[Vec<Value>],
+    terminal: bool,
+    color: bool,
+) -> String {
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| row.iter().map(Value::to_display_string).collect())
+        .collect();
+    // Array literals are homogeneous, so numbers often arrive as strings
+    let numeric: Vec<bool> = (0..headers.len())
+        .map(|col| !cells.is_empty() && cells.iter().all(|row| row[col].parse::<f64>().is_ok()))
+        .collect();
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(col, header)| {
+            cells
+                .iter()
+                .map(|row| visible_width(&row[col]))
+                .chain(std::iter::once(visible_width(header)))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let borders = if terminal {
+        &BOX_BORDERS
+    } else {
+        &ASCII_BORDERS
+    };
+    let rule = |[left, mid, right]: [&str; 3]| {
+        let segments: Vec<String> = widths
+            .iter()
+            .map(|w| borders.horizontal.repeat(w + 2))
+            .collect();
+        format!("{}{}{}\n", left, segments.join(mid), right)
+    };
+    let line = |row: &[String], header: bool| {
+        let mut out = String::from(borders.vertical);
+        for (col, cell) in row.iter().enumerate() {
+            let pad = " ".repeat(widths[col] - visible_width(cell));
+            let cell = if header && color {
+                format!("\x1b[1m{}\x1b[0m", cell)
+            } else {
+                cell.clone()
+            };
+            if numeric[col] && !header {
+                out.push_str(&format!(" {}{} {}", pad, cell, borders.vertical));
+            } else {
+                out.push_str(&format!(" {}{} {}", cell, pad, borders.vertical));
+            }
+        }
+        out.push('\n');
+        out
+    };
+
+    let mut out = rule(borders.top);
+    out.push_str(&line(headers, true));
+    out.push_str(&rule(borders.middle));
+    for row in &cells {
+        out.push_str(&line(row, false));
+    }
+    out.push_str(&rule(borders.bottom));
+    out
+}
+
+/// Print rows as an aligned table.
+///
+/// Atlas signature: `console.printTable(rows: any[][], headers: string[]) -> null`
+pub fn print_table(
+    args: &[Value],
+    span: Span,
+    output: &OutputWriter,
+) -> Result<Value, RuntimeError> {
+    if args.len() != 2 {
+        return Err(stdlib_arity_error(
+            "console.printTable",
+            2,
+            args.len(),
+            span,
+        ));
+    }
+    let headers: Vec<String> = match &args[1] {
+        Value::Array(arr) => arr
+            .as_slice()
+            .iter()
+            .map(|h| match h {
+                Value::String(s) => Ok(s.as_ref().clone()),
+                other => Err(stdlib_arg_error(
+                    "console.printTable",
+                    "string[]",
+                    other,
+                    span,
+                )),
+            })
+            .collect::<Result<_, _>>()?,
+        other => {
+            return Err(stdlib_arg_error(
+                "console.printTable",
+                "string[]",
+                other,
+                span,
+            ))
+        }
+    };
+    let rows: Vec<Vec<Value>> = match &args[0] {
+        Value::Array(arr) => arr
+            .as_slice()
+            .iter()
+            .enumerate()
+            .map(|(i, row)| match row {
+                Value::Array(cells) if cells.len() == headers.len() => {
+                    Ok(cells.as_slice().to_vec())
+                }
+                Value::Array(cells) => Err(RuntimeError::TypeError {
+                    msg: format!(
+                        "console.printTable: row {} has {} cells, expected {} (one per header)",
+                        i,
+                        cells.len(),
+                        headers.len()
+                    ),
+                    span,
+                }),
+                other => Err(stdlib_arg_error(
+                    "console.printTable",
+                    "array of rows",
+                    other,
+                    span,
+                )),
+            })
+            .collect::<Result<_, _>>()?,
+        other => {
+            return Err(stdlib_arg_error(
+                "console.printTable",
+                "array of rows",
+                other,
+                span,
+            ))
+        }
+    };
+
+    let table = render_table(&headers, &rows, is_terminal(output), use_color(output));
+    write_output(output, &table, span)?;
+    Ok(Value::Null)
+}
+
+// ============================================================================
+// Progress bars
+// ============================================================================
+
+/// A progress bar created by `console.progressBar`. On a terminal it redraws
+/// one line in place; elsewhere it stays quiet until `finish` prints a
+/// one-line summary, so logs are not flooded with partial frames.
+pub struct ProgressBar {
+    output: OutputWriter,
+    terminal: bool,
+    color: bool,
+    state: Mutex<ProgressState>,
+}
+
+struct ProgressState {
+    total: f64,
+    current: f64,
+    label: String,
+    finished: bool,
+    /// Last frame drawn, to skip redraws that would not change anything
+    last_frame: Option<(usize, u32)>,
+}
+
+impl ProgressBar {
+    fn new(total: f64, output: &OutputWriter) -> Self {
+        ProgressBar {
+            output: Arc::clone(output),
+            terminal: is_terminal(output),
+            color: use_color(output),
+            state: Mutex::new(ProgressState {
+                total,
+                current: 0.0,
+                label: String::new(),
+                finished: false,
+                last_frame: None,
+            }),
+        }
+    }
+
+    pub fn total(&self) -> f64 {
+        self.state.lock().map(|s| s.total).unwrap_or(0.0)
+    }
+
+    pub fn current(&self) -> f64 {
+        self.state.lock().map(|s| s.current).unwrap_or(0.0)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.state.lock().map(|s| s.finished).unwrap_or(true)
+    }
+
+    /// Update the bar and redraw it if anything visible changed
+    fn update(&self, span: Span, f: impl FnOnce(&mut ProgressState)) -> Result<(), RuntimeError> {
+        let mut state = self.state.lock().map_err(|_| RuntimeError::TypeError {
+            msg: "progress bar lock poisoned".into(),
+            span,
+        })?;
+        if state.finished {
+            return Ok(());
+        }
+        f(&mut state);
+        state.current = state.current.clamp(0.0, state.total);
+        if !self.terminal {
+            return Ok(());
+        }
+        let frame = (filled_cells(&state), percent(&state));
+        if state.last_frame == Some(frame) {
+            return Ok(());
+        }
+        state.last_frame = Some(frame);
+        write_output(
+            &self.output,
+            &format!("\r\x1b[2K{}", self.render(&state)),
+            span,
+        )
+    }
+
+    fn finish(&self, span: Span) -> Result<(), RuntimeError> {
+        let mut state = self.state.lock().map_err(|_| RuntimeError::TypeError {
+            msg: "progress bar lock poisoned".into(),
+            span,
+        })?;
+        if state.finished {
+            return Ok(());
+        }
+        state.finished = true;
+        let line = if self.terminal {
+            format!("\r\x1b[2K{}\n", self.render(&state))
+        } else {
+            let label = if state.label.is_empty() {
+                String::new()
+            } else {
+                format!("{} ", state.label)
+            };
+            format!(
+                "{}{}/{} ({}%)\n",
+                label,
+                format_count(state.current),
+                format_count(state.total),
+                percent(&state)
+            )
+        };
+        write_output(&self.output, &line, span)
+    }
+
+    fn render(&self, state: &ProgressState) -> String {
+        let filled = filled_cells(state);
+        let mut track = "█".repeat(filled);
+        if self.color {
+            track = format!("\x1b[32m{}\x1b[0m", track);
+        }
+        track.push_str(&"░".repeat(BAR_WIDTH - filled));
+        let label = if state.label.is_empty() {
+            String::new()
+        } else {
+            format!("{} ", state.label)
+        };
+        format!(
+            "{}{} {}/{} {:>3}%",
+            label,
+            track,
+            format_count(state.current),
+            format_count(state.total),
+            percent(state)
+        )
+    }
+}
+
+impl std::fmt::Debug for ProgressBar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ProgressBar({}/{})",
+            format_count(self.current()),
+            format_count(self.total())
+        )
+    }
+}
+
+fn fraction(state: &ProgressState) -> f64 {
+    if state.total > 0.0 {
+        state.current / state.total
+    } else {
+        1.0
+    }
+}
+
+fn filled_cells(state: &ProgressState) -> usize {
+    (fraction(state) * BAR_WIDTH as f64).floor() as usize
+}
+
+fn percent(state: &ProgressState) -> u32 {
+    (fraction(state) * 100.0).floor() as u32
+}
+
+fn format_count(n: f64) -> String {
+    Value::Number(n).to_display_string()
+}
+
+/// Create a progress bar counting up to `total`.
+///
+/// Atlas signature: `console.progressBar(total: number) -> ProgressBar`
+pub fn progress_bar(
+    args: &[Value],
+    span: Span,
+    output: &OutputWriter,
+) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error(
+            "console.progressBar",
+            1,
+            args.len(),
+            span,
+        ));
+    }
+    match &args[0] {
+        Value::Number(total) if total.is_finite() && *total >= 0.0 => Ok(Value::ProgressBar(
+            Arc::new(ProgressBar::new(*total, output)),
+        )),
+        Value::Number(total) => Err(RuntimeError::TypeError {
+            msg: format!(
+                "console.progressBar: total must be a non-negative number, got {}",
+                total
+            ),
+            span,
+        }),
+        other => Err(stdlib_arg_error(
+            "console.progressBar",
+            "number",
+            other,
+            span,
+        )),
+    }
+}
+
+/// Move the bar forward by `n`.
+///
+/// Atlas signature: `bar.advance(n: number) -> null`
+pub fn bar_advance(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    let (bar, n) = bar_and_number(args, "advance", span)?;
+    bar.update(span, |state| state.current += n)?;
+    Ok(Value::Null)
+}
+
+/// Set the bar's position.
+///
+/// Atlas signature: `bar.set(n: number) -> null`
+pub fn bar_set(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    let (bar, n) = bar_and_number(args, "set", span)?;
+    bar.update(span, |state| state.current = n)?;
+    Ok(Value::Null)
+}
+
+/// Set the text shown before the bar.
+///
+/// Atlas signature: `bar.setLabel(text: string) -> null`
+pub fn bar_set_label(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 2 {
+        return Err(stdlib_arity_error("bar.setLabel", 2, args.len(), span));
+    }
+    let bar = expect_bar(&args[0], "setLabel", span)?;
+    let label = match &args[1] {
+        Value::String(s) => s.as_ref().clone(),
+        other => return Err(stdlib_arg_error("bar.setLabel", "string", other, span)),
+    };
+    bar.update(span, |state| {
+        state.label = label;
+        state.last_frame = None;
+    })?;
+    Ok(Value::Null)
+}
+
+/// Draw the final state and end the line. Later updates are ignored.
+///
+/// Atlas signature: `bar.finish() -> null`
+pub fn bar_finish(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(stdlib_arity_error("bar.finish", 1, args.len(), span));
+    }
+    expect_bar(&args[0], "finish", span)?.finish(span)?;
+    Ok(Value::Null)
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn expect_bar<'a>(
+    value: &'a Value,
+    method: &str,
+    span: Span,
+) -> Result<&'a Arc<ProgressBar>, RuntimeError> {
+    match value {
+        Value::ProgressBar(bar) => Ok(bar),
+        other => Err(RuntimeError::TypeError {
+            msg: format!(
+                "{}() requires ProgressBar, got {}",
+                method,
+                other.type_name()
+            ),
+            span,
+        }),
+    }
+}
+
+fn bar_and_number<'a>(
+    args: &'a [Value],
+    method: &str,
+    span: Span,
+) -> Result<(&'a Arc<ProgressBar>, f64), RuntimeError> {
+    let name = format!("bar.{}", method);
+    if args.len() != 2 {
+        return Err(stdlib_arity_error(&name, 2, args.len(), span));
+    }
+    let bar = expect_bar(&args[0], method, span)?;
+    match &args[1] {
+        Value::Number(n) if n.is_finite() => Ok((bar, *n)),
+        other => Err(stdlib_arg_error(&name, "finite number", other, span)),
+    }
+}
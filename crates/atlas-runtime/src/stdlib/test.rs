@@ -30,8 +30,9 @@
 //! - `assertThrows(fn)` — assert callable throws (returns Err)
 //! - `assertNoThrow(fn)` — assert callable does not throw
 
+use crate::pretty::PrettyConfig;
 use crate::span::Span;
-use crate::value::{RuntimeError, Value};
+use crate::value::{CycleGuard, RuntimeError, Value};
 
 // ============================================================================
 // Internal helpers
@@ -78,9 +79,24 @@ fn check_arity(
 /// Deep equality for Atlas values.
 ///
 /// Arrays are compared element-by-element (not by pointer identity), which is
-/// the semantics users expect in test assertions.
-fn values_deep_equal(a: &Value, b: &Value) -> bool {
-    match (a, b) {
+/// the semantics users expect in test assertions. Distinct `shared` cells are
+/// compared by contents; a cell that contains itself is a `CircularReference`.
+fn values_deep_equal(a: &Value, b: &Value, span: Span) -> Result<bool, RuntimeError> {
+    deep_equal_walk(
+        a,
+        b,
+        &mut [CycleGuard::default(), CycleGuard::default()],
+        span,
+    )
+}
+
+fn deep_equal_walk(
+    a: &Value,
+    b: &Value,
+    seen: &mut [CycleGuard; 2],
+    span: Span,
+) -> Result<bool, RuntimeError> {
+    Ok(match (a, b) {
         (Value::Number(x), Value::Number(y)) => x == y,
         (Value::String(x), Value::String(y)) => x == y,
         (Value::Bool(x), Value::Bool(y)) => x == y,
@@ -89,43 +105,59 @@ fn values_deep_equal(a: &Value, b: &Value) -> bool {
             let xs = x.as_slice();
             let ys = y.as_slice();
             if xs.len() != ys.len() {
-                return false;
+                return Ok(false);
+            }
+            for (a, b) in xs.iter().zip(ys.iter()) {
+                if !deep_equal_walk(a, b, seen, span)? {
+                    return Ok(false);
+                }
             }
-            xs.iter()
-                .zip(ys.iter())
-                .all(|(a, b)| values_deep_equal(a, b))
+            true
         }
         (Value::Option(x), Value::Option(y)) => match (x, y) {
             (None, None) => true,
-            (Some(a), Some(b)) => values_deep_equal(a, b),
+            (Some(a), Some(b)) => deep_equal_walk(a, b, seen, span)?,
             _ => false,
         },
         (Value::Result(x), Value::Result(y)) => match (x, y) {
-            (Ok(a), Ok(b)) => values_deep_equal(a, b),
-            (Err(a), Err(b)) => values_deep_equal(a, b),
+            (Ok(a), Ok(b)) => deep_equal_walk(a, b, seen, span)?,
+            (Err(a), Err(b)) => deep_equal_walk(a, b, seen, span)?,
             _ => false,
         },
+        (Value::SharedValue(x), Value::SharedValue(y)) => {
+            if x == y {
+                return Ok(true);
+            }
+            if !seen[0].enter(x) || !seen[1].enter(y) {
+                return Err(RuntimeError::CircularReference {
+                    msg: "shared value contains itself; cannot compare".to_string(),
+                    span,
+                });
+            }
+            // Compare copies so no cell stays locked while its contents are walked
+            let (xv, yv) = (x.with(|v| (**v).clone()), y.with(|v| (**v).clone()));
+            let equal = deep_equal_walk(&xv, &yv, seen, span);
+            seen[0].exit(x);
+            seen[1].exit(y);
+            equal?
+        }
         _ => false,
+    })
+}
+
+/// Whether `haystack` has an element deep-equal to `needle`
+fn contains_deep(haystack: &[Value], needle: &Value, span: Span) -> Result<bool, RuntimeError> {
+    for v in haystack {
+        if values_deep_equal(v, needle, span)? {
+            return Ok(true);
+        }
     }
+    Ok(false)
 }
 
 /// Render a Value for display in assertion failure messages.
 fn display(v: &Value) -> String {
-    match v {
-        Value::Array(arr) => {
-            let items: Vec<String> = arr.as_slice().iter().map(display).collect();
-            format!("[{}]", items.join(", "))
-        }
-        Value::Option(opt) => match opt {
-            Some(inner) => format!("Some({})", display(inner)),
-            None => "None".to_string(),
-        },
-        Value::Result(res) => match res {
-            Ok(inner) => format!("Ok({})", display(inner)),
-            Err(inner) => format!("Err({})", display(inner)),
-        },
-        other => other.to_string(),
-    }
+    v.pretty(&PrettyConfig::default())
 }
 
 // ============================================================================
@@ -193,7 +225,7 @@ pub fn assert_equal(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
     let actual = &args[0];
     let expected = &args[1];
 
-    if !values_deep_equal(actual, expected) {
+    if !values_deep_equal(actual, expected, span)? {
         return Err(assertion_error(
             format!(
                 "Assertion failed: values not equal\n  Actual:   {}\n  Expected: {}",
@@ -215,7 +247,7 @@ pub fn assert_not_equal(args: &[Value], span: Span) -> Result<Value, RuntimeErro
     let actual = &args[0];
     let expected = &args[1];
 
-    if values_deep_equal(actual, expected) {
+    if values_deep_equal(actual, expected, span)? {
         return Err(assertion_error(
             format!(
                 "Assertion failed: values are equal (expected them to differ)\n  Value: {}",
@@ -320,7 +352,7 @@ pub fn assert_contains(args: &[Value], span: Span) -> Result<Value, RuntimeError
     };
     let needle = &args[1];
 
-    let found = arr.as_slice().iter().any(|v| values_deep_equal(v, needle));
+    let found = contains_deep(arr.as_slice(), needle, span)?;
 
     if !found {
         return Err(assertion_error(
@@ -539,7 +571,7 @@ pub fn test_ns_equal(args: &[Value], span: Span) -> Result<Value, RuntimeError>
     }
     let actual = &args[0];
     let expected = &args[1];
-    if !values_deep_equal(actual, expected) {
+    if !values_deep_equal(actual, expected, span)? {
         let suffix = if args.len() == 3 {
             match &args[2] {
                 Value::String(s) => format!(" — {}", s.as_ref()),
@@ -573,7 +605,7 @@ pub fn test_ns_not_equal(args: &[Value], span: Span) -> Result<Value, RuntimeErr
     }
     let actual = &args[0];
     let expected = &args[1];
-    if values_deep_equal(actual, expected) {
+    if values_deep_equal(actual, expected, span)? {
         let suffix = if args.len() == 3 {
             match &args[2] {
                 Value::String(s) => format!(" — {}", s.as_ref()),
@@ -709,7 +741,7 @@ pub fn test_ns_contains(args: &[Value], span: Span) -> Result<Value, RuntimeErro
         other => return Err(type_error("array", other.type_name(), span)),
     };
     let needle = &args[1];
-    let found = arr.as_slice().iter().any(|v| values_deep_equal(v, needle));
+    let found = contains_deep(arr.as_slice(), needle, span)?;
     if !found {
         return Err(assertion_error(
             format!(
@@ -3,9 +3,10 @@
 
 use super::stdlib_arity_error;
 use crate::json_value::JsonValue;
+use crate::pretty::PrettyConfig;
 use crate::span::Span;
 use crate::stdlib::collections::hash::HashKey;
-use crate::value::{RuntimeError, Value};
+use crate::value::{format_number, CycleGuard, RuntimeError, Value};
 
 // ============================================================================
 // Option<T> Functions
@@ -240,7 +241,7 @@ fn type_of_impl(args: &[Value], span: Span, name: &str) -> Result<Value, Runtime
         }
     }
 
-    fn value_type_name(value: &Value) -> &'static str {
+    fn value_type_name(value: &Value, seen: &mut CycleGuard) -> &'static str {
         match value {
             Value::Null => "null",
             Value::Bool(_) => "boolean",
@@ -259,27 +260,44 @@ fn type_of_impl(args: &[Value], span: Span, name: &str) -> Result<Value, Runtime
             Value::Stack(_) => "record",
             Value::Range { .. } => "range",
             Value::Result(_) => "record",
+            #[cfg(feature = "regex")]
             Value::Regex(_) => "record",
             Value::Future(_) => "Future",
             Value::DateTime(_) => "record",
+            Value::Bytes(_) => "bytes",
+            #[cfg(feature = "http")]
             Value::HttpRequest(_) => "record",
+            #[cfg(feature = "http")]
             Value::HttpResponse(_) => "record",
             #[cfg(feature = "http")]
             Value::HttpServerRequest(_) => "record",
             Value::ProcessOutput(_) => "record",
             Value::SqliteConnection(_) => "record",
+            Value::KvStore(_) => "record",
+            Value::FileHandle(_) => "record",
+            Value::ProgressBar(_) => "record",
             Value::TaskHandle(_) => "record",
             Value::ChannelSender(_) => "record",
             Value::ChannelReceiver(_) => "record",
             Value::AsyncMutex(_) => "record",
+            #[cfg(feature = "fs")]
             Value::Watcher(_) => "record",
+            Value::Resource(_) => "resource",
             Value::Tuple(_) => "tuple",
-            Value::SharedValue(shared) => shared.with(|inner| value_type_name(inner)),
+            // typeof sees through `shared`; a cell that only wraps itself is "shared"
+            Value::SharedValue(shared) => {
+                if !seen.enter(shared) {
+                    return "shared";
+                }
+                let name = shared.with(|inner| value_type_name(inner, seen));
+                seen.exit(shared);
+                name
+            }
             Value::EnumValue { .. } => "record",
         }
     }
 
-    let type_name = value_type_name(&args[0]);
+    let type_name = value_type_name(&args[0], &mut CycleGuard::default());
 
     Ok(Value::string(type_name))
 }
@@ -496,11 +514,10 @@ pub fn has_tag(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
 /// - bool → "true" or "false"
 /// - number → string representation (e.g., "42", "3.14")
 /// - string → same string (identity)
-/// - array → "[Array]"
+/// - array, map, set, tuple, option, result → compact `inspect` form,
+///   e.g. `[1, "a"]`, `{"k": 1}`, `Some(2)`
 /// - function → "[Function]"
-/// - json → JSON string representation
-/// - option → "Some(value)" or "None"
-/// - result → "Ok(value)" or "Err(error)"
+/// - json → "[JSON]"
 pub fn to_string(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(stdlib_arity_error("toString", 1, args.len(), span));
@@ -509,47 +526,51 @@ pub fn to_string(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
     let string_value = match &args[0] {
         Value::Null => "null".to_string(),
         Value::Bool(b) => b.to_string(),
-        Value::Number(n) => {
-            // Format number (remove unnecessary decimals)
-            if n.is_nan() {
-                "NaN".to_string()
-            } else if n.is_infinite() {
-                if *n > 0.0 {
-                    "Infinity".to_string()
-                } else {
-                    "-Infinity".to_string()
-                }
-            } else if n.fract() == 0.0 && n.abs() < 1e15 {
-                format!("{:.0}", n)
-            } else {
-                n.to_string()
-            }
-        }
+        Value::Number(n) => format_number(*n),
         Value::String(s) => s.as_ref().clone(),
-        Value::Array(_) => "[Array]".to_string(),
+        Value::Array(_)
+        | Value::Map(_)
+        | Value::Set(_)
+        | Value::Queue(_)
+        | Value::Stack(_)
+        | Value::Tuple(_)
+        | Value::Option(Some(_))
+        | Value::Result(_)
+        | Value::SharedValue(_)
+        | Value::EnumValue { .. } => args[0].pretty(&PrettyConfig::compact()),
         Value::Function(_) => "[Function]".to_string(),
         Value::Builtin(name) => format!("[Builtin {}]", name),
         Value::NativeFunction(_) => "[Native Function]".to_string(),
         Value::Closure(c) => format!("[Closure {}]", c.func.name),
         Value::JsonValue(_) => "[JSON]".to_string(),
-        Value::Option(Some(v)) => format!("Some({})", value_to_display_string(v)),
         Value::Option(None) => "None".to_string(),
-        Value::Result(Ok(v)) => format!("Ok({})", value_to_display_string(v)),
-        Value::Result(Err(e)) => format!("Err({})", value_to_display_string(e)),
-        Value::Map(_) => "[Map]".to_string(),
-        Value::Set(_) => "[Set]".to_string(),
-        Value::Queue(_) => "[Queue]".to_string(),
-        Value::Stack(_) => "[Stack]".to_string(),
         Value::Range { .. } => value_to_display_string(&args[0]),
+        #[cfg(feature = "regex")]
         Value::Regex(r) => format!("[Regex /{}/ ]", r.as_str()),
         Value::DateTime(dt) => dt.to_rfc3339(),
+        Value::Bytes(_) => args[0].to_string(),
+        #[cfg(feature = "http")]
         Value::HttpRequest(req) => format!("<HttpRequest {} {}>", req.method(), req.url()),
+        #[cfg(feature = "http")]
         Value::HttpResponse(res) => format!("<HttpResponse {}>", res.status()),
         Value::ProcessOutput(out) => format!("<ProcessOutput exit={}>", out.exit_code),
         Value::SqliteConnection(c) => {
             let status = if c.is_closed() { "closed" } else { "open" };
             format!("<SqliteConnection {}>", status)
         }
+        Value::KvStore(k) => {
+            let status = if k.is_closed() { "closed" } else { "open" };
+            format!("<KvStore {}>", status)
+        }
+        Value::FileHandle(h) => {
+            let status = if h.is_closed() { "closed" } else { "open" };
+            format!("<FileHandle {} {}>", h.path(), status)
+        }
+        Value::ProgressBar(b) => format!(
+            "<ProgressBar {}/{}>",
+            Value::Number(b.current()),
+            Value::Number(b.total())
+        ),
         Value::Future(f) => f.to_string(),
         Value::TaskHandle(h) => {
             let id = h.lock().map(|h| h.id()).unwrap_or(0);
@@ -558,24 +579,9 @@ pub fn to_string(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
         Value::ChannelSender(_) => "[ChannelSender]".to_string(),
         Value::ChannelReceiver(_) => "[ChannelReceiver]".to_string(),
         Value::AsyncMutex(_) => "[AsyncMutex]".to_string(),
+        #[cfg(feature = "fs")]
         Value::Watcher(_) => "[Watcher]".to_string(),
-        Value::SharedValue(_) => "[Shared]".to_string(),
-        Value::Tuple(elems) => {
-            let parts: Vec<String> = elems.iter().map(|v| v.to_string()).collect();
-            format!("({})", parts.join(", "))
-        }
-        Value::EnumValue {
-            enum_name,
-            variant_name,
-            data,
-        } => {
-            if data.is_empty() {
-                format!("{}::{}", enum_name, variant_name)
-            } else {
-                let args: Vec<String> = data.iter().map(|v| v.to_string()).collect();
-                format!("{}::{}({})", enum_name, variant_name, args.join(", "))
-            }
-        }
+        Value::Resource(r) => r.to_string(),
         #[cfg(feature = "http")]
         Value::HttpServerRequest(req) => {
             format!("<HttpServerRequest {} {}>", req.method, req.path)
@@ -585,6 +591,51 @@ pub fn to_string(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
     Ok(Value::string(string_value))
 }
 
+/// inspect(value, options?) — readable, truncated rendering of any value
+///
+/// Options map keys (all optional): `depth`, `maxItems`, `maxString`,
+/// `width`. Unlike `toString`, strings are quoted and long collections
+/// are broken over several lines.
+pub fn inspect(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(stdlib_arity_error("inspect", 1, args.len(), span));
+    }
+    let mut config = PrettyConfig::default();
+    if let Some(options) = args.get(1) {
+        let Value::Map(map) = options else {
+            return Err(RuntimeError::TypeError {
+                msg: format!(
+                    "inspect: options must be a map, got {}",
+                    options.type_name()
+                ),
+                span,
+            });
+        };
+        for (key, field) in [
+            ("depth", &mut config.max_depth),
+            ("maxItems", &mut config.max_items),
+            ("maxString", &mut config.max_string),
+            ("width", &mut config.width),
+        ] {
+            match map.get(&HashKey::String(std::sync::Arc::new(key.to_string()))) {
+                None => {}
+                Some(Value::Number(n)) if *n >= 0.0 && n.fract() == 0.0 => *field = *n as usize,
+                Some(other) => {
+                    return Err(RuntimeError::TypeError {
+                        msg: format!(
+                            "inspect: option '{}' must be a non-negative integer, got {}",
+                            key,
+                            other.type_name()
+                        ),
+                        span,
+                    })
+                }
+            }
+        }
+    }
+    Ok(Value::string(args[0].pretty(&config)))
+}
+
 /// Convert value to number
 ///
 /// Conversion rules:
@@ -657,23 +708,29 @@ pub fn to_bool(args: &[Value], span: Span) -> Result<Value, RuntimeError> {
         | Value::Queue(_)
         | Value::Stack(_)
         | Value::Range { .. }
-        | Value::Regex(_)
         | Value::DateTime(_)
-        | Value::HttpRequest(_)
-        | Value::HttpResponse(_)
-        | Value::HttpServerRequest(_)
+        | Value::Bytes(_)
         | Value::ProcessOutput(_)
         | Value::SqliteConnection(_)
+        | Value::KvStore(_)
+        | Value::FileHandle(_)
+        | Value::ProgressBar(_)
         | Value::Future(_)
         | Value::TaskHandle(_)
         | Value::ChannelSender(_)
         | Value::ChannelReceiver(_)
         | Value::AsyncMutex(_)
-        | Value::Watcher(_)
+        | Value::Resource(_)
         | Value::Closure(_)
         | Value::Tuple(_)
         | Value::SharedValue(_)
         | Value::EnumValue { .. } => true,
+        #[cfg(feature = "regex")]
+        Value::Regex(_) => true,
+        #[cfg(feature = "http")]
+        Value::HttpRequest(_) | Value::HttpResponse(_) | Value::HttpServerRequest(_) => true,
+        #[cfg(feature = "fs")]
+        Value::Watcher(_) => true,
     };
 
     Ok(Value::Bool(bool_value))
@@ -814,20 +871,29 @@ fn type_name(value: &Value) -> &str {
         Value::Queue(_) => "queue",
         Value::Stack(_) => "stack",
         Value::Range { .. } => "range",
+        #[cfg(feature = "regex")]
         Value::Regex(_) => "regex",
         Value::DateTime(_) => "datetime",
+        Value::Bytes(_) => "bytes",
+        #[cfg(feature = "http")]
         Value::HttpRequest(_) => "HttpRequest",
+        #[cfg(feature = "http")]
         Value::HttpResponse(_) => "HttpResponse",
         #[cfg(feature = "http")]
         Value::HttpServerRequest(_) => "HttpServerRequest",
         Value::ProcessOutput(_) => "ProcessOutput",
         Value::SqliteConnection(_) => "SqliteConnection",
+        Value::KvStore(_) => "KvStore",
+        Value::FileHandle(_) => "FileHandle",
+        Value::ProgressBar(_) => "ProgressBar",
         Value::Future(_) => "Future",
         Value::TaskHandle(_) => "TaskHandle",
         Value::ChannelSender(_) => "ChannelSender",
         Value::ChannelReceiver(_) => "ChannelReceiver",
         Value::AsyncMutex(_) => "AsyncMutex",
+        #[cfg(feature = "fs")]
         Value::Watcher(_) => "Watcher",
+        Value::Resource(_) => "Resource",
         Value::Closure(_) => "closure",
         Value::Tuple(_) => "tuple",
         Value::SharedValue(_) => "shared",
@@ -840,13 +906,7 @@ fn value_to_display_string(value: &Value) -> String {
     match value {
         Value::Null => "null".to_string(),
         Value::Bool(b) => b.to_string(),
-        Value::Number(n) => {
-            if n.fract() == 0.0 && n.abs() < 1e15 {
-                format!("{:.0}", n)
-            } else {
-                n.to_string()
-            }
-        }
+        Value::Number(n) => format_number(*n),
         Value::String(s) => format!("\"{}\"", s),
         Value::Array(_) => "[Array]".to_string(),
         Value::Function(_) => "[Function]".to_string(),
@@ -861,9 +921,13 @@ fn value_to_display_string(value: &Value) -> String {
         Value::Queue(_) => "[Queue]".to_string(),
         Value::Stack(_) => "[Stack]".to_string(),
         Value::Range { .. } => "[Range]".to_string(),
+        #[cfg(feature = "regex")]
         Value::Regex(r) => format!("[Regex /{}/ ]", r.as_str()),
         Value::DateTime(dt) => format!("[DateTime {}]", dt.to_rfc3339()),
+        Value::Bytes(b) => format!("[Bytes len={}]", b.len()),
+        #[cfg(feature = "http")]
         Value::HttpRequest(req) => format!("[HttpRequest {} {}]", req.method(), req.url()),
+        #[cfg(feature = "http")]
         Value::HttpResponse(res) => format!("[HttpResponse {}]", res.status()),
         #[cfg(feature = "http")]
         Value::HttpServerRequest(req) => {
@@ -874,6 +938,19 @@ fn value_to_display_string(value: &Value) -> String {
             let status = if c.is_closed() { "closed" } else { "open" };
             format!("[SqliteConnection {}]", status)
         }
+        Value::KvStore(k) => {
+            let status = if k.is_closed() { "closed" } else { "open" };
+            format!("[KvStore {}]", status)
+        }
+        Value::FileHandle(h) => {
+            let status = if h.is_closed() { "closed" } else { "open" };
+            format!("[FileHandle {} {}]", h.path(), status)
+        }
+        Value::ProgressBar(b) => format!(
+            "[ProgressBar {}/{}]",
+            Value::Number(b.current()),
+            Value::Number(b.total())
+        ),
         Value::Future(f) => format!("[{}]", f.as_ref()),
         Value::TaskHandle(h) => {
             let id = h.lock().map(|h| h.id()).unwrap_or(0);
@@ -882,7 +959,9 @@ fn value_to_display_string(value: &Value) -> String {
         Value::ChannelSender(_) => "[ChannelSender]".to_string(),
         Value::ChannelReceiver(_) => "[ChannelReceiver]".to_string(),
         Value::AsyncMutex(_) => "[AsyncMutex]".to_string(),
+        #[cfg(feature = "fs")]
         Value::Watcher(_) => "[Watcher]".to_string(),
+        Value::Resource(r) => format!("[Resource {}]", r.kind()),
         Value::Tuple(elems) => {
             let parts: Vec<String> = elems.iter().map(value_to_display_string).collect();
             format!("({})", parts.join(", "))
@@ -913,7 +992,7 @@ pub fn number_to_string(args: &[Value], span: Span) -> Result<Value, RuntimeErro
         return Err(stdlib_arity_error("toString", 1, args.len(), span));
     }
     match &args[0] {
-        Value::Number(n) => Ok(Value::string(fmt_number(*n))),
+        Value::Number(n) => Ok(Value::string(format_number(*n))),
         other => Err(RuntimeError::TypeError {
             msg: format!("toString: expected number, got {}", other.type_name()),
             span,
@@ -1017,20 +1096,3 @@ pub fn number_to_number(args: &[Value], span: Span) -> Result<Value, RuntimeErro
         }),
     }
 }
-
-/// Shared number formatting used by number_to_string and str().
-fn fmt_number(n: f64) -> String {
-    if n.is_nan() {
-        "NaN".to_string()
-    } else if n.is_infinite() {
-        if n > 0.0 {
-            "Infinity".to_string()
-        } else {
-            "-Infinity".to_string()
-        }
-    } else if n.fract() == 0.0 && n.abs() < 1e15 {
-        format!("{:.0}", n)
-    } else {
-        n.to_string()
-    }
-}
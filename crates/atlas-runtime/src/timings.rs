@@ -0,0 +1,151 @@
+//! Per-phase timing of the compile pipeline
+//!
+//! [`Atlas`](crate::Atlas) records how long each phase took on its most
+//! recent run — summed over every module of the program — and exposes it
+//! through [`Atlas::timings`](crate::Atlas::timings). `atlas run --timings`
+//! prints the same numbers as a table or, with `--json`, as JSON for
+//! tracking toolchain performance over time.
+
+use std::fmt::Write as _;
+use std::time::{Duration, Instant};
+
+/// A phase of the pipeline, in the order they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Lex,
+    Parse,
+    Bind,
+    Typecheck,
+    Compile,
+    Optimize,
+    Execute,
+}
+
+impl Phase {
+    /// Every phase, in pipeline order
+    pub const ALL: [Phase; 7] = [
+        Phase::Lex,
+        Phase::Parse,
+        Phase::Bind,
+        Phase::Typecheck,
+        Phase::Compile,
+        Phase::Optimize,
+        Phase::Execute,
+    ];
+
+    /// Lowercase name used in reports
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Lex => "lex",
+            Phase::Parse => "parse",
+            Phase::Bind => "bind",
+            Phase::Typecheck => "typecheck",
+            Phase::Compile => "compile",
+            Phase::Optimize => "optimize",
+            Phase::Execute => "execute",
+        }
+    }
+}
+
+/// Time spent in each phase
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PhaseTimings {
+    durations: [Duration; Phase::ALL.len()],
+    /// Number of modules that went through the front end
+    pub modules: usize,
+}
+
+impl PhaseTimings {
+    /// Empty timings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `elapsed` to `phase`
+    pub fn add(&mut self, phase: Phase, elapsed: Duration) {
+        self.durations[phase as usize] += elapsed;
+    }
+
+    /// Run `f`, charging its wall-clock time to `phase`
+    pub fn time<T>(&mut self, phase: Phase, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.add(phase, start.elapsed());
+        result
+    }
+
+    /// Fold another set of timings into this one
+    pub fn merge(&mut self, other: &PhaseTimings) {
+        for phase in Phase::ALL {
+            self.add(phase, other.get(phase));
+        }
+        self.modules += other.modules;
+    }
+
+    /// Overwrite the time recorded for `phase`
+    pub fn set(&mut self, phase: Phase, elapsed: Duration) {
+        self.durations[phase as usize] = elapsed;
+    }
+
+    /// Time spent in `phase`
+    pub fn get(&self, phase: Phase) -> Duration {
+        self.durations[phase as usize]
+    }
+
+    /// Time spent in all phases together
+    pub fn total(&self) -> Duration {
+        self.durations.iter().sum()
+    }
+
+    /// Human-readable table, one row per phase
+    pub fn to_table(&self) -> String {
+        let total = self.total();
+        let mut out = format!("{:<10} {:>12} {:>7}\n", "phase", "time", "share");
+        for phase in Phase::ALL {
+            let elapsed = self.get(phase);
+            let share = if total.is_zero() {
+                0.0
+            } else {
+                100.0 * elapsed.as_secs_f64() / total.as_secs_f64()
+            };
+            let _ = writeln!(
+                out,
+                "{:<10} {:>10.3}ms {:>6.1}%",
+                phase.name(),
+                millis(elapsed),
+                share
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{:<10} {:>10.3}ms  ({} module{})",
+            "total",
+            millis(total),
+            self.modules,
+            if self.modules == 1 { "" } else { "s" }
+        );
+        out
+    }
+
+    /// JSON report: `{"modules", "total_ms", "phases": [{"phase", "ms"}]}`
+    pub fn to_json(&self) -> serde_json::Value {
+        let phases: Vec<serde_json::Value> = Phase::ALL
+            .iter()
+            .map(|&phase| {
+                serde_json::json!({
+                    "phase": phase.name(),
+                    "ms": millis(self.get(phase)),
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "modules": self.modules,
+            "total_ms": millis(self.total()),
+            "phases": phases,
+        })
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
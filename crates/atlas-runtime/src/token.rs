@@ -37,6 +37,8 @@ pub enum TokenKind {
     String,
     /// Template string literal segment (`hello ${name}`)
     TemplateString,
+    /// Regex literal (`/\d+/i`); the lexeme is the literal as written
+    Regex,
     /// `true` keyword
     True,
     /// `false` keyword
@@ -138,6 +140,10 @@ pub enum TokenKind {
     /// `defer` keyword — deferred execution (LIFO on function exit)
     Defer,
 
+    // Compile-time code generation
+    /// `macro` keyword — token-template macro declaration
+    Macro,
+
     // Constructor syntax (H-374)
     /// `new` keyword — collection/object constructor: `new Map<K,V>()`
     New,
@@ -175,8 +181,12 @@ pub enum TokenKind {
     Ampersand,
     /// `@` (attribute prefix)
     At,
+    /// `$` (macro parameter or directive, only inside `macro` bodies)
+    Dollar,
     /// `|` (type union)
     Pipe,
+    /// `|>` (pipeline)
+    PipeGreater,
 
     // Compound assignment operators
     /// `+=` (add and assign)
@@ -229,6 +239,10 @@ pub enum TokenKind {
     Underscore,
     /// `?` (error propagation operator)
     Question,
+    /// `?.` (null-safe member access)
+    QuestionDot,
+    /// `??` (null-coalescing operator)
+    QuestionQuestion,
     /// Start of string interpolation
     InterpolationStart,
     /// `}` end of string interpolation (only in interpolated strings)
@@ -292,6 +306,7 @@ impl TokenKind {
             "static" => Some(TokenKind::Static),
             "const" => Some(TokenKind::Const),
             "defer" => Some(TokenKind::Defer),
+            "macro" => Some(TokenKind::Macro),
             "new" => Some(TokenKind::New),
             _ => None,
         }
@@ -303,6 +318,7 @@ impl TokenKind {
             TokenKind::Number => "number",
             TokenKind::String => "string",
             TokenKind::TemplateString => "template string",
+            TokenKind::Regex => "regex literal",
             TokenKind::True => "true",
             TokenKind::False => "false",
             TokenKind::Null => "null",
@@ -343,6 +359,7 @@ impl TokenKind {
             TokenKind::Static => "static",
             TokenKind::Const => "const",
             TokenKind::Defer => "defer",
+            TokenKind::Macro => "macro",
             TokenKind::Plus => "+",
             TokenKind::Minus => "-",
             TokenKind::Star => "*",
@@ -359,7 +376,9 @@ impl TokenKind {
             TokenKind::PipePipe => "||",
             TokenKind::Ampersand => "&",
             TokenKind::At => "@",
+            TokenKind::Dollar => "$",
             TokenKind::Pipe => "|",
+            TokenKind::PipeGreater => "|>",
             TokenKind::PlusEqual => "+=",
             TokenKind::MinusEqual => "-=",
             TokenKind::StarEqual => "*=",
@@ -384,6 +403,8 @@ impl TokenKind {
             TokenKind::FatArrow => "=>",
             TokenKind::Underscore => "_",
             TokenKind::Question => "?",
+            TokenKind::QuestionDot => "?.",
+            TokenKind::QuestionQuestion => "??",
             TokenKind::InterpolationStart => "${",
             TokenKind::InterpolationEnd => "}",
             TokenKind::LineComment => "// comment",
@@ -0,0 +1,292 @@
+//! Match exhaustiveness over constructor payloads.
+//!
+//! `check_exhaustiveness` knows the constructors of the scrutinee's type; this
+//! module decides whether the arms also cover every payload of each one, so
+//! `Shape::Pair(true, _)` on its own does not cover `Shape::Pair`.
+//!
+//! Nested columns are typed by the patterns written in them: a column holding
+//! `Some`/`None` is an Option, `true`/`false` a bool, `Color::Red` a Color, and
+//! so on. A column with no constructor patterns (numbers, strings, bindings)
+//! is only covered by a catch-all. Arrays split by length once some pattern in
+//! the column has a `...rest`: `[]` and `[x, ...rest]` together are complete.
+
+use crate::ast::{EnumVariant, Literal, MatchArm, Pattern};
+use crate::typechecker::TypeChecker;
+
+/// Constructor of a finite type and the number of payload fields it carries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Ctor {
+    /// Empty for the single constructor of a tuple
+    pub name: String,
+    pub arity: usize,
+}
+
+impl Ctor {
+    pub(super) fn new(name: &str, arity: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            arity,
+        }
+    }
+}
+
+/// Array constructor of exactly `arity` elements
+const ARRAY_EXACT: &str = "[]";
+/// Array constructor of `arity` or more elements
+const ARRAY_AT_LEAST: &str = "[..]";
+
+/// One row of the pattern matrix; `None` is a wildcard column
+type Row<'p> = Vec<Option<&'p Pattern>>;
+
+/// Length constructors for matching an array scrutinee with `arms`, or `None`
+/// when only a catch-all can make the match exhaustive
+pub(super) fn array_arm_constructors(arms: &[MatchArm]) -> Option<Vec<Ctor>> {
+    let rows: Vec<Row> = arms
+        .iter()
+        .filter(|arm| arm.guard.is_none())
+        .map(|arm| vec![Some(&arm.pattern)])
+        .collect();
+    let rows = expand_or(rows);
+    let heads: Vec<&Pattern> = rows.iter().filter_map(|row| row[0]).collect();
+    array_constructors(&heads)
+}
+
+impl<'a> TypeChecker<'a> {
+    /// Cases of `ctors` that the unguarded arms leave uncovered, each rendered
+    /// as a pattern that would match them (`Some(false)`, `Rgb(_, _, _)`).
+    pub(super) fn missing_cases(&self, arms: &[MatchArm], ctors: &[Ctor]) -> Vec<String> {
+        let rows: Vec<Row> = arms
+            .iter()
+            .filter(|arm| arm.guard.is_none())
+            .map(|arm| vec![Some(&arm.pattern)])
+            .collect();
+        let rows = expand_or(rows);
+
+        ctors
+            .iter()
+            .filter_map(|ctor| {
+                self.witness(specialize(&rows, ctor), ctor.arity)
+                    .map(|args| render(&ctor.name, &args))
+            })
+            .collect()
+    }
+
+    /// Constructors of a user-defined enum, in declaration order
+    pub(super) fn enum_constructors(&self, enum_name: &str) -> Option<Vec<Ctor>> {
+        let decl = self.enum_decls.get(enum_name)?;
+        Some(
+            decl.variants
+                .iter()
+                .map(|variant| {
+                    let arity = match variant {
+                        EnumVariant::Tuple { fields, .. } => fields.len(),
+                        _ => 0,
+                    };
+                    Ctor::new(&variant.name().name, arity)
+                })
+                .collect(),
+        )
+    }
+
+    /// A value the rows fail to match, one rendered pattern per column, or
+    /// `None` when the rows are exhaustive.
+    fn witness(&self, rows: Vec<Row>, width: usize) -> Option<Vec<String>> {
+        let rows = expand_or(rows);
+        if width == 0 {
+            return rows.is_empty().then(Vec::new);
+        }
+
+        let heads: Vec<&Pattern> = rows.iter().filter_map(|row| row[0]).collect();
+        match self.column_constructors(&heads) {
+            Some(ctors) => ctors.iter().find_map(|ctor| {
+                let found = self.witness(specialize(&rows, ctor), ctor.arity + width - 1)?;
+                let (args, rest) = found.split_at(ctor.arity);
+                let mut witness = vec![render(&ctor.name, args)];
+                witness.extend_from_slice(rest);
+                Some(witness)
+            }),
+            None => {
+                let defaults: Vec<Row> = rows
+                    .iter()
+                    .filter(|row| row[0].is_none_or(is_catch_all))
+                    .map(|row| row[1..].to_vec())
+                    .collect();
+                let mut witness = self.witness(defaults, width - 1)?;
+                witness.insert(0, "_".to_string());
+                Some(witness)
+            }
+        }
+    }
+
+    /// Constructors of the enum declaring `variant`
+    fn enum_of_variant(&self, variant: &str) -> Option<Vec<Ctor>> {
+        self.enum_decls
+            .iter()
+            .find(|(_, decl)| decl.variants.iter().any(|v| v.name().name == variant))
+            .and_then(|(enum_name, _)| self.enum_constructors(enum_name))
+    }
+
+    /// The full constructor set of a column, inferred from the first pattern
+    /// in it that names a constructor
+    fn column_constructors(&self, heads: &[&Pattern]) -> Option<Vec<Ctor>> {
+        if heads
+            .iter()
+            .any(|head| matches!(head, Pattern::Array { .. }))
+        {
+            return array_constructors(heads);
+        }
+        heads.iter().find_map(|head| match head {
+            Pattern::Literal(Literal::Bool(_), _) => {
+                Some(vec![Ctor::new("true", 0), Ctor::new("false", 0)])
+            }
+            Pattern::Constructor { name, .. } => match name.name.as_str() {
+                "Some" | "None" => Some(vec![Ctor::new("Some", 1), Ctor::new("None", 0)]),
+                "Ok" | "Err" => Some(vec![Ctor::new("Ok", 1), Ctor::new("Err", 1)]),
+                variant => self.enum_of_variant(variant),
+            },
+            Pattern::EnumVariant { enum_name, .. } => self.enum_constructors(&enum_name.name),
+            Pattern::BareVariant { name, .. } => self.enum_of_variant(&name.name),
+            Pattern::Tuple { elements, .. } => Some(vec![Ctor::new("", elements.len())]),
+            _ => None,
+        })
+    }
+}
+
+/// Patterns that match every value of their column. A named struct pattern
+/// is irrefutable when each field is bound or matched by a catch-all; an
+/// anonymous `{ key }` pattern can also meet a Map that lacks the key. An
+/// array pattern is irrefutable only as a bare `[...rest]`.
+fn is_catch_all(pattern: &Pattern) -> bool {
+    match pattern {
+        Pattern::Wildcard(_) | Pattern::Variable(_) => true,
+        Pattern::Struct {
+            type_name: Some(_),
+            fields,
+            ..
+        } => fields
+            .iter()
+            .all(|field| field.pattern.as_ref().is_none_or(is_catch_all)),
+        Pattern::Array {
+            elements,
+            rest: Some(rest),
+            ..
+        } => elements.is_empty() && is_catch_all(rest),
+        _ => false,
+    }
+}
+
+/// Constructors of an array column: every exact length below `n`, then `n`
+/// or more, where `n` is one past the longest fixed-length pattern and at
+/// least the longest `...rest` prefix. `None` when no pattern has a rest,
+/// since fixed lengths alone never cover every array.
+fn array_constructors(heads: &[&Pattern]) -> Option<Vec<Ctor>> {
+    let mut fixed_max = None;
+    let mut rest_max = None;
+    for head in heads {
+        if let Pattern::Array { elements, rest, .. } = head {
+            let longest = if rest.is_some() {
+                &mut rest_max
+            } else {
+                &mut fixed_max
+            };
+            *longest = Some(longest.map_or(elements.len(), |len: usize| len.max(elements.len())));
+        }
+    }
+    let rest_max = rest_max?;
+    let n = fixed_max.map_or(0, |len| len + 1).max(rest_max);
+    let mut ctors: Vec<Ctor> = (0..n).map(|len| Ctor::new(ARRAY_EXACT, len)).collect();
+    ctors.push(Ctor::new(ARRAY_AT_LEAST, n));
+    Some(ctors)
+}
+
+/// Split rows whose first column is an OR pattern into one row per alternative
+fn expand_or(rows: Vec<Row>) -> Vec<Row> {
+    let mut expanded = Vec::with_capacity(rows.len());
+    for row in rows {
+        match row.first() {
+            Some(Some(Pattern::Or(alternatives, _))) => {
+                let alternatives = alternatives
+                    .iter()
+                    .map(|alt| {
+                        let mut split = row.clone();
+                        split[0] = Some(alt);
+                        split
+                    })
+                    .collect();
+                expanded.extend(expand_or(alternatives));
+            }
+            _ => expanded.push(row),
+        }
+    }
+    expanded
+}
+
+/// Rows that can match `ctor` in the first column, with that column replaced
+/// by the constructor's payload columns
+fn specialize<'p>(rows: &[Row<'p>], ctor: &Ctor) -> Vec<Row<'p>> {
+    rows.iter()
+        .filter_map(|row| {
+            let args: Vec<Option<&Pattern>> = match row[0] {
+                None => vec![None; ctor.arity],
+                Some(head) if is_catch_all(head) => vec![None; ctor.arity],
+                Some(head) => payload(head, ctor)?,
+            };
+            let mut specialized = args;
+            specialized.extend_from_slice(&row[1..]);
+            Some(specialized)
+        })
+        .collect()
+}
+
+/// Payload patterns of `pattern` if it is `ctor`, padded with wildcards to
+/// the constructor's arity (arity errors are reported elsewhere)
+fn payload<'p>(pattern: &'p Pattern, ctor: &Ctor) -> Option<Vec<Option<&'p Pattern>>> {
+    let args: &[Pattern] = match pattern {
+        Pattern::Literal(Literal::Bool(b), _) if b.to_string() == ctor.name => &[],
+        Pattern::Constructor { name, args, .. }
+        | Pattern::EnumVariant {
+            variant_name: name,
+            args,
+            ..
+        }
+        | Pattern::BareVariant { name, args, .. }
+            if name.name == ctor.name =>
+        {
+            args
+        }
+        Pattern::Tuple { elements, .. } if ctor.name.is_empty() => elements,
+        // A fixed-length pattern only matches its exact length; a rest pattern
+        // matches any length its prefix fits in
+        Pattern::Array { elements, rest, .. }
+            if match rest {
+                None => ctor.name == ARRAY_EXACT && elements.len() == ctor.arity,
+                Some(_) => {
+                    (ctor.name == ARRAY_EXACT || ctor.name == ARRAY_AT_LEAST)
+                        && elements.len() <= ctor.arity
+                }
+            } =>
+        {
+            elements
+        }
+        _ => return None,
+    };
+    let mut columns: Vec<Option<&Pattern>> = args.iter().take(ctor.arity).map(Some).collect();
+    columns.resize(ctor.arity, None);
+    Some(columns)
+}
+
+fn render(name: &str, args: &[String]) -> String {
+    if name == ARRAY_EXACT {
+        format!("[{}]", args.join(", "))
+    } else if name == ARRAY_AT_LEAST {
+        let mut elements = args.to_vec();
+        elements.push("..".to_string());
+        format!("[{}]", elements.join(", "))
+    } else if name.is_empty() {
+        format!("({})", args.join(", "))
+    } else if args.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}({})", name, args.join(", "))
+    }
+}
@@ -1,20 +1,50 @@
 //! Expression type checking
 
 use crate::ast::*;
-use crate::diagnostic::error_codes;
+use crate::diagnostic::{error_codes, RelatedLocation};
 use crate::span::Span;
+use crate::typechecker::exhaustiveness::{self, Ctor};
 use crate::typechecker::suggestions;
 use crate::typechecker::TypeChecker;
 use crate::types::{StructuralMemberType, Type, TypeParamDef, ANY_TYPE_PARAM};
 use std::collections::{HashMap, HashSet};
 
+fn datetime_ty() -> Type {
+    Type::Generic {
+        name: "DateTime".to_string(),
+        type_args: vec![],
+    }
+}
+
+fn bytes_ty() -> Type {
+    Type::Generic {
+        name: "Bytes".to_string(),
+        type_args: vec![],
+    }
+}
+
+fn file_handle_ty() -> Type {
+    Type::Generic {
+        name: "FileHandle".to_string(),
+        type_args: vec![],
+    }
+}
+
+/// Durations are maps of `days`, `hours`, `minutes` and `seconds`
+fn duration_ty() -> Type {
+    Type::Generic {
+        name: "Map".to_string(),
+        type_args: vec![Type::String, Type::Number],
+    }
+}
+
 /// Resolve the expected parameter types for a static namespace method call.
 /// Returns `Some(params)` when we have a known signature, `None` when variadic or untracked.
 /// An empty `Some(vec![])` means zero-arg method.
 fn resolve_namespace_param_types(ns: &str, method: &str) -> Option<Vec<Type>> {
     let str = Type::String;
     let num = Type::Number;
-    let _json = Type::JsonValue;
+    let json = Type::JsonValue;
     let str_arr = Type::Array(Box::new(Type::String));
     // Normalize namespace to lowercase for case-insensitive matching (AI-friendly)
     let ns_lower = ns.to_lowercase();
@@ -26,6 +56,8 @@ fn resolve_namespace_param_types(ns: &str, method: &str) -> Option<Vec<Type>> {
         ("json", "getString" | "getNumber" | "getBool" | "getArray" | "getObject" | "isNull") => {
             Some(vec![str.clone(), str])
         }
+        ("json", "getPath" | "hasPath") => Some(vec![json, str]),
+        ("json", "setPath") => Some(vec![json, str, Type::any_placeholder()]),
         // Math namespace
         ("math", "abs" | "floor" | "ceil" | "round" | "sign") => Some(vec![num.clone()]),
         ("math", "sqrt" | "log" | "sin" | "cos" | "tan") => Some(vec![num.clone()]),
@@ -39,29 +71,39 @@ fn resolve_namespace_param_types(ns: &str, method: &str) -> Option<Vec<Type>> {
         // Env namespace
         ("env", "get" | "unset") => Some(vec![str.clone()]),
         ("env", "set") => Some(vec![str.clone(), str.clone()]),
-        ("env", "list") => Some(vec![]),
+        ("env", "list" | "all") => Some(vec![]),
         // File namespace — B24: full method set
         (
             "file",
             "read" | "exists" | "remove" | "createDir" | "removeDir" | "mkdir" | "mkdirp" | "rmdir"
             | "rmdirRecursive" | "readDir" | "walk" | "size" | "mtime" | "ctime" | "atime"
-            | "permissions" | "inode" | "isDir" | "isFile" | "isSymlink" | "readLink",
+            | "permissions" | "inode" | "isDir" | "isFile" | "isSymlink" | "readLink" | "touch",
         ) => Some(vec![str.clone()]),
         ("file", "write" | "append") => Some(vec![str.clone(), str.clone()]),
+        ("file", "readBytes") => Some(vec![str.clone()]),
+        ("file", "writeBytes") => Some(vec![str.clone(), bytes_ty()]),
+        ("file", "open") => None,        // path, optional mode
+        ("file", "writeAtomic") => None, // path, contents, optional fsync flag
+        ("file", "copy" | "rename" | "move") => Some(vec![str.clone(), str.clone()]),
+        ("file", "setPermissions") => Some(vec![str.clone(), num.clone()]),
         ("file", "symlink") => Some(vec![str.clone(), str.clone()]),
         ("file", "sortEntries") => None, // variadic array arg
         ("file", "filterEntries") => Some(vec![str.clone(), str.clone()]), // entries array + pattern
-        ("file", "tempFile" | "tempDir") => Some(vec![]),
+        ("file", "tempFile") => None,                                      // optional prefix arg
+        ("file", "tempDir") => Some(vec![]),
         ("file", "watch") => Some(vec![str.clone()]),
         ("file", "watchNext") => None, // handle arg (Value)
         // Process namespace
-        ("process", "cwd" | "pid" | "args" | "getProcessArgs" | "platform" | "arch") => {
+        ("process", "cwd" | "pid" | "args" | "getProcessArgs" | "platform" | "arch" | "gc") => {
             Some(vec![])
         }
         ("process", "exit") => Some(vec![num.clone()]), // H-266
+        ("process", "parseArgs") => None,               // spec plus optional argv
+        ("process", "argsHelp") => Some(vec![str_arr.clone()]),
+        ("process", "loadConfig") => None, // spec plus optional options record
         ("process", "run") => Some(vec![str.clone(), str_arr]),
         ("process", "shellOut") => Some(vec![str.clone()]),
-        ("process", "exec") => None, // accepts string or string[] - runtime validates
+        ("process", "exec" | "execCapture") => None, // command, optional args and options
         ("process", "shell") => Some(vec![str.clone()]),
         ("process", "spawn") => None, // array of strings arg
         ("process", "waitFor" | "isRunning" | "stdout" | "stderr") => None, // handle arg
@@ -71,10 +113,12 @@ fn resolve_namespace_param_types(ns: &str, method: &str) -> Option<Vec<Type>> {
         (
             "path",
             "dirname" | "basename" | "extension" | "extname" | "normalize" | "absolute" | "parent"
-            | "canonical" | "exists" | "isAbsolute" | "isRelative",
+            | "canonical" | "exists" | "isAbsolute" | "isRelative" | "stem" | "expandHome"
+            | "parse" | "drive" | "toPosix" | "toWindows" | "toPlatform",
         ) => Some(vec![str.clone()]),
+        ("path", "relative" | "equals" | "withExtension") => Some(vec![str.clone(), str.clone()]),
         ("path", "join") => None, // variadic: accepts 1+ string args
-        ("path", "homedir" | "cwd" | "tempdir" | "separator") => Some(vec![]),
+        ("path", "homedir" | "cwd" | "tempdir" | "separator" | "delimiter") => Some(vec![]),
         // DateTime namespace
         ("datetime", "now" | "utc") => Some(vec![]),
         ("datetime", "fromTimestamp") => Some(vec![num.clone()]),
@@ -85,6 +129,33 @@ fn resolve_namespace_param_types(ns: &str, method: &str) -> Option<Vec<Type>> {
         ("datetime", "tryParse") => None,
         // datetime.fromComponents — variadic (year,month,day,hour,min,sec) → skip arity
         ("datetime", "fromComponents") => None,
+        ("datetime", "timestamp") => Some(vec![]),
+        // formatDate(ts, format, tz?) / parseDate(text, format, tz?) — optional tz; skip arity
+        ("datetime", "formatDate" | "parseDate") => None,
+        // Instance methods called with the DateTime first: DateTime.addDays(dt, 1)
+        (
+            "datetime",
+            "year" | "month" | "day" | "hour" | "minute" | "second" | "weekday" | "dayOfYear"
+            | "toTimestamp" | "toIso" | "toRfc3339" | "toRfc2822" | "toUtc" | "toLocal"
+            | "getTimezone" | "getOffset",
+        ) => Some(vec![datetime_ty()]),
+        ("datetime", "addSeconds" | "addMinutes" | "addHours" | "addDays") => {
+            Some(vec![datetime_ty(), num.clone()])
+        }
+        ("datetime", "diff" | "diffSeconds" | "compare") => {
+            Some(vec![datetime_ty(), datetime_ty()])
+        }
+        ("datetime", "format" | "toCustom" | "toTimezone" | "inTimezone") => {
+            Some(vec![datetime_ty(), str.clone()])
+        }
+        (
+            "datetime",
+            "durationFromSeconds"
+            | "durationFromMinutes"
+            | "durationFromHours"
+            | "durationFromDays",
+        ) => Some(vec![num.clone()]),
+        ("datetime", "durationFormat") => Some(vec![duration_ty()]),
         // Regex namespace
         ("regex", "new") => Some(vec![str.clone()]),
         ("regex", "test" | "isMatch") => None, // regex value + string arg; skip arity check
@@ -94,15 +165,16 @@ fn resolve_namespace_param_types(ns: &str, method: &str) -> Option<Vec<Type>> {
         ("crypto", "blake3") => Some(vec![str.clone()]),
         ("crypto", "hmac") => Some(vec![str.clone(), str.clone(), str.clone()]),
         ("crypto", "hmacVerify") => Some(vec![str.clone(), str.clone(), str.clone(), str.clone()]),
-        // Encoding namespace — all methods take one string, return one string
+        // Encoding namespace — codecs and from* constructors take one string
         (
             "encoding",
             "base64Encode" | "base64Decode" | "base64UrlEncode" | "base64UrlDecode" | "hexEncode"
-            | "hexDecode" | "urlEncode" | "urlDecode",
+            | "hexDecode" | "urlEncode" | "urlDecode" | "fromHex" | "fromBase64",
         ) => Some(vec![str.clone()]),
+        ("encoding", "fromArray") => Some(vec![Type::Array(Box::new(num.clone()))]),
         // Http namespace — options-object API (B28). All accept optional map as last arg.
         // Use None (skip arity) so optional body/options args are not rejected.
-        ("http", "get" | "post" | "put" | "delete" | "patch") => None,
+        ("http", "get" | "post" | "put" | "delete" | "patch" | "request") => None,
         ("http", "checkPermission") => Some(vec![Type::String]),
         // http.serve(port: number, handler: fn) — skip detailed arity check (handler is fn type)
         ("http", "serve") => None,
@@ -111,16 +183,22 @@ fn resolve_namespace_param_types(ns: &str, method: &str) -> Option<Vec<Type>> {
             "net",
             "tcpConnect" | "tcpListen" | "tcpWrite" | "tcpRead" | "tcpReadBytes" | "tcpClose"
             | "tcpSetTimeout" | "tcpSetNodelay" | "tcpLocalAddr" | "tcpRemoteAddr" | "tcpAccept"
-            | "tcpListenerAddr" | "tcpListenerClose" | "udpBind" | "udpSend" | "udpReceive"
-            | "udpClose" | "udpLocalAddr" | "udpSetTimeout" | "tlsConnect" | "tlsRead" | "tlsWrite"
-            | "tlsClose" | "wsConnect" | "wsSend" | "wsSendBinary" | "wsReceive" | "wsClose"
-            | "wsPing",
+            | "tcpListenerAddr" | "tcpListenerClose" | "udpBind" | "udpConnect" | "udpSend"
+            | "udpReceive" | "udpClose" | "udpLocalAddr" | "udpSetTimeout" | "tlsConnect"
+            | "tlsRead" | "tlsWrite" | "tlsClose" | "send" | "recv" | "close" | "wsConnect"
+            | "wsSend" | "wsSendBinary" | "wsReceive" | "wsClose" | "wsPing",
         ) => None,
         // Io namespace
         ("io", "readLine") => Some(vec![]),
         ("io", "readLinePrompt") => Some(vec![str.clone()]),
         // Console namespace — variadic, skip arity check
         ("console", "log" | "println" | "print" | "error" | "warn" | "debug") => None,
+        ("console", "printTable") => Some(vec![
+            Type::any_placeholder(),
+            Type::Array(Box::new(str.clone())),
+        ]),
+        ("console", "progressBar") => Some(vec![Type::Number]),
+        ("console", "styled") => Some(vec![str.clone(), str.clone()]),
         // Reflect namespace (B40-P03)
         (
             "reflect",
@@ -132,6 +210,10 @@ fn resolve_namespace_param_types(ns: &str, method: &str) -> Option<Vec<Type>> {
         }
         // SQLite namespace (B40-P05)
         ("sqlite", "open") => Some(vec![str.clone()]),
+        // Key-value store namespace
+        ("kv", "open") => Some(vec![str.clone()]),
+        // CSV namespace — text or rows, then an optional options record
+        ("csv", "parse" | "parseRecords" | "stringify") => None,
         // future namespace (B33)
         ("future", "resolve" | "reject") => Some(vec![Type::any_placeholder()]),
         ("future", "all" | "race" | "allSettled" | "any") => None, // array arg — skip arity check
@@ -163,6 +245,7 @@ fn resolve_namespace_return_type(ns: &str, method: &str) -> Type {
     match (ns_lower.as_str(), method) {
         // Array namespace (D-062: Array.isArray(x) mirrors TypeScript — AI models know it cold)
         ("array", "isArray") => Type::Bool,
+        ("array", "range") => Type::Array(Box::new(Type::Number)),
         // Json namespace
         ("json", "parse") => Type::Generic {
             name: "Result".to_string(),
@@ -176,7 +259,11 @@ fn resolve_namespace_return_type(ns: &str, method: &str) -> Type {
         ("json", "keys") => Type::Array(Box::new(Type::String)),
         ("json", "getString" | "getArray" | "getObject") => Type::String,
         ("json", "getNumber") => Type::Number,
-        ("json", "getBool" | "isNull") => Type::Bool,
+        ("json", "getBool" | "isNull" | "hasPath") => Type::Bool,
+        ("json", "getPath" | "setPath") => Type::Generic {
+            name: "Result".to_string(),
+            type_args: vec![Type::JsonValue, Type::String],
+        },
         // Math namespace
         (
             "math",
@@ -194,16 +281,25 @@ fn resolve_namespace_return_type(ns: &str, method: &str) -> Type {
             type_args: vec![Type::String],
         },
         ("env", "set" | "unset") => Type::Null,
-        ("env", "list") => Type::JsonValue,
+        ("env", "list" | "all") => Type::JsonValue,
         // File namespace — B24: full return type coverage
         ("file", "read") => Type::Generic {
             name: "Result".to_string(),
             type_args: vec![Type::String, Type::String],
         },
+        ("file", "readBytes") => Type::Generic {
+            name: "Result".to_string(),
+            type_args: vec![bytes_ty(), Type::String],
+        },
+        ("file", "open") => Type::Generic {
+            name: "Result".to_string(),
+            type_args: vec![file_handle_ty(), Type::String],
+        },
         (
             "file",
-            "write" | "append" | "remove" | "rename" | "copy" | "createDir" | "removeDir" | "mkdir"
-            | "mkdirp" | "rmdir" | "rmdirRecursive" | "symlink",
+            "write" | "writeBytes" | "append" | "writeAtomic" | "remove" | "rename" | "copy"
+            | "move" | "touch" | "setPermissions" | "createDir" | "removeDir" | "mkdir" | "mkdirp"
+            | "rmdir" | "rmdirRecursive" | "symlink",
         ) => Type::Generic {
             name: "Result".to_string(),
             type_args: vec![Type::Null, Type::String],
@@ -211,7 +307,7 @@ fn resolve_namespace_return_type(ns: &str, method: &str) -> Type {
         ("file", "exists" | "isDir" | "isFile" | "isSymlink") => Type::Bool,
         ("file", "readDir" | "walk" | "sortEntries") => Type::Array(Box::new(Type::String)),
         ("file", "filterEntries") => Type::Array(Box::new(Type::String)),
-        ("file", "info") => Type::JsonValue,
+        ("file", "info") => crate::stdlib::io::file_info_type(),
         ("file", "size" | "inode") => Type::Number,
         ("file", "mtime" | "ctime" | "atime" | "permissions" | "readLink") => Type::String,
         ("file", "tempFile" | "tempDir") => Type::String,
@@ -229,19 +325,31 @@ fn resolve_namespace_return_type(ns: &str, method: &str) -> Type {
         // Process namespace
         ("process", "cwd") => Type::String,
         ("process", "pid") => Type::Number,
+        ("process", "gc") => Type::Number, // cells freed
         // H-275: process.platform() / process.arch() — OS and CPU info
         ("process", "platform" | "arch") => Type::String,
         // H-266: process.exit(code) — terminates process, returns never
         ("process", "exit") => Type::Never,
         // H-213: process.args() / process.getProcessArgs() — CLI argv access
         ("process", "args" | "getProcessArgs") => Type::Array(Box::new(Type::String)),
+        // process.parseArgs(spec) — fields depend on the spec, so the record is `any`
+        ("process", "parseArgs") => Type::Generic {
+            name: "Result".to_string(),
+            type_args: vec![Type::any_placeholder(), Type::String],
+        },
+        ("process", "argsHelp") => Type::String,
+        // process.loadConfig(spec, options?) — settings record, `any` like parseArgs
+        ("process", "loadConfig") => Type::Generic {
+            name: "Result".to_string(),
+            type_args: vec![Type::any_placeholder(), Type::String],
+        },
         // H-212: process.run(program, args) — direct exec returns Result<string, string>
         ("process", "run") => Type::Generic {
             name: "Result".to_string(),
             type_args: vec![Type::String, Type::String],
         },
         // B18: process.exec(cmd) / process.shell(cmd) — returns Result<ProcessOutput, string>
-        ("process", "exec" | "shell") => Type::Generic {
+        ("process", "exec" | "execCapture" | "shell") => Type::Generic {
             name: "Result".to_string(),
             type_args: vec![
                 Type::Generic {
@@ -277,13 +385,24 @@ fn resolve_namespace_return_type(ns: &str, method: &str) -> Type {
         (
             "path",
             "join" | "dirname" | "basename" | "extension" | "extname" | "normalize" | "absolute"
-            | "resolve" | "canonical" | "homedir" | "cwd" | "tempdir" | "separator",
+            | "resolve" | "canonical" | "homedir" | "cwd" | "tempdir" | "separator" | "stem"
+            | "withExtension" | "expandHome" | "relative" | "drive" | "toPosix" | "toWindows"
+            | "toPlatform" | "delimiter",
         ) => Type::String,
+        ("path", "parse") => Type::Structural {
+            members: ["root", "dir", "base", "ext", "name"]
+                .iter()
+                .map(|name| StructuralMemberType {
+                    name: name.to_string(),
+                    ty: Type::String,
+                })
+                .collect(),
+        },
         ("path", "parent") => Type::Generic {
             name: "Option".to_string(),
             type_args: vec![Type::String],
         },
-        ("path", "exists" | "isAbsolute" | "isRelative") => Type::Bool,
+        ("path", "exists" | "isAbsolute" | "isRelative" | "equals") => Type::Bool,
         // DateTime namespace — returns DateTime value (H-231)
         ("datetime", "now" | "fromTimestamp" | "fromComponents" | "utc") => Type::Generic {
             name: "DateTime".to_string(),
@@ -296,6 +415,30 @@ fn resolve_namespace_return_type(ns: &str, method: &str) -> Type {
                 type_args: vec![],
             }
         }
+        ("datetime", "timestamp" | "parseDate") => Type::Number,
+        ("datetime", "formatDate") => Type::String,
+        (
+            "datetime",
+            "year" | "month" | "day" | "hour" | "minute" | "second" | "weekday" | "dayOfYear"
+            | "toTimestamp" | "getOffset" | "diff" | "diffSeconds" | "compare",
+        ) => Type::Number,
+        (
+            "datetime",
+            "toIso" | "toRfc3339" | "toRfc2822" | "format" | "toCustom" | "getTimezone"
+            | "durationFormat",
+        ) => Type::String,
+        (
+            "datetime",
+            "toUtc" | "toLocal" | "toTimezone" | "inTimezone" | "addSeconds" | "addMinutes"
+            | "addHours" | "addDays",
+        ) => datetime_ty(),
+        (
+            "datetime",
+            "durationFromSeconds"
+            | "durationFromMinutes"
+            | "durationFromHours"
+            | "durationFromDays",
+        ) => duration_ty(),
         // Regex namespace (H-231): regex.new returns Result<Regex, string>
         ("regex", "new") => Type::Generic {
             name: "Result".to_string(),
@@ -330,15 +473,16 @@ fn resolve_namespace_return_type(ns: &str, method: &str) -> Type {
         ("crypto", "sha256" | "sha512" | "blake3") => Type::String,
         ("crypto", "hmac") => Type::String,
         ("crypto", "hmacVerify") => Type::Bool,
-        // Encoding namespace — all methods return string
+        // Encoding namespace — codecs return string, the from* constructors Bytes
         (
             "encoding",
             "base64Encode" | "base64Decode" | "base64UrlEncode" | "base64UrlDecode" | "hexEncode"
             | "hexDecode" | "urlEncode" | "urlDecode",
         ) => Type::String,
+        ("encoding", "fromHex" | "fromBase64" | "fromArray") => bytes_ty(),
         ("http", "checkPermission") => Type::Bool,
         // Http namespace — returns Result<HttpResponse, string> (B28 options-object API)
-        ("http", "get" | "post" | "put" | "delete" | "patch") => Type::Generic {
+        ("http", "get" | "post" | "put" | "delete" | "patch" | "request") => Type::Generic {
             name: "Result".to_string(),
             type_args: vec![
                 Type::Generic {
@@ -351,26 +495,31 @@ fn resolve_namespace_return_type(ns: &str, method: &str) -> Type {
         // Http server namespace — http.serve() blocks until server stops, returns void (H-413)
         ("http", "serve") => Type::Null,
         // Net namespace — connection/bind methods return Result<Unknown, String>
-        ("net", "tcpConnect" | "tcpListen" | "udpBind" | "tlsConnect" | "wsConnect") => {
-            Type::Generic {
-                name: "Result".to_string(),
-                type_args: vec![Type::Unknown, Type::String],
-            }
-        }
+        (
+            "net",
+            "tcpConnect" | "tcpListen" | "tcpAccept" | "udpBind" | "udpConnect" | "tlsConnect"
+            | "wsConnect",
+        ) => Type::Generic {
+            name: "Result".to_string(),
+            type_args: vec![Type::Unknown, Type::String],
+        },
         // Net namespace — read methods return String
-        ("net", "tcpRead" | "tcpReadBytes" | "tlsRead" | "wsReceive" | "udpReceive") => {
-            Type::String
-        }
+        ("net", "tcpRead" | "tlsRead" | "recv" | "wsReceive") => Type::String,
+        ("net", "tcpReadBytes") => Type::Array(Box::new(Type::Number)),
+        // udpReceive returns [data, sender address]
+        ("net", "udpReceive") => Type::Array(Box::new(Type::String)),
+        // Net namespace — write methods return the number of bytes written
+        ("net", "tcpWrite" | "udpSend" | "tlsWrite" | "send") => Type::Number,
         // Net namespace — addr methods return String
         ("net", "tcpLocalAddr" | "tcpRemoteAddr" | "tcpListenerAddr" | "udpLocalAddr") => {
             Type::String
         }
-        // Net namespace — void methods (write, close, set*)
+        // Net namespace — void methods (close, set*)
         (
             "net",
-            "tcpWrite" | "tcpClose" | "tcpSetTimeout" | "tcpSetNodelay" | "tcpListenerClose"
-            | "tcpAccept" | "udpSend" | "udpClose" | "udpSetTimeout" | "tlsWrite" | "tlsClose"
-            | "wsSend" | "wsSendBinary" | "wsClose" | "wsPing",
+            "tcpClose" | "tcpSetTimeout" | "tcpSetNodelay" | "tcpListenerClose" | "udpClose"
+            | "udpSetTimeout" | "tlsClose" | "close" | "wsSend" | "wsSendBinary" | "wsClose"
+            | "wsPing",
         ) => Type::Null,
         // Io namespace — returns Option<string> (None on EOF)
         ("io", "readLine" | "readLinePrompt") => Type::Generic {
@@ -378,7 +527,14 @@ fn resolve_namespace_return_type(ns: &str, method: &str) -> Type {
             type_args: vec![Type::String],
         },
         // Console namespace — all methods return void (Null)
-        ("console", "log" | "println" | "print" | "error" | "warn" | "debug") => Type::Null,
+        ("console", "log" | "println" | "print" | "error" | "warn" | "debug" | "printTable") => {
+            Type::Null
+        }
+        ("console", "progressBar") => Type::Generic {
+            name: "ProgressBar".to_string(),
+            type_args: vec![],
+        },
+        ("console", "styled") => Type::String,
         // Reflect namespace (B40-P03)
         ("reflect", "typeOf") => Type::String,
         ("reflect", "fields") => Type::Array(Box::new(Type::String)),
@@ -399,6 +555,30 @@ fn resolve_namespace_return_type(ns: &str, method: &str) -> Type {
             name: "SqliteConnection".to_string(),
             type_args: vec![],
         },
+        // Key-value store namespace
+        ("kv", "open") => Type::Generic {
+            name: "KvStore".to_string(),
+            type_args: vec![],
+        },
+        // CSV namespace
+        ("csv", "parse") => Type::Generic {
+            name: "Result".to_string(),
+            type_args: vec![
+                Type::Array(Box::new(Type::Array(Box::new(Type::String)))),
+                Type::String,
+            ],
+        },
+        ("csv", "parseRecords") => Type::Generic {
+            name: "Result".to_string(),
+            type_args: vec![
+                Type::Array(Box::new(Type::Generic {
+                    name: "Map".to_string(),
+                    type_args: vec![Type::String, Type::String],
+                })),
+                Type::String,
+            ],
+        },
+        ("csv", "stringify") => Type::String,
         // Gzip namespace
         ("gzip", "compress" | "decompress") => Type::Array(Box::new(Type::Number)),
         ("gzip", "decompressString") => Type::String,
@@ -476,6 +656,26 @@ fn resolve_namespace_return_type(ns: &str, method: &str) -> Type {
     }
 }
 
+/// Type of the entry `name` when a value of type `ty` is destructured with
+/// `{ name }`: a Map's value type or a structural type's member. Unknown when
+/// it cannot be determined statically.
+pub(super) fn destructured_field_type(ty: &Type, name: &str) -> Type {
+    match ty.normalized() {
+        Type::Generic {
+            name: map,
+            type_args,
+        } if map == "Map" && type_args.len() == 2 => type_args[1].clone(),
+        // JSON object keys hold json values of any shape
+        Type::JsonValue => Type::JsonValue,
+        Type::Structural { members } => members
+            .into_iter()
+            .find(|member| member.name == name)
+            .map(|member| member.ty)
+            .unwrap_or(Type::Unknown),
+        _ => Type::Unknown,
+    }
+}
+
 impl<'a> TypeChecker<'a> {
     /// Check an expression and return its type
     pub(super) fn check_expr(&mut self, expr: &Expr) -> Type {
@@ -485,6 +685,10 @@ impl<'a> TypeChecker<'a> {
                 Literal::String(_) => Type::String,
                 Literal::Bool(_) => Type::Bool,
                 Literal::Null => Type::Null,
+                Literal::Regex { .. } => Type::Generic {
+                    name: "Regex".to_string(),
+                    type_args: vec![],
+                },
             },
             Expr::TemplateString { parts, .. } => {
                 for part in parts {
@@ -499,7 +703,7 @@ impl<'a> TypeChecker<'a> {
                 self.used_symbols.insert(id.name.clone());
 
                 // AT3053: use-after-own — variable was moved into an `own` call
-                if self.moved_vars.contains(&id.name) {
+                if let Some(moved_at) = self.moved_vars.get(&id.name).copied() {
                     self.diagnostics.push(
                         error_codes::USE_AFTER_OWN.emit(id.span)
                             .arg("name", &id.name)
@@ -511,7 +715,11 @@ impl<'a> TypeChecker<'a> {
                                 id.name, id.name, id.name
                             ))
                             .build()
-                            .with_label("value already moved"),
+                            .with_label("value used here after move")
+                            .with_related_location(RelatedLocation::from_span(
+                                moved_at,
+                                "value moved here into an `own` parameter",
+                            )),
                     );
                 }
 
@@ -1307,7 +1515,7 @@ impl<'a> TypeChecker<'a> {
                             );
                         } else {
                             // Mark variable as moved — any subsequent use triggers AT3053
-                            self.moved_vars.insert(id.name.clone());
+                            self.moved_vars.insert(id.name.clone(), id.span);
                         }
                     }
                 }
@@ -1405,6 +1613,12 @@ impl<'a> TypeChecker<'a> {
 
     /// Check a binary expression
     fn check_binary(&mut self, binary: &BinaryExpr) -> Type {
+        if binary.op == BinaryOp::Coalesce {
+            return self.check_coalesce(binary);
+        }
+        if let Some((operands, _)) = binary.comparison_chain() {
+            return self.check_comparison_chain(&operands);
+        }
         let left_type = self.check_expr(&binary.left);
         let right_type = self.check_expr(&binary.right);
         let left_norm = left_type.normalized();
@@ -1423,10 +1637,12 @@ impl<'a> TypeChecker<'a> {
                 | BinaryOp::Gt
                 | BinaryOp::Ge
                 | BinaryOp::And
-                | BinaryOp::Or => Type::Bool,
+                | BinaryOp::Or
+                | BinaryOp::In => Type::Bool,
                 BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => {
                     Type::any_placeholder()
                 }
+                BinaryOp::Coalesce => unreachable!(), // Handled above
             };
         }
 
@@ -1524,26 +1740,9 @@ impl<'a> TypeChecker<'a> {
                 Type::Bool
             }
             BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
-                let left_cmp = self.comparable_operand_type(&left_norm);
-                let right_cmp = self.comparable_operand_type(&right_norm);
-                if self.all_union_pairs_valid(&left_cmp, &right_cmp, |a, b| {
-                    *a == Type::Number && *b == Type::Number
-                }) {
-                    Type::Bool
-                } else {
-                    self.diagnostics.push(
-                        error_codes::BINARY_OP_TYPE_ERROR
-                            .emit(binary.span)
-                            .arg("op", "<")
-                            .arg("left", left_type.display_name())
-                            .arg("right", right_type.display_name())
-                            .with_help("comparison operators (<, <=, >, >=) only work with numbers")
-                            .build()
-                            .with_label("type mismatch"),
-                    );
-                    Type::Bool // Still return bool for error recovery
-                }
+                self.check_ordering(binary.span, &left_type, &right_type)
             }
+            BinaryOp::In => self.check_membership(binary.span, &left_type, &right_type),
             BinaryOp::And | BinaryOp::Or => {
                 if !self.all_union_pairs_valid(&left_norm, &right_norm, |a, b| {
                     *a == Type::Bool && *b == Type::Bool
@@ -1561,9 +1760,138 @@ impl<'a> TypeChecker<'a> {
                 }
                 Type::Bool
             }
+            BinaryOp::Coalesce => unreachable!(), // Handled above
         }
     }
 
+    /// Check the operands of `<`, `<=`, `>`, `>=`
+    fn check_ordering(&mut self, span: Span, left_type: &Type, right_type: &Type) -> Type {
+        let left_cmp = self.comparable_operand_type(left_type);
+        let right_cmp = self.comparable_operand_type(right_type);
+        if !self.all_union_pairs_valid(&left_cmp, &right_cmp, |a, b| {
+            *a == Type::Number && *b == Type::Number
+        }) {
+            self.diagnostics.push(
+                error_codes::BINARY_OP_TYPE_ERROR
+                    .emit(span)
+                    .arg("op", "<")
+                    .arg("left", left_type.display_name())
+                    .arg("right", right_type.display_name())
+                    .with_help("comparison operators (<, <=, >, >=) only work with numbers")
+                    .build()
+                    .with_label("type mismatch"),
+            );
+        }
+        Type::Bool // Still bool on error, for recovery
+    }
+
+    /// Check `a < b <= c ...`: every operand once, then each adjacent pair
+    fn check_comparison_chain(&mut self, operands: &[&Expr]) -> Type {
+        let types: Vec<Type> = operands.iter().map(|e| self.check_expr(e)).collect();
+        for (pair, exprs) in types.windows(2).zip(operands.windows(2)) {
+            let skip = pair.iter().any(|t| {
+                let norm = t.normalized();
+                norm == Type::Unknown || Type::is_any_placeholder(&norm)
+            });
+            if !skip {
+                let span = exprs[0].span().merge(exprs[1].span());
+                self.check_ordering(span, &pair[0], &pair[1]);
+            }
+        }
+        Type::Bool
+    }
+
+    /// Check `value in container`: the value must be able to be an element
+    /// (or key, substring, number in range) of the container
+    fn check_membership(&mut self, span: Span, left_type: &Type, right_type: &Type) -> Type {
+        let left_norm = left_type.normalized();
+        let element = match right_type.normalized() {
+            Type::Array(elem) => Some(elem.normalized()),
+            Type::Generic { name, type_args }
+                if (name == "Map" || name == "Set") && !type_args.is_empty() =>
+            {
+                Some(type_args[0].normalized())
+            }
+            Type::String | Type::JsonValue => Some(Type::String),
+            Type::Range => Some(Type::Number),
+            _ => None,
+        };
+        let help = match &element {
+            Some(elem) if *elem == Type::Unknown || self.types_overlap(&left_norm, elem) => {
+                return Type::Bool;
+            }
+            Some(elem) => format!(
+                "`in` on {} looks for a {} value",
+                right_type.display_name(),
+                elem.display_name()
+            ),
+            None => "`in` checks membership in an array, map, set, string, range or json object"
+                .to_string(),
+        };
+        self.diagnostics.push(
+            error_codes::BINARY_OP_TYPE_ERROR
+                .emit(span)
+                .arg("op", "in")
+                .arg("left", left_type.display_name())
+                .arg("right", right_type.display_name())
+                .with_help(help)
+                .build()
+                .with_label("type mismatch"),
+        );
+        Type::Bool
+    }
+
+    /// Check `a ?? b`: the type of `a` without its missing case (null, None's
+    /// Option wrapper), joined with the type of `b`
+    fn check_coalesce(&mut self, binary: &BinaryExpr) -> Type {
+        let left_type = self.check_expr(&binary.left);
+        let right_type = self.check_expr(&binary.right);
+        let left_norm = left_type.normalized();
+        let right_norm = right_type.normalized();
+
+        if Type::is_any_placeholder(&left_norm) || Type::is_any_placeholder(&right_norm) {
+            return Type::any_placeholder();
+        }
+        if left_norm == Type::Unknown || right_norm == Type::Unknown {
+            return Type::Unknown;
+        }
+
+        let present = match &left_norm {
+            Type::Generic { name, type_args } if name == "Option" && type_args.len() == 1 => {
+                type_args[0].clone()
+            }
+            Type::Union(members) if members.contains(&Type::Null) => Type::union(
+                members
+                    .iter()
+                    .filter(|ty| **ty != Type::Null)
+                    .cloned()
+                    .collect(),
+            ),
+            Type::JsonValue => Type::JsonValue,
+            _ => {
+                self.diagnostics.push(
+                    error_codes::TYPE_ERROR
+                        .emit(binary.left.span())
+                        .arg(
+                            "detail",
+                            format!(
+                                "`??` needs a value that can be missing, found {}",
+                                left_type.display_name()
+                            ),
+                        )
+                        .with_help(
+                            "`??` applies to `T | null`, `Option<T>` and `json` values; a value of this type is never missing",
+                        )
+                        .build()
+                        .with_label("never null"),
+                );
+                return left_type;
+            }
+        };
+
+        Type::union(vec![present, right_type])
+    }
+
     /// Check a unary expression
     fn check_unary(&mut self, unary: &UnaryExpr) -> Type {
         let expr_type = self.check_expr(&unary.expr);
@@ -1988,172 +2316,163 @@ impl<'a> TypeChecker<'a> {
                     };
                 }
                 // H-236: Array element-type checking for mutation/concat calls
-                "arrayPush" | "array_push" | "arrayUnshift" | "array_unshift" => {
-                    if call.args.len() == 2 {
-                        let arr_type = self.check_expr(&call.args[0]);
-                        let elem_type = self.check_expr(&call.args[1]);
-                        if let Some(expected_elem) = self.array_elem_type(&arr_type) {
-                            if !Self::is_untyped_collection_elem(&expected_elem)
-                                && !self.is_assignable_with_traits(&elem_type, &expected_elem)
-                            {
-                                let method = name.as_str();
-                                self.diagnostics.push(
-                                    error_codes::TYPE_ERROR
-                                        .emit(call.args[1].span())
-                                        .arg(
-                                            "detail",
-                                            format!(
-                                                "{method} element type mismatch: expected {}, found {}",
-                                                expected_elem.display_name(),
-                                                elem_type.display_name()
-                                            ),
-                                        )
-                                        .with_help(format!(
-                                            "array element type is {} — cannot push {}",
+                "arrayPush" | "array_push" | "arrayUnshift" | "array_unshift"
+                    if call.args.len() == 2 =>
+                {
+                    let arr_type = self.check_expr(&call.args[0]);
+                    let elem_type = self.check_expr(&call.args[1]);
+                    if let Some(expected_elem) = self.array_elem_type(&arr_type) {
+                        if !Self::is_untyped_collection_elem(&expected_elem)
+                            && !self.is_assignable_with_traits(&elem_type, &expected_elem)
+                        {
+                            let method = name.as_str();
+                            self.diagnostics.push(
+                                error_codes::TYPE_ERROR
+                                    .emit(call.args[1].span())
+                                    .arg(
+                                        "detail",
+                                        format!(
+                                            "{method} element type mismatch: expected {}, found {}",
                                             expected_elem.display_name(),
                                             elem_type.display_name()
-                                        ))
-                                        .build()
-                                        .with_label("type mismatch"),
-                                );
-                            }
+                                        ),
+                                    )
+                                    .with_help(format!(
+                                        "array element type is {} — cannot push {}",
+                                        expected_elem.display_name(),
+                                        elem_type.display_name()
+                                    ))
+                                    .build()
+                                    .with_label("type mismatch"),
+                            );
                         }
-                        return arr_type;
                     }
-                }
-                "arrayConcat" => {
-                    if call.args.len() == 2 {
-                        let arr_type = self.check_expr(&call.args[0]);
-                        let other_type = self.check_expr(&call.args[1]);
-                        if let Some(expected_elem) = self.array_elem_type(&arr_type) {
-                            if !Self::is_untyped_collection_elem(&expected_elem) {
-                                if let Some(other_elem) = self.array_elem_type(&other_type) {
-                                    if !self.is_assignable_with_traits(&other_elem, &expected_elem)
-                                    {
-                                        self.diagnostics.push(
-                                            error_codes::TYPE_ERROR
-                                                .emit(call.args[1].span())
-                                                .arg(
-                                                    "detail",
-                                                    format!(
-                                                        "arrayConcat element type mismatch: expected {}[], found {}[]",
-                                                        expected_elem.display_name(),
-                                                        other_elem.display_name()
-                                                    ),
-                                                )
-                                                .with_help(format!(
-                                                    "both arrays must have element type {} to concat",
-                                                    expected_elem.display_name()
-                                                ))
-                                                .build()
-                                                .with_label("type mismatch"),
-                                        );
-                                    }
+                    return arr_type;
+                }
+                "arrayConcat" if call.args.len() == 2 => {
+                    let arr_type = self.check_expr(&call.args[0]);
+                    let other_type = self.check_expr(&call.args[1]);
+                    if let Some(expected_elem) = self.array_elem_type(&arr_type) {
+                        if !Self::is_untyped_collection_elem(&expected_elem) {
+                            if let Some(other_elem) = self.array_elem_type(&other_type) {
+                                if !self.is_assignable_with_traits(&other_elem, &expected_elem) {
+                                    self.diagnostics.push(
+                                        error_codes::TYPE_ERROR
+                                            .emit(call.args[1].span())
+                                            .arg(
+                                                "detail",
+                                                format!(
+                                                    "arrayConcat element type mismatch: expected {}[], found {}[]",
+                                                    expected_elem.display_name(),
+                                                    other_elem.display_name()
+                                                ),
+                                            )
+                                            .with_help(format!(
+                                                "both arrays must have element type {} to concat",
+                                                expected_elem.display_name()
+                                            ))
+                                            .build()
+                                            .with_label("type mismatch"),
+                                    );
                                 }
                             }
                         }
-                        return arr_type;
                     }
+                    return arr_type;
                 }
                 // H-236: HashSet element-type checking
-                "setAdd" | "set_add" | "setRemove" | "set_remove" => {
-                    if call.args.len() == 2 {
-                        let set_type = self.check_expr(&call.args[0]);
-                        let elem_type = self.check_expr(&call.args[1]);
-                        if let Some(expected_elem) = self.hashset_elem_type(&set_type) {
-                            if !Self::is_untyped_collection_elem(&expected_elem)
-                                && !self.is_assignable_with_traits(&elem_type, &expected_elem)
-                            {
-                                let method = name.as_str();
-                                self.diagnostics.push(
-                                    error_codes::TYPE_ERROR
-                                        .emit(call.args[1].span())
-                                        .arg(
-                                            "detail",
-                                            format!(
-                                                "{method} element type mismatch: expected {}, found {}",
-                                                expected_elem.display_name(),
-                                                elem_type.display_name()
-                                            ),
-                                        )
-                                        .with_help(format!(
-                                            "set element type is {} — cannot add/remove {}",
+                "setAdd" | "set_add" | "setRemove" | "set_remove" if call.args.len() == 2 => {
+                    let set_type = self.check_expr(&call.args[0]);
+                    let elem_type = self.check_expr(&call.args[1]);
+                    if let Some(expected_elem) = self.hashset_elem_type(&set_type) {
+                        if !Self::is_untyped_collection_elem(&expected_elem)
+                            && !self.is_assignable_with_traits(&elem_type, &expected_elem)
+                        {
+                            let method = name.as_str();
+                            self.diagnostics.push(
+                                error_codes::TYPE_ERROR
+                                    .emit(call.args[1].span())
+                                    .arg(
+                                        "detail",
+                                        format!(
+                                            "{method} element type mismatch: expected {}, found {}",
                                             expected_elem.display_name(),
                                             elem_type.display_name()
-                                        ))
-                                        .build()
-                                        .with_label("type mismatch"),
-                                );
-                            }
+                                        ),
+                                    )
+                                    .with_help(format!(
+                                        "set element type is {} — cannot add/remove {}",
+                                        expected_elem.display_name(),
+                                        elem_type.display_name()
+                                    ))
+                                    .build()
+                                    .with_label("type mismatch"),
+                            );
                         }
-                        return set_type;
                     }
+                    return set_type;
                 }
                 // H-236: Queue element-type checking
-                "queueEnqueue" | "queue_enqueue" => {
-                    if call.args.len() == 2 {
-                        let queue_type = self.check_expr(&call.args[0]);
-                        let elem_type = self.check_expr(&call.args[1]);
-                        if let Some(expected_elem) = self.queue_elem_type(&queue_type) {
-                            if !Self::is_untyped_collection_elem(&expected_elem)
-                                && !self.is_assignable_with_traits(&elem_type, &expected_elem)
-                            {
-                                self.diagnostics.push(
-                                    error_codes::TYPE_ERROR
-                                        .emit(call.args[1].span())
-                                        .arg(
-                                            "detail",
-                                            format!(
-                                                "queueEnqueue element type mismatch: expected {}, found {}",
-                                                expected_elem.display_name(),
-                                                elem_type.display_name()
-                                            ),
-                                        )
-                                        .with_help(format!(
-                                            "queue element type is {} — cannot enqueue {}",
+                "queueEnqueue" | "queue_enqueue" if call.args.len() == 2 => {
+                    let queue_type = self.check_expr(&call.args[0]);
+                    let elem_type = self.check_expr(&call.args[1]);
+                    if let Some(expected_elem) = self.queue_elem_type(&queue_type) {
+                        if !Self::is_untyped_collection_elem(&expected_elem)
+                            && !self.is_assignable_with_traits(&elem_type, &expected_elem)
+                        {
+                            self.diagnostics.push(
+                                error_codes::TYPE_ERROR
+                                    .emit(call.args[1].span())
+                                    .arg(
+                                        "detail",
+                                        format!(
+                                            "queueEnqueue element type mismatch: expected {}, found {}",
                                             expected_elem.display_name(),
                                             elem_type.display_name()
-                                        ))
-                                        .build()
-                                        .with_label("type mismatch"),
-                                );
-                            }
+                                        ),
+                                    )
+                                    .with_help(format!(
+                                        "queue element type is {} — cannot enqueue {}",
+                                        expected_elem.display_name(),
+                                        elem_type.display_name()
+                                    ))
+                                    .build()
+                                    .with_label("type mismatch"),
+                            );
                         }
-                        return queue_type;
                     }
+                    return queue_type;
                 }
                 // H-236: Stack element-type checking
-                "stackPush" | "stack_push" => {
-                    if call.args.len() == 2 {
-                        let stack_type = self.check_expr(&call.args[0]);
-                        let elem_type = self.check_expr(&call.args[1]);
-                        if let Some(expected_elem) = self.stack_elem_type(&stack_type) {
-                            if !Self::is_untyped_collection_elem(&expected_elem)
-                                && !self.is_assignable_with_traits(&elem_type, &expected_elem)
-                            {
-                                self.diagnostics.push(
-                                    error_codes::TYPE_ERROR
-                                        .emit(call.args[1].span())
-                                        .arg(
-                                            "detail",
-                                            format!(
-                                                "stackPush element type mismatch: expected {}, found {}",
-                                                expected_elem.display_name(),
-                                                elem_type.display_name()
-                                            ),
-                                        )
-                                        .with_help(format!(
-                                            "stack element type is {} — cannot push {}",
+                "stackPush" | "stack_push" if call.args.len() == 2 => {
+                    let stack_type = self.check_expr(&call.args[0]);
+                    let elem_type = self.check_expr(&call.args[1]);
+                    if let Some(expected_elem) = self.stack_elem_type(&stack_type) {
+                        if !Self::is_untyped_collection_elem(&expected_elem)
+                            && !self.is_assignable_with_traits(&elem_type, &expected_elem)
+                        {
+                            self.diagnostics.push(
+                                error_codes::TYPE_ERROR
+                                    .emit(call.args[1].span())
+                                    .arg(
+                                        "detail",
+                                        format!(
+                                            "stackPush element type mismatch: expected {}, found {}",
                                             expected_elem.display_name(),
                                             elem_type.display_name()
-                                        ))
-                                        .build()
-                                        .with_label("type mismatch"),
-                                );
-                            }
+                                        ),
+                                    )
+                                    .with_help(format!(
+                                        "stack element type is {} — cannot push {}",
+                                        expected_elem.display_name(),
+                                        elem_type.display_name()
+                                    ))
+                                    .build()
+                                    .with_label("type mismatch"),
+                            );
                         }
-                        return stack_type;
                     }
+                    return stack_type;
                 }
                 // Duration bare globals return Map<string, number>
                 "durationFromSeconds"
@@ -2181,6 +2500,17 @@ impl<'a> TypeChecker<'a> {
                     }
                     return Type::Bool;
                 }
+                // Embedded at compile time: a string, or the file's bytes
+                "includeStr" | "includeBytes" => {
+                    for arg in &call.args {
+                        let _ = self.check_expr(arg);
+                    }
+                    return if name == "includeStr" {
+                        Type::String
+                    } else {
+                        Type::Array(Box::new(Type::Number))
+                    };
+                }
                 // H-276: len() returns number
                 "len" => {
                     for arg in &call.args {
@@ -2188,28 +2518,25 @@ impl<'a> TypeChecker<'a> {
                     }
                     return Type::Number;
                 }
-                // H-276: typeof() returns string
-                "typeof" | "type_of" => {
+                // H-276: typeof() returns string, as does inspect()
+                "typeof" | "type_of" | "inspect" => {
                     for arg in &call.args {
                         let _ = self.check_expr(arg);
                     }
                     return Type::String;
                 }
                 // H-164: unwrap() returns the inner type T from Option<T> or Result<T, E>
-                "unwrap" | "expect" => {
-                    if call.args.len() == 1 {
-                        let arg_type = self.check_expr(&call.args[0]);
-                        let inner = match arg_type.normalized() {
-                            Type::Generic { name, type_args }
-                                if (name == "Option" || name == "Result")
-                                    && !type_args.is_empty() =>
-                            {
-                                type_args[0].clone()
-                            }
-                            _ => arg_type,
-                        };
-                        return inner;
-                    }
+                "unwrap" | "expect" if call.args.len() == 1 => {
+                    let arg_type = self.check_expr(&call.args[0]);
+                    let inner = match arg_type.normalized() {
+                        Type::Generic { name, type_args }
+                            if (name == "Option" || name == "Result") && !type_args.is_empty() =>
+                        {
+                            type_args[0].clone()
+                        }
+                        _ => arg_type,
+                    };
+                    return inner;
                 }
                 _ => {}
             }
@@ -2626,8 +2953,12 @@ impl<'a> TypeChecker<'a> {
     fn check_member(&mut self, member: &MemberExpr) -> Type {
         // Fast-path: static namespace identifiers (Json, Math, Env).
         // These are not registered in the symbol table — detect by identifier name.
+        // A variable of the same name shadows the namespace (H-305), so adding a
+        // namespace never breaks a script that already uses the name.
         if let crate::ast::Expr::Identifier(id) = member.target.as_ref() {
-            if let Some(ns_tag) = crate::method_dispatch::namespace_type_tag(&id.name) {
+            let ns_tag = crate::method_dispatch::namespace_type_tag(&id.name)
+                .filter(|_| self.symbol_table.lookup(&id.name).is_none());
+            if let Some(ns_tag) = ns_tag {
                 member.type_tag.set(Some(ns_tag));
 
                 // H-293: Json.parse<T>() returns Result<T, string> instead of Result<JsonValue, string>
@@ -2832,7 +3163,89 @@ impl<'a> TypeChecker<'a> {
 
         // Type-check the target expression
         let target_type = self.check_expr(&member.target);
+        if member.optional {
+            return self.check_null_safe_member(member, target_type);
+        }
+        self.check_member_of(member, target_type)
+    }
+
+    /// Check a `?.` access: check the member on the target with its missing
+    /// case (null, None, Err) removed, then add that case back to the result
+    fn check_null_safe_member(&mut self, member: &MemberExpr, target_type: Type) -> Type {
+        use crate::ast::NullSafeKind;
+
+        let target_norm = target_type.normalized();
+        if target_norm == Type::Unknown {
+            return Type::Unknown;
+        }
+        if Type::is_any_placeholder(&target_norm) {
+            member.null_safe.set(Some(NullSafeKind::Nullable));
+            return self.check_member_of(member, target_type);
+        }
+
+        match &target_norm {
+            Type::Generic { name, type_args } if name == "Option" && type_args.len() == 1 => {
+                member.null_safe.set(Some(NullSafeKind::Option));
+                let member_type = self.check_member_of(member, type_args[0].clone());
+                if member_type.normalized() == Type::Unknown {
+                    return Type::Unknown;
+                }
+                Type::Generic {
+                    name: "Option".to_string(),
+                    type_args: vec![member_type],
+                }
+            }
+            // `r?.m` on a Result keeps meaning `(r?).m`
+            Type::Generic { name, type_args } if name == "Result" && type_args.len() == 2 => {
+                member.null_safe.set(Some(NullSafeKind::Result));
+                let ok_type = self.check_try_operand(&target_type, member.target.span()).0;
+                self.check_member_of(member, ok_type)
+            }
+            // `j?.key` reads a key like `j["key"]`; `j?.method()` is a method call
+            Type::JsonValue if member.args.is_none() => {
+                member.null_safe.set(Some(NullSafeKind::Json));
+                Type::JsonValue
+            }
+            Type::JsonValue => {
+                member.null_safe.set(Some(NullSafeKind::Json));
+                let member_type = self.check_member_of(member, target_type);
+                if member_type.normalized() == Type::Unknown {
+                    return Type::Unknown;
+                }
+                Type::union(vec![member_type, Type::Null])
+            }
+            Type::Null => {
+                member.null_safe.set(Some(NullSafeKind::Nullable));
+                if let Some(args) = &member.args {
+                    for arg in args {
+                        self.check_expr(arg);
+                    }
+                }
+                Type::Null
+            }
+            Type::Union(members) if members.contains(&Type::Null) => {
+                member.null_safe.set(Some(NullSafeKind::Nullable));
+                let present: Vec<Type> = members
+                    .iter()
+                    .filter(|ty| **ty != Type::Null)
+                    .cloned()
+                    .collect();
+                let member_type = self.check_member_of(member, Type::union(present));
+                if member_type.normalized() == Type::Unknown {
+                    return Type::Unknown;
+                }
+                Type::union(vec![member_type, Type::Null])
+            }
+            // Never missing: an ordinary access
+            _ => {
+                member.null_safe.set(Some(NullSafeKind::Nullable));
+                self.check_member_of(member, target_type)
+            }
+        }
+    }
 
+    /// Check a member access on a target of type `target_type`
+    fn check_member_of(&mut self, member: &MemberExpr, target_type: Type) -> Type {
         // Annotate MemberExpr with TypeTag for method dispatch parity
         let type_tag = match target_type.normalized() {
             Type::JsonValue => Some(crate::method_dispatch::TypeTag::JsonValue),
@@ -2866,6 +3279,12 @@ impl<'a> TypeChecker<'a> {
             Type::Generic { ref name, .. } if name == "Regex" => {
                 Some(crate::method_dispatch::TypeTag::RegexValue)
             }
+            Type::Generic { ref name, .. } if name == "Bytes" => {
+                Some(crate::method_dispatch::TypeTag::Bytes)
+            }
+            Type::Generic { ref name, .. } if name == "FileHandle" => {
+                Some(crate::method_dispatch::TypeTag::FileHandle)
+            }
             Type::Generic { ref name, .. } if name == "HttpResponse" => {
                 Some(crate::method_dispatch::TypeTag::HttpResponse)
             }
@@ -2875,6 +3294,12 @@ impl<'a> TypeChecker<'a> {
             Type::Generic { ref name, .. } if name == "SqliteConnection" => {
                 Some(crate::method_dispatch::TypeTag::SqliteConnection)
             }
+            Type::Generic { ref name, .. } if name == "KvStore" => {
+                Some(crate::method_dispatch::TypeTag::KvStore)
+            }
+            Type::Generic { ref name, .. } if name == "ProgressBar" => {
+                Some(crate::method_dispatch::TypeTag::ProgressBar)
+            }
             Type::Generic { ref name, .. } if name == "Future" => {
                 Some(crate::method_dispatch::TypeTag::FutureValue)
             }
@@ -3723,6 +4148,25 @@ impl<'a> TypeChecker<'a> {
                 }
                 Type::union(result_types)
             }
+            Type::Generic { ref name, .. } if name == "Bytes" => {
+                if index_norm != Type::Number {
+                    self.diagnostics.push(
+                        error_codes::TYPE_ERROR
+                            .emit(index_expr.span())
+                            .arg(
+                                "detail",
+                                format!(
+                                    "Bytes index must be number, found {}",
+                                    index_type.display_name()
+                                ),
+                            )
+                            .with_help("use bytes.slice(start, end) to take a range of bytes")
+                            .build()
+                            .with_label("type mismatch"),
+                    );
+                }
+                Type::Number
+            }
             Type::Unknown => Type::Unknown,
             _ => {
                 self.diagnostics.push(
@@ -3884,18 +4328,6 @@ impl<'a> TypeChecker<'a> {
         unified
     }
 
-    /// Check if a pattern covers a given constructor name (including inside OR patterns)
-    fn pattern_covers_constructor(pattern: &crate::ast::Pattern, ctor: &str) -> bool {
-        use crate::ast::Pattern;
-        match pattern {
-            Pattern::Constructor { name, .. } => name.name == ctor,
-            Pattern::Or(alternatives, _) => alternatives
-                .iter()
-                .any(|alt| Self::pattern_covers_constructor(alt, ctor)),
-            _ => false,
-        }
-    }
-
     /// Check if a pattern covers a given bool literal (including inside OR patterns)
     fn pattern_covers_bool(pattern: &crate::ast::Pattern, val: bool) -> bool {
         use crate::ast::{Literal, Pattern};
@@ -3944,56 +4376,21 @@ impl<'a> TypeChecker<'a> {
             return;
         }
 
+        let array_ctors = match scrutinee_norm {
+            Type::Array(_) => exhaustiveness::array_arm_constructors(arms),
+            _ => None,
+        };
         match scrutinee_norm {
-            Type::Generic { name, .. } if name == "Option" => {
-                // Option<T> requires Some and None to be covered
-                let has_some = arms.iter().any(|arm| {
-                    arm.guard.is_none() && Self::pattern_covers_constructor(&arm.pattern, "Some")
-                });
-
-                let has_none = arms.iter().any(|arm| {
-                    arm.guard.is_none() && Self::pattern_covers_constructor(&arm.pattern, "None")
-                });
-
-                if !has_some || !has_none {
-                    let missing = if !has_some && !has_none {
-                        "Some(_), None".to_string()
-                    } else if !has_some {
-                        "Some(_)".to_string()
-                    } else {
-                        "None".to_string()
-                    };
-
-                    self.diagnostics.push(
-                        error_codes::NON_EXHAUSTIVE_MATCH
-                            .emit(match_span)
-                            .arg("missing", &missing)
-                            .with_help(format!("Add arm: {} => ...", missing))
-                            .build()
-                            .with_label("non-exhaustive"),
-                    );
-                }
-            }
-
-            Type::Generic { name, .. } if name == "Result" => {
-                // Result<T,E> requires Ok and Err to be covered
-                let has_ok = arms.iter().any(|arm| {
-                    arm.guard.is_none() && Self::pattern_covers_constructor(&arm.pattern, "Ok")
-                });
-
-                let has_err = arms.iter().any(|arm| {
-                    arm.guard.is_none() && Self::pattern_covers_constructor(&arm.pattern, "Err")
-                });
-
-                if !has_ok || !has_err {
-                    let missing = if !has_ok && !has_err {
-                        "Ok(_), Err(_)".to_string()
-                    } else if !has_ok {
-                        "Ok(_)".to_string()
-                    } else {
-                        "Err(_)".to_string()
-                    };
-
+            Type::Generic { name, .. } if name == "Option" || name == "Result" => {
+                // Option<T> needs Some and None, Result<T,E> needs Ok and Err,
+                // each with every payload shape covered
+                let ctors = if name == "Option" {
+                    vec![Ctor::new("Some", 1), Ctor::new("None", 0)]
+                } else {
+                    vec![Ctor::new("Ok", 1), Ctor::new("Err", 1)]
+                };
+                let missing = self.missing_cases(arms, &ctors).join(", ");
+                if !missing.is_empty() {
                     self.diagnostics.push(
                         error_codes::NON_EXHAUSTIVE_MATCH
                             .emit(match_span)
@@ -4034,7 +4431,23 @@ impl<'a> TypeChecker<'a> {
                 }
             }
 
-            Type::Number | Type::String | Type::Array(_) | Type::Null => {
+            Type::Array(_) if array_ctors.is_some() => {
+                // Rest patterns split arrays into finitely many length cases
+                let ctors = array_ctors.unwrap_or_default();
+                let missing = self.missing_cases(arms, &ctors).join(", ");
+                if !missing.is_empty() {
+                    self.diagnostics.push(
+                        error_codes::NON_EXHAUSTIVE_MATCH
+                            .emit(match_span)
+                            .arg("missing", &missing)
+                            .with_help(format!("Add arm: {} => ...", missing))
+                            .build()
+                            .with_label("non-exhaustive"),
+                    );
+                }
+            }
+
+            Type::Number | Type::String | Type::Array(_) | Type::Null | Type::JsonValue => {
                 // These types have infinite values - require wildcard
                 self.diagnostics.push(
                     error_codes::NON_EXHAUSTIVE_MATCH
@@ -4049,29 +4462,11 @@ impl<'a> TypeChecker<'a> {
                 );
             }
 
-            // H-230: user-defined enum — check that every declared variant is covered
+            // H-230: user-defined enum — check that every declared variant is
+            // covered, including the payloads of tuple variants
             Type::Generic { ref name, .. } => {
-                // Clone to avoid borrow conflict with self below
-                let variant_names: Option<Vec<String>> =
-                    self.enum_decls.get(name.as_str()).map(|decl| {
-                        decl.variants
-                            .iter()
-                            .map(|v| v.name().name.clone())
-                            .collect()
-                    });
-
-                if let Some(variant_names) = variant_names {
-                    let missing: Vec<String> = variant_names
-                        .iter()
-                        .filter(|vname| {
-                            !arms.iter().any(|arm| {
-                                arm.guard.is_none()
-                                    && Self::pattern_covers_enum_variant(&arm.pattern, vname)
-                            })
-                        })
-                        .cloned()
-                        .collect();
-
+                if let Some(ctors) = self.enum_constructors(name) {
+                    let missing = self.missing_cases(arms, &ctors);
                     if !missing.is_empty() {
                         let missing_str = missing.join(", ");
                         self.diagnostics.push(
@@ -4101,23 +4496,6 @@ impl<'a> TypeChecker<'a> {
         }
     }
 
-    /// Check if a pattern covers a specific enum variant name (by variant name only, not enum name).
-    /// Handles EnumVariant (qualified), BareVariant (bare), Constructor (legacy/builtin), and OR.
-    fn pattern_covers_enum_variant(pattern: &crate::ast::Pattern, variant_name: &str) -> bool {
-        use crate::ast::Pattern;
-        match pattern {
-            Pattern::EnumVariant {
-                variant_name: vn, ..
-            } => vn.name == variant_name,
-            Pattern::BareVariant { name, .. } => name.name == variant_name,
-            Pattern::Constructor { name, .. } => name.name == variant_name,
-            Pattern::Or(alternatives, _) => alternatives
-                .iter()
-                .any(|alt| Self::pattern_covers_enum_variant(alt, variant_name)),
-            _ => false,
-        }
-    }
-
     /// Check a pattern and return variable bindings (name, type, span)
     fn check_pattern(
         &mut self,
@@ -4140,6 +4518,10 @@ impl<'a> TypeChecker<'a> {
                         Literal::String(_) => Type::String,
                         Literal::Bool(_) => Type::Bool,
                         Literal::Null => Type::Null,
+                        Literal::Regex { .. } => Type::Generic {
+                            name: "Regex".to_string(),
+                            type_args: vec![],
+                        },
                     };
                     if !members
                         .iter()
@@ -4186,10 +4568,19 @@ impl<'a> TypeChecker<'a> {
                     );
                     return bindings;
                 }
-                Pattern::Array { elements, span } => {
+                Pattern::Array {
+                    elements,
+                    rest,
+                    span,
+                } => {
                     for member in &members {
                         if matches!(member.normalized(), Type::Array(_)) {
-                            return self.check_array_pattern(elements, member, *span);
+                            return self.check_array_pattern(
+                                elements,
+                                rest.as_deref(),
+                                member,
+                                *span,
+                            );
                         }
                     }
                     self.diagnostics.push(
@@ -4264,10 +4655,50 @@ impl<'a> TypeChecker<'a> {
                     }
                     return bindings;
                 }
+                // Range bounds compare as numbers, which a union value may not be
+                Pattern::Range { span, .. } => {
+                    self.diagnostics.push(
+                        error_codes::PATTERN_TYPE_MISMATCH
+                            .emit(*span)
+                            .arg("value_type", Type::Union(members).display_name())
+                            .arg("pattern_type", "range")
+                            .with_help("range patterns only match `number` values")
+                            .build()
+                            .with_label("type mismatch"),
+                    );
+                    return bindings;
+                }
             }
         }
 
         match pattern {
+            Pattern::Range {
+                start,
+                end,
+                inclusive,
+                span,
+            } => {
+                if !self.is_assignable_with_traits(&expected_norm, &Type::Number) {
+                    self.diagnostics.push(
+                        error_codes::PATTERN_TYPE_MISMATCH
+                            .emit(*span)
+                            .arg("value_type", expected_norm.display_name())
+                            .arg("pattern_type", "range")
+                            .with_help("range patterns only match `number` values")
+                            .build()
+                            .with_label("type mismatch"),
+                    );
+                } else if start > end || (start == end && !inclusive) {
+                    self.diagnostics.push(
+                        error_codes::TYPE_ERROR
+                            .emit(*span)
+                            .arg("detail", "range pattern can never match")
+                            .with_help("the start of a range pattern must be below its end")
+                            .build()
+                            .with_label("empty range"),
+                    );
+                }
+            }
             Pattern::Literal(lit, span) => {
                 // Check literal type matches expected type
                 let lit_type = match lit {
@@ -4275,9 +4706,16 @@ impl<'a> TypeChecker<'a> {
                     Literal::String(_) => Type::String,
                     Literal::Bool(_) => Type::Bool,
                     Literal::Null => Type::Null,
+                    Literal::Regex { .. } => Type::Generic {
+                        name: "Regex".to_string(),
+                        type_args: vec![],
+                    },
                 };
 
-                if !self.is_assignable_with_traits(&lit_type, &expected_norm) {
+                // A json value may be any scalar; the literal checks its kind at runtime
+                let json_scalar =
+                    expected_norm == Type::JsonValue && !matches!(lit, Literal::Regex { .. });
+                if !json_scalar && !self.is_assignable_with_traits(&lit_type, &expected_norm) {
                     self.diagnostics.push(
                         error_codes::PATTERN_TYPE_MISMATCH
                             .emit(*span)
@@ -4307,9 +4745,18 @@ impl<'a> TypeChecker<'a> {
                 bindings.extend(self.check_constructor_pattern(name, args, &expected_norm, *span));
             }
 
-            Pattern::Array { elements, span } => {
+            Pattern::Array {
+                elements,
+                rest,
+                span,
+            } => {
                 // Check array pattern
-                bindings.extend(self.check_array_pattern(elements, &expected_norm, *span));
+                bindings.extend(self.check_array_pattern(
+                    elements,
+                    rest.as_deref(),
+                    &expected_norm,
+                    *span,
+                ));
             }
 
             Pattern::Tuple { elements, span } => {
@@ -4367,15 +4814,13 @@ impl<'a> TypeChecker<'a> {
             }
 
             Pattern::Struct { fields, .. } => {
-                // Bind each field. For shorthand `{ x }`, bind x with Unknown type;
-                // for explicit `{ x: p }`, recurse into sub-pattern with Unknown type.
-                // Full struct type resolution is left to a future typechecker pass.
+                // Bind each field. Field types come from a Map's value type or a
+                // structural type's members; anything else binds as Unknown.
                 for field in fields {
+                    let field_ty = destructured_field_type(&expected_norm, &field.name.name);
                     match &field.pattern {
-                        Some(sub) => bindings.extend(self.check_pattern(sub, &Type::Unknown)),
-                        None => {
-                            bindings.push((field.name.name.clone(), Type::Unknown, field.name.span))
-                        }
+                        Some(sub) => bindings.extend(self.check_pattern(sub, &field_ty)),
+                        None => bindings.push((field.name.name.clone(), field_ty, field.name.span)),
                     }
                 }
             }
@@ -4607,6 +5052,7 @@ impl<'a> TypeChecker<'a> {
     fn check_array_pattern(
         &mut self,
         elements: &[Pattern],
+        rest: Option<&Pattern>,
         expected_type: &Type,
         span: Span,
     ) -> Vec<(String, Type, Span)> {
@@ -4619,6 +5065,19 @@ impl<'a> TypeChecker<'a> {
                 for pattern in elements {
                     bindings.extend(self.check_pattern(pattern, &elem_type));
                 }
+                // The rest pattern binds the remaining slice, an array itself
+                if let Some(rest) = rest {
+                    bindings.extend(self.check_pattern(rest, &Type::Array(elem_type)));
+                }
+            }
+            // A JSON array: elements and the rest slice are json too
+            Type::JsonValue => {
+                for pattern in elements {
+                    bindings.extend(self.check_pattern(pattern, &Type::JsonValue));
+                }
+                if let Some(rest) = rest {
+                    bindings.extend(self.check_pattern(rest, &Type::JsonValue));
+                }
             }
             _ => {
                 self.diagnostics.push(
@@ -4638,15 +5097,28 @@ impl<'a> TypeChecker<'a> {
 
     /// Check try expression (error propagation operator ?)
     fn check_try(&mut self, try_expr: &TryExpr) -> Type {
-        use crate::ast::TryTargetKind;
-
         // Type check the expression being tried
         let expr_type = self.check_expr(&try_expr.expr);
+        let (unwrapped, target_kind) = self.check_try_operand(&expr_type, try_expr.span);
+        // Annotate for compiler
+        *try_expr.target_kind.borrow_mut() = target_kind;
+        unwrapped
+    }
+
+    /// Check `?` applied to a value of `expr_type`: the unwrapped type, and
+    /// whether it unwraps a Result or an Option (None after an error)
+    pub(super) fn check_try_operand(
+        &mut self,
+        expr_type: &Type,
+        span: Span,
+    ) -> (Type, Option<crate::ast::TryTargetKind>) {
+        use crate::ast::TryTargetKind;
+
         let expr_norm = expr_type.normalized();
 
         // Skip if expression type is unknown (error already reported)
         if expr_norm == Type::Unknown {
-            return Type::Unknown;
+            return (Type::Unknown, None);
         }
 
         // Expression must be Result<T, E> or Option<T>
@@ -4669,13 +5141,13 @@ impl<'a> TypeChecker<'a> {
             }
             _ => {
                 self.diagnostics.push(
-                    error_codes::TYPE_ERROR.emit(try_expr.span)
+                    error_codes::TYPE_ERROR.emit(span)
                         .arg("detail", format!("? operator requires Result<T, E> or Option<T> type, found {}", expr_type.display_name()))
                         .with_help("the ? operator can only be applied to Result<T, E> or Option<T> values")
                         .build()
                         .with_label("not a Result or Option type"),
                 );
-                return Type::Unknown;
+                return (Type::Unknown, None);
             }
         };
 
@@ -4685,11 +5157,9 @@ impl<'a> TypeChecker<'a> {
 
         match source {
             TrySource::Result { ok_type, err_type } => {
-                // Annotate for compiler
-                *try_expr.target_kind.borrow_mut() = Some(TryTargetKind::Result);
-
+                let kind = Some(TryTargetKind::Result);
                 if is_top_level {
-                    return ok_type;
+                    return (ok_type, kind);
                 }
 
                 let function_return_type = self.current_function_return_type.clone().unwrap();
@@ -4716,7 +5186,7 @@ impl<'a> TypeChecker<'a> {
                         // Error types must be compatible (any-placeholder is always compatible)
                         if !err_is_any && !function_err_is_any && err_norm != function_err_norm {
                             self.diagnostics.push(
-                                error_codes::TYPE_ERROR.emit(try_expr.span)
+                                error_codes::TYPE_ERROR.emit(span)
                                     .arg("detail", format!(
                                         "? operator error type mismatch: expression has error type {}, but function returns {}",
                                         err_type.display_name(),
@@ -4731,20 +5201,18 @@ impl<'a> TypeChecker<'a> {
                             );
                         }
 
-                        ok_type
+                        (ok_type, kind)
                     }
                     _ => {
                         // Allow `?` as an unwrap in non-Result functions (runtime error on Err).
-                        ok_type
+                        (ok_type, kind)
                     }
                 }
             }
             TrySource::Option { inner_type } => {
-                // Annotate for compiler
-                *try_expr.target_kind.borrow_mut() = Some(TryTargetKind::Option);
-
+                let kind = Some(TryTargetKind::Option);
                 if is_top_level {
-                    return inner_type;
+                    return (inner_type, kind);
                 }
 
                 let function_return_type = self.current_function_return_type.clone().unwrap();
@@ -4755,11 +5223,11 @@ impl<'a> TypeChecker<'a> {
                     Type::Generic { name, type_args }
                         if name == "Option" && type_args.len() == 1 =>
                     {
-                        inner_type
+                        (inner_type, kind)
                     }
                     _ => {
                         // Allow `?` as an unwrap in non-Option functions (runtime error on None).
-                        inner_type
+                        (inner_type, kind)
                     }
                 }
             }
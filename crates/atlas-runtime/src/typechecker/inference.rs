@@ -483,6 +483,10 @@ fn collect_return_types(stmts: &[Stmt], return_types: &mut Vec<Type>) {
             Stmt::ForIn(for_in_stmt) => {
                 collect_return_types(&for_in_stmt.body.statements, return_types);
             }
+            Stmt::TryCatch(try_catch) => {
+                collect_return_types(&try_catch.body.statements, return_types);
+                collect_return_types(&try_catch.catch_block.statements, return_types);
+            }
             _ => {}
         }
     }
@@ -499,6 +503,10 @@ pub fn infer_expr_type(expr: &Expr) -> Type {
             Literal::String(_) => Type::String,
             Literal::Bool(_) => Type::Bool,
             Literal::Null => Type::Null,
+            Literal::Regex { .. } => Type::Generic {
+                name: "Regex".to_string(),
+                type_args: vec![],
+            },
         },
         Expr::TemplateString { .. } => Type::String,
         Expr::Binary(binary) => infer_binary_type(&binary.op),
@@ -529,7 +537,10 @@ fn infer_binary_type(op: &BinaryOp) -> Type {
         | BinaryOp::Gt
         | BinaryOp::Ge
         | BinaryOp::And
-        | BinaryOp::Or => Type::Bool,
+        | BinaryOp::Or
+        | BinaryOp::In => Type::Bool,
+        // The operand types are needed to name the result
+        BinaryOp::Coalesce => Type::Unknown,
     }
 }
 
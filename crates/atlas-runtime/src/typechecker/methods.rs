@@ -125,11 +125,15 @@ impl MethodTable {
             // H-231: DateTime, Regex, HttpResponse instance methods
             Type::Generic { ref name, .. } if name == "DateTime" => "DateTime",
             Type::Generic { ref name, .. } if name == "Regex" => "Regex",
+            Type::Generic { ref name, .. } if name == "Bytes" => "Bytes",
+            Type::Generic { ref name, .. } if name == "FileHandle" => "FileHandle",
             Type::Generic { ref name, .. } if name == "HttpResponse" => "HttpResponse",
             // B18: ProcessOutput instance methods
             Type::Generic { ref name, .. } if name == "ProcessOutput" => "ProcessOutput",
             // B40-P05: SqliteConnection instance methods
             Type::Generic { ref name, .. } if name == "SqliteConnection" => "SqliteConnection",
+            Type::Generic { ref name, .. } if name == "KvStore" => "KvStore",
+            Type::Generic { ref name, .. } if name == "ProgressBar" => "ProgressBar",
             // B33: Future instance methods
             Type::Generic { ref name, .. } if name == "Future" => "Future",
             _ => return None,
@@ -174,6 +178,20 @@ impl MethodTable {
         self.register("json", "getArray", vec![Type::String], option_json_array);
         self.register("json", "getObject", vec![Type::String], option_json);
 
+        // JSON path queries (`a.b[2].c`)
+        let json_result = Type::Generic {
+            name: "Result".to_string(),
+            type_args: vec![Type::JsonValue, Type::String],
+        };
+        self.register("json", "getPath", vec![Type::String], json_result.clone());
+        self.register(
+            "json",
+            "setPath",
+            vec![Type::String, Type::any_placeholder()],
+            json_result,
+        );
+        self.register("json", "hasPath", vec![Type::String], Type::Bool);
+
         // number instance methods (H-260 — D-021 TypeScript parity)
         self.register("number", "toString", vec![], Type::String);
         self.register("number", "toFixed", vec![Type::Number], Type::String);
@@ -308,6 +326,15 @@ impl MethodTable {
             },
         );
         self.register("string", "toBool", vec![], Type::Bool);
+        self.register(
+            "string",
+            "toBytes",
+            vec![],
+            Type::Generic {
+                name: "Bytes".to_string(),
+                type_args: vec![],
+            },
+        );
 
         // H-231: DateTime instance methods
         let datetime_ty = Type::Generic {
@@ -370,8 +397,72 @@ impl MethodTable {
             datetime_ty.clone(),
         );
         self.register("DateTime", "diff", vec![datetime_ty.clone()], Type::Number);
+        self.register(
+            "DateTime",
+            "diffSeconds",
+            vec![datetime_ty.clone()],
+            Type::Number,
+        );
         self.register("DateTime", "compare", vec![datetime_ty], Type::Number);
 
+        // Bytes instance methods
+        let bytes_ty = Type::Generic {
+            name: "Bytes".to_string(),
+            type_args: vec![],
+        };
+        self.register("Bytes", "len", vec![], Type::Number);
+        self.register("Bytes", "length", vec![], Type::Number);
+        self.register(
+            "Bytes",
+            "slice",
+            vec![Type::Number, Type::Number],
+            bytes_ty.clone(),
+        );
+        self.register("Bytes", "toHex", vec![], Type::String);
+        self.register("Bytes", "toBase64", vec![], Type::String);
+        self.register(
+            "Bytes",
+            "toArray",
+            vec![],
+            Type::Array(Box::new(Type::Number)),
+        );
+        self.register(
+            "Bytes",
+            "toUtf8",
+            vec![],
+            Type::Generic {
+                name: "Result".to_string(),
+                type_args: vec![Type::String, Type::String],
+            },
+        );
+
+        // FileHandle instance methods
+        self.register(
+            "FileHandle",
+            "readLine",
+            vec![],
+            Type::Generic {
+                name: "Option".to_string(),
+                type_args: vec![Type::String],
+            },
+        );
+        self.register(
+            "FileHandle",
+            "readChunk",
+            vec![Type::Number],
+            Type::Generic {
+                name: "Option".to_string(),
+                type_args: vec![bytes_ty.clone()],
+            },
+        );
+        self.register(
+            "FileHandle",
+            "writeChunk",
+            vec![Type::union(vec![Type::String, bytes_ty])],
+            Type::Null,
+        );
+        self.register("FileHandle", "close", vec![], Type::Null);
+
         // H-231: HttpResponse instance methods
         let headers_ty = Type::Generic {
             name: "Map".to_string(),
@@ -391,6 +482,15 @@ impl MethodTable {
         );
         self.register("HttpResponse", "url", vec![], Type::String);
         self.register("HttpResponse", "isSuccess", vec![], Type::Bool);
+        self.register(
+            "HttpResponse",
+            "toMap",
+            vec![],
+            Type::Generic {
+                name: "Map".to_string(),
+                type_args: vec![Type::String, Type::Unknown],
+            },
+        );
 
         // B18: ProcessOutput instance methods
         self.register("ProcessOutput", "stdout", vec![], Type::String);
@@ -426,7 +526,50 @@ impl MethodTable {
             result_arr_str,
         );
         // close() -> Result<null, string>
-        self.register("SqliteConnection", "close", vec![], result_null_str);
+        self.register("SqliteConnection", "close", vec![], result_null_str.clone());
+        // begin() / commit() / rollback() -> Result<null, string>
+        for method in ["begin", "commit", "rollback"] {
+            self.register("SqliteConnection", method, vec![], result_null_str.clone());
+        }
+        self.register("SqliteConnection", "inTransaction", vec![], Type::Bool);
+
+        // Key-value store instance methods
+        self.register(
+            "KvStore",
+            "get",
+            vec![Type::String],
+            Type::Generic {
+                name: "Option".to_string(),
+                type_args: vec![Type::any_placeholder()],
+            },
+        );
+        self.register(
+            "KvStore",
+            "set",
+            vec![Type::String, Type::any_placeholder()],
+            Type::Null,
+        );
+        self.register("KvStore", "delete", vec![Type::String], Type::Bool);
+        self.register("KvStore", "has", vec![Type::String], Type::Bool);
+        self.register(
+            "KvStore",
+            "keys",
+            vec![],
+            Type::Array(Box::new(Type::String)),
+        );
+        self.register(
+            "KvStore",
+            "transaction",
+            vec![Type::Unknown], // closure: KvStore -> T
+            Type::any_placeholder(),
+        );
+        self.register("KvStore", "close", vec![], Type::Null);
+
+        // Progress bar instance methods (console.progressBar)
+        self.register("ProgressBar", "advance", vec![Type::Number], Type::Null);
+        self.register("ProgressBar", "set", vec![Type::Number], Type::Null);
+        self.register("ProgressBar", "setLabel", vec![Type::String], Type::Null);
+        self.register("ProgressBar", "finish", vec![], Type::Null);
 
         // B33: Future instance methods
         let future_type = Type::Generic {
@@ -6,6 +6,7 @@
 //! - No truthy/falsey - conditionals require bool
 //! - Strict equality - == requires same-type operands
 
+mod exhaustiveness;
 mod expr;
 pub mod flow_sensitive;
 pub mod generics;
@@ -364,9 +365,10 @@ pub struct TypeChecker<'a> {
     /// explicitly in source (`borrow x`, `own x`, `share x`). Bare params are absent
     /// from this set — AT3054 only fires for explicit `borrow`, not implicit defaults (D-040).
     pub(super) current_fn_explicit_borrow_params: HashSet<String>,
-    /// Variables that have been moved via an `own` parameter call in the current function scope.
-    /// Any subsequent use of a name in this set triggers AT3053 (use-after-own).
-    pub(super) moved_vars: HashSet<String>,
+    /// Variables that have been moved via an `own` parameter call in the current function scope,
+    /// mapped to the span of the moving argument. Any subsequent use of a name in this map
+    /// triggers AT3053 (use-after-own), with the move site attached as a related location.
+    pub(super) moved_vars: HashMap<String, Span>,
     /// Registry of all known traits (built-in + user-defined).
     pub trait_registry: TraitRegistry,
     /// Registry of all impl blocks keyed by (type_name, trait_name).
@@ -471,7 +473,7 @@ impl<'a> TypeChecker<'a> {
             fn_ownership_registry: stdlib_ownership::build_stdlib_ownership(),
             current_fn_param_ownerships: HashMap::new(),
             current_fn_explicit_borrow_params: HashSet::new(),
-            moved_vars: HashSet::new(),
+            moved_vars: HashMap::new(),
             trait_registry: TraitRegistry::new(),
             impl_registry: ImplRegistry::default(),
             inherent_registry: HashMap::new(),
@@ -605,7 +607,12 @@ impl<'a> TypeChecker<'a> {
     /// Check a top-level item
     fn check_item(&mut self, item: &Item) {
         match item {
-            Item::Function(func) => self.check_function(func),
+            Item::Function(func) => {
+                if func.name.name == "main" {
+                    self.check_entry_point(func);
+                }
+                self.check_function(func);
+            }
             Item::Statement(stmt) => self.check_statement(stmt),
             Item::Import(_) => {
                 // Import type checking handled in BLOCKER 04-C (cross-module types)
@@ -1715,6 +1722,75 @@ impl<'a> TypeChecker<'a> {
         self.moved_vars = prev_moved_vars;
     }
 
+    /// Validate a top-level `fn main` against the entry-point contract:
+    /// `fn main()` or `fn main(args: string[])`, returning nothing, a
+    /// `number` exit code, or a `Result` (Ok exits 0, Err exits 1).
+    fn check_entry_point(&mut self, func: &FunctionDecl) {
+        let mut problems: Vec<(Span, String)> = Vec::new();
+
+        if !func.type_params.is_empty() {
+            problems.push((func.name.span, "`main` cannot be generic".to_string()));
+        }
+
+        match func.params.as_slice() {
+            [] => {}
+            [param] => {
+                let param_type = self.resolve_type_ref(&param.type_ref);
+                let is_string_array = match param_type.normalized() {
+                    Type::Array(elem) => *elem == Type::String,
+                    Type::Generic { name, type_args } => {
+                        name == "Array" && type_args == [Type::String]
+                    }
+                    _ => false,
+                };
+                if !is_string_array || param.is_rest || param.default_value.is_some() {
+                    problems.push((
+                        param.span,
+                        format!(
+                            "`main` takes its arguments as `string[]`, found `{}`",
+                            param_type.display_name()
+                        ),
+                    ));
+                }
+            }
+            [_, extra, ..] => problems.push((
+                extra.span,
+                format!(
+                    "`main` takes at most one parameter, found {}",
+                    func.params.len()
+                ),
+            )),
+        }
+
+        if let Some(return_ref) = &func.return_type {
+            let return_type = self.resolve_type_ref(return_ref);
+            let valid = match return_type.normalized() {
+                Type::Number | Type::Void | Type::Null => true,
+                Type::Generic { name, .. } => name == "Result",
+                _ => false,
+            };
+            if !valid {
+                problems.push((
+                    func.name.span,
+                    format!(
+                        "`main` must return `number`, `Result` or nothing, found `{}`",
+                        return_type.display_name()
+                    ),
+                ));
+            }
+        }
+
+        for (span, detail) in problems {
+            self.diagnostics.push(
+                error_codes::INVALID_MAIN_SIGNATURE
+                    .emit(span)
+                    .arg("detail", detail)
+                    .build()
+                    .with_label("entry point declared here"),
+            );
+        }
+    }
+
     fn check_function(&mut self, func: &FunctionDecl) {
         // Track required arity for call-site checking (B39-P05)
         // Rest params don't contribute to required arity — they collect zero or more extra args.
@@ -2140,6 +2216,10 @@ impl<'a> TypeChecker<'a> {
                     false
                 }
             }
+            // A try/catch discards tail expressions, so only explicit returns count
+            Stmt::TryCatch(try_catch) => [&try_catch.body, &try_catch.catch_block]
+                .iter()
+                .all(|b| b.tail_expr.is_none() && self.block_always_returns(b)),
             // H-278: Expression statements that call functions returning `never` terminate
             Stmt::Expr(expr_stmt) => self.expr_returns_never(&expr_stmt.expr),
             _ => false,
@@ -2231,7 +2311,7 @@ impl<'a> TypeChecker<'a> {
                     if is_explicit_borrow && !is_primitive {
                         self.diagnostics.push(
                             error_codes::BORROW_ESCAPE.emit(var.span)
-                                .arg("name", &id.name)
+                                .arg("name", id.name.as_str())
                                 .build()
                                 .with_label("borrow stored here")
                                 .with_help(
@@ -2351,6 +2431,9 @@ impl<'a> TypeChecker<'a> {
                 if drop_type_name.is_some() {
                     var.needs_drop.replace(drop_type_name);
                 }
+                if var.type_ref.is_none() {
+                    var.inferred_type.replace(Some(final_type.clone()));
+                }
 
                 // Update the symbol's type in the symbol table.
                 // IMPORTANT: Only update the symbol if it exists in the CURRENT scope.
@@ -2385,43 +2468,13 @@ impl<'a> TypeChecker<'a> {
             }
             Stmt::LetDestructure(d) => {
                 let init_type = self.check_expr(&d.init);
-                let elem_types: Vec<Type> = match init_type.normalized() {
-                    Type::Tuple(elems) => elems.clone(),
-                    // Unknown or internal any-placeholder: cannot verify statically — bind as Unknown
-                    ref t
-                        if matches!(t, Type::Unknown)
-                            || crate::types::Type::is_any_placeholder(t) =>
-                    {
-                        (0..d.names.len()).map(|_| Type::Unknown).collect()
-                    }
-                    other => {
-                        self.diagnostics.push(
-                            crate::diagnostic::Diagnostic::error(
-                                format!(
-                                    "Cannot destructure: expected a tuple, got {}",
-                                    other.display_name()
-                                ),
-                                d.span,
-                            )
-                            .with_label("not a tuple")
-                            .with_help("wrap values in a tuple: `(a, b)`"),
-                        );
-                        (0..d.names.len()).map(|_| Type::Unknown).collect()
+                let elem_types = match d.kind {
+                    DestructureKind::Tuple => self.tuple_destructure_types(d, &init_type),
+                    DestructureKind::Array { rest } => {
+                        self.array_destructure_types(d, rest, &init_type)
                     }
+                    DestructureKind::Map => self.map_destructure_types(d, &init_type),
                 };
-                if elem_types.len() != d.names.len() && !matches!(init_type, Type::Unknown) {
-                    self.diagnostics.push(
-                        crate::diagnostic::Diagnostic::error(
-                            format!(
-                                "Tuple destructure mismatch: pattern has {} names but tuple has {} elements",
-                                d.names.len(),
-                                elem_types.len()
-                            ),
-                            d.span,
-                        )
-                        .with_label("arity mismatch here"),
-                    );
-                }
                 for (name, ty) in d
                     .names
                     .iter()
@@ -2503,7 +2556,7 @@ impl<'a> TypeChecker<'a> {
                             error_codes::SHARE_VIOLATION
                                 .emit(assign.span)
                                 .arg("action", "assign to")
-                                .arg("name", &id.name)
+                                .arg("name", id.name.as_str())
                                 .build()
                                 .with_label("assignment to share param")
                                 .with_help(
@@ -2520,10 +2573,7 @@ impl<'a> TypeChecker<'a> {
                         if !symbol.mutable {
                             let diag = error_codes::IMMUTABLE_ASSIGNMENT
                                 .emit(id.span)
-                                .arg(
-                                    "detail",
-                                    format!("Cannot assign to immutable variable '{}'", id.name),
-                                )
+                                .arg("name", id.name.as_str())
                                 .build()
                                 .with_label("immutable variable")
                                 .with_related_location(crate::diagnostic::RelatedLocation {
@@ -2599,10 +2649,7 @@ impl<'a> TypeChecker<'a> {
                         if !symbol.mutable {
                             let diag = error_codes::IMMUTABLE_ASSIGNMENT
                                 .emit(id.span)
-                                .arg(
-                                    "detail",
-                                    format!("Cannot modify immutable variable '{}'", id.name),
-                                )
+                                .arg("name", id.name.as_str())
                                 .build()
                                 .with_label("immutable variable");
                             self.diagnostics.push(diag);
@@ -2709,7 +2756,7 @@ impl<'a> TypeChecker<'a> {
                         if is_explicit_borrow && !is_primitive {
                             self.diagnostics.push(
                                 error_codes::BORROW_ESCAPE.emit(ret.span)
-                                    .arg("name", &id.name)
+                                    .arg("name", id.name.as_str())
                                     .build()
                                     .with_label("borrow escapes here")
                                     .with_help(
@@ -2849,9 +2896,11 @@ impl<'a> TypeChecker<'a> {
                     Type::TypeParameter { ref name } if name == ANY_TYPE_PARAM
                 );
 
-                // Validate iterable is an array or range (or any-placeholder for dynamic cases)
+                // Validate iterable is an array, range, Map or Set (or any-placeholder
+                // for dynamic cases)
                 match iterable_norm {
                     Type::Array(_) | Type::Range => {}
+                    Type::Generic { ref name, .. } if name == "Map" || name == "Set" => {}
                     _ if iterable_is_any => {}
                     _ => {
                         self.diagnostics.push(
@@ -2860,7 +2909,7 @@ impl<'a> TypeChecker<'a> {
                                 .arg(
                                     "detail",
                                     format!(
-                                        "for-in requires an array, found {}",
+                                        "for-in requires an array, range, Map or Set, found {}",
                                         iterable_type.display_name()
                                     ),
                                 )
@@ -2878,6 +2927,13 @@ impl<'a> TypeChecker<'a> {
                 let inferred = match &iterable_norm {
                     Type::Array(element_type) => (**element_type).clone(),
                     Type::Range => Type::Number, // H-116: range iteration yields numbers
+                    // Map iteration yields (key, value) tuples, Set iteration its elements
+                    Type::Generic { name, type_args } if name == "Map" && type_args.len() == 2 => {
+                        Type::Tuple(type_args.clone())
+                    }
+                    Type::Generic { name, type_args } if name == "Set" && type_args.len() == 1 => {
+                        type_args[0].clone()
+                    }
                     _ if iterable_is_any => Type::any_placeholder(),
                     _ => Type::Unknown,
                 };
@@ -2909,7 +2965,161 @@ impl<'a> TypeChecker<'a> {
                 // Note: defer blocks cannot contain return/break/continue
                 self.check_block(&defer.body);
             }
+            Stmt::TryCatch(try_catch) => {
+                self.enter_scope();
+                self.check_block(&try_catch.body);
+                self.exit_scope();
+
+                self.enter_scope();
+                if let Some(binding) = &try_catch.error_binding {
+                    let symbol = crate::symbol::Symbol {
+                        name: binding.name.clone(),
+                        ty: crate::value::RuntimeError::record_type(),
+                        span: binding.span,
+                        mutable: false,
+                        kind: crate::symbol::SymbolKind::Variable,
+                        exported: false,
+                        visibility: crate::ast::Visibility::Private,
+                    };
+                    let _ = self.symbol_table.define(symbol);
+                    self.declared_symbols
+                        .insert(binding.name.clone(), (binding.span, SymbolKind::Variable));
+                }
+                self.check_block(&try_catch.catch_block);
+                self.exit_scope();
+            }
+        }
+    }
+
+    /// Types bound by `let (a, b) = init;`, one per name
+    fn tuple_destructure_types(&mut self, d: &LetDestructure, init_type: &Type) -> Vec<Type> {
+        let elem_types: Vec<Type> = match init_type.normalized() {
+            Type::Tuple(elems) => elems.clone(),
+            // Unknown or internal any-placeholder: cannot verify statically — bind as Unknown
+            ref t if matches!(t, Type::Unknown) || crate::types::Type::is_any_placeholder(t) => {
+                (0..d.names.len()).map(|_| Type::Unknown).collect()
+            }
+            other => {
+                self.diagnostics.push(
+                    error_codes::TYPE_ERROR
+                        .emit(d.span)
+                        .arg(
+                            "detail",
+                            format!(
+                                "Cannot destructure: expected a tuple, got {}",
+                                other.display_name()
+                            ),
+                        )
+                        .with_help("wrap values in a tuple: `(a, b)`")
+                        .build()
+                        .with_label("not a tuple"),
+                );
+                (0..d.names.len()).map(|_| Type::Unknown).collect()
+            }
+        };
+        if elem_types.len() != d.names.len() && !matches!(init_type, Type::Unknown) {
+            self.diagnostics.push(
+                error_codes::TYPE_ERROR
+                    .emit(d.span)
+                    .arg(
+                        "detail",
+                        format!(
+                            "Tuple destructure mismatch: pattern has {} names but tuple has {} elements",
+                            d.names.len(),
+                            elem_types.len()
+                        ),
+                    )
+                    .build()
+                    .with_label("arity mismatch here"),
+            );
+        }
+        elem_types
+    }
+
+    /// Types bound by `let [a, b, ...rest] = init;`: the element type for each
+    /// positional name and the array type itself for `rest`
+    fn array_destructure_types(
+        &mut self,
+        d: &LetDestructure,
+        rest: bool,
+        init_type: &Type,
+    ) -> Vec<Type> {
+        let elem_type = match init_type.normalized() {
+            Type::Array(elem) => *elem,
+            ref t if matches!(t, Type::Unknown) || crate::types::Type::is_any_placeholder(t) => {
+                Type::Unknown
+            }
+            other => {
+                self.diagnostics.push(
+                    error_codes::ARRAY_PATTERN_TYPE_MISMATCH
+                        .emit(d.span)
+                        .arg("expected", "array")
+                        .arg("found", other.display_name())
+                        .with_help("array patterns can only destructure array values")
+                        .build()
+                        .with_label("not an array"),
+                );
+                Type::Unknown
+            }
+        };
+        let positional = d.names.len() - usize::from(rest);
+        let mut types = vec![elem_type.clone(); positional];
+        if rest {
+            types.push(match elem_type {
+                Type::Unknown => Type::Unknown,
+                elem => Type::Array(Box::new(elem)),
+            });
+        }
+        types
+    }
+
+    /// Types bound by `let { a, b } = init;`: the Map's value type, or each
+    /// member's type for a record
+    fn map_destructure_types(&mut self, d: &LetDestructure, init_type: &Type) -> Vec<Type> {
+        match init_type.normalized() {
+            Type::Structural { ref members } => {
+                for name in &d.names {
+                    if !members.iter().any(|member| member.name == name.name) {
+                        self.diagnostics.push(
+                            error_codes::TYPE_ERROR
+                                .emit(name.span)
+                                .arg(
+                                    "detail",
+                                    format!(
+                                        "{} has no field `{}`",
+                                        init_type.display_name(),
+                                        name.name
+                                    ),
+                                )
+                                .build()
+                                .with_label("unknown field"),
+                        );
+                    }
+                }
+            }
+            Type::Generic { ref name, .. } if name == "Map" => {}
+            ref t if matches!(t, Type::Unknown) || crate::types::Type::is_any_placeholder(t) => {}
+            other => {
+                self.diagnostics.push(
+                    error_codes::TYPE_ERROR
+                        .emit(d.span)
+                        .arg(
+                            "detail",
+                            format!(
+                                "Cannot destructure: expected a Map or record, got {}",
+                                other.display_name()
+                            ),
+                        )
+                        .with_help("`let { key } = value;` reads entries of a Map or record")
+                        .build()
+                        .with_label("not a Map or record"),
+                );
+            }
         }
+        d.names
+            .iter()
+            .map(|name| expr::destructured_field_type(init_type, &name.name))
+            .collect()
     }
 
     fn apply_narrowings(&mut self, narrowings: &HashMap<String, Type>) {
@@ -3506,6 +3716,7 @@ impl<'a> TypeChecker<'a> {
                 "null" => Type::Null,
                 "any" => Type::any_placeholder(),
                 "json" => Type::JsonValue,
+                "range" => Type::Range,
                 "array" => Type::Array(Box::new(Type::any_placeholder())),
                 "Map" => Type::Generic {
                     name: "Map".to_string(),
@@ -3572,9 +3783,19 @@ impl<'a> TypeChecker<'a> {
                         return Type::TraitObject { name: name.clone() };
                     }
 
-                    // H-231: built-in opaque types (DateTime, HttpResponse, Regex).
+                    // H-231: built-in opaque types (DateTime, HttpResponse, Regex, KvStore,
+                    // ProgressBar, Bytes, FileHandle).
                     // These are not user-defined structs but are valid first-class types.
-                    if matches!(name.as_str(), "DateTime" | "HttpResponse" | "Regex") {
+                    if matches!(
+                        name.as_str(),
+                        "DateTime"
+                            | "HttpResponse"
+                            | "Regex"
+                            | "KvStore"
+                            | "ProgressBar"
+                            | "Bytes"
+                            | "FileHandle"
+                    ) {
                         return Type::Generic {
                             name: name.clone(),
                             type_args: vec![],
@@ -24,6 +24,9 @@ impl<'a> TypeChecker<'a> {
                     {
                         return self.narrow_name_by_type(&name, &target, is_equal);
                     }
+                    if let Some(name) = null_safe_guard(&binary.left, &binary.right) {
+                        return self.narrow_null_safe_root(&name, is_equal);
+                    }
                     (HashMap::new(), HashMap::new())
                 }
                 BinaryOp::And => {
@@ -172,6 +175,27 @@ impl<'a> TypeChecker<'a> {
         Some((arg_name, guard.target.clone()))
     }
 
+    /// `x?.a != null` means `x` is not null; `x?.a == null` says nothing
+    /// about `x`, since the member itself may be null
+    fn narrow_null_safe_root(
+        &self,
+        name: &str,
+        is_equal: bool,
+    ) -> (HashMap<String, Type>, HashMap<String, Type>) {
+        let mut present_map = HashMap::new();
+        if let Some(symbol) = self.symbol_table.lookup(name) {
+            let present = exclude_from(&symbol.ty, &Type::Null);
+            if present != Type::Never {
+                present_map.insert(name.to_string(), present);
+            }
+        }
+        if is_equal {
+            (HashMap::new(), present_map)
+        } else {
+            (present_map, HashMap::new())
+        }
+    }
+
     fn narrow_name_by_type(
         &self,
         name: &str,
@@ -210,6 +234,33 @@ impl<'a> TypeChecker<'a> {
     }
 }
 
+/// The variable at the root of a `?.` chain compared with `null`: `x` in `x?.a?.b == null`
+fn null_safe_guard(left: &Expr, right: &Expr) -> Option<String> {
+    let chain = match (left, right) {
+        (chain, Expr::Literal(Literal::Null, _)) | (Expr::Literal(Literal::Null, _), chain) => {
+            chain
+        }
+        _ => return None,
+    };
+    let Expr::Member(member) = chain else {
+        return None;
+    };
+    if !member.optional {
+        return None;
+    }
+    let mut root = member.target.as_ref();
+    while let Expr::Member(inner) = root {
+        if !inner.optional {
+            return None;
+        }
+        root = inner.target.as_ref();
+    }
+    match root {
+        Expr::Identifier(id) => Some(id.name.clone()),
+        _ => None,
+    }
+}
+
 fn typeof_target(expr: &Expr) -> Option<String> {
     if let Expr::Call(call) = expr {
         let callee_name = match &*call.callee {
@@ -233,6 +284,7 @@ fn type_from_typeof_value(value: &str) -> Option<Type> {
         "boolean" => Some(Type::Bool),
         "null" => Some(Type::Null),
         "array" => Some(Type::Array(Box::new(Type::any_placeholder()))),
+        "range" => Some(Type::Range),
         "function" => Some(Type::Function {
             type_params: Vec::new(),
             params: Vec::new(),
@@ -22,12 +22,14 @@ pub fn build_stdlib_ownership() -> HashMap<String, FnOwnershipEntry> {
     for name in [
         "channelUnbounded",
         "dateTimeNow",
+        "dateTimeTimestamp",
         "dateTimeUtc",
         "fsGetTempDir",
         "fsTmpdir",
         "fsTmpfile",
         "getCwd",
         "getPid",
+        "processNsGc",
         "mapNew",
         "setNew",
         "listEnv",
@@ -109,6 +111,7 @@ pub fn build_stdlib_ownership() -> HashMap<String, FnOwnershipEntry> {
         "durationFromSeconds",
         "Err",
         "exec",
+        "execCapture",
         "fileExists",
         "fileInfo",
         "flatten",
@@ -161,10 +164,24 @@ pub fn build_stdlib_ownership() -> HashMap<String, FnOwnershipEntry> {
         "setToArray",
         "encodingNsHexDecode",
         "encodingNsHexEncode",
+        "encodingNsFromArray",
+        "encodingNsFromBase64",
+        "encodingNsFromHex",
+        // Bytes instance method helpers
+        "bytesLen",
+        "bytesToArray",
+        "bytesToBase64",
+        "bytesToHex",
+        "bytesToUtf8",
+        "stringToBytes",
+        // FileHandle instance method helpers
+        "fileHandleClose",
+        "fileHandleReadLine",
         // HttpResponse instance method helpers
         "httpBody",
         "httpHeaders",
         "httpIsSuccess",
+        "httpResponseToMap",
         "httpStatus",
         "httpUrl",
         // B28: http namespace API
@@ -173,6 +190,7 @@ pub fn build_stdlib_ownership() -> HashMap<String, FnOwnershipEntry> {
         "httpNsPut",
         "httpNsDelete",
         "httpNsPatch",
+        "httpNsRequest",
         // Async http (separate feature)
         "httpDeleteAsync",
         "httpGetAsync",
@@ -204,6 +222,7 @@ pub fn build_stdlib_ownership() -> HashMap<String, FnOwnershipEntry> {
         "len",
         "log",
         // minifyJSON removed B23
+        "netClose",
         "Ok",
         "parseFloat",
         // parseJSON removed B23
@@ -319,6 +338,7 @@ pub fn build_stdlib_ownership() -> HashMap<String, FnOwnershipEntry> {
         "typeof",
         "udpBind",
         "udpClose",
+        "udpConnect",
         "udpLocalAddr",
         "udpReceive",
         "unsetEnv",
@@ -373,6 +393,8 @@ pub fn build_stdlib_ownership() -> HashMap<String, FnOwnershipEntry> {
         "dateTimeTryParse",
         "endsWith",
         "expect",
+        "fileHandleReadChunk",
+        "fileHandleWriteChunk",
         "fsFilterEntries",
         "fsSymlink",
         "futureCatch",
@@ -411,6 +433,8 @@ pub fn build_stdlib_ownership() -> HashMap<String, FnOwnershipEntry> {
         "lastIndexOf",
         "max",
         "min",
+        "netRecv",
+        "netSend",
         "pathEquals",
         "pathJoin",
         "pathRelative",
@@ -462,6 +486,7 @@ pub fn build_stdlib_ownership() -> HashMap<String, FnOwnershipEntry> {
     // ── 3-param functions ────────────────────────────────────────────────────
     for name in [
         "atomicCompareExchange",
+        "bytesSlice",
         "clamp",
         "mapSet",
         "cryptoNsHmac",
@@ -490,10 +515,19 @@ pub fn build_stdlib_ownership() -> HashMap<String, FnOwnershipEntry> {
     m.insert("zipAddFile".to_string(), b(2));
     // zipCreate(output, files...) — 1 required
     m.insert("zipCreate".to_string(), b(1));
+    // inspect(value, options?) — 1 required
+    m.insert("inspect".to_string(), b(1));
+    // csv.parse(text, options?) / csv.parseRecords / csv.stringify(rows, options?) — 1 required
+    m.insert("csvNsParse".to_string(), b(1));
+    m.insert("csvNsParseRecords".to_string(), b(1));
+    m.insert("csvNsStringify".to_string(), b(1));
     // zipCreateWithComment(output, comment, files...) — 2 required
     m.insert("zipCreateWithComment".to_string(), b(2));
     // arraySort(arr, compareFn?) — 1 required
     m.insert("arraySort".to_string(), b(1));
+    // dateTimeFormatDate(ts, format, tz?) / dateTimeParseDate(text, format, tz?) — 2 required
+    m.insert("dateTimeFormatDate".to_string(), b(2));
+    m.insert("dateTimeParseDate".to_string(), b(2));
     // crypto.hmacVerify(key, data, sig, algo) — 4 required
     m.insert("cryptoNsHmacVerify".to_string(), b(4));
 
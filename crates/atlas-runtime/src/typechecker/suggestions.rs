@@ -264,10 +264,11 @@ pub fn suggest_for_in_fix(found: &Type) -> String {
                 .to_string()
         }
         Type::Number => {
-            "numbers are not iterable; use `range(0, n)` to iterate over a range".to_string()
+            "numbers are not iterable; use `0..n` or `Array.range(0, n)` to iterate over a range"
+                .to_string()
         }
         _ => format!(
-            "for-in requires an array, found {}; wrap in an array or use a different loop",
+            "for-in requires an array, range, Map or Set, found {}; wrap in an array or use a different loop",
             found.display_name()
         ),
     }
@@ -450,6 +450,47 @@ impl<T> Shared<T> {
     pub fn is_exclusively_owned(&self) -> bool {
         Arc::strong_count(&self.0) == 1
     }
+
+    /// Weak handle to the cell, used by the cycle collector.
+    pub(crate) fn downgrade(&self) -> std::sync::Weak<Mutex<T>> {
+        Arc::downgrade(&self.0)
+    }
+
+    /// Address of the cell (identity for the cycle collector).
+    pub(crate) fn as_ptr(&self) -> *const Mutex<T> {
+        Arc::as_ptr(&self.0)
+    }
+}
+
+/// `shared` cells entered by a recursive walk over a value graph.
+///
+/// A `shared<T>` cell is the only way for a value to (transitively) contain
+/// itself. Recursive operations call [`enter`](Self::enter) before descending
+/// into a cell and [`exit`](Self::exit) afterwards; `enter` returns `false`
+/// when the cell is already on the current path, i.e. the walk found a cycle.
+#[derive(Debug, Default)]
+pub struct CycleGuard {
+    path: Vec<usize>,
+}
+
+impl CycleGuard {
+    /// Push `cell` onto the path; `false` if it is already there.
+    pub fn enter<T>(&mut self, cell: &Shared<T>) -> bool {
+        let addr = cell.as_ptr() as usize;
+        if self.path.contains(&addr) {
+            return false;
+        }
+        self.path.push(addr);
+        true
+    }
+
+    /// Pop `cell` off the path.
+    pub fn exit<T>(&mut self, cell: &Shared<T>) {
+        let addr = cell.as_ptr() as usize;
+        if let Some(pos) = self.path.iter().rposition(|a| *a == addr) {
+            self.path.remove(pos);
+        }
+    }
 }
 
 impl<T: PartialEq> PartialEq for Shared<T> {
@@ -507,9 +548,12 @@ pub enum Value {
         inclusive: bool,
     },
     /// Regular expression pattern
+    #[cfg(feature = "regex")]
     Regex(Arc<regex::Regex>),
     /// DateTime value (UTC timezone)
     DateTime(Arc<chrono::DateTime<chrono::Utc>>),
+    /// Immutable byte buffer (binary file contents, decoded hex/base64)
+    Bytes(Arc<Vec<u8>>),
     /// HTTP Request configuration
     #[cfg(feature = "http")]
     HttpRequest(Arc<crate::stdlib::http::HttpRequest>),
@@ -523,6 +567,12 @@ pub enum Value {
     ProcessOutput(Arc<crate::stdlib::process::ProcessOutput>),
     /// SQLite database connection
     SqliteConnection(Arc<crate::stdlib::sqlite::SqliteConnection>),
+    /// Persistent key-value store (kv.open)
+    KvStore(Arc<crate::stdlib::kv::KvStore>),
+    /// Open file for streaming reads or writes (file.open)
+    FileHandle(Arc<crate::stdlib::file_handle::FileHandle>),
+    /// Terminal progress bar (console.progressBar)
+    ProgressBar(Arc<crate::stdlib::terminal::ProgressBar>),
     /// Future value (async computation)
     Future(Arc<crate::async_runtime::AtlasFuture>),
     /// Task handle (spawned async task)
@@ -534,7 +584,10 @@ pub enum Value {
     /// Async mutex (for async synchronization)
     AsyncMutex(Arc<tokio::sync::Mutex<Value>>),
     /// Filesystem watcher (for change notifications)
+    #[cfg(feature = "fs")]
     Watcher(Arc<Mutex<crate::stdlib::fs::FsWatcher>>),
+    /// Native object injected by the embedder, released through its drop callback
+    Resource(crate::api::resource::Resource),
     /// Closure (function + captured upvalue environment)
     Closure(ClosureRef),
     /// Explicitly shared reference — reference semantics (see Shared<T>).
@@ -603,6 +656,14 @@ impl Value {
         Value::String(Arc::new(s.into()))
     }
 
+    /// Create a shared (reference-semantics) cell, registered with the
+    /// cycle collector
+    pub fn shared(value: Value) -> Self {
+        let cell = Shared::new(Box::new(value));
+        crate::vm::gc::track_shared(&cell);
+        Value::SharedValue(cell)
+    }
+
     /// Create a new array value
     pub fn array(values: Vec<Value>) -> Self {
         Value::Array(ValueArray::from_vec(values))
@@ -627,8 +688,10 @@ impl Value {
             Value::Queue(_) => "queue",
             Value::Stack(_) => "stack",
             Value::Range { .. } => "range",
+            #[cfg(feature = "regex")]
             Value::Regex(_) => "regex",
             Value::DateTime(_) => "datetime",
+            Value::Bytes(_) => "bytes",
             #[cfg(feature = "http")]
             Value::HttpRequest(_) => "HttpRequest",
             #[cfg(feature = "http")]
@@ -637,12 +700,17 @@ impl Value {
             Value::HttpServerRequest(_) => "HttpServerRequest",
             Value::ProcessOutput(_) => "ProcessOutput",
             Value::SqliteConnection(_) => "SqliteConnection",
+            Value::KvStore(_) => "KvStore",
+            Value::FileHandle(_) => "FileHandle",
+            Value::ProgressBar(_) => "ProgressBar",
             Value::Future(_) => "Future",
             Value::TaskHandle(_) => "TaskHandle",
             Value::ChannelSender(_) => "ChannelSender",
             Value::ChannelReceiver(_) => "ChannelReceiver",
             Value::AsyncMutex(_) => "AsyncMutex",
+            #[cfg(feature = "fs")]
             Value::Watcher(_) => "Watcher",
+            Value::Resource(_) => "Resource",
             Value::Closure(_) => "function",
             Value::SharedValue(_) => "shared",
             Value::EnumValue { enum_name, .. } => enum_name.as_str(),
@@ -673,6 +741,7 @@ impl PartialEq for Value {
     /// - Array, HashMap, HashSet, Queue, Stack: collections compare by content
     /// - Regex: compare by pattern string
     /// - DateTime: compare timestamps
+    /// - Bytes: compare contents
     /// - HttpRequest, HttpResponse: compare by field content
     /// - Option, Result, JsonValue: deep structural equality
     /// - Function, Builtin: compare by name
@@ -707,8 +776,10 @@ impl PartialEq for Value {
                     inclusive: b_inc,
                 },
             ) => a_start == b_start && a_end == b_end && a_inc == b_inc,
+            #[cfg(feature = "regex")]
             (Value::Regex(a), Value::Regex(b)) => a.as_str() == b.as_str(),
             (Value::DateTime(a), Value::DateTime(b)) => a == b,
+            (Value::Bytes(a), Value::Bytes(b)) => a == b,
             #[cfg(feature = "http")]
             (Value::HttpRequest(a), Value::HttpRequest(b)) => a.as_ref() == b.as_ref(),
             #[cfg(feature = "http")]
@@ -743,8 +814,13 @@ impl PartialEq for Value {
             (Value::ChannelSender(a), Value::ChannelSender(b)) => Arc::ptr_eq(a, b),
             (Value::ChannelReceiver(a), Value::ChannelReceiver(b)) => Arc::ptr_eq(a, b),
             (Value::AsyncMutex(a), Value::AsyncMutex(b)) => Arc::ptr_eq(a, b),
+            #[cfg(feature = "fs")]
             (Value::Watcher(a), Value::Watcher(b)) => Arc::ptr_eq(a, b),
             (Value::SqliteConnection(a), Value::SqliteConnection(b)) => Arc::ptr_eq(a, b),
+            (Value::KvStore(a), Value::KvStore(b)) => Arc::ptr_eq(a, b),
+            (Value::FileHandle(a), Value::FileHandle(b)) => Arc::ptr_eq(a, b),
+            (Value::ProgressBar(a), Value::ProgressBar(b)) => Arc::ptr_eq(a, b),
+            (Value::Resource(a), Value::Resource(b)) => a.ptr_eq(b),
             // Different variants are never equal
             _ => false,
         }
@@ -753,17 +829,43 @@ impl PartialEq for Value {
 
 impl Eq for Value {}
 
+/// Canonical string form of a number
+///
+/// Every path that turns a number into text — `toString`, `str`, template
+/// strings, `print`, JSON serialization and diagnostics — goes through this
+/// function, so a value prints the same way everywhere. The output follows
+/// TypeScript's `Number.prototype.toString()`:
+/// - the shortest digits that round-trip back to the same `f64`
+/// - no trailing `.0` on whole numbers, and `-0` prints as `0`
+/// - exponent notation (`1e+21`, `1.5e-7`) outside `1e-6 <= |n| < 1e21`
+/// - `NaN`, `Infinity` and `-Infinity` for the special values
+pub fn format_number(n: f64) -> String {
+    if n.is_nan() {
+        return "NaN".to_string();
+    }
+    if n.is_infinite() {
+        return if n > 0.0 { "Infinity" } else { "-Infinity" }.to_string();
+    }
+    if n == 0.0 {
+        return "0".to_string();
+    }
+    if (1e-6..1e21).contains(&n.abs()) {
+        // f64's Display is already shortest round-trip without an exponent
+        return n.to_string();
+    }
+    let exp = format!("{:e}", n);
+    match exp.split_once('e') {
+        Some((mantissa, power)) if !power.starts_with('-') => {
+            format!("{}e+{}", mantissa, power)
+        }
+        _ => exp,
+    }
+}
+
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Value::Number(n) => {
-                // Format number nicely (no trailing .0 for whole numbers)
-                if n.fract() == 0.0 && n.is_finite() {
-                    write!(f, "{:.0}", n)
-                } else {
-                    write!(f, "{}", n)
-                }
-            }
+            Value::Number(n) => write!(f, "{}", format_number(*n)),
             Value::String(s) => write!(f, "{}", s.as_ref()),
             Value::Bool(b) => write!(f, "{}", b),
             Value::Null => write!(f, "null"),
@@ -792,23 +894,18 @@ impl fmt::Display for Value {
                 end,
                 inclusive,
             } => {
-                let fmt_num = |n: f64| {
-                    if n.fract() == 0.0 && n.is_finite() {
-                        format!("{:.0}", n)
-                    } else {
-                        n.to_string()
-                    }
-                };
-                let start_str = start.map(fmt_num).unwrap_or_default();
-                let end_str = end.map(fmt_num).unwrap_or_default();
+                let start_str = start.map(format_number).unwrap_or_default();
+                let end_str = end.map(format_number).unwrap_or_default();
                 if *inclusive {
                     write!(f, "{}..={}", start_str, end_str)
                 } else {
                     write!(f, "{}..{}", start_str, end_str)
                 }
             }
+            #[cfg(feature = "regex")]
             Value::Regex(r) => write!(f, "<Regex /{}/>", r.as_str()),
             Value::DateTime(dt) => write!(f, "{}", dt.to_rfc3339()),
+            Value::Bytes(bytes) => write!(f, "<Bytes len={}>", bytes.len()),
             #[cfg(feature = "http")]
             Value::HttpRequest(req) => write!(f, "<HttpRequest {} {}>", req.method(), req.url()),
             #[cfg(feature = "http")]
@@ -824,6 +921,20 @@ impl fmt::Display for Value {
                 let status = if conn.is_closed() { "closed" } else { "open" };
                 write!(f, "<SqliteConnection {}>", status)
             }
+            Value::KvStore(store) => {
+                let status = if store.is_closed() { "closed" } else { "open" };
+                write!(f, "<KvStore {}>", status)
+            }
+            Value::FileHandle(handle) => {
+                let status = if handle.is_closed() { "closed" } else { "open" };
+                write!(f, "<FileHandle {} {}>", handle.path(), status)
+            }
+            Value::ProgressBar(bar) => write!(
+                f,
+                "<ProgressBar {}/{}>",
+                Value::Number(bar.current()),
+                Value::Number(bar.total())
+            ),
             Value::Future(_) => write!(f, "<Future>"),
             Value::TaskHandle(handle) => {
                 let id = handle.lock().map(|h| h.id()).unwrap_or(0);
@@ -832,9 +943,11 @@ impl fmt::Display for Value {
             Value::ChannelSender(_) => write!(f, "<ChannelSender>"),
             Value::ChannelReceiver(_) => write!(f, "<ChannelReceiver>"),
             Value::AsyncMutex(_) => write!(f, "<AsyncMutex>"),
+            #[cfg(feature = "fs")]
             Value::Watcher(_) => write!(f, "<Watcher>"),
+            Value::Resource(resource) => write!(f, "{}", resource),
             Value::Closure(c) => write!(f, "<fn {}>", c.func.name),
-            Value::SharedValue(s) => s.with(|v| write!(f, "shared({})", v)),
+            Value::SharedValue(s) => fmt_shared(s, f, |v, f| write!(f, "shared({})", v)),
             Value::EnumValue {
                 enum_name,
                 variant_name,
@@ -886,8 +999,10 @@ impl fmt::Debug for Value {
                 "Range(start={:?}, end={:?}, inclusive={})",
                 start, end, inclusive
             ),
+            #[cfg(feature = "regex")]
             Value::Regex(r) => write!(f, "Regex(/{}/)", r.as_str()),
             Value::DateTime(dt) => write!(f, "DateTime({})", dt.to_rfc3339()),
+            Value::Bytes(bytes) => write!(f, "Bytes({})", hex::encode(bytes.as_slice())),
             #[cfg(feature = "http")]
             Value::HttpRequest(req) => write!(f, "HttpRequest({} {})", req.method(), req.url()),
             #[cfg(feature = "http")]
@@ -906,6 +1021,9 @@ impl fmt::Debug for Value {
                     if conn.is_closed() { "closed" } else { "open" }
                 )
             }
+            Value::KvStore(store) => write!(f, "{:?}", store),
+            Value::FileHandle(handle) => write!(f, "{:?}", handle),
+            Value::ProgressBar(bar) => write!(f, "{:?}", bar),
             Value::Future(_) => write!(f, "Future"),
             Value::TaskHandle(handle) => {
                 let id = handle.lock().map(|h| h.id()).unwrap_or(0);
@@ -914,9 +1032,11 @@ impl fmt::Debug for Value {
             Value::ChannelSender(_) => write!(f, "ChannelSender"),
             Value::ChannelReceiver(_) => write!(f, "ChannelReceiver"),
             Value::AsyncMutex(_) => write!(f, "AsyncMutex"),
+            #[cfg(feature = "fs")]
             Value::Watcher(_) => write!(f, "Watcher"),
+            Value::Resource(resource) => write!(f, "{:?}", resource),
             Value::Closure(c) => write!(f, "Closure({:?})", c.func),
-            Value::SharedValue(s) => s.with(|v| write!(f, "SharedValue({:?})", v)),
+            Value::SharedValue(s) => fmt_shared(s, f, |v, f| write!(f, "SharedValue({:?})", v)),
             Value::EnumValue {
                 enum_name,
                 variant_name,
@@ -931,6 +1051,27 @@ impl fmt::Debug for Value {
     }
 }
 
+thread_local! {
+    /// Cells being formatted on this thread. `Display`/`Debug` recurse through
+    /// `fmt`, so the guard can't be passed down as an argument.
+    static FMT_GUARD: std::cell::RefCell<CycleGuard> = std::cell::RefCell::default();
+}
+
+/// Format a `shared` cell's contents, printing `<cycle>` for a cell that is
+/// already being formatted further up (relocking it would deadlock)
+fn fmt_shared(
+    cell: &Shared<Box<Value>>,
+    f: &mut fmt::Formatter<'_>,
+    write_inner: impl FnOnce(&Value, &mut fmt::Formatter<'_>) -> fmt::Result,
+) -> fmt::Result {
+    if !FMT_GUARD.with(|g| g.borrow_mut().enter(cell)) {
+        return write!(f, "<cycle>");
+    }
+    let result = cell.with(|v| write_inner(v, f));
+    FMT_GUARD.with(|g| g.borrow_mut().exit(cell));
+    result
+}
+
 /// Runtime error type with source span information
 #[derive(Debug, Error, Clone, PartialEq)]
 pub enum RuntimeError {
@@ -1001,6 +1142,12 @@ pub enum RuntimeError {
         var: String,
         span: crate::span::Span,
     },
+    /// Permission denied - database
+    #[error("Permission denied: database access to {path}")]
+    DatabasePermissionDenied {
+        path: String,
+        span: crate::span::Span,
+    },
     /// I/O error (file operations)
     #[error("I/O error: {message}")]
     IoError {
@@ -1013,12 +1160,21 @@ pub enum RuntimeError {
         type_name: String,
         span: crate::span::Span,
     },
+    /// A recursive operation reached a `shared` value that contains itself
+    #[error("Circular reference: {msg}")]
+    CircularReference {
+        msg: String,
+        span: crate::span::Span,
+    },
     /// Execution timeout exceeded
     #[error("Execution timeout: {elapsed:?} elapsed, limit was {limit:?}")]
     Timeout {
         elapsed: std::time::Duration,
         limit: std::time::Duration,
     },
+    /// The host stopped execution through an `InterruptHandle`
+    #[error("Execution interrupted by the host")]
+    Interrupted,
     /// FFI/extern function call denied
     #[error("Permission denied: FFI call to {function}")]
     FfiPermissionDenied { function: String },
@@ -1029,6 +1185,21 @@ pub enum RuntimeError {
         used: usize,
         limit: usize,
     },
+    /// A regex pattern exceeded the configured `RegexLimits`
+    #[error("Regex limit exceeded: {msg}")]
+    RegexLimitExceeded {
+        msg: String,
+        span: crate::span::Span,
+    },
+    /// Calls nested deeper than the configured `VmLimits` allow
+    #[error("Recursion limit exceeded at call depth {depth}")]
+    RecursionLimit {
+        /// Function calls active when the limit was hit
+        depth: usize,
+        /// Call stack at that point, innermost frame first
+        stack_trace: Vec<crate::diagnostic::StackTraceFrame>,
+        span: crate::span::Span,
+    },
     /// Internal runtime error
     #[error("Internal runtime error: {msg}")]
     InternalError {
@@ -1038,6 +1209,88 @@ pub enum RuntimeError {
 }
 
 impl RuntimeError {
+    /// Stable name of the error variant (e.g. `"OutOfBounds"`), used to filter
+    /// errors by kind without matching on the message text.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RuntimeError::TypeError { .. } => "TypeError",
+            RuntimeError::UndefinedVariable { .. } => "UndefinedVariable",
+            RuntimeError::DivideByZero { .. } => "DivideByZero",
+            RuntimeError::OutOfBounds { .. } => "OutOfBounds",
+            RuntimeError::InvalidNumericResult { .. } => "InvalidNumericResult",
+            RuntimeError::UnknownOpcode { .. } => "UnknownOpcode",
+            RuntimeError::StackUnderflow { .. } => "StackUnderflow",
+            RuntimeError::UnknownFunction { .. } => "UnknownFunction",
+            RuntimeError::InvalidStdlibArgument { .. } => "InvalidStdlibArgument",
+            RuntimeError::InvalidIndex { .. } => "InvalidIndex",
+            RuntimeError::FilesystemPermissionDenied { .. } => "FilesystemPermissionDenied",
+            RuntimeError::NetworkPermissionDenied { .. } => "NetworkPermissionDenied",
+            RuntimeError::ProcessPermissionDenied { .. } => "ProcessPermissionDenied",
+            RuntimeError::EnvironmentPermissionDenied { .. } => "EnvironmentPermissionDenied",
+            RuntimeError::DatabasePermissionDenied { .. } => "DatabasePermissionDenied",
+            RuntimeError::IoError { .. } => "IoError",
+            RuntimeError::UnhashableType { .. } => "UnhashableType",
+            RuntimeError::CircularReference { .. } => "CircularReference",
+            RuntimeError::Timeout { .. } => "Timeout",
+            RuntimeError::Interrupted => "Interrupted",
+            RuntimeError::FfiPermissionDenied { .. } => "FfiPermissionDenied",
+            RuntimeError::MemoryLimitExceeded { .. } => "MemoryLimitExceeded",
+            RuntimeError::RegexLimitExceeded { .. } => "RegexLimitExceeded",
+            RuntimeError::RecursionLimit { .. } => "RecursionLimit",
+            RuntimeError::InternalError { .. } => "InternalError",
+        }
+    }
+
+    /// Whether a script `try`/`catch` may recover from this error
+    ///
+    /// Resource limits and host interrupts always end the script — catching
+    /// them would let a sandboxed script outlive its own timeout — and VM
+    /// faults leave the machine in no state worth resuming.
+    pub fn is_catchable(&self) -> bool {
+        !matches!(
+            self,
+            RuntimeError::Timeout { .. }
+                | RuntimeError::Interrupted
+                | RuntimeError::MemoryLimitExceeded { .. }
+                | RuntimeError::UnknownOpcode { .. }
+                | RuntimeError::StackUnderflow { .. }
+                | RuntimeError::InternalError { .. }
+        )
+    }
+
+    /// The record a `catch (e)` block receives: `{ kind, message, code }`
+    ///
+    /// `kind` is [`kind`](Self::kind), `message` the error's display text and
+    /// `code` its diagnostic code (e.g. `"AT0005"`).
+    pub fn to_record(&self) -> Value {
+        use crate::stdlib::collections::hash::HashKey;
+        use crate::stdlib::collections::hashmap::AtlasHashMap;
+        let code = crate::runtime::runtime_error_to_diagnostic(self.clone(), Vec::new(), None).code;
+        let mut record = AtlasHashMap::new();
+        let mut field = |name: &str, value: String| {
+            record.insert(
+                HashKey::String(Arc::new(name.to_string())),
+                Value::string(value),
+            );
+        };
+        field("kind", self.kind().to_string());
+        field("message", self.to_string());
+        field("code", code);
+        Value::Map(ValueHashMap::from_atlas(record))
+    }
+
+    /// Static type of [`to_record`](Self::to_record)
+    pub fn record_type() -> crate::types::Type {
+        use crate::types::{StructuralMemberType, Type};
+        let member = |name: &str| StructuralMemberType {
+            name: name.to_string(),
+            ty: Type::String,
+        };
+        Type::Structural {
+            members: vec![member("kind"), member("message"), member("code")],
+        }
+    }
+
     /// Get the source span for this error
     pub fn span(&self) -> crate::span::Span {
         match self {
@@ -1055,11 +1308,16 @@ impl RuntimeError {
             RuntimeError::NetworkPermissionDenied { span, .. } => *span,
             RuntimeError::ProcessPermissionDenied { span, .. } => *span,
             RuntimeError::EnvironmentPermissionDenied { span, .. } => *span,
+            RuntimeError::DatabasePermissionDenied { span, .. } => *span,
             RuntimeError::IoError { span, .. } => *span,
             RuntimeError::UnhashableType { span, .. } => *span,
+            RuntimeError::CircularReference { span, .. } => *span,
             RuntimeError::Timeout { .. } => crate::span::Span::dummy(),
+            RuntimeError::Interrupted => crate::span::Span::dummy(),
             RuntimeError::FfiPermissionDenied { .. } => crate::span::Span::dummy(),
             RuntimeError::MemoryLimitExceeded { .. } => crate::span::Span::dummy(),
+            RuntimeError::RegexLimitExceeded { span, .. } => *span,
+            RuntimeError::RecursionLimit { span, .. } => *span,
             RuntimeError::InternalError { span, .. } => *span,
         }
     }
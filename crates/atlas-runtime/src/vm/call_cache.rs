@@ -0,0 +1,136 @@
+//! Inline caches for call sites
+//!
+//! A call site is identified by the instruction offset just past its `Call`
+//! operand, which is unique per site. Each site remembers the last callee it
+//! resolved:
+//!
+//! - a stdlib builtin, cached as its dispatch function pointer, so the call
+//!   skips the intrinsic/special-name checks and the registry lookup;
+//! - a compiled function, cached by `bytecode_offset`, so the call skips the
+//!   extern-function lookup and reuses the body end the JIT needs.
+//!
+//! The cache is monomorphic: a hit requires the callee on the stack to match
+//! the cached one, and a different callee simply replaces the entry.
+
+use crate::stdlib::BuiltinFn;
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+use std::sync::Arc;
+
+/// Call-site cache counters, for tests and profiling
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CallCacheStats {
+    /// Calls that used a cached callee
+    pub hits: u64,
+    /// Calls that resolved their callee (first call, or a different callee)
+    pub misses: u64,
+    /// Call sites with a cached callee
+    pub sites: usize,
+}
+
+/// A compiled function cached at a call site
+#[derive(Debug, Clone, Copy)]
+pub(super) struct CachedFunction {
+    /// `FunctionRef::bytecode_offset` of the callee
+    pub(super) bytecode_offset: usize,
+    /// End of the function body, once the JIT has asked for it
+    pub(super) function_end: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+enum CachedCallee {
+    Builtin { name: Arc<str>, dispatch: BuiltinFn },
+    Function(CachedFunction),
+}
+
+/// Instruction offsets are already well distributed; skip SipHash.
+#[derive(Default)]
+struct OffsetHasher(u64);
+
+impl Hasher for OffsetHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 << 8) | u64::from(b);
+        }
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.0 = n as u64;
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct CallCache {
+    sites: HashMap<usize, CachedCallee, BuildHasherDefault<OffsetHasher>>,
+    hits: u64,
+    misses: u64,
+}
+
+impl CallCache {
+    /// Cached dispatch function if `site` last called builtin `name`
+    #[inline]
+    pub(super) fn builtin(&mut self, site: usize, name: &Arc<str>) -> Option<BuiltinFn> {
+        match self.sites.get(&site) {
+            Some(CachedCallee::Builtin {
+                name: cached,
+                dispatch,
+            }) if Arc::ptr_eq(cached, name) || cached == name => {
+                self.hits += 1;
+                Some(*dispatch)
+            }
+            _ => None,
+        }
+    }
+
+    pub(super) fn insert_builtin(&mut self, site: usize, name: Arc<str>, dispatch: BuiltinFn) {
+        self.misses += 1;
+        self.sites
+            .insert(site, CachedCallee::Builtin { name, dispatch });
+    }
+
+    /// Cached entry if `site` last called the function at `bytecode_offset`
+    #[inline]
+    pub(super) fn function(
+        &mut self,
+        site: usize,
+        bytecode_offset: usize,
+    ) -> Option<CachedFunction> {
+        match self.sites.get(&site) {
+            Some(CachedCallee::Function(cached)) if cached.bytecode_offset == bytecode_offset => {
+                self.hits += 1;
+                Some(*cached)
+            }
+            _ => None,
+        }
+    }
+
+    pub(super) fn insert_function(&mut self, site: usize, function: CachedFunction) {
+        self.misses += 1;
+        self.sites.insert(site, CachedCallee::Function(function));
+    }
+
+    /// Record the body end of the function cached at `site`
+    pub(super) fn set_function_end(&mut self, site: usize, function_end: usize) {
+        if let Some(CachedCallee::Function(cached)) = self.sites.get_mut(&site) {
+            cached.function_end = Some(function_end);
+        }
+    }
+
+    /// Forget every site (callee resolution rules changed)
+    #[cfg(feature = "ffi")]
+    pub(super) fn clear(&mut self) {
+        self.sites.clear();
+    }
+
+    pub(super) fn stats(&self) -> CallCacheStats {
+        CallCacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            sites: self.sites.len(),
+        }
+    }
+}
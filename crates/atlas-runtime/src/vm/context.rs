@@ -25,9 +25,9 @@
 
 use std::collections::{HashMap, HashSet};
 
-use crate::diagnostic::Diagnostic;
+use crate::diagnostic::{Diagnostic, StackTraceFrame};
 use crate::value::Value;
-use crate::vm::frame::CallFrame;
+use crate::vm::frame::{CallFrame, ErrorHandler};
 
 /// Per-thread VM execution state.
 ///
@@ -49,8 +49,11 @@ pub struct VMContext {
     /// Deferred blocks run LIFO when the frame they were pushed in exits.
     pub(super) defer_stacks: Vec<Vec<(usize, usize)>>,
 
-    /// Reusable scratch buffer for string operations (reduces allocations).
-    pub(super) string_buffer: String,
+    /// Active `try` handlers, innermost last.
+    pub(super) handlers: Vec<ErrorHandler>,
+
+    /// Record of the error the last unwind caught, taken by `PushError`.
+    pub(super) caught_error: Option<Value>,
 
     /// Nominal struct type names for `HashMap`-backed struct values,
     /// keyed by the map's memory identity (pointer cast to `usize`).
@@ -64,9 +67,13 @@ pub struct VMContext {
     /// Callers retrieve and drain these via `VM::take_runtime_warnings()`.
     pub(super) runtime_warnings: Vec<Diagnostic>,
 
+    /// Call stack captured when a run fails, innermost frame first.
+    /// Callers retrieve and drain it via `VM::take_error_stack_trace()`.
+    pub(super) error_stack_trace: Vec<StackTraceFrame>,
+
     /// Parallel to `stack`: tracks where each value originated (debug builds only).
     /// `None` for computed/literal values; `Some(Local(slot))` for GetLocal;
-    /// `Some(Global(name))` for GetGlobal.  Used to mark caller's binding as
+    /// `Some(Global(slot))` for GetGlobal.  Used to mark caller's binding as
     /// consumed when a value is passed to an `own` parameter.
     #[cfg(debug_assertions)]
     pub(super) value_origins: Vec<Option<super::StackValueOrigin>>,
@@ -77,10 +84,10 @@ pub struct VMContext {
     #[cfg(debug_assertions)]
     pub(super) consumed_slots: Vec<Vec<bool>>,
 
-    /// Consumed global names (debug builds only).  A global is inserted here
+    /// Consumed global slots (debug builds only).  A global is inserted here
     /// when it is passed to an `own` parameter; subsequent `GetGlobal` errors.
     #[cfg(debug_assertions)]
-    pub(super) consumed_globals: HashSet<String>,
+    pub(super) consumed_globals: HashSet<usize>,
 }
 
 impl VMContext {
@@ -97,10 +104,12 @@ impl VMContext {
             frames: vec![main_frame],
             ip: 0,
             defer_stacks: vec![Vec::new()],
-            string_buffer: String::new(),
+            handlers: Vec::new(),
+            caught_error: None,
             struct_type_names: HashMap::new(),
             debug_pause_pending: false,
             runtime_warnings: Vec::new(),
+            error_stack_trace: Vec::new(),
             #[cfg(debug_assertions)]
             value_origins: Vec::with_capacity(1024),
             #[cfg(debug_assertions)]
@@ -110,6 +119,62 @@ impl VMContext {
         }
     }
 
+    /// Move the innermost frame into a context of its own, as its only frame
+    ///
+    /// The frame takes its stack slice (the callee slot below `stack_base`,
+    /// arguments and locals), deferred blocks and `try` handlers along, all
+    /// rebased so the frame sits at the bottom; it keeps its own `return_ip`.
+    /// The new context resumes at this context's `ip`. Used to suspend an
+    /// `async fn` at an `await`.
+    pub(super) fn split_top_frame(&mut self) -> VMContext {
+        let mut frame = self.frames.pop().expect("split_top_frame needs a frame");
+        let depth = self.frames.len();
+        let base = frame.stack_base - 1;
+        frame.stack_base = 1;
+
+        let kept = self
+            .handlers
+            .iter()
+            .take_while(|h| h.frame_index < depth)
+            .count();
+        let mut handlers = self.handlers.split_off(kept);
+        for handler in &mut handlers {
+            handler.frame_index -= depth;
+            handler.stack_len -= base;
+        }
+
+        VMContext {
+            stack: self.stack.split_off(base),
+            frames: vec![frame],
+            ip: self.ip,
+            defer_stacks: vec![self.defer_stacks.pop().unwrap_or_default()],
+            handlers,
+            caught_error: None,
+            struct_type_names: HashMap::new(),
+            debug_pause_pending: false,
+            runtime_warnings: Vec::new(),
+            error_stack_trace: Vec::new(),
+            #[cfg(debug_assertions)]
+            value_origins: self.value_origins.split_off(base),
+            #[cfg(debug_assertions)]
+            consumed_slots: vec![self.consumed_slots.pop().unwrap_or_default()],
+            #[cfg(debug_assertions)]
+            consumed_globals: HashSet::new(),
+        }
+    }
+
+    /// Hand the state that belongs to the VM as a whole rather than to one
+    /// context (struct type names, consumed globals, warnings) from `self`
+    /// to `next`, the context about to run
+    pub(super) fn hand_over(&mut self, next: &mut VMContext) {
+        next.struct_type_names = std::mem::take(&mut self.struct_type_names);
+        next.runtime_warnings.append(&mut self.runtime_warnings);
+        #[cfg(debug_assertions)]
+        {
+            next.consumed_globals = std::mem::take(&mut self.consumed_globals);
+        }
+    }
+
     /// Reset this context to a clean worker state (empty stack, no frames,
     /// all scratch buffers cleared).
     ///
@@ -122,10 +187,12 @@ impl VMContext {
         self.frames.clear();
         self.ip = 0;
         self.defer_stacks.clear();
-        self.string_buffer.clear();
+        self.handlers.clear();
+        self.caught_error = None;
         self.struct_type_names.clear();
         self.debug_pause_pending = false;
         self.runtime_warnings.clear();
+        self.error_stack_trace.clear();
         #[cfg(debug_assertions)]
         {
             self.value_origins.clear();
@@ -151,10 +218,12 @@ impl VMContext {
         self.ip = 0;
         self.defer_stacks.clear();
         self.defer_stacks.push(Vec::new());
-        self.string_buffer.clear();
+        self.handlers.clear();
+        self.caught_error = None;
         self.struct_type_names.clear();
         self.debug_pause_pending = false;
         self.runtime_warnings.clear();
+        self.error_stack_trace.clear();
         #[cfg(debug_assertions)]
         {
             self.value_origins.clear();
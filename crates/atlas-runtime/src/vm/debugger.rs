@@ -4,6 +4,7 @@
 //! breakpoints, and state inspection. Disabled by default for production use.
 
 use crate::bytecode::Opcode;
+use crate::value::{RuntimeError, Value};
 use std::collections::HashSet;
 
 /// Debugger action to take after a hook callback
@@ -27,6 +28,9 @@ pub enum StepCondition {
     Always,
     /// Pause when call-frame depth ≤ the recorded start depth (step-over).
     OverDepth(usize),
+    /// Pause at the next statement boundary in the recorded frame, or once that
+    /// frame has returned (statement-level step-over).
+    OverStatement(usize),
     /// Pause when call-frame depth < the recorded start depth (step-out).
     OutDepth(usize),
 }
@@ -37,10 +41,46 @@ impl StepCondition {
         match self {
             Self::None => false,
             Self::Always => true,
-            Self::OverDepth(start) => current_depth <= *start,
+            Self::OverDepth(start) | Self::OverStatement(start) => current_depth <= *start,
             Self::OutDepth(start) => current_depth < *start,
         }
     }
+
+    /// Statement-aware variant of [`fires`](Self::fires).
+    ///
+    /// `at_statement` is `Some(true)` when the instruction begins a source statement,
+    /// `Some(false)` when it does not, and `None` when the bytecode has no statement table.
+    pub fn fires_at(&self, current_depth: usize, at_statement: Option<bool>) -> bool {
+        match (self, at_statement) {
+            (Self::OverStatement(start), Some(at_statement)) => {
+                current_depth < *start || (current_depth == *start && at_statement)
+            }
+            _ => self.fires(current_depth),
+        }
+    }
+}
+
+/// Variable storage observed by a watchpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchSlot {
+    /// A global, by slot
+    Global(usize),
+    /// A local, by value-stack index, owned by the frame at `frame_depth`
+    Local {
+        frame_depth: usize,
+        stack_index: usize,
+    },
+}
+
+/// A write that changed a watched variable
+#[derive(Debug, Clone)]
+pub struct WatchHit {
+    /// ID of the watchpoint
+    pub id: u32,
+    /// Value before the write (`None` if unassigned)
+    pub old_value: Option<Value>,
+    /// Value after the write
+    pub new_value: Value,
 }
 
 /// Debugger hook trait for custom debugging callbacks
@@ -80,6 +120,20 @@ pub struct Debugger {
     step_mode: bool,
     /// Frame-depth-aware step condition (overrides step_mode when not None)
     step_condition: StepCondition,
+    /// Instruction offset of the last pause. The paused instruction is re-executed on
+    /// resume, so it is let through once instead of pausing again.
+    resume_ip: Option<usize>,
+    /// Offset of the most recent instruction seen by the hook (the faulting
+    /// instruction when an error escapes).
+    last_ip: usize,
+    /// Error held back by break-on-error; delivered on the next resume.
+    pending_error: Option<RuntimeError>,
+    /// Watched variables and their watchpoint IDs
+    watches: Vec<(u32, WatchSlot)>,
+    /// Write that paused execution, until the VM reports it
+    watch_hit: Option<WatchHit>,
+    /// Local watchpoints dropped because their frame returned
+    expired_watches: Vec<u32>,
 }
 
 impl Debugger {
@@ -91,6 +145,12 @@ impl Debugger {
             paused: false,
             step_mode: false,
             step_condition: StepCondition::None,
+            resume_ip: None,
+            last_ip: 0,
+            pending_error: None,
+            watches: Vec::new(),
+            watch_hit: None,
+            expired_watches: Vec::new(),
         }
     }
 
@@ -102,6 +162,12 @@ impl Debugger {
             paused: false,
             step_mode: false,
             step_condition: StepCondition::None,
+            resume_ip: None,
+            last_ip: 0,
+            pending_error: None,
+            watches: Vec::new(),
+            watch_hit: None,
+            expired_watches: Vec::new(),
         }
     }
 
@@ -190,6 +256,73 @@ impl Debugger {
         self.step_mode = false;
     }
 
+    /// Offset of the most recent instruction seen by the depth-aware hook.
+    pub fn last_ip(&self) -> usize {
+        self.last_ip
+    }
+
+    /// Hold back an error that paused execution (break-on-error).
+    pub fn set_pending_error(&mut self, error: RuntimeError) {
+        self.pending_error = Some(error);
+    }
+
+    /// Take the error held back by break-on-error, if any.
+    pub fn take_pending_error(&mut self) -> Option<RuntimeError> {
+        self.pending_error.take()
+    }
+
+    /// Watch `slot` for changes under watchpoint `id`
+    pub fn set_watch(&mut self, id: u32, slot: WatchSlot) {
+        self.watches.push((id, slot));
+    }
+
+    /// Remove all watches
+    pub fn clear_watches(&mut self) {
+        self.watches.clear();
+    }
+
+    /// Watchpoint ID watching `slot`, if any
+    #[inline]
+    pub fn watch_for(&self, slot: WatchSlot) -> Option<u32> {
+        self.watches
+            .iter()
+            .find(|(_, watched)| *watched == slot)
+            .map(|(id, _)| *id)
+    }
+
+    /// Record a write that changed a watched variable; execution pauses
+    /// before `next_ip`, which is let through on resume.
+    pub fn record_watch_hit(&mut self, hit: WatchHit, next_ip: usize) {
+        self.watch_hit = Some(hit);
+        self.paused = true;
+        self.resume_ip = Some(next_ip);
+    }
+
+    /// Take the write that paused execution, if a watchpoint fired.
+    pub fn take_watch_hit(&mut self) -> Option<WatchHit> {
+        self.watch_hit.take()
+    }
+
+    /// Drop watches on locals of frames deeper than `frame_depth`, which
+    /// have returned
+    pub fn drop_frame_watches(&mut self, frame_depth: usize) {
+        let expired = &mut self.expired_watches;
+        self.watches.retain(|(id, slot)| match slot {
+            WatchSlot::Local {
+                frame_depth: owner, ..
+            } if *owner > frame_depth => {
+                expired.push(*id);
+                false
+            }
+            _ => true,
+        });
+    }
+
+    /// Take the IDs of watchpoints dropped since the last call.
+    pub fn take_expired_watches(&mut self) -> Vec<u32> {
+        std::mem::take(&mut self.expired_watches)
+    }
+
     /// Hook called before instruction execution
     ///
     /// Returns the action to take (Continue, Step, or Pause).
@@ -215,26 +348,36 @@ impl Debugger {
     /// Frame-depth-aware variant of `before_instruction`.
     ///
     /// This is the preferred hook when running with the full debugger infrastructure,
-    /// as it supports step-over and step-out semantics.
+    /// as it supports step-over and step-out semantics. `at_statement` reports whether
+    /// `ip` begins a source statement (`None` when the bytecode carries no statement table).
     pub fn before_instruction_with_depth(
         &mut self,
         ip: usize,
         _opcode: Opcode,
         frame_depth: usize,
+        at_statement: Option<bool>,
     ) -> DebugAction {
         if !self.enabled {
             return DebugAction::Continue;
         }
+        self.last_ip = ip;
+
+        // The instruction we last paused on is re-executed on resume — let it run.
+        if self.resume_ip.take() == Some(ip) {
+            return DebugAction::Continue;
+        }
 
         // Breakpoints always take priority
         if self.has_breakpoint(ip) {
             self.paused = true;
+            self.resume_ip = Some(ip);
             return DebugAction::Pause;
         }
 
         // Frame-depth-aware step condition overrides simple step_mode
         if !matches!(self.step_condition, StepCondition::None) {
-            if self.step_condition.fires(frame_depth) {
+            if self.step_condition.fires_at(frame_depth, at_statement) {
+                self.resume_ip = Some(ip);
                 return DebugAction::Pause;
             }
             return DebugAction::Continue;
@@ -242,6 +385,7 @@ impl Debugger {
 
         // Legacy simple step mode
         if self.step_mode {
+            self.resume_ip = Some(ip);
             return DebugAction::Step;
         }
 
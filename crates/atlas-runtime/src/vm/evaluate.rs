@@ -0,0 +1,126 @@
+//! Expression evaluation against a paused VM
+//!
+//! Debugger watch windows and the paused REPL evaluate source snippets in the
+//! scope of a stack frame. The snippet runs on a snapshot of the VM, so the
+//! paused program's stack, frames, instruction pointer and globals are left
+//! exactly as they were.
+
+use super::VM;
+use crate::ast::Visibility;
+use crate::binder::Binder;
+use crate::compiler::Compiler;
+use crate::diagnostic::Diagnostic;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::runtime::{runtime_error_to_diagnostic, value_type};
+use crate::span::Span;
+use crate::symbol::{Symbol, SymbolKind, SymbolTable};
+use crate::typechecker::TypeChecker;
+use crate::value::Value;
+use std::collections::HashMap;
+
+impl VM {
+    /// Evaluate `expression` in the scope of call frame `frame_index`
+    ///
+    /// `frame_index` 0 is the innermost frame. The frame's named locals and
+    /// captured upvalues are visible and shadow globals of the same name;
+    /// program functions can be called. The expression is type-checked with
+    /// the visible names typed from their current values, then run on a
+    /// snapshot of this VM under its current security context.
+    ///
+    /// Nothing the expression does reaches the paused program: assignments
+    /// and copy-on-write collection updates land in the snapshot. Values
+    /// with reference semantics (`shared<T>`) are the exception, as their
+    /// contents are shared with the snapshot. Output still goes to this VM's
+    /// writer.
+    pub fn evaluate(&self, expression: &str, frame_index: usize) -> Result<Value, Vec<Diagnostic>> {
+        let source = expression.trim();
+        let source = if source.ends_with(';') || source.ends_with('}') {
+            source.to_string()
+        } else {
+            format!("{};", source)
+        };
+
+        let mut lexer = Lexer::new(&source).with_file("<eval>");
+        let (tokens, lex_diagnostics) = lexer.tokenize();
+        if !lex_diagnostics.is_empty() {
+            return Err(lex_diagnostics);
+        }
+        let (ast, parse_diagnostics) = Parser::new(tokens).parse();
+        let parse_errors: Vec<_> = parse_diagnostics
+            .into_iter()
+            .filter(|d| d.is_error())
+            .collect();
+        if !parse_errors.is_empty() {
+            return Err(parse_errors);
+        }
+
+        let locals: Vec<(String, Value)> = self
+            .get_named_locals_for_frame(frame_index)
+            .into_iter()
+            .map(|local| (local.name, local.value.clone()))
+            .collect();
+
+        // Locals win over globals of the same name
+        let mut visible: HashMap<&str, &Value> = self
+            .get_global_variables()
+            .map(|(name, value)| (name.as_str(), value))
+            .collect();
+        for (name, value) in &locals {
+            visible.insert(name, value);
+        }
+
+        let mut symbol_table = SymbolTable::new();
+        for (name, value) in visible {
+            let _ = symbol_table.define(Symbol {
+                name: name.to_string(),
+                ty: value_type(value),
+                mutable: false,
+                kind: SymbolKind::Variable,
+                span: Span::dummy(),
+                exported: false,
+                visibility: Visibility::Private,
+            });
+        }
+        let (mut symbol_table, bind_diagnostics) =
+            Binder::with_symbol_table(symbol_table).bind(&ast);
+        if bind_diagnostics.iter().any(|d| d.is_error()) {
+            return Err(bind_diagnostics);
+        }
+        let type_diagnostics = TypeChecker::new(&mut symbol_table).check(&ast);
+        if type_diagnostics.iter().any(|d| d.is_error()) {
+            return Err(type_diagnostics);
+        }
+
+        let mut bytecode = Compiler::new().compile(&ast)?;
+
+        // Locals reach the snippet as globals under names no program code
+        // uses, so a function reading a shadowed global still sees the global
+        let mut hidden = Vec::with_capacity(locals.len());
+        for (name, value) in locals {
+            let slot_name = format!("{name}@frame{frame_index}");
+            for global in bytecode.global_names.iter_mut() {
+                if *global == name {
+                    *global = slot_name.clone();
+                }
+            }
+            hidden.push((slot_name, value));
+        }
+
+        // The snippet is merged after the program's code so function values
+        // keep pointing at valid offsets
+        let mut snapshot = self.new_for_worker();
+        snapshot.load_module(bytecode);
+        for (name, value) in hidden {
+            snapshot.set_global(name, value);
+        }
+        let security = std::sync::Arc::clone(&self.current_security);
+        match snapshot.run(&security) {
+            Ok(value) => Ok(value.unwrap_or(Value::Null)),
+            Err(error) => {
+                let stack_trace = snapshot.take_error_stack_trace();
+                Err(vec![runtime_error_to_diagnostic(error, stack_trace, None)])
+            }
+        }
+    }
+}
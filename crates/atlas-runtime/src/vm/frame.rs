@@ -37,4 +37,36 @@ pub struct CallFrame {
     pub local_count: usize,
     /// Upvalues captured at closure creation time (empty for plain functions)
     pub upvalues: std::sync::Arc<Vec<crate::value::Value>>,
+    /// Frame of an `async fn`, which an `await` on a pending future suspends
+    pub is_async: bool,
+}
+
+/// A `try` block's runtime-error handler, installed by `TryBegin`
+///
+/// Handlers form a stack parallel to the call frames: a catchable error
+/// unwinds to the innermost one, dropping the frames and stack values above
+/// it and resuming at `catch_ip`.
+#[derive(Debug, Clone)]
+pub(crate) struct ErrorHandler {
+    /// Index of the frame that owns the `try` block
+    pub frame_index: usize,
+    /// First instruction of the catch block
+    pub catch_ip: usize,
+    /// Value-stack height when the `try` block was entered
+    pub stack_len: usize,
+}
+
+/// A local slot or captured upvalue of a call frame, named from bytecode debug info
+///
+/// Produced by `VM::get_named_locals_for_frame` for debugger variable views.
+#[derive(Debug, Clone)]
+pub struct FrameVariable<'a> {
+    /// Source-level name (`local_{slot}` / `upvalue_{index}` when the bytecode has no names)
+    pub name: String,
+    /// Local slot, or upvalue index when `captured`
+    pub slot: usize,
+    /// Current value
+    pub value: &'a crate::value::Value,
+    /// Whether the value is an upvalue captured from an enclosing function
+    pub captured: bool,
 }
@@ -0,0 +1,472 @@
+//! Cycle collector for reference-semantics values.
+//!
+//! Most Atlas values are copy-on-write and can never form a cycle. The two
+//! exceptions are `SharedValue` and `AsyncMutex` cells: both hand out aliases
+//! to one mutable slot, so `let m = asyncMutex(null); asyncMutexSet(m, [m]);`
+//! builds a cycle that plain reference counting never frees.
+//!
+//! Every such cell is registered in a process-wide heap when it is created.
+//! Registrations of cells that have since been freed are pruned whenever the
+//! heap doubles, so it stays proportional to the live cells even under
+//! [`GcStrategy::Disabled`] or a `Manual` program that never collects.
+//!
+//! A collection then:
+//!
+//! 1. **Marks** every cell reachable from the VM roots (stack, globals,
+//!    captured upvalues).
+//! 2. Counts, for each unmarked cell, the references held by other unmarked
+//!    cells. A cell whose strong count is higher than that is still held
+//!    from outside — by the host, another VM, or a native value the collector
+//!    cannot see into — so it and everything it reaches are marked live too.
+//! 3. **Sweeps** the rest: their contents are replaced with `null`, which
+//!    breaks the cycles and lets the reference counts drop to zero.
+//!
+//! Step 2 keeps the collector safe even when a root set is incomplete: a
+//! cell is only swept when every reference to it is accounted for by other
+//! garbage.
+
+use crate::value::{Shared, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+
+/// When the VM runs collections on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GcStrategy {
+    /// Never collect; cycles leak as with plain reference counting
+    Disabled,
+    /// Collect only when the program calls `process.gc()` or the host calls
+    /// `VM::collect_garbage`
+    Manual,
+    /// Also collect automatically once enough cells were allocated since the
+    /// last collection (see [`GcConfig::threshold`])
+    #[default]
+    Automatic,
+}
+
+/// Collector tuning
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GcConfig {
+    pub strategy: GcStrategy,
+    /// Cell allocations between automatic collections
+    pub threshold: usize,
+    /// After a collection the next one is deferred until allocations reach
+    /// `max(threshold, survivors * growth_factor)`, so programs that keep
+    /// many cells alive do not collect on every loop iteration.
+    pub growth_factor: f64,
+}
+
+impl Default for GcConfig {
+    fn default() -> Self {
+        Self {
+            strategy: GcStrategy::default(),
+            threshold: 1024,
+            growth_factor: 2.0,
+        }
+    }
+}
+
+impl GcConfig {
+    /// Config with the given strategy and default tuning
+    pub fn new(strategy: GcStrategy) -> Self {
+        Self {
+            strategy,
+            ..Self::default()
+        }
+    }
+
+    /// Set the number of allocations between automatic collections
+    pub fn with_threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold.max(1);
+        self
+    }
+
+    /// Set how far the threshold grows with the number of surviving cells
+    pub fn with_growth_factor(mut self, growth_factor: f64) -> Self {
+        self.growth_factor = growth_factor.max(1.0);
+        self
+    }
+
+    /// Allocation count that triggers the next automatic collection
+    pub(crate) fn next_threshold(&self, survivors: usize) -> usize {
+        self.threshold
+            .max((survivors as f64 * self.growth_factor) as usize)
+    }
+}
+
+/// Counters describing collector activity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcStats {
+    /// Collections run so far
+    pub collections: u64,
+    /// Cells swept over all collections
+    pub total_freed: u64,
+    /// Cells swept by the most recent collection
+    pub last_freed: usize,
+    /// Cells still registered after the most recent collection
+    pub live: usize,
+}
+
+/// A registered cell
+enum TrackedCell {
+    Shared(Weak<Mutex<Box<Value>>>),
+    AsyncMutex(Weak<tokio::sync::Mutex<Value>>),
+}
+
+impl TrackedCell {
+    fn is_alive(&self) -> bool {
+        match self {
+            TrackedCell::Shared(w) => w.strong_count() > 0,
+            TrackedCell::AsyncMutex(w) => w.strong_count() > 0,
+        }
+    }
+}
+
+/// A registered cell upgraded for the duration of a collection
+enum Cell {
+    Shared(Arc<Mutex<Box<Value>>>),
+    AsyncMutex(Arc<tokio::sync::Mutex<Value>>),
+}
+
+impl Cell {
+    fn strong_count(&self) -> usize {
+        match self {
+            Cell::Shared(c) => Arc::strong_count(c),
+            Cell::AsyncMutex(c) => Arc::strong_count(c),
+        }
+    }
+
+    /// Run `f` on the contents, or return `None` if the cell is locked
+    /// right now
+    fn with_contents<R>(&self, f: impl FnOnce(&Value) -> R) -> Option<R> {
+        match self {
+            Cell::Shared(c) => c.try_lock().ok().map(|v| f(&v)),
+            Cell::AsyncMutex(c) => c.try_lock().ok().map(|v| f(&v)),
+        }
+    }
+
+    fn contents(&self) -> Option<Value> {
+        self.with_contents(Value::clone)
+    }
+
+    /// Replace the contents with `null`, returning the old value
+    fn clear(&self) -> Option<Value> {
+        match self {
+            Cell::Shared(c) => c
+                .try_lock()
+                .ok()
+                .map(|mut v| std::mem::replace(&mut **v, Value::Null)),
+            Cell::AsyncMutex(c) => c
+                .try_lock()
+                .ok()
+                .map(|mut v| std::mem::replace(&mut *v, Value::Null)),
+        }
+    }
+}
+
+/// Registry size below which freed cells are not pruned on allocation
+const PRUNE_MIN: usize = 1024;
+
+#[derive(Default)]
+struct Heap {
+    cells: Vec<TrackedCell>,
+    stats: GcStats,
+    /// Registry size at which `register` next prunes freed cells
+    prune_at: usize,
+}
+
+impl Heap {
+    /// Drop registrations of freed cells; each `Weak` still pins its
+    /// allocation. The next prune waits until the registry doubles, which
+    /// keeps registration amortized O(1).
+    fn prune(&mut self) {
+        self.cells.retain(TrackedCell::is_alive);
+        self.prune_at = (self.cells.len() * 2).max(PRUNE_MIN);
+    }
+}
+
+static HEAP: OnceLock<Mutex<Heap>> = OnceLock::new();
+static ALLOCATED_SINCE_COLLECT: AtomicUsize = AtomicUsize::new(0);
+
+fn heap() -> &'static Mutex<Heap> {
+    HEAP.get_or_init(|| Mutex::new(Heap::default()))
+}
+
+fn register(cell: TrackedCell) {
+    if let Ok(mut heap) = heap().lock() {
+        heap.cells.push(cell);
+        if heap.cells.len() >= heap.prune_at.max(PRUNE_MIN) {
+            heap.prune();
+        }
+    }
+    ALLOCATED_SINCE_COLLECT.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Register a `SharedValue` cell with the collector
+pub fn track_shared(cell: &Shared<Box<Value>>) {
+    register(TrackedCell::Shared(cell.downgrade()));
+}
+
+/// Register an `AsyncMutex` cell with the collector
+pub fn track_async_mutex(cell: &Arc<tokio::sync::Mutex<Value>>) {
+    register(TrackedCell::AsyncMutex(Arc::downgrade(cell)));
+}
+
+/// Cells allocated since the last collection
+pub fn allocations_since_collect() -> usize {
+    ALLOCATED_SINCE_COLLECT.load(Ordering::Relaxed)
+}
+
+/// Collector counters
+pub fn stats() -> GcStats {
+    heap().lock().map(|h| h.stats).unwrap_or_default()
+}
+
+/// Run a collection with `roots` as the known-live values.
+///
+/// Returns the number of cells swept.
+pub fn collect<'a>(roots: impl IntoIterator<Item = &'a Value>) -> usize {
+    let Ok(mut heap) = heap().lock() else {
+        return 0;
+    };
+    ALLOCATED_SINCE_COLLECT.store(0, Ordering::Relaxed);
+
+    // Upgrade every live cell; the upgrade itself adds one strong reference.
+    let cells: Vec<Cell> = heap
+        .cells
+        .iter()
+        .filter_map(|cell| match cell {
+            TrackedCell::Shared(w) => w.upgrade().map(Cell::Shared),
+            TrackedCell::AsyncMutex(w) => w.upgrade().map(Cell::AsyncMutex),
+        })
+        .collect();
+    // Snapshot before traversal: cloning contents below bumps the counts.
+    let strong: Vec<usize> = cells.iter().map(|c| c.strong_count() - 1).collect();
+    let index: HashMap<usize, usize> = cells
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (cell_addr(c), i))
+        .collect();
+
+    // Phase 1: mark from roots.
+    let mut marked = vec![false; cells.len()];
+    let mut pending: Vec<Value> = roots.into_iter().cloned().collect();
+    mark_all(&cells, &index, &mut marked, &mut pending);
+
+    // Phase 2: cells referenced from outside the unmarked set are live too.
+    let mut internal = vec![0usize; cells.len()];
+    for (i, cell) in cells.iter().enumerate() {
+        if marked[i] {
+            continue;
+        }
+        // Borrow rather than clone: cloning would make every container
+        // look shared. A cell locked by a running thread is certainly in use.
+        if cell
+            .with_contents(|value| count_internal(value, &index, &mut internal))
+            .is_none()
+        {
+            marked[i] = true;
+        }
+    }
+    for i in 0..cells.len() {
+        if !marked[i] && strong[i] > internal[i] {
+            marked[i] = true;
+            if let Some(value) = cells[i].contents() {
+                pending.push(value);
+            }
+        }
+    }
+    mark_all(&cells, &index, &mut marked, &mut pending);
+
+    // Phase 3: sweep.
+    let mut garbage = Vec::new();
+    for (i, cell) in cells.iter().enumerate() {
+        if !marked[i] {
+            if let Some(old) = cell.clear() {
+                garbage.push(old);
+            }
+        }
+    }
+    let freed = garbage.len();
+
+    heap.prune();
+    let live = marked.iter().filter(|m| **m).count();
+    heap.stats.collections += 1;
+    heap.stats.total_freed += freed as u64;
+    heap.stats.last_freed = freed;
+    heap.stats.live = live;
+    drop(heap);
+
+    // Dropping the old contents may release further cells; do it unlocked.
+    drop(garbage);
+    drop(cells);
+    freed
+}
+
+fn cell_addr(cell: &Cell) -> usize {
+    match cell {
+        Cell::Shared(c) => Arc::as_ptr(c) as *const () as usize,
+        Cell::AsyncMutex(c) => Arc::as_ptr(c) as *const () as usize,
+    }
+}
+
+fn value_cell_addr(value: &Value) -> Option<usize> {
+    match value {
+        Value::SharedValue(s) => Some(s.as_ptr() as *const () as usize),
+        Value::AsyncMutex(m) => Some(Arc::as_ptr(m) as *const () as usize),
+        _ => None,
+    }
+}
+
+/// Mark every cell reachable from `pending`
+fn mark_all(
+    cells: &[Cell],
+    index: &HashMap<usize, usize>,
+    marked: &mut [bool],
+    pending: &mut Vec<Value>,
+) {
+    while let Some(value) = pending.pop() {
+        if let Some(&i) = value_cell_addr(&value).and_then(|addr| index.get(&addr)) {
+            if marked[i] {
+                continue;
+            }
+            marked[i] = true;
+            if let Some(inner) = cells[i].contents() {
+                pending.push(inner);
+            }
+            continue;
+        }
+        for_each_child(&value, false, &mut |child| pending.push(child.clone()));
+    }
+}
+
+/// Count references to tracked cells held inside `value`.
+///
+/// Only containers owned exclusively by `value` are entered: a reference
+/// inside an array that something else also holds is not attributable to
+/// this cell, so it is left uncounted (keeping its target alive).
+fn count_internal(value: &Value, index: &HashMap<usize, usize>, internal: &mut [usize]) {
+    if let Some(&i) = value_cell_addr(value).and_then(|addr| index.get(&addr)) {
+        internal[i] += 1;
+        return;
+    }
+    for_each_child(value, true, &mut |child| {
+        count_internal(child, index, internal)
+    });
+}
+
+/// Visit the values directly contained in `value`.
+///
+/// With `exclusive_only`, reference-counted containers are skipped unless
+/// `value` is their only owner.
+fn for_each_child(value: &Value, exclusive_only: bool, visit: &mut dyn FnMut(&Value)) {
+    let children: Vec<Value>;
+    let slice: &[Value] = match value {
+        Value::Array(arr) if !exclusive_only || arr.is_exclusively_owned() => arr.as_slice(),
+        Value::Tuple(items) if !exclusive_only || Arc::strong_count(items) == 1 => items.as_slice(),
+        Value::Closure(closure) if !exclusive_only || Arc::strong_count(&closure.upvalues) == 1 => {
+            closure.upvalues.as_slice()
+        }
+        Value::EnumValue { data, .. } => data.as_slice(),
+        Value::Option(Some(inner)) => std::slice::from_ref(&**inner),
+        Value::Result(Ok(inner)) | Value::Result(Err(inner)) => std::slice::from_ref(&**inner),
+        Value::Map(map) if !exclusive_only || map.is_exclusively_owned() => {
+            children = map.values();
+            children.as_slice()
+        }
+        Value::Queue(queue) if !exclusive_only || queue.is_exclusively_owned() => {
+            children = queue.inner().to_vec();
+            children.as_slice()
+        }
+        Value::Stack(stack) if !exclusive_only || stack.is_exclusively_owned() => {
+            children = stack.inner().to_vec();
+            children.as_slice()
+        }
+        _ => &[],
+    };
+    for child in slice {
+        visit(child);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::ValueArray;
+
+    fn mutex_cell(value: Value) -> Arc<tokio::sync::Mutex<Value>> {
+        let cell = Arc::new(tokio::sync::Mutex::new(value));
+        track_async_mutex(&cell);
+        cell
+    }
+
+    #[test]
+    fn test_freed_cells_are_pruned_without_collecting() {
+        for _ in 0..10 * PRUNE_MIN {
+            drop(mutex_cell(Value::Null));
+        }
+        let registered = heap().lock().unwrap().cells.len();
+        assert!(registered <= 2 * PRUNE_MIN, "{registered} cells registered");
+    }
+
+    #[test]
+    fn test_collect_frees_unreachable_cycle() {
+        let cell = mutex_cell(Value::Null);
+        *cell.blocking_lock() =
+            Value::Array(ValueArray::from_vec(vec![Value::AsyncMutex(cell.clone())]));
+        let weak = Arc::downgrade(&cell);
+        drop(cell);
+        assert!(weak.upgrade().is_some(), "cycle keeps the cell alive");
+
+        collect(std::iter::empty());
+        assert!(weak.upgrade().is_none(), "collection breaks the cycle");
+    }
+
+    #[test]
+    fn test_collect_keeps_externally_held_cycle() {
+        let cell = mutex_cell(Value::Null);
+        *cell.blocking_lock() =
+            Value::Array(ValueArray::from_vec(vec![Value::AsyncMutex(cell.clone())]));
+
+        // No roots are passed, but `cell` is still held here.
+        collect(std::iter::empty());
+        assert!(matches!(&*cell.blocking_lock(), Value::Array(a) if a.len() == 1));
+
+        *cell.blocking_lock() = Value::Null;
+    }
+
+    #[test]
+    fn test_shared_array_contents_are_not_attributed() {
+        let cell = mutex_cell(Value::Null);
+        let arr = ValueArray::from_vec(vec![Value::AsyncMutex(cell.clone())]);
+        *cell.blocking_lock() = Value::Array(arr.clone());
+        let weak = Arc::downgrade(&cell);
+        drop(cell);
+
+        // `arr` is held outside the cycle, so the cell stays reachable.
+        collect(std::iter::empty());
+        assert!(weak.upgrade().is_some());
+        drop(arr);
+    }
+
+    #[test]
+    fn test_shared_value_cycle_is_collected() {
+        let shared = Shared::new(Box::new(Value::Null));
+        track_shared(&shared);
+        shared.with_mut(|v| **v = Value::SharedValue(shared.clone()));
+        let weak = shared.downgrade();
+        drop(shared);
+
+        collect(std::iter::empty());
+        assert_eq!(weak.strong_count(), 0);
+    }
+
+    #[test]
+    fn test_next_threshold_grows_with_survivors() {
+        let config = GcConfig::default()
+            .with_threshold(100)
+            .with_growth_factor(2.0);
+        assert_eq!(config.next_threshold(10), 100);
+        assert_eq!(config.next_threshold(80), 160);
+    }
+}
@@ -0,0 +1,107 @@
+//! Global variable table
+//!
+//! The compiler interns every global name into [`Bytecode::global_names`]
+//! and `GetGlobal`/`SetGlobal` carry the slot, so a global access is a
+//! vector index. The name index here serves the lookups that start from a
+//! name: the embedding API, trait dispatch, FFI callbacks and the debugger.
+//!
+//! A [`GlobalResolver`] supplies globals that have no value yet, on first
+//! read, so a host can expose large data without converting it up front.
+//!
+//! [`Bytecode::global_names`]: crate::bytecode::Bytecode::global_names
+
+use crate::value::Value;
+use std::collections::HashMap;
+
+/// Supplies globals the host has not set, when a script first reads them
+///
+/// The VM consults the resolver only for a global with no value that is not
+/// a builtin, and stores the answer in the global's slot, so each name is
+/// resolved at most once per VM.
+pub trait GlobalResolver: Send + Sync {
+    /// Names this resolver can provide
+    ///
+    /// The embedding API declares these to the binder and type checker, so
+    /// scripts that use them compile. They are typed `Unknown`.
+    fn names(&self) -> Vec<String>;
+
+    /// Value of the global `name`, or `None` to leave it undefined
+    fn resolve(&self, name: &str) -> Option<Value>;
+}
+
+/// One value slot per global name in the VM's bytecode
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GlobalTable {
+    /// `None` until the global is first assigned
+    values: Vec<Option<Value>>,
+    /// Name → slot, mirroring `Bytecode::global_names`
+    slots: HashMap<String, usize>,
+}
+
+impl GlobalTable {
+    /// A table with one empty slot per name
+    pub(crate) fn new(names: &[String]) -> Self {
+        Self {
+            values: vec![None; names.len()],
+            slots: names
+                .iter()
+                .enumerate()
+                .map(|(slot, name)| (name.clone(), slot))
+                .collect(),
+        }
+    }
+
+    /// Slot for `name`, appending it to `names` (the bytecode's name table)
+    /// when it has none yet
+    pub(crate) fn intern(&mut self, names: &mut Vec<String>, name: &str) -> usize {
+        if let Some(&slot) = self.slots.get(name) {
+            return slot;
+        }
+        let slot = names.len();
+        names.push(name.to_string());
+        self.slots.insert(name.to_string(), slot);
+        self.values.push(None);
+        slot
+    }
+
+    /// Slot for `name`, if any code or the host has referenced it
+    #[inline]
+    pub(crate) fn slot(&self, name: &str) -> Option<usize> {
+        self.slots.get(name).copied()
+    }
+
+    /// Value of the global in `slot`, if it has been assigned
+    #[inline]
+    pub(crate) fn get(&self, slot: usize) -> Option<&Value> {
+        self.values.get(slot).and_then(Option::as_ref)
+    }
+
+    /// Value of the global called `name`, if it has been assigned
+    pub(crate) fn get_by_name(&self, name: &str) -> Option<&Value> {
+        self.slot(name).and_then(|slot| self.get(slot))
+    }
+
+    #[inline]
+    pub(crate) fn set(&mut self, slot: usize, value: Value) {
+        if slot >= self.values.len() {
+            self.values.resize(slot + 1, None);
+        }
+        self.values[slot] = Some(value);
+    }
+
+    /// Assigned globals with their names, in slot order
+    pub(crate) fn iter<'a>(
+        &'a self,
+        names: &'a [String],
+    ) -> impl Iterator<Item = (&'a String, &'a Value)> + 'a {
+        names
+            .iter()
+            .zip(&self.values)
+            .filter_map(|(name, value)| value.as_ref().map(|value| (name, value)))
+    }
+
+    /// Assigned global values (GC roots)
+    pub(crate) fn values(&self) -> impl Iterator<Item = &Value> {
+        self.values.iter().flatten()
+    }
+}
@@ -0,0 +1,1758 @@
+//! Per-opcode instruction handlers
+//!
+//! One method per opcode, called through the dispatch table in `dispatch.rs`.
+//! `execute_loop` has already consumed the opcode byte (and run the debugger
+//! and profiler hooks); a handler reads its own operands and returns
+//! [`Flow::Halt`] only when the loop must stop.
+
+use super::dispatch::{self, Flow};
+use super::frame::ErrorHandler;
+#[cfg(debug_assertions)]
+use super::StackValueOrigin;
+use super::{WatchSlot, VM};
+use crate::bytecode::Opcode;
+use crate::span::Span;
+use crate::value::{RuntimeError, Value, ValueArray};
+use std::sync::Arc;
+
+impl VM {
+    // ===== Constants =====
+
+    pub(super) fn op_constant(&mut self) -> Result<Flow, RuntimeError> {
+        let index = self.read_u16()? as usize;
+        if index >= self.bytecode.constants.len() {
+            return Err(RuntimeError::UnknownOpcode {
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            });
+        }
+        let value = self.bytecode.constants[index].clone();
+        self.push(value);
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_null(&mut self) -> Result<Flow, RuntimeError> {
+        self.push(Value::Null);
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_true(&mut self) -> Result<Flow, RuntimeError> {
+        self.push(Value::Bool(true));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_false(&mut self) -> Result<Flow, RuntimeError> {
+        self.push(Value::Bool(false));
+        Ok(Flow::Next)
+    }
+
+    // ===== Variables =====
+
+    pub(super) fn op_get_local(&mut self) -> Result<Flow, RuntimeError> {
+        let index = self.read_u16()? as usize;
+        let base = self.current_frame().stack_base;
+        let absolute_index = base + index;
+        if absolute_index >= self.ctx.stack.len() {
+            return Err(RuntimeError::StackUnderflow {
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            });
+        }
+        // Debug mode: reject reads of consumed (moved) local slots.
+        #[cfg(debug_assertions)]
+        {
+            let frame_idx = self.ctx.frames.len() - 1;
+            if self.ctx.consumed_slots[frame_idx]
+                .get(index)
+                .copied()
+                .unwrap_or(false)
+            {
+                return Err(RuntimeError::TypeError {
+                    msg: format!(
+                        "use of moved value: local[{}] was passed to 'own' parameter and is no longer valid",
+                        index
+                    ),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                });
+            }
+        }
+        let value = self.ctx.stack[absolute_index].clone();
+        self.push(value);
+        // Record that the top-of-stack value originated from this local slot.
+        #[cfg(debug_assertions)]
+        {
+            if let Some(origin) = self.ctx.value_origins.last_mut() {
+                *origin = Some(StackValueOrigin::Local(index));
+            } else {
+                return Err(RuntimeError::InternalError {
+                    msg: "Missing value origin stack for local read".to_string(),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                });
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_set_local(&mut self) -> Result<Flow, RuntimeError> {
+        let index = self.read_u16()? as usize;
+        let base = self.current_frame().stack_base;
+        let local_count = self.current_frame().local_count;
+        let absolute_index = base + index;
+        let value = self.peek(0).clone();
+
+        // SAFETY CHECK: Prevent unbounded stack growth
+        // This prevents memory explosion from invalid bytecode or compiler bugs
+        if index >= local_count {
+            return Err(RuntimeError::StackUnderflow {
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            });
+        }
+
+        let watch = self.watch_for(WatchSlot::Local {
+            frame_depth: self.ctx.frames.len(),
+            stack_index: absolute_index,
+        });
+        let old_value = watch.and_then(|_| self.ctx.stack.get(absolute_index).cloned());
+
+        // Extend stack if needed (for local variables not yet initialized).
+        // The outer `index >= local_count` guard above already ensures
+        // absolute_index < base + local_count, so extension is always
+        // within the declared local area.
+        if absolute_index >= self.ctx.stack.len() {
+            let needed = absolute_index - self.ctx.stack.len() + 1;
+            for _ in 0..needed {
+                self.push(Value::Null);
+            }
+        }
+        self.ctx.stack[absolute_index] = value;
+        if let Some(id) = watch {
+            return Ok(self.watched_write(id, old_value, self.peek(0).clone()));
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_get_global(&mut self) -> Result<Flow, RuntimeError> {
+        let slot = self.read_u16()? as usize;
+        let Some(name) = self.bytecode.global_names.get(slot) else {
+            return Err(RuntimeError::UnknownOpcode {
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            });
+        };
+        // Debug mode: reject reads of consumed globals.
+        #[cfg(debug_assertions)]
+        if self.ctx.consumed_globals.contains(&slot) {
+            return Err(RuntimeError::TypeError {
+                msg: format!(
+                    "use of moved value: '{}' was passed to 'own' parameter and is no longer valid",
+                    name
+                ),
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            });
+        }
+        let value = if name == "None" {
+            // Constructor literal: None always evaluates to Option::None
+            Value::Option(None)
+        } else if let Some(v) = self.globals.get(slot) {
+            v.clone()
+        } else if crate::stdlib::is_builtin(name) || crate::stdlib::is_array_intrinsic(name) {
+            // Builtin or intrinsic - return builtin value
+            Value::Builtin(std::sync::Arc::from(name.as_str()))
+        } else if crate::method_dispatch::is_static_namespace(name) {
+            // Static namespace (Json, Math, console, reflect, etc.) - return as builtin
+            Value::Builtin(std::sync::Arc::from(name.as_str()))
+        } else if let Some(v) = self
+            .global_resolver
+            .as_ref()
+            .and_then(|resolver| resolver.resolve(name))
+        {
+            // Host-resolved global: keep it so later reads skip the resolver
+            self.globals.set(slot, v.clone());
+            v
+        } else {
+            // B22: Math constants removed as bare identifiers. Use Math.PI, Math.E, etc.
+            return Err(RuntimeError::UndefinedVariable {
+                name: name.clone(),
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            });
+        };
+        self.push(value);
+        // Record global origin for own-consume tracking (debug builds only).
+        // Only track user-defined globals (not builtins, constructors, math constants).
+        #[cfg(debug_assertions)]
+        if self.globals.get(slot).is_some() {
+            if let Some(origin) = self.ctx.value_origins.last_mut() {
+                *origin = Some(StackValueOrigin::Global(slot));
+            } else {
+                return Err(RuntimeError::InternalError {
+                    msg: "Missing value origin stack for global read".to_string(),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                });
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_set_global(&mut self) -> Result<Flow, RuntimeError> {
+        let slot = self.read_u16()? as usize;
+        if slot >= self.bytecode.global_names.len() {
+            return Err(RuntimeError::UnknownOpcode {
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            });
+        }
+        let value = self.peek(0).clone();
+        let watch = self.watch_for(WatchSlot::Global(slot));
+        let old_value = watch.and_then(|_| self.globals.get(slot).cloned());
+        self.globals.set(slot, value);
+        if let Some(id) = watch {
+            return Ok(self.watched_write(id, old_value, self.peek(0).clone()));
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_make_closure(&mut self) -> Result<Flow, RuntimeError> {
+        let func_const_idx = self.read_u16()? as usize;
+        let n_upvalues = self.read_u16()? as usize;
+
+        // Get the FunctionRef from constant pool
+        let func = match self.bytecode.constants.get(func_const_idx) {
+            Some(Value::Function(f)) => f.clone(),
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "MakeClosure: constant is not a function".to_string(),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                })
+            }
+        };
+
+        // Pop upvalues from stack (in reverse order since stack is LIFO)
+        let mut upvalues = Vec::with_capacity(n_upvalues);
+        for _ in 0..n_upvalues {
+            upvalues.push(self.pop());
+        }
+        upvalues.reverse(); // Restore capture order
+
+        let closure = crate::value::ClosureRef {
+            func,
+            upvalues: std::sync::Arc::new(upvalues),
+        };
+        self.push(Value::Closure(closure));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_get_upvalue(&mut self) -> Result<Flow, RuntimeError> {
+        let idx = self.read_u16()? as usize;
+        let value = match self.current_frame().upvalues.get(idx) {
+            Some(v) => v.clone(),
+            None => {
+                return Err(RuntimeError::TypeError {
+                    msg: format!(
+                        "Upvalue index {} out of bounds (closure has {} upvalues)",
+                        idx,
+                        self.current_frame().upvalues.len()
+                    ),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                })
+            }
+        };
+        self.push(value);
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_set_upvalue(&mut self) -> Result<Flow, RuntimeError> {
+        let idx = self.read_u16()? as usize;
+        let value = self.peek(0).clone();
+        let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
+        let frame = self
+            .ctx
+            .frames
+            .last_mut()
+            .ok_or_else(|| RuntimeError::InternalError {
+                msg: "Missing call frame for SetUpvalue".to_string(),
+                span,
+            })?;
+        let upvalues = std::sync::Arc::make_mut(&mut frame.upvalues);
+        if idx < upvalues.len() {
+            upvalues[idx] = value;
+        } else {
+            return Err(RuntimeError::TypeError {
+                msg: format!(
+                    "SetUpvalue: index {} out of bounds (closure has {} upvalues)",
+                    idx,
+                    upvalues.len()
+                ),
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            });
+        }
+        Ok(Flow::Next)
+    }
+
+    // ===== Arithmetic =====
+
+    pub(super) fn op_add(&mut self) -> Result<Flow, RuntimeError> {
+        let b = self.pop();
+        let a = self.pop();
+        match (&a, &b) {
+            (Value::Number(x), Value::Number(y)) => {
+                let result = x + y;
+                if result.is_nan() || result.is_infinite() {
+                    return Err(RuntimeError::InvalidNumericResult {
+                        span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                    });
+                }
+                self.push(Value::Number(result));
+            }
+            (Value::String(x), Value::String(y)) => {
+                // Track memory for the concatenated string
+                let new_len = x.len() + y.len();
+                self.track_memory(Self::estimate_string_size(new_len))?;
+
+                let mut joined = String::with_capacity(new_len);
+                joined.push_str(x);
+                joined.push_str(y);
+                self.push(Value::String(Arc::new(joined)));
+            }
+            (Value::Array(x), Value::Array(y)) => {
+                let new_len = x.len() + y.len();
+                self.track_memory(Self::estimate_array_size(new_len))?;
+
+                let mut elements = Vec::with_capacity(new_len);
+                elements.extend_from_slice(x.as_slice());
+                elements.extend_from_slice(y.as_slice());
+                self.push(Value::Array(ValueArray::from_vec(elements)));
+            }
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "Invalid operands for +".to_string(),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                })
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_sub(&mut self) -> Result<Flow, RuntimeError> {
+        self.binary_numeric_op(|a, b| a - b)?;
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_mul(&mut self) -> Result<Flow, RuntimeError> {
+        self.binary_numeric_op(|a, b| a * b)?;
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_div(&mut self) -> Result<Flow, RuntimeError> {
+        let b = self.pop_number()?;
+        let a = self.pop_number()?;
+        if b == 0.0 {
+            return Err(RuntimeError::DivideByZero {
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            });
+        }
+        let result = a / b;
+        if result.is_nan() || result.is_infinite() {
+            return Err(RuntimeError::InvalidNumericResult {
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            });
+        }
+        self.push(Value::Number(result));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_mod(&mut self) -> Result<Flow, RuntimeError> {
+        let b = self.pop_number()?;
+        let a = self.pop_number()?;
+        if b == 0.0 {
+            return Err(RuntimeError::DivideByZero {
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            });
+        }
+        let result = a % b;
+        if result.is_nan() || result.is_infinite() {
+            return Err(RuntimeError::InvalidNumericResult {
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            });
+        }
+        self.push(Value::Number(result));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_negate(&mut self) -> Result<Flow, RuntimeError> {
+        let value = self.pop();
+        match value {
+            Value::Number(n) => self.push(Value::Number(-n)),
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "Cannot negate non-number".to_string(),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                })
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    // ===== Comparison =====
+
+    pub(super) fn op_equal(&mut self) -> Result<Flow, RuntimeError> {
+        let b = self.pop();
+        let a = self.pop();
+        self.push(Value::Bool(a == b));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_not_equal(&mut self) -> Result<Flow, RuntimeError> {
+        let b = self.pop();
+        let a = self.pop();
+        self.push(Value::Bool(a != b));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_less(&mut self) -> Result<Flow, RuntimeError> {
+        let b = self.pop_number()?;
+        let a = self.pop_number()?;
+        self.push(Value::Bool(a < b));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_less_equal(&mut self) -> Result<Flow, RuntimeError> {
+        let b = self.pop_number()?;
+        let a = self.pop_number()?;
+        self.push(Value::Bool(a <= b));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_greater(&mut self) -> Result<Flow, RuntimeError> {
+        let b = self.pop_number()?;
+        let a = self.pop_number()?;
+        self.push(Value::Bool(a > b));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_greater_equal(&mut self) -> Result<Flow, RuntimeError> {
+        let b = self.pop_number()?;
+        let a = self.pop_number()?;
+        self.push(Value::Bool(a >= b));
+        Ok(Flow::Next)
+    }
+
+    // ===== Logical =====
+
+    pub(super) fn op_not(&mut self) -> Result<Flow, RuntimeError> {
+        let value = self.pop();
+        match value {
+            Value::Bool(b) => self.push(Value::Bool(!b)),
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "Cannot apply ! to non-boolean".to_string(),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                })
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_and(&mut self) -> Result<Flow, RuntimeError> {
+        // Non-short-circuit And: both operands already evaluated
+        // Short-circuit is handled by compiler via JumpIfFalse
+        let b = self.pop();
+        let a = self.pop();
+        match (&a, &b) {
+            (Value::Bool(a_val), Value::Bool(b_val)) => {
+                self.push(Value::Bool(*a_val && *b_val));
+            }
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: format!("Cannot apply && to {} and {}", a.type_name(), b.type_name()),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                });
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_or(&mut self) -> Result<Flow, RuntimeError> {
+        // Non-short-circuit Or: both operands already evaluated
+        // Short-circuit is handled by compiler via JumpIfFalse
+        let b = self.pop();
+        let a = self.pop();
+        match (&a, &b) {
+            (Value::Bool(a_val), Value::Bool(b_val)) => {
+                self.push(Value::Bool(*a_val || *b_val));
+            }
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: format!("Cannot apply || to {} and {}", a.type_name(), b.type_name()),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                });
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    // ===== Control Flow =====
+
+    pub(super) fn op_jump(&mut self) -> Result<Flow, RuntimeError> {
+        let offset = self.read_i16()?;
+        self.ctx.ip = (self.ctx.ip as isize + offset as isize) as usize;
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_jump_if_false(&mut self) -> Result<Flow, RuntimeError> {
+        let offset = self.read_i16()?;
+        let condition = self.pop();
+        if !condition.is_truthy() {
+            self.ctx.ip = (self.ctx.ip as isize + offset as isize) as usize;
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_loop(&mut self) -> Result<Flow, RuntimeError> {
+        let offset = self.read_i16()?;
+        self.ctx.ip = (self.ctx.ip as isize + offset as isize) as usize;
+        // Loop back-edges are the collector's safe point.
+        self.maybe_collect_garbage();
+        Ok(Flow::Next)
+    }
+
+    // ===== Functions =====
+
+    pub(super) fn op_trait_dispatch(&mut self) -> Result<Flow, RuntimeError> {
+        let trait_idx = self.read_u16()? as usize;
+        let method_idx = self.read_u16()? as usize;
+        let arg_count = self.read_u8()? as usize;
+
+        let trait_name = match self.bytecode.constants.get(trait_idx) {
+            Some(Value::String(s)) => s.as_ref().clone(),
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "Expected string constant for trait name".to_string(),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                })
+            }
+        };
+        let method_name = match self.bytecode.constants.get(method_idx) {
+            Some(Value::String(s)) => s.as_ref().clone(),
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "Expected string constant for method name".to_string(),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                })
+            }
+        };
+
+        let mut args = Vec::with_capacity(arg_count);
+        for _ in 0..arg_count {
+            args.push(self.pop());
+        }
+        args.reverse();
+
+        let receiver = args
+            .first()
+            .cloned()
+            .ok_or_else(|| RuntimeError::TypeError {
+                msg: "Trait dispatch requires a receiver".to_string(),
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            })?;
+        let dispatch_type = self
+            .struct_name_for_value(&receiver)
+            .unwrap_or_else(|| receiver.type_name());
+        let mangled_name = format!("__impl__{}__{}__{}", dispatch_type, trait_name, method_name);
+        let function = self
+            .globals
+            .get_by_name(&mangled_name)
+            .cloned()
+            .ok_or_else(|| RuntimeError::TypeError {
+                msg: format!(
+                    "Trait method '{}' not found (impl not registered for this type)",
+                    method_name
+                ),
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            })?;
+
+        self.push(function);
+        for arg in args {
+            self.push(arg);
+        }
+        self.execute_call(arg_count)?;
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_call(&mut self) -> Result<Flow, RuntimeError> {
+        let arg_count = self.read_u8()? as usize;
+        // `await(future)` waits like the `await` operator, so it suspends an
+        // `async fn` too
+        if arg_count == 1
+            && matches!(self.peek(1), Value::Builtin(name) if name.as_ref() == "await")
+        {
+            if let Value::Future(future) = self.peek(0).clone() {
+                self.pop();
+                self.pop();
+                return self.await_future(&future);
+            }
+        }
+        self.execute_call(arg_count)?;
+        Ok(Flow::Next)
+    }
+
+    /// Run the current frame's deferred blocks in LIFO order
+    ///
+    /// Called when the frame exits, by `Return` or by unwinding to a `try`
+    /// handler in a caller.
+    pub(super) fn run_frame_defers(&mut self) -> Result<(), RuntimeError> {
+        let frame_idx = self.ctx.frames.len().saturating_sub(1);
+        while frame_idx < self.ctx.defer_stacks.len() {
+            let Some((body_start, body_len)) = self.ctx.defer_stacks[frame_idx].pop() else {
+                break;
+            };
+            // Save current IP
+            let saved_ip = self.ctx.ip;
+
+            // Execute defer body by setting IP and running until body_end
+            // We use a bounded loop that processes opcodes directly
+            self.ctx.ip = body_start;
+            let body_end = body_start + body_len;
+
+            // Execute opcodes until we reach body_end
+            while self.ctx.ip < body_end {
+                let opcode = match self.read_opcode() {
+                    Ok(op) => op,
+                    Err(_) => break,
+                };
+
+                // Execute the opcode (simplified dispatch for defer bodies)
+                // This handles common cases; complex defers may need full dispatch
+                match opcode {
+                    Opcode::Constant => {
+                        let index = self.read_u16()? as usize;
+                        let value = self.bytecode.constants[index].clone();
+                        self.push(value);
+                    }
+                    Opcode::GetGlobal => {
+                        let slot = self.read_u16()? as usize;
+                        let value = self.globals.get(slot).cloned();
+                        self.push(value.unwrap_or(Value::Null));
+                    }
+                    Opcode::Call => {
+                        let arg_count = self.read_u8()? as usize;
+                        let frames_before_call = self.ctx.frames.len();
+                        self.execute_call(arg_count)?;
+                        // H-364: If execute_call pushed a new frame (user-defined
+                        // function), drive it to completion via the full dispatch
+                        // loop so that Return, arithmetic, GetLocal etc. all work.
+                        // The loop stops when frames.len() drops back to
+                        // frames_before_call, leaving the return value on the stack.
+                        if self.ctx.frames.len() > frames_before_call {
+                            self.execute_loop(Some(frames_before_call))?;
+                        }
+                    }
+                    Opcode::Pop => {
+                        self.pop();
+                    }
+                    Opcode::GetLocal => {
+                        let index = self.read_u16()? as usize;
+                        let base = self.current_frame().stack_base;
+                        let value = self.ctx.stack[base + index].clone();
+                        self.push(value);
+                    }
+                    _ => {
+                        // For other opcodes, skip their operands
+                        let size = dispatch::operand_size(opcode);
+                        self.ctx.ip += size;
+                    }
+                }
+            }
+
+            // Restore IP
+            self.ctx.ip = saved_ip;
+        }
+        Ok(())
+    }
+
+    pub(super) fn op_return(&mut self) -> Result<Flow, RuntimeError> {
+        // Pop the return value from stack (if any)
+        let return_value = if self.ctx.stack.is_empty() {
+            Value::Null
+        } else {
+            self.pop()
+        };
+
+        // Execute deferred blocks in LIFO order before returning
+        self.run_frame_defers()?;
+
+        // Pop the call frame and defer stack
+        let frame = self.ctx.frames.pop();
+        self.ctx.defer_stacks.pop();
+        #[cfg(debug_assertions)]
+        self.ctx.consumed_slots.pop();
+        // A `return` from inside a `try` block leaves its handler installed
+        self.drop_handlers_from(self.ctx.frames.len());
+        self.drop_frame_watches();
+
+        if let Some(f) = frame {
+            // Clean up the stack (remove locals, arguments, and function value)
+            self.ctx.stack.truncate(f.stack_base);
+            #[cfg(debug_assertions)]
+            self.ctx.value_origins.truncate(f.stack_base);
+            // Also remove the function value (one slot below stack_base)
+            if f.stack_base > 0 && !self.ctx.stack.is_empty() {
+                self.ctx.stack.pop();
+                #[cfg(debug_assertions)]
+                self.ctx.value_origins.pop();
+            }
+
+            // Restore IP to return address
+            self.ctx.ip = f.return_ip;
+
+            // Push return value
+            self.push(return_value);
+        } else {
+            // Returning from main - we're done
+            // Push the return value and halt
+            self.push(return_value);
+            return Ok(Flow::Halt);
+        }
+        Ok(Flow::Next)
+    }
+
+    // ===== Arrays =====
+
+    pub(super) fn op_array(&mut self) -> Result<Flow, RuntimeError> {
+        let size = self.read_u16()? as usize;
+
+        // Track memory allocation before creating the array
+        self.track_memory(Self::estimate_array_size(size))?;
+
+        let mut elements = Vec::with_capacity(size);
+        for _ in 0..size {
+            elements.push(self.pop());
+        }
+        elements.reverse(); // Stack is LIFO, so reverse to get correct order
+        self.push(Value::Array(ValueArray::from_vec(elements)));
+        Ok(Flow::Next)
+    }
+
+    // ===== Tuples =====
+
+    pub(super) fn op_tuple(&mut self) -> Result<Flow, RuntimeError> {
+        let size = self.read_u16()? as usize;
+        let mut elements = Vec::with_capacity(size);
+        for _ in 0..size {
+            elements.push(self.pop());
+        }
+        elements.reverse(); // Stack is LIFO, so reverse to get correct order
+        self.push(Value::Tuple(Arc::new(elements)));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_tuple_get(&mut self) -> Result<Flow, RuntimeError> {
+        let index = self.read_u16()? as usize;
+        let tuple = self.pop();
+        match tuple {
+            Value::Tuple(elems) => {
+                if index >= elems.len() {
+                    return Err(RuntimeError::OutOfBounds {
+                        span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                    });
+                }
+                self.push(elems[index].clone());
+            }
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "TupleGet applied to non-tuple value".to_string(),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                });
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_get_index(&mut self) -> Result<Flow, RuntimeError> {
+        let index_val = self.pop();
+        let target = self.pop();
+        match target {
+            Value::Array(arr) => {
+                // Array indexing requires number
+                match index_val {
+                    Value::Number(index) => {
+                        if index.fract() != 0.0 || index < 0.0 {
+                            return Err(RuntimeError::InvalidIndex {
+                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                            });
+                        }
+                        let idx = index as usize;
+                        if idx >= arr.len() {
+                            return Err(RuntimeError::OutOfBounds {
+                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                            });
+                        }
+                        self.push(arr[idx].clone());
+                    }
+                    Value::Range {
+                        start,
+                        end,
+                        inclusive,
+                    } => {
+                        let start = start.unwrap_or(0.0);
+                        let mut end_val = end.unwrap_or(arr.len() as f64);
+                        if inclusive && end.is_some() {
+                            end_val += 1.0;
+                        }
+                        let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
+                        let sliced =
+                            crate::stdlib::array::slice(arr.as_slice(), start, end_val, span)?;
+                        self.push(sliced);
+                    }
+                    _ => {
+                        return Err(RuntimeError::InvalidIndex {
+                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                        });
+                    }
+                }
+            }
+            Value::String(s) => {
+                // String indexing by character position (Unicode-aware)
+                if let Value::Number(index) = index_val {
+                    if index.fract() != 0.0 || index < 0.0 {
+                        return Err(RuntimeError::InvalidIndex {
+                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                        });
+                    }
+                    let idx = index as usize;
+                    let chars: Vec<char> = s.chars().collect();
+                    if idx >= chars.len() {
+                        return Err(RuntimeError::OutOfBounds {
+                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                        });
+                    }
+                    self.push(Value::string(chars[idx].to_string()));
+                } else {
+                    return Err(RuntimeError::InvalidIndex {
+                        span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                    });
+                }
+            }
+            // Byte indexing yields the byte as a number 0-255
+            Value::Bytes(data) => {
+                let Value::Number(index) = index_val else {
+                    return Err(RuntimeError::InvalidIndex {
+                        span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                    });
+                };
+                if index.fract() != 0.0 || index < 0.0 {
+                    return Err(RuntimeError::InvalidIndex {
+                        span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                    });
+                }
+                match data.get(index as usize) {
+                    Some(byte) => self.push(Value::Number(*byte as f64)),
+                    None => {
+                        return Err(RuntimeError::OutOfBounds {
+                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                        })
+                    }
+                }
+            }
+            Value::JsonValue(json) => {
+                // JSON indexing accepts string or number
+                let result = match index_val {
+                    Value::String(key) => json.index_str(key.as_ref()),
+                    Value::Number(n) => json.index_num(n),
+                    _ => {
+                        return Err(RuntimeError::TypeError {
+                            msg: "JSON index must be string or number".to_string(),
+                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                        })
+                    }
+                };
+                self.push(Value::JsonValue(Arc::new(result)));
+            }
+            // H-116: range as for-in target — index i yields start + i
+            Value::Range { start, .. } => {
+                if let Value::Number(idx) = index_val {
+                    let s = start.unwrap_or(0.0);
+                    self.push(Value::Number(s + idx));
+                } else {
+                    return Err(RuntimeError::InvalidIndex {
+                        span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                    });
+                }
+            }
+            // Map indexing: map["key"] → Option<V>
+            // Returns Some(value) if key exists, None if not.
+            // Mirrors the Map.get() stdlib method for dynamic key access.
+            Value::Map(map) => {
+                let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
+                let key = crate::stdlib::collections::hash::HashKey::from_value(&index_val, span)?;
+                match map.get(&key).cloned() {
+                    Some(v) => self.push(Value::Option(Some(Box::new(v)))),
+                    None => self.push(Value::Option(None)),
+                }
+            }
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "Cannot index non-array/string/json".to_string(),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                })
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_set_index(&mut self) -> Result<Flow, RuntimeError> {
+        let value = self.pop();
+        let index = self.pop_number()?;
+        let mut array = self.pop();
+        match &mut array {
+            Value::Array(arr) => {
+                if index.fract() != 0.0 || index < 0.0 {
+                    return Err(RuntimeError::InvalidIndex {
+                        span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                    });
+                }
+                let idx = index as usize;
+                if idx >= arr.len() {
+                    return Err(RuntimeError::OutOfBounds {
+                        span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                    });
+                }
+                // CoW: set triggers Arc::make_mut if arr is shared
+                arr.set(idx, value);
+            }
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "Cannot index non-array".to_string(),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                })
+            }
+        }
+        // Push the mutated array back — compiler emits SetLocal/SetGlobal to write it
+        // back to the variable, then Pop removes it from the expression stack.
+        self.push(array);
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_get_field(&mut self) -> Result<Flow, RuntimeError> {
+        let key_val = self.pop();
+        let map_val = self.pop();
+        let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
+        let key = crate::stdlib::collections::hash::HashKey::from_value(&key_val, span)?;
+
+        match map_val {
+            Value::Map(map) => match map.get(&key).cloned() {
+                Some(value) => self.push(value),
+                None => {
+                    let field = match key_val {
+                        Value::String(s) => s.as_ref().to_string(),
+                        other => other.type_name().to_string(),
+                    };
+                    return Err(RuntimeError::TypeError {
+                        msg: format!("Missing field '{}'", field),
+                        span,
+                    });
+                }
+            },
+            // Destructuring a JSON object reads its keys; the values stay json
+            Value::JsonValue(json) if json.is_object() => {
+                let field = match &key_val {
+                    Value::String(s) => s.as_str(),
+                    _ => "",
+                };
+                match json.as_object().and_then(|obj| obj.get(field)) {
+                    Some(value) => self.push(Value::JsonValue(Arc::new(value.clone()))),
+                    None => {
+                        return Err(RuntimeError::TypeError {
+                            msg: format!("Missing field '{}'", field),
+                            span,
+                        });
+                    }
+                }
+            }
+            other => {
+                return Err(RuntimeError::TypeError {
+                    msg: format!(
+                        "Cannot access field on non-record type {}",
+                        other.type_name()
+                    ),
+                    span,
+                })
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_set_field(&mut self) -> Result<Flow, RuntimeError> {
+        let value = self.pop();
+        let key_val = self.pop();
+        let mut map_val = self.pop();
+        let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
+        let key = crate::stdlib::collections::hash::HashKey::from_value(&key_val, span)?;
+        let field_name = match &key_val {
+            Value::String(s) => s.as_ref().to_string(),
+            other => other.type_name().to_string(),
+        };
+
+        match &mut map_val {
+            Value::Map(map) => {
+                let existing = map
+                    .get(&key)
+                    .cloned()
+                    .ok_or_else(|| RuntimeError::TypeError {
+                        msg: format!("Missing field '{}'", field_name),
+                        span,
+                    })?;
+                if existing.type_name() != value.type_name() {
+                    return Err(RuntimeError::TypeError {
+                        msg: format!(
+                            "Type mismatch for field '{}': expected {}, found {}",
+                            field_name,
+                            existing.type_name(),
+                            value.type_name()
+                        ),
+                        span,
+                    });
+                }
+                map.insert(key, value);
+            }
+            other => {
+                return Err(RuntimeError::TypeError {
+                    msg: format!(
+                        "Cannot assign field on non-record type {}",
+                        other.type_name()
+                    ),
+                    span,
+                })
+            }
+        }
+        // Push the mutated map back — compiler emits SetLocal/SetGlobal to write it back
+        self.push(map_val);
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_slice(&mut self) -> Result<Flow, RuntimeError> {
+        let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
+        let end = match self.pop() {
+            Value::Number(n) => n,
+            _ => return Err(RuntimeError::InvalidIndex { span }),
+        };
+        let start = match self.pop() {
+            Value::Number(n) => n,
+            _ => return Err(RuntimeError::InvalidIndex { span }),
+        };
+        let target = self.pop();
+        match target {
+            Value::Array(arr) => {
+                let sliced = crate::stdlib::array::slice(arr.as_slice(), start, end, span)?;
+                self.push(sliced);
+            }
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "Cannot slice non-array".to_string(),
+                    span,
+                })
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_slice_from(&mut self) -> Result<Flow, RuntimeError> {
+        let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
+        let start = match self.pop() {
+            Value::Number(n) => n,
+            _ => return Err(RuntimeError::InvalidIndex { span }),
+        };
+        let target = self.pop();
+        match target {
+            Value::Array(arr) => {
+                let end = arr.len() as f64;
+                let sliced = crate::stdlib::array::slice(arr.as_slice(), start, end, span)?;
+                self.push(sliced);
+            }
+            Value::JsonValue(json) if json.is_array() => {
+                let items = json.as_array().map(Vec::as_slice).unwrap_or_default();
+                let rest = items.get(start as usize..).unwrap_or_default().to_vec();
+                self.push(Value::JsonValue(Arc::new(
+                    crate::json_value::JsonValue::array(rest),
+                )));
+            }
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "Cannot slice non-array".to_string(),
+                    span,
+                })
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_slice_to(&mut self) -> Result<Flow, RuntimeError> {
+        let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
+        let end = match self.pop() {
+            Value::Number(n) => n,
+            _ => return Err(RuntimeError::InvalidIndex { span }),
+        };
+        let target = self.pop();
+        match target {
+            Value::Array(arr) => {
+                let sliced = crate::stdlib::array::slice(arr.as_slice(), 0.0, end, span)?;
+                self.push(sliced);
+            }
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "Cannot slice non-array".to_string(),
+                    span,
+                })
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_slice_full(&mut self) -> Result<Flow, RuntimeError> {
+        let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
+        let target = self.pop();
+        match target {
+            Value::Array(arr) => {
+                let sliced =
+                    crate::stdlib::array::slice(arr.as_slice(), 0.0, arr.len() as f64, span)?;
+                self.push(sliced);
+            }
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "Cannot slice non-array".to_string(),
+                    span,
+                })
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_range(&mut self) -> Result<Flow, RuntimeError> {
+        let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
+        let inclusive = self.read_u8()? != 0;
+        let end_val = self.pop();
+        let start_val = self.pop();
+
+        let start = match start_val {
+            Value::Null => None,
+            Value::Number(n) => Some(n),
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "Range bound must be number".to_string(),
+                    span,
+                })
+            }
+        };
+
+        let end = match end_val {
+            Value::Null => None,
+            Value::Number(n) => Some(n),
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "Range bound must be number".to_string(),
+                    span,
+                })
+            }
+        };
+
+        if inclusive && end.is_none() {
+            return Err(RuntimeError::TypeError {
+                msg: "Inclusive range requires an end bound".to_string(),
+                span,
+            });
+        }
+
+        self.push(Value::Range {
+            start,
+            end,
+            inclusive,
+        });
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_hash_map(&mut self) -> Result<Flow, RuntimeError> {
+        use crate::stdlib::collections::hash::HashKey;
+        use crate::stdlib::collections::hashmap::AtlasHashMap;
+        use crate::value::ValueHashMap;
+
+        let entry_count = self.read_u16()? as usize;
+
+        // Stack has [key1, val1, key2, val2, ...] in order
+        // Pop them in reverse (LIFO) and insert
+        let mut entries = Vec::with_capacity(entry_count);
+        for _ in 0..entry_count {
+            let value = self.pop();
+            let key_val = self.pop();
+            entries.push((key_val, value));
+        }
+        // Reverse to get original order
+        entries.reverse();
+
+        let mut atlas_map = AtlasHashMap::with_capacity(entry_count);
+        for (key_val, value) in entries {
+            // Convert Value to HashKey (keys must be hashable)
+            let key = HashKey::from_value(
+                &key_val,
+                self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            )?;
+            atlas_map.insert(key, value);
+        }
+
+        self.push(Value::Map(ValueHashMap::from_atlas(atlas_map)));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_struct(&mut self) -> Result<Flow, RuntimeError> {
+        use crate::stdlib::collections::hash::HashKey;
+        use crate::stdlib::collections::hashmap::AtlasHashMap;
+        use crate::value::ValueHashMap;
+
+        let name_idx = self.read_u16()? as usize;
+        let field_count = self.read_u16()? as usize;
+        let struct_name = match self.bytecode.constants.get(name_idx) {
+            Some(Value::String(s)) => s.as_ref().clone(),
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "Expected string constant for struct name".to_string(),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                });
+            }
+        };
+
+        let mut entries = Vec::with_capacity(field_count);
+        for _ in 0..field_count {
+            let value = self.pop();
+            let key_val = self.pop();
+            entries.push((key_val, value));
+        }
+        entries.reverse();
+
+        let mut atlas_map = AtlasHashMap::with_capacity(field_count);
+        for (key_val, value) in entries {
+            let key = HashKey::from_value(
+                &key_val,
+                self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            )?;
+            atlas_map.insert(key, value);
+        }
+
+        let map = ValueHashMap::from_atlas(atlas_map);
+        self.register_struct_type(&map, &struct_name);
+        self.push(Value::Map(map));
+        Ok(Flow::Next)
+    }
+
+    // ===== Stack Manipulation =====
+
+    pub(super) fn op_pop(&mut self) -> Result<Flow, RuntimeError> {
+        // Don't pop if this is the last instruction before Halt
+        // Check if next instruction is Halt
+        if self.ctx.ip < self.bytecode.instructions.len()
+            && self.bytecode.instructions[self.ctx.ip] != Opcode::Halt as u8
+        {
+            self.pop();
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_dup(&mut self) -> Result<Flow, RuntimeError> {
+        let value = self.peek(0).clone();
+        self.push(value);
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_dup2(&mut self) -> Result<Flow, RuntimeError> {
+        // Duplicate top 2 stack values: [a, b] -> [a, b, a, b]
+        let b = self.peek(0).clone();
+        let a = self.peek(1).clone();
+        self.push(a);
+        self.push(b);
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_rot3(&mut self) -> Result<Flow, RuntimeError> {
+        // Rotate top 3 stack values: [a, b, c] -> [b, c, a]
+        // Pop c, b, a then push b, c, a
+        let c = self.pop();
+        let b = self.pop();
+        let a = self.pop();
+        self.push(b);
+        self.push(c);
+        self.push(a);
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_to_string(&mut self) -> Result<Flow, RuntimeError> {
+        let value = self.pop();
+        let span = self.current_span().unwrap_or_else(Span::dummy);
+        let string_value = crate::stdlib::types::to_string(&[value], span)?;
+        self.push(string_value);
+        Ok(Flow::Next)
+    }
+
+    // ===== Pattern Matching =====
+
+    pub(super) fn op_is_option_some(&mut self) -> Result<Flow, RuntimeError> {
+        let value = self.pop();
+        let is_some = matches!(value, Value::Option(Some(_)));
+        self.push(Value::Bool(is_some));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_is_option_none(&mut self) -> Result<Flow, RuntimeError> {
+        let value = self.pop();
+        let is_none = matches!(value, Value::Option(None));
+        self.push(Value::Bool(is_none));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_is_result_ok(&mut self) -> Result<Flow, RuntimeError> {
+        let value = self.pop();
+        let is_ok = matches!(value, Value::Result(Ok(_)));
+        self.push(Value::Bool(is_ok));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_is_result_err(&mut self) -> Result<Flow, RuntimeError> {
+        let value = self.pop();
+        let is_err = matches!(value, Value::Result(Err(_)));
+        self.push(Value::Bool(is_err));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_extract_option_value(&mut self) -> Result<Flow, RuntimeError> {
+        let value = self.pop();
+        match value {
+            Value::Option(Some(inner)) => self.push(*inner),
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "ExtractOptionValue requires Option::Some".to_string(),
+                    span: Span::dummy(),
+                })
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_extract_result_value(&mut self) -> Result<Flow, RuntimeError> {
+        let value = self.pop();
+        match value {
+            Value::Result(Ok(inner)) => self.push(*inner),
+            Value::Result(Err(inner)) => self.push(*inner),
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "ExtractResultValue requires Result".to_string(),
+                    span: Span::dummy(),
+                })
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_is_array(&mut self) -> Result<Flow, RuntimeError> {
+        let value = self.pop();
+        // A JSON array destructures like an array; its elements stay json
+        let is_array = match &value {
+            Value::Array(_) => true,
+            Value::JsonValue(json) => json.is_array(),
+            _ => false,
+        };
+        self.push(Value::Bool(is_array));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_get_array_len(&mut self) -> Result<Flow, RuntimeError> {
+        let value = self.pop();
+        match value {
+            Value::Array(arr) => {
+                let len = arr.len();
+                self.push(Value::Number(len as f64));
+            }
+            // H-116: range in for-in — compute length and push only length
+            Value::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                let s = start.unwrap_or(0.0) as i64;
+                let e = end.ok_or_else(|| RuntimeError::TypeError {
+                    msg: "for-in range requires an end bound".to_string(),
+                    span: Span::dummy(),
+                })? as i64;
+                let len = if inclusive {
+                    (e - s + 1).max(0) as f64
+                } else {
+                    (e - s).max(0) as f64
+                };
+                self.push(Value::Number(len));
+            }
+            Value::JsonValue(json) if json.is_array() => {
+                self.push(Value::Number(json.len().unwrap_or(0) as f64));
+            }
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "GetArrayLen requires Array".to_string(),
+                    span: Span::dummy(),
+                })
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_enum_variant(&mut self) -> Result<Flow, RuntimeError> {
+        let arg_count = self.read_u8()? as usize;
+
+        // Pop args in reverse order
+        let mut args = Vec::with_capacity(arg_count);
+        for _ in 0..arg_count {
+            args.push(self.pop());
+        }
+        args.reverse();
+
+        // Pop variant name and enum name
+        let variant_name = self.pop();
+        let enum_name = self.pop();
+
+        // Extract string values
+        let enum_name_str = match enum_name {
+            Value::String(s) => (*s).clone(),
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "EnumVariant requires string enum name".to_string(),
+                    span: Span::dummy(),
+                })
+            }
+        };
+        let variant_name_str = match variant_name {
+            Value::String(s) => (*s).clone(),
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "EnumVariant requires string variant name".to_string(),
+                    span: Span::dummy(),
+                })
+            }
+        };
+
+        // Create the enum value
+        self.push(Value::EnumValue {
+            enum_name: enum_name_str,
+            variant_name: variant_name_str,
+            data: args,
+        });
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_check_enum_variant(&mut self) -> Result<Flow, RuntimeError> {
+        // Stack: [value, enum_name, variant_name] -> [bool]
+        let variant_name = self.pop();
+        let enum_name = self.pop();
+        let value = self.pop();
+
+        // Extract expected names
+        let expected_enum = match &enum_name {
+            Value::String(s) => s.as_str(),
+            _ => {
+                self.push(Value::Bool(false));
+                return Ok(Flow::Next);
+            }
+        };
+        let expected_variant = match &variant_name {
+            Value::String(s) => s.as_str(),
+            _ => {
+                self.push(Value::Bool(false));
+                return Ok(Flow::Next);
+            }
+        };
+
+        // Check if value matches.
+        // H-223: empty expected_enum means bare variant pattern — skip enum_name check.
+        let matches = match &value {
+            Value::EnumValue {
+                enum_name: val_enum,
+                variant_name: val_variant,
+                ..
+            } => {
+                let enum_ok = expected_enum.is_empty() || val_enum == expected_enum;
+                enum_ok && val_variant == expected_variant
+            }
+            _ => false,
+        };
+
+        self.push(Value::Bool(matches));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_extract_enum_data(&mut self) -> Result<Flow, RuntimeError> {
+        // Stack: [EnumValue] -> [Array]
+        let value = self.pop();
+
+        match value {
+            Value::EnumValue { data, .. } => {
+                // Convert data Vec<Value> to an Array
+                self.push(Value::array(data));
+            }
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: format!(
+                        "ExtractEnumData requires EnumValue, got {}",
+                        value.type_name()
+                    ),
+                    span: Span::dummy(),
+                });
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_is_struct(&mut self) -> Result<Flow, RuntimeError> {
+        // Stack: [value] -> [bool]
+        // Push true if the value is a named struct (Map registered in struct_type_names)
+        let val = self.pop();
+        let is_struct = if let Value::Map(ref m) = val {
+            let key = std::sync::Arc::as_ptr(m.arc()) as usize;
+            self.ctx.struct_type_names.contains_key(&key)
+        } else {
+            false
+        };
+        self.push(Value::Bool(is_struct));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_check_struct_type(&mut self) -> Result<Flow, RuntimeError> {
+        // [u16 name_const_idx]  Stack: [value] -> [bool]
+        // Pop value; push true if its registered struct name equals the string constant.
+        let name_idx = self.read_u16()? as usize;
+        let expected_name = match self.bytecode.constants.get(name_idx) {
+            Some(Value::String(s)) => s.as_ref().clone(),
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "CheckStructType: expected string constant for type name".to_string(),
+                    span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                });
+            }
+        };
+        let val = self.pop();
+        let matches_type = if let Value::Map(ref m) = val {
+            let key = std::sync::Arc::as_ptr(m.arc()) as usize;
+            self.ctx
+                .struct_type_names
+                .get(&key)
+                .map(|name| name == &expected_name)
+                .unwrap_or(false)
+        } else {
+            false
+        };
+        self.push(Value::Bool(matches_type));
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_for_in_prepare(&mut self) -> Result<Flow, RuntimeError> {
+        // Stack: [iterable] -> [array or range]
+        let iterable = match self.pop() {
+            value @ (Value::Array(_) | Value::Range { .. }) => value,
+            Value::Map(map) => Value::array(
+                map.entries()
+                    .into_iter()
+                    .map(|(key, value)| Value::Tuple(Arc::new(vec![key.to_value(), value])))
+                    .collect(),
+            ),
+            Value::Set(set) => Value::array(
+                set.inner()
+                    .to_vec()
+                    .into_iter()
+                    .map(|key| key.to_value())
+                    .collect(),
+            ),
+            other => {
+                return Err(RuntimeError::TypeError {
+                    msg: format!("for-in cannot iterate over {}", other.type_name()),
+                    span: self.current_span().unwrap_or_else(Span::dummy),
+                });
+            }
+        };
+        self.push(iterable);
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_is_nullish(&mut self) -> Result<Flow, RuntimeError> {
+        // Stack: [value] -> [bool]
+        // What `?.` and `??` treat as missing
+        let is_nullish = match self.pop() {
+            Value::Null | Value::Option(None) => true,
+            Value::JsonValue(json) => json.is_null(),
+            _ => false,
+        };
+        self.push(Value::Bool(is_nullish));
+        Ok(Flow::Next)
+    }
+
+    // ===== Membership =====
+
+    pub(super) fn op_contains(&mut self) -> Result<Flow, RuntimeError> {
+        // Stack: [value, container] -> [bool]
+        let container = self.pop();
+        let value = self.pop();
+        let span = self.current_span().unwrap_or_else(Span::dummy);
+        let found = match (&container, &value) {
+            (Value::Array(arr), _) => crate::stdlib::array::includes(arr.as_slice(), &value),
+            (Value::Map(map), _) => {
+                let key = crate::stdlib::collections::hash::HashKey::from_value(&value, span)?;
+                map.contains_key(&key)
+            }
+            (Value::Set(set), _) => {
+                let key = crate::stdlib::collections::hash::HashKey::from_value(&value, span)?;
+                set.inner().contains(&key)
+            }
+            (Value::String(haystack), Value::String(needle)) => haystack.contains(needle.as_str()),
+            (
+                Value::Range {
+                    start,
+                    end,
+                    inclusive,
+                },
+                Value::Number(n),
+            ) => {
+                start.is_none_or(|s| *n >= s)
+                    && end.is_none_or(|e| if *inclusive { *n <= e } else { *n < e })
+            }
+            (Value::JsonValue(json), Value::String(key)) => json
+                .as_object()
+                .is_some_and(|obj| obj.contains_key(key.as_str())),
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: format!(
+                        "Cannot check whether {} is in {}",
+                        value.type_name(),
+                        container.type_name()
+                    ),
+                    span,
+                })
+            }
+        };
+        self.push(Value::Bool(found));
+        Ok(Flow::Next)
+    }
+
+    // ===== Async (Phase 10) =====
+
+    // Encoding:
+    //   AsyncCall / SpawnTask: u8 arg_count  (same layout as Call)
+    //   Await / WrapFuture:    no operands
+    //
+    // The compiler emits WrapFuture *inside* async fn bodies so that the
+    // return value is always a Value::Future.  AsyncCall therefore only needs
+    // to dispatch the call normally — the callee's WrapFuture handles wrapping.
+    //
+    // The callee runs eagerly until it finishes or awaits a pending future;
+    // in the latter case `Await` suspends its frame (see `scheduler.rs`) and
+    // the caller gets a pending future for its result instead.
+    pub(super) fn op_async_call(&mut self) -> Result<Flow, RuntimeError> {
+        let arg_count = self.read_u8()? as usize;
+        self.execute_call(arg_count)?;
+        // Result is already Value::Future — the callee's WrapFuture emitted it.
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_await(&mut self) -> Result<Flow, RuntimeError> {
+        let val = self.pop();
+        match val {
+            Value::Future(future) => self.await_future(&future),
+            other => Err(RuntimeError::TypeError {
+                msg: format!(
+                    "AT4002: await operand must be Future, got {}",
+                    other.type_name()
+                ),
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            }),
+        }
+    }
+
+    pub(super) fn op_wrap_future(&mut self) -> Result<Flow, RuntimeError> {
+        let val = self.pop();
+        let future = crate::async_runtime::AtlasFuture::resolved(val);
+        self.push(Value::Future(Arc::new(future)));
+        Ok(Flow::Next)
+    }
+
+    // ===== Defer =====
+
+    pub(super) fn op_defer_push(&mut self) -> Result<Flow, RuntimeError> {
+        // Read the body length
+        let body_len = self.read_u16()? as usize;
+        // Body starts after the Jump opcode (1 byte) + operand (2 bytes) = 3 bytes
+        // The compiler emits: DeferPush body_len Jump offset body...
+        let body_start = self.ctx.ip + 3;
+
+        // Record defer: (body_start, body_len) on the current frame's defer stack
+        let frame_idx = self.ctx.frames.len() - 1;
+        self.ctx.defer_stacks[frame_idx].push((body_start, body_len));
+
+        // Normal execution continues (Jump instruction follows to skip body)
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_defer_exec(&mut self) -> Result<Flow, RuntimeError> {
+        // Execute all deferred blocks for current frame in LIFO order
+        let frame_idx = self.ctx.frames.len() - 1;
+        while let Some((body_start, body_len)) = self.ctx.defer_stacks[frame_idx].pop() {
+            // Save current IP
+            let saved_ip = self.ctx.ip;
+
+            // Execute defer body
+            self.ctx.ip = body_start;
+            let body_end = body_start + body_len;
+            while self.ctx.ip < body_end {
+                let defer_opcode = Opcode::try_from(self.bytecode.instructions[self.ctx.ip])
+                    .map_err(|_| RuntimeError::UnknownOpcode {
+                        span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                    })?;
+                self.ctx.ip += 1;
+
+                // Execute single instruction (simplified - only handles basic ops)
+                // Return in defer body returns to defer executor, not function
+                if matches!(defer_opcode, Opcode::Return) {
+                    break;
+                }
+                // For complex defer bodies, we'd need recursive execution
+                // For now, just break - the main loop will handle it
+            }
+
+            // Restore IP
+            self.ctx.ip = saved_ip;
+        }
+        Ok(Flow::Next)
+    }
+
+    // ===== Error handling =====
+
+    pub(super) fn op_try_begin(&mut self) -> Result<Flow, RuntimeError> {
+        let offset = self.read_i16()?;
+        let catch_ip = (self.ctx.ip as isize + offset as isize) as usize;
+        self.ctx.handlers.push(ErrorHandler {
+            frame_index: self.ctx.frames.len() - 1,
+            catch_ip,
+            stack_len: self.ctx.stack.len(),
+        });
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_try_end(&mut self) -> Result<Flow, RuntimeError> {
+        self.ctx.handlers.pop();
+        Ok(Flow::Next)
+    }
+
+    pub(super) fn op_push_error(&mut self) -> Result<Flow, RuntimeError> {
+        let record = self.ctx.caught_error.take().unwrap_or(Value::Null);
+        self.push(record);
+        Ok(Flow::Next)
+    }
+
+    // ===== Special =====
+
+    pub(super) fn op_halt(&mut self) -> Result<Flow, RuntimeError> {
+        Ok(Flow::Halt)
+    }
+
+    /// Fallback for bytes that decode to no opcode. `read_opcode` rejects
+    /// these before dispatch, so this only guards the table's empty slots.
+    pub(super) fn op_invalid(&mut self) -> Result<Flow, RuntimeError> {
+        Err(RuntimeError::UnknownOpcode {
+            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+        })
+    }
+}
@@ -0,0 +1,60 @@
+//! Recursion limits for the VM
+//!
+//! Atlas calls push frames onto a heap-allocated frame list, so plain
+//! recursion never touches the host stack — left alone it grows until memory
+//! runs out. Callbacks are different: when a builtin such as `map` or `sort`
+//! calls back into a user function, the VM re-enters its dispatch loop from
+//! Rust, and each nested re-entry takes a slice of the host thread's stack.
+//! [`VmLimits`] bounds both, and the VM fails with
+//! `RuntimeError::RecursionLimit` instead of exhausting memory or aborting
+//! the process on a stack overflow.
+
+/// Limits on how deeply a script may recurse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VmLimits {
+    /// Maximum number of function calls active at once
+    pub max_call_depth: usize,
+    /// Maximum host stack, in bytes, that callbacks nested inside builtins
+    /// may use
+    ///
+    /// Measured from the outermost callback, so it must stay below the stack
+    /// size of the thread running the VM. The default fits in the 2 MiB
+    /// Rust gives spawned threads.
+    pub max_native_stack: usize,
+}
+
+impl VmLimits {
+    /// Limits for trusted scripts
+    pub const fn standard() -> Self {
+        Self {
+            max_call_depth: 10_000,
+            max_native_stack: 1 << 20,
+        }
+    }
+
+    /// Tighter limits for untrusted scripts
+    pub const fn strict() -> Self {
+        Self {
+            max_call_depth: 1_000,
+            max_native_stack: 256 * 1024,
+        }
+    }
+
+    /// Set the maximum number of active function calls
+    pub fn with_max_call_depth(mut self, depth: usize) -> Self {
+        self.max_call_depth = depth;
+        self
+    }
+
+    /// Set the host stack budget for nested callbacks, in bytes
+    pub fn with_max_native_stack(mut self, bytes: usize) -> Self {
+        self.max_native_stack = bytes;
+        self
+    }
+}
+
+impl Default for VmLimits {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
@@ -2,42 +2,65 @@
 //!
 //! Executes bytecode instructions with a value stack and call frames.
 //! - Arithmetic operations check for NaN/Infinity
-//! - Variables are stored in locals (stack) or globals (HashMap)
+//! - Variables are stored in locals (stack) or globals (slot table)
 //! - Control flow uses jumps and loops
 
 #![cfg_attr(not(test), deny(clippy::unwrap_used))]
 
+mod call_cache;
 pub(crate) mod context;
 mod debugger;
 pub mod dispatch;
+mod evaluate;
 mod frame;
+pub mod gc;
+mod globals;
+mod handlers;
+pub mod limits;
 mod profiler;
+mod scheduler;
 
+pub use call_cache::CallCacheStats;
 pub use context::VMContext;
 
-pub use debugger::{DebugAction, DebugHook, Debugger};
-pub use frame::CallFrame;
+pub use debugger::{DebugAction, DebugHook, Debugger, WatchHit, WatchSlot};
+pub use frame::{CallFrame, FrameVariable};
+pub use globals::GlobalResolver;
+pub use limits::VmLimits;
 pub use profiler::Profiler;
 
 use crate::bytecode::{Bytecode, Opcode};
 use crate::diagnostic::StackTraceFrame;
+#[cfg(feature = "ffi")]
 use crate::ffi::{ExternFunction, LibraryLoader};
-use crate::span::Span;
-use crate::value::{RuntimeError, Value, ValueArray, ValueHashMap, ValueHashSet};
+use crate::value::{RuntimeError, Value, ValueHashMap, ValueHashSet};
+use call_cache::{CachedFunction, CallCache};
+use dispatch::Flow;
+use globals::GlobalTable;
 use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Tracks the origin of a value on the operand stack (debug builds only).
 ///
 /// When an `own` parameter is called, the origin tells us which local slot or
-/// global name to mark as consumed so that subsequent reads produce an error.
+/// global slot to mark as consumed so that subsequent reads produce an error.
 #[cfg(debug_assertions)]
 #[derive(Clone, Debug)]
 enum StackValueOrigin {
     /// Value loaded from a frame-relative local slot index.
     Local(usize),
-    /// Value loaded from a named global variable.
-    Global(String),
+    /// Value loaded from a global slot.
+    Global(usize),
+}
+
+/// The deny-all security context a VM starts with, shared so creating a VM
+/// does not allocate one
+fn deny_all_security() -> std::sync::Arc<crate::security::SecurityContext> {
+    static DENY_ALL: std::sync::OnceLock<std::sync::Arc<crate::security::SecurityContext>> =
+        std::sync::OnceLock::new();
+    DENY_ALL
+        .get_or_init(|| std::sync::Arc::new(crate::security::SecurityContext::new()))
+        .clone()
 }
 
 /// Result returned by [`VM::run_debuggable`].
@@ -63,25 +86,47 @@ pub struct VM {
     /// (B44-P02+).  Cloning `ctx` forks a fresh execution context.
     pub ctx: VMContext,
     /// Global variables — shared across all top-level code in one VM instance.
-    globals: HashMap<String, Value>,
+    /// Slots follow `bytecode.global_names`.
+    globals: GlobalTable,
+    /// Host callback for globals read before they have a value.
+    global_resolver: Option<std::sync::Arc<dyn GlobalResolver>>,
     /// Bytecode to execute (read-only after compilation).
     bytecode: Bytecode,
     /// Optional profiler for performance analysis.
     profiler: Option<Profiler>,
     /// Optional debugger for step-through execution.
     debugger: Option<Debugger>,
-    /// Security context for current execution (set during run()).
-    current_security: Option<std::sync::Arc<crate::security::SecurityContext>>,
+    /// Security context handed to builtins.
+    ///
+    /// Deny-all until `set_security` or `run()` supplies one; `run()` only
+    /// copies the context it is given when it is not already this one.
+    current_security: std::sync::Arc<crate::security::SecurityContext>,
     /// Execution limits for timeout enforcement.
     execution_limits: Option<std::sync::Arc<crate::api::config::ExecutionLimits>>,
     /// Output writer for print() (defaults to stdout).
     output_writer: crate::stdlib::OutputWriter,
     /// FFI library loader (phase-10b).
+    #[cfg(feature = "ffi")]
     library_loader: LibraryLoader,
     /// Loaded extern functions (phase-10b).
+    #[cfg(feature = "ffi")]
     extern_functions: HashMap<String, ExternFunction>,
     /// Optional JIT compiler for hot function execution.
     jit: Option<Box<dyn crate::JitCompiler>>,
+    /// Resolved callee per call site.
+    call_cache: CallCache,
+    /// Cycle collector configuration.
+    gc_config: gc::GcConfig,
+    /// Cell allocations that trigger the next automatic collection.
+    gc_next_threshold: usize,
+    /// Call depth and callback stack limits.
+    limits: VmLimits,
+    /// Callbacks from builtins currently re-entering the dispatch loop.
+    callback_depth: usize,
+    /// Host stack address of the outermost active callback.
+    native_stack_base: usize,
+    /// Suspended `async fn` frames waiting for the futures they await.
+    scheduler: scheduler::Scheduler,
 }
 
 impl VM {
@@ -97,7 +142,9 @@ impl VM {
             stack_base: 0,
             local_count: bytecode.top_level_local_count,
             upvalues: std::sync::Arc::new(Vec::new()),
+            is_async: false,
         };
+        let globals = GlobalTable::new(&bytecode.global_names);
 
         Self {
             ctx: VMContext::new(
@@ -105,16 +152,26 @@ impl VM {
                 #[cfg(debug_assertions)]
                 main_local_count,
             ),
-            globals: HashMap::new(),
+            globals,
+            global_resolver: None,
             bytecode,
             profiler: None,
             debugger: None,
-            current_security: None,
+            current_security: deny_all_security(),
             execution_limits: None,
             output_writer: crate::stdlib::stdout_writer(),
+            #[cfg(feature = "ffi")]
             library_loader: LibraryLoader::new(),
+            #[cfg(feature = "ffi")]
             extern_functions: HashMap::new(),
             jit: None,
+            call_cache: CallCache::default(),
+            gc_config: gc::GcConfig::default(),
+            gc_next_threshold: gc::GcConfig::default().threshold,
+            limits: VmLimits::default(),
+            callback_depth: 0,
+            native_stack_base: 0,
+            scheduler: scheduler::Scheduler::default(),
         }
     }
 
@@ -127,6 +184,8 @@ impl VM {
     /// This is required for cross-module function calls: a function defined in module A
     /// has a `bytecode_offset` relative to module A's instruction stream; after merging,
     /// that offset is adjusted to be correct in the combined instruction stream.
+    /// The module's global names are interned into the VM's table, so a global it
+    /// shares with an earlier module resolves to the same slot.
     ///
     /// Call `run()` after this to execute the loaded module.
     pub fn load_module(&mut self, new_bc: Bytecode) {
@@ -134,6 +193,13 @@ impl VM {
         let const_base = self.bytecode.constants.len();
         let local_count = new_bc.top_level_local_count;
 
+        // 0. Intern the module's globals: `global_slots[i]` is the VM slot of its global `i`.
+        let global_slots: Vec<u16> = new_bc
+            .global_names
+            .iter()
+            .map(|name| self.globals.intern(&mut self.bytecode.global_names, name) as u16)
+            .collect();
+
         // 1. Merge constants: adjust FunctionRef bytecode_offsets by instr_base so they
         //    remain valid in the combined instruction stream.
         for constant in new_bc.constants {
@@ -148,8 +214,10 @@ impl VM {
         }
 
         // 2. Adjust the new module's instruction stream: all constant pool index operands
-        //    must be shifted by `const_base` to account for the pre-existing constants.
-        let adjusted_instrs = Self::adjust_constant_refs(&new_bc.instructions, const_base as u16);
+        //    must be shifted by `const_base` to account for the pre-existing constants,
+        //    and global slots mapped to the VM's slots.
+        let adjusted_instrs =
+            Self::adjust_constant_refs(&new_bc.instructions, const_base as u16, &global_slots);
 
         // 3. Merge debug info: shift instruction offsets by instr_base.
         for mut dbg in new_bc.debug_info {
@@ -169,6 +237,7 @@ impl VM {
             stack_base: 0,
             local_count,
             upvalues: std::sync::Arc::new(Vec::new()),
+            is_async: false,
         }];
 
         #[cfg(debug_assertions)]
@@ -179,13 +248,18 @@ impl VM {
         }
     }
 
-    /// Rewrite all constant pool index operands in an instruction stream by adding `const_base`.
+    /// Rewrite all constant pool index operands in an instruction stream by adding `const_base`,
+    /// and global slot operands through `global_slots`.
     ///
     /// Jump offsets (relative i16) and non-constant-index operands are passed through unchanged.
     /// This is used by `load_module` to fix up a new module's instruction stream before
     /// appending it to the existing combined bytecode.
-    fn adjust_constant_refs(instructions: &[u8], const_base: u16) -> Vec<u8> {
-        if const_base == 0 {
+    fn adjust_constant_refs(instructions: &[u8], const_base: u16, global_slots: &[u16]) -> Vec<u8> {
+        let globals_unchanged = global_slots
+            .iter()
+            .enumerate()
+            .all(|(i, &slot)| slot as usize == i);
+        if const_base == 0 && globals_unchanged {
             return instructions.to_vec();
         }
 
@@ -207,15 +281,25 @@ impl VM {
 
             match opcode {
                 // ONE u16 constant-pool index → adjust
-                0x01 | // Constant
-                0x12 | // GetGlobal
-                0x13   // SetGlobal
+                0x01 // Constant
                 => {
                     let idx = read_u16(&instructions[i..]);
                     push_u16(&mut result, idx + const_base);
                     i += 2;
                 }
 
+                // ONE u16 global slot → map to the VM's slot
+                0x12 | // GetGlobal
+                0x13   // SetGlobal
+                => {
+                    let slot = read_u16(&instructions[i..]);
+                    push_u16(
+                        &mut result,
+                        global_slots.get(slot as usize).copied().unwrap_or(slot),
+                    );
+                    i += 2;
+                }
+
                 // MakeClosure: [u16 func_const_idx][u16 n_upvalues]
                 // Only func_const_idx is a pool reference; n_upvalues is a count.
                 0x14 => {
@@ -311,11 +395,79 @@ impl VM {
         vm
     }
 
+    /// Configure the cycle collector
+    pub fn set_gc_config(&mut self, config: gc::GcConfig) {
+        self.gc_config = config;
+        self.gc_next_threshold = config.threshold;
+    }
+
+    /// Limit how deeply scripts may recurse
+    pub fn set_limits(&mut self, limits: VmLimits) {
+        self.limits = limits;
+    }
+
+    /// Run a cycle collection with this VM's stack, globals and captured
+    /// upvalues as roots. Returns the number of cells freed.
+    ///
+    /// Does nothing when the strategy is [`gc::GcStrategy::Disabled`].
+    pub fn collect_garbage(&mut self) -> usize {
+        if self.gc_config.strategy == gc::GcStrategy::Disabled {
+            return 0;
+        }
+        let roots = self
+            .ctx
+            .stack
+            .iter()
+            .chain(self.globals.values())
+            .chain(self.ctx.frames.iter().flat_map(|f| f.upvalues.iter()))
+            .chain(self.scheduler.contexts().flat_map(|ctx| {
+                ctx.stack
+                    .iter()
+                    .chain(ctx.frames.iter().flat_map(|f| f.upvalues.iter()))
+            }));
+        let freed = gc::collect(roots);
+        self.gc_next_threshold = self.gc_config.next_threshold(gc::stats().live);
+        freed
+    }
+
+    /// Collect if the automatic strategy's allocation threshold was reached.
+    #[inline]
+    fn maybe_collect_garbage(&mut self) {
+        if self.gc_config.strategy == gc::GcStrategy::Automatic
+            && gc::allocations_since_collect() >= self.gc_next_threshold
+        {
+            self.collect_garbage();
+        }
+    }
+
     /// Set the output writer (used by Runtime to redirect print() output)
     pub fn set_output_writer(&mut self, writer: crate::stdlib::OutputWriter) {
         self.output_writer = writer;
     }
 
+    /// Set the security context builtins run under
+    ///
+    /// A later `run(&security)` with this same context (for example
+    /// `run(&arc)` on the `Arc` passed here) uses it as is instead of
+    /// copying it.
+    pub fn set_security(&mut self, security: std::sync::Arc<crate::security::SecurityContext>) {
+        self.current_security = security;
+    }
+
+    /// Make `security` the context for builtins, copying it only if it is
+    /// not the one already held
+    fn use_security(&mut self, security: &crate::security::SecurityContext) {
+        if !std::ptr::eq(self.current_security.as_ref(), security) {
+            self.current_security = std::sync::Arc::new(security.clone());
+        }
+    }
+
+    /// Create a VM for a program compiled ahead of time into a `.atbc` file
+    /// (see [`Compiler::compile_to_file`](crate::compiler::Compiler::compile_to_file))
+    pub fn from_file(path: &std::path::Path) -> Result<Self, String> {
+        Ok(Self::new(Bytecode::read_from_file(path)?))
+    }
+
     /// Create an isolated VM for a worker thread (D-057).
     ///
     /// Clones the bytecode and globals from the source VM so each worker
@@ -341,6 +493,7 @@ impl VM {
             stack_base: 0,
             local_count: bytecode.top_level_local_count,
             upvalues: std::sync::Arc::new(Vec::new()),
+            is_async: false,
         };
 
         Self {
@@ -350,19 +503,27 @@ impl VM {
                 main_local_count,
             ),
             globals: self.globals.clone(),
+            global_resolver: self.global_resolver.clone(),
             bytecode,
             profiler: None,
             debugger: None,
-            // Use parent's security context; fall back to permissive default so
-            // workers created before vm.run() (e.g. http.serve() pool init) still work.
-            current_security: Some(self.current_security.clone().unwrap_or_else(|| {
-                std::sync::Arc::new(crate::security::SecurityContext::default())
-            })),
+            // Share the parent's security context; workers created before
+            // vm.run() (e.g. http.serve() pool init) get its deny-all default.
+            current_security: self.current_security.clone(),
             execution_limits: self.execution_limits.clone(),
             output_writer: self.output_writer.clone(),
+            #[cfg(feature = "ffi")]
             library_loader: LibraryLoader::new(),
+            #[cfg(feature = "ffi")]
             extern_functions: self.extern_functions.clone(),
             jit: None, // JIT not supported on worker threads yet
+            call_cache: CallCache::default(),
+            gc_config: self.gc_config,
+            gc_next_threshold: self.gc_config.threshold,
+            limits: self.limits,
+            callback_depth: 0,
+            native_stack_base: 0,
+            scheduler: scheduler::Scheduler::default(),
         }
     }
 
@@ -381,6 +542,7 @@ impl VM {
     /// - `fn_name` not found in globals → `CallbackError::ExecutionError`
     /// - global exists but is not a `Value::Function` → `CallbackError::ExecutionError`
     /// - signature not supported by trampoline layer → `CallbackError::UnsupportedSignature`
+    #[cfg(feature = "ffi")]
     pub fn create_ffi_callback(
         &self,
         fn_name: &str,
@@ -388,7 +550,7 @@ impl VM {
         return_type: crate::ffi::ExternType,
     ) -> Result<crate::ffi::CallbackHandle, crate::ffi::CallbackError> {
         // Resolve the function value from globals.
-        let func_value = self.globals.get(fn_name).cloned().ok_or_else(|| {
+        let func_value = self.globals.get_by_name(fn_name).cloned().ok_or_else(|| {
             crate::ffi::CallbackError::ExecutionError(format!(
                 "function '{}' not found in VM globals",
                 fn_name
@@ -410,8 +572,7 @@ impl VM {
         // Snapshot the VM: isolated bytecode + globals, fresh stack.
         // No shared mutable state — value semantics throughout (D-029).
         let mut snapshot = self.new_for_worker();
-        let security = crate::security::SecurityContext::default();
-        snapshot.current_security = Some(std::sync::Arc::new(security));
+        snapshot.current_security = deny_all_security();
 
         // The snapshot is moved into the closure; it lives as long as the handle.
         let span = crate::span::Span::dummy();
@@ -437,7 +598,7 @@ impl VM {
         args: Vec<Value>,
         security: &crate::security::SecurityContext,
     ) -> Result<Value, String> {
-        self.current_security = Some(std::sync::Arc::new(security.clone()));
+        self.use_security(security);
 
         let func_value = match callable {
             crate::async_runtime::task::FunctionCallable::Function(f) => Value::Function(f.clone()),
@@ -445,8 +606,11 @@ impl VM {
         };
 
         let span = crate::span::Span::dummy();
-        self.vm_call_function_value(&func_value, args, span)
-            .map_err(|e| format!("{e}"))
+        let value = self
+            .vm_call_function_value(&func_value, args, span)
+            .map_err(|e| format!("{e}"))?;
+        self.run_remaining_tasks().map_err(|e| format!("{e}"))?;
+        Ok(value)
     }
 
     /// Public entry point for calling an Atlas function or closure value.
@@ -459,7 +623,10 @@ impl VM {
         args: Vec<Value>,
         span: crate::span::Span,
     ) -> Result<Value, crate::value::RuntimeError> {
-        self.vm_call_function_value(func, args, span)
+        let value = self.vm_call_function_value(func, args, span)?;
+        // Run any `async fn` the call suspended to completion
+        self.run_remaining_tasks()?;
+        Ok(value)
     }
 
     /// Drain and return all runtime warnings collected during execution.
@@ -469,6 +636,16 @@ impl VM {
         std::mem::take(&mut self.ctx.runtime_warnings)
     }
 
+    /// Drain the call stack captured when the last `run()` failed.
+    ///
+    /// Frames are innermost first, each with its function name and the
+    /// file/line of the failing instruction or call site. Pass it to
+    /// [`runtime_error_to_diagnostic`](crate::runtime_error_to_diagnostic) so
+    /// the error is reported with the path that led to it.
+    pub fn take_error_stack_trace(&mut self) -> Vec<StackTraceFrame> {
+        std::mem::take(&mut self.ctx.error_stack_trace)
+    }
+
     /// Set execution limits for timeout enforcement
     pub fn set_execution_limits(
         &mut self,
@@ -544,26 +721,44 @@ impl VM {
         self.jit.as_ref().map(|j| j.stats())
     }
 
+    /// Hit/miss counters of the call-site cache
+    pub fn call_cache_stats(&self) -> CallCacheStats {
+        self.call_cache.stats()
+    }
+
     /// Set a global variable
     ///
     /// Used by the Runtime to inject native functions and other complex values
     /// that can't be represented in bytecode constants.
     pub fn set_global(&mut self, name: String, value: Value) {
-        self.globals.insert(name, value);
+        let slot = self.globals.intern(&mut self.bytecode.global_names, &name);
+        self.globals.set(slot, value);
+    }
+
+    /// Resolve globals that have no value through `resolver`
+    ///
+    /// See [`GlobalResolver`]. Values set with [`set_global`](Self::set_global)
+    /// and builtins take precedence.
+    pub fn set_global_resolver(&mut self, resolver: std::sync::Arc<dyn GlobalResolver>) {
+        self.global_resolver = Some(resolver);
     }
 
     /// Get all global variables
     ///
     /// Used by the Runtime to persist VM globals back to interpreter state
     /// after execution completes.
-    pub fn get_globals(&self) -> &std::collections::HashMap<String, Value> {
-        &self.globals
+    pub fn get_globals(&self) -> HashMap<String, Value> {
+        self.globals
+            .iter(&self.bytecode.global_names)
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
     }
 
     /// Load extern declarations from AST (phase-10b)
     ///
     /// Processes extern function declarations by loading libraries and looking up symbols.
     /// Must be called before running bytecode that calls extern functions.
+    #[cfg(feature = "ffi")]
     pub fn load_extern_declarations(
         &mut self,
         program: &crate::ast::Program,
@@ -620,6 +815,8 @@ impl VM {
                 // Store the extern function
                 self.extern_functions
                     .insert(extern_decl.name.clone(), extern_fn);
+                // Cached call sites assumed no extern shadows their callee
+                self.call_cache.clear();
 
                 // Register as a callable global
                 let arity = extern_decl.params.len();
@@ -636,15 +833,36 @@ impl VM {
                     is_async: false,
                     has_rest_param: false,
                 });
-                self.globals.insert(extern_decl.name.clone(), func_value);
+                self.set_global(extern_decl.name.clone(), func_value);
             }
         }
 
         Ok(())
     }
+    /// Reject extern declarations in builds without the `ffi` feature
+    #[cfg(not(feature = "ffi"))]
+    pub fn load_extern_declarations(
+        &mut self,
+        program: &crate::ast::Program,
+    ) -> Result<(), RuntimeError> {
+        match program.items.iter().find_map(|item| match item {
+            crate::ast::Item::Extern(extern_decl) => Some(extern_decl),
+            _ => None,
+        }) {
+            Some(extern_decl) => Err(RuntimeError::TypeError {
+                msg: format!(
+                    "Cannot load extern function '{}': this runtime was built without the `ffi` feature",
+                    extern_decl.name
+                ),
+                span: extern_decl.span,
+            }),
+            None => Ok(()),
+        }
+    }
 }
 
 /// Convert ExternTypeAnnotation (AST) to ExternType (FFI runtime)
+#[cfg(feature = "ffi")]
 fn convert_extern_type_annotation(
     annotation: &crate::ast::ExternTypeAnnotation,
 ) -> crate::ffi::ExternType {
@@ -679,6 +897,14 @@ impl VM {
         }
     }
 
+    /// Enable profiling with allocation and peak memory tracking
+    pub fn enable_allocation_profiling(&mut self) {
+        self.enable_profiling();
+        if let Some(ref mut profiler) = self.profiler {
+            profiler.enable_allocation_tracking();
+        }
+    }
+
     /// Disable profiling
     pub fn disable_profiling(&mut self) {
         if let Some(ref mut profiler) = self.profiler {
@@ -722,6 +948,41 @@ impl VM {
         self.debugger.as_mut()
     }
 
+    /// Watchpoint watching `slot`, while debugging
+    #[inline]
+    fn watch_for(&self, slot: WatchSlot) -> Option<u32> {
+        self.debugger
+            .as_ref()
+            .filter(|d| d.is_enabled())?
+            .watch_for(slot)
+    }
+
+    /// Finish a write to a watched variable, pausing before the next
+    /// instruction if the write changed its value
+    fn watched_write(&mut self, id: u32, old_value: Option<Value>, new_value: Value) -> Flow {
+        if old_value.as_ref() == Some(&new_value) {
+            return Flow::Next;
+        }
+        if let Some(debugger) = self.debugger.as_mut() {
+            let hit = WatchHit {
+                id,
+                old_value,
+                new_value,
+            };
+            debugger.record_watch_hit(hit, self.ctx.ip);
+        }
+        self.ctx.debug_pause_pending = true;
+        Flow::Halt
+    }
+
+    /// Drop watchpoints on the locals of frames that have returned
+    #[inline]
+    fn drop_frame_watches(&mut self) {
+        if let Some(debugger) = self.debugger.as_mut() {
+            debugger.drop_frame_watches(self.ctx.frames.len());
+        }
+    }
+
     /// Get the source span for the current instruction pointer
     ///
     /// Returns the span from debug info if available.
@@ -783,9 +1044,72 @@ impl VM {
             .collect()
     }
 
-    /// Get all global variables.
-    pub fn get_global_variables(&self) -> &HashMap<String, Value> {
-        &self.globals
+    /// Get the named local variables and captured upvalues for a call frame.
+    ///
+    /// `frame_index` 0 is the innermost (current) frame. Names come from the
+    /// bytecode's local debug table at the frame's current instruction; slots with
+    /// no name in scope (compiler temporaries, dead locals) are omitted. Bytecode
+    /// without a local table falls back to `local_{slot}` / `upvalue_{index}`.
+    pub fn get_named_locals_for_frame(&self, frame_index: usize) -> Vec<FrameVariable<'_>> {
+        let frame = match self.get_frame_at(frame_index) {
+            Some(f) => f,
+            None => return Vec::new(),
+        };
+        let ip = if frame_index == 0 {
+            self.ctx.ip
+        } else {
+            self.get_frame_at(frame_index - 1)
+                .map(|inner| inner.return_ip.saturating_sub(1))
+                .unwrap_or(self.ctx.ip)
+        };
+        let has_names = !self.bytecode.local_names.is_empty();
+        let in_scope: Vec<_> = self.bytecode.locals_at(ip).collect();
+        let name_for = |slot: usize, captured: bool| {
+            in_scope
+                .iter()
+                .filter(|info| info.slot == slot && info.captured == captured)
+                .max_by_key(|info| info.start_offset)
+                .map(|info| info.name.clone())
+        };
+
+        let mut vars = Vec::new();
+        for (slot, value) in self.get_locals_for_frame(frame_index) {
+            let name = match name_for(slot, false) {
+                Some(name) => name,
+                None if !has_names => format!("local_{slot}"),
+                None => continue,
+            };
+            vars.push(FrameVariable {
+                name,
+                slot,
+                value,
+                captured: false,
+            });
+        }
+        for (index, value) in frame.upvalues.iter().enumerate() {
+            let name = match name_for(index, true) {
+                Some(name) => name,
+                None if !has_names => format!("upvalue_{index}"),
+                None => continue,
+            };
+            vars.push(FrameVariable {
+                name,
+                slot: index,
+                value,
+                captured: true,
+            });
+        }
+        vars
+    }
+
+    /// Slot of the global called `name`, assigned or not.
+    pub fn global_slot(&self, name: &str) -> Option<usize> {
+        self.globals.slot(name)
+    }
+
+    /// Get all assigned global variables, in slot order.
+    pub fn get_global_variables(&self) -> impl Iterator<Item = (&String, &Value)> + '_ {
+        self.globals.iter(&self.bytecode.global_names)
     }
 
     /// Get debug information from the bytecode.
@@ -803,6 +1127,7 @@ impl VM {
     ///
     /// When `VmRunResult::Paused` is returned the VM state is fully preserved
     /// and execution can be resumed by calling `run_debuggable` again.
+    #[cfg(feature = "debugger")]
     pub fn run_debuggable(
         &mut self,
         debug_state: &mut crate::debugger::DebuggerState,
@@ -813,6 +1138,13 @@ impl VM {
 
         // Sync verified breakpoints from debug_state into the embedded Debugger.
         self.enable_debugging();
+
+        // An error held back by break-on-error is delivered on resume.
+        if let Some(err) = self.debugger.as_mut().and_then(|d| d.take_pending_error()) {
+            debug_state.stop();
+            return Err(err);
+        }
+
         let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
         let dbg = self
             .debugger
@@ -827,12 +1159,23 @@ impl VM {
                 dbg.set_breakpoint(offset);
             }
         }
+        dbg.clear_watches();
+        for wp in debug_state.watchpoints_owned() {
+            let slot = match wp.frame_depth {
+                Some(frame_depth) => WatchSlot::Local {
+                    frame_depth,
+                    stack_index: wp.slot,
+                },
+                None => WatchSlot::Global(wp.slot),
+            };
+            dbg.set_watch(wp.id, slot);
+        }
 
         // Configure the step condition.
         let step_condition = match debug_state.step_mode {
             StepMode::None => StepCondition::None,
             StepMode::Into => StepCondition::Always,
-            StepMode::Over => StepCondition::OverDepth(debug_state.step_start_frame_depth),
+            StepMode::Over => StepCondition::OverStatement(debug_state.step_start_frame_depth),
             StepMode::Out => StepCondition::OutDepth(debug_state.step_start_frame_depth),
         };
         let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
@@ -849,7 +1192,7 @@ impl VM {
         self.ctx.debug_pause_pending = false;
 
         // Run the execute loop (profiling hooks still active).
-        self.current_security = Some(std::sync::Arc::new(security.clone()));
+        self.use_security(security);
         if let Some(ref mut profiler) = self.profiler {
             if profiler.is_enabled() {
                 profiler.start_timing();
@@ -862,11 +1205,26 @@ impl VM {
             }
         }
 
+        // Watchpoints on locals of returned frames are gone for good.
+        if let Some(dbg) = self.debugger.as_mut() {
+            for id in dbg.take_expired_watches() {
+                debug_state.remove_watchpoint(id);
+            }
+        }
+
         if self.ctx.debug_pause_pending {
             // The execute_loop broke early due to a debug pause.
             let ip = self.ctx.ip;
             let location = None; // Caller resolves via SourceMap
-            let reason = if let Some(bp) = debug_state.breakpoint_at_offset(ip) {
+            let watch_hit = self.debugger.as_mut().and_then(|d| d.take_watch_hit());
+            let reason = if let Some(hit) = watch_hit {
+                let format = |value: &Value| value.pretty(&crate::pretty::PrettyConfig::compact());
+                crate::debugger::protocol::PauseReason::Watchpoint {
+                    id: hit.id,
+                    old_value: hit.old_value.as_ref().map(format),
+                    new_value: format(&hit.new_value),
+                }
+            } else if let Some(bp) = debug_state.breakpoint_at_offset(ip) {
                 crate::debugger::protocol::PauseReason::Breakpoint { id: bp.id }
             } else {
                 crate::debugger::protocol::PauseReason::Step
@@ -876,8 +1234,34 @@ impl VM {
             self.ctx.debug_pause_pending = false;
             Ok(VmRunResult::Paused { ip })
         } else {
-            debug_state.stop();
-            Ok(VmRunResult::Complete(loop_result?))
+            match loop_result {
+                Err(err) if debug_state.breaks_on_error(err.kind()) => {
+                    // Break-on-error: pause with the faulting frame still on the call
+                    // stack. The error itself is delivered when execution resumes.
+                    let ip = self
+                        .debugger
+                        .as_ref()
+                        .map(|d| d.last_ip())
+                        .unwrap_or(self.ctx.ip);
+                    self.ctx.ip = ip;
+                    let reason = crate::debugger::protocol::PauseReason::Exception {
+                        message: err.to_string(),
+                        kind: err.kind().to_string(),
+                    };
+                    debug_state.pause(reason, None, ip);
+                    if let Some(dbg) = self.debugger.as_mut() {
+                        dbg.set_pending_error(err);
+                    }
+                    Ok(VmRunResult::Paused { ip })
+                }
+                result => {
+                    debug_state.stop();
+                    if let Err(err) = &result {
+                        self.ctx.error_stack_trace = self.stack_trace(err.span());
+                    }
+                    Ok(VmRunResult::Complete(result?))
+                }
+            }
         }
     }
 
@@ -887,7 +1271,7 @@ impl VM {
         security: &crate::security::SecurityContext,
     ) -> Result<Option<Value>, RuntimeError> {
         // Store security context for builtin calls
-        self.current_security = Some(std::sync::Arc::new(security.clone()));
+        self.use_security(security);
         // Start profiling timer if profiler is enabled
         if let Some(ref mut profiler) = self.profiler {
             if profiler.is_enabled() {
@@ -901,12 +1285,27 @@ impl VM {
                 profiler.stop_timing();
             }
         }
+        // The frames are still in place at this point; capture them before
+        // the next run reuses the context
+        if let Err(err) = &result {
+            self.ctx.error_stack_trace = self.stack_trace(err.span());
+            // The abandoned frames never reach their scope exit: drop their
+            // values now so the files and other handles they hold are closed
+            // instead of lingering until the next run
+            self.ctx.stack.clear();
+            self.scheduler = scheduler::Scheduler::default();
+        }
         result
     }
 
     /// Execute bytecode until reaching the end of instructions
     fn execute_until_end(&mut self) -> Result<Option<Value>, RuntimeError> {
-        self.execute_loop(None)
+        let result = self.execute_loop(None)?;
+        if !self.ctx.debug_pause_pending {
+            // Async fns suspended along the way still have work to finish
+            self.run_remaining_tasks()?;
+        }
+        Ok(result)
     }
 
     /// Execute bytecode until a specific frame depth is reached (for function calls)
@@ -942,8 +1341,17 @@ impl VM {
                 if debugger.is_enabled() {
                     let current_ip = self.ctx.ip - 1;
                     let frame_depth = self.ctx.frames.len();
-                    let action =
-                        debugger.before_instruction_with_depth(current_ip, opcode, frame_depth);
+                    let at_statement = if self.bytecode.statement_offsets.is_empty() {
+                        None
+                    } else {
+                        Some(self.bytecode.is_statement_start(current_ip))
+                    };
+                    let action = debugger.before_instruction_with_depth(
+                        current_ip,
+                        opcode,
+                        frame_depth,
+                        at_statement,
+                    );
                     match action {
                         DebugAction::Pause | DebugAction::Step => {
                             // Back up IP so the paused instruction is re-executed on resume
@@ -962,1562 +1370,145 @@ impl VM {
             if let Some(ref mut profiler) = self.profiler {
                 if profiler.is_enabled() {
                     let instruction_ip = self.ctx.ip - 1; // ip already advanced by read_opcode
+                    profiler.sync_frames(&self.ctx.frames);
                     profiler.record_instruction_at(opcode, instruction_ip);
                     profiler.update_value_stack_depth(self.ctx.stack.len());
                     profiler.update_frame_depth(self.ctx.frames.len());
+                    if profiler.allocations().is_some() {
+                        self.profile_allocations(opcode, instruction_ip);
+                    }
                 }
             }
 
-            match opcode {
-                // ===== Constants =====
-                Opcode::Constant => {
-                    let index = self.read_u16()? as usize;
-                    if index >= self.bytecode.constants.len() {
-                        return Err(RuntimeError::UnknownOpcode {
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        });
-                    }
-                    let value = self.bytecode.constants[index].clone();
-                    self.push(value);
-                }
-                Opcode::Null => self.push(Value::Null),
-                Opcode::True => self.push(Value::Bool(true)),
-                Opcode::False => self.push(Value::Bool(false)),
-
-                // ===== Variables =====
-                Opcode::GetLocal => {
-                    let index = self.read_u16()? as usize;
-                    let base = self.current_frame().stack_base;
-                    let absolute_index = base + index;
-                    if absolute_index >= self.ctx.stack.len() {
-                        return Err(RuntimeError::StackUnderflow {
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        });
-                    }
-                    // Debug mode: reject reads of consumed (moved) local slots.
-                    #[cfg(debug_assertions)]
-                    {
-                        let frame_idx = self.ctx.frames.len() - 1;
-                        if self.ctx.consumed_slots[frame_idx]
-                            .get(index)
-                            .copied()
-                            .unwrap_or(false)
-                        {
-                            return Err(RuntimeError::TypeError {
-                                msg: format!(
-                                    "use of moved value: local[{}] was passed to 'own' parameter and is no longer valid",
-                                    index
-                                ),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            });
-                        }
-                    }
-                    let value = self.ctx.stack[absolute_index].clone();
-                    self.push(value);
-                    // Record that the top-of-stack value originated from this local slot.
-                    #[cfg(debug_assertions)]
-                    {
-                        if let Some(origin) = self.ctx.value_origins.last_mut() {
-                            *origin = Some(StackValueOrigin::Local(index));
-                        } else {
-                            return Err(RuntimeError::InternalError {
-                                msg: "Missing value origin stack for local read".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            });
-                        }
+            match dispatch::handler(opcode)(self) {
+                Ok(Flow::Next) => {}
+                Ok(Flow::Halt) => break,
+                Ok(Flow::Suspend) => return Ok(None),
+                Err(err) => {
+                    if !self.unwind_to_handler(&err, target_frame_depth)? {
+                        return Err(err);
                     }
                 }
-                Opcode::SetLocal => {
-                    let index = self.read_u16()? as usize;
-                    let base = self.current_frame().stack_base;
-                    let local_count = self.current_frame().local_count;
-                    let absolute_index = base + index;
-                    let value = self.peek(0).clone();
-
-                    // SAFETY CHECK: Prevent unbounded stack growth
-                    // This prevents memory explosion from invalid bytecode or compiler bugs
-                    if index >= local_count {
-                        return Err(RuntimeError::StackUnderflow {
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        });
-                    }
+            }
+        }
 
-                    // Extend stack if needed (for local variables not yet initialized).
-                    // The outer `index >= local_count` guard above already ensures
-                    // absolute_index < base + local_count, so extension is always
-                    // within the declared local area.
-                    if absolute_index >= self.ctx.stack.len() {
-                        let needed = absolute_index - self.ctx.stack.len() + 1;
-                        for _ in 0..needed {
-                            self.push(Value::Null);
-                        }
-                    }
-                    self.ctx.stack[absolute_index] = value;
-                }
-                Opcode::GetGlobal => {
-                    let name_index = self.read_u16()? as usize;
-                    if name_index >= self.bytecode.constants.len() {
-                        return Err(RuntimeError::UnknownOpcode {
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        });
-                    }
-                    let name = match &self.bytecode.constants[name_index] {
-                        Value::String(s) => s.as_ref().clone(),
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Expected string constant for variable name".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            })
-                        }
-                    };
-                    // Debug mode: reject reads of consumed globals.
-                    #[cfg(debug_assertions)]
-                    if self.ctx.consumed_globals.contains(&name) {
-                        return Err(RuntimeError::TypeError {
-                            msg: format!(
-                                "use of moved value: '{}' was passed to 'own' parameter and is no longer valid",
-                                name
-                            ),
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        });
-                    }
-                    let value = if name == "None" {
-                        // Constructor literal: None always evaluates to Option::None
-                        Value::Option(None)
-                    } else if let Some(v) = self.globals.get(&name) {
-                        v.clone()
-                    } else if crate::stdlib::is_builtin(&name)
-                        || crate::stdlib::is_array_intrinsic(&name)
-                    {
-                        // Builtin or intrinsic - return builtin value
-                        Value::Builtin(std::sync::Arc::from(name.as_str()))
-                    } else if crate::method_dispatch::is_static_namespace(&name) {
-                        // Static namespace (Json, Math, console, reflect, etc.) - return as builtin
-                        Value::Builtin(std::sync::Arc::from(name.as_str()))
-                    } else {
-                        // B22: Math constants removed as bare identifiers. Use Math.PI, Math.E, etc.
-                        return Err(RuntimeError::UndefinedVariable {
-                            name: name.clone(),
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        });
-                    };
-                    self.push(value);
-                    // Record global origin for own-consume tracking (debug builds only).
-                    // Only track user-defined globals (not builtins, constructors, math constants).
-                    #[cfg(debug_assertions)]
-                    if self.globals.contains_key(&name) {
-                        if let Some(origin) = self.ctx.value_origins.last_mut() {
-                            *origin = Some(StackValueOrigin::Global(name));
-                        } else {
-                            return Err(RuntimeError::InternalError {
-                                msg: "Missing value origin stack for global read".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            });
-                        }
-                    }
-                }
-                Opcode::SetGlobal => {
-                    let name_index = self.read_u16()? as usize;
-                    if name_index >= self.bytecode.constants.len() {
-                        return Err(RuntimeError::UnknownOpcode {
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        });
-                    }
-                    let name = match &self.bytecode.constants[name_index] {
-                        Value::String(s) => s.as_ref().clone(),
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Expected string constant for variable name".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            })
-                        }
-                    };
-                    let value = self.peek(0).clone();
-                    self.globals.insert(name, value);
-                }
+        // Return top of stack if present; a debug pause leaves the stack
+        // intact so the paused frame can be inspected and resumed
+        Ok(
+            if self.ctx.debug_pause_pending || self.ctx.stack.is_empty() {
+                None
+            } else {
+                Some(self.pop())
+            },
+        )
+    }
 
-                Opcode::MakeClosure => {
-                    let func_const_idx = self.read_u16()? as usize;
-                    let n_upvalues = self.read_u16()? as usize;
-
-                    // Get the FunctionRef from constant pool
-                    let func = match self.bytecode.constants.get(func_const_idx) {
-                        Some(Value::Function(f)) => f.clone(),
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "MakeClosure: constant is not a function".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            })
-                        }
-                    };
+    /// Transfer control to the innermost `try` handler that can catch `err`
+    ///
+    /// Only handlers owned by frames this `execute_loop` runs (at or above
+    /// `target_frame_depth`) are considered; an error escaping a nested loop
+    /// reaches the outer loop's handlers once the builtin that started it
+    /// returns. Frames above the handler's frame are popped after running
+    /// their deferred blocks. Returns `false`, leaving the error to
+    /// propagate, when there is no such handler or the error is not
+    /// catchable.
+    fn unwind_to_handler(
+        &mut self,
+        err: &RuntimeError,
+        target_frame_depth: Option<usize>,
+    ) -> Result<bool, RuntimeError> {
+        let floor = target_frame_depth.unwrap_or(0);
+        // Handlers of frames abandoned by an earlier error in a nested loop
+        self.drop_handlers_from(self.ctx.frames.len());
+        let handler = match self.ctx.handlers.last() {
+            Some(h) if err.is_catchable() && h.frame_index >= floor => {
+                self.ctx.handlers.pop().expect("handler checked above")
+            }
+            _ => {
+                self.drop_handlers_from(floor);
+                return Ok(false);
+            }
+        };
 
-                    // Pop upvalues from stack (in reverse order since stack is LIFO)
-                    let mut upvalues = Vec::with_capacity(n_upvalues);
-                    for _ in 0..n_upvalues {
-                        upvalues.push(self.pop());
-                    }
-                    upvalues.reverse(); // Restore capture order
+        while self.ctx.frames.len() > handler.frame_index + 1 {
+            self.run_frame_defers()?;
+            self.ctx.frames.pop();
+            self.ctx.defer_stacks.pop();
+            #[cfg(debug_assertions)]
+            self.ctx.consumed_slots.pop();
+        }
+        self.drop_frame_watches();
+        self.ctx.stack.truncate(handler.stack_len);
+        #[cfg(debug_assertions)]
+        self.ctx.value_origins.truncate(handler.stack_len);
 
-                    let closure = crate::value::ClosureRef {
-                        func,
-                        upvalues: std::sync::Arc::new(upvalues),
-                    };
-                    self.push(Value::Closure(closure));
-                }
+        self.ctx.caught_error = Some(err.to_record());
+        self.ctx.ip = handler.catch_ip;
+        Ok(true)
+    }
 
-                Opcode::GetUpvalue => {
-                    let idx = self.read_u16()? as usize;
-                    let value = match self.current_frame().upvalues.get(idx) {
-                        Some(v) => v.clone(),
-                        None => {
-                            return Err(RuntimeError::TypeError {
-                                msg: format!(
-                                    "Upvalue index {} out of bounds (closure has {} upvalues)",
-                                    idx,
-                                    self.current_frame().upvalues.len()
-                                ),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            })
-                        }
-                    };
-                    self.push(value);
-                }
+    /// Remove the handlers owned by frame `frame_index` and above
+    fn drop_handlers_from(&mut self, frame_index: usize) {
+        while self
+            .ctx
+            .handlers
+            .last()
+            .is_some_and(|h| h.frame_index >= frame_index)
+        {
+            self.ctx.handlers.pop();
+        }
+    }
 
-                Opcode::SetUpvalue => {
-                    let idx = self.read_u16()? as usize;
-                    let value = self.peek(0).clone();
-                    let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
-                    let frame =
-                        self.ctx
-                            .frames
-                            .last_mut()
-                            .ok_or_else(|| RuntimeError::InternalError {
-                                msg: "Missing call frame for SetUpvalue".to_string(),
-                                span,
-                            })?;
-                    let upvalues = std::sync::Arc::make_mut(&mut frame.upvalues);
-                    if idx < upvalues.len() {
-                        upvalues[idx] = value;
-                    } else {
-                        return Err(RuntimeError::TypeError {
-                            msg: format!(
-                                "SetUpvalue: index {} out of bounds (closure has {} upvalues)",
-                                idx,
-                                upvalues.len()
-                            ),
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        });
-                    }
-                }
+    // ===== Helper Methods =====
 
-                // ===== Arithmetic =====
-                Opcode::Add => {
-                    let b = self.pop();
-                    let a = self.pop();
-                    match (&a, &b) {
-                        (Value::Number(x), Value::Number(y)) => {
-                            let result = x + y;
-                            if result.is_nan() || result.is_infinite() {
-                                return Err(RuntimeError::InvalidNumericResult {
-                                    span: self
-                                        .current_span()
-                                        .unwrap_or_else(crate::span::Span::dummy),
-                                });
-                            }
-                            self.push(Value::Number(result));
-                        }
-                        (Value::String(x), Value::String(y)) => {
-                            // Track memory for the concatenated string
-                            let new_len = x.len() + y.len();
-                            self.track_memory(Self::estimate_string_size(new_len))?;
-
-                            // Reuse string buffer to reduce allocations
-                            self.ctx.string_buffer.clear();
-                            self.ctx.string_buffer.push_str(x);
-                            self.ctx.string_buffer.push_str(y);
-                            self.push(Value::String(Arc::new(self.ctx.string_buffer.clone())));
-                        }
-                        (Value::Array(x), Value::Array(y)) => {
-                            let new_len = x.len() + y.len();
-                            self.track_memory(Self::estimate_array_size(new_len))?;
-
-                            let mut elements = Vec::with_capacity(new_len);
-                            elements.extend_from_slice(x.as_slice());
-                            elements.extend_from_slice(y.as_slice());
-                            self.push(Value::Array(ValueArray::from_vec(elements)));
-                        }
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Invalid operands for +".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            })
-                        }
-                    }
-                }
-                Opcode::Sub => self.binary_numeric_op(|a, b| a - b)?,
-                Opcode::Mul => self.binary_numeric_op(|a, b| a * b)?,
-                Opcode::Div => {
-                    let b = self.pop_number()?;
-                    let a = self.pop_number()?;
-                    if b == 0.0 {
-                        return Err(RuntimeError::DivideByZero {
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        });
-                    }
-                    let result = a / b;
-                    if result.is_nan() || result.is_infinite() {
-                        return Err(RuntimeError::InvalidNumericResult {
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        });
-                    }
-                    self.push(Value::Number(result));
-                }
-                Opcode::Mod => {
-                    let b = self.pop_number()?;
-                    let a = self.pop_number()?;
-                    if b == 0.0 {
-                        return Err(RuntimeError::DivideByZero {
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        });
-                    }
-                    let result = a % b;
-                    if result.is_nan() || result.is_infinite() {
-                        return Err(RuntimeError::InvalidNumericResult {
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        });
-                    }
-                    self.push(Value::Number(result));
-                }
-                Opcode::Negate => {
-                    let value = self.pop();
-                    match value {
-                        Value::Number(n) => self.push(Value::Number(-n)),
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Cannot negate non-number".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            })
-                        }
-                    }
-                }
+    #[inline(always)]
+    fn push(&mut self, value: Value) {
+        self.ctx.stack.push(value);
+        #[cfg(debug_assertions)]
+        self.ctx.value_origins.push(None);
+    }
 
-                // ===== Comparison =====
-                Opcode::Equal => {
-                    let b = self.pop();
-                    let a = self.pop();
-                    self.push(Value::Bool(a == b));
-                }
-                Opcode::NotEqual => {
-                    let b = self.pop();
-                    let a = self.pop();
-                    self.push(Value::Bool(a != b));
-                }
-                Opcode::Less => {
-                    let b = self.pop_number()?;
-                    let a = self.pop_number()?;
-                    self.push(Value::Bool(a < b));
-                }
-                Opcode::LessEqual => {
-                    let b = self.pop_number()?;
-                    let a = self.pop_number()?;
-                    self.push(Value::Bool(a <= b));
-                }
-                Opcode::Greater => {
-                    let b = self.pop_number()?;
-                    let a = self.pop_number()?;
-                    self.push(Value::Bool(a > b));
-                }
-                Opcode::GreaterEqual => {
-                    let b = self.pop_number()?;
-                    let a = self.pop_number()?;
-                    self.push(Value::Bool(a >= b));
-                }
+    #[inline(always)]
+    fn pop(&mut self) -> Value {
+        #[cfg(debug_assertions)]
+        self.ctx.value_origins.pop();
+        // SAFETY: VM invariants guarantee the stack is non-empty when pop() is called.
+        // Preconditions: every opcode that calls pop() has already pushed its operands,
+        // and debug builds mirror this via value_origins.
+        unsafe { self.ctx.stack.pop().unwrap_unchecked() }
+    }
 
-                // ===== Logical =====
-                Opcode::Not => {
-                    let value = self.pop();
-                    match value {
-                        Value::Bool(b) => self.push(Value::Bool(!b)),
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Cannot apply ! to non-boolean".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            })
-                        }
-                    }
-                }
-                Opcode::And => {
-                    // Non-short-circuit And: both operands already evaluated
-                    // Short-circuit is handled by compiler via JumpIfFalse
-                    let b = self.pop();
-                    let a = self.pop();
-                    match (&a, &b) {
-                        (Value::Bool(a_val), Value::Bool(b_val)) => {
-                            self.push(Value::Bool(*a_val && *b_val));
-                        }
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: format!(
-                                    "Cannot apply && to {} and {}",
-                                    a.type_name(),
-                                    b.type_name()
-                                ),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            });
-                        }
-                    }
-                }
-                Opcode::Or => {
-                    // Non-short-circuit Or: both operands already evaluated
-                    // Short-circuit is handled by compiler via JumpIfFalse
-                    let b = self.pop();
-                    let a = self.pop();
-                    match (&a, &b) {
-                        (Value::Bool(a_val), Value::Bool(b_val)) => {
-                            self.push(Value::Bool(*a_val || *b_val));
-                        }
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: format!(
-                                    "Cannot apply || to {} and {}",
-                                    a.type_name(),
-                                    b.type_name()
-                                ),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            });
-                        }
-                    }
-                }
+    #[inline(always)]
+    fn peek(&self, distance: usize) -> &Value {
+        // SAFETY: The compiler guarantees stack depth matches operand requirements.
+        // Preconditions: each opcode that calls peek() is emitted only when sufficient
+        // values exist, and `distance` is within the current stack depth.
+        unsafe {
+            self.ctx
+                .stack
+                .get_unchecked(self.ctx.stack.len() - 1 - distance)
+        }
+    }
 
-                // ===== Control Flow =====
-                Opcode::Jump => {
-                    let offset = self.read_i16()?;
-                    self.ctx.ip = (self.ctx.ip as isize + offset as isize) as usize;
-                }
-                Opcode::JumpIfFalse => {
-                    let offset = self.read_i16()?;
-                    let condition = self.pop();
-                    if !condition.is_truthy() {
-                        self.ctx.ip = (self.ctx.ip as isize + offset as isize) as usize;
-                    }
-                }
-                Opcode::Loop => {
-                    let offset = self.read_i16()?;
-                    self.ctx.ip = (self.ctx.ip as isize + offset as isize) as usize;
-                }
+    #[inline(always)]
+    fn pop_number(&mut self) -> Result<f64, RuntimeError> {
+        match self.pop() {
+            Value::Number(n) => Ok(n),
+            _ => Err(RuntimeError::TypeError {
+                msg: "Expected number".to_string(),
+                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+            }),
+        }
+    }
 
-                // ===== Functions =====
-                Opcode::TraitDispatch => {
-                    let trait_idx = self.read_u16()? as usize;
-                    let method_idx = self.read_u16()? as usize;
-                    let arg_count = self.read_u8()? as usize;
-
-                    let trait_name = match self.bytecode.constants.get(trait_idx) {
-                        Some(Value::String(s)) => s.as_ref().clone(),
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Expected string constant for trait name".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            })
-                        }
-                    };
-                    let method_name = match self.bytecode.constants.get(method_idx) {
-                        Some(Value::String(s)) => s.as_ref().clone(),
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Expected string constant for method name".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            })
-                        }
-                    };
-
-                    let mut args = Vec::with_capacity(arg_count);
-                    for _ in 0..arg_count {
-                        args.push(self.pop());
-                    }
-                    args.reverse();
-
-                    let receiver =
-                        args.first()
-                            .cloned()
-                            .ok_or_else(|| RuntimeError::TypeError {
-                                msg: "Trait dispatch requires a receiver".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            })?;
-                    let dispatch_type = self
-                        .struct_name_for_value(&receiver)
-                        .unwrap_or_else(|| receiver.type_name());
-                    let mangled_name =
-                        format!("__impl__{}__{}__{}", dispatch_type, trait_name, method_name);
-                    let function = self.globals.get(&mangled_name).cloned().ok_or_else(|| {
-                        RuntimeError::TypeError {
-                            msg: format!(
-                                "Trait method '{}' not found (impl not registered for this type)",
-                                method_name
-                            ),
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        }
-                    })?;
-
-                    self.push(function);
-                    for arg in args {
-                        self.push(arg);
-                    }
-                    self.execute_call(arg_count)?;
-                }
-                Opcode::Call => {
-                    let arg_count = self.read_u8()? as usize;
-                    self.execute_call(arg_count)?;
-                }
-
-                Opcode::Return => {
-                    // Pop the return value from stack (if any)
-                    let return_value = if self.ctx.stack.is_empty() {
-                        Value::Null
-                    } else {
-                        self.pop()
-                    };
-
-                    // Execute deferred blocks in LIFO order before returning
-                    let frame_idx = self.ctx.frames.len().saturating_sub(1);
-                    while frame_idx < self.ctx.defer_stacks.len() {
-                        let Some((body_start, body_len)) = self.ctx.defer_stacks[frame_idx].pop()
-                        else {
-                            break;
-                        };
-                        // Save current IP
-                        let saved_ip = self.ctx.ip;
-
-                        // Execute defer body by setting IP and running until body_end
-                        // We use a bounded loop that processes opcodes directly
-                        self.ctx.ip = body_start;
-                        let body_end = body_start + body_len;
-
-                        // Execute opcodes until we reach body_end
-                        while self.ctx.ip < body_end {
-                            let opcode = match self.read_opcode() {
-                                Ok(op) => op,
-                                Err(_) => break,
-                            };
-
-                            // Execute the opcode (simplified dispatch for defer bodies)
-                            // This handles common cases; complex defers may need full dispatch
-                            match opcode {
-                                Opcode::Constant => {
-                                    let index = self.read_u16()? as usize;
-                                    let value = self.bytecode.constants[index].clone();
-                                    self.push(value);
-                                }
-                                Opcode::GetGlobal => {
-                                    let name_index = self.read_u16()? as usize;
-                                    if let Value::String(ref name) =
-                                        self.bytecode.constants[name_index]
-                                    {
-                                        if let Some(value) = self.globals.get(name.as_ref()) {
-                                            self.push(value.clone());
-                                        } else {
-                                            self.push(Value::Null);
-                                        }
-                                    }
-                                }
-                                Opcode::Call => {
-                                    let arg_count = self.read_u8()? as usize;
-                                    let frames_before_call = self.ctx.frames.len();
-                                    self.execute_call(arg_count)?;
-                                    // H-364: If execute_call pushed a new frame (user-defined
-                                    // function), drive it to completion via the full dispatch
-                                    // loop so that Return, arithmetic, GetLocal etc. all work.
-                                    // The loop stops when frames.len() drops back to
-                                    // frames_before_call, leaving the return value on the stack.
-                                    if self.ctx.frames.len() > frames_before_call {
-                                        self.execute_loop(Some(frames_before_call))?;
-                                    }
-                                }
-                                Opcode::Pop => {
-                                    self.pop();
-                                }
-                                Opcode::GetLocal => {
-                                    let index = self.read_u16()? as usize;
-                                    let base = self.current_frame().stack_base;
-                                    let value = self.ctx.stack[base + index].clone();
-                                    self.push(value);
-                                }
-                                _ => {
-                                    // For other opcodes, skip their operands
-                                    let size = dispatch::operand_size(opcode);
-                                    self.ctx.ip += size;
-                                }
-                            }
-                        }
-
-                        // Restore IP
-                        self.ctx.ip = saved_ip;
-                    }
-
-                    // Pop the call frame and defer stack
-                    let frame = self.ctx.frames.pop();
-                    self.ctx.defer_stacks.pop();
-                    #[cfg(debug_assertions)]
-                    self.ctx.consumed_slots.pop();
-
-                    if let Some(f) = frame {
-                        // Clean up the stack (remove locals, arguments, and function value)
-                        self.ctx.stack.truncate(f.stack_base);
-                        #[cfg(debug_assertions)]
-                        self.ctx.value_origins.truncate(f.stack_base);
-                        // Also remove the function value (one slot below stack_base)
-                        if f.stack_base > 0 && !self.ctx.stack.is_empty() {
-                            self.ctx.stack.pop();
-                            #[cfg(debug_assertions)]
-                            self.ctx.value_origins.pop();
-                        }
-
-                        // Restore IP to return address
-                        self.ctx.ip = f.return_ip;
-
-                        // Push return value
-                        self.push(return_value);
-                    } else {
-                        // Returning from main - we're done
-                        // Push the return value and halt
-                        self.push(return_value);
-                        break;
-                    }
-                }
-
-                // ===== Arrays =====
-                Opcode::Array => {
-                    let size = self.read_u16()? as usize;
-
-                    // Track memory allocation before creating the array
-                    self.track_memory(Self::estimate_array_size(size))?;
-
-                    let mut elements = Vec::with_capacity(size);
-                    for _ in 0..size {
-                        elements.push(self.pop());
-                    }
-                    elements.reverse(); // Stack is LIFO, so reverse to get correct order
-                    self.push(Value::Array(ValueArray::from_vec(elements)));
-                }
-                // ===== Tuples =====
-                Opcode::Tuple => {
-                    let size = self.read_u16()? as usize;
-                    let mut elements = Vec::with_capacity(size);
-                    for _ in 0..size {
-                        elements.push(self.pop());
-                    }
-                    elements.reverse(); // Stack is LIFO, so reverse to get correct order
-                    self.push(Value::Tuple(Arc::new(elements)));
-                }
-                Opcode::TupleGet => {
-                    let index = self.read_u16()? as usize;
-                    let tuple = self.pop();
-                    match tuple {
-                        Value::Tuple(elems) => {
-                            if index >= elems.len() {
-                                return Err(RuntimeError::OutOfBounds {
-                                    span: self
-                                        .current_span()
-                                        .unwrap_or_else(crate::span::Span::dummy),
-                                });
-                            }
-                            self.push(elems[index].clone());
-                        }
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "TupleGet applied to non-tuple value".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            });
-                        }
-                    }
-                }
-                Opcode::GetIndex => {
-                    let index_val = self.pop();
-                    let target = self.pop();
-                    match target {
-                        Value::Array(arr) => {
-                            // Array indexing requires number
-                            match index_val {
-                                Value::Number(index) => {
-                                    if index.fract() != 0.0 || index < 0.0 {
-                                        return Err(RuntimeError::InvalidIndex {
-                                            span: self
-                                                .current_span()
-                                                .unwrap_or_else(crate::span::Span::dummy),
-                                        });
-                                    }
-                                    let idx = index as usize;
-                                    if idx >= arr.len() {
-                                        return Err(RuntimeError::OutOfBounds {
-                                            span: self
-                                                .current_span()
-                                                .unwrap_or_else(crate::span::Span::dummy),
-                                        });
-                                    }
-                                    self.push(arr[idx].clone());
-                                }
-                                Value::Range {
-                                    start,
-                                    end,
-                                    inclusive,
-                                } => {
-                                    let start = start.unwrap_or(0.0);
-                                    let mut end_val = end.unwrap_or(arr.len() as f64);
-                                    if inclusive && end.is_some() {
-                                        end_val += 1.0;
-                                    }
-                                    let span = self
-                                        .current_span()
-                                        .unwrap_or_else(crate::span::Span::dummy);
-                                    let sliced = crate::stdlib::array::slice(
-                                        arr.as_slice(),
-                                        start,
-                                        end_val,
-                                        span,
-                                    )?;
-                                    self.push(sliced);
-                                }
-                                _ => {
-                                    return Err(RuntimeError::InvalidIndex {
-                                        span: self
-                                            .current_span()
-                                            .unwrap_or_else(crate::span::Span::dummy),
-                                    });
-                                }
-                            }
-                        }
-                        Value::String(s) => {
-                            // String indexing by character position (Unicode-aware)
-                            if let Value::Number(index) = index_val {
-                                if index.fract() != 0.0 || index < 0.0 {
-                                    return Err(RuntimeError::InvalidIndex {
-                                        span: self
-                                            .current_span()
-                                            .unwrap_or_else(crate::span::Span::dummy),
-                                    });
-                                }
-                                let idx = index as usize;
-                                let chars: Vec<char> = s.chars().collect();
-                                if idx >= chars.len() {
-                                    return Err(RuntimeError::OutOfBounds {
-                                        span: self
-                                            .current_span()
-                                            .unwrap_or_else(crate::span::Span::dummy),
-                                    });
-                                }
-                                self.push(Value::string(chars[idx].to_string()));
-                            } else {
-                                return Err(RuntimeError::InvalidIndex {
-                                    span: self
-                                        .current_span()
-                                        .unwrap_or_else(crate::span::Span::dummy),
-                                });
-                            }
-                        }
-                        Value::JsonValue(json) => {
-                            // JSON indexing accepts string or number
-                            let result = match index_val {
-                                Value::String(key) => json.index_str(key.as_ref()),
-                                Value::Number(n) => json.index_num(n),
-                                _ => {
-                                    return Err(RuntimeError::TypeError {
-                                        msg: "JSON index must be string or number".to_string(),
-                                        span: self
-                                            .current_span()
-                                            .unwrap_or_else(crate::span::Span::dummy),
-                                    })
-                                }
-                            };
-                            self.push(Value::JsonValue(Arc::new(result)));
-                        }
-                        // H-116: range as for-in target — index i yields start + i
-                        Value::Range { start, .. } => {
-                            if let Value::Number(idx) = index_val {
-                                let s = start.unwrap_or(0.0);
-                                self.push(Value::Number(s + idx));
-                            } else {
-                                return Err(RuntimeError::InvalidIndex {
-                                    span: self
-                                        .current_span()
-                                        .unwrap_or_else(crate::span::Span::dummy),
-                                });
-                            }
-                        }
-                        // Map indexing: map["key"] → Option<V>
-                        // Returns Some(value) if key exists, None if not.
-                        // Mirrors the Map.get() stdlib method for dynamic key access.
-                        Value::Map(map) => {
-                            let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
-                            let key = crate::stdlib::collections::hash::HashKey::from_value(
-                                &index_val, span,
-                            )?;
-                            match map.get(&key).cloned() {
-                                Some(v) => self.push(Value::Option(Some(Box::new(v)))),
-                                None => self.push(Value::Option(None)),
-                            }
-                        }
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Cannot index non-array/string/json".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            })
-                        }
-                    }
-                }
-                Opcode::SetIndex => {
-                    let value = self.pop();
-                    let index = self.pop_number()?;
-                    let mut array = self.pop();
-                    match &mut array {
-                        Value::Array(arr) => {
-                            if index.fract() != 0.0 || index < 0.0 {
-                                return Err(RuntimeError::InvalidIndex {
-                                    span: self
-                                        .current_span()
-                                        .unwrap_or_else(crate::span::Span::dummy),
-                                });
-                            }
-                            let idx = index as usize;
-                            if idx >= arr.len() {
-                                return Err(RuntimeError::OutOfBounds {
-                                    span: self
-                                        .current_span()
-                                        .unwrap_or_else(crate::span::Span::dummy),
-                                });
-                            }
-                            // CoW: set triggers Arc::make_mut if arr is shared
-                            arr.set(idx, value);
-                        }
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Cannot index non-array".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            })
-                        }
-                    }
-                    // Push the mutated array back — compiler emits SetLocal/SetGlobal to write it
-                    // back to the variable, then Pop removes it from the expression stack.
-                    self.push(array);
-                }
-                Opcode::GetField => {
-                    let key_val = self.pop();
-                    let map_val = self.pop();
-                    let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
-                    let key =
-                        crate::stdlib::collections::hash::HashKey::from_value(&key_val, span)?;
-
-                    match map_val {
-                        Value::Map(map) => match map.get(&key).cloned() {
-                            Some(value) => self.push(value),
-                            None => {
-                                let field = match key_val {
-                                    Value::String(s) => s.as_ref().to_string(),
-                                    other => other.type_name().to_string(),
-                                };
-                                return Err(RuntimeError::TypeError {
-                                    msg: format!("Missing field '{}'", field),
-                                    span,
-                                });
-                            }
-                        },
-                        other => {
-                            return Err(RuntimeError::TypeError {
-                                msg: format!(
-                                    "Cannot access field on non-record type {}",
-                                    other.type_name()
-                                ),
-                                span,
-                            })
-                        }
-                    }
-                }
-                Opcode::SetField => {
-                    let value = self.pop();
-                    let key_val = self.pop();
-                    let mut map_val = self.pop();
-                    let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
-                    let key =
-                        crate::stdlib::collections::hash::HashKey::from_value(&key_val, span)?;
-                    let field_name = match &key_val {
-                        Value::String(s) => s.as_ref().to_string(),
-                        other => other.type_name().to_string(),
-                    };
-
-                    match &mut map_val {
-                        Value::Map(map) => {
-                            let existing =
-                                map.get(&key)
-                                    .cloned()
-                                    .ok_or_else(|| RuntimeError::TypeError {
-                                        msg: format!("Missing field '{}'", field_name),
-                                        span,
-                                    })?;
-                            if existing.type_name() != value.type_name() {
-                                return Err(RuntimeError::TypeError {
-                                    msg: format!(
-                                        "Type mismatch for field '{}': expected {}, found {}",
-                                        field_name,
-                                        existing.type_name(),
-                                        value.type_name()
-                                    ),
-                                    span,
-                                });
-                            }
-                            map.insert(key, value);
-                        }
-                        other => {
-                            return Err(RuntimeError::TypeError {
-                                msg: format!(
-                                    "Cannot assign field on non-record type {}",
-                                    other.type_name()
-                                ),
-                                span,
-                            })
-                        }
-                    }
-                    // Push the mutated map back — compiler emits SetLocal/SetGlobal to write it back
-                    self.push(map_val);
-                }
-                Opcode::Slice => {
-                    let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
-                    let end = match self.pop() {
-                        Value::Number(n) => n,
-                        _ => return Err(RuntimeError::InvalidIndex { span }),
-                    };
-                    let start = match self.pop() {
-                        Value::Number(n) => n,
-                        _ => return Err(RuntimeError::InvalidIndex { span }),
-                    };
-                    let target = self.pop();
-                    match target {
-                        Value::Array(arr) => {
-                            let sliced =
-                                crate::stdlib::array::slice(arr.as_slice(), start, end, span)?;
-                            self.push(sliced);
-                        }
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Cannot slice non-array".to_string(),
-                                span,
-                            })
-                        }
-                    }
-                }
-                Opcode::SliceFrom => {
-                    let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
-                    let start = match self.pop() {
-                        Value::Number(n) => n,
-                        _ => return Err(RuntimeError::InvalidIndex { span }),
-                    };
-                    let target = self.pop();
-                    match target {
-                        Value::Array(arr) => {
-                            let end = arr.len() as f64;
-                            let sliced =
-                                crate::stdlib::array::slice(arr.as_slice(), start, end, span)?;
-                            self.push(sliced);
-                        }
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Cannot slice non-array".to_string(),
-                                span,
-                            })
-                        }
-                    }
-                }
-                Opcode::SliceTo => {
-                    let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
-                    let end = match self.pop() {
-                        Value::Number(n) => n,
-                        _ => return Err(RuntimeError::InvalidIndex { span }),
-                    };
-                    let target = self.pop();
-                    match target {
-                        Value::Array(arr) => {
-                            let sliced =
-                                crate::stdlib::array::slice(arr.as_slice(), 0.0, end, span)?;
-                            self.push(sliced);
-                        }
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Cannot slice non-array".to_string(),
-                                span,
-                            })
-                        }
-                    }
-                }
-                Opcode::SliceFull => {
-                    let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
-                    let target = self.pop();
-                    match target {
-                        Value::Array(arr) => {
-                            let sliced = crate::stdlib::array::slice(
-                                arr.as_slice(),
-                                0.0,
-                                arr.len() as f64,
-                                span,
-                            )?;
-                            self.push(sliced);
-                        }
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Cannot slice non-array".to_string(),
-                                span,
-                            })
-                        }
-                    }
-                }
-
-                Opcode::Range => {
-                    let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
-                    let inclusive = self.read_u8()? != 0;
-                    let end_val = self.pop();
-                    let start_val = self.pop();
-
-                    let start = match start_val {
-                        Value::Null => None,
-                        Value::Number(n) => Some(n),
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Range bound must be number".to_string(),
-                                span,
-                            })
-                        }
-                    };
-
-                    let end = match end_val {
-                        Value::Null => None,
-                        Value::Number(n) => Some(n),
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Range bound must be number".to_string(),
-                                span,
-                            })
-                        }
-                    };
-
-                    if inclusive && end.is_none() {
-                        return Err(RuntimeError::TypeError {
-                            msg: "Inclusive range requires an end bound".to_string(),
-                            span,
-                        });
-                    }
-
-                    self.push(Value::Range {
-                        start,
-                        end,
-                        inclusive,
-                    });
-                }
-
-                Opcode::HashMap => {
-                    use crate::stdlib::collections::hash::HashKey;
-                    use crate::stdlib::collections::hashmap::AtlasHashMap;
-                    use crate::value::ValueHashMap;
-
-                    let entry_count = self.read_u16()? as usize;
-
-                    // Stack has [key1, val1, key2, val2, ...] in order
-                    // Pop them in reverse (LIFO) and insert
-                    let mut entries = Vec::with_capacity(entry_count);
-                    for _ in 0..entry_count {
-                        let value = self.pop();
-                        let key_val = self.pop();
-                        entries.push((key_val, value));
-                    }
-                    // Reverse to get original order
-                    entries.reverse();
-
-                    let mut atlas_map = AtlasHashMap::with_capacity(entry_count);
-                    for (key_val, value) in entries {
-                        // Convert Value to HashKey (keys must be hashable)
-                        let key = HashKey::from_value(
-                            &key_val,
-                            self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        )?;
-                        atlas_map.insert(key, value);
-                    }
-
-                    self.push(Value::Map(ValueHashMap::from_atlas(atlas_map)));
-                }
-                Opcode::Struct => {
-                    use crate::stdlib::collections::hash::HashKey;
-                    use crate::stdlib::collections::hashmap::AtlasHashMap;
-                    use crate::value::ValueHashMap;
-
-                    let name_idx = self.read_u16()? as usize;
-                    let field_count = self.read_u16()? as usize;
-                    let struct_name = match self.bytecode.constants.get(name_idx) {
-                        Some(Value::String(s)) => s.as_ref().clone(),
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "Expected string constant for struct name".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            });
-                        }
-                    };
-
-                    let mut entries = Vec::with_capacity(field_count);
-                    for _ in 0..field_count {
-                        let value = self.pop();
-                        let key_val = self.pop();
-                        entries.push((key_val, value));
-                    }
-                    entries.reverse();
-
-                    let mut atlas_map = AtlasHashMap::with_capacity(field_count);
-                    for (key_val, value) in entries {
-                        let key = HashKey::from_value(
-                            &key_val,
-                            self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        )?;
-                        atlas_map.insert(key, value);
-                    }
-
-                    let map = ValueHashMap::from_atlas(atlas_map);
-                    self.register_struct_type(&map, &struct_name);
-                    self.push(Value::Map(map));
-                }
-
-                // ===== Stack Manipulation =====
-                Opcode::Pop => {
-                    // Don't pop if this is the last instruction before Halt
-                    // Check if next instruction is Halt
-                    if self.ctx.ip < self.bytecode.instructions.len()
-                        && self.bytecode.instructions[self.ctx.ip] != Opcode::Halt as u8
-                    {
-                        self.pop();
-                    }
-                }
-                Opcode::Dup => {
-                    let value = self.peek(0).clone();
-                    self.push(value);
-                }
-
-                Opcode::Dup2 => {
-                    // Duplicate top 2 stack values: [a, b] -> [a, b, a, b]
-                    let b = self.peek(0).clone();
-                    let a = self.peek(1).clone();
-                    self.push(a);
-                    self.push(b);
-                }
-
-                Opcode::Rot3 => {
-                    // Rotate top 3 stack values: [a, b, c] -> [b, c, a]
-                    // Pop c, b, a then push b, c, a
-                    let c = self.pop();
-                    let b = self.pop();
-                    let a = self.pop();
-                    self.push(b);
-                    self.push(c);
-                    self.push(a);
-                }
-                Opcode::ToString => {
-                    let value = self.pop();
-                    let span = self.current_span().unwrap_or_else(Span::dummy);
-                    let string_value = crate::stdlib::types::to_string(&[value], span)?;
-                    self.push(string_value);
-                }
-
-                // ===== Pattern Matching =====
-                Opcode::IsOptionSome => {
-                    let value = self.pop();
-                    let is_some = matches!(value, Value::Option(Some(_)));
-                    self.push(Value::Bool(is_some));
-                }
-                Opcode::IsOptionNone => {
-                    let value = self.pop();
-                    let is_none = matches!(value, Value::Option(None));
-                    self.push(Value::Bool(is_none));
-                }
-                Opcode::IsResultOk => {
-                    let value = self.pop();
-                    let is_ok = matches!(value, Value::Result(Ok(_)));
-                    self.push(Value::Bool(is_ok));
-                }
-                Opcode::IsResultErr => {
-                    let value = self.pop();
-                    let is_err = matches!(value, Value::Result(Err(_)));
-                    self.push(Value::Bool(is_err));
-                }
-                Opcode::ExtractOptionValue => {
-                    let value = self.pop();
-                    match value {
-                        Value::Option(Some(inner)) => self.push(*inner),
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "ExtractOptionValue requires Option::Some".to_string(),
-                                span: Span::dummy(),
-                            })
-                        }
-                    }
-                }
-                Opcode::ExtractResultValue => {
-                    let value = self.pop();
-                    match value {
-                        Value::Result(Ok(inner)) => self.push(*inner),
-                        Value::Result(Err(inner)) => self.push(*inner),
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "ExtractResultValue requires Result".to_string(),
-                                span: Span::dummy(),
-                            })
-                        }
-                    }
-                }
-                Opcode::IsArray => {
-                    let value = self.pop();
-                    let is_array = matches!(value, Value::Array(_));
-                    self.push(Value::Bool(is_array));
-                }
-                Opcode::GetArrayLen => {
-                    let value = self.pop();
-                    match value {
-                        Value::Array(arr) => {
-                            let len = arr.len();
-                            self.push(Value::Number(len as f64));
-                        }
-                        // H-116: range in for-in — compute length and push only length
-                        Value::Range {
-                            start,
-                            end,
-                            inclusive,
-                        } => {
-                            let s = start.unwrap_or(0.0) as i64;
-                            let e = end.ok_or_else(|| RuntimeError::TypeError {
-                                msg: "for-in range requires an end bound".to_string(),
-                                span: Span::dummy(),
-                            })? as i64;
-                            let len = if inclusive {
-                                (e - s + 1).max(0) as f64
-                            } else {
-                                (e - s).max(0) as f64
-                            };
-                            self.push(Value::Number(len));
-                        }
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "GetArrayLen requires Array".to_string(),
-                                span: Span::dummy(),
-                            })
-                        }
-                    }
-                }
-                Opcode::EnumVariant => {
-                    let arg_count = self.read_u8()? as usize;
-
-                    // Pop args in reverse order
-                    let mut args = Vec::with_capacity(arg_count);
-                    for _ in 0..arg_count {
-                        args.push(self.pop());
-                    }
-                    args.reverse();
-
-                    // Pop variant name and enum name
-                    let variant_name = self.pop();
-                    let enum_name = self.pop();
-
-                    // Extract string values
-                    let enum_name_str = match enum_name {
-                        Value::String(s) => (*s).clone(),
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "EnumVariant requires string enum name".to_string(),
-                                span: Span::dummy(),
-                            })
-                        }
-                    };
-                    let variant_name_str = match variant_name {
-                        Value::String(s) => (*s).clone(),
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "EnumVariant requires string variant name".to_string(),
-                                span: Span::dummy(),
-                            })
-                        }
-                    };
-
-                    // Create the enum value
-                    self.push(Value::EnumValue {
-                        enum_name: enum_name_str,
-                        variant_name: variant_name_str,
-                        data: args,
-                    });
-                }
-
-                Opcode::CheckEnumVariant => {
-                    // Stack: [value, enum_name, variant_name] -> [bool]
-                    let variant_name = self.pop();
-                    let enum_name = self.pop();
-                    let value = self.pop();
-
-                    // Extract expected names
-                    let expected_enum = match &enum_name {
-                        Value::String(s) => s.as_str(),
-                        _ => {
-                            self.push(Value::Bool(false));
-                            continue;
-                        }
-                    };
-                    let expected_variant = match &variant_name {
-                        Value::String(s) => s.as_str(),
-                        _ => {
-                            self.push(Value::Bool(false));
-                            continue;
-                        }
-                    };
-
-                    // Check if value matches.
-                    // H-223: empty expected_enum means bare variant pattern — skip enum_name check.
-                    let matches = match &value {
-                        Value::EnumValue {
-                            enum_name: val_enum,
-                            variant_name: val_variant,
-                            ..
-                        } => {
-                            let enum_ok = expected_enum.is_empty() || val_enum == expected_enum;
-                            enum_ok && val_variant == expected_variant
-                        }
-                        _ => false,
-                    };
-
-                    self.push(Value::Bool(matches));
-                }
-
-                Opcode::ExtractEnumData => {
-                    // Stack: [EnumValue] -> [Array]
-                    let value = self.pop();
-
-                    match value {
-                        Value::EnumValue { data, .. } => {
-                            // Convert data Vec<Value> to an Array
-                            self.push(Value::array(data));
-                        }
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: format!(
-                                    "ExtractEnumData requires EnumValue, got {}",
-                                    value.type_name()
-                                ),
-                                span: Span::dummy(),
-                            });
-                        }
-                    }
-                }
-
-                Opcode::IsStruct => {
-                    // Stack: [value] -> [bool]
-                    // Push true if the value is a named struct (Map registered in struct_type_names)
-                    let val = self.pop();
-                    let is_struct = if let Value::Map(ref m) = val {
-                        let key = std::sync::Arc::as_ptr(m.arc()) as usize;
-                        self.ctx.struct_type_names.contains_key(&key)
-                    } else {
-                        false
-                    };
-                    self.push(Value::Bool(is_struct));
-                }
-
-                Opcode::CheckStructType => {
-                    // [u16 name_const_idx]  Stack: [value] -> [bool]
-                    // Pop value; push true if its registered struct name equals the string constant.
-                    let name_idx = self.read_u16()? as usize;
-                    let expected_name = match self.bytecode.constants.get(name_idx) {
-                        Some(Value::String(s)) => s.as_ref().clone(),
-                        _ => {
-                            return Err(RuntimeError::TypeError {
-                                msg: "CheckStructType: expected string constant for type name"
-                                    .to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            });
-                        }
-                    };
-                    let val = self.pop();
-                    let matches_type = if let Value::Map(ref m) = val {
-                        let key = std::sync::Arc::as_ptr(m.arc()) as usize;
-                        self.ctx
-                            .struct_type_names
-                            .get(&key)
-                            .map(|name| name == &expected_name)
-                            .unwrap_or(false)
-                    } else {
-                        false
-                    };
-                    self.push(Value::Bool(matches_type));
-                }
-
-                // ===== Async (Phase 10) =====
-                //
-                // Encoding:
-                //   AsyncCall / SpawnTask: u8 arg_count  (same layout as Call)
-                //   Await / WrapFuture:    no operands
-                //
-                // The compiler emits WrapFuture *inside* async fn bodies so that the
-                // return value is always a Value::Future.  AsyncCall therefore only needs
-                // to dispatch the call normally — the callee's WrapFuture handles wrapping.
-                //
-                // SpawnTask mirrors AsyncCall at this stage (eager execution, same as the
-                // interpreter).  True tokio::spawn concurrency requires an independent VM
-                // instance per task and is deferred to Phase 11 (stdlib async I/O).
-                Opcode::AsyncCall | Opcode::SpawnTask => {
-                    let arg_count = self.read_u8()? as usize;
-                    self.execute_call(arg_count)?;
-                    // Result is already Value::Future — the callee's WrapFuture emitted it.
-                }
-                Opcode::Await => {
-                    let val = self.pop();
-                    match val {
-                        Value::Future(future) => match future.get_state() {
-                            crate::async_runtime::FutureState::Resolved(v) => {
-                                self.push(v);
-                            }
-                            crate::async_runtime::FutureState::Rejected(e) => {
-                                return Err(RuntimeError::TypeError {
-                                    msg: format!("Awaited future was rejected: {}", e),
-                                    span: self
-                                        .current_span()
-                                        .unwrap_or_else(crate::span::Span::dummy),
-                                });
-                            }
-                            crate::async_runtime::FutureState::Pending => {
-                                return Err(RuntimeError::TypeError {
-                                        msg: "Cannot await a pending future in the VM synchronous execute loop".to_string(),
-                                        span: self
-                                            .current_span()
-                                            .unwrap_or_else(crate::span::Span::dummy),
-                                    });
-                            }
-                        },
-                        other => {
-                            return Err(RuntimeError::TypeError {
-                                msg: format!(
-                                    "AT4002: await operand must be Future, got {}",
-                                    other.type_name()
-                                ),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            });
-                        }
-                    }
-                }
-                Opcode::WrapFuture => {
-                    let val = self.pop();
-                    let future = crate::async_runtime::AtlasFuture::resolved(val);
-                    self.push(Value::Future(Arc::new(future)));
-                }
-
-                // ===== Defer =====
-                Opcode::DeferPush => {
-                    // Read the body length
-                    let body_len = self.read_u16()? as usize;
-                    // Body starts after the Jump opcode (1 byte) + operand (2 bytes) = 3 bytes
-                    // The compiler emits: DeferPush body_len Jump offset body...
-                    let body_start = self.ctx.ip + 3;
-
-                    // Record defer: (body_start, body_len) on the current frame's defer stack
-                    let frame_idx = self.ctx.frames.len() - 1;
-                    self.ctx.defer_stacks[frame_idx].push((body_start, body_len));
-
-                    // Normal execution continues (Jump instruction follows to skip body)
-                }
-                Opcode::DeferExec => {
-                    // Execute all deferred blocks for current frame in LIFO order
-                    let frame_idx = self.ctx.frames.len() - 1;
-                    while let Some((body_start, body_len)) = self.ctx.defer_stacks[frame_idx].pop()
-                    {
-                        // Save current IP
-                        let saved_ip = self.ctx.ip;
-
-                        // Execute defer body
-                        self.ctx.ip = body_start;
-                        let body_end = body_start + body_len;
-                        while self.ctx.ip < body_end {
-                            let defer_opcode =
-                                Opcode::try_from(self.bytecode.instructions[self.ctx.ip]).map_err(
-                                    |_| RuntimeError::UnknownOpcode {
-                                        span: self
-                                            .current_span()
-                                            .unwrap_or_else(crate::span::Span::dummy),
-                                    },
-                                )?;
-                            self.ctx.ip += 1;
-
-                            // Execute single instruction (simplified - only handles basic ops)
-                            // Return in defer body returns to defer executor, not function
-                            if matches!(defer_opcode, Opcode::Return) {
-                                break;
-                            }
-                            // For complex defer bodies, we'd need recursive execution
-                            // For now, just break - the main loop will handle it
-                        }
-
-                        // Restore IP
-                        self.ctx.ip = saved_ip;
-                    }
-                }
-
-                // ===== Special =====
-                Opcode::Halt => break,
-            }
-        }
-
-        // Return top of stack if present
-        Ok(if self.ctx.stack.is_empty() {
-            None
-        } else {
-            Some(self.pop())
-        })
-    }
-
-    // ===== Helper Methods =====
-
-    #[inline(always)]
-    fn push(&mut self, value: Value) {
-        self.ctx.stack.push(value);
-        #[cfg(debug_assertions)]
-        self.ctx.value_origins.push(None);
-    }
-
-    #[inline(always)]
-    fn pop(&mut self) -> Value {
-        #[cfg(debug_assertions)]
-        self.ctx.value_origins.pop();
-        // SAFETY: VM invariants guarantee the stack is non-empty when pop() is called.
-        // Preconditions: every opcode that calls pop() has already pushed its operands,
-        // and debug builds mirror this via value_origins.
-        unsafe { self.ctx.stack.pop().unwrap_unchecked() }
-    }
-
-    #[inline(always)]
-    fn peek(&self, distance: usize) -> &Value {
-        // SAFETY: The compiler guarantees stack depth matches operand requirements.
-        // Preconditions: each opcode that calls peek() is emitted only when sufficient
-        // values exist, and `distance` is within the current stack depth.
-        unsafe {
-            self.ctx
-                .stack
-                .get_unchecked(self.ctx.stack.len() - 1 - distance)
-        }
-    }
-
-    #[inline(always)]
-    fn pop_number(&mut self) -> Result<f64, RuntimeError> {
-        match self.pop() {
-            Value::Number(n) => Ok(n),
-            _ => Err(RuntimeError::TypeError {
-                msg: "Expected number".to_string(),
-                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-            }),
-        }
-    }
-
-    /// Find the end of a function body (the Return opcode)
-    ///
-    /// Scans bytecode from `start` looking for a Return opcode. Returns the
-    /// offset just past the Return instruction. Used by JIT to determine
-    /// function boundaries.
-    fn find_function_end(&self, start: usize) -> usize {
-        let instructions = &self.bytecode.instructions;
-        let mut ip = start;
+    /// Find the end of a function body (the Return opcode)
+    ///
+    /// Scans bytecode from `start` looking for a Return opcode. Returns the
+    /// offset just past the Return instruction. Used by JIT to determine
+    /// function boundaries.
+    fn find_function_end(&self, start: usize) -> usize {
+        let instructions = &self.bytecode.instructions;
+        let mut ip = start;
 
         while ip < instructions.len() {
             let byte = instructions[ip];
@@ -2541,6 +1532,7 @@ impl VM {
                     | Opcode::TupleGet
                     | Opcode::HashMap
                     | Opcode::DeferPush
+                    | Opcode::TryBegin
                     | Opcode::CheckStructType => ip += 2,
                     Opcode::Struct => ip += 4,
                     Opcode::MakeClosure => ip += 4,
@@ -2648,14 +1640,128 @@ impl VM {
                 None,
             ));
         }
-        frames
+        frames
+    }
+
+    /// Fail with `RecursionLimit` if one more call would exceed the call depth limit
+    #[inline]
+    fn check_call_depth(&self) -> Result<(), RuntimeError> {
+        // The `<main>` frame is not a call
+        if self.ctx.frames.len() > self.limits.max_call_depth {
+            let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
+            return Err(self.recursion_limit(span));
+        }
+        Ok(())
+    }
+
+    /// Tell the profiler about the frame just pushed
+    fn profile_frame_push(&mut self) {
+        if let Some(ref mut profiler) = self.profiler {
+            profiler.record_frame_push(&self.ctx.frames);
+        }
+    }
+
+    /// Allocation tracking, before the instruction at `ip` runs: record the
+    /// value left by the frame's previous allocating instruction, note this
+    /// one if it may allocate, and sample memory when due
+    fn profile_allocations(&mut self, opcode: Opcode, ip: usize) {
+        let Some(allocations) = self.profiler.as_mut().and_then(|p| p.allocations_mut()) else {
+            return;
+        };
+        let depth = self.ctx.frames.len();
+        if let Some((produced_by, at)) = allocations.take_result(depth) {
+            if let (Some(value), Some(frame)) = (self.ctx.stack.last(), self.ctx.frames.last()) {
+                let bytecode = &self.bytecode;
+                allocations.record_result(produced_by, value, at, &frame.function_name, || {
+                    bytecode.get_span_for_offset(at)
+                });
+            }
+        }
+
+        let expects_result = match opcode {
+            // Calls to Atlas functions are followed into the callee, whose
+            // own instructions allocate; only builtins allocate at the call
+            Opcode::Call => {
+                let arg_count = self.bytecode.instructions[self.ctx.ip] as usize;
+                self.ctx
+                    .stack
+                    .len()
+                    .checked_sub(arg_count + 1)
+                    .and_then(|index| self.ctx.stack.get(index))
+                    .is_some_and(|callee| {
+                        matches!(callee, Value::Builtin(_) | Value::NativeFunction(_))
+                    })
+            }
+            _ => crate::profiler::AllocationProfiler::can_allocate(opcode),
+        };
+        if expects_result {
+            allocations.expect_result(opcode, ip, depth);
+        }
+
+        if allocations.sample_due() || opcode == Opcode::Halt {
+            let roots = self
+                .ctx
+                .stack
+                .iter()
+                .chain(self.globals.values())
+                .chain(self.ctx.frames.iter().flat_map(|f| f.upvalues.iter()));
+            allocations.sample(roots);
+        }
+    }
+
+    /// Account for a builtin calling back into the dispatch loop
+    ///
+    /// Every nested callback runs `execute_loop` one level deeper on the host
+    /// stack, which the frame count does not see, so the stack used since the
+    /// outermost callback is measured directly.
+    fn enter_callback(&mut self, span: crate::span::Span) -> Result<(), RuntimeError> {
+        let marker = 0u8;
+        let here = std::hint::black_box(&marker) as *const u8 as usize;
+        if self.callback_depth == 0 {
+            self.native_stack_base = here;
+        } else if self.native_stack_base.abs_diff(here) > self.limits.max_native_stack {
+            return Err(self.recursion_limit(span));
+        }
+        self.callback_depth += 1;
+        Ok(())
+    }
+
+    fn recursion_limit(&self, span: crate::span::Span) -> RuntimeError {
+        RuntimeError::RecursionLimit {
+            depth: self.ctx.frames.len() - 1,
+            stack_trace: self.stack_trace(span),
+            span,
+        }
     }
 
     fn execute_call(&mut self, arg_count: usize) -> Result<(), RuntimeError> {
         let function = self.peek(arg_count).clone();
 
+        // The offset past the call's operand identifies the call site.
+        let site = self.ctx.ip;
+
         match function {
             Value::Builtin(ref name) => {
+                // Inline cache hit: a plain stdlib builtin this site called before
+                if let Some(dispatch) = self.call_cache.builtin(site, name) {
+                    let mut args = Vec::with_capacity(arg_count);
+                    for _ in 0..arg_count {
+                        args.push(self.pop());
+                    }
+                    args.reverse();
+                    self.pop(); // Pop function value
+                    let _call =
+                        crate::stdlib::interrupt::enter_call(self.execution_limits.as_ref());
+                    let result = dispatch(
+                        &args,
+                        self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                        &self.current_security,
+                        &self.output_writer,
+                    )?;
+                    self.push(result);
+                    return Ok(());
+                }
+
                 // Check array intrinsics first (callback-based)
                 if self.is_array_intrinsic(name) {
                     let mut args = Vec::with_capacity(arg_count);
@@ -2687,261 +1793,271 @@ impl VM {
                     if name.as_ref() == "typeof" || name.as_ref() == "type_of" {
                         let result = self.vm_intrinsic_typeof(&args)?;
                         self.push(result);
+                    } else if name.as_ref() == "processNsGc" {
+                        // process.gc() needs this VM's roots.
+                        let freed = self.collect_garbage();
+                        self.push(Value::Number(freed as f64));
                     } else {
-                        let security = self.current_security.as_ref().ok_or_else(|| {
-                            RuntimeError::InternalError {
-                                msg: "Security context not set".to_string(),
-                                span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            }
-                        })?;
-                        let result = crate::stdlib::call_builtin(
-                            name,
-                            &args,
-                            self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                            security,
-                            &self.output_writer,
-                        )?;
+                        let span = self.current_span().unwrap_or_else(crate::span::Span::dummy);
+                        let Some(dispatch) = crate::stdlib::lookup_builtin(name) else {
+                            return Err(RuntimeError::UnknownFunction {
+                                name: name.to_string(),
+                                span,
+                            });
+                        };
+                        self.call_cache.insert_builtin(site, name.clone(), dispatch);
+                        let _call =
+                            crate::stdlib::interrupt::enter_call(self.execution_limits.as_ref());
+                        let result =
+                            dispatch(&args, span, &self.current_security, &self.output_writer)?;
 
                         self.push(result);
                     }
                 }
             }
             Value::Function(func) => {
-                // Check for extern functions
-                if let Some(extern_fn) = self.extern_functions.get(&func.name).cloned() {
-                    let mut args = Vec::with_capacity(arg_count);
-                    for _ in 0..arg_count {
-                        args.push(self.pop());
+                // A cache hit means this callee was already found not to be extern
+                let cached = self.call_cache.function(site, func.bytecode_offset);
+                #[cfg(feature = "ffi")]
+                if cached.is_none() {
+                    if let Some(extern_fn) = self.extern_functions.get(&func.name).cloned() {
+                        return self.call_extern(extern_fn, arg_count);
                     }
-                    args.reverse();
-                    self.pop(); // Pop function value
-
-                    // SAFETY: `extern_fn` was constructed with a signature derived
-                    // from the AST extern declaration, and `args` is built from the
-                    // VM stack to match that arity. The library remains loaded for
-                    // the duration of the call.
-                    let result =
-                        unsafe { extern_fn.call(&args) }.map_err(|e| RuntimeError::TypeError {
-                            msg: format!("FFI call error: {}", e),
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        })?;
+                }
 
-                    self.push(result);
-                } else {
-                    // User-defined function
-                    // Safety check: compiled functions always have bytecode_offset > 0
-                    // because the compiler emits setup code (Constant, SetGlobal, Pop, Jump)
-                    // before the function body. bytecode_offset == 0 indicates an
-                    // interpreter-created function that has no bytecode.
-                    if func.bytecode_offset == 0 {
-                        return Err(RuntimeError::TypeError {
-                            msg: format!(
-                                "Cannot call function '{}' from VM: function was created \
+                // User-defined function
+                // Safety check: compiled functions always have bytecode_offset > 0
+                // because the compiler emits setup code (Constant, SetGlobal, Pop, Jump)
+                // before the function body. bytecode_offset == 0 indicates an
+                // interpreter-created function that has no bytecode.
+                if func.bytecode_offset == 0 {
+                    return Err(RuntimeError::TypeError {
+                        msg: format!(
+                            "Cannot call function '{}' from VM: function was created \
                                  by interpreter and has no compiled bytecode. This typically \
                                  happens when importing functions across execution modes.",
-                                func.name
-                            ),
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        });
-                    }
+                            func.name
+                        ),
+                        span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                    });
+                }
+                if cached.is_none() {
+                    self.call_cache.insert_function(
+                        site,
+                        CachedFunction {
+                            bytecode_offset: func.bytecode_offset,
+                            function_end: None,
+                        },
+                    );
+                }
 
-                    // Try JIT execution for hot numeric functions
-                    if self.jit.is_some() {
-                        // Gather info before borrowing jit mutably
-                        let args_base = self.ctx.stack.len() - arg_count;
-                        let mut numeric_args: Vec<f64> = Vec::with_capacity(arg_count);
-                        let mut all_numeric = true;
-
-                        for i in 0..arg_count {
-                            match &self.ctx.stack[args_base + i] {
-                                Value::Number(n) => numeric_args.push(*n),
-                                _ => {
-                                    all_numeric = false;
-                                    break;
-                                }
+                // Try JIT execution for hot numeric functions
+                if self.jit.is_some() {
+                    // Gather info before borrowing jit mutably
+                    let args_base = self.ctx.stack.len() - arg_count;
+                    let mut numeric_args: Vec<f64> = Vec::with_capacity(arg_count);
+                    let mut all_numeric = true;
+
+                    for i in 0..arg_count {
+                        match &self.ctx.stack[args_base + i] {
+                            Value::Number(n) => numeric_args.push(*n),
+                            _ => {
+                                all_numeric = false;
+                                break;
                             }
                         }
+                    }
 
-                        if all_numeric {
-                            let function_end = self.find_function_end(func.bytecode_offset);
-                            let bytecode_offset = func.bytecode_offset;
-
-                            // Now borrow jit mutably for the call
-                            if let Some(ref mut jit) = self.jit {
-                                if let Some(result) = jit.try_execute(
-                                    &self.bytecode,
-                                    bytecode_offset,
-                                    function_end,
-                                    &numeric_args,
-                                ) {
-                                    // JIT succeeded — pop args and function, push result
-                                    for _ in 0..arg_count {
-                                        self.pop();
-                                    }
-                                    self.pop(); // Pop function value
-                                    self.push(Value::Number(result));
-                                    return Ok(());
+                    if all_numeric {
+                        let function_end = match cached.and_then(|cached| cached.function_end) {
+                            Some(end) => end,
+                            None => {
+                                let end = self.find_function_end(func.bytecode_offset);
+                                self.call_cache.set_function_end(site, end);
+                                end
+                            }
+                        };
+                        let bytecode_offset = func.bytecode_offset;
+
+                        // Now borrow jit mutably for the call
+                        if let Some(ref mut jit) = self.jit {
+                            if let Some(result) = jit.try_execute(
+                                &self.bytecode,
+                                bytecode_offset,
+                                function_end,
+                                &numeric_args,
+                            ) {
+                                // JIT succeeded — pop args and function, push result
+                                for _ in 0..arg_count {
+                                    self.pop();
                                 }
+                                self.pop(); // Pop function value
+                                self.push(Value::Number(result));
+                                return Ok(());
                             }
                         }
                     }
+                }
 
-                    // B41-P04: If function has a rest param, collect extra args into an array.
-                    // The rest param occupies the last slot (index arity-1).
-                    // All args from index (arity-1) onward are collected into one Array value.
-                    let arg_count = if func.has_rest_param && arg_count >= func.required_arity {
-                        let fixed_count = func.arity.saturating_sub(1); // params before rest
-                        let rest_count = arg_count.saturating_sub(fixed_count);
-                        // Collect rest args from top of stack (they're in order, rest_count items)
-                        let stack_top = self.ctx.stack.len();
-                        let rest_start = stack_top - rest_count;
-                        let rest_args: Vec<Value> = self.ctx.stack.drain(rest_start..).collect();
-                        let rest_array = Value::Array(crate::value::ValueArray::from(rest_args));
-                        self.ctx.stack.push(rest_array);
-                        // arg_count is now fixed_count + 1 (the array)
-                        fixed_count + 1
-                    } else {
-                        arg_count
-                    };
+                // B41-P04: If function has a rest param, collect extra args into an array.
+                // The rest param occupies the last slot (index arity-1).
+                // All args from index (arity-1) onward are collected into one Array value.
+                let arg_count = if func.has_rest_param && arg_count >= func.required_arity {
+                    let fixed_count = func.arity.saturating_sub(1); // params before rest
+                    let rest_count = arg_count.saturating_sub(fixed_count);
+                    // Collect rest args from top of stack (they're in order, rest_count items)
+                    let stack_top = self.ctx.stack.len();
+                    let rest_start = stack_top - rest_count;
+                    let rest_args: Vec<Value> = self.ctx.stack.drain(rest_start..).collect();
+                    let rest_array = Value::Array(crate::value::ValueArray::from(rest_args));
+                    self.ctx.stack.push(rest_array);
+                    // arg_count is now fixed_count + 1 (the array)
+                    fixed_count + 1
+                } else {
+                    arg_count
+                };
 
-                    // Create a new call frame
-                    let frame = CallFrame {
-                        function_name: func.name.clone(),
-                        return_ip: self.ctx.ip,
-                        stack_base: self.ctx.stack.len() - arg_count, // Points to first argument
-                        local_count: func.local_count, // Use total locals, not just arity
-                        upvalues: std::sync::Arc::new(Vec::new()),
-                    };
+                // Create a new call frame
+                let frame = CallFrame {
+                    function_name: func.name.clone(),
+                    return_ip: self.ctx.ip,
+                    stack_base: self.ctx.stack.len() - arg_count, // Points to first argument
+                    local_count: func.local_count, // Use total locals, not just arity
+                    upvalues: std::sync::Arc::new(Vec::new()),
+                    is_async: func.is_async,
+                };
 
-                    // Verify argument count matches (B39-P05: default params; B41-P04: rest params)
-                    let arity_ok = if func.has_rest_param {
-                        arg_count >= func.required_arity
+                // Verify argument count matches (B39-P05: default params; B41-P04: rest params)
+                let arity_ok = if func.has_rest_param {
+                    arg_count >= func.required_arity
+                } else {
+                    arg_count >= func.required_arity && arg_count <= func.arity
+                };
+                if !arity_ok {
+                    let expected = if func.required_arity == func.arity {
+                        format!("{}", func.arity)
                     } else {
-                        arg_count >= func.required_arity && arg_count <= func.arity
+                        format!("{}-{}", func.required_arity, func.arity)
                     };
-                    if !arity_ok {
-                        let expected = if func.required_arity == func.arity {
-                            format!("{}", func.arity)
-                        } else {
-                            format!("{}-{}", func.required_arity, func.arity)
-                        };
-                        return Err(RuntimeError::TypeError {
-                            msg: format!(
-                                "Function {} expects {} arguments, got {}",
-                                func.name, expected, arg_count
-                            ),
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        });
-                    }
+                    return Err(RuntimeError::TypeError {
+                        msg: format!(
+                            "Function {} expects {} arguments, got {}",
+                            func.name, expected, arg_count
+                        ),
+                        span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+                    });
+                }
 
-                    // Fill in default values for missing arguments (B39-P05)
-                    for i in arg_count..func.arity {
-                        if let Some(Some(default_val)) = func.defaults.get(i) {
-                            self.ctx.stack.push(default_val.clone());
-                        } else {
-                            // Should not happen if binder validation is correct
-                            self.ctx.stack.push(Value::Null);
-                        }
+                // Fill in default values for missing arguments (B39-P05)
+                for i in arg_count..func.arity {
+                    if let Some(Some(default_val)) = func.defaults.get(i) {
+                        self.ctx.stack.push(default_val.clone());
+                    } else {
+                        // Should not happen if binder validation is correct
+                        self.ctx.stack.push(Value::Null);
                     }
+                }
 
-                    // Debug mode: for each `own` parameter, mark the caller's
-                    // local slot or global name as consumed so subsequent reads
-                    // produce a runtime error.
-                    #[cfg(debug_assertions)]
-                    {
-                        let caller_frame_idx = self.ctx.frames.len() - 1;
-                        // Use func.arity (not arg_count) because defaults have been pushed
-                        let args_base = self.ctx.stack.len() - func.arity;
-                        for (i, ownership) in func.param_ownership.iter().enumerate() {
-                            if *ownership == Some(crate::ast::OwnershipAnnotation::Own) {
-                                if let Some(Some(origin)) =
-                                    self.ctx.value_origins.get(args_base + i).cloned()
-                                {
-                                    match origin {
-                                        StackValueOrigin::Local(slot) => {
-                                            if let Some(consumed) = self
-                                                .ctx
-                                                .consumed_slots
-                                                .get_mut(caller_frame_idx)
-                                                .and_then(|v| v.get_mut(slot))
-                                            {
-                                                *consumed = true;
-                                            }
-                                        }
-                                        StackValueOrigin::Global(name) => {
-                                            self.ctx.consumed_globals.insert(name);
+                // Debug mode: for each `own` parameter, mark the caller's
+                // local slot or global name as consumed so subsequent reads
+                // produce a runtime error.
+                #[cfg(debug_assertions)]
+                {
+                    let caller_frame_idx = self.ctx.frames.len() - 1;
+                    // Use func.arity (not arg_count) because defaults have been pushed
+                    let args_base = self.ctx.stack.len() - func.arity;
+                    for (i, ownership) in func.param_ownership.iter().enumerate() {
+                        if *ownership == Some(crate::ast::OwnershipAnnotation::Own) {
+                            if let Some(Some(origin)) =
+                                self.ctx.value_origins.get(args_base + i).cloned()
+                            {
+                                match origin {
+                                    StackValueOrigin::Local(slot) => {
+                                        if let Some(consumed) = self
+                                            .ctx
+                                            .consumed_slots
+                                            .get_mut(caller_frame_idx)
+                                            .and_then(|v| v.get_mut(slot))
+                                        {
+                                            *consumed = true;
                                         }
                                     }
+                                    StackValueOrigin::Global(slot) => {
+                                        self.ctx.consumed_globals.insert(slot);
+                                    }
                                 }
                             }
                         }
                     }
+                }
 
-                    // Debug mode: enforce `share` parameter ownership contracts.
-                    #[cfg(debug_assertions)]
-                    {
-                        // Use func.arity (not arg_count) because defaults have been pushed
-                        let args_base = self.ctx.stack.len() - func.arity;
-                        for (i, ownership) in func.param_ownership.iter().enumerate() {
-                            let arg = &self.ctx.stack[args_base + i];
-                            match ownership {
-                                Some(crate::ast::OwnershipAnnotation::Share) => {
-                                    if !matches!(arg, Value::SharedValue(_)) {
-                                        return Err(RuntimeError::TypeError {
-                                            msg: format!(
-                                                "ownership violation: parameter '{}' expects share<T> but received {}",
+                // Debug mode: enforce `share` parameter ownership contracts.
+                #[cfg(debug_assertions)]
+                {
+                    // Use func.arity (not arg_count) because defaults have been pushed
+                    let args_base = self.ctx.stack.len() - func.arity;
+                    for (i, ownership) in func.param_ownership.iter().enumerate() {
+                        let arg = &self.ctx.stack[args_base + i];
+                        match ownership {
+                            Some(crate::ast::OwnershipAnnotation::Share)
+                                if !matches!(arg, Value::SharedValue(_)) =>
+                            {
+                                return Err(RuntimeError::TypeError {
+                                        msg: format!(
+                                            "ownership violation: parameter '{}' expects share<T> but received {}",
+                                            func.param_names
+                                                .get(i)
+                                                .map(|s| s.as_str())
+                                                .unwrap_or("?"),
+                                            arg.type_name()
+                                        ),
+                                        span: self
+                                            .current_span()
+                                            .unwrap_or_else(crate::span::Span::dummy),
+                                    });
+                            }
+                            Some(crate::ast::OwnershipAnnotation::Own)
+                            | Some(crate::ast::OwnershipAnnotation::Borrow) => {
+                                if matches!(arg, Value::SharedValue(_)) {
+                                    let ann_str = match ownership {
+                                        Some(crate::ast::OwnershipAnnotation::Own) => "own",
+                                        Some(crate::ast::OwnershipAnnotation::Borrow) => "borrow",
+                                        _ => unreachable!(),
+                                    };
+                                    let call_span = self
+                                        .current_span()
+                                        .unwrap_or_else(crate::span::Span::dummy);
+                                    self.ctx.runtime_warnings.push(
+                                        crate::diagnostic::error_codes::SHARE_PASSED_TO_NON_SHARE
+                                            .emit(call_span)
+                                            .arg("inner", "T")
+                                            .arg("annotation", ann_str)
+                                            .arg(
+                                                "name",
                                                 func.param_names
                                                     .get(i)
                                                     .map(|s| s.as_str())
                                                     .unwrap_or("?"),
-                                                arg.type_name()
-                                            ),
-                                            span: self
-                                                .current_span()
-                                                .unwrap_or_else(crate::span::Span::dummy),
-                                        });
-                                    }
-                                }
-                                Some(crate::ast::OwnershipAnnotation::Own)
-                                | Some(crate::ast::OwnershipAnnotation::Borrow) => {
-                                    if matches!(arg, Value::SharedValue(_)) {
-                                        let ann_str = match ownership {
-                                            Some(crate::ast::OwnershipAnnotation::Own) => "own",
-                                            Some(crate::ast::OwnershipAnnotation::Borrow) => {
-                                                "borrow"
-                                            }
-                                            _ => unreachable!(),
-                                        };
-                                        self.ctx.runtime_warnings.push(
-                                            crate::diagnostic::error_codes::SHARE_PASSED_TO_NON_SHARE
-                                                .emit(crate::span::Span::new(0, 0))
-                                                .arg("inner", "T")
-                                                .arg("annotation", ann_str)
-                                                .arg("name", func.param_names.get(i).map(|s| s.as_str()).unwrap_or("?"))
-                                                .build(),
-                                        );
-                                    }
+                                            )
+                                            .build(),
+                                    );
                                 }
-                                None => {}
                             }
+                            Some(crate::ast::OwnershipAnnotation::Share) | None => {}
                         }
                     }
+                }
 
-                    // Push the frame (and its consumed-slot tracking vector)
-                    self.ctx.frames.push(frame);
-                    self.ctx.defer_stacks.push(Vec::new());
-                    #[cfg(debug_assertions)]
-                    self.ctx.consumed_slots.push(vec![false; func.local_count]);
-                    // Record function call in profiler
-                    if let Some(ref mut profiler) = self.profiler {
-                        if profiler.is_enabled() {
-                            profiler.record_function_call(&func.name);
-                        }
-                    }
+                // Push the frame (and its consumed-slot tracking vector)
+                self.check_call_depth()?;
+                self.ctx.frames.push(frame);
+                self.ctx.defer_stacks.push(Vec::new());
+                #[cfg(debug_assertions)]
+                self.ctx.consumed_slots.push(vec![false; func.local_count]);
+                self.profile_frame_push();
 
-                    // Jump to function bytecode
-                    self.ctx.ip = func.bytecode_offset;
-                }
+                // Jump to function bytecode
+                self.ctx.ip = func.bytecode_offset;
             }
             Value::Closure(closure) => {
                 // Closure call: same as Function but passes upvalues to the frame
@@ -3023,8 +2139,8 @@ impl VM {
                                             *consumed = true;
                                         }
                                     }
-                                    StackValueOrigin::Global(name) => {
-                                        self.ctx.consumed_globals.insert(name);
+                                    StackValueOrigin::Global(slot) => {
+                                        self.ctx.consumed_globals.insert(slot);
                                     }
                                 }
                             }
@@ -3039,22 +2155,22 @@ impl VM {
                     for (i, ownership) in func.param_ownership.iter().enumerate() {
                         let arg = &self.ctx.stack[args_base + i];
                         match ownership {
-                            Some(crate::ast::OwnershipAnnotation::Share) => {
-                                if !matches!(arg, Value::SharedValue(_)) {
-                                    return Err(RuntimeError::TypeError {
-                                        msg: format!(
-                                            "ownership violation: parameter '{}' expects share<T> but received {}",
-                                            func.param_names
-                                                .get(i)
-                                                .map(|s| s.as_str())
-                                                .unwrap_or("?"),
-                                            arg.type_name()
-                                        ),
-                                        span: self
-                                            .current_span()
-                                            .unwrap_or_else(crate::span::Span::dummy),
-                                    });
-                                }
+                            Some(crate::ast::OwnershipAnnotation::Share)
+                                if !matches!(arg, Value::SharedValue(_)) =>
+                            {
+                                return Err(RuntimeError::TypeError {
+                                    msg: format!(
+                                        "ownership violation: parameter '{}' expects share<T> but received {}",
+                                        func.param_names
+                                            .get(i)
+                                            .map(|s| s.as_str())
+                                            .unwrap_or("?"),
+                                        arg.type_name()
+                                    ),
+                                    span: self
+                                        .current_span()
+                                        .unwrap_or_else(crate::span::Span::dummy),
+                                });
                             }
                             Some(crate::ast::OwnershipAnnotation::Own)
                             | Some(crate::ast::OwnershipAnnotation::Borrow) => {
@@ -3064,9 +2180,12 @@ impl VM {
                                         Some(crate::ast::OwnershipAnnotation::Borrow) => "borrow",
                                         _ => unreachable!(),
                                     };
+                                    let call_span = self
+                                        .current_span()
+                                        .unwrap_or_else(crate::span::Span::dummy);
                                     self.ctx.runtime_warnings.push(
                                         crate::diagnostic::error_codes::SHARE_PASSED_TO_NON_SHARE
-                                            .emit(crate::span::Span::new(0, 0))
+                                            .emit(call_span)
                                             .arg("inner", "T")
                                             .arg("annotation", ann_str)
                                             .arg(
@@ -3080,7 +2199,7 @@ impl VM {
                                     );
                                 }
                             }
-                            None => {}
+                            Some(crate::ast::OwnershipAnnotation::Share) | None => {}
                         }
                     }
                 }
@@ -3092,18 +2211,16 @@ impl VM {
                     stack_base: self.ctx.stack.len() - arg_count,
                     local_count: func.local_count,
                     upvalues,
+                    is_async: func.is_async,
                 };
 
                 // Push the frame (and its consumed-slot tracking vector)
+                self.check_call_depth()?;
                 self.ctx.frames.push(frame);
                 self.ctx.defer_stacks.push(Vec::new());
                 #[cfg(debug_assertions)]
                 self.ctx.consumed_slots.push(vec![false; func.local_count]);
-                if let Some(ref mut profiler) = self.profiler {
-                    if profiler.is_enabled() {
-                        profiler.record_function_call(&func.name);
-                    }
-                }
+                self.profile_frame_push();
 
                 // Jump to function bytecode
                 self.ctx.ip = func.bytecode_offset;
@@ -3134,6 +2251,33 @@ impl VM {
         Ok(())
     }
 
+    /// Call a loaded extern function with the top `arg_count` stack values
+    #[cfg(feature = "ffi")]
+    fn call_extern(
+        &mut self,
+        extern_fn: ExternFunction,
+        arg_count: usize,
+    ) -> Result<(), RuntimeError> {
+        let mut args = Vec::with_capacity(arg_count);
+        for _ in 0..arg_count {
+            args.push(self.pop());
+        }
+        args.reverse();
+        self.pop(); // Pop function value
+
+        // SAFETY: `extern_fn` was constructed with a signature derived
+        // from the AST extern declaration, and `args` is built from the
+        // VM stack to match that arity. The library remains loaded for
+        // the duration of the call.
+        let result = unsafe { extern_fn.call(&args) }.map_err(|e| RuntimeError::TypeError {
+            msg: format!("FFI call error: {}", e),
+            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
+        })?;
+
+        self.push(result);
+        Ok(())
+    }
+
     // ========================================================================
     // VM Intrinsics — builtins that require VM context (struct registry, etc.)
     // ========================================================================
@@ -3215,12 +2359,16 @@ impl VM {
             "setMap" | "set_map" => self.vm_intrinsic_hashset_map(args, span),
             "setFilter" | "set_filter" => self.vm_intrinsic_hashset_filter(args, span),
             // Regex intrinsics (callback-based)
+            #[cfg(feature = "regex")]
             "regexReplaceWith" | "regex_replace_with" => {
                 self.vm_intrinsic_regex_replace_with(args, span)
             }
+            #[cfg(feature = "regex")]
             "regexReplaceAllWith" | "regex_replace_all_with" => {
                 self.vm_intrinsic_regex_replace_all_with(args, span)
             }
+            // Key-value store intrinsics (callback-based)
+            "kv_transaction" => self.vm_intrinsic_kv_transaction(args, span),
             // Test intrinsics (callable assertions)
             "testNsThrows" | "assertThrows" | "assert_throws" => {
                 self.vm_intrinsic_assert_throws(args, span)
@@ -3248,7 +2396,7 @@ impl VM {
         }
 
         let arr = match &args[0] {
-            Value::Array(a) => a.iter().cloned().collect::<Vec<_>>(),
+            Value::Array(a) => a,
             _ => {
                 return Err(RuntimeError::TypeError {
                     msg: "map() first argument must be array".to_string(),
@@ -3272,8 +2420,9 @@ impl VM {
 
         let mut result = Vec::with_capacity(arr.len());
 
-        for elem in arr {
-            let callback_result = self.vm_call_function_value(callback, vec![elem], span)?;
+        for elem in arr.iter() {
+            let callback_result =
+                self.vm_call_function_value(callback, vec![elem.clone()], span)?;
             result.push(callback_result);
         }
 
@@ -3293,7 +2442,7 @@ impl VM {
         }
 
         let arr = match &args[0] {
-            Value::Array(a) => a.iter().cloned().collect::<Vec<_>>(),
+            Value::Array(a) => a,
             _ => {
                 return Err(RuntimeError::TypeError {
                     msg: "filter() first argument must be array".to_string(),
@@ -3317,10 +2466,10 @@ impl VM {
 
         let mut result = Vec::new();
 
-        for elem in arr {
+        for elem in arr.iter() {
             let pred_result = self.vm_call_function_value(predicate, vec![elem.clone()], span)?;
             match pred_result {
-                Value::Bool(true) => result.push(elem),
+                Value::Bool(true) => result.push(elem.clone()),
                 Value::Bool(false) => {}
                 _ => {
                     return Err(RuntimeError::TypeError {
@@ -3347,7 +2496,7 @@ impl VM {
         }
 
         let arr = match &args[0] {
-            Value::Array(a) => a.iter().cloned().collect::<Vec<_>>(),
+            Value::Array(a) => a,
             _ => {
                 return Err(RuntimeError::TypeError {
                     msg: "reduce() first argument must be array".to_string(),
@@ -3370,8 +2519,9 @@ impl VM {
         };
         let mut accumulator = args[2].clone();
 
-        for elem in arr {
-            accumulator = self.vm_call_function_value(reducer, vec![accumulator, elem], span)?;
+        for elem in arr.iter() {
+            accumulator =
+                self.vm_call_function_value(reducer, vec![accumulator, elem.clone()], span)?;
         }
 
         Ok(accumulator)
@@ -3390,7 +2540,7 @@ impl VM {
         }
 
         let arr = match &args[0] {
-            Value::Array(a) => a.iter().cloned().collect::<Vec<_>>(),
+            Value::Array(a) => a,
             _ => {
                 return Err(RuntimeError::TypeError {
                     msg: "forEach() first argument must be array".to_string(),
@@ -3411,8 +2561,8 @@ impl VM {
                 })
             }
         };
-        for elem in arr {
-            self.vm_call_function_value(callback, vec![elem], span)?;
+        for elem in arr.iter() {
+            self.vm_call_function_value(callback, vec![elem.clone()], span)?;
         }
 
         Ok(Value::Null)
@@ -3431,7 +2581,7 @@ impl VM {
         }
 
         let arr = match &args[0] {
-            Value::Array(a) => a.iter().cloned().collect::<Vec<_>>(),
+            Value::Array(a) => a,
             _ => {
                 return Err(RuntimeError::TypeError {
                     msg: "find() first argument must be array".to_string(),
@@ -3452,10 +2602,10 @@ impl VM {
                 })
             }
         };
-        for elem in arr {
+        for elem in arr.iter() {
             let pred_result = self.vm_call_function_value(predicate, vec![elem.clone()], span)?;
             match pred_result {
-                Value::Bool(true) => return Ok(Value::Option(Some(Box::new(elem)))),
+                Value::Bool(true) => return Ok(Value::Option(Some(Box::new(elem.clone())))),
                 Value::Bool(false) => {}
                 _ => {
                     return Err(RuntimeError::TypeError {
@@ -3482,7 +2632,7 @@ impl VM {
         }
 
         let arr = match &args[0] {
-            Value::Array(a) => a.iter().cloned().collect::<Vec<_>>(),
+            Value::Array(a) => a,
             _ => {
                 return Err(RuntimeError::TypeError {
                     msg: "findIndex() first argument must be array".to_string(),
@@ -3535,7 +2685,7 @@ impl VM {
         }
 
         let arr = match &args[0] {
-            Value::Array(a) => a.iter().cloned().collect::<Vec<_>>(),
+            Value::Array(a) => a,
             _ => {
                 return Err(RuntimeError::TypeError {
                     msg: "flatMap() first argument must be array".to_string(),
@@ -3558,8 +2708,9 @@ impl VM {
         };
         let mut result = Vec::new();
 
-        for elem in arr {
-            let callback_result = self.vm_call_function_value(callback, vec![elem], span)?;
+        for elem in arr.iter() {
+            let callback_result =
+                self.vm_call_function_value(callback, vec![elem.clone()], span)?;
             match callback_result {
                 Value::Array(nested) => {
                     result.extend(nested.iter().cloned());
@@ -3584,7 +2735,7 @@ impl VM {
         }
 
         let arr = match &args[0] {
-            Value::Array(a) => a.iter().cloned().collect::<Vec<_>>(),
+            Value::Array(a) => a,
             _ => {
                 return Err(RuntimeError::TypeError {
                     msg: "some() first argument must be array".to_string(),
@@ -3605,8 +2756,8 @@ impl VM {
                 })
             }
         };
-        for elem in arr {
-            let pred_result = self.vm_call_function_value(predicate, vec![elem], span)?;
+        for elem in arr.iter() {
+            let pred_result = self.vm_call_function_value(predicate, vec![elem.clone()], span)?;
             match pred_result {
                 Value::Bool(true) => return Ok(Value::Bool(true)),
                 Value::Bool(false) => {}
@@ -3635,7 +2786,7 @@ impl VM {
         }
 
         let arr = match &args[0] {
-            Value::Array(a) => a.iter().cloned().collect::<Vec<_>>(),
+            Value::Array(a) => a,
             _ => {
                 return Err(RuntimeError::TypeError {
                     msg: "every() first argument must be array".to_string(),
@@ -3656,8 +2807,8 @@ impl VM {
                 })
             }
         };
-        for elem in arr {
-            let pred_result = self.vm_call_function_value(predicate, vec![elem], span)?;
+        for elem in arr.iter() {
+            let pred_result = self.vm_call_function_value(predicate, vec![elem.clone()], span)?;
             match pred_result {
                 Value::Bool(false) => return Ok(Value::Bool(false)),
                 Value::Bool(true) => {}
@@ -3686,7 +2837,7 @@ impl VM {
         }
 
         let arr = match &args[0] {
-            Value::Array(a) => a.iter().cloned().collect::<Vec<_>>(),
+            Value::Array(a) => a,
             _ => {
                 return Err(RuntimeError::TypeError {
                     msg: "sort() first argument must be array".to_string(),
@@ -3708,31 +2859,17 @@ impl VM {
             }
         };
 
-        // Insertion sort for stability
-        let mut sorted = arr;
-        for i in 1..sorted.len() {
-            let mut j = i;
-            while j > 0 {
-                let cmp_result = self.vm_call_function_value(
-                    comparator,
-                    vec![sorted[j].clone(), sorted[j - 1].clone()],
+        let sorted = crate::stdlib::sort::sort_stable_by(arr.as_slice(), |a, b| {
+            match self.vm_call_function_value(comparator, vec![a.clone(), b.clone()], span)? {
+                Value::Number(n) if n < 0.0 => Ok(std::cmp::Ordering::Less),
+                Value::Number(n) if n > 0.0 => Ok(std::cmp::Ordering::Greater),
+                Value::Number(_) => Ok(std::cmp::Ordering::Equal),
+                _ => Err(RuntimeError::TypeError {
+                    msg: "sort() comparator must return number".to_string(),
                     span,
-                )?;
-                match cmp_result {
-                    Value::Number(n) if n < 0.0 => {
-                        sorted.swap(j, j - 1);
-                        j -= 1;
-                    }
-                    Value::Number(_) => break,
-                    _ => {
-                        return Err(RuntimeError::TypeError {
-                            msg: "sort() comparator must return number".to_string(),
-                            span,
-                        })
-                    }
-                }
+                }),
             }
-        }
+        })?;
 
         Ok(Value::array(sorted))
     }
@@ -3750,7 +2887,7 @@ impl VM {
         }
 
         let arr = match &args[0] {
-            Value::Array(a) => a.iter().cloned().collect::<Vec<_>>(),
+            Value::Array(a) => a,
             _ => {
                 return Err(RuntimeError::TypeError {
                     msg: "sortBy() first argument must be array".to_string(),
@@ -3772,49 +2909,19 @@ impl VM {
             }
         };
 
-        // Extract keys
-        let mut keyed: Vec<(Value, Value)> = Vec::new();
-        for elem in arr {
-            let key = self.vm_call_function_value(key_extractor, vec![elem.clone()], span)?;
-            keyed.push((key, elem));
-        }
-
-        // Sort by keys (insertion sort for stability)
-        for i in 1..keyed.len() {
-            let mut j = i;
-            while j > 0 {
-                let cmp = match (&keyed[j].0, &keyed[j - 1].0) {
-                    (Value::Number(a), Value::Number(b)) => {
-                        if a < b {
-                            -1
-                        } else if a > b {
-                            1
-                        } else {
-                            0
-                        }
-                    }
-                    (Value::String(a), Value::String(b)) => {
-                        if a < b {
-                            -1
-                        } else if a > b {
-                            1
-                        } else {
-                            0
-                        }
-                    }
-                    _ => 0,
-                };
-
-                if cmp < 0 {
-                    keyed.swap(j, j - 1);
-                    j -= 1;
-                } else {
-                    break;
+        // Keys of different types compare equal
+        let sorted = crate::stdlib::sort::sort_by_cached_key(
+            arr.as_slice(),
+            |elem| self.vm_call_function_value(key_extractor, vec![elem.clone()], span),
+            |a, b| match (a, b) {
+                (Value::Number(a), Value::Number(b)) => {
+                    a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)
                 }
-            }
-        }
+                (Value::String(a), Value::String(b)) => a.cmp(b),
+                _ => std::cmp::Ordering::Equal,
+            },
+        )?;
 
-        let sorted: Vec<Value> = keyed.into_iter().map(|(_, elem)| elem).collect();
         Ok(Value::array(sorted))
     }
 
@@ -4417,6 +3524,7 @@ impl VM {
     }
 
     /// Regex intrinsic: Replace first match using callback (VM version)
+    #[cfg(feature = "regex")]
     fn vm_intrinsic_regex_replace_with(
         &mut self,
         args: &[Value],
@@ -4542,6 +3650,7 @@ impl VM {
     }
 
     /// Regex intrinsic: Replace all matches using callback (VM version)
+    #[cfg(feature = "regex")]
     fn vm_intrinsic_regex_replace_all_with(
         &mut self,
         args: &[Value],
@@ -4702,6 +3811,51 @@ impl VM {
         })
     }
 
+    /// store.transaction(fn): run `fn(store)` in a transaction. It commits
+    /// when `fn` returns and rolls back when `fn` throws or returns `Err`.
+    fn vm_intrinsic_kv_transaction(
+        &mut self,
+        args: &[Value],
+        span: crate::span::Span,
+    ) -> Result<Value, RuntimeError> {
+        if args.len() != 2 {
+            return Err(RuntimeError::TypeError {
+                msg: "transaction() expects 1 argument (callback)".to_string(),
+                span,
+            });
+        }
+        let store = crate::stdlib::kv::expect_store(&args[0], "transaction", span)?.clone();
+        let callback = match &args[1] {
+            Value::Function(_)
+            | Value::Closure(_)
+            | Value::Builtin(_)
+            | Value::NativeFunction(_) => &args[1],
+            _ => {
+                return Err(RuntimeError::TypeError {
+                    msg: "transaction() argument must be function".to_string(),
+                    span,
+                })
+            }
+        };
+
+        store.begin(span)?;
+        match self.vm_call_function_value(callback, vec![args[0].clone()], span) {
+            Ok(result @ Value::Result(Err(_))) => {
+                store.rollback(span)?;
+                Ok(result)
+            }
+            Ok(result) => {
+                store.commit(span)?;
+                Ok(result)
+            }
+            Err(err) => {
+                // The callback's error is the one worth reporting
+                let _ = store.rollback(span);
+                Err(err)
+            }
+        }
+    }
+
     /// Helper: Call a function value with arguments (VM version)
     fn vm_call_function_value(
         &mut self,
@@ -4711,14 +3865,14 @@ impl VM {
     ) -> Result<Value, RuntimeError> {
         match func {
             Value::Builtin(name) => {
-                let security =
-                    self.current_security
-                        .as_ref()
-                        .ok_or_else(|| RuntimeError::InternalError {
-                            msg: "Security context not set".to_string(),
-                            span: self.current_span().unwrap_or_else(crate::span::Span::dummy),
-                        })?;
-                crate::stdlib::call_builtin(name, &args, span, security, &self.output_writer)
+                let _call = crate::stdlib::interrupt::enter_call(self.execution_limits.as_ref());
+                crate::stdlib::call_builtin(
+                    name,
+                    &args,
+                    span,
+                    &self.current_security,
+                    &self.output_writer,
+                )
             }
             Value::Function(func_ref) => {
                 // User-defined function - execute via VM
@@ -4772,9 +3926,13 @@ impl VM {
                     stack_base,
                     local_count: func_ref.local_count,
                     upvalues: std::sync::Arc::new(Vec::new()),
+                    is_async: func_ref.is_async,
                 };
+                self.check_call_depth()?;
+                self.enter_callback(span)?;
                 self.ctx.frames.push(frame);
                 self.ctx.defer_stacks.push(Vec::new());
+                self.profile_frame_push();
                 #[cfg(debug_assertions)]
                 self.ctx
                     .consumed_slots
@@ -4784,7 +3942,9 @@ impl VM {
                 self.ctx.ip = func_ref.bytecode_offset;
 
                 // Execute until this frame returns (depth goes back to saved_frame_depth)
-                let result = self.execute_loop(Some(saved_frame_depth))?;
+                let result = self.execute_loop(Some(saved_frame_depth));
+                self.callback_depth -= 1;
+                let result = result?;
 
                 // Get the return value from stack
                 let return_value = result.unwrap_or(Value::Null);
@@ -4848,15 +4008,21 @@ impl VM {
                     stack_base,
                     local_count: func.local_count,
                     upvalues,
+                    is_async: func.is_async,
                 };
+                self.check_call_depth()?;
+                self.enter_callback(span)?;
                 self.ctx.frames.push(frame);
                 self.ctx.defer_stacks.push(Vec::new());
+                self.profile_frame_push();
                 #[cfg(debug_assertions)]
                 self.ctx.consumed_slots.push(vec![false; func.local_count]);
 
                 self.ctx.ip = func.bytecode_offset;
 
-                let result = self.execute_loop(Some(saved_frame_depth))?;
+                let result = self.execute_loop(Some(saved_frame_depth));
+                self.callback_depth -= 1;
+                let result = result?;
                 let return_value = result.unwrap_or(Value::Null);
                 // Restore the stack to exactly where it was before we started.
                 self.ctx.stack.truncate(original_stack_len);
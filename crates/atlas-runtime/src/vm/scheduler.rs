@@ -0,0 +1,277 @@
+//! Cooperative scheduling of suspended `async fn` frames
+//!
+//! An `await` on a pending future inside an `async fn` does not block the
+//! thread: the function's frame is split off into a [`Task`] with its own
+//! [`VMContext`], the caller receives a pending future for the function's
+//! result, and execution carries on in the caller. Every `await` that cannot
+//! suspend (top-level code, plain functions) and the end of a run drive the
+//! scheduler instead: tasks whose awaited future has settled are resumed in
+//! turn, and the thread only parks when none of them can make progress.
+//!
+//! Tasks resume on the VM's thread one at a time, so Atlas code never runs
+//! concurrently with itself; it interleaves at `await` points only. Resumed
+//! frames run without the debugger attached.
+
+use super::context::VMContext;
+use super::dispatch::Flow;
+use super::VM;
+use crate::async_runtime::{self, settled, AtlasFuture};
+use crate::span::Span;
+use crate::value::{RuntimeError, Value};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// An `async fn` suspended at an `await`
+struct Task {
+    /// The function's frame, stack slice, defers and handlers
+    ctx: VMContext,
+    /// The future the function is waiting for
+    awaiting: AtlasFuture,
+    /// The future its caller received, settled when the function finishes
+    result: AtlasFuture,
+}
+
+/// Suspended tasks of one VM
+#[derive(Default)]
+pub(super) struct Scheduler {
+    /// Tasks waiting to be resumed, oldest first
+    tasks: VecDeque<Task>,
+    /// Contexts set aside while a task runs on top of them
+    parked: Vec<VMContext>,
+    /// Future awaited by the running task's root frame, which suspends it
+    yielded: Option<AtlasFuture>,
+    /// Tasks that failed, with the error, until their future is awaited
+    failures: Vec<(AtlasFuture, RuntimeError)>,
+}
+
+impl Scheduler {
+    /// Contexts whose values must survive a garbage collection
+    pub(super) fn contexts(&self) -> impl Iterator<Item = &VMContext> {
+        self.tasks.iter().map(|t| &t.ctx).chain(self.parked.iter())
+    }
+
+    /// Take the oldest task that can resume, with what its `await` yields
+    ///
+    /// A task can resume once its future has settled, or with an error once
+    /// nothing else holds the future (outcome `None`).
+    fn take_ready(&mut self) -> Option<(Task, Option<Result<Value, Value>>)> {
+        let index = self
+            .tasks
+            .iter()
+            .position(|t| t.awaiting.is_orphaned() || settled(&t.awaiting).is_some())?;
+        let task = self.tasks.remove(index)?;
+        let outcome = settled(&task.awaiting);
+        Some((task, outcome))
+    }
+
+    /// Handles to every future a task is waiting for
+    fn awaited(&self) -> Vec<AtlasFuture> {
+        self.tasks.iter().map(|t| t.awaiting.clone()).collect()
+    }
+
+    /// Forget the failure behind `future` now that something awaited it
+    fn observe(&mut self, future: &AtlasFuture) {
+        self.failures.retain(|(failed, _)| !failed.same_as(future));
+    }
+}
+
+impl VM {
+    /// Await `future` for the running code
+    ///
+    /// A settled future's value is pushed, or its rejection raised. A
+    /// pending one suspends the innermost frame when it belongs to an
+    /// `async fn`; anywhere else the other tasks run until it settles.
+    pub(super) fn await_future(&mut self, future: &AtlasFuture) -> Result<Flow, RuntimeError> {
+        let span = self.current_span().unwrap_or_else(Span::dummy);
+        let outcome = match settled(future) {
+            Some(outcome) => outcome,
+            None if self.ctx.frames.last().is_some_and(|f| f.is_async) => {
+                if self.ctx.frames.len() == 1 {
+                    // The root frame of a running task: hand it back
+                    self.scheduler.yielded = Some(future.clone());
+                    return Ok(Flow::Suspend);
+                }
+                self.suspend_frame(future.clone());
+                return Ok(Flow::Next);
+            }
+            None => self.run_tasks_until(future, span)?,
+        };
+        match outcome {
+            Ok(value) => {
+                self.push(value);
+                Ok(Flow::Next)
+            }
+            Err(error) => {
+                self.scheduler.observe(future);
+                Err(rejected(&error, span))
+            }
+        }
+    }
+
+    /// Resume every suspended task until all of them have finished
+    ///
+    /// Called once the code that started them is done. Fails with the error
+    /// of the first task that failed without anything awaiting it.
+    pub(super) fn run_remaining_tasks(&mut self) -> Result<(), RuntimeError> {
+        let result = self.drain_tasks();
+        if result.is_err() {
+            self.scheduler = Scheduler::default();
+        }
+        result
+    }
+
+    fn drain_tasks(&mut self) -> Result<(), RuntimeError> {
+        while !self.scheduler.tasks.is_empty() {
+            if let Some((task, outcome)) = self.scheduler.take_ready() {
+                self.run_task(task, outcome)?;
+                continue;
+            }
+            async_runtime::wait_for_any(&self.scheduler.awaited())
+                .map_err(|reason| cannot_await(&reason, Span::dummy()))?;
+        }
+        match self.scheduler.failures.drain(..).next() {
+            Some((_, err)) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Move the innermost (`async fn`) frame into a task waiting for
+    /// `awaiting` and return a pending future for its result to the caller
+    fn suspend_frame(&mut self, awaiting: AtlasFuture) {
+        let ctx = self.ctx.split_top_frame();
+        self.drop_frame_watches();
+        self.ctx.ip = ctx.frames[0].return_ip;
+
+        let result = AtlasFuture::new_pending();
+        self.push(Value::Future(Arc::new(result.clone())));
+        self.scheduler.tasks.push_back(Task {
+            ctx,
+            awaiting,
+            result,
+        });
+    }
+
+    /// Run tasks until `target` settles, parking the thread while none of
+    /// them can resume
+    fn run_tasks_until(
+        &mut self,
+        target: &AtlasFuture,
+        span: Span,
+    ) -> Result<Result<Value, Value>, RuntimeError> {
+        loop {
+            if let Some(outcome) = settled(target) {
+                return Ok(outcome);
+            }
+            if let Some((task, outcome)) = self.scheduler.take_ready() {
+                self.run_task(task, outcome)?;
+                continue;
+            }
+            if self.scheduler.tasks.is_empty() {
+                return async_runtime::wait_for(target)
+                    .map_err(|reason| cannot_await(&reason, span));
+            }
+            if target.is_orphaned() {
+                return settled(target).ok_or_else(|| {
+                    cannot_await(
+                        "future can never settle: nothing holds a handle to resolve it",
+                        span,
+                    )
+                });
+            }
+            let mut waiting = self.scheduler.awaited();
+            waiting.push(target.clone());
+            async_runtime::wait_for_any(&waiting).map_err(|reason| cannot_await(&reason, span))?;
+        }
+    }
+
+    /// Resume `task` with the outcome of its `await` and run it until it
+    /// finishes or suspends again
+    ///
+    /// A finished task settles its result future; a failed one rejects it.
+    /// Only an error no `try` may catch (a limit being hit) is returned.
+    fn run_task(
+        &mut self,
+        task: Task,
+        outcome: Option<Result<Value, Value>>,
+    ) -> Result<(), RuntimeError> {
+        let Task {
+            ctx,
+            awaiting,
+            result,
+        } = task;
+        let mut outer = std::mem::replace(&mut self.ctx, ctx);
+        outer.hand_over(&mut self.ctx);
+        self.scheduler.parked.push(outer);
+        let debugger = self.debugger.take();
+
+        let span = self.current_span().unwrap_or_else(Span::dummy);
+        let resumed = match outcome {
+            Some(Ok(value)) => {
+                self.push(value);
+                Ok(())
+            }
+            Some(Err(error)) => {
+                self.scheduler.observe(&awaiting);
+                Err(rejected(&error, span))
+            }
+            None => Err(cannot_await(
+                "future can never settle: nothing holds a handle to resolve it",
+                span,
+            )),
+        };
+        let run = match resumed {
+            Ok(()) => self.execute_loop(Some(0)),
+            Err(err) => match self.unwind_to_handler(&err, Some(0)) {
+                Ok(true) => self.execute_loop(Some(0)),
+                Ok(false) => Err(err),
+                Err(unwind_err) => Err(unwind_err),
+            },
+        };
+
+        self.debugger = debugger;
+        let outer = self
+            .scheduler
+            .parked
+            .pop()
+            .expect("the context a task runs on is parked");
+        let mut ctx = std::mem::replace(&mut self.ctx, outer);
+        ctx.hand_over(&mut self.ctx);
+
+        match (run, self.scheduler.yielded.take()) {
+            (Ok(_), Some(awaiting)) => {
+                self.scheduler.tasks.push_back(Task {
+                    ctx,
+                    awaiting,
+                    result,
+                });
+            }
+            // `WrapFuture` turned the return value into a settled future
+            (Ok(Some(Value::Future(returned))), None) => match settled(&returned) {
+                Some(Ok(value)) => result.resolve(value),
+                Some(Err(error)) => result.reject(error),
+                None => result.resolve(Value::Future(returned)),
+            },
+            (Ok(value), None) => result.resolve(value.unwrap_or(Value::Null)),
+            (Err(err), _) if !err.is_catchable() => return Err(err),
+            (Err(err), _) => {
+                result.reject(Value::string(err.to_string()));
+                self.scheduler.failures.push((result, err));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn rejected(error: &Value, span: Span) -> RuntimeError {
+    RuntimeError::TypeError {
+        msg: format!("Awaited future was rejected: {}", error),
+        span,
+    }
+}
+
+fn cannot_await(reason: &str, span: Span) -> RuntimeError {
+    RuntimeError::TypeError {
+        msg: format!("Cannot await pending future: {}", reason),
+        span,
+    }
+}
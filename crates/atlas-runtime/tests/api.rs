@@ -1,5 +1,5 @@
 //! THIN ROUTER — DO NOT ADD TESTS HERE.
-//! Add tests to submodule files: tests/api/{core,conversions,native,reflection,...}.rs
+//! Add tests to submodule files: tests/api/{core,conversions,native,reflection,threading,...}.rs
 //! This file only declares submodules and shared helpers.
 
 use atlas_runtime::api::{ConversionError, EvalError, FromAtlas, Runtime, RuntimeConfig, ToAtlas};
@@ -39,6 +39,15 @@ mod native_part2;
 #[path = "api/sandboxing.rs"]
 mod sandboxing;
 
+#[path = "api/language_profile.rs"]
+mod language_profile;
+
+#[path = "api/eval_expr.rs"]
+mod eval_expr;
+
+#[path = "api/resources.rs"]
+mod resources;
+
 #[path = "api/reflection_part1.rs"]
 mod reflection_part1;
 
@@ -50,3 +59,21 @@ mod json;
 
 #[path = "api/runtime_api.rs"]
 mod runtime_api;
+
+#[path = "api/run_entry_point.rs"]
+mod run_entry_point;
+
+#[path = "api/compiled_programs.rs"]
+mod compiled_programs;
+
+#[path = "api/runtime_stack_traces.rs"]
+mod runtime_stack_traces;
+
+#[path = "api/timings.rs"]
+mod timings;
+
+#[path = "api/run_compiled.rs"]
+mod run_compiled;
+
+#[path = "api/threading.rs"]
+mod threading;
@@ -0,0 +1,42 @@
+//! `Atlas::compile_file`: whole programs compiled ahead of time and run from
+//! serialized bytecode.
+
+use super::*;
+
+#[test]
+fn test_compile_file_runs_from_serialized_bytecode() {
+    use atlas_runtime::{Bytecode, RunOutcome, SecurityContext, VM};
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("math.atl"),
+        "export fn triple(x: number): number { return x * 3; }",
+    )
+    .unwrap();
+    let main_path = temp_dir.path().join("main.atl");
+    std::fs::write(
+        &main_path,
+        "import { triple } from \"./math\";\nfn main(): number { return triple(4); }",
+    )
+    .unwrap();
+
+    let runtime = Atlas::new_with_security(SecurityContext::allow_all());
+    let program = runtime
+        .compile_file(main_path.to_str().unwrap())
+        .unwrap_or_else(|e| panic!("compile_file failed: {:?}", e));
+    assert!(program.has_main);
+
+    let bytecode = Bytecode::from_bytes(&program.bytecode.to_bytes()).unwrap();
+    let value = VM::new(bytecode)
+        .run(&SecurityContext::allow_all())
+        .unwrap()
+        .unwrap_or(Value::Null);
+    assert_eq!(
+        RunOutcome::new(value, program.has_main),
+        RunOutcome::Exited {
+            code: 12,
+            error: None
+        }
+    );
+}
@@ -125,3 +125,69 @@ fn test_multiple_function_definitions_single_eval() {
         .unwrap();
     assert!(matches!(result, Value::Number(n) if n == 24.0));
 }
+
+// Global resolver Tests
+
+/// Serves `count` and `rate`, recording every name it is asked for
+struct RecordingResolver {
+    asked: Arc<Mutex<Vec<String>>>,
+}
+
+impl atlas_runtime::api::GlobalResolver for RecordingResolver {
+    fn names(&self) -> Vec<String> {
+        vec!["count".to_string(), "rate".to_string()]
+    }
+
+    fn resolve(&self, name: &str) -> Option<Value> {
+        self.asked.lock().unwrap().push(name.to_string());
+        match name {
+            "count" => Some(Value::Number(4.0)),
+            "rate" => Some(Value::Number(0.5)),
+            _ => None,
+        }
+    }
+}
+
+fn runtime_with_resolver() -> (Runtime, Arc<Mutex<Vec<String>>>) {
+    let asked = Arc::new(Mutex::new(Vec::new()));
+    let mut runtime = Runtime::new();
+    runtime.set_global_resolver(RecordingResolver {
+        asked: Arc::clone(&asked),
+    });
+    (runtime, asked)
+}
+
+#[test]
+fn test_global_resolver_resolves_only_what_is_read() {
+    let (mut runtime, asked) = runtime_with_resolver();
+    let result = runtime.eval("count * 2").unwrap();
+    assert!(matches!(result, Value::Number(n) if n == 8.0));
+    assert_eq!(*asked.lock().unwrap(), vec!["count".to_string()]);
+}
+
+#[test]
+fn test_global_resolver_resolves_each_name_once() {
+    let (mut runtime, asked) = runtime_with_resolver();
+    runtime.eval("count + count").unwrap();
+    let result = runtime.eval("count * rate").unwrap();
+    assert!(matches!(result, Value::Number(n) if n == 2.0));
+    assert_eq!(
+        *asked.lock().unwrap(),
+        vec!["count".to_string(), "rate".to_string()]
+    );
+}
+
+#[test]
+fn test_set_global_takes_precedence_over_resolver() {
+    let (mut runtime, asked) = runtime_with_resolver();
+    runtime.set_global("count", Value::Number(10.0));
+    let result = runtime.eval("count").unwrap();
+    assert!(matches!(result, Value::Number(n) if n == 10.0));
+    assert!(asked.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_global_resolver_names_outside_resolver_are_undefined() {
+    let (mut runtime, _) = runtime_with_resolver();
+    assert!(runtime.eval("missing + 1").is_err());
+}
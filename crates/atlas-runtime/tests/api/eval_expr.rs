@@ -0,0 +1,120 @@
+use super::*;
+
+// --- Atlas::eval_expr ---
+
+// Tests for single-expression evaluation against host variables
+
+fn vars(pairs: &[(&str, Value)]) -> HashMap<String, Value> {
+    pairs
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect()
+}
+
+/// Diagnostic codes of a rejected expression
+fn error_codes(result: RuntimeResult<Value>) -> Vec<String> {
+    match result {
+        Err(diags) => diags.into_iter().map(|d| d.code).collect(),
+        Ok(value) => panic!("expected a diagnostic, got {:?}", value),
+    }
+}
+
+#[test]
+fn test_eval_expr_reads_vars() {
+    let runtime = Atlas::new();
+    let vars = vars(&[("price", Value::Number(20.0)), ("qty", Value::Number(3.0))]);
+    let result = runtime.eval_expr("price * qty + 1", &vars).unwrap();
+    assert_eq!(result, Value::Number(61.0));
+}
+
+#[test]
+fn test_eval_expr_uses_pure_builtins() {
+    let runtime = Atlas::new();
+    let vars = vars(&[("name", Value::string("atlas"))]);
+    let result = runtime
+        .eval_expr(
+            r#"name.toUpperCase() + ":" + toString(math.max(len(name), 2))"#,
+            &vars,
+        )
+        .unwrap();
+    assert_eq!(result, Value::string("ATLAS:5"));
+}
+
+#[test]
+fn test_eval_expr_allows_trailing_semicolon() {
+    let runtime = Atlas::new();
+    let result = runtime.eval_expr("1 + 2;", &HashMap::new()).unwrap();
+    assert_eq!(result, Value::Number(3.0));
+}
+
+#[test]
+fn test_eval_expr_checks_var_types() {
+    let runtime = Atlas::new();
+    let vars = vars(&[("flag", Value::Bool(true))]);
+    let codes = error_codes(runtime.eval_expr("flag * 2", &vars));
+    assert!(!codes.is_empty());
+}
+
+#[test]
+fn test_eval_expr_vars_are_immutable() {
+    let runtime = Atlas::new();
+    let vars = vars(&[("x", Value::Number(1.0))]);
+    assert!(runtime.eval_expr("x = 2", &vars).is_err());
+}
+
+#[rstest]
+#[case("let x = 1;")]
+#[case("fn f(): number { return 1; }")]
+#[case("while (true) { }")]
+#[case("1; 2")]
+fn test_eval_expr_rejects_statements(#[case] source: &str) {
+    let runtime = Atlas::new();
+    let codes = error_codes(runtime.eval_expr(source, &HashMap::new()));
+    assert!(codes.contains(&"AT1025".to_string()), "{source}: {codes:?}");
+}
+
+#[rstest]
+#[case(r#"file.read("secrets.txt")"#)]
+#[case(r#"console.log("hi")"#)]
+#[case(r#"process.exec("ls")"#)]
+#[case(r#"env.get("HOME")"#)]
+fn test_eval_expr_rejects_io_builtins(#[case] source: &str) {
+    let runtime = Atlas::new_with_security(atlas_runtime::SecurityContext::allow_all());
+    let codes = error_codes(runtime.eval_expr(source, &HashMap::new()));
+    assert!(codes.contains(&"AT1025".to_string()), "{source}: {codes:?}");
+}
+
+#[test]
+fn test_eval_expr_reports_runtime_errors() {
+    let runtime = Atlas::new();
+    let vars = vars(&[("n", Value::Number(0.0))]);
+    assert!(runtime.eval_expr("10 / n", &vars).is_err());
+}
+
+#[test]
+fn test_eval_expr_vars_do_not_outlive_the_call() {
+    let runtime = Atlas::new();
+    let vars = vars(&[("total", Value::Number(1.0))]);
+    assert_eq!(
+        runtime.eval_expr("total + 1", &vars).unwrap(),
+        Value::Number(2.0)
+    );
+    assert!(runtime.eval_expr("total", &HashMap::new()).is_err());
+}
+
+#[test]
+fn test_eval_expr_stops_runaway_allocation() {
+    let big = Value::string("x".repeat(4_000_000));
+    let vars = vars(&[("s", big)]);
+    let source = "len(s + s + s + s)";
+
+    let codes = error_codes(Atlas::new().eval_expr(source, &vars));
+    assert_eq!(codes, vec!["AT0501".to_string()]);
+
+    // Embedders can lift the cap
+    let runtime = Atlas::new().with_expr_limits(atlas_runtime::ExprLimits::unlimited());
+    assert_eq!(
+        runtime.eval_expr(source, &vars).unwrap(),
+        Value::Number(16_000_000.0)
+    );
+}
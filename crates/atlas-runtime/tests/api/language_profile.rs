@@ -0,0 +1,150 @@
+use super::*;
+use atlas_runtime::api::{LanguageProfile, Production, ProfileError};
+
+// --- Language profiles ---
+
+// Tests for keyword aliases, disabled productions and hidden builtins
+
+fn runtime_with(profile: LanguageProfile) -> Runtime {
+    Runtime::from_config(RuntimeConfig::new().with_language_profile(profile))
+}
+
+/// Diagnostic codes of a rejected script
+fn error_codes(result: Result<Value, EvalError>) -> Vec<String> {
+    match result {
+        Err(EvalError::ParseError(diags)) | Err(EvalError::TypeError(diags)) => {
+            diags.into_iter().map(|d| d.code).collect()
+        }
+        other => panic!("expected a diagnostic, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_expressions_only_evaluates_expressions() {
+    let mut runtime = runtime_with(LanguageProfile::expressions_only());
+    let result = runtime.eval("1 + 2 * 3").unwrap();
+    assert!(matches!(result, Value::Number(n) if n == 7.0));
+}
+
+#[rstest]
+#[case("let x = 1;")]
+#[case("fn f(): number { return 1; }")]
+#[case("while (false) { }")]
+#[case("struct Point { x: number }")]
+#[case("let f = fn(x: number): number { return x; };")]
+fn test_expressions_only_rejects_productions(#[case] code: &str) {
+    let mut runtime = runtime_with(LanguageProfile::expressions_only());
+    assert!(error_codes(runtime.eval(code)).contains(&"AT1025".to_string()));
+}
+
+#[test]
+fn test_disabled_production_leaves_others_available() {
+    let mut runtime = runtime_with(LanguageProfile::new().without(Production::Loops));
+    let result = runtime.eval("let x = 20; x + 22").unwrap();
+    assert!(matches!(result, Value::Number(n) if n == 42.0));
+    assert!(error_codes(runtime.eval("for i in [1] { }")).contains(&"AT1025".to_string()));
+}
+
+#[test]
+fn test_keyword_alias() {
+    let profile = LanguageProfile::new()
+        .with_keyword_alias("rule", "fn")
+        .unwrap();
+    let mut runtime = runtime_with(profile);
+    let result = runtime
+        .eval("rule double(x: number): number { return x * 2; } double(21)")
+        .unwrap();
+    assert!(matches!(result, Value::Number(n) if n == 42.0));
+}
+
+#[test]
+fn test_hidden_keyword_becomes_identifier() {
+    let profile = LanguageProfile::new()
+        .hide_keyword("fn")
+        .unwrap()
+        .with_keyword_alias("rule", "fn")
+        .unwrap();
+    let mut runtime = runtime_with(profile);
+    let result = runtime.eval("let fn = 3; fn + 1").unwrap();
+    assert!(matches!(result, Value::Number(n) if n == 4.0));
+}
+
+#[test]
+fn test_profile_errors() {
+    assert_eq!(
+        LanguageProfile::new().with_keyword_alias("if", "fn"),
+        Err(ProfileError::InvalidAlias("if".to_string()))
+    );
+    assert_eq!(
+        LanguageProfile::new().with_keyword_alias("my alias", "fn"),
+        Err(ProfileError::InvalidAlias("my alias".to_string()))
+    );
+    assert_eq!(
+        LanguageProfile::new().with_keyword_alias("when", "whenever"),
+        Err(ProfileError::UnknownKeyword("whenever".to_string()))
+    );
+    assert_eq!(
+        LanguageProfile::new().hide_keyword("banana"),
+        Err(ProfileError::UnknownKeyword("banana".to_string()))
+    );
+}
+
+#[rstest]
+#[case(r#"file.read("config.toml")"#)]
+#[case(r#"File.read("config.toml")"#)]
+fn test_hidden_namespace_is_rejected(#[case] code: &str) {
+    let mut runtime = runtime_with(LanguageProfile::new().hide_builtin("file"));
+    assert_eq!(error_codes(runtime.eval(code)), vec!["AT1025".to_string()]);
+}
+
+#[test]
+fn test_user_binding_may_shadow_hidden_builtin() {
+    let mut runtime = runtime_with(LanguageProfile::new().hide_builtin("len"));
+    let result = runtime
+        .eval("fn count(len: number): number { return len + 1; } count(1)")
+        .unwrap();
+    assert!(matches!(result, Value::Number(n) if n == 2.0));
+}
+
+#[test]
+fn test_only_builtins() {
+    let mut runtime = runtime_with(LanguageProfile::new().only_builtins(["math"]));
+    let result = runtime.eval("math.sqrt(16).unwrap()").unwrap();
+    assert!(matches!(result, Value::Number(n) if n == 4.0));
+    // Option and Result constructors are part of the language
+    assert!(runtime.eval("Some(1)").is_ok());
+    assert!(error_codes(runtime.eval("len([1])")).contains(&"AT1025".to_string()));
+}
+
+#[test]
+fn test_registered_natives_stay_visible() {
+    let mut runtime = runtime_with(LanguageProfile::new().only_builtins(["math"]));
+    runtime.register_function("answer", 0, |_| Ok(Value::Number(42.0)));
+    let result = runtime.eval("answer()").unwrap();
+    assert!(matches!(result, Value::Number(n) if n == 42.0));
+}
+
+#[test]
+fn test_hidden_builtin_in_impl_method_is_rejected() {
+    let mut runtime = runtime_with(LanguageProfile::new().hide_builtin("file"));
+    let code = r#"
+        struct Loader { path: string }
+        impl Loader {
+            fn load(borrow self: Loader): number {
+                file.read(self.path);
+                return 1;
+            }
+        }
+    "#;
+    assert!(error_codes(runtime.eval(code)).contains(&"AT1025".to_string()));
+}
+
+#[test]
+fn test_eval_file_applies_profile() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("rules.atlas");
+    std::fs::write(&path, "fn f(): number { return 1; }\nf();\n").unwrap();
+
+    let mut runtime = runtime_with(LanguageProfile::expressions_only());
+    assert!(error_codes(runtime.eval_file(&path)).contains(&"AT1025".to_string()));
+}
@@ -0,0 +1,128 @@
+use super::*;
+use atlas_runtime::api::{Resource, WeakResource};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// --- Host resources ---
+
+/// Stand-in for a database connection handed to scripts
+struct Connection {
+    queries: usize,
+}
+
+/// A connection resource and a counter of how many times it was closed
+fn connection() -> (Resource, Arc<AtomicUsize>) {
+    let closes = Arc::new(AtomicUsize::new(0));
+    let counter = closes.clone();
+    let resource =
+        Resource::new("db", Connection { queries: 0 }).on_drop(move |_conn: Connection| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+    (resource, closes)
+}
+
+#[test]
+fn test_resource_finalizer_runs_on_last_drop() {
+    let (resource, closes) = connection();
+    let copy = resource.clone();
+    drop(resource);
+    assert_eq!(closes.load(Ordering::SeqCst), 0);
+    drop(copy);
+    assert_eq!(closes.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_resource_close_releases_once() {
+    let (resource, closes) = connection();
+    assert!(resource.close());
+    assert!(!resource.close());
+    assert!(resource.is_closed());
+    assert_eq!(resource.with(|conn: &mut Connection| conn.queries), None);
+    drop(resource);
+    assert_eq!(closes.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_resource_with_checks_payload_type() {
+    let (resource, _) = connection();
+    assert_eq!(resource.with(|conn: &mut Connection| conn.queries), Some(0));
+    assert_eq!(resource.with(|n: &mut f64| *n), None);
+}
+
+#[test]
+fn test_weak_resource_does_not_keep_alive() {
+    let (resource, closes) = connection();
+    let weak: WeakResource = resource.downgrade();
+    assert!(weak.is_alive());
+    assert!(weak.upgrade().unwrap().ptr_eq(&resource));
+    drop(resource);
+    assert!(!weak.is_alive());
+    assert!(weak.upgrade().is_none());
+    assert_eq!(closes.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_resource_value_identity_and_display() {
+    let (resource, _) = connection();
+    let a = Value::Resource(resource.clone());
+    let (other, _) = connection();
+    assert_eq!(a, resource.clone().to_atlas());
+    assert_ne!(a, Value::Resource(other));
+    assert_eq!(a.to_string(), "<Resource db open>");
+    resource.close();
+    assert_eq!(a.to_string(), "<Resource db closed>");
+    assert!(Resource::from_atlas(&a).is_ok());
+    assert!(Resource::from_atlas(&Value::Null).is_err());
+}
+
+/// A runtime with `connect()` and `query(db)` natives backed by `resource`
+fn runtime_with(resource: Resource) -> Runtime {
+    let mut runtime = Runtime::new();
+    runtime.register_function("connect", 0, move |_args| {
+        Ok(Value::Resource(resource.clone()))
+    });
+    runtime.register_function("query", 1, |args| {
+        let Value::Resource(db) = &args[0] else {
+            return Err(RuntimeError::TypeError {
+                msg: "expected a db resource".to_string(),
+                span: Span::dummy(),
+            });
+        };
+        let count = db
+            .with(|conn: &mut Connection| {
+                conn.queries += 1;
+                conn.queries
+            })
+            .unwrap_or(0);
+        Ok(Value::Number(count as f64))
+    });
+    runtime
+}
+
+#[test]
+fn test_script_uses_injected_resource() {
+    let (resource, closes) = connection();
+    let weak = resource.downgrade();
+    let mut runtime = runtime_with(resource);
+
+    let result = runtime
+        .eval("let db = connect(); query(db); query(db)")
+        .unwrap();
+    assert_eq!(result, Value::Number(2.0));
+    assert_eq!(
+        runtime.eval("typeof(db)").unwrap(),
+        Value::string("resource")
+    );
+    assert!(weak.is_alive());
+
+    // The native closure and the global both hold the connection
+    drop(runtime);
+    assert!(!weak.is_alive());
+    assert_eq!(closes.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_resource_cannot_be_serialized() {
+    let (resource, _) = connection();
+    let result = atlas_runtime::stdlib::json::to_json(&[Value::Resource(resource)], Span::dummy());
+    assert!(matches!(result, Err(RuntimeError::TypeError { .. })));
+}
@@ -0,0 +1,49 @@
+//! `Atlas::run_compiled` on programs compiled elsewhere (such as by the
+//! daemon), and what cannot be precompiled.
+
+use super::*;
+
+#[test]
+fn test_run_compiled_matches_run_file() {
+    use atlas_runtime::{RunOutcome, SecurityContext};
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("math.atl"),
+        "export fn triple(x: number): number { return x * 3; }",
+    )
+    .unwrap();
+    let main_path = temp_dir.path().join("main.atl");
+    std::fs::write(
+        &main_path,
+        "import { triple } from \"./math\";\nfn main(): number { return triple(4); }",
+    )
+    .unwrap();
+    let main_path = main_path.to_str().unwrap();
+
+    let runtime = Atlas::new_with_security(SecurityContext::allow_all());
+    let program = runtime.compile_file(main_path).unwrap();
+    let expected = RunOutcome::Exited {
+        code: 12,
+        error: None,
+    };
+    assert_eq!(runtime.run_compiled(program).unwrap(), expected);
+    assert_eq!(runtime.run_file(main_path).unwrap(), expected);
+}
+
+#[test]
+fn test_compile_file_rejects_extern_functions() {
+    use atlas_runtime::SecurityContext;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let main_path = temp_dir.path().join("main.atl");
+    std::fs::write(&main_path, "extern \"m\" fn sqrt(x: CDouble): CDouble;\n1;").unwrap();
+
+    let errors = Atlas::new_with_security(SecurityContext::allow_all())
+        .compile_file(main_path.to_str().unwrap())
+        .unwrap_err();
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(errors[0].code, "AT5012");
+    assert!(errors[0].message.contains("ahead of time"), "{:?}", errors);
+}
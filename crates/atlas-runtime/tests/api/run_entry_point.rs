@@ -0,0 +1,54 @@
+//! `Atlas::run_file` with a `fn main` entry point: scripts, exit codes and
+//! `Result` returns.
+
+use super::*;
+
+fn run_program(source: &str) -> atlas_runtime::RunOutcome {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let main_path = temp_dir.path().join("main.atl");
+    std::fs::write(&main_path, source).unwrap();
+
+    let runtime = Atlas::new_with_security(atlas_runtime::SecurityContext::allow_all());
+    runtime
+        .run_file(main_path.to_str().unwrap())
+        .unwrap_or_else(|e| panic!("run_file failed: {:?}", e))
+}
+
+#[test]
+fn test_run_file_without_main_is_script() {
+    assert_eq!(
+        run_program("let x = 20; x + 1;"),
+        atlas_runtime::RunOutcome::Script(Value::Number(21.0))
+    );
+}
+
+#[test]
+fn test_run_file_main_number_is_exit_code() {
+    assert_eq!(
+        run_program("fn main(_args: string[]): number { return 42; }"),
+        atlas_runtime::RunOutcome::Exited {
+            code: 42,
+            error: None,
+        }
+    );
+}
+
+#[test]
+fn test_run_file_main_result_maps_to_exit_code() {
+    assert_eq!(
+        run_program("fn main(): Result<number, string> { return Ok(5); }"),
+        atlas_runtime::RunOutcome::Exited {
+            code: 0,
+            error: None,
+        }
+    );
+    assert_eq!(
+        run_program(r#"fn main(): Result<number, string> { return Err("bad input"); }"#),
+        atlas_runtime::RunOutcome::Exited {
+            code: 1,
+            error: Some(Value::string("bad input")),
+        }
+    );
+}
@@ -0,0 +1,34 @@
+//! Call stacks captured on runtime errors, in diagnostics and on the
+//! embedding `Runtime`.
+
+use super::*;
+
+/// Runtime errors carry the call stack that led to them
+#[test]
+fn test_runtime_error_diagnostic_has_stack_trace() {
+    let runtime = Atlas::new();
+    let source =
+        "fn inner(): void { let arr = [1]; arr[3]; }\nfn outer(): void { inner(); }\nouter();";
+    let diagnostics = runtime.eval(source).unwrap_err();
+    let functions: Vec<&str> = diagnostics[0]
+        .stack_trace
+        .iter()
+        .map(|frame| frame.function.as_str())
+        .collect();
+    assert_eq!(functions, ["inner", "outer"]);
+}
+
+#[test]
+fn test_embedding_runtime_keeps_last_stack_trace() {
+    let mut runtime = Runtime::new();
+    assert!(runtime.last_stack_trace().is_empty());
+    let source =
+        "fn inner(): void { let arr = [1]; arr[3]; }\nfn outer(): void { inner(); }\nouter();";
+    assert!(matches!(
+        runtime.eval(source),
+        Err(EvalError::RuntimeError(RuntimeError::OutOfBounds { .. }))
+    ));
+    let trace = runtime.last_stack_trace();
+    assert_eq!(trace.len(), 2);
+    assert_eq!(trace[0].function, "inner");
+}
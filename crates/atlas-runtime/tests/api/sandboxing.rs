@@ -1,6 +1,9 @@
 use super::*;
 
 mod sandboxing_loops;
+mod sandboxing_recursion_limits;
+mod sandboxing_regex_limits;
+mod sandboxing_time_limits;
 
 // --- Sandboxing ---
 
@@ -304,6 +307,36 @@ fn test_timeout_enforcement_vm() {
     );
 }
 
+#[test]
+fn test_timeout_not_caught_by_try() {
+    let config = RuntimeConfig::new()
+        .with_max_execution_time(Duration::from_millis(100))
+        .with_io_allowed(false);
+
+    let mut runtime = Runtime::from_config(config);
+
+    // A catch block must not swallow the time limit
+    let result = runtime.eval(
+        r#"
+        let mut x: number = 0;
+        try {
+            while (true) {
+                x = x + 1;
+            }
+        } catch {
+            x = -1;
+        }
+        "#,
+    );
+
+    let err_msg = format!("{:?}", result.unwrap_err());
+    assert!(
+        err_msg.contains("Timeout") || err_msg.contains("timeout"),
+        "Expected timeout error, got: {}",
+        err_msg
+    );
+}
+
 #[test]
 fn test_timeout_respects_limit() {
     // Create config with 500ms timeout
@@ -0,0 +1,115 @@
+//! VM recursion limits: call depth and native stack, under the standard
+//! and sandboxed configurations.
+
+use super::*;
+
+fn recursion_runtime(limits: atlas_runtime::vm::VmLimits) -> Runtime {
+    Runtime::from_config(RuntimeConfig::new().with_vm_limits(limits))
+}
+
+const RUNAWAY: &str = "fn down(n: number): number { return down(n + 1); } down(0);";
+
+#[test]
+fn test_recursion_limit_stops_runaway_recursion() {
+    let limits = atlas_runtime::vm::VmLimits::standard().with_max_call_depth(50);
+    let mut runtime = recursion_runtime(limits);
+    match runtime.eval(RUNAWAY) {
+        Err(EvalError::RuntimeError(RuntimeError::RecursionLimit {
+            depth, stack_trace, ..
+        })) => {
+            assert_eq!(depth, 50);
+            assert_eq!(stack_trace.len(), 50);
+            assert!(stack_trace.iter().all(|frame| frame.function == "down"));
+        }
+        other => panic!("Expected RecursionLimit, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_recursion_within_limit_runs() {
+    let mut runtime = Runtime::new();
+    let result = runtime
+        .eval("fn sum(n: number): number { if n == 0 { return 0; } return n + sum(n - 1); } sum(5000);")
+        .unwrap();
+    assert_eq!(result, Value::Number(12_502_500.0));
+}
+
+#[test]
+fn test_recursion_through_callbacks_reports_limit() {
+    // Each level re-enters the VM from inside `map`, using host stack the
+    // frame count does not see
+    let mut runtime = Runtime::new();
+    let result = runtime.eval(
+        r#"
+        fn nest(n: number): number {
+            let depth = n;
+            let inner = map([1], fn(x: number): number { return nest(depth + x); });
+            return inner[0];
+        }
+        nest(0);
+        "#,
+    );
+    assert!(
+        matches!(
+            result,
+            Err(EvalError::RuntimeError(RuntimeError::RecursionLimit { .. }))
+        ),
+        "Expected RecursionLimit, got {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_native_stack_limit_is_configurable() {
+    let source = r#"
+        fn nest(n: number): number {
+            if n == 3 { return n; }
+            let depth = n;
+            let inner = map([1], fn(x: number): number { return nest(depth + x); });
+            return inner[0];
+        }
+        nest(0);
+    "#;
+    let mut runtime = Runtime::new();
+    assert_eq!(runtime.eval(source).unwrap(), Value::Number(3.0));
+
+    let limits = atlas_runtime::vm::VmLimits::standard().with_max_native_stack(0);
+    let mut runtime = recursion_runtime(limits);
+    assert!(matches!(
+        runtime.eval(source),
+        Err(EvalError::RuntimeError(RuntimeError::RecursionLimit { .. }))
+    ));
+}
+
+#[test]
+fn test_recursion_limit_can_be_caught() {
+    let limits = atlas_runtime::vm::VmLimits::standard().with_max_call_depth(100);
+    let mut runtime = recursion_runtime(limits);
+    let result = runtime
+        .eval(
+            r#"
+            fn down(n: number): number { return down(n + 1); }
+            let mut kind = "";
+            try {
+                down(0);
+            } catch (e) {
+                kind = e.kind + " " + e.code;
+            }
+            kind;
+            "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::string("RecursionLimit AT0504"));
+}
+
+#[test]
+fn test_sandboxed_config_uses_strict_vm_limits() {
+    assert_eq!(
+        RuntimeConfig::sandboxed().vm_limits,
+        atlas_runtime::vm::VmLimits::strict()
+    );
+    assert_eq!(
+        RuntimeConfig::new().vm_limits,
+        atlas_runtime::vm::VmLimits::standard()
+    );
+}
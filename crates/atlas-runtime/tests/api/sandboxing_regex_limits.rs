@@ -0,0 +1,92 @@
+//! Regex compile limits: pattern length, compiled size and nesting depth.
+
+use super::*;
+
+fn regex_runtime(limits: atlas_runtime::RegexLimits) -> Runtime {
+    Runtime::from_config(RuntimeConfig::new().with_regex_limits(limits))
+}
+
+fn assert_regex_limit_exceeded(result: Result<Value, EvalError>) {
+    assert!(
+        matches!(
+            result,
+            Err(EvalError::RuntimeError(
+                RuntimeError::RegexLimitExceeded { .. }
+            ))
+        ),
+        "Expected RegexLimitExceeded, got {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_regex_pattern_length_limit() {
+    let limits = atlas_runtime::RegexLimits::standard().with_max_pattern_len(8);
+    let mut runtime = regex_runtime(limits);
+    assert_regex_limit_exceeded(runtime.eval(r#"Regex.new("abcdefghij")"#));
+    assert_regex_limit_exceeded(runtime.eval(r#"Regex.test("abcdefghij", "x")"#));
+}
+
+#[test]
+fn test_regex_compiled_size_limit() {
+    let limits = atlas_runtime::RegexLimits::standard().with_max_compiled_size(10_000);
+    let mut runtime = regex_runtime(limits);
+    assert_regex_limit_exceeded(runtime.eval(r#"Regex.new("\\w{1000}")"#));
+}
+
+#[test]
+fn test_regex_nesting_limit() {
+    let limits = atlas_runtime::RegexLimits::standard().with_max_nesting(4);
+    let mut runtime = regex_runtime(limits);
+    assert_regex_limit_exceeded(runtime.eval(r#"Regex.new("((((((a))))))")"#));
+    assert_eq!(
+        runtime.eval(r#"Regex.test("(a)", "a")"#).unwrap(),
+        Value::Bool(true)
+    );
+}
+
+#[test]
+fn test_regex_within_limits_still_reports_invalid_patterns() {
+    let mut runtime = regex_runtime(atlas_runtime::RegexLimits::strict());
+    let result = runtime
+        .eval(
+            r#"
+            match Regex.new("[unclosed") {
+                Ok(_) => "compiled",
+                Err(_) => "invalid",
+            }
+            "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::string("invalid"));
+}
+
+#[test]
+fn test_regex_limit_error_can_be_caught() {
+    let limits = atlas_runtime::RegexLimits::standard().with_max_pattern_len(4);
+    let mut runtime = regex_runtime(limits);
+    let result = runtime
+        .eval(
+            r#"
+            let mut kind = "";
+            try {
+                Regex.new("too long for the limit");
+            } catch (e) {
+                kind = e.kind + " " + e.code;
+            }
+            kind;
+            "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::string("RegexLimitExceeded AT0503"));
+}
+
+#[test]
+fn test_sandboxed_config_uses_strict_regex_limits() {
+    let config = RuntimeConfig::sandboxed();
+    assert_eq!(config.regex_limits, atlas_runtime::RegexLimits::strict());
+    assert_eq!(
+        RuntimeConfig::new().regex_limits,
+        atlas_runtime::RegexLimits::standard()
+    );
+}
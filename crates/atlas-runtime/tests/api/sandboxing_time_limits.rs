@@ -0,0 +1,94 @@
+//! Host interrupts and per-builtin time limits: long-running builtins stop
+//! at their interrupt checkpoints.
+
+use super::*;
+
+#[test]
+fn test_interrupt_handle_stops_running_loop() {
+    let mut runtime = Runtime::new();
+    let handle = runtime.interrupt_handle();
+    let interrupter = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        handle.interrupt();
+    });
+
+    let result = runtime.eval("let mut x: number = 0; while (true) { x = x + 1; }");
+    interrupter.join().unwrap();
+    assert!(
+        matches!(
+            result,
+            Err(EvalError::RuntimeError(RuntimeError::Interrupted))
+        ),
+        "Expected Interrupted, got {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_interrupt_applies_to_one_evaluation() {
+    let mut runtime = Runtime::new();
+    let handle = runtime.interrupt_handle();
+    handle.interrupt();
+    assert!(handle.is_interrupted());
+
+    // Starting an evaluation clears an interrupt nobody was running for
+    assert_eq!(runtime.eval("1 + 1").unwrap(), Value::Number(2.0));
+    assert!(!handle.is_interrupted());
+}
+
+#[test]
+fn test_builtin_call_limit_stops_large_file_read() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("large.txt");
+    std::fs::write(&path, "x".repeat(512 * 1024)).unwrap();
+
+    let config = RuntimeConfig::new().with_max_builtin_call_time(Duration::from_nanos(1));
+    let mut runtime = Runtime::from_config(config);
+    let result = runtime.eval(&format!(r#"file.read("{}")"#, path.display()));
+    assert!(
+        matches!(
+            result,
+            Err(EvalError::RuntimeError(RuntimeError::Timeout { .. }))
+        ),
+        "Expected Timeout, got {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_builtin_call_limit_stops_regex_over_many_matches() {
+    let config = RuntimeConfig::new().with_max_builtin_call_time(Duration::from_nanos(1));
+    let mut runtime = Runtime::from_config(config);
+    let result = runtime.eval(
+        r#"
+        let pattern = Regex.new("a").unwrap();
+        pattern.findAll("a".repeat(10000))
+        "#,
+    );
+    assert!(
+        matches!(
+            result,
+            Err(EvalError::RuntimeError(RuntimeError::Timeout { .. }))
+        ),
+        "Expected Timeout, got {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_builtin_call_limit_allows_short_calls() {
+    let config = RuntimeConfig::new().with_max_builtin_call_time(Duration::from_secs(10));
+    let mut runtime = Runtime::from_config(config);
+    let result = runtime.eval(
+        r#"
+        let pattern = Regex.new("a").unwrap();
+        pattern.replaceAll("banana", "[$0]")
+        "#,
+    );
+    assert_eq!(result.unwrap(), Value::string("b[a]n[a]n[a]"));
+}
+
+#[test]
+fn test_checkpoint_outside_vm_call_is_noop() {
+    assert!(atlas_runtime::stdlib::interrupt::checkpoint().is_ok());
+}
@@ -0,0 +1,79 @@
+use super::*;
+use std::thread;
+
+// --- Using runtimes across threads ---
+
+#[test]
+fn test_atlas_created_on_one_thread_used_on_another() {
+    let runtime = Atlas::new();
+    runtime.eval("1 + 1").unwrap();
+
+    let result = thread::spawn(move || {
+        runtime
+            .eval(r#"fn greet(name: string): string { return "hi " + name; } greet("there")"#)
+            .unwrap()
+    })
+    .join()
+    .unwrap();
+    assert_eq!(result, Value::string("hi there"));
+}
+
+#[test]
+fn test_atlas_shared_between_threads() {
+    let runtime = Arc::new(Atlas::new());
+
+    let workers: Vec<_> = (0..8)
+        .map(|i| {
+            let runtime = Arc::clone(&runtime);
+            thread::spawn(move || {
+                (0..10)
+                    .map(|j| runtime.eval(&format!("{} * 10 + {}", i, j)).unwrap())
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    for (i, worker) in workers.into_iter().enumerate() {
+        let expected: Vec<_> = (0..10)
+            .map(|j| Value::Number((i * 10 + j) as f64))
+            .collect();
+        assert_eq!(worker.join().unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_atlas_timings_readable_from_other_thread() {
+    let runtime = Arc::new(Atlas::new());
+    runtime.eval("1 + 1").unwrap();
+
+    let shared = Arc::clone(&runtime);
+    let modules = thread::spawn(move || shared.timings().modules)
+        .join()
+        .unwrap();
+    assert_eq!(modules, 1);
+}
+
+#[test]
+fn test_runtime_moved_between_threads() {
+    let mut runtime = Runtime::new();
+    runtime
+        .eval("fn double(borrow x: number): number { return x * 2; }")
+        .unwrap();
+
+    let (runtime, value) = thread::spawn(move || {
+        let value = runtime.call("double", vec![Value::Number(21.0)]).unwrap();
+        (runtime, value)
+    })
+    .join()
+    .unwrap();
+    assert_eq!(value, Value::Number(42.0));
+    assert!(runtime.get_global("double").is_some());
+}
+
+#[test]
+fn test_runtime_interrupt_handle_from_shared_reference() {
+    let runtime = Arc::new(Runtime::new());
+    let shared = Arc::clone(&runtime);
+    thread::spawn(move || shared.interrupt_handle().interrupt())
+        .join()
+        .unwrap();
+}
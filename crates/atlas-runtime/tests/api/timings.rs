@@ -0,0 +1,62 @@
+//! Per-phase pipeline timings recorded by `Atlas` runs.
+
+use super::*;
+
+#[test]
+fn test_timings_after_eval() {
+    use atlas_runtime::timings::Phase;
+
+    let runtime = Atlas::new();
+    assert_eq!(runtime.timings().modules, 0);
+
+    runtime.eval("let x = 1 + 2; x * 3;").unwrap();
+    let timings = runtime.timings();
+    assert_eq!(timings.modules, 1);
+    assert!(timings.total() > Duration::ZERO);
+    assert!(timings.total() >= timings.get(Phase::Parse) + timings.get(Phase::Execute));
+}
+
+#[test]
+fn test_timings_count_every_module_of_a_file() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("math.atl"),
+        "export fn triple(x: number): number { return x * 3; }",
+    )
+    .unwrap();
+    let main_path = temp_dir.path().join("main.atl");
+    std::fs::write(&main_path, "import { triple } from \"./math\";\ntriple(4);").unwrap();
+
+    let runtime = Atlas::new_with_security(atlas_runtime::SecurityContext::allow_all());
+    runtime.eval_file(main_path.to_str().unwrap()).unwrap();
+    assert_eq!(runtime.timings().modules, 2);
+
+    // Each run replaces the previous report rather than adding to it
+    runtime.eval("1;").unwrap();
+    assert_eq!(runtime.timings().modules, 1);
+}
+
+#[test]
+fn test_timings_reports() {
+    use atlas_runtime::timings::{Phase, PhaseTimings};
+
+    let mut timings = PhaseTimings::new();
+    timings.modules = 2;
+    timings.add(Phase::Parse, Duration::from_millis(3));
+    timings.add(Phase::Execute, Duration::from_millis(1));
+    assert_eq!(timings.total(), Duration::from_millis(4));
+
+    let json = timings.to_json();
+    assert_eq!(json["modules"], 2);
+    assert_eq!(json["total_ms"], 4.0);
+    let phases = json["phases"].as_array().unwrap();
+    assert_eq!(phases.len(), Phase::ALL.len());
+    assert_eq!(phases[1]["phase"], "parse");
+    assert_eq!(phases[1]["ms"], 3.0);
+
+    let table = timings.to_table();
+    assert!(table
+        .lines()
+        .any(|l| l.starts_with("parse") && l.contains("75.0%")));
+    assert!(table.contains("(2 modules)"), "{}", table);
+}
@@ -7,7 +7,6 @@ use atlas_runtime::security::SecurityContext;
 use atlas_runtime::span::Span;
 use atlas_runtime::stdlib::async_io;
 use atlas_runtime::value::Value;
-use atlas_runtime::*;
 use rstest::*;
 use std::fs;
 use tempfile::TempDir;
@@ -18,6 +17,8 @@ mod async_parity;
 mod async_runtime_loops;
 #[path = "async_runtime/async_vm.rs"]
 mod async_vm;
+#[path = "async_runtime/await_pending.rs"]
+mod await_pending;
 #[path = "async_runtime/compiler.rs"]
 mod compiler;
 #[path = "async_runtime/concurrency.rs"]
@@ -26,6 +27,8 @@ mod concurrency;
 mod stdlib_wiring;
 #[path = "async_runtime/stress.rs"]
 mod stress;
+#[path = "async_runtime/suspension.rs"]
+mod suspension;
 #[path = "async_runtime/value_future.rs"]
 mod value_future;
 
@@ -1004,7 +1007,8 @@ fn test_append_empty_string() {
 fn test_get_request_async() {
     let args = [Value::string("https://httpbin.org/get")];
 
-    let result = async_io::http_get_async(&args, Span::dummy()).unwrap();
+    let result =
+        async_io::http_get_async(&args, Span::dummy(), &SecurityContext::allow_all()).unwrap();
 
     let future = match result {
         Value::Future(f) => f,
@@ -1029,7 +1033,8 @@ fn test_post_request_async() {
         Value::string("test data"),
     ];
 
-    let result = async_io::http_post_async(&args, Span::dummy()).unwrap();
+    let result =
+        async_io::http_post_async(&args, Span::dummy(), &SecurityContext::allow_all()).unwrap();
 
     let future = match result {
         Value::Future(f) => f,
@@ -1054,7 +1059,8 @@ fn test_put_request_async() {
         Value::string("updated data"),
     ];
 
-    let result = async_io::http_put_async(&args, Span::dummy()).unwrap();
+    let result =
+        async_io::http_put_async(&args, Span::dummy(), &SecurityContext::allow_all()).unwrap();
 
     let future = match result {
         Value::Future(f) => f,
@@ -1069,7 +1075,8 @@ fn test_put_request_async() {
 fn test_delete_request_async() {
     let args = [Value::string("https://httpbin.org/delete")];
 
-    let result = async_io::http_delete_async(&args, Span::dummy()).unwrap();
+    let result =
+        async_io::http_delete_async(&args, Span::dummy(), &SecurityContext::allow_all()).unwrap();
 
     let future = match result {
         Value::Future(f) => f,
@@ -1086,9 +1093,12 @@ fn test_multiple_concurrent_requests() {
     let args2 = [Value::string("https://httpbin.org/headers")];
     let args3 = [Value::string("https://httpbin.org/user-agent")];
 
-    let r1 = async_io::http_get_async(&args1, Span::dummy()).unwrap();
-    let r2 = async_io::http_get_async(&args2, Span::dummy()).unwrap();
-    let r3 = async_io::http_get_async(&args3, Span::dummy()).unwrap();
+    let r1 =
+        async_io::http_get_async(&args1, Span::dummy(), &SecurityContext::allow_all()).unwrap();
+    let r2 =
+        async_io::http_get_async(&args2, Span::dummy(), &SecurityContext::allow_all()).unwrap();
+    let r3 =
+        async_io::http_get_async(&args3, Span::dummy(), &SecurityContext::allow_all()).unwrap();
 
     // All should complete
     match r1 {
@@ -1119,7 +1129,8 @@ fn test_request_timeout() {
     .with_timeout(1); // 1 second timeout
 
     let args = [Value::HttpRequest(Arc::new(request))];
-    let result = async_io::http_send_async(&args, Span::dummy()).unwrap();
+    let result =
+        async_io::http_send_async(&args, Span::dummy(), &SecurityContext::allow_all()).unwrap();
 
     let future = match result {
         Value::Future(f) => f,
@@ -1144,7 +1155,8 @@ fn test_network_error_handling() {
         "https://this-domain-does-not-exist-12345.com",
     )];
 
-    let result = async_io::http_get_async(&args, Span::dummy()).unwrap();
+    let result =
+        async_io::http_get_async(&args, Span::dummy(), &SecurityContext::allow_all()).unwrap();
 
     let future = match result {
         Value::Future(f) => f,
@@ -1165,7 +1177,8 @@ fn test_network_error_handling() {
 fn test_large_response_handling() {
     let args = [Value::string("https://httpbin.org/bytes/100000")];
 
-    let result = async_io::http_get_async(&args, Span::dummy()).unwrap();
+    let result =
+        async_io::http_get_async(&args, Span::dummy(), &SecurityContext::allow_all()).unwrap();
 
     let future = match result {
         Value::Future(f) => f,
@@ -1190,8 +1203,10 @@ fn test_concurrent_requests_different_hosts() {
         "https://jsonplaceholder.typicode.com/todos/1",
     )];
 
-    let r1 = async_io::http_get_async(&args1, Span::dummy()).unwrap();
-    let r2 = async_io::http_get_async(&args2, Span::dummy()).unwrap();
+    let r1 =
+        async_io::http_get_async(&args1, Span::dummy(), &SecurityContext::allow_all()).unwrap();
+    let r2 =
+        async_io::http_get_async(&args2, Span::dummy(), &SecurityContext::allow_all()).unwrap();
 
     match r1 {
         Value::Future(f) => assert!(f.is_resolved()),
@@ -1213,7 +1228,8 @@ fn test_request_with_custom_headers_async() {
         .with_header("X-Custom-Header".to_string(), "test-value".to_string());
 
     let args = [Value::HttpRequest(Arc::new(request))];
-    let result = async_io::http_send_async(&args, Span::dummy()).unwrap();
+    let result =
+        async_io::http_send_async(&args, Span::dummy(), &SecurityContext::allow_all()).unwrap();
 
     let future = match result {
         Value::Future(f) => f,
@@ -1292,8 +1308,10 @@ fn test_parallel_http_requests_with_future_all() {
     let args1 = [Value::string("https://httpbin.org/get")];
     let args2 = [Value::string("https://httpbin.org/headers")];
 
-    let r1 = async_io::http_get_async(&args1, Span::dummy()).unwrap();
-    let r2 = async_io::http_get_async(&args2, Span::dummy()).unwrap();
+    let r1 =
+        async_io::http_get_async(&args1, Span::dummy(), &SecurityContext::allow_all()).unwrap();
+    let r2 =
+        async_io::http_get_async(&args2, Span::dummy(), &SecurityContext::allow_all()).unwrap();
 
     let futures = vec![
         match r1 {
@@ -1520,7 +1538,8 @@ fn test_await_resolved_future() {
 #[ignore = "requires network"]
 fn test_await_rejected_future() {
     let args = [Value::string("https://this-will-fail.invalid")];
-    let future_result = async_io::http_get_async(&args, Span::dummy()).unwrap();
+    let future_result =
+        async_io::http_get_async(&args, Span::dummy(), &SecurityContext::allow_all()).unwrap();
 
     // Await should return error
     let await_args = [future_result];
@@ -0,0 +1,91 @@
+//! `async_runtime::wait_for` — how a wait with no frame to suspend blocks on a pending future.
+//!
+//! Tests cover:
+//! - Waiting on a worker task's join future
+//! - Several threads waiting on the same and on separate futures at once
+//! - Orphaned futures, including one settled while the orphan check runs
+
+use super::concurrency::ensure_pool;
+use atlas_runtime::async_runtime::{self, spawn_task, AtlasFuture, TaskStatus};
+use atlas_runtime::value::Value;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn wait_for_join_blocks_until_task_completes() {
+    ensure_pool();
+    let handle = spawn_task(
+        async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Value::Number(7.0)
+        },
+        None,
+    );
+    let result = async_runtime::wait_for(&handle.join()).expect("join can settle");
+    assert_eq!(result, Ok(Value::Number(7.0)));
+    assert_eq!(handle.status(), TaskStatus::Completed);
+}
+
+#[test]
+fn concurrent_waits_on_one_future_all_settle() {
+    let future = AtlasFuture::new_pending();
+    let waiters: Vec<_> = (0..4)
+        .map(|_| {
+            let future = future.clone();
+            thread::spawn(move || async_runtime::wait_for(&future))
+        })
+        .collect();
+
+    thread::sleep(Duration::from_millis(20));
+    future.resolve(Value::Number(3.0));
+    for waiter in waiters {
+        assert_eq!(waiter.join().unwrap(), Ok(Ok(Value::Number(3.0))));
+    }
+}
+
+#[test]
+fn concurrent_waits_on_separate_tasks() {
+    ensure_pool();
+    let handles: Vec<_> = (0..3)
+        .map(|i| {
+            spawn_task(
+                async move {
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    Value::Number(i as f64)
+                },
+                None,
+            )
+        })
+        .collect();
+    let waiters: Vec<_> = handles
+        .iter()
+        .map(|handle| {
+            let join = handle.join();
+            thread::spawn(move || async_runtime::wait_for(&join))
+        })
+        .collect();
+
+    for (i, waiter) in waiters.into_iter().enumerate() {
+        assert_eq!(waiter.join().unwrap(), Ok(Ok(Value::Number(i as f64))));
+    }
+}
+
+#[test]
+fn wait_for_orphaned_pending_future_errors() {
+    let future = AtlasFuture::new_pending();
+    assert!(async_runtime::wait_for(&future).is_err());
+}
+
+#[test]
+fn wait_for_sees_value_settled_while_checking_orphan() {
+    // The settler resolves and drops its handle while `wait_for` runs; however
+    // the two interleave, the value must come back rather than an orphan error.
+    for _ in 0..500 {
+        let future = AtlasFuture::new_pending();
+        let settler = future.clone();
+        let thread = thread::spawn(move || settler.resolve(Value::Number(1.0)));
+        let result = async_runtime::wait_for(&future);
+        thread.join().unwrap();
+        assert_eq!(result, Ok(Ok(Value::Number(1.0))));
+    }
+}
@@ -30,7 +30,7 @@ static POOL_INIT: Once = Once::new();
 /// Ensure the worker pool is initialised before any concurrency test runs.
 ///
 /// Uses `std::sync::Once` so concurrent test execution is safe.
-fn ensure_pool() {
+pub(super) fn ensure_pool() {
     POOL_INIT.call_once(|| {
         // Build a minimal VM (empty bytecode) to seed worker VMs.
         let base_vm = VM::new(Bytecode::default());
@@ -72,7 +72,7 @@ fn worker_pool_zero_uses_hardware_concurrency() {
     let base_vm = VM::new(Bytecode::default());
     let pool = WorkerPool::new(0, &base_vm);
     // hardware concurrency is ≥ 1 on any reasonable machine
-    assert!(pool.len() >= 1);
+    assert!(!pool.is_empty());
 }
 
 // ── TaskHandle lifecycle ──────────────────────────────────────────────────────
@@ -0,0 +1,242 @@
+//! Suspending `async fn` frames at `await` and resuming them from the VM's
+//! scheduler.
+//!
+//! Tests cover:
+//! - Async fns interleaving at their awaits instead of blocking in turn
+//! - Callers running on while a called async fn is suspended
+//! - Work started without an `await` finishing before the run ends
+//! - Errors raised after a suspension: caught, awaited, and unobserved
+//! - `try` blocks and deferred blocks living across a suspension
+
+use atlas_runtime::api::Runtime;
+use atlas_runtime::value::Value;
+use std::time::{Duration, Instant};
+
+fn eval(code: &str) -> Value {
+    let mut rt = Runtime::new();
+    rt.eval(code).expect("eval failed")
+}
+
+fn eval_err(code: &str) -> String {
+    let mut rt = Runtime::new();
+    rt.eval(code)
+        .err()
+        .map(|e| format!("{}", e))
+        .unwrap_or_default()
+}
+
+fn assert_string(code: &str, expected: &str) {
+    assert_eq!(eval(code), Value::string(expected));
+}
+
+#[test]
+fn awaits_interleave_instead_of_blocking_in_turn() {
+    assert_string(
+        r#"
+        let mut log = "";
+        async fn step(borrow name: string, borrow ms: number): string {
+            log = log + name + ":start ";
+            await task.sleep(ms);
+            log = log + name + ":end ";
+            return name;
+        }
+        let slow = step("slow", 60);
+        let fast = step("fast", 5);
+        await slow;
+        await fast;
+        log;
+    "#,
+        "slow:start fast:start fast:end slow:end ",
+    );
+}
+
+#[test]
+fn concurrent_sleeps_overlap() {
+    let started = Instant::now();
+    let result = eval(
+        r#"
+        async fn nap(): number {
+            await task.sleep(150);
+            return 1;
+        }
+        let a = nap();
+        let b = nap();
+        let c = nap();
+        (await a) + (await b) + (await c);
+    "#,
+    );
+    assert_eq!(result, Value::Number(3.0));
+    // Three sequential sleeps would take at least 450ms
+    assert!(started.elapsed() < Duration::from_millis(400));
+}
+
+#[test]
+fn caller_runs_while_callee_is_suspended() {
+    assert_string(
+        r#"
+        let mut log = "";
+        async fn fetch(): number {
+            await task.sleep(10);
+            log = log + "fetched ";
+            return 42;
+        }
+        let pending = fetch();
+        log = log + "caller ";
+        let value = await pending;
+        log + value.toString();
+    "#,
+        "caller fetched 42",
+    );
+}
+
+#[test]
+fn nested_async_calls_resume_through_each_level() {
+    let result = eval(
+        r#"
+        async fn leaf(borrow n: number): number {
+            await task.sleep(5);
+            return n * 2;
+        }
+        async fn middle(borrow n: number): number {
+            let doubled = await leaf(n);
+            await task.sleep(5);
+            return doubled + 1;
+        }
+        async fn top(): number {
+            return (await middle(1)) + (await middle(10));
+        }
+        await top();
+    "#,
+    );
+    assert_eq!(result, Value::Number(24.0));
+}
+
+#[test]
+fn unawaited_async_fn_finishes_before_run_ends() {
+    assert_string(
+        r#"
+        let mut log = "";
+        async fn background(): void {
+            await task.sleep(20);
+            log = log + "background done";
+        }
+        background();
+        log = log + "main done; ";
+        log;
+    "#,
+        "main done; ",
+    );
+
+    // The program's value is taken before the task resumes, but the run
+    // still finishes the task before returning
+    let mut rt = Runtime::new();
+    rt.eval(
+        r#"
+        let mut log = "";
+        async fn background(): void {
+            await task.sleep(20);
+            log = log + "background done";
+        }
+        background();
+    "#,
+    )
+    .expect("eval failed");
+    assert_eq!(rt.eval("log;").unwrap(), Value::string("background done"));
+}
+
+#[test]
+fn error_after_suspension_rejects_awaited_future() {
+    let err = eval_err(
+        r#"
+        async fn broken(): number {
+            await task.sleep(5);
+            let xs = [1, 2];
+            return xs[5];
+        }
+        await broken();
+    "#,
+    );
+    assert!(err.contains("Awaited future was rejected"), "{err}");
+}
+
+#[test]
+fn unobserved_failure_fails_the_run() {
+    let err = eval_err(
+        r#"
+        async fn broken(): void {
+            await task.sleep(5);
+            let xs = [1, 2];
+            xs[5];
+        }
+        broken();
+        1;
+    "#,
+    );
+    assert!(err.contains("out of bounds"), "{err}");
+}
+
+#[test]
+fn try_block_catches_across_a_suspension() {
+    assert_string(
+        r#"
+        async fn guarded(): string {
+            let mut outcome = "none";
+            try {
+                await task.sleep(5);
+                let xs = [1, 2];
+                xs[5];
+                outcome = "unreachable";
+            } catch (e) {
+                outcome = e.kind;
+            }
+            return outcome;
+        }
+        await guarded();
+    "#,
+        "OutOfBounds",
+    );
+}
+
+#[test]
+fn caller_catches_rejection_of_suspended_callee() {
+    assert_string(
+        r#"
+        async fn broken(): number {
+            await task.sleep(5);
+            let xs = [1, 2];
+            return xs[5];
+        }
+        async fn caller(): string {
+            try {
+                await broken();
+                return "unreachable";
+            } catch {
+                return "caught";
+            }
+        }
+        await caller();
+    "#,
+        "caught",
+    );
+}
+
+#[test]
+fn defer_runs_when_resumed_frame_returns() {
+    assert_string(
+        r#"
+        let mut log = "";
+        fn note(borrow entry: string): void {
+            log = log + entry;
+        }
+        async fn work(): number {
+            defer { note("deferred"); }
+            await task.sleep(5);
+            log = log + "body ";
+            return 1;
+        }
+        await work();
+        log;
+    "#,
+        "body deferred",
+    );
+}
@@ -11,7 +11,9 @@ use atlas_runtime::optimizer::{
     PeepholePass,
 };
 use atlas_runtime::parser::Parser;
-use atlas_runtime::profiler::{HotspotDetector, ProfileCollector, ProfileReport, Profiler};
+use atlas_runtime::profiler::{
+    AllocationKind, HotspotDetector, ProfileCollector, ProfileReport, Profiler, StackWeight,
+};
 use atlas_runtime::security::SecurityContext;
 use atlas_runtime::span::Span;
 use atlas_runtime::typechecker::TypeChecker;
@@ -78,6 +80,12 @@ mod bytecode_optimizer;
 #[path = "bytecode/profiler.rs"]
 mod bytecode_profiler;
 
+#[path = "bytecode/profiler_call_tree.rs"]
+mod bytecode_profiler_call_tree;
+
+#[path = "bytecode/profiler_allocations.rs"]
+mod bytecode_profiler_allocations;
+
 #[path = "bytecode/parity.rs"]
 mod bytecode_parity;
 
@@ -133,6 +133,30 @@ fn test_bytecode_roundtrip_complex_program() {
     assert_eq!(original_result, Some(Value::Number(120.0)));
 }
 
+#[test]
+fn test_bytecode_roundtrip_global_names() {
+    let source = r#"
+        fn double(borrow n: number): number {
+            return n * 2;
+        }
+        fn quad(borrow n: number): number {
+            return double(double(n));
+        }
+        quad(3);
+    "#;
+    let bc = compile_full(source);
+    // Each global is interned once, however often it is referenced
+    let mut names = bc.global_names.clone();
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), bc.global_names.len());
+    assert!(bc.global_names.iter().any(|name| name == "double"));
+
+    let restored = Bytecode::from_bytes(&bc.to_bytes()).expect("Failed to deserialize");
+    assert_eq!(restored.global_names, bc.global_names);
+    assert_eq!(run(restored), Some(Value::Number(12.0)));
+}
+
 #[test]
 fn test_bytecode_checksum_detects_corruption() {
     let source = "42";
@@ -573,6 +573,7 @@ mod migrated_profiler_report {
                 percentage: 30.0,
                 opcode: Some(atlas_runtime::bytecode::Opcode::Loop),
             }],
+            allocations: None,
         }
     }
 
@@ -608,6 +609,7 @@ mod migrated_profiler_report {
             function_calls: 0,
             top_opcodes: vec![],
             hotspots: vec![],
+            allocations: None,
         };
         let s = r.format_summary();
         assert!(s.contains("500 instructions"));
@@ -649,6 +651,7 @@ mod migrated_profiler_report {
             function_calls: 0,
             top_opcodes: vec![],
             hotspots: vec![],
+            allocations: None,
         };
         let s = r.format_detailed();
         assert!(s.contains("No hotspots"), "got: {}", s);
@@ -665,6 +668,7 @@ mod migrated_profiler_report {
             function_calls: 0,
             top_opcodes: vec![],
             hotspots: vec![],
+            allocations: None,
         };
         let s = r.format_opcode_table();
         assert!(s.contains("No opcodes"), "got: {}", s);
@@ -0,0 +1,147 @@
+//! Allocation profiling: counts and bytes by value kind, allocation sites,
+//! peak memory, and the report section.
+
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn profile_allocations(source: &str) -> VM {
+    let mut vm = VM::new(compile(source));
+    vm.enable_allocation_profiling();
+    vm.run(&SecurityContext::allow_all()).unwrap();
+    vm
+}
+
+const ALLOCATION_PROGRAM: &str = r#"
+fn label(n: number): string { return "item " + str(n); }
+fn pairs(n: number): number[] { return [n, n + 1]; }
+let mut i = 0;
+while (i < 5) {
+    let name = label(i);
+    let p = pairs(i);
+    i = i + 1;
+}
+"#;
+
+#[test]
+fn test_allocations_counted_by_kind() {
+    let vm = profile_allocations(ALLOCATION_PROGRAM);
+    let allocations = vm.profiler().unwrap().allocations().unwrap();
+
+    // One concatenation and one `str()` result per label
+    assert!(allocations.stats(AllocationKind::String).count >= 10);
+    // One pair per iteration
+    assert_eq!(allocations.stats(AllocationKind::Array).count, 5);
+    assert_eq!(allocations.stats(AllocationKind::Closure).count, 0);
+    let total = allocations.total();
+    assert!(total.bytes > 0);
+    assert_eq!(
+        total.count,
+        allocations
+            .by_kind()
+            .iter()
+            .map(|(_, stats)| stats.count)
+            .sum::<u64>()
+    );
+}
+
+#[test]
+fn test_allocation_sites_name_their_function() {
+    let vm = profile_allocations(ALLOCATION_PROGRAM);
+    let allocations = vm.profiler().unwrap().allocations().unwrap();
+
+    let pair_site = allocations
+        .sites()
+        .into_iter()
+        .find(|site| site.function == "pairs")
+        .expect("array literal in pairs");
+    assert_eq!(pair_site.stats.count, 5);
+    assert_eq!(pair_site.kinds[&AllocationKind::Array], 5);
+    assert!(pair_site.span.is_some());
+    assert!(allocations
+        .sites()
+        .iter()
+        .any(|site| site.function == "label" && site.kinds.contains_key(&AllocationKind::String)));
+}
+
+#[test]
+fn test_allocations_count_builtin_results() {
+    let vm = profile_allocations(
+        r#"
+fn double(borrow x: number): number { return x * 2; }
+let doubled = map([1, 2, 3], double);
+"#,
+    );
+    let allocations = vm.profiler().unwrap().allocations().unwrap();
+    // The literal and the array `map` returns
+    assert_eq!(allocations.stats(AllocationKind::Array).count, 2);
+}
+
+#[test]
+fn test_allocations_shared_values_not_counted() {
+    let vm = profile_allocations(
+        r#"
+let a = [1, 2, 3];
+let b = a;
+let c = b;
+"#,
+    );
+    let allocations = vm.profiler().unwrap().allocations().unwrap();
+    assert_eq!(allocations.stats(AllocationKind::Array).count, 1);
+}
+
+#[test]
+fn test_allocations_peak_memory() {
+    let vm = profile_allocations(
+        r#"
+let mut text = "";
+let mut i = 0;
+while (i < 5000) { text = text + "x"; i = i + 1; }
+"#,
+    );
+    let allocations = vm.profiler().unwrap().allocations().unwrap();
+    // The 5000-byte string is reachable from `text` when the program halts
+    assert!(
+        allocations.peak_bytes() >= 5000,
+        "peak: {}",
+        allocations.peak_bytes()
+    );
+}
+
+#[test]
+fn test_allocations_in_report() {
+    let vm = profile_allocations(ALLOCATION_PROGRAM);
+    let report = vm.profiler().unwrap().generate_report(1.0);
+    let allocations = report.allocations.as_ref().expect("allocation report");
+    assert!(allocations.sites.len() <= 20);
+    assert!(allocations
+        .sites
+        .iter()
+        .any(|site| site.function == "pairs"));
+
+    let text = report.format_detailed();
+    assert!(text.contains("[ Allocations ]"), "got: {}", text);
+    assert!(text.contains("[ Allocation Sites ]"), "got: {}", text);
+    assert!(text.contains("string"), "got: {}", text);
+    assert!(text.contains("pairs"), "got: {}", text);
+}
+
+#[test]
+fn test_allocations_off_by_default() {
+    let mut vm = VM::with_profiling(compile(ALLOCATION_PROGRAM));
+    vm.run(&SecurityContext::allow_all()).unwrap();
+    let profiler = vm.profiler().unwrap();
+    assert!(profiler.allocations().is_none());
+    let report = profiler.generate_report(1.0);
+    assert!(report.allocations.is_none());
+    assert!(!report.format_detailed().contains("[ Allocations ]"));
+}
+
+#[test]
+fn test_allocations_reset() {
+    let mut vm = profile_allocations(ALLOCATION_PROGRAM);
+    let profiler = vm.profiler_mut().unwrap();
+    profiler.reset();
+    let allocations = profiler.allocations().expect("tracking stays on");
+    assert_eq!(allocations.total().count, 0);
+    assert_eq!(allocations.peak_bytes(), 0);
+}
@@ -0,0 +1,142 @@
+//! Profiler call tree: per-path call counts, inclusive and exclusive time,
+//! and folded stacks for flamegraphs.
+
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn profile_source(source: &str) -> VM {
+    let mut vm = VM::with_profiling(compile(source));
+    vm.run(&SecurityContext::allow_all()).unwrap();
+    vm
+}
+
+const CALL_TREE_PROGRAM: &str = r#"
+fn leaf(x: number): number { return x + 1; }
+fn middle(x: number): number { return leaf(x) + leaf(x); }
+fn fib(n: number): number {
+    if (n < 2) { return n; }
+    return fib(n - 1) + fib(n - 2);
+}
+let a = middle(1);
+let b = leaf(2);
+let c = fib(3);
+"#;
+
+#[test]
+fn test_call_tree_paths_and_calls() {
+    let vm = profile_source(CALL_TREE_PROGRAM);
+    let tree = vm.profiler().unwrap().call_tree();
+
+    let main = tree.find(&["<main>"]).expect("main frame");
+    assert_eq!(tree.node(main).calls, 1);
+    // The same function reached through different callers is two paths
+    let nested_leaf = tree.find(&["<main>", "middle", "leaf"]).unwrap();
+    let direct_leaf = tree.find(&["<main>", "leaf"]).unwrap();
+    assert_ne!(nested_leaf, direct_leaf);
+    assert_eq!(tree.node(nested_leaf).calls, 2);
+    assert_eq!(tree.node(direct_leaf).calls, 1);
+    // Recursion nests: fib(3) -> fib(2), fib(1) -> fib(1), fib(0)
+    assert_eq!(tree.node(tree.find(&["<main>", "fib"]).unwrap()).calls, 1);
+    assert_eq!(
+        tree.node(tree.find(&["<main>", "fib", "fib"]).unwrap())
+            .calls,
+        2
+    );
+    assert_eq!(
+        tree.node(tree.find(&["<main>", "fib", "fib", "fib"]).unwrap())
+            .calls,
+        2
+    );
+    assert!(tree.find(&["<main>", "fib", "fib", "fib", "fib"]).is_none());
+}
+
+#[test]
+fn test_call_tree_attributes_every_instruction() {
+    let vm = profile_source(CALL_TREE_PROGRAM);
+    let profiler = vm.profiler().unwrap();
+    let tree = profiler.call_tree();
+
+    let root = tree.root();
+    assert_eq!(tree.node(root).self_instructions, 0);
+    assert_eq!(
+        tree.inclusive_instructions(root),
+        profiler.total_instructions()
+    );
+    let middle = tree.find(&["<main>", "middle"]).unwrap();
+    let leaf = tree.find(&["<main>", "middle", "leaf"]).unwrap();
+    assert!(tree.node(leaf).self_instructions > 0);
+    assert_eq!(
+        tree.inclusive_instructions(middle),
+        tree.node(middle).self_instructions + tree.node(leaf).self_instructions
+    );
+}
+
+#[test]
+fn test_call_tree_exclusive_time_within_inclusive() {
+    let vm = profile_source(CALL_TREE_PROGRAM);
+    let tree = vm.profiler().unwrap().call_tree();
+
+    let main = tree.find(&["<main>"]).unwrap();
+    let fib = tree.find(&["<main>", "fib"]).unwrap();
+    assert!(tree.exclusive_time(main) <= tree.inclusive_time(main));
+    assert!(tree.inclusive_time(fib) <= tree.inclusive_time(main));
+    assert_eq!(tree.inclusive_time(tree.root()), tree.inclusive_time(main));
+}
+
+#[test]
+fn test_call_tree_counts_callbacks_from_builtins() {
+    let vm = profile_source(
+        r#"
+fn double(borrow x: number): number { return x * 2; }
+let doubled = map([1, 2, 3], double);
+"#,
+    );
+    let profiler = vm.profiler().unwrap();
+    let double = profiler.call_tree().find(&["<main>", "double"]).unwrap();
+    assert_eq!(profiler.call_tree().node(double).calls, 3);
+    assert_eq!(profiler.collector().function_call_counts()["double"], 3);
+}
+
+#[test]
+fn test_call_tree_folded_stacks() {
+    let vm = profile_source(CALL_TREE_PROGRAM);
+    let tree = vm.profiler().unwrap().call_tree();
+    let folded = tree.folded_stacks(StackWeight::Instructions);
+
+    let weight = |stack: &str| -> u64 {
+        folded
+            .lines()
+            .find_map(|line| line.strip_prefix(stack)?.strip_prefix(' '))
+            .unwrap_or_else(|| panic!("no `{}` line in:\n{}", stack, folded))
+            .parse()
+            .unwrap()
+    };
+    let leaf = tree.find(&["<main>", "middle", "leaf"]).unwrap();
+    assert_eq!(
+        weight("<main>;middle;leaf"),
+        tree.node(leaf).self_instructions
+    );
+    weight("<main>;fib;fib;fib");
+    let total: u64 = folded
+        .lines()
+        .map(|line| line.rsplit(' ').next().unwrap().parse::<u64>().unwrap())
+        .sum();
+    assert_eq!(total, vm.profiler().unwrap().total_instructions());
+}
+
+#[test]
+fn test_call_tree_format_and_reset() {
+    let mut vm = profile_source(CALL_TREE_PROGRAM);
+    let text = vm.profiler().unwrap().call_tree().format_tree();
+    assert!(text.contains("Function"), "got: {}", text);
+    assert!(text.contains("<main>"), "got: {}", text);
+    assert!(text.contains("    leaf"), "got: {}", text);
+
+    let profiler = vm.profiler_mut().unwrap();
+    profiler.reset();
+    assert!(profiler
+        .call_tree()
+        .node(profiler.call_tree().root())
+        .children
+        .is_empty());
+}
@@ -10,6 +10,13 @@ use atlas_runtime::security::SecurityContext;
 use atlas_runtime::typechecker::TypeChecker;
 use atlas_runtime::vm::VM;
 
+#[path = "bytecode_roundtrip/atbc_files.rs"]
+mod atbc_files;
+#[path = "bytecode_roundtrip/module_cache.rs"]
+mod module_cache;
+#[path = "bytecode_roundtrip/package_cache.rs"]
+mod package_cache;
+
 fn compile_and_run(source: &str) -> Result<(), String> {
     let mut lexer = Lexer::new(source);
     let (tokens, _) = lexer.tokenize();
@@ -48,6 +55,10 @@ fn compile_roundtrip_and_run(source: &str) -> Result<(), String> {
         bytecode.top_level_local_count, restored.top_level_local_count,
         "top_level_local_count should survive serialization round-trip"
     );
+    assert_eq!(
+        bytecode.statement_offsets, restored.statement_offsets,
+        "statement offsets should survive serialization round-trip"
+    );
 
     // Run the restored bytecode
     let mut vm = VM::new(restored);
@@ -172,7 +183,7 @@ fn test_h289_multiple_evals_fresh_vm_should_fail() {
         }
 
         vm.run(&security).map_err(|e| format!("{}", e))?;
-        Ok(vm.get_globals().clone())
+        Ok(vm.get_globals())
     }
 
     // First eval: define a function
@@ -248,7 +259,7 @@ fn test_h289_multiple_evals_accumulated_bytecode() {
 
         // Copy globals back for persistence
         for (name, value) in vm.get_globals() {
-            globals.insert(name.clone(), value.clone());
+            globals.insert(name, value);
         }
 
         Ok(())
@@ -482,3 +493,12 @@ fn test_optimizer_preserves_for_in_loops() {
     let mut vm_opt = VM::new(bytecode_opt);
     vm_opt.run(&security).expect("Optimized should run");
 }
+
+#[test]
+fn test_regex_literal_flags_survive_roundtrip() {
+    let source = r#"
+        let r = /^hello$/i;
+        test.assert(r.isMatch("HELLO"), "flags lost in serialization");
+    "#;
+    compile_roundtrip_and_run(source).expect("Roundtrip execution should work");
+}
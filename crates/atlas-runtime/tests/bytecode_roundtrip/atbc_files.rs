@@ -0,0 +1,76 @@
+//! `.atbc` files: `Compiler::compile_to_file` and `VM::from_file`
+
+use super::*;
+
+fn parse(source: &str) -> atlas_runtime::ast::Program {
+    let mut lexer = Lexer::new(source);
+    let (tokens, _) = lexer.tokenize();
+    let mut parser = Parser::new(tokens);
+    let (ast, _) = parser.parse();
+    ast
+}
+
+#[test]
+fn test_compile_to_file_runs_without_source() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("main.atbc");
+    let ast = parse(
+        r#"
+        fn square(n: number): number { return n * n; }
+        let mut total: number = 0;
+        for i in 0..4 {
+            total = total + square(i);
+        }
+        total;
+    "#,
+    );
+    Compiler::new()
+        .compile_to_file(&ast, &path)
+        .expect("compile_to_file should succeed");
+
+    let mut vm = VM::from_file(&path).expect("VM::from_file should load the program");
+    let value = vm.run(&SecurityContext::allow_all()).unwrap();
+    assert_eq!(value, Some(atlas_runtime::Value::Number(14.0)));
+}
+
+#[test]
+fn test_from_file_rejects_corrupted_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("main.atbc");
+    Compiler::new()
+        .compile_to_file(&parse("let x = 1 + 2; x;"), &path)
+        .unwrap();
+
+    let mut bytes = std::fs::read(&path).unwrap();
+    let middle = bytes.len() / 2;
+    bytes[middle] ^= 0xFF;
+    std::fs::write(&path, &bytes).unwrap();
+
+    let err = VM::from_file(&path)
+        .err()
+        .expect("corruption must be detected");
+    assert!(err.contains("checksum mismatch"), "{}", err);
+}
+
+#[test]
+fn test_from_file_reports_missing_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("missing.atbc");
+    let err = Bytecode::read_from_file(&path).unwrap_err();
+    assert!(err.contains("missing.atbc"), "{}", err);
+}
+
+#[test]
+fn test_untrusted_bytes_are_validated() {
+    use atlas_runtime::bytecode::Opcode;
+    use atlas_runtime::span::Span;
+
+    // Well-formed container, but the instruction stream pops an empty stack
+    let mut bytecode = Bytecode::new();
+    bytecode.emit(Opcode::Pop, Span::dummy());
+    let bytes = bytecode.to_bytes();
+
+    assert!(Bytecode::from_bytes(&bytes).is_ok());
+    let err = Bytecode::from_untrusted_bytes(&bytes).unwrap_err();
+    assert!(err.contains("invalid bytecode"), "{}", err);
+}
@@ -0,0 +1,76 @@
+//! Shared module cache: compiled modules reused across runtimes
+
+use super::package_cache::cached_project;
+use super::*;
+use atlas_runtime::{Atlas, ModuleCache};
+
+fn run_with_module_cache(
+    dir: &tempfile::TempDir,
+    cache: &std::sync::Arc<ModuleCache>,
+) -> atlas_runtime::Value {
+    Atlas::new_with_security(SecurityContext::allow_all())
+        .with_module_cache(std::sync::Arc::clone(cache))
+        .eval_file(dir.path().join("main.atlas").to_str().unwrap())
+        .unwrap()
+}
+
+#[test]
+fn test_module_cache_reuses_modules_across_runtimes() {
+    let dir = cached_project("export fn answer(): number { return 42; }");
+    let cache = std::sync::Arc::new(ModuleCache::new());
+
+    assert_eq!(
+        run_with_module_cache(&dir, &cache),
+        atlas_runtime::Value::Number(42.0)
+    );
+    assert_eq!((cache.hits(), cache.misses()), (0, 2));
+    assert_eq!(cache.len(), 2);
+
+    assert_eq!(
+        run_with_module_cache(&dir, &cache),
+        atlas_runtime::Value::Number(42.0)
+    );
+    assert_eq!((cache.hits(), cache.misses()), (2, 2));
+}
+
+#[test]
+fn test_module_cache_invalidates_changed_module_and_importers() {
+    let dir = cached_project("export fn answer(): number { return 1; }");
+    let cache = std::sync::Arc::new(ModuleCache::new());
+    run_with_module_cache(&dir, &cache);
+
+    std::fs::write(
+        dir.path().join("vendor/lib.atlas"),
+        "export fn answer(): number { return 2; }",
+    )
+    .unwrap();
+    assert_eq!(
+        run_with_module_cache(&dir, &cache),
+        atlas_runtime::Value::Number(2.0)
+    );
+    // Only the changed file is parsed again
+    assert_eq!((cache.hits(), cache.misses()), (1, 3));
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(
+        run_with_module_cache(&dir, &cache),
+        atlas_runtime::Value::Number(2.0)
+    );
+}
+
+#[test]
+fn test_module_cache_reports_parse_errors_again() {
+    let dir = cached_project("export fn answer(): number { return ; }");
+    let cache = std::sync::Arc::new(ModuleCache::new());
+    let run = || {
+        Atlas::new_with_security(SecurityContext::allow_all())
+            .with_module_cache(std::sync::Arc::clone(&cache))
+            .eval_file(dir.path().join("main.atlas").to_str().unwrap())
+            .unwrap_err()
+    };
+    let first = run();
+    assert!(!first.is_empty());
+    assert_eq!(run(), first);
+    assert_eq!(cache.hits(), 2);
+}
@@ -0,0 +1,91 @@
+//! Package bytecode cache: compiled dependencies reused across runs
+
+use super::*;
+use atlas_runtime::bytecode::BytecodeCache;
+use atlas_runtime::Atlas;
+
+/// A project whose `vendor/` directory stands in for the package cache
+pub(crate) fn cached_project(lib_source: &str) -> tempfile::TempDir {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir(dir.path().join("vendor")).unwrap();
+    std::fs::write(dir.path().join("vendor/lib.atlas"), lib_source).unwrap();
+    std::fs::write(
+        dir.path().join("main.atlas"),
+        r#"import { answer } from "./vendor/lib"; answer();"#,
+    )
+    .unwrap();
+    dir
+}
+
+fn run_cached(dir: &tempfile::TempDir) -> (atlas_runtime::Value, usize, usize) {
+    let cache = BytecodeCache::new(dir.path().join("cache"), dir.path().join("vendor"));
+    let runtime = Atlas::new_with_security(SecurityContext::allow_all()).with_bytecode_cache(cache);
+    let value = runtime
+        .eval_file(dir.path().join("main.atlas").to_str().unwrap())
+        .unwrap();
+    let cache = runtime.bytecode_cache().unwrap();
+    (value, cache.hits(), cache.misses())
+}
+
+#[test]
+fn test_bytecode_cache_reuses_compiled_dependency() {
+    let dir = cached_project("export fn answer(): number { return 42; }");
+
+    let (value, hits, misses) = run_cached(&dir);
+    assert_eq!(value, atlas_runtime::Value::Number(42.0));
+    // Only the module under the cache's scope is cached
+    assert_eq!((hits, misses), (0, 1));
+    assert_eq!(
+        std::fs::read_dir(dir.path().join("cache")).unwrap().count(),
+        1
+    );
+
+    let (value, hits, misses) = run_cached(&dir);
+    assert_eq!(value, atlas_runtime::Value::Number(42.0));
+    assert_eq!((hits, misses), (1, 0));
+}
+
+#[test]
+fn test_bytecode_cache_invalidates_on_source_change() {
+    let dir = cached_project("export fn answer(): number { return 1; }");
+    run_cached(&dir);
+
+    std::fs::write(
+        dir.path().join("vendor/lib.atlas"),
+        "export fn answer(): number { return 2; }",
+    )
+    .unwrap();
+    let (value, hits, misses) = run_cached(&dir);
+    assert_eq!(value, atlas_runtime::Value::Number(2.0));
+    assert_eq!((hits, misses), (0, 1));
+}
+
+#[test]
+fn test_bytecode_cache_recompiles_corrupt_entry() {
+    let dir = cached_project("export fn answer(): number { return 7; }");
+    run_cached(&dir);
+
+    for entry in std::fs::read_dir(dir.path().join("cache")).unwrap() {
+        std::fs::write(entry.unwrap().path(), b"not bytecode").unwrap();
+    }
+    let (value, hits, misses) = run_cached(&dir);
+    assert_eq!(value, atlas_runtime::Value::Number(7.0));
+    assert_eq!((hits, misses), (0, 1));
+
+    // The entry was rewritten
+    let (_, hits, _) = run_cached(&dir);
+    assert_eq!(hits, 1);
+}
+
+#[test]
+fn test_bytecode_cache_key_depends_on_imports() {
+    let path = std::path::Path::new("/pkg/lib.atlas");
+    let source = "export fn f(): number { return 1; }";
+    let base = BytecodeCache::key(path, source, &[]);
+    assert_eq!(base, BytecodeCache::key(path, source, &[]));
+    assert_ne!(base, BytecodeCache::key(path, source, &["abc"]));
+    assert_ne!(
+        base,
+        BytecodeCache::key(std::path::Path::new("/pkg/other.atlas"), source, &[])
+    );
+}
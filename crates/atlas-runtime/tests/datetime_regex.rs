@@ -1796,3 +1796,24 @@ fn test_regex_split_n_limit_one_returns_two_parts() {
     "#;
     assert_eq!(eval_ok(code), "2");
 }
+
+#[test]
+fn test_regex_escape_embeds_user_input_in_larger_pattern() {
+    // Metacharacters in the input must match literally, not as syntax
+    let code = r#"
+        let needle = "(1+1)";
+        let r = unwrap(regex.new("^total: " + regex.escape(needle) + "$"));
+        r.isMatch("total: (1+1)") && !r.isMatch("total: 11")
+    "#;
+    assert_eq!(eval_ok(code), "true");
+}
+
+// ============================================================================
+// Submodules
+// ============================================================================
+
+#[path = "datetime_regex/timestamps.rs"]
+mod timestamps;
+
+#[path = "datetime_regex/regex_literals.rs"]
+mod regex_literals;
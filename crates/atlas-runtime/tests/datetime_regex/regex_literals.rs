@@ -0,0 +1,66 @@
+//! Regex literals: `/pattern/flags` evaluated and rejected at parse time
+
+use super::*;
+
+fn eval_error_codes(code: &str) -> Vec<String> {
+    let atlas = Atlas::new();
+    match atlas.eval(code) {
+        Ok(value) => panic!("expected an error, got {}", value),
+        Err(diags) => diags.into_iter().map(|d| d.code).collect(),
+    }
+}
+
+#[test]
+fn test_regex_literal_matches() {
+    let code = r#"
+        let r = /^\d{3}-\d{4}$/;
+        r.isMatch("555-1234") && !r.isMatch("5551234")
+    "#;
+    assert_eq!(eval_ok(code), "true");
+}
+
+#[test]
+fn test_regex_literal_flags() {
+    let code = r#"
+        /^hello$/i.isMatch("HeLLo") && /^b$/m.isMatch("a\nb")
+    "#;
+    assert_eq!(eval_ok(code), "true");
+}
+
+#[test]
+fn test_regex_literal_escaped_slash() {
+    let code = r#"
+        /^a\/b$/.isMatch("a/b")
+    "#;
+    assert_eq!(eval_ok(code), "true");
+}
+
+#[test]
+fn test_regex_literal_works_with_namespace_functions() {
+    let code = r#"
+        regex.isMatch(/^\w+@\w+$/, "me@host")
+    "#;
+    assert_eq!(eval_ok(code), "true");
+}
+
+#[test]
+fn test_regex_literal_does_not_change_division() {
+    let code = r#"
+        let a = 12;
+        let b = 3;
+        a / b / 2
+    "#;
+    assert_eq!(eval_ok(code), "2");
+}
+
+#[test]
+fn test_regex_literal_invalid_pattern_is_parse_error() {
+    let codes = eval_error_codes("let r = /(unclosed/;");
+    assert!(codes.contains(&"AT1024".to_string()), "{:?}", codes);
+}
+
+#[test]
+fn test_regex_literal_unknown_flag_is_parse_error() {
+    let codes = eval_error_codes("let r = /abc/g;");
+    assert!(codes.contains(&"AT1024".to_string()), "{:?}", codes);
+}
@@ -0,0 +1,96 @@
+//! Timestamp formatting and parsing
+
+use super::*;
+
+#[test]
+fn test_date_time_timestamp_is_current() {
+    let runtime = Atlas::new();
+    let result = runtime
+        .eval("DateTime.timestamp() - DateTime.now().toTimestamp()")
+        .unwrap();
+    assert!(matches!(result, Value::Number(n) if n.abs() <= 1.0));
+}
+
+#[test]
+fn test_date_time_format_date_utc() {
+    let runtime = Atlas::new();
+    let result = runtime
+        .eval(r#"DateTime.formatDate(1718448000, "%Y-%m-%d %H:%M %z")"#)
+        .unwrap();
+    assert_eq!(result, Value::string("2024-06-15 10:40 +0000"));
+}
+
+#[test]
+fn test_date_time_format_date_in_timezone() {
+    let runtime = Atlas::new();
+    let result = runtime
+        .eval(r#"DateTime.formatDate(1718448000, "%Y-%m-%d %H:%M %Z", "Asia/Tokyo")"#)
+        .unwrap();
+    assert_eq!(result, Value::string("2024-06-15 19:40 JST"));
+}
+
+#[test]
+fn test_date_time_format_date_invalid_format() {
+    assert!(eval_expect_error(r#"DateTime.formatDate(0, "%Q")"#));
+}
+
+#[test]
+fn test_date_time_parse_date_in_timezone() {
+    let runtime = Atlas::new();
+    // 09:00 CEST is 07:00 UTC
+    let result = runtime
+        .eval(r#"DateTime.parseDate("2024-06-15 09:00", "%Y-%m-%d %H:%M", "Europe/Paris")"#)
+        .unwrap();
+    assert_eq!(result, Value::Number(1718434800.0));
+}
+
+#[test]
+fn test_date_time_parse_date_only_is_midnight_utc() {
+    let runtime = Atlas::new();
+    let result = runtime
+        .eval(r#"DateTime.parseDate("2024-06-15", "%Y-%m-%d")"#)
+        .unwrap();
+    assert_eq!(result, Value::Number(1718409600.0));
+}
+
+#[test]
+fn test_date_time_parse_date_with_offset() {
+    let runtime = Atlas::new();
+    let result = runtime
+        .eval(r#"DateTime.parseDate("2024-06-15 09:00 +0200", "%Y-%m-%d %H:%M %z")"#)
+        .unwrap();
+    assert_eq!(result, Value::Number(1718434800.0));
+}
+
+#[test]
+fn test_date_time_parse_date_invalid_timezone() {
+    assert!(eval_expect_error(
+        r#"DateTime.parseDate("2024-06-15", "%Y-%m-%d", "Mars/Olympus")"#
+    ));
+}
+
+#[test]
+fn test_date_time_namespace_arithmetic() {
+    let runtime = Atlas::new();
+    let result = runtime
+        .eval(
+            r#"
+            let start = DateTime.parseIso("2024-01-01T00:00:00Z");
+            let end = DateTime.addDays(start, 2);
+            DateTime.diffSeconds(end, start) + end.diffSeconds(start)
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::Number(345600.0));
+}
+
+#[test]
+fn test_date_time_namespace_format() {
+    let runtime = Atlas::new();
+    let result = runtime
+        .eval(
+            r#"DateTime.format(DateTime.fromTimestamp(1718448000), "%d %B %Y") + " " + DateTime.durationFormat(DateTime.durationFromSeconds(5400))"#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::string("15 June 2024 1h 30m"));
+}
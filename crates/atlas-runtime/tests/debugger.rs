@@ -1,5 +1,7 @@
 //! THIN ROUTER — DO NOT ADD TESTS HERE.
-//! Add tests to the submodule files: tests/debugger/{breakpoints,session,inspector,protocol_part1,protocol_part2}.rs
+//! Add tests to the submodule files: tests/debugger/{breakpoints,session,stepping,break_on_error,
+//! watchpoints,evaluation,inspector,local_names,protocol_part1,protocol_part2,
+//! source_map_linked}.rs
 //! This file only declares submodules and shared helpers.
 
 use atlas_runtime::bytecode::{Bytecode, DebugSpan};
@@ -13,7 +15,7 @@ use atlas_runtime::debugger::inspection::{
 use atlas_runtime::debugger::protocol::{
     deserialize_event, deserialize_request, deserialize_response, serialize_event,
     serialize_request, serialize_response, Breakpoint, DebugEvent, DebugRequest, DebugResponse,
-    DebugStackFrame, PauseReason, SourceLocation, Variable,
+    DebugStackFrame, PauseReason, SourceLocation, Variable, WatchTarget,
 };
 use atlas_runtime::debugger::source_map::{
     byte_offset_to_line_column, compute_line_offsets, SourceMap,
@@ -50,13 +52,25 @@ fn new_session(source: &str) -> DebuggerSession {
 }
 
 // Domain submodules (files live in tests/debugger/)
+#[path = "debugger/break_on_error.rs"]
+mod break_on_error;
 #[path = "debugger/breakpoints.rs"]
 mod breakpoints;
+#[path = "debugger/evaluation.rs"]
+mod evaluation;
 #[path = "debugger/inspector.rs"]
 mod inspector;
+#[path = "debugger/local_names.rs"]
+mod local_names;
 #[path = "debugger/protocol_part1.rs"]
 mod protocol_part1;
 #[path = "debugger/protocol_part2.rs"]
 mod protocol_part2;
 #[path = "debugger/session.rs"]
 mod session;
+#[path = "debugger/source_map_linked.rs"]
+mod source_map_linked;
+#[path = "debugger/stepping.rs"]
+mod stepping;
+#[path = "debugger/watchpoints.rs"]
+mod watchpoints;
@@ -0,0 +1,103 @@
+//! Break-on-error tests
+
+use super::*;
+
+const OUT_OF_BOUNDS_SOURCE: &str =
+    "fn pick(items: number[], i: number): number {\n    let idx = i + 1;\n    return items[idx];\n}\nlet r = pick([1, 2, 3], 4);";
+
+#[test]
+fn test_break_on_error_pauses_in_faulting_frame() {
+    let bc = compile(OUT_OF_BOUNDS_SOURCE);
+    let mut session = DebuggerSession::new(bc, OUT_OF_BOUNDS_SOURCE, "test.atlas");
+    session.process_request(DebugRequest::SetBreakOnError {
+        enabled: true,
+        kinds: Vec::new(),
+    });
+
+    match session.run_until_pause(&security()) {
+        DebugResponse::Paused {
+            reason: PauseReason::Exception { kind, .. },
+            location,
+            ..
+        } => {
+            assert_eq!(kind, "OutOfBounds");
+            assert_eq!(location.map(|l| l.line), Some(3));
+        }
+        r => panic!("expected exception pause, got {:?}", r),
+    }
+    assert!(session.is_paused());
+
+    match session.process_request(DebugRequest::GetStack) {
+        DebugResponse::StackTrace { frames } => {
+            assert_eq!(frames[0].function_name, "pick");
+        }
+        r => panic!("unexpected: {:?}", r),
+    }
+    match session.process_request(DebugRequest::GetVariables { frame_index: 0 }) {
+        DebugResponse::Variables { variables, .. } => {
+            let idx = variables.iter().find(|v| v.name == "idx").expect("idx");
+            assert_eq!(idx.value, "5");
+        }
+        r => panic!("unexpected: {:?}", r),
+    }
+
+    // Resuming delivers the original error.
+    session.process_request(DebugRequest::Continue);
+    match session.run_until_pause(&security()) {
+        DebugResponse::Error { message } => assert!(message.contains("OutOfBounds")),
+        r => panic!("expected error after resume, got {:?}", r),
+    }
+    assert!(session.is_stopped());
+}
+
+#[test]
+fn test_break_on_error_kind_filter() {
+    let bc = compile(OUT_OF_BOUNDS_SOURCE);
+    let mut session = DebuggerSession::new(bc, OUT_OF_BOUNDS_SOURCE, "test.atlas");
+    session.process_request(DebugRequest::SetBreakOnError {
+        enabled: true,
+        kinds: vec!["DivideByZero".to_string()],
+    });
+
+    match session.run_until_pause(&security()) {
+        DebugResponse::Error { message } => assert!(message.contains("OutOfBounds")),
+        r => panic!("filtered kind must not pause, got {:?}", r),
+    }
+    assert!(session.is_stopped());
+}
+
+#[test]
+fn test_errors_propagate_without_break_on_error() {
+    let bc = compile(OUT_OF_BOUNDS_SOURCE);
+    let mut session = DebuggerSession::new(bc, OUT_OF_BOUNDS_SOURCE, "test.atlas");
+    assert!(matches!(
+        session.run_until_pause(&security()),
+        DebugResponse::Error { .. }
+    ));
+}
+
+#[test]
+fn proto_serialize_set_break_on_error() {
+    let req = DebugRequest::SetBreakOnError {
+        enabled: true,
+        kinds: vec!["OutOfBounds".to_string()],
+    };
+    let json = serialize_request(&req).unwrap();
+    let back: DebugRequest = deserialize_request(&json).unwrap();
+    assert_eq!(req, back);
+}
+
+#[test]
+fn proto_serialize_exception_pause_response() {
+    let resp = DebugResponse::Paused {
+        reason: PauseReason::Exception {
+            message: "index out of bounds".to_string(),
+            kind: "OutOfBounds".to_string(),
+        },
+        location: Some(loc(2)),
+        ip: 7,
+    };
+    let json = serialize_response(&resp).unwrap();
+    let back: DebugResponse = deserialize_response(&json).unwrap();
+    assert_eq!(resp, back);
+}
@@ -0,0 +1,87 @@
+//! Evaluation-while-paused tests
+
+use super::*;
+
+fn eval(session: &mut DebuggerSession, expression: &str, frame_index: usize) -> String {
+    match session.process_request(DebugRequest::Evaluate {
+        expression: expression.to_string(),
+        frame_index,
+    }) {
+        DebugResponse::EvalResult { value, .. } => value,
+        r => panic!("expected EvalResult for `{}`, got {:?}", expression, r),
+    }
+}
+
+/// Pause at `line` of `source`
+fn paused_at(source: &str, line: u32) -> DebuggerSession {
+    let mut session = new_session(source);
+    session.process_request(DebugRequest::SetBreakpoint {
+        location: SourceLocation::new("test.atlas", line, 1),
+    });
+    let resp = session.run_until_pause(&security());
+    assert!(
+        matches!(
+            resp,
+            DebugResponse::Paused {
+                reason: PauseReason::Breakpoint { .. },
+                ..
+            }
+        ),
+        "expected breakpoint pause, got {:?}",
+        resp
+    );
+    session.process_request(DebugRequest::ClearBreakpoints);
+    session
+}
+
+const EVAL_PROGRAM: &str = "let xs = [1, 2, 3];\nlet mut count = 0;\nfn bump(): number {\n    count = count + 1;\n    return count;\n}\nfn scale(count: number): number {\n    let doubled = count * 2;\n    return doubled;\n}\nlet r = scale(21);\nbump();";
+
+#[test]
+fn test_session_eval_sees_locals_globals_and_functions() {
+    let mut session = paused_at(EVAL_PROGRAM, 9);
+    assert_eq!(eval(&mut session, "doubled + 1", 0), "43");
+    assert_eq!(eval(&mut session, "len(xs) + xs[2]", 0), "6");
+    assert_eq!(eval(&mut session, "bump() + doubled", 0), "43");
+    assert_eq!(eval(&mut session, "Math.max(doubled, 100)", 0), "100");
+}
+
+#[test]
+fn test_session_eval_locals_shadow_globals_per_frame() {
+    let mut session = paused_at(EVAL_PROGRAM, 9);
+    // The parameter `count` in `scale`, the global `count` in the caller
+    assert_eq!(eval(&mut session, "count", 0), "21");
+    assert_eq!(eval(&mut session, "count", 1), "0");
+    assert!(matches!(
+        session.process_request(DebugRequest::Evaluate {
+            expression: "count".to_string(),
+            frame_index: 5,
+        }),
+        DebugResponse::Error { .. }
+    ));
+}
+
+#[test]
+fn test_session_eval_leaves_the_paused_program_untouched() {
+    let mut session = paused_at(EVAL_PROGRAM, 9);
+    let ip = session.current_ip();
+    let stack = session.process_request(DebugRequest::GetStack);
+
+    // `bump` assigns the global `count` in the snapshot only
+    assert_eq!(eval(&mut session, "bump()", 1), "1");
+    assert_eq!(eval(&mut session, "bump()", 1), "1");
+    assert_eq!(eval(&mut session, "count", 1), "0");
+    assert!(matches!(
+        session.process_request(DebugRequest::Evaluate {
+            expression: "xs[10]".to_string(),
+            frame_index: 0,
+        }),
+        DebugResponse::Error { .. }
+    ));
+
+    assert_eq!(session.current_ip(), ip);
+    assert_eq!(session.process_request(DebugRequest::GetStack), stack);
+    session.process_request(DebugRequest::Continue);
+    session.run_until_pause(&security());
+    assert!(session.is_stopped());
+    assert_eq!(eval(&mut session, "r + count", 0), "43");
+}
@@ -516,6 +516,25 @@ fn test_session_stack_trace_has_main() {
     }
 }
 
+#[test]
+fn test_session_stack_trace_locates_callers_at_their_calls() {
+    let source = "fn inner(): number {\n    return 1;\n}\nlet a = 0;\nlet b = inner();";
+    let bc = compile(source);
+    let mut session = DebuggerSession::new(bc, source, "test.atlas");
+    session.process_request(DebugRequest::SetBreakpoint { location: loc(2) });
+    session.run_until_pause(&security());
+    match session.process_request(DebugRequest::GetStack) {
+        DebugResponse::StackTrace { frames } => {
+            let lines: Vec<u32> = frames
+                .iter()
+                .map(|f| f.location.as_ref().unwrap().line)
+                .collect();
+            assert_eq!(lines, vec![2, 5]);
+        }
+        r => panic!("unexpected: {:?}", r),
+    }
+}
+
 // --- Debug protocol serialization ---
 
 // Integration tests for the Atlas debugger infrastructure (phase-04).
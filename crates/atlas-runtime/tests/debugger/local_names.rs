@@ -0,0 +1,128 @@
+//! Named locals and upvalues from bytecode debug info
+
+use super::*;
+
+#[test]
+fn test_bytecode_records_local_names_with_slots() {
+    let bc =
+        compile("fn add(a: number, b: number): number {\n    let sum = a + b;\n    return sum;\n}");
+    let slot_of = |name: &str| {
+        bc.local_names
+            .iter()
+            .find(|info| info.name == name && !info.captured)
+            .map(|info| info.slot)
+    };
+    assert_eq!(slot_of("a"), Some(0));
+    assert_eq!(slot_of("b"), Some(1));
+    assert_eq!(slot_of("sum"), Some(2));
+    assert!(bc
+        .local_names
+        .iter()
+        .all(|info| info.start_offset <= info.end_offset));
+}
+
+#[test]
+fn test_bytecode_records_captured_upvalue_names() {
+    let source = "fn outer(): number {\n    let base = 10;\n    let f = fn(x: number): number { x + base };\n    return f(5);\n}";
+    let bc = compile(source);
+    assert!(
+        bc.local_names
+            .iter()
+            .any(|info| info.name == "base" && info.captured && info.slot == 0),
+        "expected captured upvalue `base`, got {:?}",
+        bc.local_names
+    );
+}
+
+#[test]
+fn test_bytecode_hides_compiler_temporaries() {
+    let bc = compile("fn total(xs: number[]): number {\n    let mut t = 0;\n    for x in xs {\n        t = t + x;\n    }\n    return t;\n}");
+    assert!(bc.local_names.iter().any(|info| info.name == "x"));
+    assert!(
+        bc.local_names
+            .iter()
+            .all(|info| !info.name.starts_with("__") && !info.name.starts_with('$')),
+        "temporaries leaked: {:?}",
+        bc.local_names
+    );
+}
+
+#[test]
+fn test_session_variables_use_source_names() {
+    let source = "fn add(a: number, b: number): number {\n    let sum = a + b;\n    return sum;\n}\nlet r = add(1, 2);";
+    let bc = compile(source);
+    let mut session = DebuggerSession::new(bc, source, "test.atlas");
+    session.process_request(DebugRequest::SetBreakpoint {
+        location: SourceLocation::new("test.atlas", 3, 5),
+    });
+    match session.run_until_pause(&security()) {
+        DebugResponse::Paused { .. } => {}
+        r => panic!("expected breakpoint pause, got {:?}", r),
+    }
+
+    let variables = match session.process_request(DebugRequest::GetVariables { frame_index: 0 }) {
+        DebugResponse::Variables { variables, .. } => variables,
+        r => panic!("unexpected: {:?}", r),
+    };
+    let value_of = |name: &str| {
+        variables
+            .iter()
+            .find(|v| v.name == name)
+            .map(|v| v.value.clone())
+    };
+    assert_eq!(value_of("a").as_deref(), Some("1"));
+    assert_eq!(value_of("b").as_deref(), Some("2"));
+    assert_eq!(value_of("sum").as_deref(), Some("3"));
+    assert!(
+        variables.iter().all(|v| !v.name.starts_with("local_")),
+        "expected named locals, got {:?}",
+        variables
+    );
+}
+
+#[test]
+fn test_optimized_bytecode_keeps_local_names() {
+    let source = "fn add(a: number, b: number): number {\n    let scale = 2 * 3;\n    let sum = (a + b) * scale;\n    return sum;\n}\nlet r = add(1, 2);";
+    let tokens = Lexer::new(source).tokenize().0;
+    let (ast, _) = Parser::new(tokens).parse();
+    let bc = Compiler::with_optimization()
+        .compile(&ast)
+        .expect("compile failed");
+    let plain = compile(source);
+    assert!(
+        bc.instructions.len() < plain.instructions.len(),
+        "the optimizer should have folded `2 * 3`"
+    );
+
+    let names = |bc: &Bytecode| {
+        bc.local_names
+            .iter()
+            .map(|info| (info.name.clone(), info.slot, info.captured))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&bc), names(&plain));
+    assert!(bc.local_names.iter().all(
+        |info| info.start_offset <= info.end_offset && info.end_offset <= bc.instructions.len()
+    ));
+
+    let mut session = DebuggerSession::new(bc, source, "test.atlas");
+    session.process_request(DebugRequest::SetBreakpoint {
+        location: SourceLocation::new("test.atlas", 4, 5),
+    });
+    match session.run_until_pause(&security()) {
+        DebugResponse::Paused { .. } => {}
+        r => panic!("expected breakpoint pause, got {:?}", r),
+    }
+    let variables = match session.process_request(DebugRequest::GetVariables { frame_index: 0 }) {
+        DebugResponse::Variables { variables, .. } => variables,
+        r => panic!("unexpected: {:?}", r),
+    };
+    let value_of = |name: &str| {
+        variables
+            .iter()
+            .find(|v| v.name == name)
+            .map(|v| v.value.clone())
+    };
+    assert_eq!(value_of("scale").as_deref(), Some("6"));
+    assert_eq!(value_of("sum").as_deref(), Some("18"));
+}
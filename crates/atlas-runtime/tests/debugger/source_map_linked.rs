@@ -0,0 +1,62 @@
+//! Source maps over linked bytecode: spans from imported modules
+
+use super::*;
+
+/// An imported module's bytecode followed by the entry's, as the runtime
+/// links them; the module is lexed under its own path.
+fn linked_bytecode(module_path: &str) -> Bytecode {
+    let module_src = "fn helper(): number {\n    return 1;\n}\n";
+    let tokens = Lexer::new(module_src).with_file(module_path).tokenize().0;
+    let (ast, _) = Parser::new(tokens).parse();
+    let mut linked = Compiler::new().compile(&ast).expect("compile failed");
+    linked.append(compile("let x = 1;\nlet y = x + 1;\n"));
+    linked
+}
+
+#[test]
+fn srcmap_linked_spans_resolve_each_file() {
+    let entry_src = "let x = 1;\nlet y = x + 1;\n";
+    let bytecode = linked_bytecode("srcmap_linked/util.atlas");
+    let map = SourceMap::from_linked_spans(&bytecode.debug_info, "main.atlas", entry_src);
+
+    assert_eq!(map.files(), vec!["main.atlas", "srcmap_linked/util.atlas"]);
+    assert!(!map
+        .offsets_for_line("srcmap_linked/util.atlas", 2)
+        .is_empty());
+    assert!(!map.offsets_for_line("main.atlas", 2).is_empty());
+}
+
+#[test]
+fn srcmap_linked_spans_keep_original_span() {
+    let bytecode = linked_bytecode("srcmap_span/util.atlas");
+    let map = SourceMap::from_linked_spans(&bytecode.debug_info, "main.atlas", "");
+    let from_module = bytecode
+        .debug_info
+        .iter()
+        .find(|d| &*d.span.file() == "srcmap_span/util.atlas")
+        .expect("module spans carry the module's file");
+    let offset = from_module.instruction_offset;
+    // Several spans can share an offset; the last one recorded wins
+    let expected = bytecode
+        .debug_info
+        .iter()
+        .rev()
+        .find(|d| d.instruction_offset == offset)
+        .unwrap()
+        .span;
+    assert_eq!(map.span_for_offset(offset), Some(expected));
+}
+
+#[test]
+fn srcmap_session_breakpoint_in_imported_module() {
+    let entry_src = "let x = 1;\nlet y = x + 1;\n";
+    let bytecode = linked_bytecode("srcmap_session/util.atlas");
+    let mut session = DebuggerSession::new(bytecode, entry_src, "main.atlas");
+    let resp = session.process_request(DebugRequest::SetBreakpoint {
+        location: SourceLocation::new("srcmap_session/util.atlas", 2, 1),
+    });
+    match resp {
+        DebugResponse::BreakpointSet { breakpoint } => assert!(breakpoint.verified),
+        other => panic!("expected BreakpointSet, got {:?}", other),
+    }
+}
@@ -0,0 +1,112 @@
+//! Statement-level stepping tests
+
+use super::*;
+
+#[test]
+fn test_compiler_records_statement_starts() {
+    let bc = compile("let x = 1;\nlet y = x + 2;\nlet z = y * 3;");
+    assert!(
+        bc.statement_offsets.len() >= 3,
+        "expected one entry per statement, got {:?}",
+        bc.statement_offsets
+    );
+    assert!(bc.statement_offsets.windows(2).all(|w| w[0] < w[1]));
+    assert!(bc.is_statement_start(0));
+}
+
+#[test]
+fn test_step_over_advances_one_statement_at_a_time() {
+    let source = "let x = 1;\nlet y = x + 2;\nlet z = y * 3;";
+    let bc = compile(source);
+    let mut session = DebuggerSession::new(bc, source, "test.atlas");
+
+    let mut lines = Vec::new();
+    for _ in 0..3 {
+        session.process_request(DebugRequest::StepOver);
+        match session.run_until_pause(&security()) {
+            DebugResponse::Paused {
+                location: Some(loc),
+                ..
+            } => lines.push(loc.line),
+            r => panic!("expected a paused location, got {:?}", r),
+        }
+    }
+    assert_eq!(lines, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_step_over_does_not_stop_inside_callee() {
+    let source = "fn inc(a: number): number {\n    let b = a + 1;\n    return b;\n}\nlet r = inc(1);\nlet s = r + 1;";
+    let bc = compile(source);
+    let mut session = DebuggerSession::new(bc, source, "test.atlas");
+
+    let mut lines = Vec::new();
+    for _ in 0..10 {
+        session.process_request(DebugRequest::StepOver);
+        match session.run_until_pause(&security()) {
+            DebugResponse::Paused {
+                location: Some(loc),
+                ..
+            } if !session.is_stopped() => lines.push(loc.line),
+            _ => break,
+        }
+    }
+    assert!(
+        !lines.contains(&2) && !lines.contains(&3),
+        "step-over must not pause inside the callee, got {:?}",
+        lines
+    );
+    let r_pos = lines.iter().position(|&l| l == 5).expect("pause on line 5");
+    let s_pos = lines.iter().position(|&l| l == 6).expect("pause on line 6");
+    assert!(r_pos < s_pos, "got {:?}", lines);
+}
+
+#[test]
+fn test_continue_from_breakpoint_does_not_repause_same_instruction() {
+    let source = "let x = 1;\nlet y = 2;\nlet z = 3;";
+    let bc = compile(source);
+    let mut session = DebuggerSession::new(bc, source, "test.atlas");
+    session.process_request(DebugRequest::SetBreakpoint {
+        location: SourceLocation::new("test.atlas", 2, 1),
+    });
+
+    let first = session.run_until_pause(&security());
+    let first_ip = match first {
+        DebugResponse::Paused { ip, .. } => ip,
+        r => panic!("expected breakpoint pause, got {:?}", r),
+    };
+    assert!(session.is_paused());
+
+    session.process_request(DebugRequest::Continue);
+    session.run_until_pause(&security());
+    assert!(session.is_stopped(), "continue should run to completion");
+    assert_ne!(session.current_ip(), first_ip);
+}
+
+#[test]
+fn test_step_over_statements_in_optimized_bytecode() {
+    let source = "let x = 1 + 2;\nlet y = x * (3 + 4);\nlet z = y - 1;";
+    let bc = compile(source);
+    let size_before = bc.instructions.len();
+    let bc = atlas_runtime::optimizer::Optimizer::with_default_passes().optimize(bc);
+    assert!(bc.instructions.len() < size_before, "expected folding");
+    assert!(bc.is_statement_start(0));
+    assert!(bc
+        .statement_offsets
+        .iter()
+        .all(|&offset| offset < bc.instructions.len()));
+
+    let mut session = DebuggerSession::new(bc, source, "test.atlas");
+    let mut lines = Vec::new();
+    for _ in 0..3 {
+        session.process_request(DebugRequest::StepOver);
+        match session.run_until_pause(&security()) {
+            DebugResponse::Paused {
+                location: Some(loc),
+                ..
+            } => lines.push(loc.line),
+            r => panic!("expected a paused location, got {:?}", r),
+        }
+    }
+    assert_eq!(lines, vec![1, 2, 3]);
+}
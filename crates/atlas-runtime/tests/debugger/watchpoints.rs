@@ -0,0 +1,163 @@
+//! Watchpoint tests
+
+use super::*;
+
+fn watch(session: &mut DebuggerSession, target: WatchTarget) -> u32 {
+    match session.process_request(DebugRequest::SetWatchpoint { target }) {
+        DebugResponse::WatchpointSet { watchpoint } => watchpoint.id,
+        r => panic!("expected WatchpointSet, got {:?}", r),
+    }
+}
+
+fn global(name: &str) -> WatchTarget {
+    WatchTarget::Global {
+        name: name.to_string(),
+    }
+}
+
+#[test]
+fn test_session_watchpoint_pauses_when_global_changes() {
+    let source = "let mut x = 1;\nlet y = 2;\nx = 1;\nx = 5;\nlet z = x;";
+    let bc = compile(source);
+    let mut session = DebuggerSession::new(bc, source, "test.atlas");
+    let id = watch(&mut session, global("x"));
+
+    // Declaring `x` assigns it for the first time
+    match session.run_until_pause(&security()) {
+        DebugResponse::Paused {
+            reason:
+                PauseReason::Watchpoint {
+                    id: hit,
+                    old_value,
+                    new_value,
+                },
+            ..
+        } => {
+            assert_eq!(hit, id);
+            assert_eq!(old_value, None);
+            assert_eq!(new_value, "1");
+        }
+        r => panic!("expected watchpoint pause, got {:?}", r),
+    }
+
+    // Assigning the value it already holds is not a change
+    session.process_request(DebugRequest::Continue);
+    match session.run_until_pause(&security()) {
+        DebugResponse::Paused {
+            reason:
+                PauseReason::Watchpoint {
+                    old_value,
+                    new_value,
+                    ..
+                },
+            location,
+            ..
+        } => {
+            assert_eq!(old_value.as_deref(), Some("1"));
+            assert_eq!(new_value, "5");
+            // Paused right after the write, still in the statement that made it
+            assert_eq!(location.map(|l| l.line), Some(4));
+        }
+        r => panic!("expected watchpoint pause, got {:?}", r),
+    }
+
+    session.process_request(DebugRequest::Continue);
+    session.run_until_pause(&security());
+    assert!(session.is_stopped());
+}
+
+#[test]
+fn test_session_watchpoint_on_local_expires_with_its_frame() {
+    let source = "fn sum(n: number): number {\n    let mut total = 0;\n    let mut i = 0;\n    while i < n {\n        total = total + i;\n        i = i + 1;\n    }\n    return total;\n}\nlet a = sum(3);\nlet b = sum(3);";
+    let bc = compile(source);
+    let mut session = DebuggerSession::new(bc, source, "test.atlas");
+    session.process_request(DebugRequest::SetBreakpoint {
+        location: SourceLocation::new("test.atlas", 3, 1),
+    });
+    let resp = session.run_until_pause(&security());
+    assert!(
+        matches!(
+            resp,
+            DebugResponse::Paused {
+                reason: PauseReason::Breakpoint { .. },
+                ..
+            }
+        ),
+        "expected breakpoint pause, got {:?}",
+        resp
+    );
+    session.process_request(DebugRequest::ClearBreakpoints);
+
+    let id = watch(
+        &mut session,
+        WatchTarget::Local {
+            frame_index: 0,
+            name: "total".to_string(),
+        },
+    );
+
+    // total: 0 -> 0 (i = 0) is not a change; 0 -> 1 and 1 -> 3 are
+    let mut changes = Vec::new();
+    loop {
+        session.process_request(DebugRequest::Continue);
+        match session.run_until_pause(&security()) {
+            DebugResponse::Paused {
+                reason:
+                    PauseReason::Watchpoint {
+                        id: hit, new_value, ..
+                    },
+                ..
+            } => {
+                assert_eq!(hit, id);
+                changes.push(new_value);
+            }
+            _ => break,
+        }
+    }
+    assert_eq!(changes, vec!["1", "3"]);
+    assert!(session.is_stopped());
+
+    // The second call reuses the same slot, but the watchpoint went with the first
+    match session.process_request(DebugRequest::ListWatchpoints) {
+        DebugResponse::Watchpoints { watchpoints } => assert!(watchpoints.is_empty()),
+        r => panic!("expected Watchpoints, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_session_remove_watchpoint_stops_pausing() {
+    let source = "let mut x = 1;\nx = 2;\nx = 3;";
+    let bc = compile(source);
+    let mut session = DebuggerSession::new(bc, source, "test.atlas");
+    let id = watch(&mut session, global("x"));
+    assert_eq!(
+        session.process_request(DebugRequest::RemoveWatchpoint { id }),
+        DebugResponse::WatchpointRemoved { id }
+    );
+    match session.run_until_pause(&security()) {
+        DebugResponse::Paused { location: None, .. } => assert!(session.is_stopped()),
+        r => panic!("expected the program to finish, got {:?}", r),
+    }
+}
+
+#[test]
+fn test_session_watchpoint_on_unknown_variable_is_an_error() {
+    let source = "let x = 1;";
+    let bc = compile(source);
+    let mut session = DebuggerSession::new(bc, source, "test.atlas");
+    assert!(matches!(
+        session.process_request(DebugRequest::SetWatchpoint {
+            target: global("nope"),
+        }),
+        DebugResponse::Error { .. }
+    ));
+    assert!(matches!(
+        session.process_request(DebugRequest::SetWatchpoint {
+            target: WatchTarget::Local {
+                frame_index: 3,
+                name: "x".to_string(),
+            },
+        }),
+        DebugResponse::Error { .. }
+    ));
+}
@@ -1005,6 +1005,32 @@ fn test_format_warning_basic() {
     assert!(output.contains("Unused variable"));
 }
 
+#[test]
+fn test_format_stack_trace() {
+    use atlas_runtime::diagnostic::StackTraceFrame;
+
+    let frame = |function: &str, line| StackTraceFrame {
+        function: function.to_string(),
+        file: "main.atlas".to_string(),
+        line,
+        column: 5,
+    };
+    let formatter = DiagnosticFormatter::plain();
+    let diag = Diagnostic::error_with_code("AT0006", "array index out of bounds", Span::new(0, 1))
+        .with_file("main.atlas")
+        .with_stack_trace(vec![frame("inner", 2), frame("outer", 6)]);
+
+    let output = String::from_utf8(formatter.format_to_buffer(&diag)).unwrap();
+    assert!(
+        output.contains("stack trace:\n  at inner (main.atlas:2:5)\n  at outer (main.atlas:6:5)")
+    );
+
+    // A lone frame repeats the error location and is not printed
+    let diag = diag.with_stack_trace(vec![frame("<main>", 1)]);
+    let output = String::from_utf8(formatter.format_to_buffer(&diag)).unwrap();
+    assert!(!output.contains("stack trace"));
+}
+
 #[test]
 fn test_format_with_help() {
     let formatter = DiagnosticFormatter::plain();
@@ -1875,6 +1901,7 @@ fn make_bytecode(spans: Vec<(usize, usize, usize)>) -> Bytecode {
     Bytecode {
         instructions: vec![0; spans.last().map(|(o, _, _)| o + 1).unwrap_or(0)],
         constants: Vec::new(),
+        global_names: Vec::new(),
         debug_info: spans
             .into_iter()
             .map(|(offset, start, end)| DebugSpan {
@@ -1882,6 +1909,8 @@ fn make_bytecode(spans: Vec<(usize, usize, usize)>) -> Bytecode {
                 span: Span::new(start, end),
             })
             .collect(),
+        statement_offsets: Vec::new(),
+        local_names: Vec::new(),
         top_level_local_count: 0,
     }
 }
@@ -1982,6 +2011,39 @@ fn test_generate_removes_redundant_entries() {
     assert!(entries.len() <= 3, "Should remove redundant mappings");
 }
 
+#[test]
+fn test_generate_linked_modules_one_source_per_file() {
+    use atlas_runtime::span::{intern_file, register_source};
+
+    let module = intern_file("sourcemap_linked/util.atlas");
+    register_source(module, "fn a() {}\nfn b() {}\n");
+    let mut bytecode = make_bytecode(vec![(0, 0, 10)]);
+    bytecode.debug_info.push(DebugSpan {
+        instruction_offset: 4,
+        span: Span::new_in(10, 19, module),
+    });
+    let options = SourceMapOptions {
+        include_sources: true,
+        ..SourceMapOptions::default()
+    };
+
+    let map = generate_source_map(&bytecode, "main.atlas", Some("let x = 1;"), &options);
+    assert_eq!(
+        map.sources,
+        vec!["main.atlas", "sourcemap_linked/util.atlas"]
+    );
+    assert_eq!(
+        map.sources_content.as_ref().unwrap()[1].as_deref(),
+        Some("fn a() {}\nfn b() {}\n")
+    );
+
+    let entries = map.decode_mappings().unwrap();
+    let last = entries.last().unwrap();
+    assert_eq!(last.source_index, 1);
+    assert_eq!(last.original_line, 1);
+    assert_eq!(last.original_column, 0);
+}
+
 #[test]
 fn test_generate_from_debug_spans_direct() {
     let spans = vec![
@@ -2007,7 +2069,10 @@ fn test_generate_empty_bytecode() {
     let bytecode = Bytecode {
         instructions: Vec::new(),
         constants: Vec::new(),
+        global_names: Vec::new(),
         debug_info: Vec::new(),
+        statement_offsets: Vec::new(),
+        local_names: Vec::new(),
         top_level_local_count: 0,
     };
     let options = SourceMapOptions::default();
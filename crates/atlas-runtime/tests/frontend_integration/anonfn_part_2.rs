@@ -58,12 +58,7 @@ fn test_fn_style_anon_fn_parses() {
     );
     assert_eq!(items.len(), 1);
     if let Item::Statement(Stmt::VarDecl(decl)) = &items[0] {
-        if let Expr::AnonFn {
-            params,
-            return_type,
-            ..
-        } = &decl.init
-        {
+        if let Expr::AnonFn { params, .. } = &decl.init {
             assert_eq!(params.len(), 1, "expected 1 param");
             assert_eq!(params[0].name.name, "x");
         } else {
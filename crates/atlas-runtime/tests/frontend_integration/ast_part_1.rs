@@ -95,6 +95,7 @@ fn test_complete_program_construction() {
                 }),
                 span: Span::new(60, 91),
                 needs_drop: std::cell::RefCell::new(None),
+                inferred_type: std::cell::RefCell::new(None),
             })),
         ],
     };
@@ -135,6 +136,7 @@ fn test_all_statement_types() {
             init: Expr::Literal(Literal::Number(42.0), Span::new(4, 6)),
             span: Span::new(0, 7),
             needs_drop: std::cell::RefCell::new(None),
+            inferred_type: std::cell::RefCell::new(None),
         }),
         // Assignment
         Stmt::Assign(Assign {
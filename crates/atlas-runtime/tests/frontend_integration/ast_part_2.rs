@@ -215,6 +215,7 @@ fn test_ast_serialization() {
             init: Expr::Literal(Literal::Number(42.0), Span::new(16, 18)),
             span: Span::new(0, 19),
             needs_drop: std::cell::RefCell::new(None),
+            inferred_type: std::cell::RefCell::new(None),
         }))],
     };
 
@@ -17,10 +17,6 @@ fn num_const(bc: &mut Bytecode, n: f64) -> u16 {
     bc.add_constant(Value::Number(n))
 }
 
-fn str_const(bc: &mut Bytecode, s: &str) -> u16 {
-    bc.add_constant(Value::string(s))
-}
-
 fn push_num(bc: &mut Bytecode, n: f64) {
     let idx = num_const(bc, n);
     bc.emit(Opcode::Constant, span());
@@ -76,7 +72,7 @@ fn test_validate_null() {
 #[test]
 fn test_validate_set_and_get_global() {
     let mut bc = Bytecode::new();
-    let name = str_const(&mut bc, "myVar");
+    let name = bc.intern_global("myVar");
     push_num(&mut bc, 42.0);
     bc.emit(Opcode::SetGlobal, span());
     bc.emit_u16(name);
@@ -230,6 +226,23 @@ fn test_constant_index_zero_empty_pool() {
     )));
 }
 
+#[test]
+fn test_global_index_checked_against_global_names() {
+    let mut bc = Bytecode::new();
+    bc.add_constant(Value::string("myVar")); // a constant is not a global slot
+    bc.emit(Opcode::GetGlobal, span());
+    bc.emit_u16(0);
+    bc.emit(Opcode::Halt, span());
+    let errors = validate(&bc).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+        e.kind,
+        ValidationErrorKind::ConstantIndexOutOfBounds {
+            index: 0,
+            pool_size: 0
+        }
+    )));
+}
+
 #[test]
 fn test_constant_index_exceeds_pool() {
     let mut bc = Bytecode::new();
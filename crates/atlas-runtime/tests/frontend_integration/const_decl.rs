@@ -83,6 +83,7 @@ fn test_export_const_parses() {
 // Execution Tests
 // ============================================================================
 
+#[allow(clippy::approx_constant)]
 #[test]
 fn test_const_number_value() {
     let runtime = Atlas::new();
@@ -113,6 +114,7 @@ fn test_const_bool_value() {
     assert_eq!(result.unwrap(), Value::Bool(true));
 }
 
+#[allow(clippy::approx_constant)]
 #[test]
 fn test_const_arithmetic() {
     let runtime = Atlas::new();
@@ -165,6 +167,7 @@ X;
     );
 }
 
+#[allow(clippy::approx_constant)]
 #[test]
 fn test_const_duplicate_uses_second_value() {
     // Duplicate const is a warning, not an error — uses the second value
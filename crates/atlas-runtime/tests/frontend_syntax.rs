@@ -62,6 +62,7 @@ fn is_parser_error_code(code: &str) -> bool {
             | "AT1020"
             | "AT1021"
             | "AT1022"
+            | "AT1023"
     )
 }
 
@@ -178,6 +179,10 @@ fn get_all_diagnostics(source: &str) -> Vec<atlas_runtime::Diagnostic> {
 
 #[path = "frontend_syntax/lexer.rs"]
 mod lexer;
+#[path = "frontend_syntax/lexer_eof_recovery.rs"]
+mod lexer_eof_recovery;
+#[path = "frontend_syntax/lexer_regex_literals.rs"]
+mod lexer_regex_literals;
 
 #[path = "frontend_syntax/parser_basics.rs"]
 mod parser_basics;
@@ -217,8 +222,14 @@ mod traits_part2;
 #[path = "frontend_syntax/warnings_attributes.rs"]
 mod warnings_attributes;
 
+#[path = "frontend_syntax/warnings_suppression.rs"]
+mod warnings_suppression;
+
 #[path = "frontend_syntax/diagnostic_descriptor.rs"]
 mod diagnostic_descriptor;
 
 #[path = "frontend_syntax/visibility.rs"]
 mod visibility;
+
+#[path = "frontend_syntax/macros.rs"]
+mod macros;
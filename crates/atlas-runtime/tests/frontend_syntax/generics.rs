@@ -238,6 +238,13 @@ fn test_parse_namespace_import() {
     assert!(success, "Should parse namespace import: {:?}", msgs);
 }
 
+#[test]
+fn test_parse_module_import() {
+    let source = r#"import math from "./math.atl";"#;
+    let (success, msgs) = parse(source);
+    assert!(success, "Should parse module import: {:?}", msgs);
+}
+
 #[test]
 fn test_parse_import_relative_path() {
     let source = r#"import { x } from "./sibling";"#;
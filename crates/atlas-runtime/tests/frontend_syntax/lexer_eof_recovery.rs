@@ -0,0 +1,49 @@
+//! Lexer recovery at end of file: unterminated strings and template strings
+
+use super::*;
+
+#[test]
+fn test_unterminated_string_is_closed_at_eof() {
+    let (tokens, diagnostics) = lex(r#"let s = "unterminated string"#);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "AT1002");
+    assert_eq!(tokens[3].kind, TokenKind::String);
+    assert_eq!(tokens[3].lexeme, "unterminated string");
+    assert_eq!(tokens[4].kind, TokenKind::Eof);
+}
+
+#[test]
+fn test_unterminated_string_stops_at_end_of_its_line() {
+    let (tokens, diagnostics) = lex("let s = \"oops;\nlet y = 2;\n");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "AT1002");
+    assert_eq!(diagnostics[0].line, 1);
+    assert_eq!(tokens[3].kind, TokenKind::String);
+    assert_eq!(tokens[3].lexeme, "oops;");
+    assert_eq!(tokens[3].span.end, 14);
+    // Lexing resumes on the next line
+    let kinds: Vec<TokenKind> = tokens[4..].iter().map(|t| t.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::Let,
+            TokenKind::Identifier,
+            TokenKind::Equal,
+            TokenKind::Number,
+            TokenKind::Semicolon,
+            TokenKind::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_unterminated_template_string_runs_to_eof() {
+    let (tokens, diagnostics) = lex("`first\nsecond");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "AT1002");
+    assert_eq!(tokens[0].kind, TokenKind::TemplateString);
+    assert_eq!(tokens[0].lexeme, "first\nsecond");
+}
@@ -0,0 +1,49 @@
+//! Regex literal lexing: `/pattern/flags` versus division
+
+use super::*;
+
+#[rstest]
+#[case(r"/\d+/", r"/\d+/")]
+#[case(r"/hello/i", r"/hello/i")]
+#[case(r"/a\/b/", r"/a\/b/")]
+#[case(r"/[/]+/m", r"/[/]+/m")]
+fn test_regex_literals(#[case] input: &str, #[case] lexeme: &str) {
+    let (tokens, diagnostics) = lex(input);
+
+    assert_eq!(diagnostics.len(), 0, "Should have no errors");
+    assert_eq!(tokens[0].kind, TokenKind::Regex);
+    assert_eq!(tokens[0].lexeme, lexeme);
+}
+
+#[rstest]
+#[case("let r = /ab+c/;", 3)]
+#[case("f(/x/, /y/m)", 2)]
+#[case("return /x/;", 1)]
+fn test_regex_literal_after_operand_position(#[case] input: &str, #[case] index: usize) {
+    let (tokens, diagnostics) = lex(input);
+
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(tokens[index].kind, TokenKind::Regex);
+}
+
+#[rstest]
+#[case("a / b / c")]
+#[case("(a) / 2 / 3")]
+#[case("xs[0] / n / 2")]
+#[case("10 / 2 / 5")]
+fn test_slash_after_operand_is_division(#[case] input: &str) {
+    let (tokens, diagnostics) = lex(input);
+
+    assert_eq!(diagnostics.len(), 0);
+    assert!(tokens.iter().all(|t| t.kind != TokenKind::Regex));
+    assert_eq!(
+        tokens.iter().filter(|t| t.kind == TokenKind::Slash).count(),
+        2
+    );
+}
+
+#[test]
+fn test_unclosed_regex_falls_back_to_slash() {
+    let (tokens, _) = lex("let x = /abc\n;");
+    assert_eq!(tokens[3].kind, TokenKind::Slash);
+}
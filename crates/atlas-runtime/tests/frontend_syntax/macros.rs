@@ -0,0 +1,87 @@
+use super::*;
+use common::{assert_error_code, assert_eval_number, assert_eval_string};
+
+#[test]
+fn test_macro_expands_in_statement_and_expression_position() {
+    assert_eval_number(
+        r#"
+        macro square(x: expr) { $x * $x }
+        macro set(name: ident, value: expr) { let $name = $value; }
+
+        set!(total, square!(1 + 2));
+        total + 1;
+        "#,
+        10.0,
+    );
+}
+
+#[test]
+fn test_macro_generates_serializers_for_records() {
+    assert_eval_string(
+        r#"
+        macro to_json(T: ident, fields: ident...) {
+            fn $concat(to_json_, $T)(value: $T): string {
+                let parts: string[] = [$( $stringify($fields) + ": " + str(value.$fields) ),*];
+                return "{" + parts.join(", ") + "}";
+            }
+        }
+
+        struct User { name: string, age: number }
+        to_json!(User, name, age);
+
+        to_json_User(User { name: "ada", age: 36 });
+        "#,
+        "{name: ada, age: 36}",
+    );
+}
+
+#[test]
+fn test_macro_locals_do_not_capture_call_site_names() {
+    assert_eval_number(
+        r#"
+        macro double_into(target: ident, value: expr) {
+            let tmp = $value;
+            $target = tmp + tmp;
+        }
+
+        let tmp = 5;
+        let mut out = 0;
+        double_into!(out, tmp);
+        out + tmp;
+        "#,
+        15.0,
+    );
+}
+
+#[test]
+fn test_macro_invocation_errors() {
+    assert_error_code("macro one(x: expr) { $x }\nlet a = one!(1, 2);", "AT1023");
+    assert_error_code("let a = missing!(1);", "AT1023");
+    assert_error_code("macro bad(x: ident) { $x }\nlet a = bad!(1 + 2);", "AT1023");
+}
+
+#[test]
+fn test_macro_declaration_errors() {
+    let diagnostics = parse_errors("fn f() { macro inner() { 1 } }");
+    assert!(diagnostics
+        .iter()
+        .any(|d| d.code == "AT1023" && d.message.contains("top level")));
+
+    let diagnostics = parse_errors("macro m(rest...,  x) { 1 }");
+    assert!(diagnostics
+        .iter()
+        .any(|d| d.code == "AT1023" && d.message.contains("variadic")));
+
+    let diagnostics = parse_errors("macro m() { m!() }\nm!();");
+    assert!(diagnostics
+        .iter()
+        .any(|d| d.code == "AT1023" && d.message.contains("levels deep")));
+}
+
+#[test]
+fn test_sources_without_macros_are_untouched() {
+    let (tokens, _) = lex("let x = !(a);");
+    assert!(!Parser::uses_macros(&tokens));
+    let (tokens, _) = lex("let x = m!(a);");
+    assert!(Parser::uses_macros(&tokens));
+}
@@ -33,6 +33,14 @@ use super::*;
 // Array indexing (highest precedence)
 #[case("array_index_in_arithmetic", "arr[0] + 2 * 3;")]
 #[case("array_index_in_comparison", "arr[i] < 10;")]
+// Null coalescing binds looser than ||; `?.` binds like `.`
+#[case("coalesce_below_or", "a ?? b || c;")]
+#[case("null_safe_chain", "user?.address?.city(1) ?? fallback;")]
+// `in` sits with the comparisons; ordering comparisons chain left to right
+#[case("in_below_arithmetic", "x + 1 in xs && ok;")]
+#[case("comparison_chain", "0 <= i < n == valid;")]
+// `|>` sits between the comparisons and `+`; the piped value becomes the first argument
+#[case("pipeline_stages", "a + 1 |> f(b) |> g > c;")]
 fn test_operator_precedence(#[case] name: &str, #[case] source: &str) {
     let program = parse_valid(source);
 
@@ -180,3 +180,50 @@ fn get_label(borrow s: Status): string {
         diagnostics
     );
 }
+
+// ============================================================================
+// try / catch
+// ============================================================================
+
+#[test]
+fn test_parse_try_catch_with_binding() {
+    let (program, diagnostics) = parse_source("try { risky(); } catch (e) { log(e); }");
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    match &program.items[0] {
+        Item::Statement(Stmt::TryCatch(t)) => {
+            assert_eq!(t.body.statements.len(), 1);
+            assert_eq!(t.error_binding.as_ref().map(|b| b.name.as_str()), Some("e"));
+            assert_eq!(t.catch_block.statements.len(), 1);
+        }
+        other => panic!("Expected try/catch statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_try_catch_without_binding() {
+    let (program, diagnostics) = parse_source("try { risky(); } catch { }");
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    match &program.items[0] {
+        Item::Statement(Stmt::TryCatch(t)) => assert!(t.error_binding.is_none()),
+        other => panic!("Expected try/catch statement, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_try_without_catch_is_error() {
+    let (_, diagnostics) = parse_source("try { risky(); } let x = 1;");
+    assert!(
+        !diagnostics.is_empty(),
+        "Expected an error for try without catch"
+    );
+}
+
+#[test]
+fn test_try_is_still_an_identifier() {
+    let (program, diagnostics) = parse_source("let try = 1; try + 1;");
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    assert!(!program
+        .items
+        .iter()
+        .any(|item| matches!(item, Item::Statement(Stmt::TryCatch(_)))));
+}
@@ -29,6 +29,58 @@ fn test_block_errors(#[case] source: &str, #[case] expected: &str) {
     assert_has_parser_error(&diagnostics, expected);
 }
 
+#[test]
+fn test_unclosed_body_at_eof_keeps_the_item() {
+    let source = "fn first(): number { return 1; }\nfn second(): number {\n    let x = 1;\n    x";
+    let (program, diagnostics) = parse_source(source);
+
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].code, "AT1021");
+    // Reported at the `{` that is never closed, not at the end of the file
+    assert_eq!(diagnostics[0].line, 2);
+    assert_eq!(program.items.len(), 2);
+    match &program.items[1] {
+        Item::Function(func) => {
+            assert_eq!(func.name.name, "second");
+            assert_eq!(func.body.statements.len(), 1);
+            assert!(func.body.tail_expr.is_some());
+        }
+        other => panic!("expected function, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_nested_unclosed_bodies_report_once() {
+    let source = "impl Point {\n    fn norm(self): number {\n        if true {\n\
+                  return 1;";
+    let (program, diagnostics) = parse_source(source);
+
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].code, "AT1021");
+    assert_eq!(diagnostics[0].line, 3);
+    assert_eq!(program.items.len(), 1);
+}
+
+#[test]
+fn test_unterminated_string_keeps_later_items() {
+    let source = "fn a(): void {}\nlet s = \"oops\nfn b(): void {}\n";
+    let mut lexer = Lexer::new(source);
+    let (tokens, lex_diagnostics) = lexer.tokenize();
+    assert_eq!(lex_diagnostics.len(), 1);
+    assert_eq!(lex_diagnostics[0].code, "AT1002");
+
+    let (program, _) = Parser::new(tokens).parse();
+    let names: Vec<&str> = program
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Function(func) => Some(func.name.name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(names, vec!["a", "b"]);
+}
+
 // ============================================================================
 // Array Literal Errors
 // ============================================================================
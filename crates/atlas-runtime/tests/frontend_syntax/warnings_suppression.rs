@@ -0,0 +1,100 @@
+//! `// atlas-ignore:` inline suppression directives
+
+use super::*;
+use atlas_runtime::diagnostic::suppression::{apply_suppressions, parse_suppressions, rule_code};
+
+/// Warning codes left after applying the source's directives
+fn remaining_warnings(source: &str) -> Vec<String> {
+    let mut lexer = Lexer::new(source);
+    let (tokens, _) = lexer.tokenize();
+    let mut parser = Parser::new(tokens);
+    let (program, _) = parser.parse();
+    let mut binder = Binder::new();
+    let (mut table, _) = binder.bind(&program);
+    let mut checker = TypeChecker::new(&mut table);
+    let diags = checker.check(&program);
+    apply_suppressions(source, diags)
+        .iter()
+        .filter(|d| d.level == DiagnosticLevel::Warning)
+        .map(|d| d.code.clone())
+        .collect()
+}
+
+#[test]
+fn test_unsuppressed_warning_baseline() {
+    let source = "fn f(): number {\n    let scratch = 1;\n    return 0;\n}\nf();\n";
+    assert!(remaining_warnings(source).contains(&"AT2001".to_string()));
+}
+
+#[test]
+fn test_directive_on_previous_line_by_name() {
+    let source = "fn f(): number {\n    // atlas-ignore: unused-variable\n    let scratch = 1;\n    return 0;\n}\nf();\n";
+    assert!(!remaining_warnings(source).contains(&"AT2001".to_string()));
+}
+
+#[test]
+fn test_trailing_directive_by_code() {
+    let source =
+        "fn f(): number {\n    let scratch = 1; // atlas-ignore: AT2001\n    return 0;\n}\nf();\n";
+    assert!(!remaining_warnings(source).contains(&"AT2001".to_string()));
+}
+
+#[test]
+fn test_directive_for_other_rule_keeps_warning() {
+    let source = "fn f(): number {\n    // atlas-ignore: shadowing\n    let scratch = 1;\n    return 0;\n}\nf();\n";
+    assert!(remaining_warnings(source).contains(&"AT2001".to_string()));
+}
+
+#[test]
+fn test_directive_only_covers_next_code_line() {
+    let source = "fn f(): number {\n    // atlas-ignore: unused-variable\n    let a = 1;\n    let b = 2;\n    return 0;\n}\nf();\n";
+    assert!(remaining_warnings(source).contains(&"AT2001".to_string()));
+}
+
+#[test]
+fn test_directive_skips_blank_and_comment_lines() {
+    let source = "// atlas-ignore: unused-variable -- demo\n\n// explanation\nlet x = 1;\n";
+    let found = parse_suppressions(source);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].line, 1);
+    assert_eq!(found[0].target_line, 4);
+    assert_eq!(found[0].reason.as_deref(), Some("demo"));
+}
+
+#[test]
+fn test_multiple_rules_in_one_directive() {
+    let found = parse_suppressions("let x = 1; // atlas-ignore: AT2005, unused-variable\n");
+    assert_eq!(found[0].rules, vec!["AT2005", "unused-variable"]);
+    assert!(found[0].covers("AT2001"));
+    assert!(found[0].covers("AT2005"));
+    assert!(!found[0].covers("AT2002"));
+}
+
+#[test]
+fn test_directive_inside_string_is_ignored() {
+    let found = parse_suppressions("let s = \"// atlas-ignore: unused-variable\";\n");
+    assert!(found.is_empty());
+}
+
+#[test]
+fn test_unknown_rules_are_reported() {
+    let found = parse_suppressions("// atlas-ignore: unused-varaible, AT2001\nlet x = 1;\n");
+    assert_eq!(found[0].unknown_rules(), vec!["unused-varaible"]);
+}
+
+#[test]
+fn test_errors_are_never_suppressed() {
+    // An unused-variable warning promoted to an error by `deny`
+    let source = "let x = 1; // atlas-ignore: unused-variable\n";
+    let error = Diagnostic::error_with_code("AT2001", "unused variable", Span::new(0, 1));
+    let kept = apply_suppressions(source, vec![error.with_line(1)]);
+    assert_eq!(kept.len(), 1);
+}
+
+#[test]
+fn test_rule_names_resolve_case_insensitively() {
+    assert_eq!(rule_code("Unused-Variable"), Some("AT2001"));
+    assert_eq!(rule_code("at2005"), Some("AT2005"));
+    assert_eq!(rule_code("share-to-non-share"), Some("AW3060"));
+    assert_eq!(rule_code("not-a-rule"), None);
+}
@@ -472,3 +472,10 @@ fn test_http_invalid_host_returns_error() {
 // Sandboxing tests (from api/sandboxing.rs — referenced here for completeness)
 // ============================================================================
 // See crates/atlas-runtime/tests/api/sandboxing.rs for network permission tests
+
+// ============================================================================
+// Submodules
+// ============================================================================
+
+#[path = "http/local_server.rs"]
+mod local_server;
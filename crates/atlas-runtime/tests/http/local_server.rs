@@ -0,0 +1,151 @@
+//! Local server tests — http.request, toMap and the host allow-list
+
+use super::*;
+
+/// Answer one request on 127.0.0.1 with `status`, echoing the request line
+/// back in an `X-Request-Line` header. Returns the server's base URL.
+fn serve_once(status: &'static str) -> String {
+    serve_once_with(status, "")
+}
+
+/// `serve_once` with extra raw header lines (each ending in `\r\n`).
+fn serve_once_with(status: &'static str, extra_headers: &str) -> String {
+    use std::io::{Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let extra_headers = extra_headers.to_string();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = [0u8; 4096];
+        let n = stream.read(&mut buf).unwrap();
+        let request = String::from_utf8_lossy(&buf[..n]).to_string();
+        let line = request.lines().next().unwrap_or("");
+        let response = format!(
+            "HTTP/1.1 {}\r\nX-Request-Line: {}\r\n{}Content-Length: 5\r\n\
+             Connection: close\r\n\r\nhello",
+            status, line, extra_headers
+        );
+        stream.write_all(response.as_bytes()).unwrap();
+    });
+    format!("http://127.0.0.1:{}", port)
+}
+
+fn localhost_only() -> SecurityContext {
+    let mut security = SecurityContext::new();
+    security.grant_network("127.0.0.1");
+    security
+}
+
+fn eval_with(security: SecurityContext, code: &str) -> Result<String, String> {
+    let atlas = Atlas::new_with_security(security);
+    atlas
+        .eval(code)
+        .map(|value| value.to_string())
+        .map_err(|diags| format!("{:?}", diags))
+}
+
+#[test]
+fn test_http_request_sends_any_method() {
+    let url = serve_once("200 OK");
+    let code = format!(
+        r#"
+        let resp = unwrap(http.request(record {{ method: "options", url: "{}/items" }}));
+        unwrap(resp.header("x-request-line"))
+    "#,
+        url
+    );
+    assert_eq!(
+        eval_with(localhost_only(), &code).unwrap(),
+        "OPTIONS /items HTTP/1.1"
+    );
+}
+
+#[test]
+fn test_http_request_requires_url() {
+    let result = eval_with(
+        localhost_only(),
+        r#"http.request(record { method: "GET" })"#,
+    );
+    assert!(result.unwrap_err().contains("options.url is required"));
+}
+
+#[test]
+fn test_http_response_to_map() {
+    let url = serve_once("201 Created");
+    let code = format!(
+        r#"
+        let m = unwrap(http.post("{}/things", "payload")).toMap();
+        unwrap(m.get("status"))
+    "#,
+        url
+    );
+    assert_eq!(eval_with(localhost_only(), &code).unwrap(), "201");
+}
+
+#[test]
+fn test_http_denied_without_network_permission() {
+    let err = eval_with(
+        SecurityContext::new(),
+        r#"http.request(record { url: "http://127.0.0.1:9/" })"#,
+    )
+    .unwrap_err();
+    assert!(err.contains("network access to 127.0.0.1"), "{}", err);
+}
+
+#[test]
+fn test_http_allow_list_is_per_host() {
+    // Only 127.0.0.1 is granted; the same server under another name is not
+    let err = eval_with(localhost_only(), r#"http.get("http://localhost:9/")"#).unwrap_err();
+    assert!(err.contains("network access to localhost"), "{}", err);
+}
+
+#[test]
+fn test_http_userinfo_does_not_hide_host() {
+    // The real host is after the `@`; only that host must be checked
+    let mut security = SecurityContext::new();
+    security.grant_network("allowed.com");
+    let err = eval_with(security, r#"http.get("http://allowed.com:x@127.0.0.1:9/")"#).unwrap_err();
+    assert!(err.contains("network access to 127.0.0.1"), "{}", err);
+}
+
+#[test]
+fn test_http_redirect_to_denied_host_is_refused() {
+    let target = serve_once("200 OK").replace("127.0.0.1", "localhost");
+    let url = serve_once_with("302 Found", &format!("Location: {}/\r\n", target));
+    let code = format!(r#"http.get("{}/start")"#, url);
+    let err = eval_with(localhost_only(), &code).unwrap_err();
+    assert!(err.contains("network access to localhost"), "{}", err);
+}
+
+#[test]
+fn test_http_redirect_to_allowed_host_is_followed() {
+    let target = serve_once("200 OK");
+    let url = serve_once_with("302 Found", &format!("Location: {}/next\r\n", target));
+    let code = format!(
+        r#"
+        let resp = unwrap(http.get("{}/start"));
+        unwrap(resp.header("x-request-line"))
+    "#,
+        url
+    );
+    assert_eq!(
+        eval_with(localhost_only(), &code).unwrap(),
+        "GET /next HTTP/1.1"
+    );
+}
+
+#[test]
+fn test_http_async_needs_network_permission() {
+    use atlas_runtime::span::Span;
+    use atlas_runtime::stdlib::async_io;
+    use atlas_runtime::value::{RuntimeError, Value};
+
+    let args = [Value::string("http://localhost:9/")];
+    let err = async_io::http_get_async(&args, Span::dummy(), &localhost_only()).unwrap_err();
+    assert!(
+        matches!(err, RuntimeError::NetworkPermissionDenied { ref host, .. } if host == "localhost"),
+        "{:?}",
+        err
+    );
+}
@@ -366,7 +366,6 @@ fn test_serve_concurrent_requests() {
 
     let handles: Vec<_> = (0..5)
         .map(|_| {
-            let port = port;
             std::thread::spawn(move || {
                 let client = make_client();
                 client
@@ -1618,3 +1618,13 @@ fn test_h380_struct_pattern_wildcard_fallthrough() {
         -1.0,
     );
 }
+
+// ============================================================================
+// Submodules
+// ============================================================================
+
+#[path = "pattern_matching/range_patterns.rs"]
+mod range_patterns;
+
+#[path = "pattern_matching/rest_patterns.rs"]
+mod rest_patterns;
@@ -0,0 +1,90 @@
+//! Range patterns: `a..b` and `a..=b` arms over numbers
+
+use super::*;
+
+#[test]
+fn test_range_pattern_buckets() {
+    assert_parity_string(
+        r#"fn bucket(n: number): string {
+            return match n {
+                -10..0 => "neg",
+                0..5 => "low",
+                5..=10 => "mid",
+                _ => "high"
+            };
+        }
+        bucket(-1) + bucket(0) + bucket(4.5) + bucket(5) + bucket(10) + bucket(11);"#,
+        "neglowlowmidmidhigh",
+    );
+}
+
+#[test]
+fn test_range_pattern_in_tuple() {
+    assert_parity_string(
+        r#"fn run(): string {
+            return match (3, 1_000) {
+                (0..3, _) => "low",
+                (3..=9, 1_000) => "digit",
+                _ => "other"
+            };
+        }
+        run();"#,
+        "digit",
+    );
+}
+
+#[test]
+fn test_range_pattern_with_guard() {
+    assert_parity_number(
+        r#"fn run(borrow x: number): number {
+            return match x {
+                0..10 if x % 2 == 0 => 1,
+                0..10 => 2,
+                _ => 3
+            };
+        }
+        run(4) * 100 + run(7) * 10 + run(12);"#,
+        123.0,
+    );
+}
+
+#[test]
+fn test_range_pattern_requires_number() {
+    let (success, msgs) = typecheck(
+        r#"fn run(borrow s: string): number {
+            return match s {
+                0..5 => 1,
+                _ => 0
+            };
+        }"#,
+    );
+    assert!(!success, "string scrutinee should be rejected");
+    assert!(msgs.iter().any(|m| m.contains("range")), "{:?}", msgs);
+}
+
+#[test]
+fn test_range_pattern_rejects_union() {
+    let (success, msgs) = typecheck(
+        r#"fn run(borrow v: number | string): number {
+            return match v {
+                0..5 => 1,
+                _ => 0
+            };
+        }"#,
+    );
+    assert!(!success, "union scrutinee should be rejected: {:?}", msgs);
+}
+
+#[test]
+fn test_range_pattern_empty_is_error() {
+    let (success, msgs) = typecheck(
+        r#"fn run(borrow x: number): number {
+            return match x {
+                5..5 => 1,
+                _ => 0
+            };
+        }"#,
+    );
+    assert!(!success, "empty range should be rejected");
+    assert!(msgs.iter().any(|m| m.contains("never match")), "{:?}", msgs);
+}
@@ -0,0 +1,203 @@
+//! Rest patterns: `[x, ...rest]` arrays and destructuring `let`
+
+use super::*;
+
+#[test]
+fn test_array_rest_binds_remainder() {
+    assert_parity_number(
+        r#"fn run(borrow arr: number[]): number {
+            return match arr {
+                [first, ...rest] => first * 10 + len(rest),
+                _ => -1
+            };
+        }
+        run([5, 6, 7]);"#,
+        52.0,
+    );
+}
+
+#[test]
+fn test_array_rest_requires_minimum_length() {
+    assert_parity_string(
+        r#"fn run(borrow arr: number[]): string {
+            return match arr {
+                [a, b, ...rest] => "two or more",
+                [a] => "one",
+                _ => "none"
+            };
+        }
+        run([1]);"#,
+        "one",
+    );
+}
+
+#[test]
+fn test_array_rest_recursive_sum() {
+    assert_parity_number(
+        r#"fn sum(borrow arr: number[]): number {
+            return match arr {
+                [] => 0,
+                [head, ...tail] => head + sum(tail)
+            };
+        }
+        sum([1, 2, 3, 4]);"#,
+        10.0,
+    );
+}
+
+#[test]
+fn test_array_rest_wildcard_discards_remainder() {
+    assert_parity_number(
+        r#"fn run(borrow arr: number[]): number {
+            return match arr {
+                [x, ..._] => x,
+                [] => 0
+            };
+        }
+        run([8, 9]);"#,
+        8.0,
+    );
+}
+
+#[test]
+fn test_nested_array_patterns_keep_outer_array() {
+    // The inner pattern must not overwrite the outer array's temp slot
+    assert_parity_number(
+        r#"fn run(borrow grid: number[][]): number {
+            return match grid {
+                [[a, b], [c]] => a + b + c,
+                _ => -1
+            };
+        }
+        run([[1, 2], [30]]);"#,
+        33.0,
+    );
+}
+
+#[test]
+fn test_anonymous_pattern_matches_hashmap_entries() {
+    assert_parity_number(
+        r#"let m = new Map<string, number>();
+        m.set("port", 8080);
+        let result = match m {
+            { port } => port,
+            _ => -1,
+        };
+        result;"#,
+        8080.0,
+    );
+}
+
+#[test]
+fn test_anonymous_pattern_missing_key_tries_next_arm() {
+    assert_parity_number(
+        r#"let m = new Map<string, number>();
+        m.set("host", 1);
+        let result = match m {
+            { port } => port,
+            { host } => host * 100,
+            _ => -1,
+        };
+        result;"#,
+        100.0,
+    );
+}
+
+#[test]
+fn test_let_array_destructure_with_rest() {
+    assert_parity_number(
+        r#"let [first, second, ...others] = [1, 2, 3, 4];
+        first + second * 10 + len(others) * 100 + others[1] * 1000;"#,
+        4221.0,
+    );
+}
+
+#[test]
+fn test_let_map_destructure() {
+    assert_parity_number(
+        r#"let m = new Map<string, number>();
+        m.set("width", 3);
+        m.set("height", 4);
+        let { width, height } = m;
+        width * height;"#,
+        12.0,
+    );
+}
+
+#[test]
+fn test_let_record_destructure() {
+    assert_parity_string(
+        r#"let cfg = record { name: "atlas", version: 3 };
+        let { name, version } = cfg;
+        name + version.toString();"#,
+        "atlas3",
+    );
+}
+
+#[test]
+fn test_array_rest_with_empty_arm_is_exhaustive() {
+    let (success, msgs) = typecheck(
+        r#"fn run(borrow arr: number[]): number {
+            return match arr {
+                [] => 0,
+                [x, ...rest] => x
+            };
+        }"#,
+    );
+    assert!(success, "[] and [x, ...rest] cover every array: {:?}", msgs);
+}
+
+#[test]
+fn test_array_rest_missing_empty_case_rejected() {
+    let (success, msgs) = typecheck(
+        r#"fn run(borrow arr: number[]): number {
+            return match arr {
+                [x, ...rest] => x
+            };
+        }"#,
+    );
+    assert!(!success, "Should reject match missing the empty array");
+    assert!(
+        msgs.iter().any(|m| m.contains("missing case(s): []")),
+        "Should name the empty array: {:?}",
+        msgs
+    );
+}
+
+#[test]
+fn test_array_fixed_lengths_alone_not_exhaustive() {
+    let (success, _) = typecheck(
+        r#"fn run(borrow arr: number[]): number {
+            return match arr {
+                [] => 0,
+                [x] => x
+            };
+        }"#,
+    );
+    assert!(!success, "Fixed-length arms never cover every array");
+}
+
+#[test]
+fn test_let_destructure_binds_element_types() {
+    let (success, msgs) = typecheck(
+        r#"let [head, ...tail] = [1, 2, 3];
+        let n: number = head;
+        let rest: number[] = tail;
+        let m = new Map<string, bool>();
+        let { flag } = m;
+        let b: bool = flag;"#,
+    );
+    assert!(success, "destructured names should be typed: {:?}", msgs);
+
+    let (success, _) = typecheck(
+        r#"let [head] = ["a"];
+        let n: number = head;"#,
+    );
+    assert!(!success, "a string element must not bind as number");
+}
+
+#[test]
+fn test_let_array_destructure_rejects_non_array() {
+    let (success, _) = typecheck(r#"let [a, b] = 42;"#);
+    assert!(!success, "cannot destructure a number as an array");
+}
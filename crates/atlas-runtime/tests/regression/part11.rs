@@ -169,12 +169,12 @@ fn test_h193_error_pipeline_collects_all_phase_errors() {
         .expect_err("expected errors from both phases");
     let codes: Vec<&str> = diags.iter().map(|d| d.code.as_str()).collect();
     assert!(
-        codes.iter().any(|c| *c == "AT2003"),
+        codes.contains(&"AT2003"),
         "Expected AT2003 (duplicate function) in diagnostics, got: {:?}",
         codes
     );
     assert!(
-        codes.iter().any(|c| *c == "AT3001"),
+        codes.contains(&"AT3001"),
         "Expected AT3001 (type mismatch) in diagnostics, got: {:?}",
         codes
     );
@@ -188,10 +188,11 @@ fn test_h193_error_pipeline_collects_all_phase_errors() {
 fn test_h260_number_tostring() {
     assert_eval_string(
         r#"
-fn main(): string {
+fn render(): string {
     let n: number = 42.0;
     return n.toString();
 }
+render()
 "#,
         "42",
     );
@@ -201,10 +202,11 @@ fn main(): string {
 fn test_h260_number_tofixed() {
     assert_eval_string(
         r#"
-fn main(): string {
+fn render(): string {
     let n: number = 3.14159;
     return n.toFixed(2);
 }
+render()
 "#,
         "3.14",
     );
@@ -227,10 +229,11 @@ fn main(): number {
 fn test_h260_bool_tostring_true() {
     assert_eval_string(
         r#"
-fn main(): string {
+fn render(): string {
     let b: bool = true;
     return b.toString();
 }
+render()
 "#,
         "true",
     );
@@ -240,10 +243,11 @@ fn main(): string {
 fn test_h260_bool_tostring_false() {
     assert_eval_string(
         r#"
-fn main(): string {
+fn render(): string {
     let b: bool = false;
     return b.toString();
 }
+render()
 "#,
         "false",
     );
@@ -422,6 +422,23 @@ fn test_security_context_grant_environment() {
     assert!(ctx.check_environment("HOME").is_err());
 }
 
+#[test]
+fn test_environment_read_and_write_are_separate() {
+    let mut ctx = SecurityContext::new();
+    ctx.grant_environment("APP_MODE");
+    assert!(ctx.check_environment("APP_MODE").is_ok());
+    assert_eq!(
+        ctx.check_environment_write("APP_MODE"),
+        Err(SecurityError::EnvironmentWriteDenied {
+            var: "APP_MODE".to_string()
+        })
+    );
+
+    ctx.grant_environment_write("APP_MODE");
+    assert!(ctx.check_environment_write("APP_MODE").is_ok());
+    assert!(ctx.check_environment_write("PATH").is_err());
+}
+
 #[test]
 fn test_security_context_allow_all() {
     let ctx = SecurityContext::allow_all();
@@ -431,6 +448,7 @@ fn test_security_context_allow_all() {
     assert!(ctx.check_network("any.host.com").is_ok());
     assert!(ctx.check_process("any-command").is_ok());
     assert!(ctx.check_environment("ANY_VAR").is_ok());
+    assert!(ctx.check_environment_write("ANY_VAR").is_ok());
 }
 
 // ============================================================================
@@ -728,6 +746,58 @@ fn test_sandbox_disabled_bypasses_checks() {
     assert!(sandbox.allocate_memory(200).is_ok());
 }
 
+#[test]
+fn test_context_from_security_config() {
+    let config: atlas_config::SecurityConfig = toml::from_str(
+        r#"
+        network = { allow = ["api.example.com", "evil.example.com"], deny = ["evil.example.com"] }
+        environment = { allow = ["HOME"], write = ["APP_MODE"] }
+        "#,
+    )
+    .unwrap();
+    let ctx = SecurityContext::from_config(&config);
+
+    assert!(ctx.check_network("api.example.com").is_ok());
+    assert!(ctx.check_network("evil.example.com").is_err());
+    assert!(ctx.check_environment("HOME").is_ok());
+    assert!(ctx.check_environment("PATH").is_err());
+    assert!(ctx.check_environment_write("HOME").is_err());
+    assert!(ctx.check_environment_write("APP_MODE").is_ok());
+    assert!(ctx.check_process("ls").is_err());
+
+    let none: atlas_config::SecurityConfig = toml::from_str(r#"mode = "none""#).unwrap();
+    assert!(SecurityContext::from_config(&none)
+        .check_process("ls")
+        .is_ok());
+}
+
+#[test]
+fn test_database_permission_by_path() {
+    let data = test_path("/data");
+    let config = atlas_config::SecurityConfig {
+        database: Some(atlas_config::security::DatabasePermissions {
+            allow: vec![data.clone()],
+            deny: vec![],
+        }),
+        ..Default::default()
+    };
+    let ctx = SecurityContext::from_config(&config);
+
+    assert!(ctx
+        .check_database(data.join("app.db").to_str().unwrap())
+        .is_ok());
+    assert!(matches!(
+        ctx.check_database(test_path("/etc/app.db").to_str().unwrap()),
+        Err(SecurityError::DatabaseDenied { .. })
+    ));
+    // Filesystem grants do not extend to databases, and vice versa
+    assert!(ctx.check_filesystem_read(&data.join("app.db")).is_err());
+    assert!(SecurityContext::new().check_database(":memory:").is_ok());
+    assert!(SecurityContext::allow_all()
+        .check_database(test_path("/etc/app.db").to_str().unwrap())
+        .is_ok());
+}
+
 // --- Runtime security enforcement ---
 
 // Runtime security enforcement tests
@@ -1232,6 +1302,21 @@ fn test_audit_logger_logs_environment_denied() {
     ));
 }
 
+#[test]
+fn test_audit_logger_logs_database_denied() {
+    let logger = Arc::new(MemoryAuditLogger::new());
+    let ctx = SecurityContext::with_audit_logger(logger.clone() as Arc<dyn AuditLogger>);
+
+    let _ = ctx.check_database(test_path("/data/app.db").to_str().unwrap());
+
+    let entries = logger.entries();
+    assert_eq!(entries.len(), 1);
+    assert!(matches!(
+        &entries[0].event,
+        AuditEvent::DatabaseDenied { .. }
+    ));
+}
+
 #[test]
 fn test_audit_logger_logs_environment_granted() {
     let logger = Arc::new(MemoryAuditLogger::new());
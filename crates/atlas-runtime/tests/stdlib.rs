@@ -1,5 +1,5 @@
 //! THIN ROUTER — DO NOT ADD TESTS HERE.
-//! Add tests to the submodule files: tests/stdlib/{integration,strings,json,io,types,functions,collections,parity,vm_stdlib,docs_verification}.rs
+//! Add tests to the submodule files: tests/stdlib/{bytes,csv,features,file_handle,integration,number_format,strings,json,json_path,json_patterns,io,types,functions,collections,parity,vm_stdlib,docs_verification}.rs
 //! This file only declares submodules and shared helpers.
 
 mod common;
@@ -17,7 +17,7 @@ use common::{
     assert_error_code, assert_eval_bool, assert_eval_null, assert_eval_number,
     assert_eval_option_none, assert_eval_option_some_number, assert_eval_option_some_string,
     assert_eval_result_err, assert_eval_result_ok_number, assert_eval_string, assert_has_error,
-    compile_source, path_for_atlas, run_bytecode, temp_file_path,
+    path_for_atlas, temp_file_path,
 };
 use rstest::rstest;
 use std::fs;
@@ -67,10 +67,24 @@ mod b40_path_ops;
 mod b40_reflect;
 #[path = "stdlib/b40_sqlite.rs"]
 mod b40_sqlite;
+#[path = "stdlib/b40_sqlite_transactions.rs"]
+mod b40_sqlite_transactions;
+#[path = "stdlib/bytes.rs"]
+mod bytes;
 #[path = "stdlib/collections.rs"]
 mod collections;
+#[path = "stdlib/csv.rs"]
+mod csv;
+#[path = "stdlib/cycles.rs"]
+mod cycles;
 #[path = "stdlib/docs_verification.rs"]
 mod docs_verification;
+#[path = "stdlib/features.rs"]
+mod features;
+#[path = "stdlib/file_handle.rs"]
+mod file_handle;
+#[path = "stdlib/file_move_touch.rs"]
+mod file_move_touch;
 #[path = "stdlib/functions/mod.rs"]
 mod functions;
 #[path = "stdlib/integration.rs"]
@@ -79,6 +93,15 @@ mod integration;
 mod io;
 #[path = "stdlib/json.rs"]
 mod json;
+#[path = "stdlib/json_path.rs"]
+mod json_path;
+
+#[path = "stdlib/json_patterns.rs"]
+mod json_patterns;
+#[path = "stdlib/kv.rs"]
+mod kv;
+#[path = "stdlib/number_format.rs"]
+mod number_format;
 #[path = "stdlib/option_result_chaining.rs"]
 mod option_result_chaining;
 #[path = "stdlib/parity.rs"]
@@ -87,6 +110,8 @@ mod parity;
 mod real_world;
 #[path = "stdlib/strings.rs"]
 mod strings;
+#[path = "stdlib/terminal.rs"]
+mod terminal;
 #[path = "stdlib/types.rs"]
 mod types;
 #[path = "stdlib/vm_stdlib/mod.rs"]
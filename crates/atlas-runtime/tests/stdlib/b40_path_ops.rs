@@ -1,4 +1,4 @@
-//! B40: Path operations tests (path.parent, path.resolve)
+//! B40: Path operations tests (path.parent, path.resolve, path library completion)
 //!
 //! Tests for H-284 (path.parent) and H-285 (path.resolve)
 
@@ -145,3 +145,76 @@ fn test_path_normalize() {
     assert!(!result_str.contains(".."), "Should have resolved ..");
     assert!(!result_str.contains("//"), "Should have removed //");
 }
+
+// ============================================================================
+// Path library completion: stem, withExtension, expandHome, relative, parse
+// ============================================================================
+
+#[rstest]
+#[case::stem("path.stem(\"/a/b/report.final.pdf\")", "report.final")]
+#[case::stem_no_ext("path.stem(\"/a/b/Makefile\")", "Makefile")]
+#[case::extname_alias("path.extname(\"/a/b/c.txt\")", "txt")]
+#[case::with_ext_dot("path.withExtension(\"src/main.atl\", \".json\")", "src/main.json")]
+#[case::with_ext_bare("path.withExtension(\"src/main.atl\", \"md\")", "src/main.md")]
+#[case::with_ext_add("path.withExtension(\"README\", \"txt\")", "README.txt")]
+#[case::with_ext_remove("path.withExtension(\"a/b.tar\", \"\")", "a/b")]
+#[case::relative_sibling("path.relative(\"/a/b/c\", \"/a/d\")", "../../d")]
+#[case::relative_child("path.relative(\"/a\", \"/a/b/c.txt\")", "b/c.txt")]
+#[case::relative_same("path.relative(\"/a/b\", \"/a/b\")", ".")]
+#[case::expand_plain("path.expandHome(\"/etc/hosts\")", "/etc/hosts")]
+#[case::expand_other_user("path.expandHome(\"~bob/x\")", "~bob/x")]
+#[case::to_posix("path.toPosix(\"a\\\\b\\\\c\")", "a/b/c")]
+#[case::equals("path.equals(\"/a/b\", \"/a/b\")", "true")]
+fn test_path_library(#[case] code: &str, #[case] expected: &str) {
+    let result = eval(code);
+    assert_eq!(format!("{}", result), expected, "Failed for: {}", code);
+}
+
+#[test]
+fn test_path_expand_home_uses_homedir() {
+    // Results use forward slashes, like path.normalize and path.relative
+    let home = eval("path.homedir()").to_string().replace('\\', "/");
+    assert_eq!(eval("path.expandHome(\"~\")").to_string(), home);
+
+    let expanded = eval("path.expandHome(\"~/projects/atlas\")").to_string();
+    assert_eq!(expanded, format!("{}/projects/atlas", home));
+
+    let expanded = eval("path.expandHome(\"~\\\\atlas\")").to_string();
+    assert_eq!(expanded, format!("{}/atlas", home));
+}
+
+#[test]
+fn test_path_with_extension_windows_separators() {
+    let result = eval(r#"path.withExtension("C:\\work\\main.atl", "json")"#);
+    let expected = if cfg!(windows) {
+        "C:/work/main.json"
+    } else {
+        "C:\\work\\main.json"
+    };
+    assert_eq!(result.to_string(), expected);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_path_keeps_backslashes_in_unix_names() {
+    // A backslash is an ordinary file-name character on Unix
+    let result = eval(r#"path.join("dir", "a\\b.txt")"#);
+    assert_eq!(result.to_string(), "dir/a\\b.txt");
+    let result = eval(r#"path.dirname("/tmp/a\\b/c.txt")"#);
+    assert_eq!(result.to_string(), "/tmp/a\\b");
+    let result = eval(r#"unwrap(path.parent("/tmp/a\\b/c.txt"))"#);
+    assert_eq!(result.to_string(), "/tmp/a\\b");
+    let result = eval(r#"path.absolute("/tmp/a\\b")"#);
+    assert_eq!(result.to_string(), "/tmp/a\\b");
+}
+
+#[test]
+fn test_path_parse_typed_fields() {
+    let result = eval(
+        r#"
+        let parts = path.parse("/home/user/data.json");
+        parts.dir + "|" + parts.name + "|" + parts.ext;
+    "#,
+    );
+    assert_eq!(result.to_string(), "/home/user|data|json");
+}
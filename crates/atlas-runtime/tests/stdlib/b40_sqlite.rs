@@ -1,6 +1,7 @@
 //! B40: SQLite bindings tests (H-286)
 //!
 //! Tests for sqlite namespace: open, execute, query, close
+//! (transactions and the database capability: b40_sqlite_transactions.rs)
 
 use atlas_runtime::runtime::Atlas;
 use atlas_runtime::value::Value;
@@ -0,0 +1,118 @@
+//! B40: SQLite transactions and the database capability
+//!
+//! Tests for conn.begin/commit/rollback/inTransaction, and for opening
+//! database files under the `database` permission
+
+use atlas_runtime::runtime::Atlas;
+use atlas_runtime::security::SecurityContext;
+use atlas_runtime::value::Value;
+
+fn eval(source: &str) -> Value {
+    let runtime = Atlas::new();
+    runtime.eval(source).unwrap()
+}
+
+// ============================================================================
+// Transactions
+// ============================================================================
+
+#[test]
+fn test_sqlite_commit_keeps_changes() {
+    let result = eval(
+        r#"
+        let conn = sqlite.open(":memory:");
+        conn.execute("CREATE TABLE ledger (amount INTEGER)", [])?;
+        conn.begin()?;
+        conn.execute("INSERT INTO ledger VALUES (?)", [100])?;
+        conn.execute("INSERT INTO ledger VALUES (?)", [-50])?;
+        conn.commit()?;
+        let rows = conn.query("SELECT SUM(amount) AS total FROM ledger", [])?;
+        rows[0]["total"].asNumber()
+    "#,
+    );
+    assert_eq!(result, Value::Number(50.0));
+}
+
+#[test]
+fn test_sqlite_rollback_discards_changes() {
+    let result = eval(
+        r#"
+        let conn = sqlite.open(":memory:");
+        conn.execute("CREATE TABLE ledger (amount INTEGER)", [])?;
+        conn.begin()?;
+        conn.execute("INSERT INTO ledger VALUES (100)", [])?;
+        conn.rollback()?;
+        let rows = conn.query("SELECT COUNT(*) AS cnt FROM ledger", [])?;
+        rows[0]["cnt"].asNumber()
+    "#,
+    );
+    assert_eq!(result, Value::Number(0.0));
+}
+
+#[test]
+fn test_sqlite_in_transaction() {
+    let result = eval(
+        r#"
+        let conn = sqlite.open(":memory:");
+        let before = conn.inTransaction();
+        conn.begin()?;
+        let during = conn.inTransaction();
+        conn.commit()?;
+        [before, during, conn.inTransaction()]
+    "#,
+    );
+    assert_eq!(result.to_string(), "[false, true, false]");
+}
+
+#[test]
+fn test_sqlite_commit_without_transaction_is_err() {
+    let result = eval(
+        r#"
+        let conn = sqlite.open(":memory:");
+        match conn.commit() {
+            Ok(_) => "ok",
+            Err(_) => "err"
+        }
+    "#,
+    );
+    assert_eq!(result.to_string(), "err");
+}
+
+// ============================================================================
+// Database capability
+// ============================================================================
+
+#[test]
+fn test_sqlite_open_file_requires_database_permission() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("app.db");
+    let source = format!(r#"sqlite.open("{}");"#, path.display());
+
+    let diagnostics = Atlas::new().eval(&source).unwrap_err();
+    assert_eq!(diagnostics[0].code, "AT0305");
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_sqlite_open_file_with_database_permission() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("app.db");
+    let mut security = SecurityContext::new();
+    security.grant_database(dir.path(), true);
+    let runtime = Atlas::new_with_security(security);
+
+    let result = runtime
+        .eval(&format!(
+            r#"
+            let conn = sqlite.open("{}");
+            conn.execute("CREATE TABLE t (x INTEGER)", [])?;
+            conn.execute("INSERT INTO t VALUES (?)", [7])?;
+            let rows = conn.query("SELECT x FROM t", [])?;
+            rows[0]["x"].asNumber()
+        "#,
+            path.display()
+        ))
+        .unwrap();
+    assert_eq!(result, Value::Number(7.0));
+    assert!(path.exists());
+}
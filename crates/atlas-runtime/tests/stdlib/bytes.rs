@@ -0,0 +1,151 @@
+use super::*;
+
+// --- Bytes ---
+
+// Tests for the Bytes value type: binary file I/O, hex/base64 conversion and slicing
+
+fn runtime_with_io(temp_dir: &TempDir) -> Atlas {
+    let mut security = SecurityContext::new();
+    security.grant_filesystem_read(temp_dir.path(), true);
+    security.grant_filesystem_write(temp_dir.path(), true);
+    Atlas::new_with_security(security)
+}
+
+#[test]
+fn test_read_bytes_invalid_utf8() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("image.bin");
+    fs::write(&path, [0x89, b'P', b'N', b'G', 0xff, 0x00]).unwrap();
+    let runtime = runtime_with_io(&temp_dir);
+
+    let code = format!(
+        r#"file.readBytes("{}").unwrap().toHex()"#,
+        path_for_atlas(&path)
+    );
+    assert_eq!(runtime.eval(&code).unwrap(), Value::string("89504e47ff00"));
+}
+
+#[test]
+fn test_read_text_of_binary_file_still_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("image.bin");
+    fs::write(&path, [0xff, 0xfe]).unwrap();
+    let runtime = runtime_with_io(&temp_dir);
+
+    let code = format!(r#"file.read("{}").isErr()"#, path_for_atlas(&path));
+    assert_eq!(runtime.eval(&code).unwrap(), Value::Bool(true));
+}
+
+#[test]
+fn test_write_bytes_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("out.bin");
+    let runtime = runtime_with_io(&temp_dir);
+
+    let code = format!(
+        r#"
+        let data: Bytes = encoding.fromHex("00ff10");
+        file.writeBytes("{0}", data).unwrap();
+        file.readBytes("{0}").unwrap() == data
+        "#,
+        path_for_atlas(&path)
+    );
+    assert_eq!(runtime.eval(&code).unwrap(), Value::Bool(true));
+    assert_eq!(fs::read(&path).unwrap(), vec![0x00, 0xff, 0x10]);
+}
+
+#[test]
+fn test_read_bytes_missing_file_is_err() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = runtime_with_io(&temp_dir);
+    let code = format!(
+        r#"file.readBytes("{}").isErr()"#,
+        path_for_atlas(&temp_dir.path().join("missing.bin"))
+    );
+    assert_eq!(runtime.eval(&code).unwrap(), Value::Bool(true));
+}
+
+#[test]
+fn test_read_bytes_requires_permission() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("secret.bin");
+    fs::write(&path, [1, 2, 3]).unwrap();
+
+    let code = format!(r#"file.readBytes("{}").isErr()"#, path_for_atlas(&path));
+    let runtime = Atlas::new_with_security(SecurityContext::new());
+    assert_eq!(runtime.eval(&code).unwrap(), Value::Bool(true));
+}
+
+#[test]
+fn test_write_bytes_requires_permission() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("out.bin");
+
+    let code = format!(
+        r#"file.writeBytes("{}", "hi".toBytes()).isErr()"#,
+        path_for_atlas(&path)
+    );
+    let runtime = Atlas::new_with_security(SecurityContext::new());
+    assert_eq!(runtime.eval(&code).unwrap(), Value::Bool(true));
+    assert!(!path.exists());
+}
+
+#[rstest]
+#[case(r#"encoding.fromHex("deadbeef").len()"#, 4.0)]
+#[case(r#"len(encoding.fromBase64("AAEC"))"#, 3.0)]
+#[case(r#"encoding.fromHex("00ff")[1]"#, 255.0)]
+#[case(r#"encoding.fromArray([1, 2, 3]).slice(1, 3)[0]"#, 2.0)]
+#[case(r#""héllo".toBytes().len()"#, 6.0)]
+fn test_bytes_numbers(#[case] source: &str, #[case] expected: f64) {
+    assert_eval_number(source, expected);
+}
+
+#[rstest]
+#[case(r#"encoding.fromBase64("iVBORw==").toHex()"#, "89504e47")]
+#[case(r#"encoding.fromHex("89504e47").toBase64()"#, "iVBORw==")]
+#[case(r#""atlas".toBytes().toUtf8().unwrap()"#, "atlas")]
+#[case(r#"encoding.fromArray([104, 105]).toUtf8().unwrap()"#, "hi")]
+#[case(r#"encoding.fromHex("0102030405").slice(1, 99).toHex()"#, "02030405")]
+#[case(r#"typeof(encoding.fromHex("00"))"#, "bytes")]
+#[case(
+    r#"encoding.fromBase64("iVBORw0KGgo=").slice(1, 4).toUtf8().unwrap()"#,
+    "PNG"
+)]
+#[case(r#"encoding.hexEncode("hi")"#, "6869")]
+fn test_bytes_strings(#[case] source: &str, #[case] expected: &str) {
+    assert_eval_string(source, expected);
+}
+
+#[test]
+fn test_bytes_to_array() {
+    let runtime = Atlas::new();
+    let result = runtime.eval(r#"encoding.fromHex("00ff").toArray()"#);
+    assert_eq!(
+        result.unwrap(),
+        Value::array(vec![Value::Number(0.0), Value::Number(255.0)])
+    );
+}
+
+#[test]
+fn test_bytes_to_utf8_invalid_is_err() {
+    assert_eval_bool(r#"encoding.fromHex("ff").toUtf8().isErr()"#, true);
+}
+
+#[rstest]
+#[case(r#"encoding.fromHex("zz")"#)]
+#[case(r#"encoding.fromBase64("!!")"#)]
+#[case(r#"encoding.fromArray([256])"#)]
+#[case(r#"encoding.fromArray([1.5])"#)]
+#[case(r#"encoding.fromHex("00")[1]"#)]
+#[case(r#"encoding.fromHex("0000").slice(2, 1)"#)]
+fn test_bytes_runtime_errors(#[case] source: &str) {
+    assert_has_error(source);
+}
+
+#[rstest]
+#[case(r#"file.writeBytes("out.bin", "text")"#)]
+#[case(r#"encoding.fromHex("00")["a"]"#)]
+#[case(r#"let b: Bytes = "text";"#)]
+fn test_bytes_type_errors(#[case] source: &str) {
+    assert_error_code(source, "AT3001");
+}
@@ -0,0 +1,155 @@
+use super::*;
+use atlas_runtime::stdlib::csv;
+
+// --- CSV ---
+
+// Tests for csv.parse, csv.parseRecords and csv.stringify
+
+#[rstest]
+#[case::plain("a,b\n1,2", "[[a, b], [1, 2]]")]
+#[case::crlf_and_trailing_newline("a,b\r\n1,2\r\n", "[[a, b], [1, 2]]")]
+#[case::quoted_delimiter(r#""x, y",z"#, "[[x, y, z]]")]
+#[case::doubled_quote(r#""say ""hi""",ok"#, r#"[[say "hi", ok]]"#)]
+#[case::quoted_newline("\"line1\nline2\",b", "[[line1\nline2, b]]")]
+#[case::empty_fields(",,\n\"\"", "[[, , ], []]")]
+#[case::blank_lines_skipped("a\n\n\nb\n", "[[a], [b]]")]
+#[case::inner_quote_literal(r#"5" disk,x"#, r#"[[5" disk, x]]"#)]
+fn test_parse(#[case] text: &str, #[case] expected: &str) {
+    let runtime = Atlas::new();
+    let code = format!("csv.parse({:?}).unwrap()", text);
+    let rows = runtime.eval(&code).unwrap();
+    assert_eq!(rows.to_string(), expected, "source: {}", code);
+}
+
+#[test]
+fn test_parse_field_count() {
+    assert_eval_number(r#"csv.parse("a,\"b,c\",d\n").unwrap()[0].len()"#, 3.0);
+}
+
+#[test]
+fn test_parse_with_delimiter() {
+    assert_eval_string(
+        r#"csv.parse("a;b\n1;\"2;3\"", record { delimiter: ";" }).unwrap()[1][1]"#,
+        "2;3",
+    );
+}
+
+#[rstest]
+#[case::unterminated("a,\"b\nc", "unterminated quoted field starting on line 1")]
+#[case::after_quote("\"a\"b,c", "unexpected 'b' after closing quote on line 1")]
+fn test_parse_errors(#[case] text: &str, #[case] message: &str) {
+    assert_eval_string(
+        &format!(
+            r#"match csv.parse({:?}) {{ Ok(_) => "ok", Err(e) => e }}"#,
+            text
+        ),
+        &format!("csv.parse: {}", message),
+    );
+}
+
+#[test]
+fn test_parse_records() {
+    assert_eval_string(
+        r#"
+        let rows = csv.parseRecords("name,city\nAda,\"London, UK\"\nAlan,Wilmslow\n").unwrap();
+        rows[0].get("city").unwrap() + "|" + rows[1].get("name").unwrap() + "|" + rows.len().toString()
+        "#,
+        "London, UK|Alan|2",
+    );
+}
+
+#[rstest]
+#[case::ragged("a,b\n1\n", "line 2 has 1 fields, expected 2 from the header")]
+#[case::duplicate("a,a\n1,2\n", "duplicate column 'a' in header")]
+fn test_parse_records_errors(#[case] text: &str, #[case] message: &str) {
+    assert_eval_string(
+        &format!(
+            r#"match csv.parseRecords({:?}) {{ Ok(_) => "ok", Err(e) => e }}"#,
+            text
+        ),
+        &format!("csv.parseRecords: {}", message),
+    );
+}
+
+#[test]
+fn test_stringify_arrays_quotes_fields() {
+    assert_eval_string(
+        r#"csv.stringify([["id", "note"], ["1", "a, \"b\""], ["2", "two\nlines"]])"#,
+        "id,note\n1,\"a, \"\"b\"\"\"\n2,\"two\nlines\"\n",
+    );
+}
+
+#[test]
+fn test_parse_skips_bom() {
+    let rows = csv::parse(&[Value::string("\u{FEFF}a,b")], Span::dummy()).unwrap();
+    assert_eq!(rows.to_string(), "Ok([[a, b]])");
+}
+
+#[test]
+fn test_stringify_scalar_fields() {
+    let row = Value::array(vec![
+        Value::Number(1.5),
+        Value::Bool(true),
+        Value::Null,
+        Value::string("x"),
+    ]);
+    let out = csv::stringify(&[Value::array(vec![row])], Span::dummy()).unwrap();
+    assert_eq!(out, Value::string("1.5,true,,x\n"));
+}
+
+#[rstest]
+#[case::mixed_rows(vec![Value::array(vec![Value::string("a")]), Value::Number(1.0)])]
+#[case::nested_field(vec![Value::array(vec![Value::array(vec![])])])]
+fn test_stringify_rejects_rows(#[case] rows: Vec<Value>) {
+    assert!(csv::stringify(&[Value::array(rows)], Span::dummy()).is_err());
+}
+
+#[test]
+fn test_stringify_maps_with_columns() {
+    assert_eval_string(
+        r#"
+        let rows = csv.parseRecords("name,age\nAda,36\n").unwrap();
+        csv.stringify(rows, record { columns: ["name", "age"], delimiter: "\t" })
+        "#,
+        "name\tage\nAda\t36\n",
+    );
+}
+
+#[test]
+fn test_stringify_maps_sorted_columns_without_header() {
+    assert_eval_string(
+        r#"
+        let rows = csv.parseRecords("b,a\n2,1\n").unwrap();
+        csv.stringify(rows, record { header: false })
+        "#,
+        "1,2\n",
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    assert_eval_bool(
+        r#"
+        let rows = [["a", "b,c"], ["\"q\"", "x\r\ny"], ["", " lead"], [""]];
+        csv.parse(csv.stringify(rows)).unwrap() == rows
+        "#,
+        true,
+    );
+}
+
+#[rstest]
+#[case::rows_not_array(r#"csv.stringify("a,b");"#)]
+#[case::long_delimiter(r#"csv.parse("a", record { delimiter: "::" });"#)]
+#[case::quote_delimiter(r#"csv.parse("a", record { delimiter: "\"" });"#)]
+fn test_runtime_errors(#[case] source: &str) {
+    assert!(
+        Atlas::new().eval(source).is_err(),
+        "expected error: {}",
+        source
+    );
+}
+
+#[test]
+fn test_variable_shadows_csv_namespace() {
+    assert_eval_number(r#"let csv = "a,b,c"; csv.split(",").len()"#, 3.0);
+}
@@ -0,0 +1,114 @@
+//! Cycle detection in recursive value operations
+//!
+//! A `shared<T>` cell is the only way to make a value contain itself. These
+//! tests build such cells directly and check that serialization, equality and
+//! clone report `CircularReference` instead of hanging.
+
+use super::*;
+use atlas_runtime::runtime_error_to_diagnostic;
+use atlas_runtime::stdlib::{json, reflect, types};
+use atlas_runtime::value::Shared;
+
+/// `shared([1, <itself>])`
+fn self_referential() -> Value {
+    let cell = Shared::new(Box::new(Value::Null));
+    let shared = Value::SharedValue(cell.clone());
+    cell.with_mut(|inner| **inner = Value::array(vec![Value::Number(1.0), shared.clone()]));
+    shared
+}
+
+fn shared_array(items: &[f64]) -> Value {
+    Value::shared(Value::array(
+        items.iter().map(|n| Value::Number(*n)).collect(),
+    ))
+}
+
+fn assert_circular(result: Result<Value, RuntimeError>) {
+    match result {
+        Err(RuntimeError::CircularReference { .. }) => {}
+        other => panic!("Expected CircularReference, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_to_json_rejects_cycle() {
+    assert_circular(json::to_json(&[self_referential()], Span::dummy()));
+}
+
+#[test]
+fn test_to_json_serializes_shared_contents() {
+    let value = Value::array(vec![shared_array(&[1.0, 2.0]), shared_array(&[1.0, 2.0])]);
+    let result = json::to_json(&[value], Span::dummy()).unwrap();
+    assert_eq!(result, Value::string("[[1,2],[1,2]]"));
+}
+
+#[test]
+fn test_deep_equals_rejects_cycle() {
+    let (a, b) = (self_referential(), self_referential());
+    assert_circular(reflect::deep_equals_fn(&[a, b], Span::dummy()));
+}
+
+#[test]
+fn test_deep_equals_same_cycle_is_identity() {
+    let a = self_referential();
+    let result = reflect::deep_equals_fn(&[a.clone(), a], Span::dummy()).unwrap();
+    assert_eq!(result, Value::Bool(true));
+}
+
+#[test]
+fn test_deep_equals_compares_shared_contents() {
+    let args = [shared_array(&[1.0, 2.0]), shared_array(&[1.0, 2.0])];
+    let result = reflect::deep_equals_fn(&args, Span::dummy()).unwrap();
+    assert_eq!(result, Value::Bool(true));
+
+    let args = [shared_array(&[1.0, 2.0]), shared_array(&[1.0, 3.0])];
+    let result = reflect::deep_equals_fn(&args, Span::dummy()).unwrap();
+    assert_eq!(result, Value::Bool(false));
+}
+
+#[test]
+fn test_assert_equal_rejects_cycle() {
+    let (a, b) = (self_referential(), self_referential());
+    assert_circular(atlas_test::assert_equal(&[a, b], Span::dummy()));
+}
+
+#[test]
+fn test_clone_rejects_cycle() {
+    assert_circular(reflect::clone_fn(&[self_referential()], Span::dummy()));
+}
+
+#[test]
+fn test_clone_copies_shared_cells() {
+    let original = shared_array(&[1.0]);
+    let copy = reflect::clone_fn(std::slice::from_ref(&original), Span::dummy()).unwrap();
+    let Value::SharedValue(cell) = &copy else {
+        panic!("Expected a shared value, got {:?}", copy);
+    };
+    cell.with_mut(|inner| **inner = Value::Number(2.0));
+    assert_eq!(original.to_string(), "shared([1])");
+    assert_eq!(copy.to_string(), "shared(2)");
+}
+
+#[test]
+fn test_display_marks_cycle() {
+    let value = self_referential();
+    assert_eq!(value.to_string(), "shared([1, <cycle>])");
+    assert!(format!("{:?}", value).contains("<cycle>"));
+}
+
+#[test]
+fn test_typeof_self_wrapping_cell() {
+    let cell = Shared::new(Box::new(Value::Null));
+    let shared = Value::SharedValue(cell.clone());
+    cell.with_mut(|inner| **inner = shared.clone());
+    let result = types::typeof_fn(&[shared], Span::dummy()).unwrap();
+    assert_eq!(result, Value::string("shared"));
+}
+
+#[test]
+fn test_circular_reference_diagnostic_code() {
+    let err = json::to_json(&[self_referential()], Span::dummy()).unwrap_err();
+    assert_eq!(err.kind(), "CircularReference");
+    let diag = runtime_error_to_diagnostic(err, Vec::new(), None);
+    assert_eq!(diag.code, "AT0141");
+}
@@ -0,0 +1,32 @@
+use super::*;
+
+// --- Cargo features ---
+
+// The builtin registry only holds what the enabled features provide
+
+#[rstest]
+#[case::fs("fileNsRead", cfg!(feature = "fs"))]
+#[case::fs_path("pathJoinArray", cfg!(feature = "fs"))]
+#[case::fs_handle("fileHandleReadLine", cfg!(feature = "fs"))]
+#[case::regex("regexNew", cfg!(feature = "regex"))]
+#[case::collections_set("setNew", cfg!(feature = "collections"))]
+#[case::collections_queue("queueNew", cfg!(feature = "collections"))]
+#[case::collections_stack("stackNew", cfg!(feature = "collections"))]
+#[case::http("httpNsGet", cfg!(feature = "http"))]
+fn test_registry_follows_features(#[case] name: &str, #[case] enabled: bool) {
+    assert_eq!(is_builtin(name), enabled, "{}", name);
+}
+
+#[rstest]
+#[case::map("mapNew")]
+#[case::io("ioNsReadLine")]
+#[case::csv("csvNsParse")]
+fn test_core_builtins_always_registered(#[case] name: &str) {
+    assert!(is_builtin(name), "{}", name);
+}
+
+#[test]
+fn test_regex_literal_follows_feature() {
+    let result = Atlas::new().eval(r#"let r = /a+/; r.isMatch("caaat")"#);
+    assert_eq!(result.is_ok(), cfg!(feature = "regex"), "{:?}", result);
+}
@@ -0,0 +1,188 @@
+use super::*;
+
+// --- FileHandle ---
+
+// Tests for streaming file handles: file.open, readLine, readChunk, writeChunk, close
+
+fn runtime_with_io(temp_dir: &TempDir) -> Atlas {
+    let mut security = SecurityContext::new();
+    security.grant_filesystem_read(temp_dir.path(), true);
+    security.grant_filesystem_write(temp_dir.path(), true);
+    Atlas::new_with_security(security)
+}
+
+#[test]
+fn test_read_lines_until_eof() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("lines.txt");
+    fs::write(&path, "alpha\r\nbeta\n\ngamma").unwrap();
+    let runtime = runtime_with_io(&temp_dir);
+
+    let code = format!(
+        r#"
+        let h = file.open("{}").unwrap();
+        let mut out = "";
+        let mut line = h.readLine();
+        while line.isSome() {{
+            out = out + "[" + line.unwrap() + "]";
+            line = h.readLine();
+        }}
+        h.close();
+        out
+        "#,
+        path_for_atlas(&path)
+    );
+    assert_eq!(
+        runtime.eval(&code).unwrap(),
+        Value::string("[alpha][beta][][gamma]")
+    );
+}
+
+#[test]
+fn test_read_chunks() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("data.bin");
+    fs::write(&path, [0u8, 1, 2, 3, 4]).unwrap();
+    let runtime = runtime_with_io(&temp_dir);
+
+    let code = format!(
+        r#"
+        let h = file.open("{}").unwrap();
+        let mut sizes: number[] = [];
+        let mut chunk = h.readChunk(2);
+        while chunk.isSome() {{
+            sizes = sizes.push(chunk.unwrap().len());
+            chunk = h.readChunk(2);
+        }}
+        sizes
+        "#,
+        path_for_atlas(&path)
+    );
+    assert_eq!(
+        runtime.eval(&code).unwrap(),
+        Value::array(vec![
+            Value::Number(2.0),
+            Value::Number(2.0),
+            Value::Number(1.0)
+        ])
+    );
+}
+
+#[test]
+fn test_write_then_append() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("out.txt");
+    let runtime = runtime_with_io(&temp_dir);
+
+    let code = format!(
+        r#"
+        let w = file.open("{0}", "w").unwrap();
+        w.writeChunk("head ");
+        w.writeChunk(encoding.fromHex("6869"));
+        w.close();
+        let a = file.open("{0}", "a").unwrap();
+        a.writeChunk("!");
+        a.close();
+        "#,
+        path_for_atlas(&path)
+    );
+    runtime.eval(&code).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "head hi!");
+}
+
+#[test]
+fn test_handle_closed_at_scope_exit() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("scoped.txt");
+    let runtime = runtime_with_io(&temp_dir);
+
+    // No close(): the buffered write is flushed when the local goes away
+    let code = format!(
+        r#"
+        fn log(): void {{
+            let h = file.open("{}", "w").unwrap();
+            h.writeChunk("kept");
+        }}
+        log();
+        "#,
+        path_for_atlas(&path)
+    );
+    runtime.eval(&code).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "kept");
+}
+
+#[test]
+fn test_handle_closed_on_uncaught_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("partial.txt");
+    let runtime = runtime_with_io(&temp_dir);
+
+    let code = format!(
+        r#"
+        fn work(): number {{
+            let h = file.open("{}", "w").unwrap();
+            h.writeChunk("before the error");
+            let xs: number[] = [];
+            return xs[3];
+        }}
+        work();
+        "#,
+        path_for_atlas(&path)
+    );
+    assert!(runtime.eval(&code).is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "before the error");
+}
+
+#[test]
+fn test_open_missing_file_is_err() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime = runtime_with_io(&temp_dir);
+    let code = format!(
+        r#"file.open("{}").isErr()"#,
+        path_for_atlas(&temp_dir.path().join("missing.txt"))
+    );
+    assert_eq!(runtime.eval(&code).unwrap(), Value::Bool(true));
+}
+
+#[rstest]
+#[case("r")]
+#[case("w")]
+fn test_open_requires_permission(#[case] mode: &str) {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("secret.txt");
+    fs::write(&path, "x").unwrap();
+
+    let code = format!(
+        r#"file.open("{}", "{}").isErr()"#,
+        path_for_atlas(&path),
+        mode
+    );
+    let runtime = Atlas::new_with_security(SecurityContext::new());
+    assert_eq!(runtime.eval(&code).unwrap(), Value::Bool(true));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "x");
+}
+
+#[rstest]
+#[case(r#"let h = file.open("{p}").unwrap(); h.close(); h.readLine();"#)]
+#[case(r#"let h = file.open("{p}").unwrap(); h.close(); h.close();"#)]
+#[case(r#"let h = file.open("{p}").unwrap(); h.writeChunk("x");"#)]
+#[case(r#"let h = file.open("{p}", "a").unwrap(); h.readLine();"#)]
+#[case(r#"let h = file.open("{p}").unwrap(); h.readChunk(0);"#)]
+#[case(r#"file.open("{p}", "rw");"#)]
+fn test_handle_runtime_errors(#[case] template: &str) {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("data.txt");
+    fs::write(&path, "data").unwrap();
+    let runtime = runtime_with_io(&temp_dir);
+
+    let code = template.replace("{p}", &path_for_atlas(&path));
+    assert!(runtime.eval(&code).is_err(), "expected error: {}", code);
+}
+
+#[rstest]
+#[case(r#"let h: FileHandle = "data.txt";"#)]
+#[case(r#"file.open("data.txt").unwrap().writeChunk(42);"#)]
+#[case(r#"file.open("data.txt").unwrap().readChunk("all");"#)]
+fn test_handle_type_errors(#[case] source: &str) {
+    assert_error_code(source, "AT3001");
+}
@@ -0,0 +1,276 @@
+//! File operations tests (file.move, file.touch, file.setPermissions, file.writeAtomic,
+//! typed file.info)
+
+use atlas_runtime::runtime::Atlas;
+use atlas_runtime::security::SecurityContext;
+use atlas_runtime::value::Value;
+use std::fs;
+use tempfile::TempDir;
+
+/// Helper to escape path for Atlas string
+fn path_for_atlas(path: &std::path::Path) -> String {
+    path.to_string_lossy().replace('\\', "\\\\")
+}
+
+fn runtime_for(temp_dir: &TempDir) -> Atlas {
+    let mut security = SecurityContext::new();
+    security.grant_filesystem_write(temp_dir.path(), true);
+    security.grant_filesystem_read(temp_dir.path(), true);
+    Atlas::new_with_security(security)
+}
+
+// ============================================================================
+// file.move
+// ============================================================================
+
+#[test]
+fn test_file_move_basic() {
+    let temp_dir = TempDir::new().unwrap();
+    let src = temp_dir.path().join("a.txt");
+    let dst = temp_dir.path().join("b.txt");
+    fs::write(&src, "payload").unwrap();
+
+    let code = format!(
+        r#"file.move("{}", "{}")"#,
+        path_for_atlas(&src),
+        path_for_atlas(&dst)
+    );
+    let result = runtime_for(&temp_dir).eval(&code).unwrap();
+
+    assert!(matches!(result, Value::Result(Ok(_))));
+    assert!(!src.exists());
+    assert_eq!(fs::read_to_string(&dst).unwrap(), "payload");
+}
+
+#[test]
+fn test_file_move_missing_source_is_err() {
+    let temp_dir = TempDir::new().unwrap();
+    let src = temp_dir.path().join("missing.txt");
+    let dst = temp_dir.path().join("b.txt");
+
+    let code = format!(
+        r#"file.move("{}", "{}")"#,
+        path_for_atlas(&src),
+        path_for_atlas(&dst)
+    );
+    let result = runtime_for(&temp_dir).eval(&code).unwrap();
+
+    assert!(matches!(result, Value::Result(Err(_))));
+}
+
+#[test]
+fn test_file_move_denied_without_write_permission() {
+    let temp_dir = TempDir::new().unwrap();
+    let src = temp_dir.path().join("a.txt");
+    let dst = temp_dir.path().join("b.txt");
+    fs::write(&src, "payload").unwrap();
+
+    let code = format!(
+        r#"file.move("{}", "{}")"#,
+        path_for_atlas(&src),
+        path_for_atlas(&dst)
+    );
+    let result = Atlas::new().eval(&code).unwrap();
+
+    assert!(matches!(result, Value::Result(Err(_))));
+    assert!(src.exists());
+}
+
+#[test]
+fn test_file_move_reports_rename_error_without_copying() {
+    // Renaming a file over a directory fails on the same filesystem; only a
+    // cross-device rename falls back to copy + remove
+    let temp_dir = TempDir::new().unwrap();
+    let src = temp_dir.path().join("a.txt");
+    let dst = temp_dir.path().join("dir");
+    fs::write(&src, "payload").unwrap();
+    fs::create_dir(&dst).unwrap();
+
+    let code = format!(
+        r#"file.move("{}", "{}")"#,
+        path_for_atlas(&src),
+        path_for_atlas(&dst)
+    );
+    let result = runtime_for(&temp_dir).eval(&code).unwrap();
+
+    match result {
+        Value::Result(Err(e)) => assert!(e.to_string().contains("failed to move"), "{}", e),
+        other => panic!("expected Err, got {:?}", other),
+    }
+    assert!(src.exists());
+}
+
+// ============================================================================
+// file.touch
+// ============================================================================
+
+#[test]
+fn test_file_touch_creates_missing_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let target = temp_dir.path().join("new.txt");
+
+    let code = format!(r#"file.touch("{}")"#, path_for_atlas(&target));
+    let result = runtime_for(&temp_dir).eval(&code).unwrap();
+
+    assert!(matches!(result, Value::Result(Ok(_))));
+    assert!(target.is_file());
+    assert_eq!(fs::read_to_string(&target).unwrap(), "");
+}
+
+#[test]
+fn test_file_touch_preserves_existing_content() {
+    let temp_dir = TempDir::new().unwrap();
+    let target = temp_dir.path().join("keep.txt");
+    fs::write(&target, "keep me").unwrap();
+
+    let code = format!(r#"file.touch("{}")"#, path_for_atlas(&target));
+    let result = runtime_for(&temp_dir).eval(&code).unwrap();
+
+    assert!(matches!(result, Value::Result(Ok(_))));
+    assert_eq!(fs::read_to_string(&target).unwrap(), "keep me");
+}
+
+// ============================================================================
+// file.setPermissions
+// ============================================================================
+
+#[cfg(unix)]
+#[test]
+fn test_file_set_permissions_applies_mode() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let target = temp_dir.path().join("script.sh");
+    fs::write(&target, "#!/bin/sh").unwrap();
+
+    let code = format!(r#"file.setPermissions("{}", 493)"#, path_for_atlas(&target));
+    let result = runtime_for(&temp_dir).eval(&code).unwrap();
+
+    assert!(matches!(result, Value::Result(Ok(_))));
+    let mode = fs::metadata(&target).unwrap().permissions().mode() & 0o777;
+    assert_eq!(mode, 0o755);
+}
+
+#[test]
+fn test_file_set_permissions_rejects_invalid_mode() {
+    let temp_dir = TempDir::new().unwrap();
+    let target = temp_dir.path().join("x.txt");
+    fs::write(&target, "x").unwrap();
+
+    let code = format!(r#"file.setPermissions("{}", -1)"#, path_for_atlas(&target));
+    assert!(runtime_for(&temp_dir).eval(&code).is_err());
+}
+
+// ============================================================================
+// file.info typed fields
+// ============================================================================
+
+#[test]
+fn test_file_info_typed_fields() {
+    let temp_dir = TempDir::new().unwrap();
+    let target = temp_dir.path().join("info.txt");
+    fs::write(&target, "12345").unwrap();
+
+    let runtime = runtime_for(&temp_dir);
+    let code = format!(
+        r#"let info = file.info("{}"); info.size;"#,
+        path_for_atlas(&target)
+    );
+    assert!(matches!(runtime.eval(&code).unwrap(), Value::Number(n) if n == 5.0));
+
+    let code = format!(
+        r#"let info = file.info("{}"); info.isFile && !info.isDir && info.modified > 0;"#,
+        path_for_atlas(&target)
+    );
+    assert!(matches!(runtime.eval(&code).unwrap(), Value::Bool(true)));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_file_info_reports_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let target = temp_dir.path().join("ro.txt");
+    fs::write(&target, "x").unwrap();
+    fs::set_permissions(&target, fs::Permissions::from_mode(0o444)).unwrap();
+
+    let code = format!(
+        r#"let info = file.info("{}"); info.permissions;"#,
+        path_for_atlas(&target)
+    );
+    let result = runtime_for(&temp_dir).eval(&code).unwrap();
+    assert!(matches!(result, Value::Number(n) if n == 0o444 as f64));
+}
+
+// ============================================================================
+// file.writeAtomic
+// ============================================================================
+
+#[test]
+fn test_file_write_atomic_replaces_contents() {
+    let temp_dir = TempDir::new().unwrap();
+    let target = temp_dir.path().join("state.json");
+    fs::write(&target, "old").unwrap();
+
+    let code = format!(
+        r#"file.writeAtomic("{}", "new", true)"#,
+        path_for_atlas(&target)
+    );
+    let result = runtime_for(&temp_dir).eval(&code).unwrap();
+
+    assert!(matches!(result, Value::Result(Ok(_))));
+    assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+    // No temp file left behind next to the target
+    assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_file_write_atomic_creates_missing_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let target = temp_dir.path().join("fresh.txt");
+
+    let code = format!(r#"file.writeAtomic("{}", "hi")"#, path_for_atlas(&target));
+    let result = runtime_for(&temp_dir).eval(&code).unwrap();
+
+    assert!(matches!(result, Value::Result(Ok(_))));
+    assert_eq!(fs::read_to_string(&target).unwrap(), "hi");
+}
+
+#[test]
+fn test_file_write_atomic_permission_denied() {
+    let temp_dir = TempDir::new().unwrap();
+    let target = temp_dir.path().join("denied.txt");
+
+    let code = format!(r#"file.writeAtomic("{}", "x")"#, path_for_atlas(&target));
+    let result = Atlas::new().eval(&code).unwrap();
+
+    match result {
+        Value::Result(Err(msg)) => assert!(
+            msg.to_string()
+                .starts_with("file.writeAtomic: permission denied"),
+            "{}",
+            msg
+        ),
+        other => panic!("expected Err, got {:?}", other),
+    }
+    assert!(!target.exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_atomic_preserves_mode() {
+    use atlas_runtime::stdlib::io::write_atomic;
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let target = temp_dir.path().join("run.sh");
+    fs::write(&target, "echo old").unwrap();
+    fs::set_permissions(&target, fs::Permissions::from_mode(0o755)).unwrap();
+
+    write_atomic(&target, b"echo new", false).unwrap();
+
+    assert_eq!(fs::read_to_string(&target).unwrap(), "echo new");
+    let mode = fs::metadata(&target).unwrap().permissions().mode() & 0o777;
+    assert_eq!(mode, 0o755);
+}
@@ -28,10 +28,6 @@ fn ok_val(v: Value) -> Value {
     Value::Result(Ok(Box::new(v)))
 }
 
-fn some_val(v: Value) -> Value {
-    Value::Option(Some(Box::new(v)))
-}
-
 fn throwing_fn() -> Value {
     Value::NativeFunction(Arc::new(|_| {
         Err(RuntimeError::TypeError {
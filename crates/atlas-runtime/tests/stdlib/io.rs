@@ -352,11 +352,8 @@ fn test_file_info_file() {
     let result = runtime.eval(&code);
 
     assert!(result.is_ok());
-    // Result should be a JsonValue object
-    assert!(matches!(
-        result.unwrap(),
-        atlas_runtime::Value::JsonValue(_)
-    ));
+    // Result should be a typed record (map-backed)
+    assert!(matches!(result.unwrap(), atlas_runtime::Value::Map(_)));
 }
 
 #[test]
@@ -802,11 +799,8 @@ fn test_file_info_size_check() {
     let result = runtime.eval(&code);
 
     assert!(result.is_ok());
-    // Verify it returns a JsonValue
-    assert!(matches!(
-        result.unwrap(),
-        atlas_runtime::Value::JsonValue(_)
-    ));
+    // Verify it returns a typed record
+    assert!(matches!(result.unwrap(), atlas_runtime::Value::Map(_)));
 }
 
 #[test]
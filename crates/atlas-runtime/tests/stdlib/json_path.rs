@@ -0,0 +1,121 @@
+use super::*;
+
+// --- JSON paths ---
+
+// Tests for getPath, setPath and hasPath on json values
+
+const DOC: &str = r#"{"a":{"b":[1,2,{"c":"deep"}]},"content-type":"text/plain","":0}"#;
+
+fn with_doc(body: &str) -> String {
+    format!("let data = Json.parse({:?}).unwrap();\n{}", DOC, body)
+}
+
+#[rstest]
+#[case::dotted(r#"data.getPath("a.b[2].c").unwrap().asString()"#, "deep")]
+#[case::dollar_root(r#"data.getPath("$.a.b[2].c").unwrap().asString()"#, "deep")]
+#[case::quoted_key(
+    r#"data.getPath("[\"content-type\"]").unwrap().asString()"#,
+    "text/plain"
+)]
+#[case::single_quoted_key(
+    r#"data.getPath("$['content-type']").unwrap().asString()"#,
+    "text/plain"
+)]
+#[case::namespace(r#"Json.getPath(data, "a.b[0]").unwrap().asNumber().toString()"#, "1")]
+#[case::whole_value(
+    r#"Json.stringify(data.getPath("$").unwrap().getPath("a.b[1]").unwrap())"#,
+    "2"
+)]
+fn test_get_path(#[case] expr: &str, #[case] expected: &str) {
+    assert_eval_string(&with_doc(expr), expected);
+}
+
+#[rstest]
+#[case::missing_key("a.x.c", "no key 'x' in object at $.a")]
+#[case::out_of_bounds("a.b[7]", "index 7 out of bounds for array of length 3 at $.a.b")]
+#[case::key_on_number("a.b[0].z", "cannot read key 'z' from number at $.a.b[0]")]
+#[case::index_on_object("a[0]", "cannot read index 0 from object at $.a")]
+#[case::quoted_segment_in_location(
+    "[\"content-type\"].x",
+    r#"cannot read key 'x' from string at $["content-type"]"#
+)]
+fn test_get_path_errors(#[case] path: &str, #[case] message: &str) {
+    assert_eval_string(
+        &with_doc(&format!(
+            r#"match data.getPath({:?}) {{ Ok(_) => "ok", Err(e) => e }}"#,
+            path
+        )),
+        &format!("getPath: {}", message),
+    );
+}
+
+#[rstest]
+#[case::present("a.b[2].c", true)]
+#[case::empty_key("[\"\"]", true)]
+#[case::missing("a.b[3]", false)]
+#[case::wrong_kind("a.b.c", false)]
+fn test_has_path(#[case] path: &str, #[case] expected: bool) {
+    assert_eval_bool(&with_doc(&format!("data.hasPath({:?})", path)), expected);
+}
+
+#[test]
+fn test_set_path_replaces_and_leaves_original() {
+    assert_eval_string(
+        &with_doc(
+            r#"
+            let updated = data.setPath("a.b[2].c", "changed").unwrap();
+            Json.stringify(updated.getPath("a.b").unwrap()) + "|" + data.getPath("a.b[2].c").unwrap().asString()
+            "#,
+        ),
+        r#"[1,2,{"c":"changed"}]|deep"#,
+    );
+}
+
+#[rstest]
+#[case::new_key(r#"data.setPath("a.d", true)"#, "a.d", "true")]
+#[case::append(r#"data.setPath("a.b[3]", record { n: 1 })"#, "a.b[3]", r#"{"n":1}"#)]
+#[case::json_value(
+    r#"data.setPath("x", data.getPath("a.b[2]").unwrap())"#,
+    "x",
+    r#"{"c":"deep"}"#
+)]
+#[case::root(r#"data.setPath("", [1, 2])"#, "$", "[1,2]")]
+fn test_set_path(#[case] set: &str, #[case] read: &str, #[case] expected: &str) {
+    assert_eval_string(
+        &with_doc(&format!(
+            "Json.stringify({}.unwrap().getPath({:?}).unwrap())",
+            set, read
+        )),
+        expected,
+    );
+}
+
+#[rstest]
+#[case::missing_parent("a.q.r", "no key 'q' in object at $.a")]
+#[case::gap_in_array("a.b[5]", "index 5 out of bounds for array of length 3 at $.a.b")]
+#[case::through_scalar("a.b[0].n", "cannot read key 'n' from number at $.a.b[0]")]
+fn test_set_path_errors(#[case] path: &str, #[case] message: &str) {
+    assert_eval_string(
+        &with_doc(&format!(
+            r#"match data.setPath({:?}, 1) {{ Ok(_) => "ok", Err(e) => e }}"#,
+            path
+        )),
+        &format!("setPath: {}", message),
+    );
+}
+
+#[rstest]
+#[case::empty_key("a..b")]
+#[case::trailing_dot("a.")]
+#[case::unclosed_bracket("a[1")]
+#[case::negative_index("a[-1]")]
+#[case::unterminated_quote("[\"a]")]
+#[case::junk_after_bracket("a[0]b")]
+fn test_invalid_paths_are_runtime_errors(#[case] path: &str) {
+    let source = with_doc(&format!("data.hasPath({:?});", path));
+    assert!(
+        Atlas::new().eval(&source).is_err(),
+        "expected error: {}",
+        path
+    );
+}
@@ -0,0 +1,121 @@
+use super::*;
+
+// --- JSON shape patterns ---
+
+// Tests for matching json values against `{ "key": pattern }` and `[...]` patterns
+
+const DESCRIBE: &str = r#"
+fn describe(event: json): string {
+    return match event {
+        { "type": "user", "name": name, "tags": [first, ...rest] } =>
+            `${name.asString()} tagged ${first.asString()} +${Json.stringify(rest)}`,
+        { "type": "user", "name": name } => `user ${name.asString()}`,
+        { "type": "count", "value": 0 } => "zero",
+        { "type": "count", "value": n } if typeof(n) == "number" => `count ${n.asNumber()}`,
+        { "type": "flag", "on": true } => "on",
+        { "type": null } => "untyped",
+        { "point": { "x": x, "y": y } } => `at ${x.asNumber() + y.asNumber()}`,
+        [a, b] => `pair ${a.asNumber() * b.asNumber()}`,
+        _ => "other",
+    };
+}
+"#;
+
+fn describe(input: &str) -> String {
+    format!("{}describe(Json.parse({:?}).unwrap())", DESCRIBE, input)
+}
+
+#[rstest]
+#[case::binds_nested_array(
+    r#"{"type":"user","name":"Ada","tags":["x","y","z"]}"#,
+    r#"Ada tagged x +["y","z"]"#
+)]
+#[case::binds_field(r#"{"type":"user","name":"Alan","id":7}"#, "user Alan")]
+#[case::number_literal(r#"{"type":"count","value":0}"#, "zero")]
+#[case::guarded_binding(r#"{"type":"count","value":5}"#, "count 5")]
+#[case::bool_literal(r#"{"type":"flag","on":true}"#, "on")]
+#[case::null_literal(r#"{"type":null}"#, "untyped")]
+#[case::nested_object(r#"{"point":{"x":2,"y":3}}"#, "at 5")]
+#[case::array(r#"[3, 4]"#, "pair 12")]
+fn test_match_shapes(#[case] input: &str, #[case] expected: &str) {
+    assert_eval_string(&describe(input), expected);
+}
+
+#[rstest]
+#[case::string_is_not_number(r#"{"type":"count","value":"0"}"#)]
+#[case::number_is_not_bool(r#"{"type":"flag","on":1}"#)]
+#[case::missing_key(r#"{"kind":"user"}"#)]
+#[case::string_is_not_object(r#""user""#)]
+#[case::wrong_array_length(r#"[1, 2, 3]"#)]
+#[case::nested_not_object(r#"{"point":[2,3]}"#)]
+fn test_match_shapes_fall_through(#[case] input: &str) {
+    assert_eval_string(&describe(input), "other");
+}
+
+#[test]
+fn test_bound_fields_are_json() {
+    assert_eval_string(
+        r#"
+        let data = Json.parse("{\"user\":{\"name\":\"Ada\"}}").unwrap();
+        match data {
+            { "user": user } => user.getString("name").unwrap(),
+            _ => "none",
+        }
+        "#,
+        "Ada",
+    );
+}
+
+#[test]
+fn test_unquoted_keys_match_json() {
+    assert_eval_number(
+        r#"
+        let data = Json.parse("{\"width\":3,\"height\":4}").unwrap();
+        match data {
+            { width, height } => width.asNumber() * height.asNumber(),
+            _ => 0,
+        }
+        "#,
+        12.0,
+    );
+}
+
+#[test]
+fn test_quoted_keys_match_maps() {
+    assert_eval_string(
+        r#"
+        let headers = csv.parseRecords("content-type,status\ntext/plain,ok\n").unwrap()[0];
+        match headers {
+            { "status": "ok", "content-type": kind } => kind,
+            _ => "unknown",
+        }
+        "#,
+        "text/plain",
+    );
+}
+
+#[test]
+fn test_json_match_requires_wildcard() {
+    let diags = Atlas::new()
+        .eval(
+            r#"
+            let data = Json.parse("{}").unwrap();
+            match data {
+                { "type": kind } => kind,
+            }
+            "#,
+        )
+        .unwrap_err();
+    assert!(
+        diags
+            .iter()
+            .any(|d| d.message.contains("wildcard for json")),
+        "{:?}",
+        diags
+    );
+}
+
+#[test]
+fn test_quoted_key_requires_sub_pattern() {
+    assert_has_error(r#"match 1 { { "type" } => 1, _ => 0 }"#);
+}
@@ -0,0 +1,252 @@
+//! Key-value store tests
+//!
+//! Tests for the kv namespace: open, get/set/delete, transactions, persistence
+
+use atlas_runtime::runtime::Atlas;
+use atlas_runtime::security::SecurityContext;
+use atlas_runtime::value::Value;
+use std::path::Path;
+
+fn eval(source: &str) -> Value {
+    let runtime = Atlas::new();
+    runtime.eval(source).unwrap()
+}
+
+/// Runtime allowed to open stores under `dir`
+fn runtime_for(dir: &Path) -> Atlas {
+    let mut security = SecurityContext::new();
+    security.grant_database(dir, true);
+    Atlas::new_with_security(security)
+}
+
+// ============================================================================
+// Basic operations
+// ============================================================================
+
+#[test]
+fn test_kv_open_memory() {
+    let result = eval(
+        r#"
+        let store = kv.open(":memory:");
+        reflect.typeOf(store)
+    "#,
+    );
+    assert_eq!(result.to_string(), "KvStore");
+}
+
+#[test]
+fn test_kv_set_and_get_round_trips_values() {
+    let result = eval(
+        r#"
+        let store = kv.open(":memory:");
+        store.set("name", "atlas");
+        store.set("count", 3);
+        store.set("tags", ["a", "b"]);
+        let name: string = store.get("name").unwrap();
+        let count: number = store.get("count").unwrap();
+        let tags = store.get("tags").unwrap();
+        name + ":" + count.toString() + ":" + tags[1]
+    "#,
+    );
+    assert_eq!(result, Value::string("atlas:3:b"));
+}
+
+#[test]
+fn test_kv_get_missing_key_is_none() {
+    let result = eval(
+        r#"
+        let store = kv.open(":memory:");
+        store.get("missing").isNone()
+    "#,
+    );
+    assert_eq!(result, Value::Bool(true));
+}
+
+#[test]
+fn test_kv_set_overwrites() {
+    let result = eval(
+        r#"
+        let store = kv.open(":memory:");
+        store.set("k", 1);
+        store.set("k", 2);
+        let v: number = store.get("k").unwrap();
+        v
+    "#,
+    );
+    assert_eq!(result, Value::Number(2.0));
+}
+
+#[test]
+fn test_kv_delete_and_has() {
+    let result = eval(
+        r#"
+        let store = kv.open(":memory:");
+        store.set("k", true);
+        let removed = store.delete("k");
+        let again = store.delete("k");
+        removed && !again && !store.has("k")
+    "#,
+    );
+    assert_eq!(result, Value::Bool(true));
+}
+
+#[test]
+fn test_kv_keys_are_sorted() {
+    let result = eval(
+        r#"
+        let store = kv.open(":memory:");
+        store.set("b", 2);
+        store.set("a", 1);
+        store.set("c", 3);
+        store.keys().join(",")
+    "#,
+    );
+    assert_eq!(result, Value::string("a,b,c"));
+}
+
+#[test]
+fn test_kv_use_after_close_is_error() {
+    let result = Atlas::new().eval(
+        r#"
+        let store = kv.open(":memory:");
+        store.close();
+        store.get("k");
+    "#,
+    );
+    assert!(result.is_err());
+}
+
+// ============================================================================
+// Transactions
+// ============================================================================
+
+#[test]
+fn test_kv_transaction_commits() {
+    let result = eval(
+        r#"
+        let store = kv.open(":memory:");
+        let n = store.transaction(fn(s: KvStore): number {
+            s.set("a", 1);
+            s.set("b", 2);
+            return 2;
+        });
+        n + store.keys().length()
+    "#,
+    );
+    assert_eq!(result, Value::Number(4.0));
+}
+
+#[test]
+fn test_kv_transaction_rolls_back_on_err() {
+    let result = eval(
+        r#"
+        let store = kv.open(":memory:");
+        store.set("balance", 10);
+        let r: Result<number, string> = store.transaction(fn(s: KvStore): Result<number, string> {
+            s.set("balance", 0);
+            return Err("insufficient funds");
+        });
+        let balance: number = store.get("balance").unwrap();
+        r.isErr() && balance == 10
+    "#,
+    );
+    assert_eq!(result, Value::Bool(true));
+}
+
+#[test]
+fn test_kv_transaction_rolls_back_on_runtime_error() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("state.kv");
+
+    runtime_for(dir.path())
+        .eval(&format!(
+            r#"
+            let store = kv.open("{}");
+            store.set("k", "before");
+            store.close();
+        "#,
+            path.display()
+        ))
+        .unwrap();
+    let result = runtime_for(dir.path()).eval(&format!(
+        r#"
+        let store = kv.open("{}");
+        store.transaction(fn(s: KvStore): number {{
+            s.set("k", "after");
+            let xs = [1];
+            return xs[5];
+        }});
+    "#,
+        path.display()
+    ));
+    assert!(result.is_err());
+    let value = runtime_for(dir.path())
+        .eval(&format!(
+            r#"let store = kv.open("{}"); let v: string = store.get("k").unwrap(); v"#,
+            path.display()
+        ))
+        .unwrap();
+    assert_eq!(value, Value::string("before"));
+}
+
+#[test]
+fn test_kv_nested_transaction_is_error() {
+    let result = Atlas::new().eval(
+        r#"
+        let store = kv.open(":memory:");
+        store.transaction(fn(s: KvStore): number {
+            return s.transaction(fn(inner: KvStore): number { return 1; });
+        });
+    "#,
+    );
+    assert!(result.is_err());
+}
+
+// ============================================================================
+// Persistence and permissions
+// ============================================================================
+
+#[test]
+fn test_kv_persists_across_opens() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("state.kv");
+
+    runtime_for(dir.path())
+        .eval(&format!(
+            r#"
+            let store = kv.open("{}");
+            store.set("visits", 41);
+            store.transaction(fn(s: KvStore): null {{
+                let v: number = s.get("visits").unwrap();
+                s.set("visits", v + 1);
+                return null;
+            }});
+            store.close();
+        "#,
+            path.display()
+        ))
+        .unwrap();
+
+    let result = runtime_for(dir.path())
+        .eval(&format!(
+            r#"
+            let store = kv.open("{}");
+            let v: number = store.get("visits").unwrap();
+            v
+        "#,
+            path.display()
+        ))
+        .unwrap();
+    assert_eq!(result, Value::Number(42.0));
+}
+
+#[test]
+fn test_kv_open_file_requires_database_permission() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("state.kv");
+    let source = format!(r#"kv.open("{}");"#, path.display());
+
+    let diagnostics = Atlas::new().eval(&source).unwrap_err();
+    assert_eq!(diagnostics[0].code, "AT0305");
+    assert!(!path.exists());
+}
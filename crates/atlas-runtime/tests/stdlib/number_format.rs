@@ -0,0 +1,53 @@
+use super::*;
+use atlas_runtime::value::format_number;
+
+// --- Canonical number formatting ---
+
+// Every number-to-text path must agree with value::format_number:
+// toString(), template strings, Json.stringify and the value's Display.
+
+#[rstest]
+#[case::zero(0.0, "0")]
+#[case::negative_zero(-0.0, "0")]
+#[case::integer(42.0, "42")]
+#[case::negative_fraction(-7.5, "-7.5")]
+#[case::shortest_round_trip(0.1 + 0.2, "0.30000000000000004")]
+#[case::third(1.0 / 3.0, "0.3333333333333333")]
+#[case::beyond_i64(1e20, "100000000000000000000")]
+#[case::large_exponent(1e21, "1e+21")]
+#[case::large_mantissa(-1.5e300, "-1.5e+300")]
+#[case::small_fixed(0.000001, "0.000001")]
+#[case::small_exponent(1.5e-7, "1.5e-7")]
+#[case::nan(f64::NAN, "NaN")]
+#[case::infinity(f64::INFINITY, "Infinity")]
+#[case::neg_infinity(f64::NEG_INFINITY, "-Infinity")]
+fn test_format_number(#[case] n: f64, #[case] expected: &str) {
+    assert_eq!(format_number(n), expected);
+    assert_eq!(Value::Number(n).to_string(), expected);
+}
+
+#[rstest]
+#[case("0.1 + 0.2", "0.30000000000000004")]
+#[case("-0", "0")]
+#[case("123456789012345678901234", "1.2345678901234569e+23")]
+#[case("0.00000015", "1.5e-7")]
+#[case("10000000000000000", "10000000000000000")]
+fn test_number_paths_agree(#[case] expr: &str, #[case] expected: &str) {
+    assert_eval_string(&format!("({}).toString()", expr), expected);
+    assert_eval_string(&format!("let n = {}; `${{n}}`", expr), expected);
+    assert_eval_string(&format!("Json.stringify({})", expr), expected);
+    assert_eval_string(
+        &format!("Json.stringify([{}])", expr),
+        &format!("[{}]", expected),
+    );
+}
+
+#[test]
+fn test_range_display_uses_canonical_numbers() {
+    let range = Value::Range {
+        start: Some(0.5),
+        end: Some(1e21),
+        inclusive: false,
+    };
+    assert_eq!(range.to_string(), "0.5..1e+21");
+}
@@ -3,7 +3,6 @@
 //! Verifies: map, andThen, orElse, unwrapOrElse on Option<T> and Result<T,E>.
 //! D-052: VM-only execution path.
 
-use super::*;
 use atlas_runtime::binder::Binder;
 use atlas_runtime::compiler::Compiler;
 use atlas_runtime::lexer::Lexer;
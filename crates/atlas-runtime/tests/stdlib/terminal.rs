@@ -0,0 +1,157 @@
+//! Terminal output tests
+//!
+//! Tests for console.printTable, console.progressBar, and console.styled.
+//! Output is captured, so it is never a terminal: tables use ASCII borders,
+//! progress bars only print on finish, and styles are plain text.
+
+use atlas_runtime::api::{Runtime, RuntimeConfig};
+use atlas_runtime::stdlib::terminal::{render_table, style};
+use atlas_runtime::stdlib::OutputWriter;
+use atlas_runtime::value::Value;
+use std::sync::{Arc, Mutex};
+
+struct VecWriter(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for VecWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Run `source` with captured output, returning its value and what it printed
+fn run_captured(source: &str) -> (Value, String) {
+    let buf = Arc::new(Mutex::new(Vec::new()));
+    let output: OutputWriter = Arc::new(Mutex::new(Box::new(VecWriter(buf.clone()))));
+    let mut runtime = Runtime::from_config(RuntimeConfig::new().with_output(output));
+    let value = runtime.eval(source).unwrap();
+    let printed = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    (value, printed)
+}
+
+// ============================================================================
+// console.printTable
+// ============================================================================
+
+#[test]
+fn test_print_table_aligns_columns() {
+    let (_, printed) = run_captured(
+        r#"console.printTable([["api", "ok", "12"], ["worker", "degraded", "3"]], ["service", "status", "ms"]);"#,
+    );
+    assert_eq!(
+        printed,
+        "\
++---------+----------+----+
+| service | status   | ms |
++---------+----------+----+
+| api     | ok       | 12 |
+| worker  | degraded |  3 |
++---------+----------+----+
+"
+    );
+}
+
+#[test]
+fn test_print_table_without_rows_prints_headers() {
+    let (_, printed) = run_captured(r#"console.printTable([], ["name"]);"#);
+    assert_eq!(printed, "+------+\n| name |\n+------+\n+------+\n");
+}
+
+#[test]
+fn test_print_table_rejects_ragged_rows() {
+    let buf = Arc::new(Mutex::new(Vec::new()));
+    let output: OutputWriter = Arc::new(Mutex::new(Box::new(VecWriter(buf.clone()))));
+    let mut runtime = Runtime::from_config(RuntimeConfig::new().with_output(output));
+    let result = runtime.eval(r#"console.printTable([["a", "b"], ["c"]], ["x", "y"]);"#);
+    assert!(result.is_err());
+    assert!(buf.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_render_table_box_borders_on_terminal() {
+    let table = render_table(&["n".to_string()], &[vec![Value::Number(1.0)]], true, false);
+    assert_eq!(table, "┌───┐\n│ n │\n├───┤\n│ 1 │\n└───┘\n");
+}
+
+#[test]
+fn test_render_table_measures_styled_cells_by_visible_width() {
+    let red = style("fail", "red", true).unwrap();
+    let table = render_table(
+        &["status".to_string()],
+        &[vec![Value::string(red.clone())]],
+        false,
+        false,
+    );
+    assert!(table.contains(&format!("| {}   |", red)));
+}
+
+// ============================================================================
+// console.progressBar
+// ============================================================================
+
+#[test]
+fn test_progress_bar_prints_summary_on_finish() {
+    let (_, printed) = run_captured(
+        r#"
+        let bar = console.progressBar(4);
+        bar.setLabel("uploading");
+        bar.advance(1);
+        bar.advance(2);
+        bar.finish();
+        bar.advance(1);
+        bar.finish();
+    "#,
+    );
+    assert_eq!(printed, "uploading 3/4 (75%)\n");
+}
+
+#[test]
+fn test_progress_bar_clamps_to_total() {
+    let (_, printed) = run_captured(
+        r#"
+        let bar = console.progressBar(10);
+        bar.set(25);
+        bar.finish();
+    "#,
+    );
+    assert_eq!(printed, "10/10 (100%)\n");
+}
+
+#[test]
+fn test_progress_bar_rejects_negative_total() {
+    let mut runtime = Runtime::new();
+    assert!(runtime.eval("console.progressBar(-1);").is_err());
+}
+
+// ============================================================================
+// console.styled
+// ============================================================================
+
+#[test]
+fn test_styled_is_plain_when_not_a_terminal() {
+    let (value, _) = run_captured(r#"console.styled("done", "bold.green")"#);
+    assert_eq!(value, Value::string("done"));
+}
+
+#[test]
+fn test_styled_rejects_unknown_style() {
+    let mut runtime = Runtime::new();
+    assert!(runtime.eval(r#"console.styled("x", "bold.rde")"#).is_err());
+}
+
+#[test]
+fn test_style_ansi_codes() {
+    assert_eq!(
+        style("err", "bold.red", true).unwrap(),
+        "\x1b[1;31merr\x1b[0m"
+    );
+    assert_eq!(
+        style("x", "brightBlue.bgGray", true).unwrap(),
+        "\x1b[94;100mx\x1b[0m"
+    );
+    assert_eq!(style("plain", "", true).unwrap(), "plain");
+    assert_eq!(style("off", "underline", false).unwrap(), "off");
+}
@@ -290,7 +290,7 @@ fn test_to_string_string_empty() {
 #[test]
 fn test_to_string_array() {
     let code = r#"toString([1,2,3])"#;
-    assert_eval_string(code, "[Array]");
+    assert_eval_string(code, "[1, 2, 3]");
 }
 
 // Function toString test removed: not yet fully supported
@@ -301,6 +301,77 @@ fn test_to_string_json() {
     assert_eval_string(code, "[JSON]");
 }
 
+#[test]
+fn test_to_string_nested_collections() {
+    let code = r#"toString([[Some(1)], [Some(2), None]])"#;
+    assert_eval_string(code, r#"[[Some(1)], [Some(2), None]]"#);
+}
+
+// ============================================================================
+// inspect Tests
+// ============================================================================
+
+#[test]
+fn test_inspect_quotes_strings() {
+    assert_eval_string(r#"inspect("a\"b")"#, r#""a\"b""#);
+    assert_eval_string(r#"inspect(42)"#, "42");
+}
+
+#[test]
+fn test_inspect_map_sorted_keys() {
+    let code = r#"
+        let m = new Map<string, number>();
+        m.set("b", 2);
+        m.set("a", 1);
+        inspect(m)
+    "#;
+    assert_eval_string(code, r#"{"a": 1, "b": 2}"#);
+}
+
+#[test]
+fn test_inspect_breaks_long_collections() {
+    let code = r#"
+        let opts = new Map<string, number>();
+        opts.set("width", 10);
+        inspect([[1, 2], [3, 4]], opts)
+    "#;
+    assert_eval_string(code, "[\n  [1, 2],\n  [3, 4]\n]");
+}
+
+#[test]
+fn test_inspect_limits() {
+    let code = r#"
+        let opts = new Map<string, number>();
+        opts.set("depth", 0);
+        opts.set("maxItems", 2);
+        opts.set("maxString", 3);
+        inspect([[1], [2, 3], [4], [5]], opts) + " " + inspect("abcdef", opts)
+    "#;
+    assert_eval_string(
+        code,
+        r#"[[Array(1)], [Array(2)], ... 2 more] "abc"... (+3 chars)"#,
+    );
+}
+
+#[test]
+fn test_inspect_rejects_bad_options() {
+    let runtime = Atlas::new();
+    assert!(runtime.eval(r#"inspect(1, 2)"#).is_err());
+}
+
+#[test]
+fn test_pretty_marks_cycles() {
+    use atlas_runtime::value::Shared;
+
+    let cell = Shared::new(Box::new(Value::Null));
+    let shared = Value::SharedValue(cell.clone());
+    cell.with_mut(|inner| **inner = Value::Array(vec![Value::Number(1.0), shared.clone()].into()));
+    assert_eq!(
+        shared.pretty(&atlas_runtime::PrettyConfig::default()),
+        "shared([1, <cycle>])"
+    );
+}
+
 // ============================================================================
 // toNumber Tests
 // ============================================================================
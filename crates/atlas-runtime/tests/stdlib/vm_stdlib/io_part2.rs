@@ -454,10 +454,7 @@ fn vm_test_file_info_size_check() {
     let result = execute_with_io(&code, &temp_dir);
 
     assert!(result.is_ok());
-    assert!(matches!(
-        result.unwrap(),
-        atlas_runtime::Value::JsonValue(_)
-    ));
+    assert!(matches!(result.unwrap(), atlas_runtime::Value::Map(_)));
 }
 
 #[test]
@@ -293,7 +293,7 @@ fn test_to_string_string_empty() {
 #[test]
 fn test_to_string_array() {
     let code = r#"toString([1,2,3])"#;
-    assert_eval_string(code, "[Array]");
+    assert_eval_string(code, "[1, 2, 3]");
 }
 
 // Function toString test removed: not yet fully supported
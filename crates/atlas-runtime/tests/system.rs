@@ -73,16 +73,26 @@ fn atlas_array_to_bytes(value: &Value) -> Vec<u8> {
 }
 
 // Domain submodules (files live in tests/system/)
+#[path = "system/args.rs"]
+mod system_args;
+#[path = "system/assets.rs"]
+mod system_assets;
 #[path = "system/compression/mod.rs"]
 mod system_compression;
+#[path = "system/config.rs"]
+mod system_config;
 #[path = "system/filesystem/mod.rs"]
 mod system_filesystem;
 #[path = "system/future_ns.rs"]
 mod system_future_ns;
+#[path = "system/net.rs"]
+mod system_net;
 #[path = "system/path.rs"]
 mod system_path;
 #[path = "system/process.rs"]
 mod system_process;
+#[path = "system/process_exec_options.rs"]
+mod system_process_exec_options;
 #[path = "system/sync.rs"]
 mod system_sync;
 #[path = "system/task_ns.rs"]
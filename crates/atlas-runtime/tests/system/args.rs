@@ -0,0 +1,209 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+const SPEC: &str = r#"[
+    "service: string               # Service to deploy",
+    "tag?: string                  # Image tag",
+    "--env, -e: string = staging   # Target environment",
+    "--port, -p: number = 8080     # Port to listen on",
+    "--verbose, -v                 # Print every step",
+    "--dry-run                     # Show what would change",
+    "--label, -l: string[]         # Extra labels"
+]"#;
+
+/// Parse `argv` against SPEC and evaluate `expr` with the record bound to `o`
+fn parse(argv: &str, expr: &str) -> Value {
+    eval_ok(&format!(
+        "let o = process.parseArgs({}, {}).unwrap(); {}",
+        SPEC, argv, expr
+    ))
+}
+
+/// Parse `argv` against SPEC, expecting an error message
+fn parse_err(argv: &str) -> String {
+    let result = eval_ok(&format!(
+        "match process.parseArgs({}, {}) {{ Ok(_) => \"parsed\", Err(e) => e }}",
+        SPEC, argv
+    ));
+    match result {
+        Value::String(s) => s.as_ref().clone(),
+        other => panic!("expected error string, got {:?}", other),
+    }
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[test]
+fn test_parse_args_defaults() {
+    let result = parse(
+        r#"["api"]"#,
+        r#"
+        let service: string = o.service;
+        let env: string = o.env;
+        let port: number = o.port;
+        let verbose: bool = o.verbose;
+        let labels: string[] = o.label;
+        service + ":" + env + ":" + port.toString() + ":" + verbose.toString() + ":" + labels.length().toString()
+        "#,
+    );
+    assert_eq!(result, Value::string("api:staging:8080:false:0"));
+}
+
+#[test]
+fn test_parse_args_optional_positional_is_null() {
+    let result = parse(r#"["api"]"#, "o.tag");
+    assert_eq!(result, Value::Null);
+}
+
+#[test]
+fn test_parse_args_long_and_short_forms() {
+    let result = parse(
+        r#"["--env=prod", "-p", "9000", "api", "v2", "--dry-run"]"#,
+        r#"
+        let service: string = o.service;
+        let tag: string = o.tag;
+        let env: string = o.env;
+        let port: number = o.port;
+        let dryRun: bool = o.dryRun;
+        service + ":" + tag + ":" + env + ":" + port.toString() + ":" + dryRun.toString()
+        "#,
+    );
+    assert_eq!(result, Value::string("api:v2:prod:9000:true"));
+}
+
+#[test]
+fn test_parse_args_bundled_short_flags() {
+    let result = parse(
+        r#"["-vp9000", "api"]"#,
+        r#"
+        let verbose: bool = o.verbose;
+        let port: number = o.port;
+        verbose.toString() + ":" + port.toString()
+        "#,
+    );
+    assert_eq!(result, Value::string("true:9000"));
+}
+
+#[test]
+fn test_parse_args_repeated_option_collects() {
+    let result = parse(
+        r#"["api", "-l", "a", "--label=b", "--label", "c"]"#,
+        r#"let labels: string[] = o.label; labels.join(",")"#,
+    );
+    assert_eq!(result, Value::string("a,b,c"));
+}
+
+#[test]
+fn test_parse_args_double_dash_ends_options() {
+    let result = parse(
+        r#"["--", "--weird", "-x"]"#,
+        r#"let service: string = o.service; let tag: string = o.tag; service + ":" + tag"#,
+    );
+    assert_eq!(result, Value::string("--weird:-x"));
+}
+
+#[test]
+fn test_parse_args_negated_flag() {
+    let result = eval_ok(
+        r#"
+        let o = process.parseArgs(["--color = true  # Colorize"], ["--no-color"]).unwrap();
+        let color: bool = o.color;
+        color
+    "#,
+    );
+    assert_eq!(result, Value::Bool(false));
+}
+
+#[test]
+fn test_parse_args_rest_positional() {
+    let result = eval_ok(
+        r#"
+        let o = process.parseArgs(["cmd: string", "files..."], ["cat", "a.txt", "b.txt"]).unwrap();
+        let cmd: string = o.cmd;
+        let files: string[] = o.files;
+        cmd + ":" + files.join(",")
+    "#,
+    );
+    assert_eq!(result, Value::string("cat:a.txt,b.txt"));
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[test]
+fn test_parse_args_missing_required_positional() {
+    let msg = parse_err("[]");
+    assert!(
+        msg.starts_with("error: missing required argument <service>"),
+        "{msg}"
+    );
+    assert!(msg.contains("Usage: "), "{msg}");
+    assert!(msg.contains("[options] <service> [tag]"), "{msg}");
+}
+
+#[test]
+fn test_parse_args_unknown_option() {
+    let msg = parse_err(r#"["api", "--force"]"#);
+    assert!(msg.starts_with("error: unknown option '--force'"), "{msg}");
+}
+
+#[test]
+fn test_parse_args_bad_number() {
+    let msg = parse_err(r#"["api", "--port", "http"]"#);
+    assert!(msg.contains("--port expects a number, got 'http'"), "{msg}");
+}
+
+#[test]
+fn test_parse_args_missing_value() {
+    let msg = parse_err(r#"["api", "--env"]"#);
+    assert!(msg.contains("--env requires a value"), "{msg}");
+}
+
+#[test]
+fn test_parse_args_extra_positional() {
+    let msg = parse_err(r#"["api", "v2", "v3"]"#);
+    assert!(msg.contains("unexpected argument 'v3'"), "{msg}");
+}
+
+#[test]
+fn test_parse_args_invalid_spec_is_runtime_error() {
+    let runtime = Atlas::new_with_security(SecurityContext::allow_all());
+    assert!(runtime
+        .eval(r#"process.parseArgs(["--port: integer"], []);"#)
+        .is_err());
+    assert!(runtime
+        .eval(r#"process.parseArgs(["name?: string", "other: string"], []);"#)
+        .is_err());
+}
+
+// ============================================================================
+// Help
+// ============================================================================
+
+#[test]
+fn test_args_help_lists_arguments_and_options() {
+    let help = match eval_ok(&format!("process.argsHelp({})", SPEC)) {
+        Value::String(s) => s.as_ref().clone(),
+        other => panic!("expected string, got {:?}", other),
+    };
+    let body = help.split_once('\n').unwrap().1;
+    assert_eq!(
+        body,
+        "
+Arguments:
+  <service>                Service to deploy
+  [tag]                    Image tag
+
+Options:
+  -e, --env <string>       Target environment (default: staging)
+  -p, --port <number>      Port to listen on (default: 8080)
+  -v, --verbose            Print every step
+      --dry-run            Show what would change
+  -l, --label <string>...  Extra labels
+  -h, --help               Show this help
+"
+    );
+}
@@ -0,0 +1,121 @@
+// Compile-time asset embedding: includeStr / includeBytes
+
+use super::*;
+
+/// A project with `[assets] include = ["templates/*.txt"]` and one module
+fn project(source: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    std_fs::write(
+        dir.path().join("atlas.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[assets]\ninclude = [\"templates/*.txt\"]\n",
+    )
+    .unwrap();
+    std_fs::create_dir(dir.path().join("templates")).unwrap();
+    std_fs::write(dir.path().join("templates/greeting.txt"), "Hello, {name}!").unwrap();
+    std_fs::write(dir.path().join("secret.env"), "TOKEN=abc").unwrap();
+    std_fs::write(dir.path().join("main.atlas"), source).unwrap();
+    dir
+}
+
+fn run(dir: &TempDir) -> Result<Value, Vec<atlas_runtime::Diagnostic>> {
+    // Read permission for the entry file only: embedding happens at compile
+    // time, not at run time
+    let main = dir.path().join("main.atlas");
+    let mut security = SecurityContext::new();
+    security.grant_filesystem_read(&main, false);
+    let runtime = Atlas::new_with_security(security);
+    runtime.eval_file(main.to_str().unwrap())
+}
+
+fn error_codes(result: Result<Value, Vec<atlas_runtime::Diagnostic>>) -> Vec<String> {
+    result
+        .expect_err("expected an embedding error")
+        .into_iter()
+        .map(|d| d.code)
+        .collect()
+}
+
+#[test]
+fn test_include_str_embeds_file_contents() {
+    let dir = project(r#"let greeting = includeStr("templates/greeting.txt"); greeting;"#);
+    assert_eq!(
+        run(&dir).unwrap(),
+        Value::string("Hello, {name}!".to_string())
+    );
+}
+
+#[test]
+fn test_include_bytes_embeds_byte_values() {
+    let dir = project(r#"let data = includeBytes("templates/greeting.txt"); data;"#);
+    assert_eq!(atlas_array_to_bytes(&run(&dir).unwrap()), b"Hello, {name}!");
+}
+
+#[test]
+fn test_include_resolves_relative_to_module() {
+    let dir = project("");
+    std_fs::create_dir(dir.path().join("lib")).unwrap();
+    std_fs::write(
+        dir.path().join("lib/text.atlas"),
+        r#"export fn greeting(): string { return includeStr("../templates/greeting.txt"); }"#,
+    )
+    .unwrap();
+    std_fs::write(
+        dir.path().join("main.atlas"),
+        r#"import { greeting } from "./lib/text"; greeting();"#,
+    )
+    .unwrap();
+    assert_eq!(
+        run(&dir).unwrap(),
+        Value::string("Hello, {name}!".to_string())
+    );
+}
+
+#[test]
+fn test_module_cache_reembeds_changed_asset() {
+    let dir = project(r#"includeStr("templates/greeting.txt");"#);
+    let cache = std::sync::Arc::new(atlas_runtime::ModuleCache::new());
+    let run_cached = || {
+        Atlas::new_with_security(SecurityContext::allow_all())
+            .with_module_cache(std::sync::Arc::clone(&cache))
+            .eval_file(dir.path().join("main.atlas").to_str().unwrap())
+            .unwrap()
+    };
+    assert_eq!(run_cached(), Value::string("Hello, {name}!".to_string()));
+
+    std_fs::write(dir.path().join("templates/greeting.txt"), "Bye!").unwrap();
+    assert_eq!(run_cached(), Value::string("Bye!".to_string()));
+    // The source itself was not parsed again
+    assert_eq!(cache.hits(), 1);
+}
+
+#[test]
+fn test_include_rejects_unlisted_file() {
+    let dir = project(r#"includeStr("secret.env");"#);
+    assert_eq!(error_codes(run(&dir)), vec!["AT5009"]);
+}
+
+#[test]
+fn test_include_rejects_missing_file() {
+    let dir = project(r#"includeStr("templates/missing.txt");"#);
+    assert_eq!(error_codes(run(&dir)), vec!["AT5009"]);
+}
+
+#[test]
+fn test_include_requires_literal_path() {
+    let dir = project(r#"let p = "templates/greeting.txt"; includeStr(p);"#);
+    assert_eq!(error_codes(run(&dir)), vec!["AT5009"]);
+}
+
+#[test]
+fn test_include_without_assets_table_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    std_fs::write(dir.path().join("note.txt"), "hi").unwrap();
+    std_fs::write(dir.path().join("main.atlas"), r#"includeStr("note.txt");"#).unwrap();
+    assert_eq!(error_codes(run(&dir)), vec!["AT5009"]);
+}
+
+#[test]
+fn test_include_can_be_shadowed() {
+    let dir = project(r#"fn includeStr(p: string): string { return p + "!"; } includeStr("x");"#);
+    assert_eq!(run(&dir).unwrap(), Value::string("x!".to_string()));
+}
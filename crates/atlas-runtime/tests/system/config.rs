@@ -0,0 +1,226 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+const SPEC: &str = r#"[
+    "region: string = us-east-1   # Deployment region",
+    "replicas: number = 2",
+    "dry-run: bool",
+    "tags: string[]",
+    "token?: string"
+]"#;
+
+/// Load SPEC with `options` and evaluate `expr` with the record bound to `c`
+fn load(options: &str, expr: &str) -> Value {
+    eval_ok(&format!(
+        "let c = process.loadConfig({}, {}).unwrap(); {}",
+        SPEC, options, expr
+    ))
+}
+
+/// Load `spec` with `options`, expecting an error message
+fn load_err(spec: &str, options: &str) -> String {
+    let result = eval_ok(&format!(
+        "match process.loadConfig({}, {}) {{ Ok(_) => \"loaded\", Err(e) => e }}",
+        spec, options
+    ));
+    match result {
+        Value::String(s) => s.as_ref().clone(),
+        other => panic!("expected error string, got {:?}", other),
+    }
+}
+
+const SUMMARY: &str = r#"
+    let region: string = c.region;
+    let replicas: number = c.replicas;
+    let dryRun: bool = c.dryRun;
+    let tags: string[] = c.tags;
+    region + ":" + replicas.toString() + ":" + dryRun.toString() + ":" + tags.join(",")
+"#;
+
+// ============================================================================
+// Layers
+// ============================================================================
+
+#[test]
+fn test_load_config_defaults() {
+    let result = load(
+        r#"record { name: "cfgdefaults", args: [] }"#,
+        &format!("{} + \":\" + (c.token == null).toString()", SUMMARY.trim()),
+    );
+    assert_eq!(result, Value::string("us-east-1:2:false::true"));
+}
+
+#[test]
+fn test_load_config_file_overrides_defaults() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("deploy.toml");
+    std_fs::write(
+        &file,
+        "region = \"eu-west-1\"\ndry_run = true\ntags = [\"a\", \"b\"]\n",
+    )
+    .unwrap();
+
+    let result = load(
+        &format!(
+            r#"record {{ name: "cfgfile", file: "{}", args: [] }}"#,
+            file.display()
+        ),
+        SUMMARY,
+    );
+    assert_eq!(result, Value::string("eu-west-1:2:true:a,b"));
+}
+
+#[test]
+fn test_load_config_json_file() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("deploy.json");
+    std_fs::write(&file, r#"{ "replicas": 5, "dryRun": true }"#).unwrap();
+
+    let result = load(
+        &format!(
+            r#"record {{ name: "cfgjson", file: "{}", args: [] }}"#,
+            file.display()
+        ),
+        SUMMARY,
+    );
+    assert_eq!(result, Value::string("us-east-1:5:true:"));
+}
+
+#[test]
+fn test_load_config_missing_file_is_skipped() {
+    let result = load(
+        r#"record { name: "cfgnofile", file: "/nonexistent/atlas-config.toml", args: [] }"#,
+        SUMMARY,
+    );
+    assert_eq!(result, Value::string("us-east-1:2:false:"));
+}
+
+#[test]
+fn test_load_config_env_overrides_file() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("deploy.toml");
+    std_fs::write(&file, "region = \"eu-west-1\"\nreplicas = 3\n").unwrap();
+    std::env::set_var("CFGENV_REPLICAS", "7");
+    std::env::set_var("CFGENV_TAGS", "x, y");
+
+    let result = load(
+        &format!(
+            r#"record {{ name: "cfgenv", file: "{}", args: [] }}"#,
+            file.display()
+        ),
+        SUMMARY,
+    );
+    std::env::remove_var("CFGENV_REPLICAS");
+    std::env::remove_var("CFGENV_TAGS");
+    assert_eq!(result, Value::string("eu-west-1:7:false:x,y"));
+}
+
+#[test]
+fn test_load_config_flags_override_env() {
+    std::env::set_var("CFGFLAGS_REGION", "ap-south-1");
+    std::env::set_var("CFGFLAGS_DRY_RUN", "true");
+
+    let result = load(
+        r#"record { name: "cfgflags", args: ["deploy", "--region=sa-east-1", "--no-dry-run", "--tags", "a", "--verbose", "--tags=b"] }"#,
+        SUMMARY,
+    );
+    std::env::remove_var("CFGFLAGS_REGION");
+    std::env::remove_var("CFGFLAGS_DRY_RUN");
+    assert_eq!(result, Value::string("sa-east-1:2:false:a,b"));
+}
+
+#[test]
+fn test_load_config_custom_env_prefix() {
+    std::env::set_var("MYAPP_REGION", "us-west-2");
+    let result = load(
+        r#"record { name: "cfgprefix", envPrefix: "MYAPP", args: [] }"#,
+        "c.region",
+    );
+    std::env::remove_var("MYAPP_REGION");
+    assert_eq!(result, Value::string("us-west-2"));
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+#[test]
+fn test_load_config_missing_required_setting() {
+    let msg = load_err(
+        r#"["token: string"]"#,
+        r#"record { name: "cfgreq", args: [] }"#,
+    );
+    assert_eq!(
+        msg,
+        "config: missing required setting 'token' (set it in [tool.cfgreq], CFGREQ_TOKEN or --token)"
+    );
+}
+
+#[test]
+fn test_load_config_file_type_mismatch() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("bad.toml");
+    std_fs::write(&file, "replicas = \"three\"\n").unwrap();
+
+    let msg = load_err(
+        SPEC,
+        &format!(
+            r#"record {{ name: "cfgbadfile", file: "{}", args: [] }}"#,
+            file.display()
+        ),
+    );
+    assert!(
+        msg.ends_with("'replicas' expects a number, got string"),
+        "{msg}"
+    );
+}
+
+#[test]
+fn test_load_config_file_unknown_key() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("typo.toml");
+    std_fs::write(&file, "regoin = \"eu-west-1\"\n").unwrap();
+
+    let msg = load_err(
+        SPEC,
+        &format!(
+            r#"record {{ name: "cfgtypo", file: "{}", args: [] }}"#,
+            file.display()
+        ),
+    );
+    assert!(msg.ends_with("unknown setting 'regoin'"), "{msg}");
+}
+
+#[test]
+fn test_load_config_bad_env_value() {
+    std::env::set_var("CFGBADENV_REPLICAS", "lots");
+    let msg = load_err(SPEC, r#"record { name: "cfgbadenv", args: [] }"#);
+    std::env::remove_var("CFGBADENV_REPLICAS");
+    assert_eq!(
+        msg,
+        "config: environment variable CFGBADENV_REPLICAS expects a number, got 'lots'"
+    );
+}
+
+#[test]
+fn test_load_config_bad_flag_value() {
+    let msg = load_err(
+        SPEC,
+        r#"record { name: "cfgbadflag", args: ["--replicas", "many"] }"#,
+    );
+    assert_eq!(msg, "config: --replicas expects a number, got 'many'");
+}
+
+#[test]
+fn test_load_config_invalid_spec_is_runtime_error() {
+    let runtime = Atlas::new_with_security(SecurityContext::allow_all());
+    assert!(runtime
+        .eval(r#"process.loadConfig(["port: integer"], record { args: [] });"#)
+        .is_err());
+    assert!(runtime
+        .eval(r#"process.loadConfig(["port: number = eighty"], record { args: [] });"#)
+        .is_err());
+    assert!(runtime
+        .eval(r#"process.loadConfig(["port: number"], record { colour: "red" });"#)
+        .is_err());
+}
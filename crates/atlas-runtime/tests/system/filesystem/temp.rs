@@ -6,7 +6,8 @@ use super::*;
 
 #[test]
 fn test_tmpfile_creates_temporary_file() {
-    let result = fs::tmpfile(span()).unwrap();
+    let security = SecurityContext::new();
+    let result = fs::tmpfile(&security, span()).unwrap();
     let path = extract_string(&result);
 
     assert!(!path.is_empty());
@@ -18,7 +19,8 @@ fn test_tmpfile_creates_temporary_file() {
 
 #[test]
 fn test_tmpdir_creates_temporary_directory() {
-    let result = fs::tmpdir(span()).unwrap();
+    let security = SecurityContext::new();
+    let result = fs::tmpdir(&security, span()).unwrap();
     let path = extract_string(&result);
 
     assert!(!path.is_empty());
@@ -30,7 +32,8 @@ fn test_tmpdir_creates_temporary_directory() {
 
 #[test]
 fn test_tmpfile_named_creates_file_with_prefix() {
-    let result = fs::tmpfile_named("atlas_test", span()).unwrap();
+    let security = SecurityContext::new();
+    let result = fs::tmpfile_named("atlas_test", &security, span()).unwrap();
     let path = extract_string(&result);
 
     assert!(!path.is_empty());
@@ -52,8 +55,9 @@ fn test_get_temp_dir_returns_system_temp_directory() {
 
 #[test]
 fn test_tmpfile_creates_unique_files() {
-    let result1 = fs::tmpfile(span()).unwrap();
-    let result2 = fs::tmpfile(span()).unwrap();
+    let security = SecurityContext::new();
+    let result1 = fs::tmpfile(&security, span()).unwrap();
+    let result2 = fs::tmpfile(&security, span()).unwrap();
 
     let path1 = extract_string(&result1);
     let path2 = extract_string(&result2);
@@ -66,8 +70,9 @@ fn test_tmpfile_creates_unique_files() {
 
 #[test]
 fn test_tmpdir_creates_unique_directories() {
-    let result1 = fs::tmpdir(span()).unwrap();
-    let result2 = fs::tmpdir(span()).unwrap();
+    let security = SecurityContext::new();
+    let result1 = fs::tmpdir(&security, span()).unwrap();
+    let result2 = fs::tmpdir(&security, span()).unwrap();
 
     let path1 = extract_string(&result1);
     let path2 = extract_string(&result2);
@@ -77,3 +82,115 @@ fn test_tmpdir_creates_unique_directories() {
     std_fs::remove_dir(&path1).ok();
     std_fs::remove_dir(&path2).ok();
 }
+
+#[test]
+fn test_temp_paths_live_under_managed_root() {
+    let security = SecurityContext::new();
+    let file = extract_string(&fs::tmpfile(&security, span()).unwrap());
+    let dir = extract_string(&fs::tmpdir(&security, span()).unwrap());
+
+    let root = security
+        .temp_root()
+        .path()
+        .expect("temp root should exist after first use");
+    assert!(Path::new(&file).starts_with(&root));
+    assert!(Path::new(&dir).starts_with(&root));
+    assert!(security.temp_root().contains(Path::new(&file)));
+}
+
+#[test]
+fn test_temp_file_prefix_via_namespace() {
+    let security = SecurityContext::new();
+    let result = stdlib::call_builtin(
+        "fileNsTempFile",
+        &[Value::string("report")],
+        span(),
+        &security,
+        &stdlib::stdout_writer(),
+    )
+    .unwrap();
+    let path = extract_string(&result);
+
+    let name = Path::new(&path).file_name().unwrap().to_string_lossy();
+    assert!(name.starts_with("report_"));
+    assert!(Path::new(&path).is_file());
+}
+
+#[test]
+fn test_temp_file_prefix_rejects_separators() {
+    let security = SecurityContext::new();
+    assert!(fs::tmpfile_named("../escape", &security, span()).is_err());
+}
+
+#[test]
+fn test_temp_root_is_writable_under_deny_all_security() {
+    let security = SecurityContext::new();
+    let path = extract_string(&fs::tmpfile(&security, span()).unwrap());
+
+    assert!(security.check_filesystem_write(Path::new(&path)).is_ok());
+    assert!(security.check_filesystem_read(Path::new(&path)).is_ok());
+    assert!(security
+        .check_filesystem_write(Path::new("/definitely/not/temp"))
+        .is_err());
+}
+
+#[test]
+fn test_temp_root_belongs_to_its_context() {
+    let security = SecurityContext::new();
+    let path = extract_string(&fs::tmpfile(&security, span()).unwrap());
+
+    let other = SecurityContext::new();
+    assert!(other.check_filesystem_read(Path::new(&path)).is_err());
+    assert!(security
+        .clone()
+        .check_filesystem_read(Path::new(&path))
+        .is_ok());
+}
+
+#[test]
+fn test_temp_root_rejects_parent_components() {
+    let security = SecurityContext::new();
+    let dir = extract_string(&fs::tmpdir(&security, span()).unwrap());
+    let root = security.temp_root().path().unwrap();
+
+    let escape = root.join("..").join("..").join("etc").join("passwd");
+    assert!(security.check_filesystem_read(&escape).is_err());
+    assert!(security.check_filesystem_write(&escape).is_err());
+    // Even when the `..` stays inside the root
+    let inside = Path::new(&dir).join("..").join("scratch.txt");
+    assert!(security.check_filesystem_write(&inside).is_err());
+}
+
+#[test]
+fn test_temp_root_is_dropped_with_its_context() {
+    let security = SecurityContext::new();
+    let path = extract_string(&fs::tmpfile(&security, span()).unwrap());
+    let root = security.temp_root().path().unwrap();
+
+    drop(security);
+    assert!(!Path::new(&path).exists());
+    assert!(!root.exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_temp_root_rejects_symlinks_out_of_root() {
+    let security = SecurityContext::new();
+    let dir = extract_string(&fs::tmpdir(&security, span()).unwrap());
+    let outside = TempDir::new().unwrap();
+    let target = outside.path().join("victim.txt");
+
+    // A link to a directory outside the root
+    let link = Path::new(&dir).join("out");
+    std::os::unix::fs::symlink(outside.path(), &link).unwrap();
+    assert!(security
+        .check_filesystem_write(&link.join("victim.txt"))
+        .is_err());
+
+    // A dangling link would create its target outside the root
+    let dangling = Path::new(&dir).join("dangling");
+    std::os::unix::fs::symlink(&target, &dangling).unwrap();
+    assert!(security.check_filesystem_write(&dangling).is_err());
+    assert!(security.check_filesystem_read(&dangling).is_err());
+    assert!(!target.exists());
+}
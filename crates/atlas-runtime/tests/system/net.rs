@@ -0,0 +1,158 @@
+// net namespace — TCP/UDP sockets over loopback and the network permission model.
+// Every test talks to 127.0.0.1 only, so none of them need ATLAS_TEST_NETWORK.
+
+use super::*;
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+fn localhost_only() -> SecurityContext {
+    let mut security = SecurityContext::new();
+    security.grant_network("127.0.0.1");
+    security
+}
+
+fn eval_with(security: SecurityContext, code: &str) -> Result<String, String> {
+    let atlas = Atlas::new_with_security(security);
+    atlas
+        .eval(code)
+        .map(|value| value.to_string())
+        .map_err(|diags| format!("{:?}", diags))
+}
+
+// ============================================================================
+// TCP
+// ============================================================================
+
+#[test]
+fn test_tcp_round_trip_with_send_and_recv() {
+    // Connecting completes against the listen backlog, so one thread can
+    // drive both ends
+    let code = r#"
+        let listener = unwrap(net.tcpListen("127.0.0.1:0"));
+        let client = unwrap(net.tcpConnect(net.tcpListenerAddr(listener)));
+        let server = unwrap(net.tcpAccept(listener));
+        net.send(client, "ping");
+        let got = net.recv(server, 64);
+        net.send(server, got + "/pong");
+        let reply = net.recv(client, 64);
+        net.close(client);
+        net.close(server);
+        net.close(listener);
+        reply
+    "#;
+    assert_eq!(eval_with(localhost_only(), code).unwrap(), "ping/pong");
+}
+
+#[test]
+fn test_tcp_connect_refused_is_err_result() {
+    // Bind then close to find a port with nothing listening on it
+    let port = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().port()
+    };
+    let code = format!(
+        r#"
+        let r = net.tcpConnect("127.0.0.1:{}");
+        r.isErr()
+    "#,
+        port
+    );
+    assert_eq!(eval_with(localhost_only(), &code).unwrap(), "true");
+}
+
+#[test]
+fn test_closed_handle_is_rejected() {
+    let code = r#"
+        let listener = unwrap(net.tcpListen("127.0.0.1:0"));
+        net.close(listener);
+        net.tcpListenerAddr(listener)
+    "#;
+    let err = eval_with(localhost_only(), code).unwrap_err();
+    assert!(err.contains("has been closed"), "{}", err);
+}
+
+#[test]
+fn test_close_twice_is_an_error() {
+    let code = r#"
+        let socket = unwrap(net.udpBind("127.0.0.1:0"));
+        net.close(socket);
+        net.close(socket)
+    "#;
+    let err = eval_with(localhost_only(), code).unwrap_err();
+    assert!(err.contains("already been closed"), "{}", err);
+}
+
+// ============================================================================
+// UDP
+// ============================================================================
+
+#[test]
+fn test_udp_connect_send_and_recv() {
+    let code = r#"
+        let server = unwrap(net.udpBind("127.0.0.1:0"));
+        let client = unwrap(net.udpConnect(net.udpLocalAddr(server)));
+        net.send(client, "hello");
+        let packet = net.udpReceive(server, 64);
+        net.udpSend(server, packet[0] + " back", packet[1]);
+        let reply = net.recv(client, 64);
+        net.close(client);
+        net.close(server);
+        reply
+    "#;
+    assert_eq!(eval_with(localhost_only(), code).unwrap(), "hello back");
+}
+
+#[test]
+fn test_send_on_unconnected_udp_socket_is_rejected() {
+    let code = r#"
+        let socket = unwrap(net.udpBind("127.0.0.1:0"));
+        net.send(socket, "nowhere")
+    "#;
+    let err = eval_with(localhost_only(), code).unwrap_err();
+    assert!(err.contains("udpConnect"), "{}", err);
+}
+
+// ============================================================================
+// Permissions
+// ============================================================================
+
+#[test]
+fn test_tcp_connect_denied_without_network_permission() {
+    let err = eval_with(SecurityContext::new(), r#"net.tcpConnect("127.0.0.1:9")"#).unwrap_err();
+    assert!(err.contains("network access to 127.0.0.1"), "{}", err);
+}
+
+#[test]
+fn test_listen_denied_without_network_permission() {
+    let err = eval_with(SecurityContext::new(), r#"net.tcpListen("127.0.0.1:0")"#).unwrap_err();
+    assert!(err.contains("network access to 127.0.0.1"), "{}", err);
+}
+
+#[test]
+fn test_grant_matches_host_on_any_port() {
+    let code = r#"
+        let socket = unwrap(net.udpBind("127.0.0.1:0"));
+        net.close(socket);
+        "bound"
+    "#;
+    assert_eq!(eval_with(localhost_only(), code).unwrap(), "bound");
+}
+
+#[test]
+fn test_udp_send_checks_target_host() {
+    // Binding locally is granted, but the datagram's target is not
+    let code = r#"
+        let socket = unwrap(net.udpBind("127.0.0.1:0"));
+        net.udpSend(socket, "leak", "10.0.0.1:53")
+    "#;
+    let err = eval_with(localhost_only(), code).unwrap_err();
+    assert!(err.contains("network access to 10.0.0.1"), "{}", err);
+}
+
+#[test]
+fn test_udp_connect_denied_for_ungranted_host() {
+    let err = eval_with(localhost_only(), r#"net.udpConnect("[::1]:53")"#).unwrap_err();
+    assert!(err.contains("network access to ::1"), "{}", err);
+}
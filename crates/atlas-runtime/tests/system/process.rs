@@ -27,6 +27,41 @@ fn test_shell_command() {
     assert!(matches!(result, Value::Result(_)));
 }
 
+#[cfg(unix)]
+#[test]
+fn test_exec_with_argument_array() {
+    let code = r#"unwrap(process.exec("echo", ["hello", "world"])).stdout()"#;
+    match eval_ok(code) {
+        Value::String(s) => assert_eq!(s.as_ref(), "hello world\n"),
+        other => panic!("Expected string, got {:?}", other),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_exec_capture_returns_exit_code_and_streams() {
+    let code = r#"
+        let out = unwrap(process.execCapture("sh", ["-c", "echo out; echo err >&2; exit 3"]));
+        out.exitCode().toString() + "|" + out.stdout() + "|" + out.stderr()
+    "#;
+    assert_eq!(eval_ok(code), Value::string("3|out\n|err\n"));
+}
+
+#[test]
+fn test_exec_capture_rejects_inherit() {
+    let runtime = Atlas::new_with_security(SecurityContext::allow_all());
+    let err = runtime
+        .eval(r#"process.execCapture("echo", [], record { inherit: true })"#)
+        .unwrap_err();
+    assert!(format!("{:?}", err).contains("inherit"), "{:?}", err);
+}
+
+#[test]
+fn test_exec_missing_program_is_err_value() {
+    let code = r#"process.exec("atlas-no-such-program-12345", []).isErr()"#;
+    assert!(matches!(eval_ok(code), Value::Bool(true)));
+}
+
 // ============================================================================
 // Environment Variable Tests
 // ============================================================================
@@ -238,6 +273,62 @@ fn test_exec_requires_permission() {
     assert!(result.is_err());
 }
 
+#[cfg(unix)]
+#[test]
+fn test_exec_allows_only_granted_programs() {
+    let mut security = SecurityContext::new();
+    security.grant_process("echo");
+    let runtime = Atlas::new_with_security(security);
+    assert!(runtime.eval(r#"process.exec("echo", ["ok"])"#).is_ok());
+    // Matching is on the program as written, not its resolved path
+    assert!(runtime
+        .eval(r#"process.exec("/bin/echo", ["ok"])"#)
+        .is_err());
+    assert!(runtime.eval(r#"process.execCapture("ls")"#).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_allowed_commands_config_restricts_programs() {
+    let config = atlas_runtime::api::RuntimeConfig::new().with_allowed_commands(["echo"]);
+    let mut runtime = Runtime::from_config(config);
+    assert!(runtime.eval(r#"process.exec("echo", ["ok"])"#).is_ok());
+    assert!(runtime.eval(r#"process.exec("ls")"#).is_err());
+}
+
+#[test]
+fn test_env_read_grant_does_not_allow_set() {
+    let mut security = SecurityContext::new();
+    security.grant_environment("ATLAS_TEST_READ_ONLY");
+    let runtime = Atlas::new_with_security(security);
+    assert!(runtime.eval(r#"env.get("ATLAS_TEST_READ_ONLY")"#).is_ok());
+    assert!(runtime
+        .eval(r#"env.set("ATLAS_TEST_READ_ONLY", "x")"#)
+        .is_err());
+    assert!(runtime
+        .eval(r#"env.unset("ATLAS_TEST_READ_ONLY")"#)
+        .is_err());
+}
+
+#[test]
+fn test_env_all_lists_only_readable_variables() {
+    std::env::set_var("ATLAS_TEST_VISIBLE", "yes");
+    let mut security = SecurityContext::new();
+    security.grant_environment("ATLAS_TEST_VISIBLE");
+    let runtime = Atlas::new_with_security(security);
+    let result = runtime.eval("env.all()").unwrap();
+    let Value::JsonValue(json) = result else {
+        panic!("Expected object, got {:?}", result);
+    };
+    match json.as_ref() {
+        atlas_runtime::JsonValue::Object(vars) => {
+            assert_eq!(vars.len(), 1);
+            assert!(vars.contains_key("ATLAS_TEST_VISIBLE"));
+        }
+        other => panic!("Expected object, got {:?}", other),
+    }
+}
+
 #[test]
 fn test_env_requires_permission() {
     let code = r#"env.get("PATH")"#;
@@ -0,0 +1,67 @@
+//! process.exec options: `env` and `cwd` permission checks
+
+use super::*;
+
+#[cfg(unix)]
+#[test]
+fn test_exec_env_path_cannot_swap_allowed_program() {
+    use std::os::unix::fs::PermissionsExt;
+    let dir = TempDir::new().unwrap();
+    let fake = dir.path().join("echo");
+    std_fs::write(&fake, "#!/bin/sh\necho pwned\n").unwrap();
+    std_fs::set_permissions(&fake, std_fs::Permissions::from_mode(0o755)).unwrap();
+
+    let mut security = SecurityContext::new();
+    security.grant_process("echo");
+    security.grant_environment_write("PATH");
+    let runtime = Atlas::new_with_security(security);
+    let code = format!(
+        r#"unwrap(process.exec("echo", ["ok"], {{ env: {{ PATH: "{}" }} }})).stdout()"#,
+        dir.path().display()
+    );
+    match runtime.eval(&code).unwrap() {
+        Value::String(s) => assert_eq!(s.as_ref(), "ok\n"),
+        other => panic!("Expected string, got {:?}", other),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_exec_env_needs_environment_write_permission() {
+    let mut security = SecurityContext::new();
+    security.grant_process("echo");
+    let runtime = Atlas::new_with_security(security);
+    let code =
+        r#"process.exec("echo", ["ok"], record { env: record { LD_PRELOAD: "/tmp/evil.so" } })"#;
+    let err = format!("{:?}", runtime.eval(code).unwrap_err());
+    assert!(err.contains("environment variable LD_PRELOAD"), "{}", err);
+
+    let mut security = SecurityContext::new();
+    security.grant_process("echo");
+    security.grant_environment_write("GREETING");
+    let runtime = Atlas::new_with_security(security);
+    let code = r#"unwrap(process.exec("echo", ["ok"], record { env: record { GREETING: "hi" } })).stdout()"#;
+    assert_eq!(runtime.eval(code).unwrap(), Value::string("ok\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_exec_cwd_needs_filesystem_read_permission() {
+    let dir = TempDir::new().unwrap();
+    let code = format!(
+        r#"unwrap(process.exec("pwd", [], record {{ cwd: "{}" }})).stdout()"#,
+        dir.path().display()
+    );
+
+    let mut security = SecurityContext::new();
+    security.grant_process("pwd");
+    let runtime = Atlas::new_with_security(security);
+    let err = format!("{:?}", runtime.eval(&code).unwrap_err());
+    assert!(err.contains("working directory"), "{}", err);
+
+    let mut security = SecurityContext::new();
+    security.grant_process("pwd");
+    security.grant_filesystem_read(dir.path(), true);
+    let runtime = Atlas::new_with_security(security);
+    assert!(runtime.eval(&code).is_ok());
+}
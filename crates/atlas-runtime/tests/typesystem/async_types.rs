@@ -195,7 +195,7 @@ fn test_at4006_async_main_forbidden() {
 }
 
 /// 6. AT4001: await inside a nested sync closure inside an async fn
-/// (sync fn declared inside async fn body — await is not in the sync fn's scope)
+///    (sync fn declared inside async fn body — await is not in the sync fn's scope)
 #[test]
 fn test_at4001_await_in_nested_sync_fn() {
     let diags = errors(
@@ -229,7 +229,7 @@ fn test_at4002_await_null() {
 }
 
 /// 8. AT4001: explicit check - no await at top level with a plain sync fn
-/// (top-level IS async, so this should not fire — regression guard)
+///    (top-level IS async, so this should not fire — regression guard)
 #[test]
 fn test_top_level_no_at4001() {
     let diags = errors(
@@ -23,7 +23,7 @@ fn test_operators_in_context(#[case] source: &str) {
     r#"let data: json = unwrap(Json.parse("{\"age\":30}")); let age: number = data["age"].asNumber();"#
 )]
 #[case::json_as_bool(r#"let data: json = unwrap(Json.parse("{\"active\":true}")); let active: bool = data["active"].asBool();"#)]
-#[case::jsonIsNull(r#"let data: json = unwrap(Json.parse("{\"value\":null}")); let null_check: bool = data["value"].isNull();"#)]
+#[case::json_is_null(r#"let data: json = unwrap(Json.parse("{\"value\":null}")); let null_check: bool = data["value"].isNull();"#)]
 #[case::chained_json_access(r#"let data: json = unwrap(Json.parse("{\"user\":{\"name\":\"Bob\"}}")); let name: string = data["user"]["name"].asString();"#)]
 #[case::method_in_expression(r#"let data: json = unwrap(Json.parse("{\"count\":5}")); let x: number = data["count"].asNumber() + 10;"#)]
 #[case::method_as_arg(
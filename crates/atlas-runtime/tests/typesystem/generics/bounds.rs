@@ -285,7 +285,7 @@ fn test_error_method_not_in_bound_trait() {
     "#,
     );
     assert!(
-        diagnostics.len() >= 1,
+        !diagnostics.is_empty(),
         "Expected error for nonexistent method"
     );
     assert!(
@@ -307,7 +307,7 @@ fn test_error_method_not_in_any_bound() {
     "#,
     );
     assert!(
-        diagnostics.len() >= 1,
+        !diagnostics.is_empty(),
         "Expected error for method not in bounds"
     );
     assert!(has_error(&diagnostics), "Should have error");
@@ -326,7 +326,7 @@ fn test_error_arity_mismatch_on_bounded_method() {
         }
     "#,
     );
-    assert!(diagnostics.len() >= 1, "Expected arity mismatch error");
+    assert!(!diagnostics.is_empty(), "Expected arity mismatch error");
     // AT3005 is the arity mismatch error code for method calls
     assert!(
         has_error_code(&diagnostics, "AT3005"),
@@ -348,7 +348,7 @@ fn test_error_wrong_arg_type_on_bounded_method() {
         }
     "#,
     );
-    assert!(diagnostics.len() >= 1, "Expected type mismatch error");
+    assert!(!diagnostics.is_empty(), "Expected type mismatch error");
     assert!(has_error(&diagnostics), "Should have type error");
 }
 
@@ -366,7 +366,7 @@ fn test_error_return_type_mismatch_from_bounded_method() {
     "#,
     );
     assert!(
-        diagnostics.len() >= 1,
+        !diagnostics.is_empty(),
         "Expected return type mismatch error"
     );
     assert!(has_error(&diagnostics), "Should have return type error");
@@ -2,7 +2,7 @@
 //! D-052: Uses VM-only execution
 
 use atlas_runtime::binder::Binder;
-use atlas_runtime::diagnostic::{Diagnostic, DiagnosticLevel};
+use atlas_runtime::diagnostic::Diagnostic;
 use atlas_runtime::lexer::Lexer;
 use atlas_runtime::parser::Parser;
 use atlas_runtime::security::SecurityContext;
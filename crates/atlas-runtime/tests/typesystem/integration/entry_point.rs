@@ -0,0 +1,57 @@
+// Entry-point contract: `fn main()` / `fn main(args: string[])` returning
+// nothing, a number exit code, or a Result.
+use super::super::*;
+
+fn entry_point_errors(source: &str) -> Vec<Diagnostic> {
+    typecheck_source(source)
+        .into_iter()
+        .filter(|d| d.code == "AT3066")
+        .collect()
+}
+
+#[test]
+fn test_valid_main_signatures_accepted() {
+    for source in [
+        "fn main(): void { }",
+        "fn main(): number { return 0; }",
+        "fn main(args: string[]): number { return args.len(); }",
+        "fn main(_args: string[]): Result<number, string> { return Ok(0); }",
+    ] {
+        let errors = entry_point_errors(source);
+        assert!(errors.is_empty(), "{source}: {errors:?}");
+    }
+}
+
+#[test]
+fn test_main_with_non_string_array_param_rejected() {
+    let errors = entry_point_errors("fn main(count: number): number { return count; }");
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].message.contains("string[]"), "{errors:?}");
+}
+
+#[test]
+fn test_main_with_two_params_rejected() {
+    let errors = entry_point_errors("fn main(a: string[], b: string[]): void { }");
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].message.contains("at most one"), "{errors:?}");
+}
+
+#[test]
+fn test_main_with_string_return_rejected() {
+    let errors = entry_point_errors(r#"fn main(): string { return "done"; }"#);
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].message.contains("must return"), "{errors:?}");
+}
+
+#[test]
+fn test_nested_main_is_not_an_entry_point() {
+    let errors = entry_point_errors(
+        r#"
+fn outer(): string {
+    fn main(): string { return "inner"; }
+    return main();
+}
+        "#,
+    );
+    assert!(errors.is_empty(), "{errors:?}");
+}
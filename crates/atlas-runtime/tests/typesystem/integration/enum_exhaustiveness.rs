@@ -0,0 +1,112 @@
+// Match exhaustiveness inside constructor payloads: an arm only covers the
+// payload shapes its sub-patterns match.
+use super::super::*;
+
+fn non_exhaustive_messages(diagnostics: &[Diagnostic]) -> Vec<String> {
+    diagnostics
+        .iter()
+        .filter(|d| d.code == "AT3027")
+        .map(|d| d.message.clone())
+        .collect()
+}
+
+#[test]
+fn test_refutable_payload_does_not_cover_variant() {
+    let diagnostics = typecheck_source(
+        r#"
+enum Shape { Dot, Pair(bool, number) }
+fn describe(s: Shape): string {
+    match s {
+        Shape::Dot => "dot",
+        Shape::Pair(true, _) => "pair",
+    }
+}
+        "#,
+    );
+    let messages = non_exhaustive_messages(&diagnostics);
+    assert_eq!(messages.len(), 1, "got: {:?}", diagnostics);
+    assert!(messages[0].contains("Pair(false, _)"), "{:?}", messages);
+}
+
+#[test]
+fn test_every_payload_shape_covered_accepted() {
+    let diagnostics = typecheck_source(
+        r#"
+enum Shape { Dot, Pair(bool, number) }
+fn describe(s: Shape): string {
+    match s {
+        Shape::Dot => "dot",
+        Shape::Pair(true, _) => "on",
+        Shape::Pair(false, _) => "off",
+    }
+}
+        "#,
+    );
+    assert_no_errors(&diagnostics);
+}
+
+#[test]
+fn test_nested_enum_payload_reports_missing_variant() {
+    let diagnostics = typecheck_source(
+        r#"
+enum Color { Red, Green, Blue }
+enum Light { Off, On(Color) }
+fn describe(l: Light): string {
+    match l {
+        Light::Off => "off",
+        Light::On(Color::Red) | Light::On(Color::Green) => "warm",
+    }
+}
+        "#,
+    );
+    let messages = non_exhaustive_messages(&diagnostics);
+    assert_eq!(messages.len(), 1, "got: {:?}", diagnostics);
+    assert!(messages[0].contains("On(Blue)"), "{:?}", messages);
+}
+
+#[test]
+fn test_option_bool_payload_exhaustiveness() {
+    let partial = typecheck_source(
+        r#"
+fn describe(o: Option<bool>): string {
+    match o {
+        Some(true) => "yes",
+        None => "unknown",
+    }
+}
+        "#,
+    );
+    let messages = non_exhaustive_messages(&partial);
+    assert_eq!(messages.len(), 1, "got: {:?}", partial);
+    assert!(messages[0].contains("Some(false)"), "{:?}", messages);
+
+    let full = typecheck_source(
+        r#"
+fn describe(o: Option<bool>): string {
+    match o {
+        Some(true) => "yes",
+        Some(false) => "no",
+        None => "unknown",
+    }
+}
+        "#,
+    );
+    assert_no_errors(&full);
+}
+
+#[test]
+fn test_literal_payload_needs_catch_all() {
+    let diagnostics = typecheck_source(
+        r#"
+fn describe(r: Result<number, string>): string {
+    match r {
+        Ok(0) => "zero",
+        Err(e) => e,
+    }
+}
+        "#,
+    );
+    let messages = non_exhaustive_messages(&diagnostics);
+    assert_eq!(messages.len(), 1, "got: {:?}", diagnostics);
+    assert!(messages[0].contains("Ok(_)"), "{:?}", messages);
+}
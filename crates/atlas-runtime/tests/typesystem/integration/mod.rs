@@ -3,6 +3,8 @@
 // Split to stay under 12KB file size limit per atlas-testing.md
 
 mod array_alias;
+mod entry_point;
+mod enum_exhaustiveness;
 mod enum_types;
 mod error_codes;
 mod hashmap_generics;
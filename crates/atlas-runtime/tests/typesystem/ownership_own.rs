@@ -154,3 +154,36 @@ fn main(): void {
         at3053.unwrap().message
     );
 }
+
+#[test]
+fn test_ownership_own_use_after_own_points_at_move_site() {
+    // The move site is attached as a related location so editors can show both spans
+    let src = r#"
+fn consume(own data: string): void {}
+fn main(): void {
+    let my_value: string = "hello";
+    consume(my_value);
+    let again: string = my_value;
+}
+"#;
+    let diags = errors(src);
+    let at3053 = diags
+        .iter()
+        .find(|d| d.code == "AT3053")
+        .expect("Expected AT3053");
+    assert_eq!(at3053.related.len(), 1, "got: {:?}", at3053.related);
+    let moved = &at3053.related[0];
+    assert!(
+        moved.message.contains("moved here"),
+        "got: {}",
+        moved.message
+    );
+    assert_eq!(moved.length, "my_value".len());
+    assert!(
+        moved.line < at3053.line || moved.column != at3053.column,
+        "move site should differ from use site: {:?} vs {}:{}",
+        moved,
+        at3053.line,
+        at3053.column
+    );
+}
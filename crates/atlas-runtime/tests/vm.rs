@@ -1,5 +1,5 @@
 //! THIN ROUTER — DO NOT ADD TESTS HERE.
-//! Add tests to the submodule files: tests/vm/{integration,member,complex_programs,regression,performance,functions,nested,for_in}.rs
+//! Add tests to the submodule files: tests/vm/{integration,member,null_safe,membership,pipeline,sorting,complex_programs,regression,performance,functions,nested,for_in,for_in_collections}.rs
 //! This file only declares submodules and shared helpers.
 
 mod common;
@@ -19,7 +19,6 @@ use atlas_runtime::value::Value;
 use atlas_runtime::vm::{Profiler, VM};
 use atlas_runtime::Atlas;
 use common::{assert_error_code, assert_eval_null, assert_eval_number, assert_eval_string};
-use pretty_assertions::assert_eq;
 use rstest::rstest;
 use std::time::Instant;
 
@@ -170,14 +169,20 @@ fn run_vm(source: &str) -> Result<String, String> {
 // Domain submodules (files live in tests/vm/)
 #[path = "vm/async_vm.rs"]
 mod vm_async;
+#[path = "vm/call_cache.rs"]
+mod vm_call_cache;
 #[path = "vm/complex_programs.rs"]
 mod vm_complex_programs;
 #[path = "vm/error_handling.rs"]
 mod vm_error_handling;
 #[path = "vm/for_in.rs"]
 mod vm_for_in;
+#[path = "vm/for_in_collections.rs"]
+mod vm_for_in_collections;
 #[path = "vm/functions.rs"]
 mod vm_functions;
+#[path = "vm/gc.rs"]
+mod vm_gc;
 #[path = "vm/integration.rs"]
 mod vm_integration;
 #[path = "vm/logical.rs"]
@@ -186,9 +191,20 @@ mod vm_logical;
 mod vm_member;
 #[path = "vm/nested.rs"]
 mod vm_nested;
+#[path = "vm/null_safe.rs"]
+mod vm_null_safe;
+
+#[path = "vm/membership.rs"]
+mod vm_membership;
 #[path = "vm/opcodes.rs"]
 mod vm_opcodes;
 #[path = "vm/performance.rs"]
 mod vm_performance;
+#[path = "vm/pipeline.rs"]
+mod vm_pipeline;
 #[path = "vm/regression.rs"]
 mod vm_regression;
+#[path = "vm/sorting.rs"]
+mod vm_sorting;
+#[path = "vm/try_catch.rs"]
+mod vm_try_catch;
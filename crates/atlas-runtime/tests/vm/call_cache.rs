@@ -0,0 +1,70 @@
+//! Call-site inline cache tests (vm::call_cache)
+
+use super::*;
+use pretty_assertions::assert_eq;
+
+/// Run `source` and return the result with the VM's cache counters
+fn run_with_stats(source: &str) -> (Option<Value>, atlas_runtime::vm::CallCacheStats) {
+    let mut vm = VM::new(compile(source));
+    let result = vm.run(&SecurityContext::allow_all()).expect("VM failed");
+    (result, vm.call_cache_stats())
+}
+
+#[test]
+fn test_builtin_call_in_loop_hits_cache() {
+    let (result, stats) = run_with_stats(
+        r#"
+        let mut total = 0;
+        let mut i = 0;
+        while i < 50 {
+            total = total + len("abc");
+            i = i + 1;
+        }
+        total;
+        "#,
+    );
+    assert_eq!(result, Some(Value::Number(150.0)));
+    assert_eq!(stats.misses, 1);
+    assert_eq!(stats.hits, 49);
+}
+
+#[test]
+fn test_function_call_in_loop_hits_cache() {
+    let (result, stats) = run_with_stats(
+        r#"
+        fn double(borrow n: number): number {
+            return n * 2;
+        }
+        let mut total = 0;
+        let mut i = 0;
+        while i < 20 {
+            total = total + double(i);
+            i = i + 1;
+        }
+        total;
+        "#,
+    );
+    assert_eq!(result, Some(Value::Number(380.0)));
+    assert_eq!(stats.sites, 1);
+    assert_eq!(stats.hits, 19);
+}
+
+#[test]
+fn test_call_site_with_changing_callee_stays_correct() {
+    let (result, stats) = run_with_stats(
+        r#"
+        fn one(): number { return 1; }
+        fn ten(): number { return 10; }
+        let fs = [one, ten, ten, one];
+        let mut total = 0;
+        for f in fs {
+            total = total + f();
+        }
+        total;
+        "#,
+    );
+    assert_eq!(result, Some(Value::Number(22.0)));
+    // one → ten and ten → one replace the entry; the repeated ten hits
+    assert_eq!(stats.hits, 1);
+    assert_eq!(stats.misses, 3);
+}
@@ -1051,13 +1051,14 @@ fn test_interp_vm_trait_default_override_parity() {
         impl Greetable for Human {
             fn greet(borrow self: Human): string { return \"Hi, I'm \" + self.name; }
         }
-        fn main(): string {
+        fn greet_both(): string {
             let r = Robot { name: \"Atlas\" };
             let h = Human { name: \"Ada\", age: 36 };
             let a: string = r.greet();
             let b: string = h.greet();
             return a + \"|\" + b;
         }
+        greet_both();
     ";
 
     let vm_result = run_vm(source).expect("VM should succeed");
@@ -1275,3 +1276,34 @@ sum
         ",
     );
 }
+
+#[test]
+fn test_for_in_range_annotation_vm() {
+    assert_eval_number(
+        "
+fn upto(n: number): range { return 1..=n; }
+let r: range = 0..3;
+let mut sum: number = 0;
+for i in r { sum = sum + i; }
+for i in upto(4) { sum = sum + i; }
+sum
+        ",
+        13.0,
+    );
+}
+
+#[test]
+fn test_for_in_range_is_lazy_vm() {
+    // Materializing this range would exhaust memory; iteration stops after 3 steps
+    assert_eval_number(
+        "
+let mut count: number = 0;
+for i in 0..1_000_000_000_000 {
+    if (i == 3) { break; }
+    count = count + 1;
+}
+count
+        ",
+        3.0,
+    );
+}
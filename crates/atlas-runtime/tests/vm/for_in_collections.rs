@@ -0,0 +1,114 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+// for-in over Map and Set (ForInPrepare), and Array.range
+
+fn eval(source: &str) -> Value {
+    let runtime = Atlas::new();
+    match runtime.eval(source) {
+        Ok(value) => value,
+        Err(e) => panic!("eval failed: {:?}", e),
+    }
+}
+
+#[test]
+fn test_for_in_map_yields_key_value_tuples() {
+    let value = eval(
+        r#"
+        let mut scores = new Map<string, number>();
+        scores.set("a", 1);
+        scores.set("b", 20);
+        scores.set("c", 300);
+        let mut total: number = 0;
+        let mut matched: number = 0;
+        for entry in scores {
+            let (name, score) = entry;
+            total = total + score;
+            if name == "b" && score == 20 {
+                matched = matched + 1;
+            }
+        }
+        total * 10 + matched
+        "#,
+    );
+    assert_eq!(value, Value::Number(3211.0));
+}
+
+#[test]
+fn test_for_in_set_yields_elements() {
+    let value = eval(
+        r#"
+        let mut seen = new Set<number>();
+        seen.add(2);
+        seen.add(5);
+        seen.add(5);
+        let mut total: number = 0;
+        for n in seen {
+            total = total + n;
+        }
+        total
+        "#,
+    );
+    assert_eq!(value, Value::Number(7.0));
+}
+
+#[test]
+fn test_for_in_map_break_and_continue() {
+    let value = eval(
+        r#"
+        let mut m = new Map<number, number>();
+        m.set(1, 1);
+        m.set(2, 2);
+        m.set(3, 3);
+        m.set(4, 4);
+        let mut visited: number = 0;
+        for entry in m {
+            let (k, _v) = entry;
+            if k == 2 { continue; }
+            visited = visited + 1;
+            if visited == 2 { break; }
+        }
+        visited
+        "#,
+    );
+    assert_eq!(value, Value::Number(2.0));
+}
+
+#[test]
+fn test_array_range_with_step() {
+    let value = eval(
+        r#"
+        let mut up: number = 0;
+        for i in Array.range(0, 10, 3) {
+            up = up * 10 + i;
+        }
+        let mut down: number = 0;
+        for i in Array.range(5, 0, -2) {
+            down = down * 10 + i;
+        }
+        let mut count: number = 0;
+        for _i in Array.range(0, 4) {
+            count = count + 1;
+        }
+        up * 10000 + down * 10 + count
+        "#,
+    );
+    // 0,3,6,9 -> 369; 5,3,1 -> 531; 4 elements
+    assert_eq!(value, Value::Number(369.0 * 10000.0 + 5314.0));
+}
+
+#[test]
+fn test_array_range_zero_step_errors() {
+    let runtime = Atlas::new();
+    assert!(runtime.eval("Array.range(0, 5, 0);").is_err());
+}
+
+#[test]
+fn test_for_in_number_rejected_by_typechecker() {
+    let runtime = Atlas::new();
+    let err = runtime
+        .eval("let n: number = 3; for i in n { }")
+        .expect_err("numbers are not iterable");
+    let message = format!("{:?}", err);
+    assert!(message.contains("Map or Set"), "{message}");
+}
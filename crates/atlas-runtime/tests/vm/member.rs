@@ -257,3 +257,30 @@ fn test_vm_tuple_match_wildcard() {
     assert!(result.is_ok(), "Expected Ok, got {:?}", result);
     assert!(result.unwrap().contains("7"), "Expected 7 in result");
 }
+
+#[rstest]
+#[case(r#"let n = (1, ("a", 2)); n.1.1;"#, 2.0)]
+#[case(r#"let n = ((1, 2), (3, (4, 5))); n.1.1.0 + n.0.1;"#, 6.0)]
+#[case(
+    r#"fn pair(): ((number, number), number) { return ((7, 8), 9); } pair().0.1;"#,
+    8.0
+)]
+fn test_vm_nested_tuple_access(#[case] source: &str, #[case] expected: f64) {
+    assert_eval_number(source, expected);
+}
+
+#[test]
+fn test_vm_nested_tuple_access_typed() {
+    assert_eval_string(
+        r#"let n: (number, (string, bool)) = (1, ("a", true)); let s: string = n.1.0; s;"#,
+        "a",
+    );
+}
+
+#[rstest]
+#[case(r#"let (a, b) = 5;"#)]
+#[case(r#"let (a, b) = (1, 2, 3);"#)]
+#[case(r#"let n = (1, ("a", true)); let x: number = n.1.0;"#)]
+fn test_vm_tuple_type_errors(#[case] source: &str) {
+    assert_error_code(source, "AT3001");
+}
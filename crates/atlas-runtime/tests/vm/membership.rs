@@ -0,0 +1,107 @@
+use super::*;
+use crate::common::{assert_eval_bool, assert_has_error};
+
+// --- Membership `in` and chained comparisons ---
+
+#[rstest]
+#[case::array_hit("2 in [1, 2, 3]", true)]
+#[case::array_miss("9 in [1, 2, 3]", false)]
+#[case::array_strings("\"b\" in [\"a\", \"b\"]", true)]
+#[case::map_key("\"a\" in new Map<string, number>().set(\"a\", 1)", true)]
+#[case::map_missing_key("\"b\" in new Map<string, number>().set(\"a\", 1)", false)]
+#[case::set("\"x\" in new Set<string>().add(\"x\")", true)]
+#[case::substring("\"ell\" in \"hello\"", true)]
+#[case::not_substring("\"xyz\" in \"hello\"", false)]
+#[case::range("4 in (0..5)", true)]
+#[case::range_exclusive_end("5 in (0..5)", false)]
+#[case::range_inclusive_end("5 in (0..=5)", true)]
+#[case::range_below_start("-1 in (0..5)", false)]
+#[case::json_key("\"k\" in Json.parse(\"{\\\"k\\\":1}\").unwrap()", true)]
+#[case::json_missing_key("\"z\" in Json.parse(\"{\\\"k\\\":1}\").unwrap()", false)]
+#[case::json_not_object("\"k\" in Json.parse(\"[1]\").unwrap()", false)]
+fn test_in(#[case] expr: &str, #[case] expected: bool) {
+    assert_eval_bool(&format!("{};", expr), expected);
+}
+
+#[test]
+fn test_in_binds_tighter_than_logical() {
+    assert_eval_bool("let xs = [1, 2]; 1 in xs && !(3 in xs);", true);
+}
+
+#[test]
+fn test_for_in_still_parses() {
+    assert_eval_number(
+        "let mut n = 0; for x in [1, 2, 3] { if x in [2, 3] { n = n + x; } } n;",
+        5.0,
+    );
+}
+
+#[rstest]
+#[case::wrong_element("\"x\" in [1, 2];")]
+#[case::wrong_map_key("1 in new Map<string, number>();")]
+#[case::not_a_container("1 in 5;")]
+#[case::number_in_string("1 in \"123\";")]
+fn test_in_type_errors(#[case] source: &str) {
+    assert_error_code(source, "AT3002");
+}
+
+#[rstest]
+#[case::inside("0 <= 3 < 5", true)]
+#[case::above("0 <= 7 < 5", false)]
+#[case::below("0 <= -1 < 5", false)]
+#[case::long("1 < 2 <= 2 < 4", true)]
+#[case::mixed_directions("1 < 3 > 2", true)]
+#[case::last_link_fails("1 < 2 < 3 > 4", false)]
+fn test_comparison_chain(#[case] expr: &str, #[case] expected: bool) {
+    assert_eval_bool(&format!("{};", expr), expected);
+}
+
+#[test]
+fn test_chain_evaluates_each_operand_once() {
+    assert_eval_number(
+        r#"
+        let mut calls = 0;
+        fn mid(): number { calls = calls + 1; return 2; }
+        let _ok = 1 < mid() < 3;
+        calls;
+        "#,
+        1.0,
+    );
+}
+
+#[test]
+fn test_chain_short_circuits() {
+    assert_eval_number(
+        r#"
+        let mut calls = 0;
+        fn last(): number { calls = calls + 1; return 10; }
+        let _ok = 5 < 2 < last();
+        calls;
+        "#,
+        0.0,
+    );
+}
+
+#[test]
+fn test_chain_in_condition() {
+    assert_eval_string(
+        r#"
+        fn check(i: number, n: number): string {
+            if 0 <= i < n { return "in bounds"; }
+            return "out of bounds";
+        }
+        check(3, 5) + ", " + check(5, 5);
+        "#,
+        "in bounds, out of bounds",
+    );
+}
+
+#[test]
+fn test_chain_checks_every_link() {
+    assert_error_code("let _b = 1 < \"a\" < 3;", "AT3002");
+}
+
+#[test]
+fn test_parenthesized_comparison_is_not_a_chain() {
+    assert_has_error("let _b = (1 < 2) < 3;");
+}
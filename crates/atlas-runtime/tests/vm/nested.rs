@@ -130,6 +130,37 @@ level1();
     assert_eq!(stack_trace[2].column, 1);
 }
 
+#[test]
+fn test_vm_run_captures_error_stack_trace() {
+    let source = r#"fn level1(): void {
+    level2();
+}
+fn level2(): void {
+    let arr = [1];
+    arr[3];
+}
+level1();
+"#;
+    let mut lexer = Lexer::new(source.to_string());
+    let (tokens, _) = lexer.tokenize();
+    let (program, _) = Parser::new(tokens).parse();
+    let bytecode = Compiler::new()
+        .compile(&program)
+        .expect("Compilation failed");
+
+    let mut vm = VM::new(bytecode);
+    vm.run(&SecurityContext::allow_all())
+        .expect_err("Expected runtime error");
+
+    let stack_trace = vm.take_error_stack_trace();
+    let functions: Vec<&str> = stack_trace.iter().map(|f| f.function.as_str()).collect();
+    assert_eq!(functions, ["level2", "level1"]);
+    assert_eq!((stack_trace[0].line, stack_trace[0].column), (6, 5));
+    // Outer frames point at the call that entered them, as in `stack_trace`
+    assert_eq!((stack_trace[1].line, stack_trace[1].column), (8, 1));
+    assert!(vm.take_error_stack_trace().is_empty());
+}
+
 // ============================================================================
 // Parameter Access
 // ============================================================================
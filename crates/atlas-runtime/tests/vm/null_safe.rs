@@ -0,0 +1,157 @@
+use super::*;
+use crate::common::{assert_eval_bool, assert_has_error, assert_no_error};
+
+// --- Null-safe access `?.` and null coalescing `??` ---
+
+const FIND: &str = r#"
+struct User { name: string, nick: string | null }
+fn find(id: number): User | null {
+    if id == 1 { return User { name: "Ada", nick: "countess" }; }
+    if id == 2 { return User { name: "Alan", nick: null }; }
+    return null;
+}
+"#;
+
+#[rstest]
+#[case::present("find(1)?.name ?? \"none\"", "Ada")]
+#[case::missing("find(3)?.name ?? \"none\"", "none")]
+#[case::chained("find(1)?.nick?.toUpperCase() ?? \"-\"", "COUNTESS")]
+#[case::chained_member_null("find(2)?.nick?.toUpperCase() ?? \"-\"", "-")]
+#[case::chained_target_null("find(3)?.nick?.toUpperCase() ?? \"-\"", "-")]
+fn test_nullable_access(#[case] expr: &str, #[case] expected: &str) {
+    assert_eval_string(&format!("{}{};", FIND, expr), expected);
+}
+
+#[test]
+fn test_null_target_gives_null() {
+    assert_eval_null(&format!("{}find(3)?.name;", FIND));
+}
+
+#[test]
+fn test_target_evaluated_once() {
+    assert_eval_number(
+        r#"
+        let mut calls = 0;
+        fn next(): string | null { calls = calls + 1; return "abc"; }
+        let _len = next()?.length() ?? 0;
+        calls;
+        "#,
+        1.0,
+    );
+}
+
+#[rstest]
+#[case::some(
+    "let o: Option<string> = Some(\"hi\"); o?.toUpperCase() ?? \"none\";",
+    "HI"
+)]
+#[case::none("let o: Option<string> = None; o?.toUpperCase() ?? \"none\";", "none")]
+fn test_option_access(#[case] source: &str, #[case] expected: &str) {
+    assert_eval_string(source, expected);
+}
+
+#[test]
+fn test_option_access_keeps_option() {
+    assert_eval_string(
+        r#"
+        let o: Option<string> = Some("hi");
+        match o?.length() {
+            Some(n) => `len ${n}`,
+            None => "none",
+        };
+        "#,
+        "len 2",
+    );
+}
+
+const DOC: &str =
+    r#"let doc = Json.parse("{\"user\":{\"name\":\"Ada\",\"email\":null}}").unwrap();"#;
+
+#[rstest]
+#[case::nested_key("doc?.user?.name?.asString() ?? \"anon\"", "Ada")]
+#[case::missing_key("doc?.account?.name?.asString() ?? \"anon\"", "anon")]
+#[case::json_null("doc?.user?.email?.asString() ?? \"no email\"", "no email")]
+fn test_json_access(#[case] expr: &str, #[case] expected: &str) {
+    assert_eval_string(&format!("{}\n{};", DOC, expr), expected);
+}
+
+#[test]
+fn test_json_missing_key_is_json_null() {
+    assert_eval_bool(&format!("{}\ndoc?.account?.name.isNull();", DOC), true);
+}
+
+#[test]
+fn test_result_access_propagates() {
+    assert_eval_number(r#"Json.parse("42")?.asNumber();"#, 42.0);
+}
+
+#[test]
+fn test_coalesce_evaluates_right_only_when_missing() {
+    assert_eval_number(
+        r#"
+        let mut calls = 0;
+        fn fallback(): number { calls = calls + 1; return 0; }
+        let a: number | null = 5;
+        let b: number | null = null;
+        let _x = a ?? fallback();
+        let _y = b ?? fallback();
+        calls;
+        "#,
+        1.0,
+    );
+}
+
+#[test]
+fn test_coalesce_binds_looser_than_or() {
+    assert_eval_bool("let a: bool | null = null; a ?? false || true;", true);
+}
+
+#[test]
+fn test_coalesce_result_type_drops_null() {
+    assert_no_error(
+        r#"
+        let a: number | null = null;
+        let n: number = a ?? 0;
+        let o: Option<string> = None;
+        let s: string = o ?? "none";
+        "#,
+    );
+}
+
+#[test]
+fn test_null_safe_result_type_is_nullable() {
+    assert_has_error(&format!("{}let name: string = find(1)?.name;", FIND));
+}
+
+#[test]
+fn test_coalesce_requires_missing_case() {
+    assert_error_code("let n = 5 ?? 0;", "AT3001");
+}
+
+#[rstest]
+#[case::member("let u: number[] | null = null; u?.x = 1;")]
+#[case::nested_member("let u: number[] | null = null; u?.x.y = 1;")]
+#[case::index("let u: number[] | null = null; u?.x[0] = 1;")]
+fn test_cannot_assign_through_null_safe_access(#[case] source: &str) {
+    assert_error_code(source, "AT1019");
+}
+
+#[test]
+fn test_null_safe_check_narrows_root() {
+    assert_eval_string(
+        &format!(
+            r#"{}
+            fn describe(id: number): string {{
+                let u = find(id);
+                if u?.name != null {{
+                    return u.name;
+                }}
+                return "nobody";
+            }}
+            describe(1) + " " + describe(3);
+            "#,
+            FIND
+        ),
+        "Ada nobody",
+    );
+}
@@ -0,0 +1,85 @@
+use super::*;
+use crate::common::{assert_eval_bool, assert_has_error};
+
+// --- Pipeline operator `|>` ---
+
+#[test]
+fn test_pipeline_array_processing() {
+    assert_eval_number(
+        r#"
+        let data = [1, 2, 3, 4, 5];
+        let total = data
+            |> filter(fn(x: number): bool { return x % 2 == 1; })
+            |> map(fn(x: number): number { return x * 10; })
+            |> reduce(fn(acc: number, x: number): number { return acc + x; }, 0);
+        total;
+        "#,
+        90.0,
+    );
+}
+
+#[rstest]
+#[case::bare_function("fn double(x: number): number { return x * 2; } 3 |> double;", 6.0)]
+#[case::extra_arguments(
+    "fn sub(a: number, b: number): number { return a - b; } 10 |> sub(4);",
+    6.0
+)]
+#[case::chained("fn inc(x: number): number { return x + 1; } fn double(x: number): number { return x * 2; } 3 |> inc |> double;", 8.0)]
+#[case::namespace_method("-4 |> Math.abs();", 4.0)]
+#[case::binds_looser_than_arithmetic(
+    "fn double(x: number): number { return x * 2; } 2 + 3 |> double;",
+    10.0
+)]
+#[case::anonymous_stage("5 |> fn(x: number): number { return x * x; };", 25.0)]
+#[case::builtin("[1, 2, 3] |> len();", 3.0)]
+fn test_pipeline(#[case] source: &str, #[case] expected: f64) {
+    assert_eval_number(source, expected);
+}
+
+#[test]
+fn test_pipeline_binds_tighter_than_comparison() {
+    assert_eval_bool("[1, 2, 3] |> len() > 2;", true);
+}
+
+#[test]
+fn test_pipeline_into_try() {
+    assert_eval_number(
+        r#"
+        fn parse(text: string): Result<number, string> { return text.toNumber(); }
+        fn twice(text: string): Result<number, string> {
+            let n = text |> parse()?;
+            return Ok(n * 2);
+        }
+        twice("1").unwrap();
+        "#,
+        2.0,
+    );
+}
+
+#[test]
+fn test_pipeline_evaluates_value_before_stage_arguments() {
+    assert_eval_string(
+        r#"
+        let mut log = "";
+        fn value(): number { log = log + "v"; return 1; }
+        fn arg(): number { log = log + "a"; return 2; }
+        fn add(a: number, b: number): number { return a + b; }
+        let _r = value() |> add(arg());
+        log;
+        "#,
+        "va",
+    );
+}
+
+#[test]
+fn test_pipeline_type_errors_point_at_stage() {
+    assert_error_code(
+        "fn double(x: number): number { return x * 2; } let _r = \"a\" |> double;",
+        "AT3001",
+    );
+}
+
+#[test]
+fn test_pipeline_needs_a_stage() {
+    assert_has_error("let _r = 1 |> ;");
+}
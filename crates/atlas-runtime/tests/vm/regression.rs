@@ -1288,3 +1288,42 @@ fn test_h302_nested_while_loops() {
         "nested while loops should work correctly"
     );
 }
+
+#[test]
+fn test_array_intrinsics_iterate_the_array_they_were_given() {
+    // Callback intrinsics walk the caller's array in place; reassigning the
+    // variable from inside the callback must not change what they iterate
+    let source = r#"
+let mut items = [1, 2, 3];
+fn shrink(x: number): number {
+    items = [0];
+    return x * 2;
+}
+let doubled = map(items, shrink);
+len(doubled) * 100 + doubled[2] * 10 + len(items);
+"#;
+    assert_eq!(vm_number(source), 361.0);
+}
+
+#[test]
+fn test_sort_leaves_its_input_untouched() {
+    let source = r#"
+let items = [3, 1, 2];
+let sorted = sort(items, fn(a: number, b: number): number { return a - b; });
+items[0] * 10 + sorted[0];
+"#;
+    assert_eq!(vm_number(source), 31.0);
+}
+
+#[test]
+fn test_string_concat_results_are_independent() {
+    assert_parity_string(
+        r#"
+let a = "ab" + "cd";
+let b = "efgh" + "ijkl";
+let c = a + b;
+a + "|" + b + "|" + c;
+"#,
+        "abcd|efghijkl|abcdefghijkl",
+    );
+}
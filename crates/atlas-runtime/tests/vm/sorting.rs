@@ -0,0 +1,217 @@
+use super::*;
+use crate::common::{assert_eval_bool, assert_has_error};
+use atlas_runtime::stdlib::sort::{sort_by_cached_key, sort_stable_by};
+use std::cmp::Ordering;
+use std::convert::Infallible;
+
+// --- sort / sortBy with callbacks ---
+
+#[test]
+fn test_sort_large_array_with_few_comparisons() {
+    // Insertion sort needed ~2M comparator calls here
+    assert_eval_bool(
+        r#"
+        let mut calls = 0;
+        fn ascending(a: number, b: number): number {
+            calls = calls + 1;
+            return a - b;
+        }
+        let items = map(Array.range(0, 2000), fn(i: number): number { return (i * 7919) % 2000; });
+        let sorted = sort(items, ascending);
+        let mut ordered = true;
+        for i in 1..2000 {
+            if sorted[i - 1] > sorted[i] { ordered = false; }
+        }
+        ordered && len(sorted) == 2000 && calls < 60000;
+        "#,
+        true,
+    );
+}
+
+#[test]
+fn test_sort_keeps_equal_elements_in_order() {
+    assert_eval_string(
+        r#"
+        let words = ["bb", "a", "cc", "d", "ee", "f"];
+        let sorted = sort(words, fn(a: string, b: string): number { return len(a) - len(b); });
+        let mut out = "";
+        for word in sorted { out = out + word + " "; }
+        out;
+        "#,
+        "a d f bb cc ee ",
+    );
+}
+
+#[test]
+fn test_sort_by_mixed_keys_keeps_order() {
+    assert_eval_string(
+        r#"
+        let items = [3, 1, 2];
+        let sorted = sortBy(items, fn(x: number): any {
+            if x == 2 { return "two"; }
+            return x;
+        });
+        let mut out = "";
+        for x in sorted { out = out + str(x); }
+        out;
+        "#,
+        "132",
+    );
+}
+
+#[test]
+fn test_sort_by_large_array_keeps_equal_keys_in_order() {
+    assert_eval_bool(
+        r#"
+        // Each value is its index followed by a scrambled key digit
+        let items = map(Array.range(0, 20000), fn(i: number): number { return i * 10 + (i * 7919) % 10; });
+        let sorted = sortBy(items, fn(x: number): number { return x % 10; });
+        let mut ordered = len(sorted) == 20000;
+        for i in 1..20000 {
+            let a = sorted[i - 1];
+            let b = sorted[i];
+            if a % 10 > b % 10 || (a % 10 == b % 10 && a > b) { ordered = false; }
+        }
+        ordered;
+        "#,
+        true,
+    );
+}
+
+#[test]
+fn test_sort_comparator_must_return_number() {
+    assert_has_error(
+        r#"
+        sort([2, 1], fn(a: number, b: number): any { return "later"; });
+        "#,
+    );
+}
+
+// --- sort_stable_by ---
+
+fn sorted(items: Vec<i64>) -> Vec<i64> {
+    sort_stable_by(&items, |a, b| Ok::<_, Infallible>(a.cmp(b))).unwrap()
+}
+
+#[test]
+fn test_sort_stable_by_sorts_every_shape() {
+    let mut seed = 7u64;
+    let random: Vec<i64> = (0..2000)
+        .map(|_| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            (seed >> 33) as i64 % 100
+        })
+        .collect();
+    for input in [
+        random,
+        (0..500).collect(),
+        (0..500).rev().collect(),
+        vec![3; 300],
+        vec![],
+        vec![1],
+    ] {
+        let mut expected = input.clone();
+        expected.sort();
+        assert_eq!(sorted(input), expected);
+    }
+}
+
+#[test]
+fn test_sort_stable_by_keeps_ties_in_order() {
+    let items: Vec<(i64, usize)> = (0..200).map(|i| (i % 3, i as usize)).collect();
+    let result = sort_stable_by(&items, |a, b| Ok::<_, Infallible>(a.0.cmp(&b.0))).unwrap();
+    assert!(result
+        .windows(2)
+        .all(|w| w[0].0 < w[1].0 || (w[0].0 == w[1].0 && w[0].1 < w[1].1)));
+}
+
+#[test]
+fn test_sort_stable_by_survives_inconsistent_comparator() {
+    let mut flip = false;
+    let result = sort_stable_by(&(0..300).collect::<Vec<i64>>(), |_, _| {
+        flip = !flip;
+        Ok::<_, Infallible>(if flip {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        })
+    })
+    .unwrap();
+    assert_eq!(result.len(), 300);
+}
+
+#[test]
+fn test_sort_stable_by_stops_at_first_error() {
+    let mut calls = 0;
+    let result = sort_stable_by(&(0..100).collect::<Vec<i64>>(), |a, b| {
+        calls += 1;
+        if calls == 10 {
+            Err("boom")
+        } else {
+            Ok(a.cmp(b))
+        }
+    });
+    assert_eq!(result, Err("boom"));
+    assert_eq!(calls, 10);
+}
+
+fn comparisons(items: Vec<i64>) -> usize {
+    let mut calls = 0;
+    sort_stable_by(&items, |a, b| {
+        calls += 1;
+        Ok::<_, Infallible>(a.cmp(b))
+    })
+    .unwrap();
+    calls
+}
+
+#[test]
+fn test_sort_stable_by_ordered_input_takes_linear_comparisons() {
+    assert_eq!(comparisons((0..10_000).collect()), 9_999);
+    assert_eq!(comparisons((0..10_000).rev().collect()), 9_999);
+    assert_eq!(comparisons(vec![5; 10_000]), 9_999);
+}
+
+#[test]
+fn test_sort_stable_by_random_input_takes_n_log_n_comparisons() {
+    let n = 100_000;
+    let items: Vec<i64> = (0..n).map(|i| (i * 7919) % n).collect();
+    let bound = n as usize * (n as f64).log2().ceil() as usize;
+    assert!(comparisons(items) < bound);
+}
+
+#[test]
+fn test_sort_by_cached_key_extracts_each_key_once() {
+    let mut extracted = 0;
+    let words = vec!["ccc", "a", "bb", "dd", "e"];
+    let result = sort_by_cached_key(
+        &words,
+        |word| {
+            extracted += 1;
+            Ok::<_, Infallible>(word.len())
+        },
+        |a, b| a.cmp(b),
+    )
+    .unwrap();
+    assert_eq!(result, vec!["a", "e", "bb", "dd", "ccc"]);
+    assert_eq!(extracted, 5);
+}
+
+#[test]
+fn test_sort_by_cached_key_stops_at_first_key_error() {
+    let mut extracted = 0;
+    let result = sort_by_cached_key(
+        &(0..10).collect::<Vec<i64>>(),
+        |x| {
+            extracted += 1;
+            if *x == 3 {
+                Err("bad key")
+            } else {
+                Ok(*x)
+            }
+        },
+        |a, b| a.cmp(b),
+    );
+    assert_eq!(result, Err("bad key"));
+    assert_eq!(extracted, 4);
+}
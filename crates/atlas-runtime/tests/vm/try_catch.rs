@@ -0,0 +1,281 @@
+use super::*;
+
+// try/catch recovery from runtime errors: unwinding across frames, stack
+// cleanup, handler bookkeeping around break/continue/return.
+
+#[test]
+fn test_catch_binds_error_record() {
+    assert_eval_string(
+        r#"
+        fn at(borrow xs: number[], borrow i: number): number {
+            return xs[i];
+        }
+        let mut kind = "none";
+        try {
+            at([1, 2, 3], 10);
+            kind = "unreachable";
+        } catch (e) {
+            kind = e.kind;
+        }
+        kind;
+    "#,
+        "OutOfBounds",
+    );
+}
+
+#[test]
+fn test_catch_exposes_diagnostic_code() {
+    assert_eval_string(
+        r#"
+        fn ratio(borrow a: number, borrow b: number): number {
+            return a / b;
+        }
+        let mut code = "";
+        try {
+            ratio(1, 0);
+        } catch (e) {
+            code = e.code;
+        }
+        code;
+    "#,
+        "AT0005",
+    );
+}
+
+#[test]
+fn test_catch_block_skipped_without_error() {
+    assert_eval_number(
+        r#"
+        let mut total = 0;
+        try {
+            total = total + 1;
+        } catch {
+            total = total + 100;
+        }
+        total;
+    "#,
+        1.0,
+    );
+}
+
+#[test]
+fn test_effects_before_error_are_kept() {
+    assert_eval_number(
+        r#"
+        fn at(borrow xs: number[], borrow i: number): number {
+            return xs[i];
+        }
+        let mut step = 0;
+        try {
+            step = 1;
+            at([0], 0 + 1);
+            step = 2;
+        } catch {
+            step = step * 10;
+        }
+        step;
+    "#,
+        10.0,
+    );
+}
+
+#[test]
+fn test_locals_survive_unwinding() {
+    // The error is raised two calls deep with temporaries on the stack;
+    // the frame's own locals must still be intact in the catch block.
+    assert_eval_number(
+        r#"
+        fn at(borrow xs: number[], borrow i: number): number {
+            return xs[i];
+        }
+        fn pick(borrow xs: number[], borrow i: number): number {
+            let doubled = at(xs, i) * 2;
+            return doubled;
+        }
+        fn f(borrow n: number): number {
+            let base = 10;
+            let mut total = base;
+            try {
+                let tmp = [1, 2];
+                total = total + pick(tmp, n);
+            } catch (e) {
+                total = total + 100;
+            }
+            return total + base;
+        }
+        f(0) + f(5);
+    "#,
+        142.0,
+    );
+}
+
+#[test]
+fn test_nested_try_inner_handles_error() {
+    assert_eval_string(
+        r#"
+        fn at(borrow xs: number[], borrow i: number): number {
+            return xs[i];
+        }
+        let mut log = "";
+        try {
+            try {
+                at([0], 0 + 1);
+            } catch {
+                log = log + "inner ";
+            }
+            log = log + "after ";
+        } catch {
+            log = log + "outer";
+        }
+        log;
+    "#,
+        "inner after ",
+    );
+}
+
+#[test]
+fn test_error_in_catch_block_reaches_outer_try() {
+    assert_eval_string(
+        r#"
+        fn at(borrow xs: number[], borrow i: number): number {
+            return xs[i];
+        }
+        let mut log = "";
+        try {
+            try {
+                at([0], 0 + 1);
+            } catch {
+                log = log + "inner ";
+                at([0], 1 + 1);
+            }
+        } catch (e) {
+            log = log + e.kind;
+        }
+        log;
+    "#,
+        "inner OutOfBounds",
+    );
+}
+
+#[test]
+fn test_try_inside_loop_catches_every_iteration() {
+    assert_eval_number(
+        r#"
+        fn at(borrow xs: number[], borrow i: number): number {
+            return xs[i];
+        }
+        let mut caught = 0;
+        for i in 0..100 {
+            try {
+                at([0], i + 1);
+            } catch {
+                caught = caught + 1;
+            }
+        }
+        caught;
+    "#,
+        100.0,
+    );
+}
+
+#[test]
+fn test_error_through_builtin_callback_is_caught() {
+    assert_eval_string(
+        r#"
+        fn explode(borrow x: number): number {
+            let empty: number[] = [];
+            return empty[x];
+        }
+        let mut kind = "";
+        try {
+            map([1, 2, 3], explode);
+        } catch (e) {
+            kind = e.kind;
+        }
+        kind;
+    "#,
+        "OutOfBounds",
+    );
+}
+
+#[test]
+fn test_continue_out_of_try_leaves_no_handler() {
+    // Were the handler left installed, the error after the loop would
+    // jump back into the stale catch block instead of failing the script.
+    assert_error_code(
+        r#"
+        fn at(borrow xs: number[], borrow i: number): number {
+            return xs[i];
+        }
+        for i in [1, 2, 3] {
+            try {
+                if i == 2 {
+                    continue;
+                }
+                if i == 3 {
+                    break;
+                }
+            } catch {
+                console.log("stale handler");
+            }
+        }
+        at([0], 0 + 1);
+    "#,
+        "AT0006",
+    );
+}
+
+#[test]
+fn test_return_out_of_try_leaves_no_handler() {
+    assert_error_code(
+        r#"
+        fn at(borrow xs: number[], borrow i: number): number {
+            return xs[i];
+        }
+        fn first(borrow xs: number[]): number {
+            try {
+                return xs[0];
+            } catch {
+                return -1;
+            }
+        }
+        first([5]);
+        at([0], 0 + 1);
+    "#,
+        "AT0006",
+    );
+}
+
+#[test]
+fn test_function_returning_from_both_blocks() {
+    assert_eval_number(
+        r#"
+        fn first_or(borrow xs: number[], borrow fallback: number): number {
+            try {
+                return xs[0];
+            } catch {
+                return fallback;
+            }
+        }
+        let empty: number[] = [];
+        first_or([7], 0) + first_or(empty, 3);
+    "#,
+        10.0,
+    );
+}
+
+#[test]
+fn test_uncaught_error_outside_try_still_fails() {
+    assert_error_code(
+        r#"
+        try {
+            let ok = 1;
+        } catch {
+            console.log("never");
+        }
+        let empty: number[] = [];
+        empty[0];
+    "#,
+        "AT0006",
+    );
+}
@@ -46,6 +46,18 @@ export function activate(context: vscode.ExtensionContext) {
   client.setTrace(trace === "verbose" ? 2 : trace === "messages" ? 1 : 0);
 
   client.start();
+
+  // Debugging runs `atlas debug --dap`, which speaks DAP on stdio.
+  context.subscriptions.push(
+    vscode.debug.registerDebugAdapterDescriptorFactory("atlas", {
+      createDebugAdapterDescriptor() {
+        const atlasPath = vscode.workspace
+          .getConfiguration("atlas")
+          .get<string>("path", "atlas");
+        return new vscode.DebugAdapterExecutable(atlasPath, ["debug", "--dap"]);
+      },
+    })
+  );
 }
 
 export function deactivate(): Thenable<void> | undefined {